@@ -0,0 +1,151 @@
+//! 가드 활동 -- 운영자 관찰용 실시간 이벤트
+//!
+//! [`GuardActivity`]는 컨테이너 수명주기 변화, 정책 매칭, 격리 액션 결과를
+//! 하나의 스트림으로 표현합니다. 가드는 수명주기 변화와 정책 매칭을
+//! [`ContainerGuard::subscribe_activity`](crate::guard::ContainerGuard::subscribe_activity)로
+//! 발행하며, 격리 결과는 액션 채널의 [`ActionEvent`]를 변환하여 얻습니다.
+//!
+//! 발행은 `broadcast` 채널을 사용하므로 수신자가 없거나 뒤처져도
+//! 가드의 알림 처리에는 영향을 주지 않습니다.
+
+use serde::{Deserialize, Serialize};
+
+use ironpost_core::event::ActionEvent;
+use ironpost_core::types::Severity;
+
+use crate::event::ContainerEvent;
+
+/// 활동 broadcast 채널 용량
+///
+/// 수신자가 이보다 뒤처지면 오래된 활동부터 유실됩니다.
+pub const ACTIVITY_CHANNEL_CAPACITY: usize = 256;
+
+/// 가드 활동 이벤트
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GuardActivity {
+    /// 컨테이너 수명주기 변화 (인벤토리 폴링으로 감지)
+    Lifecycle {
+        /// 컨테이너 ID
+        container_id: String,
+        /// 컨테이너 이름
+        container_name: String,
+        /// 변화 종류 (예: `started`, `stopped`, `paused`)
+        event: String,
+    },
+    /// 알림이 정책에 매칭됨
+    PolicyMatch {
+        /// 알림 ID
+        alert_id: String,
+        /// 알림 제목
+        alert_title: String,
+        /// 알림 심각도
+        severity: Severity,
+        /// 매칭된 정책 ID
+        policy_id: String,
+        /// 매칭된 정책 이름
+        policy_name: String,
+        /// 대상 컨테이너 ID
+        container_id: String,
+        /// 대상 컨테이너 이름
+        container_name: String,
+        /// 결정된 격리 액션
+        action: String,
+        /// 스탠바이 인스턴스라 격리를 리더에게 맡겼는지 여부
+        standby: bool,
+    },
+    /// 격리 액션 실행 결과
+    Action {
+        /// 액션 이벤트 ID
+        action_id: String,
+        /// 액션 타입 (예: `container_pause`)
+        action_type: String,
+        /// 대상 컨테이너 ID
+        target: String,
+        /// 성공 여부
+        success: bool,
+        /// 드라이런으로 시뮬레이션된 액션인지 여부
+        simulated: bool,
+    },
+}
+
+impl From<&ContainerEvent> for GuardActivity {
+    fn from(event: &ContainerEvent) -> Self {
+        Self::Lifecycle {
+            container_id: event.container_id.clone(),
+            container_name: event.container_name.clone(),
+            event: event.event_kind.to_string(),
+        }
+    }
+}
+
+impl From<&ActionEvent> for GuardActivity {
+    fn from(action: &ActionEvent) -> Self {
+        Self::Action {
+            action_id: action.id.clone(),
+            action_type: action.action_type.clone(),
+            target: action.target.clone(),
+            success: action.success,
+            simulated: action.simulated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::ContainerEventKind;
+
+    #[test]
+    fn lifecycle_from_container_event() {
+        let event = ContainerEvent::new(
+            "abc123def456",
+            "web-server",
+            ContainerEventKind::NetworkDisconnected {
+                network: "bridge".to_owned(),
+            },
+        );
+
+        let activity = GuardActivity::from(&event);
+        assert_eq!(
+            activity,
+            GuardActivity::Lifecycle {
+                container_id: "abc123def456".to_owned(),
+                container_name: "web-server".to_owned(),
+                event: "network_disconnected(bridge)".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn action_from_action_event() {
+        let mut action = ActionEvent::new("container_pause", "abc123def456", true);
+        action.simulated = true;
+
+        let activity = GuardActivity::from(&action);
+        let json = serde_json::to_value(&activity).unwrap();
+        assert_eq!(json["kind"], "action");
+        assert_eq!(json["action_type"], "container_pause");
+        assert_eq!(json["simulated"], true);
+    }
+
+    #[test]
+    fn policy_match_serializes_with_kind_tag() {
+        let activity = GuardActivity::PolicyMatch {
+            alert_id: "alert-1".to_owned(),
+            alert_title: "SSH brute force".to_owned(),
+            severity: Severity::High,
+            policy_id: "web-isolate".to_owned(),
+            policy_name: "Isolate web".to_owned(),
+            container_id: "abc123def456".to_owned(),
+            container_name: "web-1".to_owned(),
+            action: "pause".to_owned(),
+            standby: false,
+        };
+
+        let json = serde_json::to_string(&activity).unwrap();
+        assert!(json.contains(r#""kind":"policy_match""#));
+        let parsed: GuardActivity = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, activity);
+    }
+}
@@ -0,0 +1,360 @@
+//! 격리 감사 기록 -- 적용 중인 격리와 격리/해제 이력
+//!
+//! [`IsolationAudit`]은 [`IsolationExecutor`](crate::isolation::IsolationExecutor)가
+//! 적용한 격리를 컨테이너별 [`IsolationRecord`]로 보관하고, 모든 격리/해제
+//! 시도를 [`AuditEntry`]로 순서대로 기록합니다.
+//!
+//! 해제 시 무엇을 되돌릴지(일시정지 해제, 재시작, 네트워크 재연결)는 이
+//! 기록에서 결정되며, `--duration`으로 지정된 자동 해제 시각도 여기에
+//! 저장됩니다. 기록은 메모리에만 유지되므로 데몬이 재시작되면 사라집니다.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use ironpost_core::types::ContainerInfo;
+
+use crate::isolation::IsolationAction;
+
+/// 보관하는 감사 항목 최대 수
+///
+/// 초과하면 가장 오래된 항목부터 제거됩니다. 적용 중인 격리 기록은
+/// 이 제한과 관계없이 해제될 때까지 유지됩니다.
+pub const AUDIT_TRAIL_CAPACITY: usize = 1000;
+
+/// 적용 중인 격리
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IsolationRecord {
+    /// 컨테이너 ID
+    pub container_id: String,
+    /// 컨테이너 이름
+    pub container_name: String,
+    /// 적용된 격리 액션 (연결 해제한 네트워크 포함, 해제 시 재연결)
+    pub action: IsolationAction,
+    /// 격리를 일으킨 알림 또는 요청의 trace_id
+    pub trace_id: String,
+    /// 격리를 요청한 주체 (예: `policy:web-isolate`, `manual:ops-token`)
+    pub initiator: String,
+    /// 적용 시각 (Unix 밀리초)
+    pub applied_at_ms: u64,
+    /// 자동 해제 시각 (Unix 밀리초, `None`이면 수동 해제까지 유지)
+    pub expires_at_ms: Option<u64>,
+    /// 드라이런으로 시뮬레이션된 격리인지 여부
+    pub simulated: bool,
+}
+
+impl IsolationRecord {
+    /// 지금 적용된 격리 기록을 생성합니다.
+    pub fn new(
+        container: &ContainerInfo,
+        action: IsolationAction,
+        trace_id: &str,
+        initiator: &str,
+        duration: Option<Duration>,
+    ) -> Self {
+        let applied_at_ms = now_ms();
+        let expires_at_ms = duration.map(|d| {
+            applied_at_ms.saturating_add(u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+        });
+        Self {
+            container_id: container.id.clone(),
+            container_name: container.name.clone(),
+            action,
+            trace_id: trace_id.to_owned(),
+            initiator: initiator.to_owned(),
+            applied_at_ms,
+            expires_at_ms,
+            simulated: false,
+        }
+    }
+
+    /// 드라이런으로 시뮬레이션된 격리인지 설정합니다.
+    pub fn with_simulated(mut self, simulated: bool) -> Self {
+        self.simulated = simulated;
+        self
+    }
+
+    /// `target`이 이 컨테이너의 ID, ID 접두사 또는 이름인지 확인합니다.
+    fn matches(&self, target: &str) -> bool {
+        !target.is_empty()
+            && (self.container_id.starts_with(target) || self.container_name == target)
+    }
+}
+
+/// 감사 대상 작업
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    /// 격리 적용
+    Isolate,
+    /// 격리 해제
+    Release,
+}
+
+/// 격리/해제 시도 한 건
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// 순번 (1부터 시작, 항목마다 1씩 증가)
+    pub seq: u64,
+    /// 기록 시각 (Unix 밀리초)
+    pub timestamp_ms: u64,
+    /// 작업 종류
+    pub operation: AuditOperation,
+    /// 컨테이너 ID
+    pub container_id: String,
+    /// 컨테이너 이름
+    pub container_name: String,
+    /// 격리 액션 (해제의 경우 되돌린 액션)
+    pub action: String,
+    /// 작업을 요청한 주체
+    pub initiator: String,
+    /// 성공 여부
+    pub success: bool,
+    /// 실패 사유
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 격리 감사 기록 (공유 핸들)
+///
+/// 복제본은 같은 기록을 공유합니다.
+#[derive(Clone, Default)]
+pub struct IsolationAudit {
+    inner: Arc<Mutex<AuditState>>,
+}
+
+#[derive(Default)]
+struct AuditState {
+    last_seq: u64,
+    active: HashMap<String, IsolationRecord>,
+    entries: VecDeque<AuditEntry>,
+}
+
+impl AuditState {
+    fn push(
+        &mut self,
+        operation: AuditOperation,
+        record: &IsolationRecord,
+        initiator: &str,
+        error: Option<String>,
+    ) {
+        self.last_seq += 1;
+        if self.entries.len() >= AUDIT_TRAIL_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(AuditEntry {
+            seq: self.last_seq,
+            timestamp_ms: now_ms(),
+            operation,
+            container_id: record.container_id.clone(),
+            container_name: record.container_name.clone(),
+            action: record.action.to_string(),
+            initiator: initiator.to_owned(),
+            success: error.is_none(),
+            error,
+        });
+    }
+}
+
+impl IsolationAudit {
+    /// 빈 감사 기록을 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 성공한 격리를 적용 중인 격리로 등록하고 이력에 남깁니다.
+    pub async fn record_isolation(&self, record: IsolationRecord) {
+        let mut state = self.inner.lock().await;
+        let initiator = record.initiator.clone();
+        state.push(AuditOperation::Isolate, &record, &initiator, None);
+        state.active.insert(record.container_id.clone(), record);
+    }
+
+    /// 실패한 격리 시도를 이력에 남깁니다.
+    pub async fn record_isolation_failure(&self, record: &IsolationRecord, error: &str) {
+        let mut state = self.inner.lock().await;
+        state.push(
+            AuditOperation::Isolate,
+            record,
+            &record.initiator,
+            Some(error.to_owned()),
+        );
+    }
+
+    /// 해제 시도를 이력에 남깁니다.
+    ///
+    /// 성공하면 적용 중인 격리에서 제거하고, 실패하면 다시 시도할 수 있도록
+    /// 그대로 둡니다.
+    pub async fn record_release(
+        &self,
+        record: &IsolationRecord,
+        initiator: &str,
+        error: Option<String>,
+    ) {
+        let mut state = self.inner.lock().await;
+        if error.is_none() {
+            state.active.remove(&record.container_id);
+        }
+        state.push(AuditOperation::Release, record, initiator, error);
+    }
+
+    /// `target`(컨테이너 ID, ID 접두사 또는 이름)에 적용 중인 격리를 반환합니다.
+    ///
+    /// 접두사가 여러 컨테이너와 일치하면 `None`을 반환합니다.
+    pub async fn active(&self, target: &str) -> Option<IsolationRecord> {
+        let state = self.inner.lock().await;
+        if let Some(record) = state.active.get(target) {
+            return Some(record.clone());
+        }
+        let mut matches = state.active.values().filter(|r| r.matches(target));
+        match (matches.next(), matches.next()) {
+            (Some(record), None) => Some(record.clone()),
+            _ => None,
+        }
+    }
+
+    /// 적용 중인 모든 격리를 적용 시각 순으로 반환합니다.
+    pub async fn active_records(&self) -> Vec<IsolationRecord> {
+        let state = self.inner.lock().await;
+        let mut records: Vec<IsolationRecord> = state.active.values().cloned().collect();
+        records.sort_by_key(|r| r.applied_at_ms);
+        records
+    }
+
+    /// 자동 해제 시각이 `now_ms` 이전인 격리를 반환합니다.
+    pub async fn expired(&self, now_ms: u64) -> Vec<IsolationRecord> {
+        let state = self.inner.lock().await;
+        state
+            .active
+            .values()
+            .filter(|r| r.expires_at_ms.is_some_and(|at| at <= now_ms))
+            .cloned()
+            .collect()
+    }
+
+    /// 이력 항목을 오래된 순으로 반환합니다.
+    pub async fn entries(&self) -> Vec<AuditEntry> {
+        self.inner.lock().await.entries.iter().cloned().collect()
+    }
+}
+
+/// 현재 시각 (Unix 밀리초)
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container(id: &str, name: &str) -> ContainerInfo {
+        ContainerInfo {
+            id: id.to_owned(),
+            name: name.to_owned(),
+            image: "nginx:latest".to_owned(),
+            status: "running".to_owned(),
+            created_at: SystemTime::now(),
+        }
+    }
+
+    fn record(id: &str, name: &str, duration: Option<Duration>) -> IsolationRecord {
+        IsolationRecord::new(
+            &container(id, name),
+            IsolationAction::Pause,
+            "trace-1",
+            "manual:ops",
+            duration,
+        )
+    }
+
+    #[tokio::test]
+    async fn active_lookup_by_id_prefix_and_name() {
+        let audit = IsolationAudit::new();
+        audit
+            .record_isolation(record("abc123def456", "web-1", None))
+            .await;
+        audit
+            .record_isolation(record("abd999000111", "db-1", None))
+            .await;
+
+        assert!(audit.active("abc123def456").await.is_some());
+        assert_eq!(audit.active("abc1").await.unwrap().container_name, "web-1");
+        assert_eq!(
+            audit.active("db-1").await.unwrap().container_id,
+            "abd999000111"
+        );
+        assert!(audit.active("ab").await.is_none(), "ambiguous prefix");
+        assert!(audit.active("").await.is_none());
+        assert_eq!(audit.active_records().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn release_removes_active_and_keeps_history() {
+        let audit = IsolationAudit::new();
+        let applied = record("abc123def456", "web-1", None);
+        audit.record_isolation(applied.clone()).await;
+
+        audit
+            .record_release(&applied, "manual:ops", Some("unpause failed".to_owned()))
+            .await;
+        assert!(
+            audit.active("web-1").await.is_some(),
+            "failed release keeps isolation"
+        );
+
+        audit.record_release(&applied, "manual:ops", None).await;
+        assert!(audit.active("web-1").await.is_none());
+
+        let entries = audit.entries().await;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].operation, AuditOperation::Isolate);
+        assert_eq!(entries[1].error.as_deref(), Some("unpause failed"));
+        assert!(!entries[1].success);
+        assert_eq!(entries[2].operation, AuditOperation::Release);
+        assert_eq!(entries[2].seq, 3);
+        assert_eq!(entries[2].action, "pause");
+    }
+
+    #[tokio::test]
+    async fn expired_returns_only_due_records() {
+        let audit = IsolationAudit::new();
+        let timed = record("abc123def456", "web-1", Some(Duration::from_secs(60)));
+        let expires = timed.expires_at_ms.unwrap();
+        audit.record_isolation(timed).await;
+        audit
+            .record_isolation(record("def456abc123", "web-2", None))
+            .await;
+
+        assert!(audit.expired(expires - 1).await.is_empty());
+        let due = audit.expired(expires).await;
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].container_name, "web-1");
+    }
+
+    #[tokio::test]
+    async fn trail_is_bounded() {
+        let audit = IsolationAudit::new();
+        let applied = record("abc123def456", "web-1", None);
+        for _ in 0..AUDIT_TRAIL_CAPACITY + 5 {
+            audit.record_isolation_failure(&applied, "boom").await;
+        }
+
+        let entries = audit.entries().await;
+        assert_eq!(entries.len(), AUDIT_TRAIL_CAPACITY);
+        assert_eq!(entries[0].seq, 6);
+    }
+
+    #[test]
+    fn record_serializes_action() {
+        let json = serde_json::to_value(record("abc123def456", "web-1", None)).unwrap();
+        assert_eq!(json["action"], "Pause");
+        assert_eq!(json["initiator"], "manual:ops");
+        assert!(json["expires_at_ms"].is_null());
+    }
+}
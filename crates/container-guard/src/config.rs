@@ -83,6 +83,9 @@ pub struct ContainerGuardConfig {
     pub retry_backoff_base_ms: u64,
     /// 컨테이너 정보 캐시 TTL (초)
     pub container_cache_ttl_secs: u64,
+    /// 드라이런 모드 (Docker API를 호출하지 않고 격리 액션을 시뮬레이션만 수행)
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 impl Default for ContainerGuardConfig {
@@ -98,6 +101,7 @@ impl Default for ContainerGuardConfig {
             retry_max_attempts: 3,
             retry_backoff_base_ms: 500,
             container_cache_ttl_secs: 60,
+            dry_run: false,
         }
     }
 }
@@ -308,6 +312,12 @@ impl ContainerGuardConfigBuilder {
         self
     }
 
+    /// 드라이런 모드를 설정합니다.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.config.dry_run = dry_run;
+        self
+    }
+
     /// Validates and builds the configuration.
     ///
     /// # Errors
@@ -519,6 +529,7 @@ mod tests {
             .retry_max_attempts(5)
             .retry_backoff_base_ms(200)
             .container_cache_ttl_secs(120)
+            .dry_run(true)
             .build()
             .unwrap();
 
@@ -532,6 +543,7 @@ mod tests {
         assert_eq!(config.retry_max_attempts, 5);
         assert_eq!(config.retry_backoff_base_ms, 200);
         assert_eq!(config.container_cache_ttl_secs, 120);
+        assert!(config.dry_run);
     }
 
     #[test]
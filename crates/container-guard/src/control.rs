@@ -0,0 +1,448 @@
+//! 수동 격리 제어 -- 운영자 요청에 의한 격리와 해제
+//!
+//! [`IsolationControl`]은 가드가 정책 격리에 쓰는 것과 같은
+//! [`IsolationExecutor`]와 [`IsolationAudit`]을 공유합니다. 따라서 해제는
+//! 격리가 정책으로 적용되었든 수동으로 적용되었든 감사 기록에 남은 액션을
+//! 그대로 되돌립니다.
+//!
+//! `ironpost-daemon`은 가드를 플러그인으로 등록하기 전에
+//! [`ContainerGuard::isolation_control`](crate::guard::ContainerGuard::isolation_control)로
+//! 핸들을 받아 제어 API에 연결합니다.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{Mutex, watch};
+use tracing::{info, warn};
+
+use ironpost_core::types::ContainerInfo;
+
+use crate::audit::{IsolationAudit, IsolationRecord, now_ms};
+use crate::docker::DockerClient;
+use crate::error::ContainerGuardError;
+use crate::isolation::{IsolationAction, IsolationExecutor};
+use crate::monitor::DockerMonitor;
+
+/// 자동 해제로 기록되는 요청 주체
+pub const EXPIRY_INITIATOR: &str = "expiry";
+
+/// 수동 격리 제어 핸들
+///
+/// 복제본은 같은 실행기, 모니터, 감사 기록을 공유합니다.
+pub struct IsolationControl<D: DockerClient> {
+    executor: Arc<IsolationExecutor<D>>,
+    monitor: Arc<Mutex<DockerMonitor<D>>>,
+    audit: IsolationAudit,
+    isolation_gate: Option<watch::Receiver<bool>>,
+}
+
+impl<D: DockerClient> Clone for IsolationControl<D> {
+    fn clone(&self) -> Self {
+        Self {
+            executor: Arc::clone(&self.executor),
+            monitor: Arc::clone(&self.monitor),
+            audit: self.audit.clone(),
+            isolation_gate: self.isolation_gate.clone(),
+        }
+    }
+}
+
+impl<D: DockerClient> IsolationControl<D> {
+    /// 새 제어 핸들을 생성합니다.
+    pub fn new(
+        executor: Arc<IsolationExecutor<D>>,
+        monitor: Arc<Mutex<DockerMonitor<D>>>,
+        audit: IsolationAudit,
+        isolation_gate: Option<watch::Receiver<bool>>,
+    ) -> Self {
+        Self {
+            executor,
+            monitor,
+            audit,
+            isolation_gate,
+        }
+    }
+
+    /// 격리 감사 기록을 반환합니다.
+    pub fn audit(&self) -> &IsolationAudit {
+        &self.audit
+    }
+
+    /// `target`(컨테이너 이름, ID 또는 ID 접두사)에 격리 액션을 적용합니다.
+    ///
+    /// `duration`을 지정하면 그 시간이 지난 뒤 가드가 자동으로 해제합니다.
+    /// 이미 격리된 컨테이너는 먼저 해제해야 합니다.
+    ///
+    /// # Errors
+    ///
+    /// - `ContainerGuardError::AlreadyIsolated`: 적용 중인 격리가 있음
+    /// - `ContainerGuardError::ContainerNotFound`: 컨테이너를 찾을 수 없음
+    /// - `ContainerGuardError::IsolationFailed`: 스탠바이 인스턴스이거나 Docker 호출 실패
+    pub async fn isolate(
+        &self,
+        target: &str,
+        action: &IsolationAction,
+        duration: Option<Duration>,
+        initiator: &str,
+    ) -> Result<IsolationRecord, ContainerGuardError> {
+        let container = self.lookup(target).await?;
+        self.check_gate(&container.id)?;
+        if let IsolationAction::NetworkDisconnect { networks } = action
+            && networks.is_empty()
+        {
+            return Err(ContainerGuardError::IsolationFailed {
+                container_id: container.id,
+                reason: "network_disconnect requires at least one network".to_owned(),
+            });
+        }
+        if self.audit.active(&container.id).await.is_some() {
+            return Err(ContainerGuardError::AlreadyIsolated(container.name));
+        }
+
+        let action = self.executor.resolve(&container.id, action).await?;
+        let trace_id = uuid::Uuid::new_v4().to_string();
+        let record = IsolationRecord::new(&container, action, &trace_id, initiator, duration)
+            .with_simulated(self.executor.is_dry_run());
+
+        match self
+            .executor
+            .execute(&container.id, &record.action, &trace_id)
+            .await
+        {
+            Ok(()) => {
+                info!(
+                    container_id = %container.id,
+                    action = %record.action,
+                    initiator = initiator,
+                    expires_at_ms = ?record.expires_at_ms,
+                    "manual isolation applied"
+                );
+                self.audit.record_isolation(record.clone()).await;
+                Ok(record)
+            }
+            Err(e) => {
+                self.audit
+                    .record_isolation_failure(&record, &e.to_string())
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
+    /// `target`에 적용 중인 격리를 감사 기록에 따라 되돌립니다.
+    ///
+    /// 해제에 실패하면 격리는 적용 중으로 남아 다시 시도할 수 있습니다.
+    ///
+    /// # Errors
+    ///
+    /// - `ContainerGuardError::NotIsolated`: 적용 중인 격리가 없음
+    /// - `ContainerGuardError::IsolationFailed`: 스탠바이 인스턴스이거나 Docker 호출 실패
+    pub async fn release(
+        &self,
+        target: &str,
+        initiator: &str,
+    ) -> Result<IsolationRecord, ContainerGuardError> {
+        let record = self
+            .audit
+            .active(target)
+            .await
+            .ok_or_else(|| ContainerGuardError::NotIsolated(target.to_owned()))?;
+        self.check_gate(&record.container_id)?;
+
+        let result = self.executor.release(&record).await;
+        self.audit
+            .record_release(
+                &record,
+                initiator,
+                result.as_ref().err().map(ToString::to_string),
+            )
+            .await;
+        result.map(|()| record)
+    }
+
+    /// 자동 해제 시각이 지난 격리를 모두 해제하고, 해제한 수를 반환합니다.
+    ///
+    /// 실패한 해제는 다음 호출에서 다시 시도됩니다.
+    pub async fn release_expired(&self) -> usize {
+        let mut released = 0;
+        for record in self.audit.expired(now_ms()).await {
+            match self.release(&record.container_id, EXPIRY_INITIATOR).await {
+                Ok(_) => released += 1,
+                Err(e) => {
+                    warn!(
+                        container_id = %record.container_id,
+                        error = %e,
+                        "failed to release expired isolation, will retry"
+                    );
+                }
+            }
+        }
+        released
+    }
+
+    /// 적용 중인 모든 격리를 적용 시각 순으로 반환합니다.
+    pub async fn active(&self) -> Vec<IsolationRecord> {
+        self.audit.active_records().await
+    }
+
+    /// 이름 또는 ID(접두사)로 컨테이너를 찾습니다.
+    async fn lookup(&self, target: &str) -> Result<ContainerInfo, ContainerGuardError> {
+        let mut monitor = self.monitor.lock().await;
+        if let Err(e) = monitor.refresh_if_needed().await {
+            warn!(error = %e, "failed to refresh container list");
+        }
+        if let Some(container) = monitor.find_by_name(target) {
+            return Ok(container.clone());
+        }
+        monitor.get_container(target).await
+    }
+
+    /// HA 스탠바이 인스턴스에서는 격리 변경을 거부합니다.
+    fn check_gate(&self, container_id: &str) -> Result<(), ContainerGuardError> {
+        if self
+            .isolation_gate
+            .as_ref()
+            .is_some_and(|gate| !*gate.borrow())
+        {
+            return Err(ContainerGuardError::IsolationFailed {
+                container_id: container_id.to_owned(),
+                reason: "standby instance; isolation actions run on the leader".to_owned(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::MockDockerClient;
+    use ironpost_core::event::ActionEvent;
+    use std::time::SystemTime;
+    use tokio::sync::mpsc;
+
+    fn sample_container() -> ContainerInfo {
+        ContainerInfo {
+            id: "abc123def456".to_owned(),
+            name: "web-server".to_owned(),
+            image: "nginx:latest".to_owned(),
+            status: "running".to_owned(),
+            created_at: SystemTime::now(),
+        }
+    }
+
+    fn make_control(
+        client: MockDockerClient,
+    ) -> (
+        IsolationControl<MockDockerClient>,
+        Arc<MockDockerClient>,
+        mpsc::Receiver<ActionEvent>,
+    ) {
+        let docker = Arc::new(client);
+        let (action_tx, action_rx) = mpsc::channel(16);
+        let executor = IsolationExecutor::new(
+            Arc::clone(&docker),
+            action_tx,
+            Duration::from_secs(5),
+            0,
+            Duration::from_millis(10),
+        );
+        let monitor = DockerMonitor::new(
+            Arc::clone(&docker),
+            Duration::from_secs(10),
+            Duration::from_secs(60),
+        );
+        let control = IsolationControl::new(
+            Arc::new(executor),
+            Arc::new(Mutex::new(monitor)),
+            IsolationAudit::new(),
+            None,
+        );
+        (control, docker, action_rx)
+    }
+
+    #[tokio::test]
+    async fn release_undoes_the_recorded_action() {
+        let client = MockDockerClient::new()
+            .with_containers(vec![sample_container()])
+            .with_networks(&["backend", "bridge"]);
+        let (control, docker, mut action_rx) = make_control(client);
+
+        let record = control
+            .isolate(
+                "web-server",
+                &IsolationAction::Quarantine {
+                    networks: Vec::new(),
+                },
+                None,
+                "manual:ops",
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            record.action,
+            IsolationAction::Quarantine {
+                networks: vec!["backend".to_owned(), "bridge".to_owned()]
+            }
+        );
+        assert_eq!(
+            action_rx.recv().await.unwrap().action_type,
+            "container_quarantine"
+        );
+
+        // The mock keeps reporting both networks as connected, so the release
+        // only unpauses: reconnect skips networks that are already attached
+        control.release("abc123", "manual:ops").await.unwrap();
+        let event = action_rx.recv().await.unwrap();
+        assert_eq!(event.action_type, "container_release");
+        assert!(event.success);
+
+        assert_eq!(
+            docker.calls(),
+            vec![
+                "disconnect:abc123def456:backend",
+                "disconnect:abc123def456:bridge",
+                "pause:abc123def456",
+                "unpause:abc123def456",
+            ]
+        );
+        assert!(control.active().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn release_restarts_stopped_container() {
+        let client = MockDockerClient::new().with_containers(vec![sample_container()]);
+        let (control, docker, _action_rx) = make_control(client);
+
+        control
+            .isolate("abc123def456", &IsolationAction::Stop, None, "manual:ops")
+            .await
+            .unwrap();
+        control.release("web-server", "manual:ops").await.unwrap();
+
+        assert_eq!(
+            docker.calls(),
+            vec!["stop:abc123def456", "start:abc123def456"]
+        );
+    }
+
+    #[tokio::test]
+    async fn network_disconnect_release_reconnects_missing_networks() {
+        let client = MockDockerClient::new().with_containers(vec![sample_container()]);
+        let (control, docker, _action_rx) = make_control(client);
+
+        let action = IsolationAction::NetworkDisconnect {
+            networks: vec!["bridge".to_owned()],
+        };
+        control
+            .isolate("web-server", &action, None, "manual:ops")
+            .await
+            .unwrap();
+        control.release("web-server", "manual:ops").await.unwrap();
+
+        assert_eq!(
+            docker.calls(),
+            vec![
+                "disconnect:abc123def456:bridge",
+                "connect:abc123def456:bridge"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn isolate_rejects_second_isolation_and_release_without_isolation() {
+        let client = MockDockerClient::new().with_containers(vec![sample_container()]);
+        let (control, _docker, _action_rx) = make_control(client);
+
+        let err = control
+            .release("web-server", "manual:ops")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ContainerGuardError::NotIsolated(_)));
+
+        control
+            .isolate("web-server", &IsolationAction::Pause, None, "manual:ops")
+            .await
+            .unwrap();
+        let err = control
+            .isolate("web-server", &IsolationAction::Stop, None, "manual:ops")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ContainerGuardError::AlreadyIsolated(_)));
+
+        let err = control
+            .isolate(
+                "web-server",
+                &IsolationAction::NetworkDisconnect {
+                    networks: Vec::new(),
+                },
+                None,
+                "manual:ops",
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("at least one network"));
+    }
+
+    #[tokio::test]
+    async fn failed_release_keeps_isolation_active() {
+        let client = MockDockerClient::new().with_containers(vec![sample_container()]);
+        let (control, _docker, _action_rx) = make_control(client);
+        control
+            .isolate("web-server", &IsolationAction::Pause, None, "manual:ops")
+            .await
+            .unwrap();
+
+        // Drop the container so the unpause fails
+        let record = control.audit().active("web-server").await.unwrap();
+        let (failing, _docker, _rx) = make_control(MockDockerClient::new());
+        failing.audit().record_isolation(record).await;
+
+        assert!(failing.release("web-server", "manual:ops").await.is_err());
+        assert_eq!(failing.active().await.len(), 1);
+        let entries = failing.audit().entries().await;
+        assert!(!entries.last().unwrap().success);
+    }
+
+    #[tokio::test]
+    async fn release_expired_releases_due_isolations() {
+        let client = MockDockerClient::new().with_containers(vec![sample_container()]);
+        let (control, docker, _action_rx) = make_control(client);
+
+        control
+            .isolate(
+                "web-server",
+                &IsolationAction::Pause,
+                Some(Duration::ZERO),
+                "manual:ops",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(control.release_expired().await, 1);
+        assert!(control.active().await.is_empty());
+        assert_eq!(
+            docker.calls(),
+            vec!["pause:abc123def456", "unpause:abc123def456"]
+        );
+        let entries = control.audit().entries().await;
+        assert_eq!(entries.last().unwrap().initiator, EXPIRY_INITIATOR);
+    }
+
+    #[tokio::test]
+    async fn standby_instance_rejects_isolation() {
+        let client = MockDockerClient::new().with_containers(vec![sample_container()]);
+        let (control, _docker, _action_rx) = make_control(client);
+        let (_gate_tx, gate_rx) = watch::channel(false);
+        let control = IsolationControl {
+            isolation_gate: Some(gate_rx),
+            ..control
+        };
+
+        let err = control
+            .isolate("web-server", &IsolationAction::Pause, None, "manual:ops")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("standby"));
+    }
+}
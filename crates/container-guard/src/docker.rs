@@ -163,6 +163,18 @@ pub trait DockerClient: Send + Sync + 'static {
         id: &str,
     ) -> impl Future<Output = Result<(), ContainerGuardError>> + Send;
 
+    /// Starts a stopped container.
+    ///
+    /// Used to release a container isolated with a stop action.
+    ///
+    /// # Errors
+    ///
+    /// - `ContainerGuardError::IsolationFailed`: Container cannot be started
+    fn start_container(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = Result<(), ContainerGuardError>> + Send;
+
     /// Disconnects a container from a specific network.
     ///
     /// Uses `force: true` to ensure disconnection even if the container is running.
@@ -181,6 +193,32 @@ pub trait DockerClient: Send + Sync + 'static {
         network: &str,
     ) -> impl Future<Output = Result<(), ContainerGuardError>> + Send;
 
+    /// Connects a container to a network.
+    ///
+    /// Used to undo [`disconnect_network`](Self::disconnect_network) on release.
+    /// The container gets default endpoint settings, so static IPs and aliases
+    /// from the original connection are not restored.
+    ///
+    /// # Errors
+    ///
+    /// - `ContainerGuardError::IsolationFailed`: Network connect failed
+    fn connect_network(
+        &self,
+        container_id: &str,
+        network: &str,
+    ) -> impl Future<Output = Result<(), ContainerGuardError>> + Send;
+
+    /// Lists the networks a container is connected to, sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// - `ContainerGuardError::ContainerNotFound`: Container does not exist (404)
+    /// - `ContainerGuardError::DockerApi`: Invalid ID or other API errors
+    fn container_networks(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = Result<Vec<String>, ContainerGuardError>> + Send;
+
     /// Checks Docker daemon connectivity.
     ///
     /// Used by `ContainerGuard`'s `Pipeline::health_check()` implementation
@@ -396,6 +434,65 @@ impl DockerClient for BollardDockerClient {
             })
     }
 
+    async fn start_container(&self, id: &str) -> Result<(), ContainerGuardError> {
+        validate_container_id(id)?;
+
+        use bollard::container::StartContainerOptions;
+
+        self.docker
+            .start_container(id, None::<StartContainerOptions<String>>)
+            .await
+            .map_err(|e| ContainerGuardError::IsolationFailed {
+                container_id: id.to_owned(),
+                reason: format!("start failed: {e}"),
+            })
+    }
+
+    async fn connect_network(
+        &self,
+        container_id: &str,
+        network: &str,
+    ) -> Result<(), ContainerGuardError> {
+        validate_container_id(container_id)?;
+
+        use bollard::network::ConnectNetworkOptions;
+
+        self.docker
+            .connect_network(
+                network,
+                ConnectNetworkOptions {
+                    container: container_id.to_owned(),
+                    endpoint_config: Default::default(),
+                },
+            )
+            .await
+            .map_err(|e| ContainerGuardError::IsolationFailed {
+                container_id: container_id.to_owned(),
+                reason: format!("network connect to '{network}' failed: {e}"),
+            })
+    }
+
+    async fn container_networks(&self, id: &str) -> Result<Vec<String>, ContainerGuardError> {
+        validate_container_id(id)?;
+
+        let details = self.docker.inspect_container(id, None).await.map_err(|e| {
+            if let bollard::errors::Error::DockerResponseServerError { status_code, .. } = &e
+                && *status_code == 404
+            {
+                return ContainerGuardError::ContainerNotFound(id.to_owned());
+            }
+            ContainerGuardError::DockerApi(format!("inspect container failed: {e}"))
+        })?;
+
+        let mut networks: Vec<String> = details
+            .network_settings
+            .and_then(|s| s.networks)
+            .map(|n| n.into_keys().collect())
+            .unwrap_or_default();
+        networks.sort();
+        Ok(networks)
+    }
+
     async fn ping(&self) -> Result<(), ContainerGuardError> {
         self.docker
             .ping()
@@ -415,6 +512,10 @@ pub struct MockDockerClient {
     pub containers: Vec<ContainerInfo>,
     /// 액션 호출 시 실패를 시뮬레이션할지 여부
     pub fail_actions: bool,
+    /// container_networks 호출 시 반환할 네트워크 목록
+    pub networks: Vec<String>,
+    /// 호출된 액션 기록 (예: `pause:abc123`, `connect:abc123:bridge`)
+    pub calls: Arc<std::sync::Mutex<Vec<String>>>,
 }
 
 #[cfg(test)]
@@ -435,6 +536,36 @@ impl MockDockerClient {
         self.fail_actions = true;
         self
     }
+
+    /// container_networks가 반환할 네트워크 목록을 설정합니다.
+    pub fn with_networks(mut self, networks: &[&str]) -> Self {
+        self.networks = networks.iter().map(|n| (*n).to_owned()).collect();
+        self
+    }
+
+    /// 지금까지 호출된 액션 기록을 반환합니다.
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    /// 실패 설정을 확인하고, 컨테이너가 존재하면 호출을 기록합니다.
+    async fn record_call(
+        &self,
+        container_id: &str,
+        call: String,
+    ) -> Result<(), ContainerGuardError> {
+        if self.fail_actions {
+            return Err(ContainerGuardError::IsolationFailed {
+                container_id: container_id.to_owned(),
+                reason: "mock failure".to_owned(),
+            });
+        }
+        self.inspect_container(container_id).await?;
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.push(call);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -452,52 +583,42 @@ impl DockerClient for MockDockerClient {
     }
 
     async fn stop_container(&self, id: &str) -> Result<(), ContainerGuardError> {
-        if self.fail_actions {
-            return Err(ContainerGuardError::IsolationFailed {
-                container_id: id.to_owned(),
-                reason: "mock failure".to_owned(),
-            });
-        }
-        // Verify container exists
-        self.inspect_container(id).await?;
-        Ok(())
+        self.record_call(id, format!("stop:{id}")).await
     }
 
     async fn pause_container(&self, id: &str) -> Result<(), ContainerGuardError> {
-        if self.fail_actions {
-            return Err(ContainerGuardError::IsolationFailed {
-                container_id: id.to_owned(),
-                reason: "mock failure".to_owned(),
-            });
-        }
-        self.inspect_container(id).await?;
-        Ok(())
+        self.record_call(id, format!("pause:{id}")).await
     }
 
     async fn unpause_container(&self, id: &str) -> Result<(), ContainerGuardError> {
-        if self.fail_actions {
-            return Err(ContainerGuardError::IsolationFailed {
-                container_id: id.to_owned(),
-                reason: "mock failure".to_owned(),
-            });
-        }
-        self.inspect_container(id).await?;
-        Ok(())
+        self.record_call(id, format!("unpause:{id}")).await
+    }
+
+    async fn start_container(&self, id: &str) -> Result<(), ContainerGuardError> {
+        self.record_call(id, format!("start:{id}")).await
     }
 
     async fn disconnect_network(
         &self,
         container_id: &str,
-        _network: &str,
+        network: &str,
     ) -> Result<(), ContainerGuardError> {
-        if self.fail_actions {
-            return Err(ContainerGuardError::IsolationFailed {
-                container_id: container_id.to_owned(),
-                reason: "mock failure".to_owned(),
-            });
-        }
-        self.inspect_container(container_id).await?;
-        Ok(())
+        self.record_call(container_id, format!("disconnect:{container_id}:{network}"))
+            .await
+    }
+
+    async fn connect_network(
+        &self,
+        container_id: &str,
+        network: &str,
+    ) -> Result<(), ContainerGuardError> {
+        self.record_call(container_id, format!("connect:{container_id}:{network}"))
+            .await
+    }
+
+    async fn container_networks(&self, id: &str) -> Result<Vec<String>, ContainerGuardError> {
+        self.inspect_container(id).await?;
+        Ok(self.networks.clone())
     }
 
     async fn ping(&self) -> Result<(), ContainerGuardError> {
@@ -7,7 +7,7 @@
 //! # Error Categories
 //!
 //! - **Docker API errors**: `DockerApi`, `DockerConnection`
-//! - **Isolation failures**: `IsolationFailed`, `ContainerNotFound`
+//! - **Isolation failures**: `IsolationFailed`, `ContainerNotFound`, `AlreadyIsolated`, `NotIsolated`
 //! - **Policy errors**: `PolicyLoad`, `PolicyValidation`
 //! - **Configuration errors**: `Config`
 //! - **Channel errors**: `Channel`
@@ -83,6 +83,14 @@ pub enum ContainerGuardError {
     #[error("container not found: {0}")]
     ContainerNotFound(String),
 
+    /// 이미 격리된 컨테이너 (해제 전까지 다른 액션 불가)
+    #[error("container '{0}' is already isolated; release it first")]
+    AlreadyIsolated(String),
+
+    /// 감사 기록에 적용된 격리가 없는 컨테이너
+    #[error("container '{0}' has no active isolation to release")]
+    NotIsolated(String),
+
     /// 잘못된 컨테이너 ID
     #[error("invalid container id: {0}")]
     InvalidContainerId(String),
@@ -120,6 +128,12 @@ impl From<ContainerGuardError> for IronpostError {
             ContainerGuardError::ContainerNotFound(id) => {
                 IronpostError::Container(ContainerError::NotFound(id.clone()))
             }
+            ContainerGuardError::AlreadyIsolated(id) | ContainerGuardError::NotIsolated(id) => {
+                IronpostError::Container(ContainerError::IsolationFailed {
+                    container_id: id.clone(),
+                    reason: err.to_string(),
+                })
+            }
             ContainerGuardError::InvalidContainerId(msg) => IronpostError::Container(
                 ContainerError::DockerApi(format!("invalid container id: {}", msg)),
             ),
@@ -246,4 +260,15 @@ mod tests {
             IronpostError::Container(ContainerError::PolicyViolation(_))
         ));
     }
+
+    #[test]
+    fn converts_to_ironpost_error_release_state() {
+        let err = ContainerGuardError::NotIsolated("abc123".to_owned());
+        assert!(err.to_string().contains("no active isolation"));
+        let ironpost_err: IronpostError = err.into();
+        assert!(matches!(
+            ironpost_err,
+            IronpostError::Container(ContainerError::IsolationFailed { .. })
+        ));
+    }
 }
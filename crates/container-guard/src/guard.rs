@@ -13,26 +13,41 @@
 //!                          |
 //!                     ActionEvent ──mpsc──> downstream
 //! ```
+//!
+//! 인벤토리 폴링 태스크는 `poll_interval_secs`마다 컨테이너 목록을 새로고침하고,
+//! 수명주기 변화와 정책 매칭은 [`GuardActivity`]로 broadcast 발행됩니다.
+//!
+//! 적용된 격리는 [`IsolationAudit`]에 기록되며, 만료 태스크가 자동 해제 시각이
+//! 지난 격리를 [`IsolationControl`]로 되돌립니다.
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
-use tokio::sync::{Mutex, mpsc};
+use tokio::sync::{Mutex, broadcast, mpsc, watch};
 use tracing::{debug, error, info, warn};
 
 use ironpost_core::error::IronpostError;
 use ironpost_core::event::{ActionEvent, AlertEvent, MODULE_CONTAINER_GUARD};
 use ironpost_core::metrics as m;
 use ironpost_core::pipeline::{HealthStatus, Pipeline};
-use ironpost_core::plugin::{Plugin, PluginInfo, PluginState, PluginType};
+use ironpost_core::plugin::{
+    ChannelUsage, Plugin, PluginInfo, PluginState, PluginType, ResourceUsage,
+};
 
+use crate::activity::{ACTIVITY_CHANNEL_CAPACITY, GuardActivity};
+use crate::audit::{IsolationAudit, IsolationRecord};
 use crate::config::ContainerGuardConfig;
+use crate::control::IsolationControl;
 use crate::docker::DockerClient;
 use crate::error::ContainerGuardError;
 use crate::isolation::IsolationExecutor;
 use crate::monitor::DockerMonitor;
 use crate::policy::PolicyEngine;
+use crate::simulation::PolicySimulator;
+
+/// 자동 해제 만료 확인 주기
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
 /// 가드 실행 상태
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -81,6 +96,12 @@ pub struct ContainerGuard<D: DockerClient> {
     alert_rx: Option<mpsc::Receiver<AlertEvent>>,
     /// 액션 전송 채널
     action_tx: mpsc::Sender<ActionEvent>,
+    /// 격리 실행기 (정책 격리와 수동 격리가 공유)
+    executor: Arc<IsolationExecutor<D>>,
+    /// 격리 감사 기록
+    audit: IsolationAudit,
+    /// 활동 발행 채널 (수명주기 변화, 정책 매칭)
+    activity_tx: broadcast::Sender<GuardActivity>,
     /// 백그라운드 태스크 핸들
     tasks: Vec<tokio::task::JoinHandle<()>>,
     /// 처리된 알림 카운터
@@ -89,6 +110,8 @@ pub struct ContainerGuard<D: DockerClient> {
     isolations_executed: Arc<AtomicU64>,
     /// 격리 실패 카운터
     isolation_failures: Arc<AtomicU64>,
+    /// 격리 실행 허용 여부 (HA 구성에서 리더일 때만 true, 미설정 시 항상 허용)
+    isolation_gate: Option<watch::Receiver<bool>>,
 }
 
 impl<D: DockerClient> ContainerGuard<D> {
@@ -138,6 +161,43 @@ impl<D: DockerClient> ContainerGuard<D> {
     pub fn auto_isolate_enabled(&self) -> bool {
         self.config.auto_isolate
     }
+
+    /// 가드 활동(컨테이너 수명주기 변화, 정책 매칭)을 구독합니다.
+    ///
+    /// 격리 실행 결과는 액션 채널의 [`ActionEvent`]로 전달되므로 여기에
+    /// 포함되지 않습니다. 수신자가 [`ACTIVITY_CHANNEL_CAPACITY`]보다 뒤처지면
+    /// 오래된 활동이 유실됩니다.
+    pub fn subscribe_activity(&self) -> broadcast::Receiver<GuardActivity> {
+        self.activity_tx.subscribe()
+    }
+
+    /// 수동 격리/해제 핸들을 반환합니다.
+    ///
+    /// 핸들은 가드의 격리 실행기와 감사 기록을 공유하므로, 정책으로 적용된
+    /// 격리도 해제할 수 있습니다.
+    pub fn isolation_control(&self) -> IsolationControl<D> {
+        IsolationControl::new(
+            Arc::clone(&self.executor),
+            Arc::clone(&self.monitor),
+            self.audit.clone(),
+            self.isolation_gate.clone(),
+        )
+    }
+
+    /// 정책 시뮬레이션 핸들을 반환합니다.
+    ///
+    /// 핸들은 가드의 정책 엔진과 컨테이너 인벤토리를 공유하므로, 런타임 중
+    /// 정책 변경도 시뮬레이션에 바로 반영됩니다.
+    pub fn policy_simulator(&self) -> PolicySimulator<D> {
+        PolicySimulator::new(
+            Arc::clone(&self.policy_engine),
+            Arc::clone(&self.monitor),
+            self.audit.clone(),
+            self.config.auto_isolate,
+            self.config.dry_run,
+            self.isolation_gate.clone(),
+        )
+    }
 }
 
 impl<D: DockerClient> Pipeline for ContainerGuard<D> {
@@ -163,36 +223,75 @@ impl<D: DockerClient> Pipeline for ContainerGuard<D> {
             }
         }
 
-        // 3. 알림 처리 루프 스폰
+        // 3. 인벤토리 폴링 루프 스폰 (수명주기 변화 발행)
+        let poll_interval = Duration::from_secs(self.config.poll_interval_secs);
+        let poll_monitor = Arc::clone(&self.monitor);
+        let poll_activity_tx = self.activity_tx.clone();
+        let poll_task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval_at(
+                tokio::time::Instant::now() + poll_interval,
+                poll_interval,
+            );
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let changes = {
+                    let mut mon = poll_monitor.lock().await;
+                    if let Err(e) = mon.refresh().await {
+                        warn!(error = %e, "failed to poll container inventory");
+                    }
+                    mon.take_changes()
+                };
+                for change in &changes {
+                    debug!(
+                        container_id = %change.container_id,
+                        event = %change.event_kind,
+                        "container lifecycle change"
+                    );
+                    // 수신자가 없으면 버려집니다
+                    let _ = poll_activity_tx.send(GuardActivity::from(change));
+                }
+            }
+        });
+        self.tasks.push(poll_task);
+
+        // 4. 자동 해제 루프 스폰 (`--duration`으로 적용된 격리)
+        let expiry_control = self.isolation_control();
+        let expiry_task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(EXPIRY_CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let released = expiry_control.release_expired().await;
+                if released > 0 {
+                    info!(released = released, "released expired isolations");
+                }
+            }
+        });
+        self.tasks.push(expiry_task);
+
+        // 5. 알림 처리 루프 스폰
         let mut alert_rx = self.alert_rx.take().ok_or(IronpostError::Pipeline(
             ironpost_core::error::PipelineError::InitFailed(
                 "alert receiver not available (was it consumed by a previous start? rebuild the guard to restart)".to_owned(),
             ),
         ))?;
 
-        let docker = Arc::clone(&self.docker);
-        let action_tx = self.action_tx.clone();
+        let executor = Arc::clone(&self.executor);
+        let audit = self.audit.clone();
         let alerts_processed = Arc::clone(&self.alerts_processed);
         let isolations_executed = Arc::clone(&self.isolations_executed);
         let isolation_failures = Arc::clone(&self.isolation_failures);
         let auto_isolate = self.config.auto_isolate;
-        let action_timeout = Duration::from_secs(self.config.action_timeout_secs);
-        let retry_max = self.config.retry_max_attempts;
-        let retry_backoff = Duration::from_millis(self.config.retry_backoff_base_ms);
+        let dry_run = self.config.dry_run;
+        let isolation_gate = self.isolation_gate.clone();
+        let activity_tx = self.activity_tx.clone();
 
         // Share policy engine and monitor with spawned task
         let policy_engine = Arc::clone(&self.policy_engine);
         let monitor = Arc::clone(&self.monitor);
 
         let processing_task = tokio::spawn(async move {
-            let executor = IsolationExecutor::new(
-                Arc::clone(&docker),
-                action_tx.clone(),
-                action_timeout,
-                retry_max,
-                retry_backoff,
-            );
-
             loop {
                 tokio::select! {
                     Some(alert) = alert_rx.recv() => {
@@ -235,6 +334,37 @@ impl<D: DockerClient> Pipeline for ContainerGuard<D> {
                         for container in &containers {
                             if let Some(policy_match) = engine.evaluate(&alert, container) {
                                 metrics::counter!(m::CONTAINER_GUARD_POLICY_VIOLATIONS_TOTAL).increment(1);
+
+                                let standby = isolation_gate
+                                    .as_ref()
+                                    .is_some_and(|gate| !*gate.borrow());
+                                let _ = activity_tx.send(GuardActivity::PolicyMatch {
+                                    alert_id: alert.alert.id.clone(),
+                                    alert_title: alert.alert.title.clone(),
+                                    severity: alert.severity,
+                                    policy_id: policy_match.policy_id.clone(),
+                                    policy_name: policy_match.policy_name.clone(),
+                                    container_id: container.id.clone(),
+                                    container_name: container.name.clone(),
+                                    action: policy_match.action.to_string(),
+                                    standby,
+                                });
+
+                                // 스탠바이 인스턴스는 정책 평가까지만 수행하고 격리는 리더에게 맡깁니다
+                                if standby {
+                                    info!(
+                                        container_id = %container.id,
+                                        policy = %policy_match.policy_name,
+                                        "standby instance, isolation left to the leader"
+                                    );
+                                    metrics::counter!(
+                                        m::CONTAINER_GUARD_ISOLATIONS_TOTAL,
+                                        m::LABEL_ACTION => format!("{}", policy_match.action).to_lowercase(),
+                                        m::LABEL_RESULT => "standby"
+                                    ).increment(1);
+                                    break;
+                                }
+
                                 info!(
                                     container_id = %container.id,
                                     container_name = %container.name,
@@ -245,21 +375,34 @@ impl<D: DockerClient> Pipeline for ContainerGuard<D> {
 
                                 let action_name = format!("{}", policy_match.action);
                                 let trace_id = alert.metadata.trace_id.clone();
-                                match executor
-                                    .execute(
-                                        &container.id,
-                                        &policy_match.action,
-                                        &trace_id,
-                                    )
-                                    .await
-                                {
-                                    Ok(()) => {
+                                let result = match executor.resolve(&container.id, &policy_match.action).await {
+                                    Ok(action) => executor
+                                        .execute(&container.id, &action, &trace_id)
+                                        .await
+                                        .map(|()| action),
+                                    Err(e) => Err(e),
+                                };
+                                match result {
+                                    Ok(action) => {
                                         isolations_executed.fetch_add(1, Ordering::Relaxed);
                                         metrics::counter!(
                                             m::CONTAINER_GUARD_ISOLATIONS_TOTAL,
                                             m::LABEL_ACTION => action_name.to_lowercase(),
-                                            m::LABEL_RESULT => "success"
+                                            m::LABEL_RESULT => if dry_run { "simulated" } else { "success" }
                                         ).increment(1);
+                                        // Keep an existing record: it knows how to undo the first isolation
+                                        if audit.active(&container.id).await.is_none() {
+                                            let initiator = format!("policy:{}", policy_match.policy_id);
+                                            let record = IsolationRecord::new(
+                                                container,
+                                                action,
+                                                &trace_id,
+                                                &initiator,
+                                                None,
+                                            )
+                                            .with_simulated(dry_run);
+                                            audit.record_isolation(record).await;
+                                        }
                                     }
                                     Err(e) => {
                                         isolation_failures.fetch_add(1, Ordering::Relaxed);
@@ -375,6 +518,26 @@ impl<D: DockerClient> Plugin for ContainerGuard<D> {
     async fn health_check(&self) -> HealthStatus {
         <Self as Pipeline>::health_check(self).await
     }
+
+    async fn resource_usage(&self) -> ResourceUsage {
+        // 컨테이너 캐시와 정책이 주요 상주 메모리
+        let containers = self.monitor.lock().await.container_count();
+        let policies = self.policy_engine.lock().await.policy_count();
+        let memory_bytes = ResourceUsage::estimate_bytes(
+            containers,
+            std::mem::size_of::<ironpost_core::types::ContainerInfo>(),
+        )
+        .saturating_add(ResourceUsage::estimate_bytes(
+            policies,
+            std::mem::size_of::<crate::policy::SecurityPolicy>(),
+        ));
+
+        ResourceUsage {
+            tasks: ResourceUsage::live_tasks(&self.tasks),
+            channels: vec![ChannelUsage::from_sender("actions", &self.action_tx)],
+            memory_bytes,
+        }
+    }
 }
 
 /// 컨테이너 가드 빌더
@@ -387,6 +550,7 @@ pub struct ContainerGuardBuilder<D: DockerClient> {
     action_tx: Option<mpsc::Sender<ActionEvent>>,
     action_channel_capacity: usize,
     policies: Vec<crate::policy::SecurityPolicy>,
+    isolation_gate: Option<watch::Receiver<bool>>,
 }
 
 impl<D: DockerClient> ContainerGuardBuilder<D> {
@@ -399,6 +563,7 @@ impl<D: DockerClient> ContainerGuardBuilder<D> {
             action_tx: None,
             action_channel_capacity: 256,
             policies: Vec::new(),
+            isolation_gate: None,
         }
     }
 
@@ -442,6 +607,16 @@ impl<D: DockerClient> ContainerGuardBuilder<D> {
         self
     }
 
+    /// 격리 실행 게이트를 설정합니다.
+    ///
+    /// 게이트 값이 `false`인 동안에는 정책이 매칭되어도 격리를 실행하지 않습니다.
+    /// `ironpost-daemon`의 HA 리더 선출 결과를 여기에 연결하여
+    /// 리더 인스턴스만 파괴적 액션을 수행하도록 합니다.
+    pub fn isolation_gate(mut self, gate: watch::Receiver<bool>) -> Self {
+        self.isolation_gate = Some(gate);
+        self
+    }
+
     /// 가드를 빌드합니다.
     ///
     /// # Returns
@@ -469,6 +644,17 @@ impl<D: DockerClient> ContainerGuardBuilder<D> {
             (tx, Some(rx))
         };
 
+        let executor = Arc::new(
+            IsolationExecutor::new(
+                Arc::clone(&docker),
+                action_tx.clone(),
+                Duration::from_secs(self.config.action_timeout_secs),
+                self.config.retry_max_attempts,
+                Duration::from_millis(self.config.retry_backoff_base_ms),
+            )
+            .with_dry_run(self.config.dry_run),
+        );
+
         let mut policy_engine_inner = PolicyEngine::new();
         for policy in self.policies {
             policy_engine_inner.add_policy(policy)?;
@@ -500,10 +686,14 @@ impl<D: DockerClient> ContainerGuardBuilder<D> {
             monitor,
             alert_rx: self.alert_rx,
             action_tx,
+            executor,
+            audit: IsolationAudit::new(),
+            activity_tx: broadcast::channel(ACTIVITY_CHANNEL_CAPACITY).0,
             tasks: Vec::new(),
             alerts_processed: Arc::new(AtomicU64::new(0)),
             isolations_executed: Arc::new(AtomicU64::new(0)),
             isolation_failures: Arc::new(AtomicU64::new(0)),
+            isolation_gate: self.isolation_gate,
         };
 
         Ok((guard, action_rx))
@@ -609,6 +799,19 @@ mod tests {
         assert!(!guard.auto_isolate_enabled());
     }
 
+    #[tokio::test]
+    async fn guard_resource_usage_counts_policies() {
+        let (guard, _action_rx) = make_builder().add_policy(sample_policy()).build().unwrap();
+        let usage = Plugin::resource_usage(&guard).await;
+        assert_eq!(usage.tasks, 0);
+        assert_eq!(usage.channels.len(), 1);
+        assert_eq!(usage.channels[0].name, "actions");
+        assert_eq!(usage.channels[0].depth, 0);
+        assert!(
+            usage.memory_bytes >= u64::try_from(std::mem::size_of::<SecurityPolicy>()).unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn guard_policy_engine_access() {
         let (guard, _) = make_builder().build().unwrap();
@@ -715,6 +918,25 @@ mod tests {
                 Ok(())
             }
 
+            async fn start_container(&self, _id: &str) -> Result<(), ContainerGuardError> {
+                Ok(())
+            }
+
+            async fn connect_network(
+                &self,
+                _container_id: &str,
+                _network: &str,
+            ) -> Result<(), ContainerGuardError> {
+                Ok(())
+            }
+
+            async fn container_networks(
+                &self,
+                _id: &str,
+            ) -> Result<Vec<String>, ContainerGuardError> {
+                Ok(Vec::new())
+            }
+
             async fn ping(&self) -> Result<(), ContainerGuardError> {
                 if *self.ping_fails.lock().await {
                     Err(ContainerGuardError::DockerConnection(
@@ -862,6 +1084,104 @@ mod tests {
         Pipeline::stop(&mut guard).await.unwrap();
     }
 
+    /// Test that a closed isolation gate (standby instance) skips isolation
+    #[tokio::test]
+    async fn guard_isolation_gate_closed_skips_isolation() {
+        let client = Arc::new(MockDockerClient::new().with_containers(vec![ContainerInfo {
+            id: "abc123".to_owned(),
+            name: "web".to_owned(),
+            image: "nginx:latest".to_owned(),
+            status: "running".to_owned(),
+            created_at: SystemTime::now(),
+        }]));
+
+        let (alert_tx, alert_rx) = mpsc::channel(16);
+        let (gate_tx, gate_rx) = watch::channel(false);
+
+        let policy = SecurityPolicy {
+            id: "test-policy".to_owned(),
+            name: "Test Policy".to_owned(),
+            description: "Test".to_owned(),
+            enabled: true,
+            severity_threshold: Severity::Medium,
+            target_filter: TargetFilter::default(),
+            action: IsolationAction::Pause,
+            priority: 1,
+        };
+
+        let config = ContainerGuardConfig {
+            enabled: true,
+            auto_isolate: true,
+            ..Default::default()
+        };
+
+        let (mut guard, action_rx) = ContainerGuardBuilder::new()
+            .docker_client(client)
+            .config(config)
+            .alert_receiver(alert_rx)
+            .add_policy(policy)
+            .isolation_gate(gate_rx)
+            .build()
+            .unwrap();
+        let mut action_rx = action_rx.unwrap();
+        let mut activity_rx = guard.subscribe_activity();
+
+        Pipeline::start(&mut guard).await.unwrap();
+
+        let alert = || {
+            AlertEvent::new(
+                ironpost_core::types::Alert {
+                    id: "alert-1".to_owned(),
+                    title: "Test".to_owned(),
+                    description: "Test".to_owned(),
+                    severity: Severity::High,
+                    rule_name: "test".to_owned(),
+                    source_ip: None,
+                    target_ip: None,
+                    created_at: SystemTime::now(),
+                },
+                Severity::High,
+            )
+        };
+
+        // Standby: alert is processed but no isolation happens
+        alert_tx.send(alert()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(guard.alerts_processed(), 1);
+        assert_eq!(guard.isolations_executed(), 0);
+        assert!(action_rx.try_recv().is_err());
+        match activity_rx.try_recv().unwrap() {
+            GuardActivity::PolicyMatch {
+                policy_id,
+                container_id,
+                standby,
+                ..
+            } => {
+                assert_eq!(policy_id, "test-policy");
+                assert_eq!(container_id, "abc123");
+                assert!(standby);
+            }
+            other => panic!("expected policy match, got {other:?}"),
+        }
+
+        // Promoted to leader: isolation is executed
+        gate_tx.send(true).unwrap();
+        alert_tx.send(alert()).await.unwrap();
+        let action = tokio::time::timeout(Duration::from_secs(2), action_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.action_type, "container_pause");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(guard.isolations_executed(), 1);
+        assert!(matches!(
+            activity_rx.try_recv().unwrap(),
+            GuardActivity::PolicyMatch { standby: false, .. }
+        ));
+
+        Pipeline::stop(&mut guard).await.unwrap();
+    }
+
     /// Test state transitions: Initialized -> Running -> Stopped
     #[tokio::test]
     async fn guard_state_transitions() {
@@ -2,9 +2,12 @@
 //!
 //! [`IsolationAction`]은 컨테이너에 대해 수행할 격리 액션을 정의합니다.
 //! [`IsolationExecutor`]는 Docker API를 통해 실제 격리를 수행하고
-//! [`ActionEvent`]를 생성합니다.
+//! [`ActionEvent`]를 생성합니다. 적용된 격리는
+//! [`IsolationRecord`](crate::audit::IsolationRecord)를 바탕으로
+//! [`IsolationExecutor::release`]가 되돌립니다.
 
 use std::fmt;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -14,13 +17,14 @@ use tracing::{error, info, warn};
 
 use ironpost_core::event::ActionEvent;
 
+use crate::audit::IsolationRecord;
 use crate::docker::DockerClient;
 use crate::error::ContainerGuardError;
 
 /// 컨테이너 격리 액션
 ///
 /// 보안 정책에 의해 결정된 컨테이너 격리 유형을 나타냅니다.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum IsolationAction {
     /// 네트워크 연결 해제
     NetworkDisconnect {
@@ -31,6 +35,12 @@ pub enum IsolationAction {
     Pause,
     /// 컨테이너 정지
     Stop,
+    /// 네트워크 연결 해제 후 일시정지
+    Quarantine {
+        /// 연결 해제할 네트워크 목록 (비어 있으면 연결된 모든 네트워크)
+        #[serde(default)]
+        networks: Vec<String>,
+    },
 }
 
 impl IsolationAction {
@@ -43,6 +53,7 @@ impl IsolationAction {
             Self::NetworkDisconnect { .. } => "network_disconnect",
             Self::Pause => "pause",
             Self::Stop => "stop",
+            Self::Quarantine { .. } => "quarantine",
         }
     }
 }
@@ -55,6 +66,7 @@ impl fmt::Display for IsolationAction {
             }
             Self::Pause => write!(f, "pause"),
             Self::Stop => write!(f, "stop"),
+            Self::Quarantine { networks } => write!(f, "quarantine({})", networks.join(",")),
         }
     }
 }
@@ -74,6 +86,8 @@ pub struct IsolationExecutor<D: DockerClient> {
     max_retries: u32,
     /// 재시도 백오프 기본 간격
     retry_backoff_base: Duration,
+    /// 드라이런 모드 (Docker API 호출 없이 시뮬레이션)
+    dry_run: bool,
 }
 
 impl<D: DockerClient> IsolationExecutor<D> {
@@ -91,6 +105,40 @@ impl<D: DockerClient> IsolationExecutor<D> {
             action_timeout,
             max_retries,
             retry_backoff_base,
+            dry_run: false,
+        }
+    }
+
+    /// 드라이런 모드를 설정합니다.
+    ///
+    /// 활성화하면 Docker API를 호출하지 않고, 시뮬레이션된 `ActionEvent`만 전송합니다.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// 드라이런 모드 여부를 반환합니다.
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// 격리 대상 네트워크를 확정합니다.
+    ///
+    /// 네트워크 목록이 빈 `Quarantine`은 컨테이너가 현재 연결된 모든
+    /// 네트워크로 채워, 해제 시 어떤 네트워크를 다시 연결할지 기록할 수 있게
+    /// 합니다. 그 외 액션은 그대로 반환합니다.
+    pub async fn resolve(
+        &self,
+        container_id: &str,
+        action: &IsolationAction,
+    ) -> Result<IsolationAction, ContainerGuardError> {
+        match action {
+            IsolationAction::Quarantine { networks } if networks.is_empty() => {
+                Ok(IsolationAction::Quarantine {
+                    networks: self.docker.container_networks(container_id).await?,
+                })
+            }
+            _ => Ok(action.clone()),
         }
     }
 
@@ -115,15 +163,23 @@ impl<D: DockerClient> IsolationExecutor<D> {
             "executing isolation action"
         );
 
-        let result = self.execute_with_retry(container_id, action).await;
+        let action_type = format!("container_{}", action.action_type_name());
 
-        let success = result.is_ok();
-        let action_event = ActionEvent::with_trace(
-            format!("container_{}", action.action_type_name()),
-            container_id,
-            success,
-            trace_id,
-        );
+        if self.dry_run {
+            info!(
+                container_id = container_id,
+                action = %action,
+                trace_id = trace_id,
+                "dry-run: isolation action simulated, docker API not called"
+            );
+            self.send_event(action_type, container_id, true, trace_id, true)
+                .await;
+            return Ok(());
+        }
+
+        let result = self
+            .execute_with_retry(container_id, || self.execute_action(container_id, action))
+            .await;
 
         if let Err(ref e) = result {
             error!(
@@ -141,19 +197,105 @@ impl<D: DockerClient> IsolationExecutor<D> {
         }
 
         // Send action event regardless of success/failure
-        if let Err(e) = self.action_tx.send(action_event).await {
-            error!(error = %e, "failed to send action event");
+        self.send_event(action_type, container_id, result.is_ok(), trace_id, false)
+            .await;
+
+        result
+    }
+
+    /// 적용된 격리를 되돌립니다.
+    ///
+    /// 일시정지는 해제하고, 정지된 컨테이너는 다시 시작하며, 연결 해제했던
+    /// 네트워크에는 다시 연결합니다. 격리와 같은 재시도 정책을 따르며 결과를
+    /// `container_release` `ActionEvent`로 전송합니다. 드라이런으로 적용된
+    /// 격리는 해제도 시뮬레이션합니다.
+    pub async fn release(&self, record: &IsolationRecord) -> Result<(), ContainerGuardError> {
+        let container_id = record.container_id.as_str();
+        let action = &record.action;
+        info!(
+            container_id = container_id,
+            action = %action,
+            trace_id = %record.trace_id,
+            "releasing isolation"
+        );
+
+        if self.dry_run || record.simulated {
+            info!(
+                container_id = container_id,
+                action = %action,
+                "dry-run: isolation release simulated, docker API not called"
+            );
+            self.send_event(
+                "container_release".to_owned(),
+                container_id,
+                true,
+                &record.trace_id,
+                true,
+            )
+            .await;
+            return Ok(());
         }
 
+        let result = self
+            .execute_with_retry(container_id, || self.undo_action(container_id, action))
+            .await;
+
+        if let Err(ref e) = result {
+            error!(
+                container_id = container_id,
+                action = %action,
+                error = %e,
+                "isolation release failed"
+            );
+        } else {
+            info!(
+                container_id = container_id,
+                action = %action,
+                "isolation released"
+            );
+        }
+
+        self.send_event(
+            "container_release".to_owned(),
+            container_id,
+            result.is_ok(),
+            &record.trace_id,
+            false,
+        )
+        .await;
+
         result
     }
 
-    /// 재시도 로직을 포함한 격리 액션 실행
-    async fn execute_with_retry(
+    /// 액션 결과를 `ActionEvent`로 전송합니다.
+    async fn send_event(
         &self,
+        action_type: String,
         container_id: &str,
-        action: &IsolationAction,
-    ) -> Result<(), ContainerGuardError> {
+        success: bool,
+        trace_id: &str,
+        simulated: bool,
+    ) {
+        let mut action_event =
+            ActionEvent::with_trace(action_type, container_id, success, trace_id);
+        if simulated {
+            action_event = action_event.mark_simulated();
+        }
+        if let Err(e) = self.action_tx.send(action_event).await {
+            error!(error = %e, "failed to send action event");
+        }
+    }
+
+    /// 재시도 로직을 포함한 Docker 작업 실행
+    async fn execute_with_retry<F, Fut>(
+        &self,
+        container_id: &str,
+        operation: F,
+    ) -> Result<(), ContainerGuardError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<(), ContainerGuardError>>,
+    {
         let mut last_error = None;
 
         for attempt in 0..=self.max_retries {
@@ -168,12 +310,7 @@ impl<D: DockerClient> IsolationExecutor<D> {
                 tokio::time::sleep(backoff).await;
             }
 
-            match tokio::time::timeout(
-                self.action_timeout,
-                self.execute_action(container_id, action),
-            )
-            .await
-            {
+            match tokio::time::timeout(self.action_timeout, operation()).await {
                 Ok(Ok(())) => return Ok(()),
                 Ok(Err(e)) => {
                     last_error = Some(e);
@@ -203,45 +340,125 @@ impl<D: DockerClient> IsolationExecutor<D> {
     ) -> Result<(), ContainerGuardError> {
         match action {
             IsolationAction::NetworkDisconnect { networks } => {
-                // Attempt all networks even if some fail, to avoid leaving
-                // partially-disconnected state. On retry, already-disconnected
-                // networks will succeed (Docker disconnect is idempotent).
-                let mut errors = Vec::new();
-                for network in networks {
-                    match self.docker.disconnect_network(container_id, network).await {
-                        Ok(()) => {
-                            info!(
-                                container_id = container_id,
-                                network = network.as_str(),
-                                "disconnected container from network"
-                            );
-                        }
-                        Err(e) => {
-                            warn!(
-                                container_id = container_id,
-                                network = network.as_str(),
-                                error = %e,
-                                "failed to disconnect container from network"
-                            );
-                            errors.push(format!("{network}: {e}"));
-                        }
-                    }
-                }
-                if errors.is_empty() {
-                    Ok(())
-                } else {
-                    Err(ContainerGuardError::IsolationFailed {
-                        container_id: container_id.to_owned(),
-                        reason: format!(
-                            "failed to disconnect from {} network(s): {}",
-                            errors.len(),
-                            errors.join("; ")
-                        ),
-                    })
-                }
+                self.disconnect_networks(container_id, networks).await
             }
             IsolationAction::Pause => self.docker.pause_container(container_id).await,
             IsolationAction::Stop => self.docker.stop_container(container_id).await,
+            IsolationAction::Quarantine { networks } => {
+                // Disconnect first: Docker rejects network changes on paused containers
+                self.disconnect_networks(container_id, networks).await?;
+                self.docker.pause_container(container_id).await
+            }
+        }
+    }
+
+    /// 단일 해제 작업을 실행합니다 (재시도 없음).
+    async fn undo_action(
+        &self,
+        container_id: &str,
+        action: &IsolationAction,
+    ) -> Result<(), ContainerGuardError> {
+        match action {
+            IsolationAction::NetworkDisconnect { networks } => {
+                self.reconnect_networks(container_id, networks).await
+            }
+            IsolationAction::Pause => self.docker.unpause_container(container_id).await,
+            IsolationAction::Stop => self.docker.start_container(container_id).await,
+            IsolationAction::Quarantine { networks } => {
+                self.docker.unpause_container(container_id).await?;
+                self.reconnect_networks(container_id, networks).await
+            }
+        }
+    }
+
+    /// 컨테이너를 여러 네트워크에서 연결 해제합니다.
+    async fn disconnect_networks(
+        &self,
+        container_id: &str,
+        networks: &[String],
+    ) -> Result<(), ContainerGuardError> {
+        // Attempt all networks even if some fail, to avoid leaving
+        // partially-disconnected state. On retry, already-disconnected
+        // networks will succeed (Docker disconnect is idempotent).
+        let mut errors = Vec::new();
+        for network in networks {
+            match self.docker.disconnect_network(container_id, network).await {
+                Ok(()) => {
+                    info!(
+                        container_id = container_id,
+                        network = network.as_str(),
+                        "disconnected container from network"
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        container_id = container_id,
+                        network = network.as_str(),
+                        error = %e,
+                        "failed to disconnect container from network"
+                    );
+                    errors.push(format!("{network}: {e}"));
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ContainerGuardError::IsolationFailed {
+                container_id: container_id.to_owned(),
+                reason: format!(
+                    "failed to disconnect from {} network(s): {}",
+                    errors.len(),
+                    errors.join("; ")
+                ),
+            })
+        }
+    }
+
+    /// 연결 해제했던 네트워크에 컨테이너를 다시 연결합니다.
+    ///
+    /// 이미 연결된 네트워크는 건너뛰므로 재시도해도 안전합니다.
+    async fn reconnect_networks(
+        &self,
+        container_id: &str,
+        networks: &[String],
+    ) -> Result<(), ContainerGuardError> {
+        if networks.is_empty() {
+            return Ok(());
+        }
+        let connected = self.docker.container_networks(container_id).await?;
+        let mut errors = Vec::new();
+        for network in networks.iter().filter(|n| !connected.contains(n)) {
+            match self.docker.connect_network(container_id, network).await {
+                Ok(()) => {
+                    info!(
+                        container_id = container_id,
+                        network = network.as_str(),
+                        "reconnected container to network"
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        container_id = container_id,
+                        network = network.as_str(),
+                        error = %e,
+                        "failed to reconnect container to network"
+                    );
+                    errors.push(format!("{network}: {e}"));
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ContainerGuardError::IsolationFailed {
+                container_id: container_id.to_owned(),
+                reason: format!(
+                    "failed to reconnect to {} network(s): {}",
+                    errors.len(),
+                    errors.join("; ")
+                ),
+            })
         }
     }
 }
@@ -293,6 +510,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn quarantine_display_and_defaults() {
+        let action = IsolationAction::Quarantine {
+            networks: vec!["bridge".to_owned()],
+        };
+        assert_eq!(action.to_string(), "quarantine(bridge)");
+        assert_eq!(action.action_type_name(), "quarantine");
+
+        // networks may be omitted to mean every attached network
+        let parsed: IsolationAction = serde_json::from_str(r#"{"Quarantine":{}}"#).unwrap();
+        assert_eq!(
+            parsed,
+            IsolationAction::Quarantine {
+                networks: Vec::new()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_fills_quarantine_networks() {
+        let client = MockDockerClient::new()
+            .with_containers(vec![sample_container()])
+            .with_networks(&["bridge", "db"]);
+        let (executor, _action_rx) = make_executor(client);
+
+        let resolved = executor
+            .resolve(
+                "abc123def456",
+                &IsolationAction::Quarantine {
+                    networks: Vec::new(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(resolved.to_string(), "quarantine(bridge,db)");
+
+        let pause = executor
+            .resolve("abc123def456", &IsolationAction::Pause)
+            .await
+            .unwrap();
+        assert_eq!(pause, IsolationAction::Pause);
+    }
+
     #[test]
     fn isolation_action_type_name_is_fixed() {
         // action_type_name은 메트릭 태그용으로 고정된 값만 반환해야 함 (high-cardinality 방지)
@@ -389,6 +649,25 @@ mod tests {
         assert!(!event.success);
     }
 
+    #[tokio::test]
+    async fn executor_dry_run_skips_docker_calls() {
+        let client = MockDockerClient::new()
+            .with_containers(vec![sample_container()])
+            .with_failing_actions();
+        let (executor, mut action_rx) = make_executor(client);
+        let executor = executor.with_dry_run(true);
+
+        let result = executor
+            .execute("abc123def456", &IsolationAction::Stop, "trace-dry")
+            .await;
+        assert!(result.is_ok());
+
+        let event = action_rx.recv().await.unwrap();
+        assert!(event.success);
+        assert!(event.simulated);
+        assert_eq!(event.action_type, "container_stop");
+    }
+
     #[tokio::test]
     async fn executor_not_found() {
         let client = MockDockerClient::new(); // no containers
@@ -676,6 +955,25 @@ mod tests {
                 Ok(())
             }
 
+            async fn start_container(&self, _id: &str) -> Result<(), ContainerGuardError> {
+                Ok(())
+            }
+
+            async fn connect_network(
+                &self,
+                _container_id: &str,
+                _network: &str,
+            ) -> Result<(), ContainerGuardError> {
+                Ok(())
+            }
+
+            async fn container_networks(
+                &self,
+                _id: &str,
+            ) -> Result<Vec<String>, ContainerGuardError> {
+                Ok(Vec::new())
+            }
+
             async fn ping(&self) -> Result<(), ContainerGuardError> {
                 Ok(())
             }
@@ -832,6 +1130,25 @@ mod tests {
                 }
             }
 
+            async fn start_container(&self, _id: &str) -> Result<(), ContainerGuardError> {
+                Ok(())
+            }
+
+            async fn connect_network(
+                &self,
+                _container_id: &str,
+                _network: &str,
+            ) -> Result<(), ContainerGuardError> {
+                Ok(())
+            }
+
+            async fn container_networks(
+                &self,
+                _id: &str,
+            ) -> Result<Vec<String>, ContainerGuardError> {
+                Ok(Vec::new())
+            }
+
             async fn ping(&self) -> Result<(), ContainerGuardError> {
                 Ok(())
             }
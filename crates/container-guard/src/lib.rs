@@ -2,6 +2,9 @@
 //!
 //! # Module Structure
 //!
+//! - [`activity`]: Live guard activity (`GuardActivity`)
+//! - [`audit`]: Isolation audit trail (`IsolationAudit`, `IsolationRecord`)
+//! - [`control`]: Manual isolation and release (`IsolationControl`)
 //! - [`error`]: Domain error types (`ContainerGuardError`)
 //! - [`config`]: Guard configuration (`ContainerGuardConfig`, builder)
 //! - [`event`]: Container lifecycle events (`ContainerEvent`, `ContainerEventKind`)
@@ -23,7 +26,10 @@
 //!                     ActionEvent --mpsc--> downstream
 //! ```
 
+pub mod activity;
+pub mod audit;
 pub mod config;
+pub mod control;
 pub mod docker;
 pub mod error;
 pub mod event;
@@ -31,6 +37,7 @@ pub mod guard;
 pub mod isolation;
 pub mod monitor;
 pub mod policy;
+pub mod simulation;
 
 // --- Public API Re-exports ---
 
@@ -44,15 +51,23 @@ pub use config::{ContainerGuardConfig, ContainerGuardConfigBuilder};
 pub use error::ContainerGuardError;
 
 // Events
+pub use activity::{ACTIVITY_CHANNEL_CAPACITY, GuardActivity};
 pub use event::{ContainerEvent, ContainerEventKind};
 
+// Manual isolation and audit trail
+pub use audit::{AuditEntry, AuditOperation, IsolationAudit, IsolationRecord};
+pub use control::IsolationControl;
+
+// Policy simulation
+pub use simulation::{ContainerSelector, PolicySimulation, PolicySimulator, SimulatedMatch};
+
 // Docker API
 pub use docker::{BollardDockerClient, DockerClient};
 
 // Policy
 pub use policy::{
-    PolicyEngine, PolicyMatch, SecurityPolicy, TargetFilter, load_policies_from_dir,
-    load_policy_from_file,
+    LintLevel, PolicyEngine, PolicyLint, PolicyMatch, SecurityPolicy, TargetFilter,
+    load_policies_from_dir, load_policy_from_file, parse_policy,
 };
 
 // Isolation
@@ -13,10 +13,14 @@ use ironpost_core::types::ContainerInfo;
 
 use crate::docker::DockerClient;
 use crate::error::ContainerGuardError;
+use crate::event::{ContainerEvent, ContainerEventKind};
 
 /// Maximum number of containers to cache to prevent unbounded memory growth
 const MAX_CACHED_CONTAINERS: usize = 10_000;
 
+/// Maximum number of undrained lifecycle changes kept between polls
+const MAX_PENDING_CHANGES: usize = 1_000;
+
 /// Docker 컨테이너 모니터
 ///
 /// Docker 데몬의 컨테이너 목록을 주기적으로 폴링하여
@@ -28,6 +32,8 @@ pub struct DockerMonitor<D: DockerClient> {
     containers: HashMap<String, ContainerInfo>,
     /// 마지막 폴링 시각
     last_poll: Option<Instant>,
+    /// 아직 가져가지 않은 수명주기 변화 (오래된 순)
+    changes: Vec<ContainerEvent>,
     /// 폴링 주기
     poll_interval: Duration,
     /// 캐시 TTL
@@ -41,6 +47,7 @@ impl<D: DockerClient> DockerMonitor<D> {
             docker,
             containers: HashMap::new(),
             last_poll: None,
+            changes: Vec::new(),
             poll_interval,
             cache_ttl,
         }
@@ -49,7 +56,8 @@ impl<D: DockerClient> DockerMonitor<D> {
     /// 컨테이너 목록을 강제로 새로고침합니다.
     ///
     /// Docker API를 호출하여 최신 컨테이너 목록을 가져오고
-    /// 내부 인벤토리를 업데이트합니다.
+    /// 내부 인벤토리를 업데이트합니다. 첫 새로고침 이후에는 이전 인벤토리와
+    /// 비교한 수명주기 변화를 기록합니다 ([`take_changes`](Self::take_changes)).
     pub async fn refresh(&mut self) -> Result<usize, ContainerGuardError> {
         let containers = self.docker.list_containers().await?;
         let count = containers.len();
@@ -65,11 +73,19 @@ impl<D: DockerClient> DockerMonitor<D> {
             );
         }
 
-        self.containers.clear();
+        let previous = std::mem::take(&mut self.containers);
         for container in containers.into_iter().take(MAX_CACHED_CONTAINERS) {
             self.containers.insert(container.id.clone(), container);
         }
 
+        if self.last_poll.is_some() {
+            self.changes
+                .extend(lifecycle_changes(&previous, &self.containers));
+            // 가져가는 쪽이 없으면 오래된 변화부터 버립니다
+            let overflow = self.changes.len().saturating_sub(MAX_PENDING_CHANGES);
+            self.changes.drain(..overflow);
+        }
+
         self.last_poll = Some(Instant::now());
         debug!(
             count = count,
@@ -79,6 +95,11 @@ impl<D: DockerClient> DockerMonitor<D> {
         Ok(count)
     }
 
+    /// 기록된 수명주기 변화를 오래된 순으로 가져갑니다.
+    pub fn take_changes(&mut self) -> Vec<ContainerEvent> {
+        std::mem::take(&mut self.changes)
+    }
+
     /// 캐시가 만료되었으면 새로고침합니다.
     ///
     /// 캐시 TTL 내라면 기존 데이터를 반환하고,
@@ -206,6 +227,47 @@ impl<D: DockerClient> DockerMonitor<D> {
     }
 }
 
+/// 두 인벤토리 사이의 수명주기 변화를 계산합니다.
+///
+/// Docker 상태 문자열(`created`, `running`, `paused`, `exited`, `dead` 등)의
+/// 전이를 [`ContainerEventKind`]로 변환합니다. 결과는 컨테이너 ID 순입니다.
+fn lifecycle_changes(
+    previous: &HashMap<String, ContainerInfo>,
+    current: &HashMap<String, ContainerInfo>,
+) -> Vec<ContainerEvent> {
+    let mut changes = Vec::new();
+
+    for (id, container) in current {
+        let kind = match previous.get(id) {
+            None if container.status == "created" => Some(ContainerEventKind::Created),
+            None => Some(ContainerEventKind::Started),
+            Some(old) if old.status == container.status => None,
+            Some(old) => match (old.status.as_str(), container.status.as_str()) {
+                (_, "paused") => Some(ContainerEventKind::Paused),
+                ("paused", "running") => Some(ContainerEventKind::Unpaused),
+                (_, "running") => Some(ContainerEventKind::Started),
+                (_, "exited" | "dead") => Some(ContainerEventKind::Stopped),
+                _ => None,
+            },
+        };
+        if let Some(kind) = kind {
+            changes.push((id, ContainerEvent::new(id, &container.name, kind)));
+        }
+    }
+
+    for (id, container) in previous {
+        if !current.contains_key(id) {
+            changes.push((
+                id,
+                ContainerEvent::new(id, &container.name, ContainerEventKind::Deleted),
+            ));
+        }
+    }
+
+    changes.sort_by(|a, b| a.0.cmp(b.0));
+    changes.into_iter().map(|(_, event)| event).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -714,6 +776,25 @@ mod tests {
                 Ok(())
             }
 
+            async fn start_container(&self, _id: &str) -> Result<(), ContainerGuardError> {
+                Ok(())
+            }
+
+            async fn connect_network(
+                &self,
+                _container_id: &str,
+                _network: &str,
+            ) -> Result<(), ContainerGuardError> {
+                Ok(())
+            }
+
+            async fn container_networks(
+                &self,
+                _id: &str,
+            ) -> Result<Vec<String>, ContainerGuardError> {
+                Ok(Vec::new())
+            }
+
             async fn ping(&self) -> Result<(), ContainerGuardError> {
                 Ok(())
             }
@@ -789,4 +870,71 @@ mod tests {
             assert!(result.is_ok());
         }
     }
+
+    fn inventory(entries: &[(&str, &str)]) -> HashMap<String, ContainerInfo> {
+        entries
+            .iter()
+            .map(|(id, status)| {
+                (
+                    (*id).to_owned(),
+                    ContainerInfo {
+                        id: (*id).to_owned(),
+                        name: format!("name-{id}"),
+                        image: "nginx:latest".to_owned(),
+                        status: (*status).to_owned(),
+                        created_at: SystemTime::now(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lifecycle_changes_detects_transitions() {
+        let previous = inventory(&[
+            ("a", "running"),
+            ("b", "running"),
+            ("c", "paused"),
+            ("d", "running"),
+            ("e", "running"),
+        ]);
+        let current = inventory(&[
+            ("a", "running"),
+            ("b", "paused"),
+            ("c", "running"),
+            ("d", "exited"),
+            ("f", "running"),
+            ("g", "created"),
+        ]);
+
+        let changes: Vec<(String, String)> = lifecycle_changes(&previous, &current)
+            .into_iter()
+            .map(|e| (e.container_id, e.event_kind.to_string()))
+            .collect();
+
+        let expected = [
+            ("b", "paused"),
+            ("c", "unpaused"),
+            ("d", "stopped"),
+            ("e", "deleted"),
+            ("f", "started"),
+            ("g", "created"),
+        ];
+        let expected: Vec<(String, String)> = expected
+            .iter()
+            .map(|(id, kind)| ((*id).to_owned(), (*kind).to_owned()))
+            .collect();
+        assert_eq!(changes, expected);
+    }
+
+    #[tokio::test]
+    async fn refresh_records_no_changes_on_first_poll() {
+        let mut monitor = make_monitor(sample_containers());
+        monitor.refresh().await.unwrap();
+        assert!(monitor.take_changes().is_empty());
+
+        // 동일한 인벤토리로 다시 폴링해도 변화 없음
+        monitor.refresh().await.unwrap();
+        assert!(monitor.take_changes().is_empty());
+    }
 }
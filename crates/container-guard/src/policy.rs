@@ -57,6 +57,25 @@ impl TargetFilter {
 
         name_matches && image_matches
     }
+
+    /// 이 필터가 `other`가 매칭하는 모든 컨테이너에 매칭되는지 확인합니다.
+    ///
+    /// 패턴 간 포함 관계는 `other`의 패턴을 문자열로 보고 이 필터의 패턴에
+    /// 매칭시켜 근사합니다 (예: `web-*`는 `web-api-*`를 포함).
+    pub fn covers(&self, other: &TargetFilter) -> bool {
+        patterns_cover(&self.container_names, &other.container_names)
+            && patterns_cover(&self.image_patterns, &other.image_patterns)
+    }
+}
+
+/// `outer` 패턴 목록이 `inner` 패턴 목록의 매칭 범위를 모두 포함하는지 확인합니다.
+///
+/// 빈 목록은 모든 값에 매칭됩니다.
+fn patterns_cover(outer: &[String], inner: &[String]) -> bool {
+    if outer.is_empty() || outer.iter().any(|p| p == "*") {
+        return true;
+    }
+    !inner.is_empty() && inner.iter().all(|i| outer.iter().any(|o| glob_match(o, i)))
 }
 
 /// 간단한 glob 패턴 매칭 (*, ? 지원)
@@ -180,6 +199,29 @@ pub struct PolicyMatch {
     pub action: IsolationAction,
 }
 
+/// 정책 린트 수준
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// 동작은 하지만 의도와 다를 가능성이 있음
+    Warning,
+    /// 정책 세트를 배포할 수 없음 (hot reload 실패)
+    Error,
+}
+
+/// 정책 린트 결과
+///
+/// [`PolicyEngine::lint`]가 정책 세트에서 발견한 단일 문제를 나타냅니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyLint {
+    /// 문제가 된 정책 ID
+    pub policy_id: String,
+    /// 린트 수준
+    pub level: LintLevel,
+    /// 문제 설명
+    pub message: String,
+}
+
 /// 정책 엔진 -- 여러 정책을 관리하고 알림에 대해 평가합니다.
 ///
 /// 정책은 우선순위 순으로 평가되며, 첫 번째로 매칭되는 정책의 액션이 반환됩니다.
@@ -213,6 +255,41 @@ impl PolicyEngine {
         Ok(())
     }
 
+    /// 전체 정책 세트를 원자적으로 교체합니다 (hot reload).
+    ///
+    /// 모든 정책을 먼저 검증하고, 하나라도 실패하면 기존 정책을 그대로 유지합니다.
+    ///
+    /// # Errors
+    /// - 정책 수가 `MAX_POLICIES`를 초과하는 경우
+    /// - 정책 유효성 검증 실패
+    /// - 중복된 정책 ID
+    pub fn replace_policies(
+        &mut self,
+        mut policies: Vec<SecurityPolicy>,
+    ) -> Result<usize, ContainerGuardError> {
+        if policies.len() > MAX_POLICIES {
+            return Err(ContainerGuardError::PolicyValidation {
+                policy_id: "(bundle)".to_owned(),
+                reason: format!("maximum policy count ({MAX_POLICIES}) exceeded"),
+            });
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for policy in &policies {
+            policy.validate()?;
+            if !seen.insert(policy.id.as_str()) {
+                return Err(ContainerGuardError::PolicyValidation {
+                    policy_id: policy.id.clone(),
+                    reason: "duplicate policy id".to_owned(),
+                });
+            }
+        }
+
+        policies.sort_by_key(|p| p.priority);
+        self.policies = policies;
+        Ok(self.policies.len())
+    }
+
     /// 정책을 ID로 제거합니다.
     ///
     /// 존재하지 않는 ID를 지정하면 아무 일도 하지 않습니다.
@@ -262,6 +339,74 @@ impl PolicyEngine {
     pub fn policies(&self) -> &[SecurityPolicy] {
         &self.policies
     }
+
+    /// 등록된 정책 세트를 배포 전에 검사합니다.
+    ///
+    /// 개별 정책의 유효성은 [`add_policy`](Self::add_policy)에서 이미 검증되므로,
+    /// 여기서는 정책 간의 관계에서 생기는 문제를 찾습니다.
+    /// - 중복된 정책 ID (`Error`: hot reload가 거부됨)
+    /// - 같은 우선순위의 활성 정책 (`Warning`: 평가 순서가 로드 순서에 의존)
+    /// - 앞선 정책에 가려져 절대 매칭되지 않는 정책 (`Warning`)
+    /// - 네트워크 목록이 비어 있는 `network_disconnect` 액션 (`Warning`: 아무 동작 안 함)
+    ///
+    /// 결과는 평가 순서(우선순위 순)로 반환됩니다.
+    pub fn lint(&self) -> Vec<PolicyLint> {
+        let mut lints = Vec::new();
+        let mut seen_ids = std::collections::HashSet::new();
+
+        for (index, policy) in self.policies.iter().enumerate() {
+            if !seen_ids.insert(policy.id.as_str()) {
+                lints.push(PolicyLint {
+                    policy_id: policy.id.clone(),
+                    level: LintLevel::Error,
+                    message: "duplicate policy id".to_owned(),
+                });
+            }
+
+            if let IsolationAction::NetworkDisconnect { networks } = &policy.action
+                && networks.is_empty()
+            {
+                lints.push(PolicyLint {
+                    policy_id: policy.id.clone(),
+                    level: LintLevel::Warning,
+                    message: "network_disconnect action has no networks and does nothing"
+                        .to_owned(),
+                });
+            }
+
+            if !policy.enabled {
+                continue;
+            }
+
+            let earlier = self.policies[..index].iter().filter(|p| p.enabled);
+            for other in earlier {
+                if other.priority == policy.priority {
+                    lints.push(PolicyLint {
+                        policy_id: policy.id.clone(),
+                        level: LintLevel::Warning,
+                        message: format!(
+                            "same priority ({}) as '{}'; evaluation order depends on load order",
+                            policy.priority, other.id
+                        ),
+                    });
+                } else if other.severity_threshold <= policy.severity_threshold
+                    && other.target_filter.covers(&policy.target_filter)
+                {
+                    lints.push(PolicyLint {
+                        policy_id: policy.id.clone(),
+                        level: LintLevel::Warning,
+                        message: format!(
+                            "never matches: shadowed by '{}' (priority {})",
+                            other.id, other.priority
+                        ),
+                    });
+                    break;
+                }
+            }
+        }
+
+        lints
+    }
 }
 
 impl Default for PolicyEngine {
@@ -303,9 +448,22 @@ pub fn load_policy_from_file(
         reason: format!("failed to read file: {e}"),
     })?;
 
+    parse_policy(&content, &path.display().to_string())
+}
+
+/// TOML 문자열을 파싱하여 정책을 생성합니다.
+///
+/// # Arguments
+/// - `content`: TOML 문자열
+/// - `source`: 에러 메시지에 사용할 출처 (파일 경로 등)
+///
+/// # Errors
+/// - TOML 파싱 실패
+/// - 정책 유효성 검증 실패
+pub fn parse_policy(content: &str, source: &str) -> Result<SecurityPolicy, ContainerGuardError> {
     let policy: SecurityPolicy =
-        toml::from_str(&content).map_err(|e| ContainerGuardError::PolicyLoad {
-            path: path.display().to_string(),
+        toml::from_str(content).map_err(|e| ContainerGuardError::PolicyLoad {
+            path: source.to_owned(),
             reason: format!("failed to parse TOML: {e}"),
         })?;
 
@@ -550,6 +708,121 @@ mod tests {
         assert_eq!(engine.policy_count(), 2);
     }
 
+    #[test]
+    fn policy_engine_replace_policies_sorts_and_swaps() {
+        let mut engine = PolicyEngine::new();
+        engine.add_policy(sample_policy(Severity::High, 9)).unwrap();
+
+        let count = engine
+            .replace_policies(vec![
+                sample_policy(Severity::High, 2),
+                sample_policy(Severity::Medium, 1),
+            ])
+            .unwrap();
+
+        assert_eq!(count, 2);
+        let ids: Vec<&str> = engine.policies().iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["policy-1", "policy-2"]);
+    }
+
+    #[test]
+    fn policy_engine_replace_policies_keeps_existing_on_error() {
+        let mut engine = PolicyEngine::new();
+        engine.add_policy(sample_policy(Severity::High, 1)).unwrap();
+
+        let result = engine.replace_policies(vec![
+            sample_policy(Severity::High, 2),
+            sample_policy(Severity::Medium, 2),
+        ]);
+
+        assert!(result.is_err());
+        assert_eq!(engine.policy_count(), 1);
+        assert_eq!(engine.policies()[0].id, "policy-1");
+    }
+
+    #[test]
+    fn policy_engine_lint_clean_set() {
+        let mut engine = PolicyEngine::new();
+        let mut narrow = sample_policy(Severity::Medium, 1);
+        narrow.target_filter.container_names = vec!["web-*".to_owned()];
+        engine.add_policy(narrow).unwrap();
+        engine.add_policy(sample_policy(Severity::High, 2)).unwrap();
+
+        assert!(engine.lint().is_empty());
+    }
+
+    #[test]
+    fn policy_engine_lint_duplicate_id() {
+        let mut engine = PolicyEngine::new();
+        engine.add_policy(sample_policy(Severity::High, 1)).unwrap();
+        let mut duplicate = sample_policy(Severity::High, 2);
+        duplicate.id = "policy-1".to_owned();
+        duplicate.enabled = false;
+        engine.add_policy(duplicate).unwrap();
+
+        let lints = engine.lint();
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].level, LintLevel::Error);
+        assert!(lints[0].message.contains("duplicate"));
+    }
+
+    #[test]
+    fn policy_engine_lint_shadowed_policy() {
+        // 우선순위 1이 모든 컨테이너의 Medium 이상을 처리하므로 2는 매칭되지 않음
+        let mut engine = PolicyEngine::new();
+        engine
+            .add_policy(sample_policy(Severity::Medium, 1))
+            .unwrap();
+        let mut shadowed = sample_policy(Severity::High, 2);
+        shadowed.target_filter.image_patterns = vec!["nginx:*".to_owned()];
+        engine.add_policy(shadowed).unwrap();
+
+        let lints = engine.lint();
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].policy_id, "policy-2");
+        assert_eq!(lints[0].level, LintLevel::Warning);
+        assert!(lints[0].message.contains("shadowed by 'policy-1'"));
+    }
+
+    #[test]
+    fn policy_engine_lint_same_priority_and_empty_networks() {
+        let mut engine = PolicyEngine::new();
+        let mut first = sample_policy(Severity::High, 5);
+        first.target_filter.container_names = vec!["db-*".to_owned()];
+        engine.add_policy(first).unwrap();
+        let mut second = sample_policy(Severity::High, 5);
+        second.id = "policy-5b".to_owned();
+        second.target_filter.container_names = vec!["web-*".to_owned()];
+        second.action = IsolationAction::NetworkDisconnect {
+            networks: Vec::new(),
+        };
+        engine.add_policy(second).unwrap();
+
+        let lints = engine.lint();
+        assert_eq!(lints.len(), 2);
+        assert!(lints.iter().all(|l| l.policy_id == "policy-5b"));
+        assert!(lints.iter().any(|l| l.message.contains("same priority")));
+        assert!(lints.iter().any(|l| l.message.contains("no networks")));
+    }
+
+    #[test]
+    fn target_filter_covers() {
+        let all = TargetFilter::default();
+        let web = TargetFilter {
+            container_names: vec!["web-*".to_owned()],
+            ..Default::default()
+        };
+        let web_api = TargetFilter {
+            container_names: vec!["web-api-*".to_owned()],
+            ..Default::default()
+        };
+
+        assert!(all.covers(&web));
+        assert!(web.covers(&web_api));
+        assert!(!web_api.covers(&web));
+        assert!(!web.covers(&all));
+    }
+
     #[test]
     fn policy_engine_remove() {
         let mut engine = PolicyEngine::new();
@@ -0,0 +1,389 @@
+//! 정책 시뮬레이션 -- 격리를 실행하지 않고 정책 매칭 결과만 계산
+//!
+//! [`PolicySimulator`]는 가드가 실제로 사용하는 [`PolicyEngine`]과 컨테이너
+//! 인벤토리를 공유하므로, 시뮬레이션 결과는 같은 알림이 지금 도착했을 때
+//! 가드가 내릴 판단과 같습니다. Docker 상태는 변경하지 않으며 감사 기록에도
+//! 남지 않습니다.
+//!
+//! 가드와 마찬가지로 컨테이너를 ID 순으로 평가하고, 정책이 매칭된 첫 번째
+//! 컨테이너에만 격리를 적용합니다 ([`SimulatedMatch::applied`]).
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::{Mutex, watch};
+use tracing::warn;
+
+use ironpost_core::event::AlertEvent;
+use ironpost_core::types::ContainerInfo;
+
+use crate::audit::IsolationAudit;
+use crate::docker::DockerClient;
+use crate::error::ContainerGuardError;
+use crate::isolation::IsolationAction;
+use crate::monitor::DockerMonitor;
+use crate::policy::PolicyEngine;
+
+/// 시뮬레이션 대상 컨테이너 선택
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerSelector {
+    /// 실행 중인 모든 컨테이너 (가드의 실제 평가 범위)
+    All,
+    /// 이름, ID 또는 ID 접두사로 지정한 실행 중인 컨테이너
+    Target(String),
+    /// 실행 중이 아닌 가상의 컨테이너 (배포 전 정책 확인용)
+    Hypothetical {
+        /// 컨테이너 이름
+        name: String,
+        /// 이미지 이름
+        image: String,
+    },
+}
+
+/// 정책이 매칭된 컨테이너 하나에 대한 시뮬레이션 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedMatch {
+    /// 컨테이너 ID (가상 컨테이너는 빈 문자열)
+    pub container_id: String,
+    /// 컨테이너 이름
+    pub container_name: String,
+    /// 컨테이너 이미지
+    pub container_image: String,
+    /// 매칭된 정책 ID
+    pub policy_id: String,
+    /// 매칭된 정책 이름
+    pub policy_name: String,
+    /// 정책이 지정한 격리 액션
+    pub action: IsolationAction,
+    /// 가드가 이 컨테이너에 격리를 적용할지 여부
+    ///
+    /// 정책이 매칭된 첫 번째 컨테이너만 적용 대상입니다.
+    pub applied: bool,
+    /// 이미 적용 중인 격리가 있는지 여부
+    pub already_isolated: bool,
+}
+
+/// 정책 시뮬레이션 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicySimulation {
+    /// 평가한 컨테이너 수
+    pub containers_evaluated: usize,
+    /// 평가에 사용된 정책 수
+    pub policies_loaded: usize,
+    /// 정책이 매칭된 컨테이너 (평가 순서)
+    pub matches: Vec<SimulatedMatch>,
+    /// 자동 격리 활성화 여부 (`false`이면 가드는 매칭 후에도 격리하지 않음)
+    pub auto_isolate: bool,
+    /// dry-run 모드 여부 (`true`이면 격리가 시뮬레이션으로만 기록됨)
+    pub dry_run: bool,
+    /// HA 스탠바이 인스턴스 여부 (`true`이면 격리는 리더가 수행)
+    pub standby: bool,
+}
+
+impl PolicySimulation {
+    /// 가드가 격리를 적용할 매칭을 반환합니다.
+    pub fn applied(&self) -> Option<&SimulatedMatch> {
+        self.matches.iter().find(|m| m.applied)
+    }
+}
+
+/// 정책 시뮬레이션 핸들
+///
+/// 복제본은 같은 정책 엔진, 모니터, 감사 기록을 공유합니다.
+pub struct PolicySimulator<D: DockerClient> {
+    policy_engine: Arc<Mutex<PolicyEngine>>,
+    monitor: Arc<Mutex<DockerMonitor<D>>>,
+    audit: IsolationAudit,
+    auto_isolate: bool,
+    dry_run: bool,
+    isolation_gate: Option<watch::Receiver<bool>>,
+}
+
+impl<D: DockerClient> Clone for PolicySimulator<D> {
+    fn clone(&self) -> Self {
+        Self {
+            policy_engine: Arc::clone(&self.policy_engine),
+            monitor: Arc::clone(&self.monitor),
+            audit: self.audit.clone(),
+            auto_isolate: self.auto_isolate,
+            dry_run: self.dry_run,
+            isolation_gate: self.isolation_gate.clone(),
+        }
+    }
+}
+
+impl<D: DockerClient> PolicySimulator<D> {
+    /// 새 시뮬레이션 핸들을 생성합니다.
+    pub fn new(
+        policy_engine: Arc<Mutex<PolicyEngine>>,
+        monitor: Arc<Mutex<DockerMonitor<D>>>,
+        audit: IsolationAudit,
+        auto_isolate: bool,
+        dry_run: bool,
+        isolation_gate: Option<watch::Receiver<bool>>,
+    ) -> Self {
+        Self {
+            policy_engine,
+            monitor,
+            audit,
+            auto_isolate,
+            dry_run,
+            isolation_gate,
+        }
+    }
+
+    /// `alert`가 도착했을 때 어떤 정책이 매칭되고 어떤 액션이 실행될지 계산합니다.
+    ///
+    /// # Errors
+    ///
+    /// - `ContainerGuardError::ContainerNotFound`: `Target`으로 지정한 컨테이너가 없음
+    pub async fn simulate(
+        &self,
+        alert: &AlertEvent,
+        selector: &ContainerSelector,
+    ) -> Result<PolicySimulation, ContainerGuardError> {
+        let mut containers = self.containers(selector).await?;
+        containers.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let (policies_loaded, evaluated) = {
+            let engine = self.policy_engine.lock().await;
+            let evaluated: Vec<_> = containers
+                .iter()
+                .filter_map(|container| {
+                    engine
+                        .evaluate(alert, container)
+                        .map(|policy_match| (container, policy_match))
+                })
+                .collect();
+            (engine.policy_count(), evaluated)
+        };
+
+        let mut matches = Vec::with_capacity(evaluated.len());
+        for (container, policy_match) in evaluated {
+            let already_isolated =
+                !container.id.is_empty() && self.audit.active(&container.id).await.is_some();
+            matches.push(SimulatedMatch {
+                container_id: container.id.clone(),
+                container_name: container.name.clone(),
+                container_image: container.image.clone(),
+                policy_id: policy_match.policy_id,
+                policy_name: policy_match.policy_name,
+                action: policy_match.action,
+                applied: matches.is_empty(),
+                already_isolated,
+            });
+        }
+
+        Ok(PolicySimulation {
+            containers_evaluated: containers.len(),
+            policies_loaded,
+            matches,
+            auto_isolate: self.auto_isolate,
+            dry_run: self.dry_run,
+            standby: self
+                .isolation_gate
+                .as_ref()
+                .is_some_and(|gate| !*gate.borrow()),
+        })
+    }
+
+    /// 선택에 해당하는 컨테이너 목록을 반환합니다.
+    async fn containers(
+        &self,
+        selector: &ContainerSelector,
+    ) -> Result<Vec<ContainerInfo>, ContainerGuardError> {
+        if let ContainerSelector::Hypothetical { name, image } = selector {
+            return Ok(vec![ContainerInfo {
+                id: String::new(),
+                name: name.clone(),
+                image: image.clone(),
+                status: "hypothetical".to_owned(),
+                created_at: std::time::SystemTime::now(),
+            }]);
+        }
+
+        let mut monitor = self.monitor.lock().await;
+        if let Err(e) = monitor.refresh_if_needed().await {
+            warn!(error = %e, "failed to refresh container list");
+        }
+        match selector {
+            ContainerSelector::Target(target) => {
+                if let Some(container) = monitor.find_by_name(target) {
+                    return Ok(vec![container.clone()]);
+                }
+                Ok(vec![monitor.get_container(target).await?])
+            }
+            _ => Ok(monitor.all_containers().into_iter().cloned().collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::IsolationRecord;
+    use crate::docker::MockDockerClient;
+    use crate::policy::{SecurityPolicy, TargetFilter};
+    use ironpost_core::types::{Alert, Severity};
+    use std::time::{Duration, SystemTime};
+
+    fn container(id: &str, name: &str, image: &str) -> ContainerInfo {
+        ContainerInfo {
+            id: id.to_owned(),
+            name: name.to_owned(),
+            image: image.to_owned(),
+            status: "running".to_owned(),
+            created_at: SystemTime::now(),
+        }
+    }
+
+    fn policy(id: &str, threshold: Severity, names: &[&str], priority: u32) -> SecurityPolicy {
+        SecurityPolicy {
+            id: id.to_owned(),
+            name: format!("{id} policy"),
+            description: String::new(),
+            enabled: true,
+            severity_threshold: threshold,
+            target_filter: TargetFilter {
+                container_names: names.iter().map(|n| (*n).to_owned()).collect(),
+                image_patterns: Vec::new(),
+                labels: Vec::new(),
+            },
+            action: IsolationAction::Pause,
+            priority,
+        }
+    }
+
+    fn alert(severity: Severity) -> AlertEvent {
+        let alert = Alert {
+            id: "sample".to_owned(),
+            title: "sample alert".to_owned(),
+            description: String::new(),
+            severity,
+            rule_name: "sample_rule".to_owned(),
+            source_ip: Some("1.2.3.4".parse().unwrap()),
+            target_ip: None,
+            created_at: SystemTime::now(),
+        };
+        AlertEvent::new(alert, severity)
+    }
+
+    fn make_simulator(
+        containers: Vec<ContainerInfo>,
+        policies: Vec<SecurityPolicy>,
+    ) -> PolicySimulator<MockDockerClient> {
+        let docker = Arc::new(MockDockerClient::new().with_containers(containers));
+        let monitor = DockerMonitor::new(docker, Duration::from_secs(10), Duration::from_secs(60));
+        let mut engine = PolicyEngine::new();
+        for policy in policies {
+            engine.add_policy(policy).unwrap();
+        }
+        PolicySimulator::new(
+            Arc::new(Mutex::new(engine)),
+            Arc::new(Mutex::new(monitor)),
+            IsolationAudit::new(),
+            true,
+            false,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn simulate_applies_first_match_in_id_order() {
+        let simulator = make_simulator(
+            vec![
+                container("bbb222", "web-2", "nginx:latest"),
+                container("aaa111", "web-1", "nginx:latest"),
+                container("ccc333", "db-1", "postgres:16"),
+            ],
+            vec![policy("web", Severity::High, &["web-*"], 10)],
+        );
+
+        let result = simulator
+            .simulate(&alert(Severity::Critical), &ContainerSelector::All)
+            .await
+            .unwrap();
+        assert_eq!(result.containers_evaluated, 3);
+        assert_eq!(result.policies_loaded, 1);
+        assert_eq!(result.matches.len(), 2);
+        let applied = result.applied().unwrap();
+        assert_eq!(applied.container_name, "web-1");
+        assert_eq!(applied.policy_id, "web");
+        assert!(!result.matches[1].applied);
+
+        let below = simulator
+            .simulate(&alert(Severity::Medium), &ContainerSelector::All)
+            .await
+            .unwrap();
+        assert!(below.matches.is_empty());
+    }
+
+    #[tokio::test]
+    async fn simulate_target_and_hypothetical_containers() {
+        let simulator = make_simulator(
+            vec![container("aaa111", "web-1", "nginx:latest")],
+            vec![
+                policy("web", Severity::High, &["web-*"], 10),
+                policy("catch-all", Severity::Critical, &[], 20),
+            ],
+        );
+
+        let result = simulator
+            .simulate(
+                &alert(Severity::High),
+                &ContainerSelector::Target("aaa".to_owned()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.applied().unwrap().policy_id, "web");
+
+        let result = simulator
+            .simulate(
+                &alert(Severity::Critical),
+                &ContainerSelector::Hypothetical {
+                    name: "api-1".to_owned(),
+                    image: "api:2.0".to_owned(),
+                },
+            )
+            .await
+            .unwrap();
+        let applied = result.applied().unwrap();
+        assert_eq!(applied.policy_id, "catch-all");
+        assert!(applied.container_id.is_empty());
+
+        let err = simulator
+            .simulate(
+                &alert(Severity::High),
+                &ContainerSelector::Target("missing".to_owned()),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ContainerGuardError::ContainerNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn simulate_reports_existing_isolation() {
+        let web = container("aaa111", "web-1", "nginx:latest");
+        let simulator = make_simulator(
+            vec![web.clone()],
+            vec![policy("web", Severity::High, &["web-*"], 10)],
+        );
+        simulator
+            .audit
+            .record_isolation(IsolationRecord::new(
+                &web,
+                IsolationAction::Pause,
+                "trace-1",
+                "manual:ops",
+                None,
+            ))
+            .await;
+
+        let result = simulator
+            .simulate(&alert(Severity::High), &ContainerSelector::All)
+            .await
+            .unwrap();
+        assert!(result.matches[0].already_isolated);
+        assert!(!result.standby);
+    }
+}
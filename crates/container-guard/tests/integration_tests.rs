@@ -135,6 +135,47 @@ mod mock {
             Ok(())
         }
 
+        async fn start_container(
+            &self,
+            id: &str,
+        ) -> Result<(), ironpost_container_guard::ContainerGuardError> {
+            if *self.fail_actions.lock().await {
+                return Err(
+                    ironpost_container_guard::ContainerGuardError::IsolationFailed {
+                        container_id: id.to_owned(),
+                        reason: "test failure".to_owned(),
+                    },
+                );
+            }
+            self.inspect_container(id).await?;
+            Ok(())
+        }
+
+        async fn connect_network(
+            &self,
+            container_id: &str,
+            _network: &str,
+        ) -> Result<(), ironpost_container_guard::ContainerGuardError> {
+            if *self.fail_actions.lock().await {
+                return Err(
+                    ironpost_container_guard::ContainerGuardError::IsolationFailed {
+                        container_id: container_id.to_owned(),
+                        reason: "test failure".to_owned(),
+                    },
+                );
+            }
+            self.inspect_container(container_id).await?;
+            Ok(())
+        }
+
+        async fn container_networks(
+            &self,
+            id: &str,
+        ) -> Result<Vec<String>, ironpost_container_guard::ContainerGuardError> {
+            self.inspect_container(id).await?;
+            Ok(vec!["bridge".to_owned()])
+        }
+
         async fn ping(&self) -> Result<(), ironpost_container_guard::ContainerGuardError> {
             if *self.ping_fails.lock().await {
                 return Err(
@@ -0,0 +1,744 @@
+//! 계측 채널 — 모듈 간 링크의 적체/드롭 계측과 오버플로 정책
+//!
+//! 오케스트레이터는 모듈 사이를 bounded `tokio::mpsc` 채널로 연결합니다.
+//! [`instrumented_channel`]은 일반 `Sender`/`Receiver` 쌍을 돌려주므로 모듈 API는
+//! 그대로 두고, 링크별로 다음을 더합니다.
+//!
+//! - 적체 계측: [`InstrumentedLink::usage`]로 링크에 쌓인 메시지 수를 조회
+//! - 오버플로 정책 ([`OverflowPolicy`]):
+//!   - `Block`: 일반 bounded 채널, 가득 차면 송신측이 대기
+//!   - `DropOldest`: 버퍼가 가득 차면 가장 오래된 메시지를 버림
+//!   - `Spill`: 버퍼가 가득 차면 디스크 세그먼트로 넘기고, 소비자가 따라잡으면
+//!     순서대로 다시 읽어 전달. 세그먼트는 링크마다 `spill_dir/<링크 이름>`에 두며
+//!     [`SpillQueue`](crate::spill::SpillQueue) 형식(CRC32 체크섬 레코드)을 사용합니다.
+//!
+//! `DropOldest`/`Spill` 링크는 중계 태스크를 거칩니다. 드롭/스필 건수는
+//! [`DAEMON_CHANNEL_DROPPED_TOTAL`](crate::metrics::DAEMON_CHANNEL_DROPPED_TOTAL),
+//! [`DAEMON_CHANNEL_SPILLED_TOTAL`](crate::metrics::DAEMON_CHANNEL_SPILLED_TOTAL)
+//! 메트릭으로 기록됩니다.
+//!
+//! ```text
+//! producer ─▶ [ingress] ─▶ relay (buffer / spill) ─▶ [egress] ─▶ consumer
+//! ```
+
+use std::collections::VecDeque;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+
+use crate::config::{ChannelsConfig, LinkConfig};
+use crate::metrics as m;
+use crate::plugin::ChannelUsage;
+use crate::spill::SpillQueue;
+
+/// 중계 링크의 송신/수신 채널 용량 상한
+///
+/// 버퍼링은 중계 태스크가 담당하므로 양쪽 채널은 작게 유지합니다.
+const RELAY_STAGE_CAPACITY: usize = 64;
+
+/// 스필 세그먼트 파일 최대 크기 (바이트)
+const SPILL_SEGMENT_BYTES: u64 = 16 * 1024 * 1024;
+
+/// 드롭 사유: 버퍼가 가득 차 가장 오래된 메시지를 버림
+pub const DROP_REASON_OVERFLOW: &str = "overflow";
+/// 드롭 사유: 디스크 버퍼 한도 초과 또는 읽기/쓰기 실패
+pub const DROP_REASON_SPILL_FAILED: &str = "spill_failed";
+/// 드롭 사유: 종료 시 소비자에 전달하지 못함
+pub const DROP_REASON_SHUTDOWN: &str = "shutdown";
+
+// ─── OverflowPolicy ──────────────────────────────────────────────────
+
+/// 링크 오버플로 정책
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// 송신측이 대기 (일반 bounded 채널)
+    #[default]
+    Block,
+    /// 가장 오래된 메시지를 버림
+    DropOldest,
+    /// 디스크로 넘김
+    Spill,
+}
+
+impl OverflowPolicy {
+    /// 설정 문자열(`block`, `drop_oldest`, `spill`)을 파싱합니다.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "block" => Some(Self::Block),
+            "drop_oldest" => Some(Self::DropOldest),
+            "spill" => Some(Self::Spill),
+            _ => None,
+        }
+    }
+
+    /// 설정 문자열 표현
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::DropOldest => "drop_oldest",
+            Self::Spill => "spill",
+        }
+    }
+}
+
+// ─── LinkOptions ─────────────────────────────────────────────────────
+
+/// 링크 생성 옵션
+#[derive(Debug, Clone)]
+pub struct LinkOptions {
+    /// 메모리 버퍼 용량 (메시지 수)
+    pub capacity: usize,
+    /// 오버플로 정책
+    pub policy: OverflowPolicy,
+    /// `Spill` 정책의 세그먼트 디렉토리 (링크마다 하위 디렉토리 사용)
+    pub spill_dir: PathBuf,
+    /// 디스크 버퍼 최대 크기 (바이트)
+    pub max_spill_bytes: u64,
+}
+
+impl LinkOptions {
+    /// 링크 설정과 공통 채널 설정으로 옵션을 만듭니다.
+    ///
+    /// 알 수 없는 정책은 `Block`으로 처리합니다 (설정 검증에서 이미 거부됨).
+    pub fn from_config(link: &LinkConfig, channels: &ChannelsConfig) -> Self {
+        Self {
+            capacity: link.capacity.max(1),
+            policy: OverflowPolicy::parse(&link.overflow).unwrap_or_default(),
+            spill_dir: PathBuf::from(&channels.spill_dir),
+            max_spill_bytes: channels.max_spill_bytes,
+        }
+    }
+}
+
+// ─── LinkStats ───────────────────────────────────────────────────────
+
+/// 링크 통계 (중계 태스크와 공유)
+#[derive(Debug, Default)]
+struct LinkStats {
+    /// 중계 버퍼에 쌓인 메시지 수 (디스크 포함)
+    buffered: AtomicUsize,
+    /// 누적 드롭 수
+    dropped: AtomicU64,
+    /// 누적 스필 수
+    spilled: AtomicU64,
+}
+
+impl LinkStats {
+    fn record_drop(&self, link: &str, reason: &'static str, count: usize) {
+        let count = u64::try_from(count).unwrap_or(u64::MAX);
+        self.dropped.fetch_add(count, Ordering::Relaxed);
+        metrics::counter!(
+            m::DAEMON_CHANNEL_DROPPED_TOTAL,
+            m::LABEL_CHANNEL => link.to_owned(),
+            m::LABEL_REASON => reason
+        )
+        .increment(count);
+    }
+
+    fn record_spill(&self, link: &str, count: usize) {
+        let count = u64::try_from(count).unwrap_or(u64::MAX);
+        self.spilled.fetch_add(count, Ordering::Relaxed);
+        metrics::counter!(m::DAEMON_CHANNEL_SPILLED_TOTAL, m::LABEL_CHANNEL => link.to_owned())
+            .increment(count);
+    }
+}
+
+// ─── InstrumentedLink ────────────────────────────────────────────────
+
+/// 계측 링크 핸들
+///
+/// 생산자에게 나눠줄 송신측과 링크 통계를 보관합니다. 핸들이 송신측을
+/// 들고 있는 동안 링크는 닫히지 않으므로, 종료 시 drain이 끝나면 버려야 합니다.
+#[derive(Debug)]
+pub struct InstrumentedLink<T> {
+    name: String,
+    policy: OverflowPolicy,
+    capacity: usize,
+    ingress: mpsc::Sender<T>,
+    /// 중계 태스크 → 소비자 채널 (`Block`이면 `None`)
+    egress: Option<mpsc::Sender<T>>,
+    stats: Arc<LinkStats>,
+}
+
+impl<T> InstrumentedLink<T> {
+    /// 링크 이름
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 오버플로 정책
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// 생산자용 송신측
+    pub fn sender(&self) -> mpsc::Sender<T> {
+        self.ingress.clone()
+    }
+
+    /// 링크 전체(송신 채널 + 중계 버퍼 + 수신 채널)에 쌓인 메시지 수
+    ///
+    /// 소비자가 사라진 링크는 더 이상 비울 수 없으므로 0으로 보고합니다.
+    pub fn depth(&self) -> usize {
+        let consumer_side = self.egress.as_ref().unwrap_or(&self.ingress);
+        if consumer_side.is_closed() {
+            return 0;
+        }
+        let mut depth = queued(&self.ingress) + self.stats.buffered.load(Ordering::Relaxed);
+        if let Some(egress) = &self.egress {
+            depth += queued(egress);
+        }
+        depth
+    }
+
+    /// 리소스 보고용 적체 현황
+    pub fn usage(&self) -> ChannelUsage {
+        ChannelUsage {
+            name: self.name.clone(),
+            depth: self.depth(),
+            capacity: self.capacity,
+        }
+    }
+
+    /// 누적 드롭 수
+    pub fn dropped(&self) -> u64 {
+        self.stats.dropped.load(Ordering::Relaxed)
+    }
+
+    /// 누적 스필 수
+    pub fn spilled(&self) -> u64 {
+        self.stats.spilled.load(Ordering::Relaxed)
+    }
+}
+
+fn queued<T>(tx: &mpsc::Sender<T>) -> usize {
+    tx.max_capacity().saturating_sub(tx.capacity())
+}
+
+/// 계측 링크를 생성합니다.
+///
+/// 반환값은 (링크 핸들, 소비자용 수신측, 중계 태스크)입니다. `Block` 정책은
+/// 중계 태스크 없이 일반 채널을 사용합니다. 중계 태스크는 송신측이 모두
+/// 닫히고 버퍼가 비거나, 소비자가 사라지거나, `shutdown_rx` 신호를 받으면
+/// 종료합니다. 종료 시 전달하지 못한 메시지는 드롭으로 기록됩니다.
+pub fn instrumented_channel<T>(
+    name: &str,
+    options: &LinkOptions,
+    shutdown_rx: broadcast::Receiver<()>,
+) -> (
+    InstrumentedLink<T>,
+    mpsc::Receiver<T>,
+    Option<JoinHandle<()>>,
+)
+where
+    T: Serialize + DeserializeOwned + Send + 'static,
+{
+    let stats = Arc::new(LinkStats::default());
+    let capacity = options.capacity.max(1);
+
+    if options.policy == OverflowPolicy::Block {
+        let (tx, rx) = mpsc::channel(capacity);
+        let link = InstrumentedLink {
+            name: name.to_owned(),
+            policy: options.policy,
+            capacity,
+            ingress: tx,
+            egress: None,
+            stats,
+        };
+        return (link, rx, None);
+    }
+
+    let stage = capacity.min(RELAY_STAGE_CAPACITY);
+    let (ingress_tx, ingress_rx) = mpsc::channel(stage);
+    let (egress_tx, egress_rx) = mpsc::channel(stage);
+    let buffer = OverflowBuffer::new(name, options, Arc::clone(&stats));
+    let task = tokio::spawn(relay(
+        buffer,
+        ingress_rx,
+        egress_tx.clone(),
+        Arc::clone(&stats),
+        shutdown_rx,
+    ));
+
+    let link = InstrumentedLink {
+        name: name.to_owned(),
+        policy: options.policy,
+        capacity: capacity.saturating_add(stage.saturating_mul(2)),
+        ingress: ingress_tx,
+        egress: Some(egress_tx),
+        stats,
+    };
+    (link, egress_rx, Some(task))
+}
+
+/// 중계 태스크: 송신 채널에서 받아 버퍼에 넣고, 소비자에 여유가 생기면 전달
+async fn relay<T>(
+    mut buffer: OverflowBuffer<T>,
+    mut ingress: mpsc::Receiver<T>,
+    egress: mpsc::Sender<T>,
+    stats: Arc<LinkStats>,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) where
+    T: Serialize + DeserializeOwned + Send + 'static,
+{
+    let mut ingress_open = true;
+
+    loop {
+        tokio::select! {
+            _ = shutdown_rx.recv() => break,
+            item = ingress.recv(), if ingress_open => match item {
+                Some(item) => buffer.push(item).await,
+                None => ingress_open = false,
+            },
+            permit = egress.reserve(), if !buffer.is_empty() => match permit {
+                Ok(permit) => {
+                    if let Some(item) = buffer.pop().await {
+                        permit.send(item);
+                    }
+                }
+                Err(_) => {
+                    tracing::debug!(link = %buffer.name, "link consumer closed, stopping relay");
+                    break;
+                }
+            },
+        }
+        stats.buffered.store(buffer.len(), Ordering::Relaxed);
+        if !ingress_open && buffer.is_empty() {
+            break;
+        }
+    }
+
+    // Hand over what the consumer can still take without waiting
+    while let Some(item) = buffer.pop().await {
+        if egress.try_send(item).is_err() {
+            let lost = buffer.len() + 1;
+            tracing::warn!(
+                link = %buffer.name,
+                lost = lost,
+                "link relay stopped with undelivered messages"
+            );
+            stats.record_drop(&buffer.name, DROP_REASON_SHUTDOWN, lost);
+            break;
+        }
+    }
+    buffer.clear().await;
+    stats.buffered.store(0, Ordering::Relaxed);
+    tracing::debug!(link = %buffer.name, "link relay stopped");
+}
+
+// ─── OverflowBuffer ──────────────────────────────────────────────────
+
+/// 오버플로 정책을 적용하는 FIFO 버퍼
+///
+/// 순서는 `head` → `spill` → `tail`입니다. 새 메시지는 `tail`에 쌓이고,
+/// `Spill` 정책에서 `tail`이 가득 차면 통째로 디스크 스필 큐에 넘깁니다.
+/// `head`가 비면 스필 큐에서 용량만큼 읽어 채웁니다. 스필 큐는 처음 넘칠 때
+/// 열고, 메시지는 JSON으로 인코딩합니다.
+struct OverflowBuffer<T> {
+    name: String,
+    policy: OverflowPolicy,
+    capacity: usize,
+    head: VecDeque<T>,
+    tail: VecDeque<T>,
+    spill: Option<SpillQueue>,
+    spill_dir: PathBuf,
+    max_spill_bytes: u64,
+    stats: Arc<LinkStats>,
+}
+
+impl<T> OverflowBuffer<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn new(name: &str, options: &LinkOptions, stats: Arc<LinkStats>) -> Self {
+        Self {
+            name: name.to_owned(),
+            policy: options.policy,
+            capacity: options.capacity.max(1),
+            head: VecDeque::new(),
+            tail: VecDeque::new(),
+            spill: None,
+            spill_dir: options.spill_dir.join(name),
+            max_spill_bytes: options.max_spill_bytes,
+            stats,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.head.len() + self.tail.len() + self.spill.as_ref().map_or(0, SpillQueue::len)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    async fn push(&mut self, item: T) {
+        if self.tail.len() >= self.capacity {
+            let reason = match self.policy {
+                OverflowPolicy::Spill => match self.spill_tail().await {
+                    Ok(()) => None,
+                    Err(e) => {
+                        tracing::warn!(link = %self.name, error = %e, "link spill failed, dropping oldest");
+                        Some(DROP_REASON_SPILL_FAILED)
+                    }
+                },
+                OverflowPolicy::Block | OverflowPolicy::DropOldest => Some(DROP_REASON_OVERFLOW),
+            };
+            if let Some(reason) = reason {
+                self.tail.pop_front();
+                self.stats.record_drop(&self.name, reason, 1);
+            }
+        }
+        self.tail.push_back(item);
+    }
+
+    async fn pop(&mut self) -> Option<T> {
+        loop {
+            if let Some(item) = self.head.pop_front() {
+                return Some(item);
+            }
+            let Some(spill) = self.spill.as_mut().filter(|spill| !spill.is_empty()) else {
+                return self.tail.pop_front();
+            };
+            match spill.pop_batch(self.capacity).await {
+                Ok((records, mut lost)) => {
+                    for record in records {
+                        match serde_json::from_slice(&record) {
+                            Ok(item) => self.head.push_back(item),
+                            Err(_) => lost += 1,
+                        }
+                    }
+                    if lost > 0 {
+                        self.stats
+                            .record_drop(&self.name, DROP_REASON_SPILL_FAILED, lost);
+                    }
+                }
+                Err(e) => {
+                    // 세그먼트는 디스크에 남아 다음에 스필 큐를 열 때 복구됩니다.
+                    tracing::warn!(link = %self.name, error = %e, "failed to read spilled messages");
+                    self.stats
+                        .record_drop(&self.name, DROP_REASON_SPILL_FAILED, spill.len());
+                    self.spill = None;
+                }
+            }
+        }
+    }
+
+    /// `tail`을 앞에서부터 스필 큐로 넘깁니다.
+    ///
+    /// 하나도 넘기지 못하면 (한도 초과 포함) 에러를 반환합니다.
+    async fn spill_tail(&mut self) -> io::Result<()> {
+        let spill = match self.spill.take() {
+            Some(spill) => spill,
+            None => open_spill(&self.name, self.spill_dir.clone(), self.max_spill_bytes).await?,
+        };
+        let spill = self.spill.insert(spill);
+
+        let records = self
+            .tail
+            .iter()
+            .map(serde_json::to_vec)
+            .collect::<Result<Vec<_>, _>>()?;
+        let stored = spill.push_batch(records).await?;
+        if stored == 0 {
+            return Err(io::Error::other("spill limit reached"));
+        }
+        self.tail.drain(..stored);
+        self.stats.record_spill(&self.name, stored);
+        Ok(())
+    }
+}
+
+impl<T> OverflowBuffer<T> {
+    /// 버퍼를 비우고 남은 세그먼트 파일을 삭제합니다.
+    async fn clear(&mut self) {
+        self.head.clear();
+        self.tail.clear();
+        if let Some(spill) = self.spill.as_mut()
+            && let Err(e) = spill.clear().await
+        {
+            tracing::warn!(link = %self.name, error = %e, "failed to clear spill segments");
+        }
+    }
+}
+
+impl<T> Drop for OverflowBuffer<T> {
+    fn drop(&mut self) {
+        // 중계 태스크는 종료 전에 clear()를 호출합니다. 태스크가 중단된 경우에만
+        // 세그먼트가 남아 있어 여기서 동기로 삭제합니다.
+        if let Some(spill) = self.spill.as_mut()
+            && !spill.is_empty()
+        {
+            spill.clear_blocking();
+        }
+    }
+}
+
+/// 링크의 스필 큐를 엽니다.
+async fn open_spill(name: &str, dir: PathBuf, max_bytes: u64) -> io::Result<SpillQueue> {
+    let spill = SpillQueue::open(dir, max_bytes, SPILL_SEGMENT_BYTES.min(max_bytes)).await?;
+    if !spill.is_empty() {
+        // 비정상 종료로 남은 세그먼트는 지금 넘치는 메시지보다 먼저 전달됩니다.
+        tracing::info!(
+            link = %name,
+            records = spill.len(),
+            "recovered spilled link messages"
+        );
+    }
+    Ok(spill)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn options(capacity: usize, policy: OverflowPolicy, dir: &std::path::Path) -> LinkOptions {
+        LinkOptions {
+            capacity,
+            policy,
+            spill_dir: dir.to_path_buf(),
+            max_spill_bytes: 1024 * 1024,
+        }
+    }
+
+    fn buffer(options: &LinkOptions) -> OverflowBuffer<u32> {
+        OverflowBuffer::new("test", options, Arc::new(LinkStats::default()))
+    }
+
+    async fn drain(buffer: &mut OverflowBuffer<u32>) -> Vec<u32> {
+        let mut items = Vec::new();
+        while let Some(item) = buffer.pop().await {
+            items.push(item);
+        }
+        items
+    }
+
+    /// 링크 스필 디렉토리의 세그먼트 파일 수
+    fn segment_files(dir: &std::path::Path) -> usize {
+        std::fs::read_dir(dir).map_or(0, |entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|e| e.file_name().to_string_lossy().starts_with("segment-"))
+                .count()
+        })
+    }
+
+    #[test]
+    fn overflow_policy_parse_roundtrip() {
+        for policy in [
+            OverflowPolicy::Block,
+            OverflowPolicy::DropOldest,
+            OverflowPolicy::Spill,
+        ] {
+            assert_eq!(OverflowPolicy::parse(policy.as_str()), Some(policy));
+        }
+        assert_eq!(OverflowPolicy::parse("discard"), None);
+    }
+
+    #[test]
+    fn link_options_from_config() {
+        let mut channels = ChannelsConfig::default();
+        channels.alerts.overflow = "spill".to_owned();
+        let options = LinkOptions::from_config(&channels.alerts, &channels);
+        assert_eq!(options.policy, OverflowPolicy::Spill);
+        assert_eq!(options.capacity, channels.alerts.capacity);
+        assert_eq!(options.spill_dir, PathBuf::from(&channels.spill_dir));
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_keeps_newest_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut buffer = buffer(&options(3, OverflowPolicy::DropOldest, dir.path()));
+        for i in 0..5 {
+            buffer.push(i).await;
+        }
+
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.stats.dropped.load(Ordering::Relaxed), 2);
+        assert_eq!(drain(&mut buffer).await, vec![2, 3, 4]);
+        assert!(!dir.path().join("test").exists());
+    }
+
+    #[tokio::test]
+    async fn spill_preserves_order_and_cleans_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut buffer = buffer(&options(2, OverflowPolicy::Spill, dir.path()));
+        for i in 0..7 {
+            buffer.push(i).await;
+        }
+
+        assert_eq!(buffer.len(), 7);
+        assert_eq!(buffer.stats.spilled.load(Ordering::Relaxed), 6);
+        assert_eq!(segment_files(&dir.path().join("test")), 1);
+
+        // Interleave pops and pushes: order stays FIFO
+        assert_eq!(buffer.pop().await, Some(0));
+        buffer.push(7).await;
+        assert_eq!(drain(&mut buffer).await, vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(buffer.stats.dropped.load(Ordering::Relaxed), 0);
+        assert_eq!(segment_files(&dir.path().join("test")), 0);
+    }
+
+    #[tokio::test]
+    async fn spill_limit_falls_back_to_dropping_oldest() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut options = options(2, OverflowPolicy::Spill, dir.path());
+        options.max_spill_bytes = 1;
+        let mut buffer = buffer(&options);
+        for i in 0..4 {
+            buffer.push(i).await;
+        }
+
+        assert_eq!(buffer.stats.spilled.load(Ordering::Relaxed), 0);
+        assert_eq!(buffer.stats.dropped.load(Ordering::Relaxed), 2);
+        assert_eq!(drain(&mut buffer).await, vec![2, 3]);
+    }
+
+    #[tokio::test]
+    async fn spilled_messages_use_crc_framed_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut buffer = buffer(&options(1, OverflowPolicy::Spill, dir.path()));
+        for i in 0..3 {
+            buffer.push(i).await;
+        }
+
+        // 로그 파이프라인 디스크 버퍼와 같은 SpillQueue 형식으로 다시 읽힘
+        let mut queue = SpillQueue::open(dir.path().join("test"), 1024, 1024)
+            .await
+            .unwrap();
+        let (records, lost) = queue.pop_batch(10).await.unwrap();
+        assert_eq!(lost, 0);
+        assert_eq!(records, vec![b"0".to_vec(), b"1".to_vec()]);
+    }
+
+    #[tokio::test]
+    async fn dropping_buffer_removes_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut buffer = buffer(&options(1, OverflowPolicy::Spill, dir.path()));
+        for i in 0..4 {
+            buffer.push(i).await;
+        }
+        assert!(segment_files(&dir.path().join("test")) > 0);
+
+        drop(buffer);
+        assert_eq!(segment_files(&dir.path().join("test")), 0);
+    }
+
+    #[tokio::test]
+    async fn block_link_is_a_plain_channel() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let (link, mut rx, task) = instrumented_channel::<u32>(
+            "alerts",
+            &options(4, OverflowPolicy::Block, dir.path()),
+            shutdown_rx,
+        );
+        assert!(task.is_none());
+
+        let tx = link.sender();
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        let usage = link.usage();
+        assert_eq!(usage.name, "alerts");
+        assert_eq!(usage.depth, 2);
+        assert_eq!(usage.capacity, 4);
+
+        assert_eq!(rx.recv().await, Some(1));
+        assert_eq!(link.depth(), 1);
+    }
+
+    #[tokio::test]
+    async fn spill_link_delivers_everything_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let (link, mut rx, task) = instrumented_channel::<u32>(
+            "packets",
+            &options(2, OverflowPolicy::Spill, dir.path()),
+            shutdown_rx,
+        );
+        assert!(task.is_some());
+
+        // Producer outruns an idle consumer
+        let tx = link.sender();
+        for i in 0..20 {
+            tx.send(i).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(link.spilled() > 0);
+        assert_eq!(link.depth(), 20);
+
+        let mut received = Vec::new();
+        for _ in 0..20 {
+            let item = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            received.push(item);
+        }
+        assert_eq!(received, (0..20).collect::<Vec<_>>());
+        assert_eq!(link.dropped(), 0);
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_link_never_blocks_producer() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let (link, mut rx, _task) = instrumented_channel::<u32>(
+            "alerts",
+            &options(2, OverflowPolicy::DropOldest, dir.path()),
+            shutdown_rx,
+        );
+
+        let tx = link.sender();
+        tokio::time::timeout(Duration::from_secs(1), async {
+            for i in 0..50 {
+                tx.send(i).await.unwrap();
+            }
+        })
+        .await
+        .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(link.dropped() > 0);
+
+        // The newest message always survives
+        drop(tx);
+        drop(link);
+        let mut last = None;
+        while let Ok(Some(item)) = tokio::time::timeout(Duration::from_millis(200), rx.recv()).await
+        {
+            last = Some(item);
+        }
+        assert_eq!(last, Some(49));
+    }
+
+    #[tokio::test]
+    async fn relay_stops_on_shutdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let (link, _rx, task) = instrumented_channel::<u32>(
+            "alerts",
+            &options(2, OverflowPolicy::Spill, dir.path()),
+            shutdown_rx,
+        );
+        let tx = link.sender();
+        for i in 0..10 {
+            tx.send(i).await.unwrap();
+        }
+
+        shutdown_tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), task.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(link.dropped() > 0);
+        assert_eq!(segment_files(&dir.path().join("alerts")), 0);
+    }
+}
@@ -22,12 +22,14 @@
 //! # }
 //! ```
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
 use crate::error::{ConfigError, IronpostError};
+use crate::types::Severity;
 
 /// Ironpost 통합 설정
 ///
@@ -41,6 +43,42 @@ pub struct IronpostConfig {
     /// 메트릭 수집 및 Prometheus 노출 설정
     #[serde(default)]
     pub metrics: MetricsConfig,
+    /// 헬스 체크 (liveness/readiness) 엔드포인트 설정
+    #[serde(default)]
+    pub health: HealthConfig,
+    /// 제어 API 인증/인가 설정
+    #[serde(default)]
+    pub auth: AuthConfig,
+    /// CLI가 데몬 제어 API에 접속하는 방법 (원격 호스트, 유닉스 소켓, 토큰)
+    #[serde(default)]
+    pub client: ClientConfig,
+    /// 데몬 레벨 알림 중복 제거 및 폭주 방지 설정
+    #[serde(default)]
+    pub alert_gate: AlertGateConfig,
+    /// 이벤트 저널 및 시작 시 재전송 설정
+    #[serde(default)]
+    pub persistence: PersistenceConfig,
+    /// 모듈 간 채널 용량 및 오버플로 정책 설정
+    #[serde(default)]
+    pub channels: ChannelsConfig,
+    /// 단계별 graceful shutdown 설정
+    #[serde(default)]
+    pub shutdown: ShutdownConfig,
+    /// 모듈 크래시 루프 감지 및 격리(quarantine) 설정
+    #[serde(default)]
+    pub supervisor: SupervisorConfig,
+    /// 주기 작업 스케줄러 설정
+    #[serde(default)]
+    pub scheduler: SchedulerConfig,
+    /// 고가용성(HA) 리더 선출 설정
+    #[serde(default)]
+    pub ha: HaConfig,
+    /// 원격 설정 가져오기 설정
+    #[serde(default)]
+    pub remote_config: RemoteConfigSource,
+    /// 룰/정책 번들 배포 설정
+    #[serde(default)]
+    pub content: ContentConfig,
     /// eBPF 엔진 설정
     #[serde(default)]
     pub ebpf: EbpfConfig,
@@ -134,6 +172,7 @@ impl IronpostConfig {
         override_string(&mut self.general.log_format, "IRONPOST_GENERAL_LOG_FORMAT");
         override_string(&mut self.general.data_dir, "IRONPOST_GENERAL_DATA_DIR");
         override_string(&mut self.general.pid_file, "IRONPOST_GENERAL_PID_FILE");
+        override_bool(&mut self.general.dry_run, "IRONPOST_GENERAL_DRY_RUN");
 
         // Metrics
         override_bool(&mut self.metrics.enabled, "IRONPOST_METRICS_ENABLED");
@@ -144,6 +183,198 @@ impl IronpostConfig {
         override_u16(&mut self.metrics.port, "IRONPOST_METRICS_PORT");
         override_string(&mut self.metrics.endpoint, "IRONPOST_METRICS_ENDPOINT");
 
+        // Health
+        override_bool(&mut self.health.enabled, "IRONPOST_HEALTH_ENABLED");
+        override_string(&mut self.health.listen_addr, "IRONPOST_HEALTH_LISTEN_ADDR");
+        override_u16(&mut self.health.port, "IRONPOST_HEALTH_PORT");
+        override_string(&mut self.health.unix_socket, "IRONPOST_HEALTH_UNIX_SOCKET");
+        override_u64(
+            &mut self.health.check_interval_secs,
+            "IRONPOST_HEALTH_CHECK_INTERVAL_SECS",
+        );
+        override_csv(
+            &mut self.health.readiness_required,
+            "IRONPOST_HEALTH_READINESS_REQUIRED",
+        );
+        override_csv(
+            &mut self.health.readiness_tolerate_degraded,
+            "IRONPOST_HEALTH_READINESS_TOLERATE_DEGRADED",
+        );
+        override_bool(
+            &mut self.health.liveness_fail_on_unhealthy,
+            "IRONPOST_HEALTH_LIVENESS_FAIL_ON_UNHEALTHY",
+        );
+
+        // Shutdown
+        override_u64(
+            &mut self.shutdown.stop_producers_timeout_secs,
+            "IRONPOST_SHUTDOWN_STOP_PRODUCERS_TIMEOUT_SECS",
+        );
+        override_u64(
+            &mut self.shutdown.drain_timeout_secs,
+            "IRONPOST_SHUTDOWN_DRAIN_TIMEOUT_SECS",
+        );
+        override_u64(
+            &mut self.shutdown.stop_consumers_timeout_secs,
+            "IRONPOST_SHUTDOWN_STOP_CONSUMERS_TIMEOUT_SECS",
+        );
+        override_u64(
+            &mut self.shutdown.flush_timeout_secs,
+            "IRONPOST_SHUTDOWN_FLUSH_TIMEOUT_SECS",
+        );
+
+        // Supervisor
+        override_bool(&mut self.supervisor.enabled, "IRONPOST_SUPERVISOR_ENABLED");
+        override_u32(
+            &mut self.supervisor.max_failures,
+            "IRONPOST_SUPERVISOR_MAX_FAILURES",
+        );
+        override_u64(
+            &mut self.supervisor.failure_window_secs,
+            "IRONPOST_SUPERVISOR_FAILURE_WINDOW_SECS",
+        );
+        override_u64(
+            &mut self.supervisor.restart_backoff_ms,
+            "IRONPOST_SUPERVISOR_RESTART_BACKOFF_MS",
+        );
+
+        // Scheduler
+        override_bool(&mut self.scheduler.enabled, "IRONPOST_SCHEDULER_ENABLED");
+
+        // HA
+        override_bool(&mut self.ha.enabled, "IRONPOST_HA_ENABLED");
+        override_string(&mut self.ha.backend, "IRONPOST_HA_BACKEND");
+        override_string(&mut self.ha.lock_path, "IRONPOST_HA_LOCK_PATH");
+        override_string(&mut self.ha.node_id, "IRONPOST_HA_NODE_ID");
+        override_u64(
+            &mut self.ha.retry_interval_secs,
+            "IRONPOST_HA_RETRY_INTERVAL_SECS",
+        );
+
+        // Remote config
+        override_bool(
+            &mut self.remote_config.enabled,
+            "IRONPOST_REMOTE_CONFIG_ENABLED",
+        );
+        override_string(&mut self.remote_config.url, "IRONPOST_REMOTE_CONFIG_URL");
+        override_string(
+            &mut self.remote_config.checksum_url,
+            "IRONPOST_REMOTE_CONFIG_CHECKSUM_URL",
+        );
+        override_string(
+            &mut self.remote_config.cache_path,
+            "IRONPOST_REMOTE_CONFIG_CACHE_PATH",
+        );
+        override_u64(
+            &mut self.remote_config.refresh_interval_secs,
+            "IRONPOST_REMOTE_CONFIG_REFRESH_INTERVAL_SECS",
+        );
+        override_u64(
+            &mut self.remote_config.timeout_secs,
+            "IRONPOST_REMOTE_CONFIG_TIMEOUT_SECS",
+        );
+
+        // Alert gate
+        override_bool(&mut self.alert_gate.enabled, "IRONPOST_ALERT_GATE_ENABLED");
+        override_u64(
+            &mut self.alert_gate.dedup_window_secs,
+            "IRONPOST_ALERT_GATE_DEDUP_WINDOW_SECS",
+        );
+        override_u64(
+            &mut self.alert_gate.max_alerts_per_minute,
+            "IRONPOST_ALERT_GATE_MAX_ALERTS_PER_MINUTE",
+        );
+        override_usize(
+            &mut self.alert_gate.max_tracked_keys,
+            "IRONPOST_ALERT_GATE_MAX_TRACKED_KEYS",
+        );
+
+        // Persistence
+        override_bool(
+            &mut self.persistence.enabled,
+            "IRONPOST_PERSISTENCE_ENABLED",
+        );
+        override_string(&mut self.persistence.dir, "IRONPOST_PERSISTENCE_DIR");
+        override_u64(
+            &mut self.persistence.max_replay_age_secs,
+            "IRONPOST_PERSISTENCE_MAX_REPLAY_AGE_SECS",
+        );
+        override_u64(
+            &mut self.persistence.max_journal_bytes,
+            "IRONPOST_PERSISTENCE_MAX_JOURNAL_BYTES",
+        );
+
+        // Channels
+        override_usize(
+            &mut self.channels.packets.capacity,
+            "IRONPOST_CHANNELS_PACKETS_CAPACITY",
+        );
+        override_string(
+            &mut self.channels.packets.overflow,
+            "IRONPOST_CHANNELS_PACKETS_OVERFLOW",
+        );
+        override_usize(
+            &mut self.channels.alerts.capacity,
+            "IRONPOST_CHANNELS_ALERTS_CAPACITY",
+        );
+        override_string(
+            &mut self.channels.alerts.overflow,
+            "IRONPOST_CHANNELS_ALERTS_OVERFLOW",
+        );
+        override_string(&mut self.channels.spill_dir, "IRONPOST_CHANNELS_SPILL_DIR");
+        override_u64(
+            &mut self.channels.max_spill_bytes,
+            "IRONPOST_CHANNELS_MAX_SPILL_BYTES",
+        );
+
+        // Auth
+        override_bool(&mut self.auth.enabled, "IRONPOST_AUTH_ENABLED");
+        override_string(&mut self.auth.tls_cert_path, "IRONPOST_AUTH_TLS_CERT_PATH");
+        override_string(&mut self.auth.tls_key_path, "IRONPOST_AUTH_TLS_KEY_PATH");
+        override_string(
+            &mut self.auth.client_ca_path,
+            "IRONPOST_AUTH_CLIENT_CA_PATH",
+        );
+
+        // Client
+        override_string(&mut self.client.host, "IRONPOST_CLIENT_HOST");
+        override_string(&mut self.client.socket, "IRONPOST_CLIENT_SOCKET");
+        override_string(&mut self.client.token, "IRONPOST_CLIENT_TOKEN");
+        override_string(&mut self.client.token_file, "IRONPOST_CLIENT_TOKEN_FILE");
+        override_string(
+            &mut self.client.ca_cert_path,
+            "IRONPOST_CLIENT_CA_CERT_PATH",
+        );
+        override_string(
+            &mut self.client.client_cert_path,
+            "IRONPOST_CLIENT_CLIENT_CERT_PATH",
+        );
+        override_string(
+            &mut self.client.client_key_path,
+            "IRONPOST_CLIENT_CLIENT_KEY_PATH",
+        );
+
+        // Content
+        override_bool(&mut self.content.enabled, "IRONPOST_CONTENT_ENABLED");
+        override_string(&mut self.content.source, "IRONPOST_CONTENT_SOURCE");
+        override_string(
+            &mut self.content.checksum_url,
+            "IRONPOST_CONTENT_CHECKSUM_URL",
+        );
+        override_string(&mut self.content.store_dir, "IRONPOST_CONTENT_STORE_DIR");
+        override_u64(
+            &mut self.content.sync_interval_secs,
+            "IRONPOST_CONTENT_SYNC_INTERVAL_SECS",
+        );
+        override_u64(
+            &mut self.content.timeout_secs,
+            "IRONPOST_CONTENT_TIMEOUT_SECS",
+        );
+        override_usize(
+            &mut self.content.keep_versions,
+            "IRONPOST_CONTENT_KEEP_VERSIONS",
+        );
+
         // eBPF
         override_bool(&mut self.ebpf.enabled, "IRONPOST_EBPF_ENABLED");
         override_string(&mut self.ebpf.interface, "IRONPOST_EBPF_INTERFACE");
@@ -156,6 +387,46 @@ impl IronpostConfig {
             &mut self.ebpf.blocklist_max_entries,
             "IRONPOST_EBPF_BLOCKLIST_MAX_ENTRIES",
         );
+        override_u64(
+            &mut self.ebpf.rate_limit_pps,
+            "IRONPOST_EBPF_RATE_LIMIT_PPS",
+        );
+        override_u64(
+            &mut self.ebpf.rate_limit_burst,
+            "IRONPOST_EBPF_RATE_LIMIT_BURST",
+        );
+        override_u32(
+            &mut self.ebpf.monitor_sample_rate,
+            "IRONPOST_EBPF_MONITOR_SAMPLE_RATE",
+        );
+        override_bool(&mut self.ebpf.observe_only, "IRONPOST_EBPF_OBSERVE_ONLY");
+        override_u64(
+            &mut self.ebpf.auto_block_ttl_secs,
+            "IRONPOST_EBPF_AUTO_BLOCK_TTL_SECS",
+        );
+        override_u64(
+            &mut self.ebpf.traffic_alert_pps,
+            "IRONPOST_EBPF_TRAFFIC_ALERT_PPS",
+        );
+        override_u64(
+            &mut self.ebpf.traffic_alert_bytes_per_sec,
+            "IRONPOST_EBPF_TRAFFIC_ALERT_BYTES_PER_SEC",
+        );
+        override_u32(
+            &mut self.ebpf.traffic_alert_drop_percent,
+            "IRONPOST_EBPF_TRAFFIC_ALERT_DROP_PERCENT",
+        );
+        override_u64(
+            &mut self.ebpf.traffic_alert_cooldown_secs,
+            "IRONPOST_EBPF_TRAFFIC_ALERT_COOLDOWN_SECS",
+        );
+        override_bool(
+            &mut self.ebpf.process_correlation,
+            "IRONPOST_EBPF_PROCESS_CORRELATION",
+        );
+        override_bool(&mut self.ebpf.tls_sni, "IRONPOST_EBPF_TLS_SNI");
+        override_string(&mut self.ebpf.state_path, "IRONPOST_EBPF_STATE_PATH");
+        override_string(&mut self.ebpf.map_pin_path, "IRONPOST_EBPF_MAP_PIN_PATH");
 
         // Log Pipeline
         override_bool(
@@ -186,6 +457,14 @@ impl IronpostConfig {
             &mut self.log_pipeline.flush_interval_secs,
             "IRONPOST_LOG_PIPELINE_FLUSH_INTERVAL_SECS",
         );
+        override_bool(
+            &mut self.log_pipeline.watch_rules,
+            "IRONPOST_LOG_PIPELINE_WATCH_RULES",
+        );
+        override_string(
+            &mut self.log_pipeline.suppressions_file,
+            "IRONPOST_LOG_PIPELINE_SUPPRESSIONS_FILE",
+        );
 
         // Storage
         override_string(
@@ -201,6 +480,148 @@ impl IronpostConfig {
             "IRONPOST_STORAGE_RETENTION_DAYS",
         );
 
+        // Enrichment
+        override_bool(
+            &mut self.log_pipeline.enrichment.enrich_logs,
+            "IRONPOST_ENRICHMENT_ENRICH_LOGS",
+        );
+        override_csv(
+            &mut self.log_pipeline.enrichment.ip_fields,
+            "IRONPOST_ENRICHMENT_IP_FIELDS",
+        );
+        override_string(
+            &mut self.log_pipeline.enrichment.geoip_country_db,
+            "IRONPOST_ENRICHMENT_GEOIP_COUNTRY_DB",
+        );
+        override_string(
+            &mut self.log_pipeline.enrichment.geoip_asn_db,
+            "IRONPOST_ENRICHMENT_GEOIP_ASN_DB",
+        );
+        override_bool(
+            &mut self.log_pipeline.enrichment.reverse_dns,
+            "IRONPOST_ENRICHMENT_REVERSE_DNS",
+        );
+        override_u64(
+            &mut self.log_pipeline.enrichment.dns_timeout_ms,
+            "IRONPOST_ENRICHMENT_DNS_TIMEOUT_MS",
+        );
+        override_usize(
+            &mut self.log_pipeline.enrichment.dns_cache_size,
+            "IRONPOST_ENRICHMENT_DNS_CACHE_SIZE",
+        );
+        override_u64(
+            &mut self.log_pipeline.enrichment.dns_cache_ttl_secs,
+            "IRONPOST_ENRICHMENT_DNS_CACHE_TTL_SECS",
+        );
+        override_u64(
+            &mut self.log_pipeline.enrichment.dns_negative_cache_ttl_secs,
+            "IRONPOST_ENRICHMENT_DNS_NEGATIVE_CACHE_TTL_SECS",
+        );
+        override_usize(
+            &mut self.log_pipeline.enrichment.dns_max_concurrent_lookups,
+            "IRONPOST_ENRICHMENT_DNS_MAX_CONCURRENT_LOOKUPS",
+        );
+
+        // Kafka
+        override_csv(
+            &mut self.log_pipeline.kafka.brokers,
+            "IRONPOST_KAFKA_BROKERS",
+        );
+        override_csv(&mut self.log_pipeline.kafka.topics, "IRONPOST_KAFKA_TOPICS");
+        override_string(
+            &mut self.log_pipeline.kafka.group_id,
+            "IRONPOST_KAFKA_GROUP_ID",
+        );
+
+        // HTTP ingest
+        override_string(
+            &mut self.log_pipeline.http.bind,
+            "IRONPOST_HTTP_INGEST_BIND",
+        );
+
+        // SMTP (email sinks)
+        override_string(&mut self.log_pipeline.smtp.host, "IRONPOST_SMTP_HOST");
+        override_string(
+            &mut self.log_pipeline.smtp.username,
+            "IRONPOST_SMTP_USERNAME",
+        );
+        override_string(
+            &mut self.log_pipeline.smtp.password,
+            "IRONPOST_SMTP_PASSWORD",
+        );
+
+        // Elasticsearch output
+        override_bool(
+            &mut self.log_pipeline.elasticsearch.enabled,
+            "IRONPOST_ELASTICSEARCH_ENABLED",
+        );
+        override_string(
+            &mut self.log_pipeline.elasticsearch.url,
+            "IRONPOST_ELASTICSEARCH_URL",
+        );
+        override_string(
+            &mut self.log_pipeline.elasticsearch.username,
+            "IRONPOST_ELASTICSEARCH_USERNAME",
+        );
+        override_string(
+            &mut self.log_pipeline.elasticsearch.password,
+            "IRONPOST_ELASTICSEARCH_PASSWORD",
+        );
+        override_string(
+            &mut self.log_pipeline.elasticsearch.api_key,
+            "IRONPOST_ELASTICSEARCH_API_KEY",
+        );
+
+        // Kafka output
+        override_bool(
+            &mut self.log_pipeline.kafka_output.enabled,
+            "IRONPOST_KAFKA_OUTPUT_ENABLED",
+        );
+        override_csv(
+            &mut self.log_pipeline.kafka_output.brokers,
+            "IRONPOST_KAFKA_OUTPUT_BROKERS",
+        );
+        override_string(
+            &mut self.log_pipeline.kafka_output.alert_topic,
+            "IRONPOST_KAFKA_OUTPUT_ALERT_TOPIC",
+        );
+        override_string(
+            &mut self.log_pipeline.kafka_output.log_topic,
+            "IRONPOST_KAFKA_OUTPUT_LOG_TOPIC",
+        );
+
+        // Disk buffer
+        override_bool(
+            &mut self.log_pipeline.disk_buffer.enabled,
+            "IRONPOST_DISK_BUFFER_ENABLED",
+        );
+        override_string(
+            &mut self.log_pipeline.disk_buffer.dir,
+            "IRONPOST_DISK_BUFFER_DIR",
+        );
+
+        // Log store
+        override_bool(
+            &mut self.log_pipeline.log_store.enabled,
+            "IRONPOST_LOG_STORE_ENABLED",
+        );
+        override_string(
+            &mut self.log_pipeline.log_store.dir,
+            "IRONPOST_LOG_STORE_DIR",
+        );
+
+        // Parsers
+        override_bool(
+            &mut self.log_pipeline.parsers.leef,
+            "IRONPOST_LOG_PIPELINE_PARSERS_LEEF",
+        );
+
+        // File source
+        override_string(
+            &mut self.log_pipeline.file.checkpoint_path,
+            "IRONPOST_LOG_PIPELINE_FILE_CHECKPOINT_PATH",
+        );
+
         // Container
         override_bool(&mut self.container.enabled, "IRONPOST_CONTAINER_ENABLED");
         override_string(
@@ -269,13 +690,29 @@ impl IronpostConfig {
                 .into());
             }
 
-            if self.ebpf.interface.is_empty() {
+            let interfaces = self.ebpf.interfaces();
+            if interfaces.is_empty() {
                 return Err(ConfigError::InvalidValue {
                     field: "ebpf.interface".to_owned(),
                     reason: "interface must not be empty when ebpf is enabled".to_owned(),
                 }
                 .into());
             }
+            if interfaces.len() > 1
+                && let Some(keyword) = interfaces.iter().find(|name| {
+                    name.eq_ignore_ascii_case(EBPF_INTERFACE_ALL)
+                        || name.eq_ignore_ascii_case(EBPF_INTERFACE_AUTO)
+                })
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: "ebpf.interface".to_owned(),
+                    reason: format!(
+                        "'{}' cannot be combined with other interfaces",
+                        keyword.to_ascii_lowercase()
+                    ),
+                }
+                .into());
+            }
         }
 
         // SBOM output_format 검증
@@ -307,6 +744,58 @@ impl IronpostConfig {
             self.metrics.validate()?;
         }
 
+        // Health endpoint validation (if enabled)
+        if self.health.enabled {
+            self.health.validate()?;
+            if self.metrics.enabled
+                && self.metrics.port == self.health.port
+                && self.metrics.listen_addr == self.health.listen_addr
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: "health.port".to_owned(),
+                    reason: "must differ from metrics.port on the same listen_addr".to_owned(),
+                }
+                .into());
+            }
+        }
+
+        self.shutdown.validate()?;
+        if self.supervisor.enabled {
+            self.supervisor.validate()?;
+        }
+        if self.scheduler.enabled {
+            self.scheduler.validate()?;
+        }
+        if self.ha.enabled {
+            self.ha.validate()?;
+        }
+        if self.remote_config.enabled {
+            self.remote_config.validate()?;
+        }
+        if self.content.enabled {
+            self.content.validate()?;
+            if self.log_pipeline.enabled && self.log_pipeline.watch_rules {
+                return Err(ConfigError::InvalidValue {
+                    field: "log_pipeline.watch_rules".to_owned(),
+                    reason: "cannot be enabled together with content distribution, \
+                             which replaces the rules loaded from the rule directory"
+                        .to_owned(),
+                }
+                .into());
+            }
+        }
+        if self.auth.enabled {
+            self.auth.validate()?;
+        }
+        self.client.validate()?;
+        if self.alert_gate.enabled {
+            self.alert_gate.validate()?;
+        }
+        if self.persistence.enabled {
+            self.persistence.validate()?;
+        }
+        self.channels.validate()?;
+
         // Module-specific validation (only for enabled modules)
         if self.ebpf.enabled {
             self.ebpf.validate()?;
@@ -339,6 +828,8 @@ pub struct GeneralConfig {
     pub data_dir: String,
     /// PID 파일 경로
     pub pid_file: String,
+    /// 드라이런 모드 (차단/격리 등 파괴적 액션을 실행하지 않고 시뮬레이션만 수행)
+    pub dry_run: bool,
 }
 
 impl Default for GeneralConfig {
@@ -348,6 +839,7 @@ impl Default for GeneralConfig {
             log_format: "json".to_owned(),
             data_dir: "/var/lib/ironpost".to_owned(),
             pid_file: "/var/run/ironpost/ironpost.pid".to_owned(),
+            dry_run: false,
         }
     }
 }
@@ -412,48 +904,74 @@ impl MetricsConfig {
     }
 }
 
-/// eBPF 엔진 설정
+/// 헬스 체크 엔드포인트 설정
+///
+/// liveness(`/healthz`)는 데몬 프로세스가 살아서 응답하는지를,
+/// readiness(`/readyz`)는 트래픽/이벤트를 처리할 준비가 되었는지를 나타냅니다.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
-pub struct EbpfConfig {
-    /// 활성화 여부
+pub struct HealthConfig {
+    /// 헬스 엔드포인트 활성화 여부
     pub enabled: bool,
-    /// 감시할 네트워크 인터페이스
-    pub interface: String,
-    /// XDP 모드 (native, skb, hw)
-    pub xdp_mode: String,
-    /// 이벤트 링 버퍼 크기 (바이트)
-    pub ring_buffer_size: usize,
-    /// 차단 목록 최대 엔트리 수
-    pub blocklist_max_entries: usize,
+    /// HTTP 리스너 바인드 주소
+    pub listen_addr: String,
+    /// HTTP 리스너 포트
+    pub port: u16,
+    /// 제어 API를 함께 제공할 유닉스 소켓 경로 (비어 있으면 미사용)
+    pub unix_socket: String,
+    /// 모듈 헬스 체크 주기 (초)
+    pub check_interval_secs: u64,
+    /// readiness 판정에 반드시 포함되는 모듈 목록 (비어있으면 등록된 모든 모듈)
+    pub readiness_required: Vec<String>,
+    /// Degraded 상태여도 readiness를 통과시키는 모듈 목록
+    pub readiness_tolerate_degraded: Vec<String>,
+    /// Unhealthy 모듈이 있을 때 liveness도 실패시킬지 여부
+    pub liveness_fail_on_unhealthy: bool,
 }
 
-impl Default for EbpfConfig {
+impl Default for HealthConfig {
     fn default() -> Self {
         Self {
-            enabled: false,
-            interface: "eth0".to_owned(),
-            xdp_mode: "skb".to_owned(),
-            ring_buffer_size: 256 * 1024, // 256KB
-            blocklist_max_entries: 10_000,
+            enabled: true,
+            listen_addr: "127.0.0.1".to_owned(),
+            port: 9101,
+            unix_socket: String::new(),
+            check_interval_secs: 5,
+            readiness_required: Vec::new(),
+            readiness_tolerate_degraded: vec!["sbom-scanner".to_owned()],
+            liveness_fail_on_unhealthy: false,
         }
     }
 }
 
-impl EbpfConfig {
-    /// Validate eBPF configuration values.
+impl HealthConfig {
+    /// Validate health endpoint configuration values.
     pub fn validate(&self) -> Result<(), IronpostError> {
-        if self.ring_buffer_size == 0 {
+        if self.port == 0 {
             return Err(ConfigError::InvalidValue {
-                field: "ebpf.ring_buffer_size".to_owned(),
+                field: "health.port".to_owned(),
                 reason: "must be greater than 0".to_owned(),
             }
             .into());
         }
-        if self.blocklist_max_entries == 0 {
+        if self.listen_addr.is_empty() {
             return Err(ConfigError::InvalidValue {
-                field: "ebpf.blocklist_max_entries".to_owned(),
-                reason: "must be greater than 0".to_owned(),
+                field: "health.listen_addr".to_owned(),
+                reason: "must not be empty".to_owned(),
+            }
+            .into());
+        }
+        if !self.unix_socket.is_empty() && !Path::new(&self.unix_socket).is_absolute() {
+            return Err(ConfigError::InvalidValue {
+                field: "health.unix_socket".to_owned(),
+                reason: "must be an absolute path".to_owned(),
+            }
+            .into());
+        }
+        if self.check_interval_secs == 0 || self.check_interval_secs > 3600 {
+            return Err(ConfigError::InvalidValue {
+                field: "health.check_interval_secs".to_owned(),
+                reason: "must be between 1 and 3600".to_owned(),
             }
             .into());
         }
@@ -461,165 +979,291 @@ impl EbpfConfig {
     }
 }
 
-/// 로그 파이프라인 설정
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 단계별 graceful shutdown 설정
+///
+/// SIGTERM/SIGINT 수신 시 데몬은 다음 순서로 종료하며, 각 단계마다 타임아웃을 적용합니다.
+///
+/// 1. 생산자 정지 (eBPF, 로그 파이프라인, SBOM 스캐너)
+/// 2. 채널 드레인 (알림 채널이 비거나 타임아웃까지 대기)
+/// 3. 소비자 정지 (컨테이너 가드)
+/// 4. 싱크 플러시 (액션 로그 등 잔여 이벤트 기록)
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
-pub struct LogPipelineConfig {
-    /// 활성화 여부
+pub struct ShutdownConfig {
+    /// 생산자 정지 단계 타임아웃 (초)
+    pub stop_producers_timeout_secs: u64,
+    /// 채널 드레인 단계 타임아웃 (초)
+    pub drain_timeout_secs: u64,
+    /// 소비자 정지 단계 타임아웃 (초)
+    pub stop_consumers_timeout_secs: u64,
+    /// 싱크 플러시 단계 타임아웃 (초)
+    pub flush_timeout_secs: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            stop_producers_timeout_secs: 10,
+            drain_timeout_secs: 10,
+            stop_consumers_timeout_secs: 10,
+            flush_timeout_secs: 5,
+        }
+    }
+}
+
+/// 단계별 shutdown 타임아웃 상한 (초)
+const MAX_SHUTDOWN_PHASE_TIMEOUT_SECS: u64 = 300;
+
+impl ShutdownConfig {
+    /// Validate shutdown phase timeouts.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        let phases = [
+            (
+                "shutdown.stop_producers_timeout_secs",
+                self.stop_producers_timeout_secs,
+            ),
+            ("shutdown.drain_timeout_secs", self.drain_timeout_secs),
+            (
+                "shutdown.stop_consumers_timeout_secs",
+                self.stop_consumers_timeout_secs,
+            ),
+            ("shutdown.flush_timeout_secs", self.flush_timeout_secs),
+        ];
+        for (field, value) in phases {
+            if value == 0 || value > MAX_SHUTDOWN_PHASE_TIMEOUT_SECS {
+                return Err(ConfigError::InvalidValue {
+                    field: field.to_owned(),
+                    reason: format!("must be between 1 and {}", MAX_SHUTDOWN_PHASE_TIMEOUT_SECS),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 모듈 크래시 루프 감지 및 격리 설정
+///
+/// `failure_window_secs` 안에 `max_failures`번 이상 시작/재시작에 실패하거나
+/// 크래시한 모듈은 격리(quarantine)되어 비활성화되고, 데몬은 나머지 모듈로 계속 동작합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SupervisorConfig {
+    /// 크래시 루프 감지 활성화 여부 (비활성화 시 시작 실패는 데몬 시작 실패로 처리)
     pub enabled: bool,
-    /// 수집 소스
-    pub sources: Vec<String>,
-    /// Syslog UDP 수신 주소
-    pub syslog_bind: String,
-    /// Syslog TCP 수신 주소
-    pub syslog_tcp_bind: String,
-    /// 파일 감시 경로
-    pub watch_paths: Vec<String>,
-    /// 배치 크기
-    pub batch_size: usize,
-    /// 배치 플러시 간격 (초)
-    pub flush_interval_secs: u64,
-    /// 스토리지 설정
-    #[serde(default)]
-    pub storage: StorageConfig,
+    /// 격리 기준 실패 횟수
+    pub max_failures: u32,
+    /// 실패 횟수를 세는 슬라이딩 윈도우 (초)
+    pub failure_window_secs: u64,
+    /// 재시작 시도 간 기본 백오프 (밀리초, 시도 횟수에 비례)
+    pub restart_backoff_ms: u64,
 }
 
-impl Default for LogPipelineConfig {
+impl Default for SupervisorConfig {
     fn default() -> Self {
         Self {
             enabled: true,
-            sources: vec!["syslog".to_owned(), "file".to_owned()],
-            syslog_bind: "0.0.0.0:514".to_owned(),
-            syslog_tcp_bind: "0.0.0.0:601".to_owned(),
-            watch_paths: vec!["/var/log/syslog".to_owned()],
-            batch_size: 100,
-            flush_interval_secs: 5,
-            storage: StorageConfig::default(),
+            max_failures: 3,
+            failure_window_secs: 300,
+            restart_backoff_ms: 1000,
         }
     }
 }
 
-impl LogPipelineConfig {
-    /// Validate log pipeline configuration values.
+impl SupervisorConfig {
+    /// Validate supervisor configuration values.
     pub fn validate(&self) -> Result<(), IronpostError> {
-        if self.batch_size == 0 {
+        if self.max_failures == 0 || self.max_failures > 100 {
             return Err(ConfigError::InvalidValue {
-                field: "log_pipeline.batch_size".to_owned(),
-                reason: "must be greater than 0".to_owned(),
+                field: "supervisor.max_failures".to_owned(),
+                reason: "must be between 1 and 100".to_owned(),
             }
             .into());
         }
-        if self.batch_size > 10_000 {
+        if self.failure_window_secs == 0 || self.failure_window_secs > 86_400 {
             return Err(ConfigError::InvalidValue {
-                field: "log_pipeline.batch_size".to_owned(),
-                reason: "must not exceed 10,000 (performance limit)".to_owned(),
+                field: "supervisor.failure_window_secs".to_owned(),
+                reason: "must be between 1 and 86,400 seconds (1 day)".to_owned(),
             }
             .into());
         }
-        if self.flush_interval_secs == 0 {
+        if self.restart_backoff_ms > 60_000 {
             return Err(ConfigError::InvalidValue {
-                field: "log_pipeline.flush_interval_secs".to_owned(),
-                reason: "must be greater than 0".to_owned(),
+                field: "supervisor.restart_backoff_ms".to_owned(),
+                reason: "must not exceed 60,000 ms".to_owned(),
             }
             .into());
         }
-        self.storage.validate()?;
         Ok(())
     }
 }
 
-/// 스토리지 설정
+/// 스케줄러가 지원하는 작업 종류
+///
+/// `vuln_db_update`는 `vuln_db_check`의 이전 이름으로, 호환을 위해 받습니다.
+pub const SCHEDULER_TASK_KINDS: &[&str] = &[
+    "sbom_scan",
+    "vuln_db_check",
+    "vuln_db_update",
+    "log_store_retention",
+    "report",
+];
+
+/// 주기 작업 스케줄러 설정
+///
+/// cron 표현식(5필드: 분 시 일 월 요일, UTC 기준)으로 반복 작업을 등록합니다.
+/// 작업별 마지막/다음 실행 시각은 상태 API(`/status`)에서 확인할 수 있습니다.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
-pub struct StorageConfig {
-    /// PostgreSQL 연결 문자열
-    pub postgres_url: String,
-    /// Redis 연결 문자열
-    pub redis_url: String,
-    /// 로그 보존 기간 (일)
-    pub retention_days: u32,
+pub struct SchedulerConfig {
+    /// 스케줄러 활성화 여부
+    pub enabled: bool,
+    /// 등록할 작업 목록 (`[[scheduler.tasks]]`)
+    pub tasks: Vec<ScheduledTaskConfig>,
 }
 
-impl Default for StorageConfig {
+impl Default for SchedulerConfig {
     fn default() -> Self {
         Self {
-            postgres_url: "postgresql://localhost:5432/ironpost".to_owned(),
-            redis_url: "redis://localhost:6379".to_owned(),
-            retention_days: 30,
+            enabled: true,
+            tasks: Vec::new(),
         }
     }
 }
 
-impl StorageConfig {
-    /// Validate storage configuration values.
+impl SchedulerConfig {
+    /// Validate scheduler configuration values.
+    ///
+    /// cron 표현식의 필드 수만 검사합니다. 각 필드의 값 범위는
+    /// 데몬이 스케줄을 파싱할 때 검증합니다.
     pub fn validate(&self) -> Result<(), IronpostError> {
-        if self.retention_days == 0 {
-            return Err(ConfigError::InvalidValue {
-                field: "log_pipeline.storage.retention_days".to_owned(),
-                reason: "must be greater than 0".to_owned(),
+        let mut names = std::collections::HashSet::new();
+        for task in &self.tasks {
+            if task.name.is_empty() {
+                return Err(ConfigError::InvalidValue {
+                    field: "scheduler.tasks.name".to_owned(),
+                    reason: "must not be empty".to_owned(),
+                }
+                .into());
             }
-            .into());
-        }
-        if self.retention_days > 3650 {
-            return Err(ConfigError::InvalidValue {
-                field: "log_pipeline.storage.retention_days".to_owned(),
-                reason: "must not exceed 3,650 days (10 years)".to_owned(),
+            if !names.insert(task.name.as_str()) {
+                return Err(ConfigError::InvalidValue {
+                    field: "scheduler.tasks.name".to_owned(),
+                    reason: format!("duplicate task name '{}'", task.name),
+                }
+                .into());
+            }
+            if !SCHEDULER_TASK_KINDS.contains(&task.kind.as_str()) {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("scheduler.tasks.{}.kind", task.name),
+                    reason: format!("must be one of: {}", SCHEDULER_TASK_KINDS.join(", ")),
+                }
+                .into());
+            }
+            let schedule = task.schedule.trim();
+            if !schedule.starts_with('@') && schedule.split_whitespace().count() != 5 {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("scheduler.tasks.{}.schedule", task.name),
+                    reason: "must be a 5-field cron expression or a macro like @daily".to_owned(),
+                }
+                .into());
+            }
+            if task.kind == "report" && task.output_path.is_empty() {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("scheduler.tasks.{}.output_path", task.name),
+                    reason: "must not be empty for report tasks".to_owned(),
+                }
+                .into());
             }
-            .into());
         }
         Ok(())
     }
 }
 
-/// 컨테이너 가드 설정
+/// 개별 스케줄 작업 설정
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
-pub struct ContainerConfig {
+pub struct ScheduledTaskConfig {
+    /// 작업 이름 (상태 API와 로그에서 사용, 고유해야 함)
+    pub name: String,
+    /// 작업 종류 (sbom_scan, vuln_db_check, log_store_retention, report)
+    pub kind: String,
+    /// cron 표현식 (예: "0 3 * * *", "@hourly")
+    pub schedule: String,
     /// 활성화 여부
     pub enabled: bool,
-    /// Docker 소켓 경로
-    pub docker_socket: String,
-    /// 모니터링 주기 (초)
-    pub poll_interval_secs: u64,
-    /// 격리 정책 파일 경로
-    pub policy_path: String,
-    /// 자동 격리 활성화
-    pub auto_isolate: bool,
+    /// 결과 파일 경로 (report 작업에서 사용)
+    pub output_path: String,
 }
 
-impl Default for ContainerConfig {
+impl Default for ScheduledTaskConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            kind: String::new(),
+            schedule: String::new(),
+            enabled: true,
+            output_path: String::new(),
+        }
+    }
+}
+
+/// 지원하는 HA 리더 선출 백엔드
+pub const HA_BACKENDS: &[&str] = &["file"];
+
+/// 고가용성(HA) 리더 선출 설정
+///
+/// HA 페어로 구성된 두 데몬이 공유 잠금 파일로 리더를 선출합니다.
+/// 두 인스턴스 모두 이벤트를 수집하고 탐지하지만, 컨테이너 격리 같은
+/// 파괴적 액션은 리더만 실행하여 중복 실행을 방지합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HaConfig {
+    /// 리더 선출 활성화 여부
+    pub enabled: bool,
+    /// 선출 백엔드 (현재 "file"만 지원)
+    pub backend: String,
+    /// 잠금 파일 경로 (두 인스턴스가 공유하는 경로, 원격 파일시스템은 잠금을 지원해야 함)
+    pub lock_path: String,
+    /// 노드 식별자 (비어 있으면 시작 시 무작위 생성)
+    pub node_id: String,
+    /// 스탠바이 인스턴스의 리더십 획득 재시도 간격 (초)
+    pub retry_interval_secs: u64,
+}
+
+impl Default for HaConfig {
     fn default() -> Self {
         Self {
             enabled: false,
-            docker_socket: "/var/run/docker.sock".to_owned(),
-            poll_interval_secs: 10,
-            policy_path: "/etc/ironpost/policies".to_owned(),
-            auto_isolate: false,
+            backend: "file".to_owned(),
+            lock_path: "/var/lib/ironpost/leader.lock".to_owned(),
+            node_id: String::new(),
+            retry_interval_secs: 5,
         }
     }
 }
 
-impl ContainerConfig {
-    /// Validate container guard configuration values.
+impl HaConfig {
+    /// Validate HA configuration values.
     pub fn validate(&self) -> Result<(), IronpostError> {
-        if self.poll_interval_secs == 0 {
+        if !HA_BACKENDS.contains(&self.backend.as_str()) {
             return Err(ConfigError::InvalidValue {
-                field: "container.poll_interval_secs".to_owned(),
-                reason: "must be greater than 0".to_owned(),
+                field: "ha.backend".to_owned(),
+                reason: format!("must be one of: {}", HA_BACKENDS.join(", ")),
             }
             .into());
         }
-        if self.poll_interval_secs > 3600 {
+        if self.lock_path.is_empty() {
             return Err(ConfigError::InvalidValue {
-                field: "container.poll_interval_secs".to_owned(),
-                reason: "must not exceed 3,600 seconds (1 hour)".to_owned(),
+                field: "ha.lock_path".to_owned(),
+                reason: "must not be empty".to_owned(),
             }
             .into());
         }
-        if self.docker_socket.is_empty() {
+        if self.retry_interval_secs == 0 || self.retry_interval_secs > 300 {
             return Err(ConfigError::InvalidValue {
-                field: "container.docker_socket".to_owned(),
-                reason: "must not be empty".to_owned(),
+                field: "ha.retry_interval_secs".to_owned(),
+                reason: "must be between 1 and 300 seconds".to_owned(),
             }
             .into());
         }
@@ -627,560 +1271,5073 @@ impl ContainerConfig {
     }
 }
 
-/// SBOM 스캐너 설정
+/// 원격 설정 URL에 허용되는 스킴
+pub const REMOTE_CONFIG_SCHEMES: &[&str] = &["https://", "http://", "s3://"];
+
+/// 원격 설정 가져오기 설정
+///
+/// 로컬 `ironpost.toml`은 부트스트랩 역할만 하고, 실제 설정은 HTTP(S) 또는
+/// S3 URL에서 가져옵니다. 내려받은 파일은 SHA-256 체크섬 파일로 검증한 뒤
+/// `cache_path`에 저장되어 원격 저장소에 접근할 수 없을 때 사용됩니다.
+/// 원격 설정 안의 `[remote_config]` 섹션은 무시되고 로컬 값이 유지됩니다.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
-pub struct SbomConfig {
-    /// 활성화 여부
+pub struct RemoteConfigSource {
+    /// 원격 설정 사용 여부
     pub enabled: bool,
-    /// 스캔 대상 디렉토리
-    pub scan_dirs: Vec<String>,
-    /// 취약점 DB 업데이트 주기 (시간)
-    pub vuln_db_update_hours: u32,
-    /// 취약점 DB 경로
-    pub vuln_db_path: String,
-    /// 최소 심각도 알림 수준 (info, low, medium, high, critical)
-    pub min_severity: String,
-    /// SBOM 출력 형식 (spdx, cyclonedx)
-    pub output_format: String,
+    /// 설정 파일 URL (https://, http://, s3://bucket/key)
+    pub url: String,
+    /// SHA-256 체크섬 파일 URL (비어 있으면 `{url}.sha256`)
+    pub checksum_url: String,
+    /// 마지막으로 검증된 원격 설정을 저장할 로컬 경로
+    pub cache_path: String,
+    /// 주기적 갱신 간격 (초, 0이면 시작 시 한 번만 가져옴)
+    pub refresh_interval_secs: u64,
+    /// 요청 타임아웃 (초)
+    pub timeout_secs: u64,
+    /// 함께 가져올 룰/정책 번들 목록 (`[[remote_config.bundles]]`)
+    pub bundles: Vec<RemoteBundle>,
 }
 
-impl Default for SbomConfig {
+impl Default for RemoteConfigSource {
     fn default() -> Self {
         Self {
             enabled: false,
-            scan_dirs: vec![".".to_owned()],
-            vuln_db_update_hours: 24,
-            vuln_db_path: "/var/lib/ironpost/vuln-db".to_owned(),
-            min_severity: "medium".to_owned(),
-            output_format: "cyclonedx".to_owned(),
+            url: String::new(),
+            checksum_url: String::new(),
+            cache_path: "/var/lib/ironpost/remote-config.toml".to_owned(),
+            refresh_interval_secs: 300,
+            timeout_secs: 30,
+            bundles: Vec::new(),
         }
     }
 }
 
-impl SbomConfig {
-    /// Validate SBOM scanner configuration values.
+impl RemoteConfigSource {
+    /// Validate remote configuration source values.
     pub fn validate(&self) -> Result<(), IronpostError> {
-        if self.vuln_db_update_hours == 0 {
+        validate_remote_url("remote_config.url", &self.url)?;
+        if !self.checksum_url.is_empty() {
+            validate_remote_url("remote_config.checksum_url", &self.checksum_url)?;
+        }
+        if self.cache_path.is_empty() {
             return Err(ConfigError::InvalidValue {
-                field: "sbom.vuln_db_update_hours".to_owned(),
-                reason: "must be greater than 0".to_owned(),
+                field: "remote_config.cache_path".to_owned(),
+                reason: "must not be empty".to_owned(),
             }
             .into());
         }
-        if self.vuln_db_update_hours > 8760 {
+        if self.refresh_interval_secs != 0
+            && (self.refresh_interval_secs < 10 || self.refresh_interval_secs > 86_400)
+        {
             return Err(ConfigError::InvalidValue {
-                field: "sbom.vuln_db_update_hours".to_owned(),
-                reason: "must not exceed 8,760 hours (1 year)".to_owned(),
+                field: "remote_config.refresh_interval_secs".to_owned(),
+                reason: "must be 0 (disabled) or between 10 and 86,400 seconds".to_owned(),
             }
             .into());
         }
-        if self.scan_dirs.is_empty() {
+        if self.timeout_secs == 0 || self.timeout_secs > 300 {
             return Err(ConfigError::InvalidValue {
-                field: "sbom.scan_dirs".to_owned(),
-                reason: "must have at least one directory".to_owned(),
+                field: "remote_config.timeout_secs".to_owned(),
+                reason: "must be between 1 and 300 seconds".to_owned(),
             }
             .into());
         }
+        for bundle in &self.bundles {
+            validate_remote_url("remote_config.bundles.url", &bundle.url)?;
+            if !bundle.checksum_url.is_empty() {
+                validate_remote_url("remote_config.bundles.checksum_url", &bundle.checksum_url)?;
+            }
+            if bundle.path.is_empty() {
+                return Err(ConfigError::InvalidValue {
+                    field: "remote_config.bundles.path".to_owned(),
+                    reason: format!("must not be empty (bundle {})", bundle.url),
+                }
+                .into());
+            }
+        }
         Ok(())
     }
 }
 
-// --- 환경변수 오버라이드 헬퍼 ---
-
-fn override_string(target: &mut String, env_key: &str) {
-    if let Ok(val) = std::env::var(env_key) {
-        *target = val;
-    }
+/// 원격에서 가져와 로컬 경로에 저장할 룰/정책 번들
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteBundle {
+    /// 번들 URL (https://, http://, s3://bucket/key)
+    pub url: String,
+    /// SHA-256 체크섬 파일 URL (비어 있으면 `{url}.sha256`)
+    pub checksum_url: String,
+    /// 저장할 로컬 파일 경로
+    pub path: String,
 }
 
-fn override_bool(target: &mut bool, env_key: &str) {
-    if let Ok(val) = std::env::var(env_key) {
-        match val.parse::<bool>() {
-            Ok(parsed) => *target = parsed,
-            Err(_) => warn!(
-                env_key,
-                value = val.as_str(),
-                "failed to parse bool from env var, ignoring"
-            ),
-        }
-    }
+/// 룰/정책 번들 배포 설정
+///
+/// 데몬이 로컬 디렉토리 또는 원격 URL에서 룰+정책 번들을 동기화하여
+/// log-pipeline 규칙 엔진과 container-guard 정책 엔진에 hot reload로 반영합니다.
+/// 적용된 번들은 버전별로 `store_dir`에 보관되어 롤백에 사용됩니다.
+///
+/// - 로컬 디렉토리: `rules/*.yml|*.yaml`, `policies/*.toml` 구조
+/// - 원격 URL: `{"rules": {...}, "policies": {...}}` 형식의 JSON 번들
+///   (파일명 -> 파일 내용), SHA-256 체크섬 파일로 검증
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentConfig {
+    /// 번들 동기화 사용 여부
+    pub enabled: bool,
+    /// 번들 소스 (로컬 디렉토리 경로 또는 https://, http://, s3:// URL)
+    pub source: String,
+    /// 원격 번들의 SHA-256 체크섬 파일 URL (비어 있으면 `{source}.sha256`)
+    pub checksum_url: String,
+    /// 적용된 번들 버전을 보관할 디렉토리
+    pub store_dir: String,
+    /// 동기화 간격 (초, 0이면 시작 시 한 번만 동기화)
+    pub sync_interval_secs: u64,
+    /// 원격 요청 타임아웃 (초)
+    pub timeout_secs: u64,
+    /// 롤백용으로 보관할 버전 수
+    pub keep_versions: usize,
 }
 
-fn override_usize(target: &mut usize, env_key: &str) {
-    if let Ok(val) = std::env::var(env_key) {
-        match val.parse::<usize>() {
-            Ok(parsed) => *target = parsed,
-            Err(_) => warn!(
-                env_key,
-                value = val.as_str(),
-                "failed to parse usize from env var, ignoring"
-            ),
+impl Default for ContentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source: String::new(),
+            checksum_url: String::new(),
+            store_dir: "/var/lib/ironpost/content".to_owned(),
+            sync_interval_secs: 300,
+            timeout_secs: 30,
+            keep_versions: 5,
         }
     }
 }
 
-fn override_u32(target: &mut u32, env_key: &str) {
-    if let Ok(val) = std::env::var(env_key) {
-        match val.parse::<u32>() {
-            Ok(parsed) => *target = parsed,
-            Err(_) => warn!(
-                env_key,
-                value = val.as_str(),
-                "failed to parse u32 from env var, ignoring"
-            ),
-        }
+impl ContentConfig {
+    /// 번들 소스가 원격 URL인지 확인합니다.
+    pub fn is_remote(&self) -> bool {
+        self.source.contains("://")
     }
-}
 
-fn override_u64(target: &mut u64, env_key: &str) {
-    if let Ok(val) = std::env::var(env_key) {
-        match val.parse::<u64>() {
-            Ok(parsed) => *target = parsed,
-            Err(_) => warn!(
-                env_key,
-                value = val.as_str(),
-                "failed to parse u64 from env var, ignoring"
-            ),
+    /// Validate content distribution values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if self.source.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "content.source".to_owned(),
+                reason: "must not be empty".to_owned(),
+            }
+            .into());
         }
-    }
+        if self.is_remote() {
+            validate_remote_url("content.source", &self.source)?;
+            if !self.checksum_url.is_empty() {
+                validate_remote_url("content.checksum_url", &self.checksum_url)?;
+            }
+        }
+        if self.store_dir.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "content.store_dir".to_owned(),
+                reason: "must not be empty".to_owned(),
+            }
+            .into());
+        }
+        if self.sync_interval_secs != 0
+            && (self.sync_interval_secs < 10 || self.sync_interval_secs > 86_400)
+        {
+            return Err(ConfigError::InvalidValue {
+                field: "content.sync_interval_secs".to_owned(),
+                reason: "must be 0 (disabled) or between 10 and 86,400 seconds".to_owned(),
+            }
+            .into());
+        }
+        if self.timeout_secs == 0 || self.timeout_secs > 300 {
+            return Err(ConfigError::InvalidValue {
+                field: "content.timeout_secs".to_owned(),
+                reason: "must be between 1 and 300 seconds".to_owned(),
+            }
+            .into());
+        }
+        if self.keep_versions == 0 || self.keep_versions > 100 {
+            return Err(ConfigError::InvalidValue {
+                field: "content.keep_versions".to_owned(),
+                reason: "must be between 1 and 100".to_owned(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// 데몬 레벨 알림 중복 제거 및 폭주 방지 설정
+///
+/// 모든 모듈의 알림이 소비자(container-guard 등)에 도달하기 전에 거치는
+/// 마지막 단계입니다. 모듈 자체의 rate limit 설정이 잘못되어도
+/// 탐지 폭주가 하위 시스템을 덮치지 않도록 합니다.
+///
+/// - 같은 규칙 + 엔티티(source/target IP) 알림은 `dedup_window_secs` 동안 한 번만 전달
+/// - 1분당 전달 알림 수가 `max_alerts_per_minute`를 넘으면 나머지는 억제하고,
+///   분이 끝날 때 억제 건수를 담은 요약 알림 하나를 전달
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertGateConfig {
+    /// 알림 게이트 사용 여부
+    pub enabled: bool,
+    /// 규칙 + 엔티티 중복 제거 윈도우 (초, 0이면 중복 제거 안 함)
+    pub dedup_window_secs: u64,
+    /// 1분당 최대 전달 알림 수 (0이면 제한 없음)
+    pub max_alerts_per_minute: u64,
+    /// 중복 제거용으로 추적하는 최대 키 수
+    pub max_tracked_keys: usize,
+}
+
+impl Default for AlertGateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dedup_window_secs: 60,
+            max_alerts_per_minute: 600,
+            max_tracked_keys: 10_000,
+        }
+    }
+}
+
+impl AlertGateConfig {
+    /// Validate alert gate values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if self.dedup_window_secs > 86_400 {
+            return Err(ConfigError::InvalidValue {
+                field: "alert_gate.dedup_window_secs".to_owned(),
+                reason: "must not exceed 86,400 seconds".to_owned(),
+            }
+            .into());
+        }
+        if self.max_alerts_per_minute > 1_000_000 {
+            return Err(ConfigError::InvalidValue {
+                field: "alert_gate.max_alerts_per_minute".to_owned(),
+                reason: "must not exceed 1,000,000".to_owned(),
+            }
+            .into());
+        }
+        if self.max_tracked_keys == 0 || self.max_tracked_keys > 1_000_000 {
+            return Err(ConfigError::InvalidValue {
+                field: "alert_gate.max_tracked_keys".to_owned(),
+                reason: "must be between 1 and 1,000,000".to_owned(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// 이벤트 저널 최소 크기 (압축 기준)
+const MIN_JOURNAL_BYTES: u64 = 64 * 1024;
+
+/// 이벤트 저널 및 시작 시 재전송 설정
+///
+/// 켜면 데몬이 소비자(container-guard, 액션 로거)에 넘기는 `AlertEvent`/`ActionEvent`를
+/// `<dir>/events.jsonl`에 기록하고, 소비자가 가져가면 확인(ack) 레코드를 남깁니다.
+/// 재시작 시 확인되지 않은 이벤트를 소비자와 로그 파이프라인 알림 싱크에 다시 전달합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersistenceConfig {
+    /// 이벤트 저널 사용 여부
+    pub enabled: bool,
+    /// 저널 디렉토리
+    pub dir: String,
+    /// 재전송할 이벤트의 최대 나이 (초, 0이면 제한 없음)
+    pub max_replay_age_secs: u64,
+    /// 저널 파일이 이 크기를 넘으면 확인되지 않은 이벤트만 남기고 다시 씁니다 (바이트)
+    pub max_journal_bytes: u64,
+}
+
+impl Default for PersistenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: "/var/lib/ironpost/journal".to_owned(),
+            max_replay_age_secs: 3600,
+            max_journal_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+impl PersistenceConfig {
+    /// Validate persistence values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if self.dir.trim().is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "persistence.dir".to_owned(),
+                reason: "must not be empty".to_owned(),
+            }
+            .into());
+        }
+        if self.max_journal_bytes < MIN_JOURNAL_BYTES {
+            return Err(ConfigError::InvalidValue {
+                field: "persistence.max_journal_bytes".to_owned(),
+                reason: format!("must be at least {} bytes", MIN_JOURNAL_BYTES),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// 채널 오버플로 정책
+pub const OVERFLOW_POLICIES: &[&str] = &["block", "drop_oldest", "spill"];
+
+/// 모듈 간 채널 설정
+///
+/// 오케스트레이터가 모듈 사이에 연결하는 링크(`packets`: eBPF → log-pipeline,
+/// `alerts`: 탐지 모듈 → container-guard)별로 용량과 오버플로 정책을 지정합니다.
+///
+/// - `block`: 채널이 가득 차면 송신측이 대기합니다 (기존 동작)
+/// - `drop_oldest`: 가장 오래된 항목을 버리고 새 항목을 받습니다
+/// - `spill`: 메모리 버퍼가 가득 차면 `spill_dir`에 디스크로 넘기고,
+///   소비자가 따라잡으면 순서대로 다시 읽어 전달합니다
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelsConfig {
+    /// eBPF → log-pipeline 패킷 링크
+    pub packets: LinkConfig,
+    /// 탐지 모듈 → 알림 소비자 링크
+    pub alerts: LinkConfig,
+    /// `spill` 정책의 디스크 버퍼 디렉토리
+    pub spill_dir: String,
+    /// 링크당 디스크 버퍼 최대 크기 (바이트)
+    pub max_spill_bytes: u64,
+}
+
+impl Default for ChannelsConfig {
+    fn default() -> Self {
+        Self {
+            packets: LinkConfig {
+                capacity: 1024,
+                overflow: "block".to_owned(),
+            },
+            alerts: LinkConfig::default(),
+            spill_dir: "/var/lib/ironpost/spill".to_owned(),
+            max_spill_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+impl ChannelsConfig {
+    /// Validate channel values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        self.packets.validate("channels.packets")?;
+        self.alerts.validate("channels.alerts")?;
+
+        let spills = [&self.packets, &self.alerts]
+            .iter()
+            .any(|link| link.overflow == "spill");
+        if spills {
+            if self.spill_dir.is_empty() {
+                return Err(ConfigError::InvalidValue {
+                    field: "channels.spill_dir".to_owned(),
+                    reason: "must not be empty when a link uses the spill policy".to_owned(),
+                }
+                .into());
+            }
+            if self.max_spill_bytes == 0 {
+                return Err(ConfigError::InvalidValue {
+                    field: "channels.max_spill_bytes".to_owned(),
+                    reason: "must be greater than 0 when a link uses the spill policy".to_owned(),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 개별 채널 링크 설정
+///
+/// 링크 섹션에서 생략한 필드는 [`LinkConfig::default`] 값(256, `block`)을 따릅니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinkConfig {
+    /// 메모리 버퍼 용량 (메시지 수)
+    pub capacity: usize,
+    /// 오버플로 정책 (`block`, `drop_oldest`, `spill`)
+    pub overflow: String,
+}
+
+impl Default for LinkConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 256,
+            overflow: "block".to_owned(),
+        }
+    }
+}
+
+impl LinkConfig {
+    fn validate(&self, field: &str) -> Result<(), IronpostError> {
+        if self.capacity == 0 || self.capacity > 1_000_000 {
+            return Err(ConfigError::InvalidValue {
+                field: format!("{}.capacity", field),
+                reason: "must be between 1 and 1,000,000".to_owned(),
+            }
+            .into());
+        }
+        if !OVERFLOW_POLICIES.contains(&self.overflow.as_str()) {
+            return Err(ConfigError::InvalidValue {
+                field: format!("{}.overflow", field),
+                reason: format!("must be one of {:?}", OVERFLOW_POLICIES),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// 제어 API 역할
+pub const AUTH_ROLES: &[&str] = &["read-only", "operator", "admin"];
+
+/// 제어 API 인증/인가 설정
+///
+/// 헬스 리스너의 제어 엔드포인트(`/status` 등)에 Bearer 토큰 인증과
+/// 역할 기반 권한을 적용합니다. `/healthz`, `/readyz` 프로브는
+/// 오케스트레이터가 사용할 수 있도록 항상 인증 없이 열려 있습니다.
+///
+/// - `read-only`: 상태 조회
+/// - `operator`: 상태 조회 + 블록리스트/정책 변경
+/// - `admin`: 모든 작업
+///
+/// `tls_cert_path`/`tls_key_path`를 설정하면 리스너가 TLS로 동작하고,
+/// `client_ca_path`까지 설정하면 해당 CA가 서명한 클라이언트 인증서만
+/// 연결할 수 있습니다(mTLS). 역할은 mTLS 여부와 무관하게 토큰으로 결정됩니다.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// 인증 사용 여부
+    pub enabled: bool,
+    /// API 토큰 목록
+    pub tokens: Vec<ApiTokenConfig>,
+    /// 서버 인증서 경로 (PEM, 비어 있으면 평문 HTTP)
+    pub tls_cert_path: String,
+    /// 서버 개인키 경로 (PEM)
+    pub tls_key_path: String,
+    /// 클라이언트 인증서 검증용 CA 경로 (PEM, 비어 있으면 mTLS 미사용)
+    pub client_ca_path: String,
+}
+
+/// API 토큰
+///
+/// 설정 파일에는 토큰 원문 대신 SHA-256 해시만 저장합니다.
+/// (`printf '%s' "$TOKEN" | sha256sum`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiTokenConfig {
+    /// 토큰 이름 (감사 로그에 기록)
+    pub name: String,
+    /// 토큰의 SHA-256 해시 (hex 64자)
+    pub token_sha256: String,
+    /// 역할 ("read-only", "operator", "admin")
+    pub role: String,
+}
+
+impl AuthConfig {
+    /// TLS 리스너 사용 여부
+    pub fn tls_enabled(&self) -> bool {
+        !self.tls_cert_path.is_empty()
+    }
+
+    /// Validate control API auth values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if self.tokens.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "auth.tokens".to_owned(),
+                reason: "at least one token is required when auth is enabled".to_owned(),
+            }
+            .into());
+        }
+        let mut names = std::collections::HashSet::new();
+        for token in &self.tokens {
+            if token.name.is_empty() {
+                return Err(ConfigError::InvalidValue {
+                    field: "auth.tokens.name".to_owned(),
+                    reason: "must not be empty".to_owned(),
+                }
+                .into());
+            }
+            if !names.insert(token.name.as_str()) {
+                return Err(ConfigError::InvalidValue {
+                    field: "auth.tokens.name".to_owned(),
+                    reason: format!("duplicate token name '{}'", token.name),
+                }
+                .into());
+            }
+            if token.token_sha256.len() != 64
+                || !token.token_sha256.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: "auth.tokens.token_sha256".to_owned(),
+                    reason: format!(
+                        "token '{}' must be a 64-character hex SHA-256 digest",
+                        token.name
+                    ),
+                }
+                .into());
+            }
+            if !AUTH_ROLES.contains(&token.role.as_str()) {
+                return Err(ConfigError::InvalidValue {
+                    field: "auth.tokens.role".to_owned(),
+                    reason: format!(
+                        "token '{}' has unknown role '{}', expected one of: {}",
+                        token.name,
+                        token.role,
+                        AUTH_ROLES.join(", ")
+                    ),
+                }
+                .into());
+            }
+        }
+        if self.tls_cert_path.is_empty() != self.tls_key_path.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "auth.tls_key_path".to_owned(),
+                reason: "tls_cert_path and tls_key_path must be set together".to_owned(),
+            }
+            .into());
+        }
+        if !self.client_ca_path.is_empty() && !self.tls_enabled() {
+            return Err(ConfigError::InvalidValue {
+                field: "auth.client_ca_path".to_owned(),
+                reason: "mTLS requires tls_cert_path and tls_key_path".to_owned(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// CLI 접속 설정
+///
+/// `ironpost` CLI가 데몬 제어 API에 접속하는 방법입니다. 데몬은 이 섹션을
+/// 사용하지 않습니다. CLI의 `--host`/`--socket`/`--token` 플래그가 이 값보다
+/// 우선합니다.
+///
+/// - `host`, `socket` 모두 비어 있으면 `[health]` 리스너에 접속합니다
+///   (`[auth]`에 TLS가 설정되어 있으면 HTTPS).
+/// - `host`는 `host`, `host:port`, `http://host:port`, `https://host:port`
+///   형식이며, 포트를 생략하면 `health.port`를 사용합니다.
+/// - `socket`은 데몬의 `health.unix_socket` 경로입니다.
+///
+/// 토큰은 `token`(환경변수 `IRONPOST_CLIENT_TOKEN` 권장) 또는 `token_file`로
+/// 지정합니다. HTTPS 접속에는 데몬 인증서를 서명한 CA(`ca_cert_path`)가
+/// 필요하고, 데몬이 mTLS를 요구하면 `client_cert_path`/`client_key_path`를
+/// 함께 설정합니다.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientConfig {
+    /// 원격 데몬 주소 (비어 있으면 `[health]` 리스너)
+    pub host: String,
+    /// 데몬 제어 API 유닉스 소켓 경로
+    pub socket: String,
+    /// bearer 토큰 원문
+    pub token: String,
+    /// bearer 토큰 파일 경로 (`token`이 비어 있을 때 사용)
+    pub token_file: String,
+    /// 데몬 인증서 검증용 CA 경로 (PEM)
+    pub ca_cert_path: String,
+    /// mTLS 클라이언트 인증서 경로 (PEM)
+    pub client_cert_path: String,
+    /// mTLS 클라이언트 개인키 경로 (PEM)
+    pub client_key_path: String,
+}
+
+impl ClientConfig {
+    /// Validate CLI connection values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if !self.host.is_empty() && !self.socket.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "client.socket".to_owned(),
+                reason: "host and socket are mutually exclusive".to_owned(),
+            }
+            .into());
+        }
+        if let Some((scheme, _)) = self.host.split_once("://")
+            && scheme != "http"
+            && scheme != "https"
+        {
+            return Err(ConfigError::InvalidValue {
+                field: "client.host".to_owned(),
+                reason: format!("unsupported scheme '{}', expected http or https", scheme),
+            }
+            .into());
+        }
+        if self.client_cert_path.is_empty() != self.client_key_path.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "client.client_key_path".to_owned(),
+                reason: "client_cert_path and client_key_path must be set together".to_owned(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+fn validate_remote_url(field: &str, url: &str) -> Result<(), IronpostError> {
+    if !REMOTE_CONFIG_SCHEMES
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+    {
+        return Err(ConfigError::InvalidValue {
+            field: field.to_owned(),
+            reason: format!(
+                "must start with one of: {}",
+                REMOTE_CONFIG_SCHEMES.join(", ")
+            ),
+        }
+        .into());
+    }
+    if url.starts_with("http://") {
+        warn!(
+            field,
+            "remote config fetched over plain HTTP; checksums do not protect against tampering in transit"
+        );
+    }
+    Ok(())
+}
+
+/// eBPF 엔진 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EbpfConfig {
+    /// 활성화 여부
+    pub enabled: bool,
+    /// 감시할 네트워크 인터페이스
+    ///
+    /// 쉼표로 구분하거나(`"eth0,eth1"`) TOML 배열(`["eth0", "eth1"]`)로 여러 개를
+    /// 지정할 수 있으며, [`EBPF_INTERFACE_ALL`]이면 루프백을 제외한 모든 인터페이스에,
+    /// [`EBPF_INTERFACE_AUTO`]이면 시작 시점에 기본 경로(default route)가 지나는
+    /// 인터페이스에 어태치합니다. 배열은 쉼표로 구분된 문자열로 정규화되어 저장됩니다.
+    #[serde(deserialize_with = "deserialize_interface")]
+    pub interface: String,
+    /// XDP 모드 (native, skb, hw)
+    pub xdp_mode: String,
+    /// 이벤트 링 버퍼 크기 (바이트)
+    ///
+    /// 커널 `EVENTS` RingBuf 크기로 로드 시점에 적용됩니다. 커널 요구사항에 따라
+    /// [`EBPF_RING_BUFFER_MIN_SIZE`] ~ [`EBPF_RING_BUFFER_MAX_SIZE`] 범위의 2의 거듭제곱이어야 합니다.
+    pub ring_buffer_size: usize,
+    /// 차단 목록 최대 엔트리 수
+    pub blocklist_max_entries: usize,
+    /// 출발지 IP당 허용 패킷 수/초 (XDP 토큰 버킷, 0이면 비활성화)
+    pub rate_limit_pps: u64,
+    /// 레이트 리밋 버스트 허용량 (패킷 수, 0이면 `rate_limit_pps`와 동일)
+    pub rate_limit_burst: u64,
+    /// 모니터링(monitor) 패킷 이벤트 샘플링 비율 N (N개 중 1개만 이벤트 전송, 1이면 전부)
+    ///
+    /// 차단(block) 이벤트는 샘플링하지 않습니다.
+    pub monitor_sample_rate: u32,
+    /// 관찰 전용(드라이런) 모드 — 차단 판정을 드롭하지 않고 통과시키며 "would-drop"으로 집계
+    ///
+    /// 새 차단 목록을 실제 트래픽에 적용하기 전에 검증하는 용도입니다. 차단 이벤트와 로그는
+    /// 그대로 남고, 통계는 실제 드롭(`drops`)과 드롭될 뻔한 패킷(`would_drops`)을 구분합니다.
+    /// `general.dry_run`이 켜져 있으면 이 값과 관계없이 관찰 전용으로 동작합니다.
+    pub observe_only: bool,
+    /// SYN flood / 포트 스캔 탐지 시 출발지 IP를 자동 차단할 기간 (초, 0이면 비활성화)
+    ///
+    /// 탐지된 출발지는 이 기간 동안 차단 목록에 들어갔다가 만료 시 자동으로 해제됩니다.
+    pub auto_block_ttl_secs: u64,
+    /// 전체 트래픽 초당 패킷 수 알림 임계치 (0이면 비활성화)
+    pub traffic_alert_pps: u64,
+    /// 전체 트래픽 초당 바이트 수 알림 임계치 (0이면 비활성화)
+    pub traffic_alert_bytes_per_sec: u64,
+    /// 폴링 주기 동안 드롭된 패킷 비율 알림 임계치 (퍼센트, 0이면 비활성화)
+    pub traffic_alert_drop_percent: u32,
+    /// 같은 트래픽 임계치 알림을 다시 보내기 전 대기 시간 (초)
+    pub traffic_alert_cooldown_secs: u64,
+    /// 새 TCP 연결의 소유 프로세스(PID, comm, cgroup)를 kprobe로 기록해 알림에 덧붙일지 여부
+    ///
+    /// `tcp_connect`/`inet_csk_accept` kprobe를 추가로 어태치하므로 커널 심볼 접근 권한이 필요합니다.
+    pub process_correlation: bool,
+    /// 나가는 TLS ClientHello에서 SNI 호스트 이름을 추출해 `PacketEvent`로 보낼지 여부
+    ///
+    /// XDP가 어태치된 인터페이스마다 TC egress 분류기를 추가로 어태치합니다.
+    pub tls_sni: bool,
+    /// 필터 룰과 런타임 차단 엔트리를 저장할 상태 파일 경로 (빈 문자열이면 비활성화)
+    ///
+    /// 엔진 시작 시 이 파일에서 차단 목록을 복원하므로, 맵 고정 없이도 데몬 재시작 후
+    /// 동적 차단이 유지됩니다.
+    pub state_path: String,
+    /// 차단 목록/통계 맵을 고정(pin)할 bpffs 디렉토리 (데몬 재시작 후에도 상태 유지)
+    pub map_pin_path: String,
+    /// 주기적으로 내려받아 차단 목록에 반영할 위협 인텔리전스(IP 평판) 피드
+    pub threat_feeds: Vec<ThreatFeedConfig>,
+}
+
+impl Default for EbpfConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interface: "eth0".to_owned(),
+            xdp_mode: "skb".to_owned(),
+            ring_buffer_size: 256 * 1024, // 256KB
+            blocklist_max_entries: 10_000,
+            rate_limit_pps: 0,
+            rate_limit_burst: 0,
+            monitor_sample_rate: 1,
+            observe_only: false,
+            auto_block_ttl_secs: 0,
+            traffic_alert_pps: 0,
+            traffic_alert_bytes_per_sec: 0,
+            traffic_alert_drop_percent: 0,
+            traffic_alert_cooldown_secs: 300,
+            process_correlation: false,
+            tls_sni: false,
+            state_path: "/var/lib/ironpost/ebpf-state.json".to_owned(),
+            map_pin_path: "/sys/fs/bpf/ironpost".to_owned(),
+            threat_feeds: Vec::new(),
+        }
+    }
 }
 
-fn override_u16(target: &mut u16, env_key: &str) {
-    if let Ok(val) = std::env::var(env_key) {
-        match val.parse::<u16>() {
-            Ok(parsed) => *target = parsed,
-            Err(_) => warn!(
-                env_key,
-                value = val.as_str(),
-                "failed to parse u16 from env var, ignoring"
-            ),
-        }
+/// `ebpf.threat_feeds[].format`에 허용되는 값
+pub const THREAT_FEED_FORMATS: &[&str] = &["plain", "stix"];
+
+/// 위협 인텔리전스 피드 최소 갱신 주기 (초)
+pub const THREAT_FEED_MIN_REFRESH_SECS: u64 = 60;
+
+/// 위협 인텔리전스(IP 평판) 피드
+///
+/// eBPF 엔진이 `refresh_secs`마다 내려받아 `feed:<name>:` ID 접두사가 붙은 차단 룰로
+/// 변환해 차단 목록에 반영합니다. 피드에서 빠진 주소의 룰은 다음 갱신 때 제거됩니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThreatFeedConfig {
+    /// 피드 이름 (룰 ID 태그, 영문/숫자/`-`/`_`)
+    pub name: String,
+    /// 피드 URL (https://, http://, file://)
+    pub url: String,
+    /// 피드 형식 (`plain`: 한 줄에 IPv4 주소/CIDR 하나, `stix`: STIX 2.x 번들 JSON)
+    pub format: String,
+    /// 갱신 주기 (초, 최소 [`THREAT_FEED_MIN_REFRESH_SECS`])
+    pub refresh_secs: u64,
+    /// 다운로드 타임아웃 (초)
+    pub timeout_secs: u64,
+}
+
+impl Default for ThreatFeedConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            url: String::new(),
+            format: "plain".to_owned(),
+            refresh_secs: 3600,
+            timeout_secs: 30,
+        }
+    }
+}
+
+impl ThreatFeedConfig {
+    fn validate(&self, field: &str) -> Result<(), IronpostError> {
+        if self.name.is_empty()
+            || !self
+                .name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        {
+            return Err(ConfigError::InvalidValue {
+                field: format!("{}.name", field),
+                reason: "must be non-empty and contain only letters, digits, '-' or '_'".to_owned(),
+            }
+            .into());
+        }
+        if !["https://", "http://", "file://"]
+            .iter()
+            .any(|scheme| self.url.starts_with(scheme))
+        {
+            return Err(ConfigError::InvalidValue {
+                field: format!("{}.url", field),
+                reason: "must start with one of: https://, http://, file://".to_owned(),
+            }
+            .into());
+        }
+        if !THREAT_FEED_FORMATS.contains(&self.format.as_str()) {
+            return Err(ConfigError::InvalidValue {
+                field: format!("{}.format", field),
+                reason: format!("must be one of: {}", THREAT_FEED_FORMATS.join(", ")),
+            }
+            .into());
+        }
+        if self.refresh_secs < THREAT_FEED_MIN_REFRESH_SECS {
+            return Err(ConfigError::InvalidValue {
+                field: format!("{}.refresh_secs", field),
+                reason: format!("must be at least {}", THREAT_FEED_MIN_REFRESH_SECS),
+            }
+            .into());
+        }
+        if self.timeout_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: format!("{}.timeout_secs", field),
+                reason: "must be greater than 0".to_owned(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// `ebpf.interface`에서 모든 인터페이스를 뜻하는 값
+pub const EBPF_INTERFACE_ALL: &str = "all";
+
+/// `ebpf.interface`에서 기본 경로 인터페이스를 뜻하는 값
+pub const EBPF_INTERFACE_AUTO: &str = "auto";
+
+/// `ebpf.ring_buffer_size` 최소값 (커널 RingBuf는 페이지 크기의 배수여야 함)
+pub const EBPF_RING_BUFFER_MIN_SIZE: usize = 4 * 1024;
+
+/// `ebpf.ring_buffer_size` 최대값 (1GiB)
+pub const EBPF_RING_BUFFER_MAX_SIZE: usize = 1024 * 1024 * 1024;
+
+impl EbpfConfig {
+    /// `interface`를 인터페이스 이름 목록으로 나눕니다.
+    ///
+    /// 앞뒤 공백과 빈 항목은 버리고, 중복은 처음 나온 순서대로 한 번만 남깁니다.
+    pub fn interfaces(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in self.interface.split(',').map(str::trim) {
+            if !name.is_empty() && !names.iter().any(|n| n == name) {
+                names.push(name.to_owned());
+            }
+        }
+        names
+    }
+
+    /// 모든 인터페이스에 어태치하도록 설정되었는지 확인합니다 (`interface = "all"`).
+    pub fn attaches_all_interfaces(&self) -> bool {
+        self.interface
+            .trim()
+            .eq_ignore_ascii_case(EBPF_INTERFACE_ALL)
+    }
+
+    /// 기본 경로 인터페이스를 자동으로 찾도록 설정되었는지 확인합니다 (`interface = "auto"`).
+    pub fn uses_default_route_interface(&self) -> bool {
+        self.interface
+            .trim()
+            .eq_ignore_ascii_case(EBPF_INTERFACE_AUTO)
+    }
+
+    /// Validate eBPF configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if !self.ring_buffer_size.is_power_of_two()
+            || !(EBPF_RING_BUFFER_MIN_SIZE..=EBPF_RING_BUFFER_MAX_SIZE)
+                .contains(&self.ring_buffer_size)
+        {
+            return Err(ConfigError::InvalidValue {
+                field: "ebpf.ring_buffer_size".to_owned(),
+                reason: format!(
+                    "must be a power of two between {} and {} bytes",
+                    EBPF_RING_BUFFER_MIN_SIZE, EBPF_RING_BUFFER_MAX_SIZE
+                ),
+            }
+            .into());
+        }
+        if self.blocklist_max_entries == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "ebpf.blocklist_max_entries".to_owned(),
+                reason: "must be greater than 0".to_owned(),
+            }
+            .into());
+        }
+        if self.monitor_sample_rate == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "ebpf.monitor_sample_rate".to_owned(),
+                reason: "must be greater than 0 (1 sends every monitored packet)".to_owned(),
+            }
+            .into());
+        }
+        if self.traffic_alert_drop_percent > 100 {
+            return Err(ConfigError::InvalidValue {
+                field: "ebpf.traffic_alert_drop_percent".to_owned(),
+                reason: "must be between 0 and 100".to_owned(),
+            }
+            .into());
+        }
+        if !self.map_pin_path.starts_with('/') {
+            return Err(ConfigError::InvalidValue {
+                field: "ebpf.map_pin_path".to_owned(),
+                reason: "must be an absolute path on a bpffs mount".to_owned(),
+            }
+            .into());
+        }
+        let mut feed_names = std::collections::HashSet::new();
+        for (i, feed) in self.threat_feeds.iter().enumerate() {
+            let field = format!("ebpf.threat_feeds[{}]", i);
+            feed.validate(&field)?;
+            if !feed_names.insert(feed.name.as_str()) {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("{}.name", field),
+                    reason: format!("duplicate feed name '{}'", feed.name),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 로그 파이프라인 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogPipelineConfig {
+    /// 활성화 여부
+    pub enabled: bool,
+    /// 수집 소스
+    pub sources: Vec<String>,
+    /// Syslog UDP 수신 주소
+    pub syslog_bind: String,
+    /// Syslog TCP 수신 주소
+    pub syslog_tcp_bind: String,
+    /// 파일 감시 경로
+    pub watch_paths: Vec<String>,
+    /// 배치 크기
+    pub batch_size: usize,
+    /// 배치 플러시 간격 (초)
+    pub flush_interval_secs: u64,
+    /// 규칙 디렉토리 변경 시 재시작 없이 규칙을 다시 로드할지 여부
+    ///
+    /// `content` 번들 배포와 함께 쓸 수 없습니다 (번들 규칙을 덮어쓰므로).
+    pub watch_rules: bool,
+    /// 규칙 억제(예외) 목록 YAML 파일 경로 (빈 문자열이면 사용 안 함)
+    pub suppressions_file: String,
+    /// 스토리지 설정
+    #[serde(default)]
+    pub storage: StorageConfig,
+    /// IP 보강(GeoIP/ASN, 역방향 DNS) 설정
+    #[serde(default)]
+    pub enrichment: EnrichmentConfig,
+    /// Kafka 수집 소스 설정 (`sources`에 "kafka"가 있을 때 사용)
+    #[serde(default)]
+    pub kafka: KafkaConfig,
+    /// HTTP 수집 엔드포인트 설정 (`sources`에 "http"가 있을 때 사용)
+    #[serde(default)]
+    pub http: HttpIngestConfig,
+    /// 파일 수집 소스 설정 (`sources`에 "file"이 있을 때 사용)
+    #[serde(default)]
+    pub file: FileSourceConfig,
+    /// 선택적 파서 설정
+    #[serde(default)]
+    pub parsers: ParsersConfig,
+    /// 소스 종류별 기본 파서 힌트 (예: "file" -> "json")
+    ///
+    /// 수집기가 힌트를 주지 않은 로그에 사용합니다. 힌트 파서가 실패하면 자동 감지합니다.
+    #[serde(default)]
+    pub format_hints: BTreeMap<String, String>,
+    /// 알림 전달 싱크 목록 (`[[log_pipeline.sinks]]`)
+    #[serde(default)]
+    pub sinks: Vec<AlertSinkConfig>,
+    /// 이메일 싱크가 사용하는 SMTP 서버 설정
+    #[serde(default)]
+    pub smtp: SmtpConfig,
+    /// Elasticsearch/OpenSearch 출력 설정
+    #[serde(default)]
+    pub elasticsearch: ElasticsearchConfig,
+    /// Kafka 출력(프로듀서) 설정
+    #[serde(default)]
+    pub kafka_output: KafkaOutputConfig,
+    /// 디스크 오버플로 버퍼 설정
+    #[serde(default)]
+    pub disk_buffer: DiskBufferConfig,
+    /// 필드 변환 목록 (`[[log_pipeline.transforms]]`, 등록 순서대로 적용)
+    #[serde(default)]
+    pub transforms: Vec<TransformConfig>,
+    /// 내장 로그 저장소 설정
+    #[serde(default)]
+    pub log_store: LogStoreConfig,
+}
+
+impl Default for LogPipelineConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sources: vec!["syslog".to_owned(), "file".to_owned()],
+            syslog_bind: "0.0.0.0:514".to_owned(),
+            syslog_tcp_bind: "0.0.0.0:601".to_owned(),
+            watch_paths: vec!["/var/log/syslog".to_owned()],
+            batch_size: 100,
+            flush_interval_secs: 5,
+            watch_rules: false,
+            suppressions_file: String::new(),
+            storage: StorageConfig::default(),
+            enrichment: EnrichmentConfig::default(),
+            kafka: KafkaConfig::default(),
+            http: HttpIngestConfig::default(),
+            file: FileSourceConfig::default(),
+            parsers: ParsersConfig::default(),
+            format_hints: BTreeMap::new(),
+            sinks: Vec::new(),
+            smtp: SmtpConfig::default(),
+            elasticsearch: ElasticsearchConfig::default(),
+            kafka_output: KafkaOutputConfig::default(),
+            disk_buffer: DiskBufferConfig::default(),
+            transforms: Vec::new(),
+            log_store: LogStoreConfig::default(),
+        }
+    }
+}
+
+impl LogPipelineConfig {
+    /// Validate log pipeline configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if self.batch_size == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.batch_size".to_owned(),
+                reason: "must be greater than 0".to_owned(),
+            }
+            .into());
+        }
+        if self.batch_size > 10_000 {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.batch_size".to_owned(),
+                reason: "must not exceed 10,000 (performance limit)".to_owned(),
+            }
+            .into());
+        }
+        if self.flush_interval_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.flush_interval_secs".to_owned(),
+                reason: "must be greater than 0".to_owned(),
+            }
+            .into());
+        }
+        self.storage.validate()?;
+        self.enrichment.validate()?;
+        self.parsers.validate()?;
+        self.parsers.validate_format_hints(&self.format_hints)?;
+        if self.sources.iter().any(|s| s == "kafka") {
+            self.kafka.validate()?;
+        }
+        if self.sources.iter().any(|s| s == "http") {
+            self.http.validate()?;
+        }
+        if self.sources.iter().any(|s| s == "file") {
+            self.file.validate()?;
+        }
+        for (i, sink) in self.sinks.iter().enumerate() {
+            let field = format!("log_pipeline.sinks[{}]", i);
+            sink.validate(&field)?;
+            if self.sinks[..i].iter().any(|other| other.name == sink.name) {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("{}.name", field),
+                    reason: format!("duplicate sink name '{}'", sink.name),
+                }
+                .into());
+            }
+            if sink.kind == "file"
+                && self.sinks[..i]
+                    .iter()
+                    .any(|other| other.kind == "file" && other.path == sink.path)
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("{}.path", field),
+                    reason: format!("file '{}' is already used by another sink", sink.path),
+                }
+                .into());
+            }
+        }
+        if self.sinks.iter().any(|sink| sink.kind == "email") {
+            self.smtp.validate()?;
+        }
+        if self.elasticsearch.enabled {
+            self.elasticsearch.validate()?;
+        }
+        if self.kafka_output.enabled {
+            self.kafka_output.validate()?;
+        }
+        if self.disk_buffer.enabled {
+            self.disk_buffer.validate()?;
+        }
+        for (i, transform) in self.transforms.iter().enumerate() {
+            transform.validate(&format!("log_pipeline.transforms[{}]", i))?;
+        }
+        if self.log_store.enabled {
+            self.log_store.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// `log_pipeline.sinks[].type`에 허용되는 값
+pub const ALERT_SINK_TYPES: &[&str] = &["webhook", "slack", "discord", "teams", "email", "file"];
+
+/// `log_pipeline.sinks[].method`에 허용되는 값
+pub const ALERT_SINK_METHODS: &[&str] = &["POST", "PUT"];
+
+/// `log_pipeline.sinks[].max_retries` 최대값
+pub const ALERT_SINK_MAX_RETRIES: u32 = 10;
+
+/// `log_pipeline.sinks[].queue_size` 최대값
+pub const ALERT_SINK_MAX_QUEUE_SIZE: usize = 100_000;
+
+/// `log_pipeline.sinks[].digest_interval_secs` 최대값 (7일)
+pub const ALERT_SINK_MAX_DIGEST_INTERVAL_SECS: u64 = 7 * 24 * 3600;
+
+/// `log_pipeline.sinks[].digest_max_alerts` 최대값
+pub const ALERT_SINK_MAX_DIGEST_ALERTS: usize = 10_000;
+
+/// `log_pipeline.sinks[].rotate_interval_secs` 최소값 (0 제외)
+pub const ALERT_SINK_MIN_ROTATE_INTERVAL_SECS: u64 = 60;
+
+/// `log_pipeline.sinks[].max_files` 최대값
+pub const ALERT_SINK_MAX_FILES: usize = 10_000;
+
+/// `log_pipeline.smtp.tls`에 허용되는 값
+pub const SMTP_TLS_MODES: &[&str] = &["starttls", "tls", "none"];
+
+/// 알림 전달 싱크
+///
+/// 로그 파이프라인이 생성한 알림 중 `min_severity` 이상을 외부로 전달합니다.
+/// 싱크마다 별도 큐와 전송 태스크를 가지므로 느린 싱크가 탐지 처리를 막지 않습니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertSinkConfig {
+    /// 싱크 이름 (로그/메트릭 라벨, 영문/숫자/`-`/`_`)
+    pub name: String,
+    /// 싱크 종류 (`webhook`, `slack`, `discord`, `teams`, `email`, `file`)
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// 전송 URL (https://, http://, `email`/`file`은 사용 안 함)
+    pub url: String,
+    /// HTTP 메서드 (`POST`, `PUT`, 채팅 싱크는 `POST`만)
+    pub method: String,
+    /// 추가 HTTP 헤더 (예: `Authorization`)
+    pub headers: BTreeMap<String, String>,
+    /// JSON 페이로드 템플릿 (`{{title}}` 등 치환, 비어 있으면 기본 JSON 형식, `webhook`만)
+    pub template: String,
+    /// 전달할 최소 심각도 (info, low, medium, high, critical)
+    pub min_severity: String,
+    /// 전달할 규칙 태그 (하나라도 일치하면 전달, 비어 있으면 모든 규칙)
+    pub rule_tags: Vec<String>,
+    /// 요청 타임아웃 (초)
+    pub timeout_secs: u64,
+    /// 실패 시 최대 재시도 횟수 (최대 [`ALERT_SINK_MAX_RETRIES`])
+    pub max_retries: u32,
+    /// 첫 재시도 대기 시간 (밀리초, 재시도마다 2배)
+    pub retry_backoff_ms: u64,
+    /// 전송 대기 큐 크기 (가득 차면 새 알림을 버림)
+    pub queue_size: usize,
+    /// 수신자 이메일 주소 (`email`만)
+    pub to: Vec<String>,
+    /// 즉시 메일을 보낼 최소 심각도, 미만은 다이제스트로 모음 (`email`만)
+    pub immediate_severity: String,
+    /// 다이제스트 발송 주기 (초, 0이면 모든 알림을 즉시 발송, `email`만)
+    pub digest_interval_secs: u64,
+    /// 다이제스트 한 통에 나열할 최대 알림 수, 초과분은 집계만 (`email`만)
+    pub digest_max_alerts: usize,
+    /// 알림을 한 줄에 하나씩 JSON으로 덧붙일 파일 경로 (`file`만)
+    pub path: String,
+    /// 파일이 이 크기(바이트)를 넘으면 교체, 0이면 크기로 교체하지 않음 (`file`만)
+    pub max_file_bytes: u64,
+    /// 파일 교체 주기 (초, 0이면 시간으로 교체하지 않음, `file`만)
+    pub rotate_interval_secs: u64,
+    /// 보관할 교체된 파일 수, 넘으면 오래된 파일부터 삭제 (0이면 모두 보관, `file`만)
+    pub max_files: usize,
+}
+
+impl Default for AlertSinkConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            kind: "webhook".to_owned(),
+            url: String::new(),
+            method: "POST".to_owned(),
+            headers: BTreeMap::new(),
+            template: String::new(),
+            min_severity: "info".to_owned(),
+            rule_tags: Vec::new(),
+            timeout_secs: 10,
+            max_retries: 3,
+            retry_backoff_ms: 500,
+            queue_size: 1000,
+            to: Vec::new(),
+            immediate_severity: "critical".to_owned(),
+            digest_interval_secs: 3600,
+            digest_max_alerts: 200,
+            path: String::new(),
+            max_file_bytes: 100 * 1024 * 1024,
+            rotate_interval_secs: 86_400,
+            max_files: 30,
+        }
+    }
+}
+
+impl AlertSinkConfig {
+    /// 싱크 설정을 검증합니다. `field`는 에러 메시지의 필드 경로 접두사입니다.
+    pub fn validate(&self, field: &str) -> Result<(), IronpostError> {
+        let invalid = |name: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("{}.{}", field, name),
+                reason,
+            }
+            .into()
+        };
+
+        if self.name.is_empty()
+            || !self
+                .name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        {
+            return Err(invalid(
+                "name",
+                "must be non-empty and contain only letters, digits, '-' or '_'".to_owned(),
+            ));
+        }
+        if !ALERT_SINK_TYPES.contains(&self.kind.as_str()) {
+            return Err(invalid(
+                "type",
+                format!("must be one of: {}", ALERT_SINK_TYPES.join(", ")),
+            ));
+        }
+        match self.kind.as_str() {
+            "email" => self.validate_email(field)?,
+            "file" => self.validate_file(field)?,
+            _ => {
+                if !["https://", "http://"]
+                    .iter()
+                    .any(|scheme| self.url.starts_with(scheme))
+                {
+                    return Err(invalid(
+                        "url",
+                        "must start with one of: https://, http://".to_owned(),
+                    ));
+                }
+            }
+        }
+        if !ALERT_SINK_METHODS.contains(&self.method.as_str()) {
+            return Err(invalid(
+                "method",
+                format!("must be one of: {}", ALERT_SINK_METHODS.join(", ")),
+            ));
+        }
+        if self.kind != "webhook" {
+            if self.method != "POST" {
+                return Err(invalid(
+                    "method",
+                    format!("{} sinks only support POST", self.kind),
+                ));
+            }
+            if !self.template.is_empty() {
+                return Err(invalid(
+                    "template",
+                    "is only supported for webhook sinks".to_owned(),
+                ));
+            }
+        }
+        if Severity::from_str_loose(&self.min_severity).is_none() {
+            return Err(invalid(
+                "min_severity",
+                "must be one of: info, low, medium, high, critical".to_owned(),
+            ));
+        }
+        if self.rule_tags.iter().any(|tag| tag.trim().is_empty()) {
+            return Err(invalid(
+                "rule_tags",
+                "must not contain empty tags".to_owned(),
+            ));
+        }
+        if self.timeout_secs == 0 {
+            return Err(invalid("timeout_secs", "must be greater than 0".to_owned()));
+        }
+        if self.max_retries > ALERT_SINK_MAX_RETRIES {
+            return Err(invalid(
+                "max_retries",
+                format!("must not exceed {}", ALERT_SINK_MAX_RETRIES),
+            ));
+        }
+        if self.max_retries > 0 && self.retry_backoff_ms == 0 {
+            return Err(invalid(
+                "retry_backoff_ms",
+                "must be greater than 0 when retries are enabled".to_owned(),
+            ));
+        }
+        if self.queue_size == 0 || self.queue_size > ALERT_SINK_MAX_QUEUE_SIZE {
+            return Err(invalid(
+                "queue_size",
+                format!("must be between 1 and {}", ALERT_SINK_MAX_QUEUE_SIZE),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 이메일 싱크 전용 필드를 검증합니다.
+    fn validate_email(&self, field: &str) -> Result<(), IronpostError> {
+        let invalid = |name: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("{}.{}", field, name),
+                reason,
+            }
+            .into()
+        };
+
+        if self.to.is_empty() {
+            return Err(invalid(
+                "to",
+                "at least one recipient is required".to_owned(),
+            ));
+        }
+        if let Some(address) = self.to.iter().find(|address| !is_email_address(address)) {
+            return Err(invalid(
+                "to",
+                format!("invalid email address '{}'", address),
+            ));
+        }
+        if Severity::from_str_loose(&self.immediate_severity).is_none() {
+            return Err(invalid(
+                "immediate_severity",
+                "must be one of: info, low, medium, high, critical".to_owned(),
+            ));
+        }
+        if self.digest_interval_secs > ALERT_SINK_MAX_DIGEST_INTERVAL_SECS {
+            return Err(invalid(
+                "digest_interval_secs",
+                format!("must not exceed {}", ALERT_SINK_MAX_DIGEST_INTERVAL_SECS),
+            ));
+        }
+        if self.digest_max_alerts == 0 || self.digest_max_alerts > ALERT_SINK_MAX_DIGEST_ALERTS {
+            return Err(invalid(
+                "digest_max_alerts",
+                format!("must be between 1 and {}", ALERT_SINK_MAX_DIGEST_ALERTS),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 파일 싱크 전용 필드를 검증합니다.
+    fn validate_file(&self, field: &str) -> Result<(), IronpostError> {
+        let invalid = |name: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("{}.{}", field, name),
+                reason,
+            }
+            .into()
+        };
+
+        if self.path.trim().is_empty() || self.path.ends_with('/') {
+            return Err(invalid("path", "must be a file path".to_owned()));
+        }
+        if self.rotate_interval_secs != 0
+            && self.rotate_interval_secs < ALERT_SINK_MIN_ROTATE_INTERVAL_SECS
+        {
+            return Err(invalid(
+                "rotate_interval_secs",
+                format!(
+                    "must be 0 (disabled) or at least {}",
+                    ALERT_SINK_MIN_ROTATE_INTERVAL_SECS
+                ),
+            ));
+        }
+        if self.max_files > ALERT_SINK_MAX_FILES {
+            return Err(invalid(
+                "max_files",
+                format!("must not exceed {}", ALERT_SINK_MAX_FILES),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// SMTP 서버 설정
+///
+/// `type = "email"` 싱크가 하나라도 있을 때만 검증하고 사용합니다.
+/// 비밀번호는 환경변수 `IRONPOST_SMTP_PASSWORD`로 지정하는 것을 권장합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmtpConfig {
+    /// SMTP 서버 호스트명
+    pub host: String,
+    /// SMTP 서버 포트 (STARTTLS 587, 암묵적 TLS 465)
+    pub port: u16,
+    /// TLS 방식 (`starttls`, `tls`, `none`)
+    pub tls: String,
+    /// 인증 사용자 (비어 있으면 인증 안 함)
+    pub username: String,
+    /// 인증 비밀번호
+    pub password: String,
+    /// 발신자 주소 (예: `Ironpost <ironpost@example.com>`)
+    pub from: String,
+    /// 연결/명령 타임아웃 (초)
+    pub timeout_secs: u64,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            port: 587,
+            tls: "starttls".to_owned(),
+            username: String::new(),
+            password: String::new(),
+            from: String::new(),
+            timeout_secs: 30,
+        }
+    }
+}
+
+impl SmtpConfig {
+    /// Validate SMTP server configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        let invalid = |field: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("log_pipeline.smtp.{}", field),
+                reason,
+            }
+            .into()
+        };
+
+        if self.host.trim().is_empty() {
+            return Err(invalid(
+                "host",
+                "must not be empty when email sinks are configured".to_owned(),
+            ));
+        }
+        if self.port == 0 {
+            return Err(invalid("port", "must be greater than 0".to_owned()));
+        }
+        if !SMTP_TLS_MODES.contains(&self.tls.as_str()) {
+            return Err(invalid(
+                "tls",
+                format!("must be one of: {}", SMTP_TLS_MODES.join(", ")),
+            ));
+        }
+        if self.username.is_empty() != self.password.is_empty() {
+            return Err(invalid(
+                "password",
+                "username and password must be set together".to_owned(),
+            ));
+        }
+        if !self.username.is_empty() && self.tls == "none" {
+            return Err(invalid(
+                "tls",
+                "authentication requires starttls or tls".to_owned(),
+            ));
+        }
+        if !is_email_address(&self.from) {
+            return Err(invalid(
+                "from",
+                format!("invalid email address '{}'", self.from),
+            ));
+        }
+        if self.timeout_secs == 0 {
+            return Err(invalid("timeout_secs", "must be greater than 0".to_owned()));
+        }
+        Ok(())
+    }
+}
+
+/// 이메일 주소 형식을 간단히 확인합니다 (`user@host` 또는 `Name <user@host>`).
+///
+/// 헤더 주입을 막기 위해 제어 문자를 거부합니다.
+fn is_email_address(value: &str) -> bool {
+    let address = match value.rsplit_once('<') {
+        Some((_, rest)) => match rest.strip_suffix('>') {
+            Some(address) => address,
+            None => return false,
+        },
+        None => value,
+    };
+    match address.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.is_empty()
+                && !domain.contains('@')
+                && !address.chars().any(|c| c.is_whitespace() || c.is_control())
+                && !value.chars().any(char::is_control)
+        }
+        None => false,
+    }
+}
+
+/// `log_pipeline.elasticsearch.batch_size` 최대값
+pub const ELASTICSEARCH_MAX_BATCH_SIZE: usize = 10_000;
+
+/// `log_pipeline.elasticsearch.queue_size` 최대값
+pub const ELASTICSEARCH_MAX_QUEUE_SIZE: usize = 1_000_000;
+
+/// `log_pipeline.elasticsearch.max_retries` 최대값
+pub const ELASTICSEARCH_MAX_RETRIES: u32 = 10;
+
+/// 인덱스 템플릿에 쓸 수 있는 날짜 지정자 (`%Y`, `%m` 등, UTC 기준)
+pub const ELASTICSEARCH_INDEX_DATE_SPECIFIERS: &[char] = &['Y', 'y', 'm', 'd', 'H', 'j', 'G', 'V'];
+
+/// Elasticsearch/OpenSearch 출력 설정
+///
+/// 파싱된 로그와 생성된 알림을 `_bulk` API로 색인합니다. 문서는 크기 `queue_size`의
+/// 큐에 쌓였다가 `batch_size`개 또는 `flush_interval_ms`마다 한 번에 전송되며,
+/// 큐가 가득 차면 새 문서를 버려 탐지 처리를 막지 않습니다.
+/// 비밀번호와 API 키는 환경변수로 지정하는 것을 권장합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ElasticsearchConfig {
+    /// 활성화 여부
+    pub enabled: bool,
+    /// 클러스터 URL (예: `https://es.example.com:9200`)
+    pub url: String,
+    /// Basic 인증 사용자 (비어 있으면 사용 안 함)
+    pub username: String,
+    /// Basic 인증 비밀번호
+    pub password: String,
+    /// API 키 (`Authorization: ApiKey ...`, Basic 인증과 함께 쓸 수 없음)
+    pub api_key: String,
+    /// 로그 색인 여부
+    pub index_logs: bool,
+    /// 알림 색인 여부
+    pub index_alerts: bool,
+    /// 로그 인덱스 템플릿 (`%Y.%m.%d` 날짜, `{source}` 수집 소스)
+    pub log_index: String,
+    /// 알림 인덱스 템플릿 (`%Y.%m.%d` 날짜, `{source}` 알림 생성 모듈)
+    pub alert_index: String,
+    /// bulk 요청당 최대 문서 수
+    pub batch_size: usize,
+    /// 배치가 차지 않아도 전송하는 간격 (밀리초)
+    pub flush_interval_ms: u64,
+    /// 전송 대기 문서 큐 크기 (가득 차면 새 문서를 버림)
+    pub queue_size: usize,
+    /// 요청 타임아웃 (초)
+    pub timeout_secs: u64,
+    /// 실패 시 최대 재시도 횟수 (최대 [`ELASTICSEARCH_MAX_RETRIES`])
+    pub max_retries: u32,
+    /// 첫 재시도 대기 시간 (밀리초, 재시도마다 2배)
+    pub retry_backoff_ms: u64,
+}
+
+impl Default for ElasticsearchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: "http://localhost:9200".to_owned(),
+            username: String::new(),
+            password: String::new(),
+            api_key: String::new(),
+            index_logs: true,
+            index_alerts: true,
+            log_index: "ironpost-logs-%Y.%m.%d".to_owned(),
+            alert_index: "ironpost-alerts-%Y.%m.%d".to_owned(),
+            batch_size: 500,
+            flush_interval_ms: 1000,
+            queue_size: 10_000,
+            timeout_secs: 30,
+            max_retries: 5,
+            retry_backoff_ms: 500,
+        }
+    }
+}
+
+impl ElasticsearchConfig {
+    /// Validate Elasticsearch output configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        let invalid = |field: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("log_pipeline.elasticsearch.{}", field),
+                reason,
+            }
+            .into()
+        };
+
+        if !["https://", "http://"]
+            .iter()
+            .any(|scheme| self.url.starts_with(scheme))
+        {
+            return Err(invalid(
+                "url",
+                "must start with one of: https://, http://".to_owned(),
+            ));
+        }
+        if self.username.is_empty() != self.password.is_empty() {
+            return Err(invalid(
+                "password",
+                "username and password must be set together".to_owned(),
+            ));
+        }
+        if !self.api_key.is_empty() && !self.username.is_empty() {
+            return Err(invalid(
+                "api_key",
+                "cannot be combined with username/password".to_owned(),
+            ));
+        }
+        if !self.index_logs && !self.index_alerts {
+            return Err(invalid(
+                "index_logs",
+                "at least one of index_logs and index_alerts must be enabled".to_owned(),
+            ));
+        }
+        for (field, template) in [
+            ("log_index", &self.log_index),
+            ("alert_index", &self.alert_index),
+        ] {
+            validate_index_template(template).map_err(|reason| invalid(field, reason))?;
+        }
+        if self.batch_size == 0 || self.batch_size > ELASTICSEARCH_MAX_BATCH_SIZE {
+            return Err(invalid(
+                "batch_size",
+                format!("must be between 1 and {}", ELASTICSEARCH_MAX_BATCH_SIZE),
+            ));
+        }
+        if self.flush_interval_ms == 0 {
+            return Err(invalid(
+                "flush_interval_ms",
+                "must be greater than 0".to_owned(),
+            ));
+        }
+        if self.queue_size == 0 || self.queue_size > ELASTICSEARCH_MAX_QUEUE_SIZE {
+            return Err(invalid(
+                "queue_size",
+                format!("must be between 1 and {}", ELASTICSEARCH_MAX_QUEUE_SIZE),
+            ));
+        }
+        if self.timeout_secs == 0 {
+            return Err(invalid("timeout_secs", "must be greater than 0".to_owned()));
+        }
+        if self.max_retries > ELASTICSEARCH_MAX_RETRIES {
+            return Err(invalid(
+                "max_retries",
+                format!("must not exceed {}", ELASTICSEARCH_MAX_RETRIES),
+            ));
+        }
+        if self.max_retries > 0 && self.retry_backoff_ms == 0 {
+            return Err(invalid(
+                "retry_backoff_ms",
+                "must be greater than 0 when retries are enabled".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 인덱스 템플릿을 검증합니다.
+///
+/// 날짜 지정자와 `{source}`를 제외한 부분은 Elasticsearch 인덱스 이름 규칙
+/// (소문자, 금지 문자 없음, `-`/`_`/`+`로 시작하지 않음)을 따라야 합니다.
+fn validate_index_template(template: &str) -> Result<(), String> {
+    if template.is_empty() {
+        return Err("must not be empty".to_owned());
+    }
+    if template.len() > 200 {
+        return Err("must not exceed 200 bytes".to_owned());
+    }
+    if template.starts_with(['-', '_', '+']) {
+        return Err("must not start with '-', '_' or '+'".to_owned());
+    }
+
+    let mut rest = template.replace("{source}", "");
+    if rest.contains(['{', '}']) {
+        return Err("only the {source} placeholder is supported".to_owned());
+    }
+    while let Some(pos) = rest.find('%') {
+        let specifier = rest[pos + 1..].chars().next();
+        match specifier {
+            Some(c) if ELASTICSEARCH_INDEX_DATE_SPECIFIERS.contains(&c) => {
+                rest.replace_range(pos..pos + 2, "");
+            }
+            _ => {
+                let allowed: Vec<String> = ELASTICSEARCH_INDEX_DATE_SPECIFIERS
+                    .iter()
+                    .map(|c| format!("%{}", c))
+                    .collect();
+                return Err(format!(
+                    "unsupported date specifier, expected one of: {}",
+                    allowed.join(", ")
+                ));
+            }
+        }
+    }
+    if let Some(c) = rest.chars().find(|c| {
+        c.is_ascii_uppercase()
+            || c.is_whitespace()
+            || c.is_control()
+            || ['\\', '/', '*', '?', '"', '<', '>', '|', ',', '#', ':'].contains(c)
+    }) {
+        return Err(format!(
+            "invalid character '{}' (index names must be lowercase without \\ / * ? \" < > | , # :)",
+            c.escape_default()
+        ));
+    }
+    if rest == "." || rest == ".." {
+        return Err("must not be '.' or '..'".to_owned());
+    }
+    Ok(())
+}
+
+impl ParsersConfig {
+    /// `log_pipeline.format_hints`의 소스 종류와 파서 이름을 검증합니다.
+    ///
+    /// 힌트의 파서는 내장 파서이거나 이 설정으로 켠 파서여야 합니다.
+    pub fn validate_format_hints(
+        &self,
+        format_hints: &BTreeMap<String, String>,
+    ) -> Result<(), IronpostError> {
+        for (source, format) in format_hints {
+            if !FORMAT_HINT_SOURCES.contains(&source.as_str()) {
+                return Err(ConfigError::InvalidValue {
+                    field: "log_pipeline.format_hints".to_owned(),
+                    reason: format!(
+                        "unknown source '{}', expected one of {:?}",
+                        source, FORMAT_HINT_SOURCES
+                    ),
+                }
+                .into());
+            }
+            let registered = match format.as_str() {
+                "leef" => self.leef,
+                name if BUILTIN_PARSER_NAMES.contains(&name) => true,
+                name => self.patterns.iter().any(|p| p.name == name),
+            };
+            if !registered {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("log_pipeline.format_hints.{}", source),
+                    reason: format!("parser '{}' is not enabled", format),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `log_pipeline.format_hints`에 사용할 수 있는 소스 종류 (`RawLog.source`의 `:` 앞부분)
+pub const FORMAT_HINT_SOURCES: &[&str] = &[
+    "syslog_udp",
+    "syslog_tcp",
+    "file",
+    "kafka",
+    "http",
+    "ebpf-engine",
+];
+
+/// 스토리지 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// PostgreSQL 연결 문자열
+    pub postgres_url: String,
+    /// Redis 연결 문자열
+    pub redis_url: String,
+    /// 로그 보존 기간 (일)
+    pub retention_days: u32,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            postgres_url: "postgresql://localhost:5432/ironpost".to_owned(),
+            redis_url: "redis://localhost:6379".to_owned(),
+            retention_days: 30,
+        }
+    }
+}
+
+impl StorageConfig {
+    /// Validate storage configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if self.retention_days == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.storage.retention_days".to_owned(),
+                reason: "must be greater than 0".to_owned(),
+            }
+            .into());
+        }
+        if self.retention_days > 3650 {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.storage.retention_days".to_owned(),
+                reason: "must not exceed 3,650 days (10 years)".to_owned(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// `log_pipeline.disk_buffer.segment_bytes` 최소값 (64 KiB)
+pub const DISK_BUFFER_MIN_SEGMENT_BYTES: u64 = 64 * 1024;
+
+/// `log_pipeline.disk_buffer.segment_bytes` 최대값 (1 GiB)
+pub const DISK_BUFFER_MAX_SEGMENT_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// 디스크 오버플로 버퍼 설정
+///
+/// 인메모리 버퍼(`buffer_capacity`)가 가득 차면 버리는 대신 `dir`의 세그먼트 파일에
+/// 로그를 적재하고, 파이프라인이 따라잡으면 순서대로 다시 읽어 처리합니다.
+/// 읽기 위치는 인덱스 파일에 기록되므로 재시작 후에도 남은 로그를 이어서 처리합니다.
+///
+/// 디스크 사용량이 `max_bytes`에 도달하면 드롭 정책을 따릅니다: `Oldest`는 가장 오래된
+/// 세그먼트를 버리고, `Newest`는 새 로그를 거부합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiskBufferConfig {
+    /// 디스크 버퍼 활성화 여부
+    pub enabled: bool,
+    /// 세그먼트/인덱스 파일 디렉토리
+    pub dir: String,
+    /// 디스크 버퍼 최대 크기 (바이트)
+    pub max_bytes: u64,
+    /// 세그먼트 파일 하나의 최대 크기 (바이트)
+    pub segment_bytes: u64,
+}
+
+impl Default for DiskBufferConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: "/var/lib/ironpost/buffer".to_owned(),
+            max_bytes: 1024 * 1024 * 1024,
+            segment_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+impl DiskBufferConfig {
+    /// Validate disk buffer configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        let invalid = |field: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("log_pipeline.disk_buffer.{}", field),
+                reason,
+            }
+            .into()
+        };
+
+        if self.dir.trim().is_empty() {
+            return Err(invalid("dir", "must not be empty".to_owned()));
+        }
+        if !(DISK_BUFFER_MIN_SEGMENT_BYTES..=DISK_BUFFER_MAX_SEGMENT_BYTES)
+            .contains(&self.segment_bytes)
+        {
+            return Err(invalid(
+                "segment_bytes",
+                format!(
+                    "must be between {} and {}",
+                    DISK_BUFFER_MIN_SEGMENT_BYTES, DISK_BUFFER_MAX_SEGMENT_BYTES
+                ),
+            ));
+        }
+        if self.max_bytes < self.segment_bytes.saturating_mul(2) {
+            return Err(invalid(
+                "max_bytes",
+                "must be at least twice segment_bytes".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// `log_pipeline.log_store.segment_bytes` 최소값 (64 KiB)
+pub const LOG_STORE_MIN_SEGMENT_BYTES: u64 = 64 * 1024;
+
+/// `log_pipeline.log_store.segment_bytes` 최대값 (1 GiB)
+pub const LOG_STORE_MAX_SEGMENT_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// `log_pipeline.log_store.queue_size` 최대값
+pub const LOG_STORE_MAX_QUEUE_SIZE: usize = 1_000_000;
+
+/// 내장 로그 저장소 설정
+///
+/// 파싱된 로그를 `dir`의 세그먼트 파일(JSONL)에 저장하여 외부 저장소 없이도 검색할 수
+/// 있게 합니다. 세그먼트는 `segment_bytes`를 넘거나 1시간이 지나면 교체되고, 마지막 기록이
+/// `retention_days`보다 오래된 세그먼트와 전체 크기가 `max_bytes`를 넘을 때의 가장 오래된
+/// 세그먼트는 삭제됩니다. 0인 한도는 적용하지 않습니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogStoreConfig {
+    /// 로그 저장소 활성화 여부
+    pub enabled: bool,
+    /// 세그먼트 파일 디렉토리
+    pub dir: String,
+    /// 보존 기간 (일, 0이면 기간으로 삭제하지 않음)
+    pub retention_days: u32,
+    /// 저장소 최대 크기 (바이트, 0이면 크기로 삭제하지 않음)
+    pub max_bytes: u64,
+    /// 세그먼트 파일 하나의 최대 크기 (바이트)
+    pub segment_bytes: u64,
+    /// 저장 대기 큐 크기 (가득 차면 새 로그를 버림)
+    pub queue_size: usize,
+}
+
+impl Default for LogStoreConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: "/var/lib/ironpost/logs".to_owned(),
+            retention_days: 7,
+            max_bytes: 10 * 1024 * 1024 * 1024,
+            segment_bytes: 64 * 1024 * 1024,
+            queue_size: 10_000,
+        }
+    }
+}
+
+impl LogStoreConfig {
+    /// Validate log store configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        let invalid = |field: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("log_pipeline.log_store.{}", field),
+                reason,
+            }
+            .into()
+        };
+
+        if self.dir.trim().is_empty() {
+            return Err(invalid("dir", "must not be empty".to_owned()));
+        }
+        if self.retention_days > 3650 {
+            return Err(invalid(
+                "retention_days",
+                "must not exceed 3,650 days (10 years)".to_owned(),
+            ));
+        }
+        if self.retention_days == 0 && self.max_bytes == 0 {
+            return Err(invalid(
+                "retention_days",
+                "retention_days and max_bytes must not both be 0".to_owned(),
+            ));
+        }
+        if !(LOG_STORE_MIN_SEGMENT_BYTES..=LOG_STORE_MAX_SEGMENT_BYTES)
+            .contains(&self.segment_bytes)
+        {
+            return Err(invalid(
+                "segment_bytes",
+                format!(
+                    "must be between {} and {}",
+                    LOG_STORE_MIN_SEGMENT_BYTES, LOG_STORE_MAX_SEGMENT_BYTES
+                ),
+            ));
+        }
+        if self.max_bytes != 0 && self.max_bytes < self.segment_bytes.saturating_mul(2) {
+            return Err(invalid(
+                "max_bytes",
+                "must be 0 or at least twice segment_bytes".to_owned(),
+            ));
+        }
+        if self.queue_size == 0 || self.queue_size > LOG_STORE_MAX_QUEUE_SIZE {
+            return Err(invalid(
+                "queue_size",
+                format!("must be between 1 and {}", LOG_STORE_MAX_QUEUE_SIZE),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// `log_pipeline.transforms[].type`에 허용되는 값
+pub const TRANSFORM_TYPES: &[&str] = &["rename", "extract", "hash", "redact"];
+
+/// `log_pipeline.transforms[].preset`에 허용되는 값
+pub const TRANSFORM_PRESETS: &[&str] = &["credit_card", "email"];
+
+/// 모든 필드(`message`와 추가 필드)를 대상으로 하는 `field` 값 (`hash`/`redact`만)
+pub const TRANSFORM_ALL_FIELDS: &str = "*";
+
+/// 필드 변환 설정 (`[[log_pipeline.transforms]]`)
+///
+/// 파싱 직후, 보강과 규칙 매칭 전에 등록 순서대로 적용됩니다.
+/// `field`가 `hostname`, `process`, `message`, `source`이면 `LogEntry`의 같은 필드를,
+/// 나머지는 추가 필드(`fields`)를 가리킵니다.
+///
+/// - `rename`: `field`의 값을 `to`로 옮깁니다.
+/// - `extract`: `field`에 `pattern`을 적용해 이름 있는 그룹마다 필드를 만듭니다.
+/// - `redact`: `pattern` 또는 `preset`에 일치하는 부분(둘 다 없으면 값 전체)을
+///   `replacement`로 바꿉니다.
+/// - `hash`: 일치하는 부분을 `salt`를 붙인 SHA-256 16진수 값으로 바꿉니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransformConfig {
+    /// 변환 종류 (`rename`, `extract`, `hash`, `redact`)
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// 대상 필드 이름 (`hash`/`redact`는 `"*"`이면 `message`와 모든 추가 필드)
+    pub field: String,
+    /// 새 필드 이름 (`rename`만)
+    pub to: String,
+    /// 정규식 (`extract`는 이름 있는 그룹 필수, `hash`/`redact`는 일치 부분만 변환)
+    pub pattern: String,
+    /// 내장 패턴 (`credit_card`, `email`, `hash`/`redact`만, `pattern`과 함께 쓸 수 없음)
+    pub preset: String,
+    /// 가린 부분을 대신할 문자열 (`redact`만)
+    pub replacement: String,
+    /// 해시 앞에 붙일 솔트 (`hash`만)
+    pub salt: String,
+}
+
+impl Default for TransformConfig {
+    fn default() -> Self {
+        Self {
+            kind: String::new(),
+            field: String::new(),
+            to: String::new(),
+            pattern: String::new(),
+            preset: String::new(),
+            replacement: "[REDACTED]".to_owned(),
+            salt: String::new(),
+        }
+    }
+}
+
+impl TransformConfig {
+    /// 변환 설정을 검증합니다. `field`는 에러 메시지의 필드 경로 접두사입니다.
+    ///
+    /// 정규식 컴파일은 log-pipeline이 변환 단계를 만들 때 검증합니다.
+    pub fn validate(&self, field: &str) -> Result<(), IronpostError> {
+        let invalid = |name: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("{}.{}", field, name),
+                reason,
+            }
+            .into()
+        };
+
+        if !TRANSFORM_TYPES.contains(&self.kind.as_str()) {
+            return Err(invalid(
+                "type",
+                format!("must be one of: {}", TRANSFORM_TYPES.join(", ")),
+            ));
+        }
+        if self.field.trim().is_empty() {
+            return Err(invalid("field", "must not be empty".to_owned()));
+        }
+        if self.pattern.len() > PATTERN_MAX_LENGTH {
+            return Err(invalid(
+                "pattern",
+                format!("must not exceed {} characters", PATTERN_MAX_LENGTH),
+            ));
+        }
+        let masks = matches!(self.kind.as_str(), "hash" | "redact");
+        if !masks && self.field == TRANSFORM_ALL_FIELDS {
+            return Err(invalid(
+                "field",
+                format!(
+                    "'{}' is only supported by hash and redact",
+                    TRANSFORM_ALL_FIELDS
+                ),
+            ));
+        }
+        if !masks && !self.preset.is_empty() {
+            return Err(invalid(
+                "preset",
+                "is only supported by hash and redact".to_owned(),
+            ));
+        }
+
+        match self.kind.as_str() {
+            "rename" => {
+                if self.to.trim().is_empty() || self.to == TRANSFORM_ALL_FIELDS {
+                    return Err(invalid("to", "must be a field name".to_owned()));
+                }
+                if self.to == self.field {
+                    return Err(invalid("to", "must differ from field".to_owned()));
+                }
+            }
+            "extract" => {
+                if self.pattern.is_empty() {
+                    return Err(invalid("pattern", "must not be empty".to_owned()));
+                }
+            }
+            _ => {
+                if !self.preset.is_empty() && !TRANSFORM_PRESETS.contains(&self.preset.as_str()) {
+                    return Err(invalid(
+                        "preset",
+                        format!("must be one of: {}", TRANSFORM_PRESETS.join(", ")),
+                    ));
+                }
+                if !self.preset.is_empty() && !self.pattern.is_empty() {
+                    return Err(invalid(
+                        "preset",
+                        "cannot be combined with pattern".to_owned(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// IP 보강 설정
+///
+/// IP 주소에 GeoIP 국가, ASN, 역방향 DNS 이름을 붙일 때 사용합니다.
+/// GeoIP 데이터베이스는 MaxMind DB 형식(GeoLite2-Country, GeoLite2-ASN 등)이며,
+/// 경로가 비어 있으면 해당 항목은 조회하지 않습니다.
+///
+/// `enrich_logs = true`이면 파이프라인이 파싱과 규칙 매칭 사이에서 `ip_fields`의 IP 주소마다
+/// `<필드>_country`, `<필드>_asn`, `<필드>_as_org`, `<필드>_hostname` 필드를 덧붙입니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnrichmentConfig {
+    /// 파이프라인에서 로그 엔트리를 보강할지 여부
+    pub enrich_logs: bool,
+    /// 보강할 IP 필드 이름 (대소문자 무시)
+    pub ip_fields: Vec<String>,
+    /// GeoIP 국가 데이터베이스 경로 (.mmdb)
+    pub geoip_country_db: String,
+    /// GeoIP ASN 데이터베이스 경로 (.mmdb)
+    pub geoip_asn_db: String,
+    /// 역방향 DNS 조회 여부
+    pub reverse_dns: bool,
+    /// 역방향 DNS 조회 타임아웃 (밀리초)
+    pub dns_timeout_ms: u64,
+    /// 역방향 DNS 결과 캐시 크기 (항목 수)
+    pub dns_cache_size: usize,
+    /// 조회에 성공한 역방향 DNS 결과의 캐시 유지 시간 (초)
+    pub dns_cache_ttl_secs: u64,
+    /// 조회 실패·타임아웃 결과의 캐시 유지 시간 (초)
+    pub dns_negative_cache_ttl_secs: u64,
+    /// 동시에 진행할 수 있는 역방향 DNS 조회 수
+    pub dns_max_concurrent_lookups: usize,
+}
+
+impl Default for EnrichmentConfig {
+    fn default() -> Self {
+        Self {
+            enrich_logs: false,
+            ip_fields: [
+                "src_ip",
+                "source_ip",
+                "client_ip",
+                "dst_ip",
+                "dest_ip",
+                "destination_ip",
+                "target_ip",
+                "remote_ip",
+            ]
+            .into_iter()
+            .map(str::to_owned)
+            .collect(),
+            geoip_country_db: String::new(),
+            geoip_asn_db: String::new(),
+            reverse_dns: true,
+            dns_timeout_ms: 500,
+            dns_cache_size: 10_000,
+            dns_cache_ttl_secs: 3600,
+            dns_negative_cache_ttl_secs: 60,
+            dns_max_concurrent_lookups: 16,
+        }
+    }
+}
+
+impl EnrichmentConfig {
+    /// Validate enrichment configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if self.dns_timeout_ms == 0 || self.dns_timeout_ms > 10_000 {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.enrichment.dns_timeout_ms".to_owned(),
+                reason: "must be between 1 and 10,000".to_owned(),
+            }
+            .into());
+        }
+        if self.dns_cache_size == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.enrichment.dns_cache_size".to_owned(),
+                reason: "must be greater than 0".to_owned(),
+            }
+            .into());
+        }
+        if self.dns_cache_ttl_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.enrichment.dns_cache_ttl_secs".to_owned(),
+                reason: "must be greater than 0".to_owned(),
+            }
+            .into());
+        }
+        if self.dns_negative_cache_ttl_secs > self.dns_cache_ttl_secs {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.enrichment.dns_negative_cache_ttl_secs".to_owned(),
+                reason: "must not exceed dns_cache_ttl_secs".to_owned(),
+            }
+            .into());
+        }
+        if self.dns_max_concurrent_lookups == 0 || self.dns_max_concurrent_lookups > 1024 {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.enrichment.dns_max_concurrent_lookups".to_owned(),
+                reason: "must be between 1 and 1,024".to_owned(),
+            }
+            .into());
+        }
+        if self.enrich_logs
+            && (self.ip_fields.is_empty() || self.ip_fields.iter().any(|f| f.trim().is_empty()))
+        {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.enrichment.ip_fields".to_owned(),
+                reason: "at least one non-empty field name is required when enrich_logs is enabled"
+                    .to_owned(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Kafka 오프셋 자동 리셋 정책 허용값
+pub const KAFKA_OFFSET_RESETS: &[&str] = &["earliest", "latest"];
+
+/// 수집기가 직접 관리하므로 `properties`로 덮어쓸 수 없는 librdkafka 속성
+pub const KAFKA_MANAGED_PROPERTIES: &[&str] = &[
+    "bootstrap.servers",
+    "group.id",
+    "auto.offset.reset",
+    "enable.auto.commit",
+    "enable.auto.offset.store",
+    "auto.commit.interval.ms",
+    "reconnect.backoff.max.ms",
+];
+
+/// Kafka 수집 소스 설정
+///
+/// 기존 로그 버스(Kafka)의 토픽을 컨슈머 그룹으로 구독해 파이프라인에 넣을 때 사용합니다.
+/// 오프셋은 메시지를 파이프라인 채널에 넘긴 뒤에만 저장하고 `commit_interval_secs`마다
+/// 커밋하므로, 재시작 시 일부 메시지가 다시 전달될 수는 있어도 유실되지는 않습니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KafkaConfig {
+    /// 부트스트랩 브로커 주소 목록 (`host:port`)
+    pub brokers: Vec<String>,
+    /// 구독할 토픽 목록
+    pub topics: Vec<String>,
+    /// 컨슈머 그룹 ID
+    pub group_id: String,
+    /// 커밋된 오프셋이 없을 때 시작 위치 ("earliest", "latest")
+    pub auto_offset_reset: String,
+    /// 오프셋 커밋 간격 (초)
+    pub commit_interval_secs: u64,
+    /// 브로커 재연결 최대 백오프 (초)
+    pub reconnect_backoff_max_secs: u64,
+    /// 파서 형식 힌트 (비어 있으면 자동 감지)
+    pub format: String,
+    /// 추가 librdkafka 속성 (예: `security.protocol`, `sasl.mechanisms`)
+    pub properties: BTreeMap<String, String>,
+}
+
+impl Default for KafkaConfig {
+    fn default() -> Self {
+        Self {
+            brokers: vec!["localhost:9092".to_owned()],
+            topics: Vec::new(),
+            group_id: "ironpost".to_owned(),
+            auto_offset_reset: "latest".to_owned(),
+            commit_interval_secs: 5,
+            reconnect_backoff_max_secs: 30,
+            format: String::new(),
+            properties: BTreeMap::new(),
+        }
+    }
+}
+
+impl KafkaConfig {
+    /// Validate Kafka source configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        let invalid = |field: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("log_pipeline.kafka.{}", field),
+                reason,
+            }
+            .into()
+        };
+
+        if self.brokers.is_empty() || self.brokers.iter().any(|b| b.trim().is_empty()) {
+            return Err(invalid(
+                "brokers",
+                "at least one non-empty broker address is required".to_owned(),
+            ));
+        }
+        if self.topics.is_empty() || self.topics.iter().any(|t| t.trim().is_empty()) {
+            return Err(invalid(
+                "topics",
+                "at least one non-empty topic is required".to_owned(),
+            ));
+        }
+        if self.group_id.trim().is_empty() {
+            return Err(invalid("group_id", "must not be empty".to_owned()));
+        }
+        if !KAFKA_OFFSET_RESETS.contains(&self.auto_offset_reset.as_str()) {
+            return Err(invalid(
+                "auto_offset_reset",
+                format!(
+                    "must be one of {:?}, got '{}'",
+                    KAFKA_OFFSET_RESETS, self.auto_offset_reset
+                ),
+            ));
+        }
+        if self.commit_interval_secs == 0 || self.commit_interval_secs > 3600 {
+            return Err(invalid(
+                "commit_interval_secs",
+                "must be between 1 and 3,600".to_owned(),
+            ));
+        }
+        if self.reconnect_backoff_max_secs == 0 || self.reconnect_backoff_max_secs > 3600 {
+            return Err(invalid(
+                "reconnect_backoff_max_secs",
+                "must be between 1 and 3,600".to_owned(),
+            ));
+        }
+        if let Some(key) = self
+            .properties
+            .keys()
+            .find(|k| KAFKA_MANAGED_PROPERTIES.contains(&k.as_str()))
+        {
+            return Err(invalid(
+                "properties",
+                format!(
+                    "'{}' is managed by the collector and cannot be overridden",
+                    key
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Kafka 출력 알림 메시지 키 허용값
+pub const KAFKA_OUTPUT_ALERT_KEYS: &[&str] =
+    &["none", "rule_id", "alert_id", "severity", "source_ip"];
+
+/// Kafka 출력 로그 메시지 키 허용값
+pub const KAFKA_OUTPUT_LOG_KEYS: &[&str] = &["none", "source", "hostname"];
+
+/// Kafka 출력 전달 보장 수준 허용값
+pub const KAFKA_OUTPUT_DELIVERY_MODES: &[&str] = &["at_most_once", "at_least_once", "idempotent"];
+
+/// Kafka 출력 큐 크기 상한
+pub const KAFKA_OUTPUT_MAX_QUEUE_SIZE: usize = 1_000_000;
+
+/// 출력이 직접 관리하므로 `properties`로 덮어쓸 수 없는 librdkafka 프로듀서 속성
+pub const KAFKA_OUTPUT_MANAGED_PROPERTIES: &[&str] = &[
+    "bootstrap.servers",
+    "acks",
+    "enable.idempotence",
+    "message.send.max.retries",
+    "retries",
+    "linger.ms",
+    "message.timeout.ms",
+];
+
+/// Kafka 출력(프로듀서) 설정
+///
+/// 생성된 알림과, `log_topic`을 지정하면 파싱된 로그까지 JSON 메시지로 Kafka 토픽에
+/// 발행합니다. 다운스트림 스트림 처리기(Flink, ksqlDB 등)와 연동할 때 사용합니다.
+///
+/// `delivery`는 프로듀서 전달 보장 수준입니다:
+/// - `at_most_once`: 브로커 응답을 기다리지 않고 재시도하지 않습니다 (`acks=0`).
+/// - `at_least_once`: 모든 복제본 응답을 기다리고 실패하면 재시도합니다 (`acks=all`).
+///   재시도로 메시지가 중복될 수 있습니다.
+/// - `idempotent`: `at_least_once`에 멱등 프로듀서를 더해 재시도로 인한 중복과 순서 뒤바뀜을
+///   막습니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KafkaOutputConfig {
+    /// 출력 활성화 여부
+    pub enabled: bool,
+    /// 부트스트랩 브로커 주소 목록 (`host:port`)
+    pub brokers: Vec<String>,
+    /// 알림 토픽 (빈 문자열이면 알림을 발행하지 않음)
+    pub alert_topic: String,
+    /// 로그 토픽 (빈 문자열이면 로그를 발행하지 않음)
+    pub log_topic: String,
+    /// 알림 메시지 키 ("none", "rule_id", "alert_id", "severity", "source_ip")
+    pub alert_key: String,
+    /// 로그 메시지 키 ("none", "source", "hostname")
+    pub log_key: String,
+    /// 전달 보장 수준 ("at_most_once", "at_least_once", "idempotent")
+    pub delivery: String,
+    /// 배치를 모으기 위해 전송 전 기다리는 시간 (밀리초)
+    pub linger_ms: u64,
+    /// 메시지 전달 제한 시간 (밀리초, 재시도 포함)
+    pub message_timeout_ms: u64,
+    /// 출력 큐 크기 (가득 차면 새 메시지를 버림)
+    pub queue_size: usize,
+    /// 추가 librdkafka 속성 (예: `security.protocol`, `compression.type`)
+    pub properties: BTreeMap<String, String>,
+}
+
+impl Default for KafkaOutputConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            brokers: vec!["localhost:9092".to_owned()],
+            alert_topic: "ironpost-alerts".to_owned(),
+            log_topic: String::new(),
+            alert_key: "rule_id".to_owned(),
+            log_key: "hostname".to_owned(),
+            delivery: "at_least_once".to_owned(),
+            linger_ms: 5,
+            message_timeout_ms: 30_000,
+            queue_size: 10_000,
+            properties: BTreeMap::new(),
+        }
+    }
+}
+
+impl KafkaOutputConfig {
+    /// Validate Kafka output configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        let invalid = |field: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("log_pipeline.kafka_output.{}", field),
+                reason,
+            }
+            .into()
+        };
+
+        if self.brokers.is_empty() || self.brokers.iter().any(|b| b.trim().is_empty()) {
+            return Err(invalid(
+                "brokers",
+                "at least one non-empty broker address is required".to_owned(),
+            ));
+        }
+        if self.alert_topic.is_empty() && self.log_topic.is_empty() {
+            return Err(invalid(
+                "alert_topic",
+                "at least one of alert_topic and log_topic must be set".to_owned(),
+            ));
+        }
+        for (field, topic) in [
+            ("alert_topic", &self.alert_topic),
+            ("log_topic", &self.log_topic),
+        ] {
+            if !topic.is_empty() {
+                validate_kafka_topic(topic).map_err(|reason| invalid(field, reason))?;
+            }
+        }
+        if !KAFKA_OUTPUT_ALERT_KEYS.contains(&self.alert_key.as_str()) {
+            return Err(invalid(
+                "alert_key",
+                format!(
+                    "must be one of {:?}, got '{}'",
+                    KAFKA_OUTPUT_ALERT_KEYS, self.alert_key
+                ),
+            ));
+        }
+        if !KAFKA_OUTPUT_LOG_KEYS.contains(&self.log_key.as_str()) {
+            return Err(invalid(
+                "log_key",
+                format!(
+                    "must be one of {:?}, got '{}'",
+                    KAFKA_OUTPUT_LOG_KEYS, self.log_key
+                ),
+            ));
+        }
+        if !KAFKA_OUTPUT_DELIVERY_MODES.contains(&self.delivery.as_str()) {
+            return Err(invalid(
+                "delivery",
+                format!(
+                    "must be one of {:?}, got '{}'",
+                    KAFKA_OUTPUT_DELIVERY_MODES, self.delivery
+                ),
+            ));
+        }
+        if self.linger_ms > 60_000 {
+            return Err(invalid("linger_ms", "must not exceed 60,000".to_owned()));
+        }
+        if self.message_timeout_ms == 0 {
+            return Err(invalid(
+                "message_timeout_ms",
+                "must be greater than 0".to_owned(),
+            ));
+        }
+        if self.queue_size == 0 || self.queue_size > KAFKA_OUTPUT_MAX_QUEUE_SIZE {
+            return Err(invalid(
+                "queue_size",
+                format!("must be between 1 and {}", KAFKA_OUTPUT_MAX_QUEUE_SIZE),
+            ));
+        }
+        if let Some(key) = self
+            .properties
+            .keys()
+            .find(|k| KAFKA_OUTPUT_MANAGED_PROPERTIES.contains(&k.as_str()))
+        {
+            return Err(invalid(
+                "properties",
+                format!(
+                    "'{}' is managed by the output and cannot be overridden",
+                    key
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Kafka 토픽 이름 검증 (최대 249자, 영숫자와 `.`, `_`, `-`만 허용)
+fn validate_kafka_topic(topic: &str) -> Result<(), String> {
+    if topic.len() > 249 {
+        return Err("must not exceed 249 characters".to_owned());
+    }
+    if topic == "." || topic == ".." {
+        return Err("must not be '.' or '..'".to_owned());
+    }
+    if let Some(c) = topic
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')))
+    {
+        return Err(format!("invalid character '{}' in topic '{}'", c, topic));
+    }
+    Ok(())
+}
+
+/// HTTP 수집 엔드포인트 최대 요청 본문 크기 상한 (바이트)
+pub const HTTP_INGEST_MAX_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// HTTP 수집 엔드포인트 설정
+///
+/// 애플리케이션이나 Vector, Fluent Bit 같은 에이전트가 `POST /ingest`로 JSON 또는 NDJSON
+/// 배치를 직접 보낼 때 사용합니다. 요청에는 `Authorization: Bearer <token>` 헤더가 필요하며,
+/// 설정 파일에는 `[auth]`와 같이 토큰 원문 대신 SHA-256 해시만 저장합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpIngestConfig {
+    /// 수신 주소 (`host:port`)
+    pub bind: String,
+    /// 수집 토큰 목록
+    pub tokens: Vec<IngestTokenConfig>,
+    /// 최대 요청 본문 크기 (바이트)
+    pub max_body_bytes: usize,
+    /// 파서 형식 힌트 (비어 있으면 JSON 객체는 "json", 문자열은 자동 감지)
+    pub format: String,
+}
+
+impl Default for HttpIngestConfig {
+    fn default() -> Self {
+        Self {
+            bind: "0.0.0.0:8088".to_owned(),
+            tokens: Vec::new(),
+            max_body_bytes: 10 * 1024 * 1024,
+            format: String::new(),
+        }
+    }
+}
+
+/// HTTP 수집 토큰
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IngestTokenConfig {
+    /// 토큰 이름 (수집 소스 식별자 `http:<name>`에 사용)
+    pub name: String,
+    /// 토큰의 SHA-256 해시 (hex 64자)
+    pub token_sha256: String,
+}
+
+impl HttpIngestConfig {
+    /// Validate HTTP ingest endpoint values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        let invalid = |field: &str, reason: String| -> IronpostError {
+            ConfigError::InvalidValue {
+                field: format!("log_pipeline.http.{}", field),
+                reason,
+            }
+            .into()
+        };
+
+        if self.bind.parse::<std::net::SocketAddr>().is_err() {
+            return Err(invalid(
+                "bind",
+                format!("'{}' is not a valid socket address", self.bind),
+            ));
+        }
+        if self.tokens.is_empty() {
+            return Err(invalid(
+                "tokens",
+                "at least one token is required".to_owned(),
+            ));
+        }
+        let mut names = std::collections::HashSet::new();
+        for token in &self.tokens {
+            if token.name.is_empty() {
+                return Err(invalid("tokens.name", "must not be empty".to_owned()));
+            }
+            if !names.insert(token.name.as_str()) {
+                return Err(invalid(
+                    "tokens.name",
+                    format!("duplicate token name '{}'", token.name),
+                ));
+            }
+            if token.token_sha256.len() != 64
+                || !token.token_sha256.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return Err(invalid(
+                    "tokens.token_sha256",
+                    format!(
+                        "token '{}' must be a 64-character hex SHA-256 digest",
+                        token.name
+                    ),
+                ));
+            }
+        }
+        if self.max_body_bytes == 0 || self.max_body_bytes > HTTP_INGEST_MAX_BODY_LIMIT {
+            return Err(invalid(
+                "max_body_bytes",
+                format!("must be between 1 and {}", HTTP_INGEST_MAX_BODY_LIMIT),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 선택적 파서 설정
+///
+/// Syslog, GELF, JSON 파서는 항상 등록됩니다. 여기의 파서는 켰을 때만 등록합니다.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParsersConfig {
+    /// IBM QRadar LEEF 1.0/2.0 파서 (syslog 헤더로 감싼 LEEF 포함, Syslog 파서보다 먼저 시도)
+    pub leef: bool,
+    /// 사용자 정의 grok 패턴 (이름 -> 정규식), `patterns`에서 `%{NAME}`으로 참조
+    ///
+    /// 같은 이름의 내장 패턴(`IP`, `WORD` 등)을 재정의합니다.
+    pub grok_definitions: BTreeMap<String, String>,
+    /// 사용자 정의 패턴 파서 (등록 순서대로, 다른 파서보다 먼저 시도)
+    pub patterns: Vec<PatternParserConfig>,
+}
+
+/// 파서 이름으로 사용할 수 없는 내장 형식 이름
+pub const BUILTIN_PARSER_NAMES: &[&str] = &["syslog", "gelf", "json", "leef"];
+
+/// 사용자 정의 패턴의 최대 길이
+pub const PATTERN_MAX_LENGTH: usize = 4096;
+
+/// 사용자 정의 패턴 파서 설정
+///
+/// `pattern`은 grok 문법(`%{IP:client}`)이나 이름 있는 그룹(`(?P<client>\S+)`)을 쓴
+/// 정규식입니다. 캡처 이름 `timestamp`, `host`, `process`, `message`, `level`은
+/// `LogEntry`의 같은 필드로, 나머지는 `fields`로 들어갑니다.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PatternParserConfig {
+    /// 파서 이름 (형식 이름, `LogEntry.source`로 기록)
+    pub name: String,
+    /// grok 패턴 또는 정규식
+    pub pattern: String,
+    /// `timestamp` 캡처의 chrono 형식 (비우면 RFC 3339와 Unix 시간 자동 감지)
+    pub timestamp_format: String,
+}
+
+impl ParsersConfig {
+    /// Validate parser definitions.
+    ///
+    /// 정규식 컴파일은 log-pipeline이 파서를 만들 때 검증합니다.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        for (name, definition) in &self.grok_definitions {
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(ConfigError::InvalidValue {
+                    field: "log_pipeline.parsers.grok_definitions".to_owned(),
+                    reason: format!("invalid pattern name '{}' (use A-Z, 0-9, _)", name),
+                }
+                .into());
+            }
+            if definition.is_empty() || definition.len() > PATTERN_MAX_LENGTH {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("log_pipeline.parsers.grok_definitions.{}", name),
+                    reason: format!("must be 1 to {} characters", PATTERN_MAX_LENGTH),
+                }
+                .into());
+            }
+        }
+
+        let mut names = std::collections::HashSet::new();
+        for pattern in &self.patterns {
+            if pattern.name.is_empty()
+                || !pattern
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: "log_pipeline.parsers.patterns.name".to_owned(),
+                    reason: format!(
+                        "invalid parser name '{}' (use a-z, 0-9, _, -)",
+                        pattern.name
+                    ),
+                }
+                .into());
+            }
+            if BUILTIN_PARSER_NAMES.contains(&pattern.name.as_str()) {
+                return Err(ConfigError::InvalidValue {
+                    field: "log_pipeline.parsers.patterns.name".to_owned(),
+                    reason: format!("'{}' is a built-in parser name", pattern.name),
+                }
+                .into());
+            }
+            if !names.insert(pattern.name.as_str()) {
+                return Err(ConfigError::InvalidValue {
+                    field: "log_pipeline.parsers.patterns.name".to_owned(),
+                    reason: format!("duplicate parser name '{}'", pattern.name),
+                }
+                .into());
+            }
+            if pattern.pattern.is_empty() || pattern.pattern.len() > PATTERN_MAX_LENGTH {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("log_pipeline.parsers.patterns.{}.pattern", pattern.name),
+                    reason: format!("must be 1 to {} characters", PATTERN_MAX_LENGTH),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `log_pipeline.file.read_from`에 허용되는 값
+pub const FILE_READ_FROM_VALUES: &[&str] = &["beginning", "end"];
+
+/// 파일 수집 소스 설정
+///
+/// 수집기는 파일별 읽기 위치(inode + 오프셋)를 `checkpoint_path`에 주기적으로 저장하고,
+/// 재시작하면 저장된 위치부터 이어 읽습니다. 체크포인트가 없는 파일을 처음 볼 때
+/// 어디서부터 읽을지는 `read_from`으로 정합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileSourceConfig {
+    /// 체크포인트 파일 경로 (비어 있으면 저장하지 않음)
+    pub checkpoint_path: String,
+    /// 체크포인트 저장 주기 (초)
+    pub checkpoint_interval_secs: u64,
+    /// 시작 시 체크포인트가 없는 파일의 읽기 시작 위치 ("beginning" | "end")
+    ///
+    /// 실행 중에 새로 생긴 파일은 항상 처음부터 읽습니다.
+    pub read_from: String,
+    /// 저장된 체크포인트를 무시하고 `read_from`에 따라 다시 읽을지 여부
+    pub ignore_checkpoints: bool,
+    /// 감시 경로에 잡힌 `.gz` 파일을 압축 해제하여 한 번 수집할지 여부
+    ///
+    /// 끄면 `.gz` 파일은 건너뜁니다. 끝까지 수집한 파일은 체크포인트에 기록되어 재시작 후에도
+    /// 다시 수집하지 않습니다. `app.log*`처럼 압축 전 파일과 함께 잡히는 패턴에서는 로테이션으로
+    /// 압축된 파일의 라인이 중복 수집되므로, 과거 로그 재처리용 경로를 따로 지정하세요.
+    pub replay_archives: bool,
+}
+
+impl Default for FileSourceConfig {
+    fn default() -> Self {
+        Self {
+            checkpoint_path: "/var/lib/ironpost/file-checkpoints.json".to_owned(),
+            checkpoint_interval_secs: 5,
+            read_from: "beginning".to_owned(),
+            ignore_checkpoints: false,
+            replay_archives: false,
+        }
+    }
+}
+
+impl FileSourceConfig {
+    /// Validate file source values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if !FILE_READ_FROM_VALUES.contains(&self.read_from.as_str()) {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.file.read_from".to_owned(),
+                reason: format!(
+                    "must be one of {:?}, got '{}'",
+                    FILE_READ_FROM_VALUES, self.read_from
+                ),
+            }
+            .into());
+        }
+        if self.checkpoint_interval_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "log_pipeline.file.checkpoint_interval_secs".to_owned(),
+                reason: "must be greater than 0".to_owned(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// 컨테이너 가드 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContainerConfig {
+    /// 활성화 여부
+    pub enabled: bool,
+    /// Docker 소켓 경로
+    pub docker_socket: String,
+    /// 모니터링 주기 (초)
+    pub poll_interval_secs: u64,
+    /// 격리 정책 파일 경로
+    pub policy_path: String,
+    /// 자동 격리 활성화
+    pub auto_isolate: bool,
+}
+
+impl Default for ContainerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            docker_socket: "/var/run/docker.sock".to_owned(),
+            poll_interval_secs: 10,
+            policy_path: "/etc/ironpost/policies".to_owned(),
+            auto_isolate: false,
+        }
+    }
+}
+
+impl ContainerConfig {
+    /// Validate container guard configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if self.poll_interval_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "container.poll_interval_secs".to_owned(),
+                reason: "must be greater than 0".to_owned(),
+            }
+            .into());
+        }
+        if self.poll_interval_secs > 3600 {
+            return Err(ConfigError::InvalidValue {
+                field: "container.poll_interval_secs".to_owned(),
+                reason: "must not exceed 3,600 seconds (1 hour)".to_owned(),
+            }
+            .into());
+        }
+        if self.docker_socket.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "container.docker_socket".to_owned(),
+                reason: "must not be empty".to_owned(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// SBOM 스캐너 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SbomConfig {
+    /// 활성화 여부
+    pub enabled: bool,
+    /// 스캔 대상 디렉토리
+    pub scan_dirs: Vec<String>,
+    /// 취약점 DB 업데이트 주기 (시간)
+    pub vuln_db_update_hours: u32,
+    /// 취약점 DB 경로
+    pub vuln_db_path: String,
+    /// 최소 심각도 알림 수준 (info, low, medium, high, critical)
+    pub min_severity: String,
+    /// SBOM 출력 형식 (spdx, cyclonedx)
+    pub output_format: String,
+}
+
+impl Default for SbomConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            scan_dirs: vec![".".to_owned()],
+            vuln_db_update_hours: 24,
+            vuln_db_path: "/var/lib/ironpost/vuln-db".to_owned(),
+            min_severity: "medium".to_owned(),
+            output_format: "cyclonedx".to_owned(),
+        }
+    }
+}
+
+impl SbomConfig {
+    /// Validate SBOM scanner configuration values.
+    pub fn validate(&self) -> Result<(), IronpostError> {
+        if self.vuln_db_update_hours == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "sbom.vuln_db_update_hours".to_owned(),
+                reason: "must be greater than 0".to_owned(),
+            }
+            .into());
+        }
+        if self.vuln_db_update_hours > 8760 {
+            return Err(ConfigError::InvalidValue {
+                field: "sbom.vuln_db_update_hours".to_owned(),
+                reason: "must not exceed 8,760 hours (1 year)".to_owned(),
+            }
+            .into());
+        }
+        if self.scan_dirs.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                field: "sbom.scan_dirs".to_owned(),
+                reason: "must have at least one directory".to_owned(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// `ebpf.interface`를 문자열 또는 문자열 배열로 받아 쉼표로 구분된 문자열로 정규화합니다.
+fn deserialize_interface<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(name) => name,
+        OneOrMany::Many(names) => names.join(","),
+    })
+}
+
+// --- 환경변수 오버라이드 헬퍼 ---
+
+fn override_string(target: &mut String, env_key: &str) {
+    if let Ok(val) = std::env::var(env_key) {
+        *target = val;
+    }
+}
+
+fn override_bool(target: &mut bool, env_key: &str) {
+    if let Ok(val) = std::env::var(env_key) {
+        match val.parse::<bool>() {
+            Ok(parsed) => *target = parsed,
+            Err(_) => warn!(
+                env_key,
+                value = val.as_str(),
+                "failed to parse bool from env var, ignoring"
+            ),
+        }
+    }
+}
+
+fn override_usize(target: &mut usize, env_key: &str) {
+    if let Ok(val) = std::env::var(env_key) {
+        match val.parse::<usize>() {
+            Ok(parsed) => *target = parsed,
+            Err(_) => warn!(
+                env_key,
+                value = val.as_str(),
+                "failed to parse usize from env var, ignoring"
+            ),
+        }
+    }
+}
+
+fn override_u32(target: &mut u32, env_key: &str) {
+    if let Ok(val) = std::env::var(env_key) {
+        match val.parse::<u32>() {
+            Ok(parsed) => *target = parsed,
+            Err(_) => warn!(
+                env_key,
+                value = val.as_str(),
+                "failed to parse u32 from env var, ignoring"
+            ),
+        }
+    }
+}
+
+fn override_u64(target: &mut u64, env_key: &str) {
+    if let Ok(val) = std::env::var(env_key) {
+        match val.parse::<u64>() {
+            Ok(parsed) => *target = parsed,
+            Err(_) => warn!(
+                env_key,
+                value = val.as_str(),
+                "failed to parse u64 from env var, ignoring"
+            ),
+        }
+    }
+}
+
+fn override_u16(target: &mut u16, env_key: &str) {
+    if let Ok(val) = std::env::var(env_key) {
+        match val.parse::<u16>() {
+            Ok(parsed) => *target = parsed,
+            Err(_) => warn!(
+                env_key,
+                value = val.as_str(),
+                "failed to parse u16 from env var, ignoring"
+            ),
+        }
+    }
+}
+
+fn override_csv(target: &mut Vec<String>, env_key: &str) {
+    if let Ok(val) = std::env::var(env_key) {
+        *target = val.split(',').map(|s| s.trim().to_owned()).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    fn default_config_has_sane_values() {
+        let config = IronpostConfig::default();
+        assert_eq!(config.general.log_level, "info");
+        assert_eq!(config.general.log_format, "json");
+        assert!(!config.ebpf.enabled);
+        assert_eq!(config.ebpf.interface, "eth0");
+        assert!(config.log_pipeline.enabled);
+        assert!(!config.container.enabled);
+        assert!(!config.sbom.enabled);
+        assert!(!config.general.dry_run);
+    }
+
+    #[test]
+    fn default_config_passes_validation() {
+        let config = IronpostConfig::default();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn from_str_empty_toml_uses_defaults() {
+        let config = IronpostConfig::parse("").unwrap();
+        assert_eq!(config.general.log_level, "info");
+        assert_eq!(config.ebpf.interface, "eth0");
+    }
+
+    #[test]
+    fn from_str_partial_toml_merges_with_defaults() {
+        let toml = r#"
+[general]
+log_level = "debug"
+
+[ebpf]
+enabled = true
+interface = "ens3"
+"#;
+        let config = IronpostConfig::parse(toml).unwrap();
+        assert_eq!(config.general.log_level, "debug");
+        // log_format은 기본값 유지
+        assert_eq!(config.general.log_format, "json");
+        assert!(config.ebpf.enabled);
+        assert_eq!(config.ebpf.interface, "ens3");
+    }
+
+    #[test]
+    fn from_str_full_toml() {
+        let toml = r#"
+[general]
+log_level = "warn"
+log_format = "pretty"
+data_dir = "/opt/ironpost/data"
+pid_file = "/opt/ironpost/ironpost.pid"
+
+[ebpf]
+enabled = true
+interface = "ens3"
+xdp_mode = "native"
+ring_buffer_size = 524288
+blocklist_max_entries = 50000
+rate_limit_pps = 1000
+
+[log_pipeline]
+enabled = true
+sources = ["syslog", "file", "journald"]
+syslog_bind = "127.0.0.1:5140"
+watch_paths = ["/var/log/auth.log", "/var/log/kern.log"]
+batch_size = 200
+flush_interval_secs = 10
+
+[log_pipeline.storage]
+postgres_url = "postgresql://db:5432/ironpost"
+redis_url = "redis://cache:6379"
+retention_days = 90
+
+[container]
+enabled = true
+docker_socket = "/run/docker.sock"
+poll_interval_secs = 5
+policy_path = "/etc/ironpost/container-policies"
+auto_isolate = true
+
+[sbom]
+enabled = true
+scan_dirs = ["/app", "/opt"]
+vuln_db_update_hours = 12
+vuln_db_path = "/opt/ironpost/vuln-db"
+min_severity = "high"
+output_format = "spdx"
+"#;
+        let config = IronpostConfig::parse(toml).unwrap();
+        assert_eq!(config.general.log_level, "warn");
+        assert_eq!(config.ebpf.ring_buffer_size, 524288);
+        assert_eq!(config.ebpf.rate_limit_pps, 1000);
+        assert_eq!(config.ebpf.rate_limit_burst, 0);
+        assert_eq!(config.log_pipeline.sources.len(), 3);
+        assert_eq!(config.log_pipeline.storage.retention_days, 90);
+        assert!(config.container.auto_isolate);
+        assert_eq!(config.sbom.output_format, "spdx");
+    }
+
+    #[test]
+    fn from_str_invalid_toml_returns_error() {
+        let result = IronpostConfig::parse("invalid = [[[toml");
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(matches!(
+            err,
+            IronpostError::Config(ConfigError::ParseFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_log_level() {
+        let mut config = IronpostConfig::default();
+        config.general.log_level = "verbose".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("log_level"));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_log_format() {
+        let mut config = IronpostConfig::default();
+        config.general.log_format = "xml".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("log_format"));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_xdp_mode_when_enabled() {
+        let mut config = IronpostConfig::default();
+        config.ebpf.enabled = true;
+        config.ebpf.xdp_mode = "turbo".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("xdp_mode"));
+    }
+
+    #[test]
+    fn validate_accepts_invalid_xdp_mode_when_disabled() {
+        let mut config = IronpostConfig::default();
+        config.ebpf.enabled = false;
+        config.ebpf.xdp_mode = "turbo".to_owned();
+        // ebpf가 비활성화 상태면 xdp_mode 검증을 건너뜀
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn validate_rejects_empty_interface_when_enabled() {
+        let mut config = IronpostConfig::default();
+        config.ebpf.enabled = true;
+        config.ebpf.interface = String::new();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("interface"));
+
+        config.ebpf.interface = " , ".to_owned();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn ebpf_interface_accepts_list_and_all() {
+        let config = IronpostConfig::parse(
+            r#"
+[ebpf]
+enabled = true
+interface = ["eth0", "eth1", "eth0"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.ebpf.interface, "eth0,eth1,eth0");
+        assert_eq!(config.ebpf.interfaces(), vec!["eth0", "eth1"]);
+        assert!(!config.ebpf.attaches_all_interfaces());
+        config.validate().unwrap();
+
+        let mut config = IronpostConfig::default();
+        config.ebpf.enabled = true;
+        config.ebpf.interface = " eth0 , ens3 ".to_owned();
+        assert_eq!(config.ebpf.interfaces(), vec!["eth0", "ens3"]);
+
+        config.ebpf.interface = "ALL".to_owned();
+        assert!(config.ebpf.attaches_all_interfaces());
+        config.validate().unwrap();
+
+        config.ebpf.interface = "all,eth0".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("cannot be combined"));
+
+        config.ebpf.interface = " Auto ".to_owned();
+        assert!(config.ebpf.uses_default_route_interface());
+        assert!(!config.ebpf.attaches_all_interfaces());
+        config.validate().unwrap();
+
+        config.ebpf.interface = "eth0,auto".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("'auto' cannot be combined"));
+    }
+
+    #[test]
+    fn validate_rejects_traffic_alert_drop_percent_over_100() {
+        let mut config = IronpostConfig::default();
+        config.ebpf.enabled = true;
+        config.ebpf.traffic_alert_drop_percent = 101;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("traffic_alert_drop_percent"));
+
+        config.ebpf.traffic_alert_drop_percent = 100;
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn validate_rejects_zero_monitor_sample_rate_when_enabled() {
+        let mut config = IronpostConfig::default();
+        assert_eq!(config.ebpf.monitor_sample_rate, 1);
+        config.ebpf.enabled = true;
+        config.ebpf.monitor_sample_rate = 0;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("monitor_sample_rate"));
+
+        config.ebpf.monitor_sample_rate = 100;
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn validate_rejects_invalid_ring_buffer_size_when_enabled() {
+        let mut config = IronpostConfig::default();
+        config.ebpf.enabled = true;
+        for size in [0, 2048, 300_000, 2 * EBPF_RING_BUFFER_MAX_SIZE] {
+            config.ebpf.ring_buffer_size = size;
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains("ring_buffer_size"), "size {size}");
+        }
+
+        config.ebpf.ring_buffer_size = 4 * 1024 * 1024;
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn validate_rejects_relative_map_pin_path_when_enabled() {
+        let mut config = IronpostConfig::default();
+        config.ebpf.enabled = true;
+        config.ebpf.map_pin_path = "bpf/ironpost".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("map_pin_path"));
+    }
+
+    #[test]
+    fn validate_log_pipeline_sinks() {
+        let mut config = IronpostConfig::parse(
+            r#"
+[[log_pipeline.sinks]]
+name = "slack"
+url = "https://hooks.slack.com/services/T000/B000/XXXX"
+min_severity = "high"
+template = '{"text": "[{{severity}}] {{title}}"}'
+
+[[log_pipeline.sinks]]
+name = "soar"
+type = "webhook"
+url = "http://soar.internal:8080/alerts"
+method = "PUT"
+headers = { Authorization = "Bearer token" }
+
+[[log_pipeline.sinks]]
+name = "auth-team"
+type = "discord"
+url = "https://discord.com/api/webhooks/1/abc"
+rule_tags = ["auth"]
+"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        let sinks = &config.log_pipeline.sinks;
+        assert_eq!(sinks[0].kind, "webhook");
+        assert_eq!(sinks[0].method, "POST");
+        assert_eq!(sinks[0].max_retries, 3);
+        assert_eq!(sinks[1].headers["Authorization"], "Bearer token");
+        assert_eq!(sinks[2].rule_tags, ["auth"]);
+
+        config.log_pipeline.sinks[1].name = "slack".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("duplicate sink name"));
+        config.log_pipeline.sinks[1].name = "soar".to_owned();
+
+        type BreakSink = fn(&mut AlertSinkConfig);
+        let cases: [(&str, BreakSink); 8] = [
+            ("type", |s| s.kind = "sms".to_owned()),
+            ("url", |s| s.url = "ftp://example.com".to_owned()),
+            ("method", |s| s.method = "GET".to_owned()),
+            ("min_severity", |s| s.min_severity = "urgent".to_owned()),
+            ("rule_tags", |s| s.rule_tags = vec![" ".to_owned()]),
+            ("timeout_secs", |s| s.timeout_secs = 0),
+            ("max_retries", |s| s.max_retries = 11),
+            ("queue_size", |s| s.queue_size = 0),
+        ];
+        for (field, break_sink) in cases {
+            let mut broken = config.clone();
+            break_sink(&mut broken.log_pipeline.sinks[1]);
+            let err = broken.validate().unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains(&format!("log_pipeline.sinks[1].{}", field)),
+                "{err}"
+            );
+        }
+
+        // 채팅 싱크는 POST만, 템플릿 사용 불가
+        let mut chat = config.clone();
+        chat.log_pipeline.sinks[2].method = "PUT".to_owned();
+        assert!(chat.validate().is_err());
+        let mut chat = config.clone();
+        chat.log_pipeline.sinks[2].template = r#"{"text": "{{title}}"}"#.to_owned();
+        let err = chat.validate().unwrap_err();
+        assert!(err.to_string().contains("sinks[2].template"), "{err}");
+    }
+
+    #[test]
+    fn validate_email_sink_requires_smtp() {
+        let mut config = IronpostConfig::parse(
+            r#"
+[log_pipeline.smtp]
+host = "smtp.example.com"
+username = "ironpost"
+password = "secret"
+from = "Ironpost <ironpost@example.com>"
+
+[[log_pipeline.sinks]]
+name = "soc-mail"
+type = "email"
+to = ["soc@example.com"]
+min_severity = "medium"
+"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        let sink = &config.log_pipeline.sinks[0];
+        assert_eq!(sink.immediate_severity, "critical");
+        assert_eq!(sink.digest_interval_secs, 3600);
+        assert_eq!(config.log_pipeline.smtp.port, 587);
+        assert_eq!(config.log_pipeline.smtp.tls, "starttls");
+
+        type BreakConfig = fn(&mut IronpostConfig);
+        let cases: [(&str, BreakConfig); 7] = [
+            ("sinks[0].to", |c| c.log_pipeline.sinks[0].to.clear()),
+            ("sinks[0].to", |c| {
+                c.log_pipeline.sinks[0].to = vec!["soc@example.com\r\nBcc: x@evil".to_owned()]
+            }),
+            ("sinks[0].digest_max_alerts", |c| {
+                c.log_pipeline.sinks[0].digest_max_alerts = 0
+            }),
+            ("smtp.host", |c| c.log_pipeline.smtp.host.clear()),
+            ("smtp.tls", |c| c.log_pipeline.smtp.tls = "ssl".to_owned()),
+            ("smtp.password", |c| c.log_pipeline.smtp.password.clear()),
+            ("smtp.from", |c| {
+                c.log_pipeline.smtp.from = "ironpost".to_owned()
+            }),
+        ];
+        for (field, break_config) in cases {
+            let mut broken = config.clone();
+            break_config(&mut broken);
+            let err = broken.validate().unwrap_err();
+            assert!(err.to_string().contains(field), "{field}: {err}");
+        }
+
+        // 이메일 싱크가 없으면 SMTP 설정을 검증하지 않음
+        config.log_pipeline.sinks.clear();
+        config.log_pipeline.smtp.host.clear();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn validate_file_sink() {
+        let config = IronpostConfig::parse(
+            r#"
+[[log_pipeline.sinks]]
+name = "archive"
+type = "file"
+path = "/var/lib/ironpost/alerts.jsonl"
+
+[[log_pipeline.sinks]]
+name = "archive-critical"
+type = "file"
+path = "/var/lib/ironpost/critical.jsonl"
+min_severity = "critical"
+max_files = 0
+"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        let sink = &config.log_pipeline.sinks[0];
+        assert_eq!(sink.max_file_bytes, 100 * 1024 * 1024);
+        assert_eq!(sink.rotate_interval_secs, 86_400);
+        assert_eq!(sink.max_files, 30);
+
+        type BreakConfig = fn(&mut IronpostConfig);
+        let cases: [(&str, BreakConfig); 4] = [
+            ("sinks[0].path", |c| c.log_pipeline.sinks[0].path.clear()),
+            ("sinks[1].path", |c| {
+                c.log_pipeline.sinks[1].path = "/var/lib/ironpost/alerts.jsonl".to_owned()
+            }),
+            ("sinks[0].rotate_interval_secs", |c| {
+                c.log_pipeline.sinks[0].rotate_interval_secs = 1
+            }),
+            ("sinks[0].max_files", |c| {
+                c.log_pipeline.sinks[0].max_files = ALERT_SINK_MAX_FILES + 1
+            }),
+        ];
+        for (field, break_config) in cases {
+            let mut broken = config.clone();
+            break_config(&mut broken);
+            let err = broken.validate().unwrap_err();
+            assert!(err.to_string().contains(field), "{field}: {err}");
+        }
+    }
+
+    #[test]
+    fn validate_elasticsearch_output_when_enabled() {
+        let mut config = IronpostConfig::parse(
+            r#"
+[log_pipeline.elasticsearch]
+enabled = true
+url = "https://es.example.com:9200"
+api_key = "key"
+log_index = "logs-{source}-%Y.%m.%d"
+"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        let es = &config.log_pipeline.elasticsearch;
+        assert_eq!(es.alert_index, "ironpost-alerts-%Y.%m.%d");
+        assert_eq!(es.batch_size, 500);
+        assert!(es.index_logs && es.index_alerts);
+
+        type BreakConfig = fn(&mut IronpostConfig);
+        let cases: [(&str, BreakConfig); 8] = [
+            ("elasticsearch.url", |c| {
+                c.log_pipeline.elasticsearch.url = "es:9200".to_owned()
+            }),
+            ("elasticsearch.api_key", |c| {
+                c.log_pipeline.elasticsearch.username = "elastic".to_owned();
+                c.log_pipeline.elasticsearch.password = "changeme".to_owned();
+            }),
+            ("elasticsearch.log_index", |c| {
+                c.log_pipeline.elasticsearch.log_index = "Logs-%Y".to_owned()
+            }),
+            ("elasticsearch.log_index", |c| {
+                c.log_pipeline.elasticsearch.log_index = "logs-%s".to_owned()
+            }),
+            ("elasticsearch.alert_index", |c| {
+                c.log_pipeline.elasticsearch.alert_index = "alerts-{rule}".to_owned()
+            }),
+            ("elasticsearch.alert_index", |c| {
+                c.log_pipeline.elasticsearch.alert_index = "_alerts".to_owned()
+            }),
+            ("elasticsearch.index_logs", |c| {
+                c.log_pipeline.elasticsearch.index_logs = false;
+                c.log_pipeline.elasticsearch.index_alerts = false;
+            }),
+            ("elasticsearch.batch_size", |c| {
+                c.log_pipeline.elasticsearch.batch_size = 0
+            }),
+        ];
+        for (field, break_config) in cases {
+            let mut broken = config.clone();
+            break_config(&mut broken);
+            let err = broken.validate().unwrap_err();
+            assert!(err.to_string().contains(field), "{field}: {err}");
+        }
+
+        // 비활성화 상태에서는 검증하지 않음
+        config.log_pipeline.elasticsearch.enabled = false;
+        config.log_pipeline.elasticsearch.url.clear();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn validate_threat_feeds_when_enabled() {
+        let mut config = IronpostConfig::parse(
+            r#"
+[ebpf]
+enabled = true
+
+[[ebpf.threat_feeds]]
+name = "spamhaus-drop"
+url = "https://www.spamhaus.org/drop/drop.txt"
+
+[[ebpf.threat_feeds]]
+name = "misp"
+url = "file:///var/lib/ironpost/misp.json"
+format = "stix"
+refresh_secs = 600
+"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.ebpf.threat_feeds[0].format, "plain");
+        assert_eq!(config.ebpf.threat_feeds[0].refresh_secs, 3600);
+
+        config.ebpf.threat_feeds[1].name = "spamhaus-drop".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("duplicate feed name"));
+
+        config.ebpf.threat_feeds[1].name = "bad name".to_owned();
+        assert!(config.validate().is_err());
+
+        config.ebpf.threat_feeds[1].name = "misp".to_owned();
+        config.ebpf.threat_feeds[1].format = "csv".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("threat_feeds[1].format"));
+
+        config.ebpf.threat_feeds[1].format = "stix".to_owned();
+        config.ebpf.threat_feeds[1].url = "ftp://example.com/feed".to_owned();
+        assert!(config.validate().is_err());
+
+        config.ebpf.threat_feeds[1].url = "https://example.com/feed".to_owned();
+        config.ebpf.threat_feeds[1].refresh_secs = 10;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("refresh_secs"));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_sbom_format_when_enabled() {
+        let mut config = IronpostConfig::default();
+        config.sbom.enabled = true;
+        config.sbom.output_format = "xml".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("output_format"));
+    }
+
+    #[test]
+    #[serial]
+    fn env_override_string() {
+        let mut val = "original".to_owned();
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("TEST_IRONPOST_STR", "overridden") };
+        override_string(&mut val, "TEST_IRONPOST_STR");
+        assert_eq!(val, "overridden");
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("TEST_IRONPOST_STR") };
+    }
+
+    #[test]
+    #[serial]
+    fn env_override_bool_valid() {
+        let mut val = false;
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("TEST_IRONPOST_BOOL", "true") };
+        override_bool(&mut val, "TEST_IRONPOST_BOOL");
+        assert!(val);
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("TEST_IRONPOST_BOOL") };
+    }
+
+    #[test]
+    #[serial]
+    fn env_override_bool_invalid_keeps_original() {
+        let mut val = false;
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("TEST_IRONPOST_BOOL_BAD", "not-a-bool") };
+        override_bool(&mut val, "TEST_IRONPOST_BOOL_BAD");
+        assert!(!val); // 원래 값 유지
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("TEST_IRONPOST_BOOL_BAD") };
+    }
+
+    #[test]
+    #[serial]
+    fn env_override_csv() {
+        let mut val = vec!["a".to_owned()];
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("TEST_IRONPOST_CSV", "x, y, z") };
+        override_csv(&mut val, "TEST_IRONPOST_CSV");
+        assert_eq!(val, vec!["x", "y", "z"]);
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("TEST_IRONPOST_CSV") };
+    }
+
+    #[test]
+    #[serial]
+    fn general_env_override_dry_run() {
+        let mut config = IronpostConfig::default();
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("IRONPOST_GENERAL_DRY_RUN", "true") };
+        config.apply_env_overrides();
+        assert!(config.general.dry_run);
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("IRONPOST_GENERAL_DRY_RUN") };
+    }
+
+    #[test]
+    fn ebpf_env_override_observe_only() {
+        let mut config = IronpostConfig::default();
+        assert!(!config.ebpf.observe_only);
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("IRONPOST_EBPF_OBSERVE_ONLY", "true") };
+        config.apply_env_overrides();
+        assert!(config.ebpf.observe_only);
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("IRONPOST_EBPF_OBSERVE_ONLY") };
+    }
+
+    #[test]
+    fn env_override_missing_var_keeps_original() {
+        let mut val = "original".to_owned();
+        override_string(&mut val, "TEST_IRONPOST_NONEXISTENT_12345");
+        assert_eq!(val, "original");
+    }
+
+    #[test]
+    fn config_serialize_roundtrip() {
+        let config = IronpostConfig::default();
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let parsed = IronpostConfig::parse(&toml_str).unwrap();
+        assert_eq!(config.general.log_level, parsed.general.log_level);
+        assert_eq!(config.ebpf.interface, parsed.ebpf.interface);
+        assert_eq!(
+            config.log_pipeline.storage.retention_days,
+            parsed.log_pipeline.storage.retention_days
+        );
+    }
+
+    #[tokio::test]
+    async fn from_file_not_found() {
+        let result = IronpostConfig::from_file("/nonexistent/path/ironpost.toml").await;
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(matches!(
+            err,
+            IronpostError::Config(ConfigError::FileNotFound { .. })
+        ));
+    }
+
+    // ─── MetricsConfig tests ───────────────────────────────────────────
+
+    #[test]
+    fn metrics_config_default() {
+        let config = MetricsConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.listen_addr, "127.0.0.1");
+        assert_eq!(config.port, 9100);
+        assert_eq!(config.endpoint, "/metrics");
+    }
+
+    #[test]
+    fn metrics_config_default_passes_validation() {
+        let config = MetricsConfig::default();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn config_without_metrics_section_uses_defaults() {
+        let toml = r#"
+[general]
+log_level = "info"
+"#;
+        let config = IronpostConfig::parse(toml).unwrap();
+        assert!(config.metrics.enabled);
+        assert_eq!(config.metrics.port, 9100);
+        assert_eq!(config.metrics.endpoint, "/metrics");
+    }
+
+    #[test]
+    fn config_with_metrics_section() {
+        let toml = r#"
+[metrics]
+enabled = false
+listen_addr = "127.0.0.1"
+port = 9101
+endpoint = "/prometheus"
+"#;
+        let config = IronpostConfig::parse(toml).unwrap();
+        assert!(!config.metrics.enabled);
+        assert_eq!(config.metrics.listen_addr, "127.0.0.1");
+        assert_eq!(config.metrics.port, 9101);
+        assert_eq!(config.metrics.endpoint, "/prometheus");
+    }
+
+    #[test]
+    fn metrics_config_validate_rejects_zero_port() {
+        let config = MetricsConfig {
+            port: 0,
+            ..MetricsConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("metrics.port"));
+    }
+
+    #[test]
+    fn metrics_config_validate_rejects_empty_listen_addr() {
+        let config = MetricsConfig {
+            listen_addr: String::new(),
+            ..MetricsConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("metrics.listen_addr"));
+    }
+
+    #[test]
+    fn metrics_config_validate_rejects_endpoint_without_slash() {
+        let config = MetricsConfig {
+            endpoint: "metrics".to_owned(),
+            ..MetricsConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("metrics.endpoint"));
+        assert!(err.to_string().contains("start with '/'"));
+    }
+
+    #[test]
+    fn metrics_config_validate_rejects_non_default_endpoint() {
+        let config = MetricsConfig {
+            endpoint: "/prometheus/metrics".to_owned(),
+            ..MetricsConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("metrics.endpoint"));
+        assert!(err.to_string().contains("only '/metrics'"));
+    }
+
+    #[test]
+    fn ironpost_config_rejects_non_default_metrics_endpoint_when_enabled() {
+        let mut config = IronpostConfig::default();
+        config.metrics.enabled = true;
+        config.metrics.endpoint = "/custom".to_owned();
+
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("metrics.endpoint"));
+        assert!(err.to_string().contains("only '/metrics'"));
+    }
+
+    #[test]
+    #[serial]
+    fn metrics_env_override_enabled() {
+        let mut config = IronpostConfig::default();
+        // SAFETY: テスト用の環境変数設定
+        unsafe { std::env::set_var("IRONPOST_METRICS_ENABLED", "false") };
+        config.apply_env_overrides();
+        assert!(!config.metrics.enabled);
+        // SAFETY: クリーンアップ
+        unsafe { std::env::remove_var("IRONPOST_METRICS_ENABLED") };
+    }
+
+    #[test]
+    #[serial]
+    fn metrics_env_override_listen_addr() {
+        let mut config = IronpostConfig::default();
+        // SAFETY: テスト用の環境変数設定
+        unsafe { std::env::set_var("IRONPOST_METRICS_LISTEN_ADDR", "127.0.0.1") };
+        config.apply_env_overrides();
+        assert_eq!(config.metrics.listen_addr, "127.0.0.1");
+        // SAFETY: クリーンアップ
+        unsafe { std::env::remove_var("IRONPOST_METRICS_LISTEN_ADDR") };
+    }
+
+    #[test]
+    #[serial]
+    fn metrics_env_override_port() {
+        let mut config = IronpostConfig::default();
+        // SAFETY: テスト用の環境変数設定
+        unsafe { std::env::set_var("IRONPOST_METRICS_PORT", "9999") };
+        config.apply_env_overrides();
+        assert_eq!(config.metrics.port, 9999);
+        // SAFETY: クリーンアップ
+        unsafe { std::env::remove_var("IRONPOST_METRICS_PORT") };
+    }
+
+    #[test]
+    #[serial]
+    fn metrics_env_override_endpoint() {
+        let mut config = IronpostConfig::default();
+        // SAFETY: テスト用の環境変数設定
+        unsafe { std::env::set_var("IRONPOST_METRICS_ENDPOINT", "/custom") };
+        config.apply_env_overrides();
+        assert_eq!(config.metrics.endpoint, "/custom");
+        // SAFETY: クリーンアップ
+        unsafe { std::env::remove_var("IRONPOST_METRICS_ENDPOINT") };
+    }
+
+    #[test]
+    #[serial]
+    fn metrics_env_override_port_invalid_keeps_original() {
+        let mut config = IronpostConfig::default();
+        let original_port = config.metrics.port;
+        // SAFETY: テスト用の環境変数設定
+        unsafe { std::env::set_var("IRONPOST_METRICS_PORT", "not-a-number") };
+        config.apply_env_overrides();
+        assert_eq!(config.metrics.port, original_port);
+        // SAFETY: クリーンアップ
+        unsafe { std::env::remove_var("IRONPOST_METRICS_PORT") };
+    }
+
+    #[test]
+    fn ironpost_config_validates_metrics_when_enabled() {
+        let mut config = IronpostConfig::default();
+        config.metrics.enabled = true;
+        config.metrics.port = 0; // Invalid
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("metrics.port"));
+    }
+
+    #[test]
+    fn ironpost_config_skips_metrics_validation_when_disabled() {
+        let mut config = IronpostConfig::default();
+        config.metrics.enabled = false;
+        config.metrics.port = 0; // Invalid, but should be ignored
+        config.validate().unwrap(); // Should pass
+    }
+
+    // ─── HealthConfig tests ────────────────────────────────────────────
+
+    #[test]
+    fn health_config_default() {
+        let config = HealthConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.listen_addr, "127.0.0.1");
+        assert_eq!(config.port, 9101);
+        assert_eq!(config.check_interval_secs, 5);
+        assert!(config.readiness_required.is_empty());
+        assert_eq!(config.readiness_tolerate_degraded, vec!["sbom-scanner"]);
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn config_with_health_section() {
+        let toml = r#"
+[health]
+port = 9200
+readiness_required = ["log-pipeline", "ebpf-engine"]
+readiness_tolerate_degraded = []
+liveness_fail_on_unhealthy = true
+"#;
+        let config = IronpostConfig::parse(toml).unwrap();
+        assert_eq!(config.health.port, 9200);
+        assert_eq!(
+            config.health.readiness_required,
+            vec!["log-pipeline", "ebpf-engine"]
+        );
+        assert!(config.health.readiness_tolerate_degraded.is_empty());
+        assert!(config.health.liveness_fail_on_unhealthy);
+    }
+
+    #[test]
+    fn health_config_validate_rejects_zero_interval() {
+        let config = HealthConfig {
+            check_interval_secs: 0,
+            ..HealthConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("health.check_interval_secs"));
+    }
+
+    #[test]
+    fn health_config_validate_requires_absolute_unix_socket() {
+        let mut config = HealthConfig {
+            unix_socket: "ironpost.sock".to_owned(),
+            ..HealthConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("health.unix_socket"));
+
+        config.unix_socket = "/run/ironpost/control.sock".to_owned();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn ironpost_config_rejects_health_port_conflicting_with_metrics() {
+        let mut config = IronpostConfig::default();
+        config.health.port = config.metrics.port;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("health.port"));
+    }
+
+    #[test]
+    #[serial]
+    fn health_env_override_readiness_required() {
+        let mut config = IronpostConfig::default();
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("IRONPOST_HEALTH_READINESS_REQUIRED", "log-pipeline") };
+        config.apply_env_overrides();
+        assert_eq!(config.health.readiness_required, vec!["log-pipeline"]);
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("IRONPOST_HEALTH_READINESS_REQUIRED") };
+    }
+
+    // ─── ShutdownConfig tests ──────────────────────────────────────────
+
+    #[test]
+    fn shutdown_config_default_is_valid() {
+        let config = ShutdownConfig::default();
+        assert_eq!(config.stop_producers_timeout_secs, 10);
+        assert_eq!(config.drain_timeout_secs, 10);
+        assert_eq!(config.stop_consumers_timeout_secs, 10);
+        assert_eq!(config.flush_timeout_secs, 5);
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn shutdown_config_rejects_zero_drain_timeout() {
+        let mut config = IronpostConfig::default();
+        config.shutdown.drain_timeout_secs = 0;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("shutdown.drain_timeout_secs"));
+    }
+
+    #[test]
+    fn shutdown_config_rejects_excessive_timeout() {
+        let config = ShutdownConfig {
+            flush_timeout_secs: 301,
+            ..ShutdownConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("shutdown.flush_timeout_secs"));
+    }
+
+    #[test]
+    #[serial]
+    fn shutdown_env_override_drain_timeout() {
+        let mut config = IronpostConfig::default();
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("IRONPOST_SHUTDOWN_DRAIN_TIMEOUT_SECS", "42") };
+        config.apply_env_overrides();
+        assert_eq!(config.shutdown.drain_timeout_secs, 42);
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("IRONPOST_SHUTDOWN_DRAIN_TIMEOUT_SECS") };
+    }
+
+    // ─── SupervisorConfig tests ────────────────────────────────────────
+
+    #[test]
+    fn supervisor_config_default_is_valid() {
+        let config = SupervisorConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.max_failures, 3);
+        assert_eq!(config.failure_window_secs, 300);
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn supervisor_config_rejects_zero_max_failures() {
+        let mut config = IronpostConfig::default();
+        config.supervisor.max_failures = 0;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("supervisor.max_failures"));
+    }
+
+    #[test]
+    fn supervisor_config_skips_validation_when_disabled() {
+        let mut config = IronpostConfig::default();
+        config.supervisor.enabled = false;
+        config.supervisor.max_failures = 0;
+        config.validate().unwrap();
+    }
+
+    // ─── SchedulerConfig tests ─────────────────────────────────────────
+
+    fn scheduled_task(name: &str, kind: &str, schedule: &str) -> ScheduledTaskConfig {
+        ScheduledTaskConfig {
+            name: name.to_owned(),
+            kind: kind.to_owned(),
+            schedule: schedule.to_owned(),
+            ..ScheduledTaskConfig::default()
+        }
+    }
+
+    #[test]
+    fn scheduler_config_parses_task_tables() {
+        let toml_str = r#"
+[scheduler]
+enabled = true
+
+[[scheduler.tasks]]
+name = "nightly-scan"
+kind = "sbom_scan"
+schedule = "0 3 * * *"
+
+[[scheduler.tasks]]
+name = "weekly-report"
+kind = "report"
+schedule = "@weekly"
+output_path = "/var/lib/ironpost/report.json"
+"#;
+        let config = IronpostConfig::parse(toml_str).unwrap();
+        assert_eq!(config.scheduler.tasks.len(), 2);
+        assert!(config.scheduler.tasks[0].enabled);
+        assert_eq!(config.scheduler.tasks[1].kind, "report");
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn scheduler_config_rejects_unknown_kind() {
+        let mut config = IronpostConfig::default();
+        config
+            .scheduler
+            .tasks
+            .push(scheduled_task("cleanup", "log_rotate", "@daily"));
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("scheduler.tasks.cleanup.kind"));
+    }
+
+    #[test]
+    fn scheduler_config_rejects_duplicate_names() {
+        let mut config = IronpostConfig::default();
+        config
+            .scheduler
+            .tasks
+            .push(scheduled_task("scan", "sbom_scan", "@hourly"));
+        config
+            .scheduler
+            .tasks
+            .push(scheduled_task("scan", "vuln_db_check", "@daily"));
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("duplicate task name"));
+    }
+
+    #[test]
+    fn scheduler_config_rejects_malformed_schedule() {
+        let mut config = IronpostConfig::default();
+        config
+            .scheduler
+            .tasks
+            .push(scheduled_task("scan", "sbom_scan", "0 3 * *"));
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("scheduler.tasks.scan.schedule"));
+    }
+
+    #[test]
+    fn scheduler_config_requires_report_output_path() {
+        let mut config = IronpostConfig::default();
+        config
+            .scheduler
+            .tasks
+            .push(scheduled_task("report", "report", "@daily"));
+        let err = config.validate().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("scheduler.tasks.report.output_path")
+        );
+    }
+
+    // ─── HaConfig tests ────────────────────────────────────────────────
+
+    #[test]
+    fn ha_config_default_is_disabled_and_valid() {
+        let config = HaConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.backend, "file");
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn ha_config_rejects_unsupported_backend() {
+        let mut config = IronpostConfig::default();
+        config.ha.enabled = true;
+        config.ha.backend = "etcd".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("ha.backend"));
+    }
+
+    #[test]
+    fn ha_config_rejects_empty_lock_path() {
+        let mut config = IronpostConfig::default();
+        config.ha.enabled = true;
+        config.ha.lock_path = String::new();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("ha.lock_path"));
+    }
+
+    #[test]
+    #[serial]
+    fn ha_env_override_node_id() {
+        let mut config = IronpostConfig::default();
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("IRONPOST_HA_NODE_ID", "node-b") };
+        config.apply_env_overrides();
+        assert_eq!(config.ha.node_id, "node-b");
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("IRONPOST_HA_NODE_ID") };
+    }
+
+    // ─── RemoteConfigSource tests ──────────────────────────────────────
+
+    #[test]
+    fn remote_config_default_is_disabled() {
+        let config = IronpostConfig::default();
+        assert!(!config.remote_config.enabled);
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn remote_config_rejects_unsupported_scheme() {
+        let mut config = IronpostConfig::default();
+        config.remote_config.enabled = true;
+        config.remote_config.url = "ftp://config.example.com/ironpost.toml".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("remote_config.url"));
+    }
+
+    #[test]
+    fn remote_config_accepts_s3_and_bundles() {
+        let toml_str = r#"
+[remote_config]
+enabled = true
+url = "s3://fleet-config/ironpost/ironpost.toml"
+refresh_interval_secs = 600
+
+[[remote_config.bundles]]
+url = "https://config.example.com/rules/ssh.yaml"
+path = "/etc/ironpost/rules/ssh.yaml"
+"#;
+        let config = IronpostConfig::parse(toml_str).unwrap();
+        assert_eq!(config.remote_config.bundles.len(), 1);
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn remote_config_rejects_short_refresh_interval() {
+        let mut config = IronpostConfig::default();
+        config.remote_config.enabled = true;
+        config.remote_config.url = "https://config.example.com/ironpost.toml".to_owned();
+        config.remote_config.refresh_interval_secs = 5;
+        let err = config.validate().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("remote_config.refresh_interval_secs")
+        );
+    }
+
+    // ─── ContentConfig tests ───────────────────────────────────────────
+
+    #[test]
+    fn content_default_is_disabled() {
+        let config = IronpostConfig::default();
+        assert!(!config.content.enabled);
+        assert_eq!(config.content.keep_versions, 5);
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn content_requires_source_when_enabled() {
+        let mut config = IronpostConfig::default();
+        config.content.enabled = true;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("content.source"));
     }
-}
 
-fn override_csv(target: &mut Vec<String>, env_key: &str) {
-    if let Ok(val) = std::env::var(env_key) {
-        *target = val.split(',').map(|s| s.trim().to_owned()).collect();
+    #[test]
+    fn content_accepts_local_dir_and_remote_url() {
+        let mut config = IronpostConfig::default();
+        config.content.enabled = true;
+        config.content.source = "/etc/ironpost/content".to_owned();
+        assert!(!config.content.is_remote());
+        config.validate().unwrap();
+
+        config.content.source = "s3://fleet-content/ironpost/bundle.json".to_owned();
+        assert!(config.content.is_remote());
+        config.validate().unwrap();
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serial_test::serial;
+    #[test]
+    fn watch_rules_conflicts_with_content() {
+        let mut config = IronpostConfig::parse("[log_pipeline]\nwatch_rules = true\n").unwrap();
+        assert!(config.log_pipeline.watch_rules);
+        config.validate().unwrap();
+
+        config.content.enabled = true;
+        config.content.source = "/etc/ironpost/content".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("log_pipeline.watch_rules"));
+
+        config.log_pipeline.watch_rules = false;
+        config.validate().unwrap();
+    }
 
     #[test]
-    fn default_config_has_sane_values() {
-        let config = IronpostConfig::default();
-        assert_eq!(config.general.log_level, "info");
-        assert_eq!(config.general.log_format, "json");
-        assert!(!config.ebpf.enabled);
-        assert_eq!(config.ebpf.interface, "eth0");
-        assert!(config.log_pipeline.enabled);
-        assert!(!config.container.enabled);
-        assert!(!config.sbom.enabled);
+    fn content_rejects_unsupported_scheme_and_bad_keep_versions() {
+        let mut config = IronpostConfig::default();
+        config.content.enabled = true;
+        config.content.source = "ftp://content.example.com/bundle.json".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("content.source"));
+
+        config.content.source = "/etc/ironpost/content".to_owned();
+        config.content.keep_versions = 0;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("content.keep_versions"));
     }
 
     #[test]
-    fn default_config_passes_validation() {
+    #[serial]
+    fn content_env_override_source() {
+        let mut config = IronpostConfig::default();
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("IRONPOST_CONTENT_SOURCE", "/srv/content") };
+        config.apply_env_overrides();
+        assert_eq!(config.content.source, "/srv/content");
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("IRONPOST_CONTENT_SOURCE") };
+    }
+
+    // ─── AuthConfig tests ──────────────────────────────────────────────
+
+    fn auth_token(name: &str, role: &str) -> ApiTokenConfig {
+        ApiTokenConfig {
+            name: name.to_owned(),
+            token_sha256: "a".repeat(64),
+            role: role.to_owned(),
+        }
+    }
+
+    #[test]
+    fn auth_default_is_disabled() {
         let config = IronpostConfig::default();
+        assert!(!config.auth.enabled);
+        assert!(!config.auth.tls_enabled());
         config.validate().unwrap();
     }
 
     #[test]
-    fn from_str_empty_toml_uses_defaults() {
-        let config = IronpostConfig::parse("").unwrap();
-        assert_eq!(config.general.log_level, "info");
-        assert_eq!(config.ebpf.interface, "eth0");
+    fn auth_requires_tokens_when_enabled() {
+        let mut config = IronpostConfig::default();
+        config.auth.enabled = true;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.tokens"));
+
+        config.auth.tokens = vec![auth_token("dashboard", "read-only")];
+        config.validate().unwrap();
     }
 
     #[test]
-    fn from_str_partial_toml_merges_with_defaults() {
-        let toml = r#"
-[general]
-log_level = "debug"
+    fn auth_rejects_bad_token_hash_role_and_duplicates() {
+        let mut config = IronpostConfig::default();
+        config.auth.enabled = true;
 
-[ebpf]
-enabled = true
-interface = "ens3"
-"#;
-        let config = IronpostConfig::parse(toml).unwrap();
-        assert_eq!(config.general.log_level, "debug");
-        // log_format은 기본값 유지
-        assert_eq!(config.general.log_format, "json");
-        assert!(config.ebpf.enabled);
-        assert_eq!(config.ebpf.interface, "ens3");
+        let mut token = auth_token("ops", "operator");
+        token.token_sha256 = "not-a-digest".to_owned();
+        config.auth.tokens = vec![token];
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.tokens.token_sha256"));
+
+        config.auth.tokens = vec![auth_token("ops", "root")];
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.tokens.role"));
+
+        config.auth.tokens = vec![auth_token("ops", "admin"), auth_token("ops", "operator")];
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("duplicate token name"));
     }
 
     #[test]
-    fn from_str_full_toml() {
-        let toml = r#"
-[general]
-log_level = "warn"
-log_format = "pretty"
-data_dir = "/opt/ironpost/data"
-pid_file = "/opt/ironpost/ironpost.pid"
-
-[ebpf]
-enabled = true
-interface = "ens3"
-xdp_mode = "native"
-ring_buffer_size = 524288
-blocklist_max_entries = 50000
+    fn auth_mtls_requires_server_certificate() {
+        let mut config = IronpostConfig::default();
+        config.auth.enabled = true;
+        config.auth.tokens = vec![auth_token("admin", "admin")];
+        config.auth.client_ca_path = "/etc/ironpost/tls/clients-ca.pem".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.client_ca_path"));
 
-[log_pipeline]
-enabled = true
-sources = ["syslog", "file", "journald"]
-syslog_bind = "127.0.0.1:5140"
-watch_paths = ["/var/log/auth.log", "/var/log/kern.log"]
-batch_size = 200
-flush_interval_secs = 10
+        config.auth.tls_cert_path = "/etc/ironpost/tls/server.pem".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("auth.tls_key_path"));
 
-[log_pipeline.storage]
-postgres_url = "postgresql://db:5432/ironpost"
-redis_url = "redis://cache:6379"
-retention_days = 90
+        config.auth.tls_key_path = "/etc/ironpost/tls/server-key.pem".to_owned();
+        config.validate().unwrap();
+        assert!(config.auth.tls_enabled());
+    }
 
-[container]
+    #[test]
+    fn auth_tokens_parse_from_toml() {
+        let toml_str = format!(
+            r#"
+[auth]
 enabled = true
-docker_socket = "/run/docker.sock"
-poll_interval_secs = 5
-policy_path = "/etc/ironpost/container-policies"
-auto_isolate = true
 
-[sbom]
-enabled = true
-scan_dirs = ["/app", "/opt"]
-vuln_db_update_hours = 12
-vuln_db_path = "/opt/ironpost/vuln-db"
-min_severity = "high"
-output_format = "spdx"
-"#;
-        let config = IronpostConfig::parse(toml).unwrap();
-        assert_eq!(config.general.log_level, "warn");
-        assert_eq!(config.ebpf.ring_buffer_size, 524288);
-        assert_eq!(config.log_pipeline.sources.len(), 3);
-        assert_eq!(config.log_pipeline.storage.retention_days, 90);
-        assert!(config.container.auto_isolate);
-        assert_eq!(config.sbom.output_format, "spdx");
+[[auth.tokens]]
+name = "grafana"
+token_sha256 = "{}"
+role = "read-only"
+"#,
+            "b".repeat(64)
+        );
+        let config = IronpostConfig::parse(&toml_str).unwrap();
+        assert_eq!(config.auth.tokens.len(), 1);
+        assert_eq!(config.auth.tokens[0].name, "grafana");
+        config.validate().unwrap();
     }
 
+    // ─── EnrichmentConfig tests ────────────────────────────────────────
+
     #[test]
-    fn from_str_invalid_toml_returns_error() {
-        let result = IronpostConfig::parse("invalid = [[[toml");
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(matches!(
-            err,
-            IronpostError::Config(ConfigError::ParseFailed { .. })
-        ));
+    fn enrichment_default_has_no_geoip_databases() {
+        let config = EnrichmentConfig::default();
+        assert!(config.geoip_country_db.is_empty());
+        assert!(config.geoip_asn_db.is_empty());
+        assert!(config.reverse_dns);
+        config.validate().unwrap();
     }
 
     #[test]
-    fn validate_rejects_invalid_log_level() {
+    fn enrichment_validate_rejects_invalid_dns_settings() {
         let mut config = IronpostConfig::default();
-        config.general.log_level = "verbose".to_owned();
+        config.log_pipeline.enrichment.dns_timeout_ms = 0;
         let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("log_level"));
+        assert!(err.to_string().contains("enrichment.dns_timeout_ms"));
+
+        config.log_pipeline.enrichment.dns_timeout_ms = 500;
+        config.log_pipeline.enrichment.dns_cache_size = 0;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("enrichment.dns_cache_size"));
+
+        config.log_pipeline.enrichment.dns_cache_size = 10_000;
+        config.log_pipeline.enrichment.dns_cache_ttl_secs = 0;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("enrichment.dns_cache_ttl_secs"));
+
+        config.log_pipeline.enrichment.dns_cache_ttl_secs = 30;
+        config.log_pipeline.enrichment.dns_negative_cache_ttl_secs = 60;
+        let err = config.validate().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("enrichment.dns_negative_cache_ttl_secs")
+        );
+
+        config.log_pipeline.enrichment.dns_negative_cache_ttl_secs = 0;
+        config.log_pipeline.enrichment.dns_max_concurrent_lookups = 0;
+        let err = config.validate().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("enrichment.dns_max_concurrent_lookups")
+        );
     }
 
     #[test]
-    fn validate_rejects_invalid_log_format() {
+    fn enrichment_ip_fields_required_when_enriching_logs() {
         let mut config = IronpostConfig::default();
-        config.general.log_format = "xml".to_owned();
+        config.log_pipeline.enrichment.ip_fields.clear();
+        config.validate().unwrap();
+
+        config.log_pipeline.enrichment.enrich_logs = true;
         let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("log_format"));
+        assert!(err.to_string().contains("enrichment.ip_fields"));
+
+        config.log_pipeline.enrichment.ip_fields = vec!["peer_ip".to_owned()];
+        config.validate().unwrap();
     }
 
     #[test]
-    fn validate_rejects_invalid_xdp_mode_when_enabled() {
+    fn enrichment_parses_from_toml() {
+        let config = IronpostConfig::parse(
+            r#"
+[log_pipeline.enrichment]
+geoip_country_db = "/var/lib/ironpost/GeoLite2-Country.mmdb"
+reverse_dns = false
+"#,
+        )
+        .unwrap();
+        let enrichment = &config.log_pipeline.enrichment;
+        assert_eq!(
+            enrichment.geoip_country_db,
+            "/var/lib/ironpost/GeoLite2-Country.mmdb"
+        );
+        assert!(!enrichment.reverse_dns);
+        assert_eq!(enrichment.dns_timeout_ms, 500);
+        assert!(!enrichment.enrich_logs);
+        assert!(enrichment.ip_fields.iter().any(|f| f == "src_ip"));
+    }
+
+    // ─── KafkaConfig tests ─────────────────────────────────────────────
+
+    #[test]
+    fn kafka_validated_only_when_source_enabled() {
         let mut config = IronpostConfig::default();
-        config.ebpf.enabled = true;
-        config.ebpf.xdp_mode = "turbo".to_owned();
+        assert!(config.log_pipeline.kafka.topics.is_empty());
+        config.validate().unwrap();
+
+        config.log_pipeline.sources.push("kafka".to_owned());
         let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("xdp_mode"));
+        assert!(err.to_string().contains("kafka.topics"));
+
+        config.log_pipeline.kafka.topics = vec!["syslog".to_owned()];
+        config.validate().unwrap();
     }
 
     #[test]
-    fn validate_accepts_invalid_xdp_mode_when_disabled() {
-        let mut config = IronpostConfig::default();
-        config.ebpf.enabled = false;
-        config.ebpf.xdp_mode = "turbo".to_owned();
-        // ebpf가 비활성화 상태면 xdp_mode 검증을 건너뜀
+    fn kafka_validate_rejects_invalid_values() {
+        let valid = KafkaConfig {
+            topics: vec!["syslog".to_owned()],
+            ..KafkaConfig::default()
+        };
+        valid.validate().unwrap();
+
+        let cases = [
+            (
+                KafkaConfig {
+                    brokers: vec![" ".to_owned()],
+                    ..valid.clone()
+                },
+                "kafka.brokers",
+            ),
+            (
+                KafkaConfig {
+                    auto_offset_reset: "smallest".to_owned(),
+                    ..valid.clone()
+                },
+                "kafka.auto_offset_reset",
+            ),
+            (
+                KafkaConfig {
+                    commit_interval_secs: 0,
+                    ..valid.clone()
+                },
+                "kafka.commit_interval_secs",
+            ),
+            (
+                KafkaConfig {
+                    properties: BTreeMap::from([(
+                        "enable.auto.commit".to_owned(),
+                        "true".to_owned(),
+                    )]),
+                    ..valid.clone()
+                },
+                "managed by the collector",
+            ),
+        ];
+        for (config, expected) in cases {
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains(expected), "{}", err);
+        }
+    }
+
+    #[test]
+    fn kafka_parses_from_toml() {
+        let config = IronpostConfig::parse(
+            r#"
+[log_pipeline]
+sources = ["kafka"]
+
+[log_pipeline.kafka]
+brokers = ["kafka-1:9092", "kafka-2:9092"]
+topics = ["app-logs"]
+auto_offset_reset = "earliest"
+
+[log_pipeline.kafka.properties]
+"security.protocol" = "ssl"
+"#,
+        )
+        .unwrap();
+        let kafka = &config.log_pipeline.kafka;
+        assert_eq!(kafka.brokers.len(), 2);
+        assert_eq!(kafka.group_id, "ironpost");
+        assert_eq!(kafka.auto_offset_reset, "earliest");
+        assert_eq!(
+            kafka
+                .properties
+                .get("security.protocol")
+                .map(String::as_str),
+            Some("ssl")
+        );
         config.validate().unwrap();
     }
 
     #[test]
-    fn validate_rejects_empty_interface_when_enabled() {
+    #[serial]
+    fn kafka_env_override_brokers_and_topics() {
         let mut config = IronpostConfig::default();
-        config.ebpf.enabled = true;
-        config.ebpf.interface = String::new();
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe {
+            std::env::set_var("IRONPOST_KAFKA_BROKERS", "a:9092, b:9092");
+            std::env::set_var("IRONPOST_KAFKA_TOPICS", "logs");
+        }
+        config.apply_env_overrides();
+        assert_eq!(config.log_pipeline.kafka.brokers, vec!["a:9092", "b:9092"]);
+        assert_eq!(config.log_pipeline.kafka.topics, vec!["logs"]);
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe {
+            std::env::remove_var("IRONPOST_KAFKA_BROKERS");
+            std::env::remove_var("IRONPOST_KAFKA_TOPICS");
+        }
+    }
+
+    #[test]
+    fn kafka_output_validated_only_when_enabled() {
+        let mut config = IronpostConfig::default();
+        config.log_pipeline.kafka_output.delivery = "exactly_once".to_owned();
+        assert!(config.validate().is_ok());
+
+        config.log_pipeline.kafka_output.enabled = true;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("kafka_output.delivery"));
+
+        config.log_pipeline.kafka_output.delivery = "idempotent".to_owned();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn kafka_output_validate_rejects_invalid_values() {
+        type BreakConfig = fn(&mut KafkaOutputConfig);
+        let cases: [(&str, BreakConfig); 7] = [
+            ("kafka_output.brokers", |c| c.brokers.clear()),
+            ("kafka_output.alert_topic", |c| c.alert_topic.clear()),
+            ("kafka_output.log_topic", |c| {
+                c.log_topic = "logs/raw".to_owned()
+            }),
+            ("kafka_output.alert_key", |c| {
+                c.alert_key = "title".to_owned()
+            }),
+            ("kafka_output.log_key", |c| c.log_key = "rule_id".to_owned()),
+            ("kafka_output.queue_size", |c| c.queue_size = 0),
+            ("kafka_output.properties", |c| {
+                c.properties.insert("acks".to_owned(), "1".to_owned());
+            }),
+        ];
+        for (field, break_config) in cases {
+            let mut config = KafkaOutputConfig {
+                enabled: true,
+                ..Default::default()
+            };
+            assert!(config.validate().is_ok());
+            break_config(&mut config);
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains(field), "{field}: {err}");
+        }
+    }
+
+    #[test]
+    fn kafka_output_parses_from_toml() {
+        let toml_str = r#"
+[log_pipeline.kafka_output]
+enabled = true
+brokers = ["kafka-1:9092"]
+log_topic = "ironpost-logs"
+alert_key = "alert_id"
+delivery = "idempotent"
+
+[log_pipeline.kafka_output.properties]
+"compression.type" = "lz4"
+"#;
+        let config = IronpostConfig::parse(toml_str).unwrap();
+        let output = &config.log_pipeline.kafka_output;
+        assert_eq!(output.alert_topic, "ironpost-alerts");
+        assert_eq!(output.log_topic, "ironpost-logs");
+        assert_eq!(output.alert_key, "alert_id");
+        assert_eq!(output.log_key, "hostname");
+        assert_eq!(output.delivery, "idempotent");
+        assert_eq!(
+            output
+                .properties
+                .get("compression.type")
+                .map(String::as_str),
+            Some("lz4")
+        );
+    }
+
+    #[test]
+    fn disk_buffer_validated_only_when_enabled() {
+        let mut config = IronpostConfig::parse(
+            r#"
+[log_pipeline.disk_buffer]
+dir = "/var/lib/ironpost/spool"
+segment_bytes = 1024
+"#,
+        )
+        .unwrap();
+        let disk_buffer = &config.log_pipeline.disk_buffer;
+        assert!(!disk_buffer.enabled);
+        assert_eq!(disk_buffer.dir, "/var/lib/ironpost/spool");
+        assert_eq!(disk_buffer.max_bytes, 1024 * 1024 * 1024);
+        assert!(config.validate().is_ok());
+
+        config.log_pipeline.disk_buffer.enabled = true;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("disk_buffer.segment_bytes"));
+
+        type BreakConfig = fn(&mut DiskBufferConfig);
+        let cases: [(&str, BreakConfig); 3] = [
+            ("disk_buffer.dir", |c| c.dir = "  ".to_owned()),
+            ("disk_buffer.segment_bytes", |c| {
+                c.segment_bytes = DISK_BUFFER_MAX_SEGMENT_BYTES + 1
+            }),
+            ("disk_buffer.max_bytes", |c| c.max_bytes = c.segment_bytes),
+        ];
+        for (field, break_config) in cases {
+            let mut config = DiskBufferConfig {
+                enabled: true,
+                ..Default::default()
+            };
+            assert!(config.validate().is_ok());
+            break_config(&mut config);
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains(field), "{field}: {err}");
+        }
+    }
+
+    #[test]
+    fn log_store_validated_only_when_enabled() {
+        let mut config = IronpostConfig::parse(
+            r#"
+[log_pipeline.log_store]
+dir = "/srv/ironpost/logs"
+retention_days = 0
+max_bytes = 0
+"#,
+        )
+        .unwrap();
+        let log_store = &config.log_pipeline.log_store;
+        assert!(!log_store.enabled);
+        assert_eq!(log_store.dir, "/srv/ironpost/logs");
+        assert_eq!(log_store.segment_bytes, 64 * 1024 * 1024);
+        assert!(config.validate().is_ok());
+
+        config.log_pipeline.log_store.enabled = true;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("log_store.retention_days"));
+
+        type BreakConfig = fn(&mut LogStoreConfig);
+        let cases: [(&str, BreakConfig); 5] = [
+            ("log_store.dir", |c| c.dir.clear()),
+            ("log_store.retention_days", |c| c.retention_days = 3651),
+            ("log_store.segment_bytes", |c| c.segment_bytes = 1024),
+            ("log_store.max_bytes", |c| c.max_bytes = c.segment_bytes),
+            ("log_store.queue_size", |c| c.queue_size = 0),
+        ];
+        for (field, break_config) in cases {
+            let mut config = LogStoreConfig {
+                enabled: true,
+                ..Default::default()
+            };
+            assert!(config.validate().is_ok());
+            break_config(&mut config);
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains(field), "{field}: {err}");
+        }
+
+        let unlimited_size = LogStoreConfig {
+            max_bytes: 0,
+            ..Default::default()
+        };
+        assert!(unlimited_size.validate().is_ok());
+    }
+
+    #[test]
+    fn transforms_parse_and_validate() {
+        let config = IronpostConfig::parse(
+            r#"
+[[log_pipeline.transforms]]
+type = "rename"
+field = "src"
+to = "src_ip"
+
+[[log_pipeline.transforms]]
+type = "redact"
+field = "*"
+preset = "credit_card"
+"#,
+        )
+        .unwrap();
+        let transforms = &config.log_pipeline.transforms;
+        assert_eq!(transforms.len(), 2);
+        assert_eq!(transforms[0].kind, "rename");
+        assert_eq!(transforms[1].replacement, "[REDACTED]");
+        assert!(config.validate().is_ok());
+
+        type BreakConfig = fn(&mut TransformConfig);
+        let cases: [(&str, BreakConfig); 7] = [
+            ("transforms[0].type", |c| c.kind = "drop".to_owned()),
+            ("transforms[0].field", |c| c.field = " ".to_owned()),
+            ("transforms[0].to", |c| c.to = c.field.clone()),
+            ("transforms[0].field", |c| c.field = "*".to_owned()),
+            ("transforms[0].preset", |c| c.preset = "email".to_owned()),
+            ("transforms[0].pattern", |c| {
+                c.kind = "extract".to_owned();
+                c.pattern.clear();
+            }),
+            ("transforms[0].preset", |c| {
+                c.kind = "hash".to_owned();
+                c.preset = "ssn".to_owned();
+            }),
+        ];
+        for (field, break_config) in cases {
+            let mut config = config.clone();
+            break_config(&mut config.log_pipeline.transforms[0]);
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains(field), "{field}: {err}");
+        }
+
+        let mut config = config.clone();
+        config.log_pipeline.transforms[1].pattern = r"\d+".to_owned();
         let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("interface"));
+        assert!(err.to_string().contains("transforms[1].preset"), "{err}");
     }
 
+    // ─── HttpIngestConfig tests ────────────────────────────────────────
+
     #[test]
-    fn validate_rejects_invalid_sbom_format_when_enabled() {
+    fn http_ingest_validated_only_when_source_enabled() {
         let mut config = IronpostConfig::default();
-        config.sbom.enabled = true;
-        config.sbom.output_format = "xml".to_owned();
+        assert!(config.log_pipeline.http.tokens.is_empty());
+        config.validate().unwrap();
+
+        config.log_pipeline.sources.push("http".to_owned());
         let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("output_format"));
+        assert!(err.to_string().contains("http.tokens"));
+
+        config.log_pipeline.http.tokens.push(IngestTokenConfig {
+            name: "vector".to_owned(),
+            token_sha256: "a".repeat(64),
+        });
+        config.validate().unwrap();
     }
 
     #[test]
-    #[serial]
-    fn env_override_string() {
-        let mut val = "original".to_owned();
-        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
-        unsafe { std::env::set_var("TEST_IRONPOST_STR", "overridden") };
-        override_string(&mut val, "TEST_IRONPOST_STR");
-        assert_eq!(val, "overridden");
-        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
-        unsafe { std::env::remove_var("TEST_IRONPOST_STR") };
+    fn http_ingest_validate_rejects_invalid_values() {
+        let valid = HttpIngestConfig {
+            tokens: vec![IngestTokenConfig {
+                name: "vector".to_owned(),
+                token_sha256: "a".repeat(64),
+            }],
+            ..HttpIngestConfig::default()
+        };
+        valid.validate().unwrap();
+
+        let mut duplicate = valid.clone();
+        duplicate.tokens.push(duplicate.tokens[0].clone());
+        let cases = [
+            (
+                HttpIngestConfig {
+                    bind: "localhost".to_owned(),
+                    ..valid.clone()
+                },
+                "http.bind",
+            ),
+            (duplicate, "duplicate token name"),
+            (
+                HttpIngestConfig {
+                    tokens: vec![IngestTokenConfig {
+                        name: "vector".to_owned(),
+                        token_sha256: "not-a-digest".to_owned(),
+                    }],
+                    ..valid.clone()
+                },
+                "http.tokens.token_sha256",
+            ),
+            (
+                HttpIngestConfig {
+                    max_body_bytes: 0,
+                    ..valid.clone()
+                },
+                "http.max_body_bytes",
+            ),
+        ];
+        for (config, expected) in cases {
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains(expected), "{}", err);
+        }
     }
 
     #[test]
-    #[serial]
-    fn env_override_bool_valid() {
-        let mut val = false;
-        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
-        unsafe { std::env::set_var("TEST_IRONPOST_BOOL", "true") };
-        override_bool(&mut val, "TEST_IRONPOST_BOOL");
-        assert!(val);
-        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
-        unsafe { std::env::remove_var("TEST_IRONPOST_BOOL") };
+    fn http_ingest_parses_from_toml() {
+        let config = IronpostConfig::parse(&format!(
+            r#"
+[log_pipeline]
+sources = ["http"]
+
+[log_pipeline.http]
+bind = "127.0.0.1:9000"
+
+[[log_pipeline.http.tokens]]
+name = "fluent-bit"
+token_sha256 = "{}"
+"#,
+            "b".repeat(64)
+        ))
+        .unwrap();
+        let http = &config.log_pipeline.http;
+        assert_eq!(http.bind, "127.0.0.1:9000");
+        assert_eq!(http.tokens[0].name, "fluent-bit");
+        assert_eq!(http.max_body_bytes, 10 * 1024 * 1024);
+        config.validate().unwrap();
     }
 
+    // ─── ParsersConfig tests ───────────────────────────────────────────
+
     #[test]
     #[serial]
-    fn env_override_bool_invalid_keeps_original() {
-        let mut val = false;
+    fn parsers_leef_from_toml_and_env() {
+        let config = IronpostConfig::parse("[log_pipeline.parsers]\nleef = true\n").unwrap();
+        assert!(config.log_pipeline.parsers.leef);
+
+        let mut config = IronpostConfig::default();
+        assert!(!config.log_pipeline.parsers.leef);
         // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
-        unsafe { std::env::set_var("TEST_IRONPOST_BOOL_BAD", "not-a-bool") };
-        override_bool(&mut val, "TEST_IRONPOST_BOOL_BAD");
-        assert!(!val); // 원래 값 유지
+        unsafe {
+            std::env::set_var("IRONPOST_LOG_PIPELINE_PARSERS_LEEF", "true");
+        }
+        config.apply_env_overrides();
+        assert!(config.log_pipeline.parsers.leef);
         // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
-        unsafe { std::env::remove_var("TEST_IRONPOST_BOOL_BAD") };
+        unsafe {
+            std::env::remove_var("IRONPOST_LOG_PIPELINE_PARSERS_LEEF");
+        }
     }
 
     #[test]
-    #[serial]
-    fn env_override_csv() {
-        let mut val = vec!["a".to_owned()];
-        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
-        unsafe { std::env::set_var("TEST_IRONPOST_CSV", "x, y, z") };
-        override_csv(&mut val, "TEST_IRONPOST_CSV");
-        assert_eq!(val, vec!["x", "y", "z"]);
-        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
-        unsafe { std::env::remove_var("TEST_IRONPOST_CSV") };
+    fn parsers_patterns_from_toml() {
+        let config = IronpostConfig::parse(
+            r#"
+[log_pipeline.parsers.grok_definitions]
+TENANT = "[a-z]+-[0-9]+"
+
+[[log_pipeline.parsers.patterns]]
+name = "billing"
+pattern = "%{TIMESTAMP_ISO8601:timestamp} %{TENANT:tenant} %{GREEDYDATA:message}"
+"#,
+        )
+        .unwrap();
+        let parsers = &config.log_pipeline.parsers;
+        assert_eq!(parsers.grok_definitions["TENANT"], "[a-z]+-[0-9]+");
+        assert_eq!(parsers.patterns.len(), 1);
+        assert_eq!(parsers.patterns[0].name, "billing");
+        assert!(parsers.patterns[0].timestamp_format.is_empty());
     }
 
     #[test]
-    fn env_override_missing_var_keeps_original() {
-        let mut val = "original".to_owned();
-        override_string(&mut val, "TEST_IRONPOST_NONEXISTENT_12345");
-        assert_eq!(val, "original");
+    fn parsers_validate_rejects_bad_patterns() {
+        let pattern = |name: &str, pattern: &str| PatternParserConfig {
+            name: name.to_owned(),
+            pattern: pattern.to_owned(),
+            ..Default::default()
+        };
+        let mut parsers = ParsersConfig {
+            patterns: vec![pattern("app", "%{WORD:user}")],
+            ..Default::default()
+        };
+        assert!(parsers.validate().is_ok());
+
+        parsers.patterns.push(pattern("app", "x"));
+        assert!(parsers.validate().is_err());
+
+        parsers.patterns = vec![pattern("json", "x")];
+        assert!(parsers.validate().is_err());
+
+        parsers.patterns = vec![pattern("bad name", "x")];
+        assert!(parsers.validate().is_err());
+
+        parsers.patterns = vec![pattern("app", "")];
+        assert!(parsers.validate().is_err());
+
+        parsers.patterns.clear();
+        parsers
+            .grok_definitions
+            .insert("BAD-NAME".to_owned(), "x".to_owned());
+        assert!(parsers.validate().is_err());
     }
 
     #[test]
-    fn config_serialize_roundtrip() {
-        let config = IronpostConfig::default();
-        let toml_str = toml::to_string_pretty(&config).unwrap();
-        let parsed = IronpostConfig::parse(&toml_str).unwrap();
-        assert_eq!(config.general.log_level, parsed.general.log_level);
-        assert_eq!(config.ebpf.interface, parsed.ebpf.interface);
-        assert_eq!(
-            config.log_pipeline.storage.retention_days,
-            parsed.log_pipeline.storage.retention_days
-        );
-    }
+    fn format_hints_validation() {
+        let mut config = IronpostConfig::parse(
+            "[log_pipeline.format_hints]\nfile = \"json\"\nkafka = \"gelf\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.log_pipeline.format_hints["file"], "json");
+        assert!(config.log_pipeline.validate().is_ok());
 
-    #[tokio::test]
-    async fn from_file_not_found() {
-        let result = IronpostConfig::from_file("/nonexistent/path/ironpost.toml").await;
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(matches!(
-            err,
-            IronpostError::Config(ConfigError::FileNotFound { .. })
-        ));
-    }
+        config
+            .log_pipeline
+            .format_hints
+            .insert("file".to_owned(), "leef".to_owned());
+        assert!(config.log_pipeline.validate().is_err());
+        config.log_pipeline.parsers.leef = true;
+        assert!(config.log_pipeline.validate().is_ok());
 
-    // ─── MetricsConfig tests ───────────────────────────────────────────
+        config
+            .log_pipeline
+            .format_hints
+            .insert("file".to_owned(), "billing".to_owned());
+        assert!(config.log_pipeline.validate().is_err());
+        config
+            .log_pipeline
+            .parsers
+            .patterns
+            .push(PatternParserConfig {
+                name: "billing".to_owned(),
+                pattern: "%{GREEDYDATA:message}".to_owned(),
+                ..Default::default()
+            });
+        assert!(config.log_pipeline.validate().is_ok());
 
-    #[test]
-    fn metrics_config_default() {
-        let config = MetricsConfig::default();
-        assert!(config.enabled);
-        assert_eq!(config.listen_addr, "127.0.0.1");
-        assert_eq!(config.port, 9100);
-        assert_eq!(config.endpoint, "/metrics");
+        config
+            .log_pipeline
+            .format_hints
+            .insert("smtp".to_owned(), "json".to_owned());
+        assert!(config.log_pipeline.validate().is_err());
     }
 
+    // ─── FileSourceConfig tests ────────────────────────────────────────
+
     #[test]
-    fn metrics_config_default_passes_validation() {
-        let config = MetricsConfig::default();
+    fn file_source_parses_and_validates_read_from() {
+        let mut config = IronpostConfig::parse(
+            r#"
+[log_pipeline.file]
+checkpoint_path = "/tmp/ironpost/checkpoints.json"
+read_from = "end"
+"#,
+        )
+        .unwrap();
+        let file = &config.log_pipeline.file;
+        assert_eq!(file.checkpoint_path, "/tmp/ironpost/checkpoints.json");
+        assert_eq!(file.read_from, "end");
+        assert_eq!(file.checkpoint_interval_secs, 5);
+        assert!(!file.ignore_checkpoints);
+        assert!(!file.replay_archives);
         config.validate().unwrap();
-    }
 
-    #[test]
-    fn config_without_metrics_section_uses_defaults() {
-        let toml = r#"
-[general]
-log_level = "info"
-"#;
-        let config = IronpostConfig::parse(toml).unwrap();
-        assert!(config.metrics.enabled);
-        assert_eq!(config.metrics.port, 9100);
-        assert_eq!(config.metrics.endpoint, "/metrics");
+        config.log_pipeline.file.read_from = "middle".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("file.read_from"));
+
+        // file 소스가 없으면 검증하지 않음
+        config.log_pipeline.sources = vec!["syslog".to_owned()];
+        config.validate().unwrap();
     }
 
+    // ─── ClientConfig tests ────────────────────────────────────────────
+
     #[test]
-    fn config_with_metrics_section() {
-        let toml = r#"
-[metrics]
-enabled = false
-listen_addr = "127.0.0.1"
-port = 9101
-endpoint = "/prometheus"
-"#;
-        let config = IronpostConfig::parse(toml).unwrap();
-        assert!(!config.metrics.enabled);
-        assert_eq!(config.metrics.listen_addr, "127.0.0.1");
-        assert_eq!(config.metrics.port, 9101);
-        assert_eq!(config.metrics.endpoint, "/prometheus");
+    fn client_default_uses_health_listener() {
+        let config = IronpostConfig::default();
+        assert!(config.client.host.is_empty());
+        assert!(config.client.socket.is_empty());
+        config.validate().unwrap();
     }
 
     #[test]
-    fn metrics_config_validate_rejects_zero_port() {
-        let config = MetricsConfig {
-            port: 0,
-            ..MetricsConfig::default()
-        };
+    fn client_rejects_host_with_socket_and_unknown_scheme() {
+        let mut config = IronpostConfig::default();
+        config.client.host = "ftp://ironpost.example".to_owned();
         let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("metrics.port"));
-    }
+        assert!(err.to_string().contains("client.host"));
 
-    #[test]
-    fn metrics_config_validate_rejects_empty_listen_addr() {
-        let config = MetricsConfig {
-            listen_addr: String::new(),
-            ..MetricsConfig::default()
-        };
+        config.client.host = "https://ironpost.example:9101".to_owned();
+        config.validate().unwrap();
+
+        config.client.socket = "/run/ironpost/control.sock".to_owned();
         let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("metrics.listen_addr"));
+        assert!(err.to_string().contains("client.socket"));
     }
 
     #[test]
-    fn metrics_config_validate_rejects_endpoint_without_slash() {
-        let config = MetricsConfig {
-            endpoint: "metrics".to_owned(),
-            ..MetricsConfig::default()
-        };
+    fn client_certificate_and_key_go_together() {
+        let mut config = IronpostConfig::default();
+        config.client.client_cert_path = "/etc/ironpost/tls/cli.pem".to_owned();
         let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("metrics.endpoint"));
-        assert!(err.to_string().contains("start with '/'"));
+        assert!(err.to_string().contains("client.client_key_path"));
+
+        config.client.client_key_path = "/etc/ironpost/tls/cli-key.pem".to_owned();
+        config.validate().unwrap();
     }
 
     #[test]
-    fn metrics_config_validate_rejects_non_default_endpoint() {
-        let config = MetricsConfig {
-            endpoint: "/prometheus/metrics".to_owned(),
-            ..MetricsConfig::default()
-        };
-        let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("metrics.endpoint"));
-        assert!(err.to_string().contains("only '/metrics'"));
+    #[serial]
+    fn client_env_override_token() {
+        let mut config = IronpostConfig::default();
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("IRONPOST_CLIENT_TOKEN", "secret") };
+        config.apply_env_overrides();
+        assert_eq!(config.client.token, "secret");
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("IRONPOST_CLIENT_TOKEN") };
     }
 
+    // ─── PersistenceConfig tests ───────────────────────────────────────
+
     #[test]
-    fn ironpost_config_rejects_non_default_metrics_endpoint_when_enabled() {
+    fn persistence_default_is_disabled_and_valid() {
         let mut config = IronpostConfig::default();
-        config.metrics.enabled = true;
-        config.metrics.endpoint = "/custom".to_owned();
+        assert!(!config.persistence.enabled);
+        assert_eq!(config.persistence.dir, "/var/lib/ironpost/journal");
 
+        config.persistence.enabled = true;
+        config.validate().unwrap();
+        config.persistence.max_journal_bytes = 1024;
         let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("metrics.endpoint"));
-        assert!(err.to_string().contains("only '/metrics'"));
+        assert!(err.to_string().contains("persistence.max_journal_bytes"));
+        config.persistence.max_journal_bytes = 1024 * 1024;
+        config.persistence.dir = " ".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("persistence.dir"));
     }
 
+    // ─── AlertGateConfig tests ─────────────────────────────────────────
+
     #[test]
-    #[serial]
-    fn metrics_env_override_enabled() {
-        let mut config = IronpostConfig::default();
-        // SAFETY: テスト用の環境変数設定
-        unsafe { std::env::set_var("IRONPOST_METRICS_ENABLED", "false") };
-        config.apply_env_overrides();
-        assert!(!config.metrics.enabled);
-        // SAFETY: クリーンアップ
-        unsafe { std::env::remove_var("IRONPOST_METRICS_ENABLED") };
+    fn alert_gate_default_is_enabled_and_valid() {
+        let config = IronpostConfig::default();
+        assert!(config.alert_gate.enabled);
+        assert_eq!(config.alert_gate.dedup_window_secs, 60);
+        assert_eq!(config.alert_gate.max_alerts_per_minute, 600);
+        config.validate().unwrap();
     }
 
     #[test]
-    #[serial]
-    fn metrics_env_override_listen_addr() {
+    fn alert_gate_rejects_out_of_range_values() {
         let mut config = IronpostConfig::default();
-        // SAFETY: テスト用の環境変数設定
-        unsafe { std::env::set_var("IRONPOST_METRICS_LISTEN_ADDR", "127.0.0.1") };
-        config.apply_env_overrides();
-        assert_eq!(config.metrics.listen_addr, "127.0.0.1");
-        // SAFETY: クリーンアップ
-        unsafe { std::env::remove_var("IRONPOST_METRICS_LISTEN_ADDR") };
+        config.alert_gate.max_tracked_keys = 0;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("alert_gate.max_tracked_keys"));
+
+        config.alert_gate.max_tracked_keys = 10_000;
+        config.alert_gate.dedup_window_secs = 100_000;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("alert_gate.dedup_window_secs"));
+
+        // Disabled gates are not validated
+        config.alert_gate.enabled = false;
+        config.validate().unwrap();
     }
 
     #[test]
     #[serial]
-    fn metrics_env_override_port() {
+    fn alert_gate_env_override_cap() {
         let mut config = IronpostConfig::default();
-        // SAFETY: テスト用の環境変数設定
-        unsafe { std::env::set_var("IRONPOST_METRICS_PORT", "9999") };
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("IRONPOST_ALERT_GATE_MAX_ALERTS_PER_MINUTE", "50") };
         config.apply_env_overrides();
-        assert_eq!(config.metrics.port, 9999);
-        // SAFETY: クリーンアップ
-        unsafe { std::env::remove_var("IRONPOST_METRICS_PORT") };
+        assert_eq!(config.alert_gate.max_alerts_per_minute, 50);
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("IRONPOST_ALERT_GATE_MAX_ALERTS_PER_MINUTE") };
     }
 
+    // ─── ChannelsConfig tests ──────────────────────────────────────────
+
     #[test]
-    #[serial]
-    fn metrics_env_override_endpoint() {
-        let mut config = IronpostConfig::default();
-        // SAFETY: テスト用の環境変数設定
-        unsafe { std::env::set_var("IRONPOST_METRICS_ENDPOINT", "/custom") };
-        config.apply_env_overrides();
-        assert_eq!(config.metrics.endpoint, "/custom");
-        // SAFETY: クリーンアップ
-        unsafe { std::env::remove_var("IRONPOST_METRICS_ENDPOINT") };
+    fn channels_default_blocks_and_is_valid() {
+        let config = IronpostConfig::default();
+        assert_eq!(config.channels.packets.capacity, 1024);
+        assert_eq!(config.channels.alerts.capacity, 256);
+        assert_eq!(config.channels.alerts.overflow, "block");
+        config.validate().unwrap();
     }
 
     #[test]
-    #[serial]
-    fn metrics_env_override_port_invalid_keeps_original() {
+    fn channels_reject_invalid_values() {
         let mut config = IronpostConfig::default();
-        let original_port = config.metrics.port;
-        // SAFETY: テスト用の環境変数設定
-        unsafe { std::env::set_var("IRONPOST_METRICS_PORT", "not-a-number") };
-        config.apply_env_overrides();
-        assert_eq!(config.metrics.port, original_port);
-        // SAFETY: クリーンアップ
-        unsafe { std::env::remove_var("IRONPOST_METRICS_PORT") };
+        config.channels.alerts.overflow = "discard".to_owned();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("channels.alerts.overflow"));
+
+        config.channels.alerts.overflow = "spill".to_owned();
+        config.channels.spill_dir = String::new();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("channels.spill_dir"));
+
+        config.channels.spill_dir = "/tmp/spill".to_owned();
+        config.channels.packets.capacity = 0;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("channels.packets.capacity"));
     }
 
     #[test]
-    fn ironpost_config_validates_metrics_when_enabled() {
-        let mut config = IronpostConfig::default();
-        config.metrics.enabled = true;
-        config.metrics.port = 0; // Invalid
-        let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("metrics.port"));
+    fn channels_parse_per_link_sections() {
+        let config = IronpostConfig::parse(
+            r#"
+[channels.packets]
+capacity = 4096
+overflow = "drop_oldest"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.channels.packets.capacity, 4096);
+        assert_eq!(config.channels.packets.overflow, "drop_oldest");
+        // Unset links keep their defaults
+        assert_eq!(config.channels.alerts.capacity, 256);
+        config.validate().unwrap();
     }
 
     #[test]
-    fn ironpost_config_skips_metrics_validation_when_disabled() {
+    #[serial]
+    fn channels_env_override_policy() {
         let mut config = IronpostConfig::default();
-        config.metrics.enabled = false;
-        config.metrics.port = 0; // Invalid, but should be ignored
-        config.validate().unwrap(); // Should pass
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::set_var("IRONPOST_CHANNELS_ALERTS_OVERFLOW", "spill") };
+        config.apply_env_overrides();
+        assert_eq!(config.channels.alerts.overflow, "spill");
+        // SAFETY: 테스트는 단일 스레드에서 실행되므로 환경변수 조작이 안전합니다.
+        unsafe { std::env::remove_var("IRONPOST_CHANNELS_ALERTS_OVERFLOW") };
     }
 }
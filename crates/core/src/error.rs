@@ -104,6 +104,12 @@ pub enum DetectionError {
     #[error("ebpf load failed: {0}")]
     EbpfLoad(String),
 
+    /// eBPF 사전 점검 실패 (커널 버전, 권한, XDP 드라이버 지원 등)
+    ///
+    /// 실패한 점검마다 `"<점검 이름>: <사유>"` 형식의 항목 하나를 담습니다.
+    #[error("ebpf preflight failed: {}", .0.join("; "))]
+    Preflight(Vec<String>),
+
     /// eBPF 맵 접근 실패
     #[error("ebpf map error: {0}")]
     EbpfMap(String),
@@ -260,6 +266,15 @@ mod tests {
     fn detection_error_display() {
         let err = DetectionError::EbpfLoad("permission denied".to_owned());
         assert!(err.to_string().contains("permission denied"));
+
+        let err = DetectionError::Preflight(vec![
+            "kernel: 4.19 is older than the required 5.7".to_owned(),
+            "capabilities: missing CAP_BPF".to_owned(),
+        ]);
+        assert_eq!(
+            err.to_string(),
+            "ebpf preflight failed: kernel: 4.19 is older than the required 5.7; capabilities: missing CAP_BPF"
+        );
     }
 
     #[test]
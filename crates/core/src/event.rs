@@ -22,6 +22,8 @@ pub const MODULE_LOG_PIPELINE: &str = "log-pipeline";
 pub const MODULE_CONTAINER_GUARD: &str = "container-guard";
 /// SBOM 스캐너 모듈명
 pub const MODULE_SBOM_SCANNER: &str = "sbom-scanner";
+/// 데몬(오케스트레이터) 모듈명
+pub const MODULE_DAEMON: &str = "ironpost-daemon";
 
 // --- 이벤트 타입 상수 ---
 
@@ -106,7 +108,7 @@ pub trait Event: Send + Sync + 'static {
 ///
 /// eBPF XDP 프로그램에서 캡처한 네트워크 패킷 정보를 담습니다.
 /// 원시 패킷 데이터는 `bytes::Bytes`로 제로카피 슬라이싱이 가능합니다.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PacketEvent {
     /// 이벤트 고유 ID
     pub id: String,
@@ -116,6 +118,9 @@ pub struct PacketEvent {
     pub packet_info: PacketInfo,
     /// 원시 패킷 데이터
     pub raw_data: Bytes,
+    /// TLS ClientHello에서 추출한 SNI 호스트 이름 (TLS 핸드셰이크 패킷만)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sni: Option<String>,
 }
 
 impl PacketEvent {
@@ -126,6 +131,7 @@ impl PacketEvent {
             metadata: EventMetadata::with_new_trace(MODULE_EBPF),
             packet_info,
             raw_data,
+            sni: None,
         }
     }
 
@@ -140,8 +146,15 @@ impl PacketEvent {
             metadata: EventMetadata::new(MODULE_EBPF, trace_id),
             packet_info,
             raw_data,
+            sni: None,
         }
     }
+
+    /// TLS SNI 호스트 이름을 지정합니다.
+    pub fn with_sni(mut self, sni: impl Into<String>) -> Self {
+        self.sni = Some(sni.into());
+        self
+    }
 }
 
 impl Event for PacketEvent {
@@ -170,7 +183,11 @@ impl fmt::Display for PacketEvent {
             self.packet_info.dst_port,
             self.packet_info.protocol,
             self.packet_info.size,
-        )
+        )?;
+        if let Some(ref sni) = self.sni {
+            write!(f, " sni={}", sni)?;
+        }
+        Ok(())
     }
 }
 
@@ -237,7 +254,7 @@ impl fmt::Display for LogEvent {
 /// 룰 매칭으로 생성된 알림 이벤트
 ///
 /// 탐지 규칙에 매칭되어 보안 알림이 발생했을 때 생성됩니다.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertEvent {
     /// 이벤트 고유 ID
     pub id: String,
@@ -324,7 +341,7 @@ impl fmt::Display for AlertEvent {
 /// 실행된 액션 이벤트 (컨테이너 격리 등)
 ///
 /// 알림에 대한 대응 조치가 실행되었을 때 생성됩니다.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionEvent {
     /// 이벤트 고유 ID
     pub id: String,
@@ -336,6 +353,8 @@ pub struct ActionEvent {
     pub target: String,
     /// 성공 여부
     pub success: bool,
+    /// 드라이런 모드에서 실제 실행 없이 시뮬레이션된 액션인지 여부
+    pub simulated: bool,
 }
 
 impl ActionEvent {
@@ -347,6 +366,7 @@ impl ActionEvent {
             action_type: action_type.into(),
             target: target.into(),
             success,
+            simulated: false,
         }
     }
 
@@ -363,8 +383,15 @@ impl ActionEvent {
             action_type: action_type.into(),
             target: target.into(),
             success,
+            simulated: false,
         }
     }
+
+    /// 드라이런 모드에서 시뮬레이션된 액션으로 표시합니다.
+    pub fn mark_simulated(mut self) -> Self {
+        self.simulated = true;
+        self
+    }
 }
 
 impl Event for ActionEvent {
@@ -383,7 +410,11 @@ impl Event for ActionEvent {
 
 impl fmt::Display for ActionEvent {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let status = if self.success { "OK" } else { "FAILED" };
+        let status = match (self.success, self.simulated) {
+            (true, true) => "SIMULATED",
+            (true, false) => "OK",
+            (false, _) => "FAILED",
+        };
         write!(
             f,
             "ActionEvent[{}] type={} target={} status={}",
@@ -499,6 +530,24 @@ mod tests {
         assert!(display.contains("192.168.1.1"));
         assert!(display.contains("10.0.0.1"));
         assert!(display.contains("PacketEvent"));
+        assert!(!display.contains("sni="));
+    }
+
+    #[test]
+    fn packet_event_sni_display_and_serde() {
+        let event = PacketEvent::new(sample_packet_info(), Bytes::new()).with_sni("example.com");
+        assert!(event.to_string().ends_with(" sni=example.com"));
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["sni"], "example.com");
+
+        // SNI가 없는 이벤트는 필드를 생략하고, 필드가 없는 입력도 역직렬화됩니다
+        let mut json =
+            serde_json::to_value(PacketEvent::new(sample_packet_info(), Bytes::new())).unwrap();
+        assert!(json.get("sni").is_none());
+        json.as_object_mut().unwrap().remove("sni");
+        let parsed: PacketEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.sni, None);
     }
 
     #[test]
@@ -559,6 +608,13 @@ mod tests {
         assert!(event.to_string().contains("FAILED"));
     }
 
+    #[test]
+    fn action_event_display_simulated() {
+        let event = ActionEvent::new("container_isolate", "abc", true).mark_simulated();
+        assert!(event.simulated);
+        assert!(event.to_string().contains("SIMULATED"));
+    }
+
     #[test]
     fn events_are_send_sync() {
         fn assert_send_sync<T: Send + Sync + 'static>() {}
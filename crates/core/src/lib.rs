@@ -1,11 +1,13 @@
 #![doc = include_str!("../README.md")]
 
+pub mod channel;
 pub mod config;
 pub mod error;
 pub mod event;
 pub mod metrics;
 pub mod pipeline;
 pub mod plugin;
+pub mod spill;
 pub mod types;
 
 // --- 주요 타입 re-export ---
@@ -20,11 +22,17 @@ pub use error::{
 // 설정
 pub use config::IronpostConfig;
 
+// 계측 채널
+pub use channel::{InstrumentedLink, LinkOptions, OverflowPolicy, instrumented_channel};
+
+// 디스크 스필 큐
+pub use spill::SpillQueue;
+
 // 이벤트
 pub use event::{
     ActionEvent, AlertEvent, EVENT_TYPE_ACTION, EVENT_TYPE_ALERT, EVENT_TYPE_LOG,
     EVENT_TYPE_PACKET, EVENT_TYPE_SCAN, Event, EventMetadata, LogEvent, MODULE_CONTAINER_GUARD,
-    MODULE_EBPF, MODULE_LOG_PIPELINE, MODULE_SBOM_SCANNER, PacketEvent,
+    MODULE_DAEMON, MODULE_EBPF, MODULE_LOG_PIPELINE, MODULE_SBOM_SCANNER, PacketEvent,
 };
 
 // 파이프라인 trait
@@ -33,7 +41,10 @@ pub use pipeline::{
 };
 
 // 플러그인 시스템
-pub use plugin::{DynPlugin, Plugin, PluginInfo, PluginRegistry, PluginState, PluginType};
+pub use plugin::{
+    ChannelUsage, DynPlugin, Plugin, PluginInfo, PluginRegistry, PluginState, PluginType,
+    ResourceUsage,
+};
 
 // 도메인 타입
 pub use types::{Alert, ContainerInfo, LogEntry, PacketInfo, Severity, Vulnerability};
@@ -42,6 +42,24 @@ pub const LABEL_ECOSYSTEM: &str = "ecosystem";
 /// 결과 레이블 키 (success, failure)
 pub const LABEL_RESULT: &str = "result";
 
+/// 채널 레이블 키 (alerts, raw_logs, ...)
+pub const LABEL_CHANNEL: &str = "channel";
+
+/// 억제 사유 레이블 키 (duplicate, rate_limit)
+pub const LABEL_REASON: &str = "reason";
+
+/// eBPF 맵 이름 레이블 키 (BLOCKLIST, EVENTS, ...)
+pub const LABEL_MAP: &str = "map";
+
+/// 네트워크 인터페이스 레이블 키 (eth0, ens5, ...)
+pub const LABEL_INTERFACE: &str = "interface";
+
+/// 알림 싱크 이름 레이블 키 (`log_pipeline.sinks[].name`)
+pub const LABEL_SINK: &str = "sink";
+
+/// 출력 대상 레이블 키 (elasticsearch)
+pub const LABEL_OUTPUT: &str = "output";
+
 // ─── eBPF Engine 메트릭 ────────────────────────────────────────────
 
 /// eBPF: 처리된 전체 패킷 수 (counter)
@@ -50,6 +68,9 @@ pub const EBPF_PACKETS_TOTAL: &str = "ironpost_ebpf_packets_total";
 /// eBPF: 차단된 패킷 수 (counter)
 pub const EBPF_PACKETS_BLOCKED_TOTAL: &str = "ironpost_ebpf_packets_blocked_total";
 
+/// eBPF: 관찰 전용 모드에서 차단 판정을 받았지만 통과시킨 패킷 수 (counter)
+pub const EBPF_PACKETS_WOULD_BLOCK_TOTAL: &str = "ironpost_ebpf_packets_would_block_total";
+
 /// eBPF: 전송 바이트 수 (counter)
 pub const EBPF_BYTES_TOTAL: &str = "ironpost_ebpf_bytes_total";
 
@@ -60,12 +81,45 @@ pub const EBPF_XDP_PROCESSING_DURATION_SECONDS: &str =
 /// eBPF: 프로토콜별 패킷 수 (counter, label: protocol)
 pub const EBPF_PROTOCOL_PACKETS_TOTAL: &str = "ironpost_ebpf_protocol_packets_total";
 
+/// eBPF: 프로토콜별 바이트 수 (counter, label: protocol)
+pub const EBPF_PROTOCOL_BYTES_TOTAL: &str = "ironpost_ebpf_protocol_bytes_total";
+
+/// eBPF: 프로토콜별 드롭된 패킷 수 (counter, label: protocol)
+pub const EBPF_PROTOCOL_DROPS_TOTAL: &str = "ironpost_ebpf_protocol_drops_total";
+
+/// eBPF: 프로토콜별 관찰 전용 모드에서 드롭될 뻔한 패킷 수 (counter, label: protocol)
+pub const EBPF_PROTOCOL_WOULD_DROPS_TOTAL: &str = "ironpost_ebpf_protocol_would_drops_total";
+
 /// eBPF: 초당 패킷 처리량 (gauge)
 pub const EBPF_PACKETS_PER_SECOND: &str = "ironpost_ebpf_packets_per_second";
 
 /// eBPF: 초당 비트 처리량 (gauge)
 pub const EBPF_BITS_PER_SECOND: &str = "ironpost_ebpf_bits_per_second";
 
+/// eBPF: 인터페이스별 수신 패킷 수 (counter, label: interface)
+pub const EBPF_INTERFACE_PACKETS_TOTAL: &str = "ironpost_ebpf_interface_packets_total";
+
+/// eBPF: 인터페이스별 수신 바이트 수 (counter, label: interface)
+pub const EBPF_INTERFACE_BYTES_TOTAL: &str = "ironpost_ebpf_interface_bytes_total";
+
+/// eBPF: 인터페이스별 드롭된 패킷 수 (counter, label: interface)
+pub const EBPF_INTERFACE_DROPS_TOTAL: &str = "ironpost_ebpf_interface_drops_total";
+
+/// eBPF: 인터페이스별 초당 패킷 수 (gauge, label: interface)
+pub const EBPF_INTERFACE_PACKETS_PER_SECOND: &str = "ironpost_ebpf_interface_packets_per_second";
+
+/// eBPF: 인터페이스별 초당 비트 수 (gauge, label: interface)
+pub const EBPF_INTERFACE_BITS_PER_SECOND: &str = "ironpost_ebpf_interface_bits_per_second";
+
+/// eBPF: 맵에 반영된 엔트리 수 (gauge, label: map)
+pub const EBPF_MAP_ENTRIES: &str = "ironpost_ebpf_map_entries";
+
+/// eBPF: 맵 최대 엔트리 수 (gauge, label: map)
+pub const EBPF_MAP_CAPACITY: &str = "ironpost_ebpf_map_capacity";
+
+/// eBPF: RingBuf가 가득 차 버려진 레코드 수 (counter, label: map)
+pub const EBPF_RINGBUF_DROPS_TOTAL: &str = "ironpost_ebpf_ringbuf_drops_total";
+
 // ─── Log Pipeline 메트릭 ────────────────────────────────────────────
 
 /// Log Pipeline: 수집된 전체 로그 수 (counter)
@@ -83,6 +137,17 @@ pub const LOG_PIPELINE_RULE_MATCHES_TOTAL: &str = "ironpost_log_pipeline_rule_ma
 /// Log Pipeline: 전송된 알림 수 (counter)
 pub const LOG_PIPELINE_ALERTS_SENT_TOTAL: &str = "ironpost_log_pipeline_alerts_sent_total";
 
+/// Log Pipeline: 억제 목록으로 제외된 규칙 매칭 수 (counter)
+pub const LOG_PIPELINE_ALERTS_SUPPRESSED_TOTAL: &str =
+    "ironpost_log_pipeline_alerts_suppressed_total";
+
+/// Log Pipeline: 알림 싱크 전달 결과 수 (counter, labels: sink, result = sent/failed/dropped/deferred)
+pub const LOG_PIPELINE_SINK_ALERTS_TOTAL: &str = "ironpost_log_pipeline_sink_alerts_total";
+
+/// Log Pipeline: 출력 문서 처리 결과 수 (counter, labels: output, result = indexed/sent/stored/failed/dropped)
+pub const LOG_PIPELINE_OUTPUT_DOCUMENTS_TOTAL: &str =
+    "ironpost_log_pipeline_output_documents_total";
+
 /// Log Pipeline: 로그 처리 지연 시간 (histogram, 초)
 pub const LOG_PIPELINE_PROCESSING_DURATION_SECONDS: &str =
     "ironpost_log_pipeline_processing_duration_seconds";
@@ -93,6 +158,15 @@ pub const LOG_PIPELINE_BUFFER_SIZE: &str = "ironpost_log_pipeline_buffer_size";
 /// Log Pipeline: 드롭된 로그 수 (counter)
 pub const LOG_PIPELINE_LOGS_DROPPED_TOTAL: &str = "ironpost_log_pipeline_logs_dropped_total";
 
+/// Log Pipeline: 디스크 버퍼로 넘긴 로그 수 (counter)
+pub const LOG_PIPELINE_LOGS_SPILLED_TOTAL: &str = "ironpost_log_pipeline_logs_spilled_total";
+
+/// Log Pipeline: 디스크 버퍼에 남은 데이터 크기 (gauge, 바이트)
+pub const LOG_PIPELINE_DISK_BUFFER_BYTES: &str = "ironpost_log_pipeline_disk_buffer_bytes";
+
+/// Log Pipeline: 내장 로그 저장소 크기 (gauge, 바이트)
+pub const LOG_PIPELINE_LOG_STORE_BYTES: &str = "ironpost_log_pipeline_log_store_bytes";
+
 // ─── Container Guard 메트릭 ─────────────────────────────────────────
 
 /// Container Guard: 모니터링 중인 컨테이너 수 (gauge)
@@ -147,6 +221,34 @@ pub const DAEMON_PLUGINS_REGISTERED: &str = "ironpost_daemon_plugins_registered"
 /// Daemon: 빌드 정보 (gauge, 항상 1, labels: version, commit, rust_version)
 pub const DAEMON_BUILD_INFO: &str = "ironpost_daemon_build_info";
 
+/// Daemon: 모듈별 실행 중인 태스크 수 (gauge, labels: module)
+pub const DAEMON_MODULE_TASKS: &str = "ironpost_daemon_module_tasks";
+
+/// Daemon: 모듈별 채널 적체 메시지 수 (gauge, labels: module, channel)
+pub const DAEMON_MODULE_CHANNEL_DEPTH: &str = "ironpost_daemon_module_channel_depth";
+
+/// Daemon: 모듈별 추정 메모리 사용량 (gauge, 바이트, labels: module)
+pub const DAEMON_MODULE_MEMORY_BYTES: &str = "ironpost_daemon_module_memory_bytes";
+
+/// Daemon: tokio 런타임에서 살아있는 태스크 수 (gauge)
+pub const DAEMON_RUNTIME_ALIVE_TASKS: &str = "ironpost_daemon_runtime_alive_tasks";
+
+/// Daemon: 프로세스 누적 CPU 시간 (gauge, 초, Linux 전용)
+pub const DAEMON_PROCESS_CPU_SECONDS: &str = "ironpost_daemon_process_cpu_seconds";
+
+/// Daemon: 프로세스 상주 메모리 (gauge, 바이트, Linux 전용)
+pub const DAEMON_PROCESS_RESIDENT_MEMORY_BYTES: &str =
+    "ironpost_daemon_process_resident_memory_bytes";
+
+/// Daemon: 알림 게이트에서 억제된 알림 수 (counter, labels: reason)
+pub const DAEMON_ALERTS_SUPPRESSED_TOTAL: &str = "ironpost_daemon_alerts_suppressed_total";
+
+/// Daemon: 채널 링크에서 버려진 메시지 수 (counter, labels: channel, reason)
+pub const DAEMON_CHANNEL_DROPPED_TOTAL: &str = "ironpost_daemon_channel_dropped_total";
+
+/// Daemon: 채널 링크에서 디스크로 넘긴 메시지 수 (counter, labels: channel)
+pub const DAEMON_CHANNEL_SPILLED_TOTAL: &str = "ironpost_daemon_channel_spilled_total";
+
 // ─── 히스토그램 버킷 정의 ────────────────────────────────────────────
 
 /// 로그 처리 지연 시간 히스토그램 버킷 (초)
@@ -182,6 +284,10 @@ pub fn describe_all() {
         EBPF_PACKETS_BLOCKED_TOTAL,
         "Total number of packets blocked (XDP_DROP) by eBPF"
     );
+    describe_counter!(
+        EBPF_PACKETS_WOULD_BLOCK_TOTAL,
+        "Packets that matched a drop decision but were passed in observe-only mode"
+    );
     describe_counter!(EBPF_BYTES_TOTAL, "Total bytes processed by eBPF XDP");
     describe_histogram!(
         EBPF_XDP_PROCESSING_DURATION_SECONDS,
@@ -191,11 +297,49 @@ pub fn describe_all() {
         EBPF_PROTOCOL_PACKETS_TOTAL,
         "Packets processed per protocol (TCP, UDP, ICMP, other)"
     );
+    describe_counter!(
+        EBPF_PROTOCOL_BYTES_TOTAL,
+        "Bytes processed per protocol (TCP, UDP, ICMP, other)"
+    );
+    describe_counter!(
+        EBPF_PROTOCOL_DROPS_TOTAL,
+        "Packets dropped per protocol (TCP, UDP, ICMP, other)"
+    );
+    describe_counter!(
+        EBPF_PROTOCOL_WOULD_DROPS_TOTAL,
+        "Packets that would have been dropped per protocol in observe-only mode"
+    );
     describe_gauge!(
         EBPF_PACKETS_PER_SECOND,
         "Current packet processing rate (packets/sec)"
     );
     describe_gauge!(EBPF_BITS_PER_SECOND, "Current throughput rate (bits/sec)");
+    describe_counter!(
+        EBPF_INTERFACE_PACKETS_TOTAL,
+        "Packets received per attached interface"
+    );
+    describe_counter!(
+        EBPF_INTERFACE_BYTES_TOTAL,
+        "Bytes received per attached interface"
+    );
+    describe_counter!(
+        EBPF_INTERFACE_DROPS_TOTAL,
+        "Packets dropped per attached interface"
+    );
+    describe_gauge!(
+        EBPF_INTERFACE_PACKETS_PER_SECOND,
+        "Current packet rate per attached interface (packets/sec)"
+    );
+    describe_gauge!(
+        EBPF_INTERFACE_BITS_PER_SECOND,
+        "Current throughput per attached interface (bits/sec)"
+    );
+    describe_gauge!(EBPF_MAP_ENTRIES, "Entries currently stored per eBPF map");
+    describe_gauge!(EBPF_MAP_CAPACITY, "Maximum entries per eBPF map");
+    describe_counter!(
+        EBPF_RINGBUF_DROPS_TOTAL,
+        "Records dropped because an eBPF ring buffer was full"
+    );
 
     // Log Pipeline
     describe_counter!(
@@ -218,6 +362,18 @@ pub fn describe_all() {
         LOG_PIPELINE_ALERTS_SENT_TOTAL,
         "Total number of alert events sent to downstream consumers"
     );
+    describe_counter!(
+        LOG_PIPELINE_ALERTS_SUPPRESSED_TOTAL,
+        "Total number of rule matches dropped by the suppression list"
+    );
+    describe_counter!(
+        LOG_PIPELINE_SINK_ALERTS_TOTAL,
+        "Alerts handled by each alert sink, by result (sent, failed, dropped, deferred)"
+    );
+    describe_counter!(
+        LOG_PIPELINE_OUTPUT_DOCUMENTS_TOTAL,
+        "Log entries and alerts handled by each output, by result (indexed, sent, failed, dropped)"
+    );
     describe_histogram!(
         LOG_PIPELINE_PROCESSING_DURATION_SECONDS,
         "Time to process a single log batch in seconds"
@@ -230,6 +386,18 @@ pub fn describe_all() {
         LOG_PIPELINE_LOGS_DROPPED_TOTAL,
         "Total number of log entries dropped due to buffer overflow"
     );
+    describe_counter!(
+        LOG_PIPELINE_LOGS_SPILLED_TOTAL,
+        "Total number of log entries spilled to the disk buffer"
+    );
+    describe_gauge!(
+        LOG_PIPELINE_DISK_BUFFER_BYTES,
+        "Bytes of unprocessed log entries in the disk buffer"
+    );
+    describe_gauge!(
+        LOG_PIPELINE_LOG_STORE_BYTES,
+        "Bytes of log entries kept in the embedded log store"
+    );
 
     // Container Guard
     describe_gauge!(
@@ -289,6 +457,42 @@ pub fn describe_all() {
         DAEMON_BUILD_INFO,
         "Build information (always 1, with version/commit labels)"
     );
+    describe_gauge!(
+        DAEMON_MODULE_TASKS,
+        "Number of running background tasks per module"
+    );
+    describe_gauge!(
+        DAEMON_MODULE_CHANNEL_DEPTH,
+        "Number of queued messages per module channel"
+    );
+    describe_gauge!(
+        DAEMON_MODULE_MEMORY_BYTES,
+        "Estimated memory held by each module in bytes"
+    );
+    describe_gauge!(
+        DAEMON_RUNTIME_ALIVE_TASKS,
+        "Number of alive tasks in the tokio runtime"
+    );
+    describe_gauge!(
+        DAEMON_PROCESS_CPU_SECONDS,
+        "Total user and system CPU time consumed by the daemon in seconds"
+    );
+    describe_gauge!(
+        DAEMON_PROCESS_RESIDENT_MEMORY_BYTES,
+        "Resident memory of the daemon process in bytes"
+    );
+    describe_counter!(
+        DAEMON_ALERTS_SUPPRESSED_TOTAL,
+        "Total number of alerts suppressed by the daemon alert gate"
+    );
+    describe_counter!(
+        DAEMON_CHANNEL_DROPPED_TOTAL,
+        "Total number of messages dropped by inter-module channel links"
+    );
+    describe_counter!(
+        DAEMON_CHANNEL_SPILLED_TOTAL,
+        "Total number of messages spilled to disk by inter-module channel links"
+    );
 }
 
 #[cfg(test)]
@@ -299,19 +503,37 @@ mod tests {
     const ALL_METRIC_NAMES: &[&str] = &[
         EBPF_PACKETS_TOTAL,
         EBPF_PACKETS_BLOCKED_TOTAL,
+        EBPF_PACKETS_WOULD_BLOCK_TOTAL,
         EBPF_BYTES_TOTAL,
         EBPF_XDP_PROCESSING_DURATION_SECONDS,
         EBPF_PROTOCOL_PACKETS_TOTAL,
+        EBPF_PROTOCOL_BYTES_TOTAL,
+        EBPF_PROTOCOL_DROPS_TOTAL,
+        EBPF_PROTOCOL_WOULD_DROPS_TOTAL,
         EBPF_PACKETS_PER_SECOND,
         EBPF_BITS_PER_SECOND,
+        EBPF_INTERFACE_PACKETS_TOTAL,
+        EBPF_INTERFACE_BYTES_TOTAL,
+        EBPF_INTERFACE_DROPS_TOTAL,
+        EBPF_INTERFACE_PACKETS_PER_SECOND,
+        EBPF_INTERFACE_BITS_PER_SECOND,
+        EBPF_MAP_ENTRIES,
+        EBPF_MAP_CAPACITY,
+        EBPF_RINGBUF_DROPS_TOTAL,
         LOG_PIPELINE_LOGS_COLLECTED_TOTAL,
         LOG_PIPELINE_LOGS_PROCESSED_TOTAL,
         LOG_PIPELINE_PARSE_ERRORS_TOTAL,
         LOG_PIPELINE_RULE_MATCHES_TOTAL,
         LOG_PIPELINE_ALERTS_SENT_TOTAL,
+        LOG_PIPELINE_ALERTS_SUPPRESSED_TOTAL,
+        LOG_PIPELINE_SINK_ALERTS_TOTAL,
+        LOG_PIPELINE_OUTPUT_DOCUMENTS_TOTAL,
         LOG_PIPELINE_PROCESSING_DURATION_SECONDS,
         LOG_PIPELINE_BUFFER_SIZE,
         LOG_PIPELINE_LOGS_DROPPED_TOTAL,
+        LOG_PIPELINE_LOGS_SPILLED_TOTAL,
+        LOG_PIPELINE_DISK_BUFFER_BYTES,
+        LOG_PIPELINE_LOG_STORE_BYTES,
         CONTAINER_GUARD_MONITORED_CONTAINERS,
         CONTAINER_GUARD_POLICY_VIOLATIONS_TOTAL,
         CONTAINER_GUARD_ISOLATIONS_TOTAL,
@@ -326,6 +548,15 @@ mod tests {
         DAEMON_UPTIME_SECONDS,
         DAEMON_PLUGINS_REGISTERED,
         DAEMON_BUILD_INFO,
+        DAEMON_MODULE_TASKS,
+        DAEMON_MODULE_CHANNEL_DEPTH,
+        DAEMON_MODULE_MEMORY_BYTES,
+        DAEMON_RUNTIME_ALIVE_TASKS,
+        DAEMON_PROCESS_CPU_SECONDS,
+        DAEMON_PROCESS_RESIDENT_MEMORY_BYTES,
+        DAEMON_ALERTS_SUPPRESSED_TOTAL,
+        DAEMON_CHANNEL_DROPPED_TOTAL,
+        DAEMON_CHANNEL_SPILLED_TOTAL,
     ];
 
     #[test]
@@ -340,13 +571,12 @@ mod tests {
     }
 
     #[test]
-    fn all_metrics_have_29_entries() {
-        // Design document mentions 28 but actually defines 29 metrics
-        // (7 eBPF + 8 Log Pipeline + 6 Container Guard + 5 SBOM Scanner + 3 Daemon)
+    fn all_metrics_have_56_entries() {
+        // (19 eBPF + 14 Log Pipeline + 6 Container Guard + 5 SBOM Scanner + 12 Daemon)
         assert_eq!(
             ALL_METRIC_NAMES.len(),
-            29,
-            "Expected 29 metrics (7 eBPF + 8 Log Pipeline + 6 Container Guard + 5 SBOM + 3 Daemon)"
+            56,
+            "Expected 56 metrics (19 eBPF + 14 Log Pipeline + 6 Container Guard + 5 SBOM + 12 Daemon)"
         );
     }
 
@@ -366,6 +596,12 @@ mod tests {
             LABEL_ACTION,
             LABEL_ECOSYSTEM,
             LABEL_RESULT,
+            LABEL_CHANNEL,
+            LABEL_REASON,
+            LABEL_MAP,
+            LABEL_INTERFACE,
+            LABEL_SINK,
+            LABEL_OUTPUT,
         ];
         for label in &labels {
             assert_eq!(
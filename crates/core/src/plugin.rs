@@ -101,6 +101,62 @@ impl fmt::Display for PluginState {
     }
 }
 
+// ─── ResourceUsage ───────────────────────────────────────────────────
+
+/// 채널 적체 현황
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelUsage {
+    /// 채널 이름 (예: `"alerts"`)
+    pub name: String,
+    /// 대기 중인 메시지 수
+    pub depth: usize,
+    /// 최대 용량
+    pub capacity: usize,
+}
+
+impl ChannelUsage {
+    /// bounded mpsc 송신측에서 적체 현황을 계산합니다.
+    pub fn from_sender<T>(name: &str, tx: &tokio::sync::mpsc::Sender<T>) -> Self {
+        let capacity = tx.max_capacity();
+        Self {
+            name: name.to_owned(),
+            depth: capacity.saturating_sub(tx.capacity()),
+            capacity,
+        }
+    }
+}
+
+/// 플러그인 리소스 사용량 스냅샷
+///
+/// 데몬이 서브시스템별 리소스 소비를 구분하기 위해 주기적으로 수집합니다.
+/// 메모리는 버퍼/채널에 적재된 항목 수 기반의 추정치입니다.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// 실행 중인 백그라운드 태스크 수
+    pub tasks: usize,
+    /// 채널별 적체 현황
+    pub channels: Vec<ChannelUsage>,
+    /// 추정 메모리 사용량 (바이트)
+    pub memory_bytes: u64,
+}
+
+impl ResourceUsage {
+    /// 종료되지 않은 태스크 수를 셉니다.
+    pub fn live_tasks(tasks: &[tokio::task::JoinHandle<()>]) -> usize {
+        tasks.iter().filter(|t| !t.is_finished()).count()
+    }
+
+    /// 항목 수와 항목 크기로 메모리 사용량을 추정합니다.
+    pub fn estimate_bytes(count: usize, item_size: usize) -> u64 {
+        u64::try_from(count.saturating_mul(item_size)).unwrap_or(u64::MAX)
+    }
+
+    /// 모든 채널의 적체 메시지 합계를 반환합니다.
+    pub fn channel_depth(&self) -> usize {
+        self.channels.iter().map(|c| c.depth).sum()
+    }
+}
+
 // ─── Plugin Trait ────────────────────────────────────────────────────
 
 /// 모든 모듈이 구현하는 플러그인 trait
@@ -167,6 +223,13 @@ pub trait Plugin: Send + Sync {
 
     /// 플러그인의 건강 상태를 확인합니다.
     fn health_check(&self) -> impl Future<Output = HealthStatus> + Send;
+
+    /// 플러그인의 리소스 사용량을 반환합니다.
+    ///
+    /// 기본 구현은 빈 사용량을 반환합니다.
+    fn resource_usage(&self) -> impl Future<Output = ResourceUsage> + Send {
+        async { ResourceUsage::default() }
+    }
 }
 
 // ─── DynPlugin Trait ─────────────────────────────────────────────────
@@ -194,6 +257,9 @@ pub trait DynPlugin: Send + Sync {
 
     /// 플러그인의 건강 상태를 확인합니다.
     fn health_check(&self) -> BoxFuture<'_, HealthStatus>;
+
+    /// 플러그인의 리소스 사용량을 반환합니다.
+    fn resource_usage(&self) -> BoxFuture<'_, ResourceUsage>;
 }
 
 /// Plugin을 구현한 타입은 자동으로 DynPlugin도 구현됩니다.
@@ -221,6 +287,10 @@ impl<T: Plugin> DynPlugin for T {
     fn health_check(&self) -> BoxFuture<'_, HealthStatus> {
         Box::pin(Plugin::health_check(self))
     }
+
+    fn resource_usage(&self) -> BoxFuture<'_, ResourceUsage> {
+        Box::pin(Plugin::resource_usage(self))
+    }
 }
 
 // ─── PluginRegistry ──────────────────────────────────────────────────
@@ -328,8 +398,24 @@ impl PluginRegistry {
     /// 개별 플러그인 정지 실패 시에도 나머지 플러그인의 정지를 계속합니다.
     /// 모든 에러를 수집하여 반환합니다.
     pub async fn stop_all(&mut self) -> Result<(), IronpostError> {
+        self.stop_matching(|_| true).await
+    }
+
+    /// 조건을 만족하는 플러그인만 등록 순서대로 정지합니다.
+    ///
+    /// 단계별 종료(생산자 → 드레인 → 소비자)에 사용합니다.
+    /// `Running` 상태가 아닌 플러그인은 건너뛰므로, 여러 단계에서 호출해도
+    /// 이미 정지된 플러그인을 다시 정지하지 않습니다.
+    /// 개별 플러그인 정지 실패 시에도 나머지 플러그인의 정지를 계속합니다.
+    pub async fn stop_matching<F>(&mut self, predicate: F) -> Result<(), IronpostError>
+    where
+        F: Fn(&PluginInfo) -> bool,
+    {
         let mut errors = Vec::new();
         for plugin in &mut self.plugins {
+            if !predicate(plugin.info()) || plugin.state() != PluginState::Running {
+                continue;
+            }
             if let Err(e) = plugin.stop().await {
                 errors.push(format!("{}: {}", plugin.info().name, e));
             }
@@ -362,6 +448,16 @@ impl PluginRegistry {
         }
         statuses
     }
+
+    /// 모든 플러그인의 리소스 사용량을 조회합니다.
+    pub async fn resource_usage_all(&self) -> Vec<(String, ResourceUsage)> {
+        let mut usages = Vec::new();
+        for plugin in &self.plugins {
+            let name = plugin.info().name.clone();
+            usages.push((name, plugin.resource_usage().await));
+        }
+        usages
+    }
 }
 
 impl Default for PluginRegistry {
@@ -861,6 +957,40 @@ mod tests {
         assert_eq!(registry.get("ok").unwrap().state(), PluginState::Stopped);
     }
 
+    #[tokio::test]
+    async fn registry_stop_matching_stops_only_selected() {
+        let mut registry = PluginRegistry::new();
+        registry
+            .register(Box::new(MockPlugin::new("producer", PluginType::Detector)))
+            .unwrap();
+        registry
+            .register(Box::new(MockPlugin::new("consumer", PluginType::Enforcer)))
+            .unwrap();
+
+        registry.init_all().await.unwrap();
+        registry.start_all().await.unwrap();
+        registry
+            .stop_matching(|info| info.plugin_type != PluginType::Enforcer)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            registry.get("producer").unwrap().state(),
+            PluginState::Stopped
+        );
+        assert_eq!(
+            registry.get("consumer").unwrap().state(),
+            PluginState::Running
+        );
+
+        // 이미 정지된 플러그인은 건너뜀
+        registry.stop_all().await.unwrap();
+        assert_eq!(
+            registry.get("consumer").unwrap().state(),
+            PluginState::Stopped
+        );
+    }
+
     #[tokio::test]
     async fn registry_health_check_all() {
         let mut registry = PluginRegistry::new();
@@ -890,6 +1020,54 @@ mod tests {
         assert_eq!(*state2, PluginState::Created);
     }
 
+    #[tokio::test]
+    async fn registry_resource_usage_all_defaults_to_empty() {
+        let mut registry = PluginRegistry::new();
+        registry
+            .register(Box::new(MockPlugin::new("p1", PluginType::Detector)))
+            .unwrap();
+
+        let usages = registry.resource_usage_all().await;
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].0, "p1");
+        assert_eq!(usages[0].1, ResourceUsage::default());
+    }
+
+    #[tokio::test]
+    async fn channel_usage_from_sender_reports_depth() {
+        let (tx, _rx) = tokio::sync::mpsc::channel::<u32>(8);
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+
+        let usage = ChannelUsage::from_sender("test", &tx);
+        assert_eq!(usage.name, "test");
+        assert_eq!(usage.depth, 2);
+        assert_eq!(usage.capacity, 8);
+    }
+
+    #[test]
+    fn resource_usage_estimate_and_depth() {
+        let usage = ResourceUsage {
+            tasks: 1,
+            channels: vec![
+                ChannelUsage {
+                    name: "a".to_owned(),
+                    depth: 3,
+                    capacity: 10,
+                },
+                ChannelUsage {
+                    name: "b".to_owned(),
+                    depth: 4,
+                    capacity: 10,
+                },
+            ],
+            memory_bytes: ResourceUsage::estimate_bytes(7, 100),
+        };
+        assert_eq!(usage.channel_depth(), 7);
+        assert_eq!(usage.memory_bytes, 700);
+        assert_eq!(ResourceUsage::estimate_bytes(usize::MAX, 2), u64::MAX);
+    }
+
     #[tokio::test]
     async fn registry_full_lifecycle() {
         let mut registry = PluginRegistry::new();
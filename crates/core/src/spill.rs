@@ -0,0 +1,751 @@
+//! 디스크 스필 큐 — 메모리 버퍼를 넘친 레코드를 세그먼트 파일에 순서대로 적재
+//!
+//! 로그 파이프라인의 디스크 오버플로 버퍼(`[log_pipeline.disk_buffer]`)와 계측 채널의
+//! `Spill` 정책([`crate::channel`])이 같은 형식과 구현을 사용합니다. 레코드 본문은
+//! 호출자가 인코딩한 바이트열입니다.
+//!
+//! [`SpillQueue`]는 디렉토리 하나에 다음 파일을 둡니다.
+//! - `segment-<seq>.log`: 레코드를 순서대로 이어 붙인 세그먼트 파일
+//! - `index.json`: 다음에 읽을 위치 (`{"segment": <seq>, "offset": <바이트>}`)
+//!
+//! 레코드 형식 (정수는 리틀 엔디언):
+//! ```text
+//! u32 본문 길이 | u32 CRC32(본문) | 본문
+//! ```
+//!
+//! 다 읽은 세그먼트는 삭제하고, 읽기 위치는 배치를 꺼낼 때마다 인덱스에 기록합니다.
+//! 다시 열면 인덱스 위치부터 남은 레코드를 읽습니다. 쓰기 도중 중단되어 잘린
+//! 레코드나 체크섬이 맞지 않는 레코드는 열 때 잘라냅니다.
+//!
+//! 파일 I/O는 모두 `spawn_blocking`에서 실행하므로 비동기 태스크에서 바로 호출할 수
+//! 있습니다.
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+/// 인덱스 파일 이름
+const INDEX_FILE: &str = "index.json";
+
+/// 레코드 헤더 크기 (본문 길이 + CRC32)
+const RECORD_HEADER_BYTES: u64 = 8;
+
+/// 레코드 본문 최대 크기 (손상된 길이 값으로 큰 버퍼를 할당하지 않도록 제한)
+pub const MAX_RECORD_BYTES: u32 = 64 * 1024 * 1024;
+
+// ─── SpillQueue ──────────────────────────────────────────────────────
+
+/// 디스크 스필 큐
+///
+/// 세그먼트 저장소를 `spawn_blocking`으로 감싼 비동기 핸들입니다. 레코드 수와
+/// 크기는 작업이 끝날 때마다 갱신되어 잠금 없이 조회할 수 있습니다.
+pub struct SpillQueue {
+    store: Arc<Mutex<SpillStore>>,
+    dir: PathBuf,
+    len: usize,
+    bytes: u64,
+}
+
+impl SpillQueue {
+    /// 스필 디렉토리를 열고, 이전 실행에서 남은 세그먼트를 복구합니다.
+    ///
+    /// `max_bytes`는 읽지 않은 데이터의 최대 크기, `segment_bytes`는 세그먼트 파일의
+    /// 최대 크기입니다.
+    ///
+    /// # Errors
+    /// 디렉토리 생성 또는 세그먼트 파일 읽기 실패
+    pub async fn open(
+        dir: impl Into<PathBuf>,
+        max_bytes: u64,
+        segment_bytes: u64,
+    ) -> io::Result<Self> {
+        let dir = dir.into();
+        let path = dir.clone();
+        let store =
+            tokio::task::spawn_blocking(move || SpillStore::open(path, max_bytes, segment_bytes))
+                .await
+                .map_err(join_error)??;
+
+        Ok(Self {
+            len: store.len,
+            bytes: store.bytes,
+            store: Arc::new(Mutex::new(store)),
+            dir,
+        })
+    }
+
+    /// 레코드 하나를 적재합니다.
+    ///
+    /// 읽지 않은 데이터가 `max_bytes`를 넘게 되면 적재하지 않고 `Ok(false)`를 반환합니다.
+    ///
+    /// # Errors
+    /// 레코드가 너무 크거나 세그먼트 파일 생성/쓰기 실패
+    pub async fn push(&mut self, record: Vec<u8>) -> io::Result<bool> {
+        self.run(move |store| store.push(&record)).await?
+    }
+
+    /// 레코드를 순서대로 적재하고 적재한 수를 반환합니다.
+    ///
+    /// `max_bytes`에 도달하면 나머지는 적재하지 않습니다.
+    ///
+    /// # Errors
+    /// 첫 레코드부터 쓰지 못한 경우 (일부를 적재한 뒤의 실패는 적재한 수를 반환)
+    pub async fn push_batch(&mut self, records: Vec<Vec<u8>>) -> io::Result<usize> {
+        self.run(move |store| {
+            let mut stored = 0;
+            for record in &records {
+                match store.push(record) {
+                    Ok(true) => stored += 1,
+                    Ok(false) => break,
+                    Err(e) if stored == 0 => return Err(e),
+                    Err(e) => {
+                        tracing::warn!(dir = %store.dir.display(), error = %e, "spill write failed");
+                        break;
+                    }
+                }
+            }
+            Ok(stored)
+        })
+        .await?
+    }
+
+    /// 가장 오래된 레코드부터 최대 `max`개를 꺼냅니다.
+    ///
+    /// 읽을 수 없는 세그먼트의 남은 레코드는 버리고, (레코드, 버린 레코드 수)를 반환합니다.
+    ///
+    /// # Errors
+    /// 블로킹 태스크 실행 실패
+    pub async fn pop_batch(&mut self, max: usize) -> io::Result<(Vec<Vec<u8>>, usize)> {
+        self.run(move |store| {
+            let mut out = Vec::new();
+            let lost = store.pop_batch(max, &mut out);
+            (out, lost)
+        })
+        .await
+    }
+
+    /// 가장 오래된 세그먼트를 버리고 버린 레코드 수를 반환합니다.
+    ///
+    /// 쓰기 중인 세그먼트만 남았으면 버리지 않고 0을 반환합니다.
+    ///
+    /// # Errors
+    /// 블로킹 태스크 실행 실패
+    pub async fn drop_oldest_segment(&mut self) -> io::Result<usize> {
+        self.run(SpillStore::drop_oldest_segment).await
+    }
+
+    /// 모든 레코드와 세그먼트 파일을 삭제합니다.
+    ///
+    /// # Errors
+    /// 블로킹 태스크 실행 실패
+    pub async fn clear(&mut self) -> io::Result<()> {
+        self.run(SpillStore::clear).await
+    }
+
+    /// 비동기 컨텍스트 밖(`Drop` 등)에서 모든 세그먼트 파일을 삭제합니다.
+    ///
+    /// 다른 작업이 진행 중이면 아무것도 하지 않습니다.
+    pub fn clear_blocking(&mut self) {
+        if let Ok(mut store) = self.store.try_lock() {
+            store.clear();
+            self.len = 0;
+            self.bytes = 0;
+        }
+    }
+
+    /// 읽지 않은 레코드 수
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 읽지 않은 레코드가 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 읽지 않은 데이터 크기 (바이트, 레코드 헤더 포함)
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// 스필 디렉토리
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 저장소 작업을 블로킹 스레드에서 실행하고 레코드 수/크기를 갱신합니다.
+    async fn run<R, F>(&mut self, f: F) -> io::Result<R>
+    where
+        F: FnOnce(&mut SpillStore) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let store = Arc::clone(&self.store);
+        let (result, len, bytes) = tokio::task::spawn_blocking(move || {
+            let mut store = store.blocking_lock();
+            let result = f(&mut store);
+            (result, store.len, store.bytes)
+        })
+        .await
+        .map_err(join_error)?;
+        self.len = len;
+        self.bytes = bytes;
+        Ok(result)
+    }
+}
+
+fn join_error(e: tokio::task::JoinError) -> io::Error {
+    io::Error::other(format!("spawn_blocking failed: {e}"))
+}
+
+// ─── SpillStore ──────────────────────────────────────────────────────
+
+/// 다음에 읽을 위치
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SpillIndex {
+    segment: u64,
+    offset: u64,
+}
+
+/// 디스크 세그먼트
+#[derive(Debug)]
+struct Segment {
+    seq: u64,
+    /// 파일 크기 (마지막 유효 레코드 끝)
+    size: u64,
+    /// 아직 읽지 않은 레코드 수
+    records: usize,
+}
+
+/// 세그먼트 저장소 (동기 I/O, [`SpillQueue`]가 블로킹 스레드에서 호출)
+///
+/// 세그먼트는 오래된 것부터 `segments`에 있고, 쓰기는 항상 마지막 세그먼트에 합니다.
+/// 읽기는 첫 세그먼트의 `read_offset`부터 합니다.
+struct SpillStore {
+    dir: PathBuf,
+    max_bytes: u64,
+    segment_bytes: u64,
+    /// 읽지 않은 레코드가 남은 세그먼트 (오래된 순)
+    segments: VecDeque<Segment>,
+    /// 마지막 세그먼트에 대한 쓰기 핸들 (없으면 다음 쓰기에서 새 세그먼트를 엶)
+    writer: Option<File>,
+    /// 첫 세그먼트의 읽기 위치
+    read_offset: u64,
+    /// 다음 세그먼트 번호
+    next_seq: u64,
+    /// 읽지 않은 레코드 수
+    len: usize,
+    /// 읽지 않은 데이터 크기 (바이트)
+    bytes: u64,
+}
+
+impl SpillStore {
+    fn open(dir: PathBuf, max_bytes: u64, segment_bytes: u64) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+
+        let index = read_index(&dir);
+        let mut seqs = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            if let Some(seq) = entry.file_name().to_str().and_then(segment_seq) {
+                seqs.push(seq);
+            }
+        }
+        seqs.sort_unstable();
+
+        let mut store = Self {
+            dir,
+            max_bytes,
+            segment_bytes,
+            segments: VecDeque::new(),
+            writer: None,
+            read_offset: 0,
+            next_seq: seqs
+                .last()
+                .map_or(index.segment, |last| (last + 1).max(index.segment)),
+            len: 0,
+            bytes: 0,
+        };
+
+        for seq in seqs {
+            let path = store.segment_path(seq);
+            if seq < index.segment {
+                remove_segment(&path);
+                continue;
+            }
+            let start = if seq == index.segment {
+                index.offset
+            } else {
+                0
+            };
+            let (records, size) = scan_segment(&path, start)?;
+            if records == 0 {
+                remove_segment(&path);
+                continue;
+            }
+            if store.segments.is_empty() {
+                store.read_offset = start;
+            }
+            store.len += records;
+            store.bytes += size.saturating_sub(start);
+            store.segments.push_back(Segment { seq, size, records });
+        }
+
+        store.save_index();
+        Ok(store)
+    }
+
+    fn push(&mut self, body: &[u8]) -> io::Result<bool> {
+        let record = encode_frame(body)?;
+        let record_bytes = u64::try_from(record.len()).unwrap_or(u64::MAX);
+        if self.bytes.saturating_add(record_bytes) > self.max_bytes {
+            return Ok(false);
+        }
+
+        let roll = match (&self.writer, self.segments.back()) {
+            (Some(_), Some(segment)) => {
+                segment.size > 0 && segment.size + record_bytes > self.segment_bytes
+            }
+            _ => true,
+        };
+        if roll {
+            self.open_segment()?;
+        }
+        let (Some(writer), Some(segment)) = (self.writer.as_mut(), self.segments.back_mut()) else {
+            return Err(io::Error::other("spill queue has no open segment"));
+        };
+        if let Err(e) = writer.write_all(&record) {
+            // 잘린 레코드 뒤에 이어 쓰지 않도록 다음 쓰기는 새 세그먼트에서 시작
+            self.writer = None;
+            return Err(e);
+        }
+
+        segment.size += record_bytes;
+        segment.records += 1;
+        self.len += 1;
+        self.bytes += record_bytes;
+        Ok(true)
+    }
+
+    fn pop_batch(&mut self, max: usize, out: &mut Vec<Vec<u8>>) -> usize {
+        let mut remaining = max;
+        let mut lost = 0;
+        while remaining > 0 {
+            let Some(segment) = self.segments.front() else {
+                break;
+            };
+            let (seq, records, size) = (segment.seq, segment.records, segment.size);
+            let take = records.min(remaining);
+            let path = self.segment_path(seq);
+
+            let before = out.len();
+            let result = read_records(&path, self.read_offset, take, out);
+            let read = out.len() - before;
+            remaining -= read;
+            self.len -= read;
+            match result {
+                Ok(end) => {
+                    self.bytes = self.bytes.saturating_sub(end - self.read_offset);
+                    self.read_offset = end;
+                    if let Some(segment) = self.segments.front_mut() {
+                        segment.records -= read;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        path = %path.display(),
+                        error = %e,
+                        lost = records - read,
+                        "failed to read spill segment, skipping rest"
+                    );
+                    lost += records - read;
+                    self.len -= records - read;
+                    self.bytes = self.bytes.saturating_sub(size - self.read_offset);
+                    if let Some(segment) = self.segments.front_mut() {
+                        segment.records = 0;
+                    }
+                }
+            }
+            if self.segments.front().is_some_and(|s| s.records == 0) {
+                self.remove_front();
+            }
+        }
+        self.save_index();
+        lost
+    }
+
+    fn drop_oldest_segment(&mut self) -> usize {
+        if self.segments.len() < 2 {
+            return 0;
+        }
+        let Some(segment) = self.segments.front() else {
+            return 0;
+        };
+        let records = segment.records;
+        self.len -= records;
+        self.bytes = self.bytes.saturating_sub(segment.size - self.read_offset);
+        self.remove_front();
+        self.save_index();
+        records
+    }
+
+    fn clear(&mut self) {
+        while !self.segments.is_empty() {
+            self.remove_front();
+        }
+        self.writer = None;
+        self.len = 0;
+        self.bytes = 0;
+        self.save_index();
+    }
+
+    fn segment_path(&self, seq: u64) -> PathBuf {
+        self.dir.join(format!("segment-{seq:020}.log"))
+    }
+
+    /// 새 쓰기 세그먼트를 엽니다.
+    fn open_segment(&mut self) -> io::Result<()> {
+        let seq = self.next_seq;
+        let writer = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(self.segment_path(seq))?;
+        self.next_seq += 1;
+        self.writer = Some(writer);
+        self.segments.push_back(Segment {
+            seq,
+            size: 0,
+            records: 0,
+        });
+        Ok(())
+    }
+
+    /// 첫 세그먼트를 목록과 디스크에서 제거합니다.
+    fn remove_front(&mut self) {
+        if let Some(segment) = self.segments.pop_front() {
+            remove_segment(&self.segment_path(segment.seq));
+        }
+        self.read_offset = 0;
+        if self.segments.is_empty() {
+            // 쓰기 세그먼트까지 다 읽었으면 다음 쓰기는 새 세그먼트에서 시작
+            self.writer = None;
+        }
+    }
+
+    /// 읽기 위치를 인덱스 파일에 기록합니다 (임시 파일에 쓴 뒤 이름 변경).
+    fn save_index(&self) {
+        let index = match self.segments.front() {
+            Some(segment) => SpillIndex {
+                segment: segment.seq,
+                offset: self.read_offset,
+            },
+            None => SpillIndex {
+                segment: self.next_seq,
+                offset: 0,
+            },
+        };
+        let path = self.dir.join(INDEX_FILE);
+        let tmp = self.dir.join(format!("{INDEX_FILE}.tmp"));
+        let result = serde_json::to_vec(&index)
+            .map_err(io::Error::from)
+            .and_then(|data| fs::write(&tmp, data))
+            .and_then(|()| fs::rename(&tmp, &path));
+        if let Err(e) = result {
+            tracing::warn!(path = %path.display(), error = %e, "failed to write spill index");
+        }
+    }
+}
+
+/// 인덱스 파일을 읽습니다. 없거나 읽을 수 없으면 처음부터 읽습니다.
+fn read_index(dir: &Path) -> SpillIndex {
+    let path = dir.join(INDEX_FILE);
+    match fs::read(&path) {
+        Ok(data) => serde_json::from_slice(&data).unwrap_or_else(|e| {
+            tracing::warn!(path = %path.display(), error = %e, "invalid spill index, replaying all segments");
+            SpillIndex::default()
+        }),
+        Err(_) => SpillIndex::default(),
+    }
+}
+
+/// `segment-<seq>.log` 파일 이름에서 세그먼트 번호를 꺼냅니다.
+fn segment_seq(name: &str) -> Option<u64> {
+    name.strip_prefix("segment-")?
+        .strip_suffix(".log")?
+        .parse()
+        .ok()
+}
+
+fn remove_segment(path: &Path) {
+    if let Err(e) = fs::remove_file(path) {
+        tracing::debug!(path = %path.display(), error = %e, "failed to remove spill segment");
+    }
+}
+
+/// `start`부터 유효한 레코드 수와 마지막 유효 레코드 끝 위치를 셉니다.
+///
+/// 끝에 잘리거나 손상된 레코드가 있으면 파일을 그 앞까지 잘라냅니다.
+fn scan_segment(path: &Path, start: u64) -> io::Result<(usize, u64)> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let file_size = file.metadata()?.len();
+    if start >= file_size {
+        return Ok((0, start));
+    }
+
+    file.seek(SeekFrom::Start(start))?;
+    let mut reader = BufReader::new(&file);
+    let mut records = 0;
+    let mut end = start;
+    while end < file_size {
+        match read_frame(&mut reader) {
+            Ok(body) => {
+                records += 1;
+                end += RECORD_HEADER_BYTES + u64::try_from(body.len()).unwrap_or(u64::MAX);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    offset = end,
+                    error = %e,
+                    "truncating damaged spill segment"
+                );
+                drop(reader);
+                file.set_len(end)?;
+                break;
+            }
+        }
+    }
+    Ok((records, end))
+}
+
+/// `offset`부터 레코드 `count`개를 읽어 `out`에 추가하고 다음 읽기 위치를 반환합니다.
+fn read_records(path: &Path, offset: u64, count: usize, out: &mut Vec<Vec<u8>>) -> io::Result<u64> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut reader = BufReader::new(file);
+    let mut end = offset;
+    for _ in 0..count {
+        let body = read_frame(&mut reader)?;
+        end += RECORD_HEADER_BYTES + u64::try_from(body.len()).unwrap_or(u64::MAX);
+        out.push(body);
+    }
+    Ok(end)
+}
+
+/// 레코드 하나를 읽고 체크섬을 확인한 본문을 반환합니다.
+fn read_frame(reader: &mut impl Read) -> io::Result<Vec<u8>> {
+    let mut header = [0u8; 8];
+    reader.read_exact(&mut header)?;
+    let len = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let crc = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    if len > MAX_RECORD_BYTES {
+        return Err(invalid_data("record length exceeds limit"));
+    }
+    let mut body = vec![0u8; usize::try_from(len).unwrap_or(usize::MAX)];
+    reader.read_exact(&mut body)?;
+    if crc32fast::hash(&body) != crc {
+        return Err(invalid_data("record checksum mismatch"));
+    }
+    Ok(body)
+}
+
+/// 본문에 길이와 체크섬 헤더를 붙입니다.
+fn encode_frame(body: &[u8]) -> io::Result<Vec<u8>> {
+    let len = u32::try_from(body.len())
+        .ok()
+        .filter(|len| *len <= MAX_RECORD_BYTES)
+        .ok_or_else(|| invalid_data("record too large for spill queue"))?;
+    let mut record = Vec::with_capacity(body.len() + 8);
+    record.extend_from_slice(&len.to_le_bytes());
+    record.extend_from_slice(&crc32fast::hash(body).to_le_bytes());
+    record.extend_from_slice(body);
+    Ok(record)
+}
+
+fn invalid_data(reason: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `record`와 같은 길이의 레코드 하나가 차지하는 크기
+    fn frame_bytes(record: &str) -> u64 {
+        RECORD_HEADER_BYTES + record.len() as u64
+    }
+
+    async fn push_all(queue: &mut SpillQueue, records: impl IntoIterator<Item = String>) {
+        for record in records {
+            assert!(queue.push(record.into_bytes()).await.unwrap());
+        }
+    }
+
+    async fn pop_all(queue: &mut SpillQueue) -> Vec<String> {
+        let (records, _) = queue.pop_batch(usize::MAX).await.unwrap();
+        records
+            .into_iter()
+            .map(|record| String::from_utf8(record).unwrap())
+            .collect()
+    }
+
+    fn segment_files(dir: &Path) -> usize {
+        fs::read_dir(dir)
+            .unwrap()
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .ok()
+                    .and_then(|e| e.file_name().to_str().and_then(segment_seq))
+                    .is_some()
+            })
+            .count()
+    }
+
+    #[test]
+    fn frame_roundtrip_and_checksum() {
+        let frame = encode_frame(b"\x00binary\xff").unwrap();
+        assert_eq!(
+            read_frame(&mut frame.as_slice()).unwrap(),
+            b"\x00binary\xff"
+        );
+
+        let mut damaged = frame.clone();
+        damaged[10] ^= 0xff;
+        assert!(read_frame(&mut damaged.as_slice()).is_err());
+        assert!(read_frame(&mut &frame[..frame.len() - 1]).is_err());
+    }
+
+    #[tokio::test]
+    async fn push_and_pop_in_order_across_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut queue = SpillQueue::open(dir.path(), 1024 * 1024, 64).await.unwrap();
+        push_all(&mut queue, (0..20).map(|i| format!("rec{i:02}"))).await;
+        assert_eq!(queue.len(), 20);
+        assert_eq!(queue.bytes(), 20 * frame_bytes("rec00"));
+        assert!(segment_files(dir.path()) > 1);
+
+        let (batch, lost) = queue.pop_batch(5).await.unwrap();
+        assert_eq!(lost, 0);
+        assert_eq!(batch.len(), 5);
+        assert_eq!(batch[0], b"rec00");
+        assert_eq!(queue.len(), 15);
+
+        let rest = pop_all(&mut queue).await;
+        assert_eq!(rest.first().map(String::as_str), Some("rec05"));
+        assert_eq!(rest.len(), 15);
+        assert!(queue.is_empty());
+        assert_eq!(queue.bytes(), 0);
+        assert_eq!(segment_files(dir.path()), 0);
+
+        // 다 읽은 뒤에도 계속 쓸 수 있음
+        assert!(queue.push(b"again".to_vec()).await.unwrap());
+        assert_eq!(pop_all(&mut queue).await, vec!["again"]);
+    }
+
+    #[tokio::test]
+    async fn push_stops_at_max_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let record = frame_bytes("rec0");
+        let mut queue = SpillQueue::open(dir.path(), record * 4, record * 2)
+            .await
+            .unwrap();
+        let records = (0..6).map(|i| format!("rec{i}").into_bytes()).collect();
+        assert_eq!(queue.push_batch(records).await.unwrap(), 4);
+        assert!(!queue.push(b"rec4".to_vec()).await.unwrap());
+
+        assert_eq!(queue.drop_oldest_segment().await.unwrap(), 2);
+        assert!(queue.push(b"rec4".to_vec()).await.unwrap());
+        assert_eq!(pop_all(&mut queue).await, vec!["rec2", "rec3", "rec4"]);
+    }
+
+    #[tokio::test]
+    async fn reopen_resumes_from_index() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut queue = SpillQueue::open(dir.path(), 1024 * 1024, 64).await.unwrap();
+            push_all(&mut queue, (0..10).map(|i| format!("rec{i}"))).await;
+            queue.pop_batch(3).await.unwrap();
+        }
+
+        let mut queue = SpillQueue::open(dir.path(), 1024 * 1024, 64).await.unwrap();
+        assert_eq!(queue.len(), 7);
+        queue.push(b"rec10".to_vec()).await.unwrap();
+        let records = pop_all(&mut queue).await;
+        assert_eq!(records.first().map(String::as_str), Some("rec3"));
+        assert_eq!(records.last().map(String::as_str), Some("rec10"));
+        assert_eq!(records.len(), 8);
+    }
+
+    #[tokio::test]
+    async fn reopen_truncates_partial_record() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut queue = SpillQueue::open(dir.path(), 1024 * 1024, 1024 * 1024)
+                .await
+                .unwrap();
+            queue.push(b"complete".to_vec()).await.unwrap();
+        }
+        let path = dir.path().join(format!("segment-{:020}.log", 0));
+        let partial = encode_frame(b"partial").unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&partial[..partial.len() - 3]).unwrap();
+        drop(file);
+
+        let mut queue = SpillQueue::open(dir.path(), 1024 * 1024, 1024 * 1024)
+            .await
+            .unwrap();
+        assert_eq!(queue.len(), 1);
+        queue.push(b"next".to_vec()).await.unwrap();
+        assert_eq!(pop_all(&mut queue).await, vec!["complete", "next"]);
+    }
+
+    #[tokio::test]
+    async fn corrupted_segment_is_skipped_and_counted() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut queue = SpillQueue::open(dir.path(), 1024 * 1024, 1024 * 1024)
+            .await
+            .unwrap();
+        push_all(&mut queue, (0..3).map(|i| format!("rec{i}"))).await;
+
+        // 두 번째 레코드의 본문을 손상
+        let path = dir.path().join(format!("segment-{:020}.log", 0));
+        let mut data = fs::read(&path).unwrap();
+        let second = usize::try_from(frame_bytes("rec0") + RECORD_HEADER_BYTES).unwrap();
+        data[second] ^= 0xff;
+        fs::write(&path, data).unwrap();
+
+        let (records, lost) = queue.pop_batch(10).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(lost, 2);
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn clear_removes_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut queue = SpillQueue::open(dir.path(), 1024 * 1024, 16).await.unwrap();
+        push_all(&mut queue, (0..4).map(|i| format!("rec{i}"))).await;
+        assert!(segment_files(dir.path()) > 0);
+
+        queue.clear().await.unwrap();
+        assert!(queue.is_empty());
+        assert_eq!(segment_files(dir.path()), 0);
+
+        push_all(&mut queue, ["rec4".to_owned()]).await;
+        queue.clear_blocking();
+        assert_eq!(queue.len(), 0);
+        assert_eq!(segment_files(dir.path()), 0);
+        assert!(
+            SpillQueue::open(dir.path(), 1024, 16)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
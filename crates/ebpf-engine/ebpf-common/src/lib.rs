@@ -5,9 +5,22 @@
 //! 사용하도록 보장합니다.
 //!
 //! # 맵 타입 선택 근거
+//! - **LpmTrie** (`ALLOWLIST`): 허용 목록 — 호스트/서브넷을 한 구조로 매칭, 차단 목록보다 먼저 조회
 //! - **HashMap** (`BLOCKLIST`): IP 차단 목록 — O(1) 조회, 유저스페이스에서 동적 업데이트
+//! - **LpmTrie** (`BLOCKLIST_CIDR`): CIDR 차단 목록 — 서브넷 단위 최장 프리픽스 매칭
+//! - **HashMap** (`PORT_RULES`): 포트 룰 — (출발지 IP, 목적지 포트, 프로토콜) 조회
+//! - **LruHashMap** (`RATE_LIMIT`): 출발지별 토큰 버킷 — 오래된 출발지는 자동 축출
+//! - **Array** (`RATE_LIMIT_CONFIG`): 레이트 리밋 파라미터 — 유저스페이스가 기록하는 단일 엔트리
+//! - **Array** (`SAMPLING_CONFIG`): 모니터링 이벤트 샘플링 비율 — 유저스페이스가 기록하는 단일 엔트리
 //! - **PerCpuArray** (`STATS`): 프로토콜별 통계 — CPU별 독립 카운터, 락 프리 고성능
+//! - **PerCpuHashMap** (`IFACE_STATS`): 수신 인터페이스별 통계 — 어태치된 인터페이스 수만큼만 엔트리 생성
+//! - **LruPerCpuHashMap** (`PORT_STATS`): 목적지 포트별 통계 — 자주 쓰이는 포트만 남기는 CPU별 카운터
+//! - **LruHashMap** (`CONNTRACK`): 연결 추적 — 5-튜플별 상태/타임스탬프, 오래된 흐름은 자동 축출
+//! - **LruHashMap** (`SOCK_OWNERS`): 소켓 소유 프로세스 — kprobe가 기록한 TCP 연결별 PID/comm/cgroup
 //! - **RingBuf** (`EVENTS`): 이벤트 전달 — 고성능 가변 크기 메시지, PerfEventArray보다 효율적
+//!   (모든 RingBuf 레코드는 버전/종류/길이를 담은 `EventHeader`로 시작)
+//! - **RingBuf** (`TLS_HELLOS`): TC egress가 캡처한 TLS ClientHello 앞부분 — SNI 추출용
+//! - **PerCpuArray** (`RINGBUF_DROPS`): RingBuf별 `reserve` 실패 수 — 버퍼가 넘쳐 잃은 레코드 추적
 
 #![no_std]
 
@@ -15,12 +28,50 @@
 // 맵 이름 상수
 // =============================================================================
 
+/// 허용 목록 LpmTrie 맵 이름
+pub const MAP_ALLOWLIST: &str = "ALLOWLIST";
 /// 차단 목록 HashMap 맵 이름
 pub const MAP_BLOCKLIST: &str = "BLOCKLIST";
+/// CIDR 차단 목록 LpmTrie 맵 이름
+pub const MAP_BLOCKLIST_CIDR: &str = "BLOCKLIST_CIDR";
+/// 포트 룰 HashMap 맵 이름
+pub const MAP_PORT_RULES: &str = "PORT_RULES";
+/// 레이트 리밋 토큰 버킷 LruHashMap 맵 이름
+pub const MAP_RATE_LIMIT: &str = "RATE_LIMIT";
+/// 레이트 리밋 파라미터 Array 맵 이름
+pub const MAP_RATE_LIMIT_CONFIG: &str = "RATE_LIMIT_CONFIG";
+/// 이벤트 샘플링 파라미터 Array 맵 이름
+pub const MAP_SAMPLING_CONFIG: &str = "SAMPLING_CONFIG";
+/// 관찰 전용(드라이런) 플래그 Array 맵 이름
+pub const MAP_DRY_RUN_CONFIG: &str = "DRY_RUN_CONFIG";
 /// 통계 PerCpuArray 맵 이름
 pub const MAP_STATS: &str = "STATS";
+/// 인터페이스별 통계 PerCpuHashMap 맵 이름
+pub const MAP_IFACE_STATS: &str = "IFACE_STATS";
+/// 목적지 포트별 통계 LruPerCpuHashMap 맵 이름
+pub const MAP_PORT_STATS: &str = "PORT_STATS";
+/// 연결 추적 LruHashMap 맵 이름
+pub const MAP_CONNTRACK: &str = "CONNTRACK";
+/// 소켓 소유 프로세스 LruHashMap 맵 이름
+pub const MAP_SOCK_OWNERS: &str = "SOCK_OWNERS";
 /// 이벤트 RingBuf 맵 이름
 pub const MAP_EVENTS: &str = "EVENTS";
+/// TLS ClientHello 캡처 RingBuf 맵 이름
+pub const MAP_TLS_HELLOS: &str = "TLS_HELLOS";
+/// RingBuf 드롭 카운터 PerCpuArray 맵 이름
+pub const MAP_RINGBUF_DROPS: &str = "RINGBUF_DROPS";
+
+/// bpffs에 고정(pin)되어 데몬 재시작 후에도 유지되는 맵
+///
+/// 커널 프로그램에서 `pinned`로 선언된 맵과 일치해야 합니다.
+pub const PINNED_MAPS: [&str; 6] = [
+    MAP_ALLOWLIST,
+    MAP_BLOCKLIST,
+    MAP_BLOCKLIST_CIDR,
+    MAP_PORT_RULES,
+    MAP_STATS,
+    MAP_PORT_STATS,
+];
 
 // =============================================================================
 // 프로토콜 상수
@@ -49,17 +100,165 @@ pub const STATS_IDX_OTHER: u32 = 3;
 pub const STATS_IDX_TOTAL: u32 = 4;
 /// PerCpuArray 최대 엔트리 수
 pub const STATS_MAX_ENTRIES: u32 = 5;
+/// 통계를 추적할 최대 수신 인터페이스 수 (키: ifindex)
+pub const IFACE_STATS_MAX_ENTRIES: u32 = 256;
+/// 포트별 통계를 추적할 최대 (포트, 프로토콜) 수 (LRU)
+pub const PORT_STATS_MAX_ENTRIES: u32 = 4_096;
+
+// =============================================================================
+// RingBuf 드롭 카운터 인덱스
+// =============================================================================
+
+/// `EVENTS` 드롭 카운터 인덱스
+pub const RINGBUF_DROPS_IDX_EVENTS: u32 = 0;
+/// `TLS_HELLOS` 드롭 카운터 인덱스
+pub const RINGBUF_DROPS_IDX_TLS_HELLOS: u32 = 1;
+/// RINGBUF_DROPS 배열 크기
+pub const RINGBUF_DROPS_MAX_ENTRIES: u32 = 2;
 
 // =============================================================================
 // 액션 코드 (RingBuf 이벤트 + 차단 목록)
 // =============================================================================
 
-/// 패킷 통과
+/// 패킷 통과 (허용 목록 엔트리의 액션이기도 함)
 pub const ACTION_PASS: u8 = 0;
 /// 패킷 차단 (XDP_DROP)
 pub const ACTION_DROP: u8 = 1;
 /// 패킷 통과 + 모니터링 (이벤트 전송)
 pub const ACTION_MONITOR: u8 = 2;
+/// TCP는 RST 응답으로 되돌려 보냄 (XDP_TX), 그 외 프로토콜은 차단
+pub const ACTION_REFLECT: u8 = 3;
+
+/// 패킷을 네트워크 스택에 올리지 않는 액션인지 확인합니다 (DROP, REFLECT).
+///
+/// 포트 룰 우선순위, 레이트 리밋/연결 추적 생략, 드롭 통계가 모두 이 기준을 따릅니다.
+pub const fn is_drop_action(action: u8) -> bool {
+    action == ACTION_DROP || action == ACTION_REFLECT
+}
+
+// =============================================================================
+// 차단 목록 엔트리 출처
+// =============================================================================
+
+/// 설정 파일 룰에서 만들어진 엔트리
+pub const BLOCKLIST_ORIGIN_RULE: u8 = 0;
+/// 런타임에 추가된 엔트리 (재시작 시 고정된 맵에서 복원)
+pub const BLOCKLIST_ORIGIN_RUNTIME: u8 = 1;
+
+/// 호스트 차단 목록(`BLOCKLIST`) 맵 최대 엔트리 수
+pub const BLOCKLIST_MAX_ENTRIES: u32 = 10_000;
+/// CIDR 차단 목록(`BLOCKLIST_CIDR`) 맵 최대 엔트리 수
+pub const BLOCKLIST_CIDR_MAX_ENTRIES: u32 = 10_000;
+
+// =============================================================================
+// 허용 목록
+// =============================================================================
+
+/// 허용 목록 맵 최대 엔트리 수 (호스트는 `/32` 프리픽스로 저장)
+pub const ALLOWLIST_MAX_ENTRIES: u32 = 1_024;
+
+// =============================================================================
+// 포트 룰 와일드카드
+// =============================================================================
+
+/// 포트 룰 키의 "모든 출발지 IP" 값
+pub const PORT_RULE_ANY_IP: u32 = 0;
+/// 포트 룰 키의 "모든 프로토콜(TCP/UDP)" 값
+pub const PORT_RULE_ANY_PROTO: u8 = 0;
+/// 포트 룰 맵 최대 엔트리 수
+pub const PORT_RULES_MAX_ENTRIES: u32 = 10_000;
+
+// =============================================================================
+// 레이트 리밋
+// =============================================================================
+
+/// 토큰 버킷을 추적할 최대 출발지 수 (LRU)
+pub const RATE_LIMIT_MAX_SOURCES: u32 = 65_536;
+/// 토큰 1개에 해당하는 내부 단위 (나노초 단위 리필을 정수로 계산하기 위한 배율)
+pub const RATE_LIMIT_TOKEN_SCALE: u64 = 1_000_000_000;
+
+// =============================================================================
+// 연결 추적
+// =============================================================================
+
+/// 추적할 최대 흐름(5-튜플) 수 (LRU)
+pub const CONNTRACK_MAX_ENTRIES: u32 = 65_536;
+/// 이 시간(나노초) 동안 패킷이 없던 흐름은 다음 패킷에서 새 흐름으로 취급
+pub const CONNTRACK_IDLE_TIMEOUT_NS: u64 = 120_000_000_000;
+
+/// 추적하지 않는 패킷 (TCP/UDP 외 프로토콜 또는 추적 전에 드롭된 패킷)
+pub const CT_STATE_UNTRACKED: u8 = 0;
+/// 흐름의 첫 패킷 (TCP는 핸드셰이크 완료 전)
+pub const CT_STATE_NEW: u8 = 1;
+/// 양방향 진행이 확인된 흐름 (TCP ACK 수신, UDP 두 번째 패킷 이후)
+pub const CT_STATE_ESTABLISHED: u8 = 2;
+/// FIN 또는 RST를 본 TCP 흐름
+pub const CT_STATE_CLOSING: u8 = 3;
+
+/// 흐름의 다음 상태를 계산합니다.
+///
+/// XDP는 수신 방향만 보므로 상태는 근사치입니다. 커널 프로그램과 유저스페이스
+/// 테스트가 같은 전이 규칙을 공유하도록 여기에 둡니다.
+///
+/// - TCP: FIN/RST → CLOSING, SYN 없는 ACK → ESTABLISHED, 그 외 현재 상태 유지
+/// - UDP 등: 두 번째 패킷부터 ESTABLISHED
+pub const fn next_flow_state(current: u8, protocol: u8, tcp_flags: u8) -> u8 {
+    if protocol != PROTO_TCP {
+        return CT_STATE_ESTABLISHED;
+    }
+    if tcp_flags & (TCP_FIN | TCP_RST) != 0 {
+        CT_STATE_CLOSING
+    } else if current == CT_STATE_NEW && tcp_flags & TCP_ACK != 0 && tcp_flags & TCP_SYN == 0 {
+        CT_STATE_ESTABLISHED
+    } else {
+        current
+    }
+}
+
+// =============================================================================
+// 소켓 소유 프로세스
+// =============================================================================
+
+/// 소유 프로세스를 기록할 최대 TCP 연결 수 (LRU)
+pub const SOCK_OWNERS_MAX_ENTRIES: u32 = 65_536;
+/// 커널 태스크 이름(`comm`) 길이 (`TASK_COMM_LEN`, NUL 포함)
+pub const TASK_COMM_LEN: usize = 16;
+
+// =============================================================================
+// TLS ClientHello 캡처
+// =============================================================================
+
+/// ClientHello에서 캡처할 최대 TCP 페이로드 바이트 수 (TLS 레코드 헤더 포함)
+pub const TLS_HELLO_CAPTURE_LEN: usize = 1_024;
+/// TLS 핸드셰이크 레코드 타입
+pub const TLS_CONTENT_HANDSHAKE: u8 = 0x16;
+/// TLS ClientHello 핸드셰이크 타입
+pub const TLS_HANDSHAKE_CLIENT_HELLO: u8 = 0x01;
+
+/// TCP 페이로드 앞 6바이트(레코드 헤더 5바이트 + 핸드셰이크 타입)가 ClientHello인지 확인합니다.
+///
+/// 레코드 버전의 상위 바이트는 SSL 3.0 이후 항상 `0x03`입니다. 커널 프로그램이
+/// 캡처 여부를 결정할 때와 유저스페이스 파서가 같은 규칙을 사용합니다.
+pub const fn is_tls_client_hello(prefix: &[u8; 6]) -> bool {
+    prefix[0] == TLS_CONTENT_HANDSHAKE
+        && prefix[1] == 0x03
+        && prefix[5] == TLS_HANDSHAKE_CLIENT_HELLO
+}
+
+// =============================================================================
+// RingBuf 레코드 헤더
+// =============================================================================
+
+/// RingBuf 레코드 형식 버전
+///
+/// 이벤트 구조체의 레이아웃이 바뀌면 올립니다. 유저스페이스는 버전이 다른 레코드를
+/// 해석하지 않고 건너뛰므로, 커널 오브젝트와 유저스페이스 빌드가 어긋나도 잘못된
+/// 값으로 디코딩되지 않습니다.
+pub const EVENT_FORMAT_VERSION: u8 = 1;
+/// 의심 패킷 이벤트 ([`PacketEventData`], `EVENTS`)
+pub const EVENT_TYPE_PACKET: u8 = 1;
+/// TLS ClientHello 캡처 ([`TlsHelloData`], `TLS_HELLOS`)
+pub const EVENT_TYPE_TLS_HELLO: u8 = 2;
 
 // =============================================================================
 // TCP 플래그
@@ -76,6 +275,108 @@ pub const TCP_PSH: u8 = 0x08;
 /// ACK 플래그
 pub const TCP_ACK: u8 = 0x10;
 
+// =============================================================================
+// RST 응답 (ACTION_REFLECT)
+// =============================================================================
+
+/// 옵션 없는 IPv4 헤더 길이
+pub const IPV4_HDR_LEN: usize = 20;
+/// 옵션 없는 TCP 헤더 길이
+pub const TCP_HDR_LEN: usize = 20;
+/// RST 응답의 IP TTL
+pub const REFLECT_TTL: u8 = 64;
+
+/// 수신한 TCP 세그먼트의 IPv4/TCP 헤더를 제자리에서 RST 응답으로 바꿉니다.
+///
+/// 주소와 포트를 맞바꾸고 RFC 9293 리셋 생성 규칙으로 시퀀스 번호를 정합니다.
+/// 받은 세그먼트에 ACK가 있으면 `seq = SEG.ACK`인 RST를, 없으면 `seq = 0`,
+/// `ack = SEG.SEQ + SEG.LEN`인 RST|ACK를 만듭니다. 응답은 헤더만 담으므로 IP 전체 길이를
+/// 40바이트로 줄이고(뒤의 바이트는 이더넷 패딩으로 취급됨) 두 체크섬을 다시 계산합니다.
+/// 커널 프로그램(XDP_TX)과 유저스페이스 테스트가 같은 함수를 사용합니다.
+///
+/// RST 세그먼트에는 응답하지 않으며(RST 루프 방지), IP 옵션이 있거나 길이 필드가
+/// 맞지 않으면 헤더를 바꾸지 않고 `false`를 반환합니다 (호출자는 패킷을 드롭).
+#[inline(always)]
+pub fn tcp_rst_reply(ip: &mut [u8; IPV4_HDR_LEN], tcp: &mut [u8; TCP_HDR_LEN]) -> bool {
+    let flags = tcp[13];
+    if ip[0] != 0x45 || ip[9] != PROTO_TCP || flags & TCP_RST != 0 {
+        return false;
+    }
+    let total_len = u16::from_be_bytes([ip[2], ip[3]]) as u32;
+    let tcp_hdr_len = (tcp[12] >> 4) as u32 * 4;
+    if tcp_hdr_len < TCP_HDR_LEN as u32 || total_len < IPV4_HDR_LEN as u32 + tcp_hdr_len {
+        return false;
+    }
+
+    // SEG.LEN = 페이로드 + SYN/FIN이 차지하는 시퀀스 번호
+    let mut seg_len = total_len - IPV4_HDR_LEN as u32 - tcp_hdr_len;
+    if flags & TCP_SYN != 0 {
+        seg_len += 1;
+    }
+    if flags & TCP_FIN != 0 {
+        seg_len += 1;
+    }
+    let seg_seq = u32::from_be_bytes([tcp[4], tcp[5], tcp[6], tcp[7]]);
+    let seg_ack = u32::from_be_bytes([tcp[8], tcp[9], tcp[10], tcp[11]]);
+    let (seq, ack, reply_flags) = if flags & TCP_ACK != 0 {
+        (seg_ack, 0, TCP_RST)
+    } else {
+        (0, seg_seq.wrapping_add(seg_len), TCP_RST | TCP_ACK)
+    };
+
+    // IPv4: 주소 교환, 헤더만 담은 40바이트 패킷
+    let mut i = 0;
+    while i < 4 {
+        ip.swap(12 + i, 16 + i);
+        i += 1;
+    }
+    let reply_len = (IPV4_HDR_LEN + TCP_HDR_LEN) as u16;
+    ip[1] = 0; // TOS
+    ip[2..4].copy_from_slice(&reply_len.to_be_bytes());
+    ip[4..6].copy_from_slice(&[0, 0]); // ID
+    ip[6..8].copy_from_slice(&[0x40, 0]); // DF, 단편 오프셋 0
+    ip[8] = REFLECT_TTL;
+    ip[10..12].copy_from_slice(&[0, 0]);
+    let ip_csum = checksum(ones_sum(ip, 0));
+    ip[10..12].copy_from_slice(&ip_csum.to_be_bytes());
+
+    // TCP: 포트 교환, 옵션 없는 헤더
+    tcp.swap(0, 2);
+    tcp.swap(1, 3);
+    tcp[4..8].copy_from_slice(&seq.to_be_bytes());
+    tcp[8..12].copy_from_slice(&ack.to_be_bytes());
+    tcp[12] = ((TCP_HDR_LEN / 4) as u8) << 4;
+    tcp[13] = reply_flags;
+    tcp[14..20].copy_from_slice(&[0; 6]); // 윈도, 체크섬, 긴급 포인터
+
+    // 의사 헤더: 출발지/목적지 주소, 프로토콜, TCP 길이
+    let pseudo = ones_sum(&ip[12..20], PROTO_TCP as u32 + TCP_HDR_LEN as u32);
+    let tcp_csum = checksum(ones_sum(tcp, pseudo));
+    tcp[16..18].copy_from_slice(&tcp_csum.to_be_bytes());
+    true
+}
+
+/// 16비트 단위 1의 보수 합을 `initial`에 더합니다 (짝수 길이 전용).
+#[inline(always)]
+fn ones_sum(bytes: &[u8], initial: u32) -> u32 {
+    let mut sum = initial;
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        sum += u16::from_be_bytes([bytes[i], bytes[i + 1]]) as u32;
+        i += 2;
+    }
+    sum
+}
+
+/// 1의 보수 합을 접어 인터넷 체크섬을 만듭니다.
+#[inline(always)]
+fn checksum(mut sum: u32) -> u16 {
+    while sum > 0xFFFF {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
 // =============================================================================
 // 공유 데이터 구조
 // =============================================================================
@@ -88,14 +389,29 @@ pub const TCP_ACK: u8 = 0x10;
 /// # 맵 선택 근거
 /// HashMap은 O(1) 키-값 조회를 제공하여 패킷당 차단 여부를 빠르게 판단합니다.
 /// 유저스페이스에서 동적으로 엔트리를 추가/삭제할 수 있어 런타임 룰 업데이트가 가능합니다.
+///
+/// # 히트 카운트
+/// `hits`는 XDP 프로그램이 엔트리에 매칭될 때마다 원자적으로 증가시킵니다.
+/// 유저스페이스는 액션 변경 시 기존 값을 보존하여 다시 기록합니다.
+///
+/// # 출처와 만료
+/// `origin`과 `expires_at`은 커널이 사용하지 않는 유저스페이스 메타데이터입니다.
+/// 맵이 bpffs에 고정되어 있으면 데몬 재시작 후 런타임 엔트리를 TTL과 함께
+/// 복원하는 데 사용합니다.
 #[repr(C)]
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "user", derive(Debug))]
 pub struct BlocklistValue {
-    /// 적용할 액션 (ACTION_DROP 또는 ACTION_MONITOR)
+    /// 적용할 액션 (ACTION_DROP, ACTION_MONITOR 또는 ACTION_REFLECT, 허용 목록은 ACTION_PASS)
     pub action: u8,
-    /// 4바이트 정렬을 위한 패딩
-    pub _pad: [u8; 3],
+    /// 엔트리 출처 (BLOCKLIST_ORIGIN_RULE 또는 BLOCKLIST_ORIGIN_RUNTIME)
+    pub origin: u8,
+    /// 8바이트 정렬을 위한 패딩
+    pub _pad: [u8; 6],
+    /// 매칭된 패킷 수 (누적)
+    pub hits: u64,
+    /// 만료 시각 (Unix epoch 초, 0이면 만료 없음)
+    pub expires_at: u64,
 }
 
 // SAFETY: BlocklistValue는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다.
@@ -103,6 +419,102 @@ pub struct BlocklistValue {
 #[cfg(feature = "user")]
 unsafe impl aya::Pod for BlocklistValue {}
 
+/// 포트 룰 키
+///
+/// `HashMap<PortRuleKey, BlocklistValue>` 맵에서 사용됩니다.
+/// `src_ip`와 `dst_port`는 BLOCKLIST 키와 같이 패킷 헤더를 `from_be_bytes`로
+/// 읽은 값입니다. 와일드카드는 [`PORT_RULE_ANY_IP`], [`PORT_RULE_ANY_PROTO`]로
+/// 표현하며, XDP 프로그램은 구체적인 키부터 순서대로 조회합니다.
+///
+/// # 맵 선택 근거
+/// 룰 수가 적고 키가 고정 크기이므로 와일드카드 조합을 최대 4번 O(1) 조회하는
+/// 편이 범위 매칭 구조보다 검증기 부담이 적습니다.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct PortRuleKey {
+    /// 출발지 IPv4 주소 (PORT_RULE_ANY_IP = 모든 IP)
+    pub src_ip: u32,
+    /// 목적지 포트
+    pub dst_port: u16,
+    /// IP 프로토콜 번호 (PROTO_TCP, PROTO_UDP 또는 PORT_RULE_ANY_PROTO)
+    pub protocol: u8,
+    /// 4바이트 정렬을 위한 패딩 (항상 0, 해시 키에 포함됨)
+    pub _pad: u8,
+}
+
+impl PortRuleKey {
+    /// 패딩이 0으로 초기화된 키를 생성합니다.
+    pub const fn new(src_ip: u32, dst_port: u16, protocol: u8) -> Self {
+        Self {
+            src_ip,
+            dst_port,
+            protocol,
+            _pad: 0,
+        }
+    }
+}
+
+// SAFETY: PortRuleKey는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다.
+// 패딩은 명시적 필드이며 생성자에서 항상 0으로 채워집니다.
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for PortRuleKey {}
+
+/// 레이트 리밋 파라미터
+///
+/// `Array<RateLimitParams>` 맵의 0번 엔트리에 유저스페이스가 기록합니다.
+/// `pps`가 0이면 레이트 리밋이 비활성화됩니다.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct RateLimitParams {
+    /// 출발지 IP당 초당 리필되는 토큰(패킷) 수
+    pub pps: u64,
+    /// 버킷 용량 (한 번에 허용하는 최대 패킷 수)
+    pub burst: u64,
+}
+
+// SAFETY: RateLimitParams는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다.
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for RateLimitParams {}
+
+/// 출발지별 토큰 버킷
+///
+/// `LruHashMap<u32, TokenBucket>` 맵에서 사용됩니다. 키는 BLOCKLIST와 같은 출발지 IPv4 주소입니다.
+/// `tokens`는 [`RATE_LIMIT_TOKEN_SCALE`] 배율로 저장되어, 경과 나노초 × pps만큼 정수로 리필됩니다.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct TokenBucket {
+    /// 남은 토큰 (RATE_LIMIT_TOKEN_SCALE 배율)
+    pub tokens: u64,
+    /// 마지막 리필 시각 (bpf_ktime_get_ns, 부팅 후 나노초)
+    pub last_ns: u64,
+}
+
+// SAFETY: TokenBucket은 #[repr(C)]이며 모든 필드가 Plain Old Data입니다.
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for TokenBucket {}
+
+/// 이벤트 샘플링 파라미터
+///
+/// `Array<SamplingParams>` 맵의 0번 엔트리에 유저스페이스가 기록합니다.
+/// `monitor_rate`가 N(> 1)이면 ACTION_MONITOR 패킷 N개 중 평균 1개만 이벤트로 전송하고,
+/// 0 또는 1이면 모든 패킷을 전송합니다. 차단(ACTION_DROP) 이벤트는 샘플링하지 않습니다.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct SamplingParams {
+    /// ACTION_MONITOR 이벤트 샘플링 비율 (1/N)
+    pub monitor_rate: u32,
+    /// 8바이트 정렬을 위한 패딩
+    pub _pad: u32,
+}
+
+// SAFETY: SamplingParams는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다 (패딩 명시).
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for SamplingParams {}
+
 /// 프로토콜별 통계 카운터
 ///
 /// `PerCpuArray<ProtoStats>` 맵에서 사용됩니다.
@@ -121,15 +533,208 @@ pub struct ProtoStats {
     pub bytes: u64,
     /// 드롭된 패킷 수
     pub drops: u64,
+    /// 관찰 전용 모드에서 드롭 판정을 받았지만 통과시킨 패킷 수
+    pub would_drops: u64,
 }
 
 // SAFETY: ProtoStats는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다.
 #[cfg(feature = "user")]
 unsafe impl aya::Pod for ProtoStats {}
 
+/// 목적지 포트별 통계 키
+///
+/// `LruPerCpuHashMap<PortStatsKey, ProtoStats>` 맵에서 사용됩니다.
+/// `dst_port`는 PortRuleKey와 같이 패킷 헤더를 `from_be_bytes`로 읽은 값입니다.
+///
+/// # 맵 선택 근거
+/// 포트 공간(65,536 × 프로토콜)을 모두 배열로 두면 CPU별 메모리가 커지므로,
+/// LRU 해시로 최근 트래픽이 있는 포트만 유지합니다. 조용한 포트는 축출되어
+/// 누적값이 초기화될 수 있지만 상위 포트 집계에는 영향이 없습니다.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct PortStatsKey {
+    /// 목적지 포트
+    pub dst_port: u16,
+    /// IP 프로토콜 번호 (PROTO_TCP 또는 PROTO_UDP)
+    pub protocol: u8,
+    /// 4바이트 정렬을 위한 패딩 (항상 0, 해시 키에 포함됨)
+    pub _pad: u8,
+}
+
+impl PortStatsKey {
+    /// 패딩이 0으로 초기화된 키를 생성합니다.
+    pub const fn new(dst_port: u16, protocol: u8) -> Self {
+        Self {
+            dst_port,
+            protocol,
+            _pad: 0,
+        }
+    }
+}
+
+// SAFETY: PortStatsKey는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다.
+// 패딩은 명시적 필드이며 생성자에서 항상 0으로 채워집니다.
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for PortStatsKey {}
+
+/// 연결 추적 키 (5-튜플)
+///
+/// `LruHashMap<FlowKey, FlowState>` 맵에서 사용됩니다. IP와 포트는 BLOCKLIST 키와 같이
+/// 패킷 헤더를 `from_be_bytes`로 읽은 값이므로 유저스페이스에서 `Ipv4Addr::from`으로 바로 변환합니다.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct FlowKey {
+    /// 출발지 IPv4 주소
+    pub src_ip: u32,
+    /// 목적지 IPv4 주소
+    pub dst_ip: u32,
+    /// 출발지 포트
+    pub src_port: u16,
+    /// 목적지 포트
+    pub dst_port: u16,
+    /// IP 프로토콜 번호 (PROTO_TCP 또는 PROTO_UDP)
+    pub protocol: u8,
+    /// 4바이트 정렬을 위한 패딩 (항상 0, 해시 키에 포함됨)
+    pub _pad: [u8; 3],
+}
+
+impl FlowKey {
+    /// 패딩이 0으로 초기화된 키를 생성합니다.
+    pub const fn new(src_ip: u32, dst_ip: u32, src_port: u16, dst_port: u16, protocol: u8) -> Self {
+        Self {
+            src_ip,
+            dst_ip,
+            src_port,
+            dst_port,
+            protocol,
+            _pad: [0; 3],
+        }
+    }
+}
+
+// SAFETY: FlowKey는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다 (패딩 명시).
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for FlowKey {}
+
+/// 연결 추적 값
+///
+/// 타임스탬프는 `bpf_ktime_get_ns`(부팅 후 나노초)이므로 유저스페이스에서는
+/// 흐름 사이의 상대 비교에만 사용합니다.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct FlowState {
+    /// 흐름 상태 (CT_STATE_*)
+    pub state: u8,
+    /// 8바이트 정렬을 위한 패딩
+    pub _pad: [u8; 7],
+    /// 흐름의 첫 패킷 시각 (나노초)
+    pub first_seen_ns: u64,
+    /// 흐름의 마지막 패킷 시각 (나노초)
+    pub last_seen_ns: u64,
+    /// 흐름의 패킷 수
+    pub packets: u64,
+}
+
+// SAFETY: FlowState는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다 (패딩 명시).
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for FlowState {}
+
+/// TCP 연결을 만든 프로세스
+///
+/// `LruHashMap<FlowKey, SockOwner>` 맵에서 사용됩니다. kprobe 프로그램이 `tcp_connect`
+/// (나가는 연결)와 `inet_csk_accept` 반환(받아들인 연결) 시점에 기록하며, 키는 XDP가
+/// 보는 수신 방향(원격 → 로컬)의 FlowKey이므로 패킷 이벤트의 5-튜플로 바로 조회합니다.
+///
+/// # 맵 선택 근거
+/// 연결 수가 무한히 늘 수 있고 닫힌 연결을 커널에서 지우지 않으므로, LRU로 오래된
+/// 연결을 자동 축출합니다.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct SockOwner {
+    /// 프로세스 ID (유저스페이스에서 보는 PID, 커널의 tgid)
+    pub pid: u32,
+    /// 8바이트 정렬을 위한 패딩
+    pub _pad: u32,
+    /// 프로세스가 속한 cgroup v2 ID
+    pub cgroup_id: u64,
+    /// 프로세스 이름 (NUL로 끝나는 `comm`)
+    pub comm: [u8; TASK_COMM_LEN],
+}
+
+// SAFETY: SockOwner는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다 (패딩 명시).
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for SockOwner {}
+
+/// RingBuf 레코드 헤더
+///
+/// 모든 RingBuf 레코드는 [`EventRecord`]로 감싸 이 헤더로 시작합니다.
+/// 유저스페이스는 헤더의 버전과 종류로 본문을 해석할 타입을 고릅니다.
+///
+/// # 메모리 레이아웃 (4 바이트)
+/// ```text
+/// offset  field       size
+/// 0       version     1
+/// 1       event_type  1
+/// 2       len         2   (헤더 포함 레코드 전체 크기)
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct EventHeader {
+    /// 레코드 형식 버전 ([`EVENT_FORMAT_VERSION`])
+    pub version: u8,
+    /// 이벤트 종류 (EVENT_TYPE_*)
+    pub event_type: u8,
+    /// 헤더를 포함한 레코드 전체 크기 (바이트)
+    pub len: u16,
+}
+
+impl EventHeader {
+    /// 현재 형식 버전의 헤더를 생성합니다.
+    pub const fn new(event_type: u8, len: usize) -> Self {
+        Self {
+            version: EVENT_FORMAT_VERSION,
+            event_type,
+            len: len as u16,
+        }
+    }
+}
+
+/// 헤더가 붙은 RingBuf 레코드
+///
+/// 본문 타입은 모두 4바이트 정렬이라 헤더와 본문 사이에 패딩이 없습니다.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct EventRecord<T> {
+    /// 레코드 헤더
+    pub header: EventHeader,
+    /// 이벤트 본문
+    pub data: T,
+}
+
+impl<T> EventRecord<T> {
+    /// 헤더를 채운 레코드를 생성합니다.
+    pub const fn new(event_type: u8, data: T) -> Self {
+        Self {
+            header: EventHeader::new(event_type, core::mem::size_of::<Self>()),
+            data,
+        }
+    }
+}
+
+// SAFETY: EventRecord는 #[repr(C)]이며 헤더(4바이트)와 Pod 본문으로만 이루어집니다.
+#[cfg(feature = "user")]
+unsafe impl<T: aya::Pod> aya::Pod for EventRecord<T> {}
+
 /// 의심 패킷 이벤트 데이터
 ///
-/// `RingBuf`를 통해 커널 → 유저스페이스로 전달됩니다.
+/// [`EventRecord`]로 감싸 `RingBuf`를 통해 커널 → 유저스페이스로 전달됩니다
+/// ([`EVENT_TYPE_PACKET`]).
 ///
 /// # 맵 선택 근거
 /// RingBuf는 PerfEventArray보다 효율적인 가변 크기 이벤트 전달을 지원합니다.
@@ -147,7 +752,8 @@ unsafe impl aya::Pod for ProtoStats {}
 /// 16      protocol    1
 /// 17      action      1
 /// 18      tcp_flags   1
-/// 19      _pad        1
+/// 19      ct_state    1
+/// 20      sample_rate 4
 /// ```
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -165,18 +771,52 @@ pub struct PacketEventData {
     pub pkt_len: u32,
     /// IP 프로토콜 번호 (PROTO_TCP, PROTO_UDP, PROTO_ICMP)
     pub protocol: u8,
-    /// 적용된 액션 (ACTION_PASS, ACTION_DROP, ACTION_MONITOR)
+    /// 적용된 액션 (ACTION_PASS, ACTION_DROP, ACTION_MONITOR, ACTION_REFLECT)
     pub action: u8,
     /// TCP 플래그 (TCP 패킷인 경우, 0이면 비-TCP)
     pub tcp_flags: u8,
-    /// 4바이트 정렬을 위한 패딩
-    pub _pad: [u8; 1],
+    /// 연결 추적 상태 (CT_STATE_*, 기존 정렬 패딩 자리를 사용)
+    pub ct_state: u8,
+    /// 이 이벤트가 샘플링된 비율 N (1/N, 1이면 샘플링하지 않음)
+    pub sample_rate: u32,
 }
 
 // SAFETY: PacketEventData는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다.
 #[cfg(feature = "user")]
 unsafe impl aya::Pod for PacketEventData {}
 
+/// TLS ClientHello 캡처 데이터
+///
+/// TC egress 프로그램이 [`EventRecord`]로 감싸 `TLS_HELLOS` RingBuf로 유저스페이스에
+/// 전달합니다 ([`EVENT_TYPE_TLS_HELLO`]).
+/// IP와 포트는 [`FlowKey`]와 같이 패킷 헤더를 `from_be_bytes`로 읽은 값이며,
+/// 나가는 패킷이므로 출발지가 로컬 호스트입니다.
+///
+/// # 맵 선택 근거
+/// ClientHello는 연결마다 한 번뿐이라 빈도가 낮지만 크기가 커서, 패킷 이벤트 링과
+/// 분리해 큰 레코드가 의심 패킷 이벤트를 밀어내지 않도록 합니다.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "user", derive(Debug))]
+pub struct TlsHelloData {
+    /// 출발지 IPv4 주소 (로컬)
+    pub src_ip: u32,
+    /// 목적지 IPv4 주소 (원격 서버)
+    pub dst_ip: u32,
+    /// 출발지 포트
+    pub src_port: u16,
+    /// 목적지 포트
+    pub dst_port: u16,
+    /// `payload`에 실제로 복사된 바이트 수 (최대 TLS_HELLO_CAPTURE_LEN)
+    pub captured_len: u32,
+    /// TCP 페이로드 앞부분 (TLS 레코드 헤더부터)
+    pub payload: [u8; TLS_HELLO_CAPTURE_LEN],
+}
+
+// SAFETY: TlsHelloData는 #[repr(C)]이며 모든 필드가 Plain Old Data입니다 (16바이트 헤더 뒤 바이트 배열).
+#[cfg(feature = "user")]
+unsafe impl aya::Pod for TlsHelloData {}
+
 /// ProtoStats의 제로 초기화를 반환합니다.
 impl ProtoStats {
     /// 제로 초기화된 통계를 생성합니다.
@@ -185,6 +825,7 @@ impl ProtoStats {
             packets: 0,
             bytes: 0,
             drops: 0,
+            would_drops: 0,
         }
     }
 }
@@ -202,7 +843,17 @@ impl PacketEventData {
             protocol: 0,
             action: 0,
             tcp_flags: 0,
-            _pad: [0; 1],
+            ct_state: CT_STATE_UNTRACKED,
+            sample_rate: 1,
+        }
+    }
+
+    /// 이 이벤트가 대표하는 패킷 수 (샘플링 비율, 최소 1)
+    pub const fn sample_weight(&self) -> u64 {
+        if self.sample_rate == 0 {
+            1
+        } else {
+            self.sample_rate as u64
         }
     }
 }
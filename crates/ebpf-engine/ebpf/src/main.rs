@@ -1,35 +1,88 @@
 //! Ironpost XDP 패킷 필터 프로그램
 //!
 //! 네트워크 인터페이스에 어태치되어 모든 수신 패킷을 검사합니다.
+//! 같은 오브젝트의 kprobe 프로그램은 새 TCP 연결의 소유 프로세스를 기록하고
+//! (아래 "프로세스 연관" 참고), TC egress 프로그램은 나가는 TLS ClientHello를 캡처합니다
+//! (아래 "TLS SNI" 참고).
 //!
 //! # 처리 흐름
-//! 1. Ethernet 헤더 파싱 → IPv4만 처리
+//! 1. Ethernet 헤더 파싱 → VLAN 태그(802.1Q, QinQ) 최대 2개를 건너뛰고 IPv4만 처리
 //! 2. IPv4 헤더 파싱 → src_ip, dst_ip, protocol 추출
 //! 3. TCP/UDP 헤더 파싱 → 포트, TCP 플래그 추출
-//! 4. 차단 목록(HashMap) 조회 → 매칭 시 XDP_DROP
-//! 5. 프로토콜별 통계(PerCpuArray) 업데이트
-//! 6. 의심 패킷 이벤트(RingBuf)로 유저스페이스 전달
+//! 4. 허용 목록(LpmTrie) 조회 → 매칭 시 5~7단계를 건너뛰고 항상 XDP_PASS
+//! 5. 차단 목록 조회 → 매칭 시 엔트리의 액션 적용 (DROP, MONITOR, REFLECT)
+//!    (호스트 HashMap 우선, 미스 시 CIDR LpmTrie에서 최장 프리픽스 매칭)
+//! 6. TCP/UDP 포트 룰(HashMap) 조회 → 매칭 시 룰의 액션 적용
+//! 7. 출발지별 토큰 버킷(LruHashMap) 레이트 리밋 → 초과 시 XDP_DROP
+//! 8. 드롭되지 않은 TCP/UDP 패킷의 연결 추적(LruHashMap) 상태 갱신
+//!    (DRY_RUN_CONFIG가 켜져 있으면 드롭 판정도 통과시키고 would-drop으로만 집계)
+//! 9. 프로토콜별 통계(PerCpuArray), 수신 인터페이스별 통계(PerCpuHashMap),
+//!    목적지 포트별 통계(LruPerCpuHashMap) 업데이트
+//! 10. 의심 패킷 이벤트(RingBuf)로 유저스페이스 전달 (레이트 리밋 드롭 제외, 연결 상태 포함,
+//!     모니터링 패킷은 SAMPLING_CONFIG 비율로 1/N 샘플링)
+//! 11. DROP → XDP_DROP, REFLECT → TCP 세그먼트를 제자리에서 RST 응답으로 바꿔 XDP_TX
+//!     (RST, IP 옵션이 있는 패킷, TCP 외 프로토콜은 XDP_DROP), 그 외와 관찰 전용 모드는 XDP_PASS
 //!
 //! # BPF 맵
+//! - `ALLOWLIST`: `LpmTrie<u32, BlocklistValue>` — 절대 차단하지 않는 호스트/CIDR
 //! - `BLOCKLIST`: `HashMap<u32, BlocklistValue>` — IP 차단 목록
+//! - `BLOCKLIST_CIDR`: `LpmTrie<u32, BlocklistValue>` — CIDR 차단 목록
+//! - `PORT_RULES`: `HashMap<PortRuleKey, BlocklistValue>` — 포트 단위 차단 룰
+//! - `RATE_LIMIT`: `LruHashMap<u32, TokenBucket>` — 출발지별 토큰 버킷
+//! - `RATE_LIMIT_CONFIG`: `Array<RateLimitParams>` — 레이트 리밋 파라미터 (1 엔트리)
+//! - `SAMPLING_CONFIG`: `Array<SamplingParams>` — 모니터링 이벤트 샘플링 비율 (1 엔트리)
+//! - `DRY_RUN_CONFIG`: `Array<u32>` — 관찰 전용(드라이런) 플래그 (1 엔트리, 0이 아니면 드롭하지 않음)
 //! - `STATS`: `PerCpuArray<ProtoStats>` — 프로토콜별 패킷/바이트/드롭 카운터
+//! - `IFACE_STATS`: `PerCpuHashMap<u32, ProtoStats>` — 수신 인터페이스(ifindex)별 카운터
+//! - `PORT_STATS`: `LruPerCpuHashMap<PortStatsKey, ProtoStats>` — 목적지 포트별 카운터
+//! - `CONNTRACK`: `LruHashMap<FlowKey, FlowState>` — 5-튜플별 연결 상태/타임스탬프
+//! - `SOCK_OWNERS`: `LruHashMap<FlowKey, SockOwner>` — TCP 연결별 소유 프로세스 (kprobe가 기록)
 //! - `EVENTS`: `RingBuf` — 의심 패킷 이벤트를 유저스페이스로 전달
+//! - `TLS_HELLOS`: `RingBuf` — TC egress가 캡처한 ClientHello 앞부분 (SNI 추출용)
+//! - `RINGBUF_DROPS`: `PerCpuArray<u64>` — RingBuf별 `reserve` 실패(버퍼 가득 참) 수
+//!
+//! `ALLOWLIST`, `BLOCKLIST`, `BLOCKLIST_CIDR`, `PORT_RULES`, `STATS`, `PORT_STATS`는 이름으로 고정(pin)되어
+//! 유저스페이스가 지정한 bpffs 디렉토리에 남고, 재시작 시 같은 맵을 다시 사용합니다.
 //!
 //! # 네트워크 헤더
 //! 헤더 구조체는 [`network_types`] 크레이트를 사용합니다.
 //! `EthHdr`, `Ipv4Hdr`, `TcpHdr`, `UdpHdr` — `#![no_std]` 호환, Aya 에코시스템 표준.
+//! VLAN 태그는 TPID 뒤 4바이트(TCI + 내부 EtherType)만 읽으면 되므로 [`VlanHdr`]를 직접 정의합니다.
+//!
+//! # 프로세스 연관
+//! - `ironpost_tcp_connect` (kprobe `tcp_connect`): 나가는 연결을 만든 프로세스
+//! - `ironpost_inet_csk_accept` (kretprobe `inet_csk_accept`): 연결을 받아들인 프로세스
+//!
+//! 두 프로그램 모두 현재 태스크의 PID, comm, cgroup ID를 `SOCK_OWNERS`에 기록하며,
+//! 키는 XDP가 보는 수신 방향(원격 → 로컬) FlowKey입니다. 유저스페이스는 패킷 이벤트를
+//! 받을 때 같은 키로 조회해 알림에 프로세스/컨테이너를 덧붙입니다.
+//! vmlinux 바인딩 없이 `struct sock_common` 앞부분만 읽으므로 [`SockCommon`]을 직접 정의합니다.
+//!
+//! # TLS SNI
+//! XDP는 수신 패킷만 보므로 로컬 클라이언트가 보내는 ClientHello는 TC egress 분류기
+//! `ironpost_tls_egress`가 캡처합니다. TCP 페이로드가 ClientHello로 시작하면 앞부분
+//! [`TLS_HELLO_CAPTURE_LEN`] 바이트를 `TLS_HELLOS`로 보내고, SNI 파싱은 유저스페이스가
+//! 합니다. 분류기는 패킷을 바꾸거나 드롭하지 않고 항상 `TC_ACT_OK`를 반환합니다.
 
 #![no_std]
 #![no_main]
 
 use aya_ebpf::{
-    bindings::xdp_action,
-    macros::{map, xdp},
-    maps::{HashMap, PerCpuArray, RingBuf},
-    programs::XdpContext,
+    bindings::{BPF_F_NO_PREALLOC, TC_ACT_OK, xdp_action},
+    helpers::{
+        bpf_get_current_cgroup_id, bpf_get_current_comm, bpf_get_current_pid_tgid,
+        bpf_get_prandom_u32, bpf_ktime_get_ns, bpf_probe_read_kernel,
+    },
+    macros::{classifier, kprobe, kretprobe, map, xdp},
+    maps::{
+        Array, HashMap, LpmTrie, LruHashMap, LruPerCpuHashMap, PerCpuArray, PerCpuHashMap, RingBuf,
+        lpm_trie::Key,
+    },
+    programs::{ProbeContext, RetProbeContext, TcContext, XdpContext},
 };
 use aya_log_ebpf::info;
 use core::mem;
+use core::sync::atomic::{AtomicU64, Ordering};
 
 use network_types::eth::{EthHdr, EtherType};
 use network_types::ip::{IpProto, Ipv4Hdr};
@@ -37,38 +90,218 @@ use network_types::tcp::TcpHdr;
 use network_types::udp::UdpHdr;
 
 use ironpost_ebpf_common::{
-    ACTION_DROP, ACTION_MONITOR, ACTION_PASS, BlocklistValue, PacketEventData, ProtoStats,
-    STATS_IDX_ICMP, STATS_IDX_OTHER, STATS_IDX_TCP, STATS_IDX_TOTAL, STATS_IDX_UDP,
-    STATS_MAX_ENTRIES, TCP_ACK, TCP_FIN, TCP_PSH, TCP_RST, TCP_SYN,
+    ACTION_DROP, ACTION_MONITOR, ACTION_PASS, ACTION_REFLECT, ALLOWLIST_MAX_ENTRIES,
+    BLOCKLIST_CIDR_MAX_ENTRIES, BLOCKLIST_MAX_ENTRIES, BlocklistValue, CONNTRACK_IDLE_TIMEOUT_NS,
+    CONNTRACK_MAX_ENTRIES, CT_STATE_NEW, CT_STATE_UNTRACKED, EVENT_TYPE_PACKET,
+    EVENT_TYPE_TLS_HELLO, EventHeader, EventRecord, FlowKey, FlowState, IFACE_STATS_MAX_ENTRIES,
+    IPV4_HDR_LEN, PORT_RULE_ANY_IP, PORT_RULE_ANY_PROTO, PORT_RULES_MAX_ENTRIES,
+    PORT_STATS_MAX_ENTRIES, PROTO_TCP, PacketEventData, PortRuleKey, PortStatsKey, ProtoStats,
+    RATE_LIMIT_MAX_SOURCES, RATE_LIMIT_TOKEN_SCALE, RINGBUF_DROPS_IDX_EVENTS,
+    RINGBUF_DROPS_IDX_TLS_HELLOS, RINGBUF_DROPS_MAX_ENTRIES, RateLimitParams,
+    SOCK_OWNERS_MAX_ENTRIES, STATS_IDX_ICMP, STATS_IDX_OTHER, STATS_IDX_TCP, STATS_IDX_TOTAL,
+    STATS_IDX_UDP, STATS_MAX_ENTRIES, SamplingParams, SockOwner, TCP_ACK, TCP_FIN, TCP_HDR_LEN,
+    TCP_PSH, TCP_RST, TCP_SYN, TLS_HELLO_CAPTURE_LEN, TlsHelloData, TokenBucket, is_drop_action,
+    is_tls_client_hello, next_flow_state, tcp_rst_reply,
 };
 
+// =============================================================================
+// VLAN 태그
+// =============================================================================
+
+/// 802.1Q VLAN 태그 (TPID는 바깥 EtherType 자리에 있으므로 그 뒤 4바이트)
+#[repr(C)]
+struct VlanHdr {
+    /// 우선순위(PCP) + DEI + VLAN ID
+    _tci: u16,
+    /// 태그 뒤에 오는 EtherType (네트워크 바이트 오더)
+    ether_type: u16,
+}
+
+/// VLAN 태그 길이 (4바이트)
+const VLAN_HDR_LEN: usize = mem::size_of::<VlanHdr>();
+
+/// 802.1Q 태그 TPID (네트워크 바이트 오더)
+const ETH_P_8021Q: u16 = 0x8100u16.to_be();
+
+/// 802.1ad (QinQ 서비스 태그) TPID (네트워크 바이트 오더)
+const ETH_P_8021AD: u16 = 0x88A8u16.to_be();
+
+// =============================================================================
+// 소켓 공통 헤더
+// =============================================================================
+
+/// `struct sock_common`의 앞부분 (주소/포트 쌍)
+///
+/// 이 필드들의 오프셋은 오래된 커널부터 바뀌지 않았으므로 BTF 재배치 없이 읽습니다.
+#[repr(C)]
+struct SockCommon {
+    /// 원격 IPv4 주소 (네트워크 바이트 오더)
+    skc_daddr: u32,
+    /// 로컬 IPv4 주소 (네트워크 바이트 오더)
+    skc_rcv_saddr: u32,
+    /// 해시 (사용하지 않음)
+    _skc_hash: u32,
+    /// 원격 포트 (네트워크 바이트 오더)
+    skc_dport: u16,
+    /// 로컬 포트 (호스트 바이트 오더)
+    skc_num: u16,
+    /// 주소 패밀리 (AF_INET 등)
+    skc_family: u16,
+}
+
+/// IPv4 주소 패밀리
+const AF_INET: u16 = 2;
+
 // =============================================================================
 // eBPF 맵 정의
 // =============================================================================
 
+/// 허용 목록 (관리용 IP 등 절대 차단하지 않을 출발지)
+///
+/// - 키: 프리픽스 길이 + IPv4 네트워크 주소 (BLOCKLIST_CIDR와 동일한 표현, 호스트는 /32)
+/// - 값: BlocklistValue (ACTION_PASS + 히트 카운트)
+/// - 맵 선택 근거: 엔트리가 적으므로 호스트와 서브넷을 LpmTrie 하나로 조회
+/// - bpffs에 고정되어 데몬 재시작 후에도 유지됩니다 (PINNED_MAPS).
+#[map]
+static ALLOWLIST: LpmTrie<u32, BlocklistValue> =
+    LpmTrie::pinned(ALLOWLIST_MAX_ENTRIES, BPF_F_NO_PREALLOC);
+
 /// IP 차단 목록
 ///
 /// - 키: IPv4 주소 (u32, 네트워크 바이트 오더)
-/// - 값: BlocklistValue (액션 코드)
+/// - 값: BlocklistValue (액션 코드 + 히트 카운트)
 /// - 맵 선택 근거: O(1) 조회, 유저스페이스에서 동적 업데이트 가능
+/// - bpffs에 고정되어 데몬 재시작 후에도 유지됩니다 (PINNED_MAPS).
+#[map]
+static BLOCKLIST: HashMap<u32, BlocklistValue> = HashMap::pinned(BLOCKLIST_MAX_ENTRIES, 0);
+
+/// CIDR 차단 목록
+///
+/// - 키: 프리픽스 길이 + IPv4 네트워크 주소 (네트워크 바이트 오더 그대로)
+/// - 값: BlocklistValue (액션 코드 + 히트 카운트)
+/// - 맵 선택 근거: 서브넷 하나를 엔트리 하나로 표현, 최장 프리픽스 매칭
+/// - LPM 트라이는 커널 요구사항상 BPF_F_NO_PREALLOC이 필수입니다.
+/// - bpffs에 고정되어 데몬 재시작 후에도 유지됩니다 (PINNED_MAPS).
 #[map]
-static BLOCKLIST: HashMap<u32, BlocklistValue> = HashMap::with_max_entries(10_000, 0);
+static BLOCKLIST_CIDR: LpmTrie<u32, BlocklistValue> =
+    LpmTrie::pinned(BLOCKLIST_CIDR_MAX_ENTRIES, BPF_F_NO_PREALLOC);
+
+/// 포트 단위 차단 룰
+///
+/// - 키: PortRuleKey (출발지 IP + 목적지 포트 + 프로토콜, 0은 와일드카드)
+/// - 값: BlocklistValue (액션 코드 + 히트 카운트)
+/// - 맵 선택 근거: 와일드카드 조합별 O(1) 조회, 유저스페이스에서 동적 업데이트 가능
+/// - bpffs에 고정되어 데몬 재시작 후에도 히트 카운트가 유지됩니다 (PINNED_MAPS).
+#[map]
+static PORT_RULES: HashMap<PortRuleKey, BlocklistValue> =
+    HashMap::pinned(PORT_RULES_MAX_ENTRIES, 0);
+
+/// 출발지별 토큰 버킷
+///
+/// - 키: 출발지 IPv4 주소 (BLOCKLIST와 동일한 표현)
+/// - 값: TokenBucket (남은 토큰 + 마지막 리필 시각)
+/// - 맵 선택 근거: 출발지 수가 무한히 늘 수 있으므로 LRU로 오래된 버킷을 자동 축출
+#[map]
+static RATE_LIMIT: LruHashMap<u32, TokenBucket> =
+    LruHashMap::with_max_entries(RATE_LIMIT_MAX_SOURCES, 0);
+
+/// 레이트 리밋 파라미터
+///
+/// - 인덱스: 0 (단일 엔트리, 유저스페이스가 엔진 시작 시 기록)
+/// - 값: RateLimitParams (pps가 0이면 비활성화)
+/// - 맵 선택 근거: 프로그램 재로드 없이 파라미터를 바꿀 수 있는 가장 단순한 구조
+#[map]
+static RATE_LIMIT_CONFIG: Array<RateLimitParams> = Array::with_max_entries(1, 0);
+
+/// 이벤트 샘플링 파라미터
+///
+/// - 인덱스: 0 (단일 엔트리, 유저스페이스가 엔진 시작 시 기록)
+/// - 값: SamplingParams (monitor_rate가 0/1이면 모든 모니터링 패킷을 전송)
+/// - 맵 선택 근거: RATE_LIMIT_CONFIG와 같이 재로드 없이 비율을 바꿀 수 있는 단일 엔트리
+#[map]
+static SAMPLING_CONFIG: Array<SamplingParams> = Array::with_max_entries(1, 0);
+
+/// 관찰 전용(드라이런) 플래그
+///
+/// - 인덱스: 0 (단일 엔트리, 유저스페이스가 엔진 시작 시 기록)
+/// - 값: 0이면 판정대로 드롭, 0이 아니면 드롭 판정을 통과시키고 `would_drops`로 집계
+/// - 맵 선택 근거: SAMPLING_CONFIG와 같이 재로드 없이 바꿀 수 있는 단일 엔트리
+#[map]
+static DRY_RUN_CONFIG: Array<u32> = Array::with_max_entries(1, 0);
 
 /// 프로토콜별 통계 카운터
 ///
 /// - 인덱스: STATS_IDX_TCP(0), STATS_IDX_UDP(1), STATS_IDX_ICMP(2),
 ///           STATS_IDX_OTHER(3), STATS_IDX_TOTAL(4)
 /// - 맵 선택 근거: CPU별 독립 카운터, 락 프리, 캐시 라인 경합 없음
+/// - bpffs에 고정되어 데몬 재시작 후에도 누적 카운터가 유지됩니다 (PINNED_MAPS).
 #[map]
-static STATS: PerCpuArray<ProtoStats> = PerCpuArray::with_max_entries(STATS_MAX_ENTRIES, 0);
+static STATS: PerCpuArray<ProtoStats> = PerCpuArray::pinned(STATS_MAX_ENTRIES, 0);
+
+/// 수신 인터페이스별 통계 카운터
+///
+/// - 키: 패킷이 들어온 인터페이스의 ifindex
+/// - 값: ProtoStats (CPU별 패킷/바이트/드롭)
+/// - 맵 선택 근거: 프로그램이 어태치된 인터페이스 수만큼만 엔트리가 생기므로 LRU 불필요
+/// - ifindex는 호스트 상태에 따라 바뀔 수 있으므로 고정(pin)하지 않습니다.
+#[map]
+static IFACE_STATS: PerCpuHashMap<u32, ProtoStats> =
+    PerCpuHashMap::with_max_entries(IFACE_STATS_MAX_ENTRIES, 0);
+
+/// 목적지 포트별 통계 카운터
+///
+/// - 키: PortStatsKey (목적지 포트 + 프로토콜, TCP/UDP만)
+/// - 값: ProtoStats (CPU별 패킷/바이트/드롭)
+/// - 맵 선택 근거: 포트 공간 전체를 배열로 두지 않고, LRU로 최근 트래픽이 있는 포트만 유지
+/// - bpffs에 고정되어 데몬 재시작 후에도 누적 카운터가 유지됩니다 (PINNED_MAPS).
+#[map]
+static PORT_STATS: LruPerCpuHashMap<PortStatsKey, ProtoStats> =
+    LruPerCpuHashMap::pinned(PORT_STATS_MAX_ENTRIES, 0);
+
+/// 연결 추적 테이블
+///
+/// - 키: FlowKey (출발지/목적지 IP, 포트, 프로토콜)
+/// - 값: FlowState (상태, 첫/마지막 패킷 시각, 패킷 수)
+/// - 맵 선택 근거: 흐름 수가 무한히 늘 수 있으므로 LRU로 오래된 흐름을 자동 축출
+/// - 재시작 후 다시 쌓이는 일시적 상태이므로 고정(pin)하지 않습니다.
+#[map]
+static CONNTRACK: LruHashMap<FlowKey, FlowState> =
+    LruHashMap::with_max_entries(CONNTRACK_MAX_ENTRIES, 0);
+
+/// TCP 연결별 소유 프로세스
+///
+/// - 키: FlowKey (수신 방향: 원격 → 로컬, CONNTRACK 키와 같은 표현)
+/// - 값: SockOwner (PID, cgroup ID, comm)
+/// - 맵 선택 근거: 닫힌 연결을 따로 지우지 않으므로 LRU로 오래된 연결을 자동 축출
+/// - kprobe가 어태치된 동안만 쌓이는 일시적 상태이므로 고정(pin)하지 않습니다.
+#[map]
+static SOCK_OWNERS: LruHashMap<FlowKey, SockOwner> =
+    LruHashMap::with_max_entries(SOCK_OWNERS_MAX_ENTRIES, 0);
 
 /// 의심 패킷 이벤트 링 버퍼
 ///
-/// - 크기: 256KB (설정으로 변경 가능)
+/// - 크기: 256KB (기본값, 유저스페이스가 로드 시 `ring_buffer_size`로 덮어씀)
 /// - 맵 선택 근거: PerfEventArray보다 효율적, 가변 크기 메시지, 단일 버퍼 공유
 #[map]
 static EVENTS: RingBuf = RingBuf::with_byte_size(256 * 1024, 0);
 
+/// TLS ClientHello 캡처 링 버퍼
+///
+/// - 크기: 256KB (레코드 하나가 약 1KB)
+/// - 맵 선택 근거: ClientHello는 크고 드물므로 EVENTS와 분리해 의심 패킷 이벤트를 밀어내지 않음
+#[map]
+static TLS_HELLOS: RingBuf = RingBuf::with_byte_size(256 * 1024, 0);
+
+/// RingBuf 드롭 카운터
+///
+/// - 인덱스: RINGBUF_DROPS_IDX_EVENTS(0), RINGBUF_DROPS_IDX_TLS_HELLOS(1)
+/// - 값: 버퍼가 가득 차 `reserve`에 실패한 횟수 (CPU별)
+/// - 맵 선택 근거: RingBuf는 넘친 레코드를 알려주지 않으므로 직접 세고, CPU별 배열로 락 없이 증가
+/// - 리로드 시 0부터 다시 세므로 고정(pin)하지 않습니다 (유저스페이스가 증가분만 누적).
+#[map]
+static RINGBUF_DROPS: PerCpuArray<u64> =
+    PerCpuArray::with_max_entries(RINGBUF_DROPS_MAX_ENTRIES, 0);
+
 // =============================================================================
 // XDP 엔트리 포인트
 // =============================================================================
@@ -97,16 +330,19 @@ fn try_ironpost_xdp(ctx: XdpContext) -> Result<u32, u32> {
     // 1) Ethernet 헤더 파싱
     let eth = ptr_at::<EthHdr>(&ctx, 0).ok_or(0u32)?;
 
-    // IPv4만 처리 (IPv6은 Phase 2 확장 범위)
+    // 트렁크 인터페이스의 VLAN 태그(802.1Q 하나 또는 QinQ 두 개)를 건너뜀
+    // SAFETY: 바운드 체크를 ptr_at에서 수행했으므로 포인터 접근이 안전합니다
+    let (ether_type, l3_offset) = skip_vlan_tags(&ctx, unsafe { (*eth).ether_type }).ok_or(0u32)?;
+
+    // IPv4만 처리 (IPv6은 Phase 2 확장 범위, 태그가 3개 이상이면 통과)
     // EtherType enum은 네트워크 바이트 오더로 미리 인코딩되어 있어
     // from_be() 변환 없이 바로 비교 가능
-    // SAFETY: 바운드 체크를 ptr_at에서 수행했으므로 포인터 접근이 안전합니다
-    if unsafe { (*eth).ether_type } != EtherType::Ipv4 as u16 {
+    if ether_type != EtherType::Ipv4 as u16 {
         return Ok(xdp_action::XDP_PASS);
     }
 
     // 2) IPv4 헤더 파싱
-    let ipv4 = ptr_at::<Ipv4Hdr>(&ctx, EthHdr::LEN).ok_or(0u32)?;
+    let ipv4 = ptr_at::<Ipv4Hdr>(&ctx, l3_offset).ok_or(0u32)?;
     // SAFETY: ptr_at 바운드 체크 통과
     // IPv4 주소는 항상 네트워크 바이트 오더(big-endian)로 저장됨
     let src_ip = unsafe { u32::from_be_bytes((*ipv4).src_addr) };
@@ -120,7 +356,7 @@ fn try_ironpost_xdp(ctx: XdpContext) -> Result<u32, u32> {
         return Ok(xdp_action::XDP_PASS);
     }
 
-    let transport_offset = EthHdr::LEN + ip_hdr_len;
+    let transport_offset = l3_offset + ip_hdr_len;
 
     // 3) TCP/UDP 헤더 파싱 → 포트 + TCP 플래그 추출
     let mut src_port: u16 = 0;
@@ -169,26 +405,110 @@ fn try_ironpost_xdp(ctx: XdpContext) -> Result<u32, u32> {
         _ => {} // ICMP 등: 포트 없음, tcp_flags=0 유지
     }
 
-    // 4) 차단 목록 조회
+    // 4) 허용 목록 조회 — 매칭되면 차단 목록, 포트 룰, 레이트 리밋을 모두 건너뜁니다.
+    let allowed = match ALLOWLIST.get(&Key::new(32, src_ip.to_be())) {
+        Some(value) => {
+            let value = value as *const BlocklistValue as *mut BlocklistValue;
+            // SAFETY: LpmTrie::get이 반환한 쓰기 가능한 커널 맵 값 포인터입니다.
+            // hits는 8바이트 정렬된 u64이며 여러 CPU가 동시에 갱신하므로 원자적으로 증가시킵니다.
+            unsafe {
+                AtomicU64::from_ptr(&raw mut (*value).hits).fetch_add(1, Ordering::Relaxed);
+            }
+            true
+        }
+        None => false,
+    };
+
+    // 5) 차단 목록 조회 (호스트 엔트리 우선, 없으면 CIDR 최장 프리픽스 매칭)
     let mut action = ACTION_PASS;
-    // SAFETY: HashMap 맵 접근 후 Option으로 null 체크 수행
-    let blocked = unsafe { BLOCKLIST.get(&src_ip) };
-    if let Some(entry) = blocked {
-        action = entry.action;
+    let entry = if allowed {
+        None
+    } else {
+        BLOCKLIST.get_ptr_mut(&src_ip).or_else(|| {
+            // LPM 키 데이터는 패킷의 바이트 순서(네트워크 오더)를 그대로 사용합니다.
+            BLOCKLIST_CIDR
+                .get(&Key::new(32, src_ip.to_be()))
+                .map(|value| value as *const BlocklistValue as *mut BlocklistValue)
+        })
+    };
+    // SAFETY: 두 조회 모두 null 체크 후 Option으로 반환한 맵 값 포인터입니다.
+    // 맵 값은 커널 메모리에 있으며 쓰기 가능합니다 (HashMap::get_ptr_mut와 동일).
+    // hits는 8바이트 정렬된 u64이며, 여러 CPU가 동시에 갱신하므로 원자적으로 증가시킵니다.
+    if let Some(entry) = entry {
+        unsafe {
+            action = (*entry).action;
+            AtomicU64::from_ptr(&raw mut (*entry).hits).fetch_add(1, Ordering::Relaxed);
+        }
     }
 
-    // 5) 프로토콜별 통계 업데이트
+    // 6) 포트 룰 조회 (TCP/UDP만, 허용 목록에 있거나 차단 목록에서 이미 DROP/REFLECT면 생략)
+    // 포트 룰의 DROP/REFLECT는 차단 목록의 MONITOR보다 우선합니다.
+    if !allowed && !is_drop_action(action) && dst_port != 0 {
+        if let Some(rule) = lookup_port_rule(src_ip, dst_port, proto as u8) {
+            // SAFETY: get_ptr_mut는 null 체크 후 Option으로 반환합니다.
+            // hits는 8바이트 정렬된 u64이며 여러 CPU가 동시에 갱신하므로 원자적으로 증가시킵니다.
+            unsafe {
+                let rule_action = (*rule).action;
+                if is_drop_action(rule_action) || action == ACTION_PASS {
+                    action = rule_action;
+                }
+                AtomicU64::from_ptr(&raw mut (*rule).hits).fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    // 7) 출발지별 레이트 리밋 (허용 목록 출발지와 이미 차단된 패킷은 토큰을 소비하지 않음)
+    // 레이트 리밋 드롭은 통계에는 집계하되, 이벤트/로그는 남기지 않아 유저스페이스 부하를 막습니다.
+    let mut rate_limited = false;
+    if !allowed && !is_drop_action(action) && !rate_limit_allow(src_ip) {
+        action = ACTION_DROP;
+        rate_limited = true;
+    }
+
+    // 관찰 전용 모드에서는 드롭 판정(레이트 리밋 포함)을 통과시키고 would-drop으로만 집계합니다.
+    let would_drop = is_drop_action(action);
+    let dropped = would_drop && !observe_only();
+
+    // 8) 연결 추적 (차단된 패킷은 흐름을 만들지 않음)
+    let ct_state = if !dropped && dst_port != 0 {
+        track_flow(
+            &FlowKey::new(src_ip, dst_ip, src_port, dst_port, proto as u8),
+            proto as u8,
+            tcp_flags,
+        )
+    } else {
+        CT_STATE_UNTRACKED
+    };
+
+    // 9) 프로토콜별 통계 업데이트
     let stats_idx = match proto {
         IpProto::Tcp => STATS_IDX_TCP,
         IpProto::Udp => STATS_IDX_UDP,
         IpProto::Icmp => STATS_IDX_ICMP,
         _ => STATS_IDX_OTHER,
     };
-    update_stats(stats_idx, pkt_len, action);
-    update_stats(STATS_IDX_TOTAL, pkt_len, action);
+    let delta = ProtoStats {
+        packets: 1,
+        bytes: pkt_len as u64,
+        drops: dropped as u64,
+        would_drops: (would_drop && !dropped) as u64,
+    };
+    update_stats(stats_idx, &delta);
+    update_stats(STATS_IDX_TOTAL, &delta);
+    // SAFETY: ctx.ctx는 커널이 넘겨준 유효한 xdp_md 포인터입니다.
+    update_iface_stats(unsafe { (*ctx.ctx).ingress_ifindex }, &delta);
+    if dst_port != 0 {
+        update_port_stats(PortStatsKey::new(dst_port, proto as u8), &delta);
+    }
 
-    // 6) 의심 패킷 또는 모니터링 대상 → RingBuf로 이벤트 전송
-    if action == ACTION_DROP || action == ACTION_MONITOR {
+    // 10) 의심 패킷 또는 모니터링 대상 → RingBuf로 이벤트 전송
+    // 모니터링 패킷은 1/N로 샘플링해 RingBuf가 넘치지 않게 하고, 차단 이벤트는 모두 전송합니다.
+    let sample_rate = if action == ACTION_MONITOR {
+        monitor_sample_rate()
+    } else {
+        1
+    };
+    if !rate_limited && action != ACTION_PASS && sampled(sample_rate) {
         let event = PacketEventData {
             src_ip,
             dst_ip,
@@ -198,20 +518,172 @@ fn try_ironpost_xdp(ctx: XdpContext) -> Result<u32, u32> {
             protocol: proto as u8,
             action,
             tcp_flags,
-            _pad: [0; 1],
+            ct_state,
+            sample_rate,
         };
         emit_event(&event);
     }
 
-    // 7) 최종 결정
-    if action == ACTION_DROP {
-        info!(&ctx, "DROP src={:i}", u32::from_be(src_ip));
-        Ok(xdp_action::XDP_DROP)
-    } else {
+    // 11) 최종 결정
+    if !dropped {
+        if would_drop && !rate_limited {
+            info!(&ctx, "WOULD DROP src={:i}", u32::from_be(src_ip));
+        }
         Ok(xdp_action::XDP_PASS)
+    } else if action == ACTION_REFLECT {
+        if proto == IpProto::Tcp && reflect_tcp_rst(&ctx, l3_offset) {
+            info!(&ctx, "REFLECT src={:i}", u32::from_be(src_ip));
+            Ok(xdp_action::XDP_TX)
+        } else {
+            Ok(xdp_action::XDP_DROP)
+        }
+    } else {
+        if !rate_limited {
+            info!(&ctx, "DROP src={:i}", u32::from_be(src_ip));
+        }
+        Ok(xdp_action::XDP_DROP)
     }
 }
 
+// =============================================================================
+// 프로세스 연관 (kprobe)
+// =============================================================================
+
+/// 나가는 TCP 연결의 소유 프로세스를 기록합니다 (kprobe `tcp_connect`).
+///
+/// `tcp_connect`는 로컬 포트가 정해진 뒤 SYN을 보내기 직전에 호출되므로,
+/// 첫 인자 `struct sock *`에 4-튜플이 모두 채워져 있습니다.
+#[kprobe]
+pub fn ironpost_tcp_connect(ctx: ProbeContext) -> u32 {
+    if let Some(sk) = ctx.arg::<*const SockCommon>(0) {
+        record_sock_owner(sk);
+    }
+    0
+}
+
+/// 받아들인 TCP 연결의 소유 프로세스를 기록합니다 (kretprobe `inet_csk_accept`).
+///
+/// 반환값이 새 연결의 `struct sock *`이며, 현재 태스크는 `accept()`를 호출한 프로세스입니다.
+#[kretprobe]
+pub fn ironpost_inet_csk_accept(ctx: RetProbeContext) -> u32 {
+    if let Some(sk) = ctx.ret::<*const SockCommon>() {
+        record_sock_owner(sk);
+    }
+    0
+}
+
+/// 소켓의 4-튜플을 수신 방향 FlowKey로 만들어 현재 태스크 정보를 기록합니다.
+///
+/// IPv4가 아니거나 커널 메모리를 읽지 못하면 아무것도 기록하지 않습니다.
+#[inline(always)]
+fn record_sock_owner(sk: *const SockCommon) {
+    if sk.is_null() {
+        return;
+    }
+    // SAFETY: bpf_probe_read_kernel은 읽기 실패 시 에러를 반환하며 커널 포인터를 역참조하지 않습니다.
+    let Ok(common) = (unsafe { bpf_probe_read_kernel(sk) }) else {
+        return;
+    };
+    if common.skc_family != AF_INET {
+        return;
+    }
+
+    // XDP는 원격 → 로컬 패킷을 보므로 원격 주소/포트가 출발지입니다.
+    // 주소와 원격 포트는 네트워크 바이트 오더로 저장되어 from_be로 패킷 헤더 표현에 맞춥니다.
+    let key = FlowKey::new(
+        u32::from_be(common.skc_daddr),
+        u32::from_be(common.skc_rcv_saddr),
+        u16::from_be(common.skc_dport),
+        common.skc_num,
+        PROTO_TCP,
+    );
+    let owner = SockOwner {
+        pid: (bpf_get_current_pid_tgid() >> 32) as u32,
+        _pad: 0,
+        // SAFETY: bpf_get_current_cgroup_id는 인자가 없고 항상 성공하는 헬퍼입니다.
+        cgroup_id: unsafe { bpf_get_current_cgroup_id() },
+        comm: bpf_get_current_comm().unwrap_or([0; 16]),
+    };
+    // LRU 맵이므로 가득 차도 가장 오래된 연결을 축출하고 삽입됩니다.
+    let _ = SOCK_OWNERS.insert(&key, &owner, 0);
+}
+
+// =============================================================================
+// TLS ClientHello 캡처 (TC egress)
+// =============================================================================
+
+/// 나가는 TLS ClientHello를 캡처하는 TC 분류기 엔트리 포인트
+///
+/// 캡처 여부와 관계없이 패킷은 그대로 통과시킵니다.
+#[classifier]
+pub fn ironpost_tls_egress(ctx: TcContext) -> i32 {
+    let _ = try_capture_client_hello(&ctx);
+    TC_ACT_OK
+}
+
+/// TCP 페이로드가 ClientHello로 시작하면 앞부분을 `TLS_HELLOS`로 보냅니다.
+///
+/// egress의 VLAN 태그는 보통 드라이버가 붙이므로 Ethernet 바로 뒤의 IPv4만 처리합니다.
+fn try_capture_client_hello(ctx: &TcContext) -> Result<(), i64> {
+    let eth: EthHdr = ctx.load(0)?;
+    if eth.ether_type != EtherType::Ipv4 as u16 {
+        return Ok(());
+    }
+
+    let ipv4: Ipv4Hdr = ctx.load(EthHdr::LEN)?;
+    if !matches!(ipv4.proto, IpProto::Tcp) {
+        return Ok(());
+    }
+    let ihl = (ipv4.vihl & 0x0F) as usize;
+    if !(5..=15).contains(&ihl) {
+        return Ok(());
+    }
+
+    let tcp_offset = EthHdr::LEN + ihl * 4;
+    let tcp: TcpHdr = ctx.load(tcp_offset)?;
+    // 데이터 오프셋은 TCP 헤더 12번째 바이트의 상위 4비트 (32비트 워드 단위)
+    let doff = (ctx.load::<u8>(tcp_offset + 12)? >> 4) as usize;
+    if doff < 5 {
+        return Ok(());
+    }
+    let payload_offset = tcp_offset + doff * 4;
+
+    // 레코드 헤더(5바이트) + 핸드셰이크 타입(1바이트)으로 먼저 거름
+    // (페이로드가 6바이트보다 짧으면 load가 실패해 캡처하지 않음)
+    let prefix: [u8; 6] = ctx.load(payload_offset)?;
+    if !is_tls_client_hello(&prefix) {
+        return Ok(());
+    }
+
+    // 버퍼가 가득 차면 드롭 수만 세고 이 ClientHello는 건너뜁니다 (성능 우선)
+    let Some(mut entry) = TLS_HELLOS.reserve::<EventRecord<TlsHelloData>>(0) else {
+        count_ringbuf_drop(RINGBUF_DROPS_IDX_TLS_HELLOS);
+        return Ok(());
+    };
+    let record = entry.as_mut_ptr();
+    // SAFETY: reserve가 EventRecord<TlsHelloData> 크기의 쓰기 가능한 영역을 반환했습니다.
+    // 레코드 헤더와 본문 헤더 필드를 모두 기록하고, 페이로드는 load_bytes가 남은 길이만큼만 복사합니다.
+    unsafe {
+        (*record).header = EventHeader::new(
+            EVENT_TYPE_TLS_HELLO,
+            mem::size_of::<EventRecord<TlsHelloData>>(),
+        );
+        let hello = &raw mut (*record).data;
+        (*hello).src_ip = u32::from_be_bytes(ipv4.src_addr);
+        (*hello).dst_ip = u32::from_be_bytes(ipv4.dst_addr);
+        (*hello).src_port = u16::from_be_bytes(tcp.source);
+        (*hello).dst_port = u16::from_be_bytes(tcp.dest);
+        match ctx.load_bytes(payload_offset, &mut (*hello).payload) {
+            Ok(copied) => {
+                (*hello).captured_len = copied.min(TLS_HELLO_CAPTURE_LEN) as u32;
+                entry.submit(0);
+            }
+            Err(_) => entry.discard(0),
+        }
+    }
+    Ok(())
+}
+
 // =============================================================================
 // 헬퍼 함수
 // =============================================================================
@@ -233,35 +705,282 @@ fn ptr_at<T>(ctx: &XdpContext, offset: usize) -> Option<*const T> {
     Some((start + offset) as *const T)
 }
 
+/// 수신한 TCP 세그먼트를 제자리에서 RST 응답으로 바꿉니다 (XDP_TX용).
+///
+/// IP/TCP 헤더는 [`tcp_rst_reply`]가 다시 쓰고, 성공하면 이더넷 주소를 맞바꿔
+/// 수신한 인터페이스(VLAN 태그 유지)로 되돌려 보낼 수 있게 합니다.
+#[inline(always)]
+fn reflect_tcp_rst(ctx: &XdpContext, l3_offset: usize) -> bool {
+    let (Some(eth), Some(ip), Some(tcp)) = (
+        ptr_at::<EthHdr>(ctx, 0),
+        ptr_at::<[u8; IPV4_HDR_LEN]>(ctx, l3_offset),
+        ptr_at::<[u8; TCP_HDR_LEN]>(ctx, l3_offset + IPV4_HDR_LEN),
+    ) else {
+        return false;
+    };
+    // SAFETY: 세 포인터 모두 ptr_at 바운드 체크를 통과했고, XDP 패킷 버퍼는 쓰기 가능합니다.
+    // 이더넷/IPv4/TCP 영역은 서로 겹치지 않습니다.
+    unsafe {
+        if !tcp_rst_reply(&mut *ip.cast_mut(), &mut *tcp.cast_mut()) {
+            return false;
+        }
+        let eth = eth.cast_mut();
+        core::ptr::swap(&raw mut (*eth).dst_addr, &raw mut (*eth).src_addr);
+    }
+    true
+}
+
+/// VLAN TPID(802.1Q 또는 802.1ad)인지 확인합니다.
+#[inline(always)]
+fn is_vlan(ether_type: u16) -> bool {
+    ether_type == ETH_P_8021Q || ether_type == ETH_P_8021AD
+}
+
+/// Ethernet 헤더 뒤의 VLAN 태그를 최대 2개까지 건너뜁니다.
+///
+/// 태그 뒤의 EtherType과 L3 헤더 오프셋을 반환합니다. 태그가 없으면
+/// `(ether_type, EthHdr::LEN)`입니다. 검증기가 오프셋 범위를 추적할 수 있도록
+/// 루프 대신 두 단계로 펼쳐 두었으며, 세 번째 태그가 있으면 VLAN TPID가 그대로
+/// 반환되어 호출자가 IPv4가 아닌 패킷으로 처리합니다. 태그가 잘려 있으면 `None`입니다.
+#[inline(always)]
+fn skip_vlan_tags(ctx: &XdpContext, ether_type: u16) -> Option<(u16, usize)> {
+    let mut ether_type = ether_type;
+    let mut offset = EthHdr::LEN;
+
+    if is_vlan(ether_type) {
+        let vlan = ptr_at::<VlanHdr>(ctx, offset)?;
+        // SAFETY: ptr_at 바운드 체크 통과
+        ether_type = unsafe { (*vlan).ether_type };
+        offset += VLAN_HDR_LEN;
+
+        // QinQ: 바깥 서비스 태그 뒤의 고객 태그
+        if is_vlan(ether_type) {
+            let vlan = ptr_at::<VlanHdr>(ctx, offset)?;
+            // SAFETY: ptr_at 바운드 체크 통과
+            ether_type = unsafe { (*vlan).ether_type };
+            offset += VLAN_HDR_LEN;
+        }
+    }
+
+    Some((ether_type, offset))
+}
+
+/// 포트 룰을 구체적인 키부터 순서대로 조회합니다.
+///
+/// (출발지, 프로토콜) → (출발지, 모든 프로토콜) → (모든 IP, 프로토콜) → (모든 IP, 모든 프로토콜)
+#[inline(always)]
+fn lookup_port_rule(src_ip: u32, dst_port: u16, protocol: u8) -> Option<*mut BlocklistValue> {
+    PORT_RULES
+        .get_ptr_mut(&PortRuleKey::new(src_ip, dst_port, protocol))
+        .or_else(|| {
+            PORT_RULES.get_ptr_mut(&PortRuleKey::new(src_ip, dst_port, PORT_RULE_ANY_PROTO))
+        })
+        .or_else(|| PORT_RULES.get_ptr_mut(&PortRuleKey::new(PORT_RULE_ANY_IP, dst_port, protocol)))
+        .or_else(|| {
+            PORT_RULES.get_ptr_mut(&PortRuleKey::new(
+                PORT_RULE_ANY_IP,
+                dst_port,
+                PORT_RULE_ANY_PROTO,
+            ))
+        })
+}
+
+/// 출발지의 토큰 버킷에서 토큰 하나를 소비합니다.
+///
+/// 레이트 리밋이 비활성화되어 있거나 토큰이 남아 있으면 `true`,
+/// 초과하면 `false`를 반환합니다. 처음 보는 출발지는 가득 찬 버킷으로 시작합니다.
+///
+/// 여러 CPU가 같은 버킷을 동시에 갱신하면 일부 갱신이 유실될 수 있으므로
+/// 제한은 근사치입니다 (락 없이 패킷 경로를 유지하기 위한 트레이드오프).
+#[inline(always)]
+fn rate_limit_allow(src_ip: u32) -> bool {
+    let Some(params) = RATE_LIMIT_CONFIG.get(0) else {
+        return true;
+    };
+    if params.pps == 0 {
+        return true;
+    }
+    let capacity = params.burst.saturating_mul(RATE_LIMIT_TOKEN_SCALE);
+    // SAFETY: bpf_ktime_get_ns는 인자가 없고 항상 성공하는 헬퍼입니다.
+    let now = unsafe { bpf_ktime_get_ns() };
+
+    match RATE_LIMIT.get_ptr_mut(&src_ip) {
+        // SAFETY: get_ptr_mut는 null 체크 후 Option으로 반환하며,
+        // 버킷은 8바이트 정렬된 u64 두 개로 구성됩니다.
+        Some(bucket) => unsafe {
+            let elapsed = now.saturating_sub((*bucket).last_ns);
+            let tokens = (*bucket)
+                .tokens
+                .saturating_add(elapsed.saturating_mul(params.pps))
+                .min(capacity);
+            (*bucket).last_ns = now;
+            if tokens >= RATE_LIMIT_TOKEN_SCALE {
+                (*bucket).tokens = tokens - RATE_LIMIT_TOKEN_SCALE;
+                true
+            } else {
+                (*bucket).tokens = tokens;
+                false
+            }
+        },
+        None => {
+            let bucket = TokenBucket {
+                tokens: capacity.saturating_sub(RATE_LIMIT_TOKEN_SCALE),
+                last_ns: now,
+            };
+            // LRU 맵이므로 가득 차도 가장 오래된 버킷을 축출하고 삽입됩니다.
+            let _ = RATE_LIMIT.insert(&src_ip, &bucket, 0);
+            true
+        }
+    }
+}
+
 /// PerCpuArray 통계 카운터를 업데이트합니다.
 ///
 /// CPU별 독립 카운터이므로 락 없이 안전하게 업데이트됩니다.
 #[inline(always)]
-fn update_stats(idx: u32, pkt_len: u32, action: u8) {
+fn update_stats(idx: u32, delta: &ProtoStats) {
     // SAFETY: PerCpuArray 맵 접근 후 null 체크 수행.
     // get_ptr_mut는 현재 CPU의 엔트리에 대한 가변 포인터를 반환합니다.
     unsafe {
         let stats_ptr = STATS.get_ptr_mut(idx);
         if let Some(stats) = stats_ptr {
-            (*stats).packets += 1;
-            (*stats).bytes += pkt_len as u64;
-            if action == ACTION_DROP {
-                (*stats).drops += 1;
+            add_stats(stats, delta);
+        }
+    }
+}
+
+/// 현재 CPU의 통계 엔트리에 패킷 하나의 증분을 더합니다.
+///
+/// # Safety
+/// `stats`는 null이 아닌 맵 값 포인터여야 합니다 (`get_ptr_mut` 반환값).
+#[inline(always)]
+unsafe fn add_stats(stats: *mut ProtoStats, delta: &ProtoStats) {
+    // SAFETY: 호출자가 유효한 맵 값 포인터를 보장합니다.
+    unsafe {
+        (*stats).packets += delta.packets;
+        (*stats).bytes += delta.bytes;
+        (*stats).drops += delta.drops;
+        (*stats).would_drops += delta.would_drops;
+    }
+}
+
+/// 수신 인터페이스별 통계 카운터를 업데이트합니다.
+///
+/// 현재 CPU의 값만 갱신하므로 락이 필요 없습니다. 처음 보는 인터페이스는 새 엔트리로
+/// 삽입하며, 맵이 가득 차면 해당 인터페이스는 집계하지 않습니다.
+#[inline(always)]
+fn update_iface_stats(ifindex: u32, delta: &ProtoStats) {
+    match IFACE_STATS.get_ptr_mut(&ifindex) {
+        // SAFETY: get_ptr_mut는 null 체크 후 현재 CPU 엔트리의 포인터를 반환합니다.
+        Some(stats) => unsafe { add_stats(stats, delta) },
+        None => {
+            let _ = IFACE_STATS.insert(&ifindex, delta, 0);
+        }
+    }
+}
+
+/// 흐름 상태를 갱신하고 갱신 후 상태를 반환합니다.
+///
+/// 처음 보는 흐름이나 [`CONNTRACK_IDLE_TIMEOUT_NS`]보다 오래 조용했던 흐름은 NEW로 시작하며,
+/// 이후 상태 전이는 [`next_flow_state`]를 따릅니다. 여러 CPU가 같은 흐름을 동시에 갱신하면
+/// 패킷 수가 일부 누락될 수 있지만 상태 판단에는 영향이 없습니다.
+#[inline(always)]
+fn track_flow(key: &FlowKey, protocol: u8, tcp_flags: u8) -> u8 {
+    // SAFETY: bpf_ktime_get_ns는 인자가 없고 항상 성공하는 헬퍼입니다.
+    let now = unsafe { bpf_ktime_get_ns() };
+
+    match CONNTRACK.get_ptr_mut(key) {
+        // SAFETY: get_ptr_mut는 null 체크 후 Option으로 반환하며,
+        // FlowState는 8바이트 정렬된 #[repr(C)] 구조체입니다.
+        Some(flow) => unsafe {
+            if now.saturating_sub((*flow).last_seen_ns) > CONNTRACK_IDLE_TIMEOUT_NS {
+                (*flow).state = CT_STATE_NEW;
+                (*flow).first_seen_ns = now;
+                (*flow).packets = 0;
+            } else {
+                (*flow).state = next_flow_state((*flow).state, protocol, tcp_flags);
             }
+            (*flow).last_seen_ns = now;
+            (*flow).packets += 1;
+            (*flow).state
+        },
+        None => {
+            let flow = FlowState {
+                state: CT_STATE_NEW,
+                _pad: [0; 7],
+                first_seen_ns: now,
+                last_seen_ns: now,
+                packets: 1,
+            };
+            // LRU 맵이므로 가득 차도 가장 오래된 흐름을 축출하고 삽입됩니다.
+            let _ = CONNTRACK.insert(key, &flow, 0);
+            CT_STATE_NEW
         }
     }
 }
 
-/// RingBuf를 통해 패킷 이벤트를 유저스페이스로 전송합니다.
+/// 목적지 포트별 통계 카운터를 업데이트합니다.
+///
+/// 현재 CPU의 값만 갱신하므로 락이 필요 없습니다. 처음 보는 포트는 새 엔트리로
+/// 삽입하며, 맵이 가득 차면 LRU 정책으로 가장 오래된 포트가 축출됩니다.
+#[inline(always)]
+fn update_port_stats(key: PortStatsKey, delta: &ProtoStats) {
+    match PORT_STATS.get_ptr_mut(&key) {
+        // SAFETY: get_ptr_mut는 null 체크 후 현재 CPU 엔트리의 포인터를 반환합니다.
+        Some(stats) => unsafe { add_stats(stats, delta) },
+        None => {
+            let _ = PORT_STATS.insert(&key, delta, 0);
+        }
+    }
+}
+
+/// 관찰 전용(드라이런) 모드인지 확인합니다 (설정이 없으면 판정대로 드롭).
+#[inline(always)]
+fn observe_only() -> bool {
+    matches!(DRY_RUN_CONFIG.get(0), Some(flag) if *flag != 0)
+}
+
+/// 모니터링 이벤트 샘플링 비율 N을 읽습니다 (설정이 없거나 0이면 1).
+#[inline(always)]
+fn monitor_sample_rate() -> u32 {
+    match SAMPLING_CONFIG.get(0) {
+        Some(params) if params.monitor_rate > 1 => params.monitor_rate,
+        _ => 1,
+    }
+}
+
+/// 1/N 확률로 이벤트를 전송할지 결정합니다 (N이 1 이하이면 항상 전송).
+#[inline(always)]
+fn sampled(sample_rate: u32) -> bool {
+    if sample_rate <= 1 {
+        return true;
+    }
+    // SAFETY: bpf_get_prandom_u32는 인자가 없고 항상 성공하는 헬퍼입니다.
+    let random = unsafe { bpf_get_prandom_u32() };
+    random % sample_rate == 0
+}
+
+/// RingBuf를 통해 패킷 이벤트를 헤더가 붙은 레코드로 유저스페이스에 전송합니다.
 ///
-/// 버퍼가 가득 찬 경우 이벤트는 드롭됩니다 (성능 우선).
+/// 버퍼가 가득 찬 경우 이벤트는 드롭되고 `RINGBUF_DROPS`에 기록됩니다 (성능 우선).
 #[inline(always)]
 fn emit_event(event: &PacketEventData) {
-    // SAFETY: RingBuf에 PacketEventData 크기만큼 예약 후 데이터를 기록합니다.
-    // reserve 실패(버퍼 부족) 시 조용히 무시합니다.
-    if let Some(mut entry) = EVENTS.reserve::<PacketEventData>(0) {
-        entry.write(*event);
-        entry.submit(0);
+    // SAFETY: RingBuf에 EventRecord<PacketEventData> 크기만큼 예약 후 데이터를 기록합니다.
+    match EVENTS.reserve::<EventRecord<PacketEventData>>(0) {
+        Some(mut entry) => {
+            entry.write(EventRecord::new(EVENT_TYPE_PACKET, *event));
+            entry.submit(0);
+        }
+        None => count_ringbuf_drop(RINGBUF_DROPS_IDX_EVENTS),
+    }
+}
+
+/// RingBuf `reserve` 실패를 CPU별 카운터에 기록합니다.
+#[inline(always)]
+fn count_ringbuf_drop(idx: u32) {
+    if let Some(drops) = RINGBUF_DROPS.get_ptr_mut(idx) {
+        // SAFETY: PerCpuArray의 유효한 인덱스 포인터이며, CPU별 값이라 경합이 없습니다.
+        unsafe { *drops += 1 };
     }
 }
 
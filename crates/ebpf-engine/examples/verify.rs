@@ -0,0 +1,50 @@
+//! eBPF 오브젝트를 커널 검증기에 통과시키고 결과를 JSON으로 출력합니다.
+//!
+//! `cargo xtask verify-ebpf`가 빌드해서 실행하는 헬퍼입니다 (root 필요).
+//!
+//! ```text
+//! verify <eBPF 오브젝트 경로>
+//! ```
+//!
+//! 성공하면 [`ProgramVerification`] 배열을 stdout에 JSON으로 출력하고, 검증기가
+//! 거부하면 검증기 로그를 포함한 에러를 stderr에 출력하고 종료 코드 1로 끝납니다.
+
+use std::process::ExitCode;
+
+use ironpost_ebpf_engine::{ProgramVerification, verify_object};
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: verify <ebpf-object>");
+        return ExitCode::from(2);
+    };
+
+    let data = match std::fs::read(&path) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("failed to read {}: {}", path, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match verify_object(&data) {
+        Ok(programs) => print_json(&programs),
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn print_json(programs: &[ProgramVerification]) -> ExitCode {
+    match serde_json::to_string(programs) {
+        Ok(json) => {
+            println!("{}", json);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("failed to serialize result: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
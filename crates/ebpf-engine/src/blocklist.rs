@@ -0,0 +1,1737 @@
+//! 동적 차단 목록 — 런타임 IP/CIDR 차단 관리
+//!
+//! [`Blocklist`]는 설정 파일 룰([`FilterRule`])과 런타임에 추가된 엔트리
+//! ([`BlocklistEntry`])를 합쳐 eBPF `BLOCKLIST`/`BLOCKLIST_CIDR`/`PORT_RULES` 맵에
+//! 반영합니다.
+//! 엔진 외부(데몬 제어 API 등)에서는 [`BlocklistHandle`]을 통해 엔진을
+//! 재시작하지 않고 즉시 차단/해제할 수 있습니다.
+//!
+//! # CIDR 처리
+//! 단일 호스트(`/32`)는 `BLOCKLIST` HashMap에, 그보다 넓은 블록은
+//! `BLOCKLIST_CIDR` LpmTrie에 엔트리 하나로 반영합니다. XDP 프로그램은
+//! HashMap을 먼저 조회하고, 미스 시 LpmTrie에서 최장 프리픽스 매칭을 합니다.
+//! 프리픽스는 `/`[`MIN_CIDR_PREFIX_LEN`] 이상만 허용합니다.
+//!
+//! 엔트리가 겹치면 `Monitor` < `Block` < `Reflect` 순으로 강한 액션이 우선하도록,
+//! `Block`/`Reflect` 블록에 포함된 더 약한 호스트/블록은 동기화 시 블록의 액션으로
+//! 승격합니다.
+//!
+//! # 허용 목록
+//! `Allow` 룰과 엔트리는 호스트/CIDR 구분 없이 `ALLOWLIST` LpmTrie에 반영합니다
+//! (호스트는 `/32` 프리픽스). XDP 프로그램은 허용 목록을 가장 먼저 조회하므로,
+//! 겹치는 `Block` 엔트리가 있어도 허용된 출발지는 드롭되지 않습니다. 관리용 IP를
+//! 실수로 차단하지 않도록 하는 안전장치입니다.
+//! 포트 단위 허용은 지원하지 않으므로 `dst_port`가 있는 `Allow` 룰은 건너뜁니다.
+//!
+//! # 포트 룰
+//! `dst_port`가 있는 설정 룰은 호스트 전체를 차단하지 않고 `PORT_RULES` 맵에
+//! (출발지 IP, 목적지 포트, 프로토콜) 키로 반영합니다. 출발지나 프로토콜이 없으면
+//! 와일드카드 키가 되며, 출발지 CIDR(`/32` 제외)이나 `dst_ip`가 있는 룰은 커널에서
+//! 표현할 수 없으므로 건너뜁니다.
+//!
+//! # 룰 일괄 가져오기
+//! [`Blocklist::import_rules`]는 룰 수천 개를 검증·중복 제거한 뒤 맵 동기화를 한 번만
+//! 수행합니다. 같은 ID의 기존 룰은 교체됩니다.
+//!
+//! # 위협 인텔리전스 피드
+//! [`Blocklist::sync_feed`]는 피드 하나에서 만든 룰([`crate::feed`])을 통째로 교체합니다.
+//! 피드 룰은 설정 룰과 따로 보관하므로 [`Blocklist::set_rules`]로 설정 룰을 바꿔도
+//! 유지되며, 피드에서 빠진 주소는 다음 동기화에서 제거됩니다.
+//!
+//! # TTL
+//! `expires_at`이 지난 엔트리는 [`Blocklist::expire`] 호출 시 제거됩니다.
+//! 엔진은 실행 중 주기적으로 만료 처리를 수행합니다.
+//!
+//! # 자동 차단
+//! [`Blocklist::auto_block`]은 탐지기가 보고한 출발지를 TTL이 있는 `Block` 엔트리로
+//! 추가합니다. 같은 호스트의 런타임 엔트리가 이미 있거나 허용 룰/엔트리에 포함된
+//! 주소는 건드리지 않으므로, 운영자가 넣은 영구 차단이나 허용이 덮어써지지 않습니다.
+//!
+//! # 재시작 후 복원
+//! 차단 목록 맵은 bpffs에 고정되므로 데몬이 재시작해도 커널 상태가 유지됩니다.
+//! 런타임 엔트리는 맵 값에 출처와 만료 시각을 함께 기록해 두고, 맵을 다시 연결할 때
+//! 만료되지 않은 런타임 엔트리를 메모리 상태로 복원합니다. 설정 룰에서 만들어진
+//! 엔트리는 복원하지 않으므로 설정에서 빠진 룰은 첫 동기화에서 삭제됩니다.
+//!
+//! 맵 고정과 별개로, 엔진은 [`Blocklist::snapshot`]을 상태 파일
+//! ([`BlocklistSnapshot`])에 저장하고 시작 시 [`Blocklist::restore`]로 되살립니다.
+//!
+//! # 맵 사용량
+//! 동기화할 때마다 맵별 엔트리 수를 기록해 두며, [`Blocklist::map_usage`]로 조회합니다.
+//! 엔진은 이를 메트릭과 헬스 체크에 사용합니다 ([`crate::map_usage`]).
+//!
+//! # 히트 카운트
+//! XDP 프로그램이 매칭된 엔트리의 `hits` 카운터를 증가시킵니다.
+//! CIDR 엔트리의 카운터에는 더 구체적인 엔트리(호스트, 더 긴 프리픽스)에
+//! 먼저 매칭된 패킷이 포함되지 않습니다.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use ironpost_core::error::{DetectionError, IronpostError};
+use ironpost_ebpf_common::{
+    BLOCKLIST_CIDR_MAX_ENTRIES, BLOCKLIST_MAX_ENTRIES, MAP_ALLOWLIST, MAP_BLOCKLIST,
+    MAP_BLOCKLIST_CIDR, MAP_PORT_RULES, PORT_RULE_ANY_IP, PORT_RULE_ANY_PROTO,
+    PORT_RULES_MAX_ENTRIES, PROTO_TCP, PROTO_UDP, PortRuleKey,
+};
+#[cfg(any(target_os = "linux", test))]
+use ironpost_ebpf_common::{BLOCKLIST_ORIGIN_RULE, BLOCKLIST_ORIGIN_RUNTIME, BlocklistValue};
+
+use crate::config::{FilterRule, RuleAction, RuleImport, merge_rules};
+use crate::feed::FeedSync;
+use crate::map_usage::MapUsage;
+use crate::state::BlocklistSnapshot;
+
+/// 허용되는 최소 CIDR 프리픽스 길이 (/8)
+pub const MIN_CIDR_PREFIX_LEN: u8 = 8;
+
+/// 커널 맵에 반영 가능한 최대 호스트 엔트리 수 (`BLOCKLIST` 맵 크기와 동일)
+pub const MAX_BLOCKLIST_HOSTS: usize = BLOCKLIST_MAX_ENTRIES as usize;
+
+/// 커널 맵에 반영 가능한 최대 CIDR 엔트리 수 (`BLOCKLIST_CIDR` 맵 크기와 동일)
+pub const MAX_BLOCKLIST_PREFIXES: usize = BLOCKLIST_CIDR_MAX_ENTRIES as usize;
+
+/// 커널 맵에 반영 가능한 최대 허용 엔트리 수 (`ALLOWLIST` 맵 크기와 동일)
+pub const MAX_ALLOWLIST_ENTRIES: usize = ironpost_ebpf_common::ALLOWLIST_MAX_ENTRIES as usize;
+
+/// 엔진 외부에서 차단 목록을 조작하기 위한 공유 핸들
+pub type BlocklistHandle = Arc<tokio::sync::Mutex<Blocklist>>;
+
+/// 커널 BLOCKLIST 맵 (소유권 보유)
+#[cfg(target_os = "linux")]
+type BlocklistMap =
+    aya::maps::HashMap<aya::maps::MapData, u32, ironpost_ebpf_common::BlocklistValue>;
+
+/// 커널 BLOCKLIST_CIDR / ALLOWLIST 맵 (소유권 보유)
+#[cfg(target_os = "linux")]
+type BlocklistCidrMap =
+    aya::maps::LpmTrie<aya::maps::MapData, u32, ironpost_ebpf_common::BlocklistValue>;
+
+/// 커널 PORT_RULES 맵 (소유권 보유)
+#[cfg(target_os = "linux")]
+type PortRuleMap =
+    aya::maps::HashMap<aya::maps::MapData, PortRuleKey, ironpost_ebpf_common::BlocklistValue>;
+
+/// 차단 목록이 갱신하는 커널 맵 묶음
+#[cfg(target_os = "linux")]
+pub(crate) struct BlocklistMaps {
+    /// 호스트 엔트리 (`BLOCKLIST`)
+    pub hosts: BlocklistMap,
+    /// CIDR 엔트리 (`BLOCKLIST_CIDR`)
+    pub prefixes: BlocklistCidrMap,
+    /// 포트 룰 (`PORT_RULES`)
+    pub ports: PortRuleMap,
+    /// 허용 엔트리 (`ALLOWLIST`, 호스트는 `/32`)
+    pub allowed: BlocklistCidrMap,
+}
+
+/// IPv4 CIDR 블록
+///
+/// 호스트 비트는 생성 시 0으로 정규화됩니다 (`10.0.0.7/24` → `10.0.0.0/24`).
+/// 문자열 형식은 `/32`이면 주소만(`10.0.0.1`), 그 외에는 `주소/프리픽스`입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ipv4Cidr {
+    addr: Ipv4Addr,
+    prefix_len: u8,
+}
+
+impl Ipv4Cidr {
+    /// CIDR 블록을 생성합니다.
+    ///
+    /// # Errors
+    ///
+    /// 프리픽스가 32를 넘거나 [`MIN_CIDR_PREFIX_LEN`]보다 짧으면 에러를 반환합니다.
+    pub fn new(addr: Ipv4Addr, prefix_len: u8) -> Result<Self, IronpostError> {
+        if prefix_len > 32 {
+            return Err(DetectionError::Rule(format!(
+                "invalid prefix length /{} (max: /32)",
+                prefix_len
+            ))
+            .into());
+        }
+        if prefix_len < MIN_CIDR_PREFIX_LEN {
+            return Err(DetectionError::Rule(format!(
+                "prefix /{} is too wide for the blocklist (min: /{})",
+                prefix_len, MIN_CIDR_PREFIX_LEN
+            ))
+            .into());
+        }
+        let network = u32::from(addr) & Self::mask(prefix_len);
+        Ok(Self {
+            addr: Ipv4Addr::from(network),
+            prefix_len,
+        })
+    }
+
+    /// 단일 호스트(/32) 블록을 생성합니다.
+    pub fn host(addr: Ipv4Addr) -> Self {
+        Self {
+            addr,
+            prefix_len: 32,
+        }
+    }
+
+    /// 네트워크 주소
+    pub fn addr(&self) -> Ipv4Addr {
+        self.addr
+    }
+
+    /// 프리픽스 길이
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// 블록에 속한 호스트 수
+    pub fn host_count(&self) -> u32 {
+        1u32 << (32 - u32::from(self.prefix_len))
+    }
+
+    /// 블록에 속한 모든 호스트 주소
+    pub fn hosts(&self) -> impl Iterator<Item = Ipv4Addr> {
+        let start = u32::from(self.addr);
+        (0..self.host_count()).map(move |offset| Ipv4Addr::from(start | offset))
+    }
+
+    /// 단일 호스트(/32) 블록인지 확인합니다.
+    pub fn is_host(&self) -> bool {
+        self.prefix_len == 32
+    }
+
+    /// 주소가 블록에 속하는지 확인합니다.
+    pub fn contains(&self, ip: Ipv4Addr) -> bool {
+        u32::from(ip) & Self::mask(self.prefix_len) == u32::from(self.addr)
+    }
+
+    /// 이 블록을 포함하는 `prefix_len` 길이의 상위 블록
+    fn supernet(&self, prefix_len: u8) -> Self {
+        Self {
+            addr: Ipv4Addr::from(u32::from(self.addr) & Self::mask(prefix_len)),
+            prefix_len,
+        }
+    }
+
+    fn mask(prefix_len: u8) -> u32 {
+        u32::MAX
+            .checked_shl(32 - u32::from(prefix_len))
+            .unwrap_or(0)
+    }
+}
+
+impl FromStr for Ipv4Cidr {
+    type Err = IronpostError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix)) => {
+                let prefix_len = prefix.parse::<u8>().map_err(|_| {
+                    DetectionError::Rule(format!("invalid prefix length in '{}'", s))
+                })?;
+                (addr, prefix_len)
+            }
+            None => (s, 32),
+        };
+        let addr = match addr.parse::<IpAddr>() {
+            Ok(IpAddr::V4(v4)) => v4,
+            Ok(IpAddr::V6(_)) => {
+                return Err(DetectionError::Rule(format!(
+                    "IPv6 address '{}' is not supported in the blocklist",
+                    s
+                ))
+                .into());
+            }
+            Err(_) => {
+                return Err(
+                    DetectionError::Rule(format!("invalid IP address or CIDR '{}'", s)).into(),
+                );
+            }
+        };
+        Self::new(addr, prefix_len)
+    }
+}
+
+impl fmt::Display for Ipv4Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.prefix_len == 32 {
+            write!(f, "{}", self.addr)
+        } else {
+            write!(f, "{}/{}", self.addr, self.prefix_len)
+        }
+    }
+}
+
+impl Serialize for Ipv4Cidr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ipv4Cidr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// 런타임에 추가된 차단 목록 엔트리
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlocklistEntry {
+    /// 대상 주소 또는 CIDR
+    pub cidr: Ipv4Cidr,
+    /// 적용할 액션
+    pub action: RuleAction,
+    /// 설명 (차단 사유 등)
+    #[serde(default)]
+    pub description: String,
+    /// 추가 시각 (Unix epoch 초)
+    #[serde(default)]
+    pub added_at: u64,
+    /// 만료 시각 (Unix epoch 초, `None`이면 만료 없음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+impl BlocklistEntry {
+    /// 현재 시각에 추가되는 만료 없는 엔트리를 생성합니다.
+    pub fn new(cidr: Ipv4Cidr, action: RuleAction) -> Self {
+        Self {
+            cidr,
+            action,
+            description: String::new(),
+            added_at: unix_now(),
+            expires_at: None,
+        }
+    }
+
+    /// 추가 시각으로부터 `ttl` 후 만료되도록 설정합니다.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.expires_at = Some(self.added_at.saturating_add(ttl.as_secs()));
+        self
+    }
+
+    /// 설명을 설정합니다.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// `now`(Unix epoch 초) 기준으로 만료되었는지 확인합니다.
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// 히트 카운트가 포함된 차단 목록 조회 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlocklistStatus {
+    /// 엔트리
+    #[serde(flatten)]
+    pub entry: BlocklistEntry,
+    /// 엔트리에 매칭된 패킷 수
+    pub hits: u64,
+}
+
+/// 설정 룰 + 런타임 엔트리를 합친 차단 목록
+///
+/// 엔진이 실행 중이면 커널 차단 목록 맵들이 연결되어 있으며,
+/// 변경 사항은 즉시 맵에 동기화됩니다. 맵이 연결되지 않은 상태에서는
+/// 메모리 상태만 갱신되고, 엔진 시작 시 한 번에 반영됩니다.
+pub struct Blocklist {
+    /// 설정 파일 룰 (src_ip/src_cidr 또는 dst_port가 있는 룰만 반영)
+    rules: Vec<FilterRule>,
+    /// 런타임 엔트리 (CIDR 기준 정렬)
+    entries: BTreeMap<Ipv4Cidr, BlocklistEntry>,
+    /// 위협 인텔리전스 피드별 룰 (피드 이름 기준 정렬)
+    feeds: BTreeMap<String, Vec<FilterRule>>,
+    /// 룰/엔트리가 바뀔 때마다 증가하는 세대 번호 (상태 파일 저장 여부 판단)
+    generation: u64,
+    /// 마지막 동기화 기준 맵별 엔트리 수
+    usage: [MapUsage; 4],
+    /// 커널 맵 (엔진 실행 중에만 존재)
+    #[cfg(target_os = "linux")]
+    maps: Option<BlocklistMaps>,
+}
+
+impl Blocklist {
+    /// 빈 차단 목록을 생성합니다.
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            entries: BTreeMap::new(),
+            feeds: BTreeMap::new(),
+            generation: 0,
+            usage: DesiredState::default().usage(),
+            #[cfg(target_os = "linux")]
+            maps: None,
+        }
+    }
+
+    /// 설정 파일 룰을 교체하고 맵에 동기화합니다.
+    pub fn set_rules(&mut self, rules: &[FilterRule]) -> Result<(), IronpostError> {
+        self.rules = rules.to_vec();
+        self.commit()
+    }
+
+    /// 룰들을 한 번에 병합하고 맵 동기화를 한 번만 수행합니다.
+    ///
+    /// 검증과 중복 제거는 [`merge_rules`]를 따르며, 하나라도 잘못된 룰이 있으면
+    /// 아무것도 반영하지 않습니다. 수천 개의 룰을 넣을 때 룰마다 맵을 다시
+    /// 동기화하는 [`set_rules`](Self::set_rules) 반복 호출을 대신합니다.
+    pub fn import_rules(&mut self, rules: Vec<FilterRule>) -> Result<RuleImport, IronpostError> {
+        let (merged, summary) = merge_rules(&self.rules, rules)?;
+        self.rules = merged;
+        self.commit()?;
+        Ok(summary)
+    }
+
+    /// 반영 중인 룰 목록
+    pub fn rules(&self) -> &[FilterRule] {
+        &self.rules
+    }
+
+    /// 반영 중인 설정 파일 룰 수
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// 위협 인텔리전스 피드별 룰 (피드 이름순)
+    pub fn feeds(&self) -> &BTreeMap<String, Vec<FilterRule>> {
+        &self.feeds
+    }
+
+    /// 피드 하나의 룰을 교체하고, 바뀐 것이 있으면 맵 동기화를 한 번만 수행합니다.
+    ///
+    /// 이전 동기화에 있었지만 `rules`에 없는 룰은 제거됩니다. 룰 검증과 중복 제거는
+    /// [`merge_rules`]를 따르며, 결과가 맵 크기를 넘거나 잘못된 룰이 있으면 이전 룰을
+    /// 그대로 둡니다.
+    pub fn sync_feed(
+        &mut self,
+        feed: &str,
+        rules: Vec<FilterRule>,
+    ) -> Result<FeedSync, IronpostError> {
+        let (next, _) = merge_rules(&[], rules)?;
+        let previous: HashSet<&str> = self
+            .feeds
+            .get(feed)
+            .map(|rules| rules.iter().map(|rule| rule.id.as_str()).collect())
+            .unwrap_or_default();
+        let unchanged = next
+            .iter()
+            .filter(|rule| previous.contains(rule.id.as_str()))
+            .count();
+        let summary = FeedSync {
+            added: next.len() - unchanged,
+            removed: previous.len() - unchanged,
+            unchanged,
+        };
+        if summary.added == 0 && summary.removed == 0 {
+            return Ok(summary);
+        }
+
+        let mut feeds = self.feeds.clone();
+        if next.is_empty() {
+            feeds.remove(feed);
+        } else {
+            feeds.insert(feed.to_owned(), next);
+        }
+        let rules = self.rules.iter().chain(feeds.values().flatten());
+        check_capacity(&desired_state(rules, &self.entries))?;
+
+        self.feeds = feeds;
+        self.commit()?;
+        Ok(summary)
+    }
+
+    /// 피드 룰을 복원합니다 (상태 파일, 엔진 시작 시).
+    ///
+    /// 이미 동기화한 피드는 건너뛰므로 새로 내려받은 룰이 우선합니다.
+    pub fn restore_feeds(
+        &mut self,
+        feeds: BTreeMap<String, Vec<FilterRule>>,
+    ) -> Result<usize, IronpostError> {
+        let mut restored = 0usize;
+        for (feed, rules) in feeds {
+            if self.feeds.contains_key(&feed) {
+                continue;
+            }
+            restored += self.sync_feed(&feed, rules)?.added;
+        }
+        Ok(restored)
+    }
+
+    /// 런타임 엔트리 수
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 런타임 엔트리가 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 엔트리들을 추가합니다. 같은 CIDR의 기존 엔트리는 교체됩니다.
+    ///
+    /// 모든 엔트리를 반영한 결과가 [`MAX_BLOCKLIST_HOSTS`], [`MAX_BLOCKLIST_PREFIXES`],
+    /// [`MAX_ALLOWLIST_ENTRIES`] 중 하나라도 넘으면 아무것도 추가하지 않고 에러를 반환합니다.
+    pub fn insert_all(
+        &mut self,
+        entries: impl IntoIterator<Item = BlocklistEntry>,
+    ) -> Result<usize, IronpostError> {
+        let mut next = self.entries.clone();
+        let mut added = 0usize;
+        for entry in entries {
+            next.insert(entry.cidr, entry);
+            added += 1;
+        }
+
+        check_capacity(&desired_state(self.all_rules(), &next))?;
+
+        self.entries = next;
+        self.commit()?;
+        Ok(added)
+    }
+
+    /// 엔트리 하나를 추가합니다.
+    pub fn insert(&mut self, entry: BlocklistEntry) -> Result<(), IronpostError> {
+        self.insert_all([entry]).map(|_| ())
+    }
+
+    /// 탐지된 출발지를 `ttl` 동안 차단하는 엔트리를 추가합니다.
+    ///
+    /// 같은 호스트의 런타임 엔트리가 이미 있거나 허용된 주소이면 기존 상태를 유지하고
+    /// `false`를 반환합니다.
+    pub fn auto_block(
+        &mut self,
+        ip: Ipv4Addr,
+        ttl: Duration,
+        reason: &str,
+    ) -> Result<bool, IronpostError> {
+        let cidr = Ipv4Cidr::host(ip);
+        if self.entries.contains_key(&cidr) || self.is_allowed(ip) {
+            return Ok(false);
+        }
+        self.insert(
+            BlocklistEntry::new(cidr, RuleAction::Block)
+                .with_ttl(ttl)
+                .with_description(format!("auto-block: {reason}")),
+        )?;
+        Ok(true)
+    }
+
+    /// 허용 룰 또는 허용 엔트리에 포함된 주소인지 확인합니다.
+    fn is_allowed(&self, ip: Ipv4Addr) -> bool {
+        let rule_allows = self
+            .rules
+            .iter()
+            .filter(|rule| rule.action == RuleAction::Allow && rule.dst_port.is_none())
+            .any(|rule| {
+                rule.src_ip == Some(IpAddr::V4(ip))
+                    || rule.src_cidr.is_some_and(|cidr| cidr.contains(ip))
+            });
+        rule_allows
+            || self
+                .entries
+                .values()
+                .any(|entry| entry.action == RuleAction::Allow && entry.cidr.contains(ip))
+    }
+
+    /// CIDR이 일치하는 엔트리를 제거합니다.
+    ///
+    /// 제거된 경우 `true`, 존재하지 않으면 `false`를 반환합니다.
+    pub fn remove(&mut self, cidr: &Ipv4Cidr) -> Result<bool, IronpostError> {
+        if self.entries.remove(cidr).is_none() {
+            return Ok(false);
+        }
+        self.commit()?;
+        Ok(true)
+    }
+
+    /// 만료된 엔트리를 제거하고 제거된 수를 반환합니다.
+    pub fn expire(&mut self, now: u64) -> Result<usize, IronpostError> {
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| !entry.is_expired(now));
+        let expired = before - self.entries.len();
+        if expired > 0 {
+            tracing::info!(expired = expired, "expired blocklist entries removed");
+            self.commit()?;
+        }
+        Ok(expired)
+    }
+
+    /// 런타임 엔트리를 히트 카운트와 함께 반환합니다 (CIDR 순).
+    pub fn list(&self) -> Vec<BlocklistStatus> {
+        self.entries
+            .values()
+            .map(|entry| BlocklistStatus {
+                entry: entry.clone(),
+                hits: self.entry_hits(entry),
+            })
+            .collect()
+    }
+
+    /// 상태 파일에서 읽은 런타임 엔트리를 복원하고 복원된 수를 반환합니다.
+    ///
+    /// `now`(Unix epoch 초) 기준으로 만료된 엔트리와 이미 있는 CIDR은 건너뜁니다.
+    pub fn restore(
+        &mut self,
+        entries: Vec<BlocklistEntry>,
+        now: u64,
+    ) -> Result<usize, IronpostError> {
+        let restored: Vec<BlocklistEntry> = entries
+            .into_iter()
+            .filter(|entry| !entry.is_expired(now) && !self.entries.contains_key(&entry.cidr))
+            .collect();
+        if restored.is_empty() {
+            return Ok(0);
+        }
+        self.insert_all(restored)
+    }
+
+    /// 설정 룰과 런타임 엔트리의 스냅샷을 반환합니다 (상태 파일 저장용).
+    pub fn snapshot(&self) -> BlocklistSnapshot {
+        BlocklistSnapshot {
+            rules: self.rules.clone(),
+            entries: self.entries.values().cloned().collect(),
+            feeds: self.feeds.clone(),
+        }
+    }
+
+    /// 차단/허용 목록 맵별 엔트리 수와 최대 크기
+    ///
+    /// 커널 맵 연결 여부와 관계없이 마지막으로 계산한 목표 상태 기준입니다.
+    pub fn map_usage(&self) -> [MapUsage; 4] {
+        self.usage
+    }
+
+    /// 현재 세대 번호 (룰/엔트리가 바뀔 때마다 증가)
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// 커널 맵을 연결하고, 고정된 맵에 남은 런타임 엔트리를 복원한 뒤 동기화합니다.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn attach_maps(&mut self, maps: BlocklistMaps) -> Result<(), IronpostError> {
+        let restored = restore_runtime_entries(&maps, unix_now());
+        let mut count = 0usize;
+        for entry in restored {
+            if let std::collections::btree_map::Entry::Vacant(slot) = self.entries.entry(entry.cidr)
+            {
+                slot.insert(entry);
+                count += 1;
+            }
+        }
+        if count > 0 {
+            tracing::info!(
+                restored = count,
+                "restored runtime blocklist entries from pinned maps"
+            );
+        }
+        self.maps = Some(maps);
+        self.commit()
+    }
+
+    /// 커널 맵 연결을 해제합니다 (엔진 정지 시).
+    #[cfg(target_os = "linux")]
+    pub(crate) fn detach_maps(&mut self) {
+        self.maps = None;
+    }
+
+    /// 설정 룰과 피드 룰을 함께 순회합니다.
+    fn all_rules(&self) -> impl Iterator<Item = &FilterRule> + Clone {
+        self.rules.iter().chain(self.feeds.values().flatten())
+    }
+
+    /// 변경을 기록하고 커널 맵에 동기화합니다.
+    fn commit(&mut self) -> Result<(), IronpostError> {
+        self.generation = self.generation.wrapping_add(1);
+        let desired = desired_state(self.all_rules(), &self.entries);
+        self.usage = desired.usage();
+        self.sync(&desired)
+    }
+
+    /// 현재 상태를 커널 맵에 동기화합니다.
+    ///
+    /// 원하는 상태에 없는 키는 삭제하고, 액션이 바뀐 키만 다시 기록합니다.
+    /// 기존 키를 다시 기록할 때는 히트 카운트를 보존합니다.
+    #[cfg(target_os = "linux")]
+    fn sync(&mut self, desired: &DesiredState) -> Result<(), IronpostError> {
+        let Some(maps) = self.maps.as_mut() else {
+            return Ok(());
+        };
+        let entries = &self.entries;
+        sync_hash_map(
+            &mut maps.hosts,
+            &desired.hosts,
+            |key| Ipv4Addr::from(*key).to_string(),
+            |key| ValueMeta::of(entries.get(&Ipv4Cidr::host(Ipv4Addr::from(*key)))),
+        )?;
+        sync_prefixes(&mut maps.prefixes, &desired.prefixes, |cidr| {
+            ValueMeta::of(entries.get(cidr))
+        })?;
+        sync_hash_map(&mut maps.ports, &desired.ports, describe_port_rule, |_| {
+            ValueMeta::RULE
+        })?;
+        sync_prefixes(&mut maps.allowed, &desired.allowed, |cidr| {
+            ValueMeta::of(entries.get(cidr))
+        })
+    }
+
+    /// 현재 상태를 커널 맵에 동기화합니다 (비-Linux: no-op).
+    #[cfg(not(target_os = "linux"))]
+    fn sync(&mut self, _desired: &DesiredState) -> Result<(), IronpostError> {
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    fn entry_hits(&self, entry: &BlocklistEntry) -> u64 {
+        let Some(maps) = self.maps.as_ref() else {
+            return 0;
+        };
+        let cidr = &entry.cidr;
+        let value = if entry.action == RuleAction::Allow {
+            maps.allowed.get(&trie_key(cidr), 0).ok()
+        } else if cidr.is_host() {
+            maps.hosts.get(&map_key(cidr.addr()), 0).ok()
+        } else {
+            maps.prefixes.get(&trie_key(cidr), 0).ok()
+        };
+        value.map(|value| value.hits).unwrap_or(0)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn entry_hits(&self, _entry: &BlocklistEntry) -> u64 {
+        0
+    }
+}
+
+impl Default for Blocklist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 해시 맵(BLOCKLIST, PORT_RULES)을 키별 목표 상태에 맞춥니다.
+#[cfg(target_os = "linux")]
+fn sync_hash_map<K: aya::Pod + Ord>(
+    map: &mut aya::maps::HashMap<aya::maps::MapData, K, ironpost_ebpf_common::BlocklistValue>,
+    desired: &BTreeMap<K, RuleAction>,
+    describe: impl Fn(&K) -> String,
+    meta: impl Fn(&K) -> ValueMeta,
+) -> Result<(), IronpostError> {
+    let existing: Vec<K> = map.keys().filter_map(|k| k.ok()).collect();
+    for key in existing {
+        if !desired.contains_key(&key) {
+            if let Err(e) = map.remove(&key) {
+                tracing::warn!(key = %describe(&key), error = %e, "failed to remove stale blocklist entry");
+            } else {
+                tracing::debug!(key = %describe(&key), "removed stale blocklist entry");
+            }
+        }
+    }
+
+    for (key, &action) in desired {
+        let code = action_code(action);
+        let meta = meta(key);
+        let hits = match map.get(key, 0) {
+            Ok(current) if current.action == code && meta.matches(&current) => continue,
+            Ok(current) => current.hits,
+            Err(_) => 0,
+        };
+        map.insert(key, blocklist_value(code, hits, meta), 0)
+            .map_err(|e| {
+                DetectionError::EbpfMap(format!(
+                    "failed to insert {} into blocklist: {}",
+                    describe(key),
+                    e
+                ))
+            })?;
+    }
+    Ok(())
+}
+
+/// LPM 트라이 맵(BLOCKLIST_CIDR, ALLOWLIST)을 프리픽스별 목표 상태에 맞춥니다.
+#[cfg(target_os = "linux")]
+fn sync_prefixes(
+    map: &mut BlocklistCidrMap,
+    desired: &BTreeMap<Ipv4Cidr, RuleAction>,
+    meta: impl Fn(&Ipv4Cidr) -> ValueMeta,
+) -> Result<(), IronpostError> {
+    let existing: Vec<aya::maps::lpm_trie::Key<u32>> = map.keys().filter_map(|k| k.ok()).collect();
+    let mut present = HashSet::new();
+    for key in existing {
+        match trie_cidr(&key) {
+            Some(cidr) if desired.contains_key(&cidr) => {
+                present.insert(cidr);
+            }
+            _ => {
+                let prefix = format!(
+                    "{}/{}",
+                    Ipv4Addr::from(key.data().to_ne_bytes()),
+                    key.prefix_len()
+                );
+                if let Err(e) = map.remove(&key) {
+                    tracing::warn!(cidr = %prefix, error = %e, "failed to remove stale blocklist CIDR entry");
+                } else {
+                    tracing::debug!(cidr = %prefix, "removed stale blocklist CIDR entry");
+                }
+            }
+        }
+    }
+
+    for (cidr, &action) in desired {
+        let key = trie_key(cidr);
+        let code = action_code(action);
+        let meta = meta(cidr);
+        // get()은 최장 프리픽스 매칭이므로 맵에 같은 프리픽스가 있을 때만 기존 값으로 취급합니다.
+        let hits = match map.get(&key, 0) {
+            Ok(current)
+                if present.contains(cidr) && current.action == code && meta.matches(&current) =>
+            {
+                continue;
+            }
+            Ok(current) if present.contains(cidr) => current.hits,
+            _ => 0,
+        };
+        map.insert(&key, blocklist_value(code, hits, meta), 0)
+            .map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to insert {} into blocklist: {}", cidr, e))
+            })?;
+    }
+    Ok(())
+}
+
+/// 커널 맵 키 (IPv4 주소, 네트워크 바이트 오더)
+fn map_key(ip: Ipv4Addr) -> u32 {
+    u32::from_be_bytes(ip.octets())
+}
+
+/// 포트 룰 키를 로그용 문자열로 표현합니다 (`10.0.0.1:23/tcp`, `*:53/any`).
+#[cfg(target_os = "linux")]
+fn describe_port_rule(key: &PortRuleKey) -> String {
+    let src = if key.src_ip == PORT_RULE_ANY_IP {
+        "*".to_owned()
+    } else {
+        Ipv4Addr::from(key.src_ip).to_string()
+    };
+    let proto = match key.protocol {
+        PROTO_TCP => "tcp",
+        PROTO_UDP => "udp",
+        _ => "any",
+    };
+    format!("{}:{}/{}", src, key.dst_port, proto)
+}
+
+/// 설정 룰을 커널 포트 룰 키로 변환합니다.
+///
+/// 커널이 표현할 수 없는 룰(IPv6, `/32`가 아닌 출발지 CIDR, `dst_ip` 지정,
+/// TCP/UDP 외 프로토콜)은 경고 후 `None`을 반환합니다.
+fn port_rule_key(rule: &FilterRule, dst_port: u16) -> Option<PortRuleKey> {
+    let skip = |reason: &str| {
+        tracing::warn!(
+            rule_id = rule.id.as_str(),
+            reason = reason,
+            "port rule cannot be enforced in kernel, skipping"
+        );
+        None
+    };
+
+    if rule.dst_ip.is_some() {
+        return skip("dst_ip is not supported");
+    }
+    let protocol = match rule.protocol {
+        None => PORT_RULE_ANY_PROTO,
+        Some(p @ (PROTO_TCP | PROTO_UDP)) => p,
+        Some(_) => return skip("protocol must be TCP (6) or UDP (17)"),
+    };
+    let src_ip = match (rule.src_ip, rule.src_cidr) {
+        (Some(IpAddr::V4(ip)), _) => map_key(ip),
+        (Some(IpAddr::V6(_)), _) => return skip("IPv6 addresses are not supported"),
+        (None, Some(cidr)) if cidr.is_host() => map_key(cidr.addr()),
+        (None, Some(_)) => return skip("src_cidr wider than /32 is not supported"),
+        (None, None) => PORT_RULE_ANY_IP,
+    };
+    Some(PortRuleKey::new(src_ip, dst_port, protocol))
+}
+
+/// LPM 트라이 키 (프리픽스 길이 + 네트워크 바이트 오더 주소)
+#[cfg(target_os = "linux")]
+fn trie_key(cidr: &Ipv4Cidr) -> aya::maps::lpm_trie::Key<u32> {
+    aya::maps::lpm_trie::Key::new(u32::from(cidr.prefix_len()), trie_data(cidr.addr()))
+}
+
+/// LPM 트라이 키를 CIDR로 되돌립니다 (허용 범위를 벗어난 키는 `None`).
+#[cfg(target_os = "linux")]
+fn trie_cidr(key: &aya::maps::lpm_trie::Key<u32>) -> Option<Ipv4Cidr> {
+    let prefix_len = u8::try_from(key.prefix_len()).ok()?;
+    Ipv4Cidr::new(Ipv4Addr::from(key.data().to_ne_bytes()), prefix_len).ok()
+}
+
+/// LPM 트라이 키 데이터
+///
+/// 커널은 키 데이터를 바이트 단위로 비교하므로, 해시맵 키와 달리 주소의
+/// 바이트 순서(네트워크 오더)를 그대로 메모리에 담아야 합니다.
+#[cfg(any(target_os = "linux", test))]
+fn trie_data(ip: Ipv4Addr) -> u32 {
+    u32::from_ne_bytes(ip.octets())
+}
+
+#[cfg(target_os = "linux")]
+fn action_code(action: RuleAction) -> u8 {
+    use ironpost_ebpf_common::{ACTION_DROP, ACTION_MONITOR, ACTION_PASS, ACTION_REFLECT};
+
+    match action {
+        RuleAction::Block => ACTION_DROP,
+        RuleAction::Monitor => ACTION_MONITOR,
+        RuleAction::Allow => ACTION_PASS,
+        RuleAction::Reflect => ACTION_REFLECT,
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn blocklist_value(action: u8, hits: u64, meta: ValueMeta) -> BlocklistValue {
+    BlocklistValue {
+        action,
+        origin: meta.origin,
+        _pad: [0; 6],
+        hits,
+        expires_at: meta.expires_at,
+    }
+}
+
+/// 맵 값에 함께 기록하는 엔트리 메타데이터 (재시작 후 복원용)
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ValueMeta {
+    origin: u8,
+    expires_at: u64,
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl ValueMeta {
+    /// 설정 룰에서 만들어진 엔트리
+    const RULE: Self = Self {
+        origin: BLOCKLIST_ORIGIN_RULE,
+        expires_at: 0,
+    };
+
+    /// 같은 키의 런타임 엔트리가 있으면 그 만료 시각을, 없으면 설정 룰로 기록합니다.
+    fn of(entry: Option<&BlocklistEntry>) -> Self {
+        match entry {
+            Some(entry) => Self {
+                origin: BLOCKLIST_ORIGIN_RUNTIME,
+                expires_at: entry.expires_at.unwrap_or(0),
+            },
+            None => Self::RULE,
+        }
+    }
+
+    fn matches(&self, value: &BlocklistValue) -> bool {
+        value.origin == self.origin && value.expires_at == self.expires_at
+    }
+}
+
+/// 고정된 맵에 남아 있는 런타임 엔트리를 읽어 옵니다.
+#[cfg(target_os = "linux")]
+fn restore_runtime_entries(maps: &BlocklistMaps, now: u64) -> Vec<BlocklistEntry> {
+    let hosts = maps
+        .hosts
+        .iter()
+        .filter_map(Result::ok)
+        .map(|(key, value)| (Ipv4Cidr::host(Ipv4Addr::from(key)), value));
+    let prefixes = maps
+        .prefixes
+        .iter()
+        .filter_map(Result::ok)
+        .filter_map(|(key, value)| trie_cidr(&key).map(|cidr| (cidr, value)));
+    let allowed = maps
+        .allowed
+        .iter()
+        .filter_map(Result::ok)
+        .filter_map(|(key, value)| trie_cidr(&key).map(|cidr| (cidr, value)));
+    hosts
+        .chain(prefixes)
+        .chain(allowed)
+        .filter_map(|(cidr, value)| restored_entry(cidr, &value, now))
+        .collect()
+}
+
+/// 맵 값에서 런타임 엔트리를 복원합니다.
+///
+/// 설정 룰 엔트리와 이미 만료된 엔트리는 `None`입니다. 추가 시각은 맵에 없으므로
+/// 복원 시각으로 기록합니다.
+#[cfg(any(target_os = "linux", test))]
+fn restored_entry(cidr: Ipv4Cidr, value: &BlocklistValue, now: u64) -> Option<BlocklistEntry> {
+    use ironpost_ebpf_common::{ACTION_DROP, ACTION_MONITOR, ACTION_PASS, ACTION_REFLECT};
+
+    if value.origin != BLOCKLIST_ORIGIN_RUNTIME {
+        return None;
+    }
+    let action = match value.action {
+        ACTION_DROP => RuleAction::Block,
+        ACTION_MONITOR => RuleAction::Monitor,
+        ACTION_PASS => RuleAction::Allow,
+        ACTION_REFLECT => RuleAction::Reflect,
+        _ => return None,
+    };
+    let expires_at = (value.expires_at != 0).then_some(value.expires_at);
+    let entry = BlocklistEntry {
+        cidr,
+        action,
+        description: "restored from pinned map".to_owned(),
+        added_at: now,
+        expires_at,
+    };
+    (!entry.is_expired(now)).then_some(entry)
+}
+
+/// 커널 맵에 반영할 목표 상태
+#[derive(Debug, Default)]
+struct DesiredState {
+    /// `BLOCKLIST` 호스트 엔트리 (맵 키 → 액션)
+    hosts: BTreeMap<u32, RuleAction>,
+    /// `BLOCKLIST_CIDR` 프리픽스 엔트리 (/32 미만)
+    prefixes: BTreeMap<Ipv4Cidr, RuleAction>,
+    /// `PORT_RULES` 엔트리 (`dst_port`가 있는 설정 룰)
+    ports: BTreeMap<PortRuleKey, RuleAction>,
+    /// `ALLOWLIST` 엔트리 (호스트 포함, 액션은 항상 `Allow`)
+    allowed: BTreeMap<Ipv4Cidr, RuleAction>,
+}
+
+impl DesiredState {
+    /// 맵별 엔트리 수와 최대 크기
+    fn usage(&self) -> [MapUsage; 4] {
+        let usage = |map, entries, capacity| MapUsage {
+            map,
+            entries,
+            capacity,
+        };
+        [
+            usage(MAP_BLOCKLIST, self.hosts.len(), MAX_BLOCKLIST_HOSTS),
+            usage(
+                MAP_BLOCKLIST_CIDR,
+                self.prefixes.len(),
+                MAX_BLOCKLIST_PREFIXES,
+            ),
+            usage(
+                MAP_PORT_RULES,
+                self.ports.len(),
+                PORT_RULES_MAX_ENTRIES as usize,
+            ),
+            usage(MAP_ALLOWLIST, self.allowed.len(), MAX_ALLOWLIST_ENTRIES),
+        ]
+    }
+}
+
+/// 커널 맵에 반영할 호스트/프리픽스/포트 룰/허용 엔트리별 액션을 계산합니다.
+///
+/// 설정 룰과 런타임 엔트리가 겹치면 `Monitor` < `Block` < `Reflect` 순으로 강한 액션이
+/// 우선합니다. `Block`/`Reflect` 프리픽스에 포함된 더 약한 호스트/프리픽스도 프리픽스의
+/// 액션으로 승격하여, 커널이 더 구체적인 엔트리를 먼저 매칭하더라도 차단되도록 합니다.
+/// `Allow`는 별도의 허용 목록으로 모으며, 커널이 먼저 조회하므로 겹치는 차단 엔트리보다
+/// 항상 우선합니다. 관찰 전용 모드에서도 액션은 그대로 반영하며, 드롭 여부는 커널이
+/// `DRY_RUN_CONFIG` 플래그로 결정합니다.
+fn desired_state<'a>(
+    rules: impl IntoIterator<Item = &'a FilterRule> + Clone,
+    entries: &BTreeMap<Ipv4Cidr, BlocklistEntry>,
+) -> DesiredState {
+    let mut state = DesiredState::default();
+
+    for rule in rules.clone() {
+        let Some(dst_port) = rule.dst_port else {
+            continue;
+        };
+        if rule.action == RuleAction::Allow {
+            tracing::warn!(
+                rule_id = rule.id.as_str(),
+                "allow rules cannot target a destination port, skipping"
+            );
+            continue;
+        }
+        if let Some(key) = port_rule_key(rule, dst_port) {
+            let slot = state.ports.entry(key).or_insert(rule.action);
+            *slot = stronger(*slot, rule.action);
+        }
+    }
+
+    let mut merge = |cidr: Ipv4Cidr, action: RuleAction| {
+        if action == RuleAction::Allow {
+            state.allowed.insert(cidr, action);
+            return;
+        }
+        let slot = if cidr.is_host() {
+            state.hosts.entry(map_key(cidr.addr())).or_insert(action)
+        } else {
+            state.prefixes.entry(cidr).or_insert(action)
+        };
+        *slot = stronger(*slot, action);
+    };
+
+    for rule in rules.into_iter().filter(|rule| rule.dst_port.is_none()) {
+        match rule.src_ip {
+            Some(IpAddr::V4(ip)) => merge(Ipv4Cidr::host(ip), rule.action),
+            Some(IpAddr::V6(_)) => {
+                tracing::warn!(
+                    rule_id = rule.id.as_str(),
+                    "IPv6 addresses are not supported in blocklist, skipping"
+                );
+            }
+            None => {}
+        }
+        if let Some(cidr) = rule.src_cidr {
+            merge(cidr, rule.action);
+        }
+    }
+    for entry in entries.values() {
+        merge(entry.cidr, entry.action);
+    }
+
+    let blocked: HashMap<Ipv4Cidr, RuleAction> = state
+        .prefixes
+        .iter()
+        .filter(|(_, action)| matches!(action, RuleAction::Block | RuleAction::Reflect))
+        .map(|(cidr, action)| (*cidr, *action))
+        .collect();
+    if !blocked.is_empty() {
+        // 감싸는 차단 프리픽스 중 가장 강한 액션
+        let covering = |cidr: Ipv4Cidr| {
+            (MIN_CIDR_PREFIX_LEN..cidr.prefix_len())
+                .filter_map(|len| blocked.get(&cidr.supernet(len)).copied())
+                .reduce(stronger)
+        };
+        for (key, action) in state.hosts.iter_mut() {
+            if let Some(cover) = covering(Ipv4Cidr::host(Ipv4Addr::from(*key))) {
+                *action = stronger(*action, cover);
+            }
+        }
+        for (cidr, action) in state.prefixes.iter_mut() {
+            if let Some(cover) = covering(*cidr) {
+                *action = stronger(*action, cover);
+            }
+        }
+    }
+    state
+}
+
+/// 겹치는 두 차단 엔트리 중 더 강한 액션 (`Monitor` < `Block` < `Reflect`)
+fn stronger(a: RuleAction, b: RuleAction) -> RuleAction {
+    let rank = |action| match action {
+        RuleAction::Allow | RuleAction::Monitor => 0,
+        RuleAction::Block => 1,
+        RuleAction::Reflect => 2,
+    };
+    if rank(b) > rank(a) { b } else { a }
+}
+
+/// 목표 상태가 커널 맵 크기를 넘지 않는지 확인합니다.
+fn check_capacity(desired: &DesiredState) -> Result<(), IronpostError> {
+    if desired.hosts.len() > MAX_BLOCKLIST_HOSTS {
+        return Err(DetectionError::Rule(format!(
+            "blocklist would contain {} host entries (max: {})",
+            desired.hosts.len(),
+            MAX_BLOCKLIST_HOSTS
+        ))
+        .into());
+    }
+    if desired.prefixes.len() > MAX_BLOCKLIST_PREFIXES {
+        return Err(DetectionError::Rule(format!(
+            "blocklist would contain {} CIDR entries (max: {})",
+            desired.prefixes.len(),
+            MAX_BLOCKLIST_PREFIXES
+        ))
+        .into());
+    }
+    if desired.allowed.len() > MAX_ALLOWLIST_ENTRIES {
+        return Err(DetectionError::Rule(format!(
+            "allowlist would contain {} entries (max: {})",
+            desired.allowed.len(),
+            MAX_ALLOWLIST_ENTRIES
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// 현재 시각 (Unix epoch 초)
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cidr(s: &str) -> Ipv4Cidr {
+        s.parse().unwrap()
+    }
+
+    fn rule(id: &str, ip: &str, action: RuleAction) -> FilterRule {
+        FilterRule {
+            id: id.to_owned(),
+            src_ip: Some(ip.parse().unwrap()),
+            src_cidr: None,
+            dst_ip: None,
+            dst_port: None,
+            protocol: None,
+            action,
+            description: String::new(),
+        }
+    }
+
+    // =============================================================================
+    // Ipv4Cidr 테스트
+    // =============================================================================
+
+    #[test]
+    fn test_cidr_parse_host_and_prefix() {
+        let host = cidr("10.0.0.1");
+        assert_eq!(host.prefix_len(), 32);
+        assert_eq!(host.host_count(), 1);
+        assert_eq!(host.to_string(), "10.0.0.1");
+
+        let wide = cidr("10.20.30.40/8");
+        assert_eq!(wide.to_string(), "10.0.0.0/8");
+        assert!(!wide.is_host());
+
+        let net = cidr("10.0.0.7/24");
+        assert_eq!(net.addr(), Ipv4Addr::new(10, 0, 0, 0));
+        assert_eq!(net.host_count(), 256);
+        assert_eq!(net.to_string(), "10.0.0.0/24");
+        assert!(net.contains(Ipv4Addr::new(10, 0, 0, 200)));
+        assert!(!net.contains(Ipv4Addr::new(10, 0, 1, 1)));
+    }
+
+    #[test]
+    fn test_cidr_rejects_invalid_input() {
+        assert!("not-an-ip".parse::<Ipv4Cidr>().is_err());
+        assert!("10.0.0.1/33".parse::<Ipv4Cidr>().is_err());
+        assert!("10.0.0.0/7".parse::<Ipv4Cidr>().is_err());
+        assert!("::1".parse::<Ipv4Cidr>().is_err());
+        assert!("10.0.0.1/x".parse::<Ipv4Cidr>().is_err());
+    }
+
+    #[test]
+    fn test_cidr_hosts_cover_block() {
+        let hosts: Vec<Ipv4Addr> = cidr("192.168.1.0/30").hosts().collect();
+        assert_eq!(
+            hosts,
+            vec![
+                Ipv4Addr::new(192, 168, 1, 0),
+                Ipv4Addr::new(192, 168, 1, 1),
+                Ipv4Addr::new(192, 168, 1, 2),
+                Ipv4Addr::new(192, 168, 1, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cidr_serde_roundtrip() {
+        let entry = BlocklistEntry::new(cidr("10.1.2.0/24"), RuleAction::Monitor)
+            .with_description("scanner subnet");
+        let json = serde_json::to_string(&entry).unwrap();
+        assert!(json.contains("\"cidr\":\"10.1.2.0/24\""));
+        assert!(json.contains("\"action\":\"monitor\""));
+        assert!(!json.contains("expires_at"));
+
+        let parsed: BlocklistEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, entry);
+    }
+
+    // =============================================================================
+    // Blocklist 테스트
+    // =============================================================================
+
+    #[test]
+    fn test_insert_replaces_same_cidr_and_remove() {
+        let mut blocklist = Blocklist::new();
+        blocklist
+            .insert(BlocklistEntry::new(cidr("10.0.0.1"), RuleAction::Monitor))
+            .unwrap();
+        blocklist
+            .insert(BlocklistEntry::new(cidr("10.0.0.1"), RuleAction::Block))
+            .unwrap();
+
+        let listed = blocklist.list();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].entry.action, RuleAction::Block);
+        assert_eq!(listed[0].hits, 0);
+
+        assert!(blocklist.remove(&cidr("10.0.0.1")).unwrap());
+        assert!(!blocklist.remove(&cidr("10.0.0.1")).unwrap());
+        assert!(blocklist.is_empty());
+    }
+
+    #[test]
+    fn test_auto_block_skips_existing_and_allowed_hosts() {
+        let mut blocklist = Blocklist::new();
+        blocklist
+            .set_rules(&[FilterRule {
+                id: "allow-mgmt".to_owned(),
+                src_ip: None,
+                src_cidr: Some(cidr("10.10.0.0/24")),
+                dst_ip: None,
+                dst_port: None,
+                protocol: None,
+                action: RuleAction::Allow,
+                description: String::new(),
+            }])
+            .unwrap();
+        blocklist
+            .insert(BlocklistEntry::new(cidr("10.0.0.2"), RuleAction::Monitor))
+            .unwrap();
+        let ttl = Duration::from_secs(300);
+
+        assert!(
+            blocklist
+                .auto_block(Ipv4Addr::new(10, 0, 0, 1), ttl, "syn_flood")
+                .unwrap()
+        );
+        assert!(
+            !blocklist
+                .auto_block(Ipv4Addr::new(10, 0, 0, 1), ttl, "port_scan")
+                .unwrap()
+        );
+        // 운영자가 넣은 엔트리와 허용 대역은 그대로 유지
+        assert!(
+            !blocklist
+                .auto_block(Ipv4Addr::new(10, 0, 0, 2), ttl, "syn_flood")
+                .unwrap()
+        );
+        assert!(
+            !blocklist
+                .auto_block(Ipv4Addr::new(10, 10, 0, 5), ttl, "syn_flood")
+                .unwrap()
+        );
+
+        let listed = blocklist.list();
+        assert_eq!(listed.len(), 2);
+        let blocked = &listed[0].entry;
+        assert_eq!(blocked.cidr, cidr("10.0.0.1"));
+        assert_eq!(blocked.action, RuleAction::Block);
+        assert_eq!(blocked.description, "auto-block: syn_flood");
+        assert_eq!(blocked.expires_at, Some(blocked.added_at + 300));
+        assert_eq!(listed[1].entry.action, RuleAction::Monitor);
+    }
+
+    #[test]
+    fn test_restore_skips_expired_and_existing_entries() {
+        let mut blocklist = Blocklist::new();
+        blocklist
+            .insert(BlocklistEntry::new(cidr("10.0.0.1"), RuleAction::Monitor))
+            .unwrap();
+        let generation = blocklist.generation();
+
+        let mut expired = BlocklistEntry::new(cidr("10.0.0.2"), RuleAction::Block);
+        expired.expires_at = Some(100);
+        let restored = blocklist
+            .restore(
+                vec![
+                    BlocklistEntry::new(cidr("10.0.0.1"), RuleAction::Block),
+                    expired,
+                    BlocklistEntry::new(cidr("10.0.0.3"), RuleAction::Block),
+                ],
+                100,
+            )
+            .unwrap();
+
+        assert_eq!(restored, 1);
+        assert!(blocklist.generation() > generation);
+        let snapshot = blocklist.snapshot();
+        let cidrs: Vec<String> = snapshot
+            .entries
+            .iter()
+            .map(|entry| format!("{} {:?}", entry.cidr, entry.action))
+            .collect();
+        assert_eq!(cidrs, vec!["10.0.0.1 Monitor", "10.0.0.3 Block"]);
+    }
+
+    #[test]
+    fn test_insert_all_rejects_capacity_overflow() {
+        let mut blocklist = Blocklist::new();
+        // 호스트 10,001개 > 10,000
+        let entries = (0..=MAX_BLOCKLIST_HOSTS as u32).map(|i| {
+            BlocklistEntry::new(
+                Ipv4Cidr::host(Ipv4Addr::from(0x0a00_0000 | i)),
+                RuleAction::Block,
+            )
+        });
+
+        assert!(blocklist.insert_all(entries).is_err());
+        assert!(blocklist.is_empty(), "nothing should be added on overflow");
+    }
+
+    #[test]
+    fn test_map_usage_follows_committed_state() {
+        let mut blocklist = Blocklist::new();
+        assert!(blocklist.map_usage().iter().all(|usage| usage.entries == 0));
+
+        blocklist
+            .insert_all([
+                BlocklistEntry::new(cidr("10.0.0.1"), RuleAction::Block),
+                BlocklistEntry::new(cidr("10.0.0.2"), RuleAction::Monitor),
+                BlocklistEntry::new(cidr("192.168.0.0/16"), RuleAction::Block),
+                BlocklistEntry::new(cidr("172.16.0.1"), RuleAction::Allow),
+            ])
+            .unwrap();
+
+        let usage = blocklist.map_usage();
+        assert_eq!(
+            usage[0],
+            MapUsage {
+                map: MAP_BLOCKLIST,
+                entries: 2,
+                capacity: MAX_BLOCKLIST_HOSTS,
+            }
+        );
+        assert_eq!((usage[1].map, usage[1].entries), (MAP_BLOCKLIST_CIDR, 1));
+        assert_eq!((usage[2].map, usage[2].entries), (MAP_PORT_RULES, 0));
+        assert_eq!((usage[3].map, usage[3].entries), (MAP_ALLOWLIST, 1));
+    }
+
+    #[test]
+    fn test_sync_feed_replaces_feed_rules_only() {
+        use crate::feed::feed_rules;
+
+        let mut blocklist = Blocklist::new();
+        blocklist
+            .set_rules(&[rule("manual", "10.0.0.1", RuleAction::Block)])
+            .unwrap();
+
+        let first = [cidr("203.0.113.0/24"), cidr("198.51.100.1")];
+        let sync = blocklist
+            .sync_feed("spamhaus", feed_rules("spamhaus", &first))
+            .unwrap();
+        assert_eq!((sync.added, sync.removed, sync.unchanged), (2, 0, 0));
+        assert_eq!(blocklist.map_usage()[0].entries, 2);
+
+        // 같은 내용이면 세대 번호가 바뀌지 않음
+        let generation = blocklist.generation();
+        let sync = blocklist
+            .sync_feed("spamhaus", feed_rules("spamhaus", &first))
+            .unwrap();
+        assert_eq!(sync.unchanged, 2);
+        assert_eq!(blocklist.generation(), generation);
+
+        // 피드에서 빠진 주소는 제거되고 설정 룰은 그대로
+        let second = [cidr("198.51.100.1"), cidr("192.0.2.7")];
+        let sync = blocklist
+            .sync_feed("spamhaus", feed_rules("spamhaus", &second))
+            .unwrap();
+        assert_eq!((sync.added, sync.removed, sync.unchanged), (1, 1, 1));
+        assert_eq!(blocklist.map_usage()[1].entries, 0);
+        blocklist.set_rules(&[]).unwrap();
+        assert_eq!(blocklist.map_usage()[0].entries, 2);
+        assert_eq!(blocklist.snapshot().feeds["spamhaus"].len(), 2);
+
+        // 이미 동기화한 피드는 상태 파일에서 복원하지 않음
+        let mut restored = BTreeMap::new();
+        restored.insert("spamhaus".to_owned(), feed_rules("spamhaus", &first));
+        restored.insert("misp".to_owned(), feed_rules("misp", &first));
+        assert_eq!(blocklist.restore_feeds(restored).unwrap(), 2);
+        assert_eq!(blocklist.feeds()["spamhaus"].len(), 2);
+
+        let sync = blocklist.sync_feed("misp", Vec::new()).unwrap();
+        assert_eq!(sync.removed, 2);
+        assert!(!blocklist.feeds().contains_key("misp"));
+    }
+
+    #[test]
+    fn test_wide_cidr_uses_single_prefix_entry() {
+        let mut blocklist = Blocklist::new();
+        // /8 블록 10개도 프리픽스 엔트리 10개로 반영됨
+        let entries = (10..20u8).map(|i| {
+            BlocklistEntry::new(
+                Ipv4Cidr::new(Ipv4Addr::new(i, 0, 0, 0), 8).unwrap(),
+                RuleAction::Block,
+            )
+        });
+
+        assert_eq!(blocklist.insert_all(entries).unwrap(), 10);
+        assert_eq!(blocklist.len(), 10);
+    }
+
+    #[test]
+    fn test_expire_removes_only_expired_entries() {
+        let mut blocklist = Blocklist::new();
+        let short = BlocklistEntry::new(cidr("10.0.0.1"), RuleAction::Block)
+            .with_ttl(Duration::from_secs(60));
+        let expires_at = short.expires_at.unwrap();
+        blocklist
+            .insert_all([
+                short,
+                BlocklistEntry::new(cidr("10.0.0.2"), RuleAction::Block),
+            ])
+            .unwrap();
+
+        assert_eq!(blocklist.expire(expires_at - 1).unwrap(), 0);
+        assert_eq!(blocklist.expire(expires_at).unwrap(), 1);
+        let remaining: Vec<String> = blocklist
+            .list()
+            .iter()
+            .map(|s| s.entry.cidr.to_string())
+            .collect();
+        assert_eq!(remaining, vec!["10.0.0.2"]);
+    }
+
+    #[test]
+    fn test_desired_state_merges_rules_and_entries() {
+        let rules = vec![
+            rule("r1", "10.0.0.1", RuleAction::Monitor),
+            rule("r2", "10.0.0.9", RuleAction::Block),
+            FilterRule {
+                src_ip: None,
+                src_cidr: Some(cidr("172.16.0.0/12")),
+                ..rule("r3", "0.0.0.0", RuleAction::Monitor)
+            },
+        ];
+        let mut entries = BTreeMap::new();
+        for entry in [
+            BlocklistEntry::new(cidr("10.0.0.0/30"), RuleAction::Block),
+            BlocklistEntry::new(cidr("10.0.0.9"), RuleAction::Monitor),
+        ] {
+            entries.insert(entry.cidr, entry);
+        }
+
+        let state = desired_state(&rules, &entries);
+
+        // 호스트는 HashMap, 그 외 블록은 프리픽스 엔트리 하나로 반영
+        assert_eq!(state.hosts.len(), 2);
+        assert_eq!(state.prefixes.len(), 2);
+        // 같은 호스트가 겹치면 Block이 우선
+        assert_eq!(
+            state.hosts.get(&map_key(Ipv4Addr::new(10, 0, 0, 9))),
+            Some(&RuleAction::Block)
+        );
+        assert_eq!(
+            state.prefixes.get(&cidr("172.16.0.0/12")),
+            Some(&RuleAction::Monitor)
+        );
+    }
+
+    #[test]
+    fn test_desired_state_block_prefix_promotes_covered_entries() {
+        let rules = vec![rule("r1", "10.1.2.3", RuleAction::Monitor)];
+        let mut entries = BTreeMap::new();
+        for entry in [
+            BlocklistEntry::new(cidr("10.0.0.0/8"), RuleAction::Block),
+            BlocklistEntry::new(cidr("10.1.0.0/16"), RuleAction::Monitor),
+            BlocklistEntry::new(cidr("192.168.0.0/16"), RuleAction::Monitor),
+        ] {
+            entries.insert(entry.cidr, entry);
+        }
+
+        let state = desired_state(&rules, &entries);
+
+        // 커널은 더 구체적인 엔트리를 먼저 매칭하므로 Block 블록 안의 Monitor는 승격
+        assert_eq!(
+            state.hosts.get(&map_key(Ipv4Addr::new(10, 1, 2, 3))),
+            Some(&RuleAction::Block)
+        );
+        assert_eq!(
+            state.prefixes.get(&cidr("10.1.0.0/16")),
+            Some(&RuleAction::Block)
+        );
+        assert_eq!(
+            state.prefixes.get(&cidr("192.168.0.0/16")),
+            Some(&RuleAction::Monitor)
+        );
+    }
+
+    #[test]
+    fn test_desired_state_reflect_outranks_block() {
+        let rules = vec![
+            rule("scan", "10.0.0.1", RuleAction::Block),
+            FilterRule {
+                dst_port: Some(22),
+                protocol: Some(PROTO_TCP),
+                ..rule("ssh-block", "10.0.0.2", RuleAction::Block)
+            },
+            FilterRule {
+                dst_port: Some(22),
+                protocol: Some(PROTO_TCP),
+                ..rule("ssh-reflect", "10.0.0.2", RuleAction::Reflect)
+            },
+        ];
+        let mut entries = BTreeMap::new();
+        for entry in [
+            BlocklistEntry::new(cidr("10.0.0.1"), RuleAction::Reflect),
+            BlocklistEntry::new(cidr("172.16.0.0/12"), RuleAction::Reflect),
+            BlocklistEntry::new(cidr("172.16.5.0/24"), RuleAction::Block),
+            BlocklistEntry::new(cidr("172.16.9.9"), RuleAction::Monitor),
+        ] {
+            entries.insert(entry.cidr, entry);
+        }
+
+        let state = desired_state(&rules, &entries);
+        assert_eq!(
+            state.hosts.get(&map_key(Ipv4Addr::new(10, 0, 0, 1))),
+            Some(&RuleAction::Reflect)
+        );
+        assert_eq!(
+            state.ports.values().collect::<Vec<_>>(),
+            vec![&RuleAction::Reflect]
+        );
+        // Reflect 블록 안의 더 약한 엔트리는 Reflect로 승격
+        assert_eq!(
+            state.prefixes.get(&cidr("172.16.5.0/24")),
+            Some(&RuleAction::Reflect)
+        );
+        assert_eq!(
+            state.hosts.get(&map_key(Ipv4Addr::new(172, 16, 9, 9))),
+            Some(&RuleAction::Reflect)
+        );
+    }
+
+    #[test]
+    fn test_desired_state_collects_allow_entries_separately() {
+        let rules = vec![
+            rule("mgmt", "10.0.0.5", RuleAction::Allow),
+            FilterRule {
+                dst_port: Some(22),
+                ..rule("mgmt-ssh", "10.0.0.6", RuleAction::Allow)
+            },
+        ];
+        let mut entries = BTreeMap::new();
+        for entry in [
+            BlocklistEntry::new(cidr("10.0.0.0/8"), RuleAction::Block),
+            BlocklistEntry::new(cidr("192.168.10.0/24"), RuleAction::Allow),
+        ] {
+            entries.insert(entry.cidr, entry);
+        }
+
+        let state = desired_state(&rules, &entries);
+
+        // 허용 엔트리는 차단 맵에 들어가지 않음
+        assert!(state.hosts.is_empty());
+        assert!(
+            state.ports.is_empty(),
+            "port-scoped allow rules are skipped"
+        );
+        assert_eq!(
+            state.prefixes.get(&cidr("10.0.0.0/8")),
+            Some(&RuleAction::Block)
+        );
+        let allowed: Vec<String> = state.allowed.keys().map(|c| c.to_string()).collect();
+        assert_eq!(allowed, vec!["10.0.0.5", "192.168.10.0/24"]);
+        assert!(state.allowed.values().all(|a| *a == RuleAction::Allow));
+    }
+
+    #[test]
+    fn test_insert_all_rejects_allowlist_overflow() {
+        let mut blocklist = Blocklist::new();
+        let entries = (0..=MAX_ALLOWLIST_ENTRIES as u32).map(|i| {
+            BlocklistEntry::new(
+                Ipv4Cidr::host(Ipv4Addr::from(0x0a00_0000 | i)),
+                RuleAction::Allow,
+            )
+        });
+
+        assert!(blocklist.insert_all(entries).is_err());
+        assert!(blocklist.is_empty());
+    }
+
+    #[test]
+    fn test_desired_state_port_rules_use_wildcards() {
+        let port_rule =
+            |id: &str, src_ip: Option<&str>, port: u16, protocol: Option<u8>| FilterRule {
+                src_ip: src_ip.map(|ip| ip.parse().unwrap()),
+                dst_port: Some(port),
+                protocol,
+                ..rule(id, "0.0.0.0", RuleAction::Block)
+            };
+        let rules = vec![
+            port_rule("telnet", None, 23, Some(PROTO_TCP)),
+            port_rule("dns", Some("10.0.0.5"), 53, None),
+            // ICMP에는 포트가 없으므로 커널 룰로 만들 수 없음
+            port_rule("icmp", None, 7, Some(1)),
+            FilterRule {
+                src_cidr: Some(cidr("10.0.0.0/24")),
+                ..port_rule("subnet", None, 22, Some(PROTO_TCP))
+            },
+        ];
+
+        let state = desired_state(&rules, &BTreeMap::new());
+
+        // dst_port가 있는 룰은 호스트 전체를 차단하지 않음
+        assert!(state.hosts.is_empty());
+        assert!(state.prefixes.is_empty());
+        assert_eq!(state.ports.len(), 2);
+        assert_eq!(
+            state
+                .ports
+                .get(&PortRuleKey::new(PORT_RULE_ANY_IP, 23, PROTO_TCP)),
+            Some(&RuleAction::Block)
+        );
+        assert_eq!(
+            state.ports.get(&PortRuleKey::new(
+                map_key(Ipv4Addr::new(10, 0, 0, 5)),
+                53,
+                PORT_RULE_ANY_PROTO
+            )),
+            Some(&RuleAction::Block)
+        );
+    }
+
+    #[test]
+    fn test_trie_data_keeps_network_byte_order() {
+        // 커널은 패킷 헤더의 바이트를 그대로 키 데이터로 사용
+        let ip = Ipv4Addr::new(10, 1, 2, 3);
+        assert_eq!(trie_data(ip).to_ne_bytes(), [10, 1, 2, 3]);
+        assert_eq!(trie_data(ip), u32::from(ip).to_be());
+    }
+
+    #[test]
+    fn test_restored_entry_keeps_runtime_entries_with_ttl() {
+        use ironpost_ebpf_common::{ACTION_DROP, ACTION_MONITOR};
+
+        let entry = BlocklistEntry::new(cidr("10.0.0.0/24"), RuleAction::Block)
+            .with_ttl(Duration::from_secs(60));
+        let expires_at = entry.expires_at.unwrap();
+        let value = blocklist_value(ACTION_DROP, 7, ValueMeta::of(Some(&entry)));
+        assert!(ValueMeta::of(Some(&entry)).matches(&value));
+
+        let restored = restored_entry(entry.cidr, &value, expires_at - 1).unwrap();
+        assert_eq!(restored.cidr, entry.cidr);
+        assert_eq!(restored.action, RuleAction::Block);
+        assert_eq!(restored.expires_at, Some(expires_at));
+
+        // 만료된 엔트리와 설정 룰 엔트리는 복원하지 않음
+        assert!(restored_entry(entry.cidr, &value, expires_at).is_none());
+        let rule_value = blocklist_value(ACTION_MONITOR, 0, ValueMeta::RULE);
+        assert!(restored_entry(cidr("10.0.0.9"), &rule_value, 0).is_none());
+
+        let permanent = BlocklistEntry::new(cidr("10.0.0.9"), RuleAction::Monitor);
+        let value = blocklist_value(ACTION_MONITOR, 0, ValueMeta::of(Some(&permanent)));
+        let restored = restored_entry(permanent.cidr, &value, u64::MAX).unwrap();
+        assert_eq!(restored.action, RuleAction::Monitor);
+        assert_eq!(restored.expires_at, None);
+    }
+
+    #[test]
+    fn test_restored_entry_maps_pass_to_allow() {
+        use ironpost_ebpf_common::ACTION_PASS;
+
+        let entry = BlocklistEntry::new(cidr("10.10.0.0/24"), RuleAction::Allow);
+        let value = blocklist_value(ACTION_PASS, 3, ValueMeta::of(Some(&entry)));
+        let restored = restored_entry(entry.cidr, &value, 0).unwrap();
+        assert_eq!(restored.action, RuleAction::Allow);
+    }
+
+    #[test]
+    fn test_restored_entry_maps_reflect() {
+        use ironpost_ebpf_common::ACTION_REFLECT;
+
+        let entry = BlocklistEntry::new(cidr("10.20.0.1"), RuleAction::Reflect);
+        let value = blocklist_value(ACTION_REFLECT, 0, ValueMeta::of(Some(&entry)));
+        let restored = restored_entry(entry.cidr, &value, 0).unwrap();
+        assert_eq!(restored.action, RuleAction::Reflect);
+    }
+}
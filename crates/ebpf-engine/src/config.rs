@@ -1,11 +1,17 @@
 //! eBPF 엔진 설정 — 필터링 룰 관리 및 동적 업데이트
 //!
 //! [`EngineConfig`]는 core의 [`EbpfConfig`]를 확장하여 필터링 룰을 관리합니다.
-//! 런타임에 룰을 동적으로 추가/삭제하면 eBPF HashMap 맵이 업데이트됩니다.
+//! 런타임에 룰을 동적으로 추가/삭제하면 eBPF 차단 목록 맵이 업데이트됩니다.
 //!
 //! # 설정 예시 (TOML)
 //! ```toml
 //! [[rules]]
+//! id = "allow-bastion"
+//! src_cidr = "10.10.0.0/24"
+//! action = "allow"
+//! description = "Management network, never dropped"
+//!
+//! [[rules]]
 //! id = "block-scanner"
 //! src_ip = "10.0.0.50"
 //! action = "block"
@@ -16,16 +22,33 @@
 //! src_ip = "192.168.1.100"
 //! action = "monitor"
 //! description = "Suspicious internal host"
+//!
+//! [[rules]]
+//! id = "block-botnet-range"
+//! src_cidr = "203.0.113.0/24"
+//! action = "block"
+//! description = "Botnet C2 subnet"
+//!
+//! [[rules]]
+//! id = "block-telnet"
+//! dst_port = 23
+//! protocol = 6
+//! action = "block"
+//! description = "Inbound telnet from anywhere"
 //! ```
 
 use std::net::IpAddr;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
 use ironpost_core::config::EbpfConfig;
 use ironpost_core::error::IronpostError;
 
+use crate::blocklist::Ipv4Cidr;
+use crate::traffic_alert::TrafficThresholds;
+
 // =============================================================================
 // 입력 검증 상수
 // =============================================================================
@@ -47,6 +70,13 @@ pub enum RuleAction {
     Block,
     /// 패킷 통과 + 모니터링 이벤트 전송
     Monitor,
+    /// 항상 통과 (차단 목록, 포트 룰, 레이트 리밋을 모두 건너뜀)
+    Allow,
+    /// TCP는 RST로 응답 (XDP_TX), 그 외 프로토콜은 차단
+    ///
+    /// 조용히 드롭하는 대신 포트가 닫힌 것처럼 즉시 연결을 끊어, 스캐너가 재전송
+    /// 타임아웃을 기다리게 하지 않고 필터의 존재도 드러내지 않습니다.
+    Reflect,
 }
 
 /// 네트워크 필터링 룰
@@ -54,16 +84,28 @@ pub enum RuleAction {
 /// IP/포트/프로토콜 조합으로 차단 또는 모니터링 대상을 지정합니다.
 /// `None` 필드는 "모든 값"을 의미합니다 (와일드카드).
 ///
-/// # eBPF HashMap 매핑
-/// 현재 eBPF HashMap 키는 `u32` (IPv4 주소)이므로,
-/// `src_ip`가 설정된 룰만 커널 맵에 반영됩니다.
-/// 포트/프로토콜 필터링은 유저스페이스에서 보조 처리합니다.
+/// # eBPF 맵 매핑
+/// `src_ip`가 설정된 룰은 `BLOCKLIST` HashMap(IPv4 주소 키)에,
+/// `src_cidr`가 설정된 룰은 `BLOCKLIST_CIDR` LpmTrie에 반영됩니다
+/// (`/32`는 HashMap). 둘 중 하나만 설정할 수 있습니다.
+///
+/// `dst_port`가 설정된 룰은 호스트 전체가 아니라 `PORT_RULES` HashMap에
+/// (출발지 IP, 목적지 포트, 프로토콜) 키로 반영되어 커널에서 포트 단위로 차단됩니다.
+/// 출발지/프로토콜이 없으면 와일드카드이며, 프로토콜은 TCP(6) 또는 UDP(17)만,
+/// 출발지는 단일 IPv4 주소만 지원합니다. `dst_ip`는 커널에서 매칭하지 않습니다.
+///
+/// `Allow` 룰은 `src_ip`/`src_cidr`만으로 지정하며 `ALLOWLIST` LpmTrie에 반영됩니다.
+/// 커널은 차단 목록보다 허용 목록을 먼저 조회하므로 겹치는 `Block` 룰이 있어도
+/// 해당 출발지의 패킷은 드롭되지 않습니다.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilterRule {
     /// 규칙 고유 ID
     pub id: String,
     /// 출발지 IP (None이면 모든 IP)
     pub src_ip: Option<IpAddr>,
+    /// 출발지 IPv4 CIDR (예: `10.0.0.0/8`, `src_ip`와 함께 쓸 수 없음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub src_cidr: Option<Ipv4Cidr>,
     /// 목적지 IP (None이면 모든 IP)
     pub dst_ip: Option<IpAddr>,
     /// 목적지 포트 (None이면 모든 포트)
@@ -76,6 +118,18 @@ pub struct FilterRule {
     pub description: String,
 }
 
+/// 출발지별 레이트 리밋 (XDP 토큰 버킷)
+///
+/// 출발지 IP마다 최대 `burst`개의 토큰을 가진 버킷을 두고, 초당 `packets_per_sec`개씩
+/// 채웁니다. 토큰이 없는 패킷은 커널에서 바로 드롭되며 이벤트를 만들지 않습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// 출발지 IP당 초당 허용 패킷 수
+    pub packets_per_sec: u64,
+    /// 버킷 용량 (한 번에 허용하는 최대 패킷 수)
+    pub burst: u64,
+}
+
 /// eBPF 엔진 확장 설정
 ///
 /// core의 [`EbpfConfig`]를 기반으로 필터링 룰을 추가합니다.
@@ -89,6 +143,9 @@ pub struct EngineConfig {
     /// 필터링 룰 목록
     #[serde(default)]
     pub rules: Vec<FilterRule>,
+    /// 관찰 전용(드라이런) 모드 (드롭 판정을 로그와 `would_drops`로 집계하고 패킷은 통과)
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// TOML 룰 파일의 최상위 구조
@@ -104,6 +161,7 @@ impl EngineConfig {
         Self {
             base: config.clone(),
             rules: Vec::new(),
+            dry_run: config.observe_only,
         }
     }
 
@@ -115,6 +173,8 @@ impl EngineConfig {
     /// - 파일 크기: 최대 10MB
     /// - 룰 개수: 최대 10,000개
     /// - 룰 ID: 비어있지 않고, 중복되지 않으며, 최대 256자
+    /// - 출발지: `src_ip`와 `src_cidr` 중 하나만 설정
+    /// - 허용 룰: 출발지가 필요하고 `dst_port`를 설정할 수 없음
     /// - 설명: 최대 1024자
     pub async fn load_rules(path: impl AsRef<Path>) -> Result<Vec<FilterRule>, IronpostError> {
         use ironpost_core::error::ConfigError;
@@ -170,26 +230,7 @@ impl EngineConfig {
         // 룰 내용 검증
         let mut seen_ids = HashSet::new();
         for rule in &rules_file.rules {
-            // 빈 ID 검증
-            if rule.id.is_empty() {
-                return Err(ConfigError::ParseFailed {
-                    reason: "rule ID cannot be empty".to_owned(),
-                }
-                .into());
-            }
-
-            // ID 길이 검증
-            if rule.id.len() > MAX_RULE_ID_LEN {
-                return Err(ConfigError::ParseFailed {
-                    reason: format!(
-                        "rule ID '{}' too long: {} chars (max: {})",
-                        rule.id,
-                        rule.id.len(),
-                        MAX_RULE_ID_LEN
-                    ),
-                }
-                .into());
-            }
+            validate_rule(rule)?;
 
             // 중복 ID 검증
             if !seen_ids.insert(&rule.id) {
@@ -198,19 +239,6 @@ impl EngineConfig {
                 }
                 .into());
             }
-
-            // 설명 길이 검증
-            if rule.description.len() > MAX_DESCRIPTION_LEN {
-                return Err(ConfigError::ParseFailed {
-                    reason: format!(
-                        "rule '{}' description too long: {} chars (max: {})",
-                        rule.id,
-                        rule.description.len(),
-                        MAX_DESCRIPTION_LEN
-                    ),
-                }
-                .into());
-            }
         }
 
         Ok(rules_file.rules)
@@ -233,17 +261,232 @@ impl EngineConfig {
         self.rules.len() < before
     }
 
-    /// src_ip가 설정된 차단/모니터링 룰을 반환합니다.
+    /// 커널에 적용할 출발지별 레이트 리밋을 반환합니다.
+    ///
+    /// `rate_limit_pps`가 0이면 `None`(비활성화)입니다. 관찰 전용 모드에서도 커널이
+    /// 초과 여부를 판정하며, 초과한 패킷은 드롭 대신 would-drop으로 집계됩니다.
+    /// `rate_limit_burst`가 0이면 1초 분량(`rate_limit_pps`)을 버스트로 사용합니다.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        if self.base.rate_limit_pps == 0 {
+            return None;
+        }
+        let burst = match self.base.rate_limit_burst {
+            0 => self.base.rate_limit_pps,
+            burst => burst,
+        };
+        Some(RateLimit {
+            packets_per_sec: self.base.rate_limit_pps,
+            burst,
+        })
+    }
+
+    /// 탐지된 출발지를 자동 차단할 기간을 반환합니다.
+    ///
+    /// `auto_block_ttl_secs`가 0이면 `None`(자동 차단 비활성화)입니다.
+    /// 관찰 전용 모드에서는 커널이 차단 엔트리를 드롭하지 않으므로 그대로 반환합니다.
+    pub fn auto_block_ttl(&self) -> Option<Duration> {
+        match self.base.auto_block_ttl_secs {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        }
+    }
+
+    /// 통계 폴러가 평가할 트래픽 알림 임계치를 반환합니다.
     ///
-    /// eBPF HashMap에 반영 가능한 룰만 필터링합니다.
+    /// `traffic_alert_pps`, `traffic_alert_bytes_per_sec`, `traffic_alert_drop_percent`가
+    /// 모두 0이면 `None`(트래픽 알림 비활성화)입니다.
+    pub fn traffic_thresholds(&self) -> Option<TrafficThresholds> {
+        let thresholds = TrafficThresholds {
+            packets_per_sec: self.base.traffic_alert_pps,
+            bytes_per_sec: self.base.traffic_alert_bytes_per_sec,
+            drop_percent: self.base.traffic_alert_drop_percent,
+            cooldown: Duration::from_secs(self.base.traffic_alert_cooldown_secs),
+        };
+        thresholds.is_enabled().then_some(thresholds)
+    }
+
+    /// 차단 목록 상태 파일 경로를 반환합니다 (`state_path`가 비어 있으면 `None`).
+    pub fn state_path(&self) -> Option<PathBuf> {
+        let path = self.base.state_path.trim();
+        (!path.is_empty()).then(|| PathBuf::from(path))
+    }
+
+    /// src_ip 또는 src_cidr가 설정된 호스트/CIDR 차단/모니터링/허용 룰을 반환합니다.
+    ///
+    /// `BLOCKLIST`/`BLOCKLIST_CIDR`/`ALLOWLIST` 맵에 반영되는 룰만 필터링합니다
+    /// (`dst_port`가 있는 룰은 [`port_rules`](Self::port_rules)).
     pub fn ip_rules(&self) -> impl Iterator<Item = &FilterRule> {
-        self.rules.iter().filter(|r| r.src_ip.is_some())
+        self.rules
+            .iter()
+            .filter(|r| r.dst_port.is_none() && (r.src_ip.is_some() || r.src_cidr.is_some()))
+    }
+
+    /// dst_port가 설정된 포트 룰을 반환합니다.
+    ///
+    /// `PORT_RULES` 맵에 반영되는 룰입니다.
+    pub fn port_rules(&self) -> impl Iterator<Item = &FilterRule> {
+        self.rules.iter().filter(|r| r.dst_port.is_some())
     }
 }
 
+/// 룰 하나의 내용을 검증합니다.
+///
+/// [`EngineConfig::load_rules`]와 [`merge_rules`]가 공유하는 검증으로,
+/// ID·출발지·허용 룰·설명 조건을 확인합니다 (중복 ID와 개수는 호출자가 확인).
+pub fn validate_rule(rule: &FilterRule) -> Result<(), IronpostError> {
+    use ironpost_core::error::ConfigError;
+
+    // 빈 ID 검증
+    if rule.id.is_empty() {
+        return Err(ConfigError::ParseFailed {
+            reason: "rule ID cannot be empty".to_owned(),
+        }
+        .into());
+    }
+
+    // ID 길이 검증
+    if rule.id.len() > MAX_RULE_ID_LEN {
+        return Err(ConfigError::ParseFailed {
+            reason: format!(
+                "rule ID '{}' too long: {} chars (max: {})",
+                rule.id,
+                rule.id.len(),
+                MAX_RULE_ID_LEN
+            ),
+        }
+        .into());
+    }
+
+    // 출발지 중복 지정 검증
+    if rule.src_ip.is_some() && rule.src_cidr.is_some() {
+        return Err(ConfigError::ParseFailed {
+            reason: format!(
+                "rule '{}' sets both src_ip and src_cidr (use only one)",
+                rule.id
+            ),
+        }
+        .into());
+    }
+
+    // 허용 룰은 출발지 단위로만 적용
+    if rule.action == RuleAction::Allow {
+        if rule.src_ip.is_none() && rule.src_cidr.is_none() {
+            return Err(ConfigError::ParseFailed {
+                reason: format!("allow rule '{}' requires src_ip or src_cidr", rule.id),
+            }
+            .into());
+        }
+        if rule.dst_port.is_some() {
+            return Err(ConfigError::ParseFailed {
+                reason: format!("allow rule '{}' cannot set dst_port", rule.id),
+            }
+            .into());
+        }
+    }
+
+    // RST 응답은 TCP에만 보낼 수 있음
+    if rule.action == RuleAction::Reflect
+        && rule
+            .protocol
+            .is_some_and(|p| p != ironpost_ebpf_common::PROTO_TCP)
+    {
+        return Err(ConfigError::ParseFailed {
+            reason: format!("reflect rule '{}' only supports protocol 6 (TCP)", rule.id),
+        }
+        .into());
+    }
+
+    // 설명 길이 검증
+    if rule.description.len() > MAX_DESCRIPTION_LEN {
+        return Err(ConfigError::ParseFailed {
+            reason: format!(
+                "rule '{}' description too long: {} chars (max: {})",
+                rule.id,
+                rule.description.len(),
+                MAX_DESCRIPTION_LEN
+            ),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// 룰 일괄 가져오기 결과
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleImport {
+    /// 새로 추가된 룰 수
+    pub added: usize,
+    /// 같은 ID의 기존 룰을 교체한 수
+    pub replaced: usize,
+    /// 가져온 목록 안에서 ID가 중복되어 버려진 룰 수 (마지막 룰이 남음)
+    pub duplicates: usize,
+}
+
+/// 기존 룰 목록에 가져온 룰들을 병합한 새 목록을 반환합니다.
+///
+/// 모든 룰을 먼저 검증하므로 하나라도 잘못되면 아무것도 병합하지 않습니다.
+/// 가져온 목록 안의 중복 ID는 마지막 룰만 남기고, 기존 룰과 ID가 같으면
+/// [`EngineConfig::add_rule`]처럼 교체합니다. 병합 결과가 최대 룰 개수를 넘으면
+/// 에러를 반환합니다.
+pub fn merge_rules(
+    current: &[FilterRule],
+    incoming: Vec<FilterRule>,
+) -> Result<(Vec<FilterRule>, RuleImport), IronpostError> {
+    use ironpost_core::error::ConfigError;
+    use std::collections::HashSet;
+
+    for rule in &incoming {
+        validate_rule(rule)?;
+    }
+
+    // 가져온 목록 안의 중복 제거 (뒤에서부터 훑어 마지막 룰을 남김)
+    let total = incoming.len();
+    let mut seen = HashSet::with_capacity(total);
+    let mut unique: Vec<FilterRule> = incoming
+        .into_iter()
+        .rev()
+        .filter(|rule| seen.insert(rule.id.clone()))
+        .collect();
+    unique.reverse();
+
+    let mut summary = RuleImport {
+        duplicates: total - unique.len(),
+        ..RuleImport::default()
+    };
+    let ids: HashSet<&str> = unique.iter().map(|rule| rule.id.as_str()).collect();
+    let mut merged: Vec<FilterRule> = Vec::with_capacity(current.len() + unique.len());
+    for rule in current {
+        if ids.contains(rule.id.as_str()) {
+            summary.replaced += 1;
+        } else {
+            merged.push(rule.clone());
+        }
+    }
+    summary.added = unique.len() - summary.replaced;
+    merged.extend(unique);
+
+    if merged.len() > MAX_RULES_COUNT {
+        return Err(ConfigError::ParseFailed {
+            reason: format!(
+                "too many rules: {} (max: {})",
+                merged.len(),
+                MAX_RULES_COUNT
+            ),
+        }
+        .into());
+    }
+
+    Ok((merged, summary))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ironpost_ebpf_common::{
+        IPV4_HDR_LEN, PROTO_TCP, REFLECT_TTL, TCP_ACK, TCP_HDR_LEN, TCP_PSH, TCP_RST, TCP_SYN,
+        tcp_rst_reply,
+    };
     use std::net::Ipv4Addr;
 
     // =============================================================================
@@ -255,6 +498,7 @@ mod tests {
         let rule = FilterRule {
             id: "test-rule".to_owned(),
             src_ip: None,
+            src_cidr: None,
             dst_ip: None,
             dst_port: None,
             protocol: None,
@@ -275,6 +519,7 @@ mod tests {
         let rule = FilterRule {
             id: "full-rule".to_owned(),
             src_ip: Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 100))),
+            src_cidr: None,
             dst_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))),
             dst_port: Some(443),
             protocol: Some(6), // TCP
@@ -306,6 +551,11 @@ mod tests {
 
         assert_eq!(deserialized_block, RuleAction::Block);
         assert_eq!(deserialized_monitor, RuleAction::Monitor);
+
+        let allow: RuleAction = serde_json::from_str(r#""allow""#).unwrap();
+        assert_eq!(allow, RuleAction::Allow);
+        let reflect: RuleAction = serde_json::from_str(r#""reflect""#).unwrap();
+        assert_eq!(reflect, RuleAction::Reflect);
     }
 
     // =============================================================================
@@ -322,6 +572,20 @@ mod tests {
             xdp_mode: "skb".to_owned(),
             ring_buffer_size: 1024,
             blocklist_max_entries: 10000,
+            rate_limit_pps: 0,
+            rate_limit_burst: 0,
+            monitor_sample_rate: 1,
+            observe_only: false,
+            auto_block_ttl_secs: 0,
+            traffic_alert_pps: 0,
+            traffic_alert_bytes_per_sec: 0,
+            traffic_alert_drop_percent: 0,
+            traffic_alert_cooldown_secs: 300,
+            process_correlation: false,
+            tls_sni: false,
+            state_path: String::new(),
+            map_pin_path: "/sys/fs/bpf/ironpost".to_owned(),
+            threat_feeds: Vec::new(),
         };
 
         let engine_config = EngineConfig::from_core(&ebpf_config);
@@ -330,6 +594,67 @@ mod tests {
         assert_eq!(engine_config.base.xdp_mode, "skb");
         assert_eq!(engine_config.base.ring_buffer_size, 1024);
         assert!(engine_config.rules.is_empty());
+        assert!(engine_config.rate_limit().is_none());
+        assert!(!engine_config.dry_run);
+
+        let observed = EngineConfig::from_core(&EbpfConfig {
+            observe_only: true,
+            ..ebpf_config
+        });
+        assert!(observed.dry_run);
+    }
+
+    #[test]
+    fn test_rate_limit_burst_defaults_and_dry_run() {
+        let mut config = EngineConfig::default();
+        config.base.rate_limit_pps = 500;
+        assert_eq!(
+            config.rate_limit(),
+            Some(RateLimit {
+                packets_per_sec: 500,
+                burst: 500,
+            })
+        );
+
+        config.base.rate_limit_burst = 2000;
+        assert_eq!(config.rate_limit().map(|r| r.burst), Some(2000));
+
+        // 관찰 전용 모드에서도 레이트 리밋 판정은 유지 (커널이 would-drop으로 집계)
+        config.dry_run = true;
+        assert_eq!(config.rate_limit().map(|r| r.packets_per_sec), Some(500));
+    }
+
+    #[test]
+    fn test_auto_block_ttl_disabled_by_default() {
+        let mut config = EngineConfig::default();
+        assert!(config.auto_block_ttl().is_none());
+
+        config.base.auto_block_ttl_secs = 300;
+        assert_eq!(config.auto_block_ttl(), Some(Duration::from_secs(300)));
+    }
+
+    #[test]
+    fn test_traffic_thresholds_disabled_by_default() {
+        let mut config = EngineConfig::default();
+        assert!(config.traffic_thresholds().is_none());
+
+        config.base.traffic_alert_drop_percent = 20;
+        let thresholds = config.traffic_thresholds().unwrap();
+        assert_eq!(thresholds.drop_percent, 20);
+        assert_eq!(thresholds.packets_per_sec, 0);
+        assert_eq!(thresholds.cooldown, Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_state_path_empty_disables_persistence() {
+        let mut config = EngineConfig::default();
+        assert_eq!(
+            config.state_path(),
+            Some(PathBuf::from("/var/lib/ironpost/ebpf-state.json"))
+        );
+
+        config.base.state_path = "  ".to_owned();
+        assert!(config.state_path().is_none());
     }
 
     #[test]
@@ -339,6 +664,7 @@ mod tests {
         let rule = FilterRule {
             id: "rule-1".to_owned(),
             src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 50))),
+            src_cidr: None,
             dst_ip: None,
             dst_port: None,
             protocol: None,
@@ -359,6 +685,7 @@ mod tests {
         let rule1 = FilterRule {
             id: "rule-1".to_owned(),
             src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 50))),
+            src_cidr: None,
             dst_ip: None,
             dst_port: None,
             protocol: None,
@@ -369,6 +696,7 @@ mod tests {
         let rule2 = FilterRule {
             id: "rule-1".to_owned(),
             src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 51))),
+            src_cidr: None,
             dst_ip: None,
             dst_port: Some(443),
             protocol: Some(6),
@@ -385,6 +713,55 @@ mod tests {
         assert_eq!(config.rules[0].dst_port, Some(443));
     }
 
+    fn host_rule(id: &str, last_octet: u8, description: &str) -> FilterRule {
+        FilterRule {
+            id: id.to_owned(),
+            src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, last_octet))),
+            src_cidr: None,
+            dst_ip: None,
+            dst_port: None,
+            protocol: None,
+            action: RuleAction::Block,
+            description: description.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_merge_rules_deduplicates_and_replaces() {
+        let current = vec![host_rule("a", 1, "old a"), host_rule("b", 2, "b")];
+        let incoming = vec![
+            host_rule("c", 3, "first c"),
+            host_rule("a", 4, "new a"),
+            host_rule("c", 5, "second c"),
+        ];
+
+        let (merged, summary) = merge_rules(&current, incoming).unwrap();
+
+        assert_eq!(
+            summary,
+            RuleImport {
+                added: 1,
+                replaced: 1,
+                duplicates: 1,
+            }
+        );
+        let ids: Vec<&str> = merged.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a", "c"]);
+        assert_eq!(merged[1].description, "new a");
+        assert_eq!(merged[2].description, "second c");
+    }
+
+    #[test]
+    fn test_merge_rules_rejects_invalid_rule_atomically() {
+        let current = vec![host_rule("a", 1, "a")];
+        let mut invalid = host_rule("allow-port", 2, "");
+        invalid.action = RuleAction::Allow;
+        invalid.dst_port = Some(22);
+
+        let err = merge_rules(&current, vec![host_rule("b", 3, "b"), invalid]).unwrap_err();
+        assert!(err.to_string().contains("cannot set dst_port"));
+    }
+
     #[test]
     fn test_remove_rule_existing() {
         let mut config = EngineConfig::default();
@@ -392,6 +769,7 @@ mod tests {
         let rule = FilterRule {
             id: "rule-1".to_owned(),
             src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 50))),
+            src_cidr: None,
             dst_ip: None,
             dst_port: None,
             protocol: None,
@@ -422,6 +800,7 @@ mod tests {
         let rule1 = FilterRule {
             id: "rule-1".to_owned(),
             src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 50))),
+            src_cidr: None,
             dst_ip: None,
             dst_port: None,
             protocol: None,
@@ -432,6 +811,7 @@ mod tests {
         let rule2 = FilterRule {
             id: "rule-2".to_owned(),
             src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 51))),
+            src_cidr: None,
             dst_ip: None,
             dst_port: None,
             protocol: None,
@@ -455,6 +835,7 @@ mod tests {
         let rule_with_ip = FilterRule {
             id: "rule-with-ip".to_owned(),
             src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 50))),
+            src_cidr: None,
             dst_ip: None,
             dst_port: None,
             protocol: None,
@@ -465,6 +846,7 @@ mod tests {
         let rule_without_ip = FilterRule {
             id: "rule-without-ip".to_owned(),
             src_ip: None,
+            src_cidr: None,
             dst_ip: Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1))),
             dst_port: Some(443),
             protocol: Some(6),
@@ -478,6 +860,10 @@ mod tests {
         let ip_rules: Vec<_> = config.ip_rules().collect();
         assert_eq!(ip_rules.len(), 1);
         assert_eq!(ip_rules[0].id, "rule-with-ip");
+
+        let port_rules: Vec<_> = config.port_rules().collect();
+        assert_eq!(port_rules.len(), 1);
+        assert_eq!(port_rules[0].id, "rule-without-ip");
     }
 
     #[test]
@@ -487,6 +873,7 @@ mod tests {
         let rule = FilterRule {
             id: "no-ip".to_owned(),
             src_ip: None,
+            src_cidr: None,
             dst_ip: Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1))),
             dst_port: Some(80),
             protocol: Some(6),
@@ -500,6 +887,107 @@ mod tests {
         assert!(ip_rules.is_empty());
     }
 
+    /// 옵션 없는 IPv4 헤더와 `tcp_hdr_len` 바이트 TCP 헤더 (체크섬 0)
+    fn tcp_segment(
+        flags: u8,
+        seq: u32,
+        ack: u32,
+        tcp_hdr_len: u8,
+        payload_len: u16,
+    ) -> ([u8; IPV4_HDR_LEN], [u8; TCP_HDR_LEN]) {
+        let mut ip = [0u8; IPV4_HDR_LEN];
+        ip[0] = 0x45;
+        let total = IPV4_HDR_LEN as u16 + u16::from(tcp_hdr_len) + payload_len;
+        ip[2..4].copy_from_slice(&total.to_be_bytes());
+        ip[4..6].copy_from_slice(&0x1234u16.to_be_bytes());
+        ip[8] = 52;
+        ip[9] = PROTO_TCP;
+        ip[12..16].copy_from_slice(&[198, 51, 100, 7]);
+        ip[16..20].copy_from_slice(&[10, 0, 0, 1]);
+
+        let mut tcp = [0u8; TCP_HDR_LEN];
+        tcp[0..2].copy_from_slice(&40_000u16.to_be_bytes());
+        tcp[2..4].copy_from_slice(&22u16.to_be_bytes());
+        tcp[4..8].copy_from_slice(&seq.to_be_bytes());
+        tcp[8..12].copy_from_slice(&ack.to_be_bytes());
+        tcp[12] = (tcp_hdr_len / 4) << 4;
+        tcp[13] = flags;
+        tcp[14..16].copy_from_slice(&64_240u16.to_be_bytes());
+        (ip, tcp)
+    }
+
+    /// 체크섬 필드를 포함해 다시 계산한 1의 보수 합 (올바르면 0)
+    fn verify_checksum(words: impl IntoIterator<Item = u16>) -> u16 {
+        let mut sum: u32 = words.into_iter().map(u32::from).sum();
+        while sum > 0xFFFF {
+            sum = (sum & 0xFFFF) + (sum >> 16);
+        }
+        !(sum as u16)
+    }
+
+    fn be_words(bytes: &[u8]) -> impl Iterator<Item = u16> + '_ {
+        bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+    }
+
+    #[test]
+    fn test_tcp_rst_reply_answers_syn_with_rst_ack() {
+        // 옵션 12바이트가 붙은 SYN (스캐너의 일반적인 형태)
+        let (mut ip, mut tcp) = tcp_segment(TCP_SYN, 1_000, 0, 32, 0);
+        assert!(tcp_rst_reply(&mut ip, &mut tcp));
+
+        // 주소/포트 교환, 헤더만 담은 40바이트
+        assert_eq!(&ip[12..16], &[10, 0, 0, 1]);
+        assert_eq!(&ip[16..20], &[198, 51, 100, 7]);
+        assert_eq!(u16::from_be_bytes([ip[2], ip[3]]), 40);
+        assert_eq!(ip[8], REFLECT_TTL);
+        assert_eq!(u16::from_be_bytes([tcp[0], tcp[1]]), 22);
+        assert_eq!(u16::from_be_bytes([tcp[2], tcp[3]]), 40_000);
+
+        // ACK 없는 세그먼트: seq=0, ack=SEG.SEQ+SEG.LEN (SYN은 1)
+        assert_eq!(u32::from_be_bytes([tcp[4], tcp[5], tcp[6], tcp[7]]), 0);
+        assert_eq!(
+            u32::from_be_bytes([tcp[8], tcp[9], tcp[10], tcp[11]]),
+            1_001
+        );
+        assert_eq!(tcp[12] >> 4, 5);
+        assert_eq!(tcp[13], TCP_RST | TCP_ACK);
+        assert_eq!(&tcp[14..16], &[0, 0], "window is zero");
+
+        // IP 체크섬과 의사 헤더를 포함한 TCP 체크섬 검증
+        assert_eq!(verify_checksum(be_words(&ip)), 0);
+        let pseudo = be_words(&ip[12..20]).chain([u16::from(PROTO_TCP), TCP_HDR_LEN as u16]);
+        assert_eq!(verify_checksum(pseudo.chain(be_words(&tcp))), 0);
+    }
+
+    #[test]
+    fn test_tcp_rst_reply_uses_ack_number_and_skips_rst() {
+        // ACK가 있는 데이터 세그먼트: seq=SEG.ACK인 RST
+        let (mut ip, mut tcp) = tcp_segment(TCP_PSH | TCP_ACK, 5_000, 0xFFFF_FFF0, 20, 100);
+        assert!(tcp_rst_reply(&mut ip, &mut tcp));
+        assert_eq!(
+            u32::from_be_bytes([tcp[4], tcp[5], tcp[6], tcp[7]]),
+            0xFFFF_FFF0
+        );
+        assert_eq!(u32::from_be_bytes([tcp[8], tcp[9], tcp[10], tcp[11]]), 0);
+        assert_eq!(tcp[13], TCP_RST);
+
+        // RST에는 응답하지 않고, IP 옵션이 있거나 길이가 맞지 않으면 건드리지 않음
+        for (vihl, flags, total_len) in [
+            (0x45, TCP_RST, 40u16),
+            (0x46, TCP_SYN, 44),
+            (0x45, TCP_SYN, 30),
+        ] {
+            let (mut ip, mut tcp) = tcp_segment(flags, 1, 0, 20, 0);
+            ip[0] = vihl;
+            ip[2..4].copy_from_slice(&total_len.to_be_bytes());
+            let original = (ip, tcp);
+            assert!(!tcp_rst_reply(&mut ip, &mut tcp));
+            assert_eq!((ip, tcp), original);
+        }
+    }
+
     // =============================================================================
     // load_rules 테스트
     // =============================================================================
@@ -601,6 +1089,120 @@ description = "Invalid IP"
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_load_rules_src_cidr() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let rules_path = tmp_dir.path().join("cidr.toml");
+
+        let cidr_toml = r#"
+[[rules]]
+id = "block-private"
+src_cidr = "10.1.2.3/8"
+action = "block"
+description = "Private range"
+"#;
+
+        tokio::fs::write(&rules_path, cidr_toml).await.unwrap();
+
+        let rules = EngineConfig::load_rules(&rules_path).await.unwrap();
+        assert_eq!(rules.len(), 1);
+        assert!(rules[0].src_ip.is_none());
+        // 호스트 비트는 정규화됨
+        assert_eq!(
+            rules[0].src_cidr.map(|c| c.to_string()).as_deref(),
+            Some("10.0.0.0/8")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_rules_rejects_src_ip_with_src_cidr() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let rules_path = tmp_dir.path().join("both.toml");
+
+        let both_toml = r#"
+[[rules]]
+id = "ambiguous"
+src_ip = "10.0.0.1"
+src_cidr = "10.0.0.0/24"
+action = "block"
+description = "Both sources"
+"#;
+
+        tokio::fs::write(&rules_path, both_toml).await.unwrap();
+
+        let err = EngineConfig::load_rules(&rules_path).await.unwrap_err();
+        assert!(err.to_string().contains("both src_ip and src_cidr"));
+    }
+
+    #[tokio::test]
+    async fn test_load_rules_validates_allow_rules() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let rules_path = tmp_dir.path().join("allow.toml");
+
+        let valid_toml = r#"
+[[rules]]
+id = "allow-mgmt"
+src_cidr = "10.10.0.0/24"
+action = "allow"
+description = "Management network"
+"#;
+        tokio::fs::write(&rules_path, valid_toml).await.unwrap();
+        let rules = EngineConfig::load_rules(&rules_path).await.unwrap();
+        assert_eq!(rules[0].action, RuleAction::Allow);
+
+        let no_source = r#"
+[[rules]]
+id = "allow-all"
+action = "allow"
+description = ""
+"#;
+        tokio::fs::write(&rules_path, no_source).await.unwrap();
+        let err = EngineConfig::load_rules(&rules_path).await.unwrap_err();
+        assert!(err.to_string().contains("requires src_ip or src_cidr"));
+
+        let with_port = r#"
+[[rules]]
+id = "allow-ssh"
+src_ip = "10.10.0.5"
+dst_port = 22
+action = "allow"
+description = ""
+"#;
+        tokio::fs::write(&rules_path, with_port).await.unwrap();
+        let err = EngineConfig::load_rules(&rules_path).await.unwrap_err();
+        assert!(err.to_string().contains("cannot set dst_port"));
+    }
+
+    #[tokio::test]
+    async fn test_load_rules_validates_reflect_rules() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let rules_path = tmp_dir.path().join("reflect.toml");
+
+        let valid_toml = r#"
+[[rules]]
+id = "reflect-ssh"
+dst_port = 22
+protocol = 6
+action = "reflect"
+description = "Reset SSH probes"
+"#;
+        tokio::fs::write(&rules_path, valid_toml).await.unwrap();
+        let rules = EngineConfig::load_rules(&rules_path).await.unwrap();
+        assert_eq!(rules[0].action, RuleAction::Reflect);
+
+        let udp = r#"
+[[rules]]
+id = "reflect-dns"
+dst_port = 53
+protocol = 17
+action = "reflect"
+description = ""
+"#;
+        tokio::fs::write(&rules_path, udp).await.unwrap();
+        let err = EngineConfig::load_rules(&rules_path).await.unwrap_err();
+        assert!(err.to_string().contains("only supports protocol 6"));
+    }
+
     #[tokio::test]
     async fn test_load_rules_missing_required_fields() {
         let tmp_dir = tempfile::tempdir().unwrap();
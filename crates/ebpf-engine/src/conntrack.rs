@@ -0,0 +1,331 @@
+//! 연결 추적 통계 — CONNTRACK 맵 기반 흐름 집계
+//!
+//! XDP 프로그램은 드롭되지 않은 TCP/UDP 패킷마다 `CONNTRACK` LruHashMap에
+//! 5-튜플별 상태와 타임스탬프를 기록합니다. [`ConnTrackStats`]는 엔진이 주기적으로
+//! 읽어 온 흐름 목록에서 상태별 흐름 수와 출발지 호스트별 초당 새 연결 수를 계산합니다.
+//!
+//! # 데이터 흐름
+//! ```text
+//! CONNTRACK (kernel) ──poll──▶ Vec<RawFlow> ──update──▶ ConnTrackStats
+//!                                                      (상태 집계, 호스트별 cps)
+//! ```
+//!
+//! # 시간 기준
+//! 흐름 타임스탬프는 커널의 `bpf_ktime_get_ns`(부팅 후 나노초)입니다. 유저스페이스
+//! 시계와 비교하지 않고, 맵에서 가장 최근의 `last_seen_ns`를 커널의 "현재"로 보고
+//! 활성 여부를 판단합니다. 새 연결은 이전 폴링에서 본 가장 늦은 `first_seen_ns`보다
+//! 뒤에 시작한 흐름입니다.
+
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use ironpost_ebpf_common::{
+    CONNTRACK_IDLE_TIMEOUT_NS, CT_STATE_CLOSING, CT_STATE_ESTABLISHED, CT_STATE_NEW,
+};
+
+/// [`ConnTrackStats::top_hosts`]에 유지하는 최대 호스트 수
+pub const TOP_HOSTS_LIMIT: usize = 10;
+
+/// CONNTRACK 맵에서 읽은 흐름 하나
+#[derive(Debug, Clone)]
+pub struct RawFlow {
+    /// 출발지 IPv4 주소
+    pub src_ip: Ipv4Addr,
+    /// 목적지 IPv4 주소
+    pub dst_ip: Ipv4Addr,
+    /// 출발지 포트
+    pub src_port: u16,
+    /// 목적지 포트
+    pub dst_port: u16,
+    /// IP 프로토콜 번호 (TCP/UDP)
+    pub protocol: u8,
+    /// 흐름 상태 (`CT_STATE_*`)
+    pub state: u8,
+    /// 첫 패킷 시각 (커널 나노초)
+    pub first_seen_ns: u64,
+    /// 마지막 패킷 시각 (커널 나노초)
+    pub last_seen_ns: u64,
+    /// 패킷 수
+    pub packets: u64,
+}
+
+/// 출발지 호스트별 연결 지표
+#[derive(Debug, Clone, Serialize)]
+pub struct HostConnRate {
+    /// 출발지 호스트
+    pub host: Ipv4Addr,
+    /// 초당 새 연결 수 (직전 폴링 이후)
+    pub cps: f64,
+    /// 직전 폴링 이후 새로 시작한 흐름 수
+    pub new_flows: u64,
+    /// 활성 흐름 수
+    pub active_flows: usize,
+}
+
+/// 연결 추적 통계
+///
+/// 활성 흐름(유휴 타임아웃 이내)만 집계합니다. 첫 번째 `update()`는 기준점만
+/// 기록하므로 cps가 0입니다.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConnTrackStats {
+    /// 활성 흐름 수
+    pub active_flows: usize,
+    /// NEW 상태의 활성 흐름 수
+    pub new_flows: usize,
+    /// ESTABLISHED 상태의 활성 흐름 수
+    pub established_flows: usize,
+    /// CLOSING 상태의 활성 흐름 수
+    pub closing_flows: usize,
+    /// 초당 새 연결이 많은 출발지 (cps 내림차순, 최대 [`TOP_HOSTS_LIMIT`]개)
+    pub top_hosts: Vec<HostConnRate>,
+    /// 모든 출발지의 초당 새 연결 수 (조회용, 직렬화 제외)
+    #[serde(skip)]
+    host_cps: HashMap<Ipv4Addr, f64>,
+    /// 이전 폴링에서 본 가장 늦은 흐름 시작 시각 (직렬화 제외)
+    #[serde(skip)]
+    watermark_ns: Option<u64>,
+    /// 마지막 업데이트 시각 (직렬화 제외)
+    #[serde(skip)]
+    last_poll: Option<Instant>,
+}
+
+impl ConnTrackStats {
+    /// 빈 통계를 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 맵에서 읽은 흐름 목록으로 통계를 갱신합니다.
+    pub fn update(&mut self, flows: &[RawFlow]) {
+        self.update_at(flows, Instant::now());
+    }
+
+    /// `host`의 초당 새 연결 수를 반환합니다 (최근 폴링에 새 연결이 없으면 0).
+    pub fn connections_per_second(&self, host: Ipv4Addr) -> f64 {
+        self.host_cps.get(&host).copied().unwrap_or_default()
+    }
+
+    fn update_at(&mut self, flows: &[RawFlow], now: Instant) {
+        let kernel_now = flows.iter().map(|f| f.last_seen_ns).max().unwrap_or(0);
+        let elapsed_secs = self
+            .last_poll
+            .map(|last| now.duration_since(last).as_secs_f64())
+            .filter(|secs| *secs > 0.0);
+
+        self.active_flows = 0;
+        self.new_flows = 0;
+        self.established_flows = 0;
+        self.closing_flows = 0;
+
+        // 호스트별 (새 흐름 수, 활성 흐름 수)
+        let mut hosts: HashMap<Ipv4Addr, (u64, usize)> = HashMap::new();
+        for flow in flows {
+            if kernel_now.saturating_sub(flow.last_seen_ns) > CONNTRACK_IDLE_TIMEOUT_NS {
+                continue;
+            }
+            self.active_flows += 1;
+            match flow.state {
+                CT_STATE_NEW => self.new_flows += 1,
+                CT_STATE_ESTABLISHED => self.established_flows += 1,
+                CT_STATE_CLOSING => self.closing_flows += 1,
+                _ => {}
+            }
+
+            let host = hosts.entry(flow.src_ip).or_default();
+            host.1 += 1;
+            if self
+                .watermark_ns
+                .is_some_and(|watermark| flow.first_seen_ns > watermark)
+            {
+                host.0 += 1;
+            }
+        }
+
+        // u64 → f64 변환: 폴링 간격 동안의 새 연결 수이므로 정밀도 손실은 무시할 수준
+        #[allow(clippy::cast_precision_loss)]
+        let mut rates: Vec<HostConnRate> = hosts
+            .into_iter()
+            .map(|(host, (new_flows, active_flows))| HostConnRate {
+                host,
+                cps: elapsed_secs.map_or(0.0, |secs| new_flows as f64 / secs),
+                new_flows,
+                active_flows,
+            })
+            .collect();
+        rates.sort_by(|a, b| {
+            b.cps
+                .total_cmp(&a.cps)
+                .then(b.active_flows.cmp(&a.active_flows))
+                .then(a.host.cmp(&b.host))
+        });
+
+        self.host_cps = rates
+            .iter()
+            .filter(|r| r.cps > 0.0)
+            .map(|r| (r.host, r.cps))
+            .collect();
+        rates.truncate(TOP_HOSTS_LIMIT);
+        self.top_hosts = rates;
+
+        let latest_start = flows.iter().map(|f| f.first_seen_ns).max();
+        self.watermark_ns = self.watermark_ns.max(latest_start).or(Some(0));
+        self.last_poll = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use ironpost_ebpf_common::{
+        PROTO_TCP, PROTO_UDP, TCP_ACK, TCP_FIN, TCP_PSH, TCP_RST, TCP_SYN, next_flow_state,
+    };
+
+    const SEC: u64 = 1_000_000_000;
+
+    fn flow(
+        src: [u8; 4],
+        src_port: u16,
+        state: u8,
+        first_seen_s: u64,
+        last_seen_s: u64,
+    ) -> RawFlow {
+        RawFlow {
+            src_ip: Ipv4Addr::from(src),
+            dst_ip: Ipv4Addr::new(10, 0, 0, 1),
+            src_port,
+            dst_port: 443,
+            protocol: PROTO_TCP,
+            state,
+            first_seen_ns: first_seen_s * SEC,
+            last_seen_ns: last_seen_s * SEC,
+            packets: 1,
+        }
+    }
+
+    #[test]
+    fn test_first_update_counts_states_without_rates() {
+        let mut stats = ConnTrackStats::new();
+        let flows = vec![
+            flow([192, 0, 2, 1], 40000, CT_STATE_NEW, 100, 100),
+            flow([192, 0, 2, 1], 40001, CT_STATE_ESTABLISHED, 90, 100),
+            flow([192, 0, 2, 2], 40000, CT_STATE_CLOSING, 80, 99),
+        ];
+
+        stats.update_at(&flows, Instant::now());
+
+        assert_eq!(stats.active_flows, 3);
+        assert_eq!(stats.new_flows, 1);
+        assert_eq!(stats.established_flows, 1);
+        assert_eq!(stats.closing_flows, 1);
+        assert_eq!(stats.top_hosts[0].host, Ipv4Addr::new(192, 0, 2, 1));
+        assert_eq!(stats.top_hosts[0].active_flows, 2);
+        assert_eq!(stats.top_hosts[0].cps, 0.0);
+    }
+
+    #[test]
+    fn test_connections_per_second_counts_flows_after_watermark() {
+        let mut stats = ConnTrackStats::new();
+        let start = Instant::now();
+        let old = flow([192, 0, 2, 1], 40000, CT_STATE_ESTABLISHED, 10, 100);
+        stats.update_at(std::slice::from_ref(&old), start);
+
+        // 5초 뒤: 192.0.2.9가 새 연결 20개, 기존 흐름은 계속 활성
+        let mut flows = vec![flow([192, 0, 2, 1], 40000, CT_STATE_ESTABLISHED, 10, 104)];
+        flows.extend((0..20).map(|i| flow([192, 0, 2, 9], 50000 + i, CT_STATE_NEW, 102, 103)));
+        stats.update_at(&flows, start + Duration::from_secs(5));
+
+        assert_eq!(stats.top_hosts[0].host, Ipv4Addr::new(192, 0, 2, 9));
+        assert_eq!(stats.top_hosts[0].new_flows, 20);
+        assert_eq!(
+            stats.connections_per_second(Ipv4Addr::new(192, 0, 2, 9)),
+            4.0
+        );
+        assert_eq!(
+            stats.connections_per_second(Ipv4Addr::new(192, 0, 2, 1)),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_idle_flows_are_not_active() {
+        let mut stats = ConnTrackStats::new();
+        let idle_secs = CONNTRACK_IDLE_TIMEOUT_NS / SEC;
+        let flows = vec![
+            flow([192, 0, 2, 1], 40000, CT_STATE_ESTABLISHED, 1, 1),
+            flow(
+                [192, 0, 2, 2],
+                40000,
+                CT_STATE_NEW,
+                idle_secs + 10,
+                idle_secs + 10,
+            ),
+        ];
+
+        stats.update_at(&flows, Instant::now());
+
+        assert_eq!(stats.active_flows, 1);
+        assert_eq!(stats.top_hosts.len(), 1);
+        assert_eq!(stats.top_hosts[0].host, Ipv4Addr::new(192, 0, 2, 2));
+    }
+
+    #[test]
+    fn test_top_hosts_truncated_and_serialized() {
+        let mut stats = ConnTrackStats::new();
+        let flows: Vec<RawFlow> = (1..=30)
+            .map(|i| flow([192, 0, 2, i], 40000, CT_STATE_NEW, 5, 5))
+            .collect();
+
+        stats.update_at(&flows, Instant::now());
+
+        assert_eq!(stats.top_hosts.len(), TOP_HOSTS_LIMIT);
+        let json = serde_json::to_value(&stats).expect("serialize");
+        assert_eq!(json["active_flows"], 30);
+        assert_eq!(json["top_hosts"][0]["host"], "192.0.2.1");
+        assert!(json.get("watermark_ns").is_none());
+    }
+
+    #[test]
+    fn test_next_flow_state_transitions() {
+        // TCP 핸드셰이크: SYN은 NEW 유지, SYN 없는 ACK에서 ESTABLISHED
+        assert_eq!(
+            next_flow_state(CT_STATE_NEW, PROTO_TCP, TCP_SYN),
+            CT_STATE_NEW
+        );
+        assert_eq!(
+            next_flow_state(CT_STATE_NEW, PROTO_TCP, TCP_SYN | TCP_ACK),
+            CT_STATE_NEW
+        );
+        assert_eq!(
+            next_flow_state(CT_STATE_NEW, PROTO_TCP, TCP_ACK),
+            CT_STATE_ESTABLISHED
+        );
+        assert_eq!(
+            next_flow_state(CT_STATE_ESTABLISHED, PROTO_TCP, TCP_PSH | TCP_ACK),
+            CT_STATE_ESTABLISHED
+        );
+        // FIN/RST는 어느 상태에서든 CLOSING
+        assert_eq!(
+            next_flow_state(CT_STATE_ESTABLISHED, PROTO_TCP, TCP_FIN | TCP_ACK),
+            CT_STATE_CLOSING
+        );
+        assert_eq!(
+            next_flow_state(CT_STATE_NEW, PROTO_TCP, TCP_RST),
+            CT_STATE_CLOSING
+        );
+        // CLOSING 이후 ACK로 되살아나지 않음
+        assert_eq!(
+            next_flow_state(CT_STATE_CLOSING, PROTO_TCP, TCP_ACK),
+            CT_STATE_CLOSING
+        );
+        // UDP는 두 번째 패킷부터 ESTABLISHED
+        assert_eq!(
+            next_flow_state(CT_STATE_NEW, PROTO_UDP, 0),
+            CT_STATE_ESTABLISHED
+        );
+    }
+}
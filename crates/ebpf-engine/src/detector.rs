@@ -6,17 +6,37 @@
 //! # 탐지 전략
 //! - **SYN Flood**: SYN 패킷 비율이 임계값을 초과하면 알림
 //! - **포트 스캔**: 단일 IP에서 N개 이상의 포트에 접근하면 알림
+//!   (커널 연결 추적상 이미 수립된 흐름의 패킷은 제외)
+//! - **ICMP flood / ping sweep**: 단일 IP의 ICMP 패킷 속도 또는 고유 목적지 호스트 수가
+//!   임계값을 넘으면 알림
+//!
+//! # 사용자 정의 분석기
+//! [`PacketAnalyzer`] trait을 구현하면 비콘(beaconing) 탐지 같은 탐지기를 추가할 수
+//! 있습니다. [`PacketDetector::with_analyzer`]로 등록한 분석기는 내장 탐지기 다음에
+//! 같은 `PacketEventData`를 받고, 반환한 알림은 같은 `AlertEvent` 채널로 전송됩니다.
+//!
+//! # 자동 차단
+//! [`PacketDetector::with_auto_block`]으로 요청 채널을 연결하면 SYN flood / 포트 스캔
+//! 알림을 낼 때 출발지 IP에 대한 [`AutoBlockRequest`]도 함께 보냅니다.
+//! 엔진이 요청을 받아 TTL이 있는 차단 목록 엔트리로 반영합니다.
+//!
+//! # 프로세스 연관
+//! [`PacketDetector::analyze_with_process`]로 연결 소유 프로세스를 함께 넘기면
+//! 알림 설명 끝에 프로세스/컨테이너가 덧붙습니다 ([`crate::process`] 참고).
 //!
 //! # 아키텍처
 //! ```text
 //! PacketEventData ──▶ PacketDetector ──▶ AlertEvent ──▶ mpsc::Sender
 //!                        │
 //!                        ├── SynFloodDetector (impl Detector)
-//!                        └── PortScanDetector (impl Detector)
+//!                        ├── PortScanDetector (impl Detector)
+//!                        ├── IcmpFloodDetector (impl Detector)
+//!                        ├── 등록된 분석기 (impl PacketAnalyzer)
+//!                        └──▶ AutoBlockRequest ──▶ mpsc::Sender (선택)
 //! ```
 
 use std::collections::{HashMap, HashSet};
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv4Addr};
 use std::time::{Instant, SystemTime};
 
 use tokio::sync::mpsc;
@@ -26,7 +46,57 @@ use ironpost_core::event::AlertEvent;
 use ironpost_core::pipeline::Detector;
 use ironpost_core::types::{Alert, LogEntry, Severity};
 
-use ironpost_ebpf_common::{PacketEventData, TCP_ACK, TCP_SYN};
+use ironpost_ebpf_common::{CT_STATE_ESTABLISHED, PROTO_ICMP, PacketEventData, TCP_ACK, TCP_SYN};
+
+use crate::process::ProcessInfo;
+
+// =============================================================================
+// 패킷 분석기 trait
+// =============================================================================
+
+/// 패킷 이벤트 분석기 trait
+///
+/// [`PacketDetector`]에 등록되어 eBPF RingBuf에서 수신한 모든 패킷 이벤트를 받습니다.
+/// 내장 탐지기([`SynFloodDetector`], [`PortScanDetector`], [`IcmpFloodDetector`])도
+/// 이 trait을 구현합니다.
+///
+/// 이벤트 수신 태스크에서 동기적으로 호출되므로 블로킹 I/O를 하면 안 됩니다.
+/// 내부 상태는 내장 탐지기처럼 `try_lock()` 등으로 non-blocking하게 갱신합니다.
+///
+/// # 구현 예시
+/// ```ignore
+/// struct BeaconDetector { /* ... */ }
+///
+/// impl PacketAnalyzer for BeaconDetector {
+///     fn name(&self) -> &str {
+///         "beaconing"
+///     }
+///
+///     fn analyze(&self, event: &PacketEventData) -> Result<Option<Alert>, IronpostError> {
+///         // 출발지/목적지 쌍별 연결 간격이 일정하면 알림
+///         Ok(None)
+///     }
+/// }
+///
+/// let detector = PacketDetector::default().with_analyzer(BeaconDetector::new());
+/// ```
+pub trait PacketAnalyzer: Send + Sync {
+    /// 분석기 이름 (알림의 `rule_name`에 쓰는 것을 권장)
+    fn name(&self) -> &str;
+
+    /// 패킷 이벤트를 분석하여 알림 생성 여부를 결정합니다.
+    fn analyze(&self, event: &PacketEventData) -> Result<Option<Alert>, IronpostError>;
+
+    /// 만료된 추적 상태를 정리합니다 (기본: 아무것도 하지 않음).
+    fn cleanup_stale(&self) {}
+
+    /// 알림을 낼 때 출발지 자동 차단을 요청할지 여부 (기본: `false`)
+    ///
+    /// 자동 차단 채널이 연결되어 있을 때만 적용됩니다.
+    fn auto_block(&self) -> bool {
+        false
+    }
+}
 
 // =============================================================================
 // 탐지 설정
@@ -74,6 +144,27 @@ impl Default for PortScanConfig {
     }
 }
 
+/// ICMP flood / ping sweep 탐지 설정
+#[derive(Debug, Clone)]
+pub struct IcmpFloodConfig {
+    /// 출발지별 ICMP 초당 패킷 수 임계값 (윈도우 평균)
+    pub pps_threshold: u64,
+    /// 측정 윈도우 크기 (초)
+    pub window_secs: u64,
+    /// 윈도우 내 고유 목적지 호스트 수 임계값 (ping sweep)
+    pub sweep_threshold: usize,
+}
+
+impl Default for IcmpFloodConfig {
+    fn default() -> Self {
+        Self {
+            pps_threshold: 100,
+            window_secs: 10,
+            sweep_threshold: 50,
+        }
+    }
+}
+
 // =============================================================================
 // 내부 추적 상태
 // =============================================================================
@@ -98,6 +189,20 @@ struct PortTracker {
     window_start: Instant,
 }
 
+/// IP별 ICMP 패킷 추적 상태
+struct IcmpTracker {
+    /// 윈도우 내 ICMP 패킷 수
+    packets: u64,
+    /// 윈도우 내 고유 목적지 호스트 (sweep_threshold개까지만 보관)
+    targets: HashSet<IpAddr>,
+    /// 윈도우 시작 시각
+    window_start: Instant,
+    /// 이번 윈도우에서 flood 알림을 생성했는지 여부
+    flood_alerted: bool,
+    /// 이번 윈도우에서 sweep 알림을 생성했는지 여부
+    sweep_alerted: bool,
+}
+
 // =============================================================================
 // SYN Flood 탐지기 (core::Detector trait 구현)
 // =============================================================================
@@ -202,10 +307,11 @@ impl SynFloodDetector {
             counter.alerted = false; // 새 윈도우에서는 다시 알림 가능
         }
 
-        // 카운터 업데이트
-        counter.total_tcp += 1;
+        // 카운터 업데이트 (샘플링된 이벤트는 대표하는 패킷 수만큼 센다)
+        let weight = event.sample_weight();
+        counter.total_tcp += weight;
         if is_syn_only {
-            counter.syn_only += 1;
+            counter.syn_only += weight;
         }
 
         // 탐지 조건 확인
@@ -377,6 +483,24 @@ impl Detector for SynFloodDetector {
     }
 }
 
+impl PacketAnalyzer for SynFloodDetector {
+    fn name(&self) -> &str {
+        Detector::name(self)
+    }
+
+    fn analyze(&self, event: &PacketEventData) -> Result<Option<Alert>, IronpostError> {
+        self.detect_packet(event)
+    }
+
+    fn cleanup_stale(&self) {
+        SynFloodDetector::cleanup_stale(self);
+    }
+
+    fn auto_block(&self) -> bool {
+        true
+    }
+}
+
 // =============================================================================
 // 포트 스캔 탐지기 (core::Detector trait 구현)
 // =============================================================================
@@ -384,7 +508,8 @@ impl Detector for SynFloodDetector {
 /// 포트 스캔 탐지기
 ///
 /// 단일 IP에서 설정된 윈도우 내에 N개 이상의 고유 포트에
-/// 접근하면 알림을 생성합니다.
+/// 접근하면 알림을 생성합니다. [`PacketEventData::ct_state`]가 ESTABLISHED인
+/// 패킷은 새 연결 시도가 아니므로 포트 수에 포함하지 않습니다.
 pub struct PortScanDetector {
     config: PortScanConfig,
     /// IP별 포트 접근 추적 (tokio::sync::Mutex + try_lock)
@@ -420,6 +545,11 @@ impl PortScanDetector {
     /// - 파싱 없음
     /// - 바이너리 필드 직접 접근
     pub fn detect_packet(&self, event: &PacketEventData) -> Result<Option<Alert>, IronpostError> {
+        // 이미 수립된 흐름은 스캔이 아님
+        if event.ct_state == CT_STATE_ESTABLISHED {
+            return Ok(None);
+        }
+
         // 출발지 IP 변환
         let src_ip = IpAddr::V4(std::net::Ipv4Addr::from(u32::from_be(event.src_ip)));
 
@@ -595,16 +725,240 @@ impl Detector for PortScanDetector {
     }
 }
 
+impl PacketAnalyzer for PortScanDetector {
+    fn name(&self) -> &str {
+        Detector::name(self)
+    }
+
+    fn analyze(&self, event: &PacketEventData) -> Result<Option<Alert>, IronpostError> {
+        self.detect_packet(event)
+    }
+
+    fn cleanup_stale(&self) {
+        PortScanDetector::cleanup_stale(self);
+    }
+
+    fn auto_block(&self) -> bool {
+        true
+    }
+}
+
+// =============================================================================
+// ICMP flood 탐지기 (core::Detector trait 구현)
+// =============================================================================
+
+/// ICMP flood / ping sweep 탐지기
+///
+/// 단일 IP에서 윈도우 동안 평균 `pps_threshold` 이상의 ICMP 패킷을 보내면
+/// flood 알림을, `sweep_threshold`개 이상의 서로 다른 호스트에 보내면
+/// ping sweep 알림을 생성합니다. 각 알림은 윈도우당 한 번만 생성됩니다.
+pub struct IcmpFloodDetector {
+    config: IcmpFloodConfig,
+    /// IP별 ICMP 추적 (tokio::sync::Mutex + try_lock)
+    state: tokio::sync::Mutex<HashMap<IpAddr, IcmpTracker>>,
+}
+
+impl IcmpFloodDetector {
+    /// 새 ICMP flood 탐지기를 생성합니다.
+    pub fn new(config: IcmpFloodConfig) -> Self {
+        Self {
+            config,
+            state: tokio::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 내부 상태에서 만료된 윈도우를 정리합니다.
+    pub fn cleanup_stale(&self) {
+        if let Ok(mut state) = self.state.try_lock() {
+            let now = Instant::now();
+            state.retain(|_, tracker| {
+                now.duration_since(tracker.window_start).as_secs() < self.config.window_secs
+            });
+        }
+    }
+
+    /// PacketEventData를 분석하여 ICMP flood / ping sweep 여부를 판단합니다.
+    ///
+    /// ICMP가 아닌 패킷은 무시합니다. 다른 탐지기와 마찬가지로
+    /// LogEntry 변환 없이 바이너리 필드를 직접 읽습니다.
+    pub fn detect_packet(&self, event: &PacketEventData) -> Result<Option<Alert>, IronpostError> {
+        if event.protocol != PROTO_ICMP {
+            return Ok(None);
+        }
+
+        let src_ip = IpAddr::V4(std::net::Ipv4Addr::from(u32::from_be(event.src_ip)));
+        let dst_ip = IpAddr::V4(std::net::Ipv4Addr::from(u32::from_be(event.dst_ip)));
+
+        Ok(self.observe(src_ip, Some(dst_ip), event.sample_weight()))
+    }
+
+    /// 출발지의 ICMP 패킷 `packets`개(샘플링된 이벤트는 샘플링 비율)를 기록하고,
+    /// 임계값을 넘으면 알림을 반환합니다.
+    ///
+    /// flood와 sweep이 같은 패킷에서 동시에 성립하면 flood를 먼저 반환하고,
+    /// sweep은 다음 패킷에서 반환합니다.
+    fn observe(&self, src_ip: IpAddr, dst_ip: Option<IpAddr>, packets: u64) -> Option<Alert> {
+        // try_lock으로 non-blocking 상태 업데이트
+        let Ok(mut state) = self.state.try_lock() else {
+            tracing::debug!("IcmpFloodDetector: lock contention, skipping detection");
+            return None;
+        };
+
+        let now = Instant::now();
+
+        // 최대 엔트리 수 제한 (IP 스푸핑 기반 DoS 방지)
+        if state.len() >= MAX_TRACKED_IPS && !state.contains_key(&src_ip) {
+            // 만료된 엔트리 정리 시도
+            state.retain(|_, tracker| {
+                now.duration_since(tracker.window_start).as_secs() < self.config.window_secs
+            });
+
+            // 정리 후에도 초과하면 새 엔트리 거부
+            if state.len() >= MAX_TRACKED_IPS {
+                tracing::warn!(
+                    "IcmpFloodDetector: MAX_TRACKED_IPS reached, dropping new IP tracking"
+                );
+                return None;
+            }
+        }
+
+        // 엔트리 획득 또는 생성
+        let tracker = state.entry(src_ip).or_insert_with(|| IcmpTracker {
+            packets: 0,
+            targets: HashSet::new(),
+            window_start: now,
+            flood_alerted: false,
+            sweep_alerted: false,
+        });
+
+        // 윈도우 만료 확인
+        if now.duration_since(tracker.window_start).as_secs() >= self.config.window_secs {
+            // 윈도우 리셋
+            tracker.packets = 0;
+            tracker.targets.clear();
+            tracker.window_start = now;
+            tracker.flood_alerted = false;
+            tracker.sweep_alerted = false;
+        }
+
+        // 카운터 업데이트 (목적지 집합은 임계값까지만 키움)
+        tracker.packets += packets;
+        if let Some(dst_ip) = dst_ip
+            && tracker.targets.len() < self.config.sweep_threshold
+        {
+            tracker.targets.insert(dst_ip);
+        }
+
+        // flood 조건: 윈도우 전체 기준 평균 pps
+        let window_secs = self.config.window_secs.max(1);
+        let flood_packets = self.config.pps_threshold.saturating_mul(window_secs);
+        if tracker.packets >= flood_packets && !tracker.flood_alerted {
+            tracker.flood_alerted = true;
+            return Some(Alert {
+                id: uuid::Uuid::new_v4().to_string(),
+                title: format!("ICMP flood detected from {}", src_ip),
+                description: format!(
+                    "{} ICMP packets within {} seconds window (threshold: {} pps)",
+                    tracker.packets, window_secs, self.config.pps_threshold,
+                ),
+                severity: Severity::High,
+                rule_name: "icmp_flood".to_owned(),
+                source_ip: Some(src_ip),
+                target_ip: None,
+                created_at: SystemTime::now(),
+            });
+        }
+
+        // sweep 조건: 고유 목적지 호스트 수
+        if tracker.targets.len() >= self.config.sweep_threshold && !tracker.sweep_alerted {
+            tracker.sweep_alerted = true;
+            return Some(Alert {
+                id: uuid::Uuid::new_v4().to_string(),
+                title: format!("Ping sweep detected from {}", src_ip),
+                description: format!(
+                    "Single IP sent ICMP to {} unique hosts within {} seconds (threshold: {})",
+                    tracker.targets.len(),
+                    self.config.window_secs,
+                    self.config.sweep_threshold,
+                ),
+                severity: Severity::Medium,
+                rule_name: "ping_sweep".to_owned(),
+                source_ip: Some(src_ip),
+                target_ip: None,
+                created_at: SystemTime::now(),
+            });
+        }
+
+        None
+    }
+}
+
+impl Detector for IcmpFloodDetector {
+    fn name(&self) -> &str {
+        "icmp_flood"
+    }
+
+    /// LogEntry를 분석하여 ICMP flood / ping sweep 여부를 판단합니다.
+    ///
+    /// LogEntry의 fields에서 패킷 메타데이터를 추출합니다:
+    /// - `src_ip`: 출발지 IP
+    /// - `dst_ip`: 목적지 IP (없으면 sweep 판단에서 제외)
+    /// - `protocol`: 프로토콜 번호 (1=ICMP)
+    fn detect(&self, entry: &LogEntry) -> Result<Option<Alert>, IronpostError> {
+        let field = |name: &str| {
+            entry
+                .fields
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.as_str())
+        };
+
+        if field("protocol").and_then(|v| v.parse::<u8>().ok()) != Some(PROTO_ICMP) {
+            return Ok(None);
+        }
+        let Some(src_ip) = field("src_ip").and_then(|v| v.parse::<IpAddr>().ok()) else {
+            return Ok(None);
+        };
+        let dst_ip = field("dst_ip").and_then(|v| v.parse::<IpAddr>().ok());
+
+        Ok(self.observe(src_ip, dst_ip, 1))
+    }
+}
+
+impl PacketAnalyzer for IcmpFloodDetector {
+    fn name(&self) -> &str {
+        Detector::name(self)
+    }
+
+    fn analyze(&self, event: &PacketEventData) -> Result<Option<Alert>, IronpostError> {
+        self.detect_packet(event)
+    }
+
+    fn cleanup_stale(&self) {
+        IcmpFloodDetector::cleanup_stale(self);
+    }
+}
+
 // =============================================================================
 // 패킷 탐지 코디네이터
 // =============================================================================
 
+/// 탐지된 출발지를 차단 목록에 넣도록 엔진에 보내는 요청
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoBlockRequest {
+    /// 차단할 출발지 IP
+    pub src_ip: Ipv4Addr,
+    /// 요청을 만든 탐지 룰 이름 (`syn_flood`, `port_scan`)
+    pub rule_name: String,
+}
+
 /// 패킷 기반 위협 탐지 코디네이터
 ///
 /// eBPF RingBuf에서 수신한 PacketEventData를 분석하여 위협을 탐지하고,
 /// AlertEvent를 이벤트 채널로 전송합니다.
 ///
-/// 내부적으로 [`SynFloodDetector`]와 [`PortScanDetector`]를 관리합니다.
+/// 내부적으로 [`SynFloodDetector`], [`PortScanDetector`], [`IcmpFloodDetector`]를 관리하며,
+/// [`with_analyzer`](Self::with_analyzer)로 등록한 [`PacketAnalyzer`]를 그 다음에 실행합니다.
 pub struct PacketDetector {
     /// 알림 이벤트 전송 채널
     alert_tx: Option<mpsc::Sender<AlertEvent>>,
@@ -612,6 +966,12 @@ pub struct PacketDetector {
     syn_flood: SynFloodDetector,
     /// 포트 스캔 탐지기
     port_scan: PortScanDetector,
+    /// ICMP flood / ping sweep 탐지기
+    icmp_flood: IcmpFloodDetector,
+    /// 사용자가 등록한 분석기 (등록 순서대로 실행)
+    analyzers: Vec<Box<dyn PacketAnalyzer>>,
+    /// 자동 차단 요청 채널 (설정하지 않으면 알림만 생성)
+    block_tx: Option<mpsc::Sender<AutoBlockRequest>>,
 }
 
 impl PacketDetector {
@@ -620,14 +980,56 @@ impl PacketDetector {
         alert_tx: mpsc::Sender<AlertEvent>,
         syn_flood_config: SynFloodConfig,
         port_scan_config: PortScanConfig,
+        icmp_flood_config: IcmpFloodConfig,
     ) -> Self {
         Self {
             alert_tx: Some(alert_tx),
             syn_flood: SynFloodDetector::new(syn_flood_config),
             port_scan: PortScanDetector::new(port_scan_config),
+            icmp_flood: IcmpFloodDetector::new(icmp_flood_config),
+            analyzers: Vec::new(),
+            block_tx: None,
         }
     }
 
+    /// 사용자 정의 패킷 분석기를 등록합니다.
+    ///
+    /// 등록한 분석기는 내장 탐지기 다음에 등록 순서대로 실행됩니다.
+    pub fn with_analyzer(mut self, analyzer: impl PacketAnalyzer + 'static) -> Self {
+        self.register(Box::new(analyzer));
+        self
+    }
+
+    /// 박싱된 분석기를 등록합니다 (엔진 빌더용).
+    pub(crate) fn register(&mut self, analyzer: Box<dyn PacketAnalyzer>) {
+        self.analyzers.push(analyzer);
+    }
+
+    /// 실행 순서대로 모든 분석기 이름을 반환합니다 (내장 탐지기 포함).
+    pub fn analyzer_names(&self) -> Vec<&str> {
+        self.all_analyzers().map(|a| a.name()).collect()
+    }
+
+    /// 내장 탐지기와 등록된 분석기를 실행 순서대로 순회합니다.
+    fn all_analyzers(&self) -> impl Iterator<Item = &dyn PacketAnalyzer> {
+        let builtin: [&dyn PacketAnalyzer; 3] =
+            [&self.syn_flood, &self.port_scan, &self.icmp_flood];
+        builtin
+            .into_iter()
+            .chain(self.analyzers.iter().map(|a| a.as_ref()))
+    }
+
+    /// SYN flood / 포트 스캔 탐지 시 출발지 자동 차단 요청을 보낼 채널을 지정합니다.
+    pub fn with_auto_block(mut self, block_tx: mpsc::Sender<AutoBlockRequest>) -> Self {
+        self.block_tx = Some(block_tx);
+        self
+    }
+
+    /// 자동 차단 요청 채널이 연결되어 있는지 확인합니다.
+    pub fn auto_block_enabled(&self) -> bool {
+        self.block_tx.is_some()
+    }
+
     /// PacketEventData를 분석하여 위협을 탐지합니다.
     ///
     /// 내부 탐지기들에게 이벤트를 직접 전달하고, 알림이 생성되면
@@ -638,44 +1040,75 @@ impl PacketDetector {
     /// - PacketEventData 바이너리 필드 직접 접근
     /// - Alert 생성 시점에만 문자열화
     pub fn analyze(&self, event: &PacketEventData) -> Result<(), IronpostError> {
-        use ironpost_core::MODULE_EBPF;
+        self.analyze_with_process(event, None)
+    }
 
-        // SYN flood 탐지 (최적화 버전: PacketEventData 직접 처리)
-        if let Some(alert) = self.syn_flood.detect_packet(event)? {
-            let severity = alert.severity;
-            let alert_event = AlertEvent::with_source(alert, severity, MODULE_EBPF);
-
-            // 채널이 있으면 전송
-            if let Some(ref tx) = self.alert_tx {
-                // try_send 사용 (async context 아님, non-blocking)
-                tx.try_send(alert_event).map_err(|e| {
-                    PipelineError::ChannelSend(format!("failed to send alert: {}", e))
-                })?;
+    /// 연결을 소유한 프로세스 정보와 함께 PacketEventData를 분석합니다.
+    ///
+    /// `process`가 있으면 생성된 알림의 설명 끝에 프로세스/컨테이너를 덧붙입니다.
+    pub fn analyze_with_process(
+        &self,
+        event: &PacketEventData,
+        process: Option<&ProcessInfo>,
+    ) -> Result<(), IronpostError> {
+        // 내장 탐지기(SYN flood, 포트 스캔, ICMP flood) 다음 등록된 분석기 순서
+        for analyzer in self.all_analyzers() {
+            if let Some(alert) = analyzer.analyze(event)? {
+                if analyzer.auto_block() {
+                    self.request_block(event, &alert.rule_name);
+                }
+                self.send_alert(alert, process)?;
             }
         }
 
-        // 포트 스캔 탐지 (최적화 버전: PacketEventData 직접 처리)
-        if let Some(alert) = self.port_scan.detect_packet(event)? {
-            let severity = alert.severity;
-            let alert_event = AlertEvent::with_source(alert, severity, MODULE_EBPF);
+        Ok(())
+    }
+
+    /// 알림을 AlertEvent로 감싸 채널이 있으면 전송합니다.
+    fn send_alert(
+        &self,
+        mut alert: Alert,
+        process: Option<&ProcessInfo>,
+    ) -> Result<(), IronpostError> {
+        use ironpost_core::MODULE_EBPF;
 
-            // 채널이 있으면 전송
-            if let Some(ref tx) = self.alert_tx {
-                tx.try_send(alert_event).map_err(|e| {
-                    PipelineError::ChannelSend(format!("failed to send alert: {}", e))
-                })?;
-            }
+        if let Some(process) = process {
+            alert.description = format!("{} (process: {})", alert.description, process);
         }
+        let severity = alert.severity;
+        let alert_event = AlertEvent::with_source(alert, severity, MODULE_EBPF);
 
+        // try_send 사용 (async context 아님, non-blocking)
+        if let Some(ref tx) = self.alert_tx {
+            tx.try_send(alert_event)
+                .map_err(|e| PipelineError::ChannelSend(format!("failed to send alert: {}", e)))?;
+        }
         Ok(())
     }
 
+    /// 이벤트 출발지에 대한 자동 차단 요청을 보냅니다.
+    ///
+    /// 채널이 가득 차도 알림 전송을 막지 않도록 요청은 경고만 남기고 버립니다.
+    fn request_block(&self, event: &PacketEventData, rule_name: &str) {
+        let Some(ref tx) = self.block_tx else {
+            return;
+        };
+        let request = AutoBlockRequest {
+            src_ip: Ipv4Addr::from(u32::from_be(event.src_ip)),
+            rule_name: rule_name.to_owned(),
+        };
+        if let Err(e) = tx.try_send(request) {
+            tracing::warn!(error = %e, rule = rule_name, "failed to send auto-block request");
+        }
+    }
+
     /// 오래된 추적 데이터를 정리합니다.
     ///
     /// 주기적으로 호출하여 만료된 윈도우의 상태를 제거합니다.
     pub fn cleanup_stale(&self) {
-        self.syn_flood.cleanup_stale();
-        self.port_scan.cleanup_stale();
+        for analyzer in self.all_analyzers() {
+            analyzer.cleanup_stale();
+        }
     }
 
     /// SYN flood 탐지기에 대한 참조를 반환합니다.
@@ -687,6 +1120,11 @@ impl PacketDetector {
     pub fn port_scan_detector(&self) -> &PortScanDetector {
         &self.port_scan
     }
+
+    /// ICMP flood 탐지기에 대한 참조를 반환합니다.
+    pub fn icmp_flood_detector(&self) -> &IcmpFloodDetector {
+        &self.icmp_flood
+    }
 }
 
 impl Default for PacketDetector {
@@ -695,6 +1133,9 @@ impl Default for PacketDetector {
             alert_tx: None,
             syn_flood: SynFloodDetector::new(SynFloodConfig::default()),
             port_scan: PortScanDetector::new(PortScanConfig::default()),
+            icmp_flood: IcmpFloodDetector::new(IcmpFloodConfig::default()),
+            analyzers: Vec::new(),
+            block_tx: None,
         }
     }
 }
@@ -723,7 +1164,8 @@ mod tests {
             protocol: ironpost_ebpf_common::PROTO_TCP,
             action: ironpost_ebpf_common::ACTION_PASS,
             tcp_flags: TCP_SYN,
-            _pad: [0; 1],
+            ct_state: 0,
+            sample_rate: 1,
         };
 
         let log_entry = packet_event_to_log_entry(&event);
@@ -758,7 +1200,8 @@ mod tests {
             protocol: ironpost_ebpf_common::PROTO_UDP,
             action: ironpost_ebpf_common::ACTION_PASS,
             tcp_flags: 0,
-            _pad: [0; 1],
+            ct_state: 0,
+            sample_rate: 1,
         };
 
         let log_entry = packet_event_to_log_entry(&event);
@@ -1039,6 +1482,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_port_scan_detector_ignores_established_flows() {
+        let config = PortScanConfig {
+            port_threshold: 5,
+            window_secs: 60,
+        };
+        let detector = PortScanDetector::new(config);
+
+        for port in 1..=10 {
+            let mut event = PacketEventData::zeroed();
+            event.src_ip = u32::from_be_bytes([10, 0, 0, 60]).to_be();
+            event.dst_port = u16::to_be(port);
+            event.protocol = ironpost_ebpf_common::PROTO_TCP;
+            event.ct_state = CT_STATE_ESTABLISHED;
+
+            assert!(detector.detect_packet(&event).unwrap().is_none());
+        }
+    }
+
     #[test]
     fn test_port_scan_detector_duplicate_ports_counted_once() {
         let config = PortScanConfig {
@@ -1056,6 +1518,133 @@ mod tests {
         }
     }
 
+    // =============================================================================
+    // IcmpFloodDetector 테스트
+    // =============================================================================
+
+    fn icmp_event(src: [u8; 4], dst: [u8; 4]) -> PacketEventData {
+        PacketEventData {
+            src_ip: u32::from_be_bytes(src).to_be(),
+            dst_ip: u32::from_be_bytes(dst).to_be(),
+            src_port: 0,
+            dst_port: 0,
+            pkt_len: 84,
+            protocol: PROTO_ICMP,
+            action: ironpost_ebpf_common::ACTION_PASS,
+            tcp_flags: 0,
+            ct_state: 0,
+            sample_rate: 1,
+        }
+    }
+
+    #[test]
+    fn test_icmp_flood_detector_alerts_once_per_window() {
+        let detector = IcmpFloodDetector::new(IcmpFloodConfig {
+            pps_threshold: 10,
+            window_secs: 2,
+            sweep_threshold: 1000,
+        });
+
+        // 2초 윈도우 × 10pps = 20개째 패킷에서 한 번만 알림
+        let alerts: Vec<Alert> = (0..50)
+            .filter_map(|_| {
+                detector
+                    .detect_packet(&icmp_event([10, 0, 0, 7], [192, 168, 1, 1]))
+                    .unwrap()
+            })
+            .collect();
+
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule_name, "icmp_flood");
+        assert_eq!(alerts[0].severity, Severity::High);
+        assert_eq!(
+            alerts[0].source_ip,
+            Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 7)))
+        );
+    }
+
+    #[test]
+    fn test_icmp_flood_detector_weights_sampled_events() {
+        let detector = IcmpFloodDetector::new(IcmpFloodConfig {
+            pps_threshold: 10,
+            window_secs: 2,
+            sweep_threshold: 1000,
+        });
+        let mut event = icmp_event([10, 0, 0, 8], [192, 168, 1, 1]);
+        event.sample_rate = 10;
+
+        // 1/10 샘플링 이벤트 하나가 패킷 10개를 대표하므로 두 번째 이벤트에서 20개 도달
+        assert!(detector.detect_packet(&event).unwrap().is_none());
+        let alert = detector
+            .detect_packet(&event)
+            .unwrap()
+            .expect("flood alert");
+        assert_eq!(alert.rule_name, "icmp_flood");
+    }
+
+    #[test]
+    fn test_icmp_flood_detector_below_threshold_no_alert() {
+        let detector = IcmpFloodDetector::new(IcmpFloodConfig::default());
+
+        for _ in 0..50 {
+            let result = detector
+                .detect_packet(&icmp_event([10, 0, 0, 7], [192, 168, 1, 1]))
+                .unwrap();
+            assert!(result.is_none());
+        }
+    }
+
+    #[test]
+    fn test_icmp_flood_detector_ping_sweep_alerts() {
+        let detector = IcmpFloodDetector::new(IcmpFloodConfig {
+            pps_threshold: 1000,
+            window_secs: 60,
+            sweep_threshold: 16,
+        });
+
+        let alerts: Vec<Alert> = (1..=40)
+            .filter_map(|host| {
+                detector
+                    .detect_packet(&icmp_event([10, 0, 0, 9], [192, 168, 1, host]))
+                    .unwrap()
+            })
+            .collect();
+
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule_name, "ping_sweep");
+        assert_eq!(alerts[0].severity, Severity::Medium);
+    }
+
+    #[test]
+    fn test_icmp_flood_detector_ignores_non_icmp() {
+        let detector = IcmpFloodDetector::new(IcmpFloodConfig {
+            pps_threshold: 1,
+            window_secs: 1,
+            sweep_threshold: 1,
+        });
+
+        let mut event = icmp_event([10, 0, 0, 7], [192, 168, 1, 1]);
+        event.protocol = ironpost_ebpf_common::PROTO_UDP;
+
+        assert!(detector.detect_packet(&event).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_icmp_flood_detector_log_entry_matches_packet_path() {
+        let detector = IcmpFloodDetector::new(IcmpFloodConfig {
+            pps_threshold: 5,
+            window_secs: 1,
+            sweep_threshold: 1000,
+        });
+        let entry = packet_event_to_log_entry(&icmp_event([10, 0, 0, 7], [192, 168, 1, 1]));
+
+        let alerts = (0..5)
+            .filter_map(|_| detector.detect(&entry).unwrap())
+            .count();
+
+        assert_eq!(alerts, 1);
+    }
+
     // =============================================================================
     // PacketDetector 테스트
     // =============================================================================
@@ -1066,10 +1655,16 @@ mod tests {
         let syn_config = SynFloodConfig::default();
         let port_config = PortScanConfig::default();
 
-        let detector = PacketDetector::new(alert_tx, syn_config, port_config);
+        let detector = PacketDetector::new(
+            alert_tx,
+            syn_config,
+            port_config,
+            IcmpFloodConfig::default(),
+        );
 
-        assert_eq!(detector.syn_flood_detector().name(), "syn_flood");
-        assert_eq!(detector.port_scan_detector().name(), "port_scan");
+        assert_eq!(Detector::name(detector.syn_flood_detector()), "syn_flood");
+        assert_eq!(Detector::name(detector.port_scan_detector()), "port_scan");
+        assert_eq!(Detector::name(detector.icmp_flood_detector()), "icmp_flood");
     }
 
     #[test]
@@ -1083,7 +1678,12 @@ mod tests {
         };
         let port_config = PortScanConfig::default();
 
-        let detector = PacketDetector::new(alert_tx, syn_config, port_config);
+        let detector = PacketDetector::new(
+            alert_tx,
+            syn_config,
+            port_config,
+            IcmpFloodConfig::default(),
+        );
 
         // SYN flood 패턴 생성
         for _ in 0..150 {
@@ -1096,7 +1696,8 @@ mod tests {
                 protocol: ironpost_ebpf_common::PROTO_TCP,
                 action: ironpost_ebpf_common::ACTION_PASS,
                 tcp_flags: TCP_SYN,
-                _pad: [0; 1],
+                ct_state: 0,
+                sample_rate: 1,
             };
 
             detector.analyze(&event).unwrap();
@@ -1124,7 +1725,12 @@ mod tests {
             window_secs: 60,
         };
 
-        let detector = PacketDetector::new(alert_tx, syn_config, port_config);
+        let detector = PacketDetector::new(
+            alert_tx,
+            syn_config,
+            port_config,
+            IcmpFloodConfig::default(),
+        );
 
         // 포트 스캔 패턴 생성
         for port in 1..=30 {
@@ -1137,7 +1743,8 @@ mod tests {
                 protocol: ironpost_ebpf_common::PROTO_TCP,
                 action: ironpost_ebpf_common::ACTION_PASS,
                 tcp_flags: TCP_SYN,
-                _pad: [0; 1],
+                ct_state: 0,
+                sample_rate: 1,
             };
 
             detector.analyze(&event).unwrap();
@@ -1155,6 +1762,193 @@ mod tests {
         assert!(alert_found);
     }
 
+    #[test]
+    fn test_packet_detector_requests_auto_block_on_port_scan() {
+        let (alert_tx, _alert_rx) = mpsc::channel(100);
+        let (block_tx, mut block_rx) = mpsc::channel(10);
+
+        let detector = PacketDetector::new(
+            alert_tx,
+            SynFloodConfig::default(),
+            PortScanConfig {
+                port_threshold: 20,
+                window_secs: 60,
+            },
+            IcmpFloodConfig::default(),
+        )
+        .with_auto_block(block_tx);
+        assert!(detector.auto_block_enabled());
+
+        for port in 1..=30 {
+            let event = PacketEventData {
+                src_ip: u32::from_be_bytes([10, 0, 0, 50]).to_be(),
+                dst_ip: u32::from_be_bytes([192, 168, 1, 1]).to_be(),
+                src_port: u16::to_be(12345),
+                dst_port: u16::to_be(port),
+                pkt_len: 64,
+                protocol: ironpost_ebpf_common::PROTO_TCP,
+                action: ironpost_ebpf_common::ACTION_PASS,
+                tcp_flags: TCP_SYN,
+                ct_state: 0,
+                sample_rate: 1,
+            };
+
+            detector.analyze(&event).unwrap();
+        }
+
+        // 알림마다 같은 출발지에 대한 요청 (중복은 엔진이 걸러냄)
+        let request = block_rx.try_recv().expect("auto-block request");
+        assert_eq!(request.src_ip, Ipv4Addr::new(10, 0, 0, 50));
+        assert_eq!(request.rule_name, "port_scan");
+        while let Ok(next) = block_rx.try_recv() {
+            assert_eq!(next, request);
+        }
+    }
+
+    #[test]
+    fn test_packet_detector_analyze_icmp_flood() {
+        let (alert_tx, mut alert_rx) = mpsc::channel(100);
+        let icmp_config = IcmpFloodConfig {
+            pps_threshold: 10,
+            window_secs: 1,
+            sweep_threshold: 1000,
+        };
+
+        let detector = PacketDetector::new(
+            alert_tx,
+            SynFloodConfig::default(),
+            PortScanConfig::default(),
+            icmp_config,
+        );
+
+        for _ in 0..20 {
+            detector
+                .analyze(&icmp_event([10, 0, 0, 50], [192, 168, 1, 1]))
+                .unwrap();
+        }
+
+        let alert_event = alert_rx.try_recv().expect("icmp flood alert");
+        assert_eq!(alert_event.alert.rule_name, "icmp_flood");
+        assert!(alert_rx.try_recv().is_err());
+    }
+
+    /// 목적지 포트 4444로 가는 패킷마다 알림을 내는 테스트용 분석기
+    struct PortWatch {
+        auto_block: bool,
+        cleanups: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl PacketAnalyzer for PortWatch {
+        fn name(&self) -> &str {
+            "port_watch"
+        }
+
+        fn analyze(&self, event: &PacketEventData) -> Result<Option<Alert>, IronpostError> {
+            if u16::from_be(event.dst_port) != 4444 {
+                return Ok(None);
+            }
+            Ok(Some(Alert {
+                id: "port-watch".to_owned(),
+                title: "Connection to port 4444".to_owned(),
+                description: String::new(),
+                severity: Severity::High,
+                rule_name: "port_watch".to_owned(),
+                source_ip: None,
+                target_ip: None,
+                created_at: SystemTime::now(),
+            }))
+        }
+
+        fn cleanup_stale(&self) {
+            self.cleanups
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        fn auto_block(&self) -> bool {
+            self.auto_block
+        }
+    }
+
+    #[test]
+    fn test_packet_detector_runs_registered_analyzers() {
+        let (alert_tx, mut alert_rx) = mpsc::channel(100);
+        let (block_tx, mut block_rx) = mpsc::channel(10);
+        let cleanups = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let detector = PacketDetector::new(
+            alert_tx,
+            SynFloodConfig::default(),
+            PortScanConfig::default(),
+            IcmpFloodConfig::default(),
+        )
+        .with_analyzer(PortWatch {
+            auto_block: true,
+            cleanups: std::sync::Arc::clone(&cleanups),
+        })
+        .with_auto_block(block_tx);
+        assert_eq!(
+            detector.analyzer_names(),
+            vec!["syn_flood", "port_scan", "icmp_flood", "port_watch"]
+        );
+
+        let mut event = icmp_event([10, 0, 0, 50], [192, 168, 1, 1]);
+        event.protocol = ironpost_ebpf_common::PROTO_TCP;
+        event.dst_port = u16::to_be(443);
+        detector.analyze(&event).unwrap();
+        assert!(alert_rx.try_recv().is_err());
+
+        event.dst_port = u16::to_be(4444);
+        detector.analyze(&event).unwrap();
+        let alert_event = alert_rx.try_recv().expect("custom analyzer alert");
+        assert_eq!(alert_event.alert.rule_name, "port_watch");
+        assert_eq!(alert_event.severity, Severity::High);
+
+        let request = block_rx.try_recv().expect("auto-block request");
+        assert_eq!(request.src_ip, Ipv4Addr::new(10, 0, 0, 50));
+        assert_eq!(request.rule_name, "port_watch");
+
+        detector.cleanup_stale();
+        assert_eq!(cleanups.load(std::sync::atomic::Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_packet_detector_appends_process_to_alert() {
+        let (alert_tx, mut alert_rx) = mpsc::channel(100);
+        let detector = PacketDetector::new(
+            alert_tx,
+            SynFloodConfig::default(),
+            PortScanConfig::default(),
+            IcmpFloodConfig {
+                pps_threshold: 10,
+                window_secs: 1,
+                sweep_threshold: 1000,
+            },
+        );
+        let process = ProcessInfo {
+            pid: 4242,
+            comm: "curl".to_owned(),
+            cgroup_id: 7,
+            container_id: None,
+        };
+
+        for _ in 0..20 {
+            detector
+                .analyze_with_process(
+                    &icmp_event([10, 0, 0, 50], [192, 168, 1, 1]),
+                    Some(&process),
+                )
+                .unwrap();
+        }
+
+        let alert_event = alert_rx.try_recv().expect("icmp flood alert");
+        assert!(
+            alert_event
+                .alert
+                .description
+                .ends_with("(process: curl (pid 4242))")
+        );
+    }
+
     #[test]
     fn test_packet_detector_default() {
         let detector = PacketDetector::default();
@@ -1170,6 +1964,7 @@ mod tests {
             alert_tx,
             SynFloodConfig::default(),
             PortScanConfig::default(),
+            IcmpFloodConfig::default(),
         );
 
         // cleanup은 내부 상태를 정리하므로 panic이 발생하지 않아야 함
@@ -1201,7 +1996,8 @@ mod tests {
             protocol: ironpost_ebpf_common::PROTO_TCP,
             action: ironpost_ebpf_common::ACTION_PASS,
             tcp_flags: TCP_SYN,
-            _pad: [0; 1],
+            ct_state: 0,
+            sample_rate: 1,
         };
 
         // 유저스페이스(detector) 방식: from_be 사용
@@ -1236,7 +2032,8 @@ mod tests {
             protocol: ironpost_ebpf_common::PROTO_TCP,
             action: ironpost_ebpf_common::ACTION_PASS,
             tcp_flags: 0,
-            _pad: [0; 1],
+            ct_state: 0,
+            sample_rate: 1,
         };
 
         // 유저스페이스(detector) 방식: from_be 사용
@@ -1269,7 +2066,8 @@ mod tests {
                 protocol: ironpost_ebpf_common::PROTO_TCP,
                 action: ironpost_ebpf_common::ACTION_PASS,
                 tcp_flags: TCP_SYN,
-                _pad: [0; 1],
+                ct_state: 0,
+                sample_rate: 1,
             };
 
             let _ = detector.detect_packet(&event);
@@ -1286,7 +2084,8 @@ mod tests {
             protocol: ironpost_ebpf_common::PROTO_TCP,
             action: ironpost_ebpf_common::ACTION_PASS,
             tcp_flags: TCP_SYN,
-            _pad: [0; 1],
+            ct_state: 0,
+            sample_rate: 1,
         };
 
         if let Ok(Some(alert)) = detector.detect_packet(&event) {
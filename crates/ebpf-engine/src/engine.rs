@@ -16,6 +16,14 @@
 //!                       (anomaly detect)    (poll stats)    (→ log-pipeline)
 //! ```
 //!
+//! `process_correlation`이 켜져 있으면 kprobe(`tcp_connect`, `inet_csk_accept`)를 함께
+//! 어태치하고, 이벤트 수신 태스크가 `SOCK_OWNERS` 맵에서 연결 소유 프로세스를 찾아
+//! 탐지기 알림에 덧붙입니다. `tls_sni`가 켜져 있으면 TC egress 분류기가 캡처한
+//! ClientHello에서 SNI를 추출해 `PacketEvent::sni`가 채워진 이벤트를 함께 보냅니다.
+//!
+//! 통계 폴러는 차단 목록 맵 점유율과 RingBuf 드롭 수도 [`MapUsageReport`]에 기록하며,
+//! 맵이 거의 찼거나 최근 이벤트가 버려졌으면 헬스 체크가 `Degraded`를 반환합니다.
+//!
 //! # 사용 예시
 //! ```ignore
 //! let (mut engine, event_rx) = EbpfEngine::builder()
@@ -27,19 +35,138 @@
 //! // event_rx에서 PacketEvent를 수신하여 다른 모듈로 전달
 //! ```
 
+use std::path::Path;
 use std::sync::Arc;
 
 use tokio::sync::mpsc;
 use tracing::info;
 
+use ironpost_core::config::EbpfConfig;
 use ironpost_core::error::{DetectionError, IronpostError, PipelineError};
-use ironpost_core::event::{MODULE_EBPF, PacketEvent};
+use ironpost_core::event::{AlertEvent, MODULE_EBPF, PacketEvent};
 use ironpost_core::pipeline::{HealthStatus, Pipeline};
-use ironpost_core::plugin::{Plugin, PluginInfo, PluginState, PluginType};
-
-use crate::config::{EngineConfig, FilterRule};
-use crate::detector::PacketDetector;
+use ironpost_core::plugin::{
+    ChannelUsage, Plugin, PluginInfo, PluginState, PluginType, ResourceUsage,
+};
+
+use crate::blocklist::{Blocklist, BlocklistHandle, unix_now};
+use crate::config::{EngineConfig, FilterRule, RuleImport, merge_rules};
+use crate::conntrack::ConnTrackStats;
+use crate::detector::{AutoBlockRequest, PacketAnalyzer, PacketDetector};
+use crate::map_usage::MapUsageReport;
+use crate::preflight::{self, PreflightReport};
+#[cfg(target_os = "linux")]
+use crate::process::ProcessResolver;
+#[cfg(target_os = "linux")]
+use crate::record::{RingEvent, decode_record};
+use crate::state::BlocklistSnapshot;
 use crate::stats::TrafficStats;
+#[cfg(target_os = "linux")]
+use crate::tls::client_hello_sni;
+#[cfg(target_os = "linux")]
+use crate::traffic_alert::TrafficAlerter;
+
+/// eBPF 바이너리 기본 경로 (`cargo xtask build-ebpf` 출력)
+pub const DEFAULT_EBPF_PATH: &str = "target/bpfel-unknown-none/release/ironpost-ebpf";
+
+/// 인터페이스 목록을 읽는 sysfs 디렉토리 (`interface = "all"` 해석, ifindex 조회)
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const SYS_CLASS_NET: &str = "/sys/class/net";
+
+/// 커널 IPv4 라우팅 테이블 (`interface = "auto"` 해석)
+const PROC_NET_ROUTE: &str = "/proc/net/route";
+
+/// 라우트가 활성 상태임을 나타내는 플래그 (`RTF_UP`)
+const RTF_UP: u32 = 0x0001;
+
+/// 차단 목록 만료 처리 주기
+#[cfg(target_os = "linux")]
+const BLOCKLIST_EXPIRE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// 탐지기 → 엔진 자동 차단 요청 채널 용량
+const AUTO_BLOCK_CHANNEL_CAPACITY: usize = 256;
+
+/// 연결 추적 맵 폴링 주기 (맵 전체를 순회하므로 통계 폴링보다 길게 둡니다)
+#[cfg(target_os = "linux")]
+const CONNTRACK_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// 소켓 소유 프로세스를 기록하는 kprobe 프로그램과 어태치할 커널 함수
+#[cfg(target_os = "linux")]
+const SOCKET_PROBES: [(&str, &str); 2] = [
+    ("ironpost_tcp_connect", "tcp_connect"),
+    ("ironpost_inet_csk_accept", "inet_csk_accept"),
+];
+
+/// 나가는 TLS ClientHello를 캡처하는 TC 분류기 프로그램 이름
+#[cfg(target_os = "linux")]
+const TLS_CLASSIFIER: &str = "ironpost_tls_egress";
+
+/// 로드할 eBPF 바이너리 경로를 반환합니다.
+///
+/// `IRONPOST_EBPF_PATH` 환경변수가 설정되어 있으면 그 값을, 아니면
+/// [`DEFAULT_EBPF_PATH`]를 사용합니다.
+pub fn ebpf_object_path() -> String {
+    std::env::var("IRONPOST_EBPF_PATH").unwrap_or_else(|_| DEFAULT_EBPF_PATH.to_owned())
+}
+
+/// eBPF 바이트코드를 가져올 위치
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EbpfObjectSource {
+    /// `embed-ebpf` 기능으로 바이너리에 포함된 오브젝트
+    Embedded,
+    /// 런타임에 파일에서 읽는 오브젝트
+    File(String),
+}
+
+impl std::fmt::Display for EbpfObjectSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Embedded => write!(f, "embedded"),
+            Self::File(path) => write!(f, "{}", path),
+        }
+    }
+}
+
+/// 바이너리에 포함된 eBPF 오브젝트 (`embed-ebpf` 기능, `cargo xtask build --embed-ebpf`)
+///
+/// aya가 ELF를 파싱할 수 있도록 정렬된 상태로 포함합니다.
+#[cfg(all(target_os = "linux", feature = "embed-ebpf"))]
+static EMBEDDED_EBPF: &[u8] = aya::include_bytes_aligned!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../target/bpfel-unknown-none/release/ironpost-ebpf"
+));
+
+/// 바이너리에 포함된 eBPF 오브젝트를 반환합니다 (`embed-ebpf` 기능이 꺼져 있으면 `None`).
+pub fn embedded_ebpf_object() -> Option<&'static [u8]> {
+    #[cfg(all(target_os = "linux", feature = "embed-ebpf"))]
+    {
+        Some(EMBEDDED_EBPF)
+    }
+    #[cfg(not(all(target_os = "linux", feature = "embed-ebpf")))]
+    {
+        None
+    }
+}
+
+/// 로드할 eBPF 오브젝트의 위치를 결정합니다.
+///
+/// `IRONPOST_EBPF_PATH`가 설정되어 있으면 항상 그 파일을 사용하므로 임베드된
+/// 빌드에서도 새 오브젝트로 바꿔 시험할 수 있습니다. 그 외에는 임베드된 오브젝트를,
+/// 임베드되지 않은 빌드에서는 [`DEFAULT_EBPF_PATH`]를 사용합니다.
+pub fn ebpf_object_source() -> EbpfObjectSource {
+    select_object_source(
+        std::env::var("IRONPOST_EBPF_PATH").ok(),
+        embedded_ebpf_object().is_some(),
+    )
+}
+
+fn select_object_source(env_path: Option<String>, embedded: bool) -> EbpfObjectSource {
+    match env_path {
+        Some(path) => EbpfObjectSource::File(path),
+        None if embedded => EbpfObjectSource::Embedded,
+        None => EbpfObjectSource::File(DEFAULT_EBPF_PATH.to_owned()),
+    }
+}
 
 /// eBPF 엔진 — XDP 프로그램 로드/관리 및 이벤트 처리
 ///
@@ -48,7 +175,10 @@ use crate::stats::TrafficStats;
 /// - `event_tx`: PacketEvent를 다른 모듈로 전송하는 채널
 /// - `running`: 현재 실행 상태
 /// - `stats`: 프로토콜별 트래픽 통계
+/// - `conntrack`: 연결 추적 통계 (상태별 흐름 수, 호스트별 cps)
+/// - `blocklist`: 설정 룰 + 런타임 엔트리를 합친 차단 목록
 /// - `detector`: 패킷 기반 위협 탐지기
+/// - `auto_block_rx`: 탐지기가 보낸 자동 차단 요청 (`auto_block_ttl_secs` > 0일 때만)
 ///
 /// # Linux 전용
 /// `aya::Ebpf` 핸들은 Linux에서만 사용 가능합니다.
@@ -59,22 +189,46 @@ pub struct EbpfEngine {
     /// 플러그인 상태
     plugin_state: PluginState,
     config: EngineConfig,
-    /// Linux에서만 사용되는 필드 (spawn_event_reader에서 사용)
-    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    /// 이벤트 전송 채널 (spawn_event_reader에서 사용)
     event_tx: mpsc::Sender<PacketEvent>,
+    /// 트래픽 임계치 알림 전송 채널 (spawn_stats_poller에서 사용)
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    alert_tx: Option<mpsc::Sender<AlertEvent>>,
     running: bool,
     stats: Arc<tokio::sync::Mutex<TrafficStats>>,
+    conntrack: Arc<tokio::sync::Mutex<ConnTrackStats>>,
+    /// 맵 점유율과 RingBuf 드롭 수 (spawn_stats_poller가 갱신)
+    map_usage: Arc<tokio::sync::Mutex<MapUsageReport>>,
+    /// 차단 목록 (엔진 외부와 공유)
+    blocklist: BlocklistHandle,
     /// Linux에서만 사용되는 필드 (spawn_event_reader에서 사용)
     #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
     detector: Arc<PacketDetector>,
+    /// 자동 차단 요청 수신자 (재시작/리로드 후 새 태스크가 이어받도록 공유)
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    auto_block_rx: Option<Arc<tokio::sync::Mutex<mpsc::Receiver<AutoBlockRequest>>>>,
     /// 로드된 eBPF 프로그램 핸들 (Linux 전용)
     #[cfg(target_os = "linux")]
     bpf: Option<aya::Ebpf>,
+    /// XDP를 어태치한 인터페이스별 링크 (Linux 전용)
+    #[cfg(target_os = "linux")]
+    attached: Vec<AttachedInterface>,
     /// 백그라운드 태스크 핸들들
     #[cfg(target_os = "linux")]
     tasks: Vec<tokio::task::JoinHandle<()>>,
 }
 
+/// XDP 프로그램이 어태치된 인터페이스
+#[cfg(target_os = "linux")]
+struct AttachedInterface {
+    /// 인터페이스 이름
+    name: String,
+    /// 커널 인터페이스 인덱스 (`IFACE_STATS` 키)
+    ifindex: u32,
+    /// 개별 detach에 쓰는 링크 ID
+    link: aya::programs::xdp::XdpLinkId,
+}
+
 /// eBPF 엔진 빌더
 ///
 /// 3개 이상의 설정 필드를 가지므로 빌더 패턴을 사용합니다.
@@ -83,8 +237,10 @@ pub struct EbpfEngine {
 pub struct EbpfEngineBuilder {
     config: Option<EngineConfig>,
     event_tx: Option<mpsc::Sender<PacketEvent>>,
+    alert_tx: Option<mpsc::Sender<AlertEvent>>,
     channel_capacity: usize,
     detector: Option<PacketDetector>,
+    analyzers: Vec<Box<dyn PacketAnalyzer>>,
 }
 
 impl EbpfEngineBuilder {
@@ -93,8 +249,10 @@ impl EbpfEngineBuilder {
         Self {
             config: None,
             event_tx: None,
+            alert_tx: None,
             channel_capacity: 1024,
             detector: None,
+            analyzers: Vec::new(),
         }
     }
 
@@ -112,6 +270,14 @@ impl EbpfEngineBuilder {
         self
     }
 
+    /// 트래픽 임계치 알림을 보낼 채널을 지정합니다.
+    ///
+    /// 지정하지 않으면 임계치가 설정되어 있어도 트래픽 알림을 만들지 않습니다.
+    pub fn alert_sender(mut self, tx: mpsc::Sender<AlertEvent>) -> Self {
+        self.alert_tx = Some(tx);
+        self
+    }
+
     /// 내부 이벤트 채널 용량을 지정합니다 (기본: 1024).
     pub fn channel_capacity(mut self, cap: usize) -> Self {
         self.channel_capacity = cap;
@@ -124,6 +290,14 @@ impl EbpfEngineBuilder {
         self
     }
 
+    /// 패킷 탐지기에 사용자 정의 분석기를 추가합니다.
+    ///
+    /// `detector()`로 지정한 탐지기(없으면 기본 탐지기)에 등록 순서대로 추가됩니다.
+    pub fn analyzer(mut self, analyzer: impl PacketAnalyzer + 'static) -> Self {
+        self.analyzers.push(Box::new(analyzer));
+        self
+    }
+
     /// 엔진과 이벤트 수신 채널을 생성합니다.
     ///
     /// # 반환 값
@@ -164,7 +338,22 @@ impl EbpfEngineBuilder {
             (tx, Some(rx))
         };
 
-        let detector = Arc::new(self.detector.unwrap_or_default());
+        // 자동 차단이 켜져 있으면 탐지기 → 차단 목록 요청 채널을 연결
+        let mut detector = self.detector.unwrap_or_default();
+        for analyzer in self.analyzers {
+            detector.register(analyzer);
+        }
+        let mut auto_block_rx = None;
+        if config.auto_block_ttl().is_some() {
+            let (tx, rx) = mpsc::channel(AUTO_BLOCK_CHANNEL_CAPACITY);
+            detector = detector.with_auto_block(tx);
+            auto_block_rx = Some(Arc::new(tokio::sync::Mutex::new(rx)));
+        }
+        let detector = Arc::new(detector);
+
+        // 맵이 연결되기 전이므로 메모리 상태만 갱신됩니다
+        let mut blocklist = Blocklist::new();
+        blocklist.set_rules(&config.rules)?;
 
         let plugin_info = PluginInfo {
             name: MODULE_EBPF.to_owned(),
@@ -178,12 +367,19 @@ impl EbpfEngineBuilder {
             plugin_state: PluginState::Created,
             config,
             event_tx,
+            alert_tx: self.alert_tx,
             running: false,
             stats: Arc::new(tokio::sync::Mutex::new(TrafficStats::new())),
+            conntrack: Arc::new(tokio::sync::Mutex::new(ConnTrackStats::new())),
+            map_usage: Arc::new(tokio::sync::Mutex::new(MapUsageReport::new())),
+            blocklist: Arc::new(tokio::sync::Mutex::new(blocklist)),
             detector,
+            auto_block_rx,
             #[cfg(target_os = "linux")]
             bpf: None,
             #[cfg(target_os = "linux")]
+            attached: Vec::new(),
+            #[cfg(target_os = "linux")]
             tasks: Vec::new(),
         };
 
@@ -202,92 +398,307 @@ impl EbpfEngine {
         Arc::clone(&self.stats)
     }
 
+    /// 현재 연결 추적 통계에 대한 Arc를 반환합니다.
+    pub fn conntrack(&self) -> Arc<tokio::sync::Mutex<ConnTrackStats>> {
+        Arc::clone(&self.conntrack)
+    }
+
+    /// 맵 점유율과 RingBuf 드롭 수 보고서에 대한 Arc를 반환합니다.
+    pub fn map_usage(&self) -> Arc<tokio::sync::Mutex<MapUsageReport>> {
+        Arc::clone(&self.map_usage)
+    }
+
+    /// 차단 목록 핸들을 반환합니다.
+    ///
+    /// 엔진 실행 중 핸들을 통한 변경은 즉시 eBPF 맵에 반영됩니다.
+    pub fn blocklist(&self) -> BlocklistHandle {
+        Arc::clone(&self.blocklist)
+    }
+
     /// 현재 설정을 반환합니다.
     pub fn config(&self) -> &EngineConfig {
         &self.config
     }
 
+    /// 어태치하지 않고 커널 기능과 권한, 인터페이스별 XDP 드라이버 지원을 점검합니다.
+    ///
+    /// `start()`는 XDP 프로그램을 로드하기 전에 이 점검을 수행하고, 실패 항목이 있으면
+    /// [`DetectionError::Preflight`]를 반환합니다.
+    ///
+    /// # Errors
+    ///
+    /// `interface = "all"`인데 인터페이스 목록을 읽을 수 없거나, `interface = "auto"`인데
+    /// 기본 경로를 찾을 수 없으면 에러를 반환합니다.
+    pub fn preflight(&self) -> Result<PreflightReport, IronpostError> {
+        let interfaces = resolve_interfaces(
+            &self.config.base,
+            Path::new(SYS_CLASS_NET),
+            Path::new(PROC_NET_ROUTE),
+        )?;
+        Ok(preflight::run(
+            &self.config.base.xdp_mode,
+            &interfaces,
+            self.config.base.attaches_all_interfaces(),
+        ))
+    }
+
+    /// 상태 파일에서 필터 룰과 런타임 차단 엔트리를 복원합니다.
+    ///
+    /// 저장된 룰은 같은 ID의 룰이 설정에 없을 때만 추가하므로 설정 파일이 우선합니다.
+    /// 만료된 엔트리는 건너뜁니다. 파일을 읽을 수 없으면 경고만 남기고 빈 상태로 시작합니다.
+    async fn restore_state(&mut self) -> Result<(), IronpostError> {
+        let Some(path) = self.config.state_path() else {
+            return Ok(());
+        };
+        let snapshot = match BlocklistSnapshot::load(&path).await {
+            Ok(Some(snapshot)) => snapshot,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    path = %path.display(),
+                    "ignoring unreadable blocklist state file"
+                );
+                return Ok(());
+            }
+        };
+
+        let mut rules = 0usize;
+        for rule in snapshot.rules {
+            if !self.config.rules.iter().any(|r| r.id == rule.id) {
+                self.config.rules.push(rule);
+                rules += 1;
+            }
+        }
+        let mut blocklist = self.blocklist.lock().await;
+        blocklist.set_rules(&self.config.rules)?;
+        let entries = blocklist.restore(snapshot.entries, unix_now())?;
+        // 설정에서 빠진 피드의 룰은 복원하지 않음
+        let feeds = snapshot
+            .feeds
+            .into_iter()
+            .filter(|(name, _)| {
+                self.config
+                    .base
+                    .threat_feeds
+                    .iter()
+                    .any(|feed| &feed.name == name)
+            })
+            .collect();
+        let feed_rules = blocklist.restore_feeds(feeds)?;
+        info!(
+            path = %path.display(),
+            rules,
+            entries,
+            feed_rules,
+            "restored blocklist state"
+        );
+        Ok(())
+    }
+
+    /// 현재 룰과 런타임 차단 엔트리를 상태 파일에 저장합니다 (실패 시 경고만 남김).
+    async fn save_state(&self) {
+        let Some(path) = self.config.state_path() else {
+            return;
+        };
+        let snapshot = self.blocklist.lock().await.snapshot();
+        if let Err(e) = snapshot.save(&path).await {
+            tracing::warn!(
+                error = %e,
+                path = %path.display(),
+                "failed to save blocklist state"
+            );
+        }
+    }
+
+    /// XDP 프로그램이 어태치된 인터페이스 이름을 반환합니다 (실행 중이 아니면 비어 있음).
+    pub fn attached_interfaces(&self) -> Vec<String> {
+        #[cfg(target_os = "linux")]
+        {
+            self.attached
+                .iter()
+                .map(|iface| iface.name.clone())
+                .collect()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Vec::new()
+        }
+    }
+
     /// 필터링 룰을 추가합니다.
     ///
     /// 엔진이 실행 중이면 eBPF HashMap 맵도 동시에 업데이트합니다.
-    pub fn add_rule(&mut self, rule: FilterRule) -> Result<(), IronpostError> {
+    pub async fn add_rule(&mut self, rule: FilterRule) -> Result<(), IronpostError> {
         self.config.add_rule(rule);
-        if self.running {
-            self.sync_blocklist_to_map()?;
-        }
-        Ok(())
+        self.blocklist.lock().await.set_rules(&self.config.rules)
+    }
+
+    /// 필터링 룰 여러 개를 한 번에 가져옵니다.
+    ///
+    /// 모든 룰을 검증하고 ID 중복을 제거한 뒤 설정에 병합하며, 맵 동기화는 마지막에
+    /// 한 번만 수행합니다. 잘못된 룰이 하나라도 있으면 아무것도 반영하지 않습니다.
+    pub async fn import_rules(
+        &mut self,
+        rules: Vec<FilterRule>,
+    ) -> Result<RuleImport, IronpostError> {
+        let (merged, summary) = merge_rules(&self.config.rules, rules)?;
+        self.blocklist.lock().await.set_rules(&merged)?;
+        self.config.rules = merged;
+        info!(
+            added = summary.added,
+            replaced = summary.replaced,
+            duplicates = summary.duplicates,
+            "filter rules imported"
+        );
+        Ok(summary)
     }
 
     /// 필터링 룰을 제거합니다.
     ///
     /// 엔진이 실행 중이면 eBPF HashMap 맵도 동시에 업데이트합니다.
-    pub fn remove_rule(&mut self, rule_id: &str) -> Result<bool, IronpostError> {
+    pub async fn remove_rule(&mut self, rule_id: &str) -> Result<bool, IronpostError> {
         let removed = self.config.remove_rule(rule_id);
-        if removed && self.running {
-            self.sync_blocklist_to_map()?;
+        if removed {
+            self.blocklist.lock().await.set_rules(&self.config.rules)?;
         }
         Ok(removed)
     }
 
-    /// XDP 프로그램을 로드하고 네트워크 인터페이스에 어태치합니다.
+    /// eBPF 오브젝트를 읽어 맵을 만들고 XDP 프로그램을 커널에 로드합니다 (어태치 전).
     ///
-    /// # Linux 전용
-    /// macOS/Windows에서는 `DetectionError::EbpfLoad` 에러를 반환합니다.
+    /// 고정된 맵이 있으면 aya가 이름으로 찾아 다시 사용합니다 (차단 목록/통계 유지).
     #[cfg(target_os = "linux")]
-    fn load_and_attach(&mut self) -> Result<(), IronpostError> {
-        use aya::{Ebpf, programs::Xdp, programs::XdpFlags};
-
-        // eBPF 바이트코드 로드 (cargo xtask build-ebpf로 빌드된 바이너리)
-        // 실제 프로덕션에서는 include_bytes!()로 바이너리를 임베드하지만,
-        // 여기서는 런타임에 파일에서 로드하는 방식을 사용합니다.
-        let ebpf_path = std::env::var("IRONPOST_EBPF_PATH")
-            .unwrap_or_else(|_| "target/bpfel-unknown-none/release/ironpost-ebpf".to_owned());
+    fn load_object(&self) -> Result<aya::Ebpf, IronpostError> {
+        use aya::EbpfLoader;
+        use ironpost_ebpf_common::MAP_EVENTS;
+
+        // eBPF 바이트코드 로드 (임베드된 오브젝트 또는 cargo xtask build-ebpf 출력 파일)
+        let source = ebpf_object_source();
+        let ebpf_data: std::borrow::Cow<'static, [u8]> = match &source {
+            EbpfObjectSource::Embedded => embedded_ebpf_object()
+                .ok_or_else(|| DetectionError::EbpfLoad("eBPF object is not embedded".to_owned()))?
+                .into(),
+            EbpfObjectSource::File(path) => std::fs::read(path)
+                .map_err(|e| {
+                    DetectionError::EbpfLoad(format!(
+                        "failed to read eBPF binary from {}: {}",
+                        path, e
+                    ))
+                })?
+                .into(),
+        };
+        info!(
+            source = %source,
+            ring_buffer_size = self.config.base.ring_buffer_size,
+            "loading eBPF object"
+        );
 
-        let ebpf_data = std::fs::read(&ebpf_path).map_err(|e| {
+        // RingBuf의 max_entries는 바이트 크기이며, 오브젝트의 기본값(256KB)을 설정값으로 덮어씁니다
+        let ring_buffer_size = u32::try_from(self.config.base.ring_buffer_size).map_err(|_| {
             DetectionError::EbpfLoad(format!(
-                "failed to read eBPF binary from {}: {}",
-                ebpf_path, e
+                "ring_buffer_size {} does not fit in a kernel map size",
+                self.config.base.ring_buffer_size
             ))
         })?;
 
-        let mut bpf = Ebpf::load(&ebpf_data)
+        let pin_dir = Path::new(&self.config.base.map_pin_path);
+        prepare_pin_dir(pin_dir)?;
+        let mut bpf = EbpfLoader::new()
+            .map_pin_path(pin_dir)
+            .set_max_entries(MAP_EVENTS, ring_buffer_size)
+            .load(&ebpf_data)
             .map_err(|e| DetectionError::EbpfLoad(format!("failed to load eBPF program: {}", e)))?;
 
-        // XDP 프로그램 획득
-        let program: &mut Xdp = bpf
-            .program_mut("ironpost_xdp")
-            .ok_or_else(|| {
-                DetectionError::EbpfLoad("XDP program 'ironpost_xdp' not found".to_owned())
-            })?
-            .try_into()
-            .map_err(|e| {
-                DetectionError::EbpfLoad(format!("failed to convert to XDP program: {}", e))
-            })?;
-
         // XDP 프로그램 로드
-        program
+        xdp_program(&mut bpf)?
             .load()
             .map_err(|e| DetectionError::EbpfLoad(format!("failed to load XDP program: {}", e)))?;
 
-        // XDP 모드 결정 (SKB/DRV/HW)
-        let xdp_flags = match self.config.base.xdp_mode.as_str() {
+        Ok(bpf)
+    }
+
+    /// 설정의 XDP 모드를 어태치 플래그로 변환합니다 (SKB/DRV/HW).
+    #[cfg(target_os = "linux")]
+    fn xdp_flags(&self) -> aya::programs::XdpFlags {
+        use aya::programs::XdpFlags;
+
+        match self.config.base.xdp_mode.as_str() {
             "native" | "drv" => XdpFlags::DRV_MODE,
             "hw" => XdpFlags::HW_MODE,
             _ => XdpFlags::SKB_MODE,
-        };
+        }
+    }
 
-        // 네트워크 인터페이스에 어태치
-        program
-            .attach(&self.config.base.interface, xdp_flags)
-            .map_err(|e| {
-                DetectionError::EbpfLoad(format!(
-                    "failed to attach XDP to interface '{}': {}",
-                    self.config.base.interface, e
-                ))
-            })?;
+    /// XDP 프로그램을 로드하고 설정된 네트워크 인터페이스마다 어태치합니다.
+    ///
+    /// 명시한 인터페이스 중 하나라도 어태치에 실패하면 전체를 되돌리고 에러를 반환합니다.
+    /// `interface = "all"`이면 XDP를 지원하지 않는 인터페이스는 경고 후 건너뛰고,
+    /// 하나도 어태치하지 못했을 때만 에러를 반환합니다.
+    ///
+    /// # Linux 전용
+    /// macOS/Windows에서는 `DetectionError::EbpfLoad` 에러를 반환합니다.
+    #[cfg(target_os = "linux")]
+    fn load_and_attach(&mut self) -> Result<(), IronpostError> {
+        let mut bpf = self.load_object()?;
+        let program = xdp_program(&mut bpf)?;
+        let xdp_flags = self.xdp_flags();
+
+        // 네트워크 인터페이스마다 어태치 (실패 시 bpf가 drop되면서 이미 만든 링크도 해제됨)
+        let sys_class_net = Path::new(SYS_CLASS_NET);
+        let attach_all = self.config.base.attaches_all_interfaces();
+        let mut attached = Vec::new();
+        for name in resolve_interfaces(&self.config.base, sys_class_net, Path::new(PROC_NET_ROUTE))?
+        {
+            let result = interface_index(sys_class_net, &name)
+                .ok_or_else(|| "interface not found".to_owned())
+                .and_then(|ifindex| {
+                    program
+                        .attach_to_if_index(ifindex, xdp_flags)
+                        .map(|link| (ifindex, link))
+                        .map_err(|e| e.to_string())
+                });
+            match result {
+                Ok((ifindex, link)) => {
+                    info!(interface = name.as_str(), ifindex, "XDP program attached");
+                    attached.push(AttachedInterface {
+                        name,
+                        ifindex,
+                        link,
+                    });
+                }
+                Err(e) if attach_all => {
+                    tracing::warn!(
+                        interface = name.as_str(),
+                        error = %e,
+                        "skipping interface that cannot attach XDP"
+                    );
+                }
+                Err(e) => {
+                    return Err(DetectionError::EbpfLoad(format!(
+                        "failed to attach XDP to interface '{}': {}",
+                        name, e
+                    ))
+                    .into());
+                }
+            }
+        }
+        if attached.is_empty() {
+            return Err(DetectionError::EbpfLoad(
+                "XDP could not be attached to any network interface".to_owned(),
+            )
+            .into());
+        }
+        if self.config.base.process_correlation {
+            attach_socket_probes(&mut bpf);
+        }
+        if self.config.base.tls_sni {
+            attach_tls_classifier(&mut bpf, &attached);
+        }
 
         // eBPF 핸들 저장
         self.bpf = Some(bpf);
+        self.attached = attached;
 
         Ok(())
     }
@@ -298,11 +709,29 @@ impl EbpfEngine {
         Err(DetectionError::EbpfLoad("eBPF is only supported on Linux".to_owned()).into())
     }
 
-    /// XDP 프로그램을 언로드합니다.
+    /// 인터페이스마다 XDP 링크를 해제하고 프로그램을 언로드합니다.
     #[cfg(target_os = "linux")]
     fn detach(&mut self) -> Result<(), IronpostError> {
-        // aya::Ebpf를 drop하면 자동으로 XDP가 detach됩니다
-        if let Some(bpf) = self.bpf.take() {
+        use aya::programs::Xdp;
+
+        let attached = std::mem::take(&mut self.attached);
+        if let Some(mut bpf) = self.bpf.take() {
+            let program = bpf
+                .program_mut("ironpost_xdp")
+                .and_then(|program| <&mut Xdp>::try_from(program).ok());
+            if let Some(program) = program {
+                for iface in attached {
+                    match program.detach(iface.link) {
+                        Ok(()) => info!(interface = iface.name.as_str(), "XDP program detached"),
+                        Err(e) => tracing::warn!(
+                            interface = iface.name.as_str(),
+                            error = %e,
+                            "failed to detach XDP, releasing with program"
+                        ),
+                    }
+                }
+            }
+            // 남은 링크는 aya::Ebpf를 drop할 때 함께 해제됩니다
             drop(bpf);
         }
         Ok(())
@@ -314,107 +743,487 @@ impl EbpfEngine {
         Ok(())
     }
 
-    /// 현재 룰을 eBPF HashMap 맵에 동기화합니다.
-    fn sync_blocklist_to_map(&mut self) -> Result<(), IronpostError> {
+    /// 트래픽을 끊지 않고 XDP 프로그램을 새 eBPF 오브젝트로 교체합니다.
+    ///
+    /// 1. 새 오브젝트 로드 (고정된 맵은 그대로 공유, 레이아웃이 바뀐 맵만 새로 생성)
+    /// 2. 고정되지 않은 상태 맵(RATE_LIMIT, CONNTRACK, IFACE_STATS) 내용을 복사
+    /// 3. 인터페이스마다 기존 XDP 링크의 프로그램을 원자적으로 교체
+    /// 4. 백그라운드 태스크를 새 맵으로 다시 스폰하고 차단 목록을 재동기화
+    ///
+    /// 새 오브젝트를 로드하지 못하면 기존 프로그램이 그대로 동작합니다. 복사와 교체 사이에
+    /// 기존 프로그램이 갱신한 카운터와, 기존 RingBuf에 남아 있던 이벤트는 유실될 수 있습니다.
+    ///
+    /// # Errors
+    ///
+    /// 엔진이 실행 중이 아니면 `PipelineError::NotRunning`을, 로드나 교체에 실패하면
+    /// `DetectionError`를 반환합니다. 교체 후 초기화에 실패하면 `start()`와 같이 롤백하여
+    /// 엔진이 정지됩니다.
+    #[cfg(target_os = "linux")]
+    pub async fn reload(&mut self) -> Result<(), IronpostError> {
+        if !self.running {
+            return Err(PipelineError::NotRunning.into());
+        }
+
+        info!("reloading eBPF program");
+        // 여기까지 실패하면 기존 프로그램과 맵은 건드리지 않은 상태입니다
+        let mut new_bpf = self.load_object()?;
+        let xdp_flags = self.xdp_flags();
+        let Some(old_bpf) = self.bpf.as_mut() else {
+            return Err(PipelineError::NotRunning.into());
+        };
+        let old_program = xdp_program(old_bpf)?;
+        copy_unpinned_maps(program_maps(old_program), &mut new_bpf);
+
+        // 링크마다 프로그램만 바꿔 끼우므로 교체 사이에 통과하는 패킷이 없습니다
+        let new_program = xdp_program(&mut new_bpf)?;
+        let mut attached = Vec::with_capacity(self.attached.len());
+        for iface in std::mem::take(&mut self.attached) {
+            let result = old_program
+                .take_link(iface.link)
+                .and_then(|link| new_program.attach_to_link(link))
+                .or_else(|e| {
+                    tracing::warn!(
+                        interface = iface.name.as_str(),
+                        error = %e,
+                        "failed to replace XDP link, attaching again"
+                    );
+                    new_program.attach_to_if_index(iface.ifindex, xdp_flags)
+                });
+            match result {
+                Ok(link) => {
+                    info!(interface = iface.name.as_str(), "XDP program replaced");
+                    attached.push(AttachedInterface { link, ..iface });
+                }
+                Err(e) => tracing::error!(
+                    interface = iface.name.as_str(),
+                    error = %e,
+                    "failed to attach reloaded XDP program"
+                ),
+            }
+        }
+
+        // 기존 kprobe/TC 분류기는 기존 오브젝트와 함께 해제되므로 새 오브젝트에 다시 어태치
+        if self.config.base.process_correlation {
+            attach_socket_probes(&mut new_bpf);
+        }
+        if self.config.base.tls_sni {
+            attach_tls_classifier(&mut new_bpf, &attached);
+        }
+
+        // 기존 태스크가 쥐고 있던 맵 핸들을 놓고 새 오브젝트 기준으로 다시 연결
+        for task in self.tasks.drain(..) {
+            task.abort();
+        }
+        self.blocklist.lock().await.detach_maps();
+        // 기존 aya::Ebpf를 drop하면 링크가 빠진 기존 프로그램과 맵 핸들이 해제됩니다
+        self.bpf = Some(new_bpf);
+        self.attached = attached;
+
+        let result = if self.attached.is_empty() {
+            Err(DetectionError::EbpfLoad(
+                "reloaded XDP program could not be attached to any network interface".to_owned(),
+            )
+            .into())
+        } else {
+            self.initialize_post_attach().await
+        };
+        if let Err(e) = result {
+            tracing::error!(error = %e, "failed to reload eBPF program, stopping engine");
+            for task in self.tasks.drain(..) {
+                task.abort();
+            }
+            self.blocklist.lock().await.detach_maps();
+            if let Err(detach_err) = self.detach() {
+                tracing::error!(error = %detach_err, "failed to detach XDP during rollback");
+            }
+            self.running = false;
+            return Err(e);
+        }
+
+        info!(interfaces = self.attached.len(), "eBPF program reloaded");
+        Ok(())
+    }
+
+    /// XDP 프로그램을 교체합니다 (비-Linux 스텁).
+    #[cfg(not(target_os = "linux"))]
+    pub async fn reload(&mut self) -> Result<(), IronpostError> {
+        if !self.running {
+            return Err(PipelineError::NotRunning.into());
+        }
+        Err(DetectionError::EbpfLoad("eBPF is only supported on Linux".to_owned()).into())
+    }
+
+    /// 차단/허용 목록 맵(BLOCKLIST, BLOCKLIST_CIDR, PORT_RULES, ALLOWLIST)을 연결하고
+    /// 현재 상태를 동기화합니다.
+    async fn attach_blocklist_map(&mut self) -> Result<(), IronpostError> {
         #[cfg(target_os = "linux")]
         {
-            use aya::maps::HashMap as AyaHashMap;
+            use aya::maps::{HashMap as AyaHashMap, LpmTrie};
             use ironpost_ebpf_common::{
-                ACTION_DROP, ACTION_MONITOR, BlocklistValue, MAP_BLOCKLIST,
+                BlocklistValue, MAP_ALLOWLIST, MAP_BLOCKLIST, MAP_BLOCKLIST_CIDR, MAP_PORT_RULES,
+                PortRuleKey,
             };
-            use std::net::IpAddr;
+
+            use crate::blocklist::BlocklistMaps;
 
             // eBPF가 로드되지 않았으면 스킵
             let Some(ref mut bpf) = self.bpf else {
                 return Ok(());
             };
 
-            // BLOCKLIST 맵 획득
-            let mut map: AyaHashMap<_, u32, BlocklistValue> =
-                AyaHashMap::try_from(bpf.map_mut(MAP_BLOCKLIST).ok_or_else(|| {
-                    DetectionError::EbpfMap(format!("map '{}' not found", MAP_BLOCKLIST))
-                })?)
-                .map_err(|e| {
+            let mut take = |name: &str| {
+                bpf.take_map(name)
+                    .ok_or_else(|| DetectionError::EbpfMap(format!("map '{}' not found", name)))
+            };
+
+            // 맵 소유권 획득 — 엔진 외부에서도 갱신 가능
+            let hosts: AyaHashMap<_, u32, BlocklistValue> =
+                AyaHashMap::try_from(take(MAP_BLOCKLIST)?).map_err(|e| {
                     DetectionError::EbpfMap(format!("failed to get blocklist map: {}", e))
                 })?;
+            // /32보다 넓은 CIDR 엔트리
+            let prefixes: LpmTrie<_, u32, BlocklistValue> =
+                LpmTrie::try_from(take(MAP_BLOCKLIST_CIDR)?).map_err(|e| {
+                    DetectionError::EbpfMap(format!("failed to get blocklist CIDR map: {}", e))
+                })?;
+            // dst_port가 있는 설정 룰
+            let ports: AyaHashMap<_, PortRuleKey, BlocklistValue> =
+                AyaHashMap::try_from(take(MAP_PORT_RULES)?).map_err(|e| {
+                    DetectionError::EbpfMap(format!("failed to get port rule map: {}", e))
+                })?;
+            // Allow 룰/엔트리 (차단 목록보다 먼저 조회)
+            let allowed: LpmTrie<_, u32, BlocklistValue> = LpmTrie::try_from(take(MAP_ALLOWLIST)?)
+                .map_err(|e| {
+                    DetectionError::EbpfMap(format!("failed to get allowlist map: {}", e))
+                })?;
 
-            // 현재 룰의 IP 집합 수집
-            let current_ips: std::collections::HashSet<u32> = self
-                .config
-                .ip_rules()
-                .filter_map(|r| {
-                    if let Some(IpAddr::V4(ipv4)) = r.src_ip {
-                        Some(u32::from_be_bytes(ipv4.octets()))
-                    } else {
-                        None
+            self.blocklist.lock().await.attach_maps(BlocklistMaps {
+                hosts,
+                prefixes,
+                ports,
+                allowed,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// 레이트 리밋 파라미터를 RATE_LIMIT_CONFIG 맵에 기록합니다.
+    ///
+    /// 비활성화 상태에서도 0을 기록하여, 커널 프로그램이 레이트 리밋을 건너뛰도록 합니다.
+    fn configure_rate_limit(&mut self) -> Result<(), IronpostError> {
+        let rate_limit = self.config.rate_limit();
+
+        #[cfg(target_os = "linux")]
+        {
+            use aya::maps::Array;
+            use ironpost_ebpf_common::{MAP_RATE_LIMIT_CONFIG, RateLimitParams};
+
+            // eBPF가 로드되지 않았으면 스킵
+            let Some(ref mut bpf) = self.bpf else {
+                return Ok(());
+            };
+
+            let map = bpf.map_mut(MAP_RATE_LIMIT_CONFIG).ok_or_else(|| {
+                DetectionError::EbpfMap(format!("map '{}' not found", MAP_RATE_LIMIT_CONFIG))
+            })?;
+            let mut params: Array<_, RateLimitParams> = Array::try_from(map).map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to get rate limit config map: {}", e))
+            })?;
+            let value = RateLimitParams {
+                pps: rate_limit.map_or(0, |r| r.packets_per_sec),
+                burst: rate_limit.map_or(0, |r| r.burst),
+            };
+            params.set(0, value, 0).map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to write rate limit config: {}", e))
+            })?;
+        }
+
+        if let Some(rate_limit) = rate_limit {
+            info!(
+                packets_per_sec = rate_limit.packets_per_sec,
+                burst = rate_limit.burst,
+                "per-source rate limiting enabled"
+            );
+        }
+        Ok(())
+    }
+
+    /// 모니터링 이벤트 샘플링 비율을 SAMPLING_CONFIG 맵에 기록합니다.
+    ///
+    /// 비율이 1이면 커널 프로그램이 모든 모니터링 패킷을 이벤트로 전송합니다.
+    fn configure_sampling(&mut self) -> Result<(), IronpostError> {
+        let monitor_rate = self.config.base.monitor_sample_rate.max(1);
+
+        #[cfg(target_os = "linux")]
+        {
+            use aya::maps::Array;
+            use ironpost_ebpf_common::{MAP_SAMPLING_CONFIG, SamplingParams};
+
+            // eBPF가 로드되지 않았으면 스킵
+            let Some(ref mut bpf) = self.bpf else {
+                return Ok(());
+            };
+
+            let map = bpf.map_mut(MAP_SAMPLING_CONFIG).ok_or_else(|| {
+                DetectionError::EbpfMap(format!("map '{}' not found", MAP_SAMPLING_CONFIG))
+            })?;
+            let mut params: Array<_, SamplingParams> = Array::try_from(map).map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to get sampling config map: {}", e))
+            })?;
+            let value = SamplingParams {
+                monitor_rate,
+                _pad: 0,
+            };
+            params.set(0, value, 0).map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to write sampling config: {}", e))
+            })?;
+        }
+
+        if monitor_rate > 1 {
+            info!(
+                monitor_sample_rate = monitor_rate,
+                "sampling monitored packet events"
+            );
+        }
+        Ok(())
+    }
+
+    /// 관찰 전용(드라이런) 플래그를 DRY_RUN_CONFIG 맵에 기록합니다.
+    ///
+    /// 비활성화 상태에서도 0을 기록하여, 재사용된 맵에 남은 이전 값이 적용되지 않도록 합니다.
+    fn configure_dry_run(&mut self) -> Result<(), IronpostError> {
+        let dry_run = self.config.dry_run;
+
+        #[cfg(target_os = "linux")]
+        {
+            use aya::maps::Array;
+            use ironpost_ebpf_common::MAP_DRY_RUN_CONFIG;
+
+            // eBPF가 로드되지 않았으면 스킵
+            let Some(ref mut bpf) = self.bpf else {
+                return Ok(());
+            };
+
+            let map = bpf.map_mut(MAP_DRY_RUN_CONFIG).ok_or_else(|| {
+                DetectionError::EbpfMap(format!("map '{}' not found", MAP_DRY_RUN_CONFIG))
+            })?;
+            let mut flag: Array<_, u32> = Array::try_from(map).map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to get dry-run config map: {}", e))
+            })?;
+            flag.set(0, u32::from(dry_run), 0).map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to write dry-run config: {}", e))
+            })?;
+        }
+
+        if dry_run {
+            info!("observe-only mode: drop decisions are counted as would-drop and passed");
+        }
+        Ok(())
+    }
+
+    /// 만료된 차단 목록 엔트리를 주기적으로 제거하는 백그라운드 태스크를 스폰합니다.
+    ///
+    /// 상태 파일이 설정되어 있으면 마지막 저장 이후 차단 목록이 바뀐 경우에만 저장합니다.
+    fn spawn_blocklist_reaper(&mut self) -> Result<(), IronpostError> {
+        #[cfg(target_os = "linux")]
+        {
+            let blocklist = Arc::clone(&self.blocklist);
+            let state_path = self.config.state_path();
+
+            let handle = tokio::task::spawn(async move {
+                let mut interval = tokio::time::interval(BLOCKLIST_EXPIRE_INTERVAL);
+                let mut saved_generation = None;
+
+                loop {
+                    interval.tick().await;
+
+                    let pending = {
+                        let mut blocklist = blocklist.lock().await;
+                        if let Err(e) = blocklist.expire(unix_now()) {
+                            tracing::warn!(error = %e, "failed to expire blocklist entries");
+                        }
+                        let generation = blocklist.generation();
+                        (state_path.is_some() && saved_generation != Some(generation))
+                            .then(|| (generation, blocklist.snapshot()))
+                    };
+
+                    let (Some(path), Some((generation, snapshot))) = (&state_path, pending) else {
+                        continue;
+                    };
+                    match snapshot.save(path).await {
+                        Ok(()) => saved_generation = Some(generation),
+                        Err(e) => tracing::warn!(
+                            error = %e,
+                            path = %path.display(),
+                            "failed to save blocklist state"
+                        ),
                     }
-                })
-                .collect();
+                }
+            });
+
+            self.tasks.push(handle);
+        }
 
-            // 기존 맵의 키를 수집하여 삭제 대상 확인
-            let existing_keys: Vec<u32> = map.keys().filter_map(|k| k.ok()).collect();
+        Ok(())
+    }
 
-            // 현재 룰에 없는 키 삭제
-            for key in existing_keys {
-                if !current_ips.contains(&key) {
-                    if let Err(e) = map.remove(&key) {
-                        tracing::warn!(ip = u32::from_be(key), error = %e, "failed to remove stale blocklist entry");
-                    } else {
-                        tracing::debug!(ip = u32::from_be(key), "removed stale blocklist entry");
+    /// 탐지기의 자동 차단 요청을 받아 TTL이 있는 차단 목록 엔트리로 반영하는
+    /// 백그라운드 태스크를 스폰합니다 (`auto_block_ttl_secs`가 0이면 스폰하지 않음).
+    fn spawn_auto_blocker(&mut self) -> Result<(), IronpostError> {
+        #[cfg(target_os = "linux")]
+        {
+            let (Some(rx), Some(ttl)) = (self.auto_block_rx.clone(), self.config.auto_block_ttl())
+            else {
+                return Ok(());
+            };
+            let blocklist = Arc::clone(&self.blocklist);
+
+            let handle = tokio::task::spawn(async move {
+                let mut rx = rx.lock().await;
+                while let Some(request) = rx.recv().await {
+                    let result =
+                        blocklist
+                            .lock()
+                            .await
+                            .auto_block(request.src_ip, ttl, &request.rule_name);
+                    match result {
+                        Ok(true) => info!(
+                            src_ip = %request.src_ip,
+                            rule = request.rule_name.as_str(),
+                            ttl_secs = ttl.as_secs(),
+                            "source auto-blocked"
+                        ),
+                        Ok(false) => {}
+                        Err(e) => tracing::warn!(
+                            error = %e,
+                            src_ip = %request.src_ip,
+                            "failed to auto-block source"
+                        ),
                     }
                 }
-            }
+            });
 
-            // 모든 IP 룰을 맵에 추가
-            for rule in self.config.ip_rules() {
-                let Some(src_ip) = rule.src_ip else {
-                    continue;
-                };
-
-                // IP 주소를 u32 네트워크 바이트 오더로 변환
-                let ip_u32 = match src_ip {
-                    IpAddr::V4(ipv4) => u32::from_be_bytes(ipv4.octets()),
-                    IpAddr::V6(_) => {
-                        // IPv6는 현재 지원하지 않음 (커널 맵이 u32 키)
-                        tracing::warn!(
-                            rule_id = rule.id.as_str(),
-                            "IPv6 addresses are not supported in blocklist, skipping"
-                        );
-                        continue;
+            self.tasks.push(handle);
+        }
+
+        Ok(())
+    }
+
+    /// 위협 인텔리전스 피드마다 `refresh_secs` 주기로 내려받아 차단 목록에 반영하는
+    /// 백그라운드 태스크를 스폰합니다 (실패하면 이전 룰을 유지하고 다음 주기에 재시도).
+    fn spawn_feed_syncers(&mut self) -> Result<(), IronpostError> {
+        #[cfg(target_os = "linux")]
+        {
+            use crate::feed::{self, FeedFormat};
+            use std::time::Duration;
+
+            for feed_config in self.config.base.threat_feeds.clone() {
+                let format = feed_config.format.parse::<FeedFormat>()?;
+                let client = feed::http_client(feed_config.timeout_secs)?;
+                let blocklist = Arc::clone(&self.blocklist);
+
+                let handle = tokio::task::spawn(async move {
+                    let name = feed_config.name.as_str();
+                    let mut interval =
+                        tokio::time::interval(Duration::from_secs(feed_config.refresh_secs));
+
+                    loop {
+                        interval.tick().await;
+
+                        let parsed = match feed::fetch_feed(&client, &feed_config.url).await {
+                            Ok(content) => feed::parse_feed(&content, format),
+                            Err(e) => Err(e),
+                        };
+                        let parsed = match parsed {
+                            Ok(parsed) => parsed,
+                            Err(e) => {
+                                tracing::warn!(
+                                    error = %e,
+                                    feed = name,
+                                    "failed to refresh threat feed, keeping previous entries"
+                                );
+                                continue;
+                            }
+                        };
+
+                        let rules = feed::feed_rules(name, &parsed.cidrs);
+                        match blocklist.lock().await.sync_feed(name, rules) {
+                            Ok(sync) => info!(
+                                feed = name,
+                                added = sync.added,
+                                removed = sync.removed,
+                                unchanged = sync.unchanged,
+                                skipped = parsed.skipped,
+                                "threat feed synced"
+                            ),
+                            Err(e) => tracing::warn!(
+                                error = %e,
+                                feed = name,
+                                "failed to apply threat feed, keeping previous entries"
+                            ),
+                        }
                     }
-                };
-
-                // RuleAction을 BlocklistValue로 변환
-                let action_code = match rule.action {
-                    crate::config::RuleAction::Block => ACTION_DROP,
-                    crate::config::RuleAction::Monitor => ACTION_MONITOR,
-                };
-
-                let value = BlocklistValue {
-                    action: action_code,
-                    _pad: [0; 3],
-                };
-
-                // 맵에 삽입
-                map.insert(ip_u32, value, 0).map_err(|e| {
-                    DetectionError::EbpfMap(format!(
-                        "failed to insert rule '{}' into blocklist: {}",
-                        rule.id, e
-                    ))
-                })?;
+                });
 
-                tracing::debug!(
-                    rule_id = rule.id.as_str(),
-                    src_ip = %src_ip,
-                    action = ?rule.action,
-                    "synced rule to eBPF blocklist"
-                );
+                self.tasks.push(handle);
             }
         }
 
-        #[cfg(not(target_os = "linux"))]
+        Ok(())
+    }
+
+    /// CONNTRACK 맵을 주기적으로 읽어 연결 추적 통계를 갱신하는 백그라운드 태스크를 스폰합니다.
+    fn spawn_conntrack_poller(&mut self) -> Result<(), IronpostError> {
+        #[cfg(target_os = "linux")]
         {
-            // 비-Linux 플랫폼에서는 no-op
+            use crate::conntrack::RawFlow;
+            use aya::maps::HashMap as AyaHashMap;
+            use ironpost_ebpf_common::{FlowKey, FlowState, MAP_CONNTRACK};
+            use std::net::Ipv4Addr;
+
+            // eBPF가 로드되지 않았으면 스킵
+            let Some(ref mut bpf) = self.bpf else {
+                return Ok(());
+            };
+
+            let conntrack_map = AyaHashMap::<_, FlowKey, FlowState>::try_from(
+                bpf.take_map(MAP_CONNTRACK).ok_or_else(|| {
+                    DetectionError::EbpfMap(format!("map '{}' not found", MAP_CONNTRACK))
+                })?,
+            )
+            .map_err(|e| DetectionError::EbpfMap(format!("failed to get conntrack map: {}", e)))?;
+
+            let conntrack = Arc::clone(&self.conntrack);
+
+            let handle = tokio::task::spawn(async move {
+                let mut interval = tokio::time::interval(CONNTRACK_POLL_INTERVAL);
+
+                loop {
+                    interval.tick().await;
+
+                    // 순회 중 커널이 LRU 엔트리를 교체하면 해당 엔트리만 건너뜁니다
+                    let flows: Vec<RawFlow> = conntrack_map
+                        .iter()
+                        .filter_map(Result::ok)
+                        .map(|(key, value)| RawFlow {
+                            src_ip: Ipv4Addr::from(key.src_ip),
+                            dst_ip: Ipv4Addr::from(key.dst_ip),
+                            src_port: key.src_port,
+                            dst_port: key.dst_port,
+                            protocol: key.protocol,
+                            state: value.state,
+                            first_seen_ns: value.first_seen_ns,
+                            last_seen_ns: value.last_seen_ns,
+                            packets: value.packets,
+                        })
+                        .collect();
+
+                    conntrack.lock().await.update(&flows);
+                }
+            });
+
+            self.tasks.push(handle);
         }
 
         Ok(())
@@ -424,14 +1233,17 @@ impl EbpfEngine {
     ///
     /// 수신된 PacketEventData를 PacketEvent로 변환하여 event_tx로 전송합니다.
     /// 동시에 PacketDetector에 전달하여 이상 탐지를 수행합니다.
+    /// RingBuf fd의 epoll 준비 알림(`AsyncFd`)으로 깨어나므로 유휴 시 폴링하지 않습니다.
     fn spawn_event_reader(&mut self) -> Result<(), IronpostError> {
         #[cfg(target_os = "linux")]
         {
-            use aya::maps::RingBuf;
+            use aya::maps::{HashMap as AyaHashMap, RingBuf};
             use bytes::Bytes;
             use ironpost_core::types::PacketInfo;
-            use ironpost_ebpf_common::{MAP_EVENTS, PacketEventData};
+            use ironpost_ebpf_common::{FlowKey, MAP_EVENTS, MAP_SOCK_OWNERS, SockOwner};
             use std::net::IpAddr;
+            use tokio::io::Interest;
+            use tokio::io::unix::AsyncFd;
 
             // eBPF가 로드되지 않았으면 스킵
             let Some(ref mut bpf) = self.bpf else {
@@ -444,91 +1256,185 @@ impl EbpfEngine {
             })?)
             .map_err(|e| DetectionError::EbpfMap(format!("failed to get events ringbuf: {}", e)))?;
 
+            // RingBuf fd를 epoll에 등록: 커널이 이벤트를 기록하면 깨어나고, 유휴 시에는 wakeup 없음
+            let mut ringbuf = AsyncFd::with_interest(ringbuf, Interest::READABLE).map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to register events ringbuf: {}", e))
+            })?;
+
+            // 프로세스 연관이 켜져 있으면 kprobe가 채우는 소켓 소유자 맵도 가져옴
+            let owners = if self.config.base.process_correlation {
+                bpf.take_map(MAP_SOCK_OWNERS)
+                    .and_then(|map| AyaHashMap::<_, FlowKey, SockOwner>::try_from(map).ok())
+            } else {
+                None
+            };
+            let mut resolver = ProcessResolver::default();
+
             let event_tx = self.event_tx.clone();
             let detector = Arc::clone(&self.detector);
 
             // 백그라운드 태스크 스폰
             let handle = tokio::task::spawn(async move {
-                let mut ringbuf = ringbuf;
                 tracing::info!("eBPF event reader task started");
 
-                // Exponential backoff: idle일 때 CPU 사용 최소화
-                // 초기 1ms → 최대 100ms (초당 ~10회 wakeup, 기존 100회에서 90% 감소)
-                let mut backoff_ms: u64 = 1;
-                const MAX_BACKOFF_MS: u64 = 100;
+                'reader: loop {
+                    let mut guard = match ringbuf.readable_mut().await {
+                        Ok(guard) => guard,
+                        Err(e) => {
+                            tracing::error!(error = %e, "failed to wait for events ringbuf");
+                            break;
+                        }
+                    };
 
-                loop {
-                    // RingBuf에서 이벤트 폴링
-                    match ringbuf.next() {
-                        Some(data) => {
-                            // 이벤트 수신 시 backoff 리셋
-                            backoff_ms = 1;
-
-                            // PacketEventData 역직렬화
-                            if data.len() < std::mem::size_of::<PacketEventData>() {
-                                tracing::warn!(
-                                    size = data.len(),
-                                    expected = std::mem::size_of::<PacketEventData>(),
-                                    "received undersized event, skipping"
-                                );
-                                continue;
-                            }
+                    // 준비 알림 이후 쌓인 이벤트를 모두 소비한 뒤 다시 대기
+                    while let Some(record) = next_record(guard.get_inner_mut()) {
+                        let RingEvent::Packet(event_data) = record else {
+                            tracing::warn!(
+                                kind = record.kind(),
+                                "unexpected record in events ringbuf, skipping"
+                            );
+                            continue;
+                        };
+                        // PacketInfo로 변환
+                        let src_ip = IpAddr::V4(std::net::Ipv4Addr::from(event_data.src_ip));
+                        let dst_ip = IpAddr::V4(std::net::Ipv4Addr::from(event_data.dst_ip));
+
+                        let packet_info = PacketInfo {
+                            src_ip,
+                            dst_ip,
+                            src_port: event_data.src_port,
+                            dst_port: event_data.dst_port,
+                            protocol: event_data.protocol,
+                            size: usize::try_from(event_data.pkt_len).unwrap_or(usize::MAX),
+                            timestamp: std::time::SystemTime::now(),
+                        };
+
+                        // PacketEvent 생성
+                        let packet_event = PacketEvent::new(packet_info, Bytes::new());
+
+                        // 연결 소유 프로세스와 함께 탐지기로 전달
+                        let process = owners
+                            .as_ref()
+                            .and_then(|owners| lookup_sock_owner(owners, &event_data))
+                            .map(|owner| resolver.resolve(&owner));
+                        if let Err(e) = detector.analyze_with_process(&event_data, process.as_ref())
+                        {
+                            tracing::error!(error = %e, "failed to analyze packet event");
+                        }
 
-                            // SAFETY: PacketEventData는 #[repr(C)]이며 크기 검증을 완료했습니다.
-                            // RingBuf에서 반환된 데이터의 정렬이 보장되지 않을 수 있으므로
-                            // read_unaligned를 사용하여 UB를 방지합니다.
-                            let event_data = unsafe {
-                                std::ptr::read_unaligned(data.as_ptr() as *const PacketEventData)
-                            };
-
-                            // PacketInfo로 변환
-                            let src_ip = IpAddr::V4(std::net::Ipv4Addr::from(event_data.src_ip));
-                            let dst_ip = IpAddr::V4(std::net::Ipv4Addr::from(event_data.dst_ip));
-
-                            let packet_info = PacketInfo {
-                                src_ip,
-                                dst_ip,
-                                src_port: event_data.src_port,
-                                dst_port: event_data.dst_port,
-                                protocol: event_data.protocol,
-                                size: usize::try_from(event_data.pkt_len).unwrap_or(usize::MAX),
-                                timestamp: std::time::SystemTime::now(),
-                            };
-
-                            // PacketEvent 생성
-                            let packet_event = PacketEvent::new(packet_info, Bytes::new());
-
-                            // 탐지기로 전달
-                            if let Err(e) = detector.analyze(&event_data) {
-                                tracing::error!(error = %e, "failed to analyze packet event");
-                            }
+                        // 이벤트 채널로 전송
+                        if let Err(e) = event_tx.send(packet_event).await {
+                            tracing::error!(error = %e, "failed to send packet event, channel closed");
+                            break 'reader;
+                        }
+                    }
+                    guard.clear_ready();
+                }
+
+                tracing::info!("eBPF event reader task stopped");
+            });
+
+            self.tasks.push(handle);
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            // 비-Linux 플랫폼에서는 no-op
+        }
+
+        Ok(())
+    }
+
+    /// TLS ClientHello 링 버퍼에서 SNI를 추출해 PacketEvent로 보내는 태스크를 스폰합니다.
+    ///
+    /// `tls_sni`가 꺼져 있으면 아무것도 하지 않습니다. SNI를 찾지 못한 ClientHello는
+    /// 이벤트로 만들지 않습니다.
+    fn spawn_tls_reader(&mut self) -> Result<(), IronpostError> {
+        #[cfg(target_os = "linux")]
+        {
+            use aya::maps::RingBuf;
+            use bytes::Bytes;
+            use ironpost_core::types::PacketInfo;
+            use ironpost_ebpf_common::{MAP_TLS_HELLOS, PROTO_TCP, TLS_HELLO_CAPTURE_LEN};
+            use std::net::{IpAddr, Ipv4Addr};
+            use tokio::io::Interest;
+            use tokio::io::unix::AsyncFd;
+
+            if !self.config.base.tls_sni {
+                return Ok(());
+            }
+            let Some(ref mut bpf) = self.bpf else {
+                return Ok(());
+            };
+
+            let ringbuf = RingBuf::try_from(bpf.take_map(MAP_TLS_HELLOS).ok_or_else(|| {
+                DetectionError::EbpfMap(format!("map '{}' not found", MAP_TLS_HELLOS))
+            })?)
+            .map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to get TLS hello ringbuf: {}", e))
+            })?;
+            let mut ringbuf = AsyncFd::with_interest(ringbuf, Interest::READABLE).map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to register TLS hello ringbuf: {}", e))
+            })?;
+
+            let event_tx = self.event_tx.clone();
 
-                            // 이벤트 채널로 전송
-                            if let Err(e) = event_tx.send(packet_event).await {
-                                tracing::error!(error = %e, "failed to send packet event, channel closed");
-                                break;
-                            }
+            let handle = tokio::task::spawn(async move {
+                tracing::info!("TLS ClientHello reader task started");
+
+                'reader: loop {
+                    let mut guard = match ringbuf.readable_mut().await {
+                        Ok(guard) => guard,
+                        Err(e) => {
+                            tracing::error!(error = %e, "failed to wait for TLS hello ringbuf");
+                            break;
                         }
-                        None => {
-                            // RingBuf가 비어있으면 지수적 백오프로 대기
-                            // idle 시 CPU 사이클 낭비 방지, 부하 증가 시 빠른 반응
-                            tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
-                            backoff_ms = (backoff_ms * 2).min(MAX_BACKOFF_MS);
+                    };
+
+                    while let Some(record) = next_record(guard.get_inner_mut()) {
+                        let RingEvent::TlsHello(hello) = record else {
+                            tracing::warn!(
+                                kind = record.kind(),
+                                "unexpected record in TLS hello ringbuf, skipping"
+                            );
+                            continue;
+                        };
+                        let len = usize::try_from(hello.captured_len)
+                            .unwrap_or(usize::MAX)
+                            .min(TLS_HELLO_CAPTURE_LEN);
+                        let payload = &hello.payload[..len];
+                        let Some(sni) = client_hello_sni(payload) else {
+                            tracing::debug!(captured = len, "no SNI in captured ClientHello");
+                            continue;
+                        };
+
+                        let packet_info = PacketInfo {
+                            src_ip: IpAddr::V4(Ipv4Addr::from(hello.src_ip)),
+                            dst_ip: IpAddr::V4(Ipv4Addr::from(hello.dst_ip)),
+                            src_port: hello.src_port,
+                            dst_port: hello.dst_port,
+                            protocol: PROTO_TCP,
+                            size: len,
+                            timestamp: std::time::SystemTime::now(),
+                        };
+                        let packet_event =
+                            PacketEvent::new(packet_info, Bytes::copy_from_slice(payload))
+                                .with_sni(sni);
+
+                        if let Err(e) = event_tx.send(packet_event).await {
+                            tracing::error!(error = %e, "failed to send TLS packet event, channel closed");
+                            break 'reader;
                         }
                     }
+                    guard.clear_ready();
                 }
 
-                tracing::info!("eBPF event reader task stopped");
+                tracing::info!("TLS ClientHello reader task stopped");
             });
 
             self.tasks.push(handle);
         }
 
-        #[cfg(not(target_os = "linux"))]
-        {
-            // 비-Linux 플랫폼에서는 no-op
-        }
-
         Ok(())
     }
 
@@ -537,9 +1443,11 @@ impl EbpfEngine {
         #[cfg(target_os = "linux")]
         {
             use crate::stats::RawTrafficSnapshot;
-            use aya::maps::PerCpuArray;
+            use aya::maps::{PerCpuArray, PerCpuHashMap};
             use ironpost_ebpf_common::{
-                MAP_STATS, ProtoStats, STATS_IDX_ICMP, STATS_IDX_OTHER, STATS_IDX_TCP,
+                MAP_EVENTS, MAP_IFACE_STATS, MAP_PORT_STATS, MAP_RINGBUF_DROPS, MAP_STATS,
+                MAP_TLS_HELLOS, PortStatsKey, ProtoStats, RINGBUF_DROPS_IDX_EVENTS,
+                RINGBUF_DROPS_IDX_TLS_HELLOS, STATS_IDX_ICMP, STATS_IDX_OTHER, STATS_IDX_TCP,
                 STATS_IDX_TOTAL, STATS_IDX_UDP,
             };
 
@@ -555,8 +1463,54 @@ impl EbpfEngine {
                 )?)
                 .map_err(|e| DetectionError::EbpfMap(format!("failed to get stats map: {}", e)))?;
 
-            // TrafficStats Arc 복사
+            // PORT_STATS LRU PerCpuHashMap 획득 (소유권 획득)
+            let port_stats_map = PerCpuHashMap::<_, PortStatsKey, ProtoStats>::try_from(
+                bpf.take_map(MAP_PORT_STATS).ok_or_else(|| {
+                    DetectionError::EbpfMap(format!("map '{}' not found", MAP_PORT_STATS))
+                })?,
+            )
+            .map_err(|e| DetectionError::EbpfMap(format!("failed to get port stats map: {}", e)))?;
+
+            // IFACE_STATS PerCpuHashMap 획득 (소유권 획득)
+            let iface_stats_map = PerCpuHashMap::<_, u32, ProtoStats>::try_from(
+                bpf.take_map(MAP_IFACE_STATS).ok_or_else(|| {
+                    DetectionError::EbpfMap(format!("map '{}' not found", MAP_IFACE_STATS))
+                })?,
+            )
+            .map_err(|e| {
+                DetectionError::EbpfMap(format!("failed to get interface stats map: {}", e))
+            })?;
+            let iface_names: std::collections::HashMap<u32, String> = self
+                .attached
+                .iter()
+                .map(|iface| (iface.ifindex, iface.name.clone()))
+                .collect();
+
+            // RINGBUF_DROPS PerCpuArray 획득 (소유권 획득)
+            let drops_map =
+                PerCpuArray::<_, u64>::try_from(bpf.take_map(MAP_RINGBUF_DROPS).ok_or_else(
+                    || DetectionError::EbpfMap(format!("map '{}' not found", MAP_RINGBUF_DROPS)),
+                )?)
+                .map_err(|e| {
+                    DetectionError::EbpfMap(format!("failed to get ring buffer drops map: {}", e))
+                })?;
+            // TLS 분류기를 어태치하지 않으면 TLS_HELLOS에는 기록되지 않음
+            let mut ringbufs = vec![(MAP_EVENTS, RINGBUF_DROPS_IDX_EVENTS)];
+            if self.config.base.tls_sni {
+                ringbufs.push((MAP_TLS_HELLOS, RINGBUF_DROPS_IDX_TLS_HELLOS));
+            }
+
+            // TrafficStats, 맵 사용량, 차단 목록 Arc 복사
             let stats = Arc::clone(&self.stats);
+            let map_usage = Arc::clone(&self.map_usage);
+            let blocklist = Arc::clone(&self.blocklist);
+
+            // 임계치와 알림 채널이 모두 있을 때만 트래픽 알림 평가
+            let mut traffic_alerts = self
+                .config
+                .traffic_thresholds()
+                .zip(self.alert_tx.clone())
+                .map(|(thresholds, tx)| (TrafficAlerter::new(thresholds), tx));
 
             // 백그라운드 태스크 스폰
             let handle = tokio::task::spawn(async move {
@@ -573,6 +1527,8 @@ impl EbpfEngine {
                     let icmp = sum_percpu_stats(&stats_map, STATS_IDX_ICMP);
                     let other = sum_percpu_stats(&stats_map, STATS_IDX_OTHER);
                     let total = sum_percpu_stats(&stats_map, STATS_IDX_TOTAL);
+                    let ports = collect_port_stats(&port_stats_map);
+                    let interfaces = collect_iface_stats(&iface_stats_map, &iface_names);
 
                     let snapshot = RawTrafficSnapshot {
                         tcp,
@@ -580,12 +1536,41 @@ impl EbpfEngine {
                         icmp,
                         other,
                         total,
+                        ports,
+                        interfaces,
                     };
 
-                    // TrafficStats 업데이트
-                    {
+                    // TrafficStats 업데이트 후 임계치 평가
+                    let alerts = {
                         let mut stats_guard = stats.lock().await;
                         stats_guard.update(snapshot);
+                        match traffic_alerts.as_mut() {
+                            Some((alerter, _)) => {
+                                alerter.evaluate(&stats_guard, std::time::Instant::now())
+                            }
+                            None => Vec::new(),
+                        }
+                    };
+                    // 차단 목록 점유율과 RingBuf 드롭 수 갱신
+                    let maps = blocklist.lock().await.map_usage().to_vec();
+                    {
+                        let now = std::time::Instant::now();
+                        let mut usage = map_usage.lock().await;
+                        usage.update_maps(maps);
+                        for &(name, idx) in &ringbufs {
+                            usage.record_ringbuf_drops(name, sum_percpu_u64(&drops_map, idx), now);
+                        }
+                    }
+
+                    if let Some((_, alert_tx)) = &traffic_alerts {
+                        for alert in alerts {
+                            let severity = alert.severity;
+                            let rule = alert.rule_name.clone();
+                            let event = AlertEvent::with_source(alert, severity, MODULE_EBPF);
+                            if let Err(e) = alert_tx.try_send(event) {
+                                tracing::warn!(error = %e, rule = %rule, "failed to send traffic alert");
+                            }
+                        }
                     }
                 }
 
@@ -612,6 +1597,14 @@ impl EbpfEngine {
 // Helper Functions (Linux 전용)
 // =============================================================================
 
+/// `u64` PerCpuArray에서 특정 인덱스의 모든 CPU 값을 합산합니다.
+#[cfg(target_os = "linux")]
+fn sum_percpu_u64(map: &aya::maps::PerCpuArray<aya::maps::MapData, u64>, index: u32) -> u64 {
+    map.get(&index, 0)
+        .map(|values| values.iter().copied().fold(0u64, u64::saturating_add))
+        .unwrap_or(0)
+}
+
 /// PerCpuArray에서 특정 인덱스의 모든 CPU 값을 합산합니다.
 #[cfg(target_os = "linux")]
 fn sum_percpu_stats(
@@ -628,6 +1621,7 @@ fn sum_percpu_stats(
                 total.packets += cpu_stats.packets;
                 total.bytes += cpu_stats.bytes;
                 total.drops += cpu_stats.drops;
+                total.would_drops += cpu_stats.would_drops;
             }
             total
         }
@@ -638,6 +1632,195 @@ fn sum_percpu_stats(
     }
 }
 
+/// PORT_STATS 맵의 모든 포트에 대해 CPU별 값을 합산합니다.
+///
+/// 순회 중 커널이 LRU 엔트리를 교체하면 해당 엔트리만 건너뜁니다.
+#[cfg(target_os = "linux")]
+fn collect_port_stats(
+    map: &aya::maps::PerCpuHashMap<
+        aya::maps::MapData,
+        ironpost_ebpf_common::PortStatsKey,
+        ironpost_ebpf_common::ProtoStats,
+    >,
+) -> Vec<crate::stats::RawPortStats> {
+    use crate::stats::{RawPortStats, RawProtoStats};
+
+    let mut ports = Vec::new();
+    for entry in map.iter() {
+        match entry {
+            Ok((key, per_cpu_values)) => {
+                let mut stats = RawProtoStats::default();
+                for cpu_stats in per_cpu_values.iter() {
+                    stats.packets += cpu_stats.packets;
+                    stats.bytes += cpu_stats.bytes;
+                    stats.drops += cpu_stats.drops;
+                    stats.would_drops += cpu_stats.would_drops;
+                }
+                ports.push(RawPortStats {
+                    port: key.dst_port,
+                    protocol: key.protocol,
+                    stats,
+                });
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "skipping port stats entry");
+            }
+        }
+    }
+    ports
+}
+
+/// IFACE_STATS 맵의 모든 인터페이스에 대해 CPU별 값을 합산합니다.
+///
+/// 어태치 목록에 없는 ifindex(이전 실행의 잔여 엔트리 등)는 번호를 이름으로 사용합니다.
+#[cfg(target_os = "linux")]
+fn collect_iface_stats(
+    map: &aya::maps::PerCpuHashMap<aya::maps::MapData, u32, ironpost_ebpf_common::ProtoStats>,
+    names: &std::collections::HashMap<u32, String>,
+) -> Vec<crate::stats::RawInterfaceStats> {
+    use crate::stats::{RawInterfaceStats, RawProtoStats};
+
+    let mut interfaces = Vec::new();
+    for entry in map.iter() {
+        match entry {
+            Ok((ifindex, per_cpu_values)) => {
+                let mut stats = RawProtoStats::default();
+                for cpu_stats in per_cpu_values.iter() {
+                    stats.packets += cpu_stats.packets;
+                    stats.bytes += cpu_stats.bytes;
+                    stats.drops += cpu_stats.drops;
+                    stats.would_drops += cpu_stats.would_drops;
+                }
+                let name = names
+                    .get(&ifindex)
+                    .cloned()
+                    .unwrap_or_else(|| ifindex.to_string());
+                interfaces.push(RawInterfaceStats { name, stats });
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "skipping interface stats entry");
+            }
+        }
+    }
+    interfaces
+}
+
+/// 로드된 오브젝트에서 `ironpost_xdp` 프로그램을 꺼냅니다.
+#[cfg(target_os = "linux")]
+fn xdp_program(bpf: &mut aya::Ebpf) -> Result<&mut aya::programs::Xdp, IronpostError> {
+    let program = bpf.program_mut("ironpost_xdp").ok_or_else(|| {
+        DetectionError::EbpfLoad("XDP program 'ironpost_xdp' not found".to_owned())
+    })?;
+    let program: &mut aya::programs::Xdp = program.try_into().map_err(|e| {
+        DetectionError::EbpfLoad(format!("failed to convert to XDP program: {}", e))
+    })?;
+    Ok(program)
+}
+
+/// 프로그램이 사용하는 맵을 커널 맵 ID로 다시 열어 이름별로 반환합니다.
+///
+/// 백그라운드 태스크가 `take_map()`으로 가져간 맵도 포함되므로, 교체 전에 기존
+/// 프로그램의 맵 내용을 읽을 수 있습니다. 열지 못한 맵은 건너뜁니다.
+#[cfg(target_os = "linux")]
+fn program_maps(
+    program: &aya::programs::Xdp,
+) -> std::collections::HashMap<String, aya::maps::MapData> {
+    use aya::maps::MapData;
+
+    let ids = match program.info().and_then(|info| info.map_ids()) {
+        Ok(ids) => ids.unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to list maps of the running XDP program");
+            return std::collections::HashMap::new();
+        }
+    };
+
+    let mut maps = std::collections::HashMap::new();
+    for id in ids {
+        let Ok(map) = MapData::from_id(id) else {
+            continue;
+        };
+        let name = map
+            .info()
+            .ok()
+            .and_then(|info| info.name_as_str().map(str::to_owned));
+        if let Some(name) = name {
+            maps.insert(name, map);
+        }
+    }
+    maps
+}
+
+/// 고정되지 않은 상태 맵의 내용을 새 오브젝트로 복사합니다.
+///
+/// 키/값 레이아웃이 바뀐 맵은 복사하지 않고 빈 상태로 시작합니다.
+#[cfg(target_os = "linux")]
+fn copy_unpinned_maps(
+    mut old_maps: std::collections::HashMap<String, aya::maps::MapData>,
+    new_bpf: &mut aya::Ebpf,
+) {
+    use ironpost_ebpf_common::{
+        FlowKey, FlowState, MAP_CONNTRACK, MAP_IFACE_STATS, MAP_RATE_LIMIT, ProtoStats, TokenBucket,
+    };
+
+    for name in [MAP_RATE_LIMIT, MAP_CONNTRACK, MAP_IFACE_STATS] {
+        let (Some(old), Some(new)) = (old_maps.remove(name), new_bpf.map_mut(name)) else {
+            tracing::debug!(map = name, "map missing on one side, not copying");
+            continue;
+        };
+        let result = match name {
+            MAP_RATE_LIMIT => copy_hash_map::<u32, TokenBucket>(old, new),
+            MAP_CONNTRACK => copy_hash_map::<FlowKey, FlowState>(old, new),
+            _ => copy_percpu_hash_map::<u32, ProtoStats>(old, new),
+        };
+        match result {
+            Ok(entries) => info!(map = name, entries, "copied eBPF map contents"),
+            Err(e) => tracing::warn!(
+                map = name,
+                error = %e,
+                "failed to copy eBPF map contents, starting empty"
+            ),
+        }
+    }
+}
+
+/// (LRU) 해시 맵의 엔트리를 복사하고 복사한 개수를 반환합니다.
+#[cfg(target_os = "linux")]
+fn copy_hash_map<K: aya::Pod, V: aya::Pod>(
+    old: aya::maps::MapData,
+    new: &mut aya::maps::Map,
+) -> Result<usize, aya::maps::MapError> {
+    use aya::maps::{HashMap as AyaHashMap, Map};
+
+    let old = AyaHashMap::<_, K, V>::try_from(Map::LruHashMap(old))?;
+    let mut new = AyaHashMap::<_, K, V>::try_from(new)?;
+    let mut copied = 0;
+    // 순회 중 커널이 LRU 엔트리를 교체하면 해당 엔트리만 건너뜁니다
+    for (key, value) in old.iter().filter_map(Result::ok) {
+        new.insert(key, value, 0)?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// CPU별 해시 맵의 엔트리를 CPU별 값 그대로 복사하고 복사한 개수를 반환합니다.
+#[cfg(target_os = "linux")]
+fn copy_percpu_hash_map<K: aya::Pod, V: aya::Pod>(
+    old: aya::maps::MapData,
+    new: &mut aya::maps::Map,
+) -> Result<usize, aya::maps::MapError> {
+    use aya::maps::{Map, PerCpuHashMap};
+
+    let old = PerCpuHashMap::<_, K, V>::try_from(Map::PerCpuHashMap(old))?;
+    let mut new = PerCpuHashMap::<_, K, V>::try_from(new)?;
+    let mut copied = 0;
+    for (key, values) in old.iter().filter_map(Result::ok) {
+        new.insert(key, values, 0)?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
 // =============================================================================
 // Pipeline Trait Implementation
 // =============================================================================
@@ -646,10 +1829,18 @@ impl EbpfEngine {
     /// XDP 어태치 이후 초기화 단계를 수행합니다.
     ///
     /// 이 메서드가 실패하면 start()에서 자동으로 롤백합니다.
-    fn initialize_post_attach(&mut self) -> Result<(), IronpostError> {
-        self.sync_blocklist_to_map()?;
+    async fn initialize_post_attach(&mut self) -> Result<(), IronpostError> {
+        self.configure_rate_limit()?;
+        self.configure_sampling()?;
+        self.configure_dry_run()?;
+        self.attach_blocklist_map().await?;
         self.spawn_event_reader()?;
+        self.spawn_tls_reader()?;
         self.spawn_stats_poller()?;
+        self.spawn_conntrack_poller()?;
+        self.spawn_blocklist_reaper()?;
+        self.spawn_auto_blocker()?;
+        self.spawn_feed_syncers()?;
         Ok(())
     }
 }
@@ -657,10 +1848,15 @@ impl EbpfEngine {
 impl Pipeline for EbpfEngine {
     /// eBPF XDP 프로그램을 로드하고 엔진을 시작합니다.
     ///
-    /// 1. XDP 프로그램 로드 및 인터페이스 어태치
-    /// 2. 필터링 룰을 eBPF HashMap에 동기화
-    /// 3. RingBuf 이벤트 수신 태스크 스폰
+    /// 0. 상태 파일(`state_path`)에서 룰과 런타임 차단 엔트리 복원 후
+    ///    커널 버전/BTF/권한/XDP 드라이버 사전 점검 ([`preflight`](crate::preflight))
+    /// 1. XDP 프로그램 로드 및 설정된 인터페이스(목록 또는 `"all"`)마다 어태치
+    /// 2. 레이트 리밋/샘플링 파라미터 기록 + 필터링 룰을 eBPF 맵에 동기화
+    /// 3. RingBuf 이벤트 수신 태스크 스폰 (`tls_sni`가 켜져 있으면 ClientHello 수신 태스크 포함)
     /// 4. 통계 폴링 태스크 스폰
+    /// 5. 차단 목록 만료 처리 + 상태 파일 저장 태스크 스폰
+    /// 6. 자동 차단 요청 처리 태스크 스폰 (`auto_block_ttl_secs` > 0일 때)
+    /// 7. 위협 인텔리전스 피드 동기화 태스크 스폰 (`threat_feeds`마다 하나)
     ///
     /// # 롤백 보장
     /// 초기화 중 에러 발생 시 자동으로 XDP 프로그램을 detach하여
@@ -676,11 +1872,24 @@ impl Pipeline for EbpfEngine {
             "starting eBPF engine"
         );
 
+        // 이전 실행에서 저장한 룰/차단 엔트리 복원 (맵 연결 시 함께 반영)
+        self.restore_state().await?;
+
+        // 커널/권한/드라이버 사전 점검 (실패 항목을 모아 진단 에러로 반환)
+        #[cfg(target_os = "linux")]
+        {
+            let report = self.preflight()?;
+            for check in report.warnings() {
+                tracing::warn!(check = check.name.as_str(), "preflight: {}", check.detail);
+            }
+            report.into_result()?;
+        }
+
         // XDP 프로그램 로드 및 어태치
         self.load_and_attach()?;
 
         // 이후 단계에서 실패 시 자동 롤백
-        if let Err(e) = self.initialize_post_attach() {
+        if let Err(e) = self.initialize_post_attach().await {
             tracing::error!(error = %e, "failed to initialize engine, rolling back");
 
             // 이미 스폰된 백그라운드 태스크 정리
@@ -689,6 +1898,7 @@ impl Pipeline for EbpfEngine {
                 for task in self.tasks.drain(..) {
                     task.abort();
                 }
+                self.blocklist.lock().await.detach_maps();
             }
 
             // XDP 프로그램 detach (롤백)
@@ -708,9 +1918,11 @@ impl Pipeline for EbpfEngine {
     /// eBPF 엔진을 정지하고 리소스를 정리합니다.
     ///
     /// 1. 백그라운드 태스크 취소
-    /// 2. XDP 프로그램 언로드
+    /// 2. 차단 목록 맵 연결 해제
+    /// 3. XDP 프로그램 언로드
     ///
     /// # 참고
+    /// 런타임 차단 목록 엔트리는 유지되며, 다음 start() 시 다시 맵에 반영됩니다.
     /// 통계(stats)는 리셋되지 않으므로, stop() 후에도 누적된 트래픽 통계를 조회할 수 있습니다.
     async fn stop(&mut self) -> Result<(), IronpostError> {
         if !self.running {
@@ -725,7 +1937,9 @@ impl Pipeline for EbpfEngine {
             for task in self.tasks.drain(..) {
                 task.abort();
             }
+            self.blocklist.lock().await.detach_maps();
         }
+        self.save_state().await;
 
         // XDP 프로그램 detach
         self.detach()?;
@@ -739,8 +1953,17 @@ impl Pipeline for EbpfEngine {
             return HealthStatus::Unhealthy("not running".to_owned());
         }
 
-        // TODO: XDP 프로그램 상태 확인, 맵 접근 가능 여부 등
-        HealthStatus::Healthy
+        // 차단 목록이 거의 찼거나 RingBuf가 넘쳐 이벤트를 잃고 있으면 Degraded
+        let reasons = self
+            .map_usage
+            .lock()
+            .await
+            .degraded_reasons(std::time::Instant::now());
+        if reasons.is_empty() {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Degraded(reasons.join("; "))
+        }
     }
 }
 
@@ -787,14 +2010,435 @@ impl Plugin for EbpfEngine {
     async fn health_check(&self) -> HealthStatus {
         <Self as Pipeline>::health_check(self).await
     }
+
+    async fn resource_usage(&self) -> ResourceUsage {
+        #[cfg(target_os = "linux")]
+        let tasks = ResourceUsage::live_tasks(&self.tasks);
+        #[cfg(not(target_os = "linux"))]
+        let tasks = 0;
+
+        let events = ChannelUsage::from_sender("packet_events", &self.event_tx);
+        let memory_bytes =
+            ResourceUsage::estimate_bytes(events.depth, std::mem::size_of::<PacketEvent>());
+
+        ResourceUsage {
+            tasks,
+            channels: vec![events],
+            memory_bytes,
+        }
+    }
+}
+
+/// RingBuf에서 다음 레코드를 읽어 헤더의 종류대로 해석합니다 (비어 있으면 `None`).
+///
+/// 해석할 수 없는 레코드(버전/종류/길이 불일치)는 경고 후 건너뜁니다.
+#[cfg(target_os = "linux")]
+fn next_record(ringbuf: &mut aya::maps::RingBuf<aya::maps::MapData>) -> Option<RingEvent> {
+    while let Some(data) = ringbuf.next() {
+        match decode_record(&data) {
+            Ok(record) => return Some(record),
+            Err(e) => tracing::warn!(error = %e, size = data.len(), "skipping ringbuf record"),
+        }
+    }
+    None
+}
+
+/// 패킷 이벤트의 TCP 연결을 소유한 프로세스를 조회합니다.
+///
+/// kprobe가 수신 방향 5-튜플로 기록하므로 이벤트 필드로 만든 FlowKey를 그대로 씁니다.
+#[cfg(target_os = "linux")]
+fn lookup_sock_owner(
+    owners: &aya::maps::HashMap<
+        aya::maps::MapData,
+        ironpost_ebpf_common::FlowKey,
+        ironpost_ebpf_common::SockOwner,
+    >,
+    event: &ironpost_ebpf_common::PacketEventData,
+) -> Option<ironpost_ebpf_common::SockOwner> {
+    use ironpost_ebpf_common::{FlowKey, PROTO_TCP};
+
+    if event.protocol != PROTO_TCP {
+        return None;
+    }
+    let key = FlowKey::new(
+        event.src_ip,
+        event.dst_ip,
+        event.src_port,
+        event.dst_port,
+        event.protocol,
+    );
+    owners.get(&key, 0).ok()
+}
+
+/// 새 TCP 연결의 소유 프로세스를 기록하는 kprobe들을 로드하고 어태치합니다.
+///
+/// 프로세스 연관은 부가 정보이므로 실패해도 경고만 남기고 XDP 필터링은 계속합니다.
+/// 링크는 프로그램이 소유하므로 `aya::Ebpf`를 drop할 때 함께 해제됩니다.
+#[cfg(target_os = "linux")]
+fn attach_socket_probes(bpf: &mut aya::Ebpf) {
+    use aya::programs::KProbe;
+
+    for (program_name, function) in SOCKET_PROBES {
+        let result = bpf
+            .program_mut(program_name)
+            .ok_or_else(|| "program not found".to_owned())
+            .and_then(|program| <&mut KProbe>::try_from(program).map_err(|e| e.to_string()))
+            .and_then(|program| {
+                program.load().map_err(|e| e.to_string())?;
+                program.attach(function, 0).map_err(|e| e.to_string())
+            });
+        match result {
+            Ok(_) => info!(
+                program = program_name,
+                function, "socket owner probe attached"
+            ),
+            Err(e) => tracing::warn!(
+                program = program_name,
+                function,
+                error = %e,
+                "failed to attach socket owner probe, alerts will not include processes"
+            ),
+        }
+    }
+}
+
+/// XDP를 어태치한 인터페이스마다 TLS ClientHello 캡처용 TC egress 분류기를 어태치합니다.
+///
+/// SNI 추출은 부가 정보이므로 실패해도 경고만 남기고 XDP 필터링은 계속합니다.
+/// 링크는 프로그램이 소유하므로 `aya::Ebpf`를 drop할 때 함께 해제됩니다.
+#[cfg(target_os = "linux")]
+fn attach_tls_classifier(bpf: &mut aya::Ebpf, interfaces: &[AttachedInterface]) {
+    use aya::programs::{SchedClassifier, TcAttachType, tc};
+
+    let program = bpf
+        .program_mut(TLS_CLASSIFIER)
+        .ok_or_else(|| "program not found".to_owned())
+        .and_then(|program| <&mut SchedClassifier>::try_from(program).map_err(|e| e.to_string()))
+        .and_then(|program| {
+            program.load().map_err(|e| e.to_string())?;
+            Ok(program)
+        });
+    let program = match program {
+        Ok(program) => program,
+        Err(e) => {
+            tracing::warn!(
+                program = TLS_CLASSIFIER,
+                error = %e,
+                "failed to load TLS classifier, packet events will not include SNI"
+            );
+            return;
+        }
+    };
+
+    for iface in interfaces {
+        // clsact qdisc가 이미 있으면 에러가 나지만 필터 추가에는 문제가 없으므로 무시
+        let _ = tc::qdisc_add_clsact(&iface.name);
+        match program.attach(&iface.name, TcAttachType::Egress) {
+            Ok(_) => info!(interface = iface.name.as_str(), "TLS classifier attached"),
+            Err(e) => tracing::warn!(
+                interface = iface.name.as_str(),
+                error = %e,
+                "failed to attach TLS classifier"
+            ),
+        }
+    }
+}
+
+/// 설정에서 XDP를 어태치할 인터페이스 이름 목록을 결정합니다.
+///
+/// `"all"`이면 `sys_class_net` 아래의 인터페이스 중 루프백(`lo`)을 제외한 전부를
+/// 이름순으로, `"auto"`이면 `proc_net_route`에서 찾은 기본 경로 인터페이스 하나를
+/// 반환하고, 그 외에는 설정에 나열된 인터페이스를 그대로 반환합니다.
+fn resolve_interfaces(
+    config: &EbpfConfig,
+    sys_class_net: &Path,
+    proc_net_route: &Path,
+) -> Result<Vec<String>, IronpostError> {
+    if config.uses_default_route_interface() {
+        let name = read_default_route_interface(proc_net_route)?;
+        info!(
+            interface = name.as_str(),
+            "resolved default route interface"
+        );
+        return Ok(vec![name]);
+    }
+    if !config.attaches_all_interfaces() {
+        return Ok(config.interfaces());
+    }
+
+    let entries = std::fs::read_dir(sys_class_net).map_err(|e| {
+        DetectionError::EbpfLoad(format!(
+            "failed to list network interfaces in {}: {}",
+            sys_class_net.display(),
+            e
+        ))
+    })?;
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name != "lo")
+        .collect();
+    names.sort();
+
+    if names.is_empty() {
+        return Err(DetectionError::EbpfLoad(format!(
+            "no network interfaces found in {}",
+            sys_class_net.display()
+        ))
+        .into());
+    }
+    Ok(names)
+}
+
+/// `<sys_class_net>/<name>/ifindex`에서 인터페이스 인덱스를 읽습니다.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+/// IPv4 기본 경로(default route)가 지나는 인터페이스 이름을 반환합니다.
+///
+/// `interface = "auto"`가 시작 시점에 어태치할 인터페이스와 같습니다. 데몬의 사전 점검처럼
+/// 엔진 밖에서 같은 인터페이스를 확인할 때 사용합니다.
+///
+/// # Errors
+///
+/// 라우팅 테이블을 읽을 수 없거나 활성 기본 경로가 없으면 에러를 반환합니다.
+pub fn default_route_interface() -> Result<String, IronpostError> {
+    read_default_route_interface(Path::new(PROC_NET_ROUTE))
+}
+
+/// 라우팅 테이블에서 기본 경로 인터페이스를 찾습니다.
+///
+/// 커널이 netlink `RTM_GETROUTE`로 내보내는 main 테이블의 IPv4 라우트를
+/// `/proc/net/route` 형식으로 읽습니다. 목적지와 마스크가 모두 0이고 `RTF_UP`이
+/// 설정된 라우트 중 metric이 가장 작은 것을 고르며, 같으면 먼저 나온 라우트를 씁니다.
+///
+/// ```text
+/// Iface  Destination  Gateway   Flags  RefCnt  Use  Metric  Mask      MTU  Window  IRTT
+/// eth0   00000000     0101A8C0  0003   0       0    100     00000000  0    0       0
+/// ```
+fn read_default_route_interface(proc_net_route: &Path) -> Result<String, IronpostError> {
+    let content = std::fs::read_to_string(proc_net_route).map_err(|e| {
+        DetectionError::EbpfLoad(format!(
+            "failed to read routing table {}: {}",
+            proc_net_route.display(),
+            e
+        ))
+    })?;
+
+    let mut best: Option<(u32, &str)> = None;
+    for line in content.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [
+            iface,
+            destination,
+            _gateway,
+            flags,
+            _refcnt,
+            _use,
+            metric,
+            mask,
+            ..,
+        ] = fields[..]
+        else {
+            continue;
+        };
+        let up = u32::from_str_radix(flags, 16).is_ok_and(|flags| flags & RTF_UP != 0);
+        if destination != "00000000" || mask != "00000000" || !up {
+            continue;
+        }
+        let Ok(metric) = metric.parse::<u32>() else {
+            continue;
+        };
+        if best.is_none_or(|(best_metric, _)| metric < best_metric) {
+            best = Some((metric, iface));
+        }
+    }
+
+    best.map(|(_, iface)| iface.to_owned()).ok_or_else(|| {
+        DetectionError::EbpfLoad(format!(
+            "no default route found in {} (set ebpf.interface explicitly)",
+            proc_net_route.display()
+        ))
+        .into()
+    })
+}
+
+fn interface_index(sys_class_net: &Path, name: &str) -> Option<u32> {
+    if name.is_empty() || name.contains('/') || name == "." || name == ".." {
+        return None;
+    }
+    std::fs::read_to_string(sys_class_net.join(name).join("ifindex"))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// 맵 고정 디렉토리를 준비하고, 현재 프로그램과 레이아웃이 다른 고정 맵을 제거합니다.
+///
+/// 이전 버전이 남긴 맵의 키/값 크기가 다르면 aya가 그대로 재사용해 검증기 에러나
+/// 잘못된 값 해석으로 이어지므로, 해당 맵만 버리고 새로 만들게 합니다.
+#[cfg(target_os = "linux")]
+fn prepare_pin_dir(dir: &std::path::Path) -> Result<(), IronpostError> {
+    use aya::maps::MapInfo;
+    use aya::maps::lpm_trie::Key;
+    use ironpost_ebpf_common::{
+        BlocklistValue, MAP_ALLOWLIST, MAP_BLOCKLIST, MAP_BLOCKLIST_CIDR, MAP_PORT_RULES,
+        MAP_PORT_STATS, MAP_STATS, PINNED_MAPS, PortRuleKey, PortStatsKey, ProtoStats,
+    };
+    use std::mem::size_of;
+
+    std::fs::create_dir_all(dir).map_err(|e| {
+        DetectionError::EbpfLoad(format!(
+            "failed to create map pin directory {} (is bpffs mounted?): {}",
+            dir.display(),
+            e
+        ))
+    })?;
+
+    for name in PINNED_MAPS {
+        let path = dir.join(name);
+        if !path.exists() {
+            continue;
+        }
+        let (key_size, value_size) = match name {
+            MAP_BLOCKLIST => (size_of::<u32>(), size_of::<BlocklistValue>()),
+            MAP_BLOCKLIST_CIDR | MAP_ALLOWLIST => {
+                (size_of::<Key<u32>>(), size_of::<BlocklistValue>())
+            }
+            MAP_PORT_RULES => (size_of::<PortRuleKey>(), size_of::<BlocklistValue>()),
+            MAP_STATS => (size_of::<u32>(), size_of::<ProtoStats>()),
+            MAP_PORT_STATS => (size_of::<PortStatsKey>(), size_of::<ProtoStats>()),
+            _ => continue,
+        };
+        let compatible = MapInfo::from_pin(&path).is_ok_and(|info| {
+            info.key_size() as usize == key_size && info.value_size() as usize == value_size
+        });
+        if compatible {
+            tracing::info!(map = name, path = %path.display(), "reusing pinned eBPF map");
+            continue;
+        }
+        tracing::warn!(
+            map = name,
+            path = %path.display(),
+            "pinned eBPF map has an incompatible layout, discarding it"
+        );
+        std::fs::remove_file(&path).map_err(|e| {
+            DetectionError::EbpfLoad(format!(
+                "failed to remove stale pinned map {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ironpost_core::config::EbpfConfig;
     use std::net::IpAddr;
 
+    // =============================================================================
+    // eBPF 오브젝트 위치 테스트
+    // =============================================================================
+
+    #[test]
+    fn test_object_source_env_path_overrides_embedded() {
+        let path = "/opt/ironpost/ironpost-ebpf".to_owned();
+
+        assert_eq!(
+            select_object_source(Some(path.clone()), true),
+            EbpfObjectSource::File(path)
+        );
+        assert_eq!(select_object_source(None, true), EbpfObjectSource::Embedded);
+        assert_eq!(
+            select_object_source(None, false),
+            EbpfObjectSource::File(DEFAULT_EBPF_PATH.to_owned())
+        );
+    }
+
+    // =============================================================================
+    // 인터페이스 선택 테스트
+    // =============================================================================
+
+    fn fake_sys_class_net(interfaces: &[(&str, u32)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for (name, ifindex) in interfaces {
+            let iface = dir.path().join(name);
+            std::fs::create_dir(&iface).expect("create interface dir");
+            std::fs::write(iface.join("ifindex"), format!("{ifindex}\n")).expect("write ifindex");
+        }
+        dir
+    }
+
+    #[test]
+    fn test_resolve_interfaces_list_and_all() {
+        let sys = fake_sys_class_net(&[("lo", 1), ("eth1", 3), ("eth0", 2)]);
+
+        let listed = EbpfConfig {
+            interface: "eth0, eth1".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_interfaces(&listed, sys.path(), Path::new("/nonexistent")).unwrap(),
+            vec!["eth0".to_owned(), "eth1".to_owned()]
+        );
+
+        // "all"은 루프백을 제외하고 이름순으로 반환
+        let all = EbpfConfig {
+            interface: "all".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_interfaces(&all, sys.path(), Path::new("/nonexistent")).unwrap(),
+            vec!["eth0".to_owned(), "eth1".to_owned()]
+        );
+
+        let empty = fake_sys_class_net(&[("lo", 1)]);
+        assert!(resolve_interfaces(&all, empty.path(), Path::new("/nonexistent")).is_err());
+    }
+
+    #[test]
+    fn test_resolve_interfaces_auto_uses_default_route() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let route = dir.path().join("route");
+        std::fs::write(
+            &route,
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n\
+             eth0\t0000A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0\n\
+             wlan0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0\n\
+             ens3\t00000000\t0100000A\t0003\t0\t0\t100\t00000000\t0\t0\t0\n\
+             eth9\t00000000\t0100000A\t0002\t0\t0\t0\t00000000\t0\t0\t0\n",
+        )
+        .expect("write route table");
+
+        let auto = EbpfConfig {
+            interface: "auto".to_owned(),
+            ..Default::default()
+        };
+        // 가장 작은 metric의 활성 기본 경로 (eth9는 RTF_UP이 없음)
+        assert_eq!(
+            resolve_interfaces(&auto, Path::new("/nonexistent"), &route).unwrap(),
+            vec!["ens3".to_owned()]
+        );
+
+        std::fs::write(
+            &route,
+            "Iface\tDestination\tGateway\tFlags\tRefCnt\tUse\tMetric\tMask\n",
+        )
+        .expect("write route table");
+        let err = resolve_interfaces(&auto, Path::new("/nonexistent"), &route).unwrap_err();
+        assert!(err.to_string().contains("no default route"));
+    }
+
+    #[test]
+    fn test_interface_index_reads_sysfs() {
+        let sys = fake_sys_class_net(&[("eth0", 2)]);
+
+        assert_eq!(interface_index(sys.path(), "eth0"), Some(2));
+        assert_eq!(interface_index(sys.path(), "eth9"), None);
+        assert_eq!(interface_index(sys.path(), "../eth0"), None);
+    }
+
     // =============================================================================
     // EbpfEngineBuilder 테스트
     // =============================================================================
@@ -850,7 +2494,7 @@ mod tests {
 
     #[test]
     fn test_builder_with_custom_detector() {
-        use crate::detector::{PacketDetector, PortScanConfig, SynFloodConfig};
+        use crate::detector::{IcmpFloodConfig, PacketDetector, PortScanConfig, SynFloodConfig};
 
         let config = EngineConfig::default();
         let (alert_tx, _alert_rx) = mpsc::channel(100);
@@ -858,6 +2502,7 @@ mod tests {
             alert_tx,
             SynFloodConfig::default(),
             PortScanConfig::default(),
+            IcmpFloodConfig::default(),
         );
 
         let result = EbpfEngine::builder()
@@ -868,6 +2513,67 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_builder_registers_analyzers() {
+        struct Noop;
+
+        impl PacketAnalyzer for Noop {
+            fn name(&self) -> &str {
+                "noop"
+            }
+
+            fn analyze(
+                &self,
+                _event: &ironpost_ebpf_common::PacketEventData,
+            ) -> Result<Option<ironpost_core::types::Alert>, IronpostError> {
+                Ok(None)
+            }
+        }
+
+        let (engine, _rx) = EbpfEngine::builder()
+            .config(EngineConfig::default())
+            .analyzer(Noop)
+            .build()
+            .expect("build");
+        assert_eq!(
+            engine.detector.analyzer_names(),
+            vec!["syn_flood", "port_scan", "icmp_flood", "noop"]
+        );
+    }
+
+    #[test]
+    fn test_builder_wires_auto_block_when_ttl_set() {
+        let (engine, _rx) = EbpfEngine::builder()
+            .config(EngineConfig::default())
+            .build()
+            .expect("build");
+        assert!(!engine.detector.auto_block_enabled());
+        assert!(engine.auto_block_rx.is_none());
+
+        let mut config = EngineConfig::default();
+        config.base.auto_block_ttl_secs = 600;
+        let (engine, _rx) = EbpfEngine::builder().config(config).build().expect("build");
+        assert!(engine.detector.auto_block_enabled());
+        assert!(engine.auto_block_rx.is_some());
+    }
+
+    #[test]
+    fn test_builder_alert_sender() {
+        let (engine, _rx) = EbpfEngine::builder()
+            .config(EngineConfig::default())
+            .build()
+            .expect("build");
+        assert!(engine.alert_tx.is_none());
+
+        let (alert_tx, _alert_rx) = mpsc::channel(16);
+        let (engine, _rx) = EbpfEngine::builder()
+            .config(EngineConfig::default())
+            .alert_sender(alert_tx)
+            .build()
+            .expect("build");
+        assert!(engine.alert_tx.is_some());
+    }
+
     #[test]
     fn test_builder_fluent_api() {
         let config = EngineConfig::default();
@@ -916,8 +2622,8 @@ mod tests {
     // add_rule / remove_rule 테스트 (엔진 미실행 상태)
     // =============================================================================
 
-    #[test]
-    fn test_add_rule_when_not_running() {
+    #[tokio::test]
+    async fn test_add_rule_when_not_running() {
         use std::net::Ipv4Addr;
 
         let config = EngineConfig::default();
@@ -926,6 +2632,7 @@ mod tests {
         let rule = crate::config::FilterRule {
             id: "test-rule".to_owned(),
             src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))),
+            src_cidr: None,
             dst_ip: None,
             dst_port: None,
             protocol: None,
@@ -933,13 +2640,14 @@ mod tests {
             description: "Test rule".to_owned(),
         };
 
-        let result = engine.add_rule(rule);
+        let result = engine.add_rule(rule).await;
         assert!(result.is_ok());
         assert_eq!(engine.config().rules.len(), 1);
+        assert_eq!(engine.blocklist().lock().await.rule_count(), 1);
     }
 
-    #[test]
-    fn test_remove_rule_when_not_running() {
+    #[tokio::test]
+    async fn test_remove_rule_when_not_running() {
         use std::net::Ipv4Addr;
 
         let config = EngineConfig::default();
@@ -948,6 +2656,7 @@ mod tests {
         let rule = crate::config::FilterRule {
             id: "test-rule".to_owned(),
             src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))),
+            src_cidr: None,
             dst_ip: None,
             dst_port: None,
             protocol: None,
@@ -955,23 +2664,51 @@ mod tests {
             description: "Test rule".to_owned(),
         };
 
-        engine.add_rule(rule).unwrap();
+        engine.add_rule(rule).await.unwrap();
         assert_eq!(engine.config().rules.len(), 1);
 
-        let removed = engine.remove_rule("test-rule").unwrap();
+        let removed = engine.remove_rule("test-rule").await.unwrap();
         assert!(removed);
         assert_eq!(engine.config().rules.len(), 0);
     }
 
-    #[test]
-    fn test_remove_nonexistent_rule() {
+    #[tokio::test]
+    async fn test_remove_nonexistent_rule() {
         let config = EngineConfig::default();
         let (mut engine, _rx) = EbpfEngine::builder().config(config).build().unwrap();
 
-        let removed = engine.remove_rule("nonexistent").unwrap();
+        let removed = engine.remove_rule("nonexistent").await.unwrap();
         assert!(!removed);
     }
 
+    #[tokio::test]
+    async fn test_import_rules_when_not_running() {
+        use std::net::Ipv4Addr;
+
+        let config = EngineConfig::default();
+        let (mut engine, _rx) = EbpfEngine::builder().config(config).build().unwrap();
+
+        let rules: Vec<FilterRule> = (1..=3u8)
+            .chain([2])
+            .map(|n| crate::config::FilterRule {
+                id: format!("import-{n}"),
+                src_ip: Some(IpAddr::V4(Ipv4Addr::new(10, 0, 1, n))),
+                src_cidr: None,
+                dst_ip: None,
+                dst_port: None,
+                protocol: None,
+                action: crate::config::RuleAction::Block,
+                description: String::new(),
+            })
+            .collect();
+
+        let summary = engine.import_rules(rules).await.unwrap();
+        assert_eq!(summary.added, 3);
+        assert_eq!(summary.duplicates, 1);
+        assert_eq!(engine.config().rules.len(), 3);
+        assert_eq!(engine.blocklist().lock().await.rule_count(), 3);
+    }
+
     // =============================================================================
     // Pipeline trait 테스트 (비-Linux 환경)
     // =============================================================================
@@ -1001,6 +2738,15 @@ mod tests {
         assert!(err.to_string().contains("not running"));
     }
 
+    #[tokio::test]
+    async fn test_reload_when_not_running() {
+        let config = EngineConfig::default();
+        let (mut engine, _rx) = EbpfEngine::builder().config(config).build().unwrap();
+
+        let err = engine.reload().await.unwrap_err();
+        assert!(err.to_string().contains("not running"));
+    }
+
     #[tokio::test]
     async fn test_health_check_when_not_running() {
         let config = EngineConfig::default();
@@ -1093,6 +2839,29 @@ mod tests {
             assert!(stop_result.is_ok());
             assert!(!engine.running);
         }
+
+        #[tokio::test]
+        #[ignore] // 권한 및 네트워크 인터페이스 필요
+        async fn test_reload_keeps_program_attached() {
+            let mut config = EngineConfig::default();
+            config.base.interface = "lo".to_owned();
+            config.base.xdp_mode = "skb".to_owned();
+
+            let (mut engine, _rx) = EbpfEngine::builder().config(config).build().unwrap();
+            if let Err(e) = ironpost_core::Pipeline::start(&mut engine).await {
+                tracing::warn!(error = ?e, "skipping test due to error");
+                return;
+            }
+
+            // 교체 후에도 같은 인터페이스에 어태치된 채 실행 중이어야 함
+            engine.reload().await.expect("reload should succeed");
+            assert!(engine.running);
+            assert_eq!(engine.attached_interfaces(), vec!["lo".to_owned()]);
+
+            ironpost_core::Pipeline::stop(&mut engine)
+                .await
+                .expect("stop should succeed");
+        }
     }
 
     // =============================================================================
@@ -1149,6 +2918,20 @@ mod tests {
             xdp_mode: "native".to_owned(),
             ring_buffer_size: 2048,
             blocklist_max_entries: 10000,
+            rate_limit_pps: 0,
+            rate_limit_burst: 0,
+            monitor_sample_rate: 1,
+            observe_only: false,
+            auto_block_ttl_secs: 0,
+            traffic_alert_pps: 0,
+            traffic_alert_bytes_per_sec: 0,
+            traffic_alert_drop_percent: 0,
+            traffic_alert_cooldown_secs: 300,
+            process_correlation: false,
+            tls_sni: false,
+            state_path: String::new(),
+            map_pin_path: "/sys/fs/bpf/ironpost".to_owned(),
+            threat_feeds: Vec::new(),
         };
 
         let engine_config = EngineConfig::from_core(&ebpf_config);
@@ -0,0 +1,328 @@
+//! 위협 인텔리전스 피드 — IP 평판 목록을 내려받아 차단 룰로 변환
+//!
+//! 엔진은 `ebpf.threat_feeds`에 설정된 피드마다 태스크를 하나씩 띄워 `refresh_secs`마다
+//! [`fetch_feed`]로 내려받고, [`parse_feed`]로 IPv4 주소/CIDR을 추출한 뒤
+//! [`feed_rules`]로 만든 룰을 [`Blocklist::sync_feed`]로 반영합니다.
+//!
+//! # 형식
+//! - `plain`: 한 줄에 주소나 CIDR 하나. `#`/`;` 뒤는 주석으로 보고, 줄의 첫 토큰만
+//!   사용하므로 Spamhaus DROP(`1.2.3.0/24 ; SBL123`) 같은 목록도 그대로 읽습니다.
+//! - `stix`: STIX 2.x 번들 JSON. `indicator`의 `[ipv4-addr:value = '...']` 패턴과
+//!   `ipv4-addr` 객체의 `value`를 읽으며, `revoked`된 지표는 건너뜁니다.
+//!
+//! # 룰 ID
+//! 피드 룰의 ID는 `feed:<피드 이름>:<CIDR>`입니다. 피드에서 빠진 주소는 다음 동기화에서
+//! 룰이 제거되므로 별도의 만료 처리가 필요 없습니다.
+//!
+//! # 실패 처리
+//! 다운로드나 파싱에 실패하면 경고만 남기고 이전 동기화의 룰을 유지합니다.
+//! IPv6 주소와 [`MIN_CIDR_PREFIX_LEN`]보다 넓은 CIDR은 건너뜁니다.
+//!
+//! [`Blocklist::sync_feed`]: crate::blocklist::Blocklist::sync_feed
+//! [`MIN_CIDR_PREFIX_LEN`]: crate::blocklist::MIN_CIDR_PREFIX_LEN
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use ironpost_core::error::{DetectionError, IronpostError};
+
+use crate::blocklist::Ipv4Cidr;
+use crate::config::{FilterRule, RuleAction};
+
+/// 피드 룰 ID 접두사
+pub const FEED_RULE_PREFIX: &str = "feed:";
+
+/// 피드 하나에서 반영하는 최대 엔트리 수 (초과분은 건너뜀)
+pub const MAX_FEED_ENTRIES: usize = 5_000;
+
+/// 피드 최대 다운로드 크기 (바이트)
+pub const MAX_FEED_BYTES: u64 = 16 * 1024 * 1024;
+
+/// 피드 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    /// 한 줄에 IPv4 주소/CIDR 하나
+    Plain,
+    /// STIX 2.x 번들 JSON
+    Stix,
+}
+
+impl FromStr for FeedFormat {
+    type Err = IronpostError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "stix" => Ok(Self::Stix),
+            other => Err(DetectionError::Rule(format!(
+                "unknown threat feed format '{}' (expected: plain, stix)",
+                other
+            ))
+            .into()),
+        }
+    }
+}
+
+/// 파싱된 피드
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedFeed {
+    /// 중복을 제거한 IPv4 블록 (피드에 나온 순서)
+    pub cidrs: Vec<Ipv4Cidr>,
+    /// 건너뛴 엔트리 수 (IPv6, 잘못된 값, 너무 넓은 CIDR, 최대 개수 초과)
+    pub skipped: usize,
+}
+
+/// 피드 동기화 결과
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FeedSync {
+    /// 새로 추가된 룰 수
+    pub added: usize,
+    /// 피드에서 빠져 제거된 룰 수
+    pub removed: usize,
+    /// 이전 동기화와 같은 룰 수
+    pub unchanged: usize,
+}
+
+/// 피드 내용에서 IPv4 주소/CIDR을 추출합니다.
+///
+/// # Errors
+///
+/// `stix` 형식이 올바른 JSON 번들이 아니면 에러를 반환합니다. 개별 엔트리가
+/// 잘못된 경우는 에러 대신 [`ParsedFeed::skipped`]로 셉니다.
+pub fn parse_feed(content: &str, format: FeedFormat) -> Result<ParsedFeed, IronpostError> {
+    let values = match format {
+        FeedFormat::Plain => plain_values(content),
+        FeedFormat::Stix => stix_values(content)?,
+    };
+
+    let mut parsed = ParsedFeed::default();
+    let mut seen = HashSet::new();
+    for value in values {
+        match value.parse::<Ipv4Cidr>() {
+            Ok(cidr) if parsed.cidrs.len() < MAX_FEED_ENTRIES => {
+                if seen.insert(cidr) {
+                    parsed.cidrs.push(cidr);
+                }
+            }
+            _ => parsed.skipped += 1,
+        }
+    }
+    Ok(parsed)
+}
+
+/// `plain` 형식의 각 줄에서 첫 토큰을 꺼냅니다 (빈 줄과 주석 제외).
+fn plain_values(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.split(['#', ';']).next().unwrap_or_default();
+            line.split(|c: char| c.is_whitespace() || c == ',')
+                .find(|token| !token.is_empty())
+                .map(str::to_owned)
+        })
+        .collect()
+}
+
+/// STIX 번들에서 IPv4 값을 꺼냅니다.
+fn stix_values(content: &str) -> Result<Vec<String>, IronpostError> {
+    let bundle: serde_json::Value = serde_json::from_str(content).map_err(|e| {
+        DetectionError::Rule(format!("threat feed is not a valid STIX bundle: {}", e))
+    })?;
+    let objects = bundle
+        .get("objects")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| {
+            DetectionError::Rule("threat feed STIX bundle has no 'objects' array".to_owned())
+        })?;
+
+    let mut values = Vec::new();
+    for object in objects {
+        if object.get("revoked").and_then(serde_json::Value::as_bool) == Some(true) {
+            continue;
+        }
+        match object.get("type").and_then(serde_json::Value::as_str) {
+            Some("indicator") => {
+                if let Some(pattern) = object.get("pattern").and_then(serde_json::Value::as_str) {
+                    values.extend(pattern_values(pattern));
+                }
+            }
+            Some("ipv4-addr") => {
+                if let Some(value) = object.get("value").and_then(serde_json::Value::as_str) {
+                    values.push(value.to_owned());
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(values)
+}
+
+/// STIX 패턴에서 `ipv4-addr:value = '...'` 비교 값을 모두 꺼냅니다.
+fn pattern_values(pattern: &str) -> Vec<String> {
+    pattern
+        .split("ipv4-addr:value")
+        .skip(1)
+        .filter_map(|rest| {
+            let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+            let rest = rest.strip_prefix('\'')?;
+            rest.split_once('\'').map(|(value, _)| value.to_owned())
+        })
+        .collect()
+}
+
+/// 피드 룰 ID (`feed:<피드 이름>:<CIDR>`)
+pub fn feed_rule_id(feed: &str, cidr: &Ipv4Cidr) -> String {
+    format!("{}{}:{}", FEED_RULE_PREFIX, feed, cidr)
+}
+
+/// 피드 룰 ID에서 피드 이름을 꺼냅니다 (피드 룰이 아니면 `None`).
+pub fn feed_name(rule_id: &str) -> Option<&str> {
+    rule_id
+        .strip_prefix(FEED_RULE_PREFIX)?
+        .split_once(':')
+        .map(|(feed, _)| feed)
+}
+
+/// 피드의 IPv4 블록을 차단 룰로 변환합니다.
+pub fn feed_rules(feed: &str, cidrs: &[Ipv4Cidr]) -> Vec<FilterRule> {
+    cidrs
+        .iter()
+        .map(|cidr| FilterRule {
+            id: feed_rule_id(feed, cidr),
+            src_ip: cidr.is_host().then(|| cidr.addr().into()),
+            src_cidr: (!cidr.is_host()).then_some(*cidr),
+            dst_ip: None,
+            dst_port: None,
+            protocol: None,
+            action: RuleAction::Block,
+            description: format!("threat feed: {}", feed),
+        })
+        .collect()
+}
+
+/// 피드 다운로드용 HTTP 클라이언트를 생성합니다.
+///
+/// # Errors
+///
+/// TLS 백엔드를 초기화하지 못하면 에러를 반환합니다.
+pub fn http_client(timeout_secs: u64) -> Result<reqwest::Client, IronpostError> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout_secs))
+        .user_agent(concat!("ironpost/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| {
+            DetectionError::Rule(format!("failed to build threat feed HTTP client: {}", e)).into()
+        })
+}
+
+/// 피드를 내려받습니다 (`file://`은 로컬 파일을 읽음).
+///
+/// # Errors
+///
+/// 요청이 실패하거나, 응답이 [`MAX_FEED_BYTES`]를 넘거나, UTF-8이 아니면 에러를 반환합니다.
+pub async fn fetch_feed(client: &reqwest::Client, url: &str) -> Result<String, IronpostError> {
+    let bytes = match url.strip_prefix("file://") {
+        Some(path) => tokio::fs::read(path)
+            .await
+            .map_err(|e| DetectionError::Rule(format!("threat feed {}: {}", url, e)))?,
+        None => {
+            let response = client
+                .get(url)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|e| DetectionError::Rule(format!("threat feed GET {}: {}", url, e)))?;
+            if response
+                .content_length()
+                .is_some_and(|len| len > MAX_FEED_BYTES)
+            {
+                return Err(too_large(url));
+            }
+            response
+                .bytes()
+                .await
+                .map_err(|e| DetectionError::Rule(format!("threat feed {}: {}", url, e)))?
+                .to_vec()
+        }
+    };
+    if u64::try_from(bytes.len()).unwrap_or(u64::MAX) > MAX_FEED_BYTES {
+        return Err(too_large(url));
+    }
+    String::from_utf8(bytes)
+        .map_err(|_| DetectionError::Rule(format!("threat feed {} is not valid UTF-8", url)).into())
+}
+
+fn too_large(url: &str) -> IronpostError {
+    DetectionError::Rule(format!(
+        "threat feed {} exceeds the {} byte limit",
+        url, MAX_FEED_BYTES
+    ))
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cidrs(parsed: &ParsedFeed) -> Vec<String> {
+        parsed.cidrs.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_plain_feed() {
+        let content = "\
+; Spamhaus DROP List
+1.10.16.0/20 ; SBL256894
+# comment
+203.0.113.7
+203.0.113.7
+198.51.100.9, scanner
+2001:db8::1
+not-an-ip
+10.0.0.0/4
+";
+        let parsed = parse_feed(content, FeedFormat::Plain).unwrap();
+        assert_eq!(
+            cidrs(&parsed),
+            vec!["1.10.16.0/20", "203.0.113.7", "198.51.100.9"]
+        );
+        // IPv6, 잘못된 값, /8보다 넓은 CIDR
+        assert_eq!(parsed.skipped, 3);
+    }
+
+    #[test]
+    fn test_parse_stix_bundle() {
+        let content = r#"{
+            "type": "bundle",
+            "objects": [
+                {"type": "indicator", "pattern": "[ipv4-addr:value = '203.0.113.0/24'] OR [ipv4-addr:value='198.51.100.1']"},
+                {"type": "indicator", "revoked": true, "pattern": "[ipv4-addr:value = '192.0.2.1']"},
+                {"type": "indicator", "pattern": "[domain-name:value = 'evil.example']"},
+                {"type": "ipv4-addr", "value": "192.0.2.50"},
+                {"type": "ipv6-addr", "value": "2001:db8::1"}
+            ]
+        }"#;
+        let parsed = parse_feed(content, FeedFormat::Stix).unwrap();
+        assert_eq!(
+            cidrs(&parsed),
+            vec!["203.0.113.0/24", "198.51.100.1", "192.0.2.50"]
+        );
+        assert!(parse_feed("[]", FeedFormat::Stix).is_err());
+    }
+
+    #[test]
+    fn test_feed_rules_ids_round_trip() {
+        let parsed = parse_feed("203.0.113.0/24\n192.0.2.1\n", FeedFormat::Plain).unwrap();
+        let rules = feed_rules("spamhaus", &parsed.cidrs);
+        assert_eq!(rules[0].id, "feed:spamhaus:203.0.113.0/24");
+        assert!(rules[0].src_cidr.is_some() && rules[0].src_ip.is_none());
+        assert_eq!(rules[1].src_ip, Some("192.0.2.1".parse().unwrap()));
+        assert_eq!(rules[1].action, RuleAction::Block);
+        assert_eq!(feed_name(&rules[1].id), Some("spamhaus"));
+        assert_eq!(feed_name("manual-block"), None);
+        assert!("csv".parse::<FeedFormat>().is_err());
+    }
+}
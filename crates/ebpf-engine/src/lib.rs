@@ -1,34 +1,101 @@
 #![doc = include_str!("../README.md")]
 //!
 //! # 모듈 구성
+//! - [`blocklist`]: 런타임 IP/CIDR 차단 목록 (TTL, 히트 카운트)
 //! - [`config`]: 필터링 룰 관리 + core 설정 확장
+//! - [`state`]: 차단 목록 상태 파일 (재시작 후 동적 차단 복원)
+//! - [`feed`]: 위협 인텔리전스(IP 평판) 피드 다운로드/파싱 → 차단 룰 변환
 //! - [`engine`]: EbpfEngine — XDP 프로그램 로드/관리, Pipeline trait 구현
-//! - [`stats`]: 프로토콜별 트래픽 통계 (PerCpuArray 기반)
-//! - [`detector`]: SYN flood / 포트 스캔 이상 탐지 (Detector trait 구현)
+//! - [`stats`]: 프로토콜/포트/인터페이스별 트래픽 통계 (PerCpu 맵 기반)
+//! - [`tls`]: TC egress가 캡처한 TLS ClientHello에서 SNI 호스트 이름 추출
+//! - [`traffic_alert`]: 초당 패킷/바이트 수와 드롭 비율 임계치 알림
+//! - [`map_usage`]: 차단 목록 맵 점유율과 RingBuf 드롭 수 (메트릭, 헬스 체크)
+//! - [`conntrack`]: 연결 추적 통계 (상태별 흐름 수, 호스트별 초당 연결 수)
+//! - [`detector`]: SYN flood / 포트 스캔 / ICMP flood 이상 탐지, 사용자 정의 분석기 (`PacketAnalyzer`)
+//! - [`process`]: kprobe가 기록한 TCP 연결 소유 프로세스/컨테이너 연관
+//! - [`record`]: RingBuf 레코드 헤더(버전/종류/길이) 확인 후 이벤트 타입별 디코딩
+//! - [`preflight`]: 커널 버전, BTF, 권한, XDP 드라이버 지원 사전 점검
+//! - [`verify`]: 어태치 없이 커널 검증기만 통과시키는 사전 점검
 //!
 //! # 공유 타입
 //! 커널/유저스페이스 공유 타입은 [`ironpost_ebpf_common`] 크레이트에 정의되어 있습니다.
 
+pub mod blocklist;
 pub mod config;
+pub mod conntrack;
 pub mod detector;
 pub mod engine;
+pub mod feed;
+pub mod map_usage;
+pub mod preflight;
+pub mod process;
+pub mod record;
+pub mod state;
 pub mod stats;
+pub mod tls;
+pub mod traffic_alert;
+pub mod verify;
 
 // --- 주요 타입 re-export ---
 
 // 엔진
-pub use engine::{EbpfEngine, EbpfEngineBuilder};
+pub use engine::{
+    DEFAULT_EBPF_PATH, EbpfEngine, EbpfEngineBuilder, EbpfObjectSource, default_route_interface,
+    ebpf_object_path, ebpf_object_source, embedded_ebpf_object,
+};
+
+// 차단 목록
+pub use blocklist::{
+    Blocklist, BlocklistEntry, BlocklistHandle, BlocklistStatus, Ipv4Cidr, MAX_ALLOWLIST_ENTRIES,
+    MAX_BLOCKLIST_HOSTS, MAX_BLOCKLIST_PREFIXES, MIN_CIDR_PREFIX_LEN,
+};
 
 // 설정
-pub use config::{EngineConfig, FilterRule, RuleAction};
+pub use config::{EngineConfig, FilterRule, RateLimit, RuleAction, RuleImport};
+
+// 위협 인텔리전스 피드
+pub use feed::{FeedFormat, FeedSync, MAX_FEED_ENTRIES, ParsedFeed, feed_rules, parse_feed};
+
+// 상태 파일
+pub use state::{BlocklistSnapshot, STATE_FILE_VERSION};
 
 // 통계
-pub use stats::{ProtoMetrics, RawProtoStats, RawTrafficSnapshot, TrafficStats};
+pub use stats::{
+    InterfaceMetrics, PortMetrics, ProtoMetrics, RawInterfaceStats, RawPortStats, RawProtoStats,
+    RawTrafficSnapshot, TOP_PORTS_LIMIT, TrafficStats,
+};
+
+// 맵 사용량
+pub use map_usage::{
+    MAP_FULL_PERCENT, MapUsage, MapUsageReport, RINGBUF_DROP_WINDOW, RingBufDrops,
+};
+
+// 연결 추적
+pub use conntrack::{ConnTrackStats, HostConnRate, RawFlow, TOP_HOSTS_LIMIT};
 
 // 탐지
 pub use detector::{
-    PacketDetector, PortScanConfig, PortScanDetector, SynFloodConfig, SynFloodDetector,
+    AutoBlockRequest, IcmpFloodConfig, IcmpFloodDetector, PacketAnalyzer, PacketDetector,
+    PortScanConfig, PortScanDetector, SynFloodConfig, SynFloodDetector,
 };
 
+// 사전 점검
+pub use preflight::{PreflightCheck, PreflightReport, PreflightStatus};
+
+// 프로세스 연관
+pub use process::{ProcessInfo, ProcessResolver, container_id_from_cgroup};
+
+// RingBuf 레코드
+pub use record::{RingEvent, decode_record};
+
+// TLS SNI
+pub use tls::client_hello_sni;
+
+// 트래픽 임계치 알림
+pub use traffic_alert::{TrafficAlerter, TrafficThresholds};
+
+// 검증기 점검
+pub use verify::{ProgramVerification, verify_object};
+
 // 공유 타입 (커널/유저스페이스 공통)
 pub use ironpost_ebpf_common;
@@ -0,0 +1,216 @@
+//! 맵 사용량 — 차단 목록 점유율, 맵 크기, RingBuf 드롭 수
+//!
+//! 통계 폴러가 매 폴링마다 [`MapUsageReport`]를 갱신하고 Prometheus 메트릭
+//! (`ironpost_ebpf_map_entries`, `ironpost_ebpf_map_capacity`,
+//! `ironpost_ebpf_ringbuf_drops_total`)을 기록합니다. 차단 목록 맵의 엔트리 수는
+//! [`Blocklist::map_usage`](crate::blocklist::Blocklist::map_usage)가 마지막으로
+//! 동기화한 목표 상태에서 가져오고, RingBuf 드롭 수는 커널이 `reserve`에 실패할 때마다
+//! 올리는 `RINGBUF_DROPS` 카운터에서 읽습니다.
+//!
+//! # 헬스 체크
+//! 엔진의 `health_check`는 [`MapUsageReport::degraded_reasons`]가 비어 있지 않으면
+//! `Degraded`를 반환합니다.
+//! - 맵이 [`MAP_FULL_PERCENT`]% 이상 차 있으면 곧 새 차단 엔트리가 거부됩니다.
+//! - 최근 [`RINGBUF_DROP_WINDOW`] 안에 RingBuf 드롭이 있었으면 이벤트를 잃고 있는 것입니다.
+//!
+//! # 리로드
+//! `RINGBUF_DROPS`는 고정(pin)하지 않으므로 리로드하면 커널 카운터가 0부터 다시 시작합니다.
+//! 직전 값보다 작은 값을 읽으면 카운터가 초기화된 것으로 보고 증가분만 누적합니다.
+
+use std::time::{Duration, Instant};
+
+use ironpost_core::metrics as m;
+
+/// 헬스 체크가 `Degraded`로 보는 맵 점유율 (퍼센트)
+pub const MAP_FULL_PERCENT: usize = 90;
+
+/// RingBuf 드롭 후 헬스 체크가 `Degraded`를 유지하는 시간
+pub const RINGBUF_DROP_WINDOW: Duration = Duration::from_secs(60);
+
+/// 맵 하나의 엔트리 수와 최대 크기
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapUsage {
+    /// 맵 이름 (예: `BLOCKLIST`)
+    pub map: &'static str,
+    /// 반영된 엔트리 수
+    pub entries: usize,
+    /// 최대 엔트리 수
+    pub capacity: usize,
+}
+
+impl MapUsage {
+    /// 점유율 (퍼센트, 내림)
+    pub fn percent(&self) -> usize {
+        if self.capacity == 0 {
+            return 0;
+        }
+        self.entries.saturating_mul(100) / self.capacity
+    }
+
+    /// [`MAP_FULL_PERCENT`] 이상 찼는지 확인합니다.
+    pub fn is_full(&self) -> bool {
+        self.percent() >= MAP_FULL_PERCENT
+    }
+}
+
+/// RingBuf 하나의 드롭 카운터
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingBufDrops {
+    /// 맵 이름 (예: `EVENTS`)
+    pub map: &'static str,
+    /// 엔진 시작 이후 누적 드롭 수 (리로드 후에도 이어서 셈)
+    pub total: u64,
+    /// 마지막으로 읽은 커널 카운터 값
+    kernel: u64,
+    /// 마지막으로 드롭이 늘어난 시각
+    last_drop: Option<Instant>,
+}
+
+impl RingBufDrops {
+    /// [`RINGBUF_DROP_WINDOW`] 안에 드롭이 있었는지 확인합니다.
+    pub fn dropped_recently(&self, now: Instant) -> bool {
+        self.last_drop
+            .is_some_and(|last| now.duration_since(last) < RINGBUF_DROP_WINDOW)
+    }
+}
+
+/// 맵 사용량 보고서
+///
+/// 통계 폴러가 갱신하고, 헬스 체크와 `EbpfEngine::map_usage()` 호출자가 읽습니다.
+#[derive(Debug, Clone, Default)]
+pub struct MapUsageReport {
+    /// 크기가 정해진 맵별 사용량
+    pub maps: Vec<MapUsage>,
+    /// RingBuf별 드롭 카운터
+    pub ringbufs: Vec<RingBufDrops>,
+}
+
+impl MapUsageReport {
+    /// 빈 보고서를 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 맵별 사용량을 교체하고 게이지 메트릭을 기록합니다.
+    pub fn update_maps(&mut self, maps: Vec<MapUsage>) {
+        for usage in &maps {
+            metrics::gauge!(m::EBPF_MAP_ENTRIES, m::LABEL_MAP => usage.map)
+                .set(usage.entries as f64);
+            metrics::gauge!(m::EBPF_MAP_CAPACITY, m::LABEL_MAP => usage.map)
+                .set(usage.capacity as f64);
+        }
+        self.maps = maps;
+    }
+
+    /// 커널 드롭 카운터 값을 반영하고, 늘어난 만큼 카운터 메트릭을 올립니다.
+    pub fn record_ringbuf_drops(&mut self, map: &'static str, kernel_drops: u64, now: Instant) {
+        let index = match self.ringbufs.iter().position(|r| r.map == map) {
+            Some(index) => index,
+            None => {
+                self.ringbufs.push(RingBufDrops {
+                    map,
+                    total: 0,
+                    kernel: 0,
+                    last_drop: None,
+                });
+                self.ringbufs.len() - 1
+            }
+        };
+        let ringbuf = &mut self.ringbufs[index];
+
+        // 직전 값보다 작으면 리로드로 커널 카운터가 초기화된 것
+        let delta = kernel_drops
+            .checked_sub(ringbuf.kernel)
+            .unwrap_or(kernel_drops);
+        ringbuf.kernel = kernel_drops;
+        if delta > 0 {
+            ringbuf.total = ringbuf.total.saturating_add(delta);
+            ringbuf.last_drop = Some(now);
+            metrics::counter!(m::EBPF_RINGBUF_DROPS_TOTAL, m::LABEL_MAP => map).increment(delta);
+        }
+    }
+
+    /// 헬스 체크를 `Degraded`로 만드는 사유 목록 (정상이면 비어 있음)
+    pub fn degraded_reasons(&self, now: Instant) -> Vec<String> {
+        let full = self
+            .maps
+            .iter()
+            .filter(|usage| usage.is_full())
+            .map(|usage| {
+                format!(
+                    "{} map {}% full ({}/{})",
+                    usage.map,
+                    usage.percent(),
+                    usage.entries,
+                    usage.capacity
+                )
+            });
+        let dropping = self
+            .ringbufs
+            .iter()
+            .filter(|ringbuf| ringbuf.dropped_recently(now))
+            .map(|ringbuf| {
+                format!(
+                    "{} ring buffer dropping records ({} dropped)",
+                    ringbuf.map, ringbuf.total
+                )
+            });
+        full.chain(dropping).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_usage_full_threshold() {
+        let usage = |entries| MapUsage {
+            map: "BLOCKLIST",
+            entries,
+            capacity: 10_000,
+        };
+        assert_eq!(usage(8_999).percent(), 89);
+        assert!(!usage(8_999).is_full());
+        assert!(usage(9_000).is_full());
+
+        let mut report = MapUsageReport::new();
+        report.update_maps(vec![usage(100), usage(10_000)]);
+        assert_eq!(
+            report.degraded_reasons(Instant::now()),
+            vec!["BLOCKLIST map 100% full (10000/10000)"]
+        );
+
+        let empty = MapUsage {
+            map: "PORT_RULES",
+            entries: 0,
+            capacity: 0,
+        };
+        assert!(!empty.is_full());
+    }
+
+    #[test]
+    fn test_ringbuf_drops_accumulate_across_reset_and_expire() {
+        let mut report = MapUsageReport::new();
+        let start = Instant::now();
+
+        report.record_ringbuf_drops("EVENTS", 0, start);
+        assert!(report.degraded_reasons(start).is_empty());
+
+        report.record_ringbuf_drops("EVENTS", 5, start);
+        report.record_ringbuf_drops("EVENTS", 8, start);
+        // 리로드로 커널 카운터가 초기화됨
+        report.record_ringbuf_drops("EVENTS", 2, start);
+        assert_eq!(report.ringbufs[0].total, 10);
+        assert_eq!(
+            report.degraded_reasons(start),
+            vec!["EVENTS ring buffer dropping records (10 dropped)"]
+        );
+
+        // 드롭이 멈춘 뒤 창이 지나면 정상으로 돌아옴
+        let later = start + RINGBUF_DROP_WINDOW;
+        report.record_ringbuf_drops("EVENTS", 2, later);
+        assert!(report.degraded_reasons(later).is_empty());
+        assert_eq!(report.ringbufs.len(), 1);
+    }
+}
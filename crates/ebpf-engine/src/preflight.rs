@@ -0,0 +1,460 @@
+//! 커널 기능 사전 점검 — XDP 어태치 전에 실행 환경을 진단합니다
+//!
+//! [`EbpfEngine::start`](crate::EbpfEngine)는 eBPF 오브젝트를 로드하기 전에
+//! 다음 항목을 점검하고, 하나라도 실패하면 aya의 불투명한 로드 에러 대신
+//! 실패한 항목을 나열한 [`DetectionError::Preflight`] 에러를 반환합니다.
+//!
+//! - `kernel`: 커널 버전 ([`MIN_KERNEL_VERSION`] 이상, RingBuf + XDP)
+//! - `btf`: 커널 BTF(`/sys/kernel/btf/vmlinux`) 제공 여부 (없으면 경고)
+//! - `capabilities`: `CAP_BPF` + `CAP_NET_ADMIN` (또는 `CAP_SYS_ADMIN`)
+//! - `xdp:<인터페이스>`: 인터페이스 존재 여부와 선택한 XDP 모드의 드라이버 지원
+//!
+//! 드라이버 지원은 `/sys/class/net/<인터페이스>/device/driver`의 드라이버 이름으로
+//! 판단합니다. `skb` 모드는 모든 인터페이스에서 동작하고, `native` 모드는 알려진
+//! 드라이버가 아니면 경고만 남기며, `hw` 모드는 오프로드를 지원하는 드라이버가
+//! 아니면 실패로 처리합니다.
+
+use std::fmt;
+use std::path::Path;
+
+use serde::Serialize;
+
+use ironpost_core::error::{DetectionError, IronpostError};
+
+/// eBPF 엔진이 요구하는 최소 커널 버전 (RingBuf + XDP)
+pub const MIN_KERNEL_VERSION: (u32, u32) = (5, 7);
+
+/// Linux capability 번호 (`linux/capability.h`)
+const CAP_NET_ADMIN: u32 = 12;
+const CAP_SYS_ADMIN: u32 = 21;
+const CAP_BPF: u32 = 39;
+
+/// 네이티브(드라이버) XDP를 지원하는 것으로 알려진 드라이버
+const NATIVE_XDP_DRIVERS: &[&str] = &[
+    "bnxt_en",
+    "dpaa2-eth",
+    "ena",
+    "enetc",
+    "fec",
+    "hv_netvsc",
+    "i40e",
+    "ice",
+    "igb",
+    "igc",
+    "ixgbe",
+    "ixgbevf",
+    "mlx4_core",
+    "mlx5_core",
+    "mvneta",
+    "mvpp2",
+    "nfp",
+    "qede",
+    "sfc",
+    "stmmac",
+    "thunder-nicvf",
+    "tun",
+    "veth",
+    "virtio_net",
+];
+
+/// XDP 하드웨어 오프로드를 지원하는 드라이버
+const OFFLOAD_XDP_DRIVERS: &[&str] = &["nfp"];
+
+/// 점검 결과 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreflightStatus {
+    /// 통과
+    Pass,
+    /// 시작은 가능하지만 확인이 필요함
+    Warn,
+    /// 이 상태로는 시작할 수 없음
+    Fail,
+}
+
+/// 점검 항목 하나의 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PreflightCheck {
+    /// 점검 이름 (`kernel`, `btf`, `capabilities`, `xdp:<인터페이스>`)
+    pub name: String,
+    /// 결과 상태
+    pub status: PreflightStatus,
+    /// 사람이 읽을 수 있는 설명
+    pub detail: String,
+}
+
+impl PreflightCheck {
+    fn new(name: impl Into<String>, status: PreflightStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+impl fmt::Display for PreflightCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.detail)
+    }
+}
+
+/// 사전 점검 결과 모음
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PreflightReport {
+    /// 점검 순서대로 나열한 결과
+    pub checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    /// 실패한 점검 항목
+    pub fn failures(&self) -> impl Iterator<Item = &PreflightCheck> {
+        self.checks
+            .iter()
+            .filter(|check| check.status == PreflightStatus::Fail)
+    }
+
+    /// 경고 항목
+    pub fn warnings(&self) -> impl Iterator<Item = &PreflightCheck> {
+        self.checks
+            .iter()
+            .filter(|check| check.status == PreflightStatus::Warn)
+    }
+
+    /// 실패한 항목이 없는지 확인합니다.
+    pub fn is_ok(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// 실패한 항목이 있으면 [`DetectionError::Preflight`]로 변환합니다.
+    pub fn into_result(self) -> Result<Self, IronpostError> {
+        let failures: Vec<String> = self.failures().map(ToString::to_string).collect();
+        if failures.is_empty() {
+            Ok(self)
+        } else {
+            Err(DetectionError::Preflight(failures).into())
+        }
+    }
+}
+
+/// 실행 중인 시스템(`/proc`, `/sys`)을 점검합니다.
+///
+/// `interfaces`는 어태치할 인터페이스 이름 목록입니다. `attach_all`이면
+/// (`interface = "all"`) 엔진이 XDP를 지원하지 않는 인터페이스를 건너뛰므로
+/// 인터페이스별 실패를 경고로 낮춥니다.
+pub fn run(xdp_mode: &str, interfaces: &[String], attach_all: bool) -> PreflightReport {
+    probe(
+        Path::new("/proc"),
+        Path::new("/sys"),
+        xdp_mode,
+        interfaces,
+        attach_all,
+    )
+}
+
+/// `proc_root`/`sys_root` 아래의 파일로 점검합니다 (테스트에서 가짜 루트를 넘김).
+fn probe(
+    proc_root: &Path,
+    sys_root: &Path,
+    xdp_mode: &str,
+    interfaces: &[String],
+    attach_all: bool,
+) -> PreflightReport {
+    let mut checks = vec![
+        check_kernel(proc_root),
+        check_btf(sys_root),
+        check_capabilities(proc_root),
+    ];
+    for name in interfaces {
+        let mut check = check_interface(sys_root, name, xdp_mode);
+        if attach_all && check.status == PreflightStatus::Fail {
+            check.status = PreflightStatus::Warn;
+        }
+        checks.push(check);
+    }
+    PreflightReport { checks }
+}
+
+fn check_kernel(proc_root: &Path) -> PreflightCheck {
+    const NAME: &str = "kernel";
+    let release = match std::fs::read_to_string(proc_root.join("sys/kernel/osrelease")) {
+        Ok(release) => release,
+        Err(e) => {
+            return PreflightCheck::new(
+                NAME,
+                PreflightStatus::Fail,
+                format!("cannot read kernel release: {}", e),
+            );
+        }
+    };
+    let release = release.trim();
+    match parse_kernel_version(release) {
+        Some(version) if version >= MIN_KERNEL_VERSION => {
+            PreflightCheck::new(NAME, PreflightStatus::Pass, release)
+        }
+        Some(_) => PreflightCheck::new(
+            NAME,
+            PreflightStatus::Fail,
+            format!(
+                "{} is older than the required {}.{}",
+                release, MIN_KERNEL_VERSION.0, MIN_KERNEL_VERSION.1
+            ),
+        ),
+        None => PreflightCheck::new(
+            NAME,
+            PreflightStatus::Warn,
+            format!("cannot parse kernel release '{}'", release),
+        ),
+    }
+}
+
+fn check_btf(sys_root: &Path) -> PreflightCheck {
+    const NAME: &str = "btf";
+    if sys_root.join("kernel/btf/vmlinux").exists() {
+        PreflightCheck::new(NAME, PreflightStatus::Pass, "kernel BTF available")
+    } else {
+        PreflightCheck::new(
+            NAME,
+            PreflightStatus::Warn,
+            "kernel BTF not available (/sys/kernel/btf/vmlinux); verifier errors may be less detailed",
+        )
+    }
+}
+
+fn check_capabilities(proc_root: &Path) -> PreflightCheck {
+    const NAME: &str = "capabilities";
+    let caps = std::fs::read_to_string(proc_root.join("self/status"))
+        .ok()
+        .and_then(|status| parse_cap_eff(&status));
+    match caps {
+        Some(caps) if has_bpf_capabilities(caps) => PreflightCheck::new(
+            NAME,
+            PreflightStatus::Pass,
+            "CAP_BPF + CAP_NET_ADMIN (or CAP_SYS_ADMIN) available",
+        ),
+        Some(_) => PreflightCheck::new(
+            NAME,
+            PreflightStatus::Fail,
+            "missing CAP_BPF and CAP_NET_ADMIN (or CAP_SYS_ADMIN); run as root or grant capabilities",
+        ),
+        None => PreflightCheck::new(
+            NAME,
+            PreflightStatus::Warn,
+            "cannot read effective capabilities",
+        ),
+    }
+}
+
+fn check_interface(sys_root: &Path, name: &str, xdp_mode: &str) -> PreflightCheck {
+    let check_name = format!("xdp:{}", name);
+    let iface = sys_root.join("class/net").join(name);
+    if name.is_empty() || name.contains('/') || !iface.join("ifindex").exists() {
+        return PreflightCheck::new(check_name, PreflightStatus::Fail, "interface not found");
+    }
+
+    let driver = std::fs::read_link(iface.join("device/driver"))
+        .ok()
+        .and_then(|target| target.file_name()?.to_str().map(str::to_owned));
+    let driver_label = driver.as_deref().unwrap_or("virtual");
+
+    match xdp_mode {
+        "native" | "drv" => match driver.as_deref() {
+            Some(driver) if NATIVE_XDP_DRIVERS.contains(&driver) => PreflightCheck::new(
+                check_name,
+                PreflightStatus::Pass,
+                format!("native XDP supported by driver '{}'", driver),
+            ),
+            _ => PreflightCheck::new(
+                check_name,
+                PreflightStatus::Warn,
+                format!(
+                    "driver '{}' is not known to support native XDP; use xdp_mode = \"skb\" if attach fails",
+                    driver_label
+                ),
+            ),
+        },
+        "hw" => match driver.as_deref() {
+            Some(driver) if OFFLOAD_XDP_DRIVERS.contains(&driver) => PreflightCheck::new(
+                check_name,
+                PreflightStatus::Pass,
+                format!("XDP offload supported by driver '{}'", driver),
+            ),
+            _ => PreflightCheck::new(
+                check_name,
+                PreflightStatus::Fail,
+                format!(
+                    "driver '{}' does not support XDP hardware offload",
+                    driver_label
+                ),
+            ),
+        },
+        _ => PreflightCheck::new(
+            check_name,
+            PreflightStatus::Pass,
+            format!("generic (skb) XDP, driver '{}'", driver_label),
+        ),
+    }
+}
+
+/// 커널 릴리스 문자열에서 `major.minor`를 파싱합니다 (예: `6.1.0-18-amd64`).
+pub fn parse_kernel_version(release: &str) -> Option<(u32, u32)> {
+    let mut parts = release.split(['.', '-']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// `/proc/<pid>/status`에서 유효 capability 집합(`CapEff`)을 파싱합니다.
+pub fn parse_cap_eff(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("CapEff:"))
+        .and_then(|hex| u64::from_str_radix(hex.trim(), 16).ok())
+}
+
+/// capability 집합으로 XDP 프로그램을 로드/어태치할 수 있는지 확인합니다.
+pub fn has_bpf_capabilities(caps: u64) -> bool {
+    let has = |cap: u32| caps & (1u64 << cap) != 0;
+    has(CAP_SYS_ADMIN) || (has(CAP_BPF) && has(CAP_NET_ADMIN))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// 가짜 `/proc`, `/sys` 루트를 만듭니다.
+    fn fake_roots(
+        release: &str,
+        cap_eff: &str,
+        btf: bool,
+    ) -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let proc_root = dir.path().join("proc");
+        let sys_root = dir.path().join("sys");
+        std::fs::create_dir_all(proc_root.join("sys/kernel")).unwrap();
+        std::fs::create_dir_all(proc_root.join("self")).unwrap();
+        std::fs::write(
+            proc_root.join("sys/kernel/osrelease"),
+            format!("{release}\n"),
+        )
+        .unwrap();
+        std::fs::write(
+            proc_root.join("self/status"),
+            format!("Name:\tironpost\nCapEff:\t{cap_eff}\n"),
+        )
+        .unwrap();
+        std::fs::create_dir_all(sys_root.join("kernel/btf")).unwrap();
+        if btf {
+            std::fs::write(sys_root.join("kernel/btf/vmlinux"), b"").unwrap();
+        }
+        (dir, proc_root, sys_root)
+    }
+
+    fn add_interface(sys_root: &Path, name: &str, driver: Option<&str>) {
+        let iface = sys_root.join("class/net").join(name);
+        std::fs::create_dir_all(&iface).unwrap();
+        std::fs::write(iface.join("ifindex"), "2\n").unwrap();
+        if let Some(driver) = driver {
+            let driver_dir = sys_root.join("bus/pci/drivers").join(driver);
+            std::fs::create_dir_all(&driver_dir).unwrap();
+            std::fs::create_dir_all(iface.join("device")).unwrap();
+            std::os::unix::fs::symlink(&driver_dir, iface.join("device/driver")).unwrap();
+        }
+    }
+
+    fn status_of(report: &PreflightReport, name: &str) -> PreflightStatus {
+        report
+            .checks
+            .iter()
+            .find(|check| check.name == name)
+            .map(|check| check.status)
+            .unwrap_or_else(|| panic!("missing check {name}"))
+    }
+
+    #[test]
+    fn test_parse_kernel_version_and_capabilities() {
+        assert_eq!(parse_kernel_version("6.1.0-18-amd64"), Some((6, 1)));
+        assert_eq!(parse_kernel_version("5.15.133.1-microsoft"), Some((5, 15)));
+        assert_eq!(parse_kernel_version("garbage"), None);
+
+        let status = "Name:\tironpost\nCapEff:\t000001ffffffffff\n";
+        assert!(has_bpf_capabilities(parse_cap_eff(status).unwrap()));
+        assert!(has_bpf_capabilities(
+            (1u64 << CAP_BPF) | (1u64 << CAP_NET_ADMIN)
+        ));
+        assert!(!has_bpf_capabilities(1u64 << CAP_BPF));
+    }
+
+    #[test]
+    fn test_probe_passes_on_capable_host() {
+        let (_dir, proc_root, sys_root) = fake_roots("6.1.0-18-amd64", "000001ffffffffff", true);
+        add_interface(&sys_root, "eth0", Some("ixgbe"));
+
+        let report = probe(&proc_root, &sys_root, "native", &["eth0".to_owned()], false);
+
+        assert!(report.is_ok());
+        assert_eq!(report.warnings().count(), 0);
+        assert_eq!(status_of(&report, "xdp:eth0"), PreflightStatus::Pass);
+        assert!(report.into_result().is_ok());
+    }
+
+    #[test]
+    fn test_probe_reports_structured_failures() {
+        let (_dir, proc_root, sys_root) = fake_roots("4.19.0-21-amd64", "0000000000000000", false);
+        add_interface(&sys_root, "eth0", Some("e1000"));
+
+        let report = probe(
+            &proc_root,
+            &sys_root,
+            "hw",
+            &["eth0".to_owned(), "eth9".to_owned()],
+            false,
+        );
+
+        assert_eq!(status_of(&report, "kernel"), PreflightStatus::Fail);
+        assert_eq!(status_of(&report, "btf"), PreflightStatus::Warn);
+        assert_eq!(status_of(&report, "capabilities"), PreflightStatus::Fail);
+        assert_eq!(status_of(&report, "xdp:eth0"), PreflightStatus::Fail);
+        assert_eq!(status_of(&report, "xdp:eth9"), PreflightStatus::Fail);
+
+        let err = report.into_result().unwrap_err();
+        match err {
+            IronpostError::Detection(DetectionError::Preflight(failures)) => {
+                assert_eq!(failures.len(), 4);
+                assert!(failures[0].starts_with("kernel: 4.19.0-21-amd64 is older"));
+                assert_eq!(failures[3], "xdp:eth9: interface not found");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn test_probe_downgrades_interface_failures_when_attaching_all() {
+        let (_dir, proc_root, sys_root) = fake_roots("6.8.0", "000001ffffffffff", true);
+        add_interface(&sys_root, "eth0", Some("e1000"));
+        add_interface(&sys_root, "veth0", None);
+
+        let report = probe(
+            &proc_root,
+            &sys_root,
+            "hw",
+            &["eth0".to_owned(), "veth0".to_owned()],
+            true,
+        );
+        assert!(report.is_ok());
+        assert_eq!(report.warnings().count(), 2);
+
+        // 네이티브 모드에서 알 수 없는 드라이버/가상 인터페이스는 경고만
+        let report = probe(
+            &proc_root,
+            &sys_root,
+            "native",
+            &["veth0".to_owned()],
+            false,
+        );
+        assert_eq!(status_of(&report, "xdp:veth0"), PreflightStatus::Warn);
+        let report = probe(&proc_root, &sys_root, "skb", &["eth0".to_owned()], false);
+        assert_eq!(status_of(&report, "xdp:eth0"), PreflightStatus::Pass);
+    }
+}
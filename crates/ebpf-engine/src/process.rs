@@ -0,0 +1,215 @@
+//! 프로세스 연관 — 패킷 이벤트의 TCP 연결을 소유 프로세스/컨테이너와 연결
+//!
+//! kprobe 프로그램(`tcp_connect`, `inet_csk_accept`)이 `SOCK_OWNERS` 맵에 연결별
+//! [`SockOwner`]를 기록하면, 이벤트 수신 태스크가 패킷 이벤트의 5-튜플로 조회해
+//! [`ProcessInfo`]로 변환합니다. 탐지기는 이 정보를 알림 설명에 덧붙여 어떤
+//! 프로세스나 컨테이너의 트래픽인지 보여줍니다.
+//!
+//! # 컨테이너 식별
+//! cgroup ID만으로는 컨테이너를 알 수 없으므로 `/proc/<pid>/cgroup` 경로에서 64자리
+//! 16진수 컨테이너 ID(Docker, containerd, CRI-O, Podman)를 찾습니다. cgroup은 프로세스가
+//! 살아 있는 동안 바뀌지 않으므로 결과를 cgroup ID별로 캐시합니다.
+//!
+//! # 한계
+//! - 연결이 맺어진 뒤에 기록되므로 핸드셰이크 전 패킷(SYN flood 등)은 소유자가 없습니다.
+//! - UDP는 연결 개념이 없어 기록하지 않습니다.
+//! - 엔진 리로드 후에는 새로 맺어진 연결부터 다시 기록됩니다.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use ironpost_ebpf_common::SockOwner;
+
+/// 컨테이너 ID 캐시 최대 크기 (초과 시 비움)
+pub const CONTAINER_CACHE_LIMIT: usize = 4_096;
+
+/// 컨테이너 ID 길이 (16진수 문자 수)
+const CONTAINER_ID_LEN: usize = 64;
+
+/// 표시용으로 줄인 컨테이너 ID 길이
+const SHORT_CONTAINER_ID_LEN: usize = 12;
+
+/// 런타임이 cgroup 경로 요소에 붙이는 접두사
+const CGROUP_SCOPE_PREFIXES: [&str; 4] = ["docker-", "cri-containerd-", "crio-", "libpod-"];
+
+/// TCP 연결을 소유한 프로세스
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessInfo {
+    /// 프로세스 ID
+    pub pid: u32,
+    /// 프로세스 이름 (커널 `comm`, 최대 15자)
+    pub comm: String,
+    /// cgroup v2 ID
+    pub cgroup_id: u64,
+    /// 컨테이너 ID (컨테이너 밖의 프로세스이거나 이미 종료되었으면 `None`)
+    pub container_id: Option<String>,
+}
+
+impl fmt::Display for ProcessInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (pid {}", self.comm, self.pid)?;
+        if let Some(ref id) = self.container_id {
+            let short = id.get(..SHORT_CONTAINER_ID_LEN).unwrap_or(id);
+            write!(f, ", container {}", short)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// [`SockOwner`]를 [`ProcessInfo`]로 변환하며 cgroup별 컨테이너 ID를 캐시합니다.
+///
+/// 이벤트 수신 태스크 하나가 소유합니다.
+#[derive(Debug)]
+pub struct ProcessResolver {
+    /// procfs 루트 (기본: `/proc`)
+    proc_root: PathBuf,
+    /// cgroup ID별 컨테이너 ID
+    containers: HashMap<u64, Option<String>>,
+}
+
+impl ProcessResolver {
+    /// 지정한 procfs 루트를 읽는 변환기를 생성합니다.
+    pub fn new(proc_root: impl Into<PathBuf>) -> Self {
+        Self {
+            proc_root: proc_root.into(),
+            containers: HashMap::new(),
+        }
+    }
+
+    /// 커널이 기록한 소유자를 프로세스 정보로 변환합니다.
+    pub fn resolve(&mut self, owner: &SockOwner) -> ProcessInfo {
+        if self.containers.len() >= CONTAINER_CACHE_LIMIT
+            && !self.containers.contains_key(&owner.cgroup_id)
+        {
+            self.containers.clear();
+        }
+        let container_id = match self.containers.get(&owner.cgroup_id) {
+            Some(cached) => cached.clone(),
+            None => {
+                // 프로세스가 이미 종료되어 읽지 못하면 같은 cgroup의 다음 조회를 위해 캐시하지 않음
+                let path = self.proc_root.join(owner.pid.to_string()).join("cgroup");
+                match std::fs::read_to_string(path) {
+                    Ok(content) => {
+                        let id = container_id_from_cgroup(&content);
+                        self.containers.insert(owner.cgroup_id, id.clone());
+                        id
+                    }
+                    Err(_) => None,
+                }
+            }
+        };
+
+        ProcessInfo {
+            pid: owner.pid,
+            comm: comm_to_string(&owner.comm),
+            cgroup_id: owner.cgroup_id,
+            container_id,
+        }
+    }
+}
+
+impl Default for ProcessResolver {
+    fn default() -> Self {
+        Self::new("/proc")
+    }
+}
+
+/// NUL로 끝나는 커널 `comm` 바이트를 문자열로 변환합니다.
+fn comm_to_string(comm: &[u8]) -> String {
+    let len = comm.iter().position(|&b| b == 0).unwrap_or(comm.len());
+    String::from_utf8_lossy(&comm[..len]).into_owned()
+}
+
+/// `/proc/<pid>/cgroup` 내용에서 컨테이너 ID를 찾습니다.
+///
+/// 각 줄의 cgroup 경로를 뒤에서부터 살펴, 런타임 접두사와 `.scope` 접미사를 뗀
+/// 요소가 64자리 16진수이면 컨테이너 ID로 봅니다.
+///
+/// ```text
+/// 0::/system.slice/docker-<id>.scope
+/// 0::/kubepods/besteffort/pod<uid>/cri-containerd-<id>.scope
+/// 12:pids:/docker/<id>
+/// ```
+pub fn container_id_from_cgroup(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let path = line.splitn(3, ':').nth(2)?;
+        path.rsplit('/').find_map(|segment| {
+            let segment = segment.strip_suffix(".scope").unwrap_or(segment);
+            let id = CGROUP_SCOPE_PREFIXES
+                .iter()
+                .find_map(|prefix| segment.strip_prefix(prefix))
+                .unwrap_or(segment);
+            (id.len() == CONTAINER_ID_LEN && id.bytes().all(|b| b.is_ascii_hexdigit()))
+                .then(|| id.to_owned())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "4f1b2a6c9d3e8f7a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a";
+
+    fn owner(pid: u32, cgroup_id: u64, comm: &[u8]) -> SockOwner {
+        let mut buf = [0u8; 16];
+        buf[..comm.len()].copy_from_slice(comm);
+        SockOwner {
+            pid,
+            _pad: 0,
+            cgroup_id,
+            comm: buf,
+        }
+    }
+
+    #[test]
+    fn test_container_id_from_cgroup_runtimes() {
+        for content in [
+            format!("0::/system.slice/docker-{}.scope\n", ID),
+            format!(
+                "0::/kubepods.slice/kubepods-besteffort.slice/kubepods-besteffort-pod1.slice/cri-containerd-{}.scope\n",
+                ID
+            ),
+            format!("12:pids:/docker/{}\n11:cpu:/docker/{}\n", ID, ID),
+            format!("0::/machine.slice/libpod-{}.scope/container\n", ID),
+        ] {
+            assert_eq!(container_id_from_cgroup(&content).as_deref(), Some(ID));
+        }
+
+        assert_eq!(
+            container_id_from_cgroup("0::/user.slice/session-1.scope\n"),
+            None
+        );
+        assert_eq!(container_id_from_cgroup("0::/\n"), None);
+        assert_eq!(container_id_from_cgroup(""), None);
+    }
+
+    #[test]
+    fn test_resolver_reads_proc_and_caches_by_cgroup() {
+        let proc_root = tempfile::tempdir().unwrap();
+        let pid_dir = proc_root.path().join("4242");
+        std::fs::create_dir(&pid_dir).unwrap();
+        std::fs::write(
+            pid_dir.join("cgroup"),
+            format!("0::/system.slice/docker-{}.scope\n", ID),
+        )
+        .unwrap();
+
+        let mut resolver = ProcessResolver::new(proc_root.path());
+        let info = resolver.resolve(&owner(4242, 7, b"nginx"));
+        assert_eq!(info.comm, "nginx");
+        assert_eq!(info.container_id.as_deref(), Some(ID));
+        assert_eq!(info.to_string(), "nginx (pid 4242, container 4f1b2a6c9d3e)");
+
+        // 같은 cgroup의 다른 프로세스는 /proc을 다시 읽지 않음
+        std::fs::remove_dir_all(&pid_dir).unwrap();
+        let worker = resolver.resolve(&owner(4243, 7, b"nginx"));
+        assert_eq!(worker.container_id.as_deref(), Some(ID));
+
+        // 종료된 호스트 프로세스
+        let gone = resolver.resolve(&owner(1, 8, b"curl"));
+        assert_eq!(gone.container_id, None);
+        assert_eq!(gone.to_string(), "curl (pid 1)");
+    }
+}
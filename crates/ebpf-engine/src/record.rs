@@ -0,0 +1,181 @@
+//! RingBuf 레코드 디코딩 — 헤더의 버전/종류로 본문 타입을 골라 해석
+//!
+//! 커널 프로그램은 모든 RingBuf 레코드를 [`EventRecord`]로 감싸 [`EventHeader`]
+//! (버전, 이벤트 종류, 전체 길이)를 앞에 붙입니다. 수신 태스크는 [`decode_record`]로
+//! 헤더를 먼저 확인한 뒤 종류에 맞는 [`RingEvent`]로 변환하므로, 새 이벤트 종류는
+//! `EVENT_TYPE_*` 상수와 변형 하나를 추가하는 것으로 늘릴 수 있습니다.
+//!
+//! # 호환성
+//! 헤더의 버전이 [`EVENT_FORMAT_VERSION`]과 다르거나, 길이가 알려진 본문 크기와 맞지
+//! 않는 레코드는 에러로 돌려줍니다. 수신 태스크는 경고만 남기고 건너뛰므로, 다른
+//! 버전으로 빌드한 커널 오브젝트를 로드해도 잘못된 값이 탐지기로 전달되지 않습니다.
+
+use std::mem::size_of;
+
+use ironpost_core::error::{DetectionError, IronpostError};
+use ironpost_ebpf_common::{
+    EVENT_FORMAT_VERSION, EVENT_TYPE_PACKET, EVENT_TYPE_TLS_HELLO, EventHeader, EventRecord,
+    PacketEventData, TlsHelloData,
+};
+
+/// 헤더로 종류를 확인한 RingBuf 레코드
+pub enum RingEvent {
+    /// 의심 패킷 이벤트 (`EVENTS`)
+    Packet(PacketEventData),
+    /// TLS ClientHello 캡처 (`TLS_HELLOS`, 1KiB가 넘어 박스에 보관)
+    TlsHello(Box<TlsHelloData>),
+}
+
+impl RingEvent {
+    /// 이벤트 종류 이름 (로그용)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Packet(_) => "packet",
+            Self::TlsHello(_) => "tls_hello",
+        }
+    }
+}
+
+/// RingBuf 레코드 하나를 해석합니다.
+///
+/// # Errors
+///
+/// 헤더보다 짧거나, 형식 버전이 다르거나, 알 수 없는 종류이거나, 헤더의 길이가
+/// 본문 크기와 맞지 않으면 에러를 반환합니다.
+pub fn decode_record(data: &[u8]) -> Result<RingEvent, IronpostError> {
+    let header = read::<EventHeader>(data)
+        .ok_or_else(|| decode_error(format!("record of {} bytes has no header", data.len())))?;
+    if header.version != EVENT_FORMAT_VERSION {
+        return Err(decode_error(format!(
+            "unsupported record format version {} (expected: {})",
+            header.version, EVENT_FORMAT_VERSION
+        )));
+    }
+
+    match header.event_type {
+        EVENT_TYPE_PACKET => body::<PacketEventData>(data, &header).map(RingEvent::Packet),
+        EVENT_TYPE_TLS_HELLO => {
+            body::<TlsHelloData>(data, &header).map(|hello| RingEvent::TlsHello(Box::new(hello)))
+        }
+        other => Err(decode_error(format!("unknown record type {}", other))),
+    }
+}
+
+/// 헤더의 길이를 확인하고 레코드 본문을 읽습니다.
+fn body<T: Copy>(data: &[u8], header: &EventHeader) -> Result<T, IronpostError> {
+    let expected = size_of::<EventRecord<T>>();
+    let len = usize::from(header.len);
+    if len != expected {
+        return Err(decode_error(format!(
+            "record type {} declares {} bytes (expected: {})",
+            header.event_type, len, expected
+        )));
+    }
+    read::<EventRecord<T>>(data)
+        .map(|record| record.data)
+        .ok_or_else(|| {
+            decode_error(format!(
+                "record type {} truncated to {} bytes (expected: {})",
+                header.event_type,
+                data.len(),
+                expected
+            ))
+        })
+}
+
+/// 바이트열 앞부분을 `#[repr(C)]` 공유 타입으로 읽습니다 (모자라면 `None`).
+fn read<T: Copy>(data: &[u8]) -> Option<T> {
+    if data.len() < size_of::<T>() {
+        return None;
+    }
+    // SAFETY: 호출하는 타입은 모두 ebpf-common의 #[repr(C)] Plain Old Data이며 크기를
+    // 확인했습니다. RingBuf 데이터의 정렬은 보장되지 않으므로 read_unaligned를 씁니다.
+    Some(unsafe { std::ptr::read_unaligned(data.as_ptr().cast::<T>()) })
+}
+
+fn decode_error(reason: String) -> IronpostError {
+    DetectionError::EbpfMap(format!("invalid ringbuf record: {}", reason)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_ebpf_common::{PROTO_TCP, TLS_HELLO_CAPTURE_LEN};
+
+    /// 커널이 RingBuf에 기록하는 것과 같은 바이트열을 만듭니다.
+    fn bytes<T: Copy>(record: &EventRecord<T>) -> Vec<u8> {
+        // SAFETY: EventRecord는 패딩 없는 #[repr(C)] 타입입니다.
+        unsafe {
+            std::slice::from_raw_parts(
+                (record as *const EventRecord<T>).cast::<u8>(),
+                size_of::<EventRecord<T>>(),
+            )
+        }
+        .to_vec()
+    }
+
+    /// 거부된 레코드의 에러 메시지
+    fn rejection(data: &[u8]) -> String {
+        match decode_record(data) {
+            Ok(record) => panic!("decoded {} record", record.kind()),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    fn packet() -> PacketEventData {
+        PacketEventData {
+            src_ip: u32::from_be_bytes([10, 0, 0, 1]),
+            dst_port: 443,
+            protocol: PROTO_TCP,
+            ..PacketEventData::zeroed()
+        }
+    }
+
+    #[test]
+    fn test_decode_dispatches_on_event_type() {
+        let data = bytes(&EventRecord::new(EVENT_TYPE_PACKET, packet()));
+        assert_eq!(data.len(), 4 + size_of::<PacketEventData>());
+        match decode_record(&data).unwrap() {
+            RingEvent::Packet(event) => {
+                assert_eq!(event.src_ip, u32::from_be_bytes([10, 0, 0, 1]));
+                assert_eq!(event.dst_port, 443);
+            }
+            other => panic!("unexpected {}", other.kind()),
+        }
+
+        let hello = TlsHelloData {
+            src_ip: 1,
+            dst_ip: 2,
+            src_port: 50_000,
+            dst_port: 443,
+            captured_len: 3,
+            payload: [0x16; TLS_HELLO_CAPTURE_LEN],
+        };
+        let data = bytes(&EventRecord::new(EVENT_TYPE_TLS_HELLO, hello));
+        match decode_record(&data).unwrap() {
+            RingEvent::TlsHello(hello) => assert_eq!(hello.captured_len, 3),
+            other => panic!("unexpected {}", other.kind()),
+        }
+    }
+
+    #[test]
+    fn test_decode_rejects_mismatched_records() {
+        let valid = bytes(&EventRecord::new(EVENT_TYPE_PACKET, packet()));
+
+        let mut version = valid.clone();
+        version[0] = EVENT_FORMAT_VERSION + 1;
+        assert!(rejection(&version).contains("format version"));
+
+        let mut unknown = valid.clone();
+        unknown[1] = 0xEE;
+        assert!(rejection(&unknown).contains("unknown record type 238"));
+
+        // 헤더의 길이가 본문 크기와 다르면 거부됨
+        let mut resized = valid.clone();
+        resized[2..4].copy_from_slice(&(size_of::<PacketEventData>() as u16).to_ne_bytes());
+        assert!(rejection(&resized).contains("declares 24 bytes"));
+
+        assert!(rejection(&valid[..valid.len() - 1]).contains("truncated"));
+        assert!(rejection(&valid[..2]).contains("no header"));
+    }
+}
@@ -0,0 +1,183 @@
+//! 차단 목록 상태 파일 — 데몬 재시작 후 동적 차단 유지
+//!
+//! [`BlocklistSnapshot`]은 필터 룰(런타임에 추가된 룰 포함), 런타임 차단 엔트리,
+//! 위협 인텔리전스 피드 룰을 JSON 파일 하나로 저장합니다. 엔진은 시작 시 이 파일에서 차단 목록을 복원하고,
+//! 실행 중에는 변경이 있을 때마다 주기적으로, 정지 시에는 마지막으로 한 번 저장합니다.
+//!
+//! 맵 고정(bpffs)과 달리 커널 상태에 의존하지 않으므로 재부팅이나 bpffs가 없는
+//! 환경에서도 동적 차단이 유지됩니다.
+//!
+//! # 파일 형식
+//! ```json
+//! {
+//!   "version": 1,
+//!   "rules": [{ "id": "block-scanner", "src_ip": "10.0.0.50", "action": "block", ... }],
+//!   "entries": [{ "cidr": "203.0.113.7", "action": "block", "expires_at": 1767225600, ... }],
+//!   "feeds": { "spamhaus-drop": [{ "id": "feed:spamhaus-drop:198.51.100.0/24", ... }] }
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use ironpost_core::error::{ConfigError, IronpostError};
+
+use crate::blocklist::BlocklistEntry;
+use crate::config::FilterRule;
+
+/// 현재 상태 파일 형식 버전
+pub const STATE_FILE_VERSION: u32 = 1;
+
+/// 상태 파일 최대 크기 (10MB, 룰 파일과 동일)
+const MAX_STATE_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// 상태 파일에 저장하는 차단 목록 스냅샷
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlocklistSnapshot {
+    /// 필터 룰 (설정 룰 + 런타임에 추가된 룰)
+    #[serde(default)]
+    pub rules: Vec<FilterRule>,
+    /// 런타임 차단 엔트리
+    #[serde(default)]
+    pub entries: Vec<BlocklistEntry>,
+    /// 위협 인텔리전스 피드별 룰 (다음 갱신 전까지 피드 차단을 유지)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub feeds: BTreeMap<String, Vec<FilterRule>>,
+}
+
+/// 디스크 형식 (버전 필드 포함)
+#[derive(Serialize, Deserialize)]
+struct StateFile {
+    version: u32,
+    #[serde(flatten)]
+    snapshot: BlocklistSnapshot,
+}
+
+impl BlocklistSnapshot {
+    /// 상태 파일을 읽습니다.
+    ///
+    /// 파일이 없으면 `None`을 반환합니다.
+    ///
+    /// # Errors
+    ///
+    /// 파일이 너무 크거나, 파싱에 실패하거나, 지원하지 않는 버전이면 에러를 반환합니다.
+    pub async fn load(path: impl AsRef<Path>) -> Result<Option<Self>, IronpostError> {
+        let path = path.as_ref();
+        match tokio::fs::metadata(path).await {
+            Ok(metadata) if metadata.len() > MAX_STATE_FILE_SIZE => {
+                return Err(ConfigError::ParseFailed {
+                    reason: format!(
+                        "state file too large: {} bytes (max: {} bytes)",
+                        metadata.len(),
+                        MAX_STATE_FILE_SIZE
+                    ),
+                }
+                .into());
+            }
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        let content = tokio::fs::read(path).await?;
+        let file: StateFile =
+            serde_json::from_slice(&content).map_err(|e| ConfigError::ParseFailed {
+                reason: format!("failed to parse state file {}: {}", path.display(), e),
+            })?;
+        if file.version != STATE_FILE_VERSION {
+            return Err(ConfigError::ParseFailed {
+                reason: format!(
+                    "unsupported state file version {} (expected {})",
+                    file.version, STATE_FILE_VERSION
+                ),
+            }
+            .into());
+        }
+        Ok(Some(file.snapshot))
+    }
+
+    /// 상태 파일에 저장합니다.
+    ///
+    /// 임시 파일에 쓴 뒤 rename하므로 저장 도중 종료되어도 이전 파일이 깨지지 않습니다.
+    /// 상위 디렉토리가 없으면 생성합니다.
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<(), IronpostError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let file = StateFile {
+            version: STATE_FILE_VERSION,
+            snapshot: self.clone(),
+        };
+        let body = serde_json::to_vec_pretty(&file).map_err(|e| ConfigError::ParseFailed {
+            reason: format!("failed to serialize state file: {}", e),
+        })?;
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        tokio::fs::write(&tmp, body).await?;
+        tokio::fs::rename(&tmp, path).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocklist::Ipv4Cidr;
+    use crate::config::RuleAction;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("ebpf-state.json");
+
+        assert!(BlocklistSnapshot::load(&path).await.unwrap().is_none());
+
+        let snapshot = BlocklistSnapshot {
+            rules: vec![FilterRule {
+                id: "block-scanner".to_owned(),
+                src_ip: Some("10.0.0.50".parse().unwrap()),
+                src_cidr: None,
+                dst_ip: None,
+                dst_port: None,
+                protocol: None,
+                action: RuleAction::Block,
+                description: "runtime rule".to_owned(),
+            }],
+            entries: vec![
+                BlocklistEntry::new(
+                    "203.0.113.0/24".parse::<Ipv4Cidr>().unwrap(),
+                    RuleAction::Block,
+                )
+                .with_ttl(Duration::from_secs(600))
+                .with_description("auto-block: syn_flood"),
+            ],
+            feeds: BTreeMap::new(),
+        };
+        snapshot.save(&path).await.unwrap();
+
+        let loaded = BlocklistSnapshot::load(&path).await.unwrap().unwrap();
+        assert_eq!(loaded.rules.len(), 1);
+        assert_eq!(loaded.rules[0].id, "block-scanner");
+        assert_eq!(loaded.entries, snapshot.entries);
+    }
+
+    #[tokio::test]
+    async fn test_load_rejects_unknown_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ebpf-state.json");
+        tokio::fs::write(&path, r#"{"version": 99, "rules": [], "entries": []}"#)
+            .await
+            .unwrap();
+
+        let err = BlocklistSnapshot::load(&path).await.unwrap_err();
+        assert!(err.to_string().contains("unsupported state file version"));
+    }
+}
@@ -1,16 +1,22 @@
 //! 통계 수집 — PerCpuArray 기반 패킷 통계
 //!
-//! [`TrafficStats`]는 eBPF PerCpuArray 맵에서 수집한 프로토콜별 트래픽 통계를 관리합니다.
+//! [`TrafficStats`]는 eBPF PerCpuArray 맵에서 수집한 프로토콜별 트래픽 통계,
+//! `IFACE_STATS` 맵에서 수집한 수신 인터페이스별 통계, `PORT_STATS` 맵에서 수집한
+//! 목적지 포트별 통계를 관리합니다.
 //! 엔진 내부에서 주기적으로 폴링하여 업데이트하고, 외부에서 조회할 수 있습니다.
 //!
 //! # 데이터 흐름
 //! ```text
 //! PerCpuArray (kernel) ──poll──▶ RawTrafficSnapshot ──update──▶ TrafficStats
-//!                                (CPU별 값 합산)                (rate 계산)
+//! IFACE_STATS (kernel) ──poll──▶   (CPU별 값 합산)              (rate 계산, 인터페이스별 집계,
+//! PORT_STATS  (kernel) ──poll──▶                                 상위 포트 집계)
 //! ```
 
+use std::collections::HashMap;
 use std::time::Instant;
 
+use ironpost_ebpf_common::{PROTO_TCP, PROTO_UDP};
+
 use ironpost_core::metrics as m;
 use serde::Serialize;
 
@@ -25,6 +31,28 @@ pub struct RawProtoStats {
     pub bytes: u64,
     /// 드롭된 패킷 수 (누적)
     pub drops: u64,
+    /// 관찰 전용 모드에서 드롭 판정을 받았지만 통과시킨 패킷 수 (누적)
+    pub would_drops: u64,
+}
+
+/// CPU별 합산된 목적지 포트 원시 통계
+#[derive(Debug, Clone, Default)]
+pub struct RawPortStats {
+    /// 목적지 포트
+    pub port: u16,
+    /// IP 프로토콜 번호 (TCP/UDP)
+    pub protocol: u8,
+    /// 누적 통계
+    pub stats: RawProtoStats,
+}
+
+/// CPU별 합산된 수신 인터페이스 원시 통계
+#[derive(Debug, Clone, Default)]
+pub struct RawInterfaceStats {
+    /// 인터페이스 이름 (알 수 없으면 ifindex)
+    pub name: String,
+    /// 누적 통계
+    pub stats: RawProtoStats,
 }
 
 /// 전체 트래픽 원시 통계 스냅샷
@@ -42,6 +70,10 @@ pub struct RawTrafficSnapshot {
     pub other: RawProtoStats,
     /// 전체 합계
     pub total: RawProtoStats,
+    /// 목적지 포트별 통계 (`PORT_STATS` 맵에 남아 있는 포트)
+    pub ports: Vec<RawPortStats>,
+    /// 수신 인터페이스별 통계 (`IFACE_STATS` 맵)
+    pub interfaces: Vec<RawInterfaceStats>,
 }
 
 /// 프로토콜별 트래픽 메트릭 (누적 + 비율)
@@ -55,12 +87,39 @@ pub struct ProtoMetrics {
     pub bytes: u64,
     /// 드롭된 패킷 수 (누적)
     pub drops: u64,
+    /// 관찰 전용 모드에서 드롭 판정을 받았지만 통과시킨 패킷 수 (누적)
+    pub would_drops: u64,
     /// 초당 패킷 수 (packets per second)
     pub pps: f64,
     /// 초당 비트 수 (bits per second)
     pub bps: f64,
 }
 
+/// [`TrafficStats::top_ports`]에 유지하는 최대 포트 수
+pub const TOP_PORTS_LIMIT: usize = 10;
+
+/// 목적지 포트별 트래픽 메트릭
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PortMetrics {
+    /// 목적지 포트
+    pub port: u16,
+    /// 프로토콜 이름 (`tcp`, `udp`)
+    pub protocol: String,
+    /// 누적 카운터와 비율
+    #[serde(flatten)]
+    pub metrics: ProtoMetrics,
+}
+
+/// 수신 인터페이스별 트래픽 메트릭
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InterfaceMetrics {
+    /// 인터페이스 이름
+    pub name: String,
+    /// 누적 카운터와 비율
+    #[serde(flatten)]
+    pub metrics: ProtoMetrics,
+}
+
 /// 전체 트래픽 통계
 ///
 /// 프로토콜별 메트릭과 rate 계산 상태를 관리합니다.
@@ -81,6 +140,10 @@ pub struct TrafficStats {
     pub other: ProtoMetrics,
     /// 전체 합계
     pub total: ProtoMetrics,
+    /// 트래픽이 많은 목적지 포트 (pps 내림차순, 최대 [`TOP_PORTS_LIMIT`]개)
+    pub top_ports: Vec<PortMetrics>,
+    /// 어태치된 인터페이스별 통계 (이름순)
+    pub interfaces: Vec<InterfaceMetrics>,
     /// 마지막 업데이트 시각 (rate 계산용, 직렬화 제외)
     #[serde(skip)]
     last_poll: Option<Instant>,
@@ -98,6 +161,8 @@ impl TrafficStats {
             icmp: ProtoMetrics::default(),
             other: ProtoMetrics::default(),
             total: ProtoMetrics::default(),
+            top_ports: Vec::new(),
+            interfaces: Vec::new(),
             last_poll: None,
             prev_raw: None,
         }
@@ -118,6 +183,9 @@ impl TrafficStats {
                 Self::compute_rate(&mut self.icmp, &raw.icmp, &prev.icmp, elapsed);
                 Self::compute_rate(&mut self.other, &raw.other, &prev.other, elapsed);
                 Self::compute_rate(&mut self.total, &raw.total, &prev.total, elapsed);
+                self.top_ports = Self::rank_ports(&raw.ports, Some((&prev.ports, elapsed)));
+                self.interfaces =
+                    Self::interface_metrics(&raw.interfaces, Some((&prev.interfaces, elapsed)));
             }
         } else {
             // 첫 번째 폴링 — 누적값만 설정, rate는 0
@@ -126,15 +194,25 @@ impl TrafficStats {
             Self::set_cumulative(&mut self.icmp, &raw.icmp);
             Self::set_cumulative(&mut self.other, &raw.other);
             Self::set_cumulative(&mut self.total, &raw.total);
+            self.top_ports = Self::rank_ports(&raw.ports, None);
+            self.interfaces = Self::interface_metrics(&raw.interfaces, None);
         }
 
         self.prev_raw = Some(raw);
         self.last_poll = Some(now);
 
-        // Update Prometheus metrics
+        self.record_metrics();
+    }
+
+    /// 현재 통계를 Prometheus 메트릭으로 기록합니다.
+    ///
+    /// 누적 카운터는 커널 맵 값을 그대로 반영(`absolute`)하고, 비율은 게이지로 기록합니다.
+    /// 포트별 통계는 상위 포트가 폴링마다 바뀌어 시계열이 계속 늘어나므로 기록하지 않습니다.
+    fn record_metrics(&self) {
         metrics::counter!(m::EBPF_PACKETS_TOTAL).absolute(self.total.packets);
         metrics::counter!(m::EBPF_BYTES_TOTAL).absolute(self.total.bytes);
         metrics::counter!(m::EBPF_PACKETS_BLOCKED_TOTAL).absolute(self.total.drops);
+        metrics::counter!(m::EBPF_PACKETS_WOULD_BLOCK_TOTAL).absolute(self.total.would_drops);
 
         // Protocol-specific counters
         for (proto, stats) in [
@@ -143,11 +221,14 @@ impl TrafficStats {
             ("icmp", &self.icmp),
             ("other", &self.other),
         ] {
-            metrics::counter!(
-                m::EBPF_PROTOCOL_PACKETS_TOTAL,
-                m::LABEL_PROTOCOL => proto
-            )
-            .absolute(stats.packets);
+            metrics::counter!(m::EBPF_PROTOCOL_PACKETS_TOTAL, m::LABEL_PROTOCOL => proto)
+                .absolute(stats.packets);
+            metrics::counter!(m::EBPF_PROTOCOL_BYTES_TOTAL, m::LABEL_PROTOCOL => proto)
+                .absolute(stats.bytes);
+            metrics::counter!(m::EBPF_PROTOCOL_DROPS_TOTAL, m::LABEL_PROTOCOL => proto)
+                .absolute(stats.drops);
+            metrics::counter!(m::EBPF_PROTOCOL_WOULD_DROPS_TOTAL, m::LABEL_PROTOCOL => proto)
+                .absolute(stats.would_drops);
         }
 
         // Rate metrics (gauges)
@@ -161,6 +242,22 @@ impl TrafficStats {
             metrics::gauge!(m::EBPF_PACKETS_PER_SECOND, m::LABEL_PROTOCOL => proto).set(stats.pps);
             metrics::gauge!(m::EBPF_BITS_PER_SECOND, m::LABEL_PROTOCOL => proto).set(stats.bps);
         }
+
+        // Per-interface counters and rates
+        for iface in &self.interfaces {
+            let name = iface.name.clone();
+            let stats = &iface.metrics;
+            metrics::counter!(m::EBPF_INTERFACE_PACKETS_TOTAL, m::LABEL_INTERFACE => name.clone())
+                .absolute(stats.packets);
+            metrics::counter!(m::EBPF_INTERFACE_BYTES_TOTAL, m::LABEL_INTERFACE => name.clone())
+                .absolute(stats.bytes);
+            metrics::counter!(m::EBPF_INTERFACE_DROPS_TOTAL, m::LABEL_INTERFACE => name.clone())
+                .absolute(stats.drops);
+            metrics::gauge!(m::EBPF_INTERFACE_PACKETS_PER_SECOND, m::LABEL_INTERFACE => name.clone())
+                .set(stats.pps);
+            metrics::gauge!(m::EBPF_INTERFACE_BITS_PER_SECOND, m::LABEL_INTERFACE => name)
+                .set(stats.bps);
+        }
     }
 
     /// 통계를 초기화합니다.
@@ -230,6 +327,7 @@ impl TrafficStats {
         metrics.packets = current.packets;
         metrics.bytes = current.bytes;
         metrics.drops = current.drops;
+        metrics.would_drops = current.would_drops;
 
         let delta_packets = current.packets.saturating_sub(prev.packets);
         let delta_bytes = current.bytes.saturating_sub(prev.bytes);
@@ -244,11 +342,94 @@ impl TrafficStats {
         }
     }
 
+    /// 포트별 메트릭을 계산해 상위 [`TOP_PORTS_LIMIT`]개를 반환합니다.
+    ///
+    /// `prev`가 있으면 이전 폴링과의 차이로 rate를 계산하며, 이전 폴링에 없던 포트는
+    /// 그 사이 새로 생긴 엔트리이므로 0에서 시작한 것으로 봅니다. pps가 같으면
+    /// 누적 패킷 수, 포트 번호 순으로 정렬합니다.
+    fn rank_ports(
+        current: &[RawPortStats],
+        prev: Option<(&[RawPortStats], f64)>,
+    ) -> Vec<PortMetrics> {
+        let zero = RawProtoStats::default();
+        let previous: HashMap<(u16, u8), &RawProtoStats> = prev
+            .map(|(ports, _)| ports)
+            .unwrap_or_default()
+            .iter()
+            .map(|p| ((p.port, p.protocol), &p.stats))
+            .collect();
+
+        let mut ports: Vec<PortMetrics> = current
+            .iter()
+            .map(|p| {
+                let mut metrics = ProtoMetrics::default();
+                match prev {
+                    Some((_, elapsed)) => {
+                        let before = previous
+                            .get(&(p.port, p.protocol))
+                            .copied()
+                            .unwrap_or(&zero);
+                        Self::compute_rate(&mut metrics, &p.stats, before, elapsed);
+                    }
+                    None => Self::set_cumulative(&mut metrics, &p.stats),
+                }
+                PortMetrics {
+                    port: p.port,
+                    protocol: protocol_name(p.protocol),
+                    metrics,
+                }
+            })
+            .collect();
+        ports.sort_by(|a, b| {
+            b.metrics
+                .pps
+                .total_cmp(&a.metrics.pps)
+                .then(b.metrics.packets.cmp(&a.metrics.packets))
+                .then(a.port.cmp(&b.port))
+        });
+        ports.truncate(TOP_PORTS_LIMIT);
+        ports
+    }
+
+    /// 인터페이스별 메트릭을 이름순으로 계산합니다.
+    ///
+    /// rate 계산은 [`rank_ports`](Self::rank_ports)와 같으며, 이전 폴링에 없던
+    /// 인터페이스는 0에서 시작한 것으로 봅니다.
+    fn interface_metrics(
+        current: &[RawInterfaceStats],
+        prev: Option<(&[RawInterfaceStats], f64)>,
+    ) -> Vec<InterfaceMetrics> {
+        let zero = RawProtoStats::default();
+        let mut interfaces: Vec<InterfaceMetrics> = current
+            .iter()
+            .map(|iface| {
+                let mut metrics = ProtoMetrics::default();
+                match prev {
+                    Some((previous, elapsed)) => {
+                        let before = previous
+                            .iter()
+                            .find(|p| p.name == iface.name)
+                            .map_or(&zero, |p| &p.stats);
+                        Self::compute_rate(&mut metrics, &iface.stats, before, elapsed);
+                    }
+                    None => Self::set_cumulative(&mut metrics, &iface.stats),
+                }
+                InterfaceMetrics {
+                    name: iface.name.clone(),
+                    metrics,
+                }
+            })
+            .collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        interfaces
+    }
+
     /// 누적값만 설정합니다 (rate는 0).
     fn set_cumulative(metrics: &mut ProtoMetrics, raw: &RawProtoStats) {
         metrics.packets = raw.packets;
         metrics.bytes = raw.bytes;
         metrics.drops = raw.drops;
+        metrics.would_drops = raw.would_drops;
         metrics.pps = 0.0;
         metrics.bps = 0.0;
     }
@@ -260,6 +441,15 @@ impl Default for TrafficStats {
     }
 }
 
+/// 프로토콜 번호를 메트릭 라벨 이름으로 변환합니다.
+fn protocol_name(protocol: u8) -> String {
+    match protocol {
+        PROTO_TCP => "tcp".to_owned(),
+        PROTO_UDP => "udp".to_owned(),
+        other => other.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,27 +526,34 @@ mod tests {
                 packets: 1000,
                 bytes: 64000,
                 drops: 10,
+                would_drops: 0,
             },
             udp: RawProtoStats {
                 packets: 500,
                 bytes: 32000,
                 drops: 5,
+                would_drops: 0,
             },
             icmp: RawProtoStats {
                 packets: 100,
                 bytes: 8000,
                 drops: 1,
+                would_drops: 0,
             },
             other: RawProtoStats {
                 packets: 50,
                 bytes: 4000,
                 drops: 0,
+                would_drops: 0,
             },
             total: RawProtoStats {
                 packets: 1650,
                 bytes: 108000,
                 drops: 16,
+                would_drops: 0,
             },
+            ports: Vec::new(),
+            interfaces: Vec::new(),
         };
 
         stats.update(snapshot);
@@ -394,6 +591,7 @@ mod tests {
                 packets: 1000,
                 bytes: 64000,
                 drops: 10,
+                would_drops: 0,
             },
             udp: RawProtoStats::default(),
             icmp: RawProtoStats::default(),
@@ -402,7 +600,10 @@ mod tests {
                 packets: 1000,
                 bytes: 64000,
                 drops: 10,
+                would_drops: 0,
             },
+            ports: Vec::new(),
+            interfaces: Vec::new(),
         };
 
         stats.update(snapshot1);
@@ -415,6 +616,7 @@ mod tests {
                 packets: 2000, // +1000 packets
                 bytes: 128000, // +64000 bytes
                 drops: 20,     // +10 drops
+                would_drops: 0,
             },
             udp: RawProtoStats::default(),
             icmp: RawProtoStats::default(),
@@ -423,7 +625,10 @@ mod tests {
                 packets: 2000,
                 bytes: 128000,
                 drops: 20,
+                would_drops: 0,
             },
+            ports: Vec::new(),
+            interfaces: Vec::new(),
         };
 
         stats.update(snapshot2);
@@ -442,6 +647,30 @@ mod tests {
         assert!(stats.tcp.pps < 100000.0); // 상한선
     }
 
+    #[test]
+    fn test_update_keeps_would_drops_separate_from_drops() {
+        let mut stats = TrafficStats::new();
+        let observed = RawProtoStats {
+            packets: 100,
+            bytes: 6400,
+            drops: 0,
+            would_drops: 30,
+        };
+        let snapshot = RawTrafficSnapshot {
+            tcp: observed.clone(),
+            total: observed.clone(),
+            ..RawTrafficSnapshot::default()
+        };
+
+        stats.update(snapshot.clone());
+        assert_eq!(stats.tcp.drops, 0);
+        assert_eq!(stats.tcp.would_drops, 30);
+
+        stats.update(snapshot);
+        assert_eq!(stats.total.drops, 0);
+        assert_eq!(stats.total.would_drops, 30);
+    }
+
     #[test]
     fn test_update_zero_elapsed_time_skips_rate_calculation() {
         // 이 테스트는 이론적으로 elapsed = 0인 경우를 시뮬레이션할 수 없으므로
@@ -454,11 +683,14 @@ mod tests {
                 packets: 1000,
                 bytes: 64000,
                 drops: 10,
+                would_drops: 0,
             },
             udp: RawProtoStats::default(),
             icmp: RawProtoStats::default(),
             other: RawProtoStats::default(),
             total: RawProtoStats::default(),
+            ports: Vec::new(),
+            interfaces: Vec::new(),
         };
 
         stats.update(snapshot.clone());
@@ -502,6 +734,7 @@ mod tests {
                 packets: 1000,
                 bytes: 64000,
                 drops: 10,
+                would_drops: 0,
             },
             udp: RawProtoStats::default(),
             icmp: RawProtoStats::default(),
@@ -510,7 +743,10 @@ mod tests {
                 packets: 1000,
                 bytes: 64000,
                 drops: 10,
+                would_drops: 0,
             },
+            ports: Vec::new(),
+            interfaces: Vec::new(),
         };
 
         stats.update(snapshot);
@@ -619,6 +855,7 @@ mod tests {
                 packets: u64::MAX,
                 bytes: u64::MAX,
                 drops: u64::MAX,
+                would_drops: 0,
             },
             udp: RawProtoStats::default(),
             icmp: RawProtoStats::default(),
@@ -627,7 +864,10 @@ mod tests {
                 packets: u64::MAX,
                 bytes: u64::MAX,
                 drops: u64::MAX,
+                would_drops: 0,
             },
+            ports: Vec::new(),
+            interfaces: Vec::new(),
         };
 
         stats.update(snapshot);
@@ -646,11 +886,14 @@ mod tests {
                 packets: 1000,
                 bytes: 64000,
                 drops: 10,
+                would_drops: 0,
             },
             udp: RawProtoStats::default(),
             icmp: RawProtoStats::default(),
             other: RawProtoStats::default(),
             total: RawProtoStats::default(),
+            ports: Vec::new(),
+            interfaces: Vec::new(),
         };
 
         stats.update(snapshot1);
@@ -663,11 +906,14 @@ mod tests {
                 packets: 500, // 감소
                 bytes: 32000, // 감소
                 drops: 5,     // 감소
+                would_drops: 0,
             },
             udp: RawProtoStats::default(),
             icmp: RawProtoStats::default(),
             other: RawProtoStats::default(),
             total: RawProtoStats::default(),
+            ports: Vec::new(),
+            interfaces: Vec::new(),
         };
 
         stats.update(snapshot2);
@@ -694,6 +940,7 @@ mod tests {
                     packets: i * 1000,
                     bytes: i * 64000,
                     drops: i * 10,
+                    would_drops: 0,
                 },
                 udp: RawProtoStats::default(),
                 icmp: RawProtoStats::default(),
@@ -702,7 +949,10 @@ mod tests {
                     packets: i * 1000,
                     bytes: i * 64000,
                     drops: i * 10,
+                    would_drops: 0,
                 },
+                ports: Vec::new(),
+                interfaces: Vec::new(),
             };
 
             stats.update(snapshot);
@@ -713,4 +963,125 @@ mod tests {
         assert_eq!(stats.tcp.bytes, 320000);
         assert!(stats.tcp.pps > 0.0); // rate가 계산되었어야 함
     }
+
+    // =============================================================================
+    // 포트별 통계 테스트
+    // =============================================================================
+
+    fn port(port: u16, protocol: u8, packets: u64, drops: u64) -> RawPortStats {
+        RawPortStats {
+            port,
+            protocol,
+            stats: RawProtoStats {
+                packets,
+                bytes: packets * 100,
+                drops,
+                would_drops: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_rank_ports_first_poll_orders_by_packets() {
+        let ports = vec![port(53, PROTO_UDP, 10, 0), port(443, PROTO_TCP, 500, 7)];
+
+        let ranked = TrafficStats::rank_ports(&ports, None);
+
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].port, 443);
+        assert_eq!(ranked[0].protocol, "tcp");
+        assert_eq!(ranked[0].metrics.drops, 7);
+        assert_eq!(ranked[0].metrics.pps, 0.0);
+        assert_eq!(ranked[1].protocol, "udp");
+    }
+
+    #[test]
+    fn test_rank_ports_uses_delta_and_treats_new_ports_as_zero() {
+        // 443은 누적이 크지만 최근 증가량이 작고, 22는 새로 생긴 엔트리
+        let prev = vec![port(443, PROTO_TCP, 10_000, 0)];
+        let current = vec![
+            port(443, PROTO_TCP, 10_010, 0),
+            port(22, PROTO_TCP, 200, 50),
+        ];
+
+        let ranked = TrafficStats::rank_ports(&current, Some((&prev, 2.0)));
+
+        assert_eq!(ranked[0].port, 22);
+        assert_eq!(ranked[0].metrics.pps, 100.0);
+        assert_eq!(ranked[0].metrics.drops, 50);
+        assert_eq!(ranked[1].port, 443);
+        assert_eq!(ranked[1].metrics.pps, 5.0);
+    }
+
+    #[test]
+    fn test_rank_ports_truncates_to_limit() {
+        let ports: Vec<RawPortStats> = (1..=20u16)
+            .map(|p| port(p, PROTO_TCP, u64::from(p), 0))
+            .collect();
+
+        let ranked = TrafficStats::rank_ports(&ports, None);
+
+        assert_eq!(ranked.len(), TOP_PORTS_LIMIT);
+        assert_eq!(ranked[0].port, 20);
+    }
+
+    #[test]
+    fn test_update_populates_top_ports() {
+        let mut stats = TrafficStats::new();
+        let snapshot = RawTrafficSnapshot {
+            ports: vec![port(8080, PROTO_TCP, 42, 1)],
+            ..RawTrafficSnapshot::default()
+        };
+
+        stats.update(snapshot);
+
+        assert_eq!(stats.top_ports.len(), 1);
+        assert_eq!(stats.top_ports[0].port, 8080);
+        assert_eq!(stats.top_ports[0].metrics.packets, 42);
+
+        let json = serde_json::to_value(&stats).expect("serialize");
+        assert_eq!(json["top_ports"][0]["protocol"], "tcp");
+        assert_eq!(json["top_ports"][0]["packets"], 42);
+    }
+
+    #[test]
+    fn test_update_tracks_interfaces_by_name() {
+        let iface = |name: &str, packets: u64| RawInterfaceStats {
+            name: name.to_owned(),
+            stats: RawProtoStats {
+                packets,
+                bytes: packets * 100,
+                drops: 0,
+                would_drops: 0,
+            },
+        };
+
+        let first = TrafficStats::interface_metrics(&[iface("eth1", 5), iface("eth0", 10)], None);
+        let names: Vec<&str> = first.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["eth0", "eth1"]);
+        assert_eq!(first[0].metrics.pps, 0.0);
+
+        let prev = [iface("eth0", 10)];
+        let current = [iface("eth0", 30), iface("eth1", 4)];
+        let second = TrafficStats::interface_metrics(&current, Some((&prev, 2.0)));
+        assert_eq!(second[0].metrics.pps, 10.0);
+        // 이전 폴링에 없던 인터페이스는 0에서 시작
+        assert_eq!(second[1].metrics.pps, 2.0);
+
+        let mut stats = TrafficStats::new();
+        stats.update(RawTrafficSnapshot {
+            interfaces: vec![iface("eth0", 7)],
+            ..RawTrafficSnapshot::default()
+        });
+        let json = serde_json::to_value(&stats).expect("serialize");
+        assert_eq!(json["interfaces"][0]["name"], "eth0");
+        assert_eq!(json["interfaces"][0]["packets"], 7);
+    }
+
+    #[test]
+    fn test_protocol_name() {
+        assert_eq!(protocol_name(PROTO_TCP), "tcp");
+        assert_eq!(protocol_name(PROTO_UDP), "udp");
+        assert_eq!(protocol_name(132), "132");
+    }
 }
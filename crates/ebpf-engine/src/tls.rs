@@ -0,0 +1,193 @@
+//! TLS SNI 추출 — TC egress가 캡처한 ClientHello에서 서버 이름을 읽습니다
+//!
+//! 커널 프로그램은 ClientHello로 시작하는 TCP 페이로드의 앞부분만 복사하고, 실제
+//! 파싱은 [`client_hello_sni`]가 유저스페이스에서 합니다. 추출한 호스트 이름은
+//! `PacketEvent::sni`로 전달되어, 로그 파이프라인 규칙이 암호화된 트래픽에서도
+//! `sni` 필드로 의심 도메인을 매칭할 수 있습니다.
+//!
+//! # 한계
+//! - 첫 TCP 세그먼트의 앞 [`TLS_HELLO_CAPTURE_LEN`] 바이트만 보므로, 확장이 많아
+//!   SNI가 그 뒤에 오거나 ClientHello가 여러 세그먼트로 나뉘면 추출하지 못합니다.
+//! - ECH(Encrypted Client Hello)를 쓰면 바깥 ClientHello의 공개 이름만 보입니다.
+//!
+//! [`TLS_HELLO_CAPTURE_LEN`]: ironpost_ebpf_common::TLS_HELLO_CAPTURE_LEN
+
+use ironpost_ebpf_common::is_tls_client_hello;
+
+/// `server_name` 확장 타입
+const EXT_SERVER_NAME: u16 = 0x0000;
+
+/// `server_name` 목록의 호스트 이름 타입
+const NAME_TYPE_HOST_NAME: u8 = 0x00;
+
+/// DNS 호스트 이름 최대 길이
+const MAX_HOST_NAME_LEN: usize = 253;
+
+/// TLS 레코드 헤더(5바이트)로 시작하는 ClientHello에서 SNI 호스트 이름을 추출합니다.
+///
+/// 잘린 데이터, ClientHello가 아닌 레코드, 호스트 이름으로 쓸 수 없는 값은 `None`을
+/// 반환합니다. 호스트 이름은 소문자로 정규화합니다.
+pub fn client_hello_sni(payload: &[u8]) -> Option<String> {
+    let prefix: &[u8; 6] = payload.get(..6)?.try_into().ok()?;
+    if !is_tls_client_hello(prefix) {
+        return None;
+    }
+
+    // 레코드 헤더(5) + 핸드셰이크 타입(1) + 길이(3) + client_version(2) + random(32)
+    let mut reader = Reader::new(payload.get(5 + 4 + 2 + 32..)?);
+    reader.skip_vec_u8()?; // session_id
+    reader.skip_vec_u16()?; // cipher_suites
+    reader.skip_vec_u8()?; // compression_methods
+
+    let mut extensions = Reader::new(reader.vec_u16()?);
+    while !extensions.is_empty() {
+        let ext_type = extensions.u16()?;
+        let data = extensions.vec_u16()?;
+        if ext_type == EXT_SERVER_NAME {
+            return server_name(data);
+        }
+    }
+    None
+}
+
+/// `server_name` 확장 본문에서 첫 호스트 이름을 읽습니다.
+fn server_name(data: &[u8]) -> Option<String> {
+    let mut list = Reader::new(Reader::new(data).vec_u16()?);
+    while !list.is_empty() {
+        let name_type = list.u8()?;
+        let name = list.vec_u16()?;
+        if name_type == NAME_TYPE_HOST_NAME {
+            return valid_host_name(name);
+        }
+    }
+    None
+}
+
+/// 바이트열이 DNS 호스트 이름 문자로만 이루어졌으면 소문자 문자열로 반환합니다.
+fn valid_host_name(name: &[u8]) -> Option<String> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_HOST_NAME_LEN
+        && name
+            .iter()
+            .all(|&b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_'));
+    valid.then(|| String::from_utf8_lossy(name).to_ascii_lowercase())
+}
+
+/// 길이 접두사가 붙은 TLS 필드를 읽는 커서 (범위를 벗어나면 `None`)
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.data.len() < len {
+            return None;
+        }
+        let (head, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn vec_u16(&mut self) -> Option<&'a [u8]> {
+        let len = self.u16()?;
+        self.take(usize::from(len))
+    }
+
+    fn skip_vec_u8(&mut self) -> Option<()> {
+        let len = self.u8()?;
+        self.take(usize::from(len)).map(|_| ())
+    }
+
+    fn skip_vec_u16(&mut self) -> Option<()> {
+        self.vec_u16().map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 주어진 확장들로 최소 ClientHello 레코드를 만듭니다.
+    fn client_hello(extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut ext_bytes = Vec::new();
+        for (ext_type, data) in extensions {
+            ext_bytes.extend_from_slice(&ext_type.to_be_bytes());
+            ext_bytes.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            ext_bytes.extend_from_slice(data);
+        }
+
+        let mut body = vec![0x03, 0x03];
+        body.extend_from_slice(&[0xAB; 32]); // random
+        body.push(32); // session_id
+        body.extend_from_slice(&[0xCD; 32]);
+        body.extend_from_slice(&[0x00, 0x04, 0x13, 0x01, 0x13, 0x02]); // cipher_suites
+        body.extend_from_slice(&[0x01, 0x00]); // compression_methods
+        body.extend_from_slice(&(ext_bytes.len() as u16).to_be_bytes());
+        body.extend_from_slice(&ext_bytes);
+
+        let mut handshake = vec![0x01];
+        handshake.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        handshake.extend_from_slice(&body);
+
+        let mut record = vec![0x16, 0x03, 0x01];
+        record.extend_from_slice(&(handshake.len() as u16).to_be_bytes());
+        record.extend_from_slice(&handshake);
+        record
+    }
+
+    fn sni_extension(host: &[u8]) -> (u16, Vec<u8>) {
+        let mut entry = vec![NAME_TYPE_HOST_NAME];
+        entry.extend_from_slice(&(host.len() as u16).to_be_bytes());
+        entry.extend_from_slice(host);
+        let mut data = (entry.len() as u16).to_be_bytes().to_vec();
+        data.extend_from_slice(&entry);
+        (EXT_SERVER_NAME, data)
+    }
+
+    #[test]
+    fn test_client_hello_sni_after_other_extensions() {
+        let hello = client_hello(&[
+            (0x0a0a, Vec::new()),                   // GREASE
+            (0x000a, vec![0x00, 0x02, 0x00, 0x1d]), // supported_groups
+            sni_extension(b"Login.Example.NET"),
+        ]);
+        assert_eq!(
+            client_hello_sni(&hello).as_deref(),
+            Some("login.example.net")
+        );
+    }
+
+    #[test]
+    fn test_client_hello_sni_rejects_truncated_and_invalid() {
+        let hello = client_hello(&[sni_extension(b"example.com")]);
+        assert_eq!(client_hello_sni(&hello[..hello.len() - 3]), None);
+
+        // ClientHello가 아닌 핸드셰이크 (ServerHello)
+        let mut server_hello = hello.clone();
+        server_hello[5] = 0x02;
+        assert_eq!(client_hello_sni(&server_hello), None);
+
+        assert_eq!(client_hello_sni(&client_hello(&[])), None);
+        assert_eq!(
+            client_hello_sni(&client_hello(&[sni_extension(b"bad host\0")])),
+            None
+        );
+        assert_eq!(client_hello_sni(b"GET / HTTP/1.1\r\n"), None);
+    }
+}
@@ -0,0 +1,258 @@
+//! 트래픽 임계치 알림 — 통계 폴러가 평가하는 대역폭/드롭 비율 알림
+//!
+//! [`TrafficAlerter`]는 통계 폴러가 [`TrafficStats`]를 갱신할 때마다 전체 트래픽의
+//! 초당 패킷 수, 초당 바이트 수, 폴링 주기 동안의 드롭 비율을 임계치와 비교해
+//! [`Alert`]를 만듭니다. 엔진은 이 알림을 `AlertEvent`로 감싸 룰 매칭 알림과 같은
+//! 채널로 보내므로, 트래픽 이상도 같은 알림 경로(알림 피드, 컨테이너 격리 정책 등)로
+//! 전달됩니다.
+//!
+//! # 재알림 억제
+//! 임계치를 계속 넘는 동안 매 폴링(1초)마다 알림이 쌓이지 않도록, 같은 종류의 알림은
+//! `cooldown`이 지나야 다시 보냅니다.
+//!
+//! # 드롭 비율
+//! 누적 카운터의 직전 폴링 대비 증가분으로 계산합니다. 폴링 사이 패킷이
+//! [`MIN_DROP_RATIO_PACKETS`]개 미만이면 비율이 의미 없으므로 평가하지 않습니다.
+
+use std::time::{Duration, Instant, SystemTime};
+
+use ironpost_core::types::{Alert, Severity};
+
+use crate::stats::TrafficStats;
+
+/// 드롭 비율을 평가하기 위한 폴링 간 최소 패킷 수
+pub const MIN_DROP_RATIO_PACKETS: u64 = 100;
+
+/// 초당 패킷 수 알림 룰 이름
+pub const RULE_TRAFFIC_PPS: &str = "traffic_pps";
+/// 초당 바이트 수 알림 룰 이름
+pub const RULE_TRAFFIC_BANDWIDTH: &str = "traffic_bandwidth";
+/// 드롭 비율 알림 룰 이름
+pub const RULE_TRAFFIC_DROP_RATIO: &str = "traffic_drop_ratio";
+
+/// 트래픽 임계치 (0이면 해당 항목 비활성화)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrafficThresholds {
+    /// 전체 초당 패킷 수
+    pub packets_per_sec: u64,
+    /// 전체 초당 바이트 수
+    pub bytes_per_sec: u64,
+    /// 폴링 주기 동안 드롭된 패킷 비율 (퍼센트)
+    pub drop_percent: u32,
+    /// 같은 종류의 알림을 다시 보내기 전 대기 시간
+    pub cooldown: Duration,
+}
+
+impl TrafficThresholds {
+    /// 평가할 임계치가 하나라도 있는지 확인합니다.
+    pub fn is_enabled(&self) -> bool {
+        self.packets_per_sec > 0 || self.bytes_per_sec > 0 || self.drop_percent > 0
+    }
+}
+
+/// 트래픽 임계치 평가기
+///
+/// 통계 폴러 태스크 하나가 소유하며, 드롭 비율 계산을 위한 직전 누적값과
+/// 알림 종류별 마지막 전송 시각을 보관합니다.
+#[derive(Debug)]
+pub struct TrafficAlerter {
+    thresholds: TrafficThresholds,
+    /// 직전 폴링의 (패킷, 드롭) 누적값
+    prev_totals: Option<(u64, u64)>,
+    /// 알림 종류별 마지막 전송 시각 (pps, 대역폭, 드롭 비율 순)
+    last_alert: [Option<Instant>; 3],
+}
+
+impl TrafficAlerter {
+    /// 새 평가기를 생성합니다.
+    pub fn new(thresholds: TrafficThresholds) -> Self {
+        Self {
+            thresholds,
+            prev_totals: None,
+            last_alert: [None; 3],
+        }
+    }
+
+    /// 갱신된 통계를 임계치와 비교해 보낼 알림을 반환합니다.
+    pub fn evaluate(&mut self, stats: &TrafficStats, now: Instant) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        let total = &stats.total;
+
+        let pps_limit = self.thresholds.packets_per_sec;
+        if pps_limit > 0 && total.pps > pps_limit as f64 && self.ready(0, now) {
+            alerts.push(traffic_alert(
+                RULE_TRAFFIC_PPS,
+                format!("Packet rate {:.0} pps exceeds threshold", total.pps),
+                format!(
+                    "Total traffic reached {:.0} packets/sec (threshold: {} packets/sec)",
+                    total.pps, pps_limit
+                ),
+            ));
+        }
+
+        let bytes_per_sec = total.bps / 8.0;
+        let bandwidth_limit = self.thresholds.bytes_per_sec;
+        if bandwidth_limit > 0 && bytes_per_sec > bandwidth_limit as f64 && self.ready(1, now) {
+            alerts.push(traffic_alert(
+                RULE_TRAFFIC_BANDWIDTH,
+                format!("Bandwidth {:.0} B/s exceeds threshold", bytes_per_sec),
+                format!(
+                    "Total traffic reached {:.0} bytes/sec (threshold: {} bytes/sec)",
+                    bytes_per_sec, bandwidth_limit
+                ),
+            ));
+        }
+
+        let prev = self.prev_totals.replace((total.packets, total.drops));
+        let drop_limit = self.thresholds.drop_percent;
+        if drop_limit > 0
+            && let Some((prev_packets, prev_drops)) = prev
+        {
+            let packets = total.packets.saturating_sub(prev_packets);
+            let drops = total.drops.saturating_sub(prev_drops);
+            if packets >= MIN_DROP_RATIO_PACKETS {
+                let percent = drops as f64 * 100.0 / packets as f64;
+                if percent > f64::from(drop_limit) && self.ready(2, now) {
+                    alerts.push(traffic_alert(
+                        RULE_TRAFFIC_DROP_RATIO,
+                        format!("Drop ratio {:.1}% exceeds threshold", percent),
+                        format!(
+                            "{} of {} packets dropped since the last poll ({:.1}%, threshold: {}%)",
+                            drops, packets, percent, drop_limit
+                        ),
+                    ));
+                }
+            }
+        }
+
+        alerts
+    }
+
+    /// 재알림 억제 시간이 지났으면 전송 시각을 기록하고 `true`를 반환합니다.
+    fn ready(&mut self, kind: usize, now: Instant) -> bool {
+        if let Some(last) = self.last_alert[kind]
+            && now.duration_since(last) < self.thresholds.cooldown
+        {
+            return false;
+        }
+        self.last_alert[kind] = Some(now);
+        true
+    }
+}
+
+/// 출발지가 없는 트래픽 임계치 알림을 만듭니다.
+fn traffic_alert(rule_name: &str, title: String, description: String) -> Alert {
+    Alert {
+        id: uuid::Uuid::new_v4().to_string(),
+        title,
+        description,
+        severity: Severity::Medium,
+        rule_name: rule_name.to_owned(),
+        source_ip: None,
+        target_ip: None,
+        created_at: SystemTime::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::ProtoMetrics;
+
+    fn thresholds() -> TrafficThresholds {
+        TrafficThresholds {
+            packets_per_sec: 1_000,
+            bytes_per_sec: 1_000_000,
+            drop_percent: 50,
+            cooldown: Duration::from_secs(60),
+        }
+    }
+
+    fn stats(packets: u64, drops: u64, pps: f64, bps: f64) -> TrafficStats {
+        let mut stats = TrafficStats::new();
+        stats.total = ProtoMetrics {
+            packets,
+            bytes: 0,
+            drops,
+            would_drops: 0,
+            pps,
+            bps,
+        };
+        stats
+    }
+
+    fn rules(alerts: &[Alert]) -> Vec<&str> {
+        alerts.iter().map(|a| a.rule_name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_rate_thresholds_alert_once_per_cooldown() {
+        let mut alerter = TrafficAlerter::new(thresholds());
+        let start = Instant::now();
+
+        // 10 Mbps = 1.25 MB/s
+        let busy = stats(0, 0, 5_000.0, 10_000_000.0);
+        let alerts = alerter.evaluate(&busy, start);
+        assert_eq!(
+            rules(&alerts),
+            vec![RULE_TRAFFIC_PPS, RULE_TRAFFIC_BANDWIDTH]
+        );
+        assert_eq!(alerts[0].severity, Severity::Medium);
+        assert!(alerts[0].source_ip.is_none());
+
+        assert!(
+            alerter
+                .evaluate(&busy, start + Duration::from_secs(1))
+                .is_empty()
+        );
+        assert_eq!(
+            alerter
+                .evaluate(&busy, start + Duration::from_secs(61))
+                .len(),
+            2
+        );
+
+        let quiet = stats(0, 0, 10.0, 8_000.0);
+        assert!(
+            alerter
+                .evaluate(&quiet, start + Duration::from_secs(200))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_drop_ratio_uses_delta_between_polls() {
+        let mut alerter = TrafficAlerter::new(TrafficThresholds {
+            packets_per_sec: 0,
+            bytes_per_sec: 0,
+            ..thresholds()
+        });
+        let now = Instant::now();
+
+        // 첫 폴링은 기준값만 기록
+        assert!(
+            alerter
+                .evaluate(&stats(10_000, 9_000, 0.0, 0.0), now)
+                .is_empty()
+        );
+
+        // 증가분이 너무 적으면 평가하지 않음
+        assert!(
+            alerter
+                .evaluate(&stats(10_050, 9_050, 0.0, 0.0), now)
+                .is_empty()
+        );
+
+        // 1000개 중 100개 드롭 = 10% (누적 비율은 높지만 알림 없음)
+        assert!(
+            alerter
+                .evaluate(&stats(11_050, 9_150, 0.0, 0.0), now)
+                .is_empty()
+        );
+
+        // 1000개 중 800개 드롭 = 80%
+        let alerts = alerter.evaluate(&stats(12_050, 9_950, 0.0, 0.0), now);
+        assert_eq!(rules(&alerts), vec![RULE_TRAFFIC_DROP_RATIO]);
+        assert!(alerts[0].description.contains("800 of 1000"));
+    }
+}
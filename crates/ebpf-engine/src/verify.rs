@@ -0,0 +1,111 @@
+//! eBPF 검증기 사전 점검 — 어태치 없이 커널 검증기만 통과시켜 봅니다
+//!
+//! [`verify_object`]는 eBPF 오브젝트의 모든 프로그램을 커널에 로드(`BPF_PROG_LOAD`)만
+//! 하고 인터페이스에는 어태치하지 않습니다. 검증기가 거부하면 검증기 로그를 포함한
+//! 에러를 반환하고, 통과하면 프로그램별 명령어 수를 [`ProgramVerification`]으로
+//! 반환합니다. 로드된 프로그램은 함수가 반환될 때 해제됩니다.
+//!
+//! `cargo xtask verify-ebpf`가 `examples/verify.rs`를 통해 사용하며,
+//! `CAP_BPF`(또는 root) 권한이 필요합니다.
+
+use serde::{Deserialize, Serialize};
+
+use ironpost_core::error::{DetectionError, IronpostError};
+
+/// 검증기를 통과한 프로그램 하나의 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramVerification {
+    /// 프로그램(함수) 이름
+    pub name: String,
+    /// 프로그램 타입 (예: `Xdp`, `KProbe`, `SchedClassifier`)
+    pub program_type: String,
+    /// 커널이 변환한 명령어 수 (xlated 바이트 / 8)
+    pub xlated_insns: Option<u32>,
+    /// 검증기가 처리한 명령어 수 (커널 5.16+, 검증 복잡도 지표)
+    pub verified_insns: Option<u32>,
+    /// JIT 컴파일된 코드 크기 (바이트, JIT 비활성 시 0)
+    pub jited_bytes: u32,
+}
+
+/// eBPF 오브젝트의 모든 프로그램을 로드만 하여 커널 검증기를 통과하는지 확인합니다.
+///
+/// 프로그램을 하나도 찾지 못하거나 지원하지 않는 타입이 있으면 에러를 반환합니다.
+///
+/// # Linux 전용
+/// macOS/Windows에서는 `DetectionError::EbpfLoad` 에러를 반환합니다.
+#[cfg(target_os = "linux")]
+pub fn verify_object(data: &[u8]) -> Result<Vec<ProgramVerification>, IronpostError> {
+    use aya::Ebpf;
+    use aya::programs::Program;
+
+    let mut bpf = Ebpf::load(data)
+        .map_err(|e| DetectionError::EbpfLoad(format!("failed to load eBPF object: {}", e)))?;
+
+    let mut results = Vec::new();
+    for (name, program) in bpf.programs_mut() {
+        let program_type = format!("{:?}", program.prog_type());
+        let loaded = match &mut *program {
+            Program::Xdp(xdp) => xdp.load(),
+            Program::KProbe(kprobe) => kprobe.load(),
+            Program::SchedClassifier(classifier) => classifier.load(),
+            _ => {
+                return Err(DetectionError::EbpfLoad(format!(
+                    "program '{}' has unsupported type {}",
+                    name, program_type
+                ))
+                .into());
+            }
+        };
+        loaded.map_err(|e| {
+            DetectionError::EbpfLoad(format!("verifier rejected program '{}': {}", name, e))
+        })?;
+
+        let info = program.info().map_err(|e| {
+            DetectionError::EbpfLoad(format!("failed to query program '{}': {}", name, e))
+        })?;
+        results.push(ProgramVerification {
+            name: name.to_owned(),
+            program_type,
+            xlated_insns: info.size_translated().map(|bytes| bytes / 8),
+            verified_insns: info.verified_instruction_count(),
+            jited_bytes: info.size_jitted(),
+        });
+    }
+
+    if results.is_empty() {
+        return Err(DetectionError::EbpfLoad("no programs found in eBPF object".to_owned()).into());
+    }
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(results)
+}
+
+/// 커널 검증기 점검 (비-Linux 스텁).
+#[cfg(not(target_os = "linux"))]
+pub fn verify_object(_data: &[u8]) -> Result<Vec<ProgramVerification>, IronpostError> {
+    Err(DetectionError::EbpfLoad("eBPF is only supported on Linux".to_owned()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_object_rejects_invalid_elf() {
+        let result = verify_object(b"not an elf object");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_program_verification_json_roundtrip() {
+        let verification = ProgramVerification {
+            name: "ironpost_xdp".to_owned(),
+            program_type: "Xdp".to_owned(),
+            xlated_insns: Some(412),
+            verified_insns: Some(1830),
+            jited_bytes: 2210,
+        };
+        let json = serde_json::to_string(&verification).expect("serialize");
+        let parsed: ProgramVerification = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed, verification);
+    }
+}
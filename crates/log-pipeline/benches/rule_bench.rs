@@ -37,10 +37,11 @@ fn create_simple_rule(id: &str) -> DetectionRule {
             conditions: vec![FieldCondition {
                 field: "process".to_owned(),
                 modifier: ConditionModifier::Exact,
-                value: "sshd".to_owned(),
+                values: vec!["sshd".to_owned()],
             }],
-            threshold: None,
+            ..Default::default()
         },
+        correlation: None,
         tags: vec!["test".to_owned()],
     }
 }
@@ -56,10 +57,11 @@ fn create_regex_rule(id: &str, pattern: &str) -> DetectionRule {
             conditions: vec![FieldCondition {
                 field: "message".to_owned(),
                 modifier: ConditionModifier::Regex,
-                value: pattern.to_owned(),
+                values: vec![pattern.to_owned()],
             }],
-            threshold: None,
+            ..Default::default()
         },
+        correlation: None,
         tags: vec!["test".to_owned()],
     }
 }
@@ -76,21 +78,22 @@ fn create_complex_rule(id: &str) -> DetectionRule {
                 FieldCondition {
                     field: "process".to_owned(),
                     modifier: ConditionModifier::Exact,
-                    value: "sshd".to_owned(),
+                    values: vec!["sshd".to_owned()],
                 },
                 FieldCondition {
                     field: "message".to_owned(),
                     modifier: ConditionModifier::Contains,
-                    value: "Failed password".to_owned(),
+                    values: vec!["Failed password".to_owned()],
                 },
                 FieldCondition {
                     field: "source_ip".to_owned(),
                     modifier: ConditionModifier::Regex,
-                    value: r"192\.168\.\d+\.\d+".to_owned(),
+                    values: vec![r"192\.168\.\d+\.\d+".to_owned()],
                 },
             ],
-            threshold: None,
+            ..Default::default()
         },
+        correlation: None,
         tags: vec!["authentication".to_owned(), "brute_force".to_owned()],
     }
 }
@@ -106,14 +109,16 @@ fn create_threshold_rule(id: &str) -> DetectionRule {
             conditions: vec![FieldCondition {
                 field: "process".to_owned(),
                 modifier: ConditionModifier::Exact,
-                value: "sshd".to_owned(),
+                values: vec!["sshd".to_owned()],
             }],
             threshold: Some(ThresholdConfig {
                 field: "source_ip".to_owned(),
                 count: 5,
                 timeframe_secs: 300,
             }),
+            ..Default::default()
         },
+        correlation: None,
         tags: vec!["test".to_owned()],
     }
 }
@@ -258,8 +258,9 @@ mod tests {
                 status: RuleStatus::Enabled,
                 detection: DetectionCondition {
                     conditions: vec![],
-                    threshold: None,
+                    ..Default::default()
                 },
+                correlation: None,
                 tags: vec![],
             },
             entry: LogEntry {
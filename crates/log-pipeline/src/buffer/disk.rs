@@ -0,0 +1,324 @@
+//! 디스크 오버플로 버퍼 -- 인메모리 버퍼를 넘친 로그를 세그먼트 파일에 적재합니다.
+//!
+//! 세그먼트/인덱스 파일과 레코드 프레임(길이, CRC32)은 계측 채널의 `Spill` 정책과 같은
+//! [`SpillQueue`] 구현을 사용합니다. 이 모듈은 [`RawLog`]를 레코드 본문으로 인코딩합니다.
+//!
+//! 본문 형식 (정수는 리틀 엔디언):
+//! ```text
+//! u64 수신 시각(초) | u32 나노초 | u32 소스 길이 | 소스
+//! | u8 힌트 여부 | u32 힌트 길이 | 힌트 | 데이터
+//! ```
+//!
+//! 재시작하면 읽기 위치부터 남은 로그를 다시 읽습니다. 파일 I/O는 [`SpillQueue`]가
+//! `spawn_blocking`에서 실행합니다.
+
+use std::io;
+use std::path::Path;
+use std::time::{Duration, UNIX_EPOCH};
+
+use bytes::Bytes;
+
+use ironpost_core::config::DiskBufferConfig;
+use ironpost_core::metrics as m;
+use ironpost_core::spill::SpillQueue;
+
+use crate::collector::RawLog;
+use crate::error::LogPipelineError;
+
+/// 디스크 오버플로 버퍼
+pub struct DiskSpill {
+    queue: SpillQueue,
+}
+
+impl DiskSpill {
+    /// 디스크 버퍼를 열고, 이전 실행에서 남은 세그먼트를 복구합니다.
+    ///
+    /// # Errors
+    /// 디렉토리 생성 또는 세그먼트 파일 읽기 실패
+    pub async fn open(config: &DiskBufferConfig) -> Result<Self, LogPipelineError> {
+        let queue = SpillQueue::open(&config.dir, config.max_bytes, config.segment_bytes).await?;
+        if !queue.is_empty() {
+            tracing::info!(
+                dir = %queue.dir().display(),
+                records = queue.len(),
+                bytes = queue.bytes(),
+                "recovered disk buffer"
+            );
+        }
+        let spill = Self { queue };
+        spill.report();
+        Ok(spill)
+    }
+
+    /// 로그를 디스크에 적재합니다.
+    ///
+    /// 디스크 사용량이 `max_bytes`를 넘게 되면 적재하지 않고 `Ok(false)`를 반환합니다.
+    ///
+    /// # Errors
+    /// 세그먼트 파일 생성 또는 쓰기 실패
+    pub async fn push(&mut self, raw_log: &RawLog) -> io::Result<bool> {
+        let stored = self.queue.push(encode_body(raw_log)?).await?;
+        if stored {
+            metrics::counter!(m::LOG_PIPELINE_LOGS_SPILLED_TOTAL).increment(1);
+            self.report();
+        }
+        Ok(stored)
+    }
+
+    /// 가장 오래된 로그부터 최대 `max`개를 읽어 `out`에 추가합니다.
+    ///
+    /// 읽거나 디코딩할 수 없는 로그는 버리고, 버린 로그 수를 반환합니다.
+    pub async fn pop_batch(&mut self, max: usize, out: &mut Vec<RawLog>) -> usize {
+        let (records, mut lost) = match self.queue.pop_batch(max).await {
+            Ok(popped) => popped,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to read disk buffer");
+                return 0;
+            }
+        };
+        for record in records {
+            match decode_body(&record) {
+                Ok(raw_log) => out.push(raw_log),
+                Err(e) => {
+                    tracing::warn!(error = %e, "dropping undecodable disk buffer record");
+                    lost += 1;
+                }
+            }
+        }
+        self.report();
+        lost
+    }
+
+    /// 가장 오래된 세그먼트를 버리고 버린 로그 수를 반환합니다.
+    ///
+    /// 쓰기 중인 세그먼트만 남았으면 버리지 않고 0을 반환합니다.
+    pub async fn drop_oldest_segment(&mut self) -> usize {
+        let dropped = self.queue.drop_oldest_segment().await.unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "failed to drop disk buffer segment");
+            0
+        });
+        self.report();
+        dropped
+    }
+
+    /// 읽지 않은 로그 수
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// 읽지 않은 로그가 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// 읽지 않은 데이터 크기 (바이트)
+    pub fn bytes(&self) -> u64 {
+        self.queue.bytes()
+    }
+
+    /// 디스크 버퍼 디렉토리
+    pub fn dir(&self) -> &Path {
+        self.queue.dir()
+    }
+
+    fn report(&self) {
+        metrics::gauge!(m::LOG_PIPELINE_DISK_BUFFER_BYTES).set(self.queue.bytes() as f64);
+    }
+}
+
+fn encode_body(raw_log: &RawLog) -> io::Result<Vec<u8>> {
+    let received = raw_log
+        .received_at
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let hint = raw_log.format_hint.as_deref().unwrap_or_default();
+
+    let mut body = Vec::with_capacity(25 + raw_log.source.len() + hint.len() + raw_log.data.len());
+    body.extend_from_slice(&received.as_secs().to_le_bytes());
+    body.extend_from_slice(&received.subsec_nanos().to_le_bytes());
+    put_field(&mut body, raw_log.source.as_bytes())?;
+    body.push(u8::from(raw_log.format_hint.is_some()));
+    put_field(&mut body, hint.as_bytes())?;
+    body.extend_from_slice(&raw_log.data);
+    Ok(body)
+}
+
+fn put_field(body: &mut Vec<u8>, field: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(field.len()).map_err(|_| invalid_data("field too large"))?;
+    body.extend_from_slice(&len.to_le_bytes());
+    body.extend_from_slice(field);
+    Ok(())
+}
+
+fn decode_body(body: &[u8]) -> io::Result<RawLog> {
+    let mut rest = body;
+    let secs = u64::from_le_bytes(take_array(&mut rest)?);
+    let nanos = u32::from_le_bytes(take_array(&mut rest)?);
+    let source = take_string(&mut rest)?;
+    let [has_hint] = take_array(&mut rest)?;
+    let hint = take_string(&mut rest)?;
+
+    Ok(RawLog {
+        data: Bytes::copy_from_slice(rest),
+        source,
+        received_at: UNIX_EPOCH + Duration::new(secs, nanos.min(999_999_999)),
+        format_hint: (has_hint != 0).then_some(hint),
+    })
+}
+
+fn take<'a>(rest: &mut &'a [u8], n: usize) -> io::Result<&'a [u8]> {
+    let (head, tail) = rest
+        .split_at_checked(n)
+        .ok_or_else(|| invalid_data("truncated record body"))?;
+    *rest = tail;
+    Ok(head)
+}
+
+fn take_array<const N: usize>(rest: &mut &[u8]) -> io::Result<[u8; N]> {
+    let mut array = [0u8; N];
+    array.copy_from_slice(take(rest, N)?);
+    Ok(array)
+}
+
+fn take_string(rest: &mut &[u8]) -> io::Result<String> {
+    let len = u32::from_le_bytes(take_array(rest)?) as usize;
+    String::from_utf8(take(rest, len)?.to_vec())
+        .map_err(|_| invalid_data("invalid UTF-8 in record"))
+}
+
+fn invalid_data(reason: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(dir: &Path, max_bytes: u64, segment_bytes: u64) -> DiskBufferConfig {
+        DiskBufferConfig {
+            enabled: true,
+            dir: dir.display().to_string(),
+            max_bytes,
+            segment_bytes,
+        }
+    }
+
+    fn raw_log(data: &str) -> RawLog {
+        RawLog::new(Bytes::from(data.to_owned()), "test")
+    }
+
+    async fn pop_all(spill: &mut DiskSpill) -> Vec<String> {
+        let mut out = Vec::new();
+        spill.pop_batch(usize::MAX, &mut out).await;
+        out.iter()
+            .map(|log| String::from_utf8_lossy(&log.data).into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn body_roundtrip_preserves_fields() {
+        let log = RawLog::new(
+            Bytes::from_static(b"\x00binary\xff"),
+            "file:/var/log/app.log",
+        )
+        .with_format_hint("json");
+        let decoded = decode_body(&encode_body(&log).unwrap()).unwrap();
+
+        assert_eq!(decoded.data, log.data);
+        assert_eq!(decoded.source, log.source);
+        assert_eq!(decoded.received_at, log.received_at);
+        assert_eq!(decoded.format_hint.as_deref(), Some("json"));
+        assert!(
+            decode_body(&encode_body(&raw_log("x")).unwrap())
+                .unwrap()
+                .format_hint
+                .is_none()
+        );
+        assert!(decode_body(b"short").is_err());
+    }
+
+    #[tokio::test]
+    async fn push_and_pop_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spill = DiskSpill::open(&config(dir.path(), 1024 * 1024, 128))
+            .await
+            .unwrap();
+        for i in 0..20 {
+            assert!(spill.push(&raw_log(&format!("log{i}"))).await.unwrap());
+        }
+        assert_eq!(spill.len(), 20);
+        assert!(spill.bytes() > 0);
+
+        let mut out = Vec::new();
+        assert_eq!(spill.pop_batch(5, &mut out).await, 0);
+        assert_eq!(&out[0].data[..], b"log0");
+        assert_eq!(&out[0].source, "test");
+
+        let rest = pop_all(&mut spill).await;
+        assert_eq!(rest.first().map(String::as_str), Some("log5"));
+        assert_eq!(rest.len(), 15);
+        assert!(spill.is_empty());
+        assert_eq!(spill.bytes(), 0);
+    }
+
+    #[tokio::test]
+    async fn push_rejects_when_max_bytes_reached() {
+        let dir = tempfile::tempdir().unwrap();
+        // 프레임 헤더 8바이트 포함
+        let record_bytes = encode_body(&raw_log("log0")).unwrap().len() as u64 + 8;
+        let mut spill = DiskSpill::open(&config(dir.path(), record_bytes * 4, record_bytes * 2))
+            .await
+            .unwrap();
+        for i in 0..4 {
+            assert!(spill.push(&raw_log(&format!("log{i}"))).await.unwrap());
+        }
+        assert!(!spill.push(&raw_log("log4")).await.unwrap());
+
+        assert_eq!(spill.drop_oldest_segment().await, 2);
+        assert!(spill.push(&raw_log("log4")).await.unwrap());
+        assert_eq!(pop_all(&mut spill).await, vec!["log2", "log3", "log4"]);
+    }
+
+    #[tokio::test]
+    async fn reopen_resumes_from_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(dir.path(), 1024 * 1024, 128);
+        {
+            let mut spill = DiskSpill::open(&config).await.unwrap();
+            for i in 0..10 {
+                spill.push(&raw_log(&format!("log{i}"))).await.unwrap();
+            }
+            let mut out = Vec::new();
+            spill.pop_batch(3, &mut out).await;
+        }
+
+        let mut spill = DiskSpill::open(&config).await.unwrap();
+        assert_eq!(spill.len(), 7);
+        let logs = pop_all(&mut spill).await;
+        assert_eq!(logs.first().map(String::as_str), Some("log3"));
+        assert_eq!(logs.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn undecodable_record_is_counted_as_lost() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(dir.path(), 1024 * 1024, 1024 * 1024);
+        {
+            // 같은 형식의 큐에 RawLog가 아닌 본문을 섞어 넣음
+            let mut queue = SpillQueue::open(&config.dir, config.max_bytes, config.segment_bytes)
+                .await
+                .unwrap();
+            queue
+                .push(encode_body(&raw_log("good")).unwrap())
+                .await
+                .unwrap();
+            queue.push(b"bad".to_vec()).await.unwrap();
+        }
+
+        let mut spill = DiskSpill::open(&config).await.unwrap();
+        let mut out = Vec::new();
+        assert_eq!(spill.pop_batch(10, &mut out).await, 1);
+        assert_eq!(out.len(), 1);
+        assert!(spill.is_empty());
+    }
+}
@@ -0,0 +1,706 @@
+//! 로그 버퍼링 -- 인메모리 버퍼 및 배치 플러시
+//!
+//! [`LogBuffer`]는 수집된 원시 로그를 인메모리에 버퍼링하고,
+//! 배치 크기 또는 시간 간격에 따라 플러시합니다.
+//!
+//! # 오버플로우 정책
+//! 버퍼가 가득 찬 경우:
+//! - [`DropPolicy::Oldest`]: 가장 오래된 엔트리를 드롭
+//! - [`DropPolicy::Newest`]: 새 유입을 거부
+//!
+//! # 디스크 오버플로
+//! [`LogBuffer::with_disk_spill`]로 [`DiskSpill`]을 붙이면 버퍼가 가득 찼을 때 드롭하는
+//! 대신 디스크 세그먼트에 적재하고, 드레인할 때 메모리 다음으로 디스크에서 순서대로
+//! 읽습니다. 디스크에 남은 로그가 있는 동안에는 새 로그도 디스크에 적재해 순서를
+//! 유지합니다. 드롭 정책은 디스크 한도(`max_bytes`)에 도달했을 때 적용됩니다.
+//! 디스크 I/O는 블로킹 스레드에서 실행되므로 [`LogBuffer::push`]와 드레인 메서드는
+//! `async`입니다.
+
+pub mod disk;
+
+pub use disk::DiskSpill;
+
+use std::collections::VecDeque;
+
+use crate::collector::RawLog;
+use crate::config::DropPolicy;
+
+/// 인메모리 로그 버퍼
+///
+/// 수집된 원시 로그를 임시 저장하고, 배치 단위로 파서에 전달합니다.
+/// 버퍼 용량이 초과되면 설정된 드롭 정책에 따라 엔트리를 제거합니다.
+pub struct LogBuffer {
+    /// 버퍼 내부 저장소
+    buffer: VecDeque<RawLog>,
+    /// 최대 용량
+    capacity: usize,
+    /// 드롭 정책
+    drop_policy: DropPolicy,
+    /// 드롭된 엔트리 카운터 (통계용)
+    dropped_count: u64,
+    /// 총 유입 엔트리 카운터
+    total_received: u64,
+    /// 디스크 오버플로 버퍼 (설정된 경우)
+    spill: Option<DiskSpill>,
+}
+
+impl LogBuffer {
+    /// 새 로그 버퍼를 생성합니다.
+    pub fn new(capacity: usize, drop_policy: DropPolicy) -> Self {
+        // capacity가 0이면 최소 1로 설정
+        let actual_capacity = if capacity == 0 {
+            tracing::warn!("buffer capacity is 0, setting to minimum 1");
+            1
+        } else {
+            capacity
+        };
+
+        Self {
+            buffer: VecDeque::with_capacity(actual_capacity.min(10_000)),
+            capacity: actual_capacity,
+            drop_policy,
+            dropped_count: 0,
+            total_received: 0,
+            spill: None,
+        }
+    }
+
+    /// 디스크 오버플로 버퍼를 붙입니다.
+    ///
+    /// 이전 실행에서 디스크에 남은 로그는 메모리 버퍼 다음 순서로 드레인됩니다.
+    pub fn with_disk_spill(mut self, spill: DiskSpill) -> Self {
+        self.attach_disk_spill(spill);
+        self
+    }
+
+    /// 생성된 버퍼에 디스크 오버플로 버퍼를 붙입니다 (이미 있으면 교체).
+    pub fn attach_disk_spill(&mut self, spill: DiskSpill) {
+        self.spill = Some(spill);
+    }
+
+    /// 디스크 오버플로 버퍼가 붙어 있는지 확인합니다.
+    pub fn has_disk_spill(&self) -> bool {
+        self.spill.is_some()
+    }
+
+    /// 로그를 버퍼에 추가합니다.
+    ///
+    /// 버퍼가 가득 찬 경우 드롭 정책에 따라 처리합니다.
+    /// 드롭이 발생하면 `true`를 반환합니다.
+    pub async fn push(&mut self, raw_log: RawLog) -> bool {
+        self.total_received += 1;
+
+        if let Some(spill) = self.spill.as_mut()
+            && (self.buffer.len() >= self.capacity || !spill.is_empty())
+        {
+            match spill.push(&raw_log).await {
+                Ok(true) => return false,
+                Ok(false) if self.drop_policy == DropPolicy::Oldest => {
+                    let dropped = spill.drop_oldest_segment().await;
+                    if dropped > 0 {
+                        self.dropped_count += dropped as u64;
+                        tracing::warn!(
+                            dropped = self.dropped_count,
+                            segment_records = dropped,
+                            "disk buffer full, dropped oldest segment"
+                        );
+                        if matches!(spill.push(&raw_log).await, Ok(true)) {
+                            return true;
+                        }
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::warn!(error = %e, "failed to write to disk buffer");
+                }
+            }
+            // 디스크에 넣지 못했으면 메모리 버퍼 정책을 따릅니다.
+        }
+
+        if self.buffer.len() >= self.capacity {
+            match self.drop_policy {
+                DropPolicy::Oldest => {
+                    self.buffer.pop_front();
+                    self.dropped_count += 1;
+                    tracing::warn!(
+                        dropped = self.dropped_count,
+                        capacity = self.capacity,
+                        "buffer full, dropped oldest entry"
+                    );
+                    self.buffer.push_back(raw_log);
+                    return true;
+                }
+                DropPolicy::Newest => {
+                    self.dropped_count += 1;
+                    tracing::warn!(
+                        dropped = self.dropped_count,
+                        capacity = self.capacity,
+                        "buffer full, rejected new entry"
+                    );
+                    return true;
+                }
+            }
+        }
+
+        self.buffer.push_back(raw_log);
+        false
+    }
+
+    /// 배치 크기만큼 또는 버퍼에 남은 만큼 엔트리를 드레인합니다.
+    ///
+    /// 버퍼가 비어있으면 빈 Vec을 반환합니다.
+    pub async fn drain_batch(&mut self, batch_size: usize) -> Vec<RawLog> {
+        let count = batch_size.min(self.buffer.len());
+        let mut batch: Vec<RawLog> = self.buffer.drain(..count).collect();
+        self.drain_spill(batch_size - count, &mut batch).await;
+        batch
+    }
+
+    /// 버퍼의 모든 엔트리를 드레인합니다.
+    ///
+    /// 디스크 오버플로 버퍼에서는 한 번에 최대 `capacity`개만 읽습니다.
+    pub async fn drain_all(&mut self) -> Vec<RawLog> {
+        let mut batch: Vec<RawLog> = self.buffer.drain(..).collect();
+        self.drain_spill(self.capacity, &mut batch).await;
+        batch
+    }
+
+    /// 디스크 오버플로 버퍼에서 최대 `max`개를 읽어 `batch`에 추가합니다.
+    async fn drain_spill(&mut self, max: usize, batch: &mut Vec<RawLog>) {
+        let Some(spill) = self.spill.as_mut() else {
+            return;
+        };
+        if max == 0 || spill.is_empty() {
+            return;
+        }
+        let lost = spill.pop_batch(max, batch).await;
+        if lost > 0 {
+            self.dropped_count += lost as u64;
+        }
+    }
+
+    /// 현재 버퍼에 저장된 엔트리 수를 반환합니다 (디스크 오버플로 버퍼 포함).
+    pub fn len(&self) -> usize {
+        self.buffer.len() + self.disk_len()
+    }
+
+    /// 버퍼가 비어있는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty() && self.disk_len() == 0
+    }
+
+    /// 디스크 오버플로 버퍼에 남은 엔트리 수를 반환합니다.
+    pub fn disk_len(&self) -> usize {
+        self.spill.as_ref().map_or(0, DiskSpill::len)
+    }
+
+    /// 디스크 오버플로 버퍼에 남은 데이터 크기(바이트)를 반환합니다.
+    pub fn disk_bytes(&self) -> u64 {
+        self.spill.as_ref().map_or(0, DiskSpill::bytes)
+    }
+
+    /// 버퍼 최대 용량을 반환합니다.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 지금까지 드롭된 엔트리 수를 반환합니다.
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped_count
+    }
+
+    /// 총 유입 엔트리 수를 반환합니다.
+    pub fn total_received(&self) -> u64 {
+        self.total_received
+    }
+
+    /// 메모리 버퍼 사용률을 0.0~1.0 범위로 반환합니다.
+    pub fn utilization(&self) -> f64 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        f64::from(u32::try_from(self.buffer.len()).unwrap_or(u32::MAX))
+            / f64::from(u32::try_from(self.capacity).unwrap_or(u32::MAX))
+    }
+
+    /// 메모리 버퍼에 적재된 로그의 메모리 사용량을 추정합니다 (바이트).
+    ///
+    /// 엔트리 구조체 크기와 페이로드/소스 문자열 길이를 합산합니다.
+    pub fn memory_estimate(&self) -> usize {
+        self.buffer
+            .iter()
+            .map(|log| {
+                std::mem::size_of::<RawLog>()
+                    + log.data.len()
+                    + log.source.len()
+                    + log.format_hint.as_ref().map_or(0, String::len)
+            })
+            .sum()
+    }
+
+    /// 배치 플러시 조건을 확인합니다.
+    ///
+    /// 버퍼에 `batch_size` 이상의 엔트리가 있으면 `true`를 반환합니다.
+    pub fn should_flush(&self, batch_size: usize) -> bool {
+        self.len() >= batch_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    fn make_raw_log(msg: &str) -> RawLog {
+        RawLog::new(Bytes::copy_from_slice(msg.as_bytes()), "test")
+    }
+
+    #[tokio::test]
+    async fn push_and_drain() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        buf.push(make_raw_log("log1")).await;
+        buf.push(make_raw_log("log2")).await;
+        buf.push(make_raw_log("log3")).await;
+        assert_eq!(buf.len(), 3);
+
+        let batch = buf.drain_batch(2).await;
+        assert_eq!(batch.len(), 2);
+        assert_eq!(buf.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn drain_all() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        for i in 0..5 {
+            buf.push(make_raw_log(&format!("log{i}"))).await;
+        }
+        let all = buf.drain_all().await;
+        assert_eq!(all.len(), 5);
+        assert!(buf.is_empty());
+    }
+
+    #[tokio::test]
+    async fn oldest_drop_policy() {
+        let mut buf = LogBuffer::new(3, DropPolicy::Oldest);
+        buf.push(make_raw_log("log1")).await;
+        buf.push(make_raw_log("log2")).await;
+        buf.push(make_raw_log("log3")).await;
+        assert_eq!(buf.len(), 3);
+        assert_eq!(buf.dropped_count(), 0);
+
+        // 4번째 추가 시 가장 오래된 것이 드롭됨
+        let dropped = buf.push(make_raw_log("log4")).await;
+        assert!(dropped);
+        assert_eq!(buf.len(), 3);
+        assert_eq!(buf.dropped_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn newest_drop_policy() {
+        let mut buf = LogBuffer::new(2, DropPolicy::Newest);
+        buf.push(make_raw_log("log1")).await;
+        buf.push(make_raw_log("log2")).await;
+
+        // 3번째는 거부됨
+        let dropped = buf.push(make_raw_log("log3")).await;
+        assert!(dropped);
+        assert_eq!(buf.len(), 2);
+        assert_eq!(buf.dropped_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn utilization_calculation() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        assert_eq!(buf.utilization(), 0.0);
+
+        for i in 0..50 {
+            buf.push(make_raw_log(&format!("log{i}"))).await;
+        }
+        let util = buf.utilization();
+        assert!(util > 0.49 && util < 0.51);
+    }
+
+    #[tokio::test]
+    async fn should_flush() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        assert!(!buf.should_flush(10));
+
+        for i in 0..10 {
+            buf.push(make_raw_log(&format!("log{i}"))).await;
+        }
+        assert!(buf.should_flush(10));
+        assert!(!buf.should_flush(11));
+    }
+
+    #[tokio::test]
+    async fn total_received_tracks_all() {
+        let mut buf = LogBuffer::new(2, DropPolicy::Oldest);
+        buf.push(make_raw_log("1")).await;
+        buf.push(make_raw_log("2")).await;
+        buf.push(make_raw_log("3")).await; // drops 1
+
+        assert_eq!(buf.total_received(), 3);
+        assert_eq!(buf.dropped_count(), 1);
+        assert_eq!(buf.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn drain_batch_larger_than_buffer() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        buf.push(make_raw_log("log1")).await;
+        buf.push(make_raw_log("log2")).await;
+
+        let batch = buf.drain_batch(100).await;
+        assert_eq!(batch.len(), 2); // returns what's available
+        assert!(buf.is_empty());
+    }
+
+    // === Edge Case Tests ===
+
+    #[tokio::test]
+    async fn create_buffer_with_zero_capacity() {
+        let mut buf = LogBuffer::new(0, DropPolicy::Oldest);
+        // With capacity 0, buffer will still accept items (VecDeque behavior)
+        // but capacity check will fail, so it drops oldest and adds new
+        let _dropped = buf.push(make_raw_log("log1")).await;
+        // Implementation may allow push even with 0 capacity
+        // Buffer might have 0 or 1 items depending on implementation
+        assert!(buf.len() <= 1);
+        // Either dropped or not is acceptable behavior
+    }
+
+    #[tokio::test]
+    async fn create_buffer_with_capacity_one() {
+        let mut buf = LogBuffer::new(1, DropPolicy::Oldest);
+        buf.push(make_raw_log("log1")).await;
+        assert_eq!(buf.len(), 1);
+
+        let dropped = buf.push(make_raw_log("log2")).await;
+        assert!(dropped);
+        assert_eq!(buf.len(), 1);
+        assert_eq!(buf.dropped_count(), 1);
+    }
+
+    #[test]
+    fn create_buffer_with_very_large_capacity() {
+        let buf = LogBuffer::new(1_000_000, DropPolicy::Oldest);
+        assert_eq!(buf.capacity(), 1_000_000);
+        // VecDeque pre-allocation is capped at 10,000
+        assert!(buf.is_empty());
+    }
+
+    #[tokio::test]
+    async fn drain_from_empty_buffer() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        let batch = buf.drain_batch(10).await;
+        assert_eq!(batch.len(), 0);
+        assert!(buf.is_empty());
+    }
+
+    #[tokio::test]
+    async fn drain_all_from_empty_buffer() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        let all = buf.drain_all().await;
+        assert_eq!(all.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn drain_batch_with_zero_size() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        buf.push(make_raw_log("log1")).await;
+        let batch = buf.drain_batch(0).await;
+        assert_eq!(batch.len(), 0);
+        assert_eq!(buf.len(), 1); // Nothing drained
+    }
+
+    #[tokio::test]
+    async fn multiple_drain_operations() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        for i in 0..10 {
+            buf.push(make_raw_log(&format!("log{i}"))).await;
+        }
+
+        let batch1 = buf.drain_batch(3).await;
+        assert_eq!(batch1.len(), 3);
+        assert_eq!(buf.len(), 7);
+
+        let batch2 = buf.drain_batch(4).await;
+        assert_eq!(batch2.len(), 4);
+        assert_eq!(buf.len(), 3);
+
+        let batch3 = buf.drain_all().await;
+        assert_eq!(batch3.len(), 3);
+        assert!(buf.is_empty());
+    }
+
+    #[tokio::test]
+    async fn oldest_policy_maintains_fifo_order() {
+        let mut buf = LogBuffer::new(3, DropPolicy::Oldest);
+        buf.push(make_raw_log("log1")).await;
+        buf.push(make_raw_log("log2")).await;
+        buf.push(make_raw_log("log3")).await;
+        buf.push(make_raw_log("log4")).await; // Drops log1
+
+        let batch = buf.drain_all().await;
+        assert_eq!(batch.len(), 3);
+        // Should contain log2, log3, log4 in order
+        assert!(String::from_utf8_lossy(&batch[0].data).contains("log2"));
+        assert!(String::from_utf8_lossy(&batch[1].data).contains("log3"));
+        assert!(String::from_utf8_lossy(&batch[2].data).contains("log4"));
+    }
+
+    #[tokio::test]
+    async fn newest_policy_preserves_first_entries() {
+        let mut buf = LogBuffer::new(3, DropPolicy::Newest);
+        buf.push(make_raw_log("log1")).await;
+        buf.push(make_raw_log("log2")).await;
+        buf.push(make_raw_log("log3")).await;
+        buf.push(make_raw_log("log4")).await; // Rejected
+        buf.push(make_raw_log("log5")).await; // Rejected
+
+        let batch = buf.drain_all().await;
+        assert_eq!(batch.len(), 3);
+        assert_eq!(buf.dropped_count(), 2);
+        // Should still contain log1, log2, log3
+        assert!(String::from_utf8_lossy(&batch[0].data).contains("log1"));
+    }
+
+    #[test]
+    fn utilization_with_empty_buffer() {
+        let buf = LogBuffer::new(100, DropPolicy::Oldest);
+        assert_eq!(buf.utilization(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn utilization_with_full_buffer() {
+        let mut buf = LogBuffer::new(10, DropPolicy::Newest);
+        for i in 0..10 {
+            buf.push(make_raw_log(&format!("log{i}"))).await;
+        }
+        let util = buf.utilization();
+        assert!((util - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn utilization_with_zero_capacity() {
+        let buf = LogBuffer::new(0, DropPolicy::Oldest);
+        assert_eq!(buf.utilization(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn should_flush_boundary_values() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        for i in 0..9 {
+            buf.push(make_raw_log(&format!("log{i}"))).await;
+        }
+        assert!(!buf.should_flush(10));
+
+        buf.push(make_raw_log("log9")).await;
+        assert!(buf.should_flush(10));
+        assert!(!buf.should_flush(11));
+    }
+
+    #[tokio::test]
+    async fn should_flush_with_zero_batch_size() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        buf.push(make_raw_log("log1")).await;
+        assert!(buf.should_flush(0)); // Always true with 0 batch size
+    }
+
+    #[tokio::test]
+    async fn total_received_increments_on_every_push() {
+        let mut buf = LogBuffer::new(2, DropPolicy::Oldest);
+        assert_eq!(buf.total_received(), 0);
+
+        buf.push(make_raw_log("1")).await;
+        assert_eq!(buf.total_received(), 1);
+
+        buf.push(make_raw_log("2")).await;
+        assert_eq!(buf.total_received(), 2);
+
+        buf.push(make_raw_log("3")).await; // Drops first
+        assert_eq!(buf.total_received(), 3);
+        assert_eq!(buf.dropped_count(), 1);
+        assert_eq!(buf.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn stress_test_many_push_and_drain_cycles() {
+        let mut buf = LogBuffer::new(100, DropPolicy::Oldest);
+        for cycle in 0..10 {
+            for i in 0..50 {
+                buf.push(make_raw_log(&format!("cycle{cycle}_log{i}")))
+                    .await;
+            }
+            let batch = buf.drain_batch(30).await;
+            assert_eq!(batch.len(), 30);
+        }
+        assert_eq!(buf.total_received(), 500);
+        assert!(buf.len() <= 100);
+    }
+
+    #[tokio::test]
+    async fn alternating_policies_behavior() {
+        // Test Oldest
+        let mut buf1 = LogBuffer::new(2, DropPolicy::Oldest);
+        buf1.push(make_raw_log("a")).await;
+        buf1.push(make_raw_log("b")).await;
+        buf1.push(make_raw_log("c")).await; // Drops "a"
+
+        let batch1 = buf1.drain_all().await;
+        assert!(String::from_utf8_lossy(&batch1[0].data).contains("b"));
+        assert!(String::from_utf8_lossy(&batch1[1].data).contains("c"));
+
+        // Test Newest
+        let mut buf2 = LogBuffer::new(2, DropPolicy::Newest);
+        buf2.push(make_raw_log("a")).await;
+        buf2.push(make_raw_log("b")).await;
+        buf2.push(make_raw_log("c")).await; // Rejects "c"
+
+        let batch2 = buf2.drain_all().await;
+        assert!(String::from_utf8_lossy(&batch2[0].data).contains("a"));
+        assert!(String::from_utf8_lossy(&batch2[1].data).contains("b"));
+    }
+
+    #[tokio::test]
+    async fn large_raw_log_data() {
+        let mut buf = LogBuffer::new(10, DropPolicy::Oldest);
+        let large_data = "x".repeat(1_000_000);
+        buf.push(make_raw_log(&large_data)).await;
+        assert_eq!(buf.len(), 1);
+
+        let batch = buf.drain_all().await;
+        assert_eq!(batch[0].data.len(), 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn push_returns_false_when_no_drop() {
+        let mut buf = LogBuffer::new(10, DropPolicy::Oldest);
+        let dropped = buf.push(make_raw_log("log1")).await;
+        assert!(!dropped);
+    }
+
+    #[tokio::test]
+    async fn push_returns_true_when_drop_occurs_oldest() {
+        let mut buf = LogBuffer::new(2, DropPolicy::Oldest);
+        buf.push(make_raw_log("1")).await;
+        buf.push(make_raw_log("2")).await;
+        let dropped = buf.push(make_raw_log("3")).await;
+        assert!(dropped);
+    }
+
+    #[tokio::test]
+    async fn push_returns_true_when_drop_occurs_newest() {
+        let mut buf = LogBuffer::new(2, DropPolicy::Newest);
+        buf.push(make_raw_log("1")).await;
+        buf.push(make_raw_log("2")).await;
+        let dropped = buf.push(make_raw_log("3")).await;
+        assert!(dropped);
+    }
+
+    #[tokio::test]
+    async fn capacity_remains_constant() {
+        let mut buf = LogBuffer::new(50, DropPolicy::Oldest);
+        assert_eq!(buf.capacity(), 50);
+
+        for i in 0..100 {
+            buf.push(make_raw_log(&format!("{i}"))).await;
+        }
+        assert_eq!(buf.capacity(), 50); // Capacity never changes
+
+        buf.drain_all().await;
+        assert_eq!(buf.capacity(), 50);
+    }
+
+    #[tokio::test]
+    async fn memory_estimate_grows_with_entries() {
+        let mut buf = LogBuffer::new(10, DropPolicy::Oldest);
+        assert_eq!(buf.memory_estimate(), 0);
+
+        buf.push(make_raw_log("hello")).await;
+        let one = buf.memory_estimate();
+        assert!(one >= std::mem::size_of::<RawLog>() + "hello".len());
+
+        buf.push(make_raw_log("world")).await;
+        assert_eq!(buf.memory_estimate(), one * 2);
+    }
+
+    // === Disk Spill Tests ===
+
+    fn spill_config(dir: &std::path::Path) -> ironpost_core::config::DiskBufferConfig {
+        ironpost_core::config::DiskBufferConfig {
+            enabled: true,
+            dir: dir.display().to_string(),
+            max_bytes: 1024 * 1024,
+            segment_bytes: 64 * 1024,
+        }
+    }
+
+    fn data(batch: &[RawLog]) -> Vec<String> {
+        batch
+            .iter()
+            .map(|log| String::from_utf8_lossy(&log.data).into_owned())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn overflow_spills_to_disk_and_drains_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let spill = DiskSpill::open(&spill_config(dir.path())).await.unwrap();
+        let mut buf = LogBuffer::new(2, DropPolicy::Oldest).with_disk_spill(spill);
+        for i in 0..5 {
+            assert!(!buf.push(make_raw_log(&format!("log{i}"))).await);
+        }
+        assert_eq!(buf.len(), 5);
+        assert_eq!(buf.disk_len(), 3);
+        assert!(buf.disk_bytes() > 0);
+        assert_eq!(buf.dropped_count(), 0);
+        assert!(buf.should_flush(5));
+
+        let batch = buf.drain_batch(3).await;
+        assert_eq!(data(&batch), vec!["log0", "log1", "log2"]);
+
+        // 디스크에 남은 로그가 있으면 새 로그도 디스크 뒤에 이어짐
+        buf.push(make_raw_log("log5")).await;
+        assert_eq!(buf.disk_len(), 3);
+        assert_eq!(
+            data(&buf.drain_batch(10).await),
+            vec!["log3", "log4", "log5"]
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[tokio::test]
+    async fn spilled_logs_survive_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let spill = DiskSpill::open(&spill_config(dir.path())).await.unwrap();
+            let mut buf = LogBuffer::new(1, DropPolicy::Oldest).with_disk_spill(spill);
+            for i in 0..4 {
+                buf.push(make_raw_log(&format!("log{i}"))).await;
+            }
+            assert_eq!(data(&buf.drain_batch(2).await), vec!["log0", "log1"]);
+        }
+
+        let spill = DiskSpill::open(&spill_config(dir.path())).await.unwrap();
+        let mut buf = LogBuffer::new(10, DropPolicy::Oldest).with_disk_spill(spill);
+        assert_eq!(buf.len(), 2);
+        assert_eq!(data(&buf.drain_all().await), vec!["log2", "log3"]);
+    }
+
+    #[tokio::test]
+    async fn drain_all_reads_at_most_capacity_from_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let spill = DiskSpill::open(&spill_config(dir.path())).await.unwrap();
+        let mut buf = LogBuffer::new(2, DropPolicy::Newest).with_disk_spill(spill);
+        for i in 0..7 {
+            buf.push(make_raw_log(&format!("log{i}"))).await;
+        }
+
+        // 메모리 2개 + 디스크 2개, 이후 디스크에서 2개씩
+        assert_eq!(buf.drain_all().await.len(), 4);
+        assert_eq!(buf.drain_all().await.len(), 2);
+        assert_eq!(buf.drain_all().await.len(), 1);
+        assert!(buf.is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+//! 압축 로그 파일(gzip) 읽기 — 로테이션된 과거 로그 재처리
+//!
+//! [`FileCollector`](super::FileCollector)는 `.gz` 파일을 tail하지 않고, `replay_archives`가
+//! 켜져 있으면 압축을 풀어 처음부터 끝까지 한 번만 수집합니다. 과거 로그를 룰 엔진에 다시
+//! 통과시켜 사후 탐지를 할 때 사용합니다.
+//!
+//! 여러 gzip 멤버를 이어 붙인 파일도 끝까지 읽습니다. 압축 해제는 블로킹 I/O이므로
+//! 수집기는 [`ArchiveReader`]를 `spawn_blocking` 안에서 사용합니다.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+use bytes::Bytes;
+use flate2::read::MultiGzDecoder;
+
+/// 압축 로그 파일인지 확장자로 확인합니다.
+pub fn is_archive(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "gz")
+}
+
+/// gzip 파일 라인 리더
+///
+/// 위치는 압축을 푼 데이터 기준 바이트 오프셋입니다.
+#[derive(Debug)]
+pub struct ArchiveReader {
+    /// 압축 해제 리더
+    reader: BufReader<MultiGzDecoder<File>>,
+    /// 압축을 푼 데이터에서 읽은 위치
+    offset: u64,
+}
+
+impl ArchiveReader {
+    /// 압축 파일을 열고 `skip` 바이트(압축 해제 기준)를 건너뜁니다.
+    ///
+    /// 열린 파일의 메타데이터를 함께 반환합니다.
+    pub fn open(path: &Path, skip: u64) -> std::io::Result<(Self, std::fs::Metadata)> {
+        let file = File::open(path)?;
+        let meta = file.metadata()?;
+        let mut reader = BufReader::new(MultiGzDecoder::new(file));
+        let offset = std::io::copy(&mut (&mut reader).take(skip), &mut std::io::sink())?;
+        Ok((Self { reader, offset }, meta))
+    }
+
+    /// 압축을 푼 데이터에서 읽은 위치를 반환합니다.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// 최대 `max_lines`개의 라인을 읽습니다.
+    ///
+    /// 압축 파일은 더 이상 쓰이지 않으므로 개행 없는 마지막 라인도 그대로 읽습니다.
+    /// `max_line_length`를 넘는 라인과 빈 라인은 건너뜁니다.
+    /// 반환값: (읽은 라인들, 끝 도달 여부)
+    pub fn read_lines(
+        &mut self,
+        max_lines: usize,
+        max_line_length: usize,
+    ) -> std::io::Result<(Vec<Bytes>, bool)> {
+        let mut lines = Vec::new();
+        let mut line_buffer = Vec::new();
+
+        while lines.len() < max_lines {
+            line_buffer.clear();
+            let limit = max_line_length.saturating_add(1) as u64;
+            let bytes_read = (&mut self.reader)
+                .take(limit)
+                .read_until(b'\n', &mut line_buffer)?;
+            if bytes_read == 0 {
+                return Ok((lines, true));
+            }
+            self.offset += bytes_read as u64;
+
+            if !line_buffer.ends_with(b"\n") && line_buffer.len() > max_line_length {
+                let mut skipped = Vec::new();
+                self.offset += self.reader.read_until(b'\n', &mut skipped)? as u64;
+                tracing::warn!(
+                    "Skipping line exceeding max length ({} bytes, max: {})",
+                    line_buffer.len() + skipped.len(),
+                    max_line_length
+                );
+                continue;
+            }
+
+            let line = line_buffer.trim_ascii_end();
+            if !line.trim_ascii_start().is_empty() {
+                lines.push(Bytes::copy_from_slice(line));
+            }
+        }
+
+        let at_end = self.reader.fill_buf()?.is_empty();
+        Ok((lines, at_end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    fn write_gz(path: &Path, members: &[&str]) {
+        let mut file = File::create(path).unwrap();
+        for member in members {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(member.as_bytes()).unwrap();
+            file.write_all(&encoder.finish().unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn detects_archive_extension() {
+        assert!(is_archive(Path::new("/var/log/syslog.2.gz")));
+        assert!(!is_archive(Path::new("/var/log/syslog.1")));
+        assert!(!is_archive(Path::new("/var/log/gz")));
+    }
+
+    #[test]
+    fn reads_all_members_in_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log.2.gz");
+        write_gz(&path, &["one\ntwo\n", "\nthree"]);
+
+        let (mut reader, _) = ArchiveReader::open(&path, 0).unwrap();
+        let (lines, at_end) = reader.read_lines(2, 1024).unwrap();
+        assert_eq!(lines, vec![Bytes::from("one"), Bytes::from("two")]);
+        assert!(!at_end);
+
+        let (lines, at_end) = reader.read_lines(10, 1024).unwrap();
+        assert_eq!(lines, vec![Bytes::from("three")]);
+        assert!(at_end);
+        assert_eq!(reader.offset(), 14);
+    }
+
+    #[test]
+    fn open_skips_already_read_bytes_and_long_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log.gz");
+        write_gz(&path, &[&format!("one\n{}\nlast\n", "x".repeat(100))]);
+
+        let (mut reader, _) = ArchiveReader::open(&path, 4).unwrap();
+        let (lines, at_end) = reader.read_lines(10, 32).unwrap();
+        assert_eq!(lines, vec![Bytes::from("last")]);
+        assert!(at_end);
+    }
+
+    #[test]
+    fn corrupt_archive_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.gz");
+        std::fs::write(&path, b"not gzip at all\n").unwrap();
+
+        let (mut reader, _) = ArchiveReader::open(&path, 0).unwrap();
+        assert!(reader.read_lines(10, 1024).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! 파일 수집기 체크포인트 — 재시작 후 읽던 위치부터 이어 읽기
+//!
+//! [`FileCheckpoints`]는 [`FileCollector`](super::FileCollector)가 추적 중인 파일별
+//! 읽기 위치(경로, inode, 오프셋)를 JSON 파일 하나로 저장합니다. 수집기는 시작 시 이 파일을
+//! 읽고, 실행 중에는 위치가 바뀌었을 때 주기적으로, 종료 시에는 마지막으로 한 번 저장합니다.
+//!
+//! 복원할 때는 inode가 같은 파일을 우선 찾으므로, 수집기가 멈춘 사이 로테이션으로 옮겨진
+//! 파일도 glob 패턴에 잡히면 읽던 위치부터 이어 읽습니다.
+//!
+//! # 파일 형식
+//! ```json
+//! {
+//!   "version": 1,
+//!   "files": [
+//!     { "path": "/var/log/nginx/access.log", "file_id": 1835029, "offset": 48213 },
+//!     { "path": "/var/log/nginx/access.log.2.gz", "file_id": 1835011, "offset": 920114, "complete": true }
+//!   ]
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::LogPipelineError;
+
+/// 현재 체크포인트 파일 형식 버전
+pub const CHECKPOINT_FILE_VERSION: u32 = 1;
+
+/// 체크포인트 파일 최대 크기 (10MB)
+const MAX_CHECKPOINT_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// 파일 하나의 읽기 위치
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileCheckpoint {
+    /// 마지막으로 읽은 경로
+    pub path: PathBuf,
+    /// 파일 식별자 (Unix: inode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<u64>,
+    /// 마지막으로 읽은 완성된 라인의 끝 위치 (바이트 오프셋, 압축 파일은 압축 해제 기준)
+    pub offset: u64,
+    /// 압축 파일을 끝까지 수집했는지 (다시 수집하지 않음)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub complete: bool,
+}
+
+/// 체크포인트 파일에 저장하는 파일별 읽기 위치 목록
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileCheckpoints {
+    /// 파일별 읽기 위치
+    #[serde(default)]
+    pub files: Vec<FileCheckpoint>,
+}
+
+/// 디스크 형식 (버전 필드 포함)
+#[derive(Serialize, Deserialize)]
+struct CheckpointFile {
+    version: u32,
+    #[serde(flatten)]
+    checkpoints: FileCheckpoints,
+}
+
+impl FileCheckpoints {
+    /// 체크포인트 파일을 읽습니다.
+    ///
+    /// 파일이 없으면 `None`을 반환합니다.
+    ///
+    /// # Errors
+    ///
+    /// 파일이 너무 크거나, 파싱에 실패하거나, 지원하지 않는 버전이면 에러를 반환합니다.
+    pub async fn load(path: impl AsRef<Path>) -> Result<Option<Self>, LogPipelineError> {
+        let path = path.as_ref();
+        match tokio::fs::metadata(path).await {
+            Ok(metadata) if metadata.len() > MAX_CHECKPOINT_FILE_SIZE => {
+                return Err(invalid(format!(
+                    "checkpoint file too large: {} bytes (max: {} bytes)",
+                    metadata.len(),
+                    MAX_CHECKPOINT_FILE_SIZE
+                )));
+            }
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        let content = tokio::fs::read(path).await?;
+        let file: CheckpointFile = serde_json::from_slice(&content).map_err(|e| {
+            invalid(format!(
+                "failed to parse checkpoint file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        if file.version != CHECKPOINT_FILE_VERSION {
+            return Err(invalid(format!(
+                "unsupported checkpoint file version {} (expected {})",
+                file.version, CHECKPOINT_FILE_VERSION
+            )));
+        }
+        Ok(Some(file.checkpoints))
+    }
+
+    /// 체크포인트 파일에 저장합니다.
+    ///
+    /// 임시 파일에 쓴 뒤 rename하므로 저장 도중 종료되어도 이전 파일이 깨지지 않습니다.
+    /// 상위 디렉토리가 없으면 생성합니다.
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<(), LogPipelineError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let file = CheckpointFile {
+            version: CHECKPOINT_FILE_VERSION,
+            checkpoints: self.clone(),
+        };
+        let body = serde_json::to_vec_pretty(&file)
+            .map_err(|e| invalid(format!("failed to serialize checkpoint file: {}", e)))?;
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        tokio::fs::write(&tmp, body).await?;
+        tokio::fs::rename(&tmp, path).await?;
+        Ok(())
+    }
+
+    /// 파일에 해당하는 체크포인트를 꺼냅니다.
+    ///
+    /// 식별자가 있으면 식별자로, 없으면 경로로 찾습니다.
+    pub fn take(&mut self, path: &Path, file_id: Option<u64>) -> Option<FileCheckpoint> {
+        let index = self.files.iter().position(|checkpoint| match file_id {
+            Some(id) => checkpoint.file_id == Some(id),
+            None => checkpoint.file_id.is_none() && checkpoint.path == path,
+        })?;
+        Some(self.files.swap_remove(index))
+    }
+}
+
+fn invalid(reason: String) -> LogPipelineError {
+    LogPipelineError::Collector {
+        source_type: "file".to_owned(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("file-checkpoints.json");
+
+        assert!(FileCheckpoints::load(&path).await.unwrap().is_none());
+
+        let checkpoints = FileCheckpoints {
+            files: vec![
+                FileCheckpoint {
+                    path: PathBuf::from("/var/log/app.log"),
+                    file_id: Some(42),
+                    offset: 1024,
+                    complete: false,
+                },
+                FileCheckpoint {
+                    path: PathBuf::from("/var/log/other.log"),
+                    file_id: None,
+                    offset: 7,
+                    complete: true,
+                },
+            ],
+        };
+        checkpoints.save(&path).await.unwrap();
+
+        let loaded = FileCheckpoints::load(&path).await.unwrap().unwrap();
+        assert_eq!(loaded, checkpoints);
+    }
+
+    #[tokio::test]
+    async fn load_rejects_unknown_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file-checkpoints.json");
+        tokio::fs::write(&path, r#"{"version": 99, "files": []}"#)
+            .await
+            .unwrap();
+
+        let err = FileCheckpoints::load(&path).await.unwrap_err();
+        assert!(err.to_string().contains("version"));
+    }
+
+    #[test]
+    fn take_matches_by_file_id_then_path() {
+        let mut checkpoints = FileCheckpoints {
+            files: vec![
+                FileCheckpoint {
+                    path: PathBuf::from("/var/log/app.log"),
+                    file_id: Some(42),
+                    offset: 10,
+                    complete: false,
+                },
+                FileCheckpoint {
+                    path: PathBuf::from("/var/log/plain.log"),
+                    file_id: None,
+                    offset: 20,
+                    complete: false,
+                },
+            ],
+        };
+
+        // 옮겨진 파일도 식별자로 찾음
+        let moved = checkpoints
+            .take(Path::new("/var/log/app.log.1"), Some(42))
+            .unwrap();
+        assert_eq!(moved.offset, 10);
+        assert!(
+            checkpoints
+                .take(Path::new("/var/log/app.log"), Some(42))
+                .is_none()
+        );
+
+        let plain = checkpoints
+            .take(Path::new("/var/log/plain.log"), None)
+            .unwrap();
+        assert_eq!(plain.offset, 20);
+        assert!(checkpoints.files.is_empty());
+    }
+}
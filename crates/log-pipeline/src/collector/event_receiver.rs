@@ -118,7 +118,7 @@ impl EventReceiver {
     /// 패킷 정보를 JSON으로 직렬화하여 일반 로그 파서가 처리할 수 있도록 합니다.
     /// trace_id를 보존하여 이벤트 추적 연속성을 유지합니다.
     fn packet_event_to_raw_log(event: &PacketEvent) -> Result<RawLog, LogPipelineError> {
-        let json = serde_json::json!({
+        let mut json = serde_json::json!({
             "source": "ebpf",
             "event_type": "packet",
             "trace_id": event.metadata.trace_id,
@@ -136,6 +136,10 @@ impl EventReceiver {
                 event.packet_info.dst_port,
             ),
         });
+        // TLS SNI가 있으면 규칙이 `sni` 필드로 도메인을 매칭할 수 있도록 추가
+        if let Some(ref sni) = event.sni {
+            json["sni"] = serde_json::Value::String(sni.clone());
+        }
 
         let data = serde_json::to_vec(&json).map_err(|e| LogPipelineError::Collector {
             source_type: "event_receiver".to_owned(),
@@ -189,6 +193,15 @@ mod tests {
         assert_eq!(value["src_ip"], "192.168.1.1");
         assert_eq!(value["dst_port"], 80);
         assert_eq!(value["protocol"], 6);
+        assert!(value.get("sni").is_none());
+    }
+
+    #[test]
+    fn packet_event_to_raw_log_includes_sni() {
+        let event = sample_packet_event().with_sni("login.example.net");
+        let raw = EventReceiver::packet_event_to_raw_log(&event).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&raw.data).unwrap();
+        assert_eq!(value["sni"], "login.example.net");
     }
 
     #[test]
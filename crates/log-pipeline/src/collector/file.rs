@@ -1,38 +1,94 @@
 //! 파일 기반 로그 수집기
 //!
 //! 로그 파일을 감시하며 새로운 라인이 추가되면 수집합니다.
-//! `tail -f`와 유사한 동작을 비동기 방식으로 구현합니다.
+//! `tail -F`와 유사한 동작을 비동기 방식으로 구현합니다.
 //!
-//! # 로테이션 감지
-//! - inode 변경 감지 (logrotate 등)
-//! - 파일 크기 축소 감지 (truncation)
-//! - 새 파일 자동 열기
+//! # 감시 경로
+//! `watch_paths`에는 파일 경로나 glob 패턴(`/var/log/*.log`, `/var/log/nginx/*/access.log`)을
+//! 지정할 수 있습니다. 패턴은 폴링마다 다시 검색하므로 나중에 생긴 파일도 처음부터 수집하고,
+//! 사라진 파일은 남은 내용을 마저 읽은 뒤 추적을 멈춥니다.
+//!
+//! # 로테이션 처리
+//! 파일 핸들을 열어 둔 채로 읽으므로 logrotate가 파일을 옮겨도 이어서 읽을 수 있습니다.
+//! - rename (`create` 방식): 경로의 inode가 바뀌면 이전 파일을 끝까지 읽은 뒤 새 파일을
+//!   처음부터 읽습니다. 로테이션 직전에 쓰인 라인도 유실되지 않습니다.
+//! - truncate (`copytruncate` 방식): 파일 크기가 읽은 위치보다 작아지면 처음부터 다시 읽습니다.
+//! - 옮겨진 파일이 패턴에 다시 잡히면(`app.log*` 등) 읽던 위치부터 이어 읽어 중복을 막습니다.
+//!
+//! 개행으로 끝나지 않은 마지막 라인은 아직 쓰는 중일 수 있으므로 개행이 올 때까지 기다립니다.
+//!
+//! # 체크포인트
+//! `checkpoint_path`를 지정하면 파일별 읽기 위치를 주기적으로 저장하고, 재시작하면 저장된
+//! 위치부터 이어 읽습니다 ([`FileCheckpoints`]). 위치는 채널로 보낸 라인 기준이므로 종료 직전에
+//! 보낸 라인이 처리되지 못했다면 유실될 수 있고, 저장 주기 사이에 종료되면 일부 라인을 다시 읽습니다.
+//! 체크포인트가 없는 파일을 시작 시 어디서부터 읽을지는 [`ReadFrom`]으로 정합니다.
+//!
+//! # 압축 파일
+//! `.gz` 파일은 tail하지 않습니다. `replay_archives`가 켜져 있으면 압축을 풀어 한 번만
+//! 수집하고([`ArchiveReader`]), 끝까지 읽은 파일은 체크포인트에 완료로 기록합니다.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use bytes::Bytes;
 use tokio::fs::{File, metadata};
-use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, BufReader};
 use tokio::sync::mpsc;
 use tokio::time::sleep;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
+use ironpost_core::config::FileSourceConfig;
+
+use super::archive::{ArchiveReader, is_archive};
+use super::checkpoint::{FileCheckpoint, FileCheckpoints};
 use super::{CollectorStatus, RawLog};
 use crate::error::LogPipelineError;
 
+/// 로테이션으로 옮겨진 파일의 읽기 위치를 기억하는 시간
+///
+/// 옮겨진 파일이 glob 패턴에 다시 잡히는 것은 다음 폴링이므로 짧게 유지합니다.
+const RETIRED_FILE_TTL: Duration = Duration::from_secs(60);
+
+/// 폴링 한 번에 압축 파일에서 읽을 최대 배치 수 (배치당 `max_lines_per_read` 라인)
+///
+/// 과거 로그는 한꺼번에 쌓여 있으므로 tail보다 많이 읽되, 다른 파일의 tail이 밀리지 않도록 제한합니다.
+const ARCHIVE_BATCHES_PER_POLL: usize = 10;
+
 /// 파일 수집기 설정
 #[derive(Debug, Clone)]
 pub struct FileCollectorConfig {
-    /// 감시할 파일 경로 목록
+    /// 감시할 파일 경로 또는 glob 패턴 목록
     pub watch_paths: Vec<PathBuf>,
     /// 파일 상태 체크 주기 (밀리초)
     pub poll_interval_ms: u64,
-    /// 한 번에 읽을 최대 라인 수
+    /// 한 번에 읽을 최대 라인 수 (파일별)
     pub max_lines_per_read: usize,
-    /// 최대 라인 길이 (바이트)
+    /// 최대 라인 길이 (바이트, 초과 라인은 건너뜀)
     pub max_line_length: usize,
+    /// glob 패턴으로 동시에 추적할 최대 파일 수
+    pub max_files: usize,
+    /// 체크포인트 파일 경로 (`None`이면 저장하지 않음)
+    pub checkpoint_path: Option<PathBuf>,
+    /// 체크포인트 저장 주기
+    pub checkpoint_interval: Duration,
+    /// 시작 시 체크포인트가 없는 파일의 읽기 시작 위치
+    pub read_from: ReadFrom,
+    /// 저장된 체크포인트를 무시할지 여부
+    pub ignore_checkpoints: bool,
+    /// `.gz` 파일을 압축 해제하여 한 번 수집할지 여부 (끄면 건너뜀)
+    pub replay_archives: bool,
+}
+
+/// 체크포인트가 없는 파일의 읽기 시작 위치
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReadFrom {
+    /// 파일 처음부터 읽기
+    #[default]
+    Beginning,
+    /// 시작 시점의 파일 끝부터 새로 추가되는 라인만 읽기
+    End,
 }
 
 impl Default for FileCollectorConfig {
@@ -42,48 +98,119 @@ impl Default for FileCollectorConfig {
             poll_interval_ms: 1000,
             max_lines_per_read: 1000,
             max_line_length: 64 * 1024, // 64KB
+            max_files: 1024,
+            checkpoint_path: None,
+            checkpoint_interval: Duration::from_secs(5),
+            read_from: ReadFrom::Beginning,
+            ignore_checkpoints: false,
+            replay_archives: false,
+        }
+    }
+}
+
+impl FileCollectorConfig {
+    /// 감시 경로와 core의 `FileSourceConfig`에서 수집기 설정을 생성합니다.
+    pub fn from_core(watch_paths: &[String], core: &FileSourceConfig) -> Self {
+        let checkpoint_path = core.checkpoint_path.trim();
+        Self {
+            watch_paths: watch_paths.iter().map(PathBuf::from).collect(),
+            checkpoint_path: (!checkpoint_path.is_empty()).then(|| PathBuf::from(checkpoint_path)),
+            checkpoint_interval: Duration::from_secs(core.checkpoint_interval_secs),
+            read_from: match core.read_from.as_str() {
+                "end" => ReadFrom::End,
+                _ => ReadFrom::Beginning,
+            },
+            ignore_checkpoints: core.ignore_checkpoints,
+            replay_archives: core.replay_archives,
+            ..Self::default()
         }
     }
 }
 
+/// glob 패턴인지 확인합니다.
+pub fn is_glob_pattern(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
 /// 파일별 추적 상태
 #[derive(Debug)]
-#[allow(dead_code)]
 struct FileState {
     /// 파일 경로
-    #[allow(dead_code)]
     path: PathBuf,
-    /// 마지막 읽기 위치 (바이트 오프셋)
-    #[allow(dead_code)]
+    /// 열린 파일 (로테이션 뒤에도 남은 라인을 마저 읽기 위해 유지)
+    reader: Option<BufReader<File>>,
+    /// 마지막으로 읽은 완성된 라인의 끝 위치 (바이트 오프셋)
     offset: u64,
-    /// 현재 파일의 inode (Unix 전용)
-    #[cfg(unix)]
-    #[allow(dead_code)]
-    inode: Option<u64>,
+    /// 열린 파일의 식별자 (Unix: inode, 그 외 플랫폼은 로테이션 감지 안 함)
+    file_id: Option<u64>,
+    /// glob 패턴으로 찾은 파일인지 (경로가 사라지면 추적 중단)
+    discovered: bool,
+    /// 추적을 끝내고 목록에서 제거할지
+    finished: bool,
+    /// 수집기 시작 시점부터 있던 파일인지 (`read_from` 적용 대상)
+    at_startup: bool,
+    /// 압축 파일 수집 진행 상태 (일반 파일은 `None`)
+    archive: Option<ArchiveProgress>,
+}
+
+impl FileState {
+    fn new(path: PathBuf, discovered: bool, at_startup: bool) -> Self {
+        let archive = is_archive(&path).then_some(ArchiveProgress::Pending);
+        Self {
+            path,
+            reader: None,
+            offset: 0,
+            file_id: None,
+            discovered,
+            finished: false,
+            at_startup,
+            archive,
+        }
+    }
+}
+
+/// 압축 파일 수집 진행 상태
+#[derive(Debug)]
+enum ArchiveProgress {
+    /// 아직 열지 않음
+    Pending,
+    /// 수집 중
+    Reading(Box<ArchiveReader>),
+    /// 끝까지 수집함 (또는 읽을 수 없어 포기함)
+    Done,
+    /// `replay_archives`가 꺼져 있어 건너뜀
+    Skipped,
 }
 
 /// 파일 기반 로그 수집기
 ///
-/// 지정된 파일 목록을 주기적으로 폴링하여 새로운 로그 라인을 수집합니다.
-/// 파일 로테이션(inode 변경, truncation)을 자동 감지합니다.
-#[allow(dead_code)]
+/// 지정된 파일과 glob 패턴을 주기적으로 폴링하여 새로운 로그 라인을 수집합니다.
+/// 파일 로테이션(rename, truncation)을 자동 감지합니다.
 pub struct FileCollector {
     /// 수집기 설정
-    #[allow(dead_code)]
     config: FileCollectorConfig,
     /// 수집된 로그 전송 채널
-    #[allow(dead_code)]
     tx: mpsc::Sender<RawLog>,
     /// graceful shutdown을 위한 취소 토큰
     cancel_token: CancellationToken,
+    /// glob 패턴 목록
+    patterns: Vec<String>,
     /// 파일별 추적 상태
-    #[allow(dead_code)]
     file_states: Vec<FileState>,
+    /// 로테이션으로 옮겨진 파일의 (식별자 → 읽은 위치, 옮겨진 시각)
+    retired: HashMap<u64, (u64, Instant)>,
+    /// 체크포인트 파일에서 읽었지만 아직 복원하지 않은 읽기 위치
+    checkpoints: FileCheckpoints,
+    /// 디스크에 있는 체크포인트 (`None`이면 알 수 없으므로 다음 저장 때 덮어씀)
+    saved_checkpoints: Option<FileCheckpoints>,
+    /// 마지막 체크포인트 저장 시각
+    checkpoint_saved_at: Instant,
+    /// 첫 폴링 전인지 (시작 시점에 있던 파일 구분용)
+    starting: bool,
     /// 현재 상태
     status: CollectorStatus,
 }
 
-#[allow(dead_code)]
 impl FileCollector {
     /// 새 파일 수집기를 생성합니다.
     pub fn new(config: FileCollectorConfig, tx: mpsc::Sender<RawLog>) -> Self {
@@ -96,22 +223,30 @@ impl FileCollector {
         tx: mpsc::Sender<RawLog>,
         cancel_token: CancellationToken,
     ) -> Self {
-        let file_states = config
+        let (patterns, literals): (Vec<_>, Vec<_>) = config
             .watch_paths
             .iter()
-            .map(|path| FileState {
-                path: path.clone(),
-                offset: 0,
-                #[cfg(unix)]
-                inode: None,
-            })
+            .partition(|path| is_glob_pattern(&path.to_string_lossy()));
+        let patterns = patterns
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        let file_states = literals
+            .into_iter()
+            .map(|path| FileState::new(path.clone(), false, true))
             .collect();
 
         Self {
             config,
             tx,
             cancel_token,
+            patterns,
             file_states,
+            retired: HashMap::new(),
+            checkpoints: FileCheckpoints::default(),
+            saved_checkpoints: None,
+            checkpoint_saved_at: Instant::now(),
+            starting: true,
             status: CollectorStatus::Idle,
         }
     }
@@ -123,224 +258,542 @@ impl FileCollector {
     pub async fn run(&mut self) -> Result<(), LogPipelineError> {
         self.status = CollectorStatus::Running;
         info!(
-            "Starting file collector for {} files",
-            self.file_states.len()
+            "Starting file collector for {} files and {} patterns",
+            self.file_states.len(),
+            self.patterns.len()
         );
 
+        self.load_checkpoints().await;
         let poll_interval = Duration::from_millis(self.config.poll_interval_ms);
 
         loop {
+            if let Err(e) = self.poll_once().await {
+                self.save_checkpoints(true).await;
+                self.status = CollectorStatus::Error(e.to_string());
+                return Err(e);
+            }
+            self.save_checkpoints(false).await;
+
+            // 폴링 간격 대기
+            tokio::select! {
+                _ = sleep(poll_interval) => {}
+                _ = self.cancel_token.cancelled() => break,
+            }
+        }
+
+        info!("File collector received shutdown signal");
+        self.save_checkpoints(true).await;
+        self.status = CollectorStatus::Stopped;
+        Ok(())
+    }
+
+    /// 패턴을 다시 검색하고 모든 파일에서 새 라인을 한 번 읽습니다.
+    async fn poll_once(&mut self) -> Result<(), LogPipelineError> {
+        self.discover().await;
+
+        for i in 0..self.file_states.len() {
             if self.cancel_token.is_cancelled() {
-                info!("File collector received shutdown signal");
-                self.status = CollectorStatus::Stopped;
-                break;
+                return Ok(());
             }
+            self.poll_file(i).await?;
+        }
 
-            for i in 0..self.file_states.len() {
-                if self.cancel_token.is_cancelled() {
-                    info!("File collector received shutdown signal");
-                    self.status = CollectorStatus::Stopped;
-                    return Ok(());
-                }
+        self.file_states.retain(|state| !state.finished);
+        for state in &mut self.file_states {
+            state.at_startup = false;
+        }
+        self.starting = false;
+        self.retired
+            .retain(|_, (_, retired_at)| retired_at.elapsed() < RETIRED_FILE_TTL);
+        Ok(())
+    }
 
-                let path = self.file_states[i].path.clone();
-                let mut offset = self.file_states[i].offset;
-                #[cfg(unix)]
-                let mut inode = self.file_states[i].inode;
+    /// glob 패턴에 새로 잡힌 파일을 추적 목록에 추가합니다.
+    async fn discover(&mut self) {
+        if self.patterns.is_empty() {
+            return;
+        }
 
-                // 파일 로테이션 확인
-                #[cfg(unix)]
-                {
-                    if let Ok(rotated) = Self::check_rotation(&path, inode).await
-                        && rotated
-                    {
-                        info!("File rotation detected: {:?}", path);
-                        offset = 0;
-                        inode = Self::get_inode(&path).await.ok();
-                    }
-                }
+        let patterns = self.patterns.clone();
+        let matches = match tokio::task::spawn_blocking(move || expand_patterns(&patterns)).await {
+            Ok(matches) => matches,
+            Err(e) => {
+                error!("Failed to expand watch patterns: {}", e);
+                return;
+            }
+        };
 
-                // Truncation 감지
-                if let Ok(meta) = metadata(&path).await {
-                    let file_size = meta.len();
-                    if file_size < offset {
-                        warn!(
-                            "File truncation detected: {:?} (size: {}, offset: {})",
-                            path, file_size, offset
-                        );
-                        offset = 0;
-                    }
-                }
+        for path in matches {
+            if self.file_states.iter().any(|state| state.path == path) {
+                continue;
+            }
+            if self.file_states.len() >= self.config.max_files {
+                warn!(
+                    "Watched file limit reached ({}), ignoring {:?}",
+                    self.config.max_files, path
+                );
+                break;
+            }
+            info!("Discovered new file matching watch pattern: {:?}", path);
+            self.file_states
+                .push(FileState::new(path, true, self.starting));
+        }
+    }
 
-                // 새 라인 읽기
-                match Self::read_new_lines(&path, offset).await {
-                    Ok((lines, new_offset)) => {
-                        // 상태 업데이트
-                        self.file_states[i].offset = new_offset;
-                        #[cfg(unix)]
-                        {
-                            self.file_states[i].inode = inode;
-                        }
-
-                        // 읽은 라인을 RawLog로 변환하여 전송
-                        for line_bytes in lines {
-                            let raw_log =
-                                RawLog::new(line_bytes, format!("file:{}", path.display()))
-                                    .with_format_hint("syslog");
-
-                            if let Err(e) = self.tx.send(raw_log).await {
-                                error!("Failed to send log: {}", e);
-                                self.status = CollectorStatus::Error(e.to_string());
-                                return Err(LogPipelineError::Channel(e.to_string()));
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to read file {:?}: {}", path, e);
-                        // 에러 발생 시 백오프 후 계속 진행
-                        tokio::select! {
-                            _ = sleep(Duration::from_secs(1)) => {}
-                            _ = self.cancel_token.cancelled() => {
-                                info!("File collector received shutdown signal");
-                                self.status = CollectorStatus::Stopped;
-                                return Ok(());
-                            }
-                        }
-                    }
+    /// 파일 하나에서 새 라인을 읽어 전송하고 로테이션을 처리합니다.
+    ///
+    /// 채널이 닫혔을 때만 에러를 반환합니다. 파일 에러는 로그만 남기고 다음 폴링에서
+    /// 다시 시도합니다.
+    async fn poll_file(&mut self, i: usize) -> Result<(), LogPipelineError> {
+        if self.file_states[i].archive.is_some() {
+            for _ in 0..ARCHIVE_BATCHES_PER_POLL {
+                if !self.poll_archive(i).await? {
+                    break;
                 }
             }
+            return Ok(());
+        }
+        if self.file_states[i].reader.is_none() && !self.open_file(i).await {
+            return Ok(());
+        }
 
-            // 폴링 간격 대기
-            tokio::select! {
-                _ = sleep(poll_interval) => {}
-                _ = self.cancel_token.cancelled() => {
-                    info!("File collector received shutdown signal");
-                    self.status = CollectorStatus::Stopped;
-                    break;
+        // 1. 열린 파일에서 새 라인 읽기
+        let at_eof = match self.read_and_send(i, false).await? {
+            Some(at_eof) => at_eof,
+            None => return Ok(()),
+        };
+
+        // 2. 경로가 아직 같은 파일을 가리키는지 확인
+        let state = &mut self.file_states[i];
+        let current = metadata(&state.path).await.ok();
+        let same_file = current
+            .as_ref()
+            .is_some_and(|meta| file_id(meta) == state.file_id);
+
+        if same_file {
+            let size = current.map(|meta| meta.len()).unwrap_or_default();
+            if size < state.offset {
+                warn!(
+                    "File truncation detected: {:?} (size: {}, offset: {})",
+                    state.path, size, state.offset
+                );
+                if let Some(reader) = state.reader.as_mut()
+                    && let Err(e) = reader.seek(std::io::SeekFrom::Start(0)).await
+                {
+                    warn!("Failed to rewind {:?}: {}", state.path, e);
+                    state.reader = None;
                 }
+                state.offset = 0;
             }
+            return Ok(());
+        }
+
+        // 경로가 다른 파일로 바뀌었거나 사라짐: 이전 파일을 끝까지 읽은 뒤 놓아줌
+        if !at_eof {
+            return Ok(());
+        }
+        if self.read_and_send(i, true).await?.is_none() {
+            return Ok(());
         }
 
+        let state = &mut self.file_states[i];
+        if current.is_some() {
+            info!("File rotation detected: {:?}", state.path);
+        } else {
+            debug!("File removed: {:?}", state.path);
+        }
+        if let Some(id) = state.file_id.take() {
+            self.retired.insert(id, (state.offset, Instant::now()));
+        }
+        state.reader = None;
+        state.offset = 0;
+        state.finished = state.discovered && current.is_none();
         Ok(())
     }
 
-    /// 단일 파일에서 새로운 라인을 읽습니다.
+    /// 압축 파일을 한 번에 `max_lines_per_read`개 라인씩 끝까지 수집합니다.
     ///
-    /// 주어진 오프셋부터 파일을 읽어 새로운 라인들을 반환합니다.
-    /// 반환값: (읽은 라인들, 새로운 오프셋)
-    async fn read_new_lines(
-        path: &Path,
-        offset: u64,
-    ) -> Result<(Vec<Bytes>, u64), LogPipelineError> {
-        let file = File::open(path)
+    /// 압축 파일은 더 이상 쓰이지 않는다고 보고 로테이션은 확인하지 않습니다.
+    /// 아직 읽을 라인이 남아 있으면 `true`를 반환합니다.
+    async fn poll_archive(&mut self, i: usize) -> Result<bool, LogPipelineError> {
+        let state = &mut self.file_states[i];
+        let progress = state.archive.take().unwrap_or(ArchiveProgress::Done);
+        let mut reader = match progress {
+            ArchiveProgress::Reading(reader) => reader,
+            ArchiveProgress::Pending if !self.config.replay_archives => {
+                debug!(
+                    "Skipping compressed file {:?} (replay_archives disabled)",
+                    state.path
+                );
+                state.archive = Some(ArchiveProgress::Skipped);
+                return Ok(false);
+            }
+            ArchiveProgress::Pending => match self.open_archive(i).await {
+                Some(reader) => reader,
+                None => return Ok(false),
+            },
+            done @ (ArchiveProgress::Done | ArchiveProgress::Skipped) => {
+                // 삭제된 압축 파일은 추적 중단
+                state.finished = state.discovered && metadata(&state.path).await.is_err();
+                state.archive = Some(done);
+                return Ok(false);
+            }
+        };
+
+        let max_lines = self.config.max_lines_per_read;
+        let max_line_length = self.config.max_line_length;
+        let result = tokio::task::spawn_blocking(move || {
+            let read = reader.read_lines(max_lines, max_line_length);
+            (reader, read)
+        })
+        .await;
+
+        let state = &mut self.file_states[i];
+        let (reader, read) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                error!(
+                    "Compressed file reader task failed for {:?}: {}",
+                    state.path, e
+                );
+                state.archive = Some(ArchiveProgress::Done);
+                return Ok(false);
+            }
+        };
+        let (lines, at_end) = match read {
+            Ok(read) => read,
+            Err(e) => {
+                warn!("Failed to decompress {:?}: {}", state.path, e);
+                state.archive = Some(ArchiveProgress::Done);
+                return Ok(false);
+            }
+        };
+        state.offset = reader.offset();
+        if at_end {
+            info!(
+                "Replayed compressed file {:?} ({} bytes decompressed)",
+                state.path, state.offset
+            );
+            state.archive = Some(ArchiveProgress::Done);
+        } else {
+            state.archive = Some(ArchiveProgress::Reading(reader));
+        }
+
+        let source = format!("file:{}", state.path.display());
+        for line in lines {
+            let raw_log = RawLog::new(line, source.clone());
+            if let Err(e) = self.tx.send(raw_log).await {
+                error!("Failed to send log: {}", e);
+                return Err(LogPipelineError::Channel(e.to_string()));
+            }
+        }
+        Ok(!at_end)
+    }
+
+    /// 압축 파일을 열고 체크포인트 위치까지 건너뜁니다.
+    ///
+    /// 이미 끝까지 수집한 파일이거나 열 수 없으면 `None`을 반환합니다.
+    async fn open_archive(&mut self, i: usize) -> Option<Box<ArchiveReader>> {
+        let path = self.file_states[i].path.clone();
+        let id = match metadata(&path).await {
+            Ok(meta) => file_id(&meta),
+            Err(e) => {
+                debug!("Waiting for {:?}: {}", path, e);
+                let state = &mut self.file_states[i];
+                state.archive = Some(ArchiveProgress::Pending);
+                state.finished = state.discovered;
+                return None;
+            }
+        };
+        self.file_states[i].file_id = id;
+
+        let checkpoint = self.checkpoints.take(&path, id);
+        if let Some(checkpoint) = checkpoint.as_ref().filter(|c| c.complete) {
+            debug!("Compressed file {:?} already replayed", path);
+            self.file_states[i].offset = checkpoint.offset;
+            self.file_states[i].archive = Some(ArchiveProgress::Done);
+            return None;
+        }
+
+        let skip = checkpoint.map(|c| c.offset).unwrap_or(0);
+        let open_path = path.clone();
+        let opened = tokio::task::spawn_blocking(move || ArchiveReader::open(&open_path, skip))
             .await
-            .map_err(|e| LogPipelineError::Collector {
-                source_type: "file".to_owned(),
-                reason: format!("failed to open {:?}: {}", path, e),
-            })?;
+            .map_err(std::io::Error::other)
+            .and_then(|opened| opened);
+        match opened {
+            Ok((reader, _)) => {
+                info!("Replaying compressed file {:?} from offset {}", path, skip);
+                self.file_states[i].offset = reader.offset();
+                Some(Box::new(reader))
+            }
+            Err(e) => {
+                warn!("Failed to open compressed file {:?}: {}", path, e);
+                self.file_states[i].archive = Some(ArchiveProgress::Done);
+                None
+            }
+        }
+    }
+
+    /// 파일을 열고 읽기 시작 위치를 정합니다. 열지 못하면 `false`를 반환합니다.
+    async fn open_file(&mut self, i: usize) -> bool {
+        let path = self.file_states[i].path.clone();
+        let (file, meta) = match open(&path).await {
+            Ok(opened) => opened,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                debug!("Waiting for {:?} to be created", path);
+                return false;
+            }
+            Err(e) => {
+                warn!("Failed to open {:?}: {}", path, e);
+                return false;
+            }
+        };
+
+        let id = file_id(&meta);
+        // 다른 경로로 아직 읽고 있는 파일 (로테이션 직후 패턴에 먼저 잡힌 경우)
+        if id.is_some()
+            && self
+                .file_states
+                .iter()
+                .enumerate()
+                .any(|(j, state)| j != i && state.file_id == id)
+        {
+            return false;
+        }
+
+        // 로테이션으로 옮겨진 파일이나 체크포인트가 있는 파일이면 읽던 위치부터 이어 읽음
+        let resumed = match id.and_then(|id| self.retired.remove(&id)) {
+            Some((offset, _)) => Some(offset),
+            None => self
+                .checkpoints
+                .take(&path, id)
+                .map(|checkpoint| checkpoint.offset),
+        };
+        let offset = match resumed {
+            Some(offset) if offset <= meta.len() => offset,
+            // 멈춘 사이 잘린 파일
+            Some(_) => 0,
+            None if self.file_states[i].at_startup && self.config.read_from == ReadFrom::End => {
+                meta.len()
+            }
+            None => 0,
+        };
 
         let mut reader = BufReader::new(file);
+        if offset > 0
+            && let Err(e) = reader.seek(std::io::SeekFrom::Start(offset)).await
+        {
+            warn!("Failed to seek {:?} to {}: {}", path, offset, e);
+            return false;
+        }
 
-        // 오프셋으로 이동
-        reader
-            .seek(std::io::SeekFrom::Start(offset))
-            .await
-            .map_err(|e| LogPipelineError::Collector {
-                source_type: "file".to_owned(),
-                reason: format!("failed to seek to offset {}: {}", offset, e),
-            })?;
+        info!("Following {:?} from offset {}", path, offset);
+        let state = &mut self.file_states[i];
+        state.reader = Some(reader);
+        state.offset = offset;
+        state.file_id = id;
+        true
+    }
+
+    /// 열린 파일에서 라인을 읽어 전송합니다.
+    ///
+    /// EOF 도달 여부를 반환하며, 읽기 에러가 나면 파일을 닫고 `None`을 반환합니다.
+    async fn read_and_send(
+        &mut self,
+        i: usize,
+        flush_partial: bool,
+    ) -> Result<Option<bool>, LogPipelineError> {
+        let state = &mut self.file_states[i];
+        let Some(reader) = state.reader.as_mut() else {
+            return Ok(None);
+        };
+
+        let result = Self::read_new_lines(
+            reader,
+            &mut state.offset,
+            self.config.max_lines_per_read,
+            self.config.max_line_length,
+            flush_partial,
+        )
+        .await;
+        let (lines, at_eof) = match result {
+            Ok(read) => read,
+            Err(e) => {
+                warn!("Failed to read {:?}: {}", state.path, e);
+                state.reader = None;
+                state.file_id = None;
+                return Ok(None);
+            }
+        };
+
+        let source = format!("file:{}", state.path.display());
+        for line in lines {
+            let raw_log = RawLog::new(line, source.clone());
+            if let Err(e) = self.tx.send(raw_log).await {
+                error!("Failed to send log: {}", e);
+                return Err(LogPipelineError::Channel(e.to_string()));
+            }
+        }
+        Ok(Some(at_eof))
+    }
 
+    /// 열린 파일의 현재 위치부터 새로운 라인을 읽습니다.
+    ///
+    /// 개행으로 끝나지 않은 마지막 라인은 `flush_partial`이 아니면 읽지 않은 것으로 두고
+    /// 다음 폴링에서 다시 읽습니다. `max_line_length`를 넘는 라인은 건너뜁니다.
+    /// 반환값: (읽은 라인들, EOF 도달 여부)
+    async fn read_new_lines(
+        reader: &mut BufReader<File>,
+        offset: &mut u64,
+        max_lines: usize,
+        max_line_length: usize,
+        flush_partial: bool,
+    ) -> std::io::Result<(Vec<Bytes>, bool)> {
         let mut lines = Vec::new();
-        let mut current_offset = offset;
-        let mut line_buffer = String::new();
-        const MAX_LINE_LENGTH: usize = 64 * 1024; // 64KB default
+        let mut line_buffer = Vec::new();
 
         loop {
-            line_buffer.clear();
-
-            // 라인 길이 제한을 적용하며 읽기
-            let bytes_read = reader.read_line(&mut line_buffer).await.map_err(|e| {
-                LogPipelineError::Collector {
-                    source_type: "file".to_owned(),
-                    reason: format!("failed to read line: {}", e),
-                }
-            })?;
-
-            // 라인이 최대 길이를 초과하는지 확인
-            if line_buffer.len() > MAX_LINE_LENGTH {
-                return Err(LogPipelineError::Collector {
-                    source_type: "file".to_owned(),
-                    reason: format!(
-                        "line exceeds max length: {} (max: {})",
-                        line_buffer.len(),
-                        MAX_LINE_LENGTH
-                    ),
-                });
+            // 한 번에 너무 많은 라인을 읽지 않도록 제한
+            if lines.len() >= max_lines {
+                debug!(
+                    "Read batch limit reached ({} lines), will continue in next iteration",
+                    max_lines
+                );
+                return Ok((lines, false));
             }
 
+            line_buffer.clear();
+            let limit = max_line_length.saturating_add(1) as u64;
+            let bytes_read = (&mut *reader)
+                .take(limit)
+                .read_until(b'\n', &mut line_buffer)
+                .await?;
             if bytes_read == 0 {
                 // EOF 도달
-                break;
+                return Ok((lines, true));
             }
 
-            current_offset = current_offset
-                .checked_add(u64::try_from(bytes_read).map_err(|_| {
-                    LogPipelineError::Collector {
-                        source_type: "file".to_owned(),
-                        reason: format!("offset overflow: {}", bytes_read),
-                    }
-                })?)
-                .ok_or_else(|| LogPipelineError::Collector {
-                    source_type: "file".to_owned(),
-                    reason: "offset overflow".to_owned(),
-                })?;
+            let complete = line_buffer.ends_with(b"\n");
+            if !complete && line_buffer.len() > max_line_length {
+                let skipped = discard_line(reader).await?;
+                let total = bytes_read as u64 + skipped;
+                warn!(
+                    "Skipping line exceeding max length ({} bytes, max: {})",
+                    total, max_line_length
+                );
+                *offset += total;
+                continue;
+            }
+            if !complete && !flush_partial {
+                // 아직 쓰는 중인 라인: 다음 폴링에서 처음부터 다시 읽음
+                reader.seek(std::io::SeekFrom::Start(*offset)).await?;
+                return Ok((lines, true));
+            }
+
+            *offset += bytes_read as u64;
 
             // 빈 라인이 아니면 추가
-            if !line_buffer.trim().is_empty() {
-                lines.push(Bytes::from(line_buffer.trim_end().to_owned()));
+            let line = line_buffer.trim_ascii_end();
+            if !line.trim_ascii_start().is_empty() {
+                lines.push(Bytes::copy_from_slice(line));
             }
+        }
+    }
 
-            // 한 번에 너무 많은 라인을 읽지 않도록 제한
-            if lines.len() >= 1000 {
-                debug!("Read batch limit reached (1000 lines), will continue in next iteration");
-                break;
+    /// 체크포인트 파일에서 읽기 위치를 불러옵니다 (실패 시 경고만 남김).
+    async fn load_checkpoints(&mut self) {
+        let Some(path) = self.config.checkpoint_path.as_ref() else {
+            return;
+        };
+        if self.config.ignore_checkpoints {
+            info!(path = %path.display(), "ignoring saved file checkpoints");
+            return;
+        }
+        match FileCheckpoints::load(path).await {
+            Ok(Some(checkpoints)) => {
+                info!(
+                    path = %path.display(),
+                    files = checkpoints.files.len(),
+                    "restored file checkpoints"
+                );
+                self.saved_checkpoints = Some(checkpoints.clone());
+                self.checkpoints = checkpoints;
             }
+            Ok(None) => self.saved_checkpoints = Some(FileCheckpoints::default()),
+            Err(e) => warn!(
+                error = %e,
+                path = %path.display(),
+                "ignoring unreadable file checkpoint file"
+            ),
         }
-
-        Ok((lines, current_offset))
     }
 
-    /// 파일 로테이션 여부를 확인합니다.
+    /// 현재 읽기 위치를 체크포인트 파일에 저장합니다 (실패 시 경고만 남김).
     ///
-    /// Unix 시스템에서 inode를 비교하여 로테이션을 감지합니다.
-    #[cfg(unix)]
-    async fn check_rotation(
-        path: &Path,
-        last_inode: Option<u64>,
-    ) -> Result<bool, LogPipelineError> {
-        let current_inode = Self::get_inode(path).await?;
-
-        if let Some(last) = last_inode {
-            Ok(current_inode != last)
-        } else {
-            // 첫 번째 체크, 로테이션 아님
-            Ok(false)
+    /// `force`가 아니면 저장 주기가 지났고 위치가 바뀌었을 때만 저장합니다.
+    async fn save_checkpoints(&mut self, force: bool) {
+        let Some(path) = self.config.checkpoint_path.as_ref() else {
+            return;
+        };
+        if !force && self.checkpoint_saved_at.elapsed() < self.config.checkpoint_interval {
+            return;
         }
-    }
 
-    /// 파일의 inode를 가져옵니다 (Unix 전용).
-    #[cfg(unix)]
-    async fn get_inode(path: &Path) -> Result<u64, LogPipelineError> {
-        use std::os::unix::fs::MetadataExt;
+        let snapshot = self.snapshot();
+        if self.saved_checkpoints.as_ref() == Some(&snapshot) {
+            return;
+        }
+        if let Err(e) = snapshot.save(path).await {
+            warn!(
+                error = %e,
+                path = %path.display(),
+                "failed to save file checkpoints"
+            );
+            return;
+        }
+        self.saved_checkpoints = Some(snapshot);
+        self.checkpoint_saved_at = Instant::now();
+    }
 
-        let meta = metadata(path)
-            .await
-            .map_err(|e| LogPipelineError::Collector {
-                source_type: "file".to_owned(),
-                reason: format!("failed to get metadata for {:?}: {}", path, e),
-            })?;
+    /// 열린 파일의 읽기 위치와 아직 복원하지 못한 체크포인트를 모읍니다.
+    fn snapshot(&self) -> FileCheckpoints {
+        let mut files: Vec<_> = self
+            .file_states
+            .iter()
+            .filter_map(|state| {
+                let complete = match state.archive {
+                    None if state.reader.is_some() => false,
+                    Some(ArchiveProgress::Reading(_)) => false,
+                    Some(ArchiveProgress::Done) => true,
+                    _ => return None,
+                };
+                Some(FileCheckpoint {
+                    path: state.path.clone(),
+                    file_id: state.file_id,
+                    offset: state.offset,
+                    complete,
+                })
+            })
+            .collect();
+        // 아직 열지 못한 감시 대상 파일의 위치는 유지
+        files.extend(
+            self.checkpoints
+                .files
+                .iter()
+                .filter(|checkpoint| {
+                    self.file_states
+                        .iter()
+                        .any(|state| state.path == checkpoint.path)
+                })
+                .cloned(),
+        );
+        FileCheckpoints { files }
+    }
 
-        Ok(meta.ino())
+    /// 현재 추적 중인 파일 수를 반환합니다.
+    pub fn watched_files(&self) -> usize {
+        self.file_states.len()
     }
 
     /// 현재 상태를 반환합니다.
@@ -349,18 +802,139 @@ impl FileCollector {
     }
 }
 
+/// glob 패턴에 맞는 일반 파일 목록을 반환합니다 (블로킹 I/O).
+fn expand_patterns(patterns: &[String]) -> Vec<PathBuf> {
+    let mut matches = Vec::new();
+    for pattern in patterns {
+        let paths = match glob::glob(pattern) {
+            Ok(paths) => paths,
+            Err(e) => {
+                warn!("Invalid watch pattern '{}': {}", pattern, e);
+                continue;
+            }
+        };
+        for entry in paths {
+            match entry {
+                Ok(path) if path.is_file() => {
+                    if !matches.contains(&path) {
+                        matches.push(path);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => debug!("Skipping unreadable path in '{}': {}", pattern, e),
+            }
+        }
+    }
+    matches
+}
+
+/// 파일을 열고 열린 핸들의 메타데이터를 함께 반환합니다.
+async fn open(path: &Path) -> std::io::Result<(File, std::fs::Metadata)> {
+    let file = File::open(path).await?;
+    let meta = file.metadata().await?;
+    Ok((file, meta))
+}
+
+/// 로테이션 감지에 쓰는 파일 식별자 (Unix: inode)
+fn file_id(meta: &std::fs::Metadata) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some(meta.ino())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = meta;
+        None
+    }
+}
+
+/// 다음 개행까지 버리고 버린 바이트 수를 반환합니다.
+async fn discard_line(reader: &mut BufReader<File>) -> std::io::Result<u64> {
+    let mut skipped = 0u64;
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            return Ok(skipped);
+        }
+        match available.iter().position(|&b| b == b'\n') {
+            Some(pos) => {
+                reader.consume(pos + 1);
+                return Ok(skipped + pos as u64 + 1);
+            }
+            None => {
+                let n = available.len();
+                reader.consume(n);
+                skipped += n as u64;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::io::{Seek, Write};
-    use tempfile::NamedTempFile;
+    use tempfile::{NamedTempFile, TempDir};
     use tokio::fs;
 
+    async fn read_all(path: &Path, offset: u64, flush_partial: bool) -> (Vec<Bytes>, u64) {
+        let mut reader = BufReader::new(File::open(path).await.unwrap());
+        reader.seek(std::io::SeekFrom::Start(offset)).await.unwrap();
+        let mut offset = offset;
+        let (lines, at_eof) =
+            FileCollector::read_new_lines(&mut reader, &mut offset, 1000, 64, flush_partial)
+                .await
+                .unwrap();
+        assert!(at_eof);
+        (lines, offset)
+    }
+
+    fn append(path: &Path, text: &str) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    fn drain(rx: &mut mpsc::Receiver<RawLog>) -> Vec<String> {
+        let mut lines = Vec::new();
+        while let Ok(raw) = rx.try_recv() {
+            lines.push(String::from_utf8(raw.data.to_vec()).unwrap());
+        }
+        lines
+    }
+
+    fn collector(watch_paths: Vec<PathBuf>, tx: mpsc::Sender<RawLog>) -> FileCollector {
+        FileCollector::new(
+            FileCollectorConfig {
+                watch_paths,
+                ..FileCollectorConfig::default()
+            },
+            tx,
+        )
+    }
+
+    /// 체크포인트를 불러오고 한 번 폴링한 뒤 저장합니다 (재시작 한 번에 해당).
+    async fn run_once_with_checkpoints(
+        config: FileCollectorConfig,
+        tx: mpsc::Sender<RawLog>,
+    ) -> FileCollector {
+        let mut collector = FileCollector::new(config, tx);
+        collector.load_checkpoints().await;
+        collector.poll_once().await.unwrap();
+        collector.save_checkpoints(true).await;
+        collector
+    }
+
     #[test]
     fn default_config() {
         let config = FileCollectorConfig::default();
         assert_eq!(config.poll_interval_ms, 1000);
         assert_eq!(config.max_lines_per_read, 1000);
+        assert_eq!(config.max_files, 1024);
     }
 
     #[test]
@@ -370,6 +944,14 @@ mod tests {
         assert_eq!(*collector.status(), CollectorStatus::Idle);
     }
 
+    #[test]
+    fn glob_patterns_are_detected() {
+        assert!(is_glob_pattern("/var/log/*.log"));
+        assert!(is_glob_pattern("/var/log/app-?.log"));
+        assert!(is_glob_pattern("/var/log/[ab].log"));
+        assert!(!is_glob_pattern("/var/log/syslog"));
+    }
+
     #[tokio::test]
     async fn read_new_lines_from_file() {
         // 테스트 파일 생성
@@ -379,19 +961,14 @@ mod tests {
         writeln!(temp_file, "line 3").unwrap();
         temp_file.flush().unwrap();
 
-        let (tx, _rx) = mpsc::channel(10);
-        let _collector = FileCollector::new(FileCollectorConfig::default(), tx);
-
         // 오프셋 0부터 읽기
-        let (lines, new_offset) = FileCollector::read_new_lines(temp_file.path(), 0)
-            .await
-            .unwrap();
+        let (lines, new_offset) = read_all(temp_file.path(), 0, false).await;
 
         assert_eq!(lines.len(), 3);
         assert_eq!(lines[0].as_ref(), b"line 1");
         assert_eq!(lines[1].as_ref(), b"line 2");
         assert_eq!(lines[2].as_ref(), b"line 3");
-        assert!(new_offset > 0);
+        assert_eq!(new_offset, 21);
     }
 
     #[tokio::test]
@@ -403,13 +980,8 @@ mod tests {
         writeln!(temp_file, "line 3").unwrap();
         temp_file.flush().unwrap();
 
-        let (tx, _rx) = mpsc::channel(10);
-        let _collector = FileCollector::new(FileCollectorConfig::default(), tx);
-
         // 첫 번째 라인 이후부터 읽기
-        let (lines, _) = FileCollector::read_new_lines(temp_file.path(), first_offset)
-            .await
-            .unwrap();
+        let (lines, _) = read_all(temp_file.path(), first_offset, false).await;
 
         assert_eq!(lines.len(), 2);
         assert_eq!(lines[0].as_ref(), b"line 2");
@@ -420,12 +992,7 @@ mod tests {
     async fn read_empty_file() {
         let temp_file = NamedTempFile::new().unwrap();
 
-        let (tx, _rx) = mpsc::channel(10);
-        let _collector = FileCollector::new(FileCollectorConfig::default(), tx);
-
-        let (lines, new_offset) = FileCollector::read_new_lines(temp_file.path(), 0)
-            .await
-            .unwrap();
+        let (lines, new_offset) = read_all(temp_file.path(), 0, false).await;
 
         assert_eq!(lines.len(), 0);
         assert_eq!(new_offset, 0);
@@ -439,12 +1006,7 @@ mod tests {
         writeln!(temp_file, "line 2").unwrap();
         temp_file.flush().unwrap();
 
-        let (tx, _rx) = mpsc::channel(10);
-        let _collector = FileCollector::new(FileCollectorConfig::default(), tx);
-
-        let (lines, _) = FileCollector::read_new_lines(temp_file.path(), 0)
-            .await
-            .unwrap();
+        let (lines, _) = read_all(temp_file.path(), 0, false).await;
 
         // 빈 라인은 제외되어야 함
         assert_eq!(lines.len(), 2);
@@ -452,61 +1014,326 @@ mod tests {
         assert_eq!(lines[1].as_ref(), b"line 2");
     }
 
-    #[cfg(unix)]
     #[tokio::test]
-    async fn get_inode_returns_valid_inode() {
+    async fn partial_line_waits_for_newline() {
         let temp_file = NamedTempFile::new().unwrap();
-        let inode = FileCollector::get_inode(temp_file.path()).await.unwrap();
-        assert!(inode > 0);
+        append(temp_file.path(), "done\nhalf");
+
+        let (lines, offset) = read_all(temp_file.path(), 0, false).await;
+        assert_eq!(lines, vec![Bytes::from_static(b"done")]);
+        assert_eq!(offset, 5);
+
+        let (lines, offset) = read_all(temp_file.path(), offset, true).await;
+        assert_eq!(lines, vec![Bytes::from_static(b"half")]);
+        assert_eq!(offset, 9);
     }
 
-    #[cfg(unix)]
     #[tokio::test]
-    async fn check_rotation_detects_no_change() {
+    async fn overlong_line_is_skipped() {
         let temp_file = NamedTempFile::new().unwrap();
-        let inode = FileCollector::get_inode(temp_file.path()).await.unwrap();
+        append(temp_file.path(), &format!("{}\nnext\n", "x".repeat(200)));
 
-        let (tx, _rx) = mpsc::channel(10);
-        let _collector = FileCollector::new(FileCollectorConfig::default(), tx);
+        let (lines, offset) = read_all(temp_file.path(), 0, false).await;
+        assert_eq!(lines, vec![Bytes::from_static(b"next")]);
+        assert_eq!(offset, 206);
+    }
+
+    #[tokio::test]
+    async fn missing_file_is_followed_once_created() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        let (tx, mut rx) = mpsc::channel(100);
+        let mut collector = collector(vec![path.clone()], tx);
+
+        collector.poll_once().await.unwrap();
+        assert!(drain(&mut rx).is_empty());
+
+        append(&path, "hello\n");
+        collector.poll_once().await.unwrap();
+        assert_eq!(drain(&mut rx), vec!["hello"]);
+    }
+
+    #[test]
+    fn config_from_core() {
+        let core = FileSourceConfig {
+            checkpoint_path: "/tmp/ironpost/checkpoints.json".to_owned(),
+            read_from: "end".to_owned(),
+            ..FileSourceConfig::default()
+        };
+        let config = FileCollectorConfig::from_core(&["/var/log/*.log".to_owned()], &core);
+        assert_eq!(config.watch_paths, vec![PathBuf::from("/var/log/*.log")]);
+        assert_eq!(
+            config.checkpoint_path,
+            Some(PathBuf::from("/tmp/ironpost/checkpoints.json"))
+        );
+        assert_eq!(config.read_from, ReadFrom::End);
+
+        let disabled = FileSourceConfig {
+            checkpoint_path: String::new(),
+            ..FileSourceConfig::default()
+        };
+        let config = FileCollectorConfig::from_core(&[], &disabled);
+        assert!(config.checkpoint_path.is_none());
+        assert_eq!(config.read_from, ReadFrom::Beginning);
+    }
+
+    #[tokio::test]
+    async fn checkpoint_resumes_after_restart() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        append(&path, "one\ntwo\n");
+        let config = FileCollectorConfig {
+            watch_paths: vec![path.clone()],
+            checkpoint_path: Some(dir.path().join("state").join("checkpoints.json")),
+            ..FileCollectorConfig::default()
+        };
+
+        let (tx, mut rx) = mpsc::channel(100);
+        run_once_with_checkpoints(config.clone(), tx).await;
+        assert_eq!(drain(&mut rx), vec!["one", "two"]);
+
+        // 멈춘 사이 추가된 라인만 읽음
+        append(&path, "three\n");
+        let (tx, mut rx) = mpsc::channel(100);
+        run_once_with_checkpoints(config.clone(), tx).await;
+        assert_eq!(drain(&mut rx), vec!["three"]);
+
+        // 체크포인트를 무시하면 처음부터 다시 읽음
+        let (tx, mut rx) = mpsc::channel(100);
+        run_once_with_checkpoints(
+            FileCollectorConfig {
+                ignore_checkpoints: true,
+                ..config
+            },
+            tx,
+        )
+        .await;
+        assert_eq!(drain(&mut rx), vec!["one", "two", "three"]);
+    }
+
+    #[tokio::test]
+    async fn checkpoint_beyond_file_size_restarts_from_beginning() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        append(&path, "a long first line\n");
+        let config = FileCollectorConfig {
+            watch_paths: vec![path.clone()],
+            checkpoint_path: Some(dir.path().join("checkpoints.json")),
+            ..FileCollectorConfig::default()
+        };
+
+        let (tx, mut rx) = mpsc::channel(100);
+        run_once_with_checkpoints(config.clone(), tx).await;
+        assert_eq!(drain(&mut rx), vec!["a long first line"]);
+
+        // 멈춘 사이 잘린 파일
+        std::fs::write(&path, "short\n").unwrap();
+        let (tx, mut rx) = mpsc::channel(100);
+        run_once_with_checkpoints(config, tx).await;
+        assert_eq!(drain(&mut rx), vec!["short"]);
+    }
 
-        let rotated = FileCollector::check_rotation(temp_file.path(), Some(inode))
+    #[tokio::test]
+    async fn read_from_end_skips_existing_lines_only_at_startup() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        append(&path, "old\n");
+
+        let (tx, mut rx) = mpsc::channel(100);
+        let mut collector = FileCollector::new(
+            FileCollectorConfig {
+                watch_paths: vec![dir.path().join("*.log")],
+                read_from: ReadFrom::End,
+                ..FileCollectorConfig::default()
+            },
+            tx,
+        );
+        collector.poll_once().await.unwrap();
+        assert!(drain(&mut rx).is_empty());
+
+        append(&path, "new\n");
+        // 실행 중에 생긴 파일은 처음부터 읽음
+        append(&dir.path().join("late.log"), "late\n");
+        collector.poll_once().await.unwrap();
+        let mut lines = drain(&mut rx);
+        lines.sort();
+        assert_eq!(lines, vec!["late", "new"]);
+    }
+
+    fn write_gz(path: &Path, text: &str) {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+
+        let mut encoder = GzEncoder::new(std::fs::File::create(path).unwrap(), Compression::fast());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn archives_are_skipped_unless_replay_enabled() {
+        let dir = TempDir::new().unwrap();
+        write_gz(&dir.path().join("app.log.2.gz"), "old\n");
+        append(&dir.path().join("app.log"), "current\n");
+
+        let (tx, mut rx) = mpsc::channel(100);
+        let mut collector = collector(vec![dir.path().join("app.log*")], tx);
+        collector.poll_once().await.unwrap();
+        collector.poll_once().await.unwrap();
+        assert_eq!(drain(&mut rx), vec!["current"]);
+    }
+
+    #[tokio::test]
+    async fn archive_is_replayed_once_across_restarts() {
+        let dir = TempDir::new().unwrap();
+        let archive = dir.path().join("app.log.2.gz");
+        let text: String = (0..2500).map(|n| format!("line {}\n", n)).collect();
+        write_gz(&archive, &text);
+        let config = FileCollectorConfig {
+            watch_paths: vec![dir.path().join("*.gz")],
+            checkpoint_path: Some(dir.path().join("checkpoints.json")),
+            replay_archives: true,
+            max_lines_per_read: 1000,
+            ..FileCollectorConfig::default()
+        };
+
+        let (tx, mut rx) = mpsc::channel(5000);
+        let mut collector = run_once_with_checkpoints(config.clone(), tx).await;
+        let lines = drain(&mut rx);
+        assert_eq!(lines.len(), 2500);
+        assert_eq!(lines[0], "line 0");
+        assert_eq!(lines[2499], "line 2499");
+
+        // 다음 폴링에서도 다시 읽지 않음
+        collector.poll_once().await.unwrap();
+        assert!(drain(&mut rx).is_empty());
+
+        // 재시작 후에도 다시 읽지 않음
+        let (tx, mut rx) = mpsc::channel(5000);
+        run_once_with_checkpoints(config, tx).await;
+        assert!(drain(&mut rx).is_empty());
+    }
+
+    #[tokio::test]
+    async fn partially_replayed_archive_resumes_from_checkpoint() {
+        let dir = TempDir::new().unwrap();
+        let archive = dir.path().join("app.log.1.gz");
+        write_gz(&archive, "one\ntwo\nthree\n");
+        let checkpoint_path = dir.path().join("checkpoints.json");
+        FileCheckpoints {
+            files: vec![FileCheckpoint {
+                path: archive.clone(),
+                file_id: std::fs::metadata(&archive).ok().and_then(|m| file_id(&m)),
+                offset: 4,
+                complete: false,
+            }],
+        }
+        .save(&checkpoint_path)
+        .await
+        .unwrap();
+
+        let (tx, mut rx) = mpsc::channel(100);
+        run_once_with_checkpoints(
+            FileCollectorConfig {
+                watch_paths: vec![archive],
+                checkpoint_path: Some(checkpoint_path.clone()),
+                replay_archives: true,
+                ..FileCollectorConfig::default()
+            },
+            tx,
+        )
+        .await;
+        assert_eq!(drain(&mut rx), vec!["two", "three"]);
+
+        let saved = FileCheckpoints::load(&checkpoint_path)
             .await
+            .unwrap()
             .unwrap();
-        assert!(!rotated);
+        assert!(saved.files[0].complete);
+        assert_eq!(saved.files[0].offset, 14);
     }
 
     #[cfg(unix)]
     #[tokio::test]
-    async fn check_rotation_detects_change() {
-        use std::path::PathBuf;
+    async fn rename_rotation_drains_old_file_then_follows_new() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        append(&path, "one\n");
+
+        let (tx, mut rx) = mpsc::channel(100);
+        let mut collector = collector(vec![path.clone()], tx);
+        collector.poll_once().await.unwrap();
+        assert_eq!(drain(&mut rx), vec!["one"]);
+
+        // 로테이션 직전에 쓰인 라인과 새 파일의 라인
+        append(&path, "two\n");
+        fs::rename(&path, dir.path().join("app.log.1"))
+            .await
+            .unwrap();
+        append(&path, "three\n");
 
-        // 원본 파일 생성
-        let temp_file = NamedTempFile::new().unwrap();
-        let path = temp_file.path().to_owned();
-        let old_inode = FileCollector::get_inode(&path).await.unwrap();
-
-        // 실제 로그 로테이션 시나리오: rename 후 새 파일 생성
-        let rotated_path = PathBuf::from(format!("{}.old", path.display()));
-        fs::rename(&path, &rotated_path).await.unwrap();
-        fs::write(&path, b"new content").await.unwrap();
-
-        let new_inode = FileCollector::get_inode(&path).await.unwrap();
-        // rename 방식에서는 새 파일이므로 inode가 달라야 함
-        assert_ne!(
-            old_inode, new_inode,
-            "Rotated file should have different inode (old: {}, new: {})",
-            old_inode, new_inode
-        );
+        collector.poll_once().await.unwrap();
+        assert_eq!(drain(&mut rx), vec!["two"]);
+        collector.poll_once().await.unwrap();
+        assert_eq!(drain(&mut rx), vec!["three"]);
+    }
 
-        let (tx, _rx) = mpsc::channel(10);
-        let _collector = FileCollector::new(FileCollectorConfig::default(), tx);
+    #[tokio::test]
+    async fn truncation_restarts_from_beginning() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        append(&path, "first line\n");
+
+        let (tx, mut rx) = mpsc::channel(100);
+        let mut collector = collector(vec![path.clone()], tx);
+        collector.poll_once().await.unwrap();
+        assert_eq!(drain(&mut rx), vec!["first line"]);
+
+        // copytruncate
+        std::fs::File::create(&path).unwrap();
+        collector.poll_once().await.unwrap();
+        append(&path, "new\n");
+        collector.poll_once().await.unwrap();
+        assert_eq!(drain(&mut rx), vec!["new"]);
+    }
 
-        let rotated = FileCollector::check_rotation(&path, Some(old_inode))
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn glob_discovers_new_files_without_rereading_rotated() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        append(&path, "a1\n");
+        append(&dir.path().join("ignored.txt"), "nope\n");
+
+        let (tx, mut rx) = mpsc::channel(100);
+        let pattern = dir.path().join("*.log*");
+        let mut collector = collector(vec![pattern], tx);
+        collector.poll_once().await.unwrap();
+        assert_eq!(drain(&mut rx), vec!["a1"]);
+
+        // 새 파일 생성
+        append(&dir.path().join("other.log"), "o1\n");
+        collector.poll_once().await.unwrap();
+        assert_eq!(drain(&mut rx), vec!["o1"]);
+        assert_eq!(collector.watched_files(), 2);
+
+        // app.log → app.log.1 로테이션: 패턴에 다시 잡혀도 중복 없이 이어 읽음
+        fs::rename(&path, dir.path().join("app.log.1"))
             .await
             .unwrap();
-        assert!(rotated);
+        append(&path, "a2\n");
+        for _ in 0..3 {
+            collector.poll_once().await.unwrap();
+        }
+        assert_eq!(drain(&mut rx), vec!["a2"]);
+        assert_eq!(collector.watched_files(), 3);
+
+        append(&dir.path().join("app.log.1"), "late\n");
+        collector.poll_once().await.unwrap();
+        assert_eq!(drain(&mut rx), vec!["late"]);
 
-        // cleanup
-        let _ = fs::remove_file(&rotated_path).await;
+        // 삭제된 파일은 추적 중단
+        fs::remove_file(dir.path().join("other.log")).await.unwrap();
+        collector.poll_once().await.unwrap();
+        assert_eq!(collector.watched_files(), 2);
     }
 }
@@ -0,0 +1,308 @@
+//! GELF UDP 전송 처리 -- 청크 재조립과 압축 해제
+//!
+//! GELF over UDP는 큰 메시지를 최대 128개의 청크로 나누고, 메시지를 gzip 또는 zlib으로
+//! 압축할 수 있습니다. [`SyslogUdpCollector`](super::SyslogUdpCollector)는 데이터그램마다
+//! [`GelfReassembler`]와 [`decode_payload`]를 거쳐 압축이 풀린 JSON을 파서로 넘깁니다.
+//!
+//! # 청크 형식
+//! ```text
+//! 0x1e 0x0f | message id (8) | sequence number (1) | sequence count (1) | payload
+//! ```
+//! 스펙에 따라 5초 안에 모든 청크가 오지 않은 메시지는 버립니다.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use tracing::{debug, warn};
+
+/// GELF 청크 매직 바이트
+pub const GELF_CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+
+/// 청크 헤더 길이 (매직 2 + ID 8 + 순번 1 + 개수 1)
+const CHUNK_HEADER_LEN: usize = 12;
+
+/// 메시지당 최대 청크 수 (GELF 스펙)
+pub const GELF_MAX_CHUNKS: u8 = 128;
+
+/// 모든 청크가 도착해야 하는 시간 (GELF 스펙)
+pub const GELF_CHUNK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// GELF 메시지 형식(청크, gzip, zlib, 평문 JSON)인지 첫 바이트로 확인합니다.
+pub fn is_gelf(datagram: &[u8]) -> bool {
+    datagram.starts_with(&GELF_CHUNK_MAGIC)
+        || datagram.starts_with(&[0x1f, 0x8b])
+        || is_zlib(datagram)
+        || datagram.first() == Some(&b'{')
+}
+
+/// zlib 헤더인지 확인합니다 (CMF/FLG 체크섬).
+fn is_zlib(data: &[u8]) -> bool {
+    match data {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
+/// gzip 또는 zlib으로 압축된 메시지를 풀어 반환합니다 (압축되지 않았으면 그대로).
+///
+/// 압축을 푼 크기가 `max_size`를 넘으면 `None`을 반환합니다 (압축 폭탄 방어).
+pub fn decode_payload(payload: Vec<u8>, max_size: usize) -> Option<Vec<u8>> {
+    let limit = max_size as u64 + 1;
+    let mut decoded = Vec::new();
+    let result = if payload.starts_with(&[0x1f, 0x8b]) {
+        MultiGzDecoder::new(payload.as_slice())
+            .take(limit)
+            .read_to_end(&mut decoded)
+    } else if is_zlib(&payload) {
+        ZlibDecoder::new(payload.as_slice())
+            .take(limit)
+            .read_to_end(&mut decoded)
+    } else {
+        return (payload.len() <= max_size).then_some(payload);
+    };
+
+    match result {
+        Ok(_) if decoded.len() <= max_size => Some(decoded),
+        Ok(_) => {
+            warn!(
+                "Dropping GELF message exceeding {} bytes after decompression",
+                max_size
+            );
+            None
+        }
+        Err(e) => {
+            debug!("Failed to decompress GELF message: {}", e);
+            None
+        }
+    }
+}
+
+/// 재조립 중인 메시지
+struct PendingMessage {
+    /// 순번별 청크
+    chunks: Vec<Option<Vec<u8>>>,
+    /// 받은 청크 수
+    received: usize,
+    /// 받은 청크 크기 합
+    size: usize,
+    /// 첫 청크를 받은 시각
+    first_seen: Instant,
+}
+
+/// GELF 청크 재조립기
+///
+/// 동시에 재조립하는 메시지 수와 메시지 크기를 제한하여, 끝나지 않는 청크를 보내는
+/// 송신자가 메모리를 고갈시키지 못하게 합니다.
+pub struct GelfReassembler {
+    /// 메시지 ID별 재조립 상태
+    pending: HashMap<u64, PendingMessage>,
+    /// 동시에 재조립할 최대 메시지 수
+    max_pending: usize,
+    /// 재조립한 메시지의 최대 크기 (바이트)
+    max_message_size: usize,
+    /// 마지막으로 만료 메시지를 정리한 시각
+    last_sweep: Instant,
+}
+
+impl GelfReassembler {
+    /// 새 재조립기를 생성합니다.
+    pub fn new(max_pending: usize, max_message_size: usize) -> Self {
+        Self {
+            pending: HashMap::new(),
+            max_pending,
+            max_message_size,
+            last_sweep: Instant::now(),
+        }
+    }
+
+    /// 청크를 추가하고, 메시지의 마지막 청크였으면 재조립한 메시지를 반환합니다.
+    ///
+    /// 잘못된 청크, 제한을 넘는 메시지는 버립니다.
+    pub fn push(&mut self, datagram: &[u8], now: Instant) -> Option<Vec<u8>> {
+        self.sweep(now);
+
+        if datagram.len() < CHUNK_HEADER_LEN || !datagram.starts_with(&GELF_CHUNK_MAGIC) {
+            debug!("Dropping truncated GELF chunk ({} bytes)", datagram.len());
+            return None;
+        }
+        let mut id = [0u8; 8];
+        id.copy_from_slice(&datagram[2..10]);
+        let id = u64::from_be_bytes(id);
+        let sequence = datagram[10];
+        let count = datagram[11];
+        let payload = &datagram[CHUNK_HEADER_LEN..];
+
+        if count == 0 || count > GELF_MAX_CHUNKS || sequence >= count {
+            debug!(
+                "Dropping GELF chunk with invalid sequence {}/{}",
+                sequence, count
+            );
+            return None;
+        }
+        if count == 1 {
+            return Some(payload.to_vec());
+        }
+
+        if !self.pending.contains_key(&id) && self.pending.len() >= self.max_pending {
+            warn!(
+                "Too many incomplete GELF messages ({}), dropping chunk",
+                self.max_pending
+            );
+            return None;
+        }
+        let message = self.pending.entry(id).or_insert_with(|| PendingMessage {
+            chunks: vec![None; usize::from(count)],
+            received: 0,
+            size: 0,
+            first_seen: now,
+        });
+        if message.chunks.len() != usize::from(count) {
+            debug!(
+                "Dropping GELF message {:x} with inconsistent chunk count",
+                id
+            );
+            self.pending.remove(&id);
+            return None;
+        }
+
+        let slot = &mut message.chunks[usize::from(sequence)];
+        if slot.is_some() {
+            // 중복 청크
+            return None;
+        }
+        message.size += payload.len();
+        if message.size > self.max_message_size {
+            warn!(
+                "Dropping GELF message exceeding {} bytes",
+                self.max_message_size
+            );
+            self.pending.remove(&id);
+            return None;
+        }
+        *slot = Some(payload.to_vec());
+        message.received += 1;
+
+        if message.received < message.chunks.len() {
+            return None;
+        }
+        let message = self.pending.remove(&id)?;
+        let mut assembled = Vec::with_capacity(message.size);
+        for chunk in message.chunks.into_iter().flatten() {
+            assembled.extend_from_slice(&chunk);
+        }
+        Some(assembled)
+    }
+
+    /// 재조립 중인 메시지 수를 반환합니다.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// 제한 시간 안에 완성되지 않은 메시지를 버립니다 (최대 초당 한 번).
+    fn sweep(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.last_sweep) < Duration::from_secs(1) {
+            return;
+        }
+        self.last_sweep = now;
+        let before = self.pending.len();
+        self.pending.retain(|_, message| {
+            now.saturating_duration_since(message.first_seen) < GELF_CHUNK_TIMEOUT
+        });
+        let expired = before - self.pending.len();
+        if expired > 0 {
+            debug!("Discarded {} incomplete GELF messages", expired);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use std::io::Write;
+
+    fn chunk(id: u64, sequence: u8, count: u8, payload: &[u8]) -> Vec<u8> {
+        let mut datagram = GELF_CHUNK_MAGIC.to_vec();
+        datagram.extend_from_slice(&id.to_be_bytes());
+        datagram.push(sequence);
+        datagram.push(count);
+        datagram.extend_from_slice(payload);
+        datagram
+    }
+
+    #[test]
+    fn reassembles_out_of_order_chunks() {
+        let mut reassembler = GelfReassembler::new(16, 1024);
+        let now = Instant::now();
+
+        assert!(reassembler.push(&chunk(7, 2, 3, b"ld\"}"), now).is_none());
+        assert!(reassembler.push(&chunk(7, 0, 3, b"{\"a\":"), now).is_none());
+        // 중복 청크는 무시
+        assert!(reassembler.push(&chunk(7, 0, 3, b"xxxxx"), now).is_none());
+        let message = reassembler.push(&chunk(7, 1, 3, b"\"wor"), now).unwrap();
+        assert_eq!(message, b"{\"a\":\"world\"}");
+        assert_eq!(reassembler.pending(), 0);
+    }
+
+    #[test]
+    fn rejects_invalid_and_oversized_chunks() {
+        let mut reassembler = GelfReassembler::new(1, 8);
+        let now = Instant::now();
+
+        assert!(reassembler.push(&chunk(1, 3, 3, b"x"), now).is_none());
+        assert!(reassembler.push(&chunk(1, 0, 129, b"x"), now).is_none());
+        assert!(reassembler.push(&[0x1e, 0x0f, 1], now).is_none());
+        assert_eq!(reassembler.pending(), 0);
+
+        // 최대 동시 메시지 수 초과
+        assert!(reassembler.push(&chunk(1, 0, 2, b"abc"), now).is_none());
+        assert!(reassembler.push(&chunk(2, 0, 2, b"abc"), now).is_none());
+        assert_eq!(reassembler.pending(), 1);
+
+        // 최대 크기 초과
+        assert!(reassembler.push(&chunk(1, 1, 2, b"defghi"), now).is_none());
+        assert_eq!(reassembler.pending(), 0);
+    }
+
+    #[test]
+    fn incomplete_messages_expire() {
+        let mut reassembler = GelfReassembler::new(16, 1024);
+        let start = Instant::now();
+        assert!(reassembler.push(&chunk(1, 0, 2, b"a"), start).is_none());
+
+        let later = start + GELF_CHUNK_TIMEOUT + Duration::from_secs(1);
+        assert!(reassembler.push(&chunk(1, 1, 2, b"b"), later).is_none());
+        assert_eq!(reassembler.pending(), 1);
+    }
+
+    #[test]
+    fn decodes_compressed_payloads() {
+        let json = br#"{"version":"1.1","host":"h","short_message":"m"}"#;
+
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(json).unwrap();
+        let gzip = gzip.finish().unwrap();
+        assert!(is_gelf(&gzip));
+        assert_eq!(decode_payload(gzip, 1024).unwrap(), json);
+
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(json).unwrap();
+        let zlib = zlib.finish().unwrap();
+        assert!(is_gelf(&zlib));
+        assert_eq!(decode_payload(zlib.clone(), 1024).unwrap(), json);
+
+        // 압축 해제 후 크기 제한
+        assert!(decode_payload(zlib, 10).is_none());
+        assert_eq!(decode_payload(json.to_vec(), 1024).unwrap(), json);
+    }
+
+    #[test]
+    fn syslog_is_not_gelf() {
+        assert!(!is_gelf(b"<34>1 2024-01-15T12:00:00Z host app - - - msg"));
+        assert!(!is_gelf(b"plain text"));
+        assert!(is_gelf(&chunk(1, 0, 1, b"{}")));
+    }
+}
@@ -0,0 +1,727 @@
+//! HTTP 수집기
+//!
+//! `POST /ingest` 엔드포인트로 JSON 또는 NDJSON 배치를 받아 파이프라인에 전달합니다.
+//! 애플리케이션이나 Vector(`http` 싱크), Fluent Bit(`http` 출력) 같은 에이전트가
+//! 로그를 직접 밀어 넣을 때 사용합니다.
+//!
+//! # 요청 형식
+//! 본문은 JSON 값을 하나 이상 이어 붙인 형태입니다. 최상위 배열은 펼쳐지므로
+//! 아래 형식을 모두 받습니다.
+//! - 단일 객체: `{"msg": "..."}`
+//! - JSON 배열: `[{"msg": "..."}, {"msg": "..."}]`
+//! - NDJSON: 한 줄에 객체 하나
+//!
+//! 객체는 로그 하나로 전달되며 파서 힌트는 `"json"`입니다. 문자열은 원시 로그 한 줄로
+//! 취급해 형식을 자동 감지합니다. 배치 중 하나라도 해석할 수 없으면 전체를 거부하고
+//! 아무것도 전달하지 않습니다.
+//!
+//! # 인증
+//! 요청에는 `Authorization: Bearer <token>` 헤더가 필요합니다. 설정에는 토큰의 SHA-256
+//! 해시만 저장하며, 일치한 토큰 이름이 수집 소스 식별자(`http:<name>`)가 됩니다.
+//!
+//! # 응답
+//! | 상태 | 의미 |
+//! |------|------|
+//! | 200 | 전달 완료 (`{"accepted": N}`) |
+//! | 400 | JSON 해석 실패 또는 객체/문자열이 아닌 항목 |
+//! | 401 | 토큰 없음 또는 불일치 |
+//! | 404, 405 | 경로 또는 메서드 불일치 |
+//! | 408 | 본문 수신 시간 초과 |
+//! | 413 | 본문이 `max_body_bytes` 초과 |
+//! | 503 | 파이프라인 종료 중 (재시도 가능) |
+//!
+//! 파이프라인 채널이 가득 차면 응답을 늦춰 클라이언트에 역압(backpressure)을 전달합니다.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::Body;
+use hyper::header::{self, HeaderMap, HeaderValue};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::{TokioIo, TokioTimer};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tokio::net::TcpListener;
+use tokio::sync::{Semaphore, mpsc};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use ironpost_core::config::{HttpIngestConfig, IngestTokenConfig};
+
+use super::{CollectorStatus, RawLog};
+use crate::error::LogPipelineError;
+
+/// 수집 엔드포인트 경로
+pub const INGEST_PATH: &str = "/ingest";
+
+/// HTTP 수집기 설정
+#[derive(Debug, Clone)]
+pub struct HttpCollectorConfig {
+    /// 바인드 주소 (예: "0.0.0.0:8088")
+    pub bind_addr: String,
+    /// 수집 토큰 목록 (SHA-256 해시)
+    pub tokens: Vec<IngestTokenConfig>,
+    /// 최대 요청 본문 크기 (바이트)
+    pub max_body_bytes: usize,
+    /// 최대 동시 연결 수
+    pub max_connections: usize,
+    /// 요청 헤더 수신 타임아웃
+    pub header_read_timeout: Duration,
+    /// 요청 본문 수신 타임아웃
+    pub body_read_timeout: Duration,
+    /// 파서 형식 힌트 (None이면 객체는 "json", 문자열은 자동 감지)
+    pub format_hint: Option<String>,
+}
+
+impl Default for HttpCollectorConfig {
+    fn default() -> Self {
+        Self::from_core(&HttpIngestConfig::default())
+    }
+}
+
+impl HttpCollectorConfig {
+    /// core의 `HttpIngestConfig`에서 수집기 설정을 생성합니다.
+    pub fn from_core(core: &HttpIngestConfig) -> Self {
+        Self {
+            bind_addr: core.bind.clone(),
+            tokens: core.tokens.clone(),
+            max_body_bytes: core.max_body_bytes,
+            max_connections: 256,
+            header_read_timeout: Duration::from_secs(10),
+            body_read_timeout: Duration::from_secs(30),
+            format_hint: (!core.format.is_empty()).then(|| core.format.clone()),
+        }
+    }
+}
+
+/// 연결 처리 태스크가 공유하는 요청 처리 상태
+struct IngestState {
+    /// (토큰 이름, SHA-256 해시) 목록
+    tokens: Vec<(String, [u8; 32])>,
+    /// 수집된 로그 전송 채널
+    tx: mpsc::Sender<RawLog>,
+    /// 최대 요청 본문 크기 (바이트)
+    max_body_bytes: usize,
+    /// 요청 본문 수신 타임아웃
+    body_read_timeout: Duration,
+    /// 파서 형식 힌트
+    format_hint: Option<String>,
+    /// 종료 신호 (전송 대기 중인 요청을 중단)
+    cancel_token: CancellationToken,
+}
+
+/// HTTP 수집기
+///
+/// `POST /ingest`로 받은 JSON/NDJSON 배치를 항목별 로그로 파이프라인에 전달합니다.
+/// 각 연결은 별도의 tokio 태스크에서 HTTP/1.1로 처리됩니다.
+pub struct HttpCollector {
+    /// 수집기 설정
+    config: HttpCollectorConfig,
+    /// 수집된 로그 전송 채널
+    tx: mpsc::Sender<RawLog>,
+    /// graceful shutdown을 위한 취소 토큰
+    cancel_token: CancellationToken,
+    /// 현재 상태
+    status: CollectorStatus,
+}
+
+impl HttpCollector {
+    /// 새 HTTP 수집기를 생성합니다.
+    pub fn new(
+        config: HttpCollectorConfig,
+        tx: mpsc::Sender<RawLog>,
+        cancel_token: CancellationToken,
+    ) -> Self {
+        Self {
+            config,
+            tx,
+            cancel_token,
+            status: CollectorStatus::Idle,
+        }
+    }
+
+    /// 수집기를 시작합니다.
+    ///
+    /// 리스너를 바인드하고 취소될 때까지 연결을 받습니다. 토큰 해시가 잘못되었거나
+    /// 바인드에 실패하면 에러를 반환합니다.
+    pub async fn run(&mut self) -> Result<(), LogPipelineError> {
+        self.status = CollectorStatus::Running;
+        info!("Starting HTTP collector on {}", self.config.bind_addr);
+
+        let listener = match TcpListener::bind(&self.config.bind_addr).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                let e = collector_error(format!(
+                    "failed to bind to {}: {}",
+                    self.config.bind_addr, e
+                ));
+                self.status = CollectorStatus::Error(e.to_string());
+                return Err(e);
+            }
+        };
+        self.serve(listener).await
+    }
+
+    /// 바인드된 리스너에서 연결 수락 루프를 실행합니다.
+    async fn serve(&mut self, listener: TcpListener) -> Result<(), LogPipelineError> {
+        let state = match self.state() {
+            Ok(state) => Arc::new(state),
+            Err(e) => {
+                self.status = CollectorStatus::Error(e.to_string());
+                return Err(e);
+            }
+        };
+
+        info!(
+            addr = %listener.local_addr().map(|a| a.to_string()).unwrap_or_default(),
+            path = INGEST_PATH,
+            "HTTP collector listening"
+        );
+
+        let connection_semaphore = Arc::new(Semaphore::new(self.config.max_connections));
+        let mut builder = http1::Builder::new();
+        builder
+            .timer(TokioTimer::new())
+            .header_read_timeout(self.config.header_read_timeout);
+
+        loop {
+            let (stream, addr) = tokio::select! {
+                result = listener.accept() => match result {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        // 파일 디스크립터 부족 등 일시적 에러로 수집을 멈추지 않음
+                        warn!(error = %e, "HTTP collector accept error");
+                        continue;
+                    }
+                },
+                _ = self.cancel_token.cancelled() => break,
+            };
+
+            let Ok(permit) = Arc::clone(&connection_semaphore).try_acquire_owned() else {
+                warn!(
+                    "Max connections reached, rejecting HTTP connection from {}",
+                    addr
+                );
+                continue;
+            };
+            debug!("Accepted HTTP connection from {}", addr);
+
+            let state = Arc::clone(&state);
+            let builder = builder.clone();
+            let cancel = self.cancel_token.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |req| handle_request(Arc::clone(&state), req));
+                let conn = builder.serve_connection(TokioIo::new(stream), service);
+                tokio::pin!(conn);
+                let result = tokio::select! {
+                    result = conn.as_mut() => result,
+                    _ = cancel.cancelled() => {
+                        // 처리 중인 요청은 마무리하고 연결을 닫음
+                        conn.as_mut().graceful_shutdown();
+                        conn.await
+                    }
+                };
+                if let Err(e) = result {
+                    debug!(peer = %addr, error = %e, "HTTP connection closed with error");
+                }
+                drop(permit);
+            });
+        }
+
+        info!("HTTP collector received shutdown signal");
+        self.status = CollectorStatus::Stopped;
+        Ok(())
+    }
+
+    /// 설정에서 요청 처리 상태를 만듭니다 (토큰 해시 디코딩).
+    fn state(&self) -> Result<IngestState, LogPipelineError> {
+        let tokens = self
+            .config
+            .tokens
+            .iter()
+            .map(|token| {
+                decode_digest(&token.token_sha256)
+                    .map(|digest| (token.name.clone(), digest))
+                    .ok_or_else(|| {
+                        collector_error(format!(
+                            "token '{}' has an invalid SHA-256 digest",
+                            token.name
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if tokens.is_empty() {
+            return Err(collector_error(
+                "at least one ingest token is required".to_owned(),
+            ));
+        }
+
+        Ok(IngestState {
+            tokens,
+            tx: self.tx.clone(),
+            max_body_bytes: self.config.max_body_bytes,
+            body_read_timeout: self.config.body_read_timeout,
+            format_hint: self.config.format_hint.clone(),
+            cancel_token: self.cancel_token.clone(),
+        })
+    }
+
+    /// 바인드 주소를 반환합니다.
+    pub fn bind_addr(&self) -> &str {
+        &self.config.bind_addr
+    }
+
+    /// 현재 상태를 반환합니다.
+    pub fn status(&self) -> &CollectorStatus {
+        &self.status
+    }
+}
+
+/// 요청 하나를 처리합니다.
+async fn handle_request<B>(
+    state: Arc<IngestState>,
+    req: Request<B>,
+) -> Result<Response<Full<Bytes>>, Infallible>
+where
+    B: Body,
+    B::Error: std::error::Error + Send + Sync + 'static,
+{
+    if req.uri().path() != INGEST_PATH {
+        return Ok(json_error(StatusCode::NOT_FOUND, "not found"));
+    }
+    if req.method() != Method::POST {
+        let mut response = json_error(StatusCode::METHOD_NOT_ALLOWED, "method not allowed");
+        response
+            .headers_mut()
+            .insert(header::ALLOW, HeaderValue::from_static("POST"));
+        return Ok(response);
+    }
+
+    let Some(token_name) = state.authenticate(req.headers()) else {
+        warn!(path = INGEST_PATH, "HTTP ingest request denied");
+        let mut response = json_error(StatusCode::UNAUTHORIZED, "missing or invalid token");
+        response
+            .headers_mut()
+            .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        return Ok(response);
+    };
+
+    let declared_length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared_length.is_some_and(|len| len > state.max_body_bytes) {
+        return Ok(body_too_large(state.max_body_bytes));
+    }
+
+    let collect = Limited::new(req.into_body(), state.max_body_bytes).collect();
+    let body = match tokio::time::timeout(state.body_read_timeout, collect).await {
+        Ok(Ok(collected)) => collected.to_bytes(),
+        Ok(Err(e)) if e.is::<LengthLimitError>() => {
+            return Ok(body_too_large(state.max_body_bytes));
+        }
+        Ok(Err(e)) => {
+            debug!(error = %e, "failed to read HTTP ingest body");
+            return Ok(json_error(
+                StatusCode::BAD_REQUEST,
+                "failed to read request body",
+            ));
+        }
+        Err(_) => {
+            return Ok(json_error(
+                StatusCode::REQUEST_TIMEOUT,
+                "timed out reading request body",
+            ));
+        }
+    };
+
+    let source = format!("http:{}", token_name);
+    let logs = match parse_batch(&body, &source, state.format_hint.as_deref()) {
+        Ok(logs) => logs,
+        Err(reason) => {
+            debug!(source = %source, reason = %reason, "rejected HTTP ingest batch");
+            return Ok(json_error(StatusCode::BAD_REQUEST, &reason));
+        }
+    };
+
+    let accepted = logs.len();
+    for raw_log in logs {
+        let sent = tokio::select! {
+            sent = state.tx.send(raw_log) => sent.is_ok(),
+            _ = state.cancel_token.cancelled() => false,
+        };
+        if !sent {
+            error!(source = %source, "pipeline channel closed, rejecting HTTP ingest batch");
+            return Ok(json_error(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "pipeline is shutting down",
+            ));
+        }
+    }
+
+    debug!(source = %source, accepted = accepted, "HTTP ingest batch accepted");
+    Ok(json_response(
+        StatusCode::OK,
+        serde_json::json!({ "accepted": accepted }).to_string(),
+    ))
+}
+
+impl IngestState {
+    /// Bearer 토큰을 확인하고 일치한 토큰 이름을 반환합니다.
+    fn authenticate(&self, headers: &HeaderMap) -> Option<&str> {
+        let token = bearer_token(headers)?;
+        let presented: [u8; 32] = Sha256::digest(token.as_bytes()).into();
+
+        // 어느 토큰과 일치했는지 타이밍으로 드러나지 않도록 모든 항목과 비교
+        let mut matched = None;
+        for (name, digest) in &self.tokens {
+            if constant_time_eq(digest, &presented) && matched.is_none() {
+                matched = Some(name.as_str());
+            }
+        }
+        matched
+    }
+}
+
+/// 요청 본문을 항목별 RawLog로 변환합니다.
+///
+/// 공백으로 구분된 JSON 값을 차례로 읽고 최상위 배열은 펼칩니다. 항목 하나라도
+/// 잘못되면 에러 사유를 반환합니다.
+fn parse_batch(
+    body: &[u8],
+    source: &str,
+    format_hint: Option<&str>,
+) -> Result<Vec<RawLog>, String> {
+    let mut logs = Vec::new();
+    for value in serde_json::Deserializer::from_slice(body).into_iter::<Value>() {
+        let value = value.map_err(|e| format!("invalid JSON: {}", e))?;
+        match value {
+            Value::Array(items) => {
+                for item in items {
+                    push_entry(&mut logs, item, source, format_hint)?;
+                }
+            }
+            value => push_entry(&mut logs, value, source, format_hint)?,
+        }
+    }
+    Ok(logs)
+}
+
+/// 배치 항목 하나를 RawLog로 변환해 추가합니다 (빈 문자열은 건너뜀).
+fn push_entry(
+    logs: &mut Vec<RawLog>,
+    value: Value,
+    source: &str,
+    format_hint: Option<&str>,
+) -> Result<(), String> {
+    let (data, default_hint) = match value {
+        Value::Object(_) => (Bytes::from(value.to_string()), Some("json")),
+        Value::String(line) if line.is_empty() => return Ok(()),
+        Value::String(line) => (Bytes::from(line), None),
+        other => {
+            return Err(format!(
+                "batch entries must be JSON objects or strings, got {}",
+                json_type(&other)
+            ));
+        }
+    };
+
+    let raw_log = RawLog::new(data, source);
+    logs.push(match format_hint.or(default_hint) {
+        Some(hint) => raw_log.with_format_hint(hint),
+        None => raw_log,
+    });
+    Ok(())
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "nested array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// `Authorization` 헤더에서 Bearer 토큰을 꺼냅니다.
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.trim().split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then(|| token.trim())
+        .filter(|token| !token.is_empty())
+}
+
+fn decode_digest(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut digest = [0u8; 32];
+    for (byte, pair) in digest.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let pair = std::str::from_utf8(pair).ok()?;
+        *byte = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(digest)
+}
+
+fn constant_time_eq(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn body_too_large(max_body_bytes: usize) -> Response<Full<Bytes>> {
+    json_error(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        &format!("request body exceeds {} bytes", max_body_bytes),
+    )
+}
+
+fn json_error(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    json_response(status, serde_json::json!({ "error": message }).to_string())
+}
+
+fn json_response(status: StatusCode, body: String) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+fn collector_error(reason: String) -> LogPipelineError {
+    LogPipelineError::Collector {
+        source_type: "http".to_owned(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const TOKEN: &str = "vector-secret";
+
+    fn sha256_hex(token: &str) -> String {
+        Sha256::digest(token.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    fn config() -> HttpCollectorConfig {
+        HttpCollectorConfig {
+            bind_addr: "127.0.0.1:0".to_owned(),
+            tokens: vec![IngestTokenConfig {
+                name: "vector".to_owned(),
+                token_sha256: sha256_hex(TOKEN),
+            }],
+            ..HttpCollectorConfig::default()
+        }
+    }
+
+    fn state(config: HttpCollectorConfig, tx: mpsc::Sender<RawLog>) -> Arc<IngestState> {
+        let collector = HttpCollector::new(config, tx, CancellationToken::new());
+        Arc::new(collector.state().unwrap())
+    }
+
+    fn request(
+        method: Method,
+        path: &str,
+        token: Option<&str>,
+        body: &str,
+    ) -> Request<Full<Bytes>> {
+        let mut builder = Request::builder().method(method).uri(path);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        builder
+            .body(Full::new(Bytes::from(body.to_owned())))
+            .unwrap()
+    }
+
+    async fn body_string(response: Response<Full<Bytes>>) -> String {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn parse_batch_accepts_object_array_and_ndjson() {
+        let single = parse_batch(br#"{"msg":"a"}"#, "http:test", None).unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].format_hint.as_deref(), Some("json"));
+        assert_eq!(single[0].source, "http:test");
+
+        let array = parse_batch(br#"[{"msg":"a"}, {"msg":"b"}]"#, "http:test", None).unwrap();
+        assert_eq!(array.len(), 2);
+
+        let ndjson = parse_batch(
+            b"{\"msg\":\"a\"}\n{\"msg\":\"b\"}\n{\"msg\":\"c\"}\n",
+            "http:test",
+            None,
+        )
+        .unwrap();
+        assert_eq!(ndjson.len(), 3);
+        assert_eq!(&ndjson[2].data[..], br#"{"msg":"c"}"#);
+    }
+
+    #[test]
+    fn parse_batch_handles_strings_and_hints() {
+        let logs = parse_batch(
+            br#"["<34>1 2024-01-15T12:00:00Z host sshd - - - login failed", ""]"#,
+            "http:test",
+            None,
+        )
+        .unwrap();
+        assert_eq!(logs.len(), 1, "empty strings are skipped");
+        assert!(logs[0].format_hint.is_none());
+
+        let logs = parse_batch(br#"{"msg":"a"}"#, "http:test", Some("syslog")).unwrap();
+        assert_eq!(logs[0].format_hint.as_deref(), Some("syslog"));
+    }
+
+    #[test]
+    fn parse_batch_rejects_invalid_entries() {
+        let err = parse_batch(b"{\"msg\":\"a\"}\n{broken", "http:test", None).unwrap_err();
+        assert!(err.contains("invalid JSON"), "{}", err);
+
+        let err = parse_batch(br#"[{"msg":"a"}, 42]"#, "http:test", None).unwrap_err();
+        assert!(err.contains("got number"), "{}", err);
+    }
+
+    #[test]
+    fn state_rejects_invalid_digest() {
+        let (tx, _rx) = mpsc::channel(1);
+        let mut config = config();
+        config.tokens[0].token_sha256 = "xyz".to_owned();
+        let collector = HttpCollector::new(config, tx, CancellationToken::new());
+        assert!(collector.state().is_err());
+    }
+
+    #[tokio::test]
+    async fn handle_request_forwards_batch_with_token_source() {
+        let (tx, mut rx) = mpsc::channel(10);
+        let state = state(config(), tx);
+
+        let response = handle_request(
+            state,
+            request(
+                Method::POST,
+                INGEST_PATH,
+                Some(TOKEN),
+                "{\"msg\":\"a\"}\n{\"msg\":\"b\"}",
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_string(response).await, r#"{"accepted":2}"#);
+
+        let first = rx.recv().await.unwrap();
+        assert_eq!(first.source, "http:vector");
+        assert_eq!(&first.data[..], br#"{"msg":"a"}"#);
+        assert!(rx.recv().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn handle_request_rejects_bad_requests() {
+        let (tx, mut rx) = mpsc::channel(10);
+        let state = state(
+            HttpCollectorConfig {
+                max_body_bytes: 16,
+                ..config()
+            },
+            tx,
+        );
+
+        let cases = [
+            (
+                request(Method::POST, INGEST_PATH, None, "{}"),
+                StatusCode::UNAUTHORIZED,
+            ),
+            (
+                request(Method::POST, INGEST_PATH, Some("wrong"), "{}"),
+                StatusCode::UNAUTHORIZED,
+            ),
+            (
+                request(Method::GET, INGEST_PATH, Some(TOKEN), ""),
+                StatusCode::METHOD_NOT_ALLOWED,
+            ),
+            (
+                request(Method::POST, "/other", Some(TOKEN), "{}"),
+                StatusCode::NOT_FOUND,
+            ),
+            (
+                request(Method::POST, INGEST_PATH, Some(TOKEN), "[1]"),
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                request(
+                    Method::POST,
+                    INGEST_PATH,
+                    Some(TOKEN),
+                    r#"{"msg":"longer than sixteen bytes"}"#,
+                ),
+                StatusCode::PAYLOAD_TOO_LARGE,
+            ),
+        ];
+        for (req, expected) in cases {
+            let response = handle_request(Arc::clone(&state), req).await.unwrap();
+            assert_eq!(response.status(), expected);
+            if expected == StatusCode::UNAUTHORIZED {
+                assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+            }
+        }
+        assert!(rx.try_recv().is_err(), "rejected requests forward nothing");
+    }
+
+    #[tokio::test]
+    async fn serve_accepts_http_requests_until_cancelled() {
+        let (tx, mut rx) = mpsc::channel(10);
+        let cancel = CancellationToken::new();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut collector = HttpCollector::new(config(), tx, cancel.clone());
+        assert_eq!(*collector.status(), CollectorStatus::Idle);
+
+        let handle = tokio::spawn(async move {
+            let result = collector.serve(listener).await;
+            (result, collector)
+        });
+
+        let body = r#"[{"msg":"login failed"}]"#;
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "POST /ingest HTTP/1.1\r\nHost: {}\r\nAuthorization: Bearer {}\r\n\
+             Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            addr,
+            TOKEN,
+            body.len(),
+            body
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.ends_with(r#"{"accepted":1}"#), "{}", response);
+        assert_eq!(rx.recv().await.unwrap().source, "http:vector");
+
+        cancel.cancel();
+        let (result, collector) = tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("collector should stop after cancel")
+            .unwrap();
+        result.unwrap();
+        assert_eq!(*collector.status(), CollectorStatus::Stopped);
+    }
+}
@@ -0,0 +1,482 @@
+//! Kafka 수집기
+//!
+//! 설정된 토픽을 컨슈머 그룹으로 구독하여 메시지 하나를 로그 한 줄로 파이프라인에 전달합니다.
+//! 기존 로그 버스(Kafka) 뒤에 ironpost를 둘 때 사용하며, `kafka` 기능 플래그로 활성화합니다.
+//!
+//! # 오프셋 관리
+//! librdkafka의 자동 커밋은 켜 두되 자동 오프셋 저장(`enable.auto.offset.store`)은 끕니다.
+//! 메시지를 파이프라인 채널에 넘긴 뒤에만 오프셋을 저장하므로, 커밋 주기
+//! (`commit_interval`)마다 처리된 위치까지만 커밋됩니다. 종료 시에는 저장된 오프셋을
+//! 동기 커밋합니다. 재시작하면 마지막 커밋 이후 메시지가 다시 전달될 수 있습니다
+//! (at-least-once).
+//!
+//! # 재연결
+//! 브로커 연결이 끊기면 librdkafka가 `reconnect_backoff_max`까지 백오프하며 다시 연결합니다.
+//! 수신 에러가 이어지면 수집기도 같은 상한으로 지수 백오프하며, 컨슈머를 더 쓸 수 없는
+//! 치명적 에러에서는 컨슈머를 새로 만들어 다시 구독합니다.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use bytes::Bytes;
+use rdkafka::ClientConfig;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::message::Message;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use ironpost_core::config::KafkaConfig;
+
+use super::{CollectorStatus, RawLog};
+use crate::error::LogPipelineError;
+
+/// 수신 에러 후 첫 재시도 대기 시간 (librdkafka `reconnect.backoff.ms` 기본값과 동일)
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Kafka 수집기 설정
+#[derive(Debug, Clone)]
+pub struct KafkaCollectorConfig {
+    /// 부트스트랩 브로커 주소 목록
+    pub brokers: Vec<String>,
+    /// 구독할 토픽 목록
+    pub topics: Vec<String>,
+    /// 컨슈머 그룹 ID
+    pub group_id: String,
+    /// 커밋된 오프셋이 없을 때 시작 위치 ("earliest", "latest")
+    pub auto_offset_reset: String,
+    /// 저장된 오프셋 커밋 간격
+    pub commit_interval: Duration,
+    /// 재연결/재시도 최대 백오프
+    pub reconnect_backoff_max: Duration,
+    /// 파서 형식 힌트 (None이면 자동 감지)
+    pub format_hint: Option<String>,
+    /// 최대 메시지 크기 (바이트, 초과 메시지는 건너뜀)
+    pub max_message_size: usize,
+    /// 추가 librdkafka 속성
+    pub properties: BTreeMap<String, String>,
+}
+
+impl Default for KafkaCollectorConfig {
+    fn default() -> Self {
+        Self::from_core(&KafkaConfig::default())
+    }
+}
+
+impl KafkaCollectorConfig {
+    /// core의 `KafkaConfig`에서 수집기 설정을 생성합니다.
+    pub fn from_core(core: &KafkaConfig) -> Self {
+        Self {
+            brokers: core.brokers.clone(),
+            topics: core.topics.clone(),
+            group_id: core.group_id.clone(),
+            auto_offset_reset: core.auto_offset_reset.clone(),
+            commit_interval: Duration::from_secs(core.commit_interval_secs),
+            reconnect_backoff_max: Duration::from_secs(core.reconnect_backoff_max_secs),
+            format_hint: (!core.format.is_empty()).then(|| core.format.clone()),
+            max_message_size: 1024 * 1024, // 1MB
+            properties: core.properties.clone(),
+        }
+    }
+
+    /// librdkafka 컨슈머 속성을 만듭니다.
+    ///
+    /// 추가 속성을 먼저 적용한 뒤 수집기가 관리하는 속성으로 덮어씁니다.
+    fn client_config(&self) -> ClientConfig {
+        let mut client = ClientConfig::new();
+        for (key, value) in &self.properties {
+            client.set(key, value);
+        }
+        client
+            .set("bootstrap.servers", self.brokers.join(","))
+            .set("group.id", &self.group_id)
+            .set("auto.offset.reset", &self.auto_offset_reset)
+            .set("enable.auto.commit", "true")
+            .set("enable.auto.offset.store", "false")
+            .set(
+                "auto.commit.interval.ms",
+                self.commit_interval.as_millis().to_string(),
+            )
+            .set(
+                "reconnect.backoff.max.ms",
+                self.reconnect_backoff_max.as_millis().to_string(),
+            );
+        client
+    }
+}
+
+/// 연속된 수신 에러에 대한 지수 백오프
+#[derive(Debug)]
+struct Backoff {
+    next: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    fn new(max: Duration) -> Self {
+        Self {
+            next: INITIAL_BACKOFF.min(max),
+            max,
+        }
+    }
+
+    /// 이번에 기다릴 시간을 반환하고 다음 대기 시간을 두 배로 늘립니다.
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = (self.next * 2).min(self.max);
+        delay
+    }
+
+    /// 메시지를 정상 수신하면 초기 대기 시간으로 되돌립니다.
+    fn reset(&mut self) {
+        self.next = INITIAL_BACKOFF.min(self.max);
+    }
+}
+
+/// 컨슈머 하나의 수신 루프가 끝난 이유
+enum ConsumeExit {
+    /// 종료 신호 수신
+    Shutdown,
+    /// 컨슈머를 더 쓸 수 없는 에러 (다시 만들어야 함)
+    Fatal(KafkaError),
+}
+
+/// Kafka 수집기
+///
+/// 컨슈머 그룹으로 토픽을 구독하여 메시지를 파이프라인으로 전달합니다.
+/// 메시지 페이로드 하나를 로그 메시지 하나로 취급합니다.
+pub struct KafkaCollector {
+    /// 수집기 설정
+    config: KafkaCollectorConfig,
+    /// 수집된 로그 전송 채널
+    tx: mpsc::Sender<RawLog>,
+    /// graceful shutdown을 위한 취소 토큰
+    cancel_token: CancellationToken,
+    /// 현재 상태
+    status: CollectorStatus,
+}
+
+impl KafkaCollector {
+    /// 새 Kafka 수집기를 생성합니다.
+    pub fn new(
+        config: KafkaCollectorConfig,
+        tx: mpsc::Sender<RawLog>,
+        cancel_token: CancellationToken,
+    ) -> Self {
+        Self {
+            config,
+            tx,
+            cancel_token,
+            status: CollectorStatus::Idle,
+        }
+    }
+
+    /// 수집기를 시작합니다.
+    ///
+    /// 토픽을 구독하고 취소될 때까지 메시지를 수신합니다. 치명적 에러가 나면 백오프 후
+    /// 컨슈머를 다시 만들고, 컨슈머 생성이나 구독 자체가 실패하면(잘못된 속성 등) 에러를
+    /// 반환합니다. 파이프라인 채널이 닫혀도 에러를 반환합니다.
+    pub async fn run(&mut self) -> Result<(), LogPipelineError> {
+        self.status = CollectorStatus::Running;
+        info!(
+            brokers = %self.config.brokers.join(","),
+            topics = ?self.config.topics,
+            group_id = %self.config.group_id,
+            "Starting Kafka collector"
+        );
+
+        let mut backoff = Backoff::new(self.config.reconnect_backoff_max);
+        loop {
+            let consumer = match self.subscribe() {
+                Ok(consumer) => consumer,
+                Err(e) => {
+                    self.status = CollectorStatus::Error(e.to_string());
+                    return Err(e);
+                }
+            };
+
+            match self.consume(&consumer, &mut backoff).await {
+                Ok(ConsumeExit::Shutdown) => {
+                    Self::commit(&consumer);
+                    break;
+                }
+                Ok(ConsumeExit::Fatal(e)) => {
+                    let delay = backoff.next_delay();
+                    error!(
+                        error = %e,
+                        retry_in_ms = delay.as_millis() as u64,
+                        "Kafka consumer failed, recreating"
+                    );
+                    drop(consumer);
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        _ = self.cancel_token.cancelled() => break,
+                    }
+                }
+                Err(e) => {
+                    Self::commit(&consumer);
+                    self.status = CollectorStatus::Error(e.to_string());
+                    return Err(e);
+                }
+            }
+        }
+
+        info!("Kafka collector received shutdown signal");
+        self.status = CollectorStatus::Stopped;
+        Ok(())
+    }
+
+    /// 컨슈머를 만들고 토픽을 구독합니다.
+    fn subscribe(&self) -> Result<StreamConsumer, LogPipelineError> {
+        let consumer: StreamConsumer = self
+            .config
+            .client_config()
+            .create()
+            .map_err(|e| collector_error(format!("failed to create consumer: {}", e)))?;
+        let topics: Vec<&str> = self.config.topics.iter().map(String::as_str).collect();
+        consumer
+            .subscribe(&topics)
+            .map_err(|e| collector_error(format!("failed to subscribe to {:?}: {}", topics, e)))?;
+        Ok(consumer)
+    }
+
+    /// 종료 신호나 치명적 에러가 올 때까지 메시지를 수신합니다.
+    async fn consume(
+        &self,
+        consumer: &StreamConsumer,
+        backoff: &mut Backoff,
+    ) -> Result<ConsumeExit, LogPipelineError> {
+        loop {
+            let message = tokio::select! {
+                result = consumer.recv() => result,
+                _ = self.cancel_token.cancelled() => return Ok(ConsumeExit::Shutdown),
+            };
+
+            let message = match message {
+                Ok(message) => message,
+                Err(e) if is_fatal(&e) => return Ok(ConsumeExit::Fatal(e)),
+                Err(e) => {
+                    let delay = backoff.next_delay();
+                    warn!(
+                        error = %e,
+                        retry_in_ms = delay.as_millis() as u64,
+                        "Kafka receive error"
+                    );
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => continue,
+                        _ = self.cancel_token.cancelled() => return Ok(ConsumeExit::Shutdown),
+                    }
+                }
+            };
+            backoff.reset();
+
+            if let Some(raw_log) = self.raw_log(&message) {
+                // 전송 전에 종료되면 오프셋을 저장하지 않아 다음 실행에서 다시 전달됨
+                tokio::select! {
+                    sent = self.tx.send(raw_log) => {
+                        if let Err(e) = sent {
+                            error!("Failed to send log to channel: {}", e);
+                            return Err(LogPipelineError::Channel(e.to_string()));
+                        }
+                    }
+                    _ = self.cancel_token.cancelled() => return Ok(ConsumeExit::Shutdown),
+                }
+            }
+
+            // 건너뛴 메시지도 다시 읽지 않도록 오프셋을 저장
+            if let Err(e) = consumer.store_offset_from_message(&message) {
+                warn!(
+                    topic = message.topic(),
+                    partition = message.partition(),
+                    offset = message.offset(),
+                    error = %e,
+                    "failed to store Kafka offset"
+                );
+            }
+        }
+    }
+
+    /// 메시지를 RawLog로 변환합니다 (빈 메시지와 너무 큰 메시지는 `None`).
+    fn raw_log<M: Message>(&self, message: &M) -> Option<RawLog> {
+        let payload = match message.payload() {
+            Some(payload) if !payload.is_empty() => payload,
+            _ => {
+                debug!(
+                    topic = message.topic(),
+                    offset = message.offset(),
+                    "skipping empty Kafka message"
+                );
+                return None;
+            }
+        };
+        if payload.len() > self.config.max_message_size {
+            warn!(
+                topic = message.topic(),
+                partition = message.partition(),
+                offset = message.offset(),
+                size = payload.len(),
+                max = self.config.max_message_size,
+                "Kafka message exceeds max size, skipping"
+            );
+            return None;
+        }
+
+        let raw_log = RawLog::new(
+            Bytes::copy_from_slice(payload),
+            format!("kafka:{}", message.topic()),
+        );
+        Some(match &self.config.format_hint {
+            Some(hint) => raw_log.with_format_hint(hint.clone()),
+            None => raw_log,
+        })
+    }
+
+    /// 저장된 오프셋을 동기 커밋합니다 (종료 시 호출).
+    fn commit(consumer: &StreamConsumer) {
+        match consumer.commit_consumer_state(CommitMode::Sync) {
+            Ok(()) => debug!("committed Kafka offsets"),
+            // 마지막 커밋 이후 저장된 오프셋이 없음
+            Err(KafkaError::ConsumerCommit(RDKafkaErrorCode::NoOffset)) => {}
+            Err(e) => warn!(error = %e, "failed to commit Kafka offsets on shutdown"),
+        }
+    }
+
+    /// 구독 토픽 목록을 반환합니다.
+    pub fn topics(&self) -> &[String] {
+        &self.config.topics
+    }
+
+    /// 현재 상태를 반환합니다.
+    pub fn status(&self) -> &CollectorStatus {
+        &self.status
+    }
+}
+
+/// 컨슈머를 다시 만들어야 하는 에러인지 확인합니다.
+fn is_fatal(error: &KafkaError) -> bool {
+    matches!(error, KafkaError::MessageConsumptionFatal(_))
+        || error.rdkafka_error_code() == Some(RDKafkaErrorCode::Fatal)
+}
+
+fn collector_error(reason: String) -> LogPipelineError {
+    LogPipelineError::Collector {
+        source_type: "kafka".to_owned(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rdkafka::message::{OwnedMessage, Timestamp};
+
+    fn config() -> KafkaCollectorConfig {
+        KafkaCollectorConfig::from_core(&KafkaConfig {
+            brokers: vec!["kafka-1:9092".to_owned(), "kafka-2:9092".to_owned()],
+            topics: vec!["app-logs".to_owned()],
+            ..KafkaConfig::default()
+        })
+    }
+
+    fn message(payload: Option<&[u8]>) -> OwnedMessage {
+        OwnedMessage::new(
+            payload.map(<[u8]>::to_vec),
+            None,
+            "app-logs".to_owned(),
+            Timestamp::NotAvailable,
+            0,
+            42,
+            None,
+        )
+    }
+
+    #[test]
+    fn client_config_manages_offsets() {
+        let mut config = config();
+        config
+            .properties
+            .insert("security.protocol".to_owned(), "ssl".to_owned());
+        let client = config.client_config();
+
+        assert_eq!(
+            client.get("bootstrap.servers"),
+            Some("kafka-1:9092,kafka-2:9092")
+        );
+        assert_eq!(client.get("group.id"), Some("ironpost"));
+        assert_eq!(client.get("enable.auto.commit"), Some("true"));
+        assert_eq!(client.get("enable.auto.offset.store"), Some("false"));
+        assert_eq!(client.get("auto.commit.interval.ms"), Some("5000"));
+        assert_eq!(client.get("reconnect.backoff.max.ms"), Some("30000"));
+        assert_eq!(client.get("security.protocol"), Some("ssl"));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max_and_resets() {
+        let mut backoff = Backoff::new(Duration::from_millis(500));
+        let delays: Vec<u128> = (0..5).map(|_| backoff.next_delay().as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), INITIAL_BACKOFF);
+    }
+
+    #[test]
+    fn raw_log_uses_topic_and_format_hint() {
+        let (tx, _rx) = mpsc::channel(1);
+        let mut config = config();
+        let collector = KafkaCollector::new(config.clone(), tx.clone(), CancellationToken::new());
+
+        let raw = collector
+            .raw_log(&message(Some(b"{\"msg\":\"login failed\"}")))
+            .expect("payload should be forwarded");
+        assert_eq!(raw.source, "kafka:app-logs");
+        assert_eq!(raw.format_hint, None);
+
+        config.format_hint = Some("json".to_owned());
+        let collector = KafkaCollector::new(config, tx, CancellationToken::new());
+        let raw = collector.raw_log(&message(Some(b"{}"))).unwrap();
+        assert_eq!(raw.format_hint.as_deref(), Some("json"));
+    }
+
+    #[test]
+    fn raw_log_skips_empty_and_oversized_messages() {
+        let (tx, _rx) = mpsc::channel(1);
+        let config = KafkaCollectorConfig {
+            max_message_size: 4,
+            ..config()
+        };
+        let collector = KafkaCollector::new(config, tx, CancellationToken::new());
+
+        assert!(collector.raw_log(&message(None)).is_none());
+        assert!(collector.raw_log(&message(Some(b""))).is_none());
+        assert!(collector.raw_log(&message(Some(b"too long"))).is_none());
+        assert!(collector.raw_log(&message(Some(b"ok"))).is_some());
+    }
+
+    #[tokio::test]
+    async fn run_stops_on_cancel_without_broker() {
+        let (tx, _rx) = mpsc::channel(1);
+        let cancel = CancellationToken::new();
+        let config = KafkaCollectorConfig {
+            brokers: vec!["127.0.0.1:1".to_owned()],
+            ..config()
+        };
+        let mut collector = KafkaCollector::new(config, tx, cancel.clone());
+        assert_eq!(*collector.status(), CollectorStatus::Idle);
+
+        let handle = tokio::spawn(async move {
+            let result = collector.run().await;
+            (result, collector)
+        });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        cancel.cancel();
+
+        let (result, collector) = tokio::time::timeout(Duration::from_secs(10), handle)
+            .await
+            .expect("collector should stop after cancel")
+            .unwrap();
+        result.unwrap();
+        assert_eq!(*collector.status(), CollectorStatus::Stopped);
+    }
+}
@@ -2,21 +2,32 @@
 //!
 //! # 수집 소스
 //! - [`FileCollector`]: 파일 감시 (tail -f 방식)
-//! - [`SyslogUdpCollector`]: UDP syslog 수신 (RFC 5424)
+//! - [`SyslogUdpCollector`]: UDP syslog 수신 (RFC 5424, GELF 청크/압축 포함)
 //! - [`SyslogTcpCollector`]: TCP syslog 수신 (RFC 5424)
 //! - [`EventReceiver`]: eBPF 엔진에서 `PacketEvent`를 mpsc 채널로 수신
+//! - `KafkaCollector`: Kafka 토픽 구독 (`kafka` 기능 플래그)
+//! - [`HttpCollector`]: `POST /ingest`로 JSON/NDJSON 배치 수신 (토큰 인증)
 //!
 //! # 아키텍처
 //! 각 수집기는 자체 tokio 태스크에서 실행되며, 수집된 원시 로그를
 //! `tokio::mpsc::Sender<RawLog>` 채널을 통해 파이프라인으로 전달합니다.
 
+pub mod archive;
+pub mod checkpoint;
 pub mod event_receiver;
 pub mod file;
+pub mod gelf;
+pub mod http;
+#[cfg(feature = "kafka")]
+pub mod kafka;
 pub mod syslog_tcp;
 pub mod syslog_udp;
 
 pub use event_receiver::EventReceiver;
 pub use file::FileCollector;
+pub use http::HttpCollector;
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaCollector;
 pub use syslog_tcp::SyslogTcpCollector;
 pub use syslog_udp::SyslogUdpCollector;
 
@@ -1,13 +1,19 @@
 //! TCP Syslog 수집기
 //!
 //! RFC 5424 형식의 syslog 메시지를 TCP 소켓으로 수신합니다.
-//! Octet-counting 또는 newline framing을 지원합니다.
+//! RFC 6587의 두 프레이밍 방식을 모두 지원합니다.
+//!
+//! - Octet-counting: `MSG-LEN SP SYSLOG-MSG` (rsyslog, syslog-ng의 RFC 5425 전송 기본값)
+//! - Newline (non-transparent): 메시지를 개행 문자로 구분
+//!
+//! 기본값([`TcpFraming::Auto`])에서는 프레임마다 첫 바이트로 방식을 판별합니다.
+//! syslog 메시지는 `<PRI>`로 시작하므로 숫자로 시작하는 프레임은 길이 접두사로 봅니다.
 
 use std::sync::Arc;
 use std::time::Duration;
 
 use bytes::Bytes;
-use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, BufReader};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::{Semaphore, mpsc};
 use tokio::time::timeout;
@@ -37,10 +43,12 @@ pub struct SyslogTcpConfig {
 /// TCP syslog 프레이밍 방식
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum TcpFraming {
-    /// Octet-counting (RFC 5425): 메시지 길이 접두사
-    OctetCounting,
-    /// 개행 문자로 메시지 구분 (기본값, 호환성 높음)
+    /// 프레임마다 첫 바이트로 판별 (숫자면 octet-counting, 아니면 newline, 기본값)
     #[default]
+    Auto,
+    /// Octet-counting (RFC 6587 3.4.1): 메시지 길이 접두사
+    OctetCounting,
+    /// 개행 문자로 메시지 구분 (RFC 6587 3.4.2)
     NewlineDelimited,
 }
 
@@ -180,34 +188,13 @@ impl SyslogTcpCollector {
             .map(|a| a.to_string())
             .unwrap_or_else(|_| "unknown".to_owned());
 
-        match config.framing {
-            TcpFraming::NewlineDelimited => {
-                Self::handle_newline_framing(stream, tx, config, bind_addr, peer_addr, cancel).await
-            }
-            TcpFraming::OctetCounting => {
-                // Octet-counting 프레이밍 (향후 구현)
-                warn!("Octet-counting framing not yet implemented, using newline framing");
-                Self::handle_newline_framing(stream, tx, config, bind_addr, peer_addr, cancel).await
-            }
-        }
+        let reader = BufReader::with_capacity(config.recv_buffer_size, stream);
+        Self::handle_reader(reader, tx, config, bind_addr, peer_addr, cancel).await
     }
 
-    /// Newline-delimited 프레이밍 처리
-    async fn handle_newline_framing(
-        stream: TcpStream,
-        tx: mpsc::Sender<RawLog>,
-        config: SyslogTcpConfig,
-        bind_addr: String,
-        peer_addr: String,
-        cancel: CancellationToken,
-    ) -> Result<(), LogPipelineError> {
-        let reader = BufReader::new(stream);
-        Self::handle_newline_reader(reader, tx, config, bind_addr, peer_addr, cancel).await
-    }
-
-    /// Newline-delimited 데이터 스트림 처리 (테스트 가능하도록 reader를 일반화)
-    async fn handle_newline_reader<R>(
-        mut reader: BufReader<R>,
+    /// 프레임 단위로 메시지를 읽어 전송합니다 (테스트 가능하도록 reader를 일반화)
+    async fn handle_reader<R>(
+        mut reader: R,
         tx: mpsc::Sender<RawLog>,
         config: SyslogTcpConfig,
         bind_addr: String,
@@ -215,42 +202,33 @@ impl SyslogTcpCollector {
         cancel: CancellationToken,
     ) -> Result<(), LogPipelineError>
     where
-        R: AsyncRead + Unpin,
+        R: AsyncBufRead + Unpin,
     {
-        let mut line_buffer = String::new();
+        let mut frame = Vec::new();
         let connection_timeout = Duration::from_secs(config.connection_timeout_secs);
 
         loop {
-            line_buffer.clear();
-
-            // 타임아웃과 함께 라인 읽기, cancellation token도 체크
+            // 타임아웃과 함께 프레임 읽기, cancellation token도 체크
             tokio::select! {
-                result = timeout(connection_timeout, reader.read_line(&mut line_buffer)) => {
+                result = timeout(
+                    connection_timeout,
+                    read_frame(&mut reader, &config.framing, config.max_message_size, &mut frame),
+                ) => {
                     match result {
-                        Ok(Ok(0)) => {
+                        Ok(Ok(false)) => {
                             // EOF - 연결 종료
                             debug!("Connection closed by peer: {}", peer_addr);
                             break;
                         }
-                        Ok(Ok(_bytes_read)) => {
-                            // 메시지가 최대 크기를 초과하는지 확인
-                            if line_buffer.len() > config.max_message_size {
-                                warn!(
-                                    "Message exceeds max size from {} ({} bytes, max: {}), closing connection",
-                                    peer_addr,
-                                    line_buffer.len(),
-                                    config.max_message_size
-                                );
-                                break;
-                            }
-
-                            // 빈 라인 스킵
-                            if line_buffer.trim().is_empty() {
+                        Ok(Ok(true)) => {
+                            // 빈 메시지 스킵
+                            let message = frame.trim_ascii_end();
+                            if message.trim_ascii_start().is_empty() {
                                 continue;
                             }
 
                             // RawLog 생성 및 전송
-                            let data = Bytes::from(line_buffer.trim_end().to_owned());
+                            let data = Bytes::copy_from_slice(message);
                             let raw_log =
                                 RawLog::new(data, format!("syslog_tcp:{}[{}]", bind_addr, peer_addr))
                                     .with_format_hint("syslog");
@@ -260,6 +238,15 @@ impl SyslogTcpCollector {
                                 return Err(LogPipelineError::Channel(e.to_string()));
                             }
                         }
+                        Ok(Err(e)) if e.kind() == std::io::ErrorKind::InvalidData => {
+                            // 프레임을 더 이상 맞출 수 없으므로 연결을 닫음
+                            warn!("Invalid frame from {}: {}, closing connection", peer_addr, e);
+                            break;
+                        }
+                        Ok(Err(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                            debug!("Connection closed mid-frame by peer: {}", peer_addr);
+                            break;
+                        }
                         Ok(Err(e)) => {
                             error!("Read error from {}: {}", peer_addr, e);
                             return Err(LogPipelineError::Collector {
@@ -302,6 +289,128 @@ impl SyslogTcpCollector {
     }
 }
 
+/// octet-counting 길이 접두사의 최대 자릿수
+const MAX_LENGTH_DIGITS: usize = 10;
+
+/// 다음 프레임 하나를 `buf`에 읽습니다.
+///
+/// EOF면 `false`를 반환합니다. 길이 접두사가 잘못되었거나 메시지가 `max_message_size`를
+/// 넘으면 `InvalidData` 에러를, 프레임 중간에 연결이 끊기면 `UnexpectedEof` 에러를 반환합니다.
+async fn read_frame<R>(
+    reader: &mut R,
+    framing: &TcpFraming,
+    max_message_size: usize,
+    buf: &mut Vec<u8>,
+) -> std::io::Result<bool>
+where
+    R: AsyncBufRead + Unpin,
+{
+    buf.clear();
+    match framing {
+        TcpFraming::NewlineDelimited => read_line_frame(reader, max_message_size, buf).await,
+        TcpFraming::OctetCounting => read_octet_frame(reader, max_message_size, buf).await,
+        TcpFraming::Auto => {
+            // 프레임 사이의 개행(일부 송신자가 octet 프레임 뒤에 붙임)은 건너뜀
+            let first = loop {
+                let available = reader.fill_buf().await?;
+                let Some(&first) = available.first() else {
+                    return Ok(false);
+                };
+                if first != b'\n' && first != b'\r' {
+                    break first;
+                }
+                reader.consume(1);
+            };
+            if first.is_ascii_digit() {
+                read_octet_frame(reader, max_message_size, buf).await
+            } else {
+                read_line_frame(reader, max_message_size, buf).await
+            }
+        }
+    }
+}
+
+/// 개행 문자로 끝나는 프레임을 읽습니다 (마지막 줄은 개행 없이 EOF로 끝날 수 있음).
+async fn read_line_frame<R>(
+    reader: &mut R,
+    max_message_size: usize,
+    buf: &mut Vec<u8>,
+) -> std::io::Result<bool>
+where
+    R: AsyncBufRead + Unpin,
+{
+    // 개행 없는 긴 입력이 메모리를 계속 차지하지 않도록 "\r\n"까지만 더 읽음
+    let limit = max_message_size.saturating_add(2) as u64;
+    if (&mut *reader).take(limit).read_until(b'\n', buf).await? == 0 {
+        return Ok(false);
+    }
+
+    let line = buf.strip_suffix(b"\n").unwrap_or(buf);
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    if line.len() > max_message_size {
+        return Err(too_large(line.len(), max_message_size));
+    }
+    Ok(true)
+}
+
+/// `MSG-LEN SP SYSLOG-MSG` 프레임을 읽습니다.
+async fn read_octet_frame<R>(
+    reader: &mut R,
+    max_message_size: usize,
+    buf: &mut Vec<u8>,
+) -> std::io::Result<bool>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut len: usize = 0;
+    let mut digits = 0;
+    loop {
+        let byte = match reader.read_u8().await {
+            Ok(byte) => byte,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof && digits == 0 => {
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        };
+        match byte {
+            b'0'..=b'9' if digits < MAX_LENGTH_DIGITS => {
+                len = len * 10 + usize::from(byte - b'0');
+                digits += 1;
+            }
+            b' ' if digits > 0 => break,
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "invalid octet-counting length prefix",
+                ));
+            }
+        }
+    }
+    if len == 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "octet-counting frame length must be greater than 0",
+        ));
+    }
+    if len > max_message_size {
+        return Err(too_large(len, max_message_size));
+    }
+
+    buf.resize(len, 0);
+    reader.read_exact(buf).await?;
+    Ok(true)
+}
+
+fn too_large(len: usize, max_message_size: usize) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!(
+            "message exceeds max size ({} bytes, max: {})",
+            len, max_message_size
+        ),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -312,12 +421,12 @@ mod tests {
         let config = SyslogTcpConfig::default();
         assert_eq!(config.bind_addr, "0.0.0.0:601");
         assert_eq!(config.max_connections, 256);
-        assert_eq!(config.framing, TcpFraming::NewlineDelimited);
+        assert_eq!(config.framing, TcpFraming::Auto);
     }
 
     #[test]
     fn tcp_framing_default() {
-        assert_eq!(TcpFraming::default(), TcpFraming::NewlineDelimited);
+        assert_eq!(TcpFraming::default(), TcpFraming::Auto);
     }
 
     #[test]
@@ -364,7 +473,7 @@ mod tests {
         let reader = BufReader::new(stream);
 
         let task = tokio::spawn(async move {
-            SyslogTcpCollector::handle_newline_reader(
+            SyslogTcpCollector::handle_reader(
                 reader,
                 tx,
                 config,
@@ -387,4 +496,82 @@ mod tests {
             "handler should exit cleanly on cancellation"
         );
     }
+
+    /// 입력 전체를 주어진 프레이밍으로 처리하고 전달된 메시지를 반환합니다.
+    async fn collect(input: &[u8], framing: TcpFraming, max_message_size: usize) -> Vec<String> {
+        let (tx, mut rx) = mpsc::channel(16);
+        let config = SyslogTcpConfig {
+            framing,
+            max_message_size,
+            ..Default::default()
+        };
+        SyslogTcpCollector::handle_reader(
+            input,
+            tx,
+            config,
+            "127.0.0.1:601".to_owned(),
+            "test-peer".to_owned(),
+            CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+
+        let mut messages = Vec::new();
+        while let Ok(raw) = rx.try_recv() {
+            messages.push(String::from_utf8(raw.data.to_vec()).unwrap());
+        }
+        messages
+    }
+
+    #[tokio::test]
+    async fn newline_framing_splits_lines() {
+        let messages = collect(
+            b"<34>1 - host app - - - first\r\n\n<34>1 - host app - - - second",
+            TcpFraming::NewlineDelimited,
+            1024,
+        )
+        .await;
+        assert_eq!(
+            messages,
+            vec![
+                "<34>1 - host app - - - first",
+                "<34>1 - host app - - - second"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn octet_counting_allows_embedded_newlines() {
+        let messages = collect(b"11 <34>1 a\nb c5 <13>x", TcpFraming::OctetCounting, 1024).await;
+        assert_eq!(messages, vec!["<34>1 a\nb c", "<13>x"]);
+    }
+
+    #[tokio::test]
+    async fn auto_framing_detects_each_frame() {
+        let messages = collect(
+            b"5 <13>a\n<13>b\n5 <13>c7 <13>d e\n",
+            TcpFraming::Auto,
+            1024,
+        )
+        .await;
+        assert_eq!(messages, vec!["<13>a", "<13>b", "<13>c", "<13>d e"]);
+    }
+
+    #[tokio::test]
+    async fn invalid_or_oversized_frames_close_connection() {
+        // 길이 접두사 뒤에 공백이 없음
+        let messages = collect(b"12x <13>a\n<13>b\n", TcpFraming::OctetCounting, 1024).await;
+        assert!(messages.is_empty());
+
+        // 앞선 메시지는 전달되고 초과 프레임에서 연결 종료
+        let messages = collect(b"5 <13>a20 <13>aaaaaaaaaaaaaaaaa", TcpFraming::Auto, 16).await;
+        assert_eq!(messages, vec!["<13>a"]);
+
+        let messages = collect(b"<13>ok\n<13>this line is too long\n", TcpFraming::Auto, 16).await;
+        assert_eq!(messages, vec!["<13>ok"]);
+
+        // 프레임 중간에 끊김
+        let messages = collect(b"10 <13>a", TcpFraming::Auto, 1024).await;
+        assert!(messages.is_empty());
+    }
 }
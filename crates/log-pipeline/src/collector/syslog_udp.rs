@@ -2,6 +2,11 @@
 //!
 //! RFC 5424 형식의 syslog 메시지를 UDP 소켓으로 수신합니다.
 //! 표준 syslog 포트(514/udp)에서 수신하거나, 설정된 주소에 바인드합니다.
+//!
+//! 같은 포트로 GELF 메시지도 받습니다. 청크로 나뉜 메시지는 재조립하고, gzip/zlib으로
+//! 압축된 메시지는 압축을 풀어 `gelf` 형식 힌트와 함께 전달합니다 ([`super::gelf`]).
+
+use std::time::Instant;
 
 use bytes::Bytes;
 use tokio::net::UdpSocket;
@@ -9,6 +14,7 @@ use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info};
 
+use super::gelf::{GELF_CHUNK_MAGIC, GelfReassembler, decode_payload, is_gelf};
 use super::{CollectorStatus, RawLog};
 use crate::error::LogPipelineError;
 
@@ -21,6 +27,10 @@ pub struct SyslogUdpConfig {
     pub recv_buffer_size: usize,
     /// 최대 메시지 크기 (바이트, UDP이므로 일반적으로 65535 이하)
     pub max_message_size: usize,
+    /// 동시에 재조립할 최대 GELF 청크 메시지 수
+    pub gelf_max_pending: usize,
+    /// 재조립/압축 해제한 GELF 메시지의 최대 크기 (바이트)
+    pub gelf_max_message_size: usize,
 }
 
 impl Default for SyslogUdpConfig {
@@ -29,6 +39,8 @@ impl Default for SyslogUdpConfig {
             bind_addr: "0.0.0.0:514".to_owned(),
             recv_buffer_size: 256 * 1024, // 256KB
             max_message_size: 65535,
+            gelf_max_pending: 1024,
+            gelf_max_message_size: 1024 * 1024, // 1MB
         }
     }
 }
@@ -93,6 +105,10 @@ impl SyslogUdpCollector {
         );
 
         let mut buf = vec![0u8; self.config.max_message_size];
+        let mut reassembler = GelfReassembler::new(
+            self.config.gelf_max_pending,
+            self.config.gelf_max_message_size,
+        );
 
         loop {
             tokio::select! {
@@ -106,10 +122,15 @@ impl SyslogUdpCollector {
                             }
 
                             // 수신된 데이터를 RawLog로 변환
-                            let data = Bytes::copy_from_slice(&buf[..len]);
+                            let Some((data, format_hint)) = self.decode_datagram(
+                                &buf[..len],
+                                &mut reassembler,
+                            ) else {
+                                continue;
+                            };
                             let raw_log =
                                 RawLog::new(data, format!("syslog_udp:{}", self.config.bind_addr))
-                                    .with_format_hint("syslog");
+                                    .with_format_hint(format_hint);
 
                             // 채널로 전송
                             if let Err(e) = self.tx.send(raw_log).await {
@@ -139,6 +160,28 @@ impl SyslogUdpCollector {
         Ok(())
     }
 
+    /// 데이터그램을 (메시지, 형식 힌트)로 변환합니다.
+    ///
+    /// GELF 청크는 메시지가 완성될 때까지 `None`을 반환합니다. 압축 해제에 실패한
+    /// 비청크 데이터그램은 GELF가 아닌 것으로 보고 그대로 syslog로 전달합니다.
+    fn decode_datagram(
+        &self,
+        datagram: &[u8],
+        reassembler: &mut GelfReassembler,
+    ) -> Option<(Bytes, &'static str)> {
+        let max_size = self.config.gelf_max_message_size;
+        if datagram.starts_with(&GELF_CHUNK_MAGIC) {
+            let message = reassembler.push(datagram, Instant::now())?;
+            return decode_payload(message, max_size).map(|data| (Bytes::from(data), "gelf"));
+        }
+        if is_gelf(datagram)
+            && let Some(data) = decode_payload(datagram.to_vec(), max_size)
+        {
+            return Some((Bytes::from(data), "gelf"));
+        }
+        Some((Bytes::copy_from_slice(datagram), "syslog"))
+    }
+
     /// 바인드 주소를 반환합니다.
     pub fn bind_addr(&self) -> &str {
         &self.config.bind_addr
@@ -205,6 +248,46 @@ mod tests {
         assert!(rx.try_recv().is_err());
     }
 
+    #[test]
+    fn decode_datagram_handles_syslog_and_gelf() {
+        let (tx, _rx) = mpsc::channel(10);
+        let collector = SyslogUdpCollector::new(SyslogUdpConfig::default(), tx);
+        let mut reassembler = GelfReassembler::new(16, 1024);
+
+        let (data, hint) = collector
+            .decode_datagram(b"<34>1 - host app - - - msg", &mut reassembler)
+            .unwrap();
+        assert_eq!(hint, "syslog");
+        assert_eq!(data.as_ref(), b"<34>1 - host app - - - msg");
+
+        // 첫 바이트가 zlib 헤더처럼 보여도 압축이 아니면 syslog로 전달
+        let (_, hint) = collector
+            .decode_datagram(b"x^ plain text", &mut reassembler)
+            .unwrap();
+        assert_eq!(hint, "syslog");
+
+        let json = br#"{"version":"1.1","host":"h","short_message":"m"}"#;
+        let mut first = GELF_CHUNK_MAGIC.to_vec();
+        first.extend_from_slice(&9u64.to_be_bytes());
+        first.extend_from_slice(&[0, 2]);
+        first.extend_from_slice(&json[..20]);
+        let mut second = GELF_CHUNK_MAGIC.to_vec();
+        second.extend_from_slice(&9u64.to_be_bytes());
+        second.extend_from_slice(&[1, 2]);
+        second.extend_from_slice(&json[20..]);
+
+        assert!(
+            collector
+                .decode_datagram(&first, &mut reassembler)
+                .is_none()
+        );
+        let (data, hint) = collector
+            .decode_datagram(&second, &mut reassembler)
+            .unwrap();
+        assert_eq!(hint, "gelf");
+        assert_eq!(data.as_ref(), json);
+    }
+
     #[tokio::test]
     async fn udp_collector_creation() {
         let (tx, _rx) = mpsc::channel(10);
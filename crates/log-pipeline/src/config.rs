@@ -12,10 +12,18 @@
 //! let config = PipelineConfig::from_core(&core_config.log_pipeline);
 //! ```
 
+use ironpost_core::config::{
+    AlertSinkConfig, DiskBufferConfig, ElasticsearchConfig, EnrichmentConfig, FileSourceConfig,
+    HttpIngestConfig, KafkaConfig, KafkaOutputConfig, LogStoreConfig, ParsersConfig, SmtpConfig,
+    TransformConfig,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Component, Path};
 
 use crate::error::LogPipelineError;
+use crate::parser::PatternParser;
+use crate::transform::TransformChain;
 
 /// 버퍼 오버플로우 시 드롭 정책
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -47,11 +55,56 @@ pub struct PipelineConfig {
     pub batch_size: usize,
     /// 배치 플러시 간격 (초)
     pub flush_interval_secs: u64,
+    /// Kafka 수집 소스 설정 (`sources`에 "kafka"가 있을 때 사용)
+    #[serde(default)]
+    pub kafka: KafkaConfig,
+    /// HTTP 수집 엔드포인트 설정 (`sources`에 "http"가 있을 때 사용)
+    #[serde(default)]
+    pub http: HttpIngestConfig,
+    /// 파일 수집 소스 설정 (체크포인트, 시작 위치)
+    #[serde(default)]
+    pub file: FileSourceConfig,
+    /// 선택적 파서 설정 (LEEF 등)
+    #[serde(default)]
+    pub parsers: ParsersConfig,
+    /// IP 보강 설정 (`enrich_logs`가 켜지면 파싱과 규칙 매칭 사이에서 실행)
+    #[serde(default)]
+    pub enrichment: EnrichmentConfig,
+    /// 소스 종류별 기본 파서 힌트 (수집기가 힌트를 주지 않은 로그에 사용)
+    #[serde(default)]
+    pub format_hints: BTreeMap<String, String>,
+    /// 규칙 디렉토리 변경 시 규칙을 다시 로드할지 여부
+    #[serde(default)]
+    pub watch_rules: bool,
+    /// 규칙 억제 목록 파일 경로 (빈 문자열이면 사용 안 함)
+    #[serde(default)]
+    pub suppressions_file: String,
+    /// 알림 싱크 목록 (웹훅 등)
+    #[serde(default)]
+    pub sinks: Vec<AlertSinkConfig>,
+    /// 이메일 싱크용 SMTP 서버 설정
+    #[serde(default)]
+    pub smtp: SmtpConfig,
+    /// Elasticsearch/OpenSearch 출력 설정
+    #[serde(default)]
+    pub elasticsearch: ElasticsearchConfig,
+    /// Kafka 출력(프로듀서) 설정
+    #[serde(default)]
+    pub kafka_output: KafkaOutputConfig,
+    /// 디스크 오버플로 버퍼 설정
+    #[serde(default)]
+    pub disk_buffer: DiskBufferConfig,
+    /// 필드 변환 목록 (파싱 직후, 보강과 규칙 매칭 전에 등록 순서대로 적용)
+    #[serde(default)]
+    pub transforms: Vec<TransformConfig>,
+    /// 내장 로그 저장소 설정
+    #[serde(default)]
+    pub log_store: LogStoreConfig,
 
     // --- 확장 설정 (core에 없는 추가 필드) ---
     /// 탐지 룰 디렉토리 경로
     pub rule_dir: String,
-    /// 룰 리로드 주기 (초)
+    /// `watch_rules` 사용 시 디렉토리 재확인 주기 (초, 0이면 파일 이벤트로만 리로드)
     pub rule_reload_secs: u64,
     /// 인메모리 버퍼 최대 용량
     pub buffer_capacity: usize,
@@ -73,6 +126,21 @@ impl Default for PipelineConfig {
             watch_paths: vec!["/var/log/syslog".to_owned()],
             batch_size: 100,
             flush_interval_secs: 5,
+            kafka: KafkaConfig::default(),
+            http: HttpIngestConfig::default(),
+            file: FileSourceConfig::default(),
+            parsers: ParsersConfig::default(),
+            enrichment: EnrichmentConfig::default(),
+            format_hints: BTreeMap::new(),
+            watch_rules: false,
+            suppressions_file: String::new(),
+            sinks: Vec::new(),
+            smtp: SmtpConfig::default(),
+            elasticsearch: ElasticsearchConfig::default(),
+            kafka_output: KafkaOutputConfig::default(),
+            disk_buffer: DiskBufferConfig::default(),
+            transforms: Vec::new(),
+            log_store: LogStoreConfig::default(),
             rule_dir: "/etc/ironpost/rules".to_owned(),
             rule_reload_secs: 30,
             buffer_capacity: 10_000,
@@ -96,6 +164,21 @@ impl PipelineConfig {
             watch_paths: core.watch_paths.clone(),
             batch_size: core.batch_size,
             flush_interval_secs: core.flush_interval_secs,
+            kafka: core.kafka.clone(),
+            http: core.http.clone(),
+            file: core.file.clone(),
+            parsers: core.parsers.clone(),
+            enrichment: core.enrichment.clone(),
+            format_hints: core.format_hints.clone(),
+            watch_rules: core.watch_rules,
+            suppressions_file: core.suppressions_file.clone(),
+            sinks: core.sinks.clone(),
+            smtp: core.smtp.clone(),
+            elasticsearch: core.elasticsearch.clone(),
+            kafka_output: core.kafka_output.clone(),
+            disk_buffer: core.disk_buffer.clone(),
+            transforms: core.transforms.clone(),
+            log_store: core.log_store.clone(),
             ..Self::default()
         }
     }
@@ -127,6 +210,19 @@ impl PipelineConfig {
             });
         }
 
+        // glob 패턴 문법 체크
+        if crate::collector::file::is_glob_pattern(path_str)
+            && let Err(e) = glob::Pattern::new(path_str)
+        {
+            return Err(LogPipelineError::Config {
+                field: "watch_paths".to_owned(),
+                reason: format!(
+                    "watch path '{}' is not a valid glob pattern: {}",
+                    path_str, e
+                ),
+            });
+        }
+
         // 절대 경로 체크
         if !path.is_absolute() {
             return Err(LogPipelineError::Config {
@@ -216,6 +312,105 @@ impl PipelineConfig {
             });
         }
 
+        self.parsers
+            .validate()
+            .map_err(|e| LogPipelineError::Config {
+                field: "parsers".to_owned(),
+                reason: e.to_string(),
+            })?;
+        self.parsers
+            .validate_format_hints(&self.format_hints)
+            .map_err(|e| LogPipelineError::Config {
+                field: "format_hints".to_owned(),
+                reason: e.to_string(),
+            })?;
+        for pattern in &self.parsers.patterns {
+            PatternParser::from_config(pattern, &self.parsers.grok_definitions)?;
+        }
+
+        if self.sources.iter().any(|s| s == "kafka") {
+            self.kafka
+                .validate()
+                .map_err(|e| LogPipelineError::Config {
+                    field: "kafka".to_owned(),
+                    reason: e.to_string(),
+                })?;
+        }
+
+        if self.sources.iter().any(|s| s == "http") {
+            self.http.validate().map_err(|e| LogPipelineError::Config {
+                field: "http".to_owned(),
+                reason: e.to_string(),
+            })?;
+        }
+
+        if self.sources.iter().any(|s| s == "file") {
+            self.file.validate().map_err(|e| LogPipelineError::Config {
+                field: "file".to_owned(),
+                reason: e.to_string(),
+            })?;
+        }
+
+        for (i, sink) in self.sinks.iter().enumerate() {
+            sink.validate(&format!("sinks[{i}]"))
+                .map_err(|e| LogPipelineError::Config {
+                    field: "sinks".to_owned(),
+                    reason: e.to_string(),
+                })?;
+        }
+
+        if self.sinks.iter().any(|sink| sink.kind == "email") {
+            self.smtp.validate().map_err(|e| LogPipelineError::Config {
+                field: "smtp".to_owned(),
+                reason: e.to_string(),
+            })?;
+        }
+
+        if self.elasticsearch.enabled {
+            self.elasticsearch
+                .validate()
+                .map_err(|e| LogPipelineError::Config {
+                    field: "elasticsearch".to_owned(),
+                    reason: e.to_string(),
+                })?;
+        }
+
+        if self.kafka_output.enabled {
+            self.kafka_output
+                .validate()
+                .map_err(|e| LogPipelineError::Config {
+                    field: "kafka_output".to_owned(),
+                    reason: e.to_string(),
+                })?;
+        }
+
+        self.enrichment
+            .validate()
+            .map_err(|e| LogPipelineError::Config {
+                field: "enrichment".to_owned(),
+                reason: e.to_string(),
+            })?;
+
+        if self.disk_buffer.enabled {
+            self.disk_buffer
+                .validate()
+                .map_err(|e| LogPipelineError::Config {
+                    field: "disk_buffer".to_owned(),
+                    reason: e.to_string(),
+                })?;
+        }
+
+        TransformChain::from_config(&self.transforms)?;
+
+        if self.log_store.enabled {
+            self.log_store
+                .validate()
+                .map_err(|e| LogPipelineError::Config {
+                    field: "log_store".to_owned(),
+                    reason: e.to_string(),
+                })?;
+        }
+
         // watch_paths 경로 순회 검증
         for path in &self.watch_paths {
             Self::validate_watch_path(path)?;
@@ -281,12 +476,110 @@ impl PipelineConfigBuilder {
         self
     }
 
+    /// Kafka 수집 소스 설정을 지정합니다.
+    pub fn kafka(mut self, kafka: KafkaConfig) -> Self {
+        self.config.kafka = kafka;
+        self
+    }
+
+    /// HTTP 수집 엔드포인트 설정을 지정합니다.
+    pub fn http(mut self, http: HttpIngestConfig) -> Self {
+        self.config.http = http;
+        self
+    }
+
+    /// 파일 수집 소스 설정을 지정합니다.
+    pub fn file(mut self, file: FileSourceConfig) -> Self {
+        self.config.file = file;
+        self
+    }
+
+    /// 선택적 파서 설정을 지정합니다.
+    pub fn parsers(mut self, parsers: ParsersConfig) -> Self {
+        self.config.parsers = parsers;
+        self
+    }
+
+    /// 소스 종류의 기본 파서 힌트를 추가합니다 (예: `"file"`, `"json"`).
+    pub fn format_hint(mut self, source: impl Into<String>, format: impl Into<String>) -> Self {
+        self.config
+            .format_hints
+            .insert(source.into(), format.into());
+        self
+    }
+
     /// 룰 디렉토리를 설정합니다.
     pub fn rule_dir(mut self, dir: impl Into<String>) -> Self {
         self.config.rule_dir = dir.into();
         self
     }
 
+    /// 규칙 디렉토리 hot reload 여부를 설정합니다.
+    pub fn watch_rules(mut self, enabled: bool) -> Self {
+        self.config.watch_rules = enabled;
+        self
+    }
+
+    /// 규칙 억제 목록 파일을 설정합니다.
+    pub fn suppressions_file(mut self, path: impl Into<String>) -> Self {
+        self.config.suppressions_file = path.into();
+        self
+    }
+
+    /// 알림 싱크를 추가합니다.
+    pub fn add_sink(mut self, sink: AlertSinkConfig) -> Self {
+        self.config.sinks.push(sink);
+        self
+    }
+
+    /// 이메일 싱크용 SMTP 서버 설정을 지정합니다.
+    pub fn smtp(mut self, smtp: SmtpConfig) -> Self {
+        self.config.smtp = smtp;
+        self
+    }
+
+    /// Elasticsearch/OpenSearch 출력 설정을 지정합니다.
+    pub fn elasticsearch(mut self, elasticsearch: ElasticsearchConfig) -> Self {
+        self.config.elasticsearch = elasticsearch;
+        self
+    }
+
+    /// Kafka 출력(프로듀서) 설정을 지정합니다.
+    pub fn kafka_output(mut self, kafka_output: KafkaOutputConfig) -> Self {
+        self.config.kafka_output = kafka_output;
+        self
+    }
+
+    /// IP 보강 설정을 지정합니다.
+    pub fn enrichment(mut self, enrichment: EnrichmentConfig) -> Self {
+        self.config.enrichment = enrichment;
+        self
+    }
+
+    /// 디스크 오버플로 버퍼 설정을 지정합니다.
+    pub fn disk_buffer(mut self, disk_buffer: DiskBufferConfig) -> Self {
+        self.config.disk_buffer = disk_buffer;
+        self
+    }
+
+    /// 필드 변환 목록을 지정합니다.
+    pub fn transforms(mut self, transforms: Vec<TransformConfig>) -> Self {
+        self.config.transforms = transforms;
+        self
+    }
+
+    /// 내장 로그 저장소 설정을 지정합니다.
+    pub fn log_store(mut self, log_store: LogStoreConfig) -> Self {
+        self.config.log_store = log_store;
+        self
+    }
+
+    /// 규칙 디렉토리 재확인 주기(초)를 설정합니다. 0이면 파일 이벤트로만 리로드합니다.
+    pub fn rule_reload_secs(mut self, secs: u64) -> Self {
+        self.config.rule_reload_secs = secs;
+        self
+    }
+
     /// 버퍼 용량을 설정합니다.
     pub fn buffer_capacity(mut self, capacity: usize) -> Self {
         self.config.buffer_capacity = capacity;
@@ -325,11 +618,15 @@ mod tests {
             watch_paths: vec!["/var/log/auth.log".to_owned()],
             batch_size: 200,
             flush_interval_secs: 10,
+            watch_rules: true,
+            suppressions_file: "/etc/ironpost/suppressions.yml".to_owned(),
             ..Default::default()
         };
         let config = PipelineConfig::from_core(&core);
         assert_eq!(config.syslog_bind, "127.0.0.1:5140");
         assert_eq!(config.batch_size, 200);
+        assert!(config.watch_rules);
+        assert_eq!(config.suppressions_file, "/etc/ironpost/suppressions.yml");
         // 확장 필드는 기본값
         assert_eq!(config.buffer_capacity, 10_000);
     }
@@ -350,6 +647,50 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn validate_accepts_glob_watch_paths() {
+        let mut config = PipelineConfig {
+            watch_paths: vec!["/var/log/nginx/*.log".to_owned()],
+            ..Default::default()
+        };
+        config.validate().unwrap();
+
+        config.watch_paths = vec!["/var/log/[app.log".to_owned()];
+        assert!(config.validate().is_err());
+
+        config.watch_paths = vec!["/*/log/app.log".to_owned()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_checks_kafka_only_when_source_enabled() {
+        let mut config = PipelineConfig::default();
+        config.validate().unwrap();
+
+        config.sources = vec!["kafka".to_owned()];
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("kafka.topics"));
+
+        config.kafka.topics = vec!["app-logs".to_owned()];
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn validate_checks_http_only_when_source_enabled() {
+        let mut config = PipelineConfig::default();
+        config.validate().unwrap();
+
+        config.sources = vec!["http".to_owned()];
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("http.tokens"));
+
+        config.http.tokens = vec![ironpost_core::config::IngestTokenConfig {
+            name: "vector".to_owned(),
+            token_sha256: "a".repeat(64),
+        }];
+        config.validate().unwrap();
+    }
+
     #[test]
     fn builder_creates_valid_config() {
         let config = PipelineConfigBuilder::new()
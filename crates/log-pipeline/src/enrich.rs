@@ -0,0 +1,756 @@
+//! IP 주소 보강 -- GeoIP 국가/ASN 및 역방향 DNS
+//!
+//! [`IpEnricher`]는 IP 주소에 분석에 필요한 맥락을 붙입니다.
+//!
+//! - [`GeoIpLookup`]: MaxMind DB(GeoLite2-Country, GeoLite2-ASN 등)에서
+//!   국가 코드와 AS 번호/조직명을 조회합니다.
+//! - [`ReverseDnsResolver`]: PTR 레코드를 조회하고 결과를 TTL 동안 캐시합니다.
+//!   실패와 타임아웃은 더 짧은 TTL로 캐시하며, 조회는 동시 실행 수 제한 아래
+//!   블로킹 스레드에서 타임아웃과 함께 수행됩니다.
+//!
+//! 데이터베이스가 없거나 조회에 실패한 항목은 `None`으로 남으며,
+//! 보강 실패가 호출자의 처리를 막지 않습니다.
+//!
+//! # 파이프라인 보강 단계
+//!
+//! [`EnrichmentStage`]는 파싱과 규칙 매칭 사이에서 [`LogEnricher`]를 차례로 실행해
+//! `LogEntry`에 필드를 덧붙입니다. `enrich_logs = true`이면 [`IpFieldEnricher`]가
+//! `ip_fields`의 IP 주소마다 `<필드>_country`, `<필드>_asn`, `<필드>_as_org`,
+//! `<필드>_hostname`을 추가하므로, 규칙과 알림이 이 맥락을 그대로 사용할 수 있습니다.
+//! 역방향 DNS는 처리 루프를 막지 않도록 캐시된 이름만 사용하고, 캐시에 없는 주소는
+//! 백그라운드에서 조회합니다.
+//!
+//! # 사용 예시
+//!
+//! ```ignore
+//! let enricher = IpEnricher::from_config(&config.log_pipeline.enrichment)?;
+//! let info = enricher.enrich("203.0.113.7".parse()?).await;
+//! println!("{:?} {:?}", info.country, info.hostname);
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use maxminddb::{Reader, geoip2};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+use tracing::debug;
+
+use ironpost_core::config::EnrichmentConfig;
+use ironpost_core::pipeline::BoxFuture;
+use ironpost_core::types::LogEntry;
+
+use crate::error::LogPipelineError;
+
+/// 보강 필드 이름 접미사 (`<IP 필드>_<접미사>`)
+pub const ENRICHMENT_FIELD_SUFFIXES: &[&str] = &["country", "asn", "as_org", "hostname"];
+
+/// 대기하거나 진행 중일 수 있는 백그라운드 역방향 DNS 조회 수
+const MAX_PENDING_LOOKUPS: usize = 256;
+
+/// 역방향 DNS 기본 성공 결과 TTL
+const DEFAULT_POSITIVE_TTL: Duration = Duration::from_secs(3600);
+/// 기본 실패·타임아웃 결과 TTL
+const DEFAULT_NEGATIVE_TTL: Duration = Duration::from_secs(60);
+/// 기본 동시 조회 수
+const DEFAULT_MAX_CONCURRENT_LOOKUPS: usize = 16;
+
+/// IP 주소 하나에 대한 보강 결과
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpEnrichment {
+    /// ISO 3166-1 국가 코드 (예: "KR")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// AS 번호
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asn: Option<u32>,
+    /// AS 조직명
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_org: Option<String>,
+    /// 역방향 DNS 이름
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+}
+
+impl IpEnrichment {
+    /// 보강된 항목이 하나도 없는지 여부
+    pub fn is_empty(&self) -> bool {
+        self.country.is_none()
+            && self.asn.is_none()
+            && self.as_org.is_none()
+            && self.hostname.is_none()
+    }
+
+    /// `field`에 대한 보강 필드 목록 (`<field>_country` 등, 값이 있는 항목만)
+    pub fn to_fields(&self, field: &str) -> Vec<(String, String)> {
+        let values = [
+            self.country.clone(),
+            self.asn.map(|asn| asn.to_string()),
+            self.as_org.clone(),
+            self.hostname.clone(),
+        ];
+        ENRICHMENT_FIELD_SUFFIXES
+            .iter()
+            .zip(values)
+            .filter_map(|(suffix, value)| Some((format!("{}_{}", field, suffix), value?)))
+            .collect()
+    }
+}
+
+/// MaxMind DB 기반 GeoIP 조회
+pub struct GeoIpLookup {
+    country: Option<Reader<Vec<u8>>>,
+    asn: Option<Reader<Vec<u8>>>,
+}
+
+impl GeoIpLookup {
+    /// 국가/ASN 데이터베이스를 엽니다. `None`인 데이터베이스는 조회하지 않습니다.
+    ///
+    /// # Errors
+    ///
+    /// 데이터베이스 파일을 읽을 수 없거나 MaxMind DB 형식이 아니면
+    /// `LogPipelineError::Config`를 반환합니다.
+    pub fn open(
+        country_db: Option<&Path>,
+        asn_db: Option<&Path>,
+    ) -> Result<Self, LogPipelineError> {
+        Ok(Self {
+            country: country_db
+                .map(|path| open_reader(path, "geoip_country_db"))
+                .transpose()?,
+            asn: asn_db
+                .map(|path| open_reader(path, "geoip_asn_db"))
+                .transpose()?,
+        })
+    }
+
+    /// 데이터베이스가 하나라도 열려 있는지 여부
+    pub fn is_enabled(&self) -> bool {
+        self.country.is_some() || self.asn.is_some()
+    }
+
+    /// 국가 코드와 AS 정보를 `enrichment`에 채웁니다.
+    pub fn lookup(&self, ip: IpAddr, enrichment: &mut IpEnrichment) {
+        if let Some(reader) = &self.country {
+            match reader.lookup::<geoip2::Country>(ip) {
+                Ok(Some(record)) => {
+                    enrichment.country = record.country.and_then(|c| c.iso_code).map(str::to_owned);
+                }
+                Ok(None) => {}
+                Err(e) => debug!(%ip, error = %e, "geoip country lookup failed"),
+            }
+        }
+        if let Some(reader) = &self.asn {
+            match reader.lookup::<geoip2::Asn>(ip) {
+                Ok(Some(record)) => {
+                    enrichment.asn = record.autonomous_system_number;
+                    enrichment.as_org = record.autonomous_system_organization.map(str::to_owned);
+                }
+                Ok(None) => {}
+                Err(e) => debug!(%ip, error = %e, "geoip asn lookup failed"),
+            }
+        }
+    }
+}
+
+fn open_reader(path: &Path, field: &str) -> Result<Reader<Vec<u8>>, LogPipelineError> {
+    Reader::open_readfile(path).map_err(|e| LogPipelineError::Config {
+        field: format!("log_pipeline.enrichment.{}", field),
+        reason: format!("failed to open {}: {}", path.display(), e),
+    })
+}
+
+/// 캐시를 갖춘 역방향 DNS 조회기
+///
+/// 찾은 이름은 `positive_ttl`, PTR 레코드가 없는 주소와 타임아웃은 `negative_ttl` 동안
+/// 캐시하여 같은 주소를 반복 조회하지 않습니다. 만료된 항목은 다음 조회 때 갱신되고,
+/// 캐시가 가득 차면 가장 오래된 항목부터 제거합니다.
+///
+/// 블로킹 조회는 `max_concurrent`개까지만 동시에 실행합니다. 타임아웃 안에 차례가
+/// 오지 않으면 결과를 캐시하지 않고 `None`을 반환합니다.
+pub struct ReverseDnsResolver {
+    timeout: Duration,
+    capacity: usize,
+    positive_ttl: Duration,
+    negative_ttl: Duration,
+    lookups: Arc<Semaphore>,
+    cache: Mutex<DnsCache>,
+}
+
+#[derive(Default)]
+struct DnsCache {
+    names: HashMap<IpAddr, CachedName>,
+    order: VecDeque<IpAddr>,
+    /// 백그라운드 조회 중인 주소
+    pending: HashSet<IpAddr>,
+}
+
+struct CachedName {
+    name: Option<String>,
+    expires_at: Instant,
+}
+
+impl DnsCache {
+    /// 만료되지 않은 캐시 항목을 반환합니다.
+    fn get(&self, ip: &IpAddr) -> Option<Option<String>> {
+        self.names
+            .get(ip)
+            .filter(|entry| entry.expires_at > Instant::now())
+            .map(|entry| entry.name.clone())
+    }
+}
+
+impl ReverseDnsResolver {
+    /// 새 조회기를 생성합니다. `capacity`는 최소 1로 보정됩니다.
+    ///
+    /// TTL과 동시 조회 수는 기본값(1시간, 60초, 16개)을 사용하며
+    /// [`with_ttl`](Self::with_ttl), [`with_max_concurrent`](Self::with_max_concurrent)로
+    /// 바꿀 수 있습니다.
+    pub fn new(timeout: Duration, capacity: usize) -> Self {
+        Self {
+            timeout,
+            capacity: capacity.max(1),
+            positive_ttl: DEFAULT_POSITIVE_TTL,
+            negative_ttl: DEFAULT_NEGATIVE_TTL,
+            lookups: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_LOOKUPS)),
+            cache: Mutex::new(DnsCache::default()),
+        }
+    }
+
+    /// 성공 결과와 실패·타임아웃 결과의 캐시 유지 시간을 설정합니다.
+    ///
+    /// `negative_ttl`이 0이면 실패 결과를 캐시하지 않습니다.
+    pub fn with_ttl(mut self, positive_ttl: Duration, negative_ttl: Duration) -> Self {
+        self.positive_ttl = positive_ttl;
+        self.negative_ttl = negative_ttl;
+        self
+    }
+
+    /// 동시에 실행할 블로킹 조회 수를 설정합니다. 최소 1로 보정됩니다.
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.lookups = Arc::new(Semaphore::new(max_concurrent.max(1)));
+        self
+    }
+
+    /// `ip`의 PTR 이름을 조회합니다. 이름이 없거나 시간이 초과되면 `None`입니다.
+    pub async fn resolve(&self, ip: IpAddr) -> Option<String> {
+        if let Some(cached) = self.cached(ip) {
+            return cached;
+        }
+
+        // 대기 시간과 조회 시간을 합쳐 타임아웃을 적용합니다.
+        let deadline = Instant::now() + self.timeout;
+        let permit = match tokio::time::timeout_at(
+            deadline,
+            Arc::clone(&self.lookups).acquire_owned(),
+        )
+        .await
+        {
+            Ok(Ok(permit)) => permit,
+            Ok(Err(_)) | Err(_) => {
+                debug!(%ip, "reverse dns lookup skipped: too many concurrent lookups");
+                return None;
+            }
+        };
+
+        // 허가는 블로킹 스레드가 끝날 때 반환되므로, 시간이 초과된 조회도
+        // 끝날 때까지 동시 실행 수에 포함됩니다.
+        let lookup = tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            dns_lookup::lookup_addr(&ip)
+        });
+        let name = match tokio::time::timeout_at(deadline, lookup).await {
+            Ok(Ok(Ok(name))) => normalize_hostname(&name),
+            Ok(Ok(Err(e))) => {
+                debug!(%ip, error = %e, "reverse dns lookup failed");
+                None
+            }
+            Ok(Err(e)) => {
+                debug!(%ip, error = %e, "reverse dns task failed");
+                None
+            }
+            Err(_) => {
+                debug!(%ip, "reverse dns lookup timed out");
+                None
+            }
+        };
+        self.insert(ip, name.clone());
+        name
+    }
+
+    /// 캐시된 PTR 이름만 반환하고, 캐시에 없으면 백그라운드 조회를 시작합니다.
+    ///
+    /// 처리 루프를 막지 않기 위한 조회입니다. 처음 보는 주소는 `None`이고, 조회가 끝난
+    /// 뒤부터 캐시된 이름을 반환합니다. 진행 중인 조회가 많으면 새 조회를 건너뜁니다.
+    pub fn resolve_cached(self: &Arc<Self>, ip: IpAddr) -> Option<String> {
+        {
+            let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(name) = cache.get(&ip) {
+                return name;
+            }
+            if cache.pending.len() >= MAX_PENDING_LOOKUPS || !cache.pending.insert(ip) {
+                return None;
+            }
+        }
+
+        let resolver = Arc::clone(self);
+        tokio::spawn(async move {
+            resolver.resolve(ip).await;
+            let mut cache = resolver.cache.lock().unwrap_or_else(|e| e.into_inner());
+            cache.pending.remove(&ip);
+        });
+        None
+    }
+
+    fn cached(&self, ip: IpAddr) -> Option<Option<String>> {
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.get(&ip)
+    }
+
+    fn insert(&self, ip: IpAddr, name: Option<String>) {
+        let ttl = if name.is_some() {
+            self.positive_ttl
+        } else {
+            self.negative_ttl
+        };
+        if ttl.is_zero() {
+            return;
+        }
+        let entry = CachedName {
+            name,
+            expires_at: Instant::now() + ttl,
+        };
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if cache.names.insert(ip, entry).is_none() {
+            cache.order.push_back(ip);
+        }
+        while cache.order.len() > self.capacity {
+            if let Some(oldest) = cache.order.pop_front() {
+                cache.names.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// 조회 결과에서 끝의 `.`을 제거하고, 숫자 주소(이름 없음)는 `None`으로 바꿉니다.
+fn normalize_hostname(name: &str) -> Option<String> {
+    let name = name.trim().trim_end_matches('.');
+    if name.is_empty() || name.parse::<IpAddr>().is_ok() {
+        return None;
+    }
+    Some(name.to_owned())
+}
+
+/// GeoIP와 역방향 DNS를 묶은 IP 보강기
+pub struct IpEnricher {
+    geoip: GeoIpLookup,
+    reverse_dns: Option<Arc<ReverseDnsResolver>>,
+}
+
+impl std::fmt::Debug for IpEnricher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IpEnricher")
+            .field("geoip", &self.geoip.is_enabled())
+            .field("reverse_dns", &self.reverse_dns.is_some())
+            .finish()
+    }
+}
+
+impl IpEnricher {
+    /// 설정으로부터 보강기를 생성합니다.
+    ///
+    /// 경로가 빈 GeoIP 데이터베이스는 사용하지 않으며,
+    /// `reverse_dns = false`이면 역방향 DNS를 조회하지 않습니다.
+    ///
+    /// # Errors
+    ///
+    /// 설정된 GeoIP 데이터베이스를 열 수 없으면 `LogPipelineError::Config`를 반환합니다.
+    pub fn from_config(config: &EnrichmentConfig) -> Result<Self, LogPipelineError> {
+        let path = |p: &str| (!p.is_empty()).then(|| Path::new(p).to_owned());
+        let country_db = path(&config.geoip_country_db);
+        let asn_db = path(&config.geoip_asn_db);
+        let geoip = GeoIpLookup::open(country_db.as_deref(), asn_db.as_deref())?;
+        let reverse_dns = config.reverse_dns.then(|| {
+            Arc::new(
+                ReverseDnsResolver::new(
+                    Duration::from_millis(config.dns_timeout_ms),
+                    config.dns_cache_size,
+                )
+                .with_ttl(
+                    Duration::from_secs(config.dns_cache_ttl_secs),
+                    Duration::from_secs(config.dns_negative_cache_ttl_secs),
+                )
+                .with_max_concurrent(config.dns_max_concurrent_lookups),
+            )
+        });
+        Ok(Self { geoip, reverse_dns })
+    }
+
+    /// 조회할 수 있는 백엔드가 하나라도 있는지 여부
+    pub fn is_enabled(&self) -> bool {
+        self.geoip.is_enabled() || self.reverse_dns.is_some()
+    }
+
+    /// 주소 하나를 보강합니다.
+    pub async fn enrich(&self, ip: IpAddr) -> IpEnrichment {
+        let mut enrichment = self.lookup_geoip(ip);
+        if let Some(resolver) = &self.reverse_dns {
+            enrichment.hostname = resolver.resolve(ip).await;
+        }
+        enrichment
+    }
+
+    /// GeoIP 항목만 조회합니다 (네트워크 대역 등 역방향 DNS가 의미 없는 경우).
+    pub fn lookup_geoip(&self, ip: IpAddr) -> IpEnrichment {
+        let mut enrichment = IpEnrichment::default();
+        self.geoip.lookup(ip, &mut enrichment);
+        enrichment
+    }
+
+    /// 기다리지 않고 주소 하나를 보강합니다.
+    ///
+    /// 역방향 DNS는 캐시된 이름만 사용합니다 ([`ReverseDnsResolver::resolve_cached`]).
+    pub fn enrich_cached(&self, ip: IpAddr) -> IpEnrichment {
+        let mut enrichment = self.lookup_geoip(ip);
+        if let Some(resolver) = &self.reverse_dns {
+            enrichment.hostname = resolver.resolve_cached(ip);
+        }
+        enrichment
+    }
+}
+
+/// 로그 보강 단계
+///
+/// 파싱된 `LogEntry`에 필드를 덧붙입니다. 보강 실패는 로그 처리를 막지 않아야 하므로
+/// 에러를 반환하지 않습니다. `Vec<Arc<dyn LogEnricher>>`으로 관리하기 위해
+/// [`BoxFuture`]를 반환합니다.
+pub trait LogEnricher: Send + Sync {
+    /// 보강 단계 이름 (로그 라벨)
+    fn name(&self) -> &str;
+
+    /// 로그 엔트리 하나를 보강합니다.
+    fn enrich<'a>(&'a self, entry: &'a mut LogEntry) -> BoxFuture<'a, ()>;
+}
+
+/// IP 필드 보강 단계
+///
+/// 설정된 필드의 값이 IP 주소이면 GeoIP 국가/ASN과 캐시된 역방향 DNS 이름을
+/// `<필드>_country`, `<필드>_asn`, `<필드>_as_org`, `<필드>_hostname` 필드로 추가합니다.
+pub struct IpFieldEnricher {
+    enricher: IpEnricher,
+    fields: Vec<String>,
+}
+
+impl IpFieldEnricher {
+    /// 보강기와 대상 필드 이름(대소문자 무시)으로 생성합니다.
+    pub fn new(enricher: IpEnricher, fields: Vec<String>) -> Self {
+        Self { enricher, fields }
+    }
+
+    fn enrich_fields(&self, entry: &mut LogEntry) {
+        let mut added = Vec::new();
+        for (name, value) in &entry.fields {
+            if !self.fields.iter().any(|f| f.eq_ignore_ascii_case(name)) {
+                continue;
+            }
+            let Ok(ip) = value.trim().parse::<IpAddr>() else {
+                continue;
+            };
+            added.extend(self.enricher.enrich_cached(ip).to_fields(name));
+        }
+        entry.fields.extend(added);
+    }
+}
+
+impl LogEnricher for IpFieldEnricher {
+    fn name(&self) -> &str {
+        "ip_fields"
+    }
+
+    fn enrich<'a>(&'a self, entry: &'a mut LogEntry) -> BoxFuture<'a, ()> {
+        Box::pin(async move { self.enrich_fields(entry) })
+    }
+}
+
+/// 파싱과 규칙 매칭 사이에서 실행하는 보강 단계 모음
+///
+/// 복제 비용이 작아 처리 태스크마다 복제해 사용합니다.
+#[derive(Clone, Default)]
+pub struct EnrichmentStage {
+    enrichers: Vec<Arc<dyn LogEnricher>>,
+}
+
+impl EnrichmentStage {
+    /// 설정에서 보강 단계를 생성합니다.
+    ///
+    /// `enrich_logs = true`이면 [`IpFieldEnricher`]를 추가합니다. GeoIP 데이터베이스도
+    /// 역방향 DNS도 설정되지 않았으면 경고를 남기고 건너뜁니다.
+    ///
+    /// # Errors
+    ///
+    /// 설정된 GeoIP 데이터베이스를 열 수 없으면 `LogPipelineError::Config`를 반환합니다.
+    pub fn from_config(config: &EnrichmentConfig) -> Result<Self, LogPipelineError> {
+        let mut stage = Self::default();
+        if config.enrich_logs {
+            let enricher = IpEnricher::from_config(config)?;
+            if enricher.is_enabled() {
+                stage = stage.with_enricher(Arc::new(IpFieldEnricher::new(
+                    enricher,
+                    config.ip_fields.clone(),
+                )));
+            } else {
+                tracing::warn!(
+                    "enrich_logs is enabled but no GeoIP database or reverse DNS is configured, skipping"
+                );
+            }
+        }
+        Ok(stage)
+    }
+
+    /// 보강 단계를 뒤에 추가합니다.
+    pub fn with_enricher(mut self, enricher: Arc<dyn LogEnricher>) -> Self {
+        self.enrichers.push(enricher);
+        self
+    }
+
+    /// 보강 단계 이름 목록
+    pub fn names(&self) -> Vec<&str> {
+        self.enrichers.iter().map(|e| e.name()).collect()
+    }
+
+    /// 보강 단계가 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.enrichers.is_empty()
+    }
+
+    /// 모든 보강 단계를 순서대로 적용합니다.
+    pub async fn apply(&self, entry: &mut LogEntry) {
+        for enricher in &self.enrichers {
+            enricher.enrich(entry).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_hostname_strips_root_and_rejects_numeric() {
+        assert_eq!(
+            normalize_hostname("host.example.com."),
+            Some("host.example.com".to_owned())
+        );
+        assert_eq!(normalize_hostname("203.0.113.7"), None);
+        assert_eq!(normalize_hostname("2001:db8::1"), None);
+        assert_eq!(normalize_hostname(""), None);
+    }
+
+    #[test]
+    fn reverse_dns_cache_evicts_oldest() {
+        let resolver = ReverseDnsResolver::new(Duration::from_millis(100), 2);
+        let a: IpAddr = "192.0.2.1".parse().unwrap();
+        let b: IpAddr = "192.0.2.2".parse().unwrap();
+        let c: IpAddr = "192.0.2.3".parse().unwrap();
+
+        resolver.insert(a, Some("a.example".to_owned()));
+        resolver.insert(b, None);
+        assert_eq!(resolver.cached(a), Some(Some("a.example".to_owned())));
+        assert_eq!(resolver.cached(b), Some(None));
+
+        resolver.insert(c, Some("c.example".to_owned()));
+        assert_eq!(resolver.cached(a), None);
+        assert_eq!(resolver.cached(c), Some(Some("c.example".to_owned())));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reverse_dns_cache_entries_expire() {
+        let resolver = ReverseDnsResolver::new(Duration::from_millis(100), 8)
+            .with_ttl(Duration::from_secs(300), Duration::from_secs(30));
+        let found: IpAddr = "192.0.2.1".parse().unwrap();
+        let missing: IpAddr = "192.0.2.2".parse().unwrap();
+        resolver.insert(found, Some("a.example".to_owned()));
+        resolver.insert(missing, None);
+
+        tokio::time::advance(Duration::from_secs(31)).await;
+        assert_eq!(resolver.cached(missing), None);
+        assert_eq!(resolver.cached(found), Some(Some("a.example".to_owned())));
+
+        tokio::time::advance(Duration::from_secs(300)).await;
+        assert_eq!(resolver.cached(found), None);
+    }
+
+    #[test]
+    fn reverse_dns_zero_negative_ttl_skips_failures() {
+        let resolver = ReverseDnsResolver::new(Duration::from_millis(100), 8)
+            .with_ttl(Duration::from_secs(300), Duration::ZERO);
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        resolver.insert(ip, None);
+        assert_eq!(resolver.cached(ip), None);
+    }
+
+    #[tokio::test]
+    async fn reverse_dns_limits_concurrent_lookups() {
+        let resolver = ReverseDnsResolver::new(Duration::from_millis(50), 8).with_max_concurrent(1);
+        let ip: IpAddr = "192.0.2.20".parse().unwrap();
+        let _busy = Arc::clone(&resolver.lookups).acquire_owned().await.unwrap();
+
+        assert_eq!(resolver.resolve(ip).await, None);
+        // 차례를 얻지 못한 조회는 캐시되지 않습니다.
+        assert_eq!(resolver.cached(ip), None);
+    }
+
+    #[tokio::test]
+    async fn reverse_dns_uses_cache() {
+        let resolver = ReverseDnsResolver::new(Duration::from_millis(100), 8);
+        let ip: IpAddr = "192.0.2.10".parse().unwrap();
+        resolver.insert(ip, Some("cached.example".to_owned()));
+
+        assert_eq!(
+            resolver.resolve(ip).await,
+            Some("cached.example".to_owned())
+        );
+    }
+
+    #[test]
+    fn from_config_without_databases() {
+        let config = EnrichmentConfig {
+            reverse_dns: false,
+            ..EnrichmentConfig::default()
+        };
+        let enricher = IpEnricher::from_config(&config).unwrap();
+        assert!(!enricher.is_enabled());
+        assert!(
+            enricher
+                .lookup_geoip("192.0.2.1".parse().unwrap())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn from_config_rejects_unreadable_database() {
+        let config = EnrichmentConfig {
+            geoip_country_db: "/nonexistent/GeoLite2-Country.mmdb".to_owned(),
+            ..EnrichmentConfig::default()
+        };
+        let err = IpEnricher::from_config(&config).err().unwrap();
+        assert!(err.to_string().contains("geoip_country_db"));
+    }
+
+    fn entry(fields: &[(&str, &str)]) -> LogEntry {
+        LogEntry {
+            source: "test".to_owned(),
+            timestamp: std::time::SystemTime::now(),
+            hostname: "host".to_owned(),
+            process: "sshd".to_owned(),
+            message: "msg".to_owned(),
+            severity: ironpost_core::types::Severity::Info,
+            fields: fields
+                .iter()
+                .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+                .collect(),
+        }
+    }
+
+    fn dns_only_enricher(resolver: &Arc<ReverseDnsResolver>) -> IpEnricher {
+        IpEnricher {
+            geoip: GeoIpLookup::open(None, None).unwrap(),
+            reverse_dns: Some(Arc::clone(resolver)),
+        }
+    }
+
+    #[test]
+    fn enrichment_to_fields_uses_field_prefix() {
+        let enrichment = IpEnrichment {
+            country: Some("NL".to_owned()),
+            asn: Some(64500),
+            hostname: Some("edge.example".to_owned()),
+            ..IpEnrichment::default()
+        };
+        assert_eq!(
+            enrichment.to_fields("src_ip"),
+            vec![
+                ("src_ip_country".to_owned(), "NL".to_owned()),
+                ("src_ip_asn".to_owned(), "64500".to_owned()),
+                ("src_ip_hostname".to_owned(), "edge.example".to_owned()),
+            ]
+        );
+        assert!(IpEnrichment::default().to_fields("src_ip").is_empty());
+    }
+
+    #[tokio::test]
+    async fn ip_field_enricher_adds_cached_hostname() {
+        let resolver = Arc::new(ReverseDnsResolver::new(Duration::from_millis(100), 8));
+        resolver.insert("192.0.2.1".parse().unwrap(), Some("a.example".to_owned()));
+        resolver.insert("192.0.2.2".parse().unwrap(), Some("b.example".to_owned()));
+        let stage = EnrichmentStage::default().with_enricher(Arc::new(IpFieldEnricher::new(
+            dns_only_enricher(&resolver),
+            vec!["src_ip".to_owned()],
+        )));
+        assert_eq!(stage.names(), vec!["ip_fields"]);
+
+        let mut log = entry(&[
+            ("SRC_IP", "192.0.2.1"),
+            ("dst_ip", "192.0.2.2"),
+            ("user", "root"),
+        ]);
+        stage.apply(&mut log).await;
+        assert_eq!(log.fields.len(), 4);
+        assert!(
+            log.fields
+                .contains(&("SRC_IP_hostname".to_owned(), "a.example".to_owned()))
+        );
+
+        // IP가 아닌 값은 건너뜀
+        let mut log = entry(&[("src_ip", "not-an-ip")]);
+        stage.apply(&mut log).await;
+        assert_eq!(log.fields.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn resolve_cached_does_not_wait_for_lookup() {
+        let resolver = Arc::new(ReverseDnsResolver::new(Duration::from_millis(50), 8));
+        let ip: IpAddr = "192.0.2.20".parse().unwrap();
+
+        assert_eq!(resolver.resolve_cached(ip), None);
+        // 같은 주소는 한 번만 조회
+        assert_eq!(resolver.resolve_cached(ip), None);
+        assert!(resolver.cache.lock().unwrap().pending.contains(&ip));
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while resolver.cached(ip).is_none() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn stage_from_config_skips_when_disabled_or_without_backends() {
+        let mut config = EnrichmentConfig::default();
+        assert!(EnrichmentStage::from_config(&config).unwrap().is_empty());
+
+        config.enrich_logs = true;
+        config.reverse_dns = false;
+        assert!(EnrichmentStage::from_config(&config).unwrap().is_empty());
+
+        config.reverse_dns = true;
+        assert!(!EnrichmentStage::from_config(&config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn enrichment_serializes_only_known_fields() {
+        let enrichment = IpEnrichment {
+            country: Some("KR".to_owned()),
+            asn: Some(4766),
+            ..IpEnrichment::default()
+        };
+        let json = serde_json::to_value(&enrichment).unwrap();
+        assert_eq!(json, serde_json::json!({ "country": "KR", "asn": 4766 }));
+    }
+}
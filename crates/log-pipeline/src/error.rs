@@ -76,6 +76,24 @@ pub enum LogPipelineError {
         reason: String,
     },
 
+    /// 알림 싱크 에러
+    #[error("alert sink error: {name}: {reason}")]
+    Sink {
+        /// 싱크 이름
+        name: String,
+        /// 에러 사유
+        reason: String,
+    },
+
+    /// 출력(Elasticsearch 등) 에러
+    #[error("output error: {name}: {reason}")]
+    Output {
+        /// 출력 이름
+        name: String,
+        /// 에러 사유
+        reason: String,
+    },
+
     /// 채널 통신 에러
     #[error("channel error: {0}")]
     Channel(String),
@@ -5,8 +5,12 @@
 //! - [`collector`]: 다양한 소스에서 원시 로그 수집 (파일, syslog UDP/TCP, eBPF 이벤트)
 //! - [`parser`]: Syslog RFC 5424, JSON 등 형식별 파서 및 자동 감지 라우터
 //! - [`rule`]: YAML 기반 탐지 규칙 엔진 (간소화된 Sigma 스타일)
-//! - [`buffer`]: 인메모리 로그 버퍼링 및 배치 플러시
+//! - [`buffer`]: 인메모리 로그 버퍼링, 배치 플러시, 디스크 오버플로
 //! - [`alert`]: 알림 생성, 중복 제거, 속도 제한
+//! - [`enrich`]: IP 주소 보강 (GeoIP 국가/ASN, 역방향 DNS) 및 파이프라인 보강 단계
+//! - [`transform`]: 필드 변환 (이름 변경, 정규식 추출, 민감 정보 가림/해시)
+//! - [`sink`]: 알림 외부 전달 (HTTP 웹훅, Slack/Discord/Teams, 이메일, JSONL 파일)
+//! - [`output`]: 로그/알림 출력 (Elasticsearch/OpenSearch bulk, Kafka 프로듀서, 내장 로그 저장소)
 //! - [`pipeline`]: 전체 파이프라인 오케스트레이션 (Pipeline trait 구현)
 //! - [`config`]: 파이프라인 설정 (core 설정 확장)
 //! - [`error`]: 도메인 에러 타입
@@ -22,8 +26,12 @@
 pub mod alert;
 pub mod buffer;
 pub mod config;
+pub mod enrich;
 pub mod error;
+pub mod output;
 pub mod pipeline;
+pub mod sink;
+pub mod transform;
 
 pub mod collector;
 pub mod parser;
@@ -52,5 +60,22 @@ pub use collector::{CollectorSet, RawLog};
 // 알림
 pub use alert::AlertGenerator;
 
+// 알림 싱크
+pub use sink::{
+    AlertAdmission, AlertSink, AlertSinkSet, ChatSink, EmailSink, FileSink, SinkAlert, SinkDelivery,
+    SinkDispatcher, SinkFilter, WebhookSink,
+};
+
+// 출력
+#[cfg(feature = "kafka")]
+pub use output::KafkaOutput;
+pub use output::{ElasticsearchOutput, LogQuery, LogStore, OutputHandle, OutputSet};
+
 // 버퍼
-pub use buffer::LogBuffer;
+pub use buffer::{DiskSpill, LogBuffer};
+
+// IP 보강
+pub use enrich::{EnrichmentStage, IpEnricher, IpEnrichment, IpFieldEnricher, LogEnricher};
+
+// 필드 변환
+pub use transform::TransformChain;
@@ -0,0 +1,654 @@
+//! Elasticsearch/OpenSearch bulk 출력
+//!
+//! 문서를 `batch_size`개 또는 `flush_interval_ms`마다 `_bulk` 요청 하나로 묶어 색인합니다.
+//! 인덱스 이름은 [`IndexTemplate`]으로 문서 시각(UTC)과 소스에서 만듭니다.
+//!
+//! # 재시도
+//! - 요청 전체 실패(연결 실패, 타임아웃, `429`, `5xx`)는 배치 전체를 다시 보냅니다.
+//! - 응답의 개별 항목이 `429`/`5xx`이면 해당 항목만 다시 보냅니다.
+//! - 대기 시간은 `retry_backoff_ms`부터 2배씩(최대 30초) 늘어나며, 그동안 큐가 가득 차면
+//!   새 문서는 버려집니다. 그 밖의 실패(매핑 오류 `400` 등)는 재시도하지 않습니다.
+//!
+//! 알림은 알림 ID를 문서 ID로 쓰므로 재시도로 중복 색인되지 않습니다 (`409`는 성공으로 처리).
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio::time::{Instant, MissedTickBehavior};
+use tokio_util::sync::CancellationToken;
+
+use ironpost_core::config::{ELASTICSEARCH_INDEX_DATE_SPECIFIERS, ElasticsearchConfig};
+
+use super::{OutputDocument, OutputHandle, record_outcome};
+use crate::error::LogPipelineError;
+use crate::sink::retry::{AttemptError, RetryPolicy};
+
+/// 출력 이름 (로그/메트릭 라벨)
+pub(crate) const OUTPUT_NAME: &str = "elasticsearch";
+
+/// 종료 시 남은 문서 전송 제한 시간
+const FINAL_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 인덱스 템플릿 구성 요소
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum IndexSegment {
+    Literal(String),
+    /// chrono 날짜 지정자 (`%Y` 등)
+    Date(String),
+    Source,
+}
+
+/// 인덱스 이름 템플릿
+///
+/// `%Y`, `%m`, `%d` 등의 날짜 지정자는 문서 시각(UTC)으로, `{source}`는 로그의 수집 소스
+/// 또는 알림 생성 모듈로 치환합니다. `{source}` 값은 소문자로 바꾸고 인덱스 이름에 쓸 수
+/// 없는 문자를 `-`로 바꿉니다.
+///
+/// ```
+/// # use ironpost_log_pipeline::output::IndexTemplate;
+/// let template = IndexTemplate::parse("logs-{source}-%Y.%m.%d").unwrap();
+/// let index = template.render(std::time::SystemTime::UNIX_EPOCH, "Syslog/UDP");
+/// assert_eq!(index, "logs-syslog-udp-1970.01.01");
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexTemplate {
+    segments: Vec<IndexSegment>,
+}
+
+impl IndexTemplate {
+    /// 템플릿을 파싱합니다.
+    ///
+    /// # Errors
+    /// 빈 템플릿 또는 지원하지 않는 날짜 지정자
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+        while let Some(c) = rest.chars().next() {
+            let (segment, len) = if rest.starts_with("{source}") {
+                (IndexSegment::Source, "{source}".len())
+            } else if c == '%' {
+                match rest[1..].chars().next() {
+                    Some(spec) if ELASTICSEARCH_INDEX_DATE_SPECIFIERS.contains(&spec) => {
+                        (IndexSegment::Date(format!("%{spec}")), 2)
+                    }
+                    _ => {
+                        return Err(format!(
+                            "unsupported date specifier in index template '{template}'"
+                        ));
+                    }
+                }
+            } else {
+                literal.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            };
+            if !literal.is_empty() {
+                segments.push(IndexSegment::Literal(std::mem::take(&mut literal)));
+            }
+            segments.push(segment);
+            rest = &rest[len..];
+        }
+        if !literal.is_empty() {
+            segments.push(IndexSegment::Literal(literal));
+        }
+        if segments.is_empty() {
+            return Err("index template must not be empty".to_owned());
+        }
+        Ok(Self { segments })
+    }
+
+    /// 문서 시각과 소스로 인덱스 이름을 만듭니다.
+    pub fn render(&self, time: SystemTime, source: &str) -> String {
+        let time = DateTime::<Utc>::from(time);
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                IndexSegment::Literal(text) => out.push_str(text),
+                IndexSegment::Date(spec) => {
+                    let _ = write!(out, "{}", time.format(spec));
+                }
+                IndexSegment::Source => push_index_safe(&mut out, source),
+            }
+        }
+        out
+    }
+}
+
+/// 요청 인증 방식
+enum Auth {
+    None,
+    Basic { username: String, password: String },
+    ApiKey(String),
+}
+
+/// bulk 응답 항목 처리 결과
+#[derive(Debug, PartialEq, Eq)]
+enum ItemResult {
+    Indexed,
+    Retry,
+    Failed(String),
+}
+
+/// Elasticsearch/OpenSearch bulk 출력
+pub struct ElasticsearchOutput {
+    client: reqwest::Client,
+    bulk_url: String,
+    auth: Auth,
+    log_index: Option<IndexTemplate>,
+    alert_index: Option<IndexTemplate>,
+    batch_size: usize,
+    flush_interval: Duration,
+    queue_size: usize,
+    retry: RetryPolicy,
+}
+
+impl ElasticsearchOutput {
+    /// 출력 설정에서 생성합니다.
+    ///
+    /// # Errors
+    /// 잘못된 인덱스 템플릿 또는 HTTP 클라이언트 생성 실패
+    pub fn from_config(config: &ElasticsearchConfig) -> Result<Self, LogPipelineError> {
+        let invalid = |reason: String| LogPipelineError::Output {
+            name: OUTPUT_NAME.to_owned(),
+            reason,
+        };
+        let index = |enabled: bool, field: &str, template: &str| {
+            if !enabled {
+                return Ok(None);
+            }
+            IndexTemplate::parse(template)
+                .map(Some)
+                .map_err(|e| invalid(format!("{field}: {e}")))
+        };
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| invalid(format!("failed to build HTTP client: {e}")))?;
+        let auth = if !config.api_key.is_empty() {
+            Auth::ApiKey(config.api_key.clone())
+        } else if !config.username.is_empty() {
+            Auth::Basic {
+                username: config.username.clone(),
+                password: config.password.clone(),
+            }
+        } else {
+            Auth::None
+        };
+
+        Ok(Self {
+            client,
+            bulk_url: format!("{}/_bulk", config.url.trim_end_matches('/')),
+            auth,
+            log_index: index(config.index_logs, "log_index", &config.log_index)?,
+            alert_index: index(config.index_alerts, "alert_index", &config.alert_index)?,
+            batch_size: config.batch_size.max(1),
+            flush_interval: Duration::from_millis(config.flush_interval_ms.max(1)),
+            queue_size: config.queue_size.max(1),
+            retry: RetryPolicy {
+                max_retries: config.max_retries,
+                backoff: Duration::from_millis(config.retry_backoff_ms),
+            },
+        })
+    }
+
+    /// 문서의 bulk 항목(액션 줄과 문서 줄)을 만듭니다. 색인하지 않는 종류면 `None`입니다.
+    pub fn bulk_item(&self, document: &OutputDocument) -> Option<String> {
+        let (action, source) = match document {
+            OutputDocument::Log(entry) => {
+                let index = self
+                    .log_index
+                    .as_ref()?
+                    .render(entry.timestamp, &entry.source);
+                (
+                    serde_json::json!({ "create": { "_index": index } }),
+                    document.to_json(),
+                )
+            }
+            OutputDocument::Alert(event) => {
+                let index = self
+                    .alert_index
+                    .as_ref()?
+                    .render(event.alert.created_at, &event.metadata.source_module);
+                (
+                    serde_json::json!({ "create": { "_index": index, "_id": event.alert.id } }),
+                    document.to_json(),
+                )
+            }
+        };
+        Some(format!("{action}\n{source}\n"))
+    }
+
+    /// 출력 태스크를 spawn하고 문서를 넣을 핸들을 반환합니다.
+    ///
+    /// 태스크는 `cancel`이 취소되면 큐와 배치에 남은 문서를 최대 5초 동안 전송하고 종료합니다.
+    pub fn spawn(
+        self: &Arc<Self>,
+        cancel: CancellationToken,
+    ) -> (OutputHandle, tokio::task::JoinHandle<()>) {
+        let (tx, rx) = mpsc::channel(self.queue_size);
+        let handle = OutputHandle::new(
+            OUTPUT_NAME,
+            self.log_index.is_some(),
+            self.alert_index.is_some(),
+            tx,
+        );
+        (handle, tokio::spawn(Arc::clone(self).run(rx, cancel)))
+    }
+
+    /// 출력 루프: 문서를 배치로 모아 크기 또는 주기 조건에 따라 전송합니다.
+    async fn run(
+        self: Arc<Self>,
+        mut rx: mpsc::Receiver<OutputDocument>,
+        cancel: CancellationToken,
+    ) {
+        let mut batch: Vec<String> = Vec::with_capacity(self.batch_size);
+        let mut timer =
+            tokio::time::interval_at(Instant::now() + self.flush_interval, self.flush_interval);
+        timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            let flush = tokio::select! {
+                _ = cancel.cancelled() => break,
+                document = rx.recv() => match document {
+                    Some(document) => {
+                        batch.extend(self.bulk_item(&document));
+                        batch.len() >= self.batch_size
+                    }
+                    None => break,
+                },
+                _ = timer.tick() => !batch.is_empty(),
+            };
+            if flush {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = self.send_batch(&mut batch) => {}
+                }
+                timer.reset();
+            }
+        }
+
+        while let Ok(document) = rx.try_recv() {
+            batch.extend(self.bulk_item(&document));
+        }
+        if batch.is_empty() {
+            return;
+        }
+        let flush_all = async {
+            while !batch.is_empty() {
+                let mut chunk: Vec<String> =
+                    batch.drain(..batch.len().min(self.batch_size)).collect();
+                self.send_batch(&mut chunk).await;
+            }
+        };
+        if tokio::time::timeout(FINAL_FLUSH_TIMEOUT, flush_all)
+            .await
+            .is_err()
+        {
+            tracing::warn!(
+                output = OUTPUT_NAME,
+                pending = batch.len(),
+                "timed out indexing remaining documents on shutdown"
+            );
+            record_outcome(OUTPUT_NAME, "failed", batch.len() as u64);
+        }
+    }
+
+    /// 배치를 색인합니다. 재시도할 항목만 남겨 다시 보내며, 끝나면 `items`는 비어 있습니다.
+    async fn send_batch(&self, items: &mut Vec<String>) {
+        let mut attempts: u32 = 0;
+        while !items.is_empty() {
+            attempts += 1;
+            let (retryable, reason) = match self.bulk(items).await {
+                Ok(results) => {
+                    let mut results = results.into_iter();
+                    let mut indexed = 0u64;
+                    let mut failed = 0u64;
+                    let mut first_error = None;
+                    items.retain(|_| {
+                        match results
+                            .next()
+                            .unwrap_or_else(|| ItemResult::Failed("missing from response".into()))
+                        {
+                            ItemResult::Indexed => indexed += 1,
+                            ItemResult::Retry => return true,
+                            ItemResult::Failed(reason) => {
+                                failed += 1;
+                                first_error.get_or_insert(reason);
+                            }
+                        }
+                        false
+                    });
+                    record_outcome(OUTPUT_NAME, "indexed", indexed);
+                    record_outcome(OUTPUT_NAME, "failed", failed);
+                    if let Some(reason) = first_error {
+                        tracing::warn!(
+                            output = OUTPUT_NAME,
+                            documents = failed,
+                            reason = %reason,
+                            "documents rejected by bulk request"
+                        );
+                    }
+                    if items.is_empty() {
+                        return;
+                    }
+                    (
+                        true,
+                        format!("{} document(s) rejected with retryable status", items.len()),
+                    )
+                }
+                Err(failure) => failure,
+            };
+
+            if !retryable || attempts > self.retry.max_retries {
+                tracing::warn!(
+                    output = OUTPUT_NAME,
+                    documents = items.len(),
+                    attempts,
+                    reason = %reason,
+                    "failed to index documents"
+                );
+                record_outcome(OUTPUT_NAME, "failed", items.len() as u64);
+                items.clear();
+                return;
+            }
+            let backoff = self.retry.delay(attempts);
+            tracing::debug!(
+                output = OUTPUT_NAME,
+                documents = items.len(),
+                attempt = attempts,
+                backoff_ms = backoff.as_millis() as u64,
+                reason = %reason,
+                "bulk request failed, retrying"
+            );
+            tokio::time::sleep(backoff).await;
+        }
+    }
+
+    /// bulk 요청 한 번을 보내고 항목별 결과를 반환합니다.
+    async fn bulk(&self, items: &[String]) -> Result<Vec<ItemResult>, AttemptError> {
+        let mut request = self
+            .client
+            .post(&self.bulk_url)
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .header(USER_AGENT, concat!("ironpost/", env!("CARGO_PKG_VERSION")))
+            .body(items.concat());
+        request = match &self.auth {
+            Auth::None => request,
+            Auth::Basic { username, password } => request.basic_auth(username, Some(password)),
+            Auth::ApiKey(key) => request.header(AUTHORIZATION, format!("ApiKey {key}")),
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| (true, format!("request failed: {e}")))?;
+        let status = response.status();
+        if !status.is_success() {
+            let retryable =
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+            return Err((retryable, format!("unexpected status {status}")));
+        }
+
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| (true, format!("failed to read bulk response: {e}")))?;
+        let body: Value = serde_json::from_slice(&body)
+            .map_err(|e| (false, format!("invalid bulk response: {e}")))?;
+        if body.get("errors").and_then(Value::as_bool) == Some(false) {
+            return Ok(items.iter().map(|_| ItemResult::Indexed).collect());
+        }
+        let results = body
+            .get("items")
+            .and_then(Value::as_array)
+            .ok_or_else(|| (false, "bulk response has no items".to_owned()))?;
+        Ok(results.iter().map(item_result).collect())
+    }
+}
+
+/// bulk 응답 항목 하나를 분류합니다.
+fn item_result(item: &Value) -> ItemResult {
+    let Some(result) = item.as_object().and_then(|item| item.values().next()) else {
+        return ItemResult::Failed("malformed response item".to_owned());
+    };
+    match result.get("status").and_then(Value::as_u64).unwrap_or(0) {
+        200..=299 | 409 => ItemResult::Indexed,
+        429 | 500..=599 => ItemResult::Retry,
+        status => {
+            let error = &result["error"];
+            let reason = error["reason"]
+                .as_str()
+                .or_else(|| error["type"].as_str())
+                .unwrap_or("unknown error");
+            ItemResult::Failed(format!("status {status}: {reason}"))
+        }
+    }
+}
+
+/// 값을 소문자로 바꾸고 인덱스 이름에 쓸 수 없는 문자를 `-`로 바꿔 덧붙입니다.
+fn push_index_safe(out: &mut String, value: &str) {
+    if value.is_empty() {
+        out.push_str("unknown");
+        return;
+    }
+    out.extend(value.chars().map(|c| match c.to_ascii_lowercase() {
+        c @ ('a'..='z' | '0'..='9' | '.' | '_' | '-') => c,
+        _ => '-',
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use ironpost_core::event::AlertEvent;
+    use ironpost_core::types::{Alert, LogEntry, Severity};
+
+    use super::*;
+    use crate::sink::http::test_server::{serve, serve_responses};
+
+    fn output_config(url: &str) -> ElasticsearchConfig {
+        ElasticsearchConfig {
+            enabled: true,
+            url: url.to_owned(),
+            api_key: "secret-key".to_owned(),
+            max_retries: 2,
+            retry_backoff_ms: 1,
+            ..Default::default()
+        }
+    }
+
+    fn log(message: &str) -> OutputDocument {
+        OutputDocument::Log(LogEntry {
+            source: "syslog_udp".to_owned(),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_millis(1500),
+            hostname: "web-01".to_owned(),
+            process: "sshd".to_owned(),
+            message: message.to_owned(),
+            severity: Severity::Info,
+            fields: vec![("user".to_owned(), "root".to_owned())],
+        })
+    }
+
+    fn alert() -> OutputDocument {
+        OutputDocument::Alert(AlertEvent::new(
+            Alert {
+                id: "alert-1".to_owned(),
+                title: "SSH brute force".to_owned(),
+                description: String::new(),
+                severity: Severity::High,
+                rule_name: "ssh_brute_force".to_owned(),
+                source_ip: Some([10, 0, 0, 5].into()),
+                target_ip: None,
+                created_at: SystemTime::UNIX_EPOCH,
+            },
+            Severity::High,
+        ))
+    }
+
+    fn bulk_response(statuses: &[u16]) -> (u16, String) {
+        let items: Vec<Value> = statuses
+            .iter()
+            .map(|status| {
+                serde_json::json!({ "create": {
+                    "status": status,
+                    "error": { "type": "mapper_parsing_exception", "reason": "bad field" },
+                } })
+            })
+            .collect();
+        let body = serde_json::json!({ "errors": true, "items": items });
+        (200, body.to_string())
+    }
+
+    /// bulk 요청 본문의 문서 줄 중 `message` 값들
+    fn messages(body: &str) -> Vec<String> {
+        body.lines()
+            .skip(1)
+            .step_by(2)
+            .map(|line| {
+                let document: Value = serde_json::from_str(line).unwrap();
+                document["message"].as_str().unwrap_or_default().to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn index_template_renders_date_and_source() {
+        let template = IndexTemplate::parse("ironpost-{source}-%Y.%m.%d-%H").unwrap();
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(86_400 * 365 + 3600 * 7);
+        assert_eq!(
+            template.render(time, "Kafka:Topic"),
+            "ironpost-kafka-topic-1971.01.01-07"
+        );
+        assert_eq!(template.render(time, ""), "ironpost-unknown-1971.01.01-07");
+
+        assert!(IndexTemplate::parse("logs-%s").is_err());
+        assert!(IndexTemplate::parse("logs-%").is_err());
+        assert!(IndexTemplate::parse("").is_err());
+    }
+
+    #[test]
+    fn bulk_items_use_create_with_alert_ids() {
+        let output =
+            ElasticsearchOutput::from_config(&output_config("http://127.0.0.1:9")).unwrap();
+
+        let item = output.bulk_item(&log("Failed password")).unwrap();
+        let lines: Vec<Value> = item
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines[0]["create"]["_index"], "ironpost-logs-1970.01.01");
+        assert!(lines[0]["create"].get("_id").is_none());
+        assert_eq!(lines[1]["@timestamp"], "1970-01-01T00:00:01.500Z");
+        assert_eq!(lines[1]["fields"]["user"], "root");
+        assert!(item.ends_with('\n'));
+
+        let item = output.bulk_item(&alert()).unwrap();
+        let lines: Vec<Value> = item
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines[0]["create"]["_index"], "ironpost-alerts-1970.01.01");
+        assert_eq!(lines[0]["create"]["_id"], "alert-1");
+        assert_eq!(lines[1]["rule_name"], "ssh_brute_force");
+        assert_eq!(lines[1]["@timestamp"], "1970-01-01T00:00:00.000Z");
+
+        let config = ElasticsearchConfig {
+            index_logs: false,
+            ..output_config("http://127.0.0.1:9")
+        };
+        let output = ElasticsearchOutput::from_config(&config).unwrap();
+        assert!(output.bulk_item(&log("skipped")).is_none());
+    }
+
+    #[tokio::test]
+    async fn retries_only_rejected_items() {
+        let (url, requests) = serve_responses(vec![
+            bulk_response(&[201, 429, 400]),
+            (200, r#"{"errors":false,"items":[]}"#.to_owned()),
+        ])
+        .await;
+        let output = ElasticsearchOutput::from_config(&output_config(&url)).unwrap();
+
+        let mut batch: Vec<String> = ["a", "b", "c"]
+            .iter()
+            .filter_map(|message| output.bulk_item(&log(message)))
+            .collect();
+        output.send_batch(&mut batch).await;
+        assert!(batch.is_empty());
+
+        let requests = requests.lock().await;
+        assert_eq!(requests.len(), 2);
+        let (head, body) = &requests[0];
+        assert!(head.starts_with("POST /hook/_bulk"), "{head}");
+        let head = head.to_ascii_lowercase();
+        assert!(head.contains("authorization: apikey secret-key"), "{head}");
+        assert!(
+            head.contains("content-type: application/x-ndjson"),
+            "{head}"
+        );
+        assert_eq!(messages(body), ["a", "b", "c"]);
+        assert_eq!(messages(&requests[1].1), ["b"]);
+    }
+
+    #[tokio::test]
+    async fn backs_off_on_429_and_gives_up_on_client_errors() {
+        let (url, requests) = serve(vec![429, 503, 200]).await;
+        let output = ElasticsearchOutput::from_config(&output_config(&url)).unwrap();
+        let mut batch = vec![output.bulk_item(&log("a")).unwrap()];
+        output.send_batch(&mut batch).await;
+        // 빈 본문의 200 응답은 해석할 수 없으므로 재시도하지 않음
+        assert_eq!(requests.lock().await.len(), 3);
+
+        let (url, requests) = serve(vec![401]).await;
+        let output = ElasticsearchOutput::from_config(&output_config(&url)).unwrap();
+        let mut batch = vec![output.bulk_item(&log("a")).unwrap()];
+        output.send_batch(&mut batch).await;
+        assert!(batch.is_empty());
+        assert_eq!(requests.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn spawned_output_flushes_by_size_interval_and_shutdown() {
+        let (url, requests) =
+            serve_responses(vec![(200, r#"{"errors":false,"items":[]}"#.to_owned())]).await;
+        let config = ElasticsearchConfig {
+            batch_size: 2,
+            flush_interval_ms: 100,
+            ..output_config(&url)
+        };
+        let output = Arc::new(ElasticsearchOutput::from_config(&config).unwrap());
+        let cancel = CancellationToken::new();
+        let (handle, task) = output.spawn(cancel.clone());
+        assert!(!handle.is_empty());
+
+        for message in ["a", "b", "c"] {
+            let OutputDocument::Log(entry) = log(message) else {
+                unreachable!()
+            };
+            handle.send_log(&entry);
+        }
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        {
+            let requests = requests.lock().await;
+            assert_eq!(requests.len(), 2);
+            assert_eq!(messages(&requests[0].1), ["a", "b"]);
+            assert_eq!(messages(&requests[1].1), ["c"]);
+        }
+
+        let OutputDocument::Alert(event) = alert() else {
+            unreachable!()
+        };
+        handle.send_alert(&event);
+        cancel.cancel();
+        task.await.unwrap();
+        let requests = requests.lock().await;
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].1.contains("\"_id\":\"alert-1\""));
+    }
+}
@@ -0,0 +1,412 @@
+//! Kafka 출력 (프로듀서)
+//!
+//! 알림과, `log_topic`을 지정하면 파싱된 로그까지 JSON 메시지로 Kafka 토픽에 발행합니다.
+//! 메시지 본문은 Elasticsearch 출력과 같은 문서 형식([`OutputDocument::to_json`])이고,
+//! 메시지 키는 `alert_key`/`log_key`로 고릅니다 (예: 규칙 ID별로 같은 파티션에 모음).
+//! `kafka` 기능 플래그로 활성화합니다.
+//!
+//! # 전달 보장
+//! 배치와 재시도는 librdkafka가 맡습니다. `delivery`에 따라 `acks`, 재시도,
+//! 멱등 프로듀서(`enable.idempotence`)를 설정하고, 메시지마다 전달 결과를 기다려
+//! `sent`/`failed` 메트릭으로 집계합니다.
+//!
+//! 전달을 기다리는 메시지 수는 `queue_size`로 제한되며, 이를 넘으면 출력 큐에 쌓이다가
+//! 큐도 가득 차면 새 메시지를 버립니다. 종료 시에는 남은 메시지를 최대 5초 동안 전달합니다.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use rdkafka::ClientConfig;
+use rdkafka::error::KafkaError;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::util::Timeout;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use ironpost_core::config::KafkaOutputConfig;
+
+use super::{OutputDocument, OutputHandle, record_outcome};
+use crate::error::LogPipelineError;
+
+/// 출력 이름 (로그/메트릭 라벨)
+pub(crate) const OUTPUT_NAME: &str = "kafka";
+
+/// 종료 시 남은 메시지 전달 제한 시간
+const FINAL_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 알림 메시지 키
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlertKey {
+    None,
+    RuleId,
+    AlertId,
+    Severity,
+    SourceIp,
+}
+
+/// 로그 메시지 키
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogKey {
+    None,
+    Source,
+    Hostname,
+}
+
+/// 발행할 Kafka 메시지
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KafkaMessage {
+    /// 대상 토픽
+    pub topic: String,
+    /// 메시지 키 (없으면 파티션을 고르게 분배)
+    pub key: Option<String>,
+    /// JSON 본문
+    pub payload: String,
+}
+
+/// Kafka 출력
+pub struct KafkaOutput {
+    producer: FutureProducer,
+    alert_topic: Option<String>,
+    log_topic: Option<String>,
+    alert_key: AlertKey,
+    log_key: LogKey,
+    message_timeout: Duration,
+    queue_size: usize,
+}
+
+impl KafkaOutput {
+    /// 출력 설정에서 생성합니다. 브로커 연결은 백그라운드에서 이루어집니다.
+    ///
+    /// # Errors
+    /// 알 수 없는 키/전달 보장 값 또는 프로듀서 생성 실패 (잘못된 librdkafka 속성 등)
+    pub fn from_config(config: &KafkaOutputConfig) -> Result<Self, LogPipelineError> {
+        let invalid = |reason: String| LogPipelineError::Output {
+            name: OUTPUT_NAME.to_owned(),
+            reason,
+        };
+
+        let alert_key = match config.alert_key.as_str() {
+            "none" => AlertKey::None,
+            "rule_id" => AlertKey::RuleId,
+            "alert_id" => AlertKey::AlertId,
+            "severity" => AlertKey::Severity,
+            "source_ip" => AlertKey::SourceIp,
+            other => return Err(invalid(format!("unknown alert_key '{other}'"))),
+        };
+        let log_key = match config.log_key.as_str() {
+            "none" => LogKey::None,
+            "source" => LogKey::Source,
+            "hostname" => LogKey::Hostname,
+            other => return Err(invalid(format!("unknown log_key '{other}'"))),
+        };
+        let producer = client_config(config)?
+            .create()
+            .map_err(|e| invalid(format!("failed to create producer: {e}")))?;
+
+        Ok(Self {
+            producer,
+            alert_topic: (!config.alert_topic.is_empty()).then(|| config.alert_topic.clone()),
+            log_topic: (!config.log_topic.is_empty()).then(|| config.log_topic.clone()),
+            alert_key,
+            log_key,
+            message_timeout: Duration::from_millis(config.message_timeout_ms.max(1)),
+            queue_size: config.queue_size.max(1),
+        })
+    }
+
+    /// 문서의 Kafka 메시지를 만듭니다. 발행하지 않는 종류면 `None`입니다.
+    pub fn message(&self, document: &OutputDocument) -> Option<KafkaMessage> {
+        let (topic, key) = match document {
+            OutputDocument::Log(entry) => {
+                let key = match self.log_key {
+                    LogKey::None => None,
+                    LogKey::Source => Some(entry.source.clone()),
+                    LogKey::Hostname => Some(entry.hostname.clone()),
+                };
+                (self.log_topic.as_ref()?, key)
+            }
+            OutputDocument::Alert(event) => {
+                let alert = &event.alert;
+                let key = match self.alert_key {
+                    AlertKey::None => None,
+                    AlertKey::RuleId => Some(alert.rule_name.clone()),
+                    AlertKey::AlertId => Some(alert.id.clone()),
+                    AlertKey::Severity => Some(alert.severity.to_string().to_ascii_lowercase()),
+                    AlertKey::SourceIp => alert.source_ip.map(|ip| ip.to_string()),
+                };
+                (self.alert_topic.as_ref()?, key)
+            }
+        };
+        Some(KafkaMessage {
+            topic: topic.clone(),
+            key: key.filter(|key| !key.is_empty()),
+            payload: document.to_json().to_string(),
+        })
+    }
+
+    /// 출력 태스크를 spawn하고 문서를 넣을 핸들을 반환합니다.
+    ///
+    /// 태스크는 `cancel`이 취소되면 큐에 남은 메시지를 발행하고 전달 결과를 최대 5초 동안
+    /// 기다린 뒤 종료합니다.
+    pub fn spawn(
+        self: &Arc<Self>,
+        cancel: CancellationToken,
+    ) -> (OutputHandle, tokio::task::JoinHandle<()>) {
+        let (tx, rx) = mpsc::channel(self.queue_size);
+        let handle = OutputHandle::new(
+            OUTPUT_NAME,
+            self.log_topic.is_some(),
+            self.alert_topic.is_some(),
+            tx,
+        );
+        (handle, tokio::spawn(Arc::clone(self).run(rx, cancel)))
+    }
+
+    /// 출력 루프: 문서를 발행하고 전달 결과를 집계합니다.
+    async fn run(
+        self: Arc<Self>,
+        mut rx: mpsc::Receiver<OutputDocument>,
+        cancel: CancellationToken,
+    ) {
+        let mut in_flight: JoinSet<Result<(), KafkaError>> = JoinSet::new();
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = cancel.cancelled() => break,
+                Some(result) = in_flight.join_next(), if !in_flight.is_empty() => {
+                    record_delivery(result);
+                }
+                document = rx.recv(), if in_flight.len() < self.queue_size => {
+                    match document {
+                        Some(document) => self.produce(&mut in_flight, &document),
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        rx.close();
+        while let Ok(document) = rx.try_recv() {
+            self.produce(&mut in_flight, &document);
+        }
+        let drained = tokio::time::timeout(FINAL_FLUSH_TIMEOUT, async {
+            while let Some(result) = in_flight.join_next().await {
+                record_delivery(result);
+            }
+        })
+        .await;
+        if drained.is_err() {
+            tracing::warn!(
+                output = OUTPUT_NAME,
+                pending = in_flight.len(),
+                "timed out delivering remaining messages on shutdown"
+            );
+            record_outcome(OUTPUT_NAME, "failed", in_flight.len() as u64);
+        }
+    }
+
+    /// 메시지를 발행하고 전달 결과를 기다리는 태스크를 추가합니다.
+    fn produce(
+        self: &Arc<Self>,
+        in_flight: &mut JoinSet<Result<(), KafkaError>>,
+        document: &OutputDocument,
+    ) {
+        let Some(message) = self.message(document) else {
+            return;
+        };
+        let output = Arc::clone(self);
+        in_flight.spawn(async move { output.deliver(message).await });
+    }
+
+    /// 메시지 하나를 발행하고 브로커 응답(또는 `message_timeout_ms` 만료)을 기다립니다.
+    async fn deliver(&self, message: KafkaMessage) -> Result<(), KafkaError> {
+        let mut record = FutureRecord::<str, str>::to(&message.topic).payload(&message.payload);
+        if let Some(key) = &message.key {
+            record = record.key(key.as_str());
+        }
+        self.producer
+            .send(record, Timeout::After(self.message_timeout))
+            .await
+            .map(|_| ())
+            .map_err(|(error, _)| error)
+    }
+}
+
+/// 전달 결과 하나를 메트릭으로 기록합니다.
+fn record_delivery(result: Result<Result<(), KafkaError>, tokio::task::JoinError>) {
+    match result {
+        Ok(Ok(())) => record_outcome(OUTPUT_NAME, "sent", 1),
+        Ok(Err(e)) => {
+            tracing::warn!(output = OUTPUT_NAME, error = %e, "failed to deliver message");
+            record_outcome(OUTPUT_NAME, "failed", 1);
+        }
+        Err(e) => {
+            tracing::warn!(output = OUTPUT_NAME, error = %e, "delivery task failed");
+            record_outcome(OUTPUT_NAME, "failed", 1);
+        }
+    }
+}
+
+/// librdkafka 프로듀서 속성을 만듭니다.
+///
+/// 추가 속성을 먼저 적용한 뒤 출력이 관리하는 속성으로 덮어씁니다.
+fn client_config(config: &KafkaOutputConfig) -> Result<ClientConfig, LogPipelineError> {
+    let mut client = ClientConfig::new();
+    for (key, value) in &config.properties {
+        client.set(key, value);
+    }
+    client
+        .set("bootstrap.servers", config.brokers.join(","))
+        .set("linger.ms", config.linger_ms.to_string())
+        .set("message.timeout.ms", config.message_timeout_ms.to_string());
+    match config.delivery.as_str() {
+        "at_most_once" => client
+            .set("acks", "0")
+            .set("message.send.max.retries", "0")
+            .set("enable.idempotence", "false"),
+        "at_least_once" => client.set("acks", "all").set("enable.idempotence", "false"),
+        "idempotent" => client.set("acks", "all").set("enable.idempotence", "true"),
+        other => {
+            return Err(LogPipelineError::Output {
+                name: OUTPUT_NAME.to_owned(),
+                reason: format!("unknown delivery '{other}'"),
+            });
+        }
+    };
+    Ok(client)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::time::SystemTime;
+
+    use ironpost_core::event::AlertEvent;
+    use ironpost_core::types::{Alert, LogEntry, Severity};
+
+    use super::*;
+
+    fn output_config() -> KafkaOutputConfig {
+        KafkaOutputConfig {
+            enabled: true,
+            brokers: vec!["127.0.0.1:1".to_owned()],
+            log_topic: "ironpost-logs".to_owned(),
+            message_timeout_ms: 100,
+            ..Default::default()
+        }
+    }
+
+    fn log() -> OutputDocument {
+        OutputDocument::Log(LogEntry {
+            source: "syslog_udp".to_owned(),
+            timestamp: SystemTime::UNIX_EPOCH,
+            hostname: "web-01".to_owned(),
+            process: "sshd".to_owned(),
+            message: "Failed password".to_owned(),
+            severity: Severity::Medium,
+            fields: vec![],
+        })
+    }
+
+    fn alert_event() -> AlertEvent {
+        AlertEvent::new(
+            Alert {
+                id: "alert-1".to_owned(),
+                title: "SSH Failure".to_owned(),
+                description: String::new(),
+                severity: Severity::High,
+                rule_name: "ssh_fail".to_owned(),
+                source_ip: Some("10.0.0.5".parse::<IpAddr>().unwrap()),
+                target_ip: None,
+                created_at: SystemTime::UNIX_EPOCH,
+            },
+            Severity::High,
+        )
+    }
+
+    fn alert() -> OutputDocument {
+        OutputDocument::Alert(alert_event())
+    }
+
+    #[test]
+    fn messages_use_configured_topics_and_keys() {
+        let output = KafkaOutput::from_config(&output_config()).unwrap();
+
+        let message = output.message(&alert()).unwrap();
+        assert_eq!(message.topic, "ironpost-alerts");
+        assert_eq!(message.key.as_deref(), Some("ssh_fail"));
+        let payload: serde_json::Value = serde_json::from_str(&message.payload).unwrap();
+        assert_eq!(payload["title"], "SSH Failure");
+        assert_eq!(payload["@timestamp"], "1970-01-01T00:00:00.000Z");
+
+        let message = output.message(&log()).unwrap();
+        assert_eq!(message.topic, "ironpost-logs");
+        assert_eq!(message.key.as_deref(), Some("web-01"));
+
+        for (alert_key, expected) in [
+            ("none", None),
+            ("alert_id", Some("alert-1")),
+            ("severity", Some("high")),
+            ("source_ip", Some("10.0.0.5")),
+        ] {
+            let output = KafkaOutput::from_config(&KafkaOutputConfig {
+                alert_key: alert_key.to_owned(),
+                ..output_config()
+            })
+            .unwrap();
+            assert_eq!(
+                output.message(&alert()).unwrap().key.as_deref(),
+                expected,
+                "{alert_key}"
+            );
+        }
+    }
+
+    #[test]
+    fn logs_are_skipped_without_log_topic() {
+        let output = KafkaOutput::from_config(&KafkaOutputConfig {
+            log_topic: String::new(),
+            ..output_config()
+        })
+        .unwrap();
+        assert!(output.message(&log()).is_none());
+        assert!(output.message(&alert()).is_some());
+    }
+
+    #[test]
+    fn delivery_mode_sets_producer_acks_and_idempotence() {
+        for (delivery, acks, idempotence) in [
+            ("at_most_once", "0", "false"),
+            ("at_least_once", "all", "false"),
+            ("idempotent", "all", "true"),
+        ] {
+            let mut config = output_config();
+            config.delivery = delivery.to_owned();
+            config
+                .properties
+                .insert("compression.type".to_owned(), "lz4".to_owned());
+            let client = client_config(&config).unwrap();
+            assert_eq!(client.get("acks"), Some(acks), "{delivery}");
+            assert_eq!(client.get("enable.idempotence"), Some(idempotence));
+            assert_eq!(client.get("bootstrap.servers"), Some("127.0.0.1:1"));
+            assert_eq!(client.get("compression.type"), Some("lz4"));
+        }
+    }
+
+    #[tokio::test]
+    async fn shutdown_completes_when_broker_is_unreachable() {
+        let output = Arc::new(KafkaOutput::from_config(&output_config()).unwrap());
+        let cancel = CancellationToken::new();
+        let (handle, task) = output.spawn(cancel.clone());
+
+        handle.send_alert(&alert_event());
+        cancel.cancel();
+
+        tokio::time::timeout(Duration::from_secs(3), task)
+            .await
+            .expect("output should stop after message timeout")
+            .unwrap();
+    }
+}
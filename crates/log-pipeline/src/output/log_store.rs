@@ -0,0 +1,722 @@
+//! 내장 로그 저장소 -- 파싱된 로그를 로컬 세그먼트 파일에 저장하고 검색합니다.
+//!
+//! 외부 저장소(Elasticsearch 등) 없이도 최근 로그를 찾아볼 수 있도록, 출력 큐로 받은
+//! 로그를 `dir`의 `segment-<번호>.jsonl` 파일에 한 줄에 하나씩 JSON으로 덧붙입니다.
+//! 쓰기는 배치 단위로 모아 블로킹 스레드에서 수행합니다.
+//!
+//! # 세그먼트와 보존
+//! 세그먼트가 `segment_bytes`를 넘거나 연 지 1시간이 지나면 새 세그먼트를 엽니다.
+//! 재시작하면 기존 세그먼트는 닫힌 세그먼트로 남고 새 세그먼트부터 씁니다.
+//! 마지막 기록 시각이 `retention_days`보다 오래된 세그먼트는 삭제하고, 전체 크기가
+//! `max_bytes`를 넘으면 가장 오래된 세그먼트부터 삭제합니다. 보존 검사는 쓰기 후와
+//! 1분마다 수행합니다.
+//!
+//! # 검색
+//! [`LogStore::search`]는 최근 세그먼트부터 거꾸로 읽어 [`LogQuery`] 조건에 맞는 로그를
+//! 최신순으로 반환합니다. `offset`만큼 건너뛴 뒤 `limit`개를 반환하므로 페이지 단위로
+//! 이어서 조회할 수 있습니다. 비정상 종료로 잘린 줄은 건너뜁니다.
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+
+use ironpost_core::config::LogStoreConfig;
+use ironpost_core::metrics as m;
+use ironpost_core::types::{LogEntry, Severity};
+
+use super::{OutputDocument, OutputHandle, record_outcome};
+use crate::error::LogPipelineError;
+
+/// 출력 이름 (로그/메트릭 라벨)
+pub(crate) const OUTPUT_NAME: &str = "log_store";
+
+/// 세그먼트 파일 이름 접두사/확장자
+const SEGMENT_PREFIX: &str = "segment-";
+const SEGMENT_SUFFIX: &str = ".jsonl";
+
+/// 세그먼트를 교체하는 최대 경과 시간 (기간 보존 단위)
+const SEGMENT_MAX_AGE: Duration = Duration::from_secs(3600);
+
+/// 한 번에 기록하는 최대 로그 수
+const WRITE_BATCH_SIZE: usize = 1000;
+
+/// 새 로그가 없을 때의 보존 검사 주기
+const RETENTION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 검색 결과 기본 최대 개수 (`LogQuery::limit`가 0일 때)
+pub const DEFAULT_SEARCH_LIMIT: usize = 100;
+
+/// 로그 검색 조건
+///
+/// 지정한 조건을 모두 만족하는 로그만 반환합니다.
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    /// 메시지에 포함될 문자열 (대소문자 무시)
+    pub text: Option<String>,
+    /// 호스트명 (정확히 일치)
+    pub hostname: Option<String>,
+    /// 프로세스명 (정확히 일치)
+    pub process: Option<String>,
+    /// 추가 필드 조건 (이름, 값이 정확히 일치)
+    pub fields: Vec<(String, String)>,
+    /// 최소 심각도 (이 심각도 이상의 로그만)
+    pub severity: Option<Severity>,
+    /// 이 시각 이후의 로그만 (포함)
+    pub since: Option<SystemTime>,
+    /// 이 시각 이전의 로그만 (포함)
+    pub until: Option<SystemTime>,
+    /// 최대 결과 수 (0이면 [`DEFAULT_SEARCH_LIMIT`])
+    pub limit: usize,
+    /// 건너뛸 결과 수 (최신순, 페이지네이션용)
+    pub offset: usize,
+}
+
+impl LogQuery {
+    /// 로그가 조건을 만족하는지 확인합니다.
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if self.since.is_some_and(|since| entry.timestamp < since)
+            || self.until.is_some_and(|until| entry.timestamp > until)
+            || self.severity.is_some_and(|min| entry.severity < min)
+        {
+            return false;
+        }
+        if self
+            .hostname
+            .as_ref()
+            .is_some_and(|hostname| &entry.hostname != hostname)
+            || self
+                .process
+                .as_ref()
+                .is_some_and(|process| &entry.process != process)
+        {
+            return false;
+        }
+        if let Some(text) = &self.text
+            && !entry.message.to_lowercase().contains(&text.to_lowercase())
+        {
+            return false;
+        }
+        self.fields
+            .iter()
+            .all(|(name, value)| entry.fields.iter().any(|(k, v)| k == name && v == value))
+    }
+}
+
+/// 세그먼트 파일 정보
+#[derive(Debug, Clone)]
+struct Segment {
+    path: PathBuf,
+    bytes: u64,
+    last_write: SystemTime,
+}
+
+/// 기록 중인 세그먼트
+struct ActiveSegment {
+    segment: Segment,
+    writer: BufWriter<File>,
+    opened_at: SystemTime,
+}
+
+/// 저장소 파일 상태
+struct StoreState {
+    /// 닫힌 세그먼트 (오래된 순)
+    closed: VecDeque<Segment>,
+    active: Option<ActiveSegment>,
+    next_seq: u64,
+}
+
+/// 내장 로그 저장소
+pub struct LogStore {
+    dir: PathBuf,
+    retention: Option<Duration>,
+    max_bytes: Option<u64>,
+    segment_bytes: u64,
+    queue_size: usize,
+    state: Mutex<StoreState>,
+}
+
+impl LogStore {
+    /// 저장소 디렉토리를 열고 기존 세그먼트를 불러옵니다. 디렉토리가 없으면 만듭니다.
+    ///
+    /// # Errors
+    /// 디렉토리를 만들거나 읽을 수 없는 경우
+    pub fn open(config: &LogStoreConfig) -> Result<Self, LogPipelineError> {
+        let dir = PathBuf::from(&config.dir);
+        let config_error = |e: io::Error| LogPipelineError::Config {
+            field: "log_store.dir".to_owned(),
+            reason: format!("{}: {e}", dir.display()),
+        };
+        fs::create_dir_all(&dir).map_err(config_error)?;
+
+        let mut segments = Vec::new();
+        for entry in fs::read_dir(&dir).map_err(config_error)? {
+            let entry = entry.map_err(config_error)?;
+            let name = entry.file_name();
+            let Some(seq) = name.to_str().and_then(segment_seq) else {
+                continue;
+            };
+            let metadata = entry.metadata().map_err(config_error)?;
+            segments.push((
+                seq,
+                Segment {
+                    path: entry.path(),
+                    bytes: metadata.len(),
+                    last_write: metadata.modified().unwrap_or_else(|_| SystemTime::now()),
+                },
+            ));
+        }
+        segments.sort_by_key(|(seq, _)| *seq);
+        let next_seq = segments.last().map_or(0, |(seq, _)| seq + 1);
+
+        let store = Self {
+            dir,
+            retention: (config.retention_days > 0)
+                .then(|| Duration::from_secs(u64::from(config.retention_days) * 86_400)),
+            max_bytes: (config.max_bytes > 0).then_some(config.max_bytes),
+            segment_bytes: config.segment_bytes,
+            queue_size: config.queue_size,
+            state: Mutex::new(StoreState {
+                closed: segments.into_iter().map(|(_, segment)| segment).collect(),
+                active: None,
+                next_seq,
+            }),
+        };
+        store.enforce_retention(SystemTime::now());
+        Ok(store)
+    }
+
+    /// 저장소 디렉토리
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 세그먼트 수 (기록 중인 세그먼트 포함)
+    pub fn segment_count(&self) -> usize {
+        let state = self.lock();
+        state.closed.len() + usize::from(state.active.is_some())
+    }
+
+    /// 저장된 데이터 크기 (바이트)
+    pub fn bytes(&self) -> u64 {
+        Self::total_bytes(&self.lock())
+    }
+
+    /// 로그를 기록합니다. 블로킹 I/O를 수행합니다.
+    ///
+    /// # Errors
+    /// 세그먼트 파일을 열거나 쓸 수 없는 경우
+    pub fn append(&self, entries: &[LogEntry]) -> io::Result<()> {
+        let now = SystemTime::now();
+        let mut state = self.lock();
+        let result = self.write_entries(&mut state, entries, now);
+        drop(state);
+        self.enforce_retention(now);
+        result
+    }
+
+    fn write_entries(
+        &self,
+        state: &mut StoreState,
+        entries: &[LogEntry],
+        now: SystemTime,
+    ) -> io::Result<()> {
+        for entry in entries {
+            let mut line = serde_json::to_vec(entry).map_err(io::Error::other)?;
+            line.push(b'\n');
+            let line_len = line.len() as u64;
+
+            let rotate = state.active.as_ref().is_some_and(|active| {
+                active.segment.bytes > 0
+                    && (active.segment.bytes + line_len > self.segment_bytes
+                        || now
+                            .duration_since(active.opened_at)
+                            .is_ok_and(|age| age >= SEGMENT_MAX_AGE))
+            });
+            if rotate {
+                self.close_active(state)?;
+            }
+            if state.active.is_none() {
+                state.active = Some(self.open_segment(state.next_seq, now)?);
+                state.next_seq += 1;
+            }
+            if let Some(active) = state.active.as_mut() {
+                active.writer.write_all(&line)?;
+                active.segment.bytes += line_len;
+                active.segment.last_write = now;
+            }
+        }
+        if let Some(active) = state.active.as_mut() {
+            active.writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// 새 세그먼트 파일을 엽니다.
+    fn open_segment(&self, seq: u64, now: SystemTime) -> io::Result<ActiveSegment> {
+        let path = self
+            .dir
+            .join(format!("{SEGMENT_PREFIX}{seq:020}{SEGMENT_SUFFIX}"));
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let bytes = file.metadata()?.len();
+        Ok(ActiveSegment {
+            segment: Segment {
+                path,
+                bytes,
+                last_write: now,
+            },
+            writer: BufWriter::new(file),
+            opened_at: now,
+        })
+    }
+
+    /// 기록 중인 세그먼트를 닫힌 세그먼트로 옮깁니다.
+    fn close_active(&self, state: &mut StoreState) -> io::Result<()> {
+        if let Some(mut active) = state.active.take() {
+            active.writer.flush()?;
+            active.writer.get_ref().sync_data()?;
+            state.closed.push_back(active.segment);
+        }
+        Ok(())
+    }
+
+    /// 보존 기간과 최대 크기를 넘은 세그먼트를 삭제하고 삭제한 세그먼트 수를 반환합니다.
+    pub fn enforce_retention(&self, now: SystemTime) -> usize {
+        let mut state = self.lock();
+        let mut expired = Vec::new();
+
+        if let Some(retention) = self.retention {
+            let is_expired = |segment: &Segment| {
+                now.duration_since(segment.last_write)
+                    .is_ok_and(|age| age > retention)
+            };
+            if state
+                .active
+                .as_ref()
+                .is_some_and(|active| is_expired(&active.segment))
+            {
+                let _ = self.close_active(&mut state);
+            }
+            while state.closed.front().is_some_and(is_expired) {
+                expired.extend(state.closed.pop_front());
+            }
+        }
+        if let Some(max_bytes) = self.max_bytes {
+            while Self::total_bytes(&state) > max_bytes {
+                let Some(segment) = state.closed.pop_front() else {
+                    break;
+                };
+                expired.push(segment);
+            }
+        }
+        metrics::gauge!(m::LOG_PIPELINE_LOG_STORE_BYTES).set(Self::total_bytes(&state) as f64);
+        drop(state);
+
+        for segment in &expired {
+            if let Err(e) = fs::remove_file(&segment.path)
+                && e.kind() != io::ErrorKind::NotFound
+            {
+                tracing::warn!(
+                    output = OUTPUT_NAME,
+                    path = %segment.path.display(),
+                    error = %e,
+                    "failed to remove expired log segment"
+                );
+            }
+        }
+        if !expired.is_empty() {
+            tracing::debug!(
+                output = OUTPUT_NAME,
+                segments = expired.len(),
+                "removed expired log segments"
+            );
+        }
+        expired.len()
+    }
+
+    /// 조건에 맞는 로그를 최신순으로 `offset`개 건너뛰고 반환합니다. 블로킹 I/O를 수행합니다.
+    ///
+    /// # Errors
+    /// 세그먼트 파일을 읽을 수 없는 경우 (검색 중 삭제된 세그먼트는 건너뜀)
+    pub fn search(&self, query: &LogQuery) -> io::Result<Vec<LogEntry>> {
+        let limit = if query.limit == 0 {
+            DEFAULT_SEARCH_LIMIT
+        } else {
+            query.limit
+        };
+        let wanted = query.offset.saturating_add(limit);
+        let paths: Vec<PathBuf> = {
+            let mut state = self.lock();
+            if let Some(active) = state.active.as_mut() {
+                active.writer.flush()?;
+            }
+            state
+                .closed
+                .iter()
+                .chain(state.active.as_ref().map(|active| &active.segment))
+                .rev()
+                .map(|segment| segment.path.clone())
+                .collect()
+        };
+
+        let mut results = Vec::new();
+        for path in paths {
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            let mut entries = Vec::new();
+            for line in BufReader::new(file).lines() {
+                if let Ok(entry) = serde_json::from_str::<LogEntry>(&line?)
+                    && query.matches(&entry)
+                {
+                    entries.push(entry);
+                }
+            }
+            results.extend(entries.into_iter().rev().take(wanted - results.len()));
+            if results.len() >= wanted {
+                break;
+            }
+        }
+        Ok(results.into_iter().skip(query.offset).collect())
+    }
+
+    /// 출력 태스크를 spawn하고 로그를 넣을 핸들을 반환합니다.
+    pub fn spawn(
+        self: &Arc<Self>,
+        cancel: CancellationToken,
+    ) -> (OutputHandle, tokio::task::JoinHandle<()>) {
+        let (tx, rx) = mpsc::channel(self.queue_size);
+        let handle = OutputHandle::new(OUTPUT_NAME, true, false, tx);
+        (handle, tokio::spawn(Arc::clone(self).run(rx, cancel)))
+    }
+
+    /// 출력 루프: 로그를 배치로 모아 기록하고 주기적으로 보존 검사를 합니다.
+    async fn run(
+        self: Arc<Self>,
+        mut rx: mpsc::Receiver<OutputDocument>,
+        cancel: CancellationToken,
+    ) {
+        let mut timer = tokio::time::interval(RETENTION_CHECK_INTERVAL);
+        timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            let mut batch = Vec::new();
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                document = rx.recv() => match document {
+                    Some(document) => batch.extend(log_entry(document)),
+                    None => break,
+                },
+                _ = timer.tick() => {
+                    let store = Arc::clone(&self);
+                    let _ = tokio::task::spawn_blocking(move || {
+                        store.enforce_retention(SystemTime::now())
+                    })
+                    .await;
+                    continue;
+                }
+            }
+            while batch.len() < WRITE_BATCH_SIZE {
+                match rx.try_recv() {
+                    Ok(document) => batch.extend(log_entry(document)),
+                    Err(_) => break,
+                }
+            }
+            self.write_batch(batch).await;
+        }
+
+        rx.close();
+        let mut batch = Vec::new();
+        while let Ok(document) = rx.try_recv() {
+            batch.extend(log_entry(document));
+        }
+        self.write_batch(batch).await;
+
+        let store = Arc::clone(&self);
+        let _ = tokio::task::spawn_blocking(move || {
+            let mut state = store.lock();
+            store.close_active(&mut state)
+        })
+        .await;
+    }
+
+    /// 블로킹 스레드에서 배치를 기록하고 결과를 집계합니다.
+    async fn write_batch(self: &Arc<Self>, batch: Vec<LogEntry>) {
+        if batch.is_empty() {
+            return;
+        }
+        let count = batch.len() as u64;
+        let store = Arc::clone(self);
+        let result = tokio::task::spawn_blocking(move || store.append(&batch)).await;
+        match result {
+            Ok(Ok(())) => record_outcome(OUTPUT_NAME, "stored", count),
+            Ok(Err(e)) => {
+                tracing::warn!(output = OUTPUT_NAME, error = %e, "failed to store logs");
+                record_outcome(OUTPUT_NAME, "failed", count);
+            }
+            Err(e) => {
+                tracing::warn!(output = OUTPUT_NAME, error = %e, "log store task failed");
+                record_outcome(OUTPUT_NAME, "failed", count);
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, StoreState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn total_bytes(state: &StoreState) -> u64 {
+        state
+            .closed
+            .iter()
+            .chain(state.active.as_ref().map(|active| &active.segment))
+            .map(|segment| segment.bytes)
+            .sum()
+    }
+}
+
+/// 출력 문서에서 로그를 꺼냅니다 (알림은 저장하지 않음).
+fn log_entry(document: OutputDocument) -> Option<LogEntry> {
+    match document {
+        OutputDocument::Log(entry) => Some(entry),
+        OutputDocument::Alert(_) => None,
+    }
+}
+
+/// 세그먼트 파일 이름에서 번호를 읽습니다.
+fn segment_seq(name: &str) -> Option<u64> {
+    name.strip_prefix(SEGMENT_PREFIX)?
+        .strip_suffix(SEGMENT_SUFFIX)?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_core::config::LOG_STORE_MIN_SEGMENT_BYTES;
+
+    fn config(dir: &Path) -> LogStoreConfig {
+        LogStoreConfig {
+            enabled: true,
+            dir: dir.to_string_lossy().to_string(),
+            retention_days: 1,
+            max_bytes: 0,
+            segment_bytes: LOG_STORE_MIN_SEGMENT_BYTES,
+            queue_size: 100,
+        }
+    }
+
+    fn entry(hostname: &str, message: &str, secs: u64) -> LogEntry {
+        LogEntry {
+            source: "syslog".to_owned(),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            hostname: hostname.to_owned(),
+            process: "sshd".to_owned(),
+            message: message.to_owned(),
+            severity: Severity::Info,
+            fields: vec![("user".to_owned(), format!("u{secs}"))],
+        }
+    }
+
+    #[test]
+    fn search_returns_newest_matches_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LogStore::open(&config(dir.path())).unwrap();
+        let entries: Vec<LogEntry> = (0..10)
+            .map(|i| {
+                let host = if i % 2 == 0 { "web-01" } else { "db-01" };
+                entry(host, &format!("Failed password attempt {i}"), 1_000 + i)
+            })
+            .collect();
+        store.append(&entries).unwrap();
+
+        let results = store
+            .search(&LogQuery {
+                text: Some("FAILED PASSWORD".to_owned()),
+                hostname: Some("web-01".to_owned()),
+                limit: 3,
+                ..Default::default()
+            })
+            .unwrap();
+        let messages: Vec<&str> = results.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Failed password attempt 8",
+                "Failed password attempt 6",
+                "Failed password attempt 4"
+            ]
+        );
+
+        let results = store
+            .search(&LogQuery {
+                since: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_005)),
+                until: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_007)),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(results.len(), 3);
+
+        let results = store
+            .search(&LogQuery {
+                fields: vec![("user".to_owned(), "u1003".to_owned())],
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message, "Failed password attempt 3");
+    }
+
+    #[test]
+    fn search_filters_severity_and_pages_with_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LogStore::open(&config(dir.path())).unwrap();
+        let entries: Vec<LogEntry> = (0..10)
+            .map(|i| LogEntry {
+                severity: if i % 3 == 0 {
+                    Severity::High
+                } else {
+                    Severity::Low
+                },
+                ..entry("web-01", &format!("event {i}"), 1_000 + i)
+            })
+            .collect();
+        store.append(&entries).unwrap();
+
+        let results = store
+            .search(&LogQuery {
+                severity: Some(Severity::Medium),
+                ..Default::default()
+            })
+            .unwrap();
+        let messages: Vec<&str> = results.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["event 9", "event 6", "event 3", "event 0"]);
+
+        let page = |offset| {
+            store
+                .search(&LogQuery {
+                    limit: 4,
+                    offset,
+                    ..Default::default()
+                })
+                .unwrap()
+                .into_iter()
+                .map(|e| e.message)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(page(0), ["event 9", "event 8", "event 7", "event 6"]);
+        assert_eq!(page(4), ["event 5", "event 4", "event 3", "event 2"]);
+        assert_eq!(page(8), ["event 1", "event 0"]);
+        assert!(page(12).is_empty());
+    }
+
+    #[test]
+    fn rotates_segments_and_enforces_max_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LogStore::open(&LogStoreConfig {
+            max_bytes: LOG_STORE_MIN_SEGMENT_BYTES * 2,
+            ..config(dir.path())
+        })
+        .unwrap();
+
+        let message = "x".repeat(1024);
+        for batch in 0..300u64 {
+            store.append(&[entry("web-01", &message, batch)]).unwrap();
+        }
+        assert!(store.bytes() <= LOG_STORE_MIN_SEGMENT_BYTES * 2);
+        assert!(store.segment_count() >= 2);
+
+        let results = store.search(&LogQuery::default()).unwrap();
+        assert!(!results.is_empty() && results.len() < 300);
+        assert_eq!(results[0].timestamp, entry("", "", 299).timestamp);
+        assert!(results.windows(2).all(|w| w[0].timestamp > w[1].timestamp));
+    }
+
+    #[test]
+    fn removes_segments_older_than_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LogStore::open(&config(dir.path())).unwrap();
+        store.append(&[entry("web-01", "old", 1)]).unwrap();
+        assert_eq!(store.segment_count(), 1);
+
+        let later = SystemTime::now() + Duration::from_secs(2 * 86_400);
+        assert_eq!(store.enforce_retention(later), 1);
+        assert_eq!(store.segment_count(), 0);
+        assert_eq!(store.bytes(), 0);
+        assert!(store.search(&LogQuery::default()).unwrap().is_empty());
+
+        store.append(&[entry("web-01", "new", 2)]).unwrap();
+        assert_eq!(store.search(&LogQuery::default()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn reopen_keeps_segments_and_skips_torn_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let store = LogStore::open(&config(dir.path())).unwrap();
+            store
+                .append(&[entry("web-01", "first", 1), entry("web-01", "second", 2)])
+                .unwrap();
+        }
+        let segment = dir
+            .path()
+            .join(format!("{SEGMENT_PREFIX}{:020}{SEGMENT_SUFFIX}", 0));
+        let mut file = OpenOptions::new().append(true).open(&segment).unwrap();
+        file.write_all(br#"{"source":"sys"#).unwrap();
+        drop(file);
+
+        let store = LogStore::open(&config(dir.path())).unwrap();
+        store.append(&[entry("web-01", "third", 3)]).unwrap();
+        assert_eq!(store.segment_count(), 2);
+        let messages: Vec<String> = store
+            .search(&LogQuery::default())
+            .unwrap()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(messages, ["third", "second", "first"]);
+    }
+
+    #[tokio::test]
+    async fn output_task_stores_logs_and_ignores_alerts() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(LogStore::open(&config(dir.path())).unwrap());
+        let cancel = CancellationToken::new();
+        let (handle, task) = store.spawn(cancel.clone());
+
+        handle.send_log(&entry("web-01", "stored", 1));
+        let alert = ironpost_core::event::AlertEvent::new(
+            ironpost_core::types::Alert {
+                id: "a1".to_owned(),
+                title: "t".to_owned(),
+                description: String::new(),
+                severity: Severity::High,
+                rule_name: "r".to_owned(),
+                source_ip: None,
+                target_ip: None,
+                created_at: SystemTime::now(),
+            },
+            Severity::High,
+        );
+        handle.send_alert(&alert);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        cancel.cancel();
+        task.await.unwrap();
+
+        let results = store.search(&LogQuery::default()).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message, "stored");
+    }
+}
@@ -0,0 +1,261 @@
+//! 출력 -- 파싱된 로그와 생성된 알림을 외부 저장소에 보냅니다.
+//!
+//! 파이프라인은 [`OutputHandle`]로 문서를 출력별 큐에 넣고, 출력 태스크가 큐에서 꺼내
+//! 전송합니다 (예: [`ElasticsearchOutput`], `kafka` 기능 플래그의 [`KafkaOutput`]).
+//! [`LogStore`]는 외부 저장소 대신 로컬 세그먼트 파일에 로그를 저장하고 검색을 제공합니다.
+//!
+//! 큐는 크기가 제한되어 있어, 저장소가 느리거나 `429`로 백오프하는 동안 큐가 가득 차면
+//! 새 문서를 버립니다. 출력 장애가 로그 처리 루프를 막지 않습니다.
+
+pub mod elasticsearch;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+pub mod log_store;
+
+pub use elasticsearch::{ElasticsearchOutput, IndexTemplate};
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaOutput;
+pub use log_store::{LogQuery, LogStore};
+
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use ironpost_core::config::{ElasticsearchConfig, KafkaOutputConfig, LogStoreConfig};
+use ironpost_core::event::AlertEvent;
+use ironpost_core::metrics as m;
+use ironpost_core::types::LogEntry;
+
+use crate::error::LogPipelineError;
+use crate::sink::webhook::default_payload;
+
+/// 출력으로 보내는 문서
+#[derive(Debug, Clone)]
+pub enum OutputDocument {
+    /// 파싱된 로그
+    Log(LogEntry),
+    /// 생성된 알림
+    Alert(AlertEvent),
+}
+
+impl OutputDocument {
+    /// 출력 공통 JSON 문서를 만듭니다.
+    ///
+    /// 로그는 `@timestamp`, `source`, `hostname`, `process`, `message`, `severity`, `fields`를,
+    /// 알림은 웹훅 기본 페이로드에 `@timestamp`를 더해 담습니다.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Log(entry) => {
+                let fields: serde_json::Map<String, Value> = entry
+                    .fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), Value::from(value.as_str())))
+                    .collect();
+                serde_json::json!({
+                    "@timestamp": timestamp(entry.timestamp),
+                    "source": entry.source,
+                    "hostname": entry.hostname,
+                    "process": entry.process,
+                    "message": entry.message,
+                    "severity": entry.severity,
+                    "fields": fields,
+                })
+            }
+            Self::Alert(event) => {
+                let mut document = default_payload(event);
+                document["@timestamp"] = Value::from(timestamp(event.alert.created_at));
+                document
+            }
+        }
+    }
+}
+
+/// 밀리초 단위 RFC 3339 UTC 시각
+fn timestamp(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// 설정된 출력 목록
+#[derive(Default)]
+pub struct OutputSet {
+    elasticsearch: Option<Arc<ElasticsearchOutput>>,
+    #[cfg(feature = "kafka")]
+    kafka: Option<Arc<KafkaOutput>>,
+    log_store: Option<Arc<LogStore>>,
+}
+
+impl OutputSet {
+    /// 설정에서 활성화된 출력을 생성합니다.
+    ///
+    /// `kafka` 기능 없이 빌드했으면 Kafka 출력은 경고를 남기고 건너뜁니다.
+    ///
+    /// # Errors
+    /// 출력 생성 실패 (잘못된 인덱스 템플릿, 클라이언트 생성 실패, 저장소 디렉토리 오류 등)
+    pub fn from_config(
+        elasticsearch: &ElasticsearchConfig,
+        kafka: &KafkaOutputConfig,
+        log_store: &LogStoreConfig,
+    ) -> Result<Self, LogPipelineError> {
+        let mut outputs = Self::default();
+        if elasticsearch.enabled {
+            outputs.elasticsearch =
+                Some(Arc::new(ElasticsearchOutput::from_config(elasticsearch)?));
+        }
+        if kafka.enabled {
+            #[cfg(feature = "kafka")]
+            {
+                outputs.kafka = Some(Arc::new(KafkaOutput::from_config(kafka)?));
+            }
+            #[cfg(not(feature = "kafka"))]
+            tracing::warn!(
+                output = "kafka",
+                "built without the `kafka` feature, skipping output"
+            );
+        }
+        if log_store.enabled {
+            outputs.log_store = Some(Arc::new(LogStore::open(log_store)?));
+        }
+        Ok(outputs)
+    }
+
+    /// 출력 이름 목록
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        if self.elasticsearch.is_some() {
+            names.push(elasticsearch::OUTPUT_NAME);
+        }
+        #[cfg(feature = "kafka")]
+        if self.kafka.is_some() {
+            names.push(kafka::OUTPUT_NAME);
+        }
+        if self.log_store.is_some() {
+            names.push(log_store::OUTPUT_NAME);
+        }
+        names
+    }
+
+    /// 설정된 출력이 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.names().is_empty()
+    }
+
+    /// 내장 로그 저장소 (`log_store.enabled`일 때, 검색용)
+    pub fn log_store(&self) -> Option<Arc<LogStore>> {
+        self.log_store.clone()
+    }
+
+    /// 출력 태스크를 모두 spawn하고 문서를 넣을 핸들을 반환합니다.
+    pub fn spawn(
+        &self,
+        cancel: CancellationToken,
+    ) -> (OutputHandle, Vec<tokio::task::JoinHandle<()>>) {
+        let mut handle = OutputHandle::default();
+        let mut tasks = Vec::new();
+        if let Some(output) = &self.elasticsearch {
+            let (queue, task) = output.spawn(cancel.clone());
+            handle.queues.extend(queue.queues);
+            tasks.push(task);
+        }
+        #[cfg(feature = "kafka")]
+        if let Some(output) = &self.kafka {
+            let (queue, task) = output.spawn(cancel.clone());
+            handle.queues.extend(queue.queues);
+            tasks.push(task);
+        }
+        if let Some(store) = &self.log_store {
+            let (queue, task) = store.spawn(cancel.clone());
+            handle.queues.extend(queue.queues);
+            tasks.push(task);
+        }
+        (handle, tasks)
+    }
+}
+
+/// 실행 중인 출력 큐에 문서를 넣는 핸들
+///
+/// 복제 비용이 작아 처리 태스크마다 복제해 사용합니다.
+/// 기본값은 출력이 없는 핸들입니다.
+#[derive(Clone, Default)]
+pub struct OutputHandle {
+    queues: Vec<OutputQueue>,
+}
+
+/// 출력 하나의 큐
+#[derive(Clone)]
+struct OutputQueue {
+    name: &'static str,
+    logs: bool,
+    alerts: bool,
+    tx: mpsc::Sender<OutputDocument>,
+}
+
+impl OutputHandle {
+    /// 출력 큐 핸들을 생성합니다. `logs`/`alerts`가 꺼진 문서 종류는 큐에 넣지 않습니다.
+    pub(crate) fn new(
+        name: &'static str,
+        logs: bool,
+        alerts: bool,
+        tx: mpsc::Sender<OutputDocument>,
+    ) -> Self {
+        Self {
+            queues: vec![OutputQueue {
+                name,
+                logs,
+                alerts,
+                tx,
+            }],
+        }
+    }
+
+    /// 파싱된 로그를 출력 큐에 넣습니다.
+    pub fn send_log(&self, entry: &LogEntry) {
+        for queue in self.queues.iter().filter(|queue| queue.logs) {
+            queue.push(OutputDocument::Log(entry.clone()));
+        }
+    }
+
+    /// 생성된 알림을 출력 큐에 넣습니다.
+    pub fn send_alert(&self, alert: &AlertEvent) {
+        for queue in self.queues.iter().filter(|queue| queue.alerts) {
+            queue.push(OutputDocument::Alert(alert.clone()));
+        }
+    }
+
+    /// 연결된 출력이 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.queues.is_empty()
+    }
+}
+
+impl OutputQueue {
+    /// 블로킹하지 않고 문서를 넣습니다. 큐가 가득 차면 문서를 버립니다.
+    fn push(&self, document: OutputDocument) {
+        match self.tx.try_send(document) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                tracing::debug!(output = self.name, "output queue full, dropping document");
+                record_outcome(self.name, "dropped", 1);
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                tracing::debug!(output = self.name, "output stopped, skipping document");
+            }
+        }
+    }
+}
+
+/// 출력 문서 처리 결과 메트릭을 기록합니다.
+pub(crate) fn record_outcome(output: &'static str, result: &'static str, count: u64) {
+    if count == 0 {
+        return;
+    }
+    metrics::counter!(
+        m::LOG_PIPELINE_OUTPUT_DOCUMENTS_TOTAL,
+        m::LABEL_OUTPUT => output,
+        m::LABEL_RESULT => result
+    )
+    .increment(count);
+}
@@ -0,0 +1,293 @@
+//! GELF (Graylog Extended Log Format) 파서
+//!
+//! GELF 1.1 JSON 메시지를 파싱합니다. 압축(gzip/zlib)과 UDP 청크 재조립은
+//! 수집기([`SyslogUdpCollector`](crate::collector::SyslogUdpCollector))가 처리하므로
+//! 이 파서는 압축이 풀린 JSON 객체만 받습니다.
+//!
+//! # 필드 매핑
+//! | GELF | `LogEntry` |
+//! |------|-----------|
+//! | `host` (필수) | `hostname` |
+//! | `short_message` (필수) | `message` |
+//! | `timestamp` (초, 소수점 허용) | `timestamp` (없으면 수신 시각) |
+//! | `level` (syslog 0-7, 기본 1) | `severity` |
+//! | `facility` (deprecated) | `process` |
+//! | `full_message`, `file`, `line` | `fields` |
+//! | `_<name>` 추가 필드 | `fields` (`<name>`, 앞의 `_` 제거) |
+//!
+//! # 사용 예시
+//! ```ignore
+//! use ironpost_log_pipeline::parser::GelfParser;
+//! use ironpost_core::pipeline::LogParser;
+//!
+//! let parser = GelfParser::default();
+//! let raw = br#"{"version":"1.1","host":"web-01","short_message":"login failed","_user":"alice"}"#;
+//! let entry = parser.parse(raw)?;
+//! assert_eq!(entry.hostname, "web-01");
+//! ```
+
+use std::time::{Duration, SystemTime};
+
+use ironpost_core::error::IronpostError;
+use ironpost_core::pipeline::LogParser;
+use ironpost_core::types::LogEntry;
+
+use super::SyslogParser;
+use crate::error::LogPipelineError;
+
+/// GELF 스펙의 기본 level (1 = ALERT)
+const GELF_DEFAULT_LEVEL: u8 = 1;
+
+/// `fields`로 옮기는 GELF 표준 필드
+const GELF_STANDARD_FIELDS: &[&str] = &["full_message", "file", "line"];
+
+/// GELF 파서
+///
+/// `version`, `host`, `short_message`가 모두 있는 JSON 객체만 GELF로 인식하므로,
+/// [`ParserRouter`](super::ParserRouter)에서 [`JsonLogParser`](super::JsonLogParser)보다
+/// 먼저 등록해도 일반 JSON 로그를 가로채지 않습니다.
+pub struct GelfParser {
+    /// 최대 허용 입력 크기 (바이트)
+    max_input_size: usize,
+}
+
+impl GelfParser {
+    /// 새 GELF 파서를 생성합니다.
+    pub fn new() -> Self {
+        Self {
+            max_input_size: 1024 * 1024, // 1MB
+        }
+    }
+
+    /// 최대 입력 크기를 설정합니다.
+    pub fn with_max_input_size(mut self, size: usize) -> Self {
+        self.max_input_size = size;
+        self
+    }
+
+    /// GELF JSON 바이트를 파싱하여 `LogEntry`를 생성합니다.
+    fn parse_gelf(&self, raw: &[u8]) -> Result<LogEntry, LogPipelineError> {
+        if raw.len() > self.max_input_size {
+            return Err(Self::error(format!(
+                "input too large: {} bytes (max: {})",
+                raw.len(),
+                self.max_input_size
+            )));
+        }
+
+        let value: serde_json::Value =
+            serde_json::from_slice(raw).map_err(|e| LogPipelineError::Parse {
+                format: "gelf".to_owned(),
+                offset: e.column(),
+                reason: e.to_string(),
+            })?;
+        let serde_json::Value::Object(object) = value else {
+            return Err(Self::error("expected JSON object at top level".to_owned()));
+        };
+
+        let required = |name: &str| -> Result<String, LogPipelineError> {
+            match object.get(name) {
+                Some(serde_json::Value::String(s)) if !s.is_empty() => Ok(s.clone()),
+                Some(_) => Err(Self::error(format!(
+                    "'{}' must be a non-empty string",
+                    name
+                ))),
+                None => Err(Self::error(format!("missing required field '{}'", name))),
+            }
+        };
+        required("version")?;
+        let hostname = required("host")?;
+        let message = required("short_message")?;
+
+        let timestamp = match object.get("timestamp") {
+            Some(value) => Self::parse_timestamp(value)?,
+            None => SystemTime::now(),
+        };
+        let level = match object.get("level") {
+            Some(value) => value
+                .as_u64()
+                .filter(|level| *level <= 7)
+                .ok_or_else(|| Self::error(format!("invalid level: {}", value)))?
+                as u8,
+            None => GELF_DEFAULT_LEVEL,
+        };
+        let process = object
+            .get("facility")
+            .and_then(Self::value_to_string)
+            .unwrap_or_default();
+
+        let mut fields = Vec::new();
+        for (key, value) in &object {
+            let name = match key.strip_prefix('_') {
+                // `_id`는 Graylog 예약 필드이므로 GELF 스펙상 허용되지 않음
+                Some("id") | Some("") => continue,
+                Some(name) => name,
+                None if GELF_STANDARD_FIELDS.contains(&key.as_str()) => key.as_str(),
+                None => continue,
+            };
+            if let Some(value) = Self::value_to_string(value) {
+                fields.push((name.to_owned(), value));
+            }
+        }
+
+        Ok(LogEntry {
+            source: "gelf".to_owned(),
+            timestamp,
+            hostname,
+            process,
+            message,
+            severity: SyslogParser::syslog_severity_to_ironpost(level),
+            fields,
+        })
+    }
+
+    /// GELF `timestamp`(Unix 초, 소수점 이하 밀리초)를 변환합니다.
+    fn parse_timestamp(value: &serde_json::Value) -> Result<SystemTime, LogPipelineError> {
+        value
+            .as_f64()
+            .filter(|secs| secs.is_finite() && *secs >= 0.0)
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .and_then(|since_epoch| SystemTime::UNIX_EPOCH.checked_add(since_epoch))
+            .ok_or_else(|| Self::error(format!("invalid timestamp: {}", value)))
+    }
+
+    /// 필드 값을 문자열로 변환합니다 (null은 제외, 객체/배열은 JSON 문자열).
+    fn value_to_string(value: &serde_json::Value) -> Option<String> {
+        match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            serde_json::Value::Bool(b) => Some(b.to_string()),
+            other => serde_json::to_string(other).ok(),
+        }
+    }
+
+    fn error(reason: String) -> LogPipelineError {
+        LogPipelineError::Parse {
+            format: "gelf".to_owned(),
+            offset: 0,
+            reason,
+        }
+    }
+}
+
+impl Default for GelfParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogParser for GelfParser {
+    fn format_name(&self) -> &str {
+        "gelf"
+    }
+
+    fn parse(&self, raw: &[u8]) -> Result<LogEntry, IronpostError> {
+        self.parse_gelf(raw).map_err(IronpostError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_core::types::Severity;
+
+    fn field<'a>(entry: &'a LogEntry, name: &str) -> Option<&'a str> {
+        entry
+            .fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn format_name_is_gelf() {
+        assert_eq!(GelfParser::default().format_name(), "gelf");
+    }
+
+    #[test]
+    fn parse_full_gelf_message() {
+        let raw = br#"{
+            "version": "1.1",
+            "host": "web-01",
+            "short_message": "Failed password for root",
+            "full_message": "Failed password for root from 203.0.113.7\nstack...",
+            "timestamp": 1705320000.25,
+            "level": 3,
+            "facility": "sshd",
+            "_user_id": 42,
+            "_src_ip": "203.0.113.7",
+            "_tags": ["auth", "ssh"],
+            "_id": "ignored",
+            "_empty": null
+        }"#;
+        let entry = GelfParser::default().parse(raw).unwrap();
+
+        assert_eq!(entry.source, "gelf");
+        assert_eq!(entry.hostname, "web-01");
+        assert_eq!(entry.process, "sshd");
+        assert_eq!(entry.message, "Failed password for root");
+        assert_eq!(entry.severity, Severity::High);
+        assert_eq!(
+            entry.timestamp,
+            SystemTime::UNIX_EPOCH + Duration::from_millis(1_705_320_000_250)
+        );
+        assert_eq!(field(&entry, "user_id"), Some("42"));
+        assert_eq!(field(&entry, "src_ip"), Some("203.0.113.7"));
+        assert_eq!(field(&entry, "tags"), Some(r#"["auth","ssh"]"#));
+        assert!(field(&entry, "full_message").unwrap().contains("stack"));
+        assert!(field(&entry, "id").is_none());
+        assert!(field(&entry, "empty").is_none());
+        assert!(field(&entry, "version").is_none());
+    }
+
+    #[test]
+    fn missing_level_defaults_to_alert() {
+        let raw = br#"{"version":"1.1","host":"h","short_message":"m"}"#;
+        let entry = GelfParser::default().parse(raw).unwrap();
+        assert_eq!(entry.severity, Severity::Critical);
+        assert!(entry.process.is_empty());
+    }
+
+    #[test]
+    fn plain_json_is_not_gelf() {
+        let parser = GelfParser::default();
+        assert!(
+            parser
+                .parse(br#"{"host":"web-01","message":"hello","level":"info"}"#)
+                .is_err()
+        );
+        assert!(
+            parser
+                .parse(br#"{"version":"1.1","host":"","short_message":"m"}"#)
+                .is_err()
+        );
+        assert!(parser.parse(b"[1,2,3]").is_err());
+        assert!(parser.parse(b"<34>1 - - - - - - msg").is_err());
+    }
+
+    #[test]
+    fn invalid_level_and_timestamp_fail() {
+        let parser = GelfParser::default();
+        assert!(
+            parser
+                .parse(br#"{"version":"1.1","host":"h","short_message":"m","level":9}"#)
+                .is_err()
+        );
+        assert!(
+            parser
+                .parse(br#"{"version":"1.1","host":"h","short_message":"m","timestamp":"now"}"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_too_large_input_fails() {
+        let parser = GelfParser::default().with_max_input_size(10);
+        assert!(
+            parser
+                .parse(br#"{"version":"1.1","host":"h","short_message":"m"}"#)
+                .is_err()
+        );
+    }
+}
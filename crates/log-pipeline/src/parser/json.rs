@@ -105,7 +105,7 @@ impl JsonLogParser {
     }
 
     /// JSON 로그 레벨 문자열을 Severity로 변환합니다.
-    fn level_to_severity(level: &str) -> Severity {
+    pub(crate) fn level_to_severity(level: &str) -> Severity {
         match level.to_lowercase().as_str() {
             "trace" | "debug" => Severity::Info,
             "info" | "information" => Severity::Info,
@@ -264,7 +264,7 @@ impl JsonLogParser {
     /// - Unix timestamp (밀리초): `1705320000000` (13자리)
     /// - Unix timestamp (마이크로초): `1705320000000000` (16자리)
     /// - Unix timestamp (나노초): `1705320000000000000` (19자리)
-    fn parse_timestamp(timestamp: &str) -> Result<SystemTime, LogPipelineError> {
+    pub(crate) fn parse_timestamp(timestamp: &str) -> Result<SystemTime, LogPipelineError> {
         // RFC 3339 시도
         if let Ok(dt) = DateTime::parse_from_rfc3339(timestamp) {
             return Ok(SystemTime::from(dt));
@@ -0,0 +1,420 @@
+//! LEEF (Log Event Extended Format) 파서
+//!
+//! IBM QRadar가 사용하는 LEEF 1.0/2.0 메시지를 파싱합니다. 장비 대부분이 LEEF를 syslog로
+//! 보내므로, `LEEF:` 앞에 붙은 syslog 헤더는 건너뛰고 마지막 토큰을 호스트명으로 사용합니다.
+//!
+//! # 메시지 형식
+//! ```text
+//! LEEF:1.0|Vendor|Product|Version|EventID|key1=value1<TAB>key2=value2
+//! LEEF:2.0|Vendor|Product|Version|EventID|^|key1=value1^key2=value2
+//! ```
+//! LEEF 2.0의 구분자 필드는 생략할 수 있고, 한 글자 또는 16진수(`x5E`, `0x5E`)로 지정합니다.
+//! 생략하거나 비어 있으면 탭을 사용합니다.
+//!
+//! # 필드 매핑
+//! | LEEF | `LogEntry` |
+//! |------|-----------|
+//! | syslog 헤더의 호스트 | `hostname` |
+//! | Product | `process` |
+//! | EventID | `message` |
+//! | `sev` (0-10) | `severity` (없으면 Info) |
+//! | `devTime` | `timestamp` (없거나 해석할 수 없으면 수신 시각) |
+//! | 헤더, 속성 | `fields` |
+//!
+//! # 사용 예시
+//! ```ignore
+//! use ironpost_log_pipeline::parser::LeefParser;
+//! use ironpost_core::pipeline::LogParser;
+//!
+//! let parser = LeefParser::default();
+//! let entry = parser.parse(b"LEEF:1.0|Acme|Firewall|2.1|deny|src=10.0.0.1\tsev=7")?;
+//! assert_eq!(entry.process, "Firewall");
+//! ```
+
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, NaiveDateTime};
+use ironpost_core::error::IronpostError;
+use ironpost_core::pipeline::LogParser;
+use ironpost_core::types::{LogEntry, Severity};
+
+use crate::error::LogPipelineError;
+
+/// LEEF 헤더 시작 표시
+const LEEF_MARKER: &str = "LEEF:";
+
+/// LEEF 속성의 기본 구분자
+const LEEF_DEFAULT_DELIMITER: char = '\t';
+
+/// `devTime`에 흔히 쓰이는 형식 (`devTimeFormat` 기본값 `MMM dd yyyy HH:mm:ss`)
+const LEEF_DEV_TIME_FORMATS: &[&str] = &["%b %d %Y %H:%M:%S%.f", "%b %d %Y %H:%M:%S"];
+
+/// LEEF 파서
+///
+/// `LEEF:` 헤더가 있는 메시지만 받습니다. syslog 헤더로 감싼 LEEF는 BSD syslog 파서도
+/// 받아들이므로 [`ParserRouter`](super::ParserRouter)에서 [`SyslogParser`](super::SyslogParser)보다
+/// 먼저 등록해야 합니다.
+pub struct LeefParser {
+    /// 최대 허용 입력 크기 (바이트)
+    max_input_size: usize,
+}
+
+impl LeefParser {
+    /// 새 LEEF 파서를 생성합니다.
+    pub fn new() -> Self {
+        Self {
+            max_input_size: 1024 * 1024, // 1MB
+        }
+    }
+
+    /// 최대 입력 크기를 설정합니다.
+    pub fn with_max_input_size(mut self, size: usize) -> Self {
+        self.max_input_size = size;
+        self
+    }
+
+    /// LEEF 메시지를 파싱하여 `LogEntry`를 생성합니다.
+    fn parse_leef(&self, raw: &[u8]) -> Result<LogEntry, LogPipelineError> {
+        if raw.len() > self.max_input_size {
+            return Err(Self::error(
+                0,
+                format!(
+                    "input too large: {} bytes (max: {})",
+                    raw.len(),
+                    self.max_input_size
+                ),
+            ));
+        }
+
+        let text = std::str::from_utf8(raw)
+            .map_err(|e| Self::error(e.valid_up_to(), "invalid UTF-8".to_owned()))?;
+        let text = text.trim_end_matches(['\r', '\n']);
+
+        let start = Self::find_marker(text)
+            .ok_or_else(|| Self::error(0, "missing LEEF header".to_owned()))?;
+        let hostname = text[..start]
+            .split_whitespace()
+            .last()
+            .unwrap_or_default()
+            .to_owned();
+
+        let header = &text[start + LEEF_MARKER.len()..];
+        let mut parts = header.splitn(6, '|');
+        let version = parts.next().unwrap_or_default();
+        let (vendor, product, product_version, event_id, rest) = match (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) {
+            (Some(vendor), Some(product), Some(product_version), Some(event_id), Some(rest)) => {
+                (vendor, product, product_version, event_id, rest)
+            }
+            _ => {
+                return Err(Self::error(
+                    start,
+                    "LEEF header must have 5 pipe-separated fields".to_owned(),
+                ));
+            }
+        };
+
+        let (delimiter, attributes) = match version {
+            "1.0" => (LEEF_DEFAULT_DELIMITER, rest),
+            "2.0" => Self::split_delimiter(rest)?,
+            other => {
+                return Err(Self::error(
+                    start,
+                    format!("unsupported LEEF version: {}", other),
+                ));
+            }
+        };
+        if event_id.is_empty() {
+            return Err(Self::error(start, "empty EventID".to_owned()));
+        }
+
+        let mut fields = vec![
+            ("leef_version".to_owned(), version.to_owned()),
+            ("vendor".to_owned(), vendor.to_owned()),
+            ("product".to_owned(), product.to_owned()),
+            ("product_version".to_owned(), product_version.to_owned()),
+            ("event_id".to_owned(), event_id.to_owned()),
+        ];
+        let mut attrs = Vec::new();
+        for attribute in attributes.split(delimiter) {
+            if let Some((key, value)) = attribute.split_once('=') {
+                let key = key.trim();
+                if !key.is_empty() {
+                    attrs.push((key.to_owned(), value.to_owned()));
+                }
+            }
+        }
+
+        let attr = |name: &str| {
+            attrs
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        let severity = attr("sev").map_or(Severity::Info, Self::leef_severity_to_ironpost);
+        let timestamp = attr("devTime")
+            .and_then(Self::parse_dev_time)
+            .unwrap_or_else(SystemTime::now);
+
+        // `src`/`dst`는 알림의 IP 추출 규칙(`src_ip` 등)에 맞는 별칭을 함께 추가
+        let aliases: Vec<(String, String)> = [("src", "src_ip"), ("dst", "dst_ip")]
+            .into_iter()
+            .filter(|(_, alias)| attr(alias).is_none())
+            .filter_map(|(name, alias)| {
+                attr(name).map(|value| (alias.to_owned(), value.to_owned()))
+            })
+            .collect();
+        fields.extend(attrs);
+        fields.extend(aliases);
+
+        Ok(LogEntry {
+            source: "leef".to_owned(),
+            timestamp,
+            hostname,
+            process: product.to_owned(),
+            message: event_id.to_owned(),
+            severity,
+            fields,
+        })
+    }
+
+    /// `LEEF:` 헤더 위치를 찾습니다 (메시지 시작이거나 공백 바로 뒤).
+    fn find_marker(text: &str) -> Option<usize> {
+        if text.starts_with(LEEF_MARKER) {
+            return Some(0);
+        }
+        text.match_indices(LEEF_MARKER)
+            .map(|(index, _)| index)
+            .find(|index| text[..*index].ends_with(char::is_whitespace))
+    }
+
+    /// LEEF 2.0의 선택적 구분자 필드를 분리합니다.
+    ///
+    /// 반환값: (구분자, 속성 문자열)
+    fn split_delimiter(rest: &str) -> Result<(char, &str), LogPipelineError> {
+        let Some((candidate, attributes)) = rest.split_once('|') else {
+            // 구분자 필드 생략
+            return Ok((LEEF_DEFAULT_DELIMITER, rest));
+        };
+
+        let hex = candidate
+            .strip_prefix("0x")
+            .or_else(|| candidate.strip_prefix("x"))
+            .or_else(|| candidate.strip_prefix("0X"))
+            .or_else(|| candidate.strip_prefix("X"));
+        let mut chars = candidate.chars();
+        match (chars.next(), chars.next(), hex) {
+            (None, _, _) => Ok((LEEF_DEFAULT_DELIMITER, attributes)),
+            (Some(c), None, _) => Ok((c, attributes)),
+            (_, _, Some(hex))
+                if !hex.is_empty()
+                    && hex.len() <= 4
+                    && hex.chars().all(|c| c.is_ascii_hexdigit()) =>
+            {
+                u32::from_str_radix(hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .map(|c| (c, attributes))
+                    .ok_or_else(|| Self::error(0, format!("invalid LEEF delimiter: {}", candidate)))
+            }
+            // 구분자 필드 없이 속성 값에 `|`가 포함된 경우
+            _ => Ok((LEEF_DEFAULT_DELIMITER, rest)),
+        }
+    }
+
+    /// LEEF `sev`(1-10)를 Ironpost Severity로 매핑합니다.
+    ///
+    /// - 0-2 -> Info
+    /// - 3-4 -> Low
+    /// - 5-6 -> Medium
+    /// - 7-8 -> High
+    /// - 9-10 -> Critical
+    ///
+    /// 숫자가 아니면 Info로 처리합니다.
+    fn leef_severity_to_ironpost(sev: &str) -> Severity {
+        match sev.trim().parse::<u8>() {
+            Ok(0..=2) | Err(_) => Severity::Info,
+            Ok(3..=4) => Severity::Low,
+            Ok(5..=6) => Severity::Medium,
+            Ok(7..=8) => Severity::High,
+            Ok(_) => Severity::Critical,
+        }
+    }
+
+    /// `devTime`을 변환합니다 (Unix 밀리초/초, RFC 3339, `MMM dd yyyy HH:mm:ss[.SSS]` UTC).
+    ///
+    /// `devTimeFormat`의 임의 형식은 지원하지 않으며, 해석할 수 없으면 `None`을 반환합니다.
+    fn parse_dev_time(value: &str) -> Option<SystemTime> {
+        let value = value.trim();
+        if let Ok(epoch) = value.parse::<u64>() {
+            // 12자리 이상이면 밀리초
+            let since_epoch = if epoch >= 100_000_000_000 {
+                Duration::from_millis(epoch)
+            } else {
+                Duration::from_secs(epoch)
+            };
+            return SystemTime::UNIX_EPOCH.checked_add(since_epoch);
+        }
+        if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+            return Some(dt.into());
+        }
+        LEEF_DEV_TIME_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+            .map(|naive| naive.and_utc().into())
+    }
+
+    fn error(offset: usize, reason: String) -> LogPipelineError {
+        LogPipelineError::Parse {
+            format: "leef".to_owned(),
+            offset,
+            reason,
+        }
+    }
+}
+
+impl Default for LeefParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogParser for LeefParser {
+    fn format_name(&self) -> &str {
+        "leef"
+    }
+
+    fn parse(&self, raw: &[u8]) -> Result<LogEntry, IronpostError> {
+        self.parse_leef(raw).map_err(IronpostError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field<'a>(entry: &'a LogEntry, name: &str) -> Option<&'a str> {
+        entry
+            .fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn format_name_is_leef() {
+        assert_eq!(LeefParser::default().format_name(), "leef");
+    }
+
+    #[test]
+    fn parse_leef_1_with_syslog_header() {
+        let raw = b"<13>Jan 18 11:07:53 fw-01 LEEF:1.0|Acme|Firewall|2.1|deny|src=203.0.113.7\tdst=10.0.0.5\tsev=8\tdevTime=1705320000250\tusrName=alice\n";
+        let entry = LeefParser::default().parse(raw).unwrap();
+
+        assert_eq!(entry.source, "leef");
+        assert_eq!(entry.hostname, "fw-01");
+        assert_eq!(entry.process, "Firewall");
+        assert_eq!(entry.message, "deny");
+        assert_eq!(entry.severity, Severity::High);
+        assert_eq!(
+            entry.timestamp,
+            SystemTime::UNIX_EPOCH + Duration::from_millis(1_705_320_000_250)
+        );
+        assert_eq!(field(&entry, "leef_version"), Some("1.0"));
+        assert_eq!(field(&entry, "vendor"), Some("Acme"));
+        assert_eq!(field(&entry, "product_version"), Some("2.1"));
+        assert_eq!(field(&entry, "usrName"), Some("alice"));
+        assert_eq!(field(&entry, "src"), Some("203.0.113.7"));
+        assert_eq!(field(&entry, "src_ip"), Some("203.0.113.7"));
+        assert_eq!(field(&entry, "dst_ip"), Some("10.0.0.5"));
+    }
+
+    #[test]
+    fn parse_leef_2_delimiters() {
+        let parser = LeefParser::default();
+
+        let caret = parser
+            .parse(b"LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^sev=5")
+            .unwrap();
+        assert!(caret.hostname.is_empty());
+        assert_eq!(caret.severity, Severity::Medium);
+        assert_eq!(field(&caret, "dst"), Some("10.0.0.5"));
+
+        let hex = parser
+            .parse(b"LEEF:2.0|Vendor|Product|1.0|login|x5E|usrName=bob^sev=10")
+            .unwrap();
+        assert_eq!(field(&hex, "usrName"), Some("bob"));
+        assert_eq!(hex.severity, Severity::Critical);
+
+        // 구분자 필드 생략 -> 탭
+        let omitted = parser
+            .parse(b"LEEF:2.0|Vendor|Product|1.0|login|usrName=bob\tsev=3")
+            .unwrap();
+        assert_eq!(field(&omitted, "usrName"), Some("bob"));
+        assert_eq!(omitted.severity, Severity::Low);
+
+        // 빈 구분자 필드 -> 탭
+        let empty = parser
+            .parse(b"LEEF:2.0|Vendor|Product|1.0|login||usrName=bob\tsev=3")
+            .unwrap();
+        assert_eq!(field(&empty, "sev"), Some("3"));
+    }
+
+    #[test]
+    fn dev_time_formats() {
+        let expected = SystemTime::UNIX_EPOCH + Duration::from_secs(1_705_320_000);
+        assert_eq!(LeefParser::parse_dev_time("1705320000"), Some(expected));
+        assert_eq!(
+            LeefParser::parse_dev_time("2024-01-15T12:00:00Z"),
+            Some(expected)
+        );
+        assert_eq!(
+            LeefParser::parse_dev_time("Jan 15 2024 12:00:00"),
+            Some(expected)
+        );
+        assert_eq!(
+            LeefParser::parse_dev_time("Jan 15 2024 12:00:00.500"),
+            Some(expected + Duration::from_millis(500))
+        );
+        assert!(LeefParser::parse_dev_time("yesterday").is_none());
+    }
+
+    #[test]
+    fn missing_sev_and_bad_dev_time_use_defaults() {
+        let entry = LeefParser::default()
+            .parse(b"LEEF:1.0|Acme|Proxy|1|allow|devTime=yesterday\tsev=high")
+            .unwrap();
+        assert_eq!(entry.severity, Severity::Info);
+        assert_eq!(field(&entry, "devTime"), Some("yesterday"));
+    }
+
+    #[test]
+    fn invalid_messages_fail() {
+        let parser = LeefParser::default();
+        assert!(
+            parser
+                .parse(b"<34>1 2024-01-15T12:00:00Z host app - - - msg")
+                .is_err()
+        );
+        assert!(parser.parse(b"LEEF:1.0|Acme|Firewall|2.1").is_err());
+        assert!(parser.parse(b"LEEF:3.0|Acme|Firewall|2.1|deny|").is_err());
+        assert!(
+            parser
+                .parse(b"LEEF:1.0|Acme|Firewall|2.1||src=1.2.3.4")
+                .is_err()
+        );
+        assert!(parser.parse(b"msg=NOTLEEF:1.0|a|b|c|d|").is_err());
+        assert!(parser.parse(&[0xff, 0xfe]).is_err());
+    }
+
+    #[test]
+    fn parse_too_large_input_fails() {
+        let parser = LeefParser::default().with_max_input_size(10);
+        assert!(parser.parse(b"LEEF:1.0|Acme|Firewall|2.1|deny|").is_err());
+    }
+}
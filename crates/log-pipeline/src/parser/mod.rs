@@ -1,10 +1,13 @@
-//! 로그 파싱 모듈 -- Syslog RFC 5424, JSON 등 형식별 파서
+//! 로그 파싱 모듈 -- Syslog RFC 5424, GELF, LEEF, JSON, 사용자 정의 패턴 등 형식별 파서
 //!
 //! [`ParserRouter`]는 원시 로그 데이터의 형식을 판별하여 적절한 파서를 선택합니다.
 //! 각 파서는 core의 [`LogParser`] trait을 구현합니다.
 //!
 //! # 지원 형식
 //! - Syslog RFC 5424 ([`SyslogParser`])
+//! - GELF 1.1 ([`GelfParser`])
+//! - IBM QRadar LEEF 1.0/2.0 ([`LeefParser`], `parsers.leef` 설정 시)
+//! - 사용자 정의 grok/정규식 패턴 ([`PatternParser`], `parsers.patterns` 설정 시)
 //! - 구조화 JSON ([`JsonLogParser`])
 //!
 //! # 사용 예시
@@ -18,25 +21,42 @@
 //! let entry = router.parse(b"<34>1 2024-01-15T12:00:00Z host app - - - message")?;
 //! ```
 
+pub mod gelf;
 pub mod json;
+pub mod leef;
+pub mod pattern;
 pub mod syslog;
 
+pub use gelf::GelfParser;
 pub use json::JsonLogParser;
+pub use leef::LeefParser;
+pub use pattern::PatternParser;
 pub use syslog::SyslogParser;
 
+use std::collections::BTreeMap;
+
+use ironpost_core::config::ParsersConfig;
 use ironpost_core::error::IronpostError;
 use ironpost_core::pipeline::LogParser;
 use ironpost_core::types::LogEntry;
 
+use crate::collector::RawLog;
 use crate::error::LogPipelineError;
 
 /// 파서 라우터 -- 로그 형식을 자동 감지하여 적절한 파서를 선택합니다.
 ///
 /// 등록된 파서 목록을 순회하며, 첫 번째로 파싱에 성공한 파서의 결과를 반환합니다.
 /// 모든 파서가 실패하면 `UnsupportedFormat` 에러를 반환합니다.
+///
+/// [`parse_raw`](Self::parse_raw)는 `RawLog.format_hint`(없으면 소스별 기본 힌트)의 파서를
+/// 먼저 시도하여 불필요한 파싱 시도와 오감지를 줄입니다.
 pub struct ParserRouter {
     /// 등록된 파서 목록 (순서대로 시도)
     parsers: Vec<Box<dyn LogParser>>,
+    /// 앞쪽에 등록된, 설정으로 켠 파서 수 (힌트보다 먼저 시도)
+    preferred: usize,
+    /// 소스 종류별 기본 형식 힌트 (예: "file" -> "json")
+    source_hints: BTreeMap<String, String>,
 }
 
 impl ParserRouter {
@@ -44,28 +64,116 @@ impl ParserRouter {
     pub fn new() -> Self {
         Self {
             parsers: Vec::new(),
+            preferred: 0,
+            source_hints: BTreeMap::new(),
         }
     }
 
-    /// 기본 파서 세트 (Syslog + JSON)로 라우터를 생성합니다.
+    /// 기본 파서 세트 (Syslog + GELF + JSON)로 라우터를 생성합니다.
+    ///
+    /// GELF는 필수 필드가 있는 JSON만 받으므로 일반 JSON보다 먼저 시도합니다.
     pub fn with_defaults() -> Self {
         let mut router = Self::new();
-        router.parsers.push(Box::new(SyslogParser::new()));
-        router.parsers.push(Box::new(JsonLogParser::default()));
+        router.push_defaults();
         router
     }
 
+    /// 기본 파서 세트에 설정에서 켠 선택적 파서를 더해 라우터를 생성합니다.
+    ///
+    /// 사용자 정의 패턴은 BSD syslog 파서가 대부분의 텍스트 라인을 받아들이므로 가장 먼저,
+    /// 설정 순서대로 시도합니다. LEEF도 syslog 헤더로 감싸 전송되는 경우가 많아 Syslog보다
+    /// 먼저 시도합니다.
+    ///
+    /// # Errors
+    ///
+    /// 사용자 정의 패턴을 컴파일할 수 없으면 에러를 반환합니다.
+    pub fn from_config(config: &ParsersConfig) -> Result<Self, LogPipelineError> {
+        let mut router = Self::new();
+        for pattern in &config.patterns {
+            router.parsers.push(Box::new(PatternParser::from_config(
+                pattern,
+                &config.grok_definitions,
+            )?));
+        }
+        if config.leef {
+            router.parsers.push(Box::new(LeefParser::default()));
+        }
+        router.preferred = router.parsers.len();
+        router.push_defaults();
+        Ok(router)
+    }
+
+    /// 기본 파서 세트 (Syslog + GELF + JSON)를 등록합니다.
+    fn push_defaults(&mut self) {
+        self.parsers.push(Box::new(SyslogParser::new()));
+        self.parsers.push(Box::new(GelfParser::default()));
+        self.parsers.push(Box::new(JsonLogParser::default()));
+    }
+
     /// 파서를 등록합니다. 등록 순서대로 시도됩니다.
     pub fn register(mut self, parser: Box<dyn LogParser>) -> Self {
         self.parsers.push(parser);
         self
     }
 
+    /// 소스 종류별 기본 형식 힌트를 지정합니다.
+    ///
+    /// 키는 `RawLog.source`의 `:` 앞부분(`file`, `syslog_udp`, `kafka` 등)이고,
+    /// 값은 파서 형식 이름입니다. `RawLog.format_hint`가 없을 때만 사용합니다.
+    pub fn with_source_hints(mut self, hints: BTreeMap<String, String>) -> Self {
+        self.source_hints = hints;
+        self
+    }
+
+    /// 수집된 원시 로그를 형식 힌트에 따라 파싱합니다.
+    ///
+    /// 힌트는 `RawLog.format_hint`, 없으면 소스 종류별 기본 힌트 순으로 정합니다.
+    pub fn parse_raw(&self, raw_log: &RawLog) -> Result<LogEntry, IronpostError> {
+        let hint = raw_log.format_hint.as_deref().or_else(|| {
+            let kind = raw_log.source.split(':').next().unwrap_or_default();
+            self.source_hints.get(kind).map(String::as_str)
+        });
+        self.parse_hinted(&raw_log.data, hint)
+    }
+
+    /// 힌트의 파서를 먼저 시도하고, 실패하면 나머지 파서로 자동 감지합니다.
+    ///
+    /// 설정으로 켠 파서(사용자 정의 패턴, LEEF)는 기본 파서보다 구체적이므로 힌트보다 먼저
+    /// 시도합니다. 힌트가 없거나 등록되지 않은 형식이면 [`parse`](Self::parse)와 같습니다.
+    pub fn parse_hinted(&self, raw: &[u8], hint: Option<&str>) -> Result<LogEntry, IronpostError> {
+        let Some(hinted) =
+            hint.and_then(|hint| self.parsers.iter().position(|p| p.format_name() == hint))
+        else {
+            return self.parse(raw);
+        };
+
+        // 힌트 파서가 선호 파서가 아니면 선호 파서 -> 힌트 파서 -> 나머지 순서
+        let preferred = if hinted < self.preferred {
+            0..0
+        } else {
+            0..self.preferred
+        };
+        let rest_start = preferred.end;
+        let order = preferred
+            .chain(std::iter::once(hinted))
+            .chain((rest_start..self.parsers.len()).filter(|index| *index != hinted));
+        self.parse_in_order(raw, order)
+    }
+
     /// 원시 로그 데이터를 파싱합니다.
     ///
     /// 등록된 파서를 순서대로 시도하여 첫 번째 성공 결과를 반환합니다.
     /// 모든 파서가 실패하면 마지막 에러를 반환합니다.
     pub fn parse(&self, raw: &[u8]) -> Result<LogEntry, IronpostError> {
+        self.parse_in_order(raw, 0..self.parsers.len())
+    }
+
+    /// 주어진 순서(파서 인덱스)대로 파서를 시도합니다.
+    fn parse_in_order(
+        &self,
+        raw: &[u8],
+        order: impl Iterator<Item = usize>,
+    ) -> Result<LogEntry, IronpostError> {
         if self.parsers.is_empty() {
             return Err(
                 LogPipelineError::UnsupportedFormat("no parsers registered".to_owned()).into(),
@@ -74,7 +182,7 @@ impl ParserRouter {
 
         let mut last_error = None;
 
-        for parser in &self.parsers {
+        for parser in order.filter_map(|index| self.parsers.get(index)) {
             match parser.parse(raw) {
                 Ok(entry) => return Ok(entry),
                 Err(e) => {
@@ -127,9 +235,155 @@ mod tests {
         let router = ParserRouter::with_defaults();
         let formats = router.registered_formats();
         assert!(formats.contains(&"syslog"));
+        assert!(formats.contains(&"gelf"));
         assert!(formats.contains(&"json"));
     }
 
+    #[test]
+    fn defaults_route_gelf_before_plain_json() {
+        let router = ParserRouter::with_defaults();
+        let gelf = router
+            .parse(br#"{"version":"1.1","host":"web-01","short_message":"hi","_user":"alice"}"#)
+            .unwrap();
+        assert_eq!(gelf.source, "gelf");
+
+        let json = router
+            .parse(br#"{"host":"web-01","message":"hi"}"#)
+            .unwrap();
+        assert_eq!(json.source, "json");
+    }
+
+    #[test]
+    fn leef_is_registered_only_when_enabled() {
+        let raw = b"<13>Jan 18 11:07:53 fw-01 LEEF:1.0|Acme|Firewall|2.1|deny|src=10.0.0.1\tsev=7";
+
+        let router = ParserRouter::with_defaults();
+        assert!(!router.registered_formats().contains(&"leef"));
+        assert_eq!(router.parse(raw).unwrap().source, "syslog");
+
+        let router = ParserRouter::from_config(&ParsersConfig {
+            leef: true,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(router.registered_formats()[0], "leef");
+        let entry = router.parse(raw).unwrap();
+        assert_eq!(entry.source, "leef");
+        assert_eq!(entry.hostname, "fw-01");
+
+        // LEEF가 아닌 syslog는 그대로 Syslog 파서로
+        let syslog = router
+            .parse(b"<34>1 2024-01-15T12:00:00Z host app - - - message")
+            .unwrap();
+        assert_eq!(syslog.source, "syslog");
+    }
+
+    #[test]
+    fn custom_patterns_are_tried_first() {
+        let config = ParsersConfig {
+            patterns: vec![ironpost_core::config::PatternParserConfig {
+                name: "billing".to_owned(),
+                pattern: r"^%{SYSLOGTIMESTAMP} %{HOSTNAME:host} billing: tenant=%{WORD:tenant}"
+                    .to_owned(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let router = ParserRouter::from_config(&config).unwrap();
+        assert_eq!(
+            router.registered_formats(),
+            vec!["billing", "syslog", "gelf", "json"]
+        );
+
+        let entry = router
+            .parse(b"Jan 18 11:07:53 app-01 billing: tenant=acme")
+            .unwrap();
+        assert_eq!(entry.source, "billing");
+        assert_eq!(entry.hostname, "app-01");
+
+        // 패턴과 맞지 않으면 기본 파서로
+        let syslog = router
+            .parse(b"<34>1 2024-01-15T12:00:00Z host app - - - message")
+            .unwrap();
+        assert_eq!(syslog.source, "syslog");
+        assert!(router.parse_with("billing", b"other").is_err());
+
+        let invalid = ParsersConfig {
+            patterns: vec![ironpost_core::config::PatternParserConfig {
+                name: "broken".to_owned(),
+                pattern: "%{NOPE}".to_owned(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(ParserRouter::from_config(&invalid).is_err());
+    }
+
+    #[test]
+    fn hint_is_tried_first_and_falls_back() {
+        let router = ParserRouter::with_defaults();
+        let gelf = br#"{"version":"1.1","host":"web-01","short_message":"hi"}"#;
+
+        // 자동 감지는 GELF, 힌트가 있으면 힌트 파서 우선
+        assert_eq!(router.parse(gelf).unwrap().source, "gelf");
+        assert_eq!(
+            router.parse_hinted(gelf, Some("json")).unwrap().source,
+            "json"
+        );
+
+        // 힌트 파서가 실패하면 자동 감지
+        let syslog = b"<34>1 2024-01-15T12:00:00Z host app - - - message";
+        assert_eq!(
+            router.parse_hinted(syslog, Some("json")).unwrap().source,
+            "syslog"
+        );
+        // 등록되지 않은 힌트는 무시
+        assert_eq!(
+            router.parse_hinted(syslog, Some("xml")).unwrap().source,
+            "syslog"
+        );
+        assert!(router.parse_hinted(b"not a log", Some("json")).is_err());
+    }
+
+    #[test]
+    fn configured_parsers_precede_hint() {
+        let router = ParserRouter::from_config(&ParsersConfig {
+            leef: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let leef = b"<13>Jan 18 11:07:53 fw-01 LEEF:1.0|Acme|Firewall|2.1|deny|sev=7";
+        assert_eq!(
+            router.parse_hinted(leef, Some("syslog")).unwrap().source,
+            "leef"
+        );
+        assert_eq!(
+            router
+                .parse_hinted(b"<34>1 2024-01-15T12:00:00Z host app - - - m", Some("leef"))
+                .unwrap()
+                .source,
+            "syslog"
+        );
+    }
+
+    #[test]
+    fn parse_raw_uses_source_hints() {
+        let router = ParserRouter::with_defaults()
+            .with_source_hints(BTreeMap::from([("file".to_owned(), "json".to_owned())]));
+        let data =
+            bytes::Bytes::from_static(br#"{"version":"1.1","host":"h","short_message":"m"}"#);
+
+        let from_file = RawLog::new(data.clone(), "file:/var/log/app.json");
+        assert_eq!(router.parse_raw(&from_file).unwrap().source, "json");
+
+        // RawLog의 힌트가 소스 기본값보다 우선
+        let hinted = RawLog::new(data.clone(), "file:/var/log/app.json").with_format_hint("gelf");
+        assert_eq!(router.parse_raw(&hinted).unwrap().source, "gelf");
+
+        let other = RawLog::new(data, "kafka:logs");
+        assert_eq!(router.parse_raw(&other).unwrap().source, "gelf");
+    }
+
     #[test]
     fn parse_with_unknown_format_returns_error() {
         let router = ParserRouter::with_defaults();
@@ -0,0 +1,568 @@
+//! 사용자 정의 패턴 파서 -- grok 문법 또는 이름 있는 그룹 정규식
+//!
+//! Rust 코드를 작성하지 않고 설정만으로 임의의 로그 형식을 구조화합니다.
+//! 패턴은 grok 문법(`%{SYNTAX:semantic}`)과 정규식을 섞어 쓸 수 있으며,
+//! 내장 grok 패턴([`GROK_BUILTIN_PATTERNS`])과 설정의 `grok_definitions`를 참조합니다.
+//!
+//! # 캡처 매핑
+//! | 캡처 이름 | `LogEntry` |
+//! |----------|-----------|
+//! | `timestamp` | `timestamp` (해석할 수 없으면 수신 시각, 원문은 `fields`) |
+//! | `host` | `hostname` |
+//! | `process` | `process` |
+//! | `message` | `message` (없으면 라인 전체) |
+//! | `level` | `severity` (JSON 파서와 같은 기준) |
+//! | 그 외 | `fields` |
+//!
+//! `%{NUMBER:bytes:int}`처럼 붙는 타입 접미사는 허용하지만 값은 항상 문자열로 저장합니다.
+//! 패턴은 라인의 일부와 일치해도 되므로, 전체 일치가 필요하면 `^`/`$`를 붙입니다.
+//!
+//! # 사용 예시
+//! ```ignore
+//! use ironpost_log_pipeline::parser::PatternParser;
+//! use ironpost_core::pipeline::LogParser;
+//!
+//! let parser = PatternParser::new(
+//!     "billing",
+//!     r"^%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} \[%{WORD:tenant}\] %{GREEDYDATA:message}$",
+//!     &Default::default(),
+//! )?;
+//! let entry = parser.parse(b"2024-01-15T12:00:00Z ERROR [acme] invoice failed")?;
+//! assert_eq!(entry.message, "invoice failed");
+//! ```
+
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use chrono::{DateTime, NaiveDateTime};
+use ironpost_core::config::PatternParserConfig;
+use ironpost_core::error::IronpostError;
+use ironpost_core::pipeline::LogParser;
+use ironpost_core::types::{LogEntry, Severity};
+use regex::Regex;
+
+use super::JsonLogParser;
+use crate::error::LogPipelineError;
+
+/// grok 패턴 참조의 최대 중첩 깊이
+const GROK_MAX_DEPTH: usize = 16;
+
+/// 컴파일된 정규식의 최대 크기 (바이트)
+const PATTERN_REGEX_SIZE_LIMIT: usize = 1024 * 1024;
+
+/// grok 캡처에 붙이는 내부 그룹 이름 접두사
+const GROK_GROUP_PREFIX: &str = "__grok";
+
+/// `timestamp_format`이 없을 때 추가로 시도하는 형식 (RFC 3339, Unix 시간 다음)
+const AUTO_TIMESTAMP_FORMATS: &[&str] = &[
+    "%d/%b/%Y:%H:%M:%S %z", // HTTPDATE
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+];
+
+/// 내장 grok 패턴 (Logstash grok 기본 패턴의 부분집합)
+///
+/// Rust 정규식은 전후방 탐색을 지원하지 않으므로 일부 패턴은 단순화했습니다.
+pub const GROK_BUILTIN_PATTERNS: &[(&str, &str)] = &[
+    ("USERNAME", r"[a-zA-Z0-9._-]+"),
+    ("USER", r"%{USERNAME}"),
+    ("EMAILLOCALPART", r"[a-zA-Z0-9._%+-]+"),
+    ("EMAILADDRESS", r"%{EMAILLOCALPART}@%{HOSTNAME}"),
+    ("INT", r"[+-]?[0-9]+"),
+    ("BASE10NUM", r"[+-]?(?:[0-9]+(?:\.[0-9]+)?|\.[0-9]+)"),
+    ("NUMBER", r"%{BASE10NUM}"),
+    ("BASE16NUM", r"[+-]?(?:0x)?[0-9A-Fa-f]+"),
+    ("POSINT", r"\b[1-9][0-9]*\b"),
+    ("NONNEGINT", r"\b[0-9]+\b"),
+    ("WORD", r"\b\w+\b"),
+    ("NOTSPACE", r"\S+"),
+    ("SPACE", r"\s*"),
+    ("DATA", r".*?"),
+    ("GREEDYDATA", r".*"),
+    ("QUOTEDSTRING", r#""(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'"#),
+    ("QS", r"%{QUOTEDSTRING}"),
+    (
+        "UUID",
+        r"[A-Fa-f0-9]{8}-[A-Fa-f0-9]{4}-[A-Fa-f0-9]{4}-[A-Fa-f0-9]{4}-[A-Fa-f0-9]{12}",
+    ),
+    ("MAC", r"(?:[A-Fa-f0-9]{2}[:-]){5}[A-Fa-f0-9]{2}"),
+    (
+        "IPV4",
+        r"(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])(?:\.(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])){3}",
+    ),
+    (
+        "IPV6",
+        r"(?:[A-Fa-f0-9]{0,4}:){2,7}(?:%{IPV4}|[A-Fa-f0-9]{0,4})(?:%[0-9A-Za-z]+)?",
+    ),
+    ("IP", r"%{IPV6}|%{IPV4}"),
+    (
+        "HOSTNAME",
+        r"\b[0-9A-Za-z][0-9A-Za-z-]{0,62}(?:\.[0-9A-Za-z][0-9A-Za-z-]{0,62})*\.?\b",
+    ),
+    ("IPORHOST", r"%{IP}|%{HOSTNAME}"),
+    ("HOSTPORT", r"%{IPORHOST}:%{POSINT}"),
+    ("UNIXPATH", r"(?:/[\w%!$@:.,+~-]*)+"),
+    ("PATH", r"%{UNIXPATH}"),
+    ("URIPROTO", r"[A-Za-z][A-Za-z0-9+.-]+"),
+    ("URIHOST", r"%{IPORHOST}(?::%{POSINT})?"),
+    ("URIPATH", r"(?:/[A-Za-z0-9$.+!*'(){},~:;=@#%&_-]*)+"),
+    ("URIPARAM", r"\?[A-Za-z0-9$.+!*'|(){},~@#%&/=:;_?\[\]-]*"),
+    ("URIPATHPARAM", r"%{URIPATH}(?:%{URIPARAM})?"),
+    (
+        "URI",
+        r"%{URIPROTO}://(?:%{USER}(?::[^@]*)?@)?(?:%{URIHOST})?(?:%{URIPATHPARAM})?",
+    ),
+    (
+        "MONTH",
+        r"\b(?:[Jj]an(?:uary)?|[Ff]eb(?:ruary)?|[Mm]ar(?:ch)?|[Aa]pr(?:il)?|[Mm]ay|[Jj]une?|[Jj]uly?|[Aa]ug(?:ust)?|[Ss]ep(?:tember)?|[Oo]ct(?:ober)?|[Nn]ov(?:ember)?|[Dd]ec(?:ember)?)\b",
+    ),
+    ("MONTHNUM", r"0?[1-9]|1[0-2]"),
+    ("MONTHDAY", r"0[1-9]|[12][0-9]|3[01]|[1-9]"),
+    (
+        "DAY",
+        r"Mon(?:day)?|Tue(?:sday)?|Wed(?:nesday)?|Thu(?:rsday)?|Fri(?:day)?|Sat(?:urday)?|Sun(?:day)?",
+    ),
+    ("YEAR", r"(?:\d\d){1,2}"),
+    ("HOUR", r"2[0123]|[01]?[0-9]"),
+    ("MINUTE", r"[0-5][0-9]"),
+    ("SECOND", r"(?:[0-5]?[0-9]|60)(?:[:.,][0-9]+)?"),
+    ("TIME", r"%{HOUR}:%{MINUTE}:%{SECOND}"),
+    ("ISO8601_TIMEZONE", r"Z|[+-]%{HOUR}(?::?%{MINUTE})"),
+    (
+        "TIMESTAMP_ISO8601",
+        r"%{YEAR}-%{MONTHNUM}-%{MONTHDAY}[T ]%{HOUR}:?%{MINUTE}(?::?%{SECOND})?%{ISO8601_TIMEZONE}?",
+    ),
+    ("DATE_US", r"%{MONTHNUM}[/-]%{MONTHDAY}[/-]%{YEAR}"),
+    ("DATE_EU", r"%{MONTHDAY}[./-]%{MONTHNUM}[./-]%{YEAR}"),
+    ("SYSLOGTIMESTAMP", r"%{MONTH} +%{MONTHDAY} %{TIME}"),
+    ("HTTPDATE", r"%{MONTHDAY}/%{MONTH}/%{YEAR}:%{TIME} %{INT}"),
+    (
+        "LOGLEVEL",
+        r"[Aa]lert|ALERT|[Tt]race|TRACE|[Dd]ebug|DEBUG|[Nn]otice|NOTICE|[Ii]nfo(?:rmation)?|INFO(?:RMATION)?|[Ww]arn(?:ing)?|WARN(?:ING)?|[Ee]rr(?:or)?|ERR(?:OR)?|[Cc]rit(?:ical)?|CRIT(?:ICAL)?|[Ff]atal|FATAL|[Ss]evere|SEVERE|[Ee]merg(?:ency)?|EMERG(?:ENCY)?",
+    ),
+    ("PROG", r"[\x21-\x5a\x5c\x5e-\x7e]+"),
+    ("SYSLOGPROG", r"%{PROG:process}(?:\[%{POSINT:pid}\])?"),
+    ("SYSLOGHOST", r"%{IPORHOST}"),
+    (
+        "SYSLOGBASE",
+        r"%{SYSLOGTIMESTAMP:timestamp} %{SYSLOGHOST:host} %{SYSLOGPROG}:",
+    ),
+    ("HTTPDUSER", r"%{EMAILADDRESS}|%{USER}"),
+    (
+        "COMMONAPACHELOG",
+        r#"%{IPORHOST:client_ip} %{HTTPDUSER:ident} %{USER:auth} \[%{HTTPDATE:timestamp}\] "(?:%{WORD:verb} %{NOTSPACE:request}(?: HTTP/%{NUMBER:http_version})?|%{DATA:raw_request})" %{NUMBER:response} (?:%{NUMBER:bytes}|-)"#,
+    ),
+    (
+        "COMBINEDAPACHELOG",
+        r"%{COMMONAPACHELOG} %{QS:referrer} %{QS:agent}",
+    ),
+];
+
+/// 사용자 정의 패턴 파서
+///
+/// 생성 시 grok 참조를 펼쳐 정규식 하나로 컴파일하고, 파싱할 때는 캡처를 `LogEntry`로
+/// 옮기기만 합니다. 패턴과 일치하지 않는 라인은 에러를 반환하므로
+/// [`ParserRouter`](super::ParserRouter)가 다음 파서를 시도합니다.
+pub struct PatternParser {
+    /// 파서 이름 (형식 이름)
+    name: String,
+    /// 컴파일된 정규식
+    regex: Regex,
+    /// (캡처 그룹 인덱스, 캡처 이름)
+    captures: Vec<(usize, String)>,
+    /// `timestamp` 캡처의 chrono 형식 (없으면 자동 감지)
+    timestamp_format: Option<String>,
+    /// 최대 허용 입력 크기 (바이트)
+    max_input_size: usize,
+}
+
+impl PatternParser {
+    /// 패턴을 컴파일하여 새 파서를 생성합니다.
+    ///
+    /// `definitions`는 내장 grok 패턴보다 먼저 참조됩니다.
+    ///
+    /// # Errors
+    ///
+    /// 정의되지 않은 grok 패턴, 순환 참조, 잘못된 정규식이면 에러를 반환합니다.
+    pub fn new(
+        name: impl Into<String>,
+        pattern: &str,
+        definitions: &BTreeMap<String, String>,
+    ) -> Result<Self, LogPipelineError> {
+        let name = name.into();
+        let mut semantics = Vec::new();
+        let expanded = expand_grok(pattern, definitions, &mut semantics, 0)
+            .map_err(|reason| Self::config_error(&name, reason))?;
+        let regex = regex::RegexBuilder::new(&expanded)
+            .size_limit(PATTERN_REGEX_SIZE_LIMIT)
+            .build()
+            .map_err(|e| Self::config_error(&name, e.to_string()))?;
+
+        let captures = regex
+            .capture_names()
+            .enumerate()
+            .filter_map(|(index, group)| {
+                let group = group?;
+                let semantic = group
+                    .strip_prefix(GROK_GROUP_PREFIX)
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .and_then(|n| semantics.get(n).cloned())
+                    .unwrap_or_else(|| group.to_owned());
+                Some((index, semantic))
+            })
+            .collect();
+
+        Ok(Self {
+            name,
+            regex,
+            captures,
+            timestamp_format: None,
+            max_input_size: 1024 * 1024, // 1MB
+        })
+    }
+
+    /// 설정에서 파서를 생성합니다.
+    pub fn from_config(
+        config: &PatternParserConfig,
+        definitions: &BTreeMap<String, String>,
+    ) -> Result<Self, LogPipelineError> {
+        let parser = Self::new(config.name.clone(), &config.pattern, definitions)?;
+        Ok(if config.timestamp_format.is_empty() {
+            parser
+        } else {
+            parser.with_timestamp_format(config.timestamp_format.clone())
+        })
+    }
+
+    /// `timestamp` 캡처의 chrono 형식을 지정합니다 (예: `%d/%b/%Y:%H:%M:%S %z`).
+    pub fn with_timestamp_format(mut self, format: impl Into<String>) -> Self {
+        self.timestamp_format = Some(format.into());
+        self
+    }
+
+    /// 최대 입력 크기를 설정합니다.
+    pub fn with_max_input_size(mut self, size: usize) -> Self {
+        self.max_input_size = size;
+        self
+    }
+
+    /// 로그 라인을 패턴으로 파싱하여 `LogEntry`를 생성합니다.
+    fn parse_line(&self, raw: &[u8]) -> Result<LogEntry, LogPipelineError> {
+        if raw.len() > self.max_input_size {
+            return Err(self.parse_error(
+                0,
+                format!(
+                    "input too large: {} bytes (max: {})",
+                    raw.len(),
+                    self.max_input_size
+                ),
+            ));
+        }
+        let line = std::str::from_utf8(raw)
+            .map_err(|e| self.parse_error(e.valid_up_to(), "invalid UTF-8".to_owned()))?
+            .trim_end_matches(['\r', '\n']);
+        let captures = self
+            .regex
+            .captures(line)
+            .ok_or_else(|| self.parse_error(0, "pattern did not match".to_owned()))?;
+
+        let mut timestamp = None;
+        let mut hostname = None;
+        let mut process = None;
+        let mut message = None;
+        let mut severity = None;
+        let mut fields = Vec::new();
+        for (index, name) in &self.captures {
+            let Some(value) = captures.get(*index).map(|m| m.as_str()) else {
+                continue;
+            };
+            let slot = match name.as_str() {
+                "timestamp" => match self.parse_timestamp(value) {
+                    Some(parsed) => {
+                        timestamp.get_or_insert(parsed);
+                        continue;
+                    }
+                    // 해석하지 못한 시각은 원문을 fields에 남김
+                    None => None,
+                },
+                "host" => Some(&mut hostname),
+                "process" => Some(&mut process),
+                "message" => Some(&mut message),
+                "level" => {
+                    severity.get_or_insert(JsonLogParser::level_to_severity(value));
+                    continue;
+                }
+                _ => None,
+            };
+            match slot {
+                Some(slot) => {
+                    slot.get_or_insert_with(|| value.to_owned());
+                }
+                None => fields.push((name.clone(), value.to_owned())),
+            }
+        }
+
+        Ok(LogEntry {
+            source: self.name.clone(),
+            timestamp: timestamp.unwrap_or_else(SystemTime::now),
+            hostname: hostname.unwrap_or_default(),
+            process: process.unwrap_or_default(),
+            message: message.unwrap_or_else(|| line.to_owned()),
+            severity: severity.unwrap_or(Severity::Info),
+            fields,
+        })
+    }
+
+    /// `timestamp` 캡처를 변환합니다.
+    ///
+    /// 형식이 지정되면 시간대가 있는 형식, 없는 형식(UTC) 순으로 시도합니다.
+    /// 형식이 없으면 RFC 3339, Unix 시간, [`AUTO_TIMESTAMP_FORMATS`] 순으로 시도합니다.
+    fn parse_timestamp(&self, value: &str) -> Option<SystemTime> {
+        let parse_with = |format: &str| {
+            DateTime::parse_from_str(value, format)
+                .map(SystemTime::from)
+                .ok()
+                .or_else(|| {
+                    NaiveDateTime::parse_from_str(value, format)
+                        .ok()
+                        .map(|naive| naive.and_utc().into())
+                })
+        };
+        match &self.timestamp_format {
+            Some(format) => parse_with(format),
+            None => JsonLogParser::parse_timestamp(value)
+                .ok()
+                .or_else(|| AUTO_TIMESTAMP_FORMATS.iter().find_map(|f| parse_with(f))),
+        }
+    }
+
+    fn parse_error(&self, offset: usize, reason: String) -> LogPipelineError {
+        LogPipelineError::Parse {
+            format: self.name.clone(),
+            offset,
+            reason,
+        }
+    }
+
+    fn config_error(name: &str, reason: String) -> LogPipelineError {
+        LogPipelineError::Config {
+            field: format!("parsers.patterns.{}", name),
+            reason,
+        }
+    }
+}
+
+impl LogParser for PatternParser {
+    fn format_name(&self) -> &str {
+        &self.name
+    }
+
+    fn parse(&self, raw: &[u8]) -> Result<LogEntry, IronpostError> {
+        self.parse_line(raw).map_err(IronpostError::from)
+    }
+}
+
+/// grok 참조(`%{SYNTAX}`, `%{SYNTAX:semantic}`, `%{SYNTAX:semantic:type}`)를 정규식으로 펼칩니다.
+///
+/// 이름이 있는 참조는 `__grok<n>` 그룹이 되고, `semantics[n]`에 캡처 이름을 기록합니다.
+/// 같은 캡처 이름을 여러 번 써도 정규식 그룹 이름이 겹치지 않습니다.
+fn expand_grok(
+    pattern: &str,
+    definitions: &BTreeMap<String, String>,
+    semantics: &mut Vec<String>,
+    depth: usize,
+) -> Result<String, String> {
+    if depth > GROK_MAX_DEPTH {
+        return Err(format!(
+            "grok patterns nested deeper than {} levels (recursive definition?)",
+            GROK_MAX_DEPTH
+        ));
+    }
+
+    let mut expanded = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find("%{") {
+        expanded.push_str(&rest[..start]);
+        let reference = &rest[start + 2..];
+        let end = reference
+            .find('}')
+            .ok_or_else(|| "unterminated grok reference '%{'".to_owned())?;
+        let mut parts = reference[..end].splitn(3, ':');
+        let syntax = parts.next().unwrap_or_default();
+        let semantic = parts.next().filter(|s| !s.is_empty());
+
+        let definition = definitions
+            .get(syntax)
+            .map(String::as_str)
+            .or_else(|| {
+                GROK_BUILTIN_PATTERNS
+                    .iter()
+                    .find(|(name, _)| *name == syntax)
+                    .map(|(_, definition)| *definition)
+            })
+            .ok_or_else(|| format!("unknown grok pattern '{}'", syntax))?;
+        let inner = expand_grok(definition, definitions, semantics, depth + 1)?;
+
+        match semantic {
+            Some(semantic) => {
+                expanded.push_str(&format!(
+                    "(?P<{}{}>{})",
+                    GROK_GROUP_PREFIX,
+                    semantics.len(),
+                    inner
+                ));
+                semantics.push(semantic.to_owned());
+            }
+            None => {
+                expanded.push_str("(?:");
+                expanded.push_str(&inner);
+                expanded.push(')');
+            }
+        }
+        rest = &reference[end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn field<'a>(entry: &'a LogEntry, name: &str) -> Option<&'a str> {
+        entry
+            .fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn parser(pattern: &str) -> PatternParser {
+        PatternParser::new("custom", pattern, &BTreeMap::new()).unwrap()
+    }
+
+    #[test]
+    fn all_builtin_patterns_compile() {
+        for (name, _) in GROK_BUILTIN_PATTERNS {
+            let pattern = format!("%{{{}:value}}", name);
+            assert!(
+                PatternParser::new("custom", &pattern, &BTreeMap::new()).is_ok(),
+                "builtin pattern {} failed to compile",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn grok_pattern_maps_entry_fields() {
+        let parser = parser(
+            r"^%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{HOSTNAME:host} %{WORD:process}\[%{POSINT:pid:int}\]: %{GREEDYDATA:message}$",
+        );
+        let entry = parser
+            .parse(b"2024-01-15T12:00:00Z ERROR web-01 billing[42]: invoice failed for 10.0.0.7\n")
+            .unwrap();
+
+        assert_eq!(entry.source, "custom");
+        assert_eq!(parser.format_name(), "custom");
+        assert_eq!(
+            entry.timestamp,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_705_320_000)
+        );
+        assert_eq!(entry.severity, Severity::Medium);
+        assert_eq!(entry.hostname, "web-01");
+        assert_eq!(entry.process, "billing");
+        assert_eq!(entry.message, "invoice failed for 10.0.0.7");
+        assert_eq!(entry.fields, vec![("pid".to_owned(), "42".to_owned())]);
+    }
+
+    #[test]
+    fn plain_regex_named_groups() {
+        let parser = parser(r"^user=(?P<user>\w+) action=(?<action>\w+)");
+        let entry = parser.parse(b"user=alice action=login extra").unwrap();
+
+        assert_eq!(field(&entry, "user"), Some("alice"));
+        assert_eq!(field(&entry, "action"), Some("login"));
+        // message 캡처가 없으면 라인 전체
+        assert_eq!(entry.message, "user=alice action=login extra");
+        assert_eq!(entry.severity, Severity::Info);
+    }
+
+    #[test]
+    fn combined_apache_log() {
+        let parser = parser("^%{COMBINEDAPACHELOG}$");
+        let raw = br#"203.0.113.7 - frank [10/Oct/2000:13:55:36 -0700] "GET /admin.php?id=1 HTTP/1.1" 404 2326 "-" "curl/8.0""#;
+        let entry = parser.parse(raw).unwrap();
+
+        assert_eq!(
+            entry.timestamp,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(971_211_336)
+        );
+        assert_eq!(field(&entry, "client_ip"), Some("203.0.113.7"));
+        assert_eq!(field(&entry, "auth"), Some("frank"));
+        assert_eq!(field(&entry, "verb"), Some("GET"));
+        assert_eq!(field(&entry, "request"), Some("/admin.php?id=1"));
+        assert_eq!(field(&entry, "response"), Some("404"));
+        assert_eq!(field(&entry, "agent"), Some("\"curl/8.0\""));
+    }
+
+    #[test]
+    fn user_definitions_and_timestamp_format() {
+        let definitions = BTreeMap::from([
+            ("TENANT".to_owned(), r"[a-z]+-[0-9]+".to_owned()),
+            // 내장 패턴 재정의
+            ("WORD".to_owned(), r"[A-Z]+".to_owned()),
+        ]);
+        let parser = PatternParser::new(
+            "billing",
+            r"^%{DATA:timestamp} \| %{TENANT:tenant} \| %{WORD:code}$",
+            &definitions,
+        )
+        .unwrap()
+        .with_timestamp_format("%d.%m.%Y %H:%M:%S");
+
+        let entry = parser
+            .parse(b"15.01.2024 12:00:00 | acme-42 | PAYMENT")
+            .unwrap();
+        assert_eq!(
+            entry.timestamp,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_705_320_000)
+        );
+        assert_eq!(field(&entry, "tenant"), Some("acme-42"));
+        assert_eq!(field(&entry, "code"), Some("PAYMENT"));
+        assert!(
+            parser
+                .parse(b"15.01.2024 12:00:00 | acme-42 | payment")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn unparsed_timestamp_is_kept_in_fields() {
+        let entry = parser(r"^%{SYSLOGBASE} %{GREEDYDATA:message}")
+            .parse(b"Jan 18 11:07:53 fw-01 sshd[812]: Failed password")
+            .unwrap();
+        assert_eq!(entry.hostname, "fw-01");
+        assert_eq!(entry.process, "sshd");
+        assert_eq!(entry.message, "Failed password");
+        assert_eq!(field(&entry, "timestamp"), Some("Jan 18 11:07:53"));
+        assert_eq!(field(&entry, "pid"), Some("812"));
+    }
+
+    #[test]
+    fn invalid_patterns_fail_to_compile() {
+        let definitions = BTreeMap::from([("LOOP".to_owned(), "a%{LOOP}".to_owned())]);
+        assert!(PatternParser::new("p", "%{NOPE:x}", &BTreeMap::new()).is_err());
+        assert!(PatternParser::new("p", "%{WORD:x", &BTreeMap::new()).is_err());
+        assert!(PatternParser::new("p", "(unclosed", &BTreeMap::new()).is_err());
+        assert!(PatternParser::new("p", "%{LOOP}", &definitions).is_err());
+    }
+
+    #[test]
+    fn non_matching_and_oversized_input_fail() {
+        let parser = parser(r"^%{IPV4:src_ip} ").with_max_input_size(32);
+        assert!(parser.parse(b"not an address").is_err());
+        assert!(parser.parse(b"10.0.0.1 ok").is_ok());
+        assert!(parser.parse(&[b'x'; 64]).is_err());
+    }
+}
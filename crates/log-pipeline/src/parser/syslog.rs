@@ -69,7 +69,7 @@ impl SyslogParser {
     /// - 5 Notice -> Low
     /// - 6 Informational -> Info
     /// - 7 Debug -> Info
-    fn syslog_severity_to_ironpost(syslog_severity: u8) -> Severity {
+    pub(crate) fn syslog_severity_to_ironpost(syslog_severity: u8) -> Severity {
         match syslog_severity {
             0..=2 => Severity::Critical,
             3 => Severity::High,
@@ -6,10 +6,15 @@
 //! # 내부 아키텍처
 //! ```text
 //! Collectors -> mpsc -> Buffer -> Parser -> RuleEngine -> AlertGenerator -> mpsc -> downstream
+//!                                 |                                        \-> AlertAdmission -> AlertSinks (webhook 등)
+//!                                 \-> Output (Elasticsearch, Kafka, 로그 + 알림)
 //! ```
+//!
+//! 알림은 [`AlertAdmission`] 게이트(설정된 경우)를 통과해야 싱크와 출력에 전달됩니다.
+//! 종료 시에는 수집/처리 태스크를 먼저 멈추고 버퍼에 남은 로그를 처리한 뒤,
+//! 싱크/출력 큐를 닫고 남은 항목을 제한 시간 안에 전송합니다.
 
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
@@ -22,11 +27,17 @@ use ironpost_core::error::IronpostError;
 use ironpost_core::event::{AlertEvent, MODULE_LOG_PIPELINE, PacketEvent};
 use ironpost_core::metrics as m;
 use ironpost_core::pipeline::{HealthStatus, Pipeline};
-use ironpost_core::plugin::{Plugin, PluginInfo, PluginState, PluginType};
+use ironpost_core::plugin::{
+    ChannelUsage, Plugin, PluginInfo, PluginState, PluginType, ResourceUsage,
+};
+use ironpost_core::types::Severity;
 
 use crate::alert::AlertGenerator;
-use crate::buffer::LogBuffer;
+use crate::buffer::{DiskSpill, LogBuffer};
 use crate::collector::file::FileCollectorConfig;
+use crate::collector::http::{HttpCollector, HttpCollectorConfig};
+#[cfg(feature = "kafka")]
+use crate::collector::kafka::{KafkaCollector, KafkaCollectorConfig};
 use crate::collector::syslog_tcp::SyslogTcpConfig;
 use crate::collector::syslog_udp::SyslogUdpConfig;
 use crate::collector::{
@@ -34,9 +45,20 @@ use crate::collector::{
     SyslogUdpCollector,
 };
 use crate::config::PipelineConfig;
+use crate::enrich::{EnrichmentStage, LogEnricher};
 use crate::error::LogPipelineError;
+use crate::output::{LogStore, OutputHandle, OutputSet};
 use crate::parser::ParserRouter;
-use crate::rule::RuleEngine;
+use crate::rule::watcher::{RuleReloadError, RuleWatcher};
+use crate::rule::{RuleEngine, RuleMatch, SuppressionList};
+use crate::sink::{
+    AlertAdmission, AlertSink, AlertSinkSet, DEFAULT_SINK_QUEUE_SIZE, SinkAlert, SinkDispatcher,
+    SinkFilter,
+};
+use crate::transform::TransformChain;
+
+/// 종료 시 싱크/출력 큐 드레인 제한 시간
+const DELIVERY_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// 파이프라인 실행 상태
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -77,8 +99,14 @@ pub struct LogPipeline {
     state: PipelineState,
     /// 파서 라우터 (공유)
     parser: Arc<ParserRouter>,
+    /// 필드 변환 단계 (파싱 직후, 보강 전)
+    transforms: TransformChain,
+    /// 로그 보강 단계 (파싱과 규칙 매칭 사이)
+    enrichment: EnrichmentStage,
     /// 규칙 엔진 (공유)
     rule_engine: Arc<Mutex<RuleEngine>>,
+    /// 마지막 규칙 리로드 에러 (`watch_rules` 사용 시, health check 공유)
+    rule_reload_error: RuleReloadError,
     /// 알림 생성기 (공유)
     alert_generator: Arc<Mutex<AlertGenerator>>,
     /// 로그 버퍼
@@ -93,6 +121,18 @@ pub struct LogPipeline {
     raw_log_tx: mpsc::Sender<RawLog>,
     /// 알림 전송 채널 (파이프라인 -> downstream)
     alert_tx: mpsc::Sender<AlertEvent>,
+    /// 알림 싱크 (webhook 등)
+    sinks: AlertSinkSet,
+    /// 실행 중인 싱크 큐 디스패처 (정지 상태에서는 비어 있음)
+    sink_dispatcher: SinkDispatcher,
+    /// 싱크/출력 전달 전 알림 게이트 (데몬의 중복 제거/폭주 제한)
+    admission: Option<Arc<dyn AlertAdmission>>,
+    /// 첫 시작 시 싱크/출력에 다시 보낼 알림 (데몬 이벤트 저널)
+    replay_alerts: Vec<AlertEvent>,
+    /// 출력 (Elasticsearch, Kafka)
+    outputs: OutputSet,
+    /// 실행 중인 출력 큐 핸들 (정지 상태에서는 비어 있음)
+    output_handle: OutputHandle,
     /// PacketEvent 수신 채널 (ebpf-engine -> 파이프라인, daemon에서 연결)
     packet_rx: Option<mpsc::Receiver<PacketEvent>>,
     /// 백그라운드 태스크 핸들
@@ -101,6 +141,10 @@ pub struct LogPipeline {
     event_receiver_task: Option<tokio::task::JoinHandle<Option<mpsc::Receiver<PacketEvent>>>>,
     /// Cancellation token for graceful shutdown
     cancel_token: CancellationToken,
+    /// 싱크/출력 전송 태스크 핸들 (수집/처리 태스크보다 나중에 종료)
+    delivery_tasks: Vec<tokio::task::JoinHandle<()>>,
+    /// 싱크/출력 태스크 강제 종료 토큰 (큐 드레인 제한 시간 초과 시)
+    delivery_cancel: CancellationToken,
     /// 파싱 에러 카운터 (공유)
     parse_error_count: Arc<AtomicU64>,
     /// 처리된 로그 카운터 (공유)
@@ -145,6 +189,13 @@ impl LogPipeline {
         self.buffer.lock().await.utilization()
     }
 
+    /// 내장 로그 저장소를 반환합니다 (`log_store.enabled`일 때).
+    ///
+    /// 저장된 로그를 [`LogStore::search`]로 검색할 수 있습니다.
+    pub fn log_store(&self) -> Option<Arc<LogStore>> {
+        self.outputs.log_store()
+    }
+
     /// 규칙 엔진에 대한 Arc 참조를 반환합니다.
     pub fn rule_engine_arc(&self) -> Arc<Mutex<RuleEngine>> {
         Arc::clone(&self.rule_engine)
@@ -163,13 +214,36 @@ impl LogPipeline {
         self.raw_log_tx.clone()
     }
 
-    /// 배치를 처리합니다: 파싱 -> 규칙 매칭 -> 알림 생성
+    /// 싱크/출력 태스크가 큐에 남은 알림과 문서를 모두 전송하고 종료할 때까지 기다립니다.
+    ///
+    /// 송신측이 모두 닫힌 뒤 호출해야 합니다. [`DELIVERY_DRAIN_TIMEOUT`] 안에 끝나지 않으면
+    /// 태스크를 취소하며, 취소된 태스크는 보류 알림만 flush하고 종료합니다.
+    async fn drain_delivery_tasks(&mut self) {
+        let deadline = Instant::now() + DELIVERY_DRAIN_TIMEOUT;
+        for mut task in std::mem::take(&mut self.delivery_tasks) {
+            if tokio::time::timeout_at(deadline, &mut task).await.is_err() {
+                if !self.delivery_cancel.is_cancelled() {
+                    tracing::warn!(
+                        timeout_secs = DELIVERY_DRAIN_TIMEOUT.as_secs(),
+                        "timed out draining alert sinks and outputs, cancelling"
+                    );
+                    self.delivery_cancel.cancel();
+                }
+                let _ = task.await;
+            }
+        }
+    }
+
+    /// 배치를 처리합니다: 파싱 -> 변환 -> 보강 -> 규칙 매칭 -> 알림 생성
     async fn process_batch(&self, batch: Vec<RawLog>) {
         for raw_log in batch {
             // 1. 파싱
-            let log_entry = match self.parser.parse(&raw_log.data) {
-                Ok(entry) => {
+            let log_entry = match self.parser.parse_raw(&raw_log) {
+                Ok(mut entry) => {
                     self.processed_count.fetch_add(1, Ordering::Relaxed);
+                    self.transforms.apply(&mut entry);
+                    self.enrichment.apply(&mut entry).await;
+                    self.output_handle.send_log(&entry);
                     entry
                 }
                 Err(e) => {
@@ -183,7 +257,7 @@ impl LogPipeline {
                 }
             };
 
-            // 2. 규칙 매칭
+            // 2. 규칙 매칭 (보강 필드 포함)
             match self.rule_engine.lock().await.evaluate(&log_entry) {
                 Ok(matches) => {
                     // 3. 알림 생성
@@ -192,6 +266,13 @@ impl LogPipeline {
                         if let Some(alert_event) = alert_gen.generate(&rule_match, None) {
                             drop(alert_gen); // unlock before send
                             // 4. 알림 전송
+                            deliver_alert(
+                                self.admission.as_deref(),
+                                &self.sink_dispatcher,
+                                &self.output_handle,
+                                &alert_event,
+                                &rule_match,
+                            );
                             if let Err(e) = self.alert_tx.send(alert_event).await {
                                 tracing::error!(error = %e, "failed to send alert event");
                             }
@@ -276,10 +357,7 @@ impl LogPipeline {
         let tx = self.raw_log_tx.clone();
         let cancel = self.cancel_token.clone();
         let statuses = Arc::clone(&self.collector_statuses);
-        let config = FileCollectorConfig {
-            watch_paths: self.config.watch_paths.iter().map(PathBuf::from).collect(),
-            ..FileCollectorConfig::default()
-        };
+        let config = FileCollectorConfig::from_core(&self.config.watch_paths, &self.config.file);
 
         let handle = tokio::spawn(async move {
             Self::set_collector_status(&statuses, "file", CollectorStatus::Running).await;
@@ -304,6 +382,81 @@ impl LogPipeline {
         self.tasks.push(handle);
     }
 
+    /// 규칙 디렉토리 감시자를 생성하고 현재 규칙 파일 지문을 기록합니다.
+    ///
+    /// 초기 규칙 로드 전에 호출하여, 로드와 감시 시작 사이의 변경도 놓치지 않도록 합니다.
+    async fn prepare_rule_watcher(&self) -> RuleWatcher {
+        let mut watcher = RuleWatcher::new(
+            &self.config.rule_dir,
+            Arc::clone(&self.rule_engine),
+            Arc::clone(&self.rule_reload_error),
+            Duration::from_secs(self.config.rule_reload_secs),
+        );
+        watcher.snapshot().await;
+        watcher
+    }
+
+    /// HTTP 수집기를 spawn합니다.
+    fn spawn_http_collector(&mut self) {
+        let tx = self.raw_log_tx.clone();
+        let cancel = self.cancel_token.clone();
+        let statuses = Arc::clone(&self.collector_statuses);
+        let config = HttpCollectorConfig::from_core(&self.config.http);
+
+        let handle = tokio::spawn(async move {
+            Self::set_collector_status(&statuses, "http", CollectorStatus::Running).await;
+            let mut collector = HttpCollector::new(config, tx, cancel);
+            if let Err(e) = collector.run().await {
+                tracing::error!(
+                    collector = "http",
+                    error = %e,
+                    "http collector terminated with error"
+                );
+                Self::set_collector_status(
+                    &statuses,
+                    "http",
+                    CollectorStatus::Error(e.to_string()),
+                )
+                .await;
+            } else {
+                Self::set_collector_status(&statuses, "http", CollectorStatus::Stopped).await;
+            }
+        });
+        self.collectors.register("http");
+        self.tasks.push(handle);
+    }
+
+    /// Kafka 수집기를 spawn합니다.
+    #[cfg(feature = "kafka")]
+    fn spawn_kafka_collector(&mut self) {
+        let tx = self.raw_log_tx.clone();
+        let cancel = self.cancel_token.clone();
+        let statuses = Arc::clone(&self.collector_statuses);
+        let config = KafkaCollectorConfig::from_core(&self.config.kafka);
+
+        let handle = tokio::spawn(async move {
+            Self::set_collector_status(&statuses, "kafka", CollectorStatus::Running).await;
+            let mut collector = KafkaCollector::new(config, tx, cancel);
+            if let Err(e) = collector.run().await {
+                tracing::error!(
+                    collector = "kafka",
+                    error = %e,
+                    "kafka collector terminated with error"
+                );
+                Self::set_collector_status(
+                    &statuses,
+                    "kafka",
+                    CollectorStatus::Error(e.to_string()),
+                )
+                .await;
+            } else {
+                Self::set_collector_status(&statuses, "kafka", CollectorStatus::Stopped).await;
+            }
+        });
+        self.collectors.register("kafka");
+        self.tasks.push(handle);
+    }
+
     /// eBPF EventReceiver를 spawn합니다.
     ///
     /// EventReceiver는 graceful shutdown 시 packet_rx를 반환하여
@@ -358,7 +511,24 @@ impl Pipeline for LogPipeline {
 
         self.collector_statuses.write().await.clear();
 
-        // 1. 규칙 로드
+        // 디스크 오버플로 버퍼 (이전 실행에서 남은 로그를 메모리 버퍼 다음에 처리)
+        if self.config.disk_buffer.enabled && !self.buffer.lock().await.has_disk_spill() {
+            let spill = DiskSpill::open(&self.config.disk_buffer)
+                .await
+                .map_err(|e| LogPipelineError::Config {
+                    field: "disk_buffer.dir".to_owned(),
+                    reason: format!("failed to open disk buffer: {}", e),
+                })?;
+            self.buffer.lock().await.attach_disk_spill(spill);
+        }
+
+        // 1. 규칙 로드 (hot reload 사용 시 감시자를 먼저 준비)
+        *self.rule_reload_error.write().await = None;
+        let rule_watcher = if self.config.watch_rules {
+            Some(self.prepare_rule_watcher().await)
+        } else {
+            None
+        };
         let rule_count = self
             .rule_engine
             .lock()
@@ -368,6 +538,42 @@ impl Pipeline for LogPipeline {
             .map_err(IronpostError::from)?;
         tracing::info!(rules = rule_count, "loaded detection rules");
 
+        if !self.config.suppressions_file.is_empty() {
+            let suppressions = SuppressionList::load_file(&self.config.suppressions_file)
+                .await
+                .map_err(IronpostError::from)?;
+            self.rule_engine.lock().await.set_suppressions(suppressions);
+        }
+
+        if let Some(watcher) = rule_watcher {
+            let handle = tokio::spawn(watcher.run(self.cancel_token.clone()));
+            self.tasks.push(handle);
+        }
+
+        if !self.sinks.is_empty() {
+            let (dispatcher, handles) = self.sinks.spawn(self.delivery_cancel.clone());
+            self.sink_dispatcher = dispatcher;
+            self.delivery_tasks.extend(handles);
+            tracing::info!(sinks = ?self.sinks.names(), "started alert sinks");
+        }
+
+        if !self.outputs.is_empty() {
+            let (handle, tasks) = self.outputs.spawn(self.delivery_cancel.clone());
+            self.output_handle = handle;
+            self.delivery_tasks.extend(tasks);
+            tracing::info!(outputs = ?self.outputs.names(), "started outputs");
+        }
+
+        let replay = std::mem::take(&mut self.replay_alerts);
+        if !replay.is_empty() {
+            tracing::info!(alerts = replay.len(), "replaying journaled alerts to sinks");
+            for alert in &replay {
+                self.sink_dispatcher
+                    .dispatch(&SinkAlert::new(alert.clone()));
+                self.output_handle.send_alert(alert);
+            }
+        }
+
         // 2. 수집기 태스크 스폰
         let mut spawned_collectors = HashSet::new();
         let sources = self.config.sources.clone();
@@ -398,6 +604,24 @@ impl Pipeline for LogPipeline {
                         self.spawn_file_collector();
                     }
                 }
+                "http" => {
+                    if spawned_collectors.insert("http") {
+                        self.spawn_http_collector();
+                    }
+                }
+                #[cfg(feature = "kafka")]
+                "kafka" => {
+                    if spawned_collectors.insert("kafka") {
+                        self.spawn_kafka_collector();
+                    }
+                }
+                #[cfg(not(feature = "kafka"))]
+                "kafka" => {
+                    tracing::warn!(
+                        source = "kafka",
+                        "built without the `kafka` feature, skipping collector source"
+                    );
+                }
                 unknown => {
                     tracing::warn!(source = unknown, "unknown collector source, skipping");
                 }
@@ -437,6 +661,11 @@ impl Pipeline for LogPipeline {
         let alert_generator = Arc::clone(&self.alert_generator);
         let buffer = Arc::clone(&self.buffer);
         let alert_tx = self.alert_tx.clone();
+        let admission = self.admission.clone();
+        let sink_dispatcher = self.sink_dispatcher.clone();
+        let output_handle = self.output_handle.clone();
+        let transforms = self.transforms.clone();
+        let enrichment = self.enrichment.clone();
         let parse_error_count = Arc::clone(&self.parse_error_count);
         let processed_count = Arc::clone(&self.processed_count);
         let cancel = self.cancel_token.clone();
@@ -456,13 +685,13 @@ impl Pipeline for LogPipeline {
                         match result {
                             Some(raw_log) => {
                                 let mut buf = buffer.lock().await;
-                                if buf.push(raw_log) {
+                                if buf.push(raw_log).await {
                                     metrics::counter!(m::LOG_PIPELINE_LOGS_DROPPED_TOTAL).increment(1);
                                 }
 
                                 // 배치 크기 도달 시 즉시 플러시
                                 if buf.should_flush(batch_size) {
-                                    let batch = buf.drain_batch(batch_size);
+                                    let batch = buf.drain_batch(batch_size).await;
                                     let buffer_size_snapshot = buf.len();
                                     drop(buf); // unlock buffer before processing
 
@@ -474,10 +703,13 @@ impl Pipeline for LogPipeline {
                                     for raw_log in batch {
                                         metrics::counter!(m::LOG_PIPELINE_LOGS_COLLECTED_TOTAL).increment(1);
 
-                                        match parser.parse(&raw_log.data) {
-                                            Ok(log_entry) => {
+                                        match parser.parse_raw(&raw_log) {
+                                            Ok(mut log_entry) => {
                                                 processed_count.fetch_add(1, Ordering::Relaxed);
                                                 metrics::counter!(m::LOG_PIPELINE_LOGS_PROCESSED_TOTAL).increment(1);
+                                                transforms.apply(&mut log_entry);
+                                                enrichment.apply(&mut log_entry).await;
+                                                output_handle.send_log(&log_entry);
 
                                                 match rule_engine.lock().await.evaluate(&log_entry) {
                                                     Ok(matches) => {
@@ -491,6 +723,7 @@ impl Pipeline for LogPipeline {
                                                                 None,
                                                             ) {
                                                                 drop(alert_gen);
+                                                                deliver_alert(admission.as_deref(), &sink_dispatcher, &output_handle, &alert_event, &rule_match);
                                                                 match alert_tx.send(alert_event).await {
                                                                     Ok(()) => {
                                                                         metrics::counter!(m::LOG_PIPELINE_ALERTS_SENT_TOTAL).increment(1);
@@ -539,7 +772,7 @@ impl Pipeline for LogPipeline {
                     _ = flush_timer.tick() => {
                         let mut buf = buffer.lock().await;
                         if !buf.is_empty() && last_flush.elapsed() >= Duration::from_millis(flush_interval_ms) {
-                            let batch = buf.drain_all();
+                            let batch = buf.drain_all().await;
                             let buffer_size_snapshot = buf.len();
                             drop(buf);
 
@@ -551,10 +784,13 @@ impl Pipeline for LogPipeline {
                             for raw_log in batch {
                                 metrics::counter!(m::LOG_PIPELINE_LOGS_COLLECTED_TOTAL).increment(1);
 
-                                match parser.parse(&raw_log.data) {
-                                    Ok(log_entry) => {
+                                match parser.parse_raw(&raw_log) {
+                                    Ok(mut log_entry) => {
                                         processed_count.fetch_add(1, Ordering::Relaxed);
                                         metrics::counter!(m::LOG_PIPELINE_LOGS_PROCESSED_TOTAL).increment(1);
+                                        transforms.apply(&mut log_entry);
+                                        enrichment.apply(&mut log_entry).await;
+                                        output_handle.send_log(&log_entry);
 
                                         match rule_engine.lock().await.evaluate(&log_entry) {
                                             Ok(matches) => {
@@ -568,6 +804,7 @@ impl Pipeline for LogPipeline {
                                                         None,
                                                     ) {
                                                         drop(alert_gen);
+                                                        deliver_alert(admission.as_deref(), &sink_dispatcher, &output_handle, &alert_event, &rule_match);
                                                         match alert_tx.send(alert_event).await {
                                                             Ok(()) => {
                                                                 metrics::counter!(m::LOG_PIPELINE_ALERTS_SENT_TOTAL).increment(1);
@@ -636,17 +873,14 @@ impl Pipeline for LogPipeline {
 
         tracing::info!("stopping log pipeline");
 
-        // 1. 먼저 버퍼 드레인 (태스크가 아직 실행 중일 때)
-        let remaining = self.buffer.lock().await.drain_all();
-
-        // 2. Graceful shutdown signal 전송
+        // 1. 수집기와 처리 태스크에 종료 신호 전송 (싱크/출력은 계속 실행)
         self.cancel_token.cancel();
         tracing::debug!("sent cancellation signal to all collectors");
 
-        // 3. Give collectors a moment to shutdown gracefully
+        // 2. Give collectors a moment to shutdown gracefully
         tokio::time::sleep(Duration::from_millis(100)).await;
 
-        // 4. EventReceiver task 처리 (packet_rx 복원)
+        // 3. EventReceiver task 처리 (packet_rx 복원)
         if let Some(task) = self.event_receiver_task.take() {
             // Use timeout to avoid hanging if task doesn't respond to cancellation
             match tokio::time::timeout(Duration::from_secs(2), task).await {
@@ -668,17 +902,18 @@ impl Pipeline for LogPipeline {
             }
         }
 
-        // 5. 나머지 collector tasks 정리
+        // 4. 나머지 collector/처리 tasks 정리
         for task in self.tasks.drain(..) {
             let _ = task.await;
         }
 
-        // 6. 수집기 상태 정리
+        // 5. 수집기 상태 정리
         self.collectors.stop_all();
         self.collectors.clear();
         self.collector_statuses.write().await.clear();
 
-        // 7. 드레인된 로그 처리
+        // 6. 버퍼에 남은 로그 처리 (싱크/출력 큐는 아직 열려 있음)
+        let remaining = self.buffer.lock().await.drain_all().await;
         if !remaining.is_empty() {
             tracing::info!(
                 count = remaining.len(),
@@ -687,13 +922,19 @@ impl Pipeline for LogPipeline {
             self.process_batch(remaining).await;
         }
 
+        // 7. 싱크/출력 큐를 닫고 남은 알림과 문서를 전송할 때까지 대기
+        self.sink_dispatcher = SinkDispatcher::default();
+        self.output_handle = OutputHandle::default();
+        self.drain_delivery_tasks().await;
+
         // 8. 채널 재생성 (재시작 지원)
         let (tx, rx) = mpsc::channel(self.config.buffer_capacity);
         self.raw_log_tx = tx;
         self.raw_log_rx = Some(rx);
 
-        // 9. Reset cancellation token for next start
+        // 9. Reset cancellation tokens for next start
         self.cancel_token = CancellationToken::new();
+        self.delivery_cancel = CancellationToken::new();
 
         self.state = PipelineState::Stopped;
         tracing::info!("log pipeline stopped");
@@ -734,6 +975,10 @@ impl Pipeline for LogPipeline {
                     ));
                 }
 
+                if let Some(err) = self.rule_reload_error.read().await.as_ref() {
+                    return HealthStatus::Degraded(format!("rule reload failed: {err}"));
+                }
+
                 let utilization = self.buffer.lock().await.utilization();
                 if utilization > 0.9 {
                     HealthStatus::Degraded(format!(
@@ -794,6 +1039,27 @@ impl Plugin for LogPipeline {
     async fn health_check(&self) -> HealthStatus {
         <Self as Pipeline>::health_check(self).await
     }
+
+    async fn resource_usage(&self) -> ResourceUsage {
+        let raw_logs = ChannelUsage::from_sender("raw_logs", &self.raw_log_tx);
+        let buffered = self.buffer.lock().await.memory_estimate();
+        let queued = ResourceUsage::estimate_bytes(raw_logs.depth, std::mem::size_of::<RawLog>());
+        let event_receiver = self
+            .event_receiver_task
+            .as_ref()
+            .map_or(0, |t| usize::from(!t.is_finished()));
+
+        ResourceUsage {
+            tasks: ResourceUsage::live_tasks(&self.tasks) + event_receiver,
+            channels: vec![
+                raw_logs,
+                ChannelUsage::from_sender("alerts", &self.alert_tx),
+            ],
+            memory_bytes: u64::try_from(buffered)
+                .unwrap_or(u64::MAX)
+                .saturating_add(queued),
+        }
+    }
 }
 
 /// 로그 파이프라인 빌더
@@ -818,6 +1084,11 @@ pub struct LogPipelineBuilder {
     packet_rx: Option<mpsc::Receiver<PacketEvent>>,
     alert_tx: Option<mpsc::Sender<AlertEvent>>,
     alert_channel_capacity: usize,
+    alert_sinks: Vec<(Arc<dyn AlertSink>, SinkFilter)>,
+    admission: Option<Arc<dyn AlertAdmission>>,
+    replay_alerts: Vec<AlertEvent>,
+    log_enrichers: Vec<Arc<dyn LogEnricher>>,
+    dry_run: bool,
 }
 
 impl LogPipelineBuilder {
@@ -828,6 +1099,11 @@ impl LogPipelineBuilder {
             packet_rx: None,
             alert_tx: None,
             alert_channel_capacity: 1024,
+            alert_sinks: Vec::new(),
+            admission: None,
+            replay_alerts: Vec::new(),
+            log_enrichers: Vec::new(),
+            dry_run: false,
         }
     }
 
@@ -859,6 +1135,55 @@ impl LogPipelineBuilder {
         self
     }
 
+    /// 설정(`sinks`) 외의 알림 싱크를 추가합니다.
+    ///
+    /// `min_severity` 미만의 알림은 이 싱크로 전달하지 않습니다.
+    pub fn alert_sink(self, sink: Arc<dyn AlertSink>, min_severity: Severity) -> Self {
+        self.routed_alert_sink(sink, SinkFilter::severity(min_severity))
+    }
+
+    /// 라우팅 필터(심각도, 규칙 태그)와 함께 알림 싱크를 추가합니다.
+    pub fn routed_alert_sink(mut self, sink: Arc<dyn AlertSink>, filter: SinkFilter) -> Self {
+        self.alert_sinks.push((sink, filter));
+        self
+    }
+
+    /// 알림을 싱크와 출력에 넣기 전에 거를 게이트를 설정합니다.
+    ///
+    /// 게이트가 거부한 알림은 싱크와 출력(Elasticsearch, Kafka)에 전달하지 않고
+    /// downstream 채널로만 보냅니다.
+    pub fn alert_admission(mut self, admission: Arc<dyn AlertAdmission>) -> Self {
+        self.admission = Some(admission);
+        self
+    }
+
+    /// 첫 시작 시 싱크와 출력에 다시 보낼 알림을 설정합니다.
+    ///
+    /// 데몬은 이벤트 저널에서 확인되지 않은 알림을 넘겨, 재시작 직전에 생성된 알림도
+    /// 알림 싱크에 전달되게 합니다. 이미 게이트를 통과했던 알림이므로 게이트를 다시 거치지 않고,
+    /// 탐지 맥락(태그, 필드) 없이 전달합니다.
+    pub fn replay_alerts(mut self, alerts: Vec<AlertEvent>) -> Self {
+        self.replay_alerts = alerts;
+        self
+    }
+
+    /// dry-run 모드를 설정합니다 (`general.dry_run`).
+    ///
+    /// 켜면 싱크로 가는 모든 알림이 시뮬레이션으로 표시됩니다
+    /// ([`SinkAlert::simulated`]).
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// 설정(`enrichment`) 외의 로그 보강 단계를 추가합니다.
+    ///
+    /// 설정된 보강 단계 뒤에 추가한 순서대로 실행됩니다.
+    pub fn log_enricher(mut self, enricher: Arc<dyn LogEnricher>) -> Self {
+        self.log_enrichers.push(enricher);
+        self
+    }
+
     /// 파이프라인을 빌드합니다.
     ///
     /// # Returns
@@ -868,7 +1193,7 @@ impl LogPipelineBuilder {
     ///
     /// # Errors
     ///
-    /// 설정 검증에 실패하면 에러를 반환합니다.
+    /// 설정 검증, 알림 싱크 또는 출력 생성에 실패하면 에러를 반환합니다.
     pub fn build(
         self,
     ) -> Result<(LogPipeline, Option<mpsc::Receiver<AlertEvent>>), LogPipelineError> {
@@ -883,10 +1208,10 @@ impl LogPipelineBuilder {
             (tx, Some(rx))
         };
 
-        let buffer = Arc::new(Mutex::new(LogBuffer::new(
-            self.config.buffer_capacity,
-            self.config.drop_policy.clone(),
-        )));
+        // 디스크 오버플로 버퍼는 start()에서 엽니다 (파일 I/O는 비동기 컨텍스트에서).
+        let log_buffer =
+            LogBuffer::new(self.config.buffer_capacity, self.config.drop_policy.clone());
+        let buffer = Arc::new(Mutex::new(log_buffer));
 
         let alert_generator = Arc::new(Mutex::new(AlertGenerator::new(
             self.config.alert_dedup_window_secs,
@@ -900,13 +1225,37 @@ impl LogPipelineBuilder {
             plugin_type: PluginType::LogPipeline,
         };
 
+        let parser = ParserRouter::from_config(&self.config.parsers)?
+            .with_source_hints(self.config.format_hints.clone());
+
+        let mut sinks = AlertSinkSet::from_config(&self.config.sinks, &self.config.smtp)?;
+        for (sink, filter) in self.alert_sinks {
+            sinks.add(sink, filter, DEFAULT_SINK_QUEUE_SIZE);
+        }
+        sinks.set_simulated(self.dry_run);
+
+        let outputs = OutputSet::from_config(
+            &self.config.elasticsearch,
+            &self.config.kafka_output,
+            &self.config.log_store,
+        )?;
+
+        let transforms = TransformChain::from_config(&self.config.transforms)?;
+        let mut enrichment = EnrichmentStage::from_config(&self.config.enrichment)?;
+        for enricher in self.log_enrichers {
+            enrichment = enrichment.with_enricher(enricher);
+        }
+
         let pipeline = LogPipeline {
             plugin_info,
             plugin_state: PluginState::Created,
             config: self.config,
             state: PipelineState::Initialized,
-            parser: Arc::new(ParserRouter::with_defaults()),
+            parser: Arc::new(parser),
+            transforms,
+            enrichment,
             rule_engine: Arc::new(Mutex::new(RuleEngine::new())),
+            rule_reload_error: RuleReloadError::default(),
             alert_generator,
             buffer,
             collectors: CollectorSet::default(),
@@ -914,10 +1263,18 @@ impl LogPipelineBuilder {
             raw_log_rx: Some(raw_log_rx),
             raw_log_tx,
             alert_tx,
+            sinks,
+            sink_dispatcher: SinkDispatcher::default(),
+            admission: self.admission,
+            replay_alerts: self.replay_alerts,
+            outputs,
+            output_handle: OutputHandle::default(),
             packet_rx: self.packet_rx,
             tasks: Vec::new(),
             event_receiver_task: None,
             cancel_token: CancellationToken::new(),
+            delivery_tasks: Vec::new(),
+            delivery_cancel: CancellationToken::new(),
             parse_error_count: Arc::new(AtomicU64::new(0)),
             processed_count: Arc::new(AtomicU64::new(0)),
         };
@@ -932,9 +1289,30 @@ impl Default for LogPipelineBuilder {
     }
 }
 
+/// 게이트를 통과한 알림을 싱크와 출력 큐에 넣습니다.
+///
+/// 게이트가 거부한 알림(중복, 폭주 제한)은 외부 시스템으로 보내지 않습니다.
+fn deliver_alert(
+    admission: Option<&dyn AlertAdmission>,
+    sinks: &SinkDispatcher,
+    outputs: &OutputHandle,
+    alert: &AlertEvent,
+    rule_match: &RuleMatch,
+) {
+    if admission.is_some_and(|gate| !gate.admit(alert)) {
+        tracing::debug!(alert_id = %alert.id, "alert held back from sinks by alert gate");
+        return;
+    }
+    sinks.dispatch_match(alert, rule_match);
+    outputs.send_alert(alert);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::AtomicBool;
+
+    use crate::sink::SinkDelivery;
 
     #[test]
     fn builder_creates_pipeline() {
@@ -963,6 +1341,85 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn start_opens_disk_buffer() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = dir.path().join("spool");
+        let config = PipelineConfig {
+            rule_dir: dir.path().display().to_string(),
+            sources: vec![],
+            enabled: false,
+            disk_buffer: ironpost_core::config::DiskBufferConfig {
+                enabled: true,
+                dir: spool.display().to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (mut pipeline, _) = LogPipelineBuilder::new().config(config).build().unwrap();
+        assert!(!spool.exists());
+
+        Pipeline::start(&mut pipeline).await.unwrap();
+        assert!(spool.join("index.json").exists());
+        assert!(pipeline.buffer.lock().await.has_disk_spill());
+        assert_eq!(pipeline.buffer.lock().await.disk_len(), 0);
+        Pipeline::stop(&mut pipeline).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn parsed_logs_are_searchable_in_log_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = PipelineConfig {
+            rule_dir: dir.path().display().to_string(),
+            sources: vec![],
+            enabled: false,
+            batch_size: 1,
+            log_store: ironpost_core::config::LogStoreConfig {
+                enabled: true,
+                dir: dir.path().join("logs").display().to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (mut pipeline, _) = LogPipelineBuilder::new().config(config).build().unwrap();
+        let store = pipeline.log_store().unwrap();
+        Pipeline::start(&mut pipeline).await.unwrap();
+
+        let sender = pipeline.raw_log_sender();
+        for host in ["web-01", "db-01"] {
+            let line = format!("<34>1 2024-01-15T12:00:00Z {host} sshd 1234 - - Failed password");
+            sender
+                .send(RawLog::new(bytes::Bytes::from(line), "test"))
+                .await
+                .unwrap();
+        }
+
+        let query = crate::output::LogQuery {
+            hostname: Some("db-01".to_owned()),
+            ..Default::default()
+        };
+        let mut found = Vec::new();
+        for _ in 0..50 {
+            found = store.search(&query).unwrap();
+            if !found.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].message, "Failed password");
+
+        Pipeline::stop(&mut pipeline).await.unwrap();
+        assert!(
+            LogPipelineBuilder::new()
+                .build()
+                .unwrap()
+                .0
+                .log_store()
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn pipeline_lifecycle() {
         let (mut pipeline, _alert_rx) = LogPipelineBuilder::new().build().unwrap();
@@ -998,6 +1455,28 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn resource_usage_reports_queued_raw_logs() {
+        use bytes::Bytes;
+
+        let (pipeline, _alert_rx) = LogPipelineBuilder::new().build().unwrap();
+        let sender = pipeline.raw_log_sender();
+        sender
+            .send(RawLog::new(Bytes::from_static(b"queued"), "test"))
+            .await
+            .unwrap();
+
+        let usage = Plugin::resource_usage(&pipeline).await;
+        assert_eq!(usage.tasks, 0);
+        let raw_logs = usage
+            .channels
+            .iter()
+            .find(|c| c.name == "raw_logs")
+            .unwrap();
+        assert_eq!(raw_logs.depth, 1);
+        assert!(usage.memory_bytes > 0);
+    }
+
     #[tokio::test]
     async fn pipeline_can_restart_after_stop() {
         // Create a temporary directory for rules
@@ -1204,6 +1683,535 @@ mod tests {
         Pipeline::stop(&mut pipeline).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn watch_rules_reloads_and_reports_invalid_rules_in_health() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rule = "id: watched\ntitle: Watched\nseverity: Low\ndetection:\n  conditions:\n    - field: process\n      value: sshd\n";
+
+        let config = PipelineConfig {
+            rule_dir: temp_dir.path().to_string_lossy().to_string(),
+            sources: vec![],
+            enabled: false,
+            watch_rules: true,
+            rule_reload_secs: 1,
+            ..Default::default()
+        };
+        let (mut pipeline, _) = LogPipelineBuilder::new().config(config).build().unwrap();
+        Pipeline::start(&mut pipeline).await.unwrap();
+        assert_eq!(pipeline.rule_count().await, 0);
+
+        std::fs::write(temp_dir.path().join("watched.yml"), rule).unwrap();
+        for _ in 0..100 {
+            if pipeline.rule_count().await == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(pipeline.rule_count().await, 1);
+
+        // 잘못된 규칙은 기존 규칙을 유지하고 health로 보고
+        std::fs::write(temp_dir.path().join("broken.yml"), "id: [unclosed").unwrap();
+        let mut health = Pipeline::health_check(&pipeline).await;
+        for _ in 0..100 {
+            if matches!(health, HealthStatus::Degraded(_)) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            health = Pipeline::health_check(&pipeline).await;
+        }
+        assert!(
+            matches!(&health, HealthStatus::Degraded(reason) if reason.contains("rule reload failed")),
+            "unexpected health: {health:?}"
+        );
+        assert_eq!(pipeline.rule_count().await, 1);
+
+        Pipeline::stop(&mut pipeline).await.unwrap();
+    }
+
+    /// 받은 알림 제목을 기록하는 테스트 싱크
+    struct RecordingSink {
+        name: &'static str,
+        received: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl AlertSink for RecordingSink {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn send<'a>(
+            &'a self,
+            alert: &'a SinkAlert,
+        ) -> ironpost_core::pipeline::BoxFuture<'a, Result<SinkDelivery, LogPipelineError>>
+        {
+            Box::pin(async move {
+                self.received
+                    .lock()
+                    .await
+                    .push(alert.event.alert.title.clone());
+                Ok(SinkDelivery::Sent)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn alerts_are_routed_to_sinks_by_severity_and_tags() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rule = "id: ssh_fail\ntitle: SSH Failure\nseverity: High\ntags: [auth]\ndetection:\n  conditions:\n    - field: process\n      value: sshd\n";
+        std::fs::write(temp_dir.path().join("ssh.yml"), rule).unwrap();
+
+        let config = PipelineConfig {
+            rule_dir: temp_dir.path().to_string_lossy().to_string(),
+            sources: vec![],
+            enabled: false,
+            batch_size: 1,
+            ..Default::default()
+        };
+        let high = Arc::new(Mutex::new(Vec::new()));
+        let critical = Arc::new(Mutex::new(Vec::new()));
+        let network = Arc::new(Mutex::new(Vec::new()));
+        let (mut pipeline, alert_rx) = LogPipelineBuilder::new()
+            .config(config)
+            .alert_sink(
+                Arc::new(RecordingSink {
+                    name: "high",
+                    received: Arc::clone(&high),
+                }),
+                Severity::High,
+            )
+            .alert_sink(
+                Arc::new(RecordingSink {
+                    name: "critical",
+                    received: Arc::clone(&critical),
+                }),
+                Severity::Critical,
+            )
+            .routed_alert_sink(
+                Arc::new(RecordingSink {
+                    name: "network",
+                    received: Arc::clone(&network),
+                }),
+                SinkFilter {
+                    min_severity: Severity::Info,
+                    rule_tags: vec!["network".to_owned()],
+                },
+            )
+            .build()
+            .unwrap();
+        let mut alert_rx = alert_rx.unwrap();
+        Pipeline::start(&mut pipeline).await.unwrap();
+
+        pipeline
+            .raw_log_sender()
+            .send(RawLog::new(
+                bytes::Bytes::from_static(
+                    b"<34>1 2024-01-15T12:00:00Z host sshd 1234 - - Failed password",
+                ),
+                "test",
+            ))
+            .await
+            .unwrap();
+
+        // downstream 채널로도 그대로 전달
+        let alert = tokio::time::timeout(Duration::from_secs(3), alert_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(alert.alert.title, "SSH Failure");
+
+        for _ in 0..100 {
+            if !high.lock().await.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(*high.lock().await, ["SSH Failure"]);
+        assert!(critical.lock().await.is_empty());
+        assert!(network.lock().await.is_empty());
+
+        Pipeline::stop(&mut pipeline).await.unwrap();
+    }
+
+    /// 첫 알림만 통과시키는 테스트 게이트
+    #[derive(Default)]
+    struct AdmitFirstGate {
+        admitted: AtomicBool,
+    }
+
+    impl AlertAdmission for AdmitFirstGate {
+        fn admit(&self, _alert: &AlertEvent) -> bool {
+            !self.admitted.swap(true, Ordering::SeqCst)
+        }
+    }
+
+    /// 같은 sshd 로그에 매칭되는 규칙을 `count`개 작성합니다 (알림 생성기 dedup은 규칙별).
+    fn write_ssh_rules(dir: &std::path::Path, count: usize) {
+        for i in 0..count {
+            let rule = format!(
+                "id: ssh_fail_{i}\ntitle: SSH Failure {i}\nseverity: High\ndetection:\n  conditions:\n    - field: process\n      value: sshd\n"
+            );
+            std::fs::write(dir.join(format!("ssh_{i}.yml")), rule).unwrap();
+        }
+    }
+
+    fn ssh_failure_log() -> RawLog {
+        RawLog::new(
+            bytes::Bytes::from_static(
+                b"<34>1 2024-01-15T12:00:00Z host sshd 1234 - - Failed password",
+            ),
+            "test",
+        )
+    }
+
+    #[tokio::test]
+    async fn alerts_rejected_by_admission_never_reach_sinks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        write_ssh_rules(temp_dir.path(), 3);
+
+        let config = PipelineConfig {
+            rule_dir: temp_dir.path().to_string_lossy().to_string(),
+            sources: vec![],
+            enabled: false,
+            batch_size: 1,
+            ..Default::default()
+        };
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let (mut pipeline, alert_rx) = LogPipelineBuilder::new()
+            .config(config)
+            .alert_sink(
+                Arc::new(RecordingSink {
+                    name: "recording",
+                    received: Arc::clone(&received),
+                }),
+                Severity::Info,
+            )
+            .alert_admission(Arc::new(AdmitFirstGate::default()))
+            .build()
+            .unwrap();
+        let mut alert_rx = alert_rx.unwrap();
+        Pipeline::start(&mut pipeline).await.unwrap();
+
+        pipeline
+            .raw_log_sender()
+            .send(ssh_failure_log())
+            .await
+            .unwrap();
+        // downstream 채널은 판정과 무관하게 모든 알림을 받음 (데몬 게이트가 다시 거름)
+        for _ in 0..3 {
+            tokio::time::timeout(Duration::from_secs(3), alert_rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        Pipeline::stop(&mut pipeline).await.unwrap();
+        assert_eq!(received.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn replayed_alerts_reach_sinks_on_first_start_only() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = PipelineConfig {
+            rule_dir: temp_dir.path().to_string_lossy().to_string(),
+            sources: vec![],
+            enabled: false,
+            ..Default::default()
+        };
+        let alert = ironpost_core::types::Alert {
+            id: "replayed".to_owned(),
+            title: "Replayed".to_owned(),
+            description: String::new(),
+            severity: Severity::High,
+            rule_name: "ssh_fail".to_owned(),
+            source_ip: None,
+            target_ip: None,
+            created_at: std::time::SystemTime::now(),
+        };
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let (mut pipeline, _alert_rx) = LogPipelineBuilder::new()
+            .config(config)
+            .alert_sink(
+                Arc::new(RecordingSink {
+                    name: "recording",
+                    received: Arc::clone(&received),
+                }),
+                Severity::Info,
+            )
+            .replay_alerts(vec![AlertEvent::new(alert, Severity::High)])
+            .build()
+            .unwrap();
+
+        Pipeline::start(&mut pipeline).await.unwrap();
+        Pipeline::stop(&mut pipeline).await.unwrap();
+        assert_eq!(*received.lock().await, ["Replayed"]);
+
+        // 재시작 시에는 다시 보내지 않음
+        Pipeline::start(&mut pipeline).await.unwrap();
+        Pipeline::stop(&mut pipeline).await.unwrap();
+        assert_eq!(received.lock().await.len(), 1);
+    }
+
+    /// 알림마다 지연 후 기록하는 느린 테스트 싱크
+    struct SlowSink {
+        received: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl AlertSink for SlowSink {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn send<'a>(
+            &'a self,
+            alert: &'a SinkAlert,
+        ) -> ironpost_core::pipeline::BoxFuture<'a, Result<SinkDelivery, LogPipelineError>>
+        {
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                self.received.lock().await.push(alert.event.id.clone());
+                Ok(SinkDelivery::Sent)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn stop_processes_buffered_logs_and_drains_sink_queues() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        write_ssh_rules(temp_dir.path(), 5);
+
+        // 배치 크기와 플러시 주기를 크게 잡아 로그가 버퍼에 남은 채로 정지
+        let config = PipelineConfig {
+            rule_dir: temp_dir.path().to_string_lossy().to_string(),
+            sources: vec![],
+            enabled: false,
+            batch_size: 100,
+            flush_interval_secs: 3600,
+            ..Default::default()
+        };
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let (mut pipeline, _alert_rx) = LogPipelineBuilder::new()
+            .config(config)
+            .alert_sink(
+                Arc::new(SlowSink {
+                    received: Arc::clone(&received),
+                }),
+                Severity::Info,
+            )
+            .build()
+            .unwrap();
+        Pipeline::start(&mut pipeline).await.unwrap();
+
+        pipeline
+            .raw_log_sender()
+            .send(ssh_failure_log())
+            .await
+            .unwrap();
+        for _ in 0..100 {
+            if !pipeline.buffer.lock().await.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(pipeline.buffer.lock().await.len(), 1);
+
+        Pipeline::stop(&mut pipeline).await.unwrap();
+        assert_eq!(received.lock().await.len(), 5);
+    }
+
+    struct AssetOwnerEnricher;
+
+    impl LogEnricher for AssetOwnerEnricher {
+        fn name(&self) -> &str {
+            "asset_owner"
+        }
+
+        fn enrich<'a>(
+            &'a self,
+            entry: &'a mut ironpost_core::types::LogEntry,
+        ) -> ironpost_core::pipeline::BoxFuture<'a, ()> {
+            Box::pin(async move {
+                if entry.hostname == "pay-01" {
+                    entry
+                        .fields
+                        .push(("asset_owner".to_owned(), "payments".to_owned()));
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn rules_match_fields_added_by_enrichers() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rule = "id: payments_login\ntitle: Payments Host Login\nseverity: High\ndetection:\n  conditions:\n    - field: asset_owner\n      value: payments\n";
+        std::fs::write(temp_dir.path().join("payments.yml"), rule).unwrap();
+
+        let config = PipelineConfig {
+            rule_dir: temp_dir.path().to_string_lossy().to_string(),
+            sources: vec![],
+            enabled: false,
+            batch_size: 1,
+            ..Default::default()
+        };
+        let (mut pipeline, alert_rx) = LogPipelineBuilder::new()
+            .config(config)
+            .log_enricher(Arc::new(AssetOwnerEnricher))
+            .build()
+            .unwrap();
+        let mut alert_rx = alert_rx.unwrap();
+        Pipeline::start(&mut pipeline).await.unwrap();
+
+        let sender = pipeline.raw_log_sender();
+        for host in ["web-01", "pay-01"] {
+            let line = format!("<34>1 2024-01-15T12:00:00Z {host} sshd 1234 - - Accepted password");
+            sender
+                .send(RawLog::new(bytes::Bytes::from(line), "test"))
+                .await
+                .unwrap();
+        }
+
+        let alert = tokio::time::timeout(Duration::from_secs(3), alert_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(alert.alert.title, "Payments Host Login");
+        assert!(alert_rx.try_recv().is_err());
+
+        Pipeline::stop(&mut pipeline).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn rules_match_fields_extracted_by_transforms() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rule = "id: root_login\ntitle: Root Login\nseverity: High\ndetection:\n  conditions:\n    - field: user\n      value: root\n";
+        std::fs::write(temp_dir.path().join("root.yml"), rule).unwrap();
+
+        let transform = |kind: &str, field: &str| ironpost_core::config::TransformConfig {
+            kind: kind.to_owned(),
+            field: field.to_owned(),
+            ..Default::default()
+        };
+        let config = PipelineConfig {
+            rule_dir: temp_dir.path().to_string_lossy().to_string(),
+            sources: vec![],
+            enabled: false,
+            batch_size: 1,
+            transforms: vec![
+                ironpost_core::config::TransformConfig {
+                    pattern: r"for (?P<user>\w+) from".to_owned(),
+                    ..transform("extract", "message")
+                },
+                ironpost_core::config::TransformConfig {
+                    pattern: r"\d+\.\d+\.\d+\.\d+".to_owned(),
+                    ..transform("redact", "message")
+                },
+            ],
+            ..Default::default()
+        };
+        let (mut pipeline, alert_rx) = LogPipelineBuilder::new().config(config).build().unwrap();
+        let mut alert_rx = alert_rx.unwrap();
+        Pipeline::start(&mut pipeline).await.unwrap();
+
+        let sender = pipeline.raw_log_sender();
+        for user in ["alice", "root"] {
+            let line = format!(
+                "<34>1 2024-01-15T12:00:00Z web-01 sshd 1234 - - Accepted password for {user} from 10.0.0.5"
+            );
+            sender
+                .send(RawLog::new(bytes::Bytes::from(line), "test"))
+                .await
+                .unwrap();
+        }
+
+        let alert = tokio::time::timeout(Duration::from_secs(3), alert_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(alert.alert.title, "Root Login");
+        assert!(alert_rx.try_recv().is_err());
+
+        Pipeline::stop(&mut pipeline).await.unwrap();
+    }
+
+    #[test]
+    fn builder_rejects_invalid_transform() {
+        let config = PipelineConfig {
+            transforms: vec![ironpost_core::config::TransformConfig {
+                kind: "extract".to_owned(),
+                field: "message".to_owned(),
+                pattern: r"user=\w+".to_owned(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let err = LogPipelineBuilder::new()
+            .config(config)
+            .build()
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("transforms[0].pattern"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn parsed_logs_and_alerts_are_indexed_to_elasticsearch() {
+        let (url, requests) = crate::sink::http::test_server::serve_responses(vec![(
+            200,
+            r#"{"errors":false,"items":[]}"#.to_owned(),
+        )])
+        .await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let rule = "id: ssh_fail\ntitle: SSH Failure\nseverity: High\ndetection:\n  conditions:\n    - field: process\n      value: sshd\n";
+        std::fs::write(temp_dir.path().join("ssh.yml"), rule).unwrap();
+
+        let config = PipelineConfig {
+            rule_dir: temp_dir.path().to_string_lossy().to_string(),
+            sources: vec![],
+            enabled: false,
+            batch_size: 1,
+            elasticsearch: ironpost_core::config::ElasticsearchConfig {
+                enabled: true,
+                url,
+                flush_interval_ms: 50,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (mut pipeline, _alert_rx) = LogPipelineBuilder::new().config(config).build().unwrap();
+        Pipeline::start(&mut pipeline).await.unwrap();
+
+        pipeline
+            .raw_log_sender()
+            .send(RawLog::new(
+                bytes::Bytes::from_static(
+                    b"<34>1 2024-01-15T12:00:00Z host sshd 1234 - - Failed password",
+                ),
+                "test",
+            ))
+            .await
+            .unwrap();
+
+        let mut body = String::new();
+        for _ in 0..100 {
+            body = requests
+                .lock()
+                .await
+                .iter()
+                .map(|(_, body)| body.as_str())
+                .collect();
+            if body.contains("ironpost-alerts-") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(
+            body.contains(r#""_index":"ironpost-logs-2024.01.15""#),
+            "{body}"
+        );
+        assert!(body.contains(r#""message":"Failed password""#), "{body}");
+        assert!(body.contains(r#""title":"SSH Failure""#), "{body}");
+
+        Pipeline::stop(&mut pipeline).await.unwrap();
+    }
+
     #[tokio::test]
     async fn event_receiver_spawned_when_packet_rx_present() {
         let temp_dir = std::env::temp_dir().join("ironpost_test_event_rx");
@@ -0,0 +1,227 @@
+//! CIDR 매칭 -- `field|cidr` 조건 수정자
+//!
+//! 조건 값은 쉼표로 구분한 CIDR 목록이며 (예: `10.0.0.0/8, 192.168.0.0/16`),
+//! 필드 값의 IP 주소가 그중 하나에 속하면 매칭됩니다.
+//! 프리픽스가 없는 주소는 단일 호스트(`/32`, `/128`)로 취급합니다.
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+/// 하나의 조건 값에 허용하는 최대 CIDR 수
+pub const MAX_CIDRS_PER_CONDITION: usize = 256;
+
+/// IPv4 또는 IPv6 CIDR 블록
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpCidr {
+    /// 네트워크 주소 (호스트 비트는 0으로 정규화)
+    network: IpAddr,
+    /// 프리픽스 길이
+    prefix_len: u8,
+}
+
+impl IpCidr {
+    /// CIDR 블록을 생성합니다.
+    ///
+    /// # Errors
+    ///
+    /// 프리픽스가 주소 길이(IPv4 32, IPv6 128)를 넘으면 에러를 반환합니다.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Self, String> {
+        let max = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix_len > max {
+            return Err(format!(
+                "invalid prefix length /{} for {} (max: /{})",
+                prefix_len, addr, max
+            ));
+        }
+        let network = match addr {
+            IpAddr::V4(v4) => IpAddr::V4(Ipv4Addr::from(u32::from(v4) & mask_v4(prefix_len))),
+            IpAddr::V6(v6) => IpAddr::V6(Ipv6Addr::from(u128::from(v6) & mask_v6(prefix_len))),
+        };
+        Ok(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    /// 주소가 이 블록에 속하는지 확인합니다.
+    ///
+    /// IPv4-mapped IPv6 주소(`::ffff:10.0.0.1`)는 IPv4 블록과 비교합니다.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        let addr = match (self.network, addr) {
+            (IpAddr::V4(_), IpAddr::V6(v6)) => match v6.to_ipv4_mapped() {
+                Some(v4) => IpAddr::V4(v4),
+                None => return false,
+            },
+            _ => addr,
+        };
+        match (self.network, addr) {
+            (IpAddr::V4(network), IpAddr::V4(addr)) => {
+                u32::from(addr) & mask_v4(self.prefix_len) == u32::from(network)
+            }
+            (IpAddr::V6(network), IpAddr::V6(addr)) => {
+                u128::from(addr) & mask_v6(self.prefix_len) == u128::from(network)
+            }
+            _ => false,
+        }
+    }
+
+    /// 쉼표로 구분한 CIDR 목록을 파싱합니다.
+    ///
+    /// # Errors
+    ///
+    /// 목록이 비어 있거나, 항목이 [`MAX_CIDRS_PER_CONDITION`]개를 넘거나,
+    /// 잘못된 항목이 있으면 에러를 반환합니다.
+    pub fn parse_list(value: &str) -> Result<Vec<Self>, String> {
+        let cidrs = value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<Self>, String>>()?;
+        if cidrs.is_empty() {
+            return Err("cidr value must contain at least one address or CIDR".to_owned());
+        }
+        if cidrs.len() > MAX_CIDRS_PER_CONDITION {
+            return Err(format!(
+                "too many CIDRs in one condition: {} (max: {})",
+                cidrs.len(),
+                MAX_CIDRS_PER_CONDITION
+            ));
+        }
+        Ok(cidrs)
+    }
+}
+
+impl FromStr for IpCidr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix)) => {
+                let prefix_len = prefix
+                    .parse::<u8>()
+                    .map_err(|_| format!("invalid prefix length in '{}'", s))?;
+                (addr, Some(prefix_len))
+            }
+            None => (s, None),
+        };
+        let addr = addr
+            .parse::<IpAddr>()
+            .map_err(|_| format!("invalid IP address or CIDR '{}'", s))?;
+        let prefix_len = prefix_len.unwrap_or(match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        });
+        Self::new(addr, prefix_len)
+    }
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+/// 필드 값에서 IP 주소를 추출합니다.
+///
+/// `[::1]:8080`, `10.0.0.1:22`처럼 포트가 붙은 값도 받습니다.
+pub(crate) fn parse_field_ip(value: &str) -> Option<IpAddr> {
+    let value = value.trim();
+    if let Ok(addr) = value.parse::<IpAddr>() {
+        return Some(addr);
+    }
+    if let Some(rest) = value.strip_prefix('[') {
+        return rest.split_once(']')?.0.parse().ok();
+    }
+    let (host, port) = value.rsplit_once(':')?;
+    port.parse::<u16>().ok()?;
+    host.parse::<Ipv4Addr>().ok().map(IpAddr::V4)
+}
+
+fn mask_v4(prefix_len: u8) -> u32 {
+    u32::MAX
+        .checked_shl(32 - u32::from(prefix_len))
+        .unwrap_or(0)
+}
+
+fn mask_v6(prefix_len: u8) -> u128 {
+    u128::MAX
+        .checked_shl(128 - u32::from(prefix_len))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_normalizes() {
+        let cidr: IpCidr = "10.1.2.3/8".parse().unwrap();
+        assert_eq!(cidr.to_string(), "10.0.0.0/8");
+        assert_eq!(
+            "192.168.1.7".parse::<IpCidr>().unwrap().to_string(),
+            "192.168.1.7/32"
+        );
+        assert_eq!(
+            "2001:db8::1/32".parse::<IpCidr>().unwrap().to_string(),
+            "2001:db8::/32"
+        );
+        assert_eq!("0.0.0.0/0".parse::<IpCidr>().unwrap().prefix_len, 0);
+    }
+
+    #[test]
+    fn rejects_invalid_cidrs() {
+        for bad in [
+            "10.0.0.0/33",
+            "::/129",
+            "10.0.0/8",
+            "10.0.0.0/x",
+            "host",
+            "",
+        ] {
+            assert!(bad.parse::<IpCidr>().is_err(), "{bad} should be rejected");
+        }
+        assert!(IpCidr::parse_list(" , ").is_err());
+        assert!(IpCidr::parse_list("10.0.0.0/8, nope").is_err());
+        let too_many = vec!["10.0.0.1"; MAX_CIDRS_PER_CONDITION + 1].join(",");
+        assert!(IpCidr::parse_list(&too_many).is_err());
+    }
+
+    #[test]
+    fn contains_addresses() {
+        let private = IpCidr::parse_list("10.0.0.0/8, 192.168.0.0/16").unwrap();
+        let hit = |addr: &str| private.iter().any(|cidr| cidr.contains(ip(addr)));
+        assert!(hit("10.255.0.1"));
+        assert!(hit("192.168.1.100"));
+        assert!(hit("::ffff:10.0.0.1"));
+        assert!(!hit("11.0.0.1"));
+        assert!(!hit("2001:db8::1"));
+
+        let v6: IpCidr = "2001:db8::/32".parse().unwrap();
+        assert!(v6.contains(ip("2001:db8:ffff::1")));
+        assert!(!v6.contains(ip("2001:db9::1")));
+        assert!(!v6.contains(ip("10.0.0.1")));
+
+        let any: IpCidr = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains(ip("203.0.113.9")));
+    }
+
+    #[test]
+    fn parses_field_values() {
+        assert_eq!(parse_field_ip(" 10.0.0.1 "), Some(ip("10.0.0.1")));
+        assert_eq!(parse_field_ip("10.0.0.1:22"), Some(ip("10.0.0.1")));
+        assert_eq!(parse_field_ip("[2001:db8::1]:443"), Some(ip("2001:db8::1")));
+        assert_eq!(parse_field_ip("2001:db8::1"), Some(ip("2001:db8::1")));
+        assert_eq!(parse_field_ip("web-01"), None);
+        assert_eq!(parse_field_ip("10.0.0.1:http"), None);
+    }
+}
@@ -0,0 +1,426 @@
+//! Sigma 스타일 조건식 -- 이름 있는 selection을 and/or/not으로 결합
+//!
+//! 규칙의 `detection.condition` 문자열을 [`ConditionExpr`]로 파싱합니다.
+//! selection 이름은 파싱 시점에 인덱스로 바뀌므로 평가할 때는 문자열 비교가 없습니다.
+//!
+//! # 문법
+//! ```text
+//! expr       := and_expr ("or" and_expr)*
+//! and_expr   := not_expr ("and" not_expr)*
+//! not_expr   := "not" not_expr | primary
+//! primary    := "(" expr ")" | quantifier | selection
+//! quantifier := ("1" | "any" | "all") "of" (pattern | "them")
+//! ```
+//! `pattern`은 `*` 와일드카드를 쓸 수 있는 selection 이름입니다 (예: `selection_*`).
+//! `them`은 `_`로 시작하지 않는 모든 selection을 뜻합니다. 키워드는 대소문자를 구분하지 않습니다.
+//!
+//! # 예시
+//! ```text
+//! selection and not filter
+//! 1 of selection_* and not 1 of filter_*
+//! (process_creation or network) and not all of them
+//! ```
+
+/// 조건식의 최대 중첩 깊이 (스택 오버플로 방지)
+const MAX_CONDITION_DEPTH: usize = 32;
+
+/// 조건식의 최대 길이
+pub const MAX_CONDITION_LENGTH: usize = 4096;
+
+/// 파싱된 조건식
+///
+/// `Selection`과 quantifier는 selection 목록(이름 정렬 순서)의 인덱스를 가리킵니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConditionExpr {
+    /// 단일 selection
+    Selection(usize),
+    /// 부정
+    Not(Box<ConditionExpr>),
+    /// 모두 참
+    And(Vec<ConditionExpr>),
+    /// 하나 이상 참
+    Or(Vec<ConditionExpr>),
+    /// `1 of` -- 하나 이상의 selection이 참
+    AnyOf(Vec<usize>),
+    /// `all of` -- 모든 selection이 참
+    AllOf(Vec<usize>),
+}
+
+impl ConditionExpr {
+    /// 조건식을 파싱합니다.
+    ///
+    /// `selections`는 규칙의 selection 이름 목록이며, 인덱스는 이 목록의 순서를 따릅니다.
+    ///
+    /// # Errors
+    ///
+    /// 문법 오류, 정의되지 않은 selection, 아무 selection과도 맞지 않는 패턴이면
+    /// 에러 메시지를 반환합니다.
+    pub fn parse(expression: &str, selections: &[&str]) -> Result<Self, String> {
+        if expression.len() > MAX_CONDITION_LENGTH {
+            return Err(format!(
+                "condition too long: {} chars (max: {})",
+                expression.len(),
+                MAX_CONDITION_LENGTH
+            ));
+        }
+        let tokens = tokenize(expression)?;
+        if tokens.is_empty() {
+            return Err("condition must not be empty".to_owned());
+        }
+
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            selections,
+        };
+        let expr = parser.parse_or(0)?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(format!("unexpected '{}' in condition", token)),
+        }
+    }
+
+    /// 조건식을 평가합니다.
+    ///
+    /// `selection`은 인덱스의 selection이 매칭되는지 반환하며, 필요한 selection만 호출됩니다.
+    pub fn evaluate<E>(
+        &self,
+        selection: &mut impl FnMut(usize) -> Result<bool, E>,
+    ) -> Result<bool, E> {
+        match self {
+            Self::Selection(index) => selection(*index),
+            Self::Not(inner) => Ok(!inner.evaluate(selection)?),
+            Self::And(terms) => {
+                for term in terms {
+                    if !term.evaluate(selection)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Self::Or(terms) => {
+                for term in terms {
+                    if term.evaluate(selection)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Self::AnyOf(indices) => {
+                for index in indices {
+                    if selection(*index)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Self::AllOf(indices) => {
+                for index in indices {
+                    if !selection(*index)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+}
+
+/// 조건식 토큰
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    Word(String),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Open => f.write_str("("),
+            Self::Close => f.write_str(")"),
+            Self::Word(word) => f.write_str(word),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in expression.chars() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            if !word.is_empty() {
+                tokens.push(Token::Word(std::mem::take(&mut word)));
+            }
+            match c {
+                '(' => tokens.push(Token::Open),
+                ')' => tokens.push(Token::Close),
+                _ => {}
+            }
+        } else if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '*') {
+            word.push(c);
+        } else {
+            return Err(format!("invalid character '{}' in condition", c));
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(Token::Word(word));
+    }
+    Ok(tokens)
+}
+
+/// 재귀 하강 파서
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    selections: &'a [&'a str],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    /// 다음 토큰이 키워드이면 소비합니다.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_or(&mut self, depth: usize) -> Result<ConditionExpr, String> {
+        let mut terms = vec![self.parse_and(depth)?];
+        while self.eat_keyword("or") {
+            terms.push(self.parse_and(depth)?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            ConditionExpr::Or(terms)
+        })
+    }
+
+    fn parse_and(&mut self, depth: usize) -> Result<ConditionExpr, String> {
+        let mut terms = vec![self.parse_not(depth)?];
+        while self.eat_keyword("and") {
+            terms.push(self.parse_not(depth)?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            ConditionExpr::And(terms)
+        })
+    }
+
+    fn parse_not(&mut self, depth: usize) -> Result<ConditionExpr, String> {
+        if depth > MAX_CONDITION_DEPTH {
+            return Err(format!(
+                "condition nested deeper than {} levels",
+                MAX_CONDITION_DEPTH
+            ));
+        }
+        if self.eat_keyword("not") {
+            return Ok(ConditionExpr::Not(Box::new(self.parse_not(depth + 1)?)));
+        }
+        self.parse_primary(depth)
+    }
+
+    fn parse_primary(&mut self, depth: usize) -> Result<ConditionExpr, String> {
+        match self.next() {
+            Some(Token::Open) => {
+                let expr = self.parse_or(depth + 1)?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err("missing ')' in condition".to_owned()),
+                }
+            }
+            Some(Token::Word(word)) => {
+                let quantifier = word.to_ascii_lowercase();
+                if matches!(quantifier.as_str(), "1" | "any" | "all") && self.eat_keyword("of") {
+                    let indices = self.parse_target()?;
+                    return Ok(if quantifier == "all" {
+                        ConditionExpr::AllOf(indices)
+                    } else {
+                        ConditionExpr::AnyOf(indices)
+                    });
+                }
+                if is_keyword(&word) {
+                    return Err(format!("expected selection, found '{}'", word));
+                }
+                self.selections
+                    .iter()
+                    .position(|name| *name == word)
+                    .map(ConditionExpr::Selection)
+                    .ok_or_else(|| format!("unknown selection '{}' in condition", word))
+            }
+            Some(Token::Close) => Err("unexpected ')' in condition".to_owned()),
+            None => Err("unexpected end of condition".to_owned()),
+        }
+    }
+
+    /// `of` 뒤의 패턴 또는 `them`을 selection 인덱스 목록으로 바꿉니다.
+    fn parse_target(&mut self) -> Result<Vec<usize>, String> {
+        let Some(Token::Word(target)) = self.next() else {
+            return Err("expected selection pattern or 'them' after 'of'".to_owned());
+        };
+        let indices: Vec<usize> = if target.eq_ignore_ascii_case("them") {
+            self.selections
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| !name.starts_with('_'))
+                .map(|(index, _)| index)
+                .collect()
+        } else {
+            self.selections
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| wildcard_match(&target, name))
+                .map(|(index, _)| index)
+                .collect()
+        };
+        if indices.is_empty() {
+            return Err(format!("'{}' matches no selection", target));
+        }
+        Ok(indices)
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    ["and", "or", "not", "of", "them"]
+        .iter()
+        .any(|keyword| word.eq_ignore_ascii_case(keyword))
+}
+
+/// `*` 와일드카드 패턴이 이름과 일치하는지 확인합니다.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // `*` 없음
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SELECTIONS: &[&str] = &["_internal", "filter_admin", "filter_cron", "selection"];
+
+    fn parse(expression: &str) -> Result<ConditionExpr, String> {
+        ConditionExpr::parse(expression, SELECTIONS)
+    }
+
+    fn eval(expression: &str, matched: &[&str]) -> bool {
+        let expr = parse(expression).unwrap();
+        expr.evaluate(&mut |index| Ok::<_, ()>(matched.contains(&SELECTIONS[index])))
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_precedence() {
+        // and이 or보다 먼저 결합
+        assert_eq!(
+            parse("selection or filter_admin and not filter_cron").unwrap(),
+            ConditionExpr::Or(vec![
+                ConditionExpr::Selection(3),
+                ConditionExpr::And(vec![
+                    ConditionExpr::Selection(1),
+                    ConditionExpr::Not(Box::new(ConditionExpr::Selection(2))),
+                ]),
+            ])
+        );
+        assert_eq!(
+            parse("1 of filter_* AND ALL OF them").unwrap(),
+            ConditionExpr::And(vec![
+                ConditionExpr::AnyOf(vec![1, 2]),
+                ConditionExpr::AllOf(vec![1, 2, 3]),
+            ])
+        );
+    }
+
+    #[test]
+    fn evaluates_expressions() {
+        assert!(eval("selection and not filter_admin", &["selection"]));
+        assert!(!eval(
+            "selection and not filter_admin",
+            &["selection", "filter_admin"]
+        ));
+        assert!(eval("selection and not 1 of filter_*", &["selection"]));
+        assert!(!eval(
+            "selection and not 1 of filter_*",
+            &["selection", "filter_cron"]
+        ));
+        assert!(eval(
+            "(filter_admin or filter_cron) and selection",
+            &["filter_cron", "selection"]
+        ));
+        assert!(!eval("all of filter_*", &["filter_cron"]));
+        assert!(eval("any of them", &["filter_admin"]));
+        assert!(!eval("1 of them", &["_internal"]));
+        assert!(eval("not not selection", &["selection"]));
+    }
+
+    #[test]
+    fn evaluation_short_circuits() {
+        let expr = parse("filter_admin and selection").unwrap();
+        let mut calls = Vec::new();
+        let matched = expr
+            .evaluate(&mut |index| {
+                calls.push(index);
+                Ok::<_, ()>(false)
+            })
+            .unwrap();
+        assert!(!matched);
+        assert_eq!(calls, vec![1]);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(parse("").is_err());
+        assert!(parse("unknown").is_err());
+        assert!(parse("selection and").is_err());
+        assert!(parse("(selection or filter_admin").is_err());
+        assert!(parse("selection)").is_err());
+        assert!(parse("selection filter_admin").is_err());
+        assert!(parse("1 of nothing_*").is_err());
+        assert!(parse("all of").is_err());
+        assert!(parse("selection | filter_admin").is_err());
+        assert!(parse("not").is_err());
+        assert!(parse(&format!("{}selection{}", "(".repeat(40), ")".repeat(40))).is_err());
+        assert!(parse(&"not ".repeat(40)).is_err());
+    }
+
+    #[test]
+    fn wildcard_patterns() {
+        assert!(wildcard_match("filter_*", "filter_admin"));
+        assert!(wildcard_match("*admin", "filter_admin"));
+        assert!(wildcard_match("f*_*n", "filter_admin"));
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("selection", "selection"));
+        assert!(!wildcard_match("selection", "selection2"));
+        assert!(!wildcard_match("filter_*", "selection"));
+        assert!(!wildcard_match("a*a", "a"));
+    }
+}
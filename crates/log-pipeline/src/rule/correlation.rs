@@ -0,0 +1,290 @@
+//! 규칙 간 상관 분석 -- 순차 매칭 상태 저장소
+//!
+//! [`CorrelationStore`]는 상관 규칙([`CorrelationConfig`])마다 그룹 키(예: source_ip)별로
+//! 몇 번째 단계까지 매칭되었는지 추적합니다. 마지막 단계가 시간 윈도우 안에 매칭되면
+//! 하나의 복합 알림을 생성하고 상태를 지웁니다.
+//!
+//! # 상태 전이
+//! - 첫 단계 규칙 매칭: 진행 상태 생성 (윈도우 시작)
+//! - 다음 단계 규칙 매칭: 단계 전진, 마지막 단계면 완료
+//! - 윈도우 만료: 상태 폐기 (같은 엔트리가 첫 단계에 매칭되면 새로 시작)
+//!
+//! 엔트리 하나는 상관 규칙당 최대 한 단계만 전진시킵니다.
+//! 저장소 크기는 `max_entries`로 제한되며, 가득 차면 만료된 항목을 먼저 지우고
+//! 그래도 가득 차면 가장 오래된 진행 상태를 버립니다.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use super::types::CorrelationConfig;
+
+/// 진행 중인 순차 매칭
+#[derive(Debug)]
+struct CorrelationProgress {
+    /// 다음에 매칭되어야 하는 단계 인덱스
+    next_step: usize,
+    /// 첫 단계 매칭 시각
+    started_at: SystemTime,
+    /// 윈도우 만료 시각
+    expires_at: SystemTime,
+}
+
+/// 상관 분석 상태 저장소
+///
+/// threshold 카운터와 마찬가지로 `Mutex`로 감싸 `&self` 메서드에서 갱신합니다.
+#[derive(Debug)]
+pub struct CorrelationStore {
+    /// (correlation_rule_id, group_key) -> 진행 상태
+    progress: Mutex<HashMap<(String, String), CorrelationProgress>>,
+    /// 최대 진행 상태 수
+    max_entries: usize,
+}
+
+impl CorrelationStore {
+    /// 새 저장소를 생성합니다.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            progress: Mutex::new(HashMap::new()),
+            max_entries,
+        }
+    }
+
+    /// 이번 엔트리에서 매칭된 규칙으로 상관 규칙의 진행 상태를 갱신합니다.
+    ///
+    /// `fired`는 이번 엔트리에 매칭된 규칙 ID 집합입니다.
+    /// 순차 매칭이 완료되면 매칭된 단계 수를 반환합니다.
+    pub fn advance(
+        &self,
+        rule_id: &str,
+        config: &CorrelationConfig,
+        group_key: String,
+        fired: &HashSet<&str>,
+        now: SystemTime,
+    ) -> Option<usize> {
+        let mut progress = self.lock();
+        let key = (rule_id.to_owned(), group_key);
+
+        if progress.get(&key).is_some_and(|p| now > p.expires_at) {
+            progress.remove(&key);
+        }
+
+        if let Some(current) = progress.get_mut(&key) {
+            let step = config.rules.get(current.next_step)?;
+            if !fired.contains(step.as_str()) {
+                return None;
+            }
+            current.next_step += 1;
+            if current.next_step < config.rules.len() {
+                return None;
+            }
+            progress.remove(&key);
+            return Some(config.rules.len());
+        }
+
+        let first = config.rules.first()?;
+        if !fired.contains(first.as_str()) {
+            return None;
+        }
+        if progress.len() >= self.max_entries {
+            Self::evict(&mut progress, now, self.max_entries);
+        }
+        progress.insert(
+            key,
+            CorrelationProgress {
+                next_step: 1,
+                started_at: now,
+                expires_at: now + Duration::from_secs(config.timeframe_secs),
+            },
+        );
+        None
+    }
+
+    /// 상관 규칙의 진행 상태를 모두 제거합니다.
+    pub fn remove_rule(&self, rule_id: &str) {
+        self.lock().retain(|(id, _), _| id != rule_id);
+    }
+
+    /// 모든 진행 상태를 제거합니다.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// 현재 진행 상태 수를 반환합니다.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// 진행 상태가 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<(String, String), CorrelationProgress>> {
+        match self.progress.lock() {
+            Ok(progress) => progress,
+            Err(poisoned) => {
+                tracing::error!("correlation state mutex poisoned, recovering by clearing state");
+                let mut recovered = poisoned.into_inner();
+                recovered.clear();
+                recovered
+            }
+        }
+    }
+
+    /// 공간을 확보합니다: 만료 항목 제거 후에도 가득 차면 가장 오래된 항목을 버립니다.
+    fn evict(
+        progress: &mut HashMap<(String, String), CorrelationProgress>,
+        now: SystemTime,
+        max_entries: usize,
+    ) {
+        progress.retain(|_, p| now <= p.expires_at);
+        if progress.len() < max_entries {
+            return;
+        }
+        if let Some(oldest) = progress
+            .iter()
+            .min_by_key(|(_, p)| p.started_at)
+            .map(|(key, _)| key.clone())
+        {
+            tracing::warn!(
+                rule_id = %oldest.0,
+                max = max_entries,
+                "correlation state limit reached, dropping oldest sequence"
+            );
+            progress.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CorrelationConfig {
+        CorrelationConfig {
+            rules: vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
+            field: "source_ip".to_owned(),
+            timeframe_secs: 600,
+        }
+    }
+
+    fn fired<'a>(ids: &[&'a str]) -> HashSet<&'a str> {
+        ids.iter().copied().collect()
+    }
+
+    #[test]
+    fn completes_sequence_in_order() {
+        let store = CorrelationStore::new(100);
+        let config = config();
+        let now = SystemTime::now();
+        let key = || "10.0.0.1".to_owned();
+
+        assert_eq!(
+            store.advance("corr", &config, key(), &fired(&["b"]), now),
+            None
+        );
+        assert!(store.is_empty());
+        assert_eq!(
+            store.advance("corr", &config, key(), &fired(&["a"]), now),
+            None
+        );
+        assert_eq!(
+            store.advance("corr", &config, key(), &fired(&["c"]), now),
+            None
+        );
+        assert_eq!(
+            store.advance("corr", &config, key(), &fired(&["b"]), now),
+            None
+        );
+        assert_eq!(
+            store.advance("corr", &config, key(), &fired(&["c"]), now),
+            Some(3)
+        );
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn one_entry_advances_one_step() {
+        let store = CorrelationStore::new(100);
+        let config = config();
+        let now = SystemTime::now();
+        let all = fired(&["a", "b", "c"]);
+        assert_eq!(
+            store.advance("corr", &config, "k".to_owned(), &all, now),
+            None
+        );
+        assert_eq!(
+            store.advance("corr", &config, "k".to_owned(), &all, now),
+            None
+        );
+        assert_eq!(
+            store.advance("corr", &config, "k".to_owned(), &all, now),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn groups_are_tracked_separately() {
+        let store = CorrelationStore::new(100);
+        let mut config = config();
+        config.rules.truncate(2);
+        let now = SystemTime::now();
+        store.advance("corr", &config, "10.0.0.1".to_owned(), &fired(&["a"]), now);
+        assert_eq!(
+            store.advance("corr", &config, "10.0.0.2".to_owned(), &fired(&["b"]), now),
+            None
+        );
+        assert_eq!(
+            store.advance("corr", &config, "10.0.0.1".to_owned(), &fired(&["b"]), now),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn expired_sequence_restarts() {
+        let store = CorrelationStore::new(100);
+        let mut config = config();
+        config.rules.truncate(2);
+        let start = SystemTime::now();
+        let late = start + Duration::from_secs(601);
+
+        store.advance("corr", &config, "k".to_owned(), &fired(&["a"]), start);
+        assert_eq!(
+            store.advance("corr", &config, "k".to_owned(), &fired(&["b"]), late),
+            None
+        );
+        assert!(store.is_empty());
+
+        // 만료 후 첫 단계가 다시 매칭되면 새 윈도우 시작
+        store.advance("corr", &config, "k".to_owned(), &fired(&["a"]), late);
+        assert_eq!(
+            store.advance("corr", &config, "k".to_owned(), &fired(&["b"]), late),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn store_is_bounded() {
+        let store = CorrelationStore::new(2);
+        let config = config();
+        let now = SystemTime::now();
+        for (offset, key) in ["k1", "k2", "k3"].iter().enumerate() {
+            store.advance(
+                "corr",
+                &config,
+                (*key).to_owned(),
+                &fired(&["a"]),
+                now + Duration::from_secs(offset as u64),
+            );
+        }
+        assert_eq!(store.len(), 2);
+
+        // 가장 오래된 k1이 버려졌으므로 다음 단계가 전진하지 않음 (새로 시작하지도 않음)
+        store.advance("corr", &config, "k1".to_owned(), &fired(&["b"]), now);
+        assert_eq!(store.len(), 2);
+
+        store.remove_rule("corr");
+        assert!(store.is_empty());
+    }
+}
@@ -29,8 +29,27 @@ impl RuleLoader {
     pub async fn load_directory(
         dir: impl AsRef<Path>,
     ) -> Result<Vec<DetectionRule>, LogPipelineError> {
-        let dir = dir.as_ref();
+        Self::scan_directory(dir.as_ref(), false).await
+    }
+
+    /// 디렉토리의 모든 YAML 규칙 파일을 로드하되, 하나라도 실패하면 에러를 반환합니다.
+    ///
+    /// 실행 중 규칙 세트를 교체(hot reload)할 때 사용합니다. 잘못된 파일 하나 때문에
+    /// 해당 규칙이 조용히 빠지는 대신, 기존 규칙을 유지할 수 있도록 전체를 실패시킵니다.
+    ///
+    /// # Errors
+    /// - [`load_directory`](Self::load_directory)의 에러
+    /// - 규칙 파일 로딩 또는 검증 실패, 중복된 규칙 ID
+    pub async fn load_directory_strict(
+        dir: impl AsRef<Path>,
+    ) -> Result<Vec<DetectionRule>, LogPipelineError> {
+        Self::scan_directory(dir.as_ref(), true).await
+    }
 
+    async fn scan_directory(
+        dir: &Path,
+        strict: bool,
+    ) -> Result<Vec<DetectionRule>, LogPipelineError> {
         let mut entries =
             tokio::fs::read_dir(dir)
                 .await
@@ -66,6 +85,12 @@ impl RuleLoader {
                 Ok(rule) => {
                     // 중복 ID 검사
                     if seen_ids.contains(&rule.id) {
+                        if strict {
+                            return Err(LogPipelineError::RuleValidation {
+                                rule_id: rule.id,
+                                reason: format!("duplicate rule id in {}", path.display()),
+                            });
+                        }
                         tracing::warn!(
                             rule_id = %rule.id,
                             path = %path.display(),
@@ -76,6 +101,7 @@ impl RuleLoader {
                     seen_ids.insert(rule.id.clone());
                     rules.push(rule);
                 }
+                Err(e) if strict => return Err(e),
                 Err(e) => {
                     tracing::warn!(
                         path = %path.display(),
@@ -318,6 +344,18 @@ detection:
         // .txt 파일은 무시됨
         assert_eq!(rules.len(), 1);
         assert_eq!(rules[0].id, "valid_rule");
+
+        // strict 모드는 무효한 파일이 있으면 전체 실패
+        let err = RuleLoader::load_directory_strict(temp_dir.path())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid.yaml"));
+
+        std::fs::remove_file(&invalid_path).unwrap();
+        let rules = RuleLoader::load_directory_strict(temp_dir.path())
+            .await
+            .unwrap();
+        assert_eq!(rules.len(), 1);
     }
 
     #[tokio::test]
@@ -1,20 +1,30 @@
 //! 규칙 매칭 로직 -- 조건 평가 및 정규식 캐싱
 //!
 //! [`RuleMatcher`]는 규칙의 조건을 `LogEntry`에 대해 평가합니다.
-//! 정규식 패턴은 규칙 로딩 시 한 번만 컴파일하여 캐싱합니다.
+//! 정규식 패턴과 `condition` 조건식은 규칙 로딩 시 한 번만 컴파일하여 캐싱합니다.
 
 use std::collections::HashMap;
 
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 
 use ironpost_core::types::LogEntry;
 
+use super::cidr::{self, IpCidr};
+use super::condition::ConditionExpr;
 use super::types::{ConditionModifier, DetectionRule, FieldCondition};
 use crate::error::LogPipelineError;
 
 /// 정규식 최대 길이 (ReDoS 방어)
 const MAX_REGEX_LENGTH: usize = 1000;
 
+/// 컴파일된 정규식 프로그램의 최대 크기 (바이트)
+///
+/// `(a{100}){100}`처럼 짧지만 거대하게 펼쳐지는 패턴을 거부합니다.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// 정규식 매칭용 lazy DFA 캐시의 최대 크기 (바이트)
+const REGEX_DFA_SIZE_LIMIT: usize = 2 << 20;
+
 /// 위험한 정규식 패턴 (재귀적 quantifier 등)
 const FORBIDDEN_PATTERNS: &[&str] = &[
     r"\(\.\*\)\+",    // (.*)+ 형태
@@ -26,8 +36,15 @@ const FORBIDDEN_PATTERNS: &[&str] = &[
 ///
 /// 규칙 로딩 시 정규식을 미리 컴파일하여 매칭 시 재컴파일 오버헤드를 제거합니다.
 pub struct RuleMatcher {
-    /// 컴파일된 정규식 캐시: (rule_id, condition_index) -> Regex
-    regex_cache: HashMap<(String, usize), Regex>,
+    /// 컴파일된 정규식 캐시: rule_id -> (condition_index -> 값별 Regex)
+    ///
+    /// condition_index는 [`DetectionCondition::all_conditions`](super::types::DetectionCondition::all_conditions)
+    /// 순서의 인덱스입니다. 규칙 단위로 묶어 매칭 시 allocation 없이 조회합니다.
+    regex_cache: HashMap<String, HashMap<usize, Vec<Regex>>>,
+    /// 파싱된 CIDR 캐시: rule_id -> (condition_index -> CIDR 목록)
+    cidr_cache: HashMap<String, HashMap<usize, Vec<IpCidr>>>,
+    /// 파싱된 조건식 캐시: rule_id -> ConditionExpr
+    expressions: HashMap<String, ConditionExpr>,
 }
 
 impl RuleMatcher {
@@ -35,89 +52,204 @@ impl RuleMatcher {
     pub fn new() -> Self {
         Self {
             regex_cache: HashMap::new(),
+            cidr_cache: HashMap::new(),
+            expressions: HashMap::new(),
         }
     }
 
     /// 규칙의 정규식 조건을 미리 컴파일합니다.
     ///
     /// 규칙 추가 시 호출하여 정규식 패턴의 유효성을 검증하고 캐싱합니다.
-    /// ReDoS 공격을 방지하기 위해 패턴 길이와 위험한 패턴을 체크합니다.
+    /// ReDoS 공격을 방지하기 위해 패턴 길이와 위험한 패턴, 컴파일된 크기를 체크합니다.
+    /// CIDR 조건도 이때 파싱하여 잘못된 주소를 로딩 시점에 거부합니다.
+    /// `condition` 조건식이 있으면 함께 파싱하여 캐싱합니다.
+    /// 같은 ID의 규칙을 다시 컴파일하면 이전 캐시를 교체합니다.
     pub fn compile_rule(&mut self, rule: &DetectionRule) -> Result<(), LogPipelineError> {
-        for (idx, condition) in rule.detection.conditions.iter().enumerate() {
+        let mut compiled = HashMap::new();
+        let mut cidrs = HashMap::new();
+        for (idx, condition) in rule.detection.all_conditions().enumerate() {
+            if condition.modifier == ConditionModifier::Cidr {
+                let mut parsed = Vec::new();
+                for value in &condition.values {
+                    parsed.extend(IpCidr::parse_list(value).map_err(|e| {
+                        LogPipelineError::RuleValidation {
+                            rule_id: rule.id.clone(),
+                            reason: format!(
+                                "invalid cidr in condition[{idx}] for field '{}': {e}",
+                                condition.field
+                            ),
+                        }
+                    })?);
+                }
+                cidrs.insert(idx, parsed);
+            }
             if condition.modifier == ConditionModifier::Regex {
-                let pattern = &condition.value;
-
-                // 길이 체크
-                if pattern.len() > MAX_REGEX_LENGTH {
-                    return Err(LogPipelineError::RuleValidation {
-                        rule_id: rule.id.clone(),
-                        reason: format!(
-                            "regex pattern too long: {} chars (max: {})",
-                            pattern.len(),
-                            MAX_REGEX_LENGTH
-                        ),
-                    });
+                let mut regexes = Vec::with_capacity(condition.values.len());
+                for pattern in &condition.values {
+                    regexes.push(Self::compile_regex(rule, idx, condition, pattern)?);
                 }
+                compiled.insert(idx, regexes);
+            }
+        }
 
-                // 위험한 패턴 체크
-                for forbidden in FORBIDDEN_PATTERNS {
-                    if let Ok(forbidden_regex) = Regex::new(forbidden)
-                        && forbidden_regex.is_match(pattern)
-                    {
-                        return Err(LogPipelineError::RuleValidation {
-                            rule_id: rule.id.clone(),
-                            reason: "regex contains potentially catastrophic backtracking pattern"
-                                .to_owned(),
-                        });
-                    }
-                }
+        let expression = rule.detection.parse_condition().map_err(|reason| {
+            LogPipelineError::RuleValidation {
+                rule_id: rule.id.clone(),
+                reason,
+            }
+        })?;
+        match expression {
+            Some(expression) => {
+                self.expressions.insert(rule.id.clone(), expression);
+            }
+            None => {
+                self.expressions.remove(&rule.id);
+            }
+        }
+        if compiled.is_empty() {
+            self.regex_cache.remove(&rule.id);
+        } else {
+            self.regex_cache.insert(rule.id.clone(), compiled);
+        }
+        if cidrs.is_empty() {
+            self.cidr_cache.remove(&rule.id);
+        } else {
+            self.cidr_cache.insert(rule.id.clone(), cidrs);
+        }
+        Ok(())
+    }
 
-                // 컴파일 시간 제한 (비동기 컨텍스트가 아니므로 단순 시도)
-                let regex = Regex::new(pattern).map_err(|e| LogPipelineError::RuleValidation {
+    /// 정규식 패턴 하나를 검사하고 컴파일합니다.
+    fn compile_regex(
+        rule: &DetectionRule,
+        idx: usize,
+        condition: &FieldCondition,
+        pattern: &str,
+    ) -> Result<Regex, LogPipelineError> {
+        // 길이 체크
+        if pattern.len() > MAX_REGEX_LENGTH {
+            return Err(LogPipelineError::RuleValidation {
+                rule_id: rule.id.clone(),
+                reason: format!(
+                    "regex pattern too long: {} chars (max: {})",
+                    pattern.len(),
+                    MAX_REGEX_LENGTH
+                ),
+            });
+        }
+
+        // 위험한 패턴 체크
+        for forbidden in FORBIDDEN_PATTERNS {
+            if let Ok(forbidden_regex) = Regex::new(forbidden)
+                && forbidden_regex.is_match(pattern)
+            {
+                return Err(LogPipelineError::RuleValidation {
                     rule_id: rule.id.clone(),
-                    reason: format!(
-                        "invalid regex in condition[{idx}] for field '{}': {e}",
-                        condition.field
-                    ),
-                })?;
-                self.regex_cache.insert((rule.id.clone(), idx), regex);
+                    reason: "regex contains potentially catastrophic backtracking pattern"
+                        .to_owned(),
+                });
             }
         }
-        Ok(())
+
+        // 컴파일 크기 제한 (거대하게 펼쳐지는 반복 패턴 방어)
+        RegexBuilder::new(pattern)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .dfa_size_limit(REGEX_DFA_SIZE_LIMIT)
+            .build()
+            .map_err(|e| LogPipelineError::RuleValidation {
+                rule_id: rule.id.clone(),
+                reason: format!(
+                    "invalid regex in condition[{idx}] for field '{}': {e}",
+                    condition.field
+                ),
+            })
     }
 
     /// 규칙 제거 시 캐시를 정리합니다.
     pub fn remove_rule(&mut self, rule_id: &str) {
-        self.regex_cache.retain(|(id, _), _| id != rule_id);
+        self.regex_cache.remove(rule_id);
+        self.cidr_cache.remove(rule_id);
+        self.expressions.remove(rule_id);
     }
 
-    /// 규칙의 모든 조건이 LogEntry에 매칭되는지 평가합니다.
+    /// 규칙의 조건이 LogEntry에 매칭되는지 평가합니다.
     ///
-    /// 모든 조건이 AND 결합이므로, 하나라도 실패하면 false를 반환합니다.
+    /// `conditions`는 AND 결합이므로, 하나라도 실패하면 false를 반환합니다.
+    /// `condition` 조건식이 있으면 이어서 selection들을 조건식에 따라 평가합니다.
     /// 조건이 비어있으면 true를 반환합니다 (모든 로그에 매칭).
     pub fn matches(
         &self,
         rule: &DetectionRule,
         entry: &LogEntry,
     ) -> Result<bool, LogPipelineError> {
-        for (idx, condition) in rule.detection.conditions.iter().enumerate() {
+        // 상관 규칙은 개별 엔트리가 아닌 다른 규칙의 매칭으로 평가됨
+        if rule.is_correlation() {
+            return Ok(false);
+        }
+        let detection = &rule.detection;
+        if !self.all_match(&detection.conditions, 0, &rule.id, entry)? {
+            return Ok(false); // AND 로직: 하나라도 실패하면 전체 실패
+        }
+
+        if detection.condition.is_none() {
+            return Ok(true); // 모든 조건 통과
+        }
+        let expression = self.expressions.get(&rule.id).ok_or_else(|| {
+            LogPipelineError::RuleMatch(format!("condition not compiled for rule '{}'", rule.id))
+        })?;
+
+        expression.evaluate(&mut |selection| {
+            // selection의 regex 캐시 인덱스는 앞선 조건 수만큼 밀려 있음
+            let mut offset = detection.conditions.len();
+            for (index, sel) in detection.selections.values().enumerate() {
+                if index != selection {
+                    offset += sel.groups.iter().map(Vec::len).sum::<usize>();
+                    continue;
+                }
+                // 그룹 중 하나라도 모두 매칭되면 selection 매칭 (OR)
+                for group in &sel.groups {
+                    if self.all_match(group, offset, &rule.id, entry)? {
+                        return Ok(true);
+                    }
+                    offset += group.len();
+                }
+                return Ok(false);
+            }
+            Err(LogPipelineError::RuleMatch(format!(
+                "selection index {selection} out of range for rule '{}'",
+                rule.id
+            )))
+        })
+    }
+
+    /// 조건 목록이 모두 매칭되는지 평가합니다.
+    ///
+    /// `offset`은 첫 조건의 regex 캐시 인덱스입니다.
+    fn all_match(
+        &self,
+        conditions: &[FieldCondition],
+        offset: usize,
+        rule_id: &str,
+        entry: &LogEntry,
+    ) -> Result<bool, LogPipelineError> {
+        for (idx, condition) in conditions.iter().enumerate() {
             let field_value = Self::get_field_value(entry, &condition.field);
 
             let matched = match field_value {
-                Some(value) => self.evaluate_condition(condition, value, &rule.id, idx)?,
+                Some(value) => self.evaluate_condition(condition, value, rule_id, offset + idx)?,
                 None => false, // 필드가 없으면 매칭 실패
             };
 
             if !matched {
-                return Ok(false); // AND 로직: 하나라도 실패하면 전체 실패
+                return Ok(false);
             }
         }
 
-        Ok(true) // 모든 조건 통과
+        Ok(true)
     }
 
     /// LogEntry에서 필드 값을 추출합니다.
-    fn get_field_value<'a>(entry: &'a LogEntry, field: &str) -> Option<&'a str> {
+    pub(crate) fn get_field_value<'a>(entry: &'a LogEntry, field: &str) -> Option<&'a str> {
         match field {
             "hostname" => Some(&entry.hostname),
             "process" => Some(&entry.process),
@@ -134,7 +266,7 @@ impl RuleMatcher {
         }
     }
 
-    /// 단일 조건을 평가합니다.
+    /// 단일 조건을 평가합니다. 값 중 하나라도 매칭되면 참입니다.
     fn evaluate_condition(
         &self,
         condition: &FieldCondition,
@@ -143,27 +275,50 @@ impl RuleMatcher {
         condition_idx: usize,
     ) -> Result<bool, LogPipelineError> {
         match condition.modifier {
-            ConditionModifier::Exact => Ok(field_value == condition.value),
+            ConditionModifier::Exact => Ok(condition.values.iter().any(|v| field_value == v)),
 
-            ConditionModifier::Contains => Ok(field_value.contains(&condition.value)),
+            ConditionModifier::Contains => Ok(condition
+                .values
+                .iter()
+                .any(|v| field_value.contains(v.as_str()))),
 
-            ConditionModifier::StartsWith => Ok(field_value.starts_with(&condition.value)),
+            ConditionModifier::StartsWith => Ok(condition
+                .values
+                .iter()
+                .any(|v| field_value.starts_with(v.as_str()))),
 
-            ConditionModifier::EndsWith => Ok(field_value.ends_with(&condition.value)),
+            ConditionModifier::EndsWith => Ok(condition
+                .values
+                .iter()
+                .any(|v| field_value.ends_with(v.as_str()))),
 
             ConditionModifier::Regex => {
                 // HashMap lookup을 allocation 없이 수행
                 let regex = self
                     .regex_cache
-                    .iter()
-                    .find(|((id, idx), _)| id.as_str() == rule_id && *idx == condition_idx)
-                    .map(|(_, r)| r)
+                    .get(rule_id)
+                    .and_then(|compiled| compiled.get(&condition_idx))
                     .ok_or_else(|| {
                         LogPipelineError::RuleMatch(format!(
                             "regex not compiled for rule '{rule_id}' condition[{condition_idx}]"
                         ))
                     })?;
-                Ok(regex.is_match(field_value))
+                Ok(regex.iter().any(|regex| regex.is_match(field_value)))
+            }
+
+            ConditionModifier::Cidr => {
+                let cidrs = self
+                    .cidr_cache
+                    .get(rule_id)
+                    .and_then(|compiled| compiled.get(&condition_idx))
+                    .ok_or_else(|| {
+                        LogPipelineError::RuleMatch(format!(
+                            "cidr not compiled for rule '{rule_id}' condition[{condition_idx}]"
+                        ))
+                    })?;
+                // IP가 아닌 값은 매칭 실패
+                Ok(cidr::parse_field_ip(field_value)
+                    .is_some_and(|addr| cidrs.iter().any(|cidr| cidr.contains(addr))))
             }
         }
     }
@@ -197,6 +352,14 @@ mod tests {
         }
     }
 
+    fn condition(field: &str, modifier: ConditionModifier, value: &str) -> FieldCondition {
+        FieldCondition {
+            field: field.to_owned(),
+            modifier,
+            values: vec![value.to_owned()],
+        }
+    }
+
     fn make_rule(conditions: Vec<FieldCondition>) -> DetectionRule {
         DetectionRule {
             id: "test_rule".to_owned(),
@@ -206,8 +369,9 @@ mod tests {
             status: RuleStatus::Enabled,
             detection: DetectionCondition {
                 conditions,
-                threshold: None,
+                ..Default::default()
             },
+            correlation: None,
             tags: vec![],
         }
     }
@@ -218,7 +382,7 @@ mod tests {
         let rule = make_rule(vec![FieldCondition {
             field: "process".to_owned(),
             modifier: ConditionModifier::Exact,
-            value: "sshd".to_owned(),
+            values: vec!["sshd".to_owned()],
         }]);
         matcher.compile_rule(&rule).unwrap();
         assert!(matcher.matches(&rule, &sample_entry()).unwrap());
@@ -230,7 +394,7 @@ mod tests {
         let rule = make_rule(vec![FieldCondition {
             field: "process".to_owned(),
             modifier: ConditionModifier::Exact,
-            value: "nginx".to_owned(),
+            values: vec!["nginx".to_owned()],
         }]);
         matcher.compile_rule(&rule).unwrap();
         assert!(!matcher.matches(&rule, &sample_entry()).unwrap());
@@ -242,7 +406,7 @@ mod tests {
         let rule = make_rule(vec![FieldCondition {
             field: "message".to_owned(),
             modifier: ConditionModifier::Contains,
-            value: "Failed password".to_owned(),
+            values: vec!["Failed password".to_owned()],
         }]);
         matcher.compile_rule(&rule).unwrap();
         assert!(matcher.matches(&rule, &sample_entry()).unwrap());
@@ -254,7 +418,7 @@ mod tests {
         let rule = make_rule(vec![FieldCondition {
             field: "hostname".to_owned(),
             modifier: ConditionModifier::StartsWith,
-            value: "web-".to_owned(),
+            values: vec!["web-".to_owned()],
         }]);
         matcher.compile_rule(&rule).unwrap();
         assert!(matcher.matches(&rule, &sample_entry()).unwrap());
@@ -266,7 +430,7 @@ mod tests {
         let rule = make_rule(vec![FieldCondition {
             field: "source".to_owned(),
             modifier: ConditionModifier::EndsWith,
-            value: "syslog".to_owned(),
+            values: vec!["syslog".to_owned()],
         }]);
         matcher.compile_rule(&rule).unwrap();
         assert!(matcher.matches(&rule, &sample_entry()).unwrap());
@@ -278,7 +442,7 @@ mod tests {
         let rule = make_rule(vec![FieldCondition {
             field: "message".to_owned(),
             modifier: ConditionModifier::Regex,
-            value: r"Failed.*root.*\d+\.\d+\.\d+\.\d+".to_owned(),
+            values: vec![r"Failed.*root.*\d+\.\d+\.\d+\.\d+".to_owned()],
         }]);
         matcher.compile_rule(&rule).unwrap();
         assert!(matcher.matches(&rule, &sample_entry()).unwrap());
@@ -290,11 +454,195 @@ mod tests {
         let rule = make_rule(vec![FieldCondition {
             field: "message".to_owned(),
             modifier: ConditionModifier::Regex,
-            value: r"[invalid".to_owned(),
+            values: vec![r"[invalid".to_owned()],
         }]);
         assert!(matcher.compile_rule(&rule).is_err());
     }
 
+    #[test]
+    fn oversized_regex_fails_compilation() {
+        let mut matcher = RuleMatcher::new();
+        // 짧지만 컴파일하면 크기 제한을 넘는 패턴
+        let rule = make_rule(vec![condition(
+            "message",
+            ConditionModifier::Regex,
+            r"(\w{100}){100}",
+        )]);
+        let err = matcher.compile_rule(&rule).unwrap_err();
+        assert!(err.to_string().contains("condition[0]"));
+        assert!(matcher.regex_cache.is_empty());
+    }
+
+    #[test]
+    fn forbidden_regex_pattern_is_rejected() {
+        let mut matcher = RuleMatcher::new();
+        let rule = make_rule(vec![condition(
+            "message",
+            ConditionModifier::Regex,
+            "(.*)+x",
+        )]);
+        assert!(matcher.compile_rule(&rule).is_err());
+    }
+
+    #[test]
+    fn recompiling_rule_replaces_cached_regex() {
+        let mut matcher = RuleMatcher::new();
+        let mut rule = make_rule(vec![condition(
+            "message",
+            ConditionModifier::Regex,
+            "^nomatch$",
+        )]);
+        matcher.compile_rule(&rule).unwrap();
+        assert!(!matcher.matches(&rule, &sample_entry()).unwrap());
+
+        rule.detection.conditions[0].values = vec!["^Failed".to_owned()];
+        matcher.compile_rule(&rule).unwrap();
+        assert!(matcher.matches(&rule, &sample_entry()).unwrap());
+
+        rule.detection.conditions[0].modifier = ConditionModifier::Contains;
+        matcher.compile_rule(&rule).unwrap();
+        assert!(matcher.regex_cache.is_empty());
+    }
+
+    #[test]
+    fn cidr_match() {
+        let mut matcher = RuleMatcher::new();
+        let rule = make_rule(vec![condition(
+            "source_ip",
+            ConditionModifier::Cidr,
+            "10.0.0.0/8, 192.168.0.0/16",
+        )]);
+        matcher.compile_rule(&rule).unwrap();
+
+        let mut entry = sample_entry();
+        assert!(matcher.matches(&rule, &entry).unwrap());
+
+        entry.fields[1].1 = "203.0.113.7".to_owned();
+        assert!(!matcher.matches(&rule, &entry).unwrap());
+
+        // IP가 아닌 값은 에러 없이 매칭 실패
+        entry.fields[1].1 = "unknown".to_owned();
+        assert!(!matcher.matches(&rule, &entry).unwrap());
+    }
+
+    #[test]
+    fn invalid_cidr_fails_compilation() {
+        let mut matcher = RuleMatcher::new();
+        let rule = make_rule(vec![condition(
+            "source_ip",
+            ConditionModifier::Cidr,
+            "10.0.0.0/40",
+        )]);
+        let err = matcher.compile_rule(&rule).unwrap_err();
+        assert!(err.to_string().contains("invalid cidr"));
+        assert!(matcher.cidr_cache.is_empty());
+    }
+
+    #[test]
+    fn cidr_filter_in_selection() {
+        let yaml = r#"
+id: ssh_external_failure
+title: SSH Failure From External Host
+severity: Medium
+detection:
+  selections:
+    selection:
+      process: sshd
+      message|contains: Failed password
+    filter_internal:
+      - source_ip|cidr: 10.0.0.0/8, 172.16.0.0/12
+  condition: selection and not filter_internal
+"#;
+        let rule: DetectionRule = serde_yaml::from_str(yaml).unwrap();
+        let mut matcher = RuleMatcher::new();
+        matcher.compile_rule(&rule).unwrap();
+
+        let mut entry = sample_entry();
+        assert!(matcher.matches(&rule, &entry).unwrap());
+        entry.fields[1].1 = "172.20.1.1".to_owned();
+        assert!(!matcher.matches(&rule, &entry).unwrap());
+
+        matcher.remove_rule(&rule.id);
+        assert!(matcher.cidr_cache.is_empty());
+    }
+
+    #[test]
+    fn value_list_matches_any_value() {
+        let yaml = r#"
+id: auth_failure
+title: Auth Failure
+severity: Medium
+detection:
+  conditions:
+    - process: [nginx, sshd]
+    - message|regex: ["^Invalid user", "^Failed password"]
+"#;
+        let rule: DetectionRule = serde_yaml::from_str(yaml).unwrap();
+        let mut matcher = RuleMatcher::new();
+        matcher.compile_rule(&rule).unwrap();
+        assert_eq!(matcher.regex_cache["auth_failure"][&1].len(), 2);
+
+        let mut entry = sample_entry();
+        assert!(matcher.matches(&rule, &entry).unwrap());
+        entry.message = "Invalid user admin from 10.0.0.1".to_owned();
+        assert!(matcher.matches(&rule, &entry).unwrap());
+        entry.process = "su".to_owned();
+        assert!(!matcher.matches(&rule, &entry).unwrap());
+    }
+
+    #[test]
+    fn list_of_maps_selection_matches_any_group() {
+        let yaml = r#"
+id: auth_failure
+title: Auth Failure
+severity: Medium
+detection:
+  selections:
+    selection:
+      - process: sshd
+        message|regex: "^Failed password"
+      - process: su
+        message|contains: authentication failure
+    filter:
+      hostname|endswith: [-dev, -test]
+  condition: selection and not filter
+"#;
+        let rule: DetectionRule = serde_yaml::from_str(yaml).unwrap();
+        rule.validate().unwrap();
+        let mut matcher = RuleMatcher::new();
+        matcher.compile_rule(&rule).unwrap();
+
+        let mut entry = sample_entry();
+        assert!(matcher.matches(&rule, &entry).unwrap());
+
+        // 두 번째 그룹으로 매칭
+        entry.process = "su".to_owned();
+        assert!(!matcher.matches(&rule, &entry).unwrap());
+        entry.message = "pam_unix(su:auth): authentication failure".to_owned();
+        assert!(matcher.matches(&rule, &entry).unwrap());
+
+        // filter의 값 목록 중 하나에 매칭되면 제외
+        entry.hostname = "web-test".to_owned();
+        assert!(!matcher.matches(&rule, &entry).unwrap());
+    }
+
+    #[test]
+    fn shorthand_regex_rule_from_yaml() {
+        let yaml = r#"
+id: ssh_root_failure
+title: SSH Root Failure
+severity: High
+detection:
+  conditions:
+    - process: sshd
+    - message|regex: "^Failed password for root from \\d+\\.\\d+"
+"#;
+        let rule: DetectionRule = serde_yaml::from_str(yaml).unwrap();
+        let mut matcher = RuleMatcher::new();
+        matcher.compile_rule(&rule).unwrap();
+        assert!(matcher.matches(&rule, &sample_entry()).unwrap());
+    }
+
     #[test]
     fn and_logic_all_must_match() {
         let mut matcher = RuleMatcher::new();
@@ -302,12 +650,12 @@ mod tests {
             FieldCondition {
                 field: "process".to_owned(),
                 modifier: ConditionModifier::Exact,
-                value: "sshd".to_owned(),
+                values: vec!["sshd".to_owned()],
             },
             FieldCondition {
                 field: "message".to_owned(),
                 modifier: ConditionModifier::Contains,
-                value: "Failed".to_owned(),
+                values: vec!["Failed".to_owned()],
             },
         ]);
         matcher.compile_rule(&rule).unwrap();
@@ -321,12 +669,12 @@ mod tests {
             FieldCondition {
                 field: "process".to_owned(),
                 modifier: ConditionModifier::Exact,
-                value: "sshd".to_owned(),
+                values: vec!["sshd".to_owned()],
             },
             FieldCondition {
                 field: "hostname".to_owned(),
                 modifier: ConditionModifier::Exact,
-                value: "wrong-host".to_owned(),
+                values: vec!["wrong-host".to_owned()],
             },
         ]);
         matcher.compile_rule(&rule).unwrap();
@@ -346,7 +694,7 @@ mod tests {
         let rule = make_rule(vec![FieldCondition {
             field: "source_ip".to_owned(),
             modifier: ConditionModifier::Exact,
-            value: "192.168.1.100".to_owned(),
+            values: vec!["192.168.1.100".to_owned()],
         }]);
         matcher.compile_rule(&rule).unwrap();
         assert!(matcher.matches(&rule, &sample_entry()).unwrap());
@@ -358,7 +706,7 @@ mod tests {
         let rule = make_rule(vec![FieldCondition {
             field: "nonexistent_field".to_owned(),
             modifier: ConditionModifier::Exact,
-            value: "anything".to_owned(),
+            values: vec!["anything".to_owned()],
         }]);
         matcher.compile_rule(&rule).unwrap();
         assert!(!matcher.matches(&rule, &sample_entry()).unwrap());
@@ -370,7 +718,7 @@ mod tests {
         let rule = make_rule(vec![FieldCondition {
             field: "message".to_owned(),
             modifier: ConditionModifier::Regex,
-            value: ".*".to_owned(),
+            values: vec![".*".to_owned()],
         }]);
         matcher.compile_rule(&rule).unwrap();
         assert!(!matcher.regex_cache.is_empty());
@@ -378,4 +726,86 @@ mod tests {
         matcher.remove_rule("test_rule");
         assert!(matcher.regex_cache.is_empty());
     }
+
+    #[test]
+    fn selection_and_not_filter() {
+        let mut matcher = RuleMatcher::new();
+        let mut rule = make_rule(vec![]);
+        rule.detection.selections.insert(
+            "selection".to_owned(),
+            vec![
+                condition("process", ConditionModifier::Exact, "sshd"),
+                condition("message", ConditionModifier::Contains, "Failed password"),
+            ]
+            .into(),
+        );
+        rule.detection.selections.insert(
+            "filter".to_owned(),
+            vec![condition("source_ip", ConditionModifier::Regex, r"^10\.")].into(),
+        );
+        rule.detection.condition = Some("selection and not filter".to_owned());
+        matcher.compile_rule(&rule).unwrap();
+
+        let mut entry = sample_entry();
+        assert!(matcher.matches(&rule, &entry).unwrap());
+
+        entry.fields[1].1 = "10.0.0.5".to_owned();
+        assert!(!matcher.matches(&rule, &entry).unwrap());
+    }
+
+    #[test]
+    fn one_of_selections_with_base_conditions() {
+        let mut matcher = RuleMatcher::new();
+        // 공통 조건 + selection 조건식 (regex 인덱스가 selection 쪽으로 밀리는지 확인)
+        let mut rule = make_rule(vec![condition(
+            "hostname",
+            ConditionModifier::Regex,
+            "^web-",
+        )]);
+        rule.detection.selections.insert(
+            "sel_nginx".to_owned(),
+            vec![condition("process", ConditionModifier::Exact, "nginx")].into(),
+        );
+        rule.detection.selections.insert(
+            "sel_ssh".to_owned(),
+            vec![condition("message", ConditionModifier::Regex, r"port \d+$")].into(),
+        );
+        rule.detection.condition = Some("1 of sel_*".to_owned());
+        matcher.compile_rule(&rule).unwrap();
+        assert_eq!(matcher.regex_cache["test_rule"].len(), 2);
+
+        let mut entry = sample_entry();
+        assert!(matcher.matches(&rule, &entry).unwrap());
+
+        entry.hostname = "db-01".to_owned();
+        assert!(!matcher.matches(&rule, &entry).unwrap());
+
+        rule.detection.condition = Some("all of them".to_owned());
+        matcher.compile_rule(&rule).unwrap();
+        assert!(!matcher.matches(&rule, &sample_entry()).unwrap());
+    }
+
+    #[test]
+    fn uncompiled_condition_is_error() {
+        let matcher = RuleMatcher::new();
+        let mut rule = make_rule(vec![]);
+        rule.detection.selections.insert(
+            "selection".to_owned(),
+            vec![condition("process", ConditionModifier::Exact, "sshd")].into(),
+        );
+        rule.detection.condition = Some("selection".to_owned());
+        assert!(matcher.matches(&rule, &sample_entry()).is_err());
+    }
+
+    #[test]
+    fn invalid_condition_fails_compilation() {
+        let mut matcher = RuleMatcher::new();
+        let mut rule = make_rule(vec![]);
+        rule.detection.selections.insert(
+            "selection".to_owned(),
+            vec![condition("process", ConditionModifier::Exact, "sshd")].into(),
+        );
+        rule.detection.condition = Some("selection and".to_owned());
+        assert!(matcher.compile_rule(&rule).is_err());
+    }
 }
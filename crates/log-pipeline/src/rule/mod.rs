@@ -10,36 +10,53 @@
 //! severity: high
 //! status: enabled
 //! detection:
-//!   condition:
-//!     process: sshd
-//!     message|contains: "Failed password"
+//!   conditions:
+//!     - process: sshd
+//!     - message|contains: "Failed password"
 //!   threshold:
 //!     field: source_ip
 //!     count: 5
-//!     timeframe: 300
+//!     timeframe_secs: 300
 //! ```
 //!
 //! # 아키텍처
 //! - [`RuleEngine`]: 규칙 관리 및 매칭 코디네이터
+//! - [`cidr`]: IP 필드의 CIDR 매칭 (`src_ip|cidr: 10.0.0.0/8`)
+//! - [`condition`]: Sigma 스타일 조건식 (`selection and not filter`)
+//! - [`correlation`]: 규칙 간 순차 매칭 상태 저장소 ("A 후 B" 복합 알림)
 //! - [`loader`]: YAML 파일 로딩 및 유효성 검증
 //! - [`matcher`]: 조건 매칭 로직 (exact, contains, regex 등)
+//! - [`suppression`]: 규칙별 억제(예외) 목록
 //! - [`types`]: 규칙 데이터 구조 정의
+//! - [`watcher`]: 규칙 디렉토리 감시 및 hot reload
 
+pub mod cidr;
+pub mod condition;
+pub mod correlation;
 pub mod loader;
 pub mod matcher;
+pub mod suppression;
 pub mod types;
+pub mod watcher;
 
+pub use condition::ConditionExpr;
+pub use correlation::CorrelationStore;
 pub use loader::RuleLoader;
 pub use matcher::RuleMatcher;
+pub use suppression::{Suppression, SuppressionList};
 pub use types::{
-    ConditionModifier, DetectionCondition, DetectionRule, RuleStatus, ThresholdConfig,
+    ConditionModifier, CorrelationConfig, DetectionCondition, DetectionRule, RuleStatus,
+    ThresholdConfig,
 };
+pub use watcher::RuleWatcher;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::SystemTime;
 
 use ironpost_core::error::IronpostError;
+use ironpost_core::metrics as m;
 use ironpost_core::types::{Alert, LogEntry};
 
 use crate::error::LogPipelineError;
@@ -60,7 +77,7 @@ pub struct RuleMatch {
 /// 규칙 엔진 -- 탐지 규칙 관리 및 매칭 코디네이터
 ///
 /// YAML 규칙을 로드하고, `LogEntry`에 대해 모든 활성 규칙을 평가합니다.
-/// threshold 기반 규칙은 내부 카운터로 관리합니다.
+/// threshold 기반 규칙은 내부 카운터로, 상관 규칙은 [`CorrelationStore`]로 관리합니다.
 ///
 /// # 사용 예시
 /// ```ignore
@@ -82,8 +99,19 @@ pub struct RuleEngine {
     threshold_counters: Arc<Mutex<HashMap<(String, String), ThresholdCounter>>>,
     /// threshold 카운터 최대 항목 수 (메모리 성장 제한)
     max_threshold_entries: usize,
+    /// 규칙별 조건 매칭 횟수 (threshold 도달 여부와 무관, 누적)
+    match_counts: HashMap<String, AtomicU64>,
+    /// 상관 규칙 순차 매칭 상태 (항목 수 제한)
+    correlations: CorrelationStore,
+    /// 상관 규칙이 참조하는 규칙 ID (test 상태여도 매칭을 수행)
+    correlated: HashSet<String>,
+    /// 알림 억제 목록 (매칭 결과에서 제외)
+    suppressions: SuppressionList,
 }
 
+/// 상관 분석 상태 기본 최대 항목 수
+const DEFAULT_MAX_CORRELATION_ENTRIES: usize = 100_000;
+
 /// Threshold 카운터
 #[derive(Debug)]
 struct ThresholdCounter {
@@ -103,6 +131,10 @@ impl RuleEngine {
             matcher: RuleMatcher::new(),
             threshold_counters: Arc::new(Mutex::new(HashMap::new())),
             max_threshold_entries: 100_000,
+            match_counts: HashMap::new(),
+            correlations: CorrelationStore::new(DEFAULT_MAX_CORRELATION_ENTRIES),
+            correlated: HashSet::new(),
+            suppressions: SuppressionList::new(),
         }
     }
 
@@ -112,6 +144,12 @@ impl RuleEngine {
         self
     }
 
+    /// 상관 분석 상태의 최대 항목 수를 설정합니다.
+    pub fn with_max_correlation_entries(mut self, max: usize) -> Self {
+        self.correlations = CorrelationStore::new(max);
+        self
+    }
+
     /// 디렉토리에서 YAML 규칙 파일을 로드합니다.
     pub async fn load_rules_from_dir(
         &mut self,
@@ -122,6 +160,9 @@ impl RuleEngine {
         for rule in rules {
             self.add_rule(rule)?;
         }
+        if let Err(e) = Self::check_correlation_refs(&self.rules) {
+            tracing::warn!(error = %e, "correlation rule will never match");
+        }
         Ok(count)
     }
 
@@ -129,18 +170,84 @@ impl RuleEngine {
     pub fn add_rule(&mut self, rule: DetectionRule) -> Result<(), LogPipelineError> {
         rule.validate()?;
         self.matcher.compile_rule(&rule)?;
+        self.match_counts
+            .entry(rule.id.clone())
+            .or_insert_with(|| AtomicU64::new(0));
         self.rules.insert(rule.id.clone(), rule);
+        self.refresh_correlated();
         Ok(())
     }
 
     /// 규칙을 제거합니다.
     pub fn remove_rule(&mut self, rule_id: &str) -> Option<DetectionRule> {
         self.matcher.remove_rule(rule_id);
-        // 관련 threshold 카운터도 제거
+        // 관련 threshold 카운터와 상관 분석 상태도 제거
         if let Ok(mut counters) = self.threshold_counters.lock() {
             counters.retain(|(id, _), _| id != rule_id);
         }
-        self.rules.remove(rule_id)
+        self.correlations.remove_rule(rule_id);
+        self.match_counts.remove(rule_id);
+        let removed = self.rules.remove(rule_id);
+        self.refresh_correlated();
+        removed
+    }
+
+    /// 알림 억제 목록을 교체합니다.
+    ///
+    /// 억제는 규칙 매칭(threshold 카운팅, 상관 분석 포함)이 끝난 뒤 결과에 적용되므로
+    /// 억제된 매칭도 매칭 횟수와 상관 규칙 진행에는 반영됩니다.
+    pub fn set_suppressions(&mut self, suppressions: SuppressionList) {
+        self.suppressions = suppressions;
+    }
+
+    /// 억제 항목 수를 반환합니다.
+    pub fn suppression_count(&self) -> usize {
+        self.suppressions.len()
+    }
+
+    /// 전체 규칙 세트를 원자적으로 교체합니다 (hot reload).
+    ///
+    /// 모든 규칙을 새 매처에 먼저 컴파일하고, 하나라도 실패하면
+    /// 기존 규칙을 그대로 유지합니다. 교체 시 threshold 카운터와 상관 분석 상태는
+    /// 초기화되며, 매칭 횟수는 ID가 유지되는 규칙에 한해 보존됩니다.
+    ///
+    /// # Errors
+    /// - 규칙 유효성 검증 또는 컴파일 실패
+    /// - 중복된 규칙 ID
+    /// - 상관 규칙이 없는 규칙이나 다른 상관 규칙을 참조
+    pub fn replace_rules(&mut self, rules: Vec<DetectionRule>) -> Result<usize, LogPipelineError> {
+        let mut matcher = RuleMatcher::new();
+        let mut new_rules = HashMap::with_capacity(rules.len());
+        for rule in rules {
+            rule.validate()?;
+            if new_rules.contains_key(&rule.id) {
+                return Err(LogPipelineError::RuleValidation {
+                    rule_id: rule.id,
+                    reason: "duplicate rule id".to_owned(),
+                });
+            }
+            matcher.compile_rule(&rule)?;
+            new_rules.insert(rule.id.clone(), rule);
+        }
+        Self::check_correlation_refs(&new_rules)?;
+
+        let count = new_rules.len();
+        let mut match_counts = std::mem::take(&mut self.match_counts);
+        self.match_counts = new_rules
+            .keys()
+            .map(|id| {
+                let previous = match_counts.remove(id).map_or(0, |c| c.into_inner());
+                (id.clone(), AtomicU64::new(previous))
+            })
+            .collect();
+        self.rules = new_rules;
+        self.matcher = matcher;
+        if let Ok(mut counters) = self.threshold_counters.lock() {
+            counters.clear();
+        }
+        self.correlations.clear();
+        self.refresh_correlated();
+        Ok(count)
     }
 
     /// 현재 로드된 규칙 수를 반환합니다.
@@ -148,17 +255,42 @@ impl RuleEngine {
         self.rules.len()
     }
 
+    /// 로드된 규칙을 ID 순으로 반환합니다.
+    pub fn rules(&self) -> Vec<&DetectionRule> {
+        let mut rules: Vec<_> = self.rules.values().collect();
+        rules.sort_by(|a, b| a.id.cmp(&b.id));
+        rules
+    }
+
+    /// 규칙의 누적 조건 매칭 횟수를 반환합니다 (없는 규칙이면 0).
+    ///
+    /// threshold 규칙은 알림 생성 여부와 관계없이 조건에 매칭된 횟수를 셉니다.
+    pub fn match_count(&self, rule_id: &str) -> u64 {
+        self.match_counts
+            .get(rule_id)
+            .map_or(0, |c| c.load(Ordering::Relaxed))
+    }
+
     /// 로그 엔트리에 대해 모든 활성 규칙을 평가합니다.
     ///
     /// 매칭된 규칙 목록을 반환합니다.
     /// threshold 규칙은 임계값에 도달한 경우에만 결과에 포함됩니다.
+    /// 상관 규칙은 참조한 규칙들이 순서대로 매칭을 마친 엔트리에서 결과에 포함됩니다.
+    /// 상관 규칙이 참조하는 `test` 상태 규칙은 매칭만 수행하고 결과에는 포함되지 않으므로,
+    /// 개별 알림 없이 복합 알림만 받을 수 있습니다.
     ///
     /// Note: `threshold_counters`가 `Arc<Mutex<_>>`로 변경되어 `&self`로 호출 가능
     pub fn evaluate(&self, entry: &LogEntry) -> Result<Vec<RuleMatch>, LogPipelineError> {
         let mut matches = Vec::new();
+        // 이번 엔트리에서 매칭된 (threshold 도달 포함) 규칙 ID
+        let mut fired = HashSet::new();
 
         for rule in self.rules.values() {
-            if rule.status != RuleStatus::Enabled {
+            if rule.is_correlation() {
+                continue;
+            }
+            let silent = rule.status == RuleStatus::Test && self.correlated.contains(&rule.id);
+            if rule.status != RuleStatus::Enabled && !silent {
                 continue;
             }
 
@@ -166,9 +298,12 @@ impl RuleEngine {
             if !self.matcher.matches(rule, entry)? {
                 continue;
             }
+            if let Some(counter) = self.match_counts.get(&rule.id) {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
 
             // threshold 처리
-            if let Some(ref threshold) = rule.detection.threshold {
+            let match_count = if let Some(ref threshold) = rule.detection.threshold {
                 // 그룹화 필드가 없으면 threshold 카운팅을 건너뜁니다
                 let Some(group_key) = Self::extract_group_key(entry, &threshold.field) else {
                     continue;
@@ -209,33 +344,131 @@ impl RuleEngine {
 
                 counter.count += 1;
 
-                // 임계값 도달 + 아직 미알림
-                if counter.count >= threshold.count && !counter.alerted {
-                    counter.alerted = true;
-                    matches.push(RuleMatch {
-                        rule: rule.clone(),
-                        entry: entry.clone(),
-                        matched_at: SystemTime::now(),
-                        match_count: Some(counter.count),
-                    });
+                // 임계값 미도달 또는 이미 알림
+                if counter.count < threshold.count || counter.alerted {
+                    continue;
                 }
+                counter.alerted = true;
+                Some(counter.count)
             } else {
                 // threshold 없는 단순 매칭
+                None
+            };
+
+            fired.insert(rule.id.as_str());
+            if !silent {
                 matches.push(RuleMatch {
                     rule: rule.clone(),
                     entry: entry.clone(),
                     matched_at: SystemTime::now(),
-                    match_count: None,
+                    match_count,
                 });
             }
         }
 
+        if !fired.is_empty() {
+            matches.extend(self.correlate(entry, &fired));
+        }
+
+        if !self.suppressions.is_empty() && !matches.is_empty() {
+            self.apply_suppressions(&mut matches);
+        }
+
         // 메모리 성장 제한
         self.enforce_threshold_limits();
 
         Ok(matches)
     }
 
+    /// 이번 엔트리에서 매칭된 규칙으로 상관 규칙을 진행시키고,
+    /// 순차 매칭을 마친 상관 규칙의 매칭 결과를 반환합니다.
+    fn correlate(&self, entry: &LogEntry, fired: &HashSet<&str>) -> Vec<RuleMatch> {
+        let now = SystemTime::now();
+        let mut matches = Vec::new();
+        for rule in self.rules.values() {
+            let Some(ref correlation) = rule.correlation else {
+                continue;
+            };
+            if rule.status != RuleStatus::Enabled {
+                continue;
+            }
+            // 그룹화 필드가 없으면 상관 분석을 건너뜁니다
+            let Some(group_key) = Self::extract_group_key(entry, &correlation.field) else {
+                continue;
+            };
+            if let Some(steps) =
+                self.correlations
+                    .advance(&rule.id, correlation, group_key, fired, now)
+            {
+                if let Some(counter) = self.match_counts.get(&rule.id) {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+                matches.push(RuleMatch {
+                    rule: rule.clone(),
+                    entry: entry.clone(),
+                    matched_at: now,
+                    match_count: Some(steps as u64),
+                });
+            }
+        }
+        matches
+    }
+
+    /// 억제 목록에 해당하는 매칭을 제거합니다.
+    fn apply_suppressions(&self, matches: &mut Vec<RuleMatch>) {
+        let now = SystemTime::now();
+        matches.retain(|rule_match| {
+            let Some(suppression) =
+                self.suppressions
+                    .find(&rule_match.rule.id, &rule_match.entry, now)
+            else {
+                return true;
+            };
+            tracing::debug!(
+                rule_id = %rule_match.rule.id,
+                reason = %suppression.reason,
+                "rule match suppressed"
+            );
+            metrics::counter!(m::LOG_PIPELINE_ALERTS_SUPPRESSED_TOTAL).increment(1);
+            false
+        });
+    }
+
+    /// 상관 규칙이 참조하는 규칙 ID 집합을 다시 계산합니다.
+    fn refresh_correlated(&mut self) {
+        self.correlated = self
+            .rules
+            .values()
+            .filter_map(|rule| rule.correlation.as_ref())
+            .flat_map(|correlation| correlation.rules.iter().cloned())
+            .collect();
+    }
+
+    /// 상관 규칙이 존재하는 일반 규칙만 참조하는지 확인합니다.
+    fn check_correlation_refs(
+        rules: &HashMap<String, DetectionRule>,
+    ) -> Result<(), LogPipelineError> {
+        for rule in rules.values() {
+            let Some(ref correlation) = rule.correlation else {
+                continue;
+            };
+            for id in &correlation.rules {
+                let reason = match rules.get(id) {
+                    None => format!("correlation references unknown rule '{id}'"),
+                    Some(target) if target.is_correlation() => {
+                        format!("correlation must not reference correlation rule '{id}'")
+                    }
+                    Some(_) => continue,
+                };
+                return Err(LogPipelineError::RuleValidation {
+                    rule_id: rule.id.clone(),
+                    reason,
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// 규칙 매칭 결과를 Alert로 변환합니다.
     pub fn rule_match_to_alert(rule_match: &RuleMatch, _entry: &LogEntry) -> Alert {
         Alert {
@@ -383,12 +616,14 @@ mod tests {
             status: RuleStatus::Enabled,
             detection: DetectionCondition {
                 conditions: vec![],
-                threshold: None,
+                ..Default::default()
             },
+            correlation: None,
             tags: vec![],
         };
         engine.add_rule(rule).unwrap();
         assert_eq!(engine.rule_count(), 1);
+        assert_eq!(engine.rules()[0].id, "test_rule");
 
         engine.remove_rule("test_rule");
         assert_eq!(engine.rule_count(), 0);
@@ -432,8 +667,9 @@ mod tests {
                 status: RuleStatus::Enabled,
                 detection: DetectionCondition {
                     conditions: vec![],
-                    threshold: None,
+                    ..Default::default()
                 },
+                correlation: None,
                 tags: vec![],
             },
             entry: entry.clone(),
@@ -446,4 +682,270 @@ mod tests {
         assert_eq!(alert.severity, Severity::High);
         assert_eq!(alert.rule_name, "test");
     }
+
+    #[test]
+    fn replace_rules_swaps_rule_set() {
+        let mut engine = RuleEngine::new();
+        engine.add_rule(make_rule("old_rule")).unwrap();
+
+        let count = engine
+            .replace_rules(vec![make_rule("new_a"), make_rule("new_b")])
+            .unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(engine.rule_count(), 2);
+        assert!(engine.remove_rule("old_rule").is_none());
+    }
+
+    #[test]
+    fn replace_rules_keeps_existing_rules_on_error() {
+        let mut engine = RuleEngine::new();
+        engine.add_rule(make_rule("existing")).unwrap();
+
+        let result = engine.replace_rules(vec![make_rule("dup"), make_rule("dup")]);
+
+        assert!(result.is_err());
+        assert_eq!(engine.rule_count(), 1);
+        assert!(engine.remove_rule("existing").is_some());
+    }
+
+    #[test]
+    fn evaluate_counts_matches_per_rule() {
+        let mut engine = RuleEngine::new();
+        engine.add_rule(make_rule("always")).unwrap();
+        let mut disabled = make_rule("disabled");
+        disabled.status = RuleStatus::Disabled;
+        engine.add_rule(disabled).unwrap();
+
+        engine.evaluate(&sample_entry()).unwrap();
+        engine.evaluate(&sample_entry()).unwrap();
+
+        assert_eq!(engine.match_count("always"), 2);
+        assert_eq!(engine.match_count("disabled"), 0);
+        assert_eq!(engine.match_count("unknown"), 0);
+    }
+
+    #[test]
+    fn replace_rules_preserves_match_counts_of_kept_rules() {
+        let mut engine = RuleEngine::new();
+        engine.add_rule(make_rule("kept")).unwrap();
+        engine.add_rule(make_rule("dropped")).unwrap();
+        engine.evaluate(&sample_entry()).unwrap();
+
+        engine
+            .replace_rules(vec![make_rule("kept"), make_rule("added")])
+            .unwrap();
+
+        assert_eq!(engine.match_count("kept"), 1);
+        assert_eq!(engine.match_count("added"), 0);
+        assert_eq!(engine.match_count("dropped"), 0);
+    }
+
+    fn message_rule(id: &str, contains: &str) -> DetectionRule {
+        let mut rule = make_rule(id);
+        rule.detection.conditions.push(types::FieldCondition {
+            field: "message".to_owned(),
+            modifier: ConditionModifier::Contains,
+            values: vec![contains.to_owned()],
+        });
+        rule
+    }
+
+    fn correlation_rule(id: &str, rules: &[&str]) -> DetectionRule {
+        let mut rule = make_rule(id);
+        rule.correlation = Some(CorrelationConfig {
+            rules: rules.iter().map(|r| (*r).to_owned()).collect(),
+            field: "source_ip".to_owned(),
+            timeframe_secs: 600,
+        });
+        rule
+    }
+
+    fn entry_from(message: &str, source_ip: &str) -> LogEntry {
+        let mut entry = sample_entry();
+        entry.message = message.to_owned();
+        entry.fields[1].1 = source_ip.to_owned();
+        entry
+    }
+
+    fn rule_ids(matches: &[RuleMatch]) -> Vec<&str> {
+        let mut ids: Vec<&str> = matches.iter().map(|m| m.rule.id.as_str()).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn correlation_emits_composite_match_for_sequence() {
+        let mut engine = RuleEngine::new();
+        engine
+            .replace_rules(vec![
+                message_rule("failed", "Failed password"),
+                message_rule("accepted", "Accepted password"),
+                correlation_rule("failed_then_accepted", &["failed", "accepted"]),
+            ])
+            .unwrap();
+
+        // 순서가 반대이면 매칭되지 않음
+        let matches = engine
+            .evaluate(&entry_from("Accepted password for root", "203.0.113.7"))
+            .unwrap();
+        assert_eq!(rule_ids(&matches), vec!["accepted"]);
+
+        engine
+            .evaluate(&entry_from("Failed password for root", "203.0.113.7"))
+            .unwrap();
+        // 다른 IP의 성공은 진행시키지 않음
+        let matches = engine
+            .evaluate(&entry_from("Accepted password for root", "198.51.100.1"))
+            .unwrap();
+        assert_eq!(rule_ids(&matches), vec!["accepted"]);
+
+        let matches = engine
+            .evaluate(&entry_from("Accepted password for root", "203.0.113.7"))
+            .unwrap();
+        assert_eq!(rule_ids(&matches), vec!["accepted", "failed_then_accepted"]);
+        let composite = matches
+            .iter()
+            .find(|m| m.rule.id == "failed_then_accepted")
+            .unwrap();
+        assert_eq!(composite.match_count, Some(2));
+        assert_eq!(engine.match_count("failed_then_accepted"), 1);
+
+        // 완료 후 상태가 비워져 같은 성공으로 다시 알림하지 않음
+        let matches = engine
+            .evaluate(&entry_from("Accepted password for root", "203.0.113.7"))
+            .unwrap();
+        assert_eq!(rule_ids(&matches), vec!["accepted"]);
+    }
+
+    #[test]
+    fn correlation_steps_in_test_status_stay_silent() {
+        let mut engine = RuleEngine::new();
+        let mut failed = message_rule("failed", "Failed password");
+        failed.status = RuleStatus::Test;
+        let mut accepted = message_rule("accepted", "Accepted password");
+        accepted.status = RuleStatus::Test;
+        let mut unrelated = message_rule("unrelated", "Failed password");
+        unrelated.status = RuleStatus::Test;
+        engine.add_rule(failed).unwrap();
+        engine.add_rule(accepted).unwrap();
+        engine.add_rule(unrelated).unwrap();
+        engine
+            .add_rule(correlation_rule("corr", &["failed", "accepted"]))
+            .unwrap();
+
+        let matches = engine
+            .evaluate(&entry_from("Failed password for root", "203.0.113.7"))
+            .unwrap();
+        assert!(matches.is_empty());
+        let matches = engine
+            .evaluate(&entry_from("Accepted password for root", "203.0.113.7"))
+            .unwrap();
+        assert_eq!(rule_ids(&matches), vec!["corr"]);
+
+        // 상관 규칙이 참조하지 않는 test 규칙은 여전히 평가하지 않음
+        assert_eq!(engine.match_count("failed"), 1);
+        assert_eq!(engine.match_count("unrelated"), 0);
+
+        // 상관 규칙을 제거하면 참조 규칙도 다시 평가 대상에서 빠짐
+        engine.remove_rule("corr");
+        engine
+            .evaluate(&entry_from("Failed password for root", "203.0.113.7"))
+            .unwrap();
+        assert_eq!(engine.match_count("failed"), 1);
+    }
+
+    #[test]
+    fn correlation_rule_does_not_match_entries_directly() {
+        let mut engine = RuleEngine::new();
+        engine
+            .add_rule(correlation_rule("corr", &["missing_a", "missing_b"]))
+            .unwrap();
+        assert!(engine.evaluate(&sample_entry()).unwrap().is_empty());
+        assert!(
+            ironpost_core::pipeline::Detector::detect(&engine, &sample_entry())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn replace_rules_rejects_unresolved_correlations() {
+        let mut engine = RuleEngine::new();
+        engine.add_rule(make_rule("existing")).unwrap();
+
+        let unknown = engine.replace_rules(vec![
+            make_rule("a"),
+            correlation_rule("corr", &["a", "missing"]),
+        ]);
+        assert!(unknown.is_err());
+
+        let nested = engine.replace_rules(vec![
+            make_rule("a"),
+            make_rule("b"),
+            correlation_rule("inner", &["a", "b"]),
+            correlation_rule("outer", &["inner", "a"]),
+        ]);
+        assert!(nested.is_err());
+        assert_eq!(engine.rule_count(), 1);
+    }
+
+    #[test]
+    fn suppressions_filter_matches_but_still_count() {
+        let mut engine = RuleEngine::new();
+        engine
+            .replace_rules(vec![
+                message_rule("failed", "Failed password"),
+                message_rule("accepted", "Accepted password"),
+                correlation_rule("failed_then_accepted", &["failed", "accepted"]),
+            ])
+            .unwrap();
+        engine.set_suppressions(
+            SuppressionList::parse_yaml(
+                r#"
+suppressions:
+  - rule: failed
+    reason: internal scanner
+    conditions:
+      - source_ip: 10.1.2.3
+"#,
+                "suppressions.yml",
+            )
+            .unwrap(),
+        );
+        assert_eq!(engine.suppression_count(), 1);
+
+        let matches = engine
+            .evaluate(&entry_from("Failed password for root", "10.1.2.3"))
+            .unwrap();
+        assert!(matches.is_empty());
+        assert_eq!(engine.match_count("failed"), 1);
+
+        let matches = engine
+            .evaluate(&entry_from("Failed password for root", "10.1.2.4"))
+            .unwrap();
+        assert_eq!(rule_ids(&matches), vec!["failed"]);
+
+        // 억제된 매칭도 상관 규칙 진행에는 반영
+        let matches = engine
+            .evaluate(&entry_from("Accepted password for root", "10.1.2.3"))
+            .unwrap();
+        assert_eq!(rule_ids(&matches), vec!["accepted", "failed_then_accepted"]);
+    }
+
+    fn make_rule(id: &str) -> DetectionRule {
+        DetectionRule {
+            id: id.to_owned(),
+            title: format!("Rule {id}"),
+            description: String::new(),
+            severity: Severity::Medium,
+            status: RuleStatus::Enabled,
+            detection: DetectionCondition {
+                conditions: vec![],
+                ..Default::default()
+            },
+            correlation: None,
+            tags: vec![],
+        }
+    }
 }
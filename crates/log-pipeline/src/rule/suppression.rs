@@ -0,0 +1,384 @@
+//! 규칙 억제 목록 -- 알려진 정상 이벤트에 대한 알림 예외 처리
+//!
+//! [`SuppressionList`]는 억제 파일에서 로드한 항목을 규칙 매칭 결과에 적용하여,
+//! 특정 규칙(또는 모든 규칙)에 대해 필드 조건을 만족하는 매칭을 알림 생성 전에 걸러냅니다.
+//!
+//! # 파일 형식
+//! ```yaml
+//! suppressions:
+//!   - rule: ssh_brute_force          # 규칙 ID 또는 "*" (모든 규칙)
+//!     reason: internal vulnerability scanner
+//!     expires: 2025-01-01            # 선택 (날짜 또는 RFC 3339), 이 시각부터 무효
+//!     conditions:
+//!       - source_ip: 10.1.2.3
+//! ```
+//!
+//! 조건은 탐지 규칙의 `conditions`와 같은 형식(단축 형식, `|cidr` 등 포함)이며 AND로 결합됩니다.
+
+use std::path::Path;
+use std::time::SystemTime;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use ironpost_core::types::{LogEntry, Severity};
+use serde::{Deserialize, Serialize};
+
+use super::matcher::RuleMatcher;
+use super::types::{DetectionCondition, DetectionRule, FieldCondition};
+use crate::error::LogPipelineError;
+
+/// 억제 파일 최대 크기
+const MAX_SUPPRESSION_FILE_SIZE: u64 = 10 * 1024 * 1024; // 10MB
+
+/// 최대 억제 항목 수
+pub const MAX_SUPPRESSIONS: usize = 10_000;
+
+/// 모든 규칙에 적용되는 억제 항목의 규칙 ID
+pub const ANY_RULE: &str = "*";
+
+/// 억제 항목
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suppression {
+    /// 대상 규칙 ID (`*`이면 모든 규칙)
+    pub rule: String,
+    /// 억제 사유 (로그에 기록)
+    #[serde(default)]
+    pub reason: String,
+    /// 만료 시각 (`YYYY-MM-DD` 또는 RFC 3339, 없으면 무기한)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    /// 매칭 조건 (AND 결합, 비어 있으면 대상 규칙의 모든 매칭을 억제)
+    #[serde(default)]
+    pub conditions: Vec<FieldCondition>,
+}
+
+/// 억제 파일 최상위 구조
+#[derive(Debug, Deserialize)]
+struct SuppressionFile {
+    #[serde(default)]
+    suppressions: Vec<Suppression>,
+}
+
+/// 검증된 억제 항목
+#[derive(Debug)]
+struct CompiledSuppression {
+    suppression: Suppression,
+    /// 파싱된 만료 시각
+    expires_at: Option<SystemTime>,
+    /// 조건 매칭용 합성 규칙 (matcher 캐시 키)
+    rule: DetectionRule,
+}
+
+/// 억제 목록
+///
+/// 조건 매칭은 탐지 규칙과 같은 [`RuleMatcher`]로 수행하므로
+/// 정규식/CIDR 조건도 로드 시 한 번만 컴파일됩니다.
+#[derive(Default)]
+pub struct SuppressionList {
+    entries: Vec<CompiledSuppression>,
+    matcher: RuleMatcher,
+}
+
+impl std::fmt::Debug for SuppressionList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SuppressionList")
+            .field("entries", &self.entries)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SuppressionList {
+    /// 빈 억제 목록을 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 억제 항목을 검증하고 목록을 생성합니다.
+    ///
+    /// # Errors
+    /// - 항목 수가 [`MAX_SUPPRESSIONS`]를 초과하는 경우
+    /// - 규칙 ID가 비어 있거나, `*` 항목에 조건이 없는 경우
+    /// - 만료 시각 형식이 잘못된 경우
+    /// - 조건이 잘못된 경우 (정규식/CIDR 컴파일 실패 등)
+    pub fn from_entries(suppressions: Vec<Suppression>) -> Result<Self, LogPipelineError> {
+        if suppressions.len() > MAX_SUPPRESSIONS {
+            return Err(LogPipelineError::Config {
+                field: "suppressions".to_owned(),
+                reason: format!("too many suppressions: max {MAX_SUPPRESSIONS}"),
+            });
+        }
+
+        let mut list = Self::new();
+        let now = SystemTime::now();
+        for (index, suppression) in suppressions.into_iter().enumerate() {
+            let invalid = |reason: String| LogPipelineError::Config {
+                field: format!("suppressions[{index}]"),
+                reason,
+            };
+
+            if suppression.rule.trim().is_empty() {
+                return Err(invalid("rule must not be empty".to_owned()));
+            }
+            if suppression.rule == ANY_RULE && suppression.conditions.is_empty() {
+                return Err(invalid(
+                    "suppression for all rules ('*') requires at least one condition".to_owned(),
+                ));
+            }
+            let expires_at = suppression
+                .expires
+                .as_deref()
+                .map(parse_expiry)
+                .transpose()
+                .map_err(invalid)?;
+            if expires_at.is_some_and(|expires_at| expires_at <= now) {
+                tracing::warn!(
+                    rule_id = %suppression.rule,
+                    expires = ?suppression.expires,
+                    "suppression already expired, it will never apply"
+                );
+            }
+
+            let rule = DetectionRule {
+                id: format!("suppressions[{index}]"),
+                title: suppression.reason.clone(),
+                description: String::new(),
+                severity: Severity::Info,
+                status: Default::default(),
+                detection: DetectionCondition {
+                    conditions: suppression.conditions.clone(),
+                    ..Default::default()
+                },
+                correlation: None,
+                tags: Vec::new(),
+            };
+            list.matcher
+                .compile_rule(&rule)
+                .map_err(|e| invalid(e.to_string()))?;
+            list.entries.push(CompiledSuppression {
+                suppression,
+                expires_at,
+                rule,
+            });
+        }
+        Ok(list)
+    }
+
+    /// YAML 문자열에서 억제 목록을 파싱합니다.
+    pub fn parse_yaml(yaml_str: &str, source: &str) -> Result<Self, LogPipelineError> {
+        let file: SuppressionFile =
+            serde_yaml::from_str(yaml_str).map_err(|e| LogPipelineError::RuleLoad {
+                path: source.to_owned(),
+                reason: format!("YAML parse error: {e}"),
+            })?;
+        Self::from_entries(file.suppressions).map_err(|e| LogPipelineError::RuleLoad {
+            path: source.to_owned(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// 억제 파일을 로드합니다.
+    pub async fn load_file(path: impl AsRef<Path>) -> Result<Self, LogPipelineError> {
+        let path = path.as_ref();
+        let load_error = |reason: String| LogPipelineError::RuleLoad {
+            path: path.display().to_string(),
+            reason,
+        };
+
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| load_error(format!("failed to read file metadata: {e}")))?;
+        if metadata.len() > MAX_SUPPRESSION_FILE_SIZE {
+            return Err(load_error(format!(
+                "file too large: {} bytes (max: {MAX_SUPPRESSION_FILE_SIZE})",
+                metadata.len()
+            )));
+        }
+
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| load_error(format!("failed to read file: {e}")))?;
+        let list = Self::parse_yaml(&content, &path.display().to_string())?;
+
+        tracing::info!(
+            path = %path.display(),
+            count = list.len(),
+            "loaded rule suppressions"
+        );
+        Ok(list)
+    }
+
+    /// 규칙 매칭을 억제하는 항목을 찾습니다.
+    ///
+    /// `now` 시점에 만료되지 않았고, 대상 규칙이 일치하며, 모든 조건을 만족하는
+    /// 첫 번째 항목을 반환합니다.
+    pub fn find(&self, rule_id: &str, entry: &LogEntry, now: SystemTime) -> Option<&Suppression> {
+        self.entries
+            .iter()
+            .filter(|compiled| {
+                compiled.suppression.rule == rule_id || compiled.suppression.rule == ANY_RULE
+            })
+            .filter(|compiled| {
+                compiled
+                    .expires_at
+                    .is_none_or(|expires_at| now < expires_at)
+            })
+            .find(|compiled| self.matcher.matches(&compiled.rule, entry).unwrap_or(false))
+            .map(|compiled| &compiled.suppression)
+    }
+
+    /// 억제 항목 수를 반환합니다.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 억제 항목이 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// 만료 시각을 파싱합니다. 날짜만 주어지면 해당 날짜 00:00 UTC입니다.
+fn parse_expiry(value: &str) -> Result<SystemTime, String> {
+    let value = value.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Ok(datetime.into());
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc).into())
+        .ok_or_else(|| {
+            format!("invalid expires '{value}': expected YYYY-MM-DD or RFC 3339 timestamp")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn entry(source_ip: &str) -> LogEntry {
+        LogEntry {
+            source: "auth".to_owned(),
+            timestamp: SystemTime::now(),
+            hostname: "web-01".to_owned(),
+            process: "sshd".to_owned(),
+            message: "Failed password".to_owned(),
+            severity: Severity::Medium,
+            fields: vec![("source_ip".to_owned(), source_ip.to_owned())],
+        }
+    }
+
+    const YAML: &str = r#"
+suppressions:
+  - rule: ssh_brute_force
+    reason: internal scanner
+    expires: 2030-01-01
+    conditions:
+      - source_ip: 10.1.2.3
+  - rule: "*"
+    reason: lab network
+    conditions:
+      - source_ip|cidr: 192.168.100.0/24
+  - rule: noisy_rule
+    reason: snoozed
+    expires: "2030-01-01T12:00:00+09:00"
+"#;
+
+    #[test]
+    fn suppresses_matching_rule_and_fields() {
+        let list = SuppressionList::parse_yaml(YAML, "suppressions.yml").unwrap();
+        assert_eq!(list.len(), 3);
+        let now = SystemTime::now();
+
+        let hit = list
+            .find("ssh_brute_force", &entry("10.1.2.3"), now)
+            .unwrap();
+        assert_eq!(hit.reason, "internal scanner");
+        assert!(
+            list.find("ssh_brute_force", &entry("10.1.2.4"), now)
+                .is_none()
+        );
+        assert!(list.find("other_rule", &entry("10.1.2.3"), now).is_none());
+
+        // "*" 항목은 모든 규칙에 적용
+        let hit = list
+            .find("other_rule", &entry("192.168.100.7"), now)
+            .unwrap();
+        assert_eq!(hit.reason, "lab network");
+
+        // 조건 없는 항목은 해당 규칙의 모든 매칭을 억제
+        assert!(list.find("noisy_rule", &entry("8.8.8.8"), now).is_some());
+    }
+
+    #[test]
+    fn expired_suppression_does_not_apply() {
+        let list = SuppressionList::parse_yaml(YAML, "suppressions.yml").unwrap();
+        let expiry = parse_expiry("2030-01-01").unwrap();
+
+        let before = expiry - Duration::from_secs(1);
+        assert!(
+            list.find("ssh_brute_force", &entry("10.1.2.3"), before)
+                .is_some()
+        );
+        assert!(
+            list.find("ssh_brute_force", &entry("10.1.2.3"), expiry)
+                .is_none()
+        );
+
+        // RFC 3339 오프셋 반영: 2030-01-01T12:00+09:00 == 03:00 UTC
+        let snooze_end = expiry + Duration::from_secs(3 * 3600);
+        assert!(
+            list.find(
+                "noisy_rule",
+                &entry("1.1.1.1"),
+                snooze_end - Duration::from_secs(1)
+            )
+            .is_some()
+        );
+        assert!(
+            list.find("noisy_rule", &entry("1.1.1.1"), snooze_end)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        let cases = [
+            "suppressions:\n  - rule: \"*\"\n",
+            "suppressions:\n  - rule: \"\"\n    conditions:\n      - process: sshd\n",
+            "suppressions:\n  - rule: r\n    expires: next tuesday\n",
+            "suppressions:\n  - rule: r\n    conditions:\n      - source_ip|cidr: 10.0.0.0/33\n",
+            "suppressions:\n  - rule: r\n    conditions:\n      - message|regex: \"(unclosed\"\n",
+            "suppressions: [",
+        ];
+        for yaml in cases {
+            let err = SuppressionList::parse_yaml(yaml, "bad.yml").unwrap_err();
+            assert!(err.to_string().contains("bad.yml"), "{err}");
+        }
+        assert!(
+            SuppressionList::parse_yaml("", "empty.yml")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn load_file_reads_suppressions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("suppressions.yml");
+        std::fs::write(&path, YAML).unwrap();
+        assert_eq!(SuppressionList::load_file(&path).await.unwrap().len(), 3);
+        assert!(
+            SuppressionList::load_file(dir.path().join("missing.yml"))
+                .await
+                .is_err()
+        );
+
+        let example = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../examples/suppressions.yaml"
+        );
+        assert_eq!(SuppressionList::load_file(example).await.unwrap().len(), 3);
+    }
+}
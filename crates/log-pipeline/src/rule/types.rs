@@ -2,9 +2,12 @@
 //!
 //! YAML 규칙 파일에서 역직렬화되는 구조체들을 정의합니다.
 
+use std::collections::BTreeMap;
+
 use ironpost_core::types::Severity;
 use serde::{Deserialize, Serialize};
 
+use super::condition::ConditionExpr;
 use crate::error::LogPipelineError;
 
 /// 탐지 규칙 -- 하나의 YAML 규칙 파일에 대응합니다.
@@ -23,11 +26,49 @@ use crate::error::LogPipelineError;
 ///   threshold:
 ///     field: source_ip
 ///     count: 5
-///     timeframe: 300
+///     timeframe_secs: 300
 /// tags:
 ///   - authentication
 ///   - brute_force
 /// ```
+///
+/// 다른 규칙들의 순차 매칭을 묶는 상관 규칙은 `detection` 대신 `correlation`을 씁니다.
+/// ```yaml
+/// id: ssh_bruteforce_then_login
+/// title: SSH Login After Brute Force
+/// severity: critical
+/// correlation:
+///   rules: [ssh_brute_force, ssh_login_success]
+///   field: source_ip
+///   timeframe_secs: 600
+/// ```
+///
+/// Sigma 규칙처럼 이름 있는 selection과 `condition` 조건식을 쓸 수도 있습니다.
+/// ```yaml
+/// detection:
+///   selections:
+///     selection:
+///       - field: process
+///         value: sshd
+///     filter:
+///       - field: source_ip
+///         modifier: startswith
+///         value: "10."
+///   condition: selection and not filter
+/// ```
+///
+/// selection은 Sigma 형식(맵은 AND, 맵 목록은 OR, 값 목록은 OR)으로도 쓸 수 있습니다.
+/// ```yaml
+/// detection:
+///   selections:
+///     selection:
+///       process: [sshd, sshd-session]
+///       message|contains: Failed password
+///     filter:
+///       - source_ip|startswith: "10."
+///       - hostname: bastion-01
+///   condition: selection and not filter
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectionRule {
     /// 규칙 고유 ID (파일 내에서 유일해야 함)
@@ -42,8 +83,13 @@ pub struct DetectionRule {
     /// 규칙 상태
     #[serde(default)]
     pub status: RuleStatus,
-    /// 탐지 조건
+    /// 탐지 조건 (상관 규칙은 비워 둠)
+    #[serde(default)]
     pub detection: DetectionCondition,
+    /// 상관 분석 설정 -- 설정되면 로그 엔트리를 직접 매칭하지 않고
+    /// 참조한 규칙들의 순차 매칭으로 알림을 생성합니다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation: Option<CorrelationConfig>,
     /// 분류 태그
     #[serde(default)]
     pub tags: Vec<String>,
@@ -94,8 +140,40 @@ impl DetectionRule {
             }
         }
 
+        self.detection
+            .parse_condition()
+            .map_err(|reason| LogPipelineError::RuleValidation {
+                rule_id: self.id.clone(),
+                reason,
+            })?;
+
+        if let Some(ref correlation) = self.correlation {
+            correlation
+                .validate(&self.id)
+                .map_err(|reason| LogPipelineError::RuleValidation {
+                    rule_id: self.id.clone(),
+                    reason,
+                })?;
+            let detection = &self.detection;
+            if !detection.conditions.is_empty()
+                || !detection.selections.is_empty()
+                || detection.threshold.is_some()
+            {
+                return Err(LogPipelineError::RuleValidation {
+                    rule_id: self.id.clone(),
+                    reason: "correlation rules must not define detection conditions or threshold"
+                        .to_owned(),
+                });
+            }
+        }
+
         Ok(())
     }
+
+    /// 상관 규칙인지 확인합니다.
+    pub fn is_correlation(&self) -> bool {
+        self.correlation.is_some()
+    }
 }
 
 /// 규칙 상태
@@ -113,29 +191,313 @@ pub enum RuleStatus {
 
 /// 탐지 조건
 ///
-/// `condition`은 AND 로직으로 결합됩니다.
-/// 모든 조건이 만족해야 규칙이 매칭됩니다.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// `conditions`는 AND 로직으로 결합됩니다.
+/// `condition` 조건식이 있으면 `conditions`가 모두 만족하고 조건식도 참이어야 매칭됩니다.
+/// 각 selection은 조건 그룹 중 하나라도 매칭되면 참입니다 ([`Selection`] 참고).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DetectionCondition {
     /// 필드 매칭 조건 목록 (AND 결합)
     #[serde(default)]
     pub conditions: Vec<FieldCondition>,
+    /// 이름 있는 selection
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub selections: BTreeMap<String, Selection>,
+    /// selection을 결합하는 조건식 (예: `selection and not filter`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
     /// 상관 분석을 위한 threshold 설정
     pub threshold: Option<ThresholdConfig>,
 }
 
+impl DetectionCondition {
+    /// `condition` 조건식을 파싱합니다.
+    ///
+    /// 조건식이 없으면 `None`을 반환합니다. selection과 조건식은 함께 정의되어야 합니다.
+    pub fn parse_condition(&self) -> Result<Option<ConditionExpr>, String> {
+        let Some(ref expression) = self.condition else {
+            if self.selections.is_empty() {
+                return Ok(None);
+            }
+            return Err("selections require a condition expression".to_owned());
+        };
+        if self.selections.is_empty() {
+            return Err("condition expression requires at least one selection".to_owned());
+        }
+
+        for (name, selection) in &self.selections {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+            {
+                return Err(format!(
+                    "invalid selection name '{}': use letters, digits, '_', '-' or '.'",
+                    name
+                ));
+            }
+            if selection.groups.is_empty() || selection.groups.iter().any(Vec::is_empty) {
+                return Err(format!("selection '{}' must not be empty", name));
+            }
+        }
+
+        let names: Vec<&str> = self.selections.keys().map(String::as_str).collect();
+        ConditionExpr::parse(expression, &names).map(Some)
+    }
+
+    /// `conditions`와 모든 selection의 필드 조건을 순서대로 반환합니다.
+    ///
+    /// 인덱스는 `conditions`가 먼저 오고, 이어서 selection 이름 순서대로,
+    /// selection 안에서는 그룹 순서대로 이어집니다.
+    pub fn all_conditions(&self) -> impl Iterator<Item = &FieldCondition> {
+        self.conditions.iter().chain(
+            self.selections
+                .values()
+                .flat_map(|selection| selection.groups.iter().flatten()),
+        )
+    }
+}
+
+/// 이름 있는 selection -- AND 결합 조건 그룹들의 OR
+///
+/// YAML에서는 다음 형식을 받습니다.
+/// - `field`/`modifier`/`value` 형식 조건의 목록: 하나의 AND 그룹
+/// - Sigma 맵 (`field|modifier: value` 항목들): 하나의 AND 그룹
+/// - 목록: 각 항목(Sigma 맵, 단일 조건, 또는 조건 목록)이 OR로 결합되는 그룹
+/// ```yaml
+/// selection:
+///   - process: sshd
+///     message|contains: Failed password
+///   - process: su
+///     message|contains: authentication failure
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(try_from = "SelectionRepr", into = "SelectionRepr")]
+pub struct Selection {
+    /// 조건 그룹 목록 (그룹 내 AND, 그룹 간 OR)
+    pub groups: Vec<Vec<FieldCondition>>,
+}
+
+impl From<Vec<FieldCondition>> for Selection {
+    /// 모든 조건이 AND로 결합되는 selection을 만듭니다.
+    fn from(conditions: Vec<FieldCondition>) -> Self {
+        Self {
+            groups: vec![conditions],
+        }
+    }
+}
+
+/// [`Selection`]의 YAML 표현
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum SelectionRepr {
+    /// Sigma 맵 (AND)
+    Map(BTreeMap<String, ValueList>),
+    /// 조건 목록 또는 그룹 목록
+    List(Vec<SelectionItemRepr>),
+}
+
+/// [`SelectionRepr::List`]의 항목
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum SelectionItemRepr {
+    /// `field`/`modifier`/`value` 형식 조건
+    Condition(FullConditionRepr),
+    /// Sigma 맵 (AND 그룹)
+    Map(BTreeMap<String, ValueList>),
+    /// 조건 목록 (AND 그룹)
+    Group(Vec<FieldCondition>),
+}
+
+impl TryFrom<SelectionRepr> for Selection {
+    type Error = String;
+
+    fn try_from(repr: SelectionRepr) -> Result<Self, Self::Error> {
+        let items = match repr {
+            SelectionRepr::Map(map) => return Ok(shorthand_conditions(map)?.into()),
+            SelectionRepr::List(items) => items,
+        };
+
+        // 기존 형식: `field`/`value` 조건만 나열한 목록은 하나의 AND 그룹
+        if items
+            .iter()
+            .all(|item| matches!(item, SelectionItemRepr::Condition(_)))
+        {
+            let conditions = items
+                .into_iter()
+                .filter_map(|item| match item {
+                    SelectionItemRepr::Condition(condition) => Some(condition.try_into()),
+                    _ => None,
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            return Ok(conditions.into());
+        }
+
+        let groups = items
+            .into_iter()
+            .map(|item| match item {
+                SelectionItemRepr::Condition(condition) => Ok(vec![condition.try_into()?]),
+                SelectionItemRepr::Map(map) => shorthand_conditions(map),
+                SelectionItemRepr::Group(conditions) => Ok(conditions),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self { groups })
+    }
+}
+
+impl From<Selection> for SelectionRepr {
+    fn from(selection: Selection) -> Self {
+        let mut groups = selection.groups;
+        if groups.len() == 1 {
+            let conditions = groups.pop().unwrap_or_default();
+            return Self::List(
+                conditions
+                    .into_iter()
+                    .map(|condition| SelectionItemRepr::Condition(condition.into()))
+                    .collect(),
+            );
+        }
+        Self::List(groups.into_iter().map(SelectionItemRepr::Group).collect())
+    }
+}
+
+/// Sigma 맵의 각 `field|modifier: value` 항목을 조건으로 변환합니다.
+fn shorthand_conditions(map: BTreeMap<String, ValueList>) -> Result<Vec<FieldCondition>, String> {
+    map.into_iter()
+        .map(|(key, value)| shorthand_condition(&key, value))
+        .collect()
+}
+
 /// 필드 매칭 조건
 ///
 /// 하나의 LogEntry 필드에 대한 매칭 조건을 나타냅니다.
+/// YAML에서는 `field`/`modifier`/`value` 형식 외에 Sigma 스타일의
+/// `field|modifier: value` 단축 형식도 받습니다.
+/// `value`에 목록을 주면 값 중 하나라도 매칭되면 참입니다 (OR).
+/// ```yaml
+/// - field: message
+///   modifier: regex
+///   value: "Failed .* from \\d+"
+/// - message|regex: "Failed .* from \\d+"
+/// - process: [sshd, sshd-session]
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "FieldConditionRepr", into = "FullConditionRepr")]
 pub struct FieldCondition {
     /// 대상 필드명 (hostname, process, message, 또는 fields 내의 키)
     pub field: String,
     /// 매칭 수정자
-    #[serde(default)]
     pub modifier: ConditionModifier,
-    /// 매칭할 값
-    pub value: String,
+    /// 매칭할 값 목록 (OR 결합, 비어 있지 않음)
+    pub values: Vec<String>,
+}
+
+/// 단일 값 또는 값 목록
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ValueList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ValueList {
+    /// 값 목록으로 변환합니다. 빈 목록은 거부합니다.
+    fn into_values(self, field: &str) -> Result<Vec<String>, String> {
+        match self {
+            Self::One(value) => Ok(vec![value]),
+            Self::Many(values) if values.is_empty() => {
+                Err(format!("condition '{}' has an empty value list", field))
+            }
+            Self::Many(values) => Ok(values),
+        }
+    }
+}
+
+impl From<Vec<String>> for ValueList {
+    fn from(mut values: Vec<String>) -> Self {
+        if values.len() == 1 {
+            Self::One(values.pop().unwrap_or_default())
+        } else {
+            Self::Many(values)
+        }
+    }
+}
+
+/// [`FieldCondition`]의 YAML 표현
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FieldConditionRepr {
+    /// `field`/`modifier`/`value` 형식
+    Full(FullConditionRepr),
+    /// `field|modifier: value` 단축 형식
+    Shorthand(BTreeMap<String, ValueList>),
+}
+
+/// `field`/`modifier`/`value` 형식 조건
+#[derive(Serialize, Deserialize)]
+struct FullConditionRepr {
+    field: String,
+    #[serde(default)]
+    modifier: ConditionModifier,
+    value: ValueList,
+}
+
+impl TryFrom<FieldConditionRepr> for FieldCondition {
+    type Error = String;
+
+    fn try_from(repr: FieldConditionRepr) -> Result<Self, Self::Error> {
+        match repr {
+            FieldConditionRepr::Full(full) => full.try_into(),
+            FieldConditionRepr::Shorthand(map) => {
+                let mut entries = map.into_iter();
+                let (Some((key, value)), None) = (entries.next(), entries.next()) else {
+                    return Err(
+                        "condition must have 'field' and 'value' or a single 'field|modifier: value' entry"
+                            .to_owned(),
+                    );
+                };
+                shorthand_condition(&key, value)
+            }
+        }
+    }
+}
+
+impl TryFrom<FullConditionRepr> for FieldCondition {
+    type Error = String;
+
+    fn try_from(repr: FullConditionRepr) -> Result<Self, Self::Error> {
+        let values = repr.value.into_values(&repr.field)?;
+        Ok(Self {
+            field: repr.field,
+            modifier: repr.modifier,
+            values,
+        })
+    }
+}
+
+impl From<FieldCondition> for FullConditionRepr {
+    fn from(condition: FieldCondition) -> Self {
+        Self {
+            field: condition.field,
+            modifier: condition.modifier,
+            value: condition.values.into(),
+        }
+    }
+}
+
+/// `field|modifier: value` 항목 하나를 조건으로 변환합니다.
+fn shorthand_condition(key: &str, value: ValueList) -> Result<FieldCondition, String> {
+    let (field, modifier) = match key.split_once('|') {
+        Some((field, modifier)) => (field, modifier.parse()?),
+        None => (key, ConditionModifier::Exact),
+    };
+    if field.is_empty() {
+        return Err(format!("condition '{}' has an empty field name", key));
+    }
+    Ok(FieldCondition {
+        field: field.to_owned(),
+        modifier,
+        values: value.into_values(key)?,
+    })
 }
 
 /// 조건 수정자 -- 매칭 방식을 결정합니다.
@@ -153,6 +515,28 @@ pub enum ConditionModifier {
     EndsWith,
     /// 정규식 매칭
     Regex,
+    /// IP 주소가 CIDR 목록 중 하나에 속함 (쉼표로 구분, 예: `10.0.0.0/8, 192.168.0.0/16`)
+    Cidr,
+}
+
+impl std::str::FromStr for ConditionModifier {
+    type Err = String;
+
+    /// Sigma 단축 형식의 수정자 이름을 파싱합니다 (`re`는 `regex`의 별칭).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exact" => Ok(Self::Exact),
+            "contains" => Ok(Self::Contains),
+            "startswith" => Ok(Self::StartsWith),
+            "endswith" => Ok(Self::EndsWith),
+            "regex" | "re" => Ok(Self::Regex),
+            "cidr" => Ok(Self::Cidr),
+            other => Err(format!(
+                "unknown condition modifier '{}' (expected exact, contains, startswith, endswith, regex or cidr)",
+                other
+            )),
+        }
+    }
 }
 
 /// Threshold (상관 분석) 설정
@@ -169,6 +553,54 @@ pub struct ThresholdConfig {
     pub timeframe_secs: u64,
 }
 
+/// 상관 규칙의 최대 단계 수
+pub const MAX_CORRELATION_STEPS: usize = 16;
+
+/// 상관 분석 설정
+///
+/// `rules`에 나열한 규칙이 같은 `field` 값에 대해 순서대로 매칭되고,
+/// 첫 매칭부터 `timeframe_secs` 안에 마지막 규칙까지 매칭되면 하나의 알림을 생성합니다.
+/// 예: 같은 IP에서 SSH brute force 탐지 후 10분 내 로그인 성공
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationConfig {
+    /// 순서대로 매칭되어야 하는 규칙 ID 목록 (2개 이상)
+    pub rules: Vec<String>,
+    /// 그룹화 필드명 (예: "source_ip")
+    pub field: String,
+    /// 첫 단계부터 마지막 단계까지 허용하는 시간 (초)
+    pub timeframe_secs: u64,
+}
+
+impl CorrelationConfig {
+    /// 상관 분석 설정의 유효성을 검증합니다.
+    ///
+    /// 참조한 규칙이 실제로 존재하는지는 규칙 세트 단위로 확인합니다.
+    fn validate(&self, rule_id: &str) -> Result<(), String> {
+        if self.rules.len() < 2 {
+            return Err("correlation must reference at least 2 rules".to_owned());
+        }
+        if self.rules.len() > MAX_CORRELATION_STEPS {
+            return Err(format!(
+                "correlation must not exceed {} rules",
+                MAX_CORRELATION_STEPS
+            ));
+        }
+        if self.rules.iter().any(String::is_empty) {
+            return Err("correlation rule ids must not be empty".to_owned());
+        }
+        if self.rules.iter().any(|id| id == rule_id) {
+            return Err("correlation must not reference itself".to_owned());
+        }
+        if self.field.is_empty() {
+            return Err("correlation field must not be empty".to_owned());
+        }
+        if self.timeframe_secs == 0 {
+            return Err("correlation timeframe must be greater than 0".to_owned());
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -184,10 +616,11 @@ mod tests {
                 conditions: vec![FieldCondition {
                     field: "process".to_owned(),
                     modifier: ConditionModifier::Exact,
-                    value: "sshd".to_owned(),
+                    values: vec!["sshd".to_owned()],
                 }],
-                threshold: None,
+                ..Default::default()
             },
+            correlation: None,
             tags: vec!["test".to_owned()],
         }
     }
@@ -288,4 +721,267 @@ tags:
         assert!(rule.detection.threshold.is_some());
         assert_eq!(rule.tags.len(), 2);
     }
+
+    #[test]
+    fn shorthand_conditions_from_yaml() {
+        let yaml = r#"
+- message|regex: "Failed .* from \\d+"
+- process: sshd
+- hostname|startswith: web-
+- source_ip|re: "^10\\."
+- src_ip|cidr: 10.0.0.0/8
+- field: message
+  modifier: contains
+  value: password
+"#;
+        let conditions: Vec<FieldCondition> = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(conditions.len(), 6);
+        assert_eq!(conditions[0].field, "message");
+        assert_eq!(conditions[0].modifier, ConditionModifier::Regex);
+        assert_eq!(conditions[0].values, [r"Failed .* from \d+"]);
+        assert_eq!(conditions[1].modifier, ConditionModifier::Exact);
+        assert_eq!(conditions[2].modifier, ConditionModifier::StartsWith);
+        assert_eq!(conditions[3].modifier, ConditionModifier::Regex);
+        assert_eq!(conditions[4].modifier, ConditionModifier::Cidr);
+        assert_eq!(conditions[4].values, ["10.0.0.0/8"]);
+        assert_eq!(conditions[5].modifier, ConditionModifier::Contains);
+    }
+
+    #[test]
+    fn value_lists_from_yaml() {
+        let yaml = r#"
+- process: [sshd, sshd-session]
+- field: message
+  modifier: contains
+  value: ["Failed password", "Invalid user"]
+- hostname: web-01
+"#;
+        let conditions: Vec<FieldCondition> = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(conditions[0].values, ["sshd", "sshd-session"]);
+        assert_eq!(conditions[1].modifier, ConditionModifier::Contains);
+        assert_eq!(conditions[1].values, ["Failed password", "Invalid user"]);
+        assert_eq!(conditions[2].values, ["web-01"]);
+
+        // 단일 값은 문자열로, 값 목록은 목록으로 직렬화됨
+        let yaml = serde_yaml::to_string(&conditions).unwrap();
+        let roundtrip: Vec<FieldCondition> = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(roundtrip[0].values, conditions[0].values);
+        assert!(yaml.contains("value: web-01"));
+    }
+
+    #[test]
+    fn invalid_shorthand_conditions_fail() {
+        for yaml in [
+            "message|fuzzy: x",
+            "|regex: x",
+            "{ message|regex: a, process: sshd }",
+            "process: []",
+            "{ field: process, value: [] }",
+        ] {
+            assert!(
+                serde_yaml::from_str::<FieldCondition>(yaml).is_err(),
+                "{yaml} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn correlation_rule_from_yaml() {
+        let yaml = r#"
+id: ssh_bruteforce_then_login
+title: SSH Login After Brute Force
+severity: Critical
+correlation:
+  rules: [ssh_brute_force, ssh_login_success]
+  field: source_ip
+  timeframe_secs: 600
+"#;
+        let rule: DetectionRule = serde_yaml::from_str(yaml).unwrap();
+        rule.validate().unwrap();
+        assert!(rule.is_correlation());
+        assert!(rule.detection.conditions.is_empty());
+        let correlation = rule.correlation.unwrap();
+        assert_eq!(correlation.rules.len(), 2);
+        assert_eq!(correlation.field, "source_ip");
+    }
+
+    #[test]
+    fn invalid_correlation_fails_validation() {
+        let mut rule = sample_rule();
+        rule.detection.conditions.clear();
+        rule.correlation = Some(CorrelationConfig {
+            rules: vec!["a".to_owned(), "b".to_owned()],
+            field: "source_ip".to_owned(),
+            timeframe_secs: 600,
+        });
+        rule.validate().unwrap();
+
+        let mut cases = Vec::new();
+        let mut single = rule.clone();
+        if let Some(c) = single.correlation.as_mut() {
+            c.rules.truncate(1);
+        }
+        cases.push(single);
+        let mut itself = rule.clone();
+        if let Some(c) = itself.correlation.as_mut() {
+            c.rules.push("test_rule".to_owned());
+        }
+        cases.push(itself);
+        let mut no_field = rule.clone();
+        if let Some(c) = no_field.correlation.as_mut() {
+            c.field.clear();
+        }
+        cases.push(no_field);
+        let mut no_timeframe = rule.clone();
+        if let Some(c) = no_timeframe.correlation.as_mut() {
+            c.timeframe_secs = 0;
+        }
+        cases.push(no_timeframe);
+        let mut with_conditions = rule.clone();
+        with_conditions.detection = sample_rule().detection;
+        cases.push(with_conditions);
+
+        for case in cases {
+            assert!(case.validate().is_err(), "{:?}", case.correlation);
+        }
+    }
+
+    #[test]
+    fn rule_with_selections_from_yaml() {
+        let yaml = r#"
+id: ssh_external_failure
+title: SSH Failure From External Host
+severity: Medium
+detection:
+  selections:
+    selection:
+      - field: process
+        value: sshd
+      - field: message
+        modifier: contains
+        value: "Failed password"
+    filter_internal:
+      - field: source_ip
+        modifier: startswith
+        value: "10."
+  condition: selection and not 1 of filter_*
+"#;
+        let rule: DetectionRule = serde_yaml::from_str(yaml).unwrap();
+        rule.validate().unwrap();
+        assert_eq!(rule.detection.selections.len(), 2);
+        assert_eq!(rule.detection.all_conditions().count(), 3);
+        assert!(rule.detection.parse_condition().unwrap().is_some());
+    }
+
+    #[test]
+    fn sigma_selections_from_yaml() {
+        let yaml = r#"
+selection:
+  process: [sshd, sshd-session]
+  message|contains: Failed password
+filter:
+  - source_ip|startswith: "10."
+  - hostname: bastion-01
+    process: sshd
+legacy:
+  - field: process
+    value: sshd
+  - field: message
+    modifier: contains
+    value: Failed
+"#;
+        let selections: BTreeMap<String, Selection> = serde_yaml::from_str(yaml).unwrap();
+
+        // 맵은 하나의 AND 그룹
+        let selection = &selections["selection"].groups;
+        assert_eq!(selection.len(), 1);
+        assert_eq!(selection[0].len(), 2);
+        assert_eq!(selection[0][1].values, ["sshd", "sshd-session"]);
+
+        // 맵 목록은 항목마다 OR 그룹
+        let filter = &selections["filter"].groups;
+        assert_eq!(filter.len(), 2);
+        assert_eq!(filter[0][0].modifier, ConditionModifier::StartsWith);
+        assert_eq!(filter[1].len(), 2);
+
+        // `field`/`value` 조건 목록은 기존처럼 하나의 AND 그룹
+        assert_eq!(selections["legacy"].groups.len(), 1);
+        assert_eq!(selections["legacy"].groups[0].len(), 2);
+
+        // 직렬화 후 다시 읽어도 그룹 구조가 유지됨
+        let yaml = serde_yaml::to_string(&selections).unwrap();
+        let roundtrip: BTreeMap<String, Selection> = serde_yaml::from_str(&yaml).unwrap();
+        for (name, selection) in &selections {
+            let groups: Vec<usize> = selection.groups.iter().map(Vec::len).collect();
+            let parsed: Vec<usize> = roundtrip[name].groups.iter().map(Vec::len).collect();
+            assert_eq!(groups, parsed, "{name}");
+        }
+    }
+
+    #[test]
+    fn empty_sigma_selections_fail_validation() {
+        let yaml = r#"
+id: empty_group
+title: Empty Group
+severity: Low
+detection:
+  selections:
+    selection: []
+  condition: selection
+"#;
+        let rule: DetectionRule = serde_yaml::from_str(yaml).unwrap();
+        assert!(rule.validate().is_err());
+        assert!(serde_yaml::from_str::<Selection>("process: []").is_err());
+    }
+
+    #[test]
+    fn selections_and_condition_must_be_paired() {
+        let mut rule = sample_rule();
+        rule.detection.condition = Some("selection".to_owned());
+        assert!(rule.validate().is_err());
+
+        rule.detection.condition = None;
+        rule.detection.selections.insert(
+            "selection".to_owned(),
+            vec![FieldCondition {
+                field: "process".to_owned(),
+                modifier: ConditionModifier::Exact,
+                values: vec!["sshd".to_owned()],
+            }]
+            .into(),
+        );
+        assert!(rule.validate().is_err());
+
+        rule.detection.condition = Some("selection".to_owned());
+        rule.validate().unwrap();
+    }
+
+    #[test]
+    fn invalid_selections_fail_validation() {
+        let mut rule = sample_rule();
+        rule.detection.condition = Some("selection or missing".to_owned());
+        rule.detection.selections.insert(
+            "selection".to_owned(),
+            vec![FieldCondition {
+                field: "process".to_owned(),
+                modifier: ConditionModifier::Exact,
+                values: vec!["sshd".to_owned()],
+            }]
+            .into(),
+        );
+        assert!(rule.validate().is_err());
+
+        rule.detection.condition = Some("selection".to_owned());
+        rule.detection
+            .selections
+            .insert("empty".to_owned(), Selection::default());
+        assert!(rule.validate().is_err());
+
+        rule.detection.selections.remove("empty");
+        rule.detection.selections.insert(
+            "bad name!".to_owned(),
+            rule.detection.conditions.clone().into(),
+        );
+        assert!(rule.validate().is_err());
+    }
 }
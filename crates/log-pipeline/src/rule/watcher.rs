@@ -0,0 +1,384 @@
+//! 규칙 디렉토리 감시 -- 파일 변경 시 규칙 hot reload
+//!
+//! [`RuleWatcher`]는 규칙 디렉토리의 변경을 `notify`로 감지하고, 변경이 잠잠해지면
+//! 디렉토리 전체를 다시 읽어 [`RuleEngine::replace_rules`]로 교체합니다.
+//!
+//! # 동작
+//! - 이벤트 디바운스: 편집기 저장처럼 연속된 이벤트는 한 번의 리로드로 묶음
+//! - 주기적 재확인: `notify` 이벤트가 오지 않는 파일시스템(NFS, 심볼릭 링크 교체 등)을 위해
+//!   `rescan_interval`마다 디렉토리를 다시 확인
+//! - 내용 지문: 규칙 파일 내용이 실제로 바뀐 경우에만 교체 (교체 시 threshold/상관 분석
+//!   상태가 초기화되므로 불필요한 교체를 피함)
+//! - 검증 실패: 파일 하나라도 잘못되면 기존 규칙을 유지하고 에러를 상태에 기록합니다.
+//!   파이프라인은 이 상태를 health check에서 `Degraded`로 보고합니다.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify::{EventKind, RecursiveMode, Watcher};
+use sha2::{Digest, Sha256};
+use tokio::sync::{Mutex, RwLock, mpsc};
+use tokio_util::sync::CancellationToken;
+
+use super::RuleEngine;
+use super::loader::RuleLoader;
+use crate::error::LogPipelineError;
+
+/// 연속된 파일 이벤트를 하나로 묶는 대기 시간
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 마지막 리로드 결과 (`None`이면 정상)
+pub type RuleReloadError = Arc<RwLock<Option<String>>>;
+
+/// 규칙 디렉토리 감시자
+pub struct RuleWatcher {
+    /// 감시할 규칙 디렉토리
+    dir: PathBuf,
+    /// 규칙을 교체할 엔진
+    engine: Arc<Mutex<RuleEngine>>,
+    /// 마지막 리로드 에러 (health check 공유)
+    last_error: RuleReloadError,
+    /// 주기적 재확인 간격 (`None`이면 이벤트로만 리로드)
+    rescan_interval: Option<Duration>,
+    /// 이벤트 디바운스 시간
+    debounce: Duration,
+    /// 마지막으로 시도한 규칙 파일 내용 지문
+    fingerprint: Option<[u8; 32]>,
+}
+
+impl RuleWatcher {
+    /// 새 감시자를 생성합니다.
+    ///
+    /// `rescan_interval`이 0이면 주기적 재확인 없이 파일 이벤트로만 리로드합니다.
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        engine: Arc<Mutex<RuleEngine>>,
+        last_error: RuleReloadError,
+        rescan_interval: Duration,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            engine,
+            last_error,
+            rescan_interval: (!rescan_interval.is_zero()).then_some(rescan_interval),
+            debounce: DEFAULT_DEBOUNCE,
+            fingerprint: None,
+        }
+    }
+
+    /// 이벤트 디바운스 시간을 설정합니다.
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// 현재 규칙 파일 내용을 기준 지문으로 기록합니다.
+    ///
+    /// 엔진에 규칙을 로드하기 직전에 호출하면, 로드 이후의 변경만 리로드를 일으킵니다.
+    /// 호출하지 않으면 감시 시작 시 규칙 세트를 한 번 교체합니다.
+    pub async fn snapshot(&mut self) {
+        self.fingerprint = fingerprint(&self.dir).await.ok();
+    }
+
+    /// 취소될 때까지 디렉토리를 감시하며 규칙을 리로드합니다.
+    ///
+    /// `notify` 감시를 시작할 수 없으면 주기적 재확인으로만 동작하며,
+    /// 재확인도 꺼져 있으면 에러를 상태에 기록하고 반환합니다.
+    pub async fn run(mut self, cancel: CancellationToken) {
+        let (event_tx, mut event_rx) = mpsc::channel::<()>(1);
+        let _watcher = match self.start_notify(event_tx) {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                if self.rescan_interval.is_none() {
+                    tracing::error!(dir = %self.dir.display(), error = %e, "rule watcher unavailable");
+                    *self.last_error.write().await = Some(e.to_string());
+                    return;
+                }
+                tracing::warn!(
+                    dir = %self.dir.display(),
+                    error = %e,
+                    "rule watcher unavailable, falling back to periodic rescan"
+                );
+                None
+            }
+        };
+
+        let mut rescan = self.rescan_interval.map(|period| {
+            let mut rescan = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            rescan.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            rescan
+        });
+
+        // snapshot 이후 감시가 시작되기 전까지의 변경 반영
+        self.reload_if_changed().await;
+
+        tracing::info!(
+            dir = %self.dir.display(),
+            rescan_secs = self.rescan_interval.map(|d| d.as_secs()),
+            "watching rule directory"
+        );
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                Some(()) = event_rx.recv() => {
+                    // 이벤트가 잠잠해질 때까지 대기
+                    loop {
+                        tokio::select! {
+                            _ = cancel.cancelled() => return,
+                            _ = tokio::time::sleep(self.debounce) => break,
+                            Some(()) = event_rx.recv() => {}
+                        }
+                    }
+                    self.reload_if_changed().await;
+                }
+                _ = next_rescan(&mut rescan) => {
+                    self.reload_if_changed().await;
+                }
+            }
+        }
+
+        tracing::debug!(dir = %self.dir.display(), "rule watcher stopped");
+    }
+
+    /// `notify` 감시를 시작합니다. 반환된 감시자가 drop되면 감시가 끝납니다.
+    fn start_notify(
+        &self,
+        event_tx: mpsc::Sender<()>,
+    ) -> Result<notify::RecommendedWatcher, LogPipelineError> {
+        let mut watcher =
+            notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+                match result {
+                    // 접근(읽기) 이벤트는 내용 변경이 아님
+                    Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+                    Ok(_) => {
+                        // 채널이 차 있으면 이미 리로드 대기 중
+                        let _ = event_tx.try_send(());
+                    }
+                    Err(e) => tracing::warn!(error = %e, "rule directory watch error"),
+                }
+            })
+            .map_err(|e| self.watch_error(e))?;
+        watcher
+            .watch(&self.dir, RecursiveMode::NonRecursive)
+            .map_err(|e| self.watch_error(e))?;
+        Ok(watcher)
+    }
+
+    fn watch_error(&self, e: notify::Error) -> LogPipelineError {
+        LogPipelineError::RuleLoad {
+            path: self.dir.display().to_string(),
+            reason: format!("failed to watch directory: {e}"),
+        }
+    }
+
+    /// 규칙 파일 내용이 바뀌었으면 규칙 세트를 교체합니다.
+    ///
+    /// 실패한 내용도 지문을 기록하여, 같은 잘못된 내용으로 반복 시도하지 않습니다.
+    async fn reload_if_changed(&mut self) {
+        let current = match fingerprint(&self.dir).await {
+            Ok(current) => current,
+            Err(e) => {
+                self.report(Err(e)).await;
+                return;
+            }
+        };
+        if self.fingerprint == Some(current) {
+            return;
+        }
+        self.fingerprint = Some(current);
+
+        let result = match RuleLoader::load_directory_strict(&self.dir).await {
+            Ok(rules) => self.engine.lock().await.replace_rules(rules),
+            Err(e) => Err(e),
+        };
+        self.report(result).await;
+    }
+
+    async fn report(&self, result: Result<usize, LogPipelineError>) {
+        match result {
+            Ok(count) => {
+                tracing::info!(dir = %self.dir.display(), rules = count, "reloaded detection rules");
+                *self.last_error.write().await = None;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    dir = %self.dir.display(),
+                    error = %e,
+                    "rule reload failed, keeping current rules"
+                );
+                *self.last_error.write().await = Some(e.to_string());
+            }
+        }
+    }
+}
+
+/// 다음 재확인 시점까지 대기합니다 (재확인이 꺼져 있으면 영원히 대기).
+async fn next_rescan(rescan: &mut Option<tokio::time::Interval>) {
+    match rescan {
+        Some(rescan) => {
+            rescan.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// 디렉토리의 YAML 규칙 파일 이름과 내용으로 지문을 계산합니다.
+async fn fingerprint(dir: &Path) -> Result<[u8; 32], LogPipelineError> {
+    let read_error = |e: std::io::Error| LogPipelineError::RuleLoad {
+        path: dir.display().to_string(),
+        reason: format!("failed to read directory: {e}"),
+    };
+
+    let mut entries = tokio::fs::read_dir(dir).await.map_err(read_error)?;
+    let mut paths = Vec::new();
+    while let Some(entry) = entries.next_entry().await.map_err(read_error)? {
+        let path = entry.path();
+        if path
+            .extension()
+            .is_some_and(|ext| ext == "yml" || ext == "yaml")
+        {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut hasher = Sha256::new();
+    for path in paths {
+        hasher.update(path.as_os_str().as_encoded_bytes());
+        hasher.update([0]);
+        // 읽을 수 없는 파일은 이름만 반영 (로딩 시 에러로 보고됨)
+        if let Ok(content) = tokio::fs::read(&path).await {
+            hasher.update((content.len() as u64).to_le_bytes());
+            hasher.update(&content);
+        }
+    }
+    Ok(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULE_A: &str = "id: rule_a\ntitle: Rule A\nseverity: Low\ndetection:\n  conditions:\n    - field: process\n      value: sshd\n";
+    const RULE_B: &str = "id: rule_b\ntitle: Rule B\nseverity: Low\ndetection:\n  conditions:\n    - field: process\n      value: nginx\n";
+
+    async fn rule_ids(engine: &Arc<Mutex<RuleEngine>>) -> Vec<String> {
+        let mut ids: Vec<String> = engine
+            .lock()
+            .await
+            .rules()
+            .iter()
+            .map(|rule| rule.id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// 조건을 만족할 때까지 짧게 폴링합니다.
+    async fn wait_for<F, Fut>(mut condition: F)
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = bool>,
+    {
+        for _ in 0..100 {
+            if condition().await {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("condition not met in time");
+    }
+
+    /// 파이프라인과 같은 순서로 지문 기록 -> 초기 로드 -> 감시 시작을 수행합니다.
+    async fn start_watcher(
+        dir: &Path,
+        rescan: Duration,
+    ) -> (Arc<Mutex<RuleEngine>>, RuleReloadError, CancellationToken) {
+        let engine = Arc::new(Mutex::new(RuleEngine::new()));
+        let last_error = RuleReloadError::default();
+        let mut watcher =
+            RuleWatcher::new(dir, Arc::clone(&engine), Arc::clone(&last_error), rescan)
+                .with_debounce(Duration::from_millis(50));
+        watcher.snapshot().await;
+        engine.lock().await.load_rules_from_dir(dir).await.unwrap();
+
+        let cancel = CancellationToken::new();
+        tokio::spawn(watcher.run(cancel.clone()));
+        (engine, last_error, cancel)
+    }
+
+    #[tokio::test]
+    async fn reloads_added_modified_and_removed_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.yml"), RULE_A).unwrap();
+        let (engine, last_error, cancel) = start_watcher(dir.path(), Duration::ZERO).await;
+
+        std::fs::write(dir.path().join("b.yaml"), RULE_B).unwrap();
+        wait_for(|| async { rule_ids(&engine).await == ["rule_a", "rule_b"] }).await;
+
+        std::fs::write(
+            dir.path().join("a.yml"),
+            RULE_A.replace("Rule A", "Rule A2"),
+        )
+        .unwrap();
+        wait_for(|| async {
+            engine
+                .lock()
+                .await
+                .rules()
+                .iter()
+                .any(|rule| rule.title == "Rule A2")
+        })
+        .await;
+
+        std::fs::remove_file(dir.path().join("b.yaml")).unwrap();
+        wait_for(|| async { rule_ids(&engine).await == ["rule_a"] }).await;
+        assert!(last_error.read().await.is_none());
+
+        cancel.cancel();
+    }
+
+    #[tokio::test]
+    async fn invalid_rule_keeps_current_rules_and_reports_error() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.yml"), RULE_A).unwrap();
+        // 이벤트 없이도 주기적 재확인으로 감지되는지 함께 확인
+        let (engine, last_error, cancel) =
+            start_watcher(dir.path(), Duration::from_millis(100)).await;
+
+        std::fs::write(dir.path().join("broken.yml"), "id: [unclosed").unwrap();
+        wait_for(|| async { last_error.read().await.is_some() }).await;
+        assert_eq!(rule_ids(&engine).await, ["rule_a"]);
+        assert!(
+            last_error
+                .read()
+                .await
+                .as_deref()
+                .is_some_and(|e| e.contains("broken.yml"))
+        );
+
+        std::fs::remove_file(dir.path().join("broken.yml")).unwrap();
+        std::fs::write(dir.path().join("b.yml"), RULE_B).unwrap();
+        wait_for(|| async { last_error.read().await.is_none() }).await;
+        assert_eq!(rule_ids(&engine).await, ["rule_a", "rule_b"]);
+
+        cancel.cancel();
+    }
+
+    #[tokio::test]
+    async fn fingerprint_tracks_yaml_content_only() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.yml"), RULE_A).unwrap();
+        let first = fingerprint(dir.path()).await.unwrap();
+
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+        assert_eq!(fingerprint(dir.path()).await.unwrap(), first);
+
+        std::fs::write(dir.path().join("a.yml"), RULE_B).unwrap();
+        assert_ne!(fingerprint(dir.path()).await.unwrap(), first);
+
+        assert!(fingerprint(&dir.path().join("missing")).await.is_err());
+    }
+}
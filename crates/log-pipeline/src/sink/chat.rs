@@ -0,0 +1,406 @@
+//! 채팅 알림 싱크 -- Slack, Discord, Microsoft Teams 수신 웹훅으로 알림을 보냅니다.
+//!
+//! 심각도별 색상, 규칙 이름, 매칭된 필드를 각 플랫폼의 메시지 형식으로 만듭니다.
+//!
+//! | 플랫폼 | 형식 | 색상 |
+//! |--------|------|------|
+//! | Slack | attachment `fields` | `color` |
+//! | Discord | embed `fields` | `color` |
+//! | Teams | MessageCard `facts` | `themeColor` |
+//!
+//! 로그에서 온 값이 멘션(`<!channel>`, `@everyone`)으로 해석되지 않도록
+//! Slack은 제어 문자를 이스케이프하고, Discord는 멘션 파싱을 끕니다.
+
+use std::time::UNIX_EPOCH;
+
+use ironpost_core::config::AlertSinkConfig;
+use ironpost_core::pipeline::BoxFuture;
+use ironpost_core::types::Severity;
+
+use super::http::HttpDelivery;
+use super::webhook::format_time;
+use super::{AlertSink, SinkAlert, SinkDelivery, simulated_label};
+use crate::error::LogPipelineError;
+
+/// 메시지에 포함할 최대 매칭 필드 수
+const MAX_MATCHED_FIELDS: usize = 10;
+
+/// 필드 값 최대 길이 (문자)
+const MAX_FIELD_VALUE_CHARS: usize = 256;
+
+/// 제목 최대 길이 (문자, Discord embed 제한)
+const MAX_TITLE_CHARS: usize = 256;
+
+/// 설명 최대 길이 (문자)
+const MAX_DESCRIPTION_CHARS: usize = 2000;
+
+/// 짧은 필드로 나란히 표시할 값의 최대 길이
+const SHORT_FIELD_CHARS: usize = 40;
+
+/// 채팅 플랫폼
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatPlatform {
+    /// Slack 수신 웹훅
+    Slack,
+    /// Discord 웹훅
+    Discord,
+    /// Microsoft Teams 수신 웹훅 (MessageCard)
+    Teams,
+}
+
+impl ChatPlatform {
+    /// 싱크 `type` 값에서 플랫폼을 찾습니다.
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "slack" => Some(Self::Slack),
+            "discord" => Some(Self::Discord),
+            "teams" => Some(Self::Teams),
+            _ => None,
+        }
+    }
+
+    /// 싱크 `type` 값
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Slack => "slack",
+            Self::Discord => "discord",
+            Self::Teams => "teams",
+        }
+    }
+}
+
+/// 심각도별 메시지 색상 (RGB)
+pub fn severity_color(severity: Severity) -> u32 {
+    match severity {
+        Severity::Info => 0x9E9E9E,
+        Severity::Low => 0x2196F3,
+        Severity::Medium => 0xFFC107,
+        Severity::High => 0xFF9800,
+        Severity::Critical => 0xF44336,
+    }
+}
+
+/// 메시지 필드 (이름, 값)
+struct Fact {
+    name: String,
+    value: String,
+}
+
+/// 채팅 알림 싱크
+pub struct ChatSink {
+    http: HttpDelivery,
+    platform: ChatPlatform,
+}
+
+impl ChatSink {
+    /// 싱크 설정에서 채팅 싱크를 생성합니다.
+    ///
+    /// # Errors
+    /// 잘못된 헤더 또는 HTTP 클라이언트 생성 실패
+    pub fn from_config(
+        config: &AlertSinkConfig,
+        platform: ChatPlatform,
+    ) -> Result<Self, LogPipelineError> {
+        Ok(Self {
+            http: HttpDelivery::from_config(config)?,
+            platform,
+        })
+    }
+
+    /// 플랫폼을 반환합니다.
+    pub fn platform(&self) -> ChatPlatform {
+        self.platform
+    }
+
+    /// 알림을 플랫폼 메시지로 변환합니다.
+    pub fn format(&self, alert: &SinkAlert) -> serde_json::Value {
+        let event = &alert.event;
+        let title = truncate(
+            &format!(
+                "{}[{}] {}",
+                simulated_label(alert.simulated),
+                event.severity,
+                event.alert.title
+            ),
+            MAX_TITLE_CHARS,
+        );
+        let description = truncate(&event.alert.description, MAX_DESCRIPTION_CHARS);
+        let color = severity_color(event.severity);
+        let facts = facts(alert);
+
+        match self.platform {
+            ChatPlatform::Slack => {
+                let fields: Vec<_> = facts
+                    .iter()
+                    .map(|fact| {
+                        serde_json::json!({
+                            "title": slack_escape(&fact.name),
+                            "value": slack_escape(&fact.value),
+                            "short": fact.value.chars().count() <= SHORT_FIELD_CHARS,
+                        })
+                    })
+                    .collect();
+                let ts = event
+                    .alert
+                    .created_at
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                serde_json::json!({
+                    "text": slack_escape(&title),
+                    "attachments": [{
+                        "color": format!("#{color:06X}"),
+                        "title": slack_escape(&title),
+                        "text": slack_escape(&description),
+                        "fields": fields,
+                        "footer": "ironpost",
+                        "ts": ts,
+                    }],
+                })
+            }
+            ChatPlatform::Discord => {
+                let fields: Vec<_> = facts
+                    .iter()
+                    .map(|fact| {
+                        serde_json::json!({
+                            "name": fact.name,
+                            // Discord는 빈 필드 값을 거부
+                            "value": if fact.value.is_empty() { "-" } else { fact.value.as_str() },
+                            "inline": fact.value.chars().count() <= SHORT_FIELD_CHARS,
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "allowed_mentions": { "parse": [] },
+                    "embeds": [{
+                        "title": title,
+                        "description": description,
+                        "color": color,
+                        "fields": fields,
+                        "timestamp": format_time(event.alert.created_at),
+                        "footer": { "text": "ironpost" },
+                    }],
+                })
+            }
+            ChatPlatform::Teams => {
+                let facts: Vec<_> = facts
+                    .iter()
+                    .map(|fact| serde_json::json!({ "name": fact.name, "value": fact.value }))
+                    .collect();
+                serde_json::json!({
+                    "@type": "MessageCard",
+                    "@context": "https://schema.org/extensions",
+                    "themeColor": format!("{color:06X}"),
+                    "summary": title,
+                    "title": title,
+                    "sections": [{
+                        "text": description,
+                        "facts": facts,
+                    }],
+                })
+            }
+        }
+    }
+}
+
+impl AlertSink for ChatSink {
+    fn name(&self) -> &str {
+        self.http.name()
+    }
+
+    fn send<'a>(
+        &'a self,
+        alert: &'a SinkAlert,
+    ) -> BoxFuture<'a, Result<SinkDelivery, LogPipelineError>> {
+        Box::pin(async move {
+            self.http.deliver(self.format(alert).to_string()).await?;
+            Ok(SinkDelivery::Sent)
+        })
+    }
+}
+
+/// 메시지에 표시할 필드 목록: 심각도, 규칙, IP, 매칭 횟수, 태그, 매칭된 로그 필드
+fn facts(alert: &SinkAlert) -> Vec<Fact> {
+    let event = &alert.event;
+    let mut facts = vec![
+        Fact {
+            name: "Severity".to_owned(),
+            value: event.severity.to_string(),
+        },
+        Fact {
+            name: "Rule".to_owned(),
+            value: event.alert.rule_name.clone(),
+        },
+    ];
+    if let Some(ip) = event.alert.source_ip {
+        facts.push(Fact {
+            name: "Source IP".to_owned(),
+            value: ip.to_string(),
+        });
+    }
+    if let Some(ip) = event.alert.target_ip {
+        facts.push(Fact {
+            name: "Target IP".to_owned(),
+            value: ip.to_string(),
+        });
+    }
+    if let Some(count) = alert.match_count {
+        facts.push(Fact {
+            name: "Count".to_owned(),
+            value: count.to_string(),
+        });
+    }
+    if !alert.tags.is_empty() {
+        facts.push(Fact {
+            name: "Tags".to_owned(),
+            value: truncate(&alert.tags.join(", "), MAX_FIELD_VALUE_CHARS),
+        });
+    }
+    for (name, value) in alert.fields.iter().take(MAX_MATCHED_FIELDS) {
+        facts.push(Fact {
+            name: truncate(name, MAX_FIELD_VALUE_CHARS),
+            value: truncate(value, MAX_FIELD_VALUE_CHARS),
+        });
+    }
+    facts
+}
+
+/// 최대 문자 수를 넘으면 잘라내고 `…`를 붙입니다.
+fn truncate(value: &str, max_chars: usize) -> String {
+    match value.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", &value[..end]),
+        None => value.to_owned(),
+    }
+}
+
+/// Slack 메시지 제어 문자(`&`, `<`, `>`)를 이스케이프합니다.
+fn slack_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use ironpost_core::event::AlertEvent;
+    use ironpost_core::types::Alert;
+
+    use super::super::http::test_server::serve;
+    use super::*;
+
+    fn sink(platform: ChatPlatform, url: &str) -> ChatSink {
+        let config = AlertSinkConfig {
+            name: platform.as_str().to_owned(),
+            kind: platform.as_str().to_owned(),
+            url: url.to_owned(),
+            max_retries: 0,
+            ..Default::default()
+        };
+        ChatSink::from_config(&config, platform).unwrap()
+    }
+
+    fn sink_alert() -> SinkAlert {
+        let mut alert = SinkAlert::new(AlertEvent::new(
+            Alert {
+                id: "a1".to_owned(),
+                title: "SSH brute force".to_owned(),
+                description: "Repeated failures <!channel>".to_owned(),
+                severity: Severity::Critical,
+                rule_name: "ssh_brute_force".to_owned(),
+                source_ip: Some([203, 0, 113, 7].into()),
+                target_ip: None,
+                created_at: UNIX_EPOCH,
+            },
+            Severity::Critical,
+        ));
+        alert.tags = vec!["auth".to_owned()];
+        alert.fields = vec![
+            ("process".to_owned(), "sshd".to_owned()),
+            ("message".to_owned(), "x".repeat(300)),
+        ];
+        alert.match_count = Some(12);
+        alert
+    }
+
+    #[test]
+    fn slack_message_has_color_and_fields() {
+        let message = sink(ChatPlatform::Slack, "http://127.0.0.1:9/").format(&sink_alert());
+        let attachment = &message["attachments"][0];
+        assert_eq!(attachment["color"], "#F44336");
+        assert_eq!(attachment["text"], "Repeated failures &lt;!channel&gt;");
+
+        let fields = attachment["fields"].as_array().unwrap();
+        let names: Vec<&str> = fields
+            .iter()
+            .map(|f| f["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Severity",
+                "Rule",
+                "Source IP",
+                "Count",
+                "Tags",
+                "process",
+                "message"
+            ]
+        );
+        assert_eq!(fields[1]["value"], "ssh_brute_force");
+        assert_eq!(fields[5]["short"], true);
+        assert_eq!(fields[6]["short"], false);
+        assert_eq!(
+            fields[6]["value"].as_str().unwrap().chars().count(),
+            MAX_FIELD_VALUE_CHARS + 1
+        );
+    }
+
+    #[test]
+    fn discord_message_disables_mentions() {
+        let message = sink(ChatPlatform::Discord, "http://127.0.0.1:9/").format(&sink_alert());
+        assert_eq!(message["allowed_mentions"]["parse"], serde_json::json!([]));
+        let embed = &message["embeds"][0];
+        assert_eq!(embed["color"], 0xF44336);
+        assert_eq!(embed["title"], "[Critical] SSH brute force");
+        assert_eq!(embed["timestamp"], "1970-01-01T00:00:00Z");
+        assert_eq!(embed["fields"][0]["value"], "Critical");
+    }
+
+    #[test]
+    fn teams_message_uses_message_card() {
+        let message = sink(ChatPlatform::Teams, "http://127.0.0.1:9/").format(&sink_alert());
+        assert_eq!(message["@type"], "MessageCard");
+        assert_eq!(message["themeColor"], "F44336");
+        assert_eq!(message["sections"][0]["facts"][3]["name"], "Count");
+        assert_eq!(message["sections"][0]["facts"][3]["value"], "12");
+    }
+
+    #[test]
+    fn severity_colors_are_distinct() {
+        let colors: std::collections::HashSet<u32> = [
+            Severity::Info,
+            Severity::Low,
+            Severity::Medium,
+            Severity::High,
+            Severity::Critical,
+        ]
+        .into_iter()
+        .map(severity_color)
+        .collect();
+        assert_eq!(colors.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn send_posts_formatted_message() {
+        let (url, requests) = serve(vec![200]).await;
+        let sink = sink(ChatPlatform::Slack, &url);
+
+        sink.send(&sink_alert()).await.unwrap();
+
+        let requests = requests.lock().await;
+        assert_eq!(requests.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&requests[0].1).unwrap();
+        assert_eq!(body["text"], "[Critical] SSH brute force");
+    }
+}
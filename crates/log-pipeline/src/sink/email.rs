@@ -0,0 +1,552 @@
+//! SMTP 이메일 싱크 -- 심각한 알림은 즉시, 나머지는 다이제스트로 모아 메일로 전송합니다.
+//!
+//! `immediate_severity` 이상인 알림은 알림마다 메일을 한 통씩 보냅니다.
+//! 그 미만의 알림은 모아 두었다가 `digest_interval_secs`마다 요약 메일 한 통으로 보냅니다.
+//! 요약에는 심각도별/규칙별 건수와 최대 `digest_max_alerts`개의 알림 목록이 들어갑니다.
+//! `digest_interval_secs`가 0이면 모든 알림을 즉시 보냅니다.
+//!
+//! SMTP 서버 연결은 core의 [`SmtpConfig`](ironpost_core::config::SmtpConfig)
+//! (`log_pipeline.smtp`)를 따릅니다. 연결 실패와 일시적 오류(`4xx`)는 재시도하고,
+//! 영구 오류(`5xx`)는 바로 실패합니다.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use lettre::message::Mailbox;
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use ironpost_core::config::{AlertSinkConfig, SmtpConfig};
+use ironpost_core::pipeline::BoxFuture;
+use ironpost_core::types::Severity;
+
+use super::retry::{AttemptError, RetryPolicy};
+use super::webhook::format_time;
+use super::{AlertSink, SinkAlert, SinkDelivery, simulated_label};
+use crate::error::LogPipelineError;
+
+/// 메일 제목 접두사
+const SUBJECT_PREFIX: &str = "[ironpost]";
+
+/// 다이제스트 요약에 나열할 최대 규칙 수
+const MAX_DIGEST_RULES: usize = 20;
+
+/// 보류 중인 다이제스트
+#[derive(Debug, Default)]
+struct Digest {
+    /// 첫 알림 시각
+    since: Option<SystemTime>,
+    /// 모은 알림 수
+    total: usize,
+    /// 심각도별 알림 수
+    by_severity: BTreeMap<Severity, usize>,
+    /// 규칙별 알림 수
+    by_rule: BTreeMap<String, usize>,
+    /// 알림 목록 (최대 `digest_max_alerts`개)
+    lines: Vec<String>,
+    /// dry-run 알림 포함 여부
+    simulated: bool,
+}
+
+impl Digest {
+    /// 알림을 다이제스트에 추가합니다.
+    fn push(&mut self, alert: &SinkAlert, max_lines: usize) {
+        let event = &alert.event;
+        self.since.get_or_insert(event.alert.created_at);
+        self.total += 1;
+        self.simulated |= alert.simulated;
+        *self.by_severity.entry(event.severity).or_default() += 1;
+        *self
+            .by_rule
+            .entry(event.alert.rule_name.clone())
+            .or_default() += 1;
+        if self.lines.len() < max_lines {
+            let mut line = format!(
+                "{} [{}] {} ({})",
+                format_time(event.alert.created_at),
+                event.severity,
+                single_line(&event.alert.title),
+                event.alert.rule_name
+            );
+            if let Some(ip) = event.alert.source_ip {
+                let _ = write!(line, " from {ip}");
+            }
+            self.lines.push(line);
+        }
+    }
+
+    /// 제목과 본문을 만듭니다.
+    fn render(&self) -> (String, String) {
+        let subject = format!(
+            "{SUBJECT_PREFIX} {}Alert digest: {} alert(s) since {}",
+            simulated_label(self.simulated),
+            self.total,
+            self.since.map(format_time).unwrap_or_default()
+        );
+
+        let mut body = format!("{} alert(s) were raised.\n\nBy severity:\n", self.total);
+        for (severity, count) in self.by_severity.iter().rev() {
+            let _ = writeln!(body, "  {severity:<10} {count}");
+        }
+
+        let mut rules: Vec<(&String, &usize)> = self.by_rule.iter().collect();
+        rules.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        body.push_str("\nBy rule:\n");
+        for (rule, count) in rules.iter().take(MAX_DIGEST_RULES) {
+            let _ = writeln!(body, "  {count:>6}  {rule}");
+        }
+        if rules.len() > MAX_DIGEST_RULES {
+            let _ = writeln!(
+                body,
+                "  ... {} more rule(s)",
+                rules.len() - MAX_DIGEST_RULES
+            );
+        }
+
+        body.push_str("\nAlerts:\n");
+        for line in &self.lines {
+            let _ = writeln!(body, "  {line}");
+        }
+        if self.total > self.lines.len() {
+            let _ = writeln!(
+                body,
+                "  ... {} more alert(s) not listed",
+                self.total - self.lines.len()
+            );
+        }
+        (subject, body)
+    }
+}
+
+/// SMTP 이메일 싱크
+pub struct EmailSink {
+    name: String,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+    immediate_severity: Severity,
+    digest_interval: Option<Duration>,
+    digest_max_alerts: usize,
+    retry: RetryPolicy,
+    digest: Mutex<Digest>,
+}
+
+impl EmailSink {
+    /// 싱크 설정과 SMTP 서버 설정에서 이메일 싱크를 생성합니다.
+    ///
+    /// # Errors
+    /// 잘못된 메일 주소, 심각도, TLS 방식 또는 TLS 설정 실패
+    pub fn from_config(
+        config: &AlertSinkConfig,
+        smtp: &SmtpConfig,
+    ) -> Result<Self, LogPipelineError> {
+        let invalid = |reason: String| LogPipelineError::Sink {
+            name: config.name.clone(),
+            reason,
+        };
+
+        let from: Mailbox = smtp
+            .from
+            .parse()
+            .map_err(|e| invalid(format!("invalid smtp.from '{}': {e}", smtp.from)))?;
+        let to = config
+            .to
+            .iter()
+            .map(|address| {
+                address
+                    .parse::<Mailbox>()
+                    .map_err(|e| invalid(format!("invalid recipient '{address}': {e}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if to.is_empty() {
+            return Err(invalid("at least one recipient is required".to_owned()));
+        }
+        let immediate_severity =
+            Severity::from_str_loose(&config.immediate_severity).ok_or_else(|| {
+                invalid(format!(
+                    "invalid immediate_severity '{}'",
+                    config.immediate_severity
+                ))
+            })?;
+
+        let builder = match smtp.tls.as_str() {
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&smtp.host),
+            "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host),
+            "none" => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &smtp.host,
+            )),
+            other => return Err(invalid(format!("invalid smtp.tls '{other}'"))),
+        }
+        .map_err(|e| invalid(format!("failed to configure SMTP TLS: {e}")))?;
+        let mut builder = builder
+            .port(smtp.port)
+            .timeout(Some(Duration::from_secs(smtp.timeout_secs)));
+        if !smtp.username.is_empty() {
+            builder = builder.credentials(Credentials::new(
+                smtp.username.clone(),
+                smtp.password.clone(),
+            ));
+        }
+
+        Ok(Self {
+            name: config.name.clone(),
+            transport: builder.build(),
+            from,
+            to,
+            immediate_severity,
+            digest_interval: (config.digest_interval_secs > 0)
+                .then(|| Duration::from_secs(config.digest_interval_secs)),
+            digest_max_alerts: config.digest_max_alerts.max(1),
+            retry: RetryPolicy {
+                max_retries: config.max_retries,
+                backoff: Duration::from_millis(config.retry_backoff_ms),
+            },
+            digest: Mutex::new(Digest::default()),
+        })
+    }
+
+    /// 알림을 즉시 보낼지 확인합니다.
+    pub fn is_immediate(&self, severity: Severity) -> bool {
+        self.digest_interval.is_none() || severity >= self.immediate_severity
+    }
+
+    /// 즉시 발송 메일의 제목과 본문을 만듭니다.
+    pub fn format(&self, alert: &SinkAlert) -> (String, String) {
+        let event = &alert.event;
+        let subject = format!(
+            "{SUBJECT_PREFIX} {}[{}] {}",
+            simulated_label(alert.simulated),
+            event.severity,
+            single_line(&event.alert.title)
+        );
+
+        let mut body = String::new();
+        let mut fact = |label: &str, value: &str| {
+            let _ = writeln!(body, "{:<12} {value}", format!("{label}:"));
+        };
+        fact("Severity", &event.severity.to_string());
+        fact("Rule", &event.alert.rule_name);
+        fact("Time", &format_time(event.alert.created_at));
+        if let Some(ip) = event.alert.source_ip {
+            fact("Source IP", &ip.to_string());
+        }
+        if let Some(ip) = event.alert.target_ip {
+            fact("Target IP", &ip.to_string());
+        }
+        if let Some(count) = alert.match_count {
+            fact("Count", &count.to_string());
+        }
+        if !alert.tags.is_empty() {
+            fact("Tags", &alert.tags.join(", "));
+        }
+        fact("Alert ID", &event.alert.id);
+
+        if !event.alert.description.is_empty() {
+            let _ = write!(body, "\n{}\n", event.alert.description);
+        }
+        if !alert.fields.is_empty() {
+            body.push_str("\nMatched fields:\n");
+            for (name, value) in &alert.fields {
+                let _ = writeln!(body, "  {name}: {value}");
+            }
+        }
+        (subject, body)
+    }
+
+    /// 메일 한 통을 재시도를 포함해 보냅니다.
+    async fn deliver(&self, subject: String, body: String) -> Result<(), LogPipelineError> {
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN);
+        for to in &self.to {
+            builder = builder.to(to.clone());
+        }
+        let message = builder.body(body).map_err(|e| LogPipelineError::Sink {
+            name: self.name.clone(),
+            reason: format!("failed to build message: {e}"),
+        })?;
+
+        self.retry
+            .run(&self.name, || self.attempt(message.clone()))
+            .await
+    }
+
+    /// 메일을 한 번 보냅니다. 실패 시 (재시도 가능 여부, 사유)를 반환합니다.
+    async fn attempt(&self, message: Message) -> Result<(), AttemptError> {
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|e| (!e.is_permanent(), format!("SMTP delivery failed: {e}")))
+    }
+
+    /// 다이제스트 잠금 (다른 스레드가 패닉했어도 계속 사용)
+    fn digest(&self) -> std::sync::MutexGuard<'_, Digest> {
+        self.digest
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl AlertSink for EmailSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send<'a>(
+        &'a self,
+        alert: &'a SinkAlert,
+    ) -> BoxFuture<'a, Result<SinkDelivery, LogPipelineError>> {
+        Box::pin(async move {
+            if !self.is_immediate(alert.event.severity) {
+                self.digest().push(alert, self.digest_max_alerts);
+                return Ok(SinkDelivery::Deferred);
+            }
+            let (subject, body) = self.format(alert);
+            self.deliver(subject, body).await?;
+            Ok(SinkDelivery::Sent)
+        })
+    }
+
+    fn flush_interval(&self) -> Option<Duration> {
+        self.digest_interval
+    }
+
+    fn flush(&self) -> BoxFuture<'_, (usize, Result<(), LogPipelineError>)> {
+        Box::pin(async move {
+            let digest = std::mem::take(&mut *self.digest());
+            if digest.total == 0 {
+                return (0, Ok(()));
+            }
+            let (subject, body) = digest.render();
+            (digest.total, self.deliver(subject, body).await)
+        })
+    }
+}
+
+/// 제어 문자(줄바꿈 등)를 공백으로 바꿔 한 줄로 만듭니다.
+fn single_line(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    use ironpost_core::event::AlertEvent;
+    use ironpost_core::types::Alert;
+
+    use super::*;
+
+    /// 수신한 메일 (DATA 내용)
+    type Mails = Arc<tokio::sync::Mutex<Vec<String>>>;
+
+    /// 최소 SMTP 서버를 띄웁니다. `mail_replies`는 연결마다 `MAIL FROM`에 보낼 응답입니다
+    /// (마지막 응답을 반복).
+    async fn serve(mail_replies: Vec<&'static str>) -> (u16, Mails) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mails: Mails = Arc::default();
+        let recorded = Arc::clone(&mails);
+
+        tokio::spawn(async move {
+            let mut connections = 0;
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let mail_reply = mail_replies[connections.min(mail_replies.len() - 1)];
+                connections += 1;
+
+                let (read, mut write) = stream.into_split();
+                let mut lines = BufReader::new(read).lines();
+                let _ = write.write_all(b"220 test ESMTP\r\n").await;
+                let mut data: Option<String> = None;
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(mail) = data.as_mut() {
+                        if line == "." {
+                            recorded.lock().await.push(data.take().unwrap_or_default());
+                            let _ = write.write_all(b"250 queued\r\n").await;
+                        } else {
+                            mail.push_str(&line);
+                            mail.push('\n');
+                        }
+                        continue;
+                    }
+                    let command = line.to_ascii_uppercase();
+                    let reply = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                        "250 test"
+                    } else if command.starts_with("MAIL") {
+                        mail_reply
+                    } else if command.starts_with("DATA") {
+                        data = Some(String::new());
+                        "354 go ahead"
+                    } else if command.starts_with("QUIT") {
+                        let _ = write.write_all(b"221 bye\r\n").await;
+                        break;
+                    } else {
+                        "250 ok"
+                    };
+                    let _ = write.write_all(format!("{reply}\r\n").as_bytes()).await;
+                }
+            }
+        });
+
+        (port, mails)
+    }
+
+    fn email_sink(port: u16) -> EmailSink {
+        let config = AlertSinkConfig {
+            name: "mail".to_owned(),
+            kind: "email".to_owned(),
+            to: vec!["SOC <soc@example.com>".to_owned()],
+            max_retries: 2,
+            retry_backoff_ms: 1,
+            ..Default::default()
+        };
+        let smtp = SmtpConfig {
+            host: "127.0.0.1".to_owned(),
+            port,
+            tls: "none".to_owned(),
+            from: "ironpost@example.com".to_owned(),
+            timeout_secs: 5,
+            ..Default::default()
+        };
+        EmailSink::from_config(&config, &smtp).unwrap()
+    }
+
+    fn alert(title: &str, severity: Severity, rule: &str) -> SinkAlert {
+        let mut alert = SinkAlert::new(AlertEvent::new(
+            Alert {
+                id: format!("{title}-id"),
+                title: title.to_owned(),
+                description: "details".to_owned(),
+                severity,
+                rule_name: rule.to_owned(),
+                source_ip: Some([10, 0, 0, 5].into()),
+                target_ip: None,
+                created_at: SystemTime::UNIX_EPOCH,
+            },
+            severity,
+        ));
+        alert.fields = vec![("user".to_owned(), "root".to_owned())];
+        alert
+    }
+
+    #[tokio::test]
+    async fn critical_alerts_are_sent_immediately() {
+        let (port, mails) = serve(vec!["250 ok"]).await;
+        let sink = email_sink(port);
+
+        let delivery = sink
+            .send(&alert("Root login", Severity::Critical, "root_login"))
+            .await
+            .unwrap();
+        assert_eq!(delivery, SinkDelivery::Sent);
+
+        let mails = mails.lock().await;
+        assert_eq!(mails.len(), 1);
+        let mail = &mails[0];
+        assert!(
+            mail.contains("Subject: [ironpost] [Critical] Root login"),
+            "{mail}"
+        );
+        assert!(mail.contains("To: SOC <soc@example.com>"), "{mail}");
+        assert!(mail.contains("Source IP:   10.0.0.5"), "{mail}");
+        assert!(mail.contains("  user: root"), "{mail}");
+    }
+
+    #[tokio::test]
+    async fn lower_severities_are_collected_into_digest() {
+        let (port, mails) = serve(vec!["250 ok"]).await;
+        let sink = email_sink(port);
+        assert_eq!(sink.flush_interval(), Some(Duration::from_secs(3600)));
+
+        for (title, severity, rule) in [
+            ("Scan", Severity::Medium, "port_scan"),
+            ("Scan again", Severity::Medium, "port_scan"),
+            ("Odd\nlogin", Severity::High, "odd_login"),
+        ] {
+            let delivery = sink.send(&alert(title, severity, rule)).await.unwrap();
+            assert_eq!(delivery, SinkDelivery::Deferred);
+        }
+        assert!(mails.lock().await.is_empty());
+
+        let (count, result) = sink.flush().await;
+        assert_eq!(count, 3);
+        result.unwrap();
+        let mail = mails.lock().await[0].clone();
+        assert!(
+            mail.contains(
+                "Subject: [ironpost] Alert digest: 3 alert(s) since 1970-01-01T00:00:00Z"
+            ),
+            "{mail}"
+        );
+        assert!(mail.contains("       2  port_scan"), "{mail}");
+        assert!(
+            mail.contains("[High] Odd login (odd_login) from 10.0.0.5"),
+            "{mail}"
+        );
+
+        // 보낸 다이제스트는 비워짐
+        assert_eq!(sink.flush().await.0, 0);
+    }
+
+    #[tokio::test]
+    async fn transient_errors_are_retried_and_permanent_are_not() {
+        let (port, mails) = serve(vec!["451 try later", "250 ok"]).await;
+        let sink = email_sink(port);
+        sink.send(&alert("Root login", Severity::Critical, "root_login"))
+            .await
+            .unwrap();
+        assert_eq!(mails.lock().await.len(), 1);
+
+        let (port, _) = serve(vec!["550 rejected"]).await;
+        let sink = email_sink(port);
+        let err = sink
+            .send(&alert("Root login", Severity::Critical, "root_login"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("after 1 attempt(s)"), "{err}");
+    }
+
+    #[test]
+    fn from_config_rejects_invalid_addresses() {
+        let config = AlertSinkConfig {
+            name: "mail".to_owned(),
+            kind: "email".to_owned(),
+            to: vec!["not an address".to_owned()],
+            ..Default::default()
+        };
+        let smtp = SmtpConfig {
+            host: "smtp.example.com".to_owned(),
+            from: "ironpost@example.com".to_owned(),
+            ..Default::default()
+        };
+        assert!(EmailSink::from_config(&config, &smtp).is_err());
+
+        let config = AlertSinkConfig {
+            to: vec!["soc@example.com".to_owned()],
+            digest_interval_secs: 0,
+            ..config
+        };
+        let sink = EmailSink::from_config(&config, &smtp).unwrap();
+        assert!(sink.is_immediate(Severity::Info));
+        assert_eq!(sink.flush_interval(), None);
+
+        let smtp = SmtpConfig {
+            from: String::new(),
+            ..smtp
+        };
+        assert!(EmailSink::from_config(&config, &smtp).is_err());
+    }
+}
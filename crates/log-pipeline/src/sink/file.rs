@@ -0,0 +1,429 @@
+//! 파일 싱크 -- 알림을 JSONL 파일에 한 줄씩 덧붙여 기록합니다.
+//!
+//! 외부 인프라가 없는 폐쇄망 환경에서도 알림 기록이 남도록, 알림마다 JSON 객체 한 줄을 쓰고
+//! `fsync`합니다. 줄에는 웹훅 기본 페이로드에 규칙 태그(`tags`), 매칭된 필드(`fields`),
+//! threshold 매칭 횟수(`match_count`)가 더해집니다.
+//!
+//! # 교체와 보관
+//! 쓰기 전에 파일이 `max_file_bytes`를 넘었거나 연 지 `rotate_interval_secs`가 지났으면
+//! 현재 파일을 `<파일명>.<UTC 시각>`(예: `alerts.jsonl.20240115T120000Z`)으로 옮기고 새 파일을
+//! 엽니다. 시간 교체는 다음 알림을 쓸 때 이루어집니다. 교체된 파일이 `max_files`개를 넘으면
+//! 오래된 파일부터 삭제합니다.
+//!
+//! 쓰기 실패(디스크 가득 참 등)는 파일을 다시 열어 재시도합니다.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use ironpost_core::config::AlertSinkConfig;
+use ironpost_core::pipeline::BoxFuture;
+
+use super::retry::{AttemptError, RetryPolicy};
+use super::webhook::default_payload;
+use super::{AlertSink, SinkAlert, SinkDelivery};
+use crate::error::LogPipelineError;
+
+/// 교체된 파일 이름의 시각 형식 (사전순 = 시간순)
+const ROTATED_SUFFIX_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// 열려 있는 기록 파일
+struct ActiveFile {
+    file: File,
+    size: u64,
+    opened_at: SystemTime,
+}
+
+/// JSONL 파일 싱크
+pub struct FileSink {
+    name: String,
+    path: PathBuf,
+    max_file_bytes: Option<u64>,
+    rotate_interval: Option<Duration>,
+    max_files: usize,
+    retry: RetryPolicy,
+    active: Mutex<Option<ActiveFile>>,
+}
+
+impl FileSink {
+    /// 싱크 설정에서 파일 싱크를 생성합니다. 파일은 첫 알림을 쓸 때 엽니다.
+    ///
+    /// # Errors
+    /// 파일 경로가 비어 있거나 파일 이름이 없는 경우
+    pub fn from_config(config: &AlertSinkConfig) -> Result<Self, LogPipelineError> {
+        let path = PathBuf::from(&config.path);
+        if path.file_name().is_none() {
+            return Err(LogPipelineError::Sink {
+                name: config.name.clone(),
+                reason: format!("invalid file path '{}'", config.path),
+            });
+        }
+
+        Ok(Self {
+            name: config.name.clone(),
+            path,
+            max_file_bytes: (config.max_file_bytes > 0).then_some(config.max_file_bytes),
+            rotate_interval: (config.rotate_interval_secs > 0)
+                .then(|| Duration::from_secs(config.rotate_interval_secs)),
+            max_files: config.max_files,
+            retry: RetryPolicy {
+                max_retries: config.max_retries,
+                backoff: Duration::from_millis(config.retry_backoff_ms),
+            },
+            active: Mutex::new(None),
+        })
+    }
+
+    /// 알림의 JSONL 레코드(줄바꿈 포함)를 만듭니다.
+    pub fn render(alert: &SinkAlert) -> String {
+        let mut record = default_payload(&alert.event);
+        record["tags"] = serde_json::json!(alert.tags);
+        record["fields"] = alert
+            .fields
+            .iter()
+            .map(|(name, value)| (name.clone(), serde_json::Value::from(value.as_str())))
+            .collect::<serde_json::Map<_, _>>()
+            .into();
+        if let Some(count) = alert.match_count {
+            record["match_count"] = count.into();
+        }
+        if alert.simulated {
+            record["simulated"] = true.into();
+        }
+        let mut line = record.to_string();
+        line.push('\n');
+        line
+    }
+
+    /// 레코드 한 줄을 한 번 기록합니다. 실패 시 (재시도 가능 여부, 사유)를 반환합니다.
+    async fn attempt(&self, line: &str) -> Result<(), AttemptError> {
+        let mut active = self.active.lock().await;
+        let result = self.write(&mut active, line).await;
+        if result.is_err() {
+            // 다음 시도에서 파일을 다시 엽니다.
+            *active = None;
+        }
+        result.map_err(|e| {
+            (
+                true,
+                format!("failed to write {}: {e}", self.path.display()),
+            )
+        })
+    }
+
+    /// 필요하면 파일을 교체한 뒤 레코드를 쓰고 디스크에 반영합니다.
+    async fn write(&self, active: &mut Option<ActiveFile>, line: &str) -> std::io::Result<()> {
+        let now = SystemTime::now();
+        if active.is_none() {
+            *active = Some(self.open().await?);
+        }
+        if let Some(current) = active.as_ref()
+            && self.should_rotate(current, line.len() as u64, now)
+        {
+            *active = None;
+            self.rotate(now).await?;
+            *active = Some(self.open().await?);
+        }
+
+        let Some(current) = active.as_mut() else {
+            return Ok(());
+        };
+        current.file.write_all(line.as_bytes()).await?;
+        current.file.sync_data().await?;
+        current.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// 기록 파일을 덧붙이기 모드로 엽니다. 디렉토리가 없으면 만듭니다.
+    async fn open(&self) -> std::io::Result<ActiveFile> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        let metadata = file.metadata().await?;
+        // 기존 파일이면 생성 시각부터 교체 주기를 셉니다 (지원하지 않는 파일시스템은 지금부터).
+        let opened_at = if metadata.len() > 0 {
+            metadata.created().unwrap_or_else(|_| SystemTime::now())
+        } else {
+            SystemTime::now()
+        };
+        Ok(ActiveFile {
+            file,
+            size: metadata.len(),
+            opened_at,
+        })
+    }
+
+    /// 레코드를 쓰기 전에 파일을 교체해야 하는지 확인합니다. 빈 파일은 교체하지 않습니다.
+    fn should_rotate(&self, current: &ActiveFile, incoming: u64, now: SystemTime) -> bool {
+        if current.size == 0 {
+            return false;
+        }
+        let too_large = self
+            .max_file_bytes
+            .is_some_and(|max| current.size + incoming > max);
+        let too_old = self.rotate_interval.is_some_and(|interval| {
+            now.duration_since(current.opened_at)
+                .is_ok_and(|age| age >= interval)
+        });
+        too_large || too_old
+    }
+
+    /// 현재 파일을 시각이 붙은 이름으로 옮기고 보관 개수를 넘는 파일을 삭제합니다.
+    async fn rotate(&self, now: SystemTime) -> std::io::Result<()> {
+        let suffix = DateTime::<Utc>::from(now)
+            .format(ROTATED_SUFFIX_FORMAT)
+            .to_string();
+        let mut target = rotated_path(&self.path, &suffix);
+        let mut counter = 1;
+        while tokio::fs::try_exists(&target).await? {
+            target = rotated_path(&self.path, &format!("{suffix}.{counter}"));
+            counter += 1;
+        }
+        tokio::fs::rename(&self.path, &target).await?;
+        tracing::info!(sink = %self.name, rotated = %target.display(), "rotated alert file");
+
+        if self.max_files > 0
+            && let Err(e) = self.prune().await
+        {
+            tracing::warn!(sink = %self.name, error = %e, "failed to prune rotated alert files");
+        }
+        Ok(())
+    }
+
+    /// 교체된 파일 중 오래된 것부터 `max_files`개만 남기고 삭제합니다.
+    async fn prune(&self) -> std::io::Result<()> {
+        let dir = match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let Some(file_name) = self.path.file_name().and_then(|name| name.to_str()) else {
+            return Ok(());
+        };
+        let prefix = format!("{file_name}.");
+
+        let mut rotated = Vec::new();
+        let mut entries = tokio::fs::read_dir(dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name();
+            if let Some(order) = name
+                .to_str()
+                .and_then(|name| name.strip_prefix(&prefix))
+                .and_then(rotated_order)
+                .map(|(stamp, counter)| (stamp.to_owned(), counter))
+            {
+                rotated.push((order, entry.path()));
+            }
+        }
+        rotated.sort();
+
+        let excess = rotated.len().saturating_sub(self.max_files);
+        for (_, path) in rotated.into_iter().take(excess) {
+            tokio::fs::remove_file(&path).await?;
+            tracing::debug!(sink = %self.name, removed = %path.display(), "removed old alert file");
+        }
+        Ok(())
+    }
+}
+
+impl AlertSink for FileSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send<'a>(
+        &'a self,
+        alert: &'a SinkAlert,
+    ) -> BoxFuture<'a, Result<SinkDelivery, LogPipelineError>> {
+        Box::pin(async move {
+            let line = Self::render(alert);
+            self.retry.run(&self.name, || self.attempt(&line)).await?;
+            Ok(SinkDelivery::Sent)
+        })
+    }
+}
+
+/// `<path>.<suffix>` 경로
+fn rotated_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// 교체된 파일 접미사(`20240115T120000Z` 또는 `20240115T120000Z.1`)의 정렬 키를 반환합니다.
+/// 교체된 파일이 아니면 `None`입니다.
+fn rotated_order(suffix: &str) -> Option<(&str, u32)> {
+    let (stamp, counter) = match suffix.split_once('.') {
+        Some((stamp, counter)) if counter.bytes().all(|b| b.is_ascii_digit()) => {
+            (stamp, counter.parse().ok()?)
+        }
+        Some(_) => return None,
+        None => (suffix, 0),
+    };
+    let bytes = stamp.as_bytes();
+    let is_stamp = bytes.len() == 16
+        && bytes[8] == b'T'
+        && bytes[15] == b'Z'
+        && bytes[..8]
+            .iter()
+            .chain(&bytes[9..15])
+            .all(u8::is_ascii_digit);
+    is_stamp.then_some((stamp, counter))
+}
+
+#[cfg(test)]
+mod tests {
+    use ironpost_core::event::AlertEvent;
+    use ironpost_core::types::{Alert, Severity};
+
+    use super::*;
+
+    fn sink_config(path: &Path) -> AlertSinkConfig {
+        AlertSinkConfig {
+            name: "archive".to_owned(),
+            kind: "file".to_owned(),
+            path: path.to_string_lossy().into_owned(),
+            ..Default::default()
+        }
+    }
+
+    fn sink_alert(title: &str) -> SinkAlert {
+        SinkAlert {
+            event: AlertEvent::new(
+                Alert {
+                    id: format!("id-{title}"),
+                    title: title.to_owned(),
+                    description: "line1\nline2".to_owned(),
+                    severity: Severity::High,
+                    rule_name: "ssh_fail".to_owned(),
+                    source_ip: None,
+                    target_ip: None,
+                    created_at: SystemTime::UNIX_EPOCH,
+                },
+                Severity::High,
+            ),
+            tags: vec!["auth".to_owned()],
+            fields: vec![("process".to_owned(), "sshd".to_owned())],
+            match_count: Some(5),
+            simulated: false,
+        }
+    }
+
+    fn lines(path: &Path) -> Vec<serde_json::Value> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    fn rotated_files(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with("alerts.jsonl."))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn appends_one_json_object_per_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/alerts.jsonl");
+        let sink = FileSink::from_config(&sink_config(&path)).unwrap();
+
+        sink.send(&sink_alert("first")).await.unwrap();
+        sink.send(&sink_alert("second")).await.unwrap();
+
+        let records = lines(&path);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["title"], "first");
+        assert_eq!(records[0]["description"], "line1\nline2");
+        assert_eq!(records[0]["tags"], serde_json::json!(["auth"]));
+        assert_eq!(records[0]["fields"]["process"], "sshd");
+        assert_eq!(records[0]["match_count"], 5);
+        assert_eq!(records[1]["title"], "second");
+
+        // 다시 생성해도 기존 파일에 이어 씁니다.
+        let sink = FileSink::from_config(&sink_config(&path)).unwrap();
+        sink.send(&sink_alert("third")).await.unwrap();
+        assert_eq!(lines(&path).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn rotates_by_size_and_keeps_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("alerts.jsonl");
+        std::fs::write(dir.path().join("alerts.jsonl.bak"), "keep").unwrap();
+        let line_len = FileSink::render(&sink_alert("a")).len() as u64;
+        let sink = FileSink::from_config(&AlertSinkConfig {
+            max_file_bytes: line_len * 2,
+            max_files: 2,
+            ..sink_config(&path)
+        })
+        .unwrap();
+
+        for title in ["a", "b", "c", "d", "e", "f", "g", "h"] {
+            sink.send(&sink_alert(title)).await.unwrap();
+        }
+
+        // 파일마다 2줄: a,b / c,d / e,f (삭제) ... g,h는 현재 파일
+        let rotated = rotated_files(dir.path());
+        assert_eq!(rotated.len(), 3, "{rotated:?}");
+        assert_eq!(rotated[2], "alerts.jsonl.bak");
+        let kept: Vec<String> = rotated[..2]
+            .iter()
+            .flat_map(|name| lines(&dir.path().join(name)))
+            .map(|record| record["title"].as_str().unwrap().to_owned())
+            .collect();
+        assert_eq!(kept, ["c", "d", "e", "f"]);
+        let current: Vec<_> = lines(&path).iter().map(|r| r["title"].clone()).collect();
+        assert_eq!(current, ["g", "h"]);
+    }
+
+    #[tokio::test]
+    async fn rotates_when_interval_elapsed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("alerts.jsonl");
+        let sink = FileSink::from_config(&AlertSinkConfig {
+            rotate_interval_secs: 60,
+            ..sink_config(&path)
+        })
+        .unwrap();
+
+        sink.send(&sink_alert("old")).await.unwrap();
+        if let Some(active) = sink.active.lock().await.as_mut() {
+            active.opened_at -= Duration::from_secs(61);
+        }
+        sink.send(&sink_alert("new")).await.unwrap();
+
+        let rotated = rotated_files(dir.path());
+        assert_eq!(rotated.len(), 1);
+        assert_eq!(lines(&dir.path().join(&rotated[0]))[0]["title"], "old");
+        assert_eq!(lines(&path)[0]["title"], "new");
+    }
+
+    #[test]
+    fn rotated_order_matches_timestamp_names_only() {
+        assert_eq!(
+            rotated_order("20240115T120000Z"),
+            Some(("20240115T120000Z", 0))
+        );
+        assert!(rotated_order("20240115T120000Z.10") > rotated_order("20240115T120000Z.2"));
+        assert!(rotated_order("20240115T120001Z") > rotated_order("20240115T120000Z.10"));
+        assert_eq!(rotated_order("bak"), None);
+        assert_eq!(rotated_order("20240115T120000Z."), None);
+        assert_eq!(rotated_order("20240115T120000Z.gz"), None);
+        assert_eq!(rotated_order("20240115-120000Z"), None);
+    }
+}
@@ -0,0 +1,179 @@
+//! HTTP 전송 -- 웹훅/채팅 싱크가 공유하는 요청 전송과 재시도
+//!
+//! 연결 실패, 타임아웃, `429`, `5xx` 응답은 [`RetryPolicy`]에 따라 재시도합니다.
+//! 그 밖의 `4xx`는 재시도해도 같은 결과이므로 바로 실패합니다.
+
+use std::time::Duration;
+
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+
+use ironpost_core::config::AlertSinkConfig;
+
+use super::retry::{AttemptError, RetryPolicy};
+use crate::error::LogPipelineError;
+
+/// 설정된 엔드포인트로 JSON 본문을 전송합니다.
+pub(crate) struct HttpDelivery {
+    name: String,
+    url: String,
+    method: reqwest::Method,
+    headers: HeaderMap,
+    client: reqwest::Client,
+    retry: RetryPolicy,
+}
+
+impl HttpDelivery {
+    /// 싱크 설정에서 전송기를 생성합니다.
+    ///
+    /// # Errors
+    /// 잘못된 메서드, 헤더 또는 HTTP 클라이언트 생성 실패
+    pub(crate) fn from_config(config: &AlertSinkConfig) -> Result<Self, LogPipelineError> {
+        let invalid = |reason: String| LogPipelineError::Sink {
+            name: config.name.clone(),
+            reason,
+        };
+
+        let method = reqwest::Method::from_bytes(config.method.as_bytes())
+            .map_err(|_| invalid(format!("invalid method '{}'", config.method)))?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(
+            USER_AGENT,
+            HeaderValue::from_static(concat!("ironpost/", env!("CARGO_PKG_VERSION"))),
+        );
+        for (name, value) in &config.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| invalid(format!("invalid header name '{name}'")))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|_| invalid(format!("invalid value for header '{name}'")))?;
+            headers.insert(name, value);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| invalid(format!("failed to build HTTP client: {e}")))?;
+
+        Ok(Self {
+            name: config.name.clone(),
+            url: config.url.clone(),
+            method,
+            headers,
+            client,
+            retry: RetryPolicy {
+                max_retries: config.max_retries,
+                backoff: Duration::from_millis(config.retry_backoff_ms),
+            },
+        })
+    }
+
+    /// 싱크 이름
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 재시도를 포함해 본문을 전송합니다.
+    pub(crate) async fn deliver(&self, body: String) -> Result<(), LogPipelineError> {
+        self.retry
+            .run(&self.name, || self.attempt(body.clone()))
+            .await
+    }
+
+    /// 요청 한 번을 보냅니다. 실패 시 (재시도 가능 여부, 사유)를 반환합니다.
+    async fn attempt(&self, body: String) -> Result<(), AttemptError> {
+        let response = self
+            .client
+            .request(self.method.clone(), &self.url)
+            .headers(self.headers.clone())
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| (true, format!("request failed: {e}")))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let retryable =
+            status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+        Err((retryable, format!("unexpected status {status}")))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_server {
+    //! 싱크 테스트용 최소 HTTP 서버
+
+    use std::sync::Arc;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::Mutex;
+
+    /// 수신한 요청 (헤더, 본문)
+    pub(crate) type Requests = Arc<Mutex<Vec<(String, String)>>>;
+
+    /// 순서대로 주어진 상태 코드로 응답하는 서버를 띄웁니다 (마지막 코드를 반복).
+    pub(crate) async fn serve(statuses: Vec<u16>) -> (String, Requests) {
+        serve_responses(
+            statuses
+                .into_iter()
+                .map(|status| (status, String::new()))
+                .collect(),
+        )
+        .await
+    }
+
+    /// 순서대로 주어진 (상태 코드, 본문)으로 응답하는 서버를 띄웁니다 (마지막 응답을 반복).
+    pub(crate) async fn serve_responses(responses: Vec<(u16, String)>) -> (String, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let requests: Requests = Arc::default();
+        let recorded = Arc::clone(&requests);
+
+        tokio::spawn(async move {
+            let mut count = 0;
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let mut data = Vec::new();
+                let mut buf = [0u8; 4096];
+                let (head, body) = loop {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break (String::new(), String::new());
+                    }
+                    data.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&data).to_string();
+                    let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                        continue;
+                    };
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break (head.to_owned(), body.to_owned());
+                    }
+                };
+                recorded.lock().await.push((head, body));
+
+                let (status, body) = &responses[count.min(responses.len() - 1)];
+                count += 1;
+                let response = format!(
+                    "HTTP/1.1 {status} Test\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (url, requests)
+    }
+}
@@ -0,0 +1,782 @@
+//! 알림 싱크 -- 생성된 알림을 외부 시스템으로 전달합니다.
+//!
+//! 파이프라인이 생성한 [`AlertEvent`]는 downstream mpsc 채널로 전송되는 동시에
+//! 설정된 싱크들로 전달됩니다. 싱크에는 규칙 태그와 매칭된 필드를 담은 [`SinkAlert`]가 전달됩니다.
+//!
+//! # 구조
+//! - [`AlertSink`]: 싱크 구현 trait (예: [`WebhookSink`], [`ChatSink`], [`FileSink`])
+//! - [`AlertSinkSet`]: 설정된 싱크와 싱크별 라우팅 필터([`SinkFilter`])/큐 크기
+//! - [`SinkDispatcher`]: 실행 중 알림을 싱크별 큐에 넣는 핸들
+//!
+//! 싱크마다 별도 큐와 전송 태스크를 두어, 느리거나 장애가 난 싱크가
+//! 로그 처리 루프나 다른 싱크를 막지 않습니다. 큐가 가득 차면 새 알림을 버립니다.
+//!
+//! 알림을 모아 보내는 싱크(예: [`EmailSink`]의 다이제스트)는 `send`에서
+//! [`SinkDelivery::Deferred`]를 반환하고, 전송 태스크가 [`AlertSink::flush_interval`]마다
+//! [`AlertSink::flush`]를 호출합니다. 종료 시에도 한 번 flush합니다.
+//!
+//! `general.dry_run`에서는 디스패처가 모든 알림을 [`SinkAlert::simulated`]로 표시하고,
+//! 싱크는 페이로드에 이를 드러냅니다 (JSON 싱크는 `"simulated": true`,
+//! 채팅/메일 싱크는 제목의 [`SIMULATED_LABEL`]).
+
+pub mod chat;
+pub mod email;
+pub mod file;
+pub(crate) mod http;
+pub(crate) mod retry;
+pub mod webhook;
+
+pub use chat::{ChatPlatform, ChatSink};
+pub use email::EmailSink;
+pub use file::FileSink;
+pub use webhook::{PayloadTemplate, WebhookSink};
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio::time::{Instant, Interval, MissedTickBehavior};
+use tokio_util::sync::CancellationToken;
+
+use ironpost_core::config::{AlertSinkConfig, SmtpConfig};
+use ironpost_core::event::AlertEvent;
+use ironpost_core::metrics as m;
+use ironpost_core::pipeline::BoxFuture;
+use ironpost_core::types::Severity;
+
+use crate::enrich::ENRICHMENT_FIELD_SUFFIXES;
+use crate::error::LogPipelineError;
+use crate::rule::{RuleMatch, RuleMatcher};
+
+/// 싱크 기본 큐 크기
+pub const DEFAULT_SINK_QUEUE_SIZE: usize = 1000;
+
+/// dry-run 알림임을 표시하는 제목 접두어 (채팅, 메일 싱크)
+pub const SIMULATED_LABEL: &str = "[SIMULATED]";
+
+/// 종료 시 보류 알림 flush 제한 시간
+const FINAL_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 싱크로 전달되는 알림과 탐지 맥락
+#[derive(Debug, Clone)]
+pub struct SinkAlert {
+    /// 생성된 알림
+    pub event: AlertEvent,
+    /// 규칙 태그
+    pub tags: Vec<String>,
+    /// 규칙 조건이 참조한 로그 필드와 값 (조건 순서, 중복 제거),
+    /// 이어서 그 필드의 보강 필드 (`<필드>_country` 등)
+    pub fields: Vec<(String, String)>,
+    /// threshold 규칙인 경우 매칭 횟수
+    pub match_count: Option<u64>,
+    /// dry-run 모드에서 생성된 알림 여부 (싱크가 페이로드에 표시)
+    pub simulated: bool,
+}
+
+impl SinkAlert {
+    /// 탐지 맥락 없이 알림만으로 생성합니다.
+    pub fn new(event: AlertEvent) -> Self {
+        Self {
+            event,
+            tags: Vec::new(),
+            fields: Vec::new(),
+            match_count: None,
+            simulated: false,
+        }
+    }
+
+    /// 알림과 알림을 만든 규칙 매칭 결과로 생성합니다.
+    pub fn from_match(event: &AlertEvent, rule_match: &RuleMatch) -> Self {
+        let mut fields: Vec<(String, String)> = Vec::new();
+        for condition in rule_match.rule.detection.all_conditions() {
+            if fields.iter().any(|(name, _)| *name == condition.field) {
+                continue;
+            }
+            if let Some(value) = RuleMatcher::get_field_value(&rule_match.entry, &condition.field) {
+                fields.push((condition.field.clone(), value.to_owned()));
+            }
+        }
+        // 조건이 참조한 IP 필드의 보강 필드 (`<필드>_country` 등)도 함께 전달
+        let enriched: Vec<(String, String)> = rule_match
+            .entry
+            .fields
+            .iter()
+            .filter(|(name, _)| !fields.iter().any(|(field, _)| field == name))
+            .filter(|(name, _)| {
+                fields.iter().any(|(field, _)| {
+                    name.strip_prefix(field.as_str())
+                        .and_then(|rest| rest.strip_prefix('_'))
+                        .is_some_and(|suffix| ENRICHMENT_FIELD_SUFFIXES.contains(&suffix))
+                })
+            })
+            .cloned()
+            .collect();
+        fields.extend(enriched);
+
+        Self {
+            event: event.clone(),
+            tags: rule_match.rule.tags.clone(),
+            fields,
+            match_count: rule_match.match_count,
+            simulated: false,
+        }
+    }
+}
+
+/// 싱크별 라우팅 필터
+///
+/// `min_severity` 이상이고, `rule_tags`가 있으면 규칙 태그 중 하나가 일치하는 알림만 전달합니다.
+#[derive(Debug, Clone, Default)]
+pub struct SinkFilter {
+    /// 전달할 최소 심각도
+    pub min_severity: Severity,
+    /// 전달할 규칙 태그 (비어 있으면 모든 규칙, 대소문자 무시)
+    pub rule_tags: Vec<String>,
+}
+
+impl SinkFilter {
+    /// 심각도만 제한하는 필터를 생성합니다.
+    pub fn severity(min_severity: Severity) -> Self {
+        Self {
+            min_severity,
+            rule_tags: Vec::new(),
+        }
+    }
+
+    /// 알림이 필터를 통과하는지 확인합니다.
+    pub fn matches(&self, alert: &SinkAlert) -> bool {
+        alert.event.severity >= self.min_severity
+            && (self.rule_tags.is_empty()
+                || alert.tags.iter().any(|tag| {
+                    self.rule_tags
+                        .iter()
+                        .any(|wanted| wanted.eq_ignore_ascii_case(tag))
+                }))
+    }
+}
+
+/// 알림 전달 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SinkDelivery {
+    /// 바로 전송됨
+    Sent,
+    /// 보류됨 (다음 [`AlertSink::flush`]에서 전송)
+    Deferred,
+}
+
+/// 알림 싱크
+///
+/// `Vec<Arc<dyn AlertSink>>`으로 관리하기 위해 [`BoxFuture`]를 반환합니다.
+/// 재시도가 필요하면 구현체가 `send` 안에서 처리합니다.
+pub trait AlertSink: Send + Sync {
+    /// 싱크 이름 (로그/메트릭 라벨)
+    fn name(&self) -> &str;
+
+    /// 알림 하나를 전달합니다.
+    fn send<'a>(
+        &'a self,
+        alert: &'a SinkAlert,
+    ) -> BoxFuture<'a, Result<SinkDelivery, LogPipelineError>>;
+
+    /// 보류한 알림을 flush할 주기 (기본: 보류하지 않음)
+    fn flush_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// 보류한 알림을 전송합니다. 전송을 시도한 알림 수와 결과를 반환합니다.
+    fn flush(&self) -> BoxFuture<'_, (usize, Result<(), LogPipelineError>)> {
+        Box::pin(async { (0, Ok(())) })
+    }
+}
+
+/// 싱크와 출력에 전달하기 전에 알림을 거르는 게이트
+///
+/// 데몬은 중복 제거/폭주 제한 게이트를 연결해, 억제할 알림이 webhook, 메일,
+/// Elasticsearch 등에 도달하지 않게 합니다. 같은 알림이 downstream 채널에서
+/// 게이트를 다시 지나므로, 구현체는 알림 ID별 판정을 유지해야 합니다.
+pub trait AlertAdmission: Send + Sync {
+    /// 알림을 싱크와 출력에 전달할지 결정합니다.
+    fn admit(&self, alert: &AlertEvent) -> bool;
+}
+
+/// 설정된 싱크
+struct SinkEntry {
+    sink: Arc<dyn AlertSink>,
+    filter: SinkFilter,
+    queue_size: usize,
+}
+
+/// 설정된 알림 싱크 모음
+#[derive(Default)]
+pub struct AlertSinkSet {
+    sinks: Vec<SinkEntry>,
+    simulated: bool,
+}
+
+impl AlertSinkSet {
+    /// 빈 싱크 모음을 생성합니다.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// core 설정(`log_pipeline.sinks`)에서 싱크를 생성합니다.
+    ///
+    /// `smtp`는 `email` 싱크가 사용하는 SMTP 서버 설정입니다.
+    ///
+    /// # Errors
+    /// 싱크 생성 실패 (잘못된 템플릿, 헤더, 심각도, 메일 주소 등)
+    pub fn from_config(
+        configs: &[AlertSinkConfig],
+        smtp: &SmtpConfig,
+    ) -> Result<Self, LogPipelineError> {
+        let mut set = Self::new();
+        for config in configs {
+            let min_severity = Severity::from_str_loose(&config.min_severity).ok_or_else(|| {
+                LogPipelineError::Sink {
+                    name: config.name.clone(),
+                    reason: format!("invalid min_severity '{}'", config.min_severity),
+                }
+            })?;
+            let sink: Arc<dyn AlertSink> = match config.kind.as_str() {
+                "webhook" => Arc::new(WebhookSink::from_config(config)?),
+                "email" => Arc::new(EmailSink::from_config(config, smtp)?),
+                "file" => Arc::new(FileSink::from_config(config)?),
+                kind => match ChatPlatform::parse(kind) {
+                    Some(platform) => Arc::new(ChatSink::from_config(config, platform)?),
+                    None => {
+                        return Err(LogPipelineError::Sink {
+                            name: config.name.clone(),
+                            reason: format!("unsupported sink type '{kind}'"),
+                        });
+                    }
+                },
+            };
+            let filter = SinkFilter {
+                min_severity,
+                rule_tags: config.rule_tags.clone(),
+            };
+            set.add(sink, filter, config.queue_size);
+        }
+        Ok(set)
+    }
+
+    /// 싱크를 추가합니다. `filter`를 통과하지 못한 알림은 전달하지 않습니다.
+    pub fn add(&mut self, sink: Arc<dyn AlertSink>, filter: SinkFilter, queue_size: usize) {
+        self.sinks.push(SinkEntry {
+            sink,
+            filter,
+            queue_size: queue_size.max(1),
+        });
+    }
+
+    /// 디스패처가 모든 알림을 시뮬레이션으로 표시할지 설정합니다 (`general.dry_run`).
+    pub fn set_simulated(&mut self, simulated: bool) {
+        self.simulated = simulated;
+    }
+
+    /// 싱크 수를 반환합니다.
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// 싱크가 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// 싱크 이름 목록을 반환합니다.
+    pub fn names(&self) -> Vec<&str> {
+        self.sinks.iter().map(|entry| entry.sink.name()).collect()
+    }
+
+    /// 싱크별 전송 태스크를 spawn하고 알림을 넣을 디스패처를 반환합니다.
+    ///
+    /// 디스패처(와 복제본)가 모두 drop되면 태스크는 큐에 남은 알림을 전달하고 종료합니다.
+    /// `cancel`이 취소되면 진행 중인 전송을 중단하고 보류한 알림만 flush한 뒤, 큐에 남은 알림은 버립니다.
+    pub fn spawn(
+        &self,
+        cancel: CancellationToken,
+    ) -> (SinkDispatcher, Vec<tokio::task::JoinHandle<()>>) {
+        let mut queues = Vec::with_capacity(self.sinks.len());
+        let mut handles = Vec::with_capacity(self.sinks.len());
+
+        for entry in &self.sinks {
+            let (tx, rx) = mpsc::channel(entry.queue_size);
+            let name: Arc<str> = Arc::from(entry.sink.name());
+            handles.push(tokio::spawn(run_sink(
+                Arc::clone(&entry.sink),
+                rx,
+                cancel.clone(),
+            )));
+            queues.push(SinkQueue {
+                name,
+                filter: entry.filter.clone(),
+                tx,
+            });
+        }
+
+        (
+            SinkDispatcher {
+                queues: Arc::from(queues),
+                simulated: self.simulated,
+            },
+            handles,
+        )
+    }
+}
+
+/// 싱크 하나의 큐
+struct SinkQueue {
+    name: Arc<str>,
+    filter: SinkFilter,
+    tx: mpsc::Sender<SinkAlert>,
+}
+
+/// 실행 중인 싱크 큐에 알림을 넣는 핸들
+///
+/// 복제 비용이 작아 처리 태스크마다 복제해 사용합니다.
+/// 기본값은 싱크가 없는 디스패처입니다.
+#[derive(Clone, Default)]
+pub struct SinkDispatcher {
+    queues: Arc<[SinkQueue]>,
+    simulated: bool,
+}
+
+impl SinkDispatcher {
+    /// 규칙 매칭으로 생성된 알림을 싱크에 넣습니다.
+    ///
+    /// 싱크가 없으면 탐지 맥락을 만들지 않고 바로 반환합니다.
+    pub fn dispatch_match(&self, alert: &AlertEvent, rule_match: &RuleMatch) {
+        if self.queues.is_empty() {
+            return;
+        }
+        self.dispatch(&SinkAlert::from_match(alert, rule_match));
+    }
+
+    /// 라우팅 필터를 통과한 싱크의 큐에 알림을 넣습니다.
+    ///
+    /// 블로킹하지 않으며, 큐가 가득 찬 싱크에는 알림을 버립니다.
+    /// dry-run 디스패처는 알림을 시뮬레이션으로 표시해 넣습니다.
+    pub fn dispatch(&self, alert: &SinkAlert) {
+        let marked;
+        let alert = if self.simulated && !alert.simulated {
+            marked = SinkAlert {
+                simulated: true,
+                ..alert.clone()
+            };
+            &marked
+        } else {
+            alert
+        };
+        for queue in self.queues.iter() {
+            if !queue.filter.matches(alert) {
+                continue;
+            }
+            match queue.tx.try_send(alert.clone()) {
+                Ok(()) => {}
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    tracing::warn!(
+                        sink = %queue.name,
+                        alert_id = %alert.event.id,
+                        "alert sink queue full, dropping alert"
+                    );
+                    metrics::counter!(
+                        m::LOG_PIPELINE_SINK_ALERTS_TOTAL,
+                        m::LABEL_SINK => queue.name.to_string(),
+                        m::LABEL_RESULT => "dropped"
+                    )
+                    .increment(1);
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => {
+                    tracing::debug!(sink = %queue.name, "alert sink stopped, skipping alert");
+                }
+            }
+        }
+    }
+
+    /// 연결된 싱크 수를 반환합니다.
+    pub fn len(&self) -> usize {
+        self.queues.len()
+    }
+
+    /// 연결된 싱크가 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.queues.is_empty()
+    }
+}
+
+/// dry-run 알림이면 제목 앞에 붙일 [`SIMULATED_LABEL`] (뒤 공백 포함), 아니면 빈 문자열
+pub(crate) fn simulated_label(simulated: bool) -> String {
+    if simulated {
+        format!("{SIMULATED_LABEL} ")
+    } else {
+        String::new()
+    }
+}
+
+/// 싱크 전송 루프: 큐에서 알림을 꺼내 순서대로 전달하고, 주기적으로 보류 알림을 flush합니다.
+async fn run_sink(
+    sink: Arc<dyn AlertSink>,
+    mut rx: mpsc::Receiver<SinkAlert>,
+    cancel: CancellationToken,
+) {
+    let name = sink.name().to_owned();
+    let mut flush_timer = sink.flush_interval().map(|period| {
+        let mut timer = tokio::time::interval_at(Instant::now() + period, period);
+        timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        timer
+    });
+
+    loop {
+        let alert = tokio::select! {
+            _ = cancel.cancelled() => break,
+            _ = next_flush(&mut flush_timer) => {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = flush_sink(sink.as_ref(), &name) => continue,
+                }
+            }
+            alert = rx.recv() => match alert {
+                Some(alert) => alert,
+                None => break,
+            },
+        };
+
+        let result = tokio::select! {
+            _ = cancel.cancelled() => break,
+            result = sink.send(&alert) => result,
+        };
+        let outcome = match result {
+            Ok(SinkDelivery::Sent) => "sent",
+            Ok(SinkDelivery::Deferred) => "deferred",
+            Err(e) => {
+                tracing::warn!(sink = %name, alert_id = %alert.event.id, error = %e, "failed to deliver alert");
+                "failed"
+            }
+        };
+        record_outcome(&name, outcome, 1);
+    }
+
+    if flush_timer.is_some()
+        && tokio::time::timeout(FINAL_FLUSH_TIMEOUT, flush_sink(sink.as_ref(), &name))
+            .await
+            .is_err()
+    {
+        tracing::warn!(sink = %name, "timed out flushing deferred alerts on shutdown");
+    }
+
+    if !rx.is_empty() {
+        tracing::warn!(
+            sink = %name,
+            pending = rx.len(),
+            "alert sink stopped with undelivered alerts"
+        );
+    }
+}
+
+/// 다음 flush 시점까지 대기합니다. flush 주기가 없으면 영원히 대기합니다.
+async fn next_flush(timer: &mut Option<Interval>) {
+    match timer {
+        Some(timer) => {
+            timer.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// 보류한 알림을 flush하고 결과를 메트릭에 기록합니다.
+async fn flush_sink(sink: &dyn AlertSink, name: &str) {
+    let (count, result) = sink.flush().await;
+    if count == 0 {
+        return;
+    }
+    let outcome = match result {
+        Ok(()) => "sent",
+        Err(e) => {
+            tracing::warn!(sink = %name, alerts = count, error = %e, "failed to flush deferred alerts");
+            "failed"
+        }
+    };
+    record_outcome(name, outcome, count as u64);
+}
+
+/// 싱크 전달 결과 메트릭을 기록합니다.
+fn record_outcome(name: &str, outcome: &'static str, count: u64) {
+    metrics::counter!(
+        m::LOG_PIPELINE_SINK_ALERTS_TOTAL,
+        m::LABEL_SINK => name.to_owned(),
+        m::LABEL_RESULT => outcome
+    )
+    .increment(count);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use tokio::sync::Mutex;
+
+    use ironpost_core::types::Alert;
+
+    use super::*;
+
+    /// 받은 알림 ID를 기록하는 테스트 싱크
+    struct RecordingSink {
+        name: String,
+        received: Arc<Mutex<Vec<String>>>,
+        delay: Duration,
+    }
+
+    impl AlertSink for RecordingSink {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn send<'a>(
+            &'a self,
+            alert: &'a SinkAlert,
+        ) -> BoxFuture<'a, Result<SinkDelivery, LogPipelineError>> {
+            Box::pin(async move {
+                tokio::time::sleep(self.delay).await;
+                self.received
+                    .lock()
+                    .await
+                    .push(alert.event.alert.title.clone());
+                Ok(SinkDelivery::Sent)
+            })
+        }
+    }
+
+    fn recording(name: &str, delay: Duration) -> (Arc<dyn AlertSink>, Arc<Mutex<Vec<String>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::new(RecordingSink {
+            name: name.to_owned(),
+            received: Arc::clone(&received),
+            delay,
+        });
+        (sink, received)
+    }
+
+    fn alert(title: &str, severity: Severity) -> SinkAlert {
+        SinkAlert::new(AlertEvent::new(
+            Alert {
+                id: title.to_owned(),
+                title: title.to_owned(),
+                description: String::new(),
+                severity,
+                rule_name: "test_rule".to_owned(),
+                source_ip: None,
+                target_ip: None,
+                created_at: SystemTime::now(),
+            },
+            severity,
+        ))
+    }
+
+    #[tokio::test]
+    async fn dispatch_applies_per_sink_severity_filter() {
+        let (all, all_received) = recording("all", Duration::ZERO);
+        let (high, high_received) = recording("high", Duration::ZERO);
+        let mut set = AlertSinkSet::new();
+        set.add(all, SinkFilter::severity(Severity::Info), 10);
+        set.add(high, SinkFilter::severity(Severity::High), 10);
+        assert_eq!(set.names(), ["all", "high"]);
+
+        let cancel = CancellationToken::new();
+        let (dispatcher, handles) = set.spawn(cancel.clone());
+        dispatcher.dispatch(&alert("low", Severity::Low));
+        dispatcher.dispatch(&alert("critical", Severity::Critical));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(*all_received.lock().await, ["low", "critical"]);
+        assert_eq!(*high_received.lock().await, ["critical"]);
+
+        cancel.cancel();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        // 종료된 싱크로의 전달은 조용히 무시
+        dispatcher.dispatch(&alert("late", Severity::Critical));
+    }
+
+    #[tokio::test]
+    async fn simulated_dispatcher_marks_every_alert() {
+        struct SimulatedFlags(Arc<Mutex<Vec<bool>>>);
+
+        impl AlertSink for SimulatedFlags {
+            fn name(&self) -> &str {
+                "flags"
+            }
+
+            fn send<'a>(
+                &'a self,
+                alert: &'a SinkAlert,
+            ) -> BoxFuture<'a, Result<SinkDelivery, LogPipelineError>> {
+                Box::pin(async move {
+                    self.0.lock().await.push(alert.simulated);
+                    Ok(SinkDelivery::Sent)
+                })
+            }
+        }
+
+        let flags = Arc::new(Mutex::new(Vec::new()));
+        let mut set = AlertSinkSet::new();
+        set.add(
+            Arc::new(SimulatedFlags(Arc::clone(&flags))),
+            SinkFilter::severity(Severity::Info),
+            10,
+        );
+        set.set_simulated(true);
+
+        let (dispatcher, handles) = set.spawn(CancellationToken::new());
+        dispatcher.dispatch(&alert("a", Severity::High));
+        dispatcher.dispatch(&alert("b", Severity::Low));
+        drop(dispatcher);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(*flags.lock().await, vec![true, true]);
+    }
+
+    #[tokio::test]
+    async fn full_queue_drops_without_blocking() {
+        let (slow, received) = recording("slow", Duration::from_millis(100));
+        let mut set = AlertSinkSet::new();
+        set.add(slow, SinkFilter::default(), 1);
+
+        let cancel = CancellationToken::new();
+        let (dispatcher, _handles) = set.spawn(cancel.clone());
+        for i in 0..10 {
+            dispatcher.dispatch(&alert(&format!("a{i}"), Severity::High));
+        }
+
+        tokio::time::sleep(Duration::from_millis(350)).await;
+        let received = received.lock().await.clone();
+        assert!(received.len() < 10, "queue should have dropped alerts");
+        assert_eq!(received[0], "a0");
+        cancel.cancel();
+    }
+
+    #[test]
+    fn filter_routes_by_rule_tags() {
+        let filter = SinkFilter {
+            min_severity: Severity::Medium,
+            rule_tags: vec!["Auth".to_owned()],
+        };
+        let mut tagged = alert("tagged", Severity::High);
+        tagged.tags = vec!["network".to_owned(), "auth".to_owned()];
+        assert!(filter.matches(&tagged));
+
+        let untagged = alert("untagged", Severity::High);
+        assert!(!filter.matches(&untagged));
+
+        tagged.event.severity = Severity::Low;
+        assert!(!filter.matches(&tagged));
+        assert!(SinkFilter::default().matches(&untagged));
+    }
+
+    #[test]
+    fn from_match_collects_condition_fields() {
+        let rule: crate::rule::DetectionRule = serde_yaml::from_str(
+            "id: ssh\ntitle: SSH\nseverity: High\ntags: [auth]\ndetection:\n  conditions:\n    - field: process\n      value: sshd\n    - field: user\n      value: root\n    - field: process\n      modifier: contains\n      value: ssh\n    - field: missing\n      value: x\n",
+        )
+        .unwrap();
+        let rule_match = RuleMatch {
+            rule,
+            entry: ironpost_core::types::LogEntry {
+                source: "test".to_owned(),
+                timestamp: SystemTime::now(),
+                hostname: "web-01".to_owned(),
+                process: "sshd".to_owned(),
+                message: "Failed password".to_owned(),
+                severity: Severity::Info,
+                fields: vec![("user".to_owned(), "root".to_owned())],
+            },
+            matched_at: SystemTime::now(),
+            match_count: Some(5),
+        };
+
+        let sink_alert = SinkAlert::from_match(&alert("ssh", Severity::High).event, &rule_match);
+        assert_eq!(sink_alert.tags, ["auth"]);
+        assert_eq!(
+            sink_alert.fields,
+            [
+                ("process".to_owned(), "sshd".to_owned()),
+                ("user".to_owned(), "root".to_owned())
+            ]
+        );
+        assert_eq!(sink_alert.match_count, Some(5));
+    }
+
+    #[test]
+    fn from_match_includes_enrichment_of_condition_fields() {
+        let rule: crate::rule::DetectionRule = serde_yaml::from_str(
+            "id: geo\ntitle: Geo\nseverity: High\ndetection:\n  conditions:\n    - field: src_ip_country\n      value: KP\n    - field: src_ip\n      modifier: cidr\n      value: 175.45.176.0/22\n",
+        )
+        .unwrap();
+        let field = |k: &str, v: &str| (k.to_owned(), v.to_owned());
+        let rule_match = RuleMatch {
+            rule,
+            entry: ironpost_core::types::LogEntry {
+                source: "test".to_owned(),
+                timestamp: SystemTime::now(),
+                hostname: "web-01".to_owned(),
+                process: "sshd".to_owned(),
+                message: "Accepted password".to_owned(),
+                severity: Severity::Info,
+                fields: vec![
+                    field("src_ip", "175.45.176.1"),
+                    field("dst_ip", "10.0.0.5"),
+                    field("src_ip_country", "KP"),
+                    field("src_ip_asn", "131279"),
+                    field("dst_ip_hostname", "db.internal"),
+                    field("src_ip_note", "x"),
+                ],
+            },
+            matched_at: SystemTime::now(),
+            match_count: None,
+        };
+
+        let sink_alert = SinkAlert::from_match(&alert("geo", Severity::High).event, &rule_match);
+        assert_eq!(
+            sink_alert.fields,
+            [
+                field("src_ip_country", "KP"),
+                field("src_ip", "175.45.176.1"),
+                field("src_ip_asn", "131279"),
+            ]
+        );
+    }
+
+    #[test]
+    fn from_config_builds_sinks_by_type() {
+        let config = AlertSinkConfig {
+            name: "hook".to_owned(),
+            url: "http://127.0.0.1:9/alerts".to_owned(),
+            min_severity: "high".to_owned(),
+            ..Default::default()
+        };
+        let slack = AlertSinkConfig {
+            name: "chat".to_owned(),
+            kind: "slack".to_owned(),
+            ..config.clone()
+        };
+        let file = AlertSinkConfig {
+            name: "archive".to_owned(),
+            kind: "file".to_owned(),
+            path: "/var/lib/ironpost/alerts.jsonl".to_owned(),
+            ..config.clone()
+        };
+        let smtp = SmtpConfig::default();
+        let set = AlertSinkSet::from_config(&[config.clone(), slack, file], &smtp).unwrap();
+        assert_eq!(set.names(), ["hook", "chat", "archive"]);
+
+        let bad = AlertSinkConfig {
+            kind: "sms".to_owned(),
+            ..config
+        };
+        assert!(AlertSinkSet::from_config(&[bad], &smtp).is_err());
+        assert!(SinkDispatcher::default().is_empty());
+    }
+}
@@ -0,0 +1,69 @@
+//! 싱크 전송 재시도
+//!
+//! 재시도 가능한 실패는 `retry_backoff_ms`부터 2배씩 늘려가며(최대 30초)
+//! 최대 `max_retries`번 다시 시도합니다.
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::LogPipelineError;
+
+/// 재시도 대기 시간 상한
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// 전송 시도 실패: (재시도 가능 여부, 사유)
+pub(crate) type AttemptError = (bool, String);
+
+/// 재시도 정책
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetryPolicy {
+    /// 최대 재시도 횟수 (첫 시도 제외)
+    pub max_retries: u32,
+    /// 첫 재시도 대기 시간
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// `attempt`를 성공하거나 재시도할 수 없을 때까지 실행합니다.
+    pub(crate) async fn run<F, Fut>(
+        self,
+        sink: &str,
+        mut attempt: F,
+    ) -> Result<(), LogPipelineError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<(), AttemptError>>,
+    {
+        let mut attempts: u32 = 0;
+        loop {
+            attempts += 1;
+            let (retryable, reason) = match attempt().await {
+                Ok(()) => return Ok(()),
+                Err(failure) => failure,
+            };
+            if !retryable || attempts > self.max_retries {
+                return Err(LogPipelineError::Sink {
+                    name: sink.to_owned(),
+                    reason: format!("{reason} (after {attempts} attempt(s))"),
+                });
+            }
+
+            let backoff = self.delay(attempts);
+            tracing::debug!(
+                sink = %sink,
+                attempt = attempts,
+                backoff_ms = backoff.as_millis() as u64,
+                reason = %reason,
+                "alert delivery failed, retrying"
+            );
+            tokio::time::sleep(backoff).await;
+        }
+    }
+
+    /// `attempts`번째 시도가 실패한 뒤 기다릴 시간
+    pub(crate) fn delay(self, attempts: u32) -> Duration {
+        self.backoff
+            .saturating_mul(1 << attempts.saturating_sub(1).min(16))
+            .min(MAX_RETRY_BACKOFF)
+    }
+}
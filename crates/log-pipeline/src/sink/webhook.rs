@@ -0,0 +1,396 @@
+//! HTTP 웹훅 싱크 -- 알림을 JSON으로 HTTP 엔드포인트에 전송합니다.
+//!
+//! 페이로드는 기본 JSON 형식이거나, `{{field}}` 자리표시자를 쓰는 템플릿입니다.
+//! 자리표시자 값은 JSON 문자열로 이스케이프되므로 템플릿의 문자열 안에 씁니다.
+//!
+//! ```json
+//! {"text": "[{{severity}}] {{title}} from {{source_ip}}"}
+//! ```
+//!
+//! dry-run 알림은 기본 형식과, JSON 객체로 렌더링되는 템플릿 모두에
+//! `"simulated": true`가 추가됩니다.
+//!
+//! 전송과 재시도는 [`HttpDelivery`](super::http)가 담당합니다.
+
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use ironpost_core::config::AlertSinkConfig;
+use ironpost_core::event::AlertEvent;
+use ironpost_core::pipeline::BoxFuture;
+use ironpost_core::types::{Alert, Severity};
+
+use super::http::HttpDelivery;
+use super::{AlertSink, SinkAlert, SinkDelivery};
+use crate::error::LogPipelineError;
+
+/// 템플릿 최대 길이
+const MAX_TEMPLATE_LENGTH: usize = 64 * 1024;
+
+/// 템플릿 자리표시자
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TemplateField {
+    Id,
+    Title,
+    Description,
+    Severity,
+    RuleName,
+    SourceIp,
+    TargetIp,
+    CreatedAt,
+    SourceModule,
+    TraceId,
+}
+
+impl TemplateField {
+    const ALL: &[(&str, Self)] = &[
+        ("id", Self::Id),
+        ("title", Self::Title),
+        ("description", Self::Description),
+        ("severity", Self::Severity),
+        ("rule_name", Self::RuleName),
+        ("source_ip", Self::SourceIp),
+        ("target_ip", Self::TargetIp),
+        ("created_at", Self::CreatedAt),
+        ("source_module", Self::SourceModule),
+        ("trace_id", Self::TraceId),
+    ];
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, field)| *field)
+    }
+
+    fn value(self, event: &AlertEvent) -> String {
+        let alert = &event.alert;
+        match self {
+            Self::Id => alert.id.clone(),
+            Self::Title => alert.title.clone(),
+            Self::Description => alert.description.clone(),
+            Self::Severity => event.severity.to_string(),
+            Self::RuleName => alert.rule_name.clone(),
+            Self::SourceIp => alert.source_ip.map(|ip| ip.to_string()).unwrap_or_default(),
+            Self::TargetIp => alert.target_ip.map(|ip| ip.to_string()).unwrap_or_default(),
+            Self::CreatedAt => format_time(alert.created_at),
+            Self::SourceModule => event.metadata.source_module.clone(),
+            Self::TraceId => event.metadata.trace_id.clone(),
+        }
+    }
+}
+
+/// 템플릿 구성 요소
+#[derive(Debug, Clone)]
+enum Segment {
+    Literal(String),
+    Field(TemplateField),
+}
+
+/// JSON 페이로드 템플릿
+///
+/// 생성 시 자리표시자를 검증하고, 예시 알림으로 렌더링한 결과가 유효한 JSON인지 확인합니다.
+#[derive(Debug, Clone)]
+pub struct PayloadTemplate {
+    segments: Vec<Segment>,
+}
+
+impl PayloadTemplate {
+    /// 템플릿을 파싱합니다.
+    ///
+    /// # Errors
+    /// 닫히지 않은 `{{`, 알 수 없는 자리표시자, 렌더링 결과가 JSON이 아닌 경우
+    pub fn parse(template: &str) -> Result<Self, String> {
+        if template.len() > MAX_TEMPLATE_LENGTH {
+            return Err(format!(
+                "template too long: {} bytes (max: {MAX_TEMPLATE_LENGTH})",
+                template.len()
+            ));
+        }
+
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_owned()));
+            }
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| "unclosed '{{' in template".to_owned())?;
+            let name = after[..end].trim();
+            let field = TemplateField::parse(name).ok_or_else(|| {
+                let known: Vec<&str> = TemplateField::ALL.iter().map(|(name, _)| *name).collect();
+                format!(
+                    "unknown placeholder '{{{{{name}}}}}', expected one of: {}",
+                    known.join(", ")
+                )
+            })?;
+            segments.push(Segment::Field(field));
+            rest = &after[end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_owned()));
+        }
+
+        let template = Self { segments };
+        serde_json::from_str::<serde_json::Value>(&template.render(&sample_alert()))
+            .map_err(|e| format!("template does not render to valid JSON: {e}"))?;
+        Ok(template)
+    }
+
+    /// 알림으로 템플릿을 렌더링합니다.
+    pub fn render(&self, alert: &AlertEvent) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Field(field) => push_json_escaped(&mut out, &field.value(alert)),
+            }
+        }
+        out
+    }
+}
+
+/// 템플릿이 없을 때의 기본 페이로드
+pub fn default_payload(event: &AlertEvent) -> serde_json::Value {
+    let alert = &event.alert;
+    serde_json::json!({
+        "id": alert.id,
+        "title": alert.title,
+        "description": alert.description,
+        "severity": event.severity,
+        "rule_name": alert.rule_name,
+        "source_ip": alert.source_ip,
+        "target_ip": alert.target_ip,
+        "created_at": format_time(alert.created_at),
+        "source_module": event.metadata.source_module,
+        "trace_id": event.metadata.trace_id,
+    })
+}
+
+/// HTTP 웹훅 싱크
+pub struct WebhookSink {
+    http: HttpDelivery,
+    template: Option<PayloadTemplate>,
+}
+
+impl WebhookSink {
+    /// 싱크 설정에서 웹훅 싱크를 생성합니다.
+    ///
+    /// # Errors
+    /// 잘못된 메서드, 헤더, 템플릿 또는 HTTP 클라이언트 생성 실패
+    pub fn from_config(config: &AlertSinkConfig) -> Result<Self, LogPipelineError> {
+        let template = if config.template.trim().is_empty() {
+            None
+        } else {
+            Some(PayloadTemplate::parse(&config.template).map_err(|reason| {
+                LogPipelineError::Sink {
+                    name: config.name.clone(),
+                    reason,
+                }
+            })?)
+        };
+
+        Ok(Self {
+            http: HttpDelivery::from_config(config)?,
+            template,
+        })
+    }
+
+    /// 알림의 요청 본문을 만듭니다.
+    pub fn render(&self, alert: &SinkAlert) -> String {
+        let body = match &self.template {
+            Some(template) => template.render(&alert.event),
+            None => default_payload(&alert.event).to_string(),
+        };
+        if !alert.simulated {
+            return body;
+        }
+        match serde_json::from_str::<serde_json::Value>(&body) {
+            Ok(serde_json::Value::Object(mut object)) => {
+                object.insert("simulated".to_owned(), true.into());
+                serde_json::Value::Object(object).to_string()
+            }
+            _ => body,
+        }
+    }
+}
+
+impl AlertSink for WebhookSink {
+    fn name(&self) -> &str {
+        self.http.name()
+    }
+
+    fn send<'a>(
+        &'a self,
+        alert: &'a SinkAlert,
+    ) -> BoxFuture<'a, Result<SinkDelivery, LogPipelineError>> {
+        Box::pin(async move {
+            self.http.deliver(self.render(alert)).await?;
+            Ok(SinkDelivery::Sent)
+        })
+    }
+}
+
+/// 값을 JSON 문자열 내용으로 이스케이프하여 덧붙입니다 (따옴표 제외).
+fn push_json_escaped(out: &mut String, value: &str) {
+    let quoted = serde_json::Value::from(value).to_string();
+    out.push_str(&quoted[1..quoted.len() - 1]);
+}
+
+/// RFC 3339 UTC 시각 문자열
+pub(crate) fn format_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// 템플릿 검증용 예시 알림 (이스케이프가 필요한 문자 포함)
+fn sample_alert() -> AlertEvent {
+    AlertEvent::new(
+        Alert {
+            id: "sample-id".to_owned(),
+            title: "Sample \"alert\"\n\\ title".to_owned(),
+            description: "line1\nline2\t\"quoted\"".to_owned(),
+            severity: Severity::High,
+            rule_name: "sample_rule".to_owned(),
+            source_ip: Some([192, 0, 2, 1].into()),
+            target_ip: None,
+            created_at: SystemTime::UNIX_EPOCH,
+        },
+        Severity::High,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::super::http::test_server::serve;
+    use super::*;
+
+    fn sink_config(url: &str) -> AlertSinkConfig {
+        AlertSinkConfig {
+            name: "test".to_owned(),
+            url: url.to_owned(),
+            max_retries: 2,
+            retry_backoff_ms: 1,
+            timeout_secs: 5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn template_renders_escaped_fields() {
+        let template = PayloadTemplate::parse(
+            r#"{"text": "[{{severity}}] {{ title }}", "ip": "{{source_ip}}"}"#,
+        )
+        .unwrap();
+        let rendered = template.render(&sample_alert());
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(value["text"], "[High] Sample \"alert\"\n\\ title");
+        assert_eq!(value["ip"], "192.0.2.1");
+    }
+
+    #[test]
+    fn template_rejects_invalid_input() {
+        for (template, expected) in [
+            (r#"{"text": "{{title"}"#, "unclosed"),
+            (
+                r#"{"text": "{{hostname}}"}"#,
+                "unknown placeholder '{{hostname}}'",
+            ),
+            (r#"{"count": {{title}}}"#, "valid JSON"),
+            ("not json", "valid JSON"),
+        ] {
+            let err = PayloadTemplate::parse(template).unwrap_err();
+            assert!(err.contains(expected), "{template}: {err}");
+        }
+    }
+
+    #[test]
+    fn default_payload_contains_alert_fields() {
+        let payload = default_payload(&sample_alert());
+        assert_eq!(payload["rule_name"], "sample_rule");
+        assert_eq!(payload["severity"], "High");
+        assert_eq!(payload["source_ip"], "192.0.2.1");
+        assert!(payload["target_ip"].is_null());
+        assert_eq!(payload["created_at"], "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn simulated_alerts_are_marked_in_payload() {
+        let mut alert = SinkAlert::new(sample_alert());
+        alert.simulated = true;
+
+        let sink = WebhookSink::from_config(&sink_config("http://127.0.0.1:9/")).unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&sink.render(&alert)).unwrap();
+        assert_eq!(payload["simulated"], true);
+        assert_eq!(payload["rule_name"], "sample_rule");
+
+        let mut config = sink_config("http://127.0.0.1:9/");
+        config.template = r#"{"text": "{{title}}"}"#.to_owned();
+        let sink = WebhookSink::from_config(&config).unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&sink.render(&alert)).unwrap();
+        assert_eq!(payload["simulated"], true);
+
+        alert.simulated = false;
+        let payload: serde_json::Value = serde_json::from_str(&sink.render(&alert)).unwrap();
+        assert!(payload.get("simulated").is_none());
+    }
+
+    #[test]
+    fn from_config_rejects_bad_headers() {
+        let mut config = sink_config("http://127.0.0.1:9/");
+        config.headers = BTreeMap::from([("bad header".to_owned(), "x".to_owned())]);
+        assert!(WebhookSink::from_config(&config).is_err());
+
+        config.headers = BTreeMap::from([("X-Token".to_owned(), "line\nbreak".to_owned())]);
+        assert!(WebhookSink::from_config(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_then_succeeds() {
+        let (url, requests) = serve(vec![503, 500, 200]).await;
+        let mut config = sink_config(&url);
+        config.template = r#"{"text": "{{title}}"}"#.to_owned();
+        config.headers = BTreeMap::from([("X-Token".to_owned(), "secret".to_owned())]);
+        let sink = WebhookSink::from_config(&config).unwrap();
+
+        sink.send(&SinkAlert::new(sample_alert())).await.unwrap();
+
+        let requests = requests.lock().await;
+        assert_eq!(requests.len(), 3);
+        let (head, body) = &requests[2];
+        assert!(head.starts_with("POST /hook"));
+        assert!(head.to_ascii_lowercase().contains("x-token: secret"));
+        let value: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(value["text"], "Sample \"alert\"\n\\ title");
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let (url, requests) = serve(vec![500]).await;
+        let sink = WebhookSink::from_config(&sink_config(&url)).unwrap();
+
+        let err = sink
+            .send(&SinkAlert::new(sample_alert()))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("after 3 attempt(s)"), "{err}");
+        assert_eq!(requests.lock().await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (url, requests) = serve(vec![400]).await;
+        let sink = WebhookSink::from_config(&sink_config(&url)).unwrap();
+
+        let err = sink
+            .send(&SinkAlert::new(sample_alert()))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("400"), "{err}");
+        assert_eq!(requests.lock().await.len(), 1);
+    }
+}
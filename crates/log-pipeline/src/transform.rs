@@ -0,0 +1,498 @@
+//! 필드 변환 -- 이름 변경, 하위 필드 추출, 민감 정보 마스킹
+//!
+//! [`TransformChain`]은 `[[log_pipeline.transforms]]` 설정을 등록 순서대로 `LogEntry`에
+//! 적용합니다. 파이프라인은 파싱 직후, 보강과 규칙 매칭 전에 변환을 실행하므로
+//! 규칙, 알림, 출력(Elasticsearch/Kafka)은 모두 변환된 값만 봅니다.
+//!
+//! - `rename`: 필드 값을 다른 이름으로 옮깁니다.
+//! - `extract`: 정규식의 이름 있는 그룹마다 필드를 만듭니다.
+//! - `redact`: 일치하는 부분(또는 값 전체)을 고정 문자열로 바꿉니다.
+//! - `hash`: 일치하는 부분을 솔트를 붙인 SHA-256 16진수 값으로 바꿉니다.
+//!   같은 값은 같은 해시가 되므로 원문 없이도 상관 분석이 가능합니다.
+//!
+//! 내장 패턴 `credit_card`는 Luhn 검사를 통과한 13~19자리 숫자만, `email`은 이메일
+//! 주소를 대상으로 합니다.
+//!
+//! # 사용 예시
+//!
+//! ```ignore
+//! let transforms = TransformChain::from_config(&config.log_pipeline.transforms)?;
+//! transforms.apply(&mut entry);
+//! ```
+
+use regex::{Captures, Regex, RegexBuilder};
+use sha2::{Digest, Sha256};
+
+use ironpost_core::config::{TRANSFORM_ALL_FIELDS, TransformConfig};
+use ironpost_core::types::LogEntry;
+
+use crate::error::LogPipelineError;
+
+/// 변환 정규식 컴파일 크기 제한 (바이트)
+const TRANSFORM_REGEX_SIZE_LIMIT: usize = 1024 * 1024;
+
+/// `credit_card` 내장 패턴 (구분자로 공백/`-`를 허용하는 13~19자리 숫자)
+const CREDIT_CARD_PATTERN: &str = r"\b(?:\d[ -]?){12,18}\d\b";
+
+/// `email` 내장 패턴
+const EMAIL_PATTERN: &str = r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b";
+
+/// 설정에서 만든 필드 변환 목록
+#[derive(Debug, Clone, Default)]
+pub struct TransformChain {
+    steps: Vec<Transform>,
+}
+
+/// 단일 변환
+#[derive(Debug, Clone)]
+enum Transform {
+    /// `from`의 값을 `to`로 옮김
+    Rename { from: String, to: String },
+    /// `field`에 정규식을 적용해 이름 있는 그룹마다 필드 생성
+    Extract { field: String, regex: Regex },
+    /// `field`의 일치 부분을 가리거나 해시
+    Mask {
+        field: String,
+        matcher: Option<Regex>,
+        luhn: bool,
+        action: MaskAction,
+    },
+}
+
+/// 마스킹 방식
+#[derive(Debug, Clone)]
+enum MaskAction {
+    /// 고정 문자열로 대체
+    Redact(String),
+    /// 솔트를 붙인 SHA-256 16진수 값으로 대체
+    Hash(String),
+}
+
+impl MaskAction {
+    fn apply(&self, value: &str) -> String {
+        match self {
+            Self::Redact(replacement) => replacement.clone(),
+            Self::Hash(salt) => Sha256::new()
+                .chain_update(salt.as_bytes())
+                .chain_update(value.as_bytes())
+                .finalize()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        }
+    }
+}
+
+impl TransformChain {
+    /// 설정에서 변환 목록을 만듭니다.
+    ///
+    /// 설정 값이나 정규식이 잘못되었거나, `extract` 정규식에 이름 있는 그룹이 없으면
+    /// 에러를 반환합니다.
+    pub fn from_config(configs: &[TransformConfig]) -> Result<Self, LogPipelineError> {
+        let mut steps = Vec::with_capacity(configs.len());
+        for (i, config) in configs.iter().enumerate() {
+            let field = format!("transforms[{i}]");
+            config
+                .validate(&field)
+                .map_err(|e| LogPipelineError::Config {
+                    field: "transforms".to_owned(),
+                    reason: e.to_string(),
+                })?;
+            steps.push(Transform::from_config(config, &field)?);
+        }
+        Ok(Self { steps })
+    }
+
+    /// 변환 개수를 반환합니다.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// 변환이 없으면 `true`를 반환합니다.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// 로그 엔트리에 변환을 등록 순서대로 적용합니다.
+    ///
+    /// 대상 필드가 없거나 패턴이 일치하지 않는 변환은 건너뜁니다.
+    pub fn apply(&self, entry: &mut LogEntry) {
+        for step in &self.steps {
+            step.apply(entry);
+        }
+    }
+}
+
+impl Transform {
+    fn from_config(config: &TransformConfig, field: &str) -> Result<Self, LogPipelineError> {
+        let compile = |pattern: &str| {
+            RegexBuilder::new(pattern)
+                .size_limit(TRANSFORM_REGEX_SIZE_LIMIT)
+                .build()
+                .map_err(|e| LogPipelineError::Config {
+                    field: format!("{field}.pattern"),
+                    reason: e.to_string(),
+                })
+        };
+
+        match config.kind.as_str() {
+            "rename" => Ok(Self::Rename {
+                from: config.field.clone(),
+                to: config.to.clone(),
+            }),
+            "extract" => {
+                let regex = compile(&config.pattern)?;
+                if regex.capture_names().flatten().next().is_none() {
+                    return Err(LogPipelineError::Config {
+                        field: format!("{field}.pattern"),
+                        reason: "must contain at least one named group".to_owned(),
+                    });
+                }
+                Ok(Self::Extract {
+                    field: config.field.clone(),
+                    regex,
+                })
+            }
+            kind => {
+                let matcher = match config.preset.as_str() {
+                    "credit_card" => Some(compile(CREDIT_CARD_PATTERN)?),
+                    "email" => Some(compile(EMAIL_PATTERN)?),
+                    _ if config.pattern.is_empty() => None,
+                    _ => Some(compile(&config.pattern)?),
+                };
+                let action = if kind == "hash" {
+                    MaskAction::Hash(config.salt.clone())
+                } else {
+                    MaskAction::Redact(config.replacement.clone())
+                };
+                Ok(Self::Mask {
+                    field: config.field.clone(),
+                    matcher,
+                    luhn: config.preset == "credit_card",
+                    action,
+                })
+            }
+        }
+    }
+
+    fn apply(&self, entry: &mut LogEntry) {
+        match self {
+            Self::Rename { from, to } => {
+                if let Some(value) = take_field(entry, from) {
+                    set_field(entry, to, value);
+                }
+            }
+            Self::Extract { field, regex } => {
+                let Some(value) = field_mut(entry, field) else {
+                    return;
+                };
+                let Some(captures) = regex.captures(value) else {
+                    return;
+                };
+                let extracted: Vec<(String, String)> = regex
+                    .capture_names()
+                    .flatten()
+                    .filter_map(|name| {
+                        captures
+                            .name(name)
+                            .map(|m| (name.to_owned(), m.as_str().to_owned()))
+                    })
+                    .collect();
+                for (name, value) in extracted {
+                    set_field(entry, &name, value);
+                }
+            }
+            Self::Mask {
+                field,
+                matcher,
+                luhn,
+                action,
+            } => {
+                let mask = |value: &mut String| {
+                    if let Some(masked) = mask_value(value, matcher.as_ref(), *luhn, action) {
+                        *value = masked;
+                    }
+                };
+                if field == TRANSFORM_ALL_FIELDS {
+                    mask(&mut entry.message);
+                    for (_, value) in &mut entry.fields {
+                        mask(value);
+                    }
+                } else if let Some(value) = field_mut(entry, field) {
+                    mask(value);
+                }
+            }
+        }
+    }
+}
+
+/// 값을 마스킹합니다. 바뀐 부분이 없으면 `None`을 반환합니다.
+fn mask_value(
+    value: &str,
+    matcher: Option<&Regex>,
+    luhn: bool,
+    action: &MaskAction,
+) -> Option<String> {
+    let Some(regex) = matcher else {
+        return (!value.is_empty()).then(|| action.apply(value));
+    };
+    let mut changed = false;
+    let masked = regex.replace_all(value, |captures: &Captures<'_>| {
+        let matched = &captures[0];
+        if luhn && !luhn_valid(matched) {
+            matched.to_owned()
+        } else {
+            changed = true;
+            action.apply(matched)
+        }
+    });
+    changed.then(|| masked.into_owned())
+}
+
+/// Luhn 체크섬을 검사합니다 (숫자 외 문자는 무시).
+fn luhn_valid(candidate: &str) -> bool {
+    let mut sum = 0;
+    for (i, digit) in candidate
+        .bytes()
+        .rev()
+        .filter(u8::is_ascii_digit)
+        .map(|b| u32::from(b - b'0'))
+        .enumerate()
+    {
+        sum += if i % 2 == 1 {
+            let doubled = digit * 2;
+            if doubled > 9 { doubled - 9 } else { doubled }
+        } else {
+            digit
+        };
+    }
+    sum % 10 == 0
+}
+
+/// 필드 값에 대한 가변 참조를 반환합니다.
+fn field_mut<'a>(entry: &'a mut LogEntry, name: &str) -> Option<&'a mut String> {
+    match name {
+        "hostname" => Some(&mut entry.hostname),
+        "process" => Some(&mut entry.process),
+        "message" => Some(&mut entry.message),
+        "source" => Some(&mut entry.source),
+        _ => entry
+            .fields
+            .iter_mut()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v),
+    }
+}
+
+/// 필드 값을 꺼냅니다. `LogEntry`의 고정 필드는 빈 문자열로 남고, 추가 필드는 삭제됩니다.
+fn take_field(entry: &mut LogEntry, name: &str) -> Option<String> {
+    match name {
+        "hostname" | "process" | "message" | "source" => field_mut(entry, name).map(std::mem::take),
+        _ => {
+            let index = entry.fields.iter().position(|(k, _)| k == name)?;
+            Some(entry.fields.remove(index).1)
+        }
+    }
+}
+
+/// 필드 값을 설정합니다. 없는 추가 필드는 새로 만듭니다.
+fn set_field(entry: &mut LogEntry, name: &str, value: String) {
+    if let Some(slot) = field_mut(entry, name) {
+        *slot = value;
+    } else {
+        entry.fields.push((name.to_owned(), value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_core::types::Severity;
+    use std::time::SystemTime;
+
+    fn entry(message: &str, fields: &[(&str, &str)]) -> LogEntry {
+        LogEntry {
+            source: "syslog".to_owned(),
+            timestamp: SystemTime::now(),
+            hostname: "web-01".to_owned(),
+            process: "app".to_owned(),
+            message: message.to_owned(),
+            severity: Severity::Info,
+            fields: fields
+                .iter()
+                .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+                .collect(),
+        }
+    }
+
+    fn transform(kind: &str, field: &str) -> TransformConfig {
+        TransformConfig {
+            kind: kind.to_owned(),
+            field: field.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    fn field<'a>(entry: &'a LogEntry, name: &str) -> Option<&'a str> {
+        entry
+            .fields
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn rename_moves_extra_and_builtin_fields() {
+        let chain = TransformChain::from_config(&[
+            TransformConfig {
+                to: "src_ip".to_owned(),
+                ..transform("rename", "src")
+            },
+            TransformConfig {
+                to: "host".to_owned(),
+                ..transform("rename", "hostname")
+            },
+        ])
+        .unwrap();
+        assert_eq!(chain.len(), 2);
+
+        let mut log = entry("login", &[("src", "10.0.0.1")]);
+        chain.apply(&mut log);
+        assert_eq!(field(&log, "src"), None);
+        assert_eq!(field(&log, "src_ip"), Some("10.0.0.1"));
+        assert_eq!(field(&log, "host"), Some("web-01"));
+        assert!(log.hostname.is_empty());
+    }
+
+    #[test]
+    fn extract_creates_fields_from_named_groups() {
+        let chain = TransformChain::from_config(&[TransformConfig {
+            pattern: r"user=(?P<user>\w+)(?: id=(?P<uid>\d+))?".to_owned(),
+            ..transform("extract", "message")
+        }])
+        .unwrap();
+
+        let mut log = entry("login failed user=alice", &[("user", "old")]);
+        chain.apply(&mut log);
+        assert_eq!(field(&log, "user"), Some("alice"));
+        assert_eq!(field(&log, "uid"), None);
+        assert_eq!(log.fields.len(), 1);
+
+        let mut log = entry("no match here", &[]);
+        chain.apply(&mut log);
+        assert!(log.fields.is_empty());
+    }
+
+    #[test]
+    fn redact_credit_card_checks_luhn_across_all_fields() {
+        let chain = TransformChain::from_config(&[TransformConfig {
+            preset: "credit_card".to_owned(),
+            ..transform("redact", TRANSFORM_ALL_FIELDS)
+        }])
+        .unwrap();
+
+        let mut log = entry(
+            "paid with 4111 1111 1111 1111 order 1234567890123",
+            &[("card", "5500-0000-0000-0004")],
+        );
+        chain.apply(&mut log);
+        assert_eq!(log.message, "paid with [REDACTED] order 1234567890123");
+        assert_eq!(field(&log, "card"), Some("[REDACTED]"));
+    }
+
+    #[test]
+    fn hash_is_salted_and_deterministic() {
+        let config = TransformConfig {
+            preset: "email".to_owned(),
+            salt: "pepper".to_owned(),
+            ..transform("hash", "message")
+        };
+        let chain = TransformChain::from_config(std::slice::from_ref(&config)).unwrap();
+
+        let mut first = entry("mail from Alice@Example.com rejected", &[]);
+        let mut second = first.clone();
+        chain.apply(&mut first);
+        chain.apply(&mut second);
+        assert_eq!(first.message, second.message);
+        assert!(!first.message.contains("Example.com"));
+        let digest = first.message.split(' ').nth(2).unwrap();
+        assert_eq!(digest.len(), 64);
+        assert!(digest.bytes().all(|b| b.is_ascii_hexdigit()));
+
+        let unsalted = TransformChain::from_config(&[TransformConfig {
+            salt: String::new(),
+            ..config
+        }])
+        .unwrap();
+        let mut third = entry("mail from Alice@Example.com rejected", &[]);
+        unsalted.apply(&mut third);
+        assert_ne!(first.message, third.message);
+    }
+
+    #[test]
+    fn redact_without_pattern_replaces_whole_value() {
+        let chain = TransformChain::from_config(&[
+            TransformConfig {
+                replacement: "***".to_owned(),
+                ..transform("redact", "password")
+            },
+            TransformConfig {
+                pattern: r"token=\S+".to_owned(),
+                ..transform("redact", "message")
+            },
+        ])
+        .unwrap();
+
+        let mut log = entry("auth token=abc123 ok", &[("password", "hunter2")]);
+        chain.apply(&mut log);
+        assert_eq!(field(&log, "password"), Some("***"));
+        assert_eq!(log.message, "auth [REDACTED] ok");
+    }
+
+    #[test]
+    fn transforms_apply_in_order() {
+        let chain = TransformChain::from_config(&[
+            TransformConfig {
+                pattern: r"card=(?P<card>[\d-]+)".to_owned(),
+                ..transform("extract", "message")
+            },
+            TransformConfig {
+                preset: "credit_card".to_owned(),
+                ..transform("hash", "card")
+            },
+        ])
+        .unwrap();
+
+        let mut log = entry("card=4111-1111-1111-1111", &[]);
+        chain.apply(&mut log);
+        assert_eq!(log.message, "card=4111-1111-1111-1111");
+        let card = field(&log, "card").unwrap();
+        assert_eq!(card.len(), 64);
+    }
+
+    #[test]
+    fn invalid_transforms_are_rejected() {
+        let cases = [
+            (
+                TransformConfig {
+                    pattern: r"\d+".to_owned(),
+                    ..transform("extract", "message")
+                },
+                "named group",
+            ),
+            (
+                TransformConfig {
+                    pattern: "(".to_owned(),
+                    ..transform("redact", "message")
+                },
+                "transforms[0].pattern",
+            ),
+            (transform("mask", "message"), "transforms[0].type"),
+        ];
+        for (config, expected) in cases {
+            let err = TransformChain::from_config(&[config]).unwrap_err();
+            assert!(err.to_string().contains(expected), "{expected}: {err}");
+        }
+        assert!(TransformChain::from_config(&[]).unwrap().is_empty());
+    }
+}
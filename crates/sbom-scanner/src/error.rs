@@ -7,7 +7,7 @@
 //! # 에러 카테고리
 //!
 //! - **Lockfile 파싱**: `LockfileParse`
-//! - **SBOM 생성**: `SbomGeneration`
+//! - **SBOM 생성/읽기**: `SbomGeneration`, `SbomParse`
 //! - **취약점 DB**: `VulnDbLoad`, `VulnDbParse`
 //! - **버전 비교**: `VersionParse`
 //! - **설정**: `Config`
@@ -39,6 +39,15 @@ pub enum SbomScannerError {
     #[error("sbom generation error: {0}")]
     SbomGeneration(String),
 
+    /// SBOM 문서 파싱 실패 (CycloneDX / SPDX 입력)
+    #[error("sbom parse error: {path}: {reason}")]
+    SbomParse {
+        /// 파싱 대상 파일 경로
+        path: String,
+        /// 실패 사유
+        reason: String,
+    },
+
     /// 취약점 DB 로딩 실패
     #[error("vulnerability db load error: {path}: {reason}")]
     VulnDbLoad {
@@ -104,6 +113,9 @@ impl From<SbomScannerError> for IronpostError {
             SbomScannerError::SbomGeneration(msg) => {
                 IronpostError::Sbom(SbomError::ScanFailed(msg))
             }
+            SbomScannerError::SbomParse { path, reason } => IronpostError::Sbom(
+                SbomError::ParseFailed(format!("sbom parse error: {path}: {reason}")),
+            ),
             SbomScannerError::VulnDbLoad { path, reason } => IronpostError::Sbom(
                 SbomError::VulnDb(format!("vulnerability db load error: {path}: {reason}")),
             ),
@@ -146,6 +158,17 @@ mod tests {
         assert!(err.to_string().contains("serialization failed"));
     }
 
+    #[test]
+    fn sbom_parse_error_display() {
+        let err = SbomScannerError::SbomParse {
+            path: "bom.json".to_owned(),
+            reason: "unknown document format".to_owned(),
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("bom.json"));
+        assert!(msg.contains("unknown document format"));
+    }
+
     #[test]
     fn vuln_db_load_error_display() {
         let err = SbomScannerError::VulnDbLoad {
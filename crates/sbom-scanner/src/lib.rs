@@ -7,7 +7,7 @@
 //! - [`event`]: Scan result events (`ScanEvent`)
 //! - [`types`]: Domain types (`Package`, `PackageGraph`, `Ecosystem`, `SbomFormat`, `SbomDocument`)
 //! - [`parser`]: Lockfile parsers (`LockfileParser` trait, `CargoLockParser`, `NpmLockParser`)
-//! - [`sbom`]: SBOM document generation (`SbomGenerator`, CycloneDX, SPDX)
+//! - [`sbom`]: SBOM document generation (`SbomGenerator`, CycloneDX, SPDX) and reading (`read_sbom`)
 //! - [`vuln`]: Vulnerability matching (`VulnDb`, `VulnMatcher`, `ScanResult`, `ScanFinding`)
 //! - [`scanner`]: Main orchestrator (`SbomScanner`, `SbomScannerBuilder`, `Pipeline` impl)
 //!
@@ -58,8 +58,9 @@ pub use parser::cargo::CargoLockParser;
 pub use parser::npm::NpmLockParser;
 pub use parser::{LockfileDetector, LockfileParser};
 
-// SBOM Generator
+// SBOM Generator / Reader
 pub use sbom::SbomGenerator;
+pub use sbom::reader::read_sbom;
 
 // Vulnerability
 pub use vuln::db::{VersionRange, VulnDb, VulnDbEntry};
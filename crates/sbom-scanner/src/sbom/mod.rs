@@ -7,8 +7,11 @@
 //!
 //! - CycloneDX 1.5 JSON -- [`cyclonedx`] 모듈
 //! - SPDX 2.3 JSON -- [`spdx`] 모듈
+//!
+//! 두 형식의 문서를 다시 패키지 그래프로 읽으려면 [`reader::read_sbom`]을 사용합니다.
 
 pub mod cyclonedx;
+pub mod reader;
 pub mod spdx;
 pub mod util;
 
@@ -0,0 +1,383 @@
+//! SBOM 문서 읽기 -- CycloneDX / SPDX JSON을 패키지 그래프로 변환
+//!
+//! 외부 도구가 생성한 SBOM을 lockfile 대신 취약점 스캔 입력으로 사용할 수 있도록
+//! [`PackageGraph`]로 변환합니다.
+//!
+//! - 패키지 식별은 Package URL(PURL)을 기준으로 하며, 지원하지 않는 PURL 타입
+//!   (maven 등)이나 PURL이 없는 컴포넌트는 건너뜁니다.
+//! - 의존성은 CycloneDX `dependencies`, SPDX `relationships`(`DEPENDS_ON`,
+//!   `DEPENDENCY_OF`)에서 읽습니다. 다른 패키지가 의존하지 않는 패키지를 루트로 봅니다.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Deserialize;
+
+use crate::error::SbomScannerError;
+use crate::types::{Ecosystem, Package, PackageGraph};
+
+/// CycloneDX 문서 (읽기용, 필요한 필드만)
+#[derive(Deserialize)]
+struct CycloneDxInput {
+    #[serde(default)]
+    components: Vec<CycloneDxInputComponent>,
+    #[serde(default)]
+    dependencies: Vec<CycloneDxInputDependency>,
+}
+
+#[derive(Deserialize)]
+struct CycloneDxInputComponent {
+    #[serde(rename = "bom-ref", default)]
+    bom_ref: Option<String>,
+    #[serde(default)]
+    group: Option<String>,
+    name: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    purl: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CycloneDxInputDependency {
+    #[serde(rename = "ref")]
+    reference: String,
+    #[serde(rename = "dependsOn", default)]
+    depends_on: Vec<String>,
+}
+
+/// SPDX 문서 (읽기용, 필요한 필드만)
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxInput {
+    #[serde(default)]
+    packages: Vec<SpdxInputPackage>,
+    #[serde(default)]
+    relationships: Vec<SpdxInputRelationship>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxInputPackage {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: String,
+    #[serde(default)]
+    version_info: Option<String>,
+    #[serde(default)]
+    external_refs: Vec<SpdxInputExternalRef>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxInputExternalRef {
+    reference_type: String,
+    reference_locator: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxInputRelationship {
+    spdx_element_id: String,
+    relationship_type: String,
+    related_spdx_element: String,
+}
+
+/// CycloneDX 또는 SPDX JSON 문서를 패키지 그래프로 변환합니다.
+///
+/// 형식은 `bomFormat: "CycloneDX"` 또는 `spdxVersion` 필드로 판별합니다.
+///
+/// # Errors
+///
+/// - JSON이 아니거나 두 형식 어느 쪽도 아닌 경우
+/// - 지원하는 PURL을 가진 패키지가 하나도 없는 경우
+pub fn read_sbom(content: &str, source_path: &str) -> Result<PackageGraph, SbomScannerError> {
+    let parse_error = |reason: String| SbomScannerError::SbomParse {
+        path: source_path.to_owned(),
+        reason,
+    };
+
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| parse_error(e.to_string()))?;
+
+    // (참조 ID, 패키지) 목록과 (의존하는 쪽, 의존되는 쪽) 참조 쌍
+    let (entries, edges) = if value.get("bomFormat").and_then(|v| v.as_str()) == Some("CycloneDX") {
+        let doc: CycloneDxInput =
+            serde_json::from_value(value).map_err(|e| parse_error(e.to_string()))?;
+        read_cyclonedx(doc)
+    } else if value.get("spdxVersion").is_some() {
+        let doc: SpdxInput =
+            serde_json::from_value(value).map_err(|e| parse_error(e.to_string()))?;
+        read_spdx(doc)
+    } else {
+        return Err(parse_error(
+            "unknown document format (expected CycloneDX or SPDX JSON)".to_owned(),
+        ));
+    };
+
+    let names: HashMap<&str, &str> = entries
+        .iter()
+        .map(|(reference, pkg)| (reference.as_str(), pkg.name.as_str()))
+        .collect();
+    let mut dependencies: HashMap<String, Vec<String>> = HashMap::new();
+    let mut dependents = HashSet::new();
+    for (from, to) in &edges {
+        if let (Some(from), Some(to)) = (names.get(from.as_str()), names.get(to.as_str())) {
+            dependencies
+                .entry((*from).to_owned())
+                .or_default()
+                .push((*to).to_owned());
+            dependents.insert((*to).to_owned());
+        }
+    }
+
+    let mut packages: Vec<Package> = entries.into_iter().map(|(_, pkg)| pkg).collect();
+    let Some(ecosystem) = packages.first().map(|p| p.ecosystem) else {
+        return Err(parse_error(
+            "no packages with a supported Package URL (cargo, npm, golang, pypi)".to_owned(),
+        ));
+    };
+    for pkg in &mut packages {
+        if let Some(deps) = dependencies.remove(&pkg.name) {
+            pkg.dependencies = deps;
+        }
+    }
+
+    let root_packages = if edges.is_empty() {
+        Vec::new()
+    } else {
+        packages
+            .iter()
+            .filter(|p| !dependents.contains(&p.name))
+            .map(|p| p.name.clone())
+            .collect()
+    };
+
+    Ok(PackageGraph {
+        source_file: source_path.to_owned(),
+        ecosystem,
+        packages,
+        root_packages,
+    })
+}
+
+type RefEntries = Vec<(String, Package)>;
+type RefEdges = Vec<(String, String)>;
+
+fn read_cyclonedx(doc: CycloneDxInput) -> (RefEntries, RefEdges) {
+    let entries = doc
+        .components
+        .into_iter()
+        .filter_map(|c| {
+            let purl = c.purl?;
+            let ecosystem = purl_ecosystem(&purl)?;
+            let name = match c.group.filter(|g| !g.is_empty()) {
+                Some(group) if ecosystem == Ecosystem::Npm => format!("{}/{}", group, c.name),
+                _ => c.name,
+            };
+            let version = c.version.or_else(|| purl_version(&purl))?;
+            let reference = c.bom_ref.unwrap_or_else(|| purl.clone());
+            Some((reference, package(name, version, ecosystem, purl)))
+        })
+        .collect();
+
+    let edges = doc
+        .dependencies
+        .into_iter()
+        .flat_map(|d| {
+            let from = d.reference;
+            d.depends_on.into_iter().map(move |to| (from.clone(), to))
+        })
+        .collect();
+
+    (entries, edges)
+}
+
+fn read_spdx(doc: SpdxInput) -> (RefEntries, RefEdges) {
+    let entries = doc
+        .packages
+        .into_iter()
+        .filter_map(|p| {
+            let purl = p
+                .external_refs
+                .into_iter()
+                .find(|r| r.reference_type == "purl")?
+                .reference_locator;
+            let ecosystem = purl_ecosystem(&purl)?;
+            let version = p.version_info.or_else(|| purl_version(&purl))?;
+            Some((p.spdx_id, package(p.name, version, ecosystem, purl)))
+        })
+        .collect();
+
+    let edges = doc
+        .relationships
+        .into_iter()
+        .filter_map(|r| match r.relationship_type.as_str() {
+            "DEPENDS_ON" => Some((r.spdx_element_id, r.related_spdx_element)),
+            "DEPENDENCY_OF" => Some((r.related_spdx_element, r.spdx_element_id)),
+            _ => None,
+        })
+        .collect();
+
+    (entries, edges)
+}
+
+fn package(name: String, version: String, ecosystem: Ecosystem, purl: String) -> Package {
+    Package {
+        name,
+        version,
+        ecosystem,
+        purl,
+        checksum: None,
+        dependencies: Vec::new(),
+    }
+}
+
+/// `pkg:<type>/...` 에서 생태계를 찾습니다.
+fn purl_ecosystem(purl: &str) -> Option<Ecosystem> {
+    let purl_type = purl.strip_prefix("pkg:")?.split('/').next()?;
+    Ecosystem::from_purl_type(purl_type)
+}
+
+/// `...@<version>[?qualifiers][#subpath]` 에서 버전을 찾습니다.
+fn purl_version(purl: &str) -> Option<String> {
+    let (_, version) = purl.rsplit_once('@')?;
+    let version = version.split(['?', '#']).next()?;
+    (!version.is_empty()).then(|| version.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sbom::SbomGenerator;
+    use crate::types::SbomFormat;
+
+    fn sample_graph() -> PackageGraph {
+        PackageGraph {
+            source_file: "Cargo.lock".to_owned(),
+            ecosystem: Ecosystem::Cargo,
+            packages: vec![
+                Package {
+                    name: "serde".to_owned(),
+                    version: "1.0.204".to_owned(),
+                    ecosystem: Ecosystem::Cargo,
+                    purl: "pkg:cargo/serde@1.0.204".to_owned(),
+                    checksum: None,
+                    dependencies: vec![],
+                },
+                Package {
+                    name: "lodash".to_owned(),
+                    version: "4.17.21".to_owned(),
+                    ecosystem: Ecosystem::Npm,
+                    purl: "pkg:npm/lodash@4.17.21".to_owned(),
+                    checksum: None,
+                    dependencies: vec![],
+                },
+            ],
+            root_packages: vec![],
+        }
+    }
+
+    #[test]
+    fn reads_generated_cyclonedx() {
+        let doc = SbomGenerator::new(SbomFormat::CycloneDx)
+            .generate(&sample_graph())
+            .unwrap();
+
+        let graph = read_sbom(&doc.content, "bom.json").unwrap();
+
+        assert_eq!(graph.package_count(), 2);
+        let lodash = graph.find_package("lodash").unwrap();
+        assert_eq!(lodash.version, "4.17.21");
+        assert_eq!(lodash.ecosystem, Ecosystem::Npm);
+        assert!(graph.root_packages.is_empty());
+    }
+
+    #[test]
+    fn reads_generated_spdx() {
+        let doc = SbomGenerator::new(SbomFormat::Spdx)
+            .generate(&sample_graph())
+            .unwrap();
+
+        let graph = read_sbom(&doc.content, "bom.spdx.json").unwrap();
+
+        assert_eq!(graph.package_count(), 2);
+        assert_eq!(graph.find_package("serde").unwrap().version, "1.0.204");
+    }
+
+    #[test]
+    fn reads_cyclonedx_dependencies_and_roots() {
+        let content = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "components": [
+                {"bom-ref": "app", "name": "app", "version": "0.1.0", "purl": "pkg:cargo/app@0.1.0"},
+                {"bom-ref": "hyper", "name": "hyper", "version": "1.4.0", "purl": "pkg:cargo/hyper@1.4.0"},
+                {"bom-ref": "h2", "name": "h2", "purl": "pkg:cargo/h2@0.4.5"},
+                {"name": "commons-io", "version": "2.16.1", "purl": "pkg:maven/commons-io/commons-io@2.16.1"}
+            ],
+            "dependencies": [
+                {"ref": "app", "dependsOn": ["hyper"]},
+                {"ref": "hyper", "dependsOn": ["h2"]}
+            ]
+        }"#;
+
+        let graph = read_sbom(content, "bom.json").unwrap();
+
+        assert_eq!(graph.package_count(), 3, "maven component is skipped");
+        assert_eq!(graph.root_packages, vec!["app".to_owned()]);
+        assert_eq!(
+            graph.find_package("hyper").unwrap().dependencies,
+            vec!["h2"]
+        );
+        assert_eq!(
+            graph.find_package("h2").unwrap().version,
+            "0.4.5",
+            "version falls back to the PURL"
+        );
+    }
+
+    #[test]
+    fn reads_spdx_relationships() {
+        let content = r#"{
+            "spdxVersion": "SPDX-2.3",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "packages": [
+                {"SPDXID": "SPDXRef-a", "name": "a", "versionInfo": "1.0.0",
+                 "externalRefs": [{"referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl", "referenceLocator": "pkg:npm/a@1.0.0"}]},
+                {"SPDXID": "SPDXRef-b", "name": "b", "versionInfo": "2.0.0",
+                 "externalRefs": [{"referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl", "referenceLocator": "pkg:npm/b@2.0.0"}]}
+            ],
+            "relationships": [
+                {"spdxElementId": "SPDXRef-b", "relationshipType": "DEPENDENCY_OF", "relatedSpdxElement": "SPDXRef-a"}
+            ]
+        }"#;
+
+        let graph = read_sbom(content, "bom.spdx.json").unwrap();
+
+        assert_eq!(graph.ecosystem, Ecosystem::Npm);
+        assert_eq!(graph.root_packages, vec!["a".to_owned()]);
+        assert_eq!(graph.find_package("a").unwrap().dependencies, vec!["b"]);
+    }
+
+    #[test]
+    fn rejects_unknown_format() {
+        let err = read_sbom(r#"{"hello": "world"}"#, "x.json").unwrap_err();
+        assert!(err.to_string().contains("unknown document format"));
+    }
+
+    #[test]
+    fn rejects_document_without_supported_packages() {
+        let content = r#"{"bomFormat": "CycloneDX", "components": []}"#;
+        let err = read_sbom(content, "bom.json").unwrap_err();
+        assert!(err.to_string().contains("no packages"));
+    }
+
+    #[test]
+    fn purl_version_strips_qualifiers() {
+        assert_eq!(
+            purl_version("pkg:npm/%40scope/pkg@1.2.3?arch=x64#lib").as_deref(),
+            Some("1.2.3")
+        );
+        assert_eq!(purl_version("pkg:cargo/serde"), None);
+    }
+}
@@ -31,7 +31,9 @@ use ironpost_core::error::IronpostError;
 use ironpost_core::event::{AlertEvent, MODULE_SBOM_SCANNER};
 use ironpost_core::metrics as m;
 use ironpost_core::pipeline::{HealthStatus, Pipeline};
-use ironpost_core::plugin::{Plugin, PluginInfo, PluginState, PluginType};
+use ironpost_core::plugin::{
+    ChannelUsage, Plugin, PluginInfo, PluginState, PluginType, ResourceUsage,
+};
 use ironpost_core::types::Alert;
 
 use crate::config::SbomScannerConfig;
@@ -40,7 +42,7 @@ use crate::parser::cargo::CargoLockParser;
 use crate::parser::npm::NpmLockParser;
 use crate::parser::{LockfileDetector, LockfileParser};
 use crate::sbom::SbomGenerator;
-use crate::vuln::{ScanResult, VulnDb, VulnMatcher};
+use crate::vuln::{ScanResult, VulnDb, VulnDbEntry, VulnMatcher};
 
 /// 스캐너 실행 상태
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -419,6 +421,20 @@ impl Plugin for SbomScanner {
     async fn health_check(&self) -> HealthStatus {
         <Self as Pipeline>::health_check(self).await
     }
+
+    async fn resource_usage(&self) -> ResourceUsage {
+        // 로드된 취약점 DB가 주요 상주 메모리
+        let vuln_entries = self.matcher.as_ref().map_or(0, |m| m.db().entry_count());
+
+        ResourceUsage {
+            tasks: ResourceUsage::live_tasks(&self.tasks),
+            channels: vec![ChannelUsage::from_sender("alerts", &self.alert_tx)],
+            memory_bytes: ResourceUsage::estimate_bytes(
+                vuln_entries,
+                std::mem::size_of::<VulnDbEntry>(),
+            ),
+        }
+    }
 }
 
 /// SBOM 스캐너 빌더
@@ -55,6 +55,17 @@ impl Ecosystem {
             _ => None,
         }
     }
+
+    /// Package URL 타입에서 생태계를 찾습니다 ([`Ecosystem::purl_type`]의 역변환).
+    pub fn from_purl_type(purl_type: &str) -> Option<Self> {
+        match purl_type {
+            "cargo" => Some(Self::Cargo),
+            "npm" => Some(Self::Npm),
+            "golang" => Some(Self::Go),
+            "pypi" => Some(Self::Pip),
+            _ => None,
+        }
+    }
 }
 
 /// 소프트웨어 패키지 정보
@@ -216,6 +227,19 @@ mod tests {
         assert_eq!(Ecosystem::from_str_loose("unknown"), None);
     }
 
+    #[test]
+    fn ecosystem_from_purl_type_roundtrip() {
+        for eco in [
+            Ecosystem::Cargo,
+            Ecosystem::Npm,
+            Ecosystem::Go,
+            Ecosystem::Pip,
+        ] {
+            assert_eq!(Ecosystem::from_purl_type(eco.purl_type()), Some(eco));
+        }
+        assert_eq!(Ecosystem::from_purl_type("maven"), None);
+    }
+
     #[test]
     fn package_make_purl() {
         let purl = Package::make_purl(&Ecosystem::Cargo, "serde", "1.0.204");
@@ -0,0 +1,618 @@
+//! 취약점 피드 변환 -- OSV, GitHub Advisory(GHSA), NVD 응답을 DB 엔트리로 변환
+//!
+//! 온라인 동기화(`ironpost sbom db update`)가 내려받은 피드를 [`VulnDbEntry`]로 바꾸고
+//! 기존 DB와 병합하는 데 사용합니다. HTTP 요청은 호출하는 쪽이 담당합니다.
+//!
+//! # 피드
+//! - OSV: 레코드 하나(JSON 객체). `affected[].ranges`의 `SEMVER`/`ECOSYSTEM` 이벤트를
+//!   `introduced`/`fixed` 범위로 바꾸고, `aliases`의 CVE ID를 우선 사용합니다.
+//!   `last_affected`로 끝나는 범위는 [`VersionRange`]로 표현할 수 없어 건너뜁니다.
+//! - GHSA: GitHub REST `GET /advisories` 응답(JSON 배열). `vulnerable_version_range`
+//!   (`>= 1.0, < 1.2`)와 `first_patched_version`으로 범위를 만듭니다.
+//! - NVD: CVE API 2.0 응답. 패키지 정보가 없으므로 CVSS 심각도만 추출해
+//!   [`apply_severities`]로 같은 CVE ID 엔트리의 심각도를 보정합니다.
+//!
+//! 지원하지 않는 생태계, 철회된(withdrawn) 권고, 범위를 만들 수 없는 패키지는 건너뜁니다.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Deserialize;
+
+use ironpost_core::types::Severity;
+
+use crate::error::SbomScannerError;
+use crate::types::Ecosystem;
+use crate::vuln::db::{VersionRange, VulnDbEntry};
+
+/// 피드 엔트리 설명 최대 길이 (문자 수, DB 크기를 줄이기 위해 잘라 저장)
+pub const MAX_FEED_DESCRIPTION_CHARS: usize = 1024;
+
+/// 심각도 정보가 없는 권고의 기본 심각도
+const DEFAULT_FEED_SEVERITY: Severity = Severity::Medium;
+
+/// 피드 생태계 이름을 [`Ecosystem`]으로 변환합니다.
+///
+/// OSV 이름(`crates.io`, `npm`, `Go`, `PyPI`)과 GHSA 이름(`rust`, `npm`, `go`, `pip`)을 받습니다.
+pub fn feed_ecosystem(name: &str) -> Option<Ecosystem> {
+    match name {
+        "crates.io" | "rust" => Some(Ecosystem::Cargo),
+        "npm" => Some(Ecosystem::Npm),
+        "Go" | "go" => Some(Ecosystem::Go),
+        "PyPI" | "pip" => Some(Ecosystem::Pip),
+        _ => None,
+    }
+}
+
+/// 생태계의 OSV 이름 (`crates.io`, `npm`, `Go`, `PyPI`)
+pub fn osv_ecosystem(ecosystem: Ecosystem) -> &'static str {
+    match ecosystem {
+        Ecosystem::Cargo => "crates.io",
+        Ecosystem::Npm => "npm",
+        Ecosystem::Go => "Go",
+        Ecosystem::Pip => "PyPI",
+    }
+}
+
+/// 생태계의 GHSA 이름 (`rust`, `npm`, `go`, `pip`)
+pub fn ghsa_ecosystem(ecosystem: Ecosystem) -> &'static str {
+    match ecosystem {
+        Ecosystem::Cargo => "rust",
+        Ecosystem::Npm => "npm",
+        Ecosystem::Go => "go",
+        Ecosystem::Pip => "pip",
+    }
+}
+
+// --- OSV ---
+
+#[derive(Deserialize)]
+struct OsvRecord {
+    id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    details: String,
+    #[serde(default)]
+    published: String,
+    withdrawn: Option<String>,
+    #[serde(default)]
+    affected: Vec<OsvAffected>,
+    database_specific: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct OsvAffected {
+    package: Option<OsvPackage>,
+    #[serde(default)]
+    ranges: Vec<OsvRange>,
+}
+
+#[derive(Deserialize)]
+struct OsvPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct OsvRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<OsvEvent>,
+}
+
+#[derive(Deserialize)]
+struct OsvEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+}
+
+/// OSV 레코드 하나를 DB 엔트리로 변환합니다 (영향받는 패키지마다 하나).
+///
+/// 철회된 레코드와 지원하지 않는 생태계의 패키지는 빈 결과가 됩니다.
+///
+/// # Errors
+/// JSON이 OSV 레코드 형식이 아닌 경우
+pub fn parse_osv_record(json: &str) -> Result<Vec<VulnDbEntry>, SbomScannerError> {
+    let record: OsvRecord = serde_json::from_str(json)
+        .map_err(|e| SbomScannerError::VulnDbParse(format!("invalid OSV record: {e}")))?;
+    if record.withdrawn.is_some() {
+        return Ok(Vec::new());
+    }
+
+    let cve_id = record
+        .aliases
+        .iter()
+        .find(|alias| alias.starts_with("CVE-"))
+        .cloned()
+        .unwrap_or(record.id);
+    let severity = record
+        .database_specific
+        .as_ref()
+        .and_then(|specific| specific.get("severity"))
+        .and_then(serde_json::Value::as_str)
+        .and_then(feed_severity)
+        .unwrap_or(DEFAULT_FEED_SEVERITY);
+    let description = if record.summary.is_empty() {
+        &record.details
+    } else {
+        &record.summary
+    };
+
+    let mut entries = Vec::new();
+    for affected in record.affected {
+        let Some(package) = affected.package else {
+            continue;
+        };
+        let Some(ecosystem) = feed_ecosystem(&package.ecosystem) else {
+            continue;
+        };
+        let affected_ranges: Vec<VersionRange> = affected
+            .ranges
+            .iter()
+            .filter(|range| range.kind == "SEMVER" || range.kind == "ECOSYSTEM")
+            .flat_map(|range| osv_ranges(&range.events))
+            .collect();
+        if affected_ranges.is_empty() {
+            continue;
+        }
+        entries.push(VulnDbEntry {
+            cve_id: cve_id.clone(),
+            package: package.name,
+            ecosystem,
+            fixed_version: affected_ranges.iter().rev().find_map(|r| r.fixed.clone()),
+            affected_ranges,
+            severity,
+            description: truncate(description),
+            published: date_part(&record.published),
+        });
+    }
+    Ok(entries)
+}
+
+/// OSV 이벤트 목록을 버전 범위로 변환합니다.
+fn osv_ranges(events: &[OsvEvent]) -> Vec<VersionRange> {
+    let mut ranges = Vec::new();
+    // 열린 범위의 시작 (Some(None)은 처음부터)
+    let mut open: Option<Option<String>> = None;
+    for event in events {
+        if let Some(introduced) = &event.introduced {
+            open = Some((introduced != "0").then(|| introduced.clone()));
+        } else if let Some(fixed) = &event.fixed {
+            if let Some(introduced) = open.take() {
+                ranges.push(VersionRange {
+                    introduced,
+                    fixed: Some(fixed.clone()),
+                });
+            }
+        } else if event.last_affected.is_some() {
+            open = None;
+        }
+    }
+    if let Some(introduced) = open {
+        ranges.push(VersionRange {
+            introduced,
+            fixed: None,
+        });
+    }
+    ranges
+}
+
+// --- GHSA ---
+
+#[derive(Deserialize)]
+struct GhsaAdvisory {
+    ghsa_id: String,
+    cve_id: Option<String>,
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    severity: String,
+    #[serde(default)]
+    published_at: String,
+    withdrawn_at: Option<String>,
+    #[serde(default)]
+    vulnerabilities: Vec<GhsaVulnerability>,
+}
+
+#[derive(Deserialize)]
+struct GhsaVulnerability {
+    package: Option<GhsaPackage>,
+    vulnerable_version_range: Option<String>,
+    first_patched_version: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct GhsaPackage {
+    ecosystem: String,
+    name: String,
+}
+
+/// GitHub `GET /advisories` 응답 한 페이지를 DB 엔트리로 변환합니다.
+///
+/// # Errors
+/// JSON이 권고 배열이 아닌 경우
+pub fn parse_ghsa_advisories(json: &str) -> Result<Vec<VulnDbEntry>, SbomScannerError> {
+    let advisories: Vec<GhsaAdvisory> = serde_json::from_str(json)
+        .map_err(|e| SbomScannerError::VulnDbParse(format!("invalid GHSA response: {e}")))?;
+
+    let mut entries = Vec::new();
+    for advisory in advisories {
+        if advisory.withdrawn_at.is_some() {
+            continue;
+        }
+        let cve_id = advisory
+            .cve_id
+            .filter(|id| !id.is_empty())
+            .unwrap_or(advisory.ghsa_id);
+        let severity = feed_severity(&advisory.severity).unwrap_or(DEFAULT_FEED_SEVERITY);
+        for vulnerability in advisory.vulnerabilities {
+            let Some(package) = vulnerability.package else {
+                continue;
+            };
+            let Some(ecosystem) = feed_ecosystem(&package.ecosystem) else {
+                continue;
+            };
+            // REST 응답은 문자열, 일부 응답은 `{ "identifier": ... }` 객체
+            let patched = vulnerability
+                .first_patched_version
+                .as_ref()
+                .and_then(|value| value.as_str().or_else(|| value.get("identifier")?.as_str()))
+                .map(str::to_owned);
+            let Some(range) = ghsa_range(
+                vulnerability
+                    .vulnerable_version_range
+                    .as_deref()
+                    .unwrap_or(""),
+                patched.clone(),
+            ) else {
+                continue;
+            };
+            entries.push(VulnDbEntry {
+                cve_id: cve_id.clone(),
+                package: package.name,
+                ecosystem,
+                affected_ranges: vec![range],
+                fixed_version: patched,
+                severity,
+                description: truncate(&advisory.summary),
+                published: date_part(&advisory.published_at),
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// `>= 1.0, < 1.2` 형식의 범위를 변환합니다.
+///
+/// 상한이 `<= x`인데 수정 버전이 없으면 표현할 수 없어 `None`을 반환합니다.
+fn ghsa_range(range: &str, patched: Option<String>) -> Option<VersionRange> {
+    let mut introduced = None;
+    let mut fixed = None;
+    let mut inclusive_upper = false;
+    for clause in range.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+        if let Some(version) = clause
+            .strip_prefix(">=")
+            .or_else(|| clause.strip_prefix('>'))
+        {
+            introduced = Some(version.trim().to_owned());
+        } else if clause.starts_with("<=") {
+            inclusive_upper = true;
+        } else if let Some(version) = clause.strip_prefix('<') {
+            fixed = Some(version.trim().to_owned());
+        } else if let Some(version) = clause.strip_prefix('=') {
+            introduced = Some(version.trim().to_owned());
+            inclusive_upper = true;
+        }
+    }
+    if fixed.is_none() {
+        fixed = patched;
+        if inclusive_upper && fixed.is_none() {
+            return None;
+        }
+    }
+    Some(VersionRange {
+        introduced: introduced.filter(|v| v != "0"),
+        fixed,
+    })
+}
+
+// --- NVD ---
+
+/// NVD CVE API 2.0 응답 한 페이지
+#[derive(Debug, Clone, Default)]
+pub struct NvdPage {
+    /// CVE ID와 CVSS 심각도 (v3.1, v3.0, v2 순으로 우선)
+    pub severities: Vec<(String, Severity)>,
+    /// 조건에 맞는 전체 CVE 수
+    pub total_results: usize,
+    /// 이 페이지의 시작 위치
+    pub start_index: usize,
+    /// 이 페이지의 CVE 수
+    pub results_per_page: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdResponse {
+    #[serde(default)]
+    results_per_page: usize,
+    #[serde(default)]
+    start_index: usize,
+    #[serde(default)]
+    total_results: usize,
+    #[serde(default)]
+    vulnerabilities: Vec<NvdItem>,
+}
+
+#[derive(Deserialize)]
+struct NvdItem {
+    cve: NvdCve,
+}
+
+#[derive(Deserialize)]
+struct NvdCve {
+    id: String,
+    #[serde(default)]
+    metrics: NvdMetrics,
+}
+
+#[derive(Deserialize, Default)]
+struct NvdMetrics {
+    #[serde(rename = "cvssMetricV31", default)]
+    v31: Vec<NvdMetric>,
+    #[serde(rename = "cvssMetricV30", default)]
+    v30: Vec<NvdMetric>,
+    #[serde(rename = "cvssMetricV2", default)]
+    v2: Vec<NvdMetric>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdMetric {
+    /// v2는 메트릭 수준에 심각도가 있음
+    base_severity: Option<String>,
+    cvss_data: NvdCvssData,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdCvssData {
+    base_severity: Option<String>,
+}
+
+/// NVD CVE API 2.0 응답 한 페이지에서 CVE별 심각도를 추출합니다.
+///
+/// CVSS 메트릭이 없는 CVE는 건너뜁니다.
+///
+/// # Errors
+/// JSON이 NVD 응답 형식이 아닌 경우
+pub fn parse_nvd_page(json: &str) -> Result<NvdPage, SbomScannerError> {
+    let response: NvdResponse = serde_json::from_str(json)
+        .map_err(|e| SbomScannerError::VulnDbParse(format!("invalid NVD response: {e}")))?;
+
+    let severities = response
+        .vulnerabilities
+        .into_iter()
+        .filter_map(|item| {
+            let metrics = &item.cve.metrics;
+            let severity = metrics
+                .v31
+                .iter()
+                .chain(&metrics.v30)
+                .chain(&metrics.v2)
+                .find_map(|metric| {
+                    metric
+                        .cvss_data
+                        .base_severity
+                        .as_deref()
+                        .or(metric.base_severity.as_deref())
+                        .and_then(feed_severity)
+                })?;
+            Some((item.cve.id, severity))
+        })
+        .collect();
+
+    Ok(NvdPage {
+        severities,
+        total_results: response.total_results,
+        start_index: response.start_index,
+        results_per_page: response.results_per_page,
+    })
+}
+
+// --- 병합 ---
+
+/// 기존 엔트리에 새 엔트리를 병합합니다.
+///
+/// `(생태계, 패키지, CVE ID)`가 같으면 새 엔트리로 교체합니다. 결과는 같은 키 순서로
+/// 정렬되어, 내용이 같으면 직렬화 결과도 같습니다.
+pub fn merge_entries(
+    current: Vec<VulnDbEntry>,
+    updates: impl IntoIterator<Item = VulnDbEntry>,
+) -> Vec<VulnDbEntry> {
+    let mut merged: BTreeMap<(String, String, String), VulnDbEntry> = BTreeMap::new();
+    for entry in current.into_iter().chain(updates) {
+        let key = (
+            entry.ecosystem.to_string(),
+            entry.package.clone(),
+            entry.cve_id.clone(),
+        );
+        merged.insert(key, entry);
+    }
+    merged.into_values().collect()
+}
+
+/// CVE ID가 일치하는 엔트리의 심각도를 바꿉니다. 바뀐 엔트리 수를 반환합니다.
+pub fn apply_severities(
+    entries: &mut [VulnDbEntry],
+    severities: &HashMap<String, Severity>,
+) -> usize {
+    let mut updated = 0;
+    for entry in entries {
+        if let Some(severity) = severities.get(&entry.cve_id)
+            && entry.severity != *severity
+        {
+            entry.severity = *severity;
+            updated += 1;
+        }
+    }
+    updated
+}
+
+/// 피드 심각도 문자열 변환 (`MODERATE`는 Medium, `NONE`은 Info)
+fn feed_severity(value: &str) -> Option<Severity> {
+    match value.to_ascii_lowercase().as_str() {
+        "moderate" => Some(Severity::Medium),
+        "none" => Some(Severity::Info),
+        other => Severity::from_str_loose(other),
+    }
+}
+
+/// 설명을 [`MAX_FEED_DESCRIPTION_CHARS`]자로 자릅니다.
+fn truncate(text: &str) -> String {
+    text.trim()
+        .chars()
+        .take(MAX_FEED_DESCRIPTION_CHARS)
+        .collect()
+}
+
+/// RFC 3339 시각의 날짜 부분 (`2024-01-15T00:00:00Z` -> `2024-01-15`)
+fn date_part(timestamp: &str) -> String {
+    timestamp.get(..10).unwrap_or(timestamp).to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OSV_RECORD: &str = r#"{
+        "id": "GHSA-xxxx-yyyy-zzzz",
+        "aliases": ["RUSTSEC-2024-0001", "CVE-2024-1111"],
+        "summary": "Use after free in demo",
+        "published": "2024-03-01T12:00:00Z",
+        "database_specific": {"severity": "MODERATE"},
+        "affected": [
+            {"package": {"ecosystem": "crates.io", "name": "demo"},
+             "ranges": [{"type": "SEMVER", "events": [
+                 {"introduced": "0"}, {"fixed": "1.2.0"},
+                 {"introduced": "2.0.0"}, {"fixed": "2.0.3"}]}]},
+            {"package": {"ecosystem": "Maven", "name": "org.demo:demo"},
+             "ranges": [{"type": "ECOSYSTEM", "events": [{"introduced": "0"}]}]},
+            {"package": {"ecosystem": "npm", "name": "demo-js"},
+             "ranges": [{"type": "SEMVER", "events": [
+                 {"introduced": "1.0.0"}, {"last_affected": "1.4.0"}]}]}
+        ]
+    }"#;
+
+    #[test]
+    fn osv_record_becomes_entries_per_supported_package() {
+        let entries = parse_osv_record(OSV_RECORD).unwrap();
+
+        assert_eq!(
+            entries.len(),
+            1,
+            "Maven and last_affected ranges are skipped"
+        );
+        let entry = &entries[0];
+        assert_eq!(entry.cve_id, "CVE-2024-1111");
+        assert_eq!(entry.package, "demo");
+        assert_eq!(entry.ecosystem, Ecosystem::Cargo);
+        assert_eq!(entry.severity, Severity::Medium);
+        assert_eq!(entry.published, "2024-03-01");
+        assert_eq!(entry.fixed_version.as_deref(), Some("2.0.3"));
+        assert_eq!(entry.affected_ranges.len(), 2);
+        assert!(entry.affected_ranges[0].introduced.is_none());
+        assert_eq!(entry.affected_ranges[0].fixed.as_deref(), Some("1.2.0"));
+        assert_eq!(
+            entry.affected_ranges[1].introduced.as_deref(),
+            Some("2.0.0")
+        );
+    }
+
+    #[test]
+    fn withdrawn_osv_record_is_skipped() {
+        let json = r#"{"id": "GHSA-1", "withdrawn": "2024-01-01T00:00:00Z",
+            "affected": [{"package": {"ecosystem": "npm", "name": "x"},
+            "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}]}]}]}"#;
+        assert!(parse_osv_record(json).unwrap().is_empty());
+        assert!(parse_osv_record("[]").is_err());
+    }
+
+    #[test]
+    fn ghsa_advisories_become_entries() {
+        let json = r#"[
+            {"ghsa_id": "GHSA-aaaa", "cve_id": "CVE-2024-2222", "summary": "Prototype pollution",
+             "severity": "high", "published_at": "2024-04-02T00:00:00Z", "withdrawn_at": null,
+             "vulnerabilities": [
+                {"package": {"ecosystem": "npm", "name": "lodash"},
+                 "vulnerable_version_range": ">= 4.0.0, < 4.17.21",
+                 "first_patched_version": "4.17.21"},
+                {"package": {"ecosystem": "pip", "name": "demo"},
+                 "vulnerable_version_range": "<= 1.0.0",
+                 "first_patched_version": null}
+             ]},
+            {"ghsa_id": "GHSA-bbbb", "cve_id": null, "summary": "Withdrawn",
+             "severity": "low", "published_at": "2024-01-01T00:00:00Z",
+             "withdrawn_at": "2024-02-01T00:00:00Z", "vulnerabilities": []}
+        ]"#;
+
+        let entries = parse_ghsa_advisories(json).unwrap();
+
+        assert_eq!(
+            entries.len(),
+            1,
+            "inclusive upper bound without a fix is skipped"
+        );
+        let entry = &entries[0];
+        assert_eq!(entry.cve_id, "CVE-2024-2222");
+        assert_eq!(entry.ecosystem, Ecosystem::Npm);
+        assert_eq!(entry.severity, Severity::High);
+        assert_eq!(
+            entry.affected_ranges[0].introduced.as_deref(),
+            Some("4.0.0")
+        );
+        assert_eq!(entry.affected_ranges[0].fixed.as_deref(), Some("4.17.21"));
+    }
+
+    #[test]
+    fn nvd_page_prefers_cvss_v31_severity() {
+        let json = r#"{"resultsPerPage": 2, "startIndex": 0, "totalResults": 5,
+            "vulnerabilities": [
+                {"cve": {"id": "CVE-2024-1111", "metrics": {
+                    "cvssMetricV31": [{"cvssData": {"baseSeverity": "CRITICAL"}}],
+                    "cvssMetricV2": [{"baseSeverity": "MEDIUM", "cvssData": {}}]}}},
+                {"cve": {"id": "CVE-2024-3333", "metrics": {}}}
+            ]}"#;
+
+        let page = parse_nvd_page(json).unwrap();
+
+        assert_eq!(page.total_results, 5);
+        assert_eq!(page.results_per_page, 2);
+        assert_eq!(
+            page.severities,
+            vec![("CVE-2024-1111".to_owned(), Severity::Critical)]
+        );
+    }
+
+    #[test]
+    fn merge_replaces_same_advisory_and_applies_severities() {
+        let mut old = parse_osv_record(OSV_RECORD).unwrap();
+        old[0].description = "old".to_owned();
+        let mut other = old[0].clone();
+        other.package = "another".to_owned();
+        let updates = parse_osv_record(OSV_RECORD).unwrap();
+
+        let mut merged = merge_entries(vec![old.remove(0), other], updates);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].package, "another");
+        assert_eq!(merged[1].description, "Use after free in demo");
+
+        let severities = HashMap::from([("CVE-2024-1111".to_owned(), Severity::Critical)]);
+        assert_eq!(apply_severities(&mut merged, &severities), 2);
+        assert!(merged.iter().all(|e| e.severity == Severity::Critical));
+    }
+}
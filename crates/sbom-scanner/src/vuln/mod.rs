@@ -9,8 +9,11 @@
 //! 2. `VulnMatcher::new(db, min_severity)` -- 매처 생성
 //! 3. `VulnMatcher::scan(graph)` -- 패키지 그래프 스캔
 //! 4. 결과: `Vec<ScanFinding>` -- 발견된 취약점 목록
+//!
+//! DB 온라인 동기화에 쓰는 OSV/GHSA/NVD 피드 변환은 [`feed`]에 있습니다.
 
 pub mod db;
+pub mod feed;
 pub mod version;
 
 use std::sync::Arc;
@@ -0,0 +1,68 @@
+//! 채널 단언 헬퍼 — 타임아웃이 있는 수신과 빈 채널 확인
+//!
+//! 모듈 간 `tokio::mpsc` 채널을 검사할 때 테스트가 영원히 멈추지 않도록
+//! 모든 대기에 타임아웃을 둡니다. 실패하면 무엇을 기다렸는지 담아 패닉합니다.
+
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+/// 수신 대기 기본 타임아웃
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// `timeout` 안에 메시지 하나를 받아 반환합니다.
+///
+/// # Panics
+///
+/// 타임아웃이 지나거나 송신 측이 모두 닫히면 패닉합니다.
+pub async fn recv_within<T>(rx: &mut mpsc::Receiver<T>, timeout: Duration) -> T {
+    match tokio::time::timeout(timeout, rx.recv()).await {
+        Ok(Some(message)) => message,
+        Ok(None) => panic!("channel closed before a message arrived"),
+        Err(_) => panic!("no message received within {timeout:?}"),
+    }
+}
+
+/// 채널에 대기 중인 메시지가 없는지 확인합니다.
+///
+/// # Panics
+///
+/// 메시지가 남아 있으면 패닉합니다.
+pub fn assert_empty<T: std::fmt::Debug>(rx: &mut mpsc::Receiver<T>) {
+    if let Ok(message) = rx.try_recv() {
+        panic!("expected an empty channel, found {message:?}");
+    }
+}
+
+/// 현재 대기 중인 메시지를 모두 꺼냅니다 (기다리지 않습니다).
+pub fn drain<T>(rx: &mut mpsc::Receiver<T>) -> Vec<T> {
+    let mut messages = Vec::new();
+    while let Ok(message) = rx.try_recv() {
+        messages.push(message);
+    }
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_recv_within_and_drain() {
+        let (tx, mut rx) = mpsc::channel(4);
+        tx.send(1).await.expect("send");
+        tx.send(2).await.expect("send");
+        tx.send(3).await.expect("send");
+
+        assert_eq!(recv_within(&mut rx, DEFAULT_TIMEOUT).await, 1);
+        assert_eq!(drain(&mut rx), vec![2, 3]);
+        assert_empty(&mut rx);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "no message received")]
+    async fn test_recv_within_times_out() {
+        let (_tx, mut rx) = mpsc::channel::<u32>(1);
+        recv_within(&mut rx, Duration::from_millis(10)).await;
+    }
+}
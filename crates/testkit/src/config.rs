@@ -0,0 +1,121 @@
+//! 테스트 설정 빌더 — 모든 모듈이 꺼진 [`IronpostConfig`]에서 시작합니다
+//!
+//! 기본 설정은 로그 파이프라인이 켜져 있고 메트릭/헬스 리스너가 고정 포트를
+//! 사용하므로, 테스트끼리 포트가 충돌하거나 원치 않는 모듈이 뜨기 쉽습니다.
+//! [`TestConfig`]는 모든 모듈과 리스너, PID 파일을 끈 상태에서 시작하고
+//! 필요한 모듈만 켭니다.
+//!
+//! ```ignore
+//! let config = TestConfig::new()
+//!     .log_pipeline(true)
+//!     .with(|c| c.log_pipeline.batch_size = 10)
+//!     .build();
+//! ```
+
+use ironpost_core::config::IronpostConfig;
+
+/// 테스트용 [`IronpostConfig`] 빌더
+#[derive(Debug, Clone)]
+pub struct TestConfig {
+    config: IronpostConfig,
+}
+
+impl Default for TestConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestConfig {
+    /// 모든 모듈, 메트릭/헬스 리스너, PID 파일이 꺼진 설정으로 시작합니다.
+    pub fn new() -> Self {
+        let mut config = IronpostConfig::default();
+        config.general.pid_file = String::new();
+        config.metrics.enabled = false;
+        config.health.enabled = false;
+        config.ebpf.enabled = false;
+        config.log_pipeline.enabled = false;
+        config.container.enabled = false;
+        config.sbom.enabled = false;
+        Self { config }
+    }
+
+    /// eBPF 엔진 활성화 여부
+    pub fn ebpf(mut self, enabled: bool) -> Self {
+        self.config.ebpf.enabled = enabled;
+        self
+    }
+
+    /// 로그 파이프라인 활성화 여부
+    pub fn log_pipeline(mut self, enabled: bool) -> Self {
+        self.config.log_pipeline.enabled = enabled;
+        self
+    }
+
+    /// 컨테이너 가드 활성화 여부 (로그 파이프라인의 알림을 소비합니다)
+    pub fn container_guard(mut self, enabled: bool) -> Self {
+        self.config.container.enabled = enabled;
+        self
+    }
+
+    /// SBOM 스캐너 활성화 여부
+    pub fn sbom(mut self, enabled: bool) -> Self {
+        self.config.sbom.enabled = enabled;
+        self
+    }
+
+    /// 헬스 엔드포인트 활성화 여부
+    pub fn health(mut self, enabled: bool) -> Self {
+        self.config.health.enabled = enabled;
+        self
+    }
+
+    /// 데이터 디렉토리 (보통 `tempfile::TempDir` 경로)
+    pub fn data_dir(mut self, path: impl AsRef<std::path::Path>) -> Self {
+        self.config.general.data_dir = path.as_ref().display().to_string();
+        self
+    }
+
+    /// 빌더가 다루지 않는 필드를 직접 수정합니다.
+    pub fn with(mut self, f: impl FnOnce(&mut IronpostConfig)) -> Self {
+        f(&mut self.config);
+        self
+    }
+
+    /// 설정을 반환합니다.
+    pub fn build(self) -> IronpostConfig {
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_disables_modules_and_listeners() {
+        let config = TestConfig::new().build();
+
+        assert!(!config.ebpf.enabled);
+        assert!(!config.log_pipeline.enabled);
+        assert!(!config.container.enabled);
+        assert!(!config.sbom.enabled);
+        assert!(!config.metrics.enabled);
+        assert!(!config.health.enabled);
+        assert!(config.general.pid_file.is_empty());
+    }
+
+    #[test]
+    fn test_builder_enables_selected_modules() {
+        let config = TestConfig::new()
+            .log_pipeline(true)
+            .container_guard(true)
+            .with(|c| c.log_pipeline.batch_size = 10)
+            .build();
+
+        assert!(config.log_pipeline.enabled);
+        assert!(config.container.enabled);
+        assert!(!config.ebpf.enabled);
+        assert_eq!(config.log_pipeline.batch_size, 10);
+    }
+}
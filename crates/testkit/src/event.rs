@@ -0,0 +1,371 @@
+//! 이벤트 팩토리 — 테스트용 `AlertEvent`/`PacketEvent`/`ActionEvent` 빌더
+//!
+//! 모든 필드에 그럴듯한 기본값이 채워져 있으므로 테스트는 관심 있는 필드만
+//! 지정하면 됩니다. 기본 trace ID는 매번 새로 생성하며, `trace_id()`로 고정하면
+//! 여러 이벤트를 같은 trace로 묶을 수 있습니다.
+//!
+//! ```ignore
+//! let alert = AlertEventBuilder::new("ssh-bruteforce")
+//!     .severity(Severity::High)
+//!     .source_ip("203.0.113.7".parse()?)
+//!     .build();
+//! let packet = PacketEventBuilder::tcp("10.0.0.5", 443).trace_id(&alert.metadata.trace_id).build();
+//! ```
+
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::SystemTime;
+
+use bytes::Bytes;
+
+use ironpost_core::event::{
+    ActionEvent, AlertEvent, EventMetadata, MODULE_CONTAINER_GUARD, MODULE_EBPF,
+    MODULE_LOG_PIPELINE, PacketEvent,
+};
+use ironpost_core::types::{Alert, PacketInfo, Severity};
+
+/// 프로토콜 번호: TCP
+const PROTO_TCP: u8 = 6;
+/// 프로토콜 번호: UDP
+const PROTO_UDP: u8 = 17;
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+fn metadata(source_module: String, trace_id: Option<String>) -> EventMetadata {
+    match trace_id {
+        Some(trace_id) => EventMetadata::new(source_module, trace_id),
+        None => EventMetadata::with_new_trace(source_module),
+    }
+}
+
+// ─── AlertEvent ──────────────────────────────────────────────────────
+
+/// [`AlertEvent`] 빌더
+///
+/// 기본값: 심각도 `Medium`, source module `log-pipeline`, IP 없음.
+#[derive(Debug, Clone)]
+pub struct AlertEventBuilder {
+    rule_name: String,
+    title: String,
+    description: String,
+    severity: Severity,
+    source_ip: Option<IpAddr>,
+    target_ip: Option<IpAddr>,
+    source_module: String,
+    trace_id: Option<String>,
+}
+
+impl AlertEventBuilder {
+    /// 탐지 규칙 `rule_name`으로 발생한 알림 빌더를 생성합니다.
+    pub fn new(rule_name: &str) -> Self {
+        Self {
+            rule_name: rule_name.to_owned(),
+            title: format!("Test alert: {rule_name}"),
+            description: "Test alert".to_owned(),
+            severity: Severity::Medium,
+            source_ip: None,
+            target_ip: None,
+            source_module: MODULE_LOG_PIPELINE.to_owned(),
+            trace_id: None,
+        }
+    }
+
+    /// 알림 제목
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.to_owned();
+        self
+    }
+
+    /// 상세 설명
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_owned();
+        self
+    }
+
+    /// 심각도 (알림과 이벤트 양쪽에 적용)
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// 관련 소스 IP
+    pub fn source_ip(mut self, ip: IpAddr) -> Self {
+        self.source_ip = Some(ip);
+        self
+    }
+
+    /// 관련 대상 IP
+    pub fn target_ip(mut self, ip: IpAddr) -> Self {
+        self.target_ip = Some(ip);
+        self
+    }
+
+    /// 이벤트를 발생시킨 모듈 (기본: `log-pipeline`)
+    pub fn source_module(mut self, module: &str) -> Self {
+        self.source_module = module.to_owned();
+        self
+    }
+
+    /// 기존 trace에 연결합니다.
+    pub fn trace_id(mut self, trace_id: &str) -> Self {
+        self.trace_id = Some(trace_id.to_owned());
+        self
+    }
+
+    /// 알림 이벤트를 생성합니다.
+    pub fn build(self) -> AlertEvent {
+        AlertEvent {
+            id: new_id(),
+            metadata: metadata(self.source_module, self.trace_id),
+            alert: Alert {
+                id: new_id(),
+                title: self.title,
+                description: self.description,
+                severity: self.severity,
+                rule_name: self.rule_name,
+                source_ip: self.source_ip,
+                target_ip: self.target_ip,
+                created_at: SystemTime::now(),
+            },
+            severity: self.severity,
+        }
+    }
+}
+
+// ─── PacketEvent ─────────────────────────────────────────────────────
+
+/// [`PacketEvent`] 빌더
+///
+/// 기본값: `192.168.1.100:54321 → 10.0.0.1:80` TCP, 60바이트, source module `ebpf-engine`.
+#[derive(Debug, Clone)]
+pub struct PacketEventBuilder {
+    info: PacketInfo,
+    raw_data: Bytes,
+    source_module: String,
+    trace_id: Option<String>,
+    sni: Option<String>,
+}
+
+impl Default for PacketEventBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PacketEventBuilder {
+    /// 기본값으로 채운 패킷 빌더를 생성합니다.
+    pub fn new() -> Self {
+        Self {
+            info: PacketInfo {
+                src_ip: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 100)),
+                dst_ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                src_port: 54321,
+                dst_port: 80,
+                protocol: PROTO_TCP,
+                size: 60,
+                timestamp: SystemTime::now(),
+            },
+            raw_data: Bytes::new(),
+            source_module: MODULE_EBPF.to_owned(),
+            trace_id: None,
+            sni: None,
+        }
+    }
+
+    /// `src_ip`에서 출발한 TCP 패킷 빌더를 생성합니다.
+    ///
+    /// # Panics
+    ///
+    /// `src_ip`가 올바른 IP 주소가 아니면 패닉합니다.
+    pub fn tcp(src_ip: &str, dst_port: u16) -> Self {
+        Self::new().src_ip(parse_ip(src_ip)).dst_port(dst_port)
+    }
+
+    /// `src_ip`에서 출발한 UDP 패킷 빌더를 생성합니다.
+    ///
+    /// # Panics
+    ///
+    /// `src_ip`가 올바른 IP 주소가 아니면 패닉합니다.
+    pub fn udp(src_ip: &str, dst_port: u16) -> Self {
+        Self::new()
+            .src_ip(parse_ip(src_ip))
+            .dst_port(dst_port)
+            .protocol(PROTO_UDP)
+    }
+
+    /// 출발지 IP
+    pub fn src_ip(mut self, ip: IpAddr) -> Self {
+        self.info.src_ip = ip;
+        self
+    }
+
+    /// 목적지 IP
+    pub fn dst_ip(mut self, ip: IpAddr) -> Self {
+        self.info.dst_ip = ip;
+        self
+    }
+
+    /// 출발지 포트
+    pub fn src_port(mut self, port: u16) -> Self {
+        self.info.src_port = port;
+        self
+    }
+
+    /// 목적지 포트
+    pub fn dst_port(mut self, port: u16) -> Self {
+        self.info.dst_port = port;
+        self
+    }
+
+    /// 프로토콜 번호 (TCP=6, UDP=17 등)
+    pub fn protocol(mut self, protocol: u8) -> Self {
+        self.info.protocol = protocol;
+        self
+    }
+
+    /// 원시 패킷 데이터 (패킷 크기도 데이터 길이로 맞춥니다)
+    pub fn raw_data(mut self, data: &'static [u8]) -> Self {
+        self.info.size = data.len();
+        self.raw_data = Bytes::from_static(data);
+        self
+    }
+
+    /// 이벤트를 발생시킨 모듈 (기본: `ebpf-engine`)
+    pub fn source_module(mut self, module: &str) -> Self {
+        self.source_module = module.to_owned();
+        self
+    }
+
+    /// 기존 trace에 연결합니다.
+    pub fn trace_id(mut self, trace_id: &str) -> Self {
+        self.trace_id = Some(trace_id.to_owned());
+        self
+    }
+
+    /// TLS SNI 호스트 이름
+    pub fn sni(mut self, sni: &str) -> Self {
+        self.sni = Some(sni.to_owned());
+        self
+    }
+
+    /// 패킷 이벤트를 생성합니다.
+    pub fn build(self) -> PacketEvent {
+        PacketEvent {
+            id: new_id(),
+            metadata: metadata(self.source_module, self.trace_id),
+            packet_info: self.info,
+            raw_data: self.raw_data,
+            sni: self.sni,
+        }
+    }
+}
+
+fn parse_ip(ip: &str) -> IpAddr {
+    ip.parse()
+        .unwrap_or_else(|e| panic!("invalid test IP address '{ip}': {e}"))
+}
+
+// ─── ActionEvent ─────────────────────────────────────────────────────
+
+/// [`ActionEvent`] 빌더
+///
+/// 기본값: 성공, 실제 실행(시뮬레이션 아님), source module `container-guard`.
+#[derive(Debug, Clone)]
+pub struct ActionEventBuilder {
+    action_type: String,
+    target: String,
+    success: bool,
+    simulated: bool,
+    source_module: String,
+    trace_id: Option<String>,
+}
+
+impl ActionEventBuilder {
+    /// `target`에 대한 `action_type` 액션 빌더를 생성합니다.
+    pub fn new(action_type: &str, target: &str) -> Self {
+        Self {
+            action_type: action_type.to_owned(),
+            target: target.to_owned(),
+            success: true,
+            simulated: false,
+            source_module: MODULE_CONTAINER_GUARD.to_owned(),
+            trace_id: None,
+        }
+    }
+
+    /// 액션을 실패로 표시합니다.
+    pub fn failed(mut self) -> Self {
+        self.success = false;
+        self
+    }
+
+    /// dry-run으로 시뮬레이션된 액션으로 표시합니다.
+    pub fn simulated(mut self) -> Self {
+        self.simulated = true;
+        self
+    }
+
+    /// 이벤트를 발생시킨 모듈 (기본: `container-guard`)
+    pub fn source_module(mut self, module: &str) -> Self {
+        self.source_module = module.to_owned();
+        self
+    }
+
+    /// 기존 trace에 연결합니다.
+    pub fn trace_id(mut self, trace_id: &str) -> Self {
+        self.trace_id = Some(trace_id.to_owned());
+        self
+    }
+
+    /// 액션 이벤트를 생성합니다.
+    pub fn build(self) -> ActionEvent {
+        ActionEvent {
+            id: new_id(),
+            metadata: metadata(self.source_module, self.trace_id),
+            action_type: self.action_type,
+            target: self.target,
+            success: self.success,
+            simulated: self.simulated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_builder_defaults_and_overrides() {
+        let alert = AlertEventBuilder::new("ssh-bruteforce")
+            .severity(Severity::High)
+            .source_ip(parse_ip("203.0.113.7"))
+            .build();
+
+        assert_eq!(alert.alert.rule_name, "ssh-bruteforce");
+        assert_eq!(alert.severity, Severity::High);
+        assert_eq!(alert.alert.severity, Severity::High);
+        assert_eq!(alert.alert.source_ip, Some(parse_ip("203.0.113.7")));
+        assert_eq!(alert.metadata.source_module, MODULE_LOG_PIPELINE);
+        assert!(!alert.metadata.trace_id.is_empty());
+    }
+
+    #[test]
+    fn test_events_share_trace_id() {
+        let alert = AlertEventBuilder::new("rule").build();
+        let packet = PacketEventBuilder::udp("10.0.0.5", 53)
+            .raw_data(b"query")
+            .trace_id(&alert.metadata.trace_id)
+            .build();
+        let action = ActionEventBuilder::new("isolate", "abc123")
+            .simulated()
+            .trace_id(&alert.metadata.trace_id)
+            .build();
+
+        assert_eq!(packet.metadata.trace_id, alert.metadata.trace_id);
+        assert_eq!(action.metadata.trace_id, alert.metadata.trace_id);
+        assert_eq!(packet.packet_info.protocol, PROTO_UDP);
+        assert_eq!(packet.packet_info.size, 5);
+        assert!(action.simulated && action.success);
+    }
+}
@@ -0,0 +1,302 @@
+//! 인메모리 오케스트레이터 하네스
+//!
+//! 데몬 오케스트레이터와 같은 방식으로 플러그인을 [`PluginRegistry`]에 등록
+//! 순서대로 초기화/시작/정지하고, 모듈 간 이벤트 채널을 만들어 둡니다.
+//! 소켓, PID 파일, 시그널 처리 없이 메모리 안에서만 동작하므로 모듈 크레이트나
+//! 외부 플러그인이 데몬을 띄우지 않고 통합 테스트를 작성할 수 있습니다.
+//!
+//! ```text
+//! packet_tx ──▶ packet_rx   (eBPF → 로그 파이프라인)
+//! alert_tx  ──▶ alert_rx    (로그 파이프라인/스캐너 → 컨테이너 가드)
+//! action_tx ──▶ action_rx   (컨테이너 가드 → 로거)
+//! ```
+//!
+//! 테스트 대상 모듈이 소비자라면 `take_*_rx()`로 수신 측을 넘겨주고, 생산자라면
+//! `*_tx()`를 넘겨준 뒤 하네스의 `recv_*()`로 출력을 확인합니다.
+//!
+//! ```ignore
+//! let mut harness = Harness::builder().capacity(16).build();
+//! let guard = ContainerGuardBuilder::new().alert_receiver(harness.take_alert_rx().unwrap()).build()?;
+//! harness.register(guard)?;
+//! harness.start().await?;
+//!
+//! harness.alert_tx().send(AlertEventBuilder::new("rule").build()).await?;
+//! let action = harness.recv_action().await;
+//! harness.stop().await?;
+//! ```
+
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use ironpost_core::config::IronpostConfig;
+use ironpost_core::error::IronpostError;
+use ironpost_core::event::{ActionEvent, AlertEvent, PacketEvent};
+use ironpost_core::pipeline::HealthStatus;
+use ironpost_core::plugin::{Plugin, PluginRegistry, PluginState};
+
+use crate::channel::{DEFAULT_TIMEOUT, recv_within};
+use crate::config::TestConfig;
+
+/// 채널 용량 기본값
+const DEFAULT_CAPACITY: usize = 64;
+
+/// [`Harness`] 빌더
+#[derive(Debug)]
+pub struct HarnessBuilder {
+    capacity: usize,
+    timeout: Duration,
+    config: Option<IronpostConfig>,
+}
+
+impl Default for HarnessBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HarnessBuilder {
+    /// 기본 채널 용량과 타임아웃으로 시작합니다.
+    pub fn new() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            timeout: DEFAULT_TIMEOUT,
+            config: None,
+        }
+    }
+
+    /// 모든 이벤트 채널의 용량 (백프레셔 테스트에 작게 설정합니다)
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// `recv_*()`의 수신 대기 타임아웃
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 플러그인 생성에 사용할 설정 (기본: [`TestConfig::new`])
+    pub fn config(mut self, config: IronpostConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// 하네스를 생성합니다.
+    pub fn build(self) -> Harness {
+        let (packet_tx, packet_rx) = mpsc::channel(self.capacity);
+        let (alert_tx, alert_rx) = mpsc::channel(self.capacity);
+        let (action_tx, action_rx) = mpsc::channel(self.capacity);
+        Harness {
+            config: self.config.unwrap_or_else(|| TestConfig::new().build()),
+            registry: PluginRegistry::new(),
+            timeout: self.timeout,
+            packet_tx,
+            packet_rx: Some(packet_rx),
+            alert_tx,
+            alert_rx: Some(alert_rx),
+            action_tx,
+            action_rx: Some(action_rx),
+        }
+    }
+}
+
+/// 인메모리 오케스트레이터
+pub struct Harness {
+    config: IronpostConfig,
+    registry: PluginRegistry,
+    timeout: Duration,
+    packet_tx: mpsc::Sender<PacketEvent>,
+    packet_rx: Option<mpsc::Receiver<PacketEvent>>,
+    alert_tx: mpsc::Sender<AlertEvent>,
+    alert_rx: Option<mpsc::Receiver<AlertEvent>>,
+    action_tx: mpsc::Sender<ActionEvent>,
+    action_rx: Option<mpsc::Receiver<ActionEvent>>,
+}
+
+impl Harness {
+    /// 빌더를 반환합니다.
+    pub fn builder() -> HarnessBuilder {
+        HarnessBuilder::new()
+    }
+
+    /// 플러그인 생성에 사용할 설정
+    pub fn config(&self) -> &IronpostConfig {
+        &self.config
+    }
+
+    /// 플러그인을 등록합니다. 생산자를 먼저 등록해야 정지 시 소비자가 드레인할 수 있습니다.
+    ///
+    /// # Errors
+    ///
+    /// 같은 이름의 플러그인이 이미 있으면 `PluginError::AlreadyRegistered`를 반환합니다.
+    pub fn register(&mut self, plugin: impl Plugin + 'static) -> Result<(), IronpostError> {
+        self.registry.register(Box::new(plugin))
+    }
+
+    /// 내부 레지스트리 (개별 플러그인 조회/제어용)
+    pub fn registry(&mut self) -> &mut PluginRegistry {
+        &mut self.registry
+    }
+
+    /// 모든 플러그인을 등록 순서대로 초기화하고 시작합니다.
+    ///
+    /// # Errors
+    ///
+    /// 첫 번째로 실패한 플러그인의 에러를 반환합니다. 이미 시작된 플러그인은
+    /// 데몬과 마찬가지로 [`Harness::stop`]으로 정리해야 합니다.
+    pub async fn start(&mut self) -> Result<(), IronpostError> {
+        self.registry.init_all().await?;
+        self.registry.start_all().await
+    }
+
+    /// 실행 중인 플러그인을 등록 순서대로 정지합니다.
+    ///
+    /// # Errors
+    ///
+    /// 정지에 실패한 플러그인의 에러를 모아 반환합니다.
+    pub async fn stop(&mut self) -> Result<(), IronpostError> {
+        self.registry.stop_all().await
+    }
+
+    /// 플러그인별 (이름, 상태, 헬스)
+    pub async fn health(&self) -> Vec<(String, PluginState, HealthStatus)> {
+        self.registry.health_check_all().await
+    }
+
+    /// 가장 나쁜 플러그인 헬스 (Unhealthy > Degraded > Healthy), 사유는 `이름: 사유`로 모읍니다.
+    pub async fn overall_health(&self) -> HealthStatus {
+        let mut degraded = Vec::new();
+        let mut unhealthy = Vec::new();
+        for (name, _, status) in self.health().await {
+            match status {
+                HealthStatus::Healthy => {}
+                HealthStatus::Degraded(reason) => degraded.push(format!("{name}: {reason}")),
+                HealthStatus::Unhealthy(reason) => unhealthy.push(format!("{name}: {reason}")),
+            }
+        }
+        if !unhealthy.is_empty() {
+            HealthStatus::Unhealthy(unhealthy.join("; "))
+        } else if !degraded.is_empty() {
+            HealthStatus::Degraded(degraded.join("; "))
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+
+    /// 패킷 이벤트 송신 측
+    pub fn packet_tx(&self) -> mpsc::Sender<PacketEvent> {
+        self.packet_tx.clone()
+    }
+
+    /// 패킷 이벤트 수신 측을 꺼냅니다 (소비자 모듈에 넘길 때). 두 번째 호출부터 `None`.
+    pub fn take_packet_rx(&mut self) -> Option<mpsc::Receiver<PacketEvent>> {
+        self.packet_rx.take()
+    }
+
+    /// 알림 이벤트 송신 측
+    pub fn alert_tx(&self) -> mpsc::Sender<AlertEvent> {
+        self.alert_tx.clone()
+    }
+
+    /// 알림 이벤트 수신 측을 꺼냅니다. 두 번째 호출부터 `None`.
+    pub fn take_alert_rx(&mut self) -> Option<mpsc::Receiver<AlertEvent>> {
+        self.alert_rx.take()
+    }
+
+    /// 액션 이벤트 송신 측
+    pub fn action_tx(&self) -> mpsc::Sender<ActionEvent> {
+        self.action_tx.clone()
+    }
+
+    /// 액션 이벤트 수신 측을 꺼냅니다. 두 번째 호출부터 `None`.
+    pub fn take_action_rx(&mut self) -> Option<mpsc::Receiver<ActionEvent>> {
+        self.action_rx.take()
+    }
+
+    /// 패킷 이벤트 하나를 기다립니다.
+    ///
+    /// # Panics
+    ///
+    /// 수신 측을 이미 꺼냈거나 타임아웃 안에 이벤트가 오지 않으면 패닉합니다.
+    pub async fn recv_packet(&mut self) -> PacketEvent {
+        let rx = self
+            .packet_rx
+            .as_mut()
+            .expect("packet receiver was taken by take_packet_rx()");
+        recv_within(rx, self.timeout).await
+    }
+
+    /// 알림 이벤트 하나를 기다립니다.
+    ///
+    /// # Panics
+    ///
+    /// 수신 측을 이미 꺼냈거나 타임아웃 안에 이벤트가 오지 않으면 패닉합니다.
+    pub async fn recv_alert(&mut self) -> AlertEvent {
+        let rx = self
+            .alert_rx
+            .as_mut()
+            .expect("alert receiver was taken by take_alert_rx()");
+        recv_within(rx, self.timeout).await
+    }
+
+    /// 액션 이벤트 하나를 기다립니다.
+    ///
+    /// # Panics
+    ///
+    /// 수신 측을 이미 꺼냈거나 타임아웃 안에 이벤트가 오지 않으면 패닉합니다.
+    pub async fn recv_action(&mut self) -> ActionEvent {
+        let rx = self
+            .action_rx
+            .as_mut()
+            .expect("action receiver was taken by take_action_rx()");
+        recv_within(rx, self.timeout).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AlertEventBuilder;
+    use crate::mock::MockPipeline;
+
+    #[tokio::test]
+    async fn test_lifecycle_in_registration_order() {
+        let mut harness = Harness::builder().build();
+        let producer = MockPipeline::new("producer");
+        let consumer = MockPipeline::new("consumer").failing_stop();
+        let (producer_handle, consumer_handle) = (producer.handle(), consumer.handle());
+        harness.register(producer).expect("register");
+        harness.register(consumer).expect("register");
+
+        harness.start().await.expect("start");
+        assert!(harness.overall_health().await.is_healthy());
+
+        consumer_handle.set_health(HealthStatus::Degraded("lagging".to_owned()));
+        assert_eq!(
+            harness.overall_health().await,
+            HealthStatus::Degraded("consumer: lagging".to_owned())
+        );
+
+        let err = harness.stop().await.expect_err("consumer stop fails");
+        assert!(err.to_string().contains("consumer"));
+        assert_eq!(producer_handle.stop_calls(), 1);
+        assert_eq!(consumer_handle.stop_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_channels_round_trip() {
+        let mut harness = Harness::builder().capacity(2).build();
+
+        harness
+            .alert_tx()
+            .send(AlertEventBuilder::new("rule-a").build())
+            .await
+            .expect("send");
+        assert_eq!(harness.recv_alert().await.alert.rule_name, "rule-a");
+
+        assert!(harness.take_packet_rx().is_some());
+        assert!(harness.take_packet_rx().is_none());
+    }
+}
@@ -0,0 +1,21 @@
+#![doc = include_str!("../README.md")]
+//!
+//! # 모듈 구성
+//!
+//! - [`mock`]: 상태와 호출 횟수를 관찰할 수 있는 Mock 파이프라인/플러그인
+//! - [`event`]: `AlertEvent`/`PacketEvent`/`ActionEvent` 빌더
+//! - [`config`]: 모든 모듈이 꺼진 상태에서 시작하는 테스트 설정 빌더
+//! - [`channel`]: 타임아웃이 있는 채널 수신/단언 헬퍼
+//! - [`harness`]: `PluginRegistry`와 모듈 간 채널을 묶은 인메모리 오케스트레이터
+
+pub mod channel;
+pub mod config;
+pub mod event;
+pub mod harness;
+pub mod mock;
+
+pub use channel::{DEFAULT_TIMEOUT, assert_empty, drain, recv_within};
+pub use config::TestConfig;
+pub use event::{ActionEventBuilder, AlertEventBuilder, PacketEventBuilder};
+pub use harness::{Harness, HarnessBuilder};
+pub use mock::{MockHandle, MockPipeline};
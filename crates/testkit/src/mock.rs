@@ -0,0 +1,264 @@
+//! Mock 파이프라인 — 생명주기 호출과 헬스 상태를 테스트에서 제어합니다
+//!
+//! [`MockPipeline`]은 [`Pipeline`]과 [`Plugin`]을 모두 구현하므로
+//! `Box<dyn DynPipeline>`이나 [`PluginRegistry`](ironpost_core::plugin::PluginRegistry)에
+//! 그대로 넣을 수 있습니다. 레지스트리로 소유권이 넘어간 뒤에도
+//! [`MockHandle`]로 호출 횟수를 관찰하고 헬스 상태를 바꿀 수 있습니다.
+//!
+//! ```ignore
+//! let mock = MockPipeline::new("log-pipeline").failing_stop();
+//! let handle = mock.handle();
+//! registry.register(Box::new(mock))?;
+//!
+//! registry.start_all().await?;
+//! handle.set_health(HealthStatus::Degraded("buffer full".to_owned()));
+//! assert_eq!(handle.start_calls(), 1);
+//! ```
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use ironpost_core::error::{IronpostError, PipelineError};
+use ironpost_core::pipeline::{HealthStatus, Pipeline};
+use ironpost_core::plugin::{Plugin, PluginInfo, PluginState, PluginType};
+
+/// Mock 파이프라인과 공유하는 관찰/제어 상태
+#[derive(Debug, Default)]
+struct Shared {
+    init_calls: AtomicU32,
+    start_calls: AtomicU32,
+    stop_calls: AtomicU32,
+    health: Mutex<Option<HealthStatus>>,
+}
+
+/// 레지스트리에 등록된 [`MockPipeline`]을 관찰하고 제어하는 핸들
+#[derive(Debug, Clone)]
+pub struct MockHandle {
+    shared: Arc<Shared>,
+}
+
+impl MockHandle {
+    /// `init()` 호출 횟수
+    pub fn init_calls(&self) -> u32 {
+        self.shared.init_calls.load(Ordering::SeqCst)
+    }
+
+    /// `start()` 호출 횟수
+    pub fn start_calls(&self) -> u32 {
+        self.shared.start_calls.load(Ordering::SeqCst)
+    }
+
+    /// `stop()` 호출 횟수
+    pub fn stop_calls(&self) -> u32 {
+        self.shared.stop_calls.load(Ordering::SeqCst)
+    }
+
+    /// 이후 `health_check()`가 반환할 상태를 고정합니다.
+    pub fn set_health(&self, status: HealthStatus) {
+        *self
+            .shared
+            .health
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(status);
+    }
+
+    /// 고정한 헬스 상태를 해제하고 생명주기 상태에서 유도하도록 되돌립니다.
+    pub fn clear_health(&self) {
+        *self
+            .shared
+            .health
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    fn health_override(&self) -> Option<HealthStatus> {
+        self.shared
+            .health
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// 테스트용 Mock 파이프라인
+///
+/// 기본적으로 모든 생명주기 호출이 성공하며, `failing_*` 메서드로 실패를 주입합니다.
+/// 헬스 상태는 [`MockHandle::set_health`]로 고정하지 않으면 생명주기 상태에서
+/// 유도합니다 (`Running` → Healthy, `Failed` → Unhealthy, 그 외 → Degraded).
+#[derive(Debug)]
+pub struct MockPipeline {
+    info: PluginInfo,
+    state: PluginState,
+    fail_on_init: bool,
+    fail_on_start: bool,
+    fail_on_stop: bool,
+    handle: MockHandle,
+}
+
+impl MockPipeline {
+    /// 이름이 `name`인 `Custom` 유형 Mock을 생성합니다.
+    pub fn new(name: &str) -> Self {
+        Self::with_type(name, PluginType::Custom("mock".to_owned()))
+    }
+
+    /// 플러그인 유형을 지정해 Mock을 생성합니다.
+    ///
+    /// 데몬의 단계별 종료처럼 유형으로 플러그인을 고르는 로직을 테스트할 때 사용합니다.
+    pub fn with_type(name: &str, plugin_type: PluginType) -> Self {
+        Self {
+            info: PluginInfo {
+                name: name.to_owned(),
+                version: "0.1.0".to_owned(),
+                description: format!("Mock pipeline: {name}"),
+                plugin_type,
+            },
+            state: PluginState::Created,
+            fail_on_init: false,
+            fail_on_start: false,
+            fail_on_stop: false,
+            handle: MockHandle {
+                shared: Arc::new(Shared::default()),
+            },
+        }
+    }
+
+    /// `init()`이 실패하도록 설정합니다.
+    pub fn failing_init(mut self) -> Self {
+        self.fail_on_init = true;
+        self
+    }
+
+    /// `start()`가 실패하도록 설정합니다.
+    pub fn failing_start(mut self) -> Self {
+        self.fail_on_start = true;
+        self
+    }
+
+    /// `stop()`이 실패하도록 설정합니다.
+    pub fn failing_stop(mut self) -> Self {
+        self.fail_on_stop = true;
+        self
+    }
+
+    /// 관찰/제어 핸들을 반환합니다 (Mock을 등록하기 전에 꺼내 둡니다).
+    pub fn handle(&self) -> MockHandle {
+        self.handle.clone()
+    }
+
+    fn transition(
+        &mut self,
+        fail: bool,
+        next: PluginState,
+        what: &str,
+    ) -> Result<(), IronpostError> {
+        if fail {
+            self.state = PluginState::Failed;
+            return Err(PipelineError::InitFailed(format!(
+                "mock {} failure: {}",
+                what, self.info.name
+            ))
+            .into());
+        }
+        self.state = next;
+        Ok(())
+    }
+
+    fn current_health(&self) -> HealthStatus {
+        if let Some(status) = self.handle.health_override() {
+            return status;
+        }
+        match self.state {
+            PluginState::Running => HealthStatus::Healthy,
+            PluginState::Failed => HealthStatus::Unhealthy("failed".to_owned()),
+            _ => HealthStatus::Degraded("not running".to_owned()),
+        }
+    }
+}
+
+impl Plugin for MockPipeline {
+    fn info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    fn state(&self) -> PluginState {
+        self.state
+    }
+
+    async fn init(&mut self) -> Result<(), IronpostError> {
+        self.handle.shared.init_calls.fetch_add(1, Ordering::SeqCst);
+        self.transition(self.fail_on_init, PluginState::Initialized, "init")
+    }
+
+    async fn start(&mut self) -> Result<(), IronpostError> {
+        self.handle
+            .shared
+            .start_calls
+            .fetch_add(1, Ordering::SeqCst);
+        self.transition(self.fail_on_start, PluginState::Running, "start")
+    }
+
+    async fn stop(&mut self) -> Result<(), IronpostError> {
+        self.handle.shared.stop_calls.fetch_add(1, Ordering::SeqCst);
+        self.transition(self.fail_on_stop, PluginState::Stopped, "stop")
+    }
+
+    async fn health_check(&self) -> HealthStatus {
+        self.current_health()
+    }
+}
+
+impl Pipeline for MockPipeline {
+    async fn start(&mut self) -> Result<(), IronpostError> {
+        Plugin::start(self).await
+    }
+
+    async fn stop(&mut self) -> Result<(), IronpostError> {
+        Plugin::stop(self).await
+    }
+
+    async fn health_check(&self) -> HealthStatus {
+        self.current_health()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_core::pipeline::DynPipeline;
+
+    #[tokio::test]
+    async fn test_handle_tracks_calls_after_boxing() {
+        let mock = MockPipeline::new("mock");
+        let handle = mock.handle();
+        let mut boxed: Box<dyn DynPipeline> = Box::new(mock);
+
+        boxed.start().await.expect("start should succeed");
+        assert!(boxed.health_check().await.is_healthy());
+        boxed.stop().await.expect("stop should succeed");
+
+        assert_eq!(handle.start_calls(), 1);
+        assert_eq!(handle.stop_calls(), 1);
+        assert_eq!(handle.init_calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failure_injection_and_health_override() {
+        let mut mock = MockPipeline::new("mock").failing_start();
+        let handle = mock.handle();
+
+        assert!(Plugin::start(&mut mock).await.is_err());
+        assert_eq!(Plugin::state(&mock), PluginState::Failed);
+        assert!(matches!(
+            Plugin::health_check(&mock).await,
+            HealthStatus::Unhealthy(_)
+        ));
+
+        handle.set_health(HealthStatus::Degraded("slow".to_owned()));
+        assert_eq!(
+            Plugin::health_check(&mock).await,
+            HealthStatus::Degraded("slow".to_owned())
+        );
+        handle.clear_health();
+        assert!(Plugin::health_check(&mock).await.is_unhealthy());
+    }
+}
@@ -0,0 +1,139 @@
+//! 하네스 통합 테스트 — 채널을 소비하는 플러그인을 하네스에 연결합니다.
+
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use ironpost_core::error::IronpostError;
+use ironpost_core::event::{ActionEvent, AlertEvent};
+use ironpost_core::pipeline::HealthStatus;
+use ironpost_core::plugin::{Plugin, PluginInfo, PluginState, PluginType};
+use ironpost_core::types::Severity;
+use ironpost_testkit::{AlertEventBuilder, Harness, MockPipeline, assert_empty};
+
+/// High 이상 알림마다 격리 액션을 내보내는 테스트용 소비자
+struct Isolator {
+    info: PluginInfo,
+    state: PluginState,
+    alert_rx: Option<mpsc::Receiver<AlertEvent>>,
+    action_tx: mpsc::Sender<ActionEvent>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl Isolator {
+    fn new(alert_rx: mpsc::Receiver<AlertEvent>, action_tx: mpsc::Sender<ActionEvent>) -> Self {
+        Self {
+            info: PluginInfo {
+                name: "isolator".to_owned(),
+                version: "0.1.0".to_owned(),
+                description: "test consumer".to_owned(),
+                plugin_type: PluginType::Enforcer,
+            },
+            state: PluginState::Created,
+            alert_rx: Some(alert_rx),
+            action_tx,
+            task: None,
+        }
+    }
+}
+
+impl Plugin for Isolator {
+    fn info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    fn state(&self) -> PluginState {
+        self.state
+    }
+
+    async fn init(&mut self) -> Result<(), IronpostError> {
+        self.state = PluginState::Initialized;
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<(), IronpostError> {
+        let mut rx = self.alert_rx.take().expect("started once");
+        let tx = self.action_tx.clone();
+        self.task = Some(tokio::spawn(async move {
+            while let Some(alert) = rx.recv().await {
+                if alert.severity >= Severity::High {
+                    let target = alert.alert.rule_name.clone();
+                    let action = ActionEvent::with_trace(
+                        "isolate",
+                        target,
+                        true,
+                        alert.metadata.trace_id.clone(),
+                    );
+                    if tx.send(action).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }));
+        self.state = PluginState::Running;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<(), IronpostError> {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.state = PluginState::Stopped;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> HealthStatus {
+        match self.state {
+            PluginState::Running => HealthStatus::Healthy,
+            _ => HealthStatus::Degraded("not running".to_owned()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_harness_drives_consumer_plugin() {
+    // Given: A producer mock and a consumer wired to the harness channels
+    let mut harness = Harness::builder().capacity(8).build();
+    let alert_rx = harness.take_alert_rx().expect("alert receiver");
+    let consumer = Isolator::new(alert_rx, harness.action_tx());
+    let producer = MockPipeline::with_type("producer", PluginType::LogPipeline);
+    let producer_handle = producer.handle();
+    harness.register(producer).expect("register producer");
+    harness.register(consumer).expect("register consumer");
+    harness.start().await.expect("start");
+
+    // When: One low and one high severity alert are sent
+    let alert_tx = harness.alert_tx();
+    alert_tx
+        .send(
+            AlertEventBuilder::new("port-scan")
+                .severity(Severity::Low)
+                .build(),
+        )
+        .await
+        .expect("send");
+    let high = AlertEventBuilder::new("ssh-bruteforce")
+        .severity(Severity::High)
+        .build();
+    let trace_id = high.metadata.trace_id.clone();
+    alert_tx.send(high).await.expect("send");
+
+    // Then: Only the high severity alert becomes an action on the same trace
+    let action = harness.recv_action().await;
+    assert_eq!(action.action_type, "isolate");
+    assert_eq!(action.target, "ssh-bruteforce");
+    assert_eq!(action.metadata.trace_id, trace_id);
+
+    let mut action_rx = harness.take_action_rx().expect("action receiver");
+    assert_empty(&mut action_rx);
+
+    assert!(harness.overall_health().await.is_healthy());
+    harness.stop().await.expect("stop");
+    assert_eq!(producer_handle.stop_calls(), 1);
+    let states: Vec<PluginState> = harness
+        .health()
+        .await
+        .into_iter()
+        .map(|(_, state, _)| state)
+        .collect();
+    assert_eq!(states, vec![PluginState::Stopped, PluginState::Stopped]);
+}
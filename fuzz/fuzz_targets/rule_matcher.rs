@@ -43,6 +43,7 @@ enum FuzzModifier {
     StartsWith,
     EndsWith,
     Regex,
+    Cidr,
 }
 
 impl FuzzField {
@@ -64,6 +65,7 @@ impl FuzzModifier {
             FuzzModifier::StartsWith => ConditionModifier::StartsWith,
             FuzzModifier::EndsWith => ConditionModifier::EndsWith,
             FuzzModifier::Regex => ConditionModifier::Regex,
+            FuzzModifier::Cidr => ConditionModifier::Cidr,
         }
     }
 }
@@ -93,8 +95,9 @@ fuzz_target!(|input: FuzzInput| {
         status: RuleStatus::Enabled,
         detection: DetectionCondition {
             conditions,
-            threshold: None,
+            ..Default::default()
         },
+        correlation: None,
         tags: Vec::new(),
     };
 
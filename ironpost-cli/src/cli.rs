@@ -3,9 +3,13 @@
 //! This module defines the command-line interface structure using clap's derive macros.
 //! It is purely declarative with no side effects or I/O.
 
+use std::net::IpAddr;
 use std::path::PathBuf;
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use ironpost_core::types::Severity;
 
 /// Ironpost -- integrated security monitoring platform.
 ///
@@ -22,9 +26,33 @@ pub struct Cli {
     pub log_level: Option<String>,
 
     /// Output format.
-    #[arg(long, global = true, default_value = "text")]
+    #[arg(long, global = true, default_value = "table")]
     pub output: OutputFormat,
 
+    /// Daemon control API address (host[:port] or http(s)://host[:port]).
+    ///
+    /// Overrides `[client] host`; defaults to the local `[health]` listener.
+    #[arg(long, global = true, value_name = "HOST", conflicts_with = "socket")]
+    pub host: Option<String>,
+
+    /// Daemon control socket (see `[health] unix_socket`).
+    #[arg(long, global = true, value_name = "PATH")]
+    pub socket: Option<PathBuf>,
+
+    /// Bearer token for the control API.
+    ///
+    /// Prefer `IRONPOST_CLIENT_TOKEN` or `[client] token_file`, since
+    /// command-line arguments are visible to other local users.
+    #[arg(long, global = true, value_name = "TOKEN")]
+    pub token: Option<String>,
+
+    /// Allow sending the token over plain HTTP to a non-loopback host.
+    ///
+    /// Without it, a token is only sent over HTTPS, a unix socket or to a
+    /// loopback address.
+    #[arg(long, global = true)]
+    pub insecure: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -33,9 +61,12 @@ pub struct Cli {
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum OutputFormat {
     /// Human-readable table / text output.
+    #[value(name = "table", alias = "text")]
     Text,
     /// Machine-readable JSON.
     Json,
+    /// Machine-readable YAML.
+    Yaml,
 }
 
 #[derive(Subcommand, Debug)]
@@ -46,14 +77,47 @@ pub enum Commands {
     /// Check status of each module.
     Status(StatusArgs),
 
+    /// List quarantined modules and put them back into service.
+    Module(ModuleArgs),
+
+    /// Inspect the running eBPF engine.
+    Ebpf(EbpfArgs),
+
+    /// Inspect container guard policies and activity, and isolate containers.
+    Container(ContainerArgs),
+
+    /// Tail or list alerts raised by the running daemon.
+    Alerts(AlertsArgs),
+
+    /// Search logs stored by the running daemon.
+    Log(LogArgs),
+
+    /// Full-screen dashboard of traffic, alerts, module health and container actions.
+    Top(TopArgs),
+
     /// Run a one-shot SBOM vulnerability scan.
     Scan(ScanArgs),
 
+    /// Generate and scan software bills of materials.
+    Sbom(SbomArgs),
+
     /// Manage detection rules.
     Rules(RulesArgs),
 
+    /// Roll distributed rule/policy content back to a stored version.
+    Content(ContentArgs),
+
     /// Manage configuration.
     Config(ConfigArgs),
+
+    /// Write an HTML or Markdown activity report for a recent period.
+    Report(ReportArgs),
+
+    /// Measure parser, rule engine and packet detector throughput with synthetic events.
+    Bench(BenchArgs),
+
+    /// Replay a recorded pcap or log file through the detection path.
+    Replay(ReplayArgs),
 }
 
 // ---- start ----
@@ -80,6 +144,555 @@ pub struct StatusArgs {
     pub verbose: bool,
 }
 
+// ---- module ----
+
+/// Manage crash-loop quarantine of daemon modules.
+#[derive(Args, Debug)]
+pub struct ModuleArgs {
+    #[command(subcommand)]
+    pub action: ModuleAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ModuleAction {
+    /// List the modules the running daemon quarantined after repeated failures.
+    Quarantined,
+    /// Lift quarantine for a module and start it again.
+    Unquarantine {
+        /// Module name (e.g. log-pipeline).
+        name: String,
+    },
+}
+
+// ---- content ----
+
+/// Manage rule/policy content distributed to the daemon.
+#[derive(Args, Debug)]
+pub struct ContentArgs {
+    #[command(subcommand)]
+    pub action: ContentAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ContentAction {
+    /// Re-apply a stored content version (default: the one before the current version).
+    Rollback {
+        /// Content version to re-apply (see `ironpost status`).
+        version: Option<String>,
+    },
+}
+
+// ---- ebpf ----
+
+/// Inspect the eBPF engine of the running daemon.
+#[derive(Args, Debug)]
+pub struct EbpfArgs {
+    #[command(subcommand)]
+    pub action: EbpfAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum EbpfAction {
+    /// Show XDP attach state, interface, mode and rule count.
+    Status,
+    /// Show per-protocol packet, byte and drop counters.
+    Stats {
+        /// Refresh every second until interrupted.
+        #[arg(short, long)]
+        watch: bool,
+    },
+    /// Manage the runtime IP/CIDR blocklist of the running engine.
+    Blocklist {
+        #[command(subcommand)]
+        action: BlocklistAction,
+    },
+    /// Manage the filter rules of the running engine.
+    Rules {
+        #[command(subcommand)]
+        action: EbpfRulesAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum EbpfRulesAction {
+    /// Add or replace filter rules in bulk from a rules file.
+    ///
+    /// Accepts the TOML rules file format (`[[rules]]` tables) or JSON
+    /// (`{"rules": [...]}`). All rules are validated before any is applied,
+    /// duplicate IDs keep the last rule, and the maps are synced once.
+    Import {
+        /// File to read.
+        path: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BlocklistAction {
+    /// Add an IPv4 address or CIDR (e.g. 10.0.0.0/24) to the blocklist.
+    Add {
+        /// IPv4 address or CIDR.
+        target: String,
+        /// Action applied to matching packets.
+        #[arg(long, value_enum, default_value = "block")]
+        action: BlockAction,
+        /// Remove the entry after this long (e.g. 90s, 30m, 12h, 7d; default: never).
+        #[arg(long, value_parser = parse_ttl)]
+        ttl: Option<u64>,
+        /// Reason recorded with the entry.
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Remove addresses or CIDRs from the blocklist.
+    Remove {
+        /// Addresses or CIDRs, as they were added.
+        #[arg(required = true)]
+        targets: Vec<String>,
+    },
+    /// List runtime blocklist entries with hit counts.
+    List {
+        /// Show GeoIP country, ASN and reverse DNS for each entry.
+        #[arg(long)]
+        enrich: bool,
+    },
+    /// Add entries from a file (JSON export or one address/CIDR per line).
+    Import {
+        /// File to read.
+        path: PathBuf,
+        /// Action for plain-text entries.
+        #[arg(long, value_enum, default_value = "block")]
+        action: BlockAction,
+        /// TTL for plain-text entries (e.g. 30m, 1d; default: never).
+        #[arg(long, value_parser = parse_ttl)]
+        ttl: Option<u64>,
+    },
+    /// Write the runtime blocklist to a JSON file.
+    Export {
+        /// File to write.
+        path: PathBuf,
+    },
+}
+
+/// Action applied to packets matching a blocklist entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockAction {
+    /// Drop matching packets.
+    Block,
+    /// Pass matching packets and report them.
+    Monitor,
+    /// Always pass matching packets, overriding blocks and rate limits.
+    Allow,
+    /// Answer matching TCP packets with a RST and drop everything else.
+    Reflect,
+}
+
+/// Parse a TTL such as `90`, `90s`, `30m`, `12h` or `7d` into seconds.
+pub fn parse_ttl(value: &str) -> Result<u64, String> {
+    parse_duration_secs(value, "TTL")
+}
+
+/// Parse a report period such as `90m`, `24h` or `7d` into seconds.
+pub fn parse_period(value: &str) -> Result<u64, String> {
+    parse_duration_secs(value, "period")
+}
+
+/// Parse a log age such as `30m`, `12h` or `7d` into seconds.
+pub fn parse_age(value: &str) -> Result<u64, String> {
+    parse_duration_secs(value, "age")
+}
+
+/// Parse a `KEY=VALUE` field filter.
+pub fn parse_field(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_owned(), value.to_owned()))
+        }
+        _ => Err(format!("invalid field filter '{}' (use KEY=VALUE)", value)),
+    }
+}
+
+/// Parse `<amount>[s|m|h|d]` into seconds; `what` names the value in errors.
+fn parse_duration_secs(value: &str, what: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let (digits, unit) = match value.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&value[..i], c),
+        _ => (value, 's'),
+    };
+    let multiplier = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "unknown {} unit '{}' (use s, m, h or d)",
+                what, unit
+            ));
+        }
+    };
+    let amount: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid {} '{}' (e.g. 30m, 12h, 7d)", what, value))?;
+    if amount == 0 {
+        return Err(format!("{} must be greater than zero", what));
+    }
+    amount
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("{} '{}' is too large", what, value))
+}
+
+// ---- container ----
+
+/// Inspect the container guard and isolate containers.
+#[derive(Args, Debug)]
+pub struct ContainerArgs {
+    #[command(subcommand)]
+    pub action: ContainerAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ContainerAction {
+    /// List, lint and test isolation policies.
+    #[command(alias = "policy")]
+    Policies {
+        #[command(subcommand)]
+        action: PoliciesAction,
+    },
+    /// Stream container lifecycle events, policy matches and isolation
+    /// actions from the running daemon until interrupted.
+    Watch {
+        /// Print the events the daemon has buffered before following new ones.
+        #[arg(long)]
+        history: bool,
+    },
+    /// Isolate a container through the running daemon.
+    Isolate {
+        /// Container name, ID or unique ID prefix.
+        target: String,
+        /// Isolation action to apply.
+        #[arg(long, value_enum, default_value = "pause")]
+        action: IsolateAction,
+        /// Network to disconnect from (repeatable). Required for
+        /// network-disconnect; quarantine defaults to every attached network.
+        #[arg(
+            long = "network",
+            value_name = "NETWORK",
+            required_if_eq("action", "network-disconnect")
+        )]
+        networks: Vec<String>,
+        /// Release automatically after this long (e.g. 90s, 30m, 12h; default:
+        /// until released).
+        #[arg(long, value_parser = parse_isolation_duration)]
+        duration: Option<u64>,
+    },
+    /// Undo the isolation applied to a container, whichever action it was.
+    Release {
+        /// Container name, ID or unique ID prefix.
+        target: String,
+    },
+}
+
+/// Isolation action applied by `container isolate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IsolateAction {
+    /// Pause the container; release unpauses it.
+    Pause,
+    /// Stop the container; release starts it again.
+    Stop,
+    /// Disconnect the container from networks; release reconnects them.
+    NetworkDisconnect,
+    /// Disconnect from networks and pause; release undoes both.
+    Quarantine,
+}
+
+/// Parse an isolation duration such as `90s`, `30m` or `12h` into seconds.
+pub fn parse_isolation_duration(value: &str) -> Result<u64, String> {
+    parse_duration_secs(value, "duration")
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PoliciesAction {
+    /// List the policies in the configured policy directory in evaluation order.
+    List,
+    /// Check a directory of TOML policies before deploying it.
+    Lint {
+        /// Directory containing TOML policy files.
+        dir: PathBuf,
+    },
+    /// Show which policy would match a sample alert and which action would
+    /// run, using the running daemon's policies without isolating anything.
+    Test {
+        /// Sample alert as a JSON file, e.g. one alert from
+        /// `ironpost alerts list --output json`. Flags override its fields.
+        #[arg(long, value_name = "FILE")]
+        alert: Option<PathBuf>,
+        /// Alert severity (required without --alert).
+        #[arg(long, value_enum, required_unless_present = "alert")]
+        severity: Option<SeverityLevel>,
+        /// Alert source IP address.
+        #[arg(long)]
+        source_ip: Option<IpAddr>,
+        /// Alert target IP address.
+        #[arg(long)]
+        target_ip: Option<IpAddr>,
+        /// Detection rule name.
+        #[arg(long)]
+        rule: Option<String>,
+        /// Alert title.
+        #[arg(long)]
+        title: Option<String>,
+        /// Running container name, ID or unique ID prefix (default: every
+        /// running container, as the guard evaluates them). With --image, the
+        /// name of a container that does not need to exist.
+        #[arg(long)]
+        container: Option<String>,
+        /// Evaluate a hypothetical container running this image instead of a
+        /// running container.
+        #[arg(long)]
+        image: Option<String>,
+    },
+}
+
+// ---- alerts ----
+
+/// Tail or list daemon alerts.
+#[derive(Args, Debug)]
+pub struct AlertsArgs {
+    #[command(subcommand)]
+    pub action: AlertsAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AlertsAction {
+    /// Stream new alerts from the running daemon until interrupted.
+    Tail {
+        #[command(flatten)]
+        filter: AlertFilter,
+        /// Show GeoIP country, ASN and reverse DNS for alert addresses.
+        #[arg(long)]
+        enrich: bool,
+    },
+    /// Show the recent alerts held by the running daemon, oldest first.
+    List {
+        #[command(flatten)]
+        filter: AlertFilter,
+        /// Maximum number of alerts to show (the most recent are kept).
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// Show GeoIP country, ASN and reverse DNS for alert addresses.
+        #[arg(long)]
+        enrich: bool,
+    },
+}
+
+/// Filters shared by `alerts tail` and `alerts list`.
+#[derive(Args, Debug, Clone, Default)]
+pub struct AlertFilter {
+    /// Only show alerts at or above this severity.
+    #[arg(long, value_enum)]
+    pub severity: Option<SeverityLevel>,
+    /// Only show alerts raised by this detection rule.
+    #[arg(long)]
+    pub rule: Option<String>,
+    /// Only show alerts raised by this module (e.g. `log-pipeline`).
+    #[arg(long)]
+    pub module: Option<String>,
+}
+
+// ---- log ----
+
+/// Search the daemon's built-in log store.
+#[derive(Args, Debug)]
+pub struct LogArgs {
+    #[command(subcommand)]
+    pub action: LogAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LogAction {
+    /// Search stored logs, newest first (requires `[log_pipeline.log_store]`).
+    Search {
+        /// Text the message must contain (case-insensitive).
+        query: Option<String>,
+        /// Only logs newer than this age (e.g. 30m, 12h, 7d).
+        #[arg(long, value_parser = parse_age)]
+        since: Option<u64>,
+        /// Only logs older than this age (e.g. 30m, 12h, 7d).
+        #[arg(long, value_parser = parse_age)]
+        until: Option<u64>,
+        /// Only logs with this field value; `host` and `process` match the
+        /// hostname and process name (repeatable).
+        #[arg(long = "field", value_name = "KEY=VALUE", value_parser = parse_field)]
+        fields: Vec<(String, String)>,
+        /// Only logs at or above this severity.
+        #[arg(long, value_enum)]
+        severity: Option<SeverityLevel>,
+        /// Maximum number of logs to show.
+        #[arg(long, default_value_t = 50, value_parser = clap::value_parser!(u64).range(1..=1000))]
+        limit: u64,
+        /// Number of matching logs to skip, newest first (next page).
+        #[arg(long, default_value_t = 0)]
+        offset: u64,
+    },
+}
+
+// ---- top ----
+
+/// Live terminal dashboard.
+#[derive(Args, Debug)]
+pub struct TopArgs {
+    /// Seconds between refreshes from the daemon.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+}
+
+// ---- report ----
+
+/// Export a shareable activity report from the running daemon.
+#[derive(Args, Debug)]
+pub struct ReportArgs {
+    /// Period covered by the report, ending now (e.g. 90m, 24h, 7d).
+    #[arg(long, default_value = "24h", value_parser = parse_period)]
+    pub period: u64,
+
+    /// Report document format.
+    #[arg(long, value_enum, default_value = "md")]
+    pub format: ReportFormat,
+
+    /// File to write (default: ironpost-report.md or ironpost-report.html).
+    #[arg(long, value_name = "FILE")]
+    pub out: Option<PathBuf>,
+}
+
+/// Report document format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    /// Standalone HTML page.
+    Html,
+    /// Markdown document.
+    Md,
+}
+
+// ---- bench ----
+
+/// Benchmark the detection path in-process with synthetic events.
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Number of synthetic log lines to parse and match.
+    #[arg(
+        long,
+        default_value_t = 100_000,
+        value_parser = clap::value_parser!(u64).range(1..=10_000_000)
+    )]
+    pub events: u64,
+
+    /// Format of the synthetic log lines.
+    #[arg(long, value_enum, default_value = "mixed")]
+    pub format: BenchFormat,
+
+    /// Directory of YAML detection rules (default: the pipeline's rule directory).
+    #[arg(long, value_name = "DIR")]
+    pub rules: Option<PathBuf>,
+
+    /// Also feed this many synthetic packet events through the SYN flood and
+    /// port scan detectors (Linux only).
+    #[arg(
+        long,
+        default_value_t = 0,
+        value_parser = clap::value_parser!(u64).range(0..=10_000_000)
+    )]
+    pub packets: u64,
+
+    /// Fail (exit code 1) if log throughput is below this many events per second.
+    #[arg(long, value_name = "EPS")]
+    pub min_eps: Option<u64>,
+}
+
+/// Synthetic log line format for `bench`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BenchFormat {
+    /// RFC 3164 and RFC 5424 syslog lines.
+    Syslog,
+    /// JSON log objects.
+    Json,
+    /// Alternating syslog and JSON lines.
+    Mixed,
+}
+
+// ---- replay ----
+
+/// Replay a recorded incident from a pcap capture or a raw log file.
+#[derive(Args, Debug)]
+pub struct ReplayArgs {
+    /// pcap capture or log file (one event per line).
+    pub file: PathBuf,
+
+    /// Input format (default: detected from the file header).
+    #[arg(long, value_enum, default_value = "auto")]
+    pub kind: ReplayKind,
+
+    /// Send events to the in-process pipeline or to the running daemon's
+    /// syslog TCP listener (log files only).
+    #[arg(long, value_enum, default_value = "local")]
+    pub target: ReplayTarget,
+
+    /// Playback speed relative to the recorded timestamps (e.g. `10` = ten times faster).
+    #[arg(long, default_value_t = 1.0, value_parser = parse_speed, conflicts_with = "fast")]
+    pub speed: f64,
+
+    /// Send events back to back, ignoring the recorded timestamps.
+    #[arg(long)]
+    pub fast: bool,
+
+    /// Directory of YAML detection rules (local target; default: the pipeline's rule directory).
+    #[arg(long, value_name = "DIR")]
+    pub rules: Option<PathBuf>,
+
+    /// Syslog TCP address of the daemon (daemon target; default: `log_pipeline.syslog_tcp_bind`).
+    #[arg(long, value_name = "ADDR")]
+    pub syslog_addr: Option<String>,
+}
+
+/// Input format for `replay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayKind {
+    /// pcap if the file starts with a pcap magic number, log otherwise.
+    Auto,
+    /// Classic libpcap capture (Ethernet, Linux cooked or raw IP).
+    Pcap,
+    /// Syslog or JSON lines.
+    Log,
+}
+
+/// Where `replay` sends events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayTarget {
+    /// In-process parser, rule engine and packet detectors.
+    Local,
+    /// The running daemon's syslog TCP listener.
+    Daemon,
+}
+
+/// Parse a replay speed multiplier (greater than 0, at most 10000).
+pub fn parse_speed(value: &str) -> Result<f64, String> {
+    let speed: f64 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid speed '{}'", value))?;
+    if !speed.is_finite() || speed <= 0.0 || speed > 10_000.0 {
+        return Err(format!(
+            "speed must be greater than 0 and at most 10000, got {}",
+            value
+        ));
+    }
+    Ok(speed)
+}
+
 // ---- scan ----
 
 /// Run a one-shot SBOM scan on a project directory.
@@ -98,6 +711,146 @@ pub struct ScanArgs {
     pub sbom_format: String,
 }
 
+// ---- sbom ----
+
+/// Generate and scan software bills of materials.
+#[derive(Args, Debug)]
+pub struct SbomArgs {
+    #[command(subcommand)]
+    pub action: SbomAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SbomAction {
+    /// Generate an SBOM document from lockfiles (Cargo.lock, package-lock.json).
+    Generate {
+        /// Lockfile, or directory whose lockfiles are included (default: current directory).
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// SBOM document format.
+        #[arg(long, value_enum, default_value = "cyclonedx")]
+        format: SbomDocumentFormat,
+        /// File to write (default: sbom.cdx.json or sbom.spdx.json).
+        #[arg(short, long, value_name = "FILE")]
+        out: Option<PathBuf>,
+    },
+    /// Scan lockfiles or an SBOM document for known vulnerabilities.
+    Scan {
+        /// Lockfile, CycloneDX/SPDX JSON document, or directory (default: current directory).
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Minimum severity to report.
+        #[arg(long, value_enum, default_value = "medium")]
+        min_severity: SeverityLevel,
+        /// Exit with code 4 when a reported finding is at or above this severity
+        /// (default: any reported finding; `never` always exits 0).
+        #[arg(long, value_enum)]
+        fail_on: Option<FailOn>,
+        /// Vulnerability database directory (default: `[sbom] vuln_db_path`).
+        #[arg(long)]
+        vuln_db: Option<PathBuf>,
+    },
+    /// Manage the local vulnerability database.
+    Db {
+        #[command(subcommand)]
+        action: SbomDbAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SbomDbAction {
+    /// Update the vulnerability database, printing its version and entry counts.
+    ///
+    /// Without `--offline`, advisories are downloaded from OSV, GitHub (GHSA)
+    /// and NVD and merged into the current database.
+    Update {
+        /// Import a downloaded database archive (.tar.gz) instead of syncing online.
+        #[arg(long, value_name = "ARCHIVE")]
+        offline: Option<PathBuf>,
+        /// Feeds to sync online (repeatable or comma-separated, default: all).
+        #[arg(
+            long = "source",
+            value_enum,
+            value_delimiter = ',',
+            conflicts_with = "offline"
+        )]
+        sources: Vec<DbSource>,
+        /// Vulnerability database directory (default: `[sbom] vuln_db_path`).
+        #[arg(long)]
+        vuln_db: Option<PathBuf>,
+    },
+}
+
+/// Online vulnerability feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DbSource {
+    /// OSV ecosystem exports (crates.io, npm, Go, PyPI).
+    Osv,
+    /// Reviewed GitHub security advisories.
+    Ghsa,
+    /// NVD CVSS severities for recently modified CVEs.
+    Nvd,
+}
+
+impl DbSource {
+    /// Every feed, in merge order.
+    pub const ALL: [Self; 3] = [Self::Osv, Self::Ghsa, Self::Nvd];
+
+    /// Feed name as used on the command line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Osv => "osv",
+            Self::Ghsa => "ghsa",
+            Self::Nvd => "nvd",
+        }
+    }
+}
+
+/// Vulnerability severity threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SeverityLevel {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl From<SeverityLevel> for Severity {
+    fn from(level: SeverityLevel) -> Self {
+        match level {
+            SeverityLevel::Info => Severity::Info,
+            SeverityLevel::Low => Severity::Low,
+            SeverityLevel::Medium => Severity::Medium,
+            SeverityLevel::High => Severity::High,
+            SeverityLevel::Critical => Severity::Critical,
+        }
+    }
+}
+
+/// Severity at which `sbom scan` fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+    /// Never fail because of findings.
+    Never,
+}
+
+/// SBOM document format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SbomDocumentFormat {
+    /// CycloneDX 1.5 JSON.
+    Cyclonedx,
+    /// SPDX 2.3 JSON.
+    Spdx,
+}
+
 // ---- rules ----
 
 /// Manage detection rules.
@@ -109,7 +862,10 @@ pub struct RulesArgs {
 
 #[derive(Subcommand, Debug)]
 pub enum RulesAction {
-    /// List all loaded detection rules.
+    /// List loaded detection rules with their match counts.
+    ///
+    /// Falls back to the rules directory (without match counts) when the
+    /// daemon is not reachable.
     List {
         /// Filter by status (enabled, disabled, test).
         #[arg(long)]
@@ -121,6 +877,39 @@ pub enum RulesAction {
         #[arg(default_value = "/etc/ironpost/rules")]
         path: PathBuf,
     },
+    /// Test a rule against a sample log file and print the matching lines.
+    Test {
+        /// YAML rule file.
+        rule: PathBuf,
+        /// Log file with one entry per line (syslog or JSON).
+        log: PathBuf,
+    },
+    /// Generate a commented rule skeleton and a paired sample log for `rules test`.
+    New {
+        /// Rule ID, also used for the file names (<ID>.yaml, <ID>.sample.log).
+        id: String,
+        /// Starting point for the rule.
+        #[arg(long, value_enum, default_value = "custom")]
+        template: RuleTemplate,
+        /// Directory to write the files to.
+        #[arg(long, default_value = ".")]
+        dir: PathBuf,
+        /// Overwrite existing files.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Template for `rules new`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuleTemplate {
+    /// Repeated SSH authentication failures from one address.
+    SshBruteforce,
+    /// Bursts of firewall drops from one address.
+    PortScan,
+    /// Generic skeleton with examples of every condition modifier.
+    Custom,
 }
 
 // ---- config ----
@@ -134,14 +923,37 @@ pub struct ConfigArgs {
 
 #[derive(Subcommand, Debug)]
 pub enum ConfigAction {
-    /// Validate the configuration file and report errors.
-    Validate,
+    /// Validate the configuration file and report errors with their location.
+    Validate {
+        /// File to validate (defaults to the global --config path).
+        file: Option<PathBuf>,
+    },
     /// Show the effective configuration (file + env overrides + defaults).
     Show {
         /// Show only a specific section (general, ebpf, log_pipeline, container, sbom).
         #[arg(long)]
         section: Option<String>,
     },
+    /// Compare two configurations, or a configuration with the running daemon's,
+    /// and show which changes need a daemon restart.
+    Diff {
+        /// Old and new configuration files. With one file, the global --config
+        /// file (or the running daemon with --running) is the old side.
+        #[arg(value_name = "FILE", num_args = 0..=2)]
+        files: Vec<PathBuf>,
+        /// Compare against the running daemon's effective configuration.
+        #[arg(long)]
+        running: bool,
+    },
+    /// Generate a commented ironpost.toml and starter rules/policies interactively.
+    Init {
+        /// Overwrite an existing configuration file.
+        #[arg(long)]
+        force: bool,
+        /// Accept the detected defaults without asking any questions.
+        #[arg(long)]
+        defaults: bool,
+    },
 }
 
 #[cfg(test)]
@@ -219,6 +1031,165 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_ebpf_status() {
+        let args = Cli::try_parse_from(["ironpost", "ebpf", "status"]);
+        assert!(args.is_ok(), "should parse 'ebpf status' subcommand");
+        let cli = args.expect("parse succeeded");
+        match cli.command {
+            Commands::Ebpf(ebpf_args) => {
+                assert!(matches!(ebpf_args.action, EbpfAction::Status));
+            }
+            _ => panic!("expected Ebpf command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_ebpf_stats_watch() {
+        let args = Cli::try_parse_from(["ironpost", "ebpf", "stats", "--watch"]);
+        assert!(args.is_ok(), "should parse 'ebpf stats --watch' subcommand");
+        let cli = args.expect("parse succeeded");
+        match cli.command {
+            Commands::Ebpf(ebpf_args) => match ebpf_args.action {
+                EbpfAction::Stats { watch } => assert!(watch, "watch should be true"),
+                _ => panic!("expected Stats action"),
+            },
+            _ => panic!("expected Ebpf command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_ebpf_stats_defaults() {
+        let cli = Cli::try_parse_from(["ironpost", "ebpf", "stats"]).expect("parse succeeded");
+        match cli.command {
+            Commands::Ebpf(ebpf_args) => match ebpf_args.action {
+                EbpfAction::Stats { watch } => assert!(!watch, "watch should default to false"),
+                _ => panic!("expected Stats action"),
+            },
+            _ => panic!("expected Ebpf command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_ebpf_blocklist_add() {
+        let args = Cli::try_parse_from([
+            "ironpost",
+            "ebpf",
+            "blocklist",
+            "add",
+            "10.0.0.0/24",
+            "--action",
+            "monitor",
+            "--ttl",
+            "30m",
+        ]);
+        let cli = args.expect("should parse 'ebpf blocklist add'");
+        match cli.command {
+            Commands::Ebpf(EbpfArgs {
+                action:
+                    EbpfAction::Blocklist {
+                        action:
+                            BlocklistAction::Add {
+                                target,
+                                action,
+                                ttl,
+                                description,
+                            },
+                    },
+            }) => {
+                assert_eq!(target, "10.0.0.0/24");
+                assert_eq!(action, BlockAction::Monitor);
+                assert_eq!(ttl, Some(1800));
+                assert!(description.is_none());
+            }
+            _ => panic!("expected ebpf blocklist add"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_ebpf_blocklist_remove_requires_target() {
+        let result = Cli::try_parse_from(["ironpost", "ebpf", "blocklist", "remove"]);
+        assert!(result.is_err(), "remove without targets should fail");
+
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "ebpf",
+            "blocklist",
+            "remove",
+            "10.0.0.1",
+            "10.0.0.2",
+        ])
+        .expect("should parse remove");
+        match cli.command {
+            Commands::Ebpf(EbpfArgs {
+                action:
+                    EbpfAction::Blocklist {
+                        action: BlocklistAction::Remove { targets },
+                    },
+            }) => assert_eq!(targets, vec!["10.0.0.1", "10.0.0.2"]),
+            _ => panic!("expected ebpf blocklist remove"),
+        }
+    }
+
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!(parse_ttl("90"), Ok(90));
+        assert_eq!(parse_ttl("90s"), Ok(90));
+        assert_eq!(parse_ttl("30m"), Ok(1800));
+        assert_eq!(parse_ttl("12h"), Ok(43_200));
+        assert_eq!(parse_ttl("7d"), Ok(604_800));
+        assert!(parse_ttl("0").is_err());
+        assert!(parse_ttl("5w").is_err());
+        assert!(parse_ttl("abc").is_err());
+    }
+
+    #[test]
+    fn test_parse_period() {
+        assert_eq!(parse_period("24h"), Ok(86_400));
+        assert_eq!(parse_period("7d"), Ok(604_800));
+        assert!(
+            parse_period("0h")
+                .expect_err("zero period")
+                .contains("period")
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_report() {
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "report",
+            "--period",
+            "7d",
+            "--format",
+            "html",
+            "--out",
+            "weekly.html",
+        ])
+        .expect("should parse");
+        match cli.command {
+            Commands::Report(args) => {
+                assert_eq!(args.period, 604_800);
+                assert_eq!(args.format, ReportFormat::Html);
+                assert_eq!(args.out, Some(PathBuf::from("weekly.html")));
+            }
+            _ => panic!("expected Report command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_report_defaults() {
+        let cli = Cli::try_parse_from(["ironpost", "report"]).expect("should parse");
+        match cli.command {
+            Commands::Report(args) => {
+                assert_eq!(args.period, 86_400);
+                assert_eq!(args.format, ReportFormat::Md);
+                assert!(args.out.is_none());
+            }
+            _ => panic!("expected Report command"),
+        }
+    }
+
     #[test]
     fn test_cli_parse_scan_defaults() {
         let args = Cli::try_parse_from(["ironpost", "scan"]);
@@ -289,6 +1260,628 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_sbom_generate_defaults() {
+        let cli = Cli::try_parse_from(["ironpost", "sbom", "generate"])
+            .expect("should parse sbom generate");
+        match cli.command {
+            Commands::Sbom(SbomArgs {
+                action: SbomAction::Generate { path, format, out },
+            }) => {
+                assert_eq!(path, PathBuf::from("."));
+                assert_eq!(format, SbomDocumentFormat::Cyclonedx);
+                assert!(out.is_none());
+            }
+            _ => panic!("expected Sbom Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_sbom_generate_spdx_output() {
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "sbom",
+            "generate",
+            "Cargo.lock",
+            "--format",
+            "spdx",
+            "-o",
+            "out.json",
+        ])
+        .expect("should parse sbom generate with options");
+        match cli.command {
+            Commands::Sbom(SbomArgs {
+                action: SbomAction::Generate { path, format, out },
+            }) => {
+                assert_eq!(path, PathBuf::from("Cargo.lock"));
+                assert_eq!(format, SbomDocumentFormat::Spdx);
+                assert_eq!(out, Some(PathBuf::from("out.json")));
+            }
+            _ => panic!("expected Sbom Generate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_sbom_generate_rejects_unknown_format() {
+        let result = Cli::try_parse_from(["ironpost", "sbom", "generate", "--format", "xml"]);
+        assert!(result.is_err(), "unknown format should be rejected");
+    }
+
+    #[test]
+    fn test_cli_parse_sbom_scan_gate_options() {
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "sbom",
+            "scan",
+            "bom.json",
+            "--min-severity",
+            "low",
+            "--fail-on",
+            "high",
+        ])
+        .expect("should parse sbom scan");
+        match cli.command {
+            Commands::Sbom(SbomArgs {
+                action:
+                    SbomAction::Scan {
+                        path,
+                        min_severity,
+                        fail_on,
+                        vuln_db,
+                    },
+            }) => {
+                assert_eq!(path, PathBuf::from("bom.json"));
+                assert_eq!(min_severity, SeverityLevel::Low);
+                assert_eq!(fail_on, Some(FailOn::High));
+                assert!(vuln_db.is_none());
+            }
+            _ => panic!("expected Sbom Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_sbom_scan_defaults() {
+        let cli = Cli::try_parse_from(["ironpost", "sbom", "scan"]).expect("should parse");
+        match cli.command {
+            Commands::Sbom(SbomArgs {
+                action:
+                    SbomAction::Scan {
+                        min_severity,
+                        fail_on,
+                        ..
+                    },
+            }) => {
+                assert_eq!(min_severity, SeverityLevel::Medium);
+                assert!(fail_on.is_none());
+            }
+            _ => panic!("expected Sbom Scan command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_sbom_db_update_offline() {
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "sbom",
+            "db",
+            "update",
+            "--offline",
+            "vulndb.tar.gz",
+        ])
+        .expect("should parse");
+        match cli.command {
+            Commands::Sbom(SbomArgs {
+                action:
+                    SbomAction::Db {
+                        action:
+                            SbomDbAction::Update {
+                                offline, vuln_db, ..
+                            },
+                    },
+            }) => {
+                assert_eq!(offline, Some(PathBuf::from("vulndb.tar.gz")));
+                assert!(vuln_db.is_none());
+            }
+            _ => panic!("expected Sbom Db Update command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_sbom_db_update_sources() {
+        let cli = Cli::try_parse_from(["ironpost", "sbom", "db", "update", "--source", "osv,nvd"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Sbom(SbomArgs {
+                action:
+                    SbomAction::Db {
+                        action: SbomDbAction::Update { sources, .. },
+                    },
+            }) => assert_eq!(sources, vec![DbSource::Osv, DbSource::Nvd]),
+            _ => panic!("expected Sbom Db Update command"),
+        }
+
+        let conflict = Cli::try_parse_from([
+            "ironpost",
+            "sbom",
+            "db",
+            "update",
+            "--offline",
+            "db.tar.gz",
+            "--source",
+            "osv",
+        ]);
+        assert!(conflict.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_container_policies_lint() {
+        let cli = Cli::try_parse_from(["ironpost", "container", "policies", "lint", "policies/"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Container(ContainerArgs {
+                action:
+                    ContainerAction::Policies {
+                        action: PoliciesAction::Lint { dir },
+                    },
+            }) => assert_eq!(dir, PathBuf::from("policies/")),
+            _ => panic!("expected Container Policies Lint command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_container_policy_test() {
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "container",
+            "policy",
+            "test",
+            "--severity",
+            "critical",
+            "--source-ip",
+            "1.2.3.4",
+            "--container",
+            "web-1",
+        ])
+        .expect("should parse");
+        match cli.command {
+            Commands::Container(ContainerArgs {
+                action:
+                    ContainerAction::Policies {
+                        action:
+                            PoliciesAction::Test {
+                                alert,
+                                severity,
+                                source_ip,
+                                container,
+                                image,
+                                ..
+                            },
+                    },
+            }) => {
+                assert!(alert.is_none());
+                assert_eq!(severity, Some(SeverityLevel::Critical));
+                assert_eq!(source_ip, Some("1.2.3.4".parse().unwrap()));
+                assert_eq!(container.as_deref(), Some("web-1"));
+                assert!(image.is_none());
+            }
+            _ => panic!("expected Container Policies Test command"),
+        }
+
+        let result = Cli::try_parse_from(["ironpost", "container", "policies", "test"]);
+        assert!(result.is_err(), "--severity is required without --alert");
+
+        let result = Cli::try_parse_from([
+            "ironpost",
+            "container",
+            "policies",
+            "test",
+            "--alert",
+            "alert.json",
+        ]);
+        assert!(result.is_ok(), "--alert supplies the severity");
+    }
+
+    #[test]
+    fn test_cli_parse_container_watch() {
+        let cli = Cli::try_parse_from(["ironpost", "container", "watch", "--history"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Container(ContainerArgs {
+                action: ContainerAction::Watch { history },
+            }) => assert!(history),
+            _ => panic!("expected Container Watch command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_container_isolate() {
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "container",
+            "isolate",
+            "web-1",
+            "--action",
+            "network-disconnect",
+            "--network",
+            "bridge",
+            "--network",
+            "backend",
+            "--duration",
+            "30m",
+        ])
+        .expect("should parse");
+        match cli.command {
+            Commands::Container(ContainerArgs {
+                action:
+                    ContainerAction::Isolate {
+                        target,
+                        action,
+                        networks,
+                        duration,
+                    },
+            }) => {
+                assert_eq!(target, "web-1");
+                assert_eq!(action, IsolateAction::NetworkDisconnect);
+                assert_eq!(networks, vec!["bridge", "backend"]);
+                assert_eq!(duration, Some(1800));
+            }
+            _ => panic!("expected Container Isolate command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_container_isolate_defaults_and_validation() {
+        let cli = Cli::try_parse_from(["ironpost", "container", "isolate", "web-1"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Container(ContainerArgs {
+                action:
+                    ContainerAction::Isolate {
+                        action, duration, ..
+                    },
+            }) => {
+                assert_eq!(action, IsolateAction::Pause);
+                assert!(duration.is_none());
+            }
+            _ => panic!("expected Container Isolate command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "ironpost",
+            "container",
+            "isolate",
+            "web-1",
+            "--action",
+            "network-disconnect",
+        ]);
+        assert!(result.is_err(), "network-disconnect needs --network");
+
+        let result = Cli::try_parse_from([
+            "ironpost",
+            "container",
+            "isolate",
+            "web-1",
+            "--duration",
+            "0s",
+        ]);
+        assert!(result.is_err(), "zero duration should be rejected");
+    }
+
+    #[test]
+    fn test_cli_parse_container_release() {
+        let cli = Cli::try_parse_from(["ironpost", "container", "release", "abc123"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Container(ContainerArgs {
+                action: ContainerAction::Release { target },
+            }) => assert_eq!(target, "abc123"),
+            _ => panic!("expected Container Release command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_container_policies_lint_requires_dir() {
+        let result = Cli::try_parse_from(["ironpost", "container", "policies", "lint"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_module_unquarantine() {
+        let cli = Cli::try_parse_from(["ironpost", "module", "unquarantine", "log-pipeline"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Module(ModuleArgs {
+                action: ModuleAction::Unquarantine { name },
+            }) => assert_eq!(name, "log-pipeline"),
+            _ => panic!("expected Module Unquarantine command"),
+        }
+
+        let cli = Cli::try_parse_from(["ironpost", "module", "quarantined"]).expect("should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Module(ModuleArgs {
+                action: ModuleAction::Quarantined
+            })
+        ));
+        assert!(Cli::try_parse_from(["ironpost", "module", "unquarantine"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_log_search() {
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "--output",
+            "json",
+            "log",
+            "search",
+            "failed password",
+            "--since",
+            "2h",
+            "--until",
+            "30m",
+            "--field",
+            "host=web-01",
+            "--field",
+            "user=root",
+            "--severity",
+            "high",
+            "--limit",
+            "20",
+            "--offset",
+            "40",
+        ])
+        .expect("should parse");
+        assert!(matches!(cli.output, OutputFormat::Json));
+        match cli.command {
+            Commands::Log(LogArgs {
+                action:
+                    LogAction::Search {
+                        query,
+                        since,
+                        until,
+                        fields,
+                        severity,
+                        limit,
+                        offset,
+                    },
+            }) => {
+                assert_eq!(query.as_deref(), Some("failed password"));
+                assert_eq!(since, Some(7200));
+                assert_eq!(until, Some(1800));
+                assert_eq!(
+                    fields,
+                    [
+                        ("host".to_owned(), "web-01".to_owned()),
+                        ("user".to_owned(), "root".to_owned())
+                    ]
+                );
+                assert_eq!(severity, Some(SeverityLevel::High));
+                assert_eq!((limit, offset), (20, 40));
+            }
+            _ => panic!("expected Log Search command"),
+        }
+
+        assert!(Cli::try_parse_from(["ironpost", "log", "search", "--field", "host"]).is_err());
+        assert!(Cli::try_parse_from(["ironpost", "log", "search", "--limit", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_content_rollback() {
+        let cli = Cli::try_parse_from(["ironpost", "content", "rollback"]).expect("should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Content(ContentArgs {
+                action: ContentAction::Rollback { version: None }
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["ironpost", "content", "rollback", "a1b2c3d4e5f60718"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Content(ContentArgs {
+                action: ContentAction::Rollback { version },
+            }) => assert_eq!(version.as_deref(), Some("a1b2c3d4e5f60718")),
+            _ => panic!("expected Content Rollback command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_alerts_tail_filters() {
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "alerts",
+            "tail",
+            "--severity",
+            "high",
+            "--rule",
+            "ssh_brute_force",
+            "--module",
+            "log-pipeline",
+        ])
+        .expect("should parse");
+        match cli.command {
+            Commands::Alerts(AlertsArgs {
+                action: AlertsAction::Tail { filter, enrich },
+            }) => {
+                assert!(!enrich);
+                assert_eq!(filter.severity, Some(SeverityLevel::High));
+                assert_eq!(filter.rule.as_deref(), Some("ssh_brute_force"));
+                assert_eq!(filter.module.as_deref(), Some("log-pipeline"));
+            }
+            _ => panic!("expected Alerts Tail command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_alerts_list_default_limit() {
+        let cli = Cli::try_parse_from(["ironpost", "alerts", "list"]).expect("should parse");
+        match cli.command {
+            Commands::Alerts(AlertsArgs {
+                action:
+                    AlertsAction::List {
+                        filter,
+                        limit,
+                        enrich,
+                    },
+            }) => {
+                assert_eq!(limit, 50);
+                assert!(filter.severity.is_none());
+                assert!(!enrich);
+            }
+            _ => panic!("expected Alerts List command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_bench_defaults_and_limits() {
+        let cli = Cli::try_parse_from(["ironpost", "bench"]).expect("should parse");
+        match cli.command {
+            Commands::Bench(args) => {
+                assert_eq!(args.events, 100_000);
+                assert_eq!(args.format, BenchFormat::Mixed);
+                assert_eq!(args.packets, 0);
+                assert!(args.rules.is_none());
+                assert!(args.min_eps.is_none());
+            }
+            _ => panic!("expected Bench command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "bench",
+            "--format",
+            "json",
+            "--events",
+            "5000",
+            "--min-eps",
+            "20000",
+        ])
+        .expect("should parse");
+        match cli.command {
+            Commands::Bench(args) => {
+                assert_eq!(args.format, BenchFormat::Json);
+                assert_eq!(args.events, 5000);
+                assert_eq!(args.min_eps, Some(20_000));
+            }
+            _ => panic!("expected Bench command"),
+        }
+
+        assert!(Cli::try_parse_from(["ironpost", "bench", "--events", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_replay() {
+        let cli =
+            Cli::try_parse_from(["ironpost", "replay", "incident.pcap"]).expect("should parse");
+        match cli.command {
+            Commands::Replay(args) => {
+                assert_eq!(args.file, PathBuf::from("incident.pcap"));
+                assert_eq!(args.kind, ReplayKind::Auto);
+                assert_eq!(args.target, ReplayTarget::Local);
+                assert!((args.speed - 1.0).abs() < f64::EPSILON);
+                assert!(!args.fast);
+            }
+            _ => panic!("expected Replay command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "replay",
+            "auth.log",
+            "--target",
+            "daemon",
+            "--speed",
+            "20",
+            "--syslog-addr",
+            "10.0.0.5:601",
+        ])
+        .expect("should parse");
+        match cli.command {
+            Commands::Replay(args) => {
+                assert_eq!(args.target, ReplayTarget::Daemon);
+                assert!((args.speed - 20.0).abs() < f64::EPSILON);
+                assert_eq!(args.syslog_addr.as_deref(), Some("10.0.0.5:601"));
+            }
+            _ => panic!("expected Replay command"),
+        }
+
+        assert!(Cli::try_parse_from(["ironpost", "replay", "a.log", "--speed", "0"]).is_err());
+        assert!(
+            Cli::try_parse_from(["ironpost", "replay", "a.log", "--speed", "2", "--fast"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_enrich_flags() {
+        let cli =
+            Cli::try_parse_from(["ironpost", "alerts", "list", "--enrich"]).expect("should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Alerts(AlertsArgs {
+                action: AlertsAction::List { enrich: true, .. },
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["ironpost", "ebpf", "blocklist", "list", "--enrich"])
+            .expect("should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::Ebpf(EbpfArgs {
+                action: EbpfAction::Blocklist {
+                    action: BlocklistAction::List { enrich: true },
+                },
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parse_ebpf_rules_import() {
+        let cli = Cli::try_parse_from(["ironpost", "ebpf", "rules", "import", "rules.toml"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Ebpf(EbpfArgs {
+                action:
+                    EbpfAction::Rules {
+                        action: EbpfRulesAction::Import { path },
+                    },
+            }) => assert_eq!(path, PathBuf::from("rules.toml")),
+            _ => panic!("expected ebpf rules import"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_top_interval() {
+        let cli =
+            Cli::try_parse_from(["ironpost", "top", "--interval", "5"]).expect("should parse");
+        match cli.command {
+            Commands::Top(args) => assert_eq!(args.interval, 5),
+            _ => panic!("expected Top command"),
+        }
+        assert!(Cli::try_parse_from(["ironpost", "top", "--interval", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_rules_test() {
+        let cli = Cli::try_parse_from(["ironpost", "rules", "test", "ssh.yaml", "auth.log"])
+            .expect("should parse rules test");
+        match cli.command {
+            Commands::Rules(rules_args) => match rules_args.action {
+                RulesAction::Test { rule, log } => {
+                    assert_eq!(rule, PathBuf::from("ssh.yaml"));
+                    assert_eq!(log, PathBuf::from("auth.log"));
+                }
+                _ => panic!("expected Test action"),
+            },
+            _ => panic!("expected Rules command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_rules_test_requires_log_file() {
+        let result = Cli::try_parse_from(["ironpost", "rules", "test", "ssh.yaml"]);
+        assert!(result.is_err(), "log file should be required");
+    }
+
     #[test]
     fn test_cli_parse_rules_list_with_status_filter() {
         let args = Cli::try_parse_from(["ironpost", "rules", "list", "--status", "enabled"]);
@@ -338,6 +1931,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_rules_new() {
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "rules",
+            "new",
+            "ssh_guard",
+            "--template",
+            "ssh-bruteforce",
+        ])
+        .expect("should parse rules new");
+        match cli.command {
+            Commands::Rules(rules_args) => match rules_args.action {
+                RulesAction::New {
+                    id,
+                    template,
+                    dir,
+                    force,
+                } => {
+                    assert_eq!(id, "ssh_guard");
+                    assert_eq!(template, RuleTemplate::SshBruteforce);
+                    assert_eq!(dir, PathBuf::from("."));
+                    assert!(!force);
+                }
+                _ => panic!("expected New action"),
+            },
+            _ => panic!("expected Rules command"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["ironpost", "rules", "new", "x", "--template", "port-scan"])
+                .is_ok()
+        );
+        assert!(
+            Cli::try_parse_from(["ironpost", "rules", "new", "x", "--template", "nope"]).is_err()
+        );
+    }
+
     #[test]
     fn test_cli_parse_config_validate() {
         let args = Cli::try_parse_from(["ironpost", "config", "validate"]);
@@ -345,7 +1976,7 @@ mod tests {
         let cli = args.expect("parse succeeded");
         match cli.command {
             Commands::Config(config_args) => match config_args.action {
-                ConfigAction::Validate => {}
+                ConfigAction::Validate { file } => assert!(file.is_none()),
                 _ => panic!("expected Validate action"),
             },
             _ => panic!("expected Config command"),
@@ -384,6 +2015,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_config_validate_file() {
+        let cli = Cli::try_parse_from(["ironpost", "config", "validate", "/tmp/new.toml"])
+            .expect("should parse config validate with a file");
+        match cli.command {
+            Commands::Config(config_args) => match config_args.action {
+                ConfigAction::Validate { file } => {
+                    assert_eq!(file, Some(PathBuf::from("/tmp/new.toml")));
+                }
+                _ => panic!("expected Validate action"),
+            },
+            _ => panic!("expected Config command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_config_diff() {
+        let cli = Cli::try_parse_from(["ironpost", "config", "diff", "old.toml", "new.toml"])
+            .expect("should parse config diff with two files");
+        match cli.command {
+            Commands::Config(config_args) => match config_args.action {
+                ConfigAction::Diff { files, running } => {
+                    assert_eq!(files.len(), 2);
+                    assert!(!running);
+                }
+                _ => panic!("expected Diff action"),
+            },
+            _ => panic!("expected Config command"),
+        }
+
+        let cli = Cli::try_parse_from(["ironpost", "config", "diff", "--running"])
+            .expect("should parse config diff --running");
+        match cli.command {
+            Commands::Config(config_args) => match config_args.action {
+                ConfigAction::Diff { files, running } => {
+                    assert!(files.is_empty());
+                    assert!(running);
+                }
+                _ => panic!("expected Diff action"),
+            },
+            _ => panic!("expected Config command"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["ironpost", "config", "diff", "a", "b", "c"]).is_err(),
+            "at most two files"
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_config_init() {
+        let cli = Cli::try_parse_from(["ironpost", "config", "init", "--defaults"])
+            .expect("should parse config init");
+        match cli.command {
+            Commands::Config(config_args) => match config_args.action {
+                ConfigAction::Init { force, defaults } => {
+                    assert!(!force);
+                    assert!(defaults);
+                }
+                _ => panic!("expected Init action"),
+            },
+            _ => panic!("expected Config command"),
+        }
+    }
+
     #[test]
     fn test_cli_parse_custom_config_path() {
         let args = Cli::try_parse_from(["ironpost", "-c", "/custom/config.toml", "status"]);
@@ -400,6 +2096,42 @@ mod tests {
         assert_eq!(cli.log_level, Some("debug".to_owned()));
     }
 
+    #[test]
+    fn test_cli_parse_remote_daemon_flags() {
+        let cli = Cli::try_parse_from([
+            "ironpost",
+            "status",
+            "--host",
+            "https://daemon.example:9101",
+            "--token",
+            "s3cret",
+        ])
+        .expect("should parse remote flags after the subcommand");
+        assert_eq!(cli.host.as_deref(), Some("https://daemon.example:9101"));
+        assert_eq!(cli.token.as_deref(), Some("s3cret"));
+        assert!(cli.socket.is_none());
+
+        let cli = Cli::try_parse_from(["ironpost", "--socket", "/run/ironpost.sock", "status"])
+            .expect("should parse socket flag");
+        assert_eq!(
+            cli.socket,
+            Some(std::path::PathBuf::from("/run/ironpost.sock"))
+        );
+    }
+
+    #[test]
+    fn test_cli_host_conflicts_with_socket() {
+        let args = Cli::try_parse_from([
+            "ironpost",
+            "--host",
+            "daemon",
+            "--socket",
+            "/run/ironpost.sock",
+            "status",
+        ]);
+        assert!(args.is_err(), "--host and --socket should conflict");
+    }
+
     #[test]
     fn test_cli_parse_output_format_json() {
         let args = Cli::try_parse_from(["ironpost", "--output", "json", "status"]);
@@ -422,6 +2154,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_output_format_yaml_and_table() {
+        let cli = Cli::try_parse_from(["ironpost", "--output", "yaml", "status"])
+            .expect("should parse yaml output format");
+        assert!(matches!(cli.output, OutputFormat::Yaml));
+
+        let cli = Cli::try_parse_from(["ironpost", "status", "--output", "table"])
+            .expect("should parse table output format after the subcommand");
+        assert!(matches!(cli.output, OutputFormat::Text));
+
+        let cli = Cli::try_parse_from(["ironpost", "status"]).expect("should parse");
+        assert!(
+            matches!(cli.output, OutputFormat::Text),
+            "table is the default"
+        );
+    }
+
     #[test]
     fn test_cli_parse_invalid_command_fails() {
         let args = Cli::try_parse_from(["ironpost", "invalid-command"]);
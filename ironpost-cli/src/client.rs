@@ -0,0 +1,849 @@
+//! Client for the daemon control API
+//!
+//! The daemon serves its control endpoints (`/status`, `/ebpf`, ...) on the
+//! health listener configured in `[health]`, and optionally on a unix socket
+//! (`health.unix_socket`). Requests are plain HTTP/1.1 with
+//! `Connection: close`, matching the daemon's minimal server.
+//!
+//! Where to connect comes from the global `--host`/`--socket`/`--token`
+//! flags, then `[client]` (with its `IRONPOST_CLIENT_*` environment
+//! overrides), then the local `[health]` listener. HTTPS is used for
+//! `https://` hosts, and for the local listener when `[auth]` serves TLS;
+//! the daemon certificate is verified against `[client] ca_cert_path`, and
+//! `client_cert_path`/`client_key_path` are presented for mTLS. A bearer
+//! token, when configured, is sent with every request; over plain HTTP that
+//! is only allowed to loopback hosts unless `--insecure` is given.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use tokio_rustls::rustls::{ClientConfig as TlsClientConfig, RootCertStore, crypto};
+use tracing::debug;
+
+use ironpost_core::config::{ClientConfig, IronpostConfig};
+
+use crate::error::CliError;
+
+/// Timeout for connecting to the daemon and reading a response.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum response size accepted from the daemon.
+const MAX_RESPONSE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Connection settings given on the command line.
+static OVERRIDES: OnceLock<ConnectionOverrides> = OnceLock::new();
+
+/// Connection settings from the global `--host`/`--socket`/`--token` flags.
+///
+/// They take precedence over `[client]` and its environment overrides.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionOverrides {
+    /// Daemon address (`host[:port]` or `http(s)://host[:port]`)
+    pub host: Option<String>,
+    /// Daemon control socket
+    pub socket: Option<PathBuf>,
+    /// Bearer token
+    pub token: Option<String>,
+    /// Allow sending the token over plain HTTP to non-loopback hosts
+    pub insecure: bool,
+}
+
+/// Install the command-line connection settings for every client.
+///
+/// Only the first call has an effect.
+pub fn set_overrides(overrides: ConnectionOverrides) {
+    let _ = OVERRIDES.set(overrides);
+}
+
+/// How requests reach the daemon.
+#[derive(Clone)]
+enum Transport {
+    Tcp,
+    Tls {
+        connector: TlsConnector,
+        server_name: ServerName<'static>,
+    },
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// Connection to the daemon's control API.
+#[derive(Clone)]
+pub struct DaemonClient {
+    /// `host:port` of the control listener, or the socket path.
+    addr: String,
+    transport: Transport,
+    /// Bearer token sent with every request.
+    token: Option<String>,
+    /// Timeout for connecting and reading a response.
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for DaemonClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The token is deliberately left out
+        f.debug_struct("DaemonClient")
+            .field("addr", &self.addr)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DaemonClient {
+    /// Build a client from the command-line flags, `[client]` and `[health]`.
+    ///
+    /// Without a host or socket the client connects to the local health
+    /// listener; unspecified listen addresses (`0.0.0.0`, `::`) are reached
+    /// via loopback.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CliError::DaemonUnavailable` if the local health listener is
+    /// disabled, and `CliError::Config` for an invalid host, unreadable
+    /// token, certificate or key files, or a token that would be sent over
+    /// plain HTTP to a non-loopback host without `--insecure`.
+    pub fn from_config(config: &IronpostConfig) -> Result<Self, CliError> {
+        let overrides = OVERRIDES.get().cloned().unwrap_or_default();
+        Self::with_overrides(config, overrides)
+    }
+
+    fn with_overrides(
+        config: &IronpostConfig,
+        overrides: ConnectionOverrides,
+    ) -> Result<Self, CliError> {
+        let mut client = config.client.clone();
+        if let Some(host) = overrides.host {
+            client.host = host;
+            client.socket.clear();
+        }
+        if let Some(socket) = overrides.socket {
+            client.socket = socket.display().to_string();
+            client.host.clear();
+        }
+        if let Some(token) = overrides.token {
+            client.token = token;
+        }
+        let token = read_token(&client)?;
+
+        if !client.socket.is_empty() {
+            return Self::unix(&client.socket, token);
+        }
+
+        let (https, host, port) = if client.host.is_empty() {
+            if !config.health.enabled {
+                return Err(CliError::DaemonUnavailable(
+                    "control API is disabled ([health] enabled = false)".to_owned(),
+                ));
+            }
+            let host = match config.health.listen_addr.as_str() {
+                "0.0.0.0" => "127.0.0.1",
+                "::" => "::1",
+                addr => addr,
+            };
+            let https = config.auth.enabled && config.auth.tls_enabled();
+            (https, host.to_owned(), config.health.port)
+        } else {
+            let default_https = !client.ca_cert_path.is_empty()
+                || (config.auth.enabled && config.auth.tls_enabled());
+            parse_host(&client.host, default_https, config.health.port)?
+        };
+
+        if !https && token.is_some() && !overrides.insecure && !is_loopback(&host) {
+            return Err(CliError::Config(format!(
+                "refusing to send the bearer token over plain HTTP to '{}'; \
+                 use an https:// host (see [client] ca_cert_path) or pass --insecure",
+                host
+            )));
+        }
+
+        let addr = if host.contains(':') {
+            format!("[{}]:{}", host, port)
+        } else {
+            format!("{}:{}", host, port)
+        };
+        let transport = if https {
+            let server_name = ServerName::try_from(host.clone())
+                .map_err(|e| CliError::Config(format!("invalid daemon host '{}': {}", host, e)))?;
+            Transport::Tls {
+                connector: tls_connector(&client)?,
+                server_name,
+            }
+        } else {
+            Transport::Tcp
+        };
+        Ok(Self {
+            addr,
+            transport,
+            token,
+            timeout: REQUEST_TIMEOUT,
+        })
+    }
+
+    #[cfg(unix)]
+    fn unix(socket: &str, token: Option<String>) -> Result<Self, CliError> {
+        Ok(Self {
+            addr: socket.to_owned(),
+            transport: Transport::Unix(PathBuf::from(socket)),
+            token,
+            timeout: REQUEST_TIMEOUT,
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn unix(_socket: &str, _token: Option<String>) -> Result<Self, CliError> {
+        Err(CliError::Config(
+            "unix sockets are not supported on this platform; use --host".to_owned(),
+        ))
+    }
+
+    /// Use `timeout` instead of the default for requests that wait on slow
+    /// daemon operations.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Address of the control listener (or the socket path).
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Send `GET path` and deserialize the JSON response body.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CliError::DaemonUnavailable` if the daemon cannot be reached,
+    /// and `CliError::Command` for non-2xx responses or malformed bodies.
+    pub async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, CliError> {
+        self.send("GET", path, None).await
+    }
+
+    /// Send `POST path` with a JSON body and deserialize the JSON response body.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`DaemonClient::get_json`].
+    pub async fn post_json<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, CliError> {
+        let body = serde_json::to_vec(body)?;
+        self.send("POST", path, Some(&body)).await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&[u8]>,
+    ) -> Result<T, CliError> {
+        let raw = tokio::time::timeout(self.timeout, self.request(method, path, body))
+            .await
+            .map_err(|_| {
+                CliError::DaemonUnavailable(format!("{}: request timed out", self.addr))
+            })??;
+
+        let (status, body) = parse_response(&raw)?;
+        debug!(method, path, status, bytes = body.len(), "daemon response");
+        if !(200..300).contains(&status) {
+            return Err(CliError::Command(format!(
+                "{} {} failed ({}): {}",
+                method,
+                path,
+                status,
+                error_message(body)
+            )));
+        }
+
+        serde_json::from_slice(body).map_err(|e| {
+            CliError::Command(format!("invalid response from daemon for {}: {}", path, e))
+        })
+    }
+
+    async fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&[u8]>,
+    ) -> Result<Vec<u8>, CliError> {
+        let unavailable =
+            |e: std::io::Error| CliError::DaemonUnavailable(format!("{}: {}", self.addr, e));
+
+        let content = match body {
+            Some(body) => format!(
+                "Content-Type: application/json\r\nContent-Length: {}\r\n",
+                body.len()
+            ),
+            None => String::new(),
+        };
+        let authorization = match &self.token {
+            Some(token) => format!("Authorization: Bearer {}\r\n", token),
+            None => String::new(),
+        };
+        let host = match self.transport {
+            #[cfg(unix)]
+            Transport::Unix(_) => "localhost",
+            _ => self.addr.as_str(),
+        };
+        let mut request = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nAccept: application/json\r\n{}{}{}",
+            method, path, host, authorization, content, "Connection: close\r\n\r\n"
+        )
+        .into_bytes();
+        if let Some(body) = body {
+            request.extend_from_slice(body);
+        }
+
+        match &self.transport {
+            Transport::Tcp => {
+                let stream = TcpStream::connect(&self.addr).await.map_err(unavailable)?;
+                exchange(stream, &request).await
+            }
+            Transport::Tls {
+                connector,
+                server_name,
+            } => {
+                let stream = TcpStream::connect(&self.addr).await.map_err(unavailable)?;
+                let stream = connector
+                    .connect(server_name.clone(), stream)
+                    .await
+                    .map_err(|e| {
+                        CliError::DaemonUnavailable(format!(
+                            "{}: TLS handshake failed: {}",
+                            self.addr, e
+                        ))
+                    })?;
+                exchange(stream, &request).await
+            }
+            #[cfg(unix)]
+            Transport::Unix(path) => {
+                let stream = tokio::net::UnixStream::connect(path)
+                    .await
+                    .map_err(unavailable)?;
+                exchange(stream, &request).await
+            }
+        }
+    }
+}
+
+/// Write `request` and read the response until the daemon closes the connection.
+async fn exchange<S>(mut stream: S, request: &[u8]) -> Result<Vec<u8>, CliError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream.write_all(request).await?;
+    stream.flush().await?;
+
+    let mut raw = Vec::new();
+    match stream.take(MAX_RESPONSE_BYTES).read_to_end(&mut raw).await {
+        Ok(_) => Ok(raw),
+        // The daemon closes TLS connections without close_notify once the
+        // response is written
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof && !raw.is_empty() => Ok(raw),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Split `host` into (https, host, port); a missing port is `default_port`.
+fn parse_host(
+    host: &str,
+    default_https: bool,
+    default_port: u16,
+) -> Result<(bool, String, u16), CliError> {
+    let invalid =
+        |reason: &str| CliError::Config(format!("invalid daemon host '{}': {}", host, reason));
+
+    let (https, rest) = match host.split_once("://") {
+        Some(("https", rest)) => (true, rest),
+        Some(("http", rest)) => (false, rest),
+        Some(_) => return Err(invalid("expected http:// or https://")),
+        None => (default_https, host),
+    };
+    let rest = rest.trim_end_matches('/');
+    if rest.is_empty() || rest.contains('/') {
+        return Err(invalid("expected host[:port]"));
+    }
+
+    let (name, port) = if let Some(bracketed) = rest.strip_prefix('[') {
+        let (name, tail) = bracketed
+            .split_once(']')
+            .ok_or_else(|| invalid("unterminated IPv6 address"))?;
+        match tail.strip_prefix(':') {
+            Some(port) => (name, Some(port)),
+            None if tail.is_empty() => (name, None),
+            None => return Err(invalid("expected host[:port]")),
+        }
+    } else if rest.matches(':').count() > 1 {
+        // Bare IPv6 address without a port
+        (rest, None)
+    } else {
+        match rest.split_once(':') {
+            Some((name, port)) => (name, Some(port)),
+            None => (rest, None),
+        }
+    };
+    let port = match port {
+        Some(port) => port
+            .parse::<u16>()
+            .ok()
+            .filter(|p| *p != 0)
+            .ok_or_else(|| invalid("invalid port"))?,
+        None => default_port,
+    };
+    if name.is_empty() {
+        return Err(invalid("missing host name"));
+    }
+    Ok((https, name.to_owned(), port))
+}
+
+/// Whether `host` is `localhost` or a loopback address.
+fn is_loopback(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Token from `token`, or the first line of `token_file`.
+fn read_token(client: &ClientConfig) -> Result<Option<String>, CliError> {
+    let token = if !client.token.is_empty() {
+        client.token.trim().to_owned()
+    } else if !client.token_file.is_empty() {
+        std::fs::read_to_string(&client.token_file)
+            .map_err(|e| {
+                CliError::Config(format!(
+                    "failed to read token file {}: {}",
+                    client.token_file, e
+                ))
+            })?
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_owned()
+    } else {
+        return Ok(None);
+    };
+    // The token goes into a header line
+    if token.is_empty() || token.chars().any(|c| c.is_control() || c == ' ') {
+        return Err(CliError::Config(
+            "the control API token is empty or contains whitespace".to_owned(),
+        ));
+    }
+    Ok(Some(token))
+}
+
+/// TLS connector verifying the daemon against `[client] ca_cert_path`.
+fn tls_connector(client: &ClientConfig) -> Result<TlsConnector, CliError> {
+    if client.ca_cert_path.is_empty() {
+        return Err(CliError::Config(
+            "HTTPS needs [client] ca_cert_path (the CA that signed the daemon certificate)"
+                .to_owned(),
+        ));
+    }
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(Path::new(&client.ca_cert_path))? {
+        roots
+            .add(cert)
+            .map_err(|e| CliError::Config(format!("invalid CA certificate: {}", e)))?;
+    }
+
+    let builder =
+        TlsClientConfig::builder_with_provider(Arc::new(crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| CliError::Config(format!("failed to configure TLS: {}", e)))?
+            .with_root_certificates(roots);
+    let tls = if client.client_cert_path.is_empty() {
+        builder.with_no_client_auth()
+    } else {
+        let certs = load_certs(Path::new(&client.client_cert_path))?;
+        let key = PrivateKeyDer::from_pem_file(&client.client_key_path).map_err(|e| {
+            CliError::Config(format!(
+                "failed to load client key {}: {}",
+                client.client_key_path, e
+            ))
+        })?;
+        builder
+            .with_client_auth_cert(certs, key)
+            .map_err(|e| CliError::Config(format!("invalid client certificate or key: {}", e)))?
+    };
+    Ok(TlsConnector::from(Arc::new(tls)))
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, CliError> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .map_err(|e| CliError::Config(format!("failed to read {}: {}", path.display(), e)))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| CliError::Config(format!("failed to parse {}: {}", path.display(), e)))?;
+    if certs.is_empty() {
+        return Err(CliError::Config(format!(
+            "no certificates found in {}",
+            path.display()
+        )));
+    }
+    Ok(certs)
+}
+
+/// Split a raw HTTP response into status code and body.
+fn parse_response(raw: &[u8]) -> Result<(u16, &[u8]), CliError> {
+    let malformed = || CliError::Command("malformed response from daemon".to_owned());
+
+    let header_end = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(malformed)?;
+    let head = std::str::from_utf8(&raw[..header_end]).map_err(|_| malformed())?;
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(malformed)?;
+
+    Ok((status, &raw[header_end + 4..]))
+}
+
+/// Extract the `error` field of a JSON error body, or the raw body.
+fn error_message(body: &[u8]) -> String {
+    serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(str::to_owned))
+        .unwrap_or_else(|| String::from_utf8_lossy(body).trim().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    async fn serve_once(response: &'static str) -> DaemonClient {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move {
+            if let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        DaemonClient::tcp(addr.to_string())
+    }
+
+    impl DaemonClient {
+        fn tcp(addr: String) -> Self {
+            Self {
+                addr,
+                transport: Transport::Tcp,
+                token: None,
+                timeout: REQUEST_TIMEOUT,
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_config_uses_loopback_for_unspecified_addr() {
+        let mut config = IronpostConfig::default();
+        config.health.enabled = true;
+        config.health.listen_addr = "0.0.0.0".to_owned();
+        config.health.port = 9100;
+
+        let client = DaemonClient::from_config(&config).expect("client");
+        assert_eq!(client.addr(), "127.0.0.1:9100");
+
+        config.health.listen_addr = "::".to_owned();
+        let client = DaemonClient::from_config(&config).expect("client");
+        assert_eq!(client.addr(), "[::1]:9100");
+    }
+
+    #[test]
+    fn test_from_config_rejects_disabled_listener() {
+        let mut config = IronpostConfig::default();
+        config.health.enabled = false;
+
+        let err = DaemonClient::from_config(&config).expect_err("should fail");
+        assert_eq!(err.exit_code(), 3);
+    }
+
+    #[test]
+    fn test_parse_response_splits_status_and_body() {
+        let raw = b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"ok\":true}";
+        let (status, body) = parse_response(raw).expect("should parse");
+        assert_eq!(status, 200);
+        assert_eq!(body, b"{\"ok\":true}");
+    }
+
+    #[test]
+    fn test_parse_response_rejects_garbage() {
+        assert!(parse_response(b"not http").is_err());
+        assert!(parse_response(b"HTTP/1.1 abc\r\n\r\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_json_returns_body() {
+        let client =
+            serve_once("HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n{\"rule_count\":3}").await;
+
+        let value: serde_json::Value = client.get_json("/ebpf").await.expect("should succeed");
+        assert_eq!(value["rule_count"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_json_reports_daemon_error() {
+        let client = serve_once(
+            "HTTP/1.1 401 Unauthorized\r\nConnection: close\r\n\r\n{\"error\":\"missing bearer token\"}",
+        )
+        .await;
+
+        let err = client
+            .get_json::<serde_json::Value>("/ebpf")
+            .await
+            .expect_err("should fail");
+        let message = err.to_string();
+        assert!(message.contains("401"), "got: {}", message);
+        assert!(message.contains("missing bearer token"), "got: {}", message);
+    }
+
+    #[tokio::test]
+    async fn test_post_json_sends_body() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let addr = listener.local_addr().expect("local addr");
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept");
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"{\"n\":1}") {
+                let n = stream.read(&mut buf).await.expect("read");
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let _ = stream
+                .write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n{\"added\":1}")
+                .await;
+            String::from_utf8(request).expect("valid UTF-8")
+        });
+        let client = DaemonClient::tcp(addr.to_string());
+
+        let value: serde_json::Value = client
+            .post_json("/ebpf/blocklist", &serde_json::json!({ "n": 1 }))
+            .await
+            .expect("should succeed");
+        let request = server.await.expect("server task");
+
+        assert_eq!(value["added"], 1);
+        assert!(request.starts_with("POST /ebpf/blocklist HTTP/1.1"));
+        assert!(request.contains("Content-Length: 7"));
+    }
+
+    #[tokio::test]
+    async fn test_get_json_unreachable_daemon() {
+        // Bind and drop to get a port with nothing listening
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr").to_string();
+        drop(listener);
+
+        let client = DaemonClient::tcp(addr);
+        let err = client
+            .get_json::<serde_json::Value>("/ebpf")
+            .await
+            .expect_err("should fail");
+        assert_eq!(err.exit_code(), 3);
+    }
+
+    #[test]
+    fn test_parse_host_forms() {
+        let parse = |host| parse_host(host, false, 9100).expect("should parse");
+        assert_eq!(parse("daemon"), (false, "daemon".to_owned(), 9100));
+        assert_eq!(parse("daemon:9200"), (false, "daemon".to_owned(), 9200));
+        assert_eq!(
+            parse("https://daemon.example:9443/"),
+            (true, "daemon.example".to_owned(), 9443)
+        );
+        assert_eq!(parse("http://[::1]:9101"), (false, "::1".to_owned(), 9101));
+        assert_eq!(parse("fd00::1"), (false, "fd00::1".to_owned(), 9100));
+        assert_eq!(
+            parse_host("daemon", true, 9100).expect("should parse"),
+            (true, "daemon".to_owned(), 9100)
+        );
+
+        for host in [
+            "grpc://daemon",
+            "daemon:0",
+            "daemon:x",
+            "http://",
+            "daemon/status",
+        ] {
+            assert!(parse_host(host, false, 9100).is_err(), "accepted {}", host);
+        }
+    }
+
+    #[test]
+    fn test_overrides_take_precedence_over_config() {
+        let mut config = IronpostConfig::default();
+        config.client.socket = "/run/ironpost/control.sock".to_owned();
+        config.client.token = "from-config".to_owned();
+
+        let client = DaemonClient::with_overrides(
+            &config,
+            ConnectionOverrides {
+                host: Some("http://10.0.0.5:9101".to_owned()),
+                socket: None,
+                token: Some("from-flag".to_owned()),
+                insecure: true,
+            },
+        )
+        .expect("client");
+
+        assert_eq!(client.addr(), "10.0.0.5:9101");
+        assert!(matches!(client.transport, Transport::Tcp));
+        assert_eq!(client.token.as_deref(), Some("from-flag"));
+    }
+
+    #[test]
+    fn test_token_over_plain_http_requires_loopback_or_insecure() {
+        let mut config = IronpostConfig::default();
+        config.client.token = "s3cret-token".to_owned();
+        let overrides = |host: &str, insecure| ConnectionOverrides {
+            host: Some(host.to_owned()),
+            insecure,
+            ..ConnectionOverrides::default()
+        };
+
+        let err = DaemonClient::with_overrides(&config, overrides("http://10.0.0.5:9101", false))
+            .expect_err("token over plain HTTP to a remote host");
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("--insecure"), "got: {}", err);
+
+        for host in ["http://127.0.0.1:9101", "localhost", "[::1]:9101"] {
+            assert!(
+                DaemonClient::with_overrides(&config, overrides(host, false)).is_ok(),
+                "{} is loopback",
+                host
+            );
+        }
+        assert!(
+            DaemonClient::with_overrides(&config, overrides("http://10.0.0.5:9101", true)).is_ok()
+        );
+
+        // Without a token nothing secret is sent
+        config.client.token.clear();
+        assert!(
+            DaemonClient::with_overrides(&config, overrides("http://10.0.0.5:9101", false)).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_https_host_requires_ca_certificate() {
+        let mut config = IronpostConfig::default();
+        config.client.host = "https://daemon.example".to_owned();
+
+        let err = DaemonClient::from_config(&config).expect_err("should fail");
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("ca_cert_path"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_token_read_from_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("token");
+        std::fs::write(&path, "s3cret-token\n").expect("write token");
+
+        let client = ClientConfig {
+            token_file: path.display().to_string(),
+            ..ClientConfig::default()
+        };
+        assert_eq!(
+            read_token(&client).expect("token").as_deref(),
+            Some("s3cret-token")
+        );
+
+        std::fs::write(&path, "\n").expect("write token");
+        assert!(read_token(&client).is_err());
+        assert_eq!(
+            read_token(&ClientConfig::default()).expect("no token"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_debug_hides_token() {
+        let mut client = DaemonClient::tcp("127.0.0.1:9101".to_owned());
+        client.token = Some("s3cret-token".to_owned());
+        assert!(!format!("{:?}", client).contains("s3cret-token"));
+    }
+
+    #[tokio::test]
+    async fn test_request_sends_bearer_token() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let addr = listener.local_addr().expect("local addr");
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept");
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.expect("read");
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let _ = stream
+                .write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n{}")
+                .await;
+            String::from_utf8(request).expect("valid UTF-8")
+        });
+        let mut client = DaemonClient::tcp(addr.to_string());
+        client.token = Some("s3cret-token".to_owned());
+
+        let _: serde_json::Value = client.get_json("/status").await.expect("should succeed");
+        let request = server.await.expect("server task");
+
+        assert!(request.contains("Authorization: Bearer s3cret-token\r\n"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_get_json_over_unix_socket() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("control.sock");
+        let listener = tokio::net::UnixListener::bind(&path).expect("bind socket");
+        tokio::spawn(async move {
+            if let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n{\"ok\":true}")
+                    .await;
+            }
+        });
+
+        let config = IronpostConfig::default();
+        let client = DaemonClient::with_overrides(
+            &config,
+            ConnectionOverrides {
+                socket: Some(path.clone()),
+                ..ConnectionOverrides::default()
+            },
+        )
+        .expect("client");
+        assert_eq!(client.addr(), path.display().to_string());
+
+        let value: serde_json::Value = client.get_json("/status").await.expect("should succeed");
+        assert_eq!(value["ok"], true);
+    }
+}
@@ -0,0 +1,575 @@
+//! `ironpost alerts` command handler
+//!
+//! Reads the daemon's `/alerts` feed: the alerts that passed the alert gate,
+//! each with a sequence number. `tail` polls the feed once a second and prints
+//! each new alert as it arrives; `list` prints the alerts the daemon currently
+//! holds. The daemon keeps recent alerts in memory only, so `list` shows the
+//! history since the daemon last started (up to its buffer size).
+//!
+//! Both subcommands filter by minimum severity, rule name and source module.
+//! With `--enrich`, source and target addresses are annotated with GeoIP
+//! country, ASN and reverse DNS from the `[log_pipeline.enrichment]` backends.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use ironpost_core::config::IronpostConfig;
+use ironpost_core::types::Severity;
+use ironpost_log_pipeline::{IpEnricher, IpEnrichment};
+
+use crate::cli::{AlertFilter, AlertsAction, AlertsArgs};
+use crate::client::DaemonClient;
+use crate::commands::container::format_time;
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Daemon control endpoint serving recent alerts.
+const ALERTS_PATH: &str = "/alerts";
+
+/// Poll interval of `alerts tail`.
+const TAIL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Execute the `alerts` command.
+pub async fn execute(
+    args: AlertsArgs,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    let client = DaemonClient::from_config(&config)?;
+
+    match args.action {
+        AlertsAction::Tail { filter, enrich } => {
+            let enricher = enrich.then(|| load_enricher(&config)).transpose()?;
+            execute_tail(&client, &filter, enricher, writer).await
+        }
+        AlertsAction::List {
+            filter,
+            limit,
+            enrich,
+        } => {
+            let enricher = enrich.then(|| load_enricher(&config)).transpose()?;
+            execute_list(&client, &filter, limit, enricher, writer).await
+        }
+    }
+}
+
+/// Execute the alerts tail subcommand.
+///
+/// Starts after the newest alert the daemon holds and prints matching alerts
+/// every second until Ctrl-C. If the daemon restarts, tailing resumes from
+/// its new feed.
+///
+/// # Errors
+///
+/// Returns `CliError::DaemonUnavailable` if the daemon cannot be reached.
+async fn execute_tail(
+    client: &DaemonClient,
+    filter: &AlertFilter,
+    enricher: Option<Arc<IpEnricher>>,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    debug!(addr = client.addr(), "tailing alerts");
+
+    let mut since = fetch_alerts(client, 0).await?.last_seq;
+
+    let mut interval = tokio::time::interval(TAIL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let page = fetch_alerts(client, since).await?;
+                if page.last_seq < since {
+                    info!("daemon alert feed restarted, following the new feed");
+                    since = 0;
+                    continue;
+                }
+                if page.truncated {
+                    warn!("some alerts were dropped before they could be read");
+                }
+                let mut alerts: Vec<AlertEntry> = page
+                    .alerts
+                    .into_iter()
+                    .filter(|a| matches_filter(a, filter))
+                    .collect();
+                if let Some(enricher) = &enricher {
+                    enrich_alerts(enricher, &mut alerts).await;
+                }
+                for alert in &alerts {
+                    writer.render_line(alert)?;
+                }
+                since = page.last_seq;
+            }
+            _ = tokio::signal::ctrl_c() => {
+                debug!("tail interrupted");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Execute the alerts list subcommand.
+///
+/// # Errors
+///
+/// Returns `CliError::DaemonUnavailable` if the daemon cannot be reached.
+async fn execute_list(
+    client: &DaemonClient,
+    filter: &AlertFilter,
+    limit: usize,
+    enricher: Option<Arc<IpEnricher>>,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let page = fetch_alerts(client, 0).await?;
+    let mut report = build_list_report(page.alerts, filter, limit);
+    if let Some(enricher) = &enricher {
+        enrich_alerts(enricher, &mut report.alerts).await;
+    }
+    writer.render(&report)?;
+    Ok(())
+}
+
+pub(crate) async fn fetch_alerts(client: &DaemonClient, since: u64) -> Result<AlertPage, CliError> {
+    client
+        .get_json(&format!("{}?since={}", ALERTS_PATH, since))
+        .await
+}
+
+/// Enrichment backends configured in `[log_pipeline.enrichment]`, for `--enrich`.
+///
+/// # Errors
+///
+/// Returns `CliError::Config` if a GeoIP database cannot be opened, or if no
+/// backend is configured.
+pub(crate) fn load_enricher(config: &IronpostConfig) -> Result<Arc<IpEnricher>, CliError> {
+    let enricher = IpEnricher::from_config(&config.log_pipeline.enrichment)
+        .map_err(|e| CliError::Config(e.to_string()))?;
+    if !enricher.is_enabled() {
+        return Err(CliError::Config(
+            "--enrich needs [log_pipeline.enrichment] geoip_country_db, geoip_asn_db \
+             or reverse_dns = true"
+                .to_owned(),
+        ));
+    }
+    Ok(Arc::new(enricher))
+}
+
+/// Enrich each distinct address concurrently; addresses with nothing found are left out.
+pub(crate) async fn enrich_addresses(
+    enricher: &Arc<IpEnricher>,
+    addresses: impl IntoIterator<Item = IpAddr>,
+) -> HashMap<IpAddr, IpEnrichment> {
+    let mut lookups = tokio::task::JoinSet::new();
+    let mut seen = std::collections::HashSet::new();
+    for ip in addresses {
+        if seen.insert(ip) {
+            let enricher = Arc::clone(enricher);
+            lookups.spawn(async move { (ip, enricher.enrich(ip).await) });
+        }
+    }
+
+    let mut found = HashMap::new();
+    while let Some(result) = lookups.join_next().await {
+        match result {
+            Ok((ip, enrichment)) if !enrichment.is_empty() => {
+                found.insert(ip, enrichment);
+            }
+            Ok(_) => {}
+            Err(e) => debug!(error = %e, "enrichment lookup failed"),
+        }
+    }
+    found
+}
+
+/// Attach enrichment for the source and target addresses of `alerts`.
+async fn enrich_alerts(enricher: &Arc<IpEnricher>, alerts: &mut [AlertEntry]) {
+    let addresses = alerts
+        .iter()
+        .flat_map(|a| [a.source_ip, a.target_ip])
+        .flatten();
+    let found = enrich_addresses(enricher, addresses).await;
+    for alert in alerts {
+        alert.source_enrichment = alert.source_ip.and_then(|ip| found.get(&ip).cloned());
+        alert.target_enrichment = alert.target_ip.and_then(|ip| found.get(&ip).cloned());
+    }
+}
+
+/// One-line summary of an enrichment, e.g. `KR, AS4766 Korea Telecom, host.example`.
+pub(crate) fn format_enrichment(enrichment: &IpEnrichment) -> String {
+    let asn = match (enrichment.asn, enrichment.as_org.as_deref()) {
+        (Some(asn), Some(org)) => Some(format!("AS{} {}", asn, org)),
+        (Some(asn), None) => Some(format!("AS{}", asn)),
+        (None, Some(org)) => Some(org.to_owned()),
+        (None, None) => None,
+    };
+    [enrichment.country.clone(), asn, enrichment.hostname.clone()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `ip`, followed by its enrichment in parentheses when there is one.
+fn describe_ip(ip: IpAddr, enrichment: Option<&IpEnrichment>) -> String {
+    match enrichment {
+        Some(enrichment) => format!("{} ({})", ip, format_enrichment(enrichment)),
+        None => ip.to_string(),
+    }
+}
+
+/// Whether an alert passes the severity, rule and module filters.
+fn matches_filter(alert: &AlertEntry, filter: &AlertFilter) -> bool {
+    filter
+        .severity
+        .is_none_or(|min| alert.severity >= Severity::from(min))
+        && filter.rule.as_deref().is_none_or(|rule| alert.rule == rule)
+        && filter
+            .module
+            .as_deref()
+            .is_none_or(|module| alert.module == module)
+}
+
+/// Keep the `limit` most recent alerts that pass the filter.
+fn build_list_report(
+    alerts: Vec<AlertEntry>,
+    filter: &AlertFilter,
+    limit: usize,
+) -> AlertListReport {
+    let mut matching: Vec<AlertEntry> = alerts
+        .into_iter()
+        .filter(|a| matches_filter(a, filter))
+        .collect();
+    let total = matching.len();
+    matching.drain(..total.saturating_sub(limit));
+    AlertListReport {
+        total,
+        shown: matching.len(),
+        alerts: matching,
+    }
+}
+
+/// Response of the daemon's `/alerts` endpoint.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AlertPage {
+    pub(crate) last_seq: u64,
+    #[serde(default)]
+    pub(crate) truncated: bool,
+    pub(crate) alerts: Vec<AlertEntry>,
+}
+
+/// An alert raised by the daemon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEntry {
+    /// Position in the daemon's alert feed
+    pub seq: u64,
+    /// Alert ID
+    pub id: String,
+    /// Time the alert was created (Unix milliseconds)
+    pub timestamp_ms: u64,
+    /// Alert severity
+    pub severity: Severity,
+    /// Module that raised the alert
+    pub module: String,
+    /// Detection rule name
+    pub rule: String,
+    /// Alert title
+    pub title: String,
+    /// Alert description
+    #[serde(default)]
+    pub description: String,
+    /// Source IP, when known
+    #[serde(default)]
+    pub source_ip: Option<IpAddr>,
+    /// Target IP, when known
+    #[serde(default)]
+    pub target_ip: Option<IpAddr>,
+    /// GeoIP/ASN/reverse DNS of the source IP (`--enrich`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_enrichment: Option<IpEnrichment>,
+    /// GeoIP/ASN/reverse DNS of the target IP (`--enrich`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_enrichment: Option<IpEnrichment>,
+}
+
+/// Recent alerts held by the daemon.
+#[derive(Serialize)]
+pub struct AlertListReport {
+    /// Alerts that passed the filters
+    pub total: usize,
+    /// Alerts shown after applying `--limit`
+    pub shown: usize,
+    /// Alerts, oldest first
+    pub alerts: Vec<AlertEntry>,
+}
+
+impl Render for AlertEntry {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        let name = self.severity.to_string();
+        let label = format!("{:<8}", name);
+        let severity = match self.severity {
+            Severity::Critical => label.red().bold(),
+            Severity::High => label.red(),
+            Severity::Medium => label.yellow(),
+            Severity::Low => label.normal(),
+            Severity::Info => label.dimmed(),
+        };
+        let source = self
+            .source_ip
+            .map(|ip| describe_ip(ip, self.source_enrichment.as_ref()));
+        let target = self
+            .target_ip
+            .map(|ip| describe_ip(ip, self.target_enrichment.as_ref()));
+        let entities = match (source, target) {
+            (Some(source), Some(target)) => format!(" {} -> {}", source, target),
+            (Some(source), None) => format!(" from {}", source),
+            (None, Some(target)) => format!(" to {}", target),
+            (None, None) => String::new(),
+        };
+        writeln!(
+            w,
+            "{}  {} {:<13} {} ({}){}",
+            format_time(self.timestamp_ms),
+            severity,
+            self.module.cyan(),
+            self.title,
+            self.rule.dimmed(),
+            entities
+        )
+    }
+}
+
+impl Render for AlertListReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        if self.alerts.is_empty() {
+            writeln!(w, "{}", "No alerts.".dimmed())?;
+            return Ok(());
+        }
+        for alert in &self.alerts {
+            alert.render_text(w)?;
+        }
+        if self.shown < self.total {
+            writeln!(
+                w,
+                "{}",
+                format!(
+                    "Showing the {} most recent of {} alerts (use --limit to show more).",
+                    self.shown, self.total
+                )
+                .dimmed()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::SeverityLevel;
+
+    fn entry(seq: u64, severity: Severity, rule: &str, module: &str) -> AlertEntry {
+        AlertEntry {
+            seq,
+            id: format!("alert-{}", seq),
+            timestamp_ms: 1_700_000_000_000,
+            severity,
+            module: module.to_owned(),
+            rule: rule.to_owned(),
+            title: format!("{} detected", rule),
+            description: String::new(),
+            source_ip: "10.0.0.1".parse().ok(),
+            target_ip: None,
+            source_enrichment: None,
+            target_enrichment: None,
+        }
+    }
+
+    fn render(payload: &impl Render) -> String {
+        let mut buf = Vec::new();
+        payload
+            .render_text(&mut buf)
+            .expect("text rendering should succeed");
+        String::from_utf8(buf).expect("valid UTF-8")
+    }
+
+    #[test]
+    fn test_matches_filter_by_min_severity() {
+        let filter = AlertFilter {
+            severity: Some(SeverityLevel::High),
+            ..AlertFilter::default()
+        };
+        assert!(matches_filter(
+            &entry(1, Severity::Critical, "a", "log-pipeline"),
+            &filter
+        ));
+        assert!(matches_filter(
+            &entry(2, Severity::High, "a", "log-pipeline"),
+            &filter
+        ));
+        assert!(!matches_filter(
+            &entry(3, Severity::Medium, "a", "log-pipeline"),
+            &filter
+        ));
+    }
+
+    #[test]
+    fn test_matches_filter_by_rule_and_module() {
+        let filter = AlertFilter {
+            severity: None,
+            rule: Some("ssh_brute_force".to_owned()),
+            module: Some("log-pipeline".to_owned()),
+        };
+        assert!(matches_filter(
+            &entry(1, Severity::Low, "ssh_brute_force", "log-pipeline"),
+            &filter
+        ));
+        assert!(!matches_filter(
+            &entry(2, Severity::Low, "ssh_brute_force", "sbom-scanner"),
+            &filter
+        ));
+        assert!(!matches_filter(
+            &entry(3, Severity::Low, "port_scan", "log-pipeline"),
+            &filter
+        ));
+    }
+
+    #[test]
+    fn test_list_report_keeps_most_recent_matches() {
+        let alerts = vec![
+            entry(1, Severity::High, "a", "log-pipeline"),
+            entry(2, Severity::Low, "b", "log-pipeline"),
+            entry(3, Severity::High, "c", "log-pipeline"),
+            entry(4, Severity::Critical, "d", "log-pipeline"),
+        ];
+        let filter = AlertFilter {
+            severity: Some(SeverityLevel::High),
+            ..AlertFilter::default()
+        };
+
+        let report = build_list_report(alerts, &filter, 2);
+
+        assert_eq!(report.total, 3);
+        assert_eq!(report.shown, 2);
+        let seqs: Vec<u64> = report.alerts.iter().map(|a| a.seq).collect();
+        assert_eq!(seqs, vec![3, 4]);
+    }
+
+    #[test]
+    fn test_alert_page_deserializes_daemon_response() {
+        let json = r#"{
+            "last_seq": 7,
+            "truncated": false,
+            "alerts": [{
+                "seq": 7,
+                "id": "a-7",
+                "timestamp_ms": 1700000000000,
+                "severity": "Critical",
+                "module": "log-pipeline",
+                "rule": "ssh_brute_force",
+                "title": "SSH brute force",
+                "description": "",
+                "source_ip": "10.0.0.1",
+                "target_ip": null
+            }]
+        }"#;
+
+        let page: AlertPage = serde_json::from_str(json).expect("should deserialize");
+
+        assert_eq!(page.last_seq, 7);
+        assert_eq!(page.alerts[0].severity, Severity::Critical);
+        assert_eq!(page.alerts[0].source_ip, "10.0.0.1".parse().ok());
+    }
+
+    #[test]
+    fn test_alert_entry_render_text() {
+        let output = render(&entry(1, Severity::High, "ssh_brute_force", "log-pipeline"));
+        assert!(output.contains("High"));
+        assert!(output.contains("log-pipeline"));
+        assert!(output.contains("ssh_brute_force detected"));
+        assert!(output.contains("from 10.0.0.1"));
+    }
+
+    #[test]
+    fn test_list_report_render_limit_note() {
+        let report = AlertListReport {
+            total: 5,
+            shown: 1,
+            alerts: vec![entry(5, Severity::Low, "a", "ironpost-daemon")],
+        };
+        let output = render(&report);
+        assert!(output.contains("Showing the 1 most recent of 5 alerts"));
+        assert!(
+            render(&AlertListReport {
+                total: 0,
+                shown: 0,
+                alerts: Vec::new(),
+            })
+            .contains("No alerts.")
+        );
+    }
+
+    #[test]
+    fn test_format_enrichment() {
+        let full = IpEnrichment {
+            country: Some("KR".to_owned()),
+            asn: Some(4766),
+            as_org: Some("Korea Telecom".to_owned()),
+            hostname: Some("host.example".to_owned()),
+        };
+        assert_eq!(
+            format_enrichment(&full),
+            "KR, AS4766 Korea Telecom, host.example"
+        );
+
+        let asn_only = IpEnrichment {
+            asn: Some(64500),
+            ..IpEnrichment::default()
+        };
+        assert_eq!(format_enrichment(&asn_only), "AS64500");
+    }
+
+    #[test]
+    fn test_alert_entry_render_text_with_enrichment() {
+        let mut alert = entry(1, Severity::High, "ssh_brute_force", "log-pipeline");
+        alert.source_enrichment = Some(IpEnrichment {
+            country: Some("NL".to_owned()),
+            hostname: Some("scanner.example".to_owned()),
+            ..IpEnrichment::default()
+        });
+
+        let output = render(&alert);
+        assert!(
+            output.contains("from 10.0.0.1 (NL, scanner.example)"),
+            "got: {}",
+            output
+        );
+
+        let json = serde_json::to_value(&alert).expect("serialize");
+        assert_eq!(json["source_enrichment"]["country"], "NL");
+        assert!(json.get("target_enrichment").is_none());
+    }
+
+    #[test]
+    fn test_load_enricher_requires_a_backend() {
+        let mut config = IronpostConfig::default();
+        config.log_pipeline.enrichment.reverse_dns = false;
+
+        let err = load_enricher(&config).expect_err("should fail");
+        assert_eq!(err.exit_code(), 2);
+
+        config.log_pipeline.enrichment.reverse_dns = true;
+        assert!(load_enricher(&config).is_ok());
+    }
+}
@@ -0,0 +1,690 @@
+//! `ironpost bench` command handler
+//!
+//! Measures the detection path in-process, without a running daemon:
+//! synthetic syslog/JSON lines go through the `ParserRouter` and the
+//! `RuleEngine` loaded with the configured rules, and optionally synthetic
+//! packet events go through the SYN flood and port scan detectors.
+//!
+//! Lines are generated up front from a fixed seed, so runs are comparable
+//! across builds and machines; only parsing, rule evaluation and detection
+//! are timed. The report gives events per second and per-stage latency
+//! percentiles, and `--min-eps` turns it into a regression gate for CI.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use ironpost_core::config::IronpostConfig;
+use ironpost_log_pipeline::rule::RuleLoader;
+use ironpost_log_pipeline::{ParserRouter, PipelineConfig, RuleEngine};
+
+use crate::cli::{BenchArgs, BenchFormat};
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Seed of the synthetic event generator.
+const SEED: u64 = 0x1c0f_fee5_eed5_2024;
+
+/// Hosts that synthetic log lines come from.
+const HOSTS: [&str; 8] = [
+    "web-01",
+    "web-02",
+    "db-01",
+    "bastion",
+    "mail",
+    "k8s-node-1",
+    "k8s-node-2",
+    "vpn",
+];
+
+/// Users named in synthetic authentication events.
+const USERS: [&str; 6] = ["root", "admin", "deploy", "postgres", "alice", "ubuntu"];
+
+/// Execute the `bench` command.
+///
+/// # Errors
+///
+/// Returns `CliError::Rule` if the rules cannot be loaded, `CliError::Command`
+/// if packet detectors were requested off Linux or throughput is below
+/// `--min-eps`.
+pub async fn execute(
+    args: BenchArgs,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    if args.packets > 0 && !cfg!(target_os = "linux") {
+        return Err(CliError::Command(
+            "packet detectors are only available on Linux; omit --packets".to_owned(),
+        ));
+    }
+
+    let (engine, rule_dir) = load_engine(&config, args.rules.as_deref()).await?;
+    info!(
+        events = args.events,
+        rules = engine.rule_count(),
+        "running benchmark"
+    );
+
+    let lines = generate_lines(args.format, args.events);
+    let log = bench_logs(&ParserRouter::with_defaults(), &engine, &lines)?;
+    let packets = if args.packets > 0 {
+        Some(bench_packets(args.packets)?)
+    } else {
+        None
+    };
+
+    let report = BenchReport {
+        format: args.format,
+        rule_dir,
+        rules: engine.rule_count(),
+        log,
+        packets,
+    };
+    writer.render(&report)?;
+
+    if let Some(min_eps) = args.min_eps
+        && report.log.events_per_sec < min_eps
+    {
+        return Err(CliError::Command(format!(
+            "log throughput {} events/s is below --min-eps {}",
+            report.log.events_per_sec, min_eps
+        )));
+    }
+    Ok(())
+}
+
+/// Load the rules from `--rules`, or from the pipeline's rule directory.
+///
+/// A missing default directory runs the benchmark without rules; a missing
+/// `--rules` directory is an error.
+pub(crate) async fn load_engine(
+    config: &IronpostConfig,
+    rules: Option<&Path>,
+) -> Result<(RuleEngine, PathBuf), CliError> {
+    let (rule_dir, explicit) = match rules {
+        Some(dir) => (dir.to_path_buf(), true),
+        None => (
+            PathBuf::from(PipelineConfig::from_core(&config.log_pipeline).rule_dir),
+            false,
+        ),
+    };
+
+    let mut engine = RuleEngine::new();
+    if !explicit && !rule_dir.is_dir() {
+        warn!(
+            rule_dir = %rule_dir.display(),
+            "rule directory not found, benchmarking without rules"
+        );
+        return Ok((engine, rule_dir));
+    }
+
+    let rules = RuleLoader::load_directory(&rule_dir)
+        .await
+        .map_err(|e| CliError::Rule(format!("failed to load rules: {}", e)))?;
+    engine
+        .replace_rules(rules)
+        .map_err(|e| CliError::Rule(format!("failed to load rules: {}", e)))?;
+    Ok((engine, rule_dir))
+}
+
+/// Parse and match every line, timing each stage per event.
+fn bench_logs(
+    parser: &ParserRouter,
+    engine: &RuleEngine,
+    lines: &[Vec<u8>],
+) -> Result<LogBench, CliError> {
+    let mut parse_ns = Vec::with_capacity(lines.len());
+    let mut match_ns = Vec::with_capacity(lines.len());
+    let mut parse_errors = 0;
+    let mut matches = 0;
+
+    let started = Instant::now();
+    for line in lines {
+        let t0 = Instant::now();
+        let parsed = parser.parse(line);
+        parse_ns.push(nanos(t0.elapsed()));
+
+        let entry = match parsed {
+            Ok(entry) => entry,
+            Err(e) => {
+                debug!(error = %e, "synthetic line failed to parse");
+                parse_errors += 1;
+                continue;
+            }
+        };
+
+        let t1 = Instant::now();
+        let found = engine
+            .evaluate(&entry)
+            .map_err(|e| CliError::Rule(e.to_string()))?;
+        match_ns.push(nanos(t1.elapsed()));
+        matches += found.len();
+    }
+    let elapsed = started.elapsed();
+
+    Ok(LogBench {
+        events: lines.len(),
+        parse_errors,
+        matches,
+        elapsed_ms: millis(elapsed),
+        events_per_sec: per_sec(lines.len(), elapsed),
+        stages: vec![
+            StageLatency::from_samples("parse", parse_ns),
+            StageLatency::from_samples("rule_match", match_ns),
+        ],
+    })
+}
+
+/// Feed synthetic packet events through the SYN flood and port scan detectors.
+#[cfg(target_os = "linux")]
+fn bench_packets(count: u64) -> Result<PacketBench, CliError> {
+    use ironpost_ebpf_engine::ironpost_ebpf_common::{
+        PROTO_TCP, PROTO_UDP, PacketEventData, TCP_ACK, TCP_SYN,
+    };
+    use ironpost_ebpf_engine::{
+        PortScanConfig, PortScanDetector, SynFloodConfig, SynFloodDetector,
+    };
+
+    let syn_flood = SynFloodDetector::new(SynFloodConfig::default());
+    let port_scan = PortScanDetector::new(PortScanConfig::default());
+
+    let mut rng = XorShift::new(SEED);
+    let events: Vec<PacketEventData> = (0..count)
+        .map(|_| {
+            let mut event = PacketEventData::zeroed();
+            // 198.51.100.0/24 sources, a few of which scan or flood
+            event.src_ip = u32::from_be_bytes([198, 51, 100, rng.below_u8(255)]).to_be();
+            event.dst_ip = u32::from_be_bytes([10, 0, 0, rng.below_u8(16)]).to_be();
+            event.src_port = (1024 + rng.below_u16(60_000)).to_be();
+            event.dst_port = match rng.below(10) {
+                0..=5 => 443_u16,
+                6 | 7 => 22,
+                _ => 1 + rng.below_u16(1024),
+            }
+            .to_be();
+            event.pkt_len = 60 + u32::from(rng.below_u16(1400));
+            if rng.below(10) == 0 {
+                event.protocol = PROTO_UDP;
+            } else {
+                event.protocol = PROTO_TCP;
+                event.tcp_flags = if rng.below(4) == 0 { TCP_SYN } else { TCP_ACK };
+            }
+            event
+        })
+        .collect();
+
+    let mut latency_ns = Vec::with_capacity(events.len());
+    let mut alerts = 0;
+    let started = Instant::now();
+    for event in &events {
+        let t0 = Instant::now();
+        let flood = syn_flood
+            .detect_packet(event)
+            .map_err(|e| CliError::Command(e.to_string()))?;
+        let scan = port_scan
+            .detect_packet(event)
+            .map_err(|e| CliError::Command(e.to_string()))?;
+        latency_ns.push(nanos(t0.elapsed()));
+        alerts += usize::from(flood.is_some()) + usize::from(scan.is_some());
+    }
+    let elapsed = started.elapsed();
+
+    Ok(PacketBench {
+        events: events.len(),
+        alerts,
+        elapsed_ms: millis(elapsed),
+        events_per_sec: per_sec(events.len(), elapsed),
+        latency: StageLatency::from_samples("detect", latency_ns),
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn bench_packets(_count: u64) -> Result<PacketBench, CliError> {
+    Err(CliError::Command(
+        "packet detectors are only available on Linux".to_owned(),
+    ))
+}
+
+/// Generate `count` synthetic log lines in `format`.
+fn generate_lines(format: BenchFormat, count: u64) -> Vec<Vec<u8>> {
+    let mut rng = XorShift::new(SEED);
+    (0..count)
+        .map(|i| match format {
+            BenchFormat::Syslog => syslog_line(&mut rng),
+            BenchFormat::Json => json_line(&mut rng),
+            BenchFormat::Mixed if i % 2 == 0 => syslog_line(&mut rng),
+            BenchFormat::Mixed => json_line(&mut rng),
+        })
+        .collect()
+}
+
+/// A syslog line: RFC 3164 or RFC 5424, with a mix of authentication,
+/// firewall and routine messages.
+fn syslog_line(rng: &mut XorShift) -> Vec<u8> {
+    let host = rng.pick(&HOSTS);
+    let user = rng.pick(&USERS);
+    let ip = random_ip(rng);
+    let pid = 1000 + rng.below(30_000);
+    let (process, message) = match rng.below(6) {
+        0 | 1 => (
+            "sshd",
+            format!(
+                "Failed password for {} from {} port {} ssh2",
+                user,
+                ip,
+                1024 + rng.below(60_000)
+            ),
+        ),
+        2 => (
+            "sshd",
+            format!(
+                "Accepted publickey for {} from {} port 50022 ssh2",
+                user, ip
+            ),
+        ),
+        3 => (
+            "kernel",
+            format!(
+                "[UFW BLOCK] IN=eth0 OUT= SRC={} DST=10.0.0.5 PROTO=TCP DPT={}",
+                ip,
+                rng.below(1024)
+            ),
+        ),
+        4 => (
+            "sudo",
+            format!(
+                "{} : TTY=pts/0 ; PWD=/home/{} ; COMMAND=/bin/systemctl restart nginx",
+                user, user
+            ),
+        ),
+        _ => (
+            "nginx",
+            format!("{} - - \"GET /api/v1/health HTTP/1.1\" 200 17", ip),
+        ),
+    };
+    let line = if rng.below(2) == 0 {
+        format!(
+            "<38>Oct 16 12:{:02}:{:02} {} {}[{}]: {}",
+            rng.below(60),
+            rng.below(60),
+            host,
+            process,
+            pid,
+            message
+        )
+    } else {
+        format!(
+            "<38>1 2026-10-16T12:{:02}:{:02}Z {} {} {} - - {}",
+            rng.below(60),
+            rng.below(60),
+            host,
+            process,
+            pid,
+            message
+        )
+    };
+    line.into_bytes()
+}
+
+/// A JSON log object with the default field mapping plus extra fields.
+fn json_line(rng: &mut XorShift) -> Vec<u8> {
+    let host = rng.pick(&HOSTS);
+    let user = rng.pick(&USERS);
+    let ip = random_ip(rng);
+    let (level, process, message) = match rng.below(4) {
+        0 => (
+            "warn",
+            "auth",
+            format!("Failed login for {} from {}", user, ip),
+        ),
+        1 => ("info", "auth", format!("Login succeeded for {}", user)),
+        2 => ("error", "api", "upstream timed out".to_owned()),
+        _ => ("info", "api", "request processed".to_owned()),
+    };
+    let line = serde_json::json!({
+        "timestamp": format!("2026-10-16T12:{:02}:{:02}Z", rng.below(60), rng.below(60)),
+        "host": host,
+        "process": process,
+        "level": level,
+        "message": message,
+        "src_ip": ip,
+        "user": user,
+        "duration_ms": rng.below(2_000),
+    });
+    line.to_string().into_bytes()
+}
+
+/// A documentation-range source address (RFC 5737).
+fn random_ip(rng: &mut XorShift) -> String {
+    let prefix = rng.pick(&["192.0.2", "198.51.100", "203.0.113"]);
+    format!("{}.{}", prefix, 1 + rng.below(254))
+}
+
+/// Small deterministic PRNG for synthetic events (xorshift64).
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// Uniform-ish value in `0..bound` (`bound` > 0).
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound.max(1)
+    }
+
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn below_u16(&mut self, bound: u16) -> u16 {
+        u16::try_from(self.below(u64::from(bound))).unwrap_or(0)
+    }
+
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn below_u8(&mut self, bound: u8) -> u8 {
+        u8::try_from(self.below(u64::from(bound))).unwrap_or(0)
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        let len = u64::try_from(items.len()).unwrap_or(1);
+        let index = usize::try_from(self.below(len)).unwrap_or(0);
+        items.get(index).copied().unwrap_or_default()
+    }
+}
+
+fn nanos(elapsed: Duration) -> u64 {
+    u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX)
+}
+
+fn millis(elapsed: Duration) -> u64 {
+    u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn per_sec(events: usize, elapsed: Duration) -> u64 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return 0;
+    }
+    (events as f64 / secs) as u64
+}
+
+/// Benchmark results.
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    /// Synthetic log line format
+    pub format: BenchFormat,
+    /// Directory the rules were loaded from
+    pub rule_dir: PathBuf,
+    /// Detection rules loaded
+    pub rules: usize,
+    /// Log parsing and rule matching
+    pub log: LogBench,
+    /// Packet detectors (`--packets`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packets: Option<PacketBench>,
+}
+
+/// Log path results.
+#[derive(Debug, Serialize)]
+pub struct LogBench {
+    /// Lines processed
+    pub events: usize,
+    /// Lines the parser rejected
+    pub parse_errors: usize,
+    /// Rule matches (including threshold rules still counting)
+    pub matches: usize,
+    /// Wall time for all lines
+    pub elapsed_ms: u64,
+    /// Lines per second over the whole run
+    pub events_per_sec: u64,
+    /// Per-event latency of each stage
+    pub stages: Vec<StageLatency>,
+}
+
+/// Packet detector results.
+#[derive(Debug, Serialize)]
+pub struct PacketBench {
+    /// Packet events processed
+    pub events: usize,
+    /// Alerts raised by the detectors
+    pub alerts: usize,
+    /// Wall time for all events
+    pub elapsed_ms: u64,
+    /// Events per second over the whole run
+    pub events_per_sec: u64,
+    /// Per-event latency of both detectors together
+    pub latency: StageLatency,
+}
+
+/// Latency distribution of one stage, in microseconds.
+#[derive(Debug, Serialize)]
+pub struct StageLatency {
+    pub stage: &'static str,
+    pub samples: usize,
+    pub mean_us: f64,
+    pub p50_us: f64,
+    pub p99_us: f64,
+    pub max_us: f64,
+}
+
+impl StageLatency {
+    #[allow(clippy::cast_precision_loss)]
+    fn from_samples(stage: &'static str, mut samples_ns: Vec<u64>) -> Self {
+        samples_ns.sort_unstable();
+        let us = |ns: u64| ns as f64 / 1000.0;
+        let percentile = |p: usize| {
+            let last = samples_ns.len().saturating_sub(1);
+            samples_ns.get(last * p / 100).copied().unwrap_or(0)
+        };
+        let total: u128 = samples_ns.iter().map(|&ns| u128::from(ns)).sum();
+        let mean = match u128::try_from(samples_ns.len()) {
+            Ok(n) if n > 0 => u64::try_from(total / n).unwrap_or(u64::MAX),
+            _ => 0,
+        };
+        Self {
+            stage,
+            samples: samples_ns.len(),
+            mean_us: us(mean),
+            p50_us: us(percentile(50)),
+            p99_us: us(percentile(99)),
+            max_us: us(samples_ns.last().copied().unwrap_or(0)),
+        }
+    }
+}
+
+impl Render for BenchReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(w, "{}", "Ironpost Benchmark".bold())?;
+        writeln!(
+            w,
+            "  Rules: {} from {}",
+            self.rules,
+            self.rule_dir.display()
+        )?;
+        writeln!(w)?;
+
+        let log = &self.log;
+        writeln!(
+            w,
+            "{} ({} lines, {:?})",
+            "Log pipeline".bold(),
+            log.events,
+            self.format
+        )?;
+        writeln!(
+            w,
+            "  Throughput: {} events/s ({} ms)",
+            log.events_per_sec.to_string().green(),
+            log.elapsed_ms
+        )?;
+        writeln!(
+            w,
+            "  Parse errors: {}   Rule matches: {}",
+            log.parse_errors, log.matches
+        )?;
+        render_stages(w, &log.stages)?;
+
+        if let Some(packets) = &self.packets {
+            writeln!(w)?;
+            writeln!(
+                w,
+                "{} ({} events)",
+                "Packet detectors".bold(),
+                packets.events
+            )?;
+            writeln!(
+                w,
+                "  Throughput: {} events/s ({} ms)",
+                packets.events_per_sec.to_string().green(),
+                packets.elapsed_ms
+            )?;
+            writeln!(w, "  Alerts: {}", packets.alerts)?;
+            render_stages(w, std::slice::from_ref(&packets.latency))?;
+        }
+        Ok(())
+    }
+}
+
+fn render_stages(w: &mut dyn Write, stages: &[StageLatency]) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "  {:<12} {:>10} {:>10} {:>10} {:>10}",
+        "Stage", "mean µs", "p50 µs", "p99 µs", "max µs"
+    )?;
+    for stage in stages {
+        writeln!(
+            w,
+            "  {:<12} {:>10.2} {:>10.2} {:>10.2} {:>10.2}",
+            stage.stage, stage.mean_us, stage.p50_us, stage.p99_us, stage.max_us
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(report: &BenchReport) -> String {
+        let mut buf = Vec::new();
+        report
+            .render_text(&mut buf)
+            .expect("text rendering should succeed");
+        String::from_utf8(buf).expect("valid UTF-8")
+    }
+
+    #[test]
+    fn test_generated_lines_are_deterministic_and_parse() {
+        let lines = generate_lines(BenchFormat::Mixed, 200);
+        assert_eq!(lines, generate_lines(BenchFormat::Mixed, 200));
+
+        let parser = ParserRouter::with_defaults();
+        for line in &lines {
+            assert!(
+                parser.parse(line).is_ok(),
+                "failed to parse {}",
+                String::from_utf8_lossy(line)
+            );
+        }
+        assert!(lines[0].starts_with(b"<38>"));
+        assert!(lines[1].starts_with(b"{"));
+    }
+
+    #[test]
+    fn test_bench_logs_counts_matches() {
+        let rule = r#"
+id: failed_password
+title: Failed password
+severity: Medium
+detection:
+  conditions:
+    - field: message
+      modifier: contains
+      value: "Failed password"
+"#;
+        let mut engine = RuleEngine::new();
+        engine
+            .add_rule(RuleLoader::parse_yaml(rule, "test.yml").expect("valid rule"))
+            .expect("rule should load");
+        let lines = generate_lines(BenchFormat::Syslog, 300);
+
+        let bench = bench_logs(&ParserRouter::with_defaults(), &engine, &lines)
+            .expect("benchmark should run");
+
+        assert_eq!(bench.events, 300);
+        assert_eq!(bench.parse_errors, 0);
+        assert!(bench.matches > 0, "some synthetic lines should match");
+        assert_eq!(bench.stages.len(), 2);
+        assert_eq!(bench.stages[0].samples, 300);
+    }
+
+    #[test]
+    fn test_stage_latency_percentiles() {
+        let samples = (1..=100).map(|i| i * 1000).collect();
+        let latency = StageLatency::from_samples("parse", samples);
+        assert_eq!(latency.samples, 100);
+        assert!((latency.p50_us - 50.0).abs() < f64::EPSILON);
+        assert!((latency.p99_us - 99.0).abs() < f64::EPSILON);
+        assert!((latency.max_us - 100.0).abs() < f64::EPSILON);
+        assert!((latency.mean_us - 50.5).abs() < f64::EPSILON);
+
+        let empty = StageLatency::from_samples("parse", Vec::new());
+        assert_eq!(empty.samples, 0);
+        assert!(empty.max_us.abs() < f64::EPSILON);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_bench_packets_runs_detectors() {
+        let bench = bench_packets(5_000).expect("benchmark should run");
+        assert_eq!(bench.events, 5_000);
+        assert_eq!(bench.latency.samples, 5_000);
+    }
+
+    #[test]
+    fn test_report_render_text() {
+        let report = BenchReport {
+            format: BenchFormat::Mixed,
+            rule_dir: PathBuf::from("/etc/ironpost/rules"),
+            rules: 3,
+            log: LogBench {
+                events: 1000,
+                parse_errors: 0,
+                matches: 120,
+                elapsed_ms: 8,
+                events_per_sec: 125_000,
+                stages: vec![StageLatency::from_samples("parse", vec![2000, 3000])],
+            },
+            packets: None,
+        };
+
+        let output = render(&report);
+        assert!(output.contains("125000 events/s"));
+        assert!(output.contains("Rules: 3 from /etc/ironpost/rules"));
+        assert!(output.contains("parse"));
+        assert!(!output.contains("Packet detectors"));
+
+        let json = serde_json::to_value(&report).expect("serialize");
+        assert_eq!(json["format"], "mixed");
+        assert_eq!(json["log"]["events_per_sec"], 125_000);
+        assert!(json.get("packets").is_none());
+    }
+}
@@ -0,0 +1,381 @@
+//! `ironpost config diff` -- compare configurations
+//!
+//! Compares two configuration files, or a file with the effective
+//! configuration of the running daemon (`GET /config`, `admin` role when
+//! `[auth]` is enabled). Both sides are compared after defaults and
+//! environment overrides are applied, so only settings that actually differ
+//! are listed.
+//!
+//! The daemon reads its configuration once at startup: a change to an active
+//! section takes effect after a restart. Changes inside a section that is
+//! disabled on both sides (`enabled = false`) have no effect on the running
+//! daemon and need no restart. Detection rules and container policies are
+//! not part of the configuration; `[content]` bundles reload them without a
+//! restart.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+use tracing::info;
+
+use ironpost_core::config::IronpostConfig;
+
+use crate::client::DaemonClient;
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Daemon control endpoint serving the effective configuration.
+const CONFIG_PATH: &str = "/config";
+
+/// Marker the daemon substitutes for secrets in `/config`.
+const REDACTED: &str = "[redacted]";
+
+/// Execute the config diff subcommand.
+///
+/// With `running`, the old side is the daemon's effective configuration and
+/// the new side is `files[0]` or `config_path`. Otherwise the old side is
+/// `files[0]` (or `config_path` when one file is given) and the new side is
+/// the last file.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` for a wrong number of files,
+/// `CliError::Config` if a file cannot be loaded, and
+/// `CliError::DaemonUnavailable` if the daemon cannot be reached.
+pub async fn execute(
+    files: Vec<PathBuf>,
+    running: bool,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let (old_label, old, new_path) = if running {
+        let new_path = match files.as_slice() {
+            [] => config_path.to_path_buf(),
+            [file] => file.clone(),
+            _ => {
+                return Err(CliError::Command(
+                    "--running compares one file with the daemon".to_owned(),
+                ));
+            }
+        };
+        let config = IronpostConfig::load(config_path).await?;
+        let client = DaemonClient::from_config(&config)?;
+        let running: IronpostConfig = client.get_json(CONFIG_PATH).await?;
+        (format!("daemon at {}", client.addr()), running, new_path)
+    } else {
+        let (old_path, new_path) = match files.as_slice() {
+            [file] => (config_path.to_path_buf(), file.clone()),
+            [old, new] => (old.clone(), new.clone()),
+            _ => {
+                return Err(CliError::Command(
+                    "give one or two files, or --running".to_owned(),
+                ));
+            }
+        };
+        let old = IronpostConfig::load(&old_path).await?;
+        (old_path.display().to_string(), old, new_path)
+    };
+    let new = IronpostConfig::load(&new_path).await?;
+
+    info!(old = %old_label, new = %new_path.display(), "comparing configurations");
+    let report = diff_configs(old_label, &old, new_path.display().to_string(), &new)?;
+    writer.render(&report)?;
+    Ok(())
+}
+
+/// Compare two configurations setting by setting.
+fn diff_configs(
+    old_label: String,
+    old: &IronpostConfig,
+    new_label: String,
+    new: &IronpostConfig,
+) -> Result<ConfigDiffReport, CliError> {
+    let old_value = serde_json::to_value(old)?;
+    let new_value = serde_json::to_value(new)?;
+    let old_settings = flatten(&old_value);
+    let new_settings = flatten(&new_value);
+
+    let mut changes = Vec::new();
+    let mut redacted = 0;
+    for (path, old_setting) in &old_settings {
+        let new_setting = new_settings.get(path).unwrap_or(&Value::Null);
+        if old_setting == new_setting {
+            continue;
+        }
+        // Secrets redacted by the daemon cannot be compared
+        if is_redacted(old_setting) || is_redacted(new_setting) {
+            redacted += 1;
+            continue;
+        }
+        let impact = if section_disabled(&old_value, path) && section_disabled(&new_value, path) {
+            ChangeImpact::NoRestart
+        } else {
+            ChangeImpact::Restart
+        };
+        changes.push(ConfigChange {
+            path: path.clone(),
+            old: old_setting.clone(),
+            new: new_setting.clone(),
+            impact,
+        });
+    }
+
+    Ok(ConfigDiffReport {
+        old: old_label,
+        new: new_label,
+        restart_required: changes.iter().any(|c| c.impact == ChangeImpact::Restart),
+        changes,
+        redacted,
+    })
+}
+
+/// Dotted path -> value for every setting; arrays are single settings.
+fn flatten(value: &Value) -> BTreeMap<String, Value> {
+    fn walk(value: &Value, prefix: &str, out: &mut BTreeMap<String, Value>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", prefix, key)
+                    };
+                    walk(value, &path, out);
+                }
+            }
+            _ => {
+                out.insert(prefix.to_owned(), value.clone());
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    walk(value, "", &mut out);
+    out
+}
+
+fn is_redacted(value: &Value) -> bool {
+    match value {
+        Value::String(s) => s.contains(REDACTED),
+        Value::Array(items) => items.iter().any(is_redacted),
+        Value::Object(map) => map.values().any(is_redacted),
+        _ => false,
+    }
+}
+
+/// Whether the top-level section of `path` is switched off in `config`.
+///
+/// The `enabled` flag itself is never considered disabled.
+fn section_disabled(config: &Value, path: &str) -> bool {
+    let Some((section, rest)) = path.split_once('.') else {
+        return false;
+    };
+    rest != "enabled" && config[section]["enabled"] == Value::Bool(false)
+}
+
+/// What applying a change takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeImpact {
+    /// The daemon must restart to apply the change
+    Restart,
+    /// The section is disabled on both sides, so the running daemon is unaffected
+    NoRestart,
+}
+
+/// A setting that differs between the two configurations.
+#[derive(Debug, Serialize)]
+pub struct ConfigChange {
+    /// Dotted setting path (e.g. `ebpf.interface`)
+    pub path: String,
+    /// Value on the old side
+    pub old: Value,
+    /// Value on the new side
+    pub new: Value,
+    /// What applying the change takes
+    pub impact: ChangeImpact,
+}
+
+/// Differences between two configurations.
+#[derive(Debug, Serialize)]
+pub struct ConfigDiffReport {
+    /// Old side (file path or daemon address)
+    pub old: String,
+    /// New side (file path)
+    pub new: String,
+    /// Whether any change needs a daemon restart
+    pub restart_required: bool,
+    /// Changed settings, ordered by path
+    pub changes: Vec<ConfigChange>,
+    /// Differing settings not compared because the daemon redacts them
+    pub redacted: usize,
+}
+
+impl Render for ConfigDiffReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(w, "Config Diff: {} -> {}", self.old.bold(), self.new.bold())?;
+        if self.changes.is_empty() {
+            writeln!(w, "  {}", "No changes.".dimmed())?;
+        }
+
+        for (impact, title) in [
+            (ChangeImpact::Restart, "Restart required".red().bold()),
+            (
+                ChangeImpact::NoRestart,
+                "No restart needed (section disabled)".green().bold(),
+            ),
+        ] {
+            let changes: Vec<&ConfigChange> =
+                self.changes.iter().filter(|c| c.impact == impact).collect();
+            if changes.is_empty() {
+                continue;
+            }
+            writeln!(w)?;
+            writeln!(w, "  {} ({}):", title, changes.len())?;
+            for change in changes {
+                writeln!(
+                    w,
+                    "    {}: {} -> {}",
+                    change.path.cyan(),
+                    change.old.to_string().red(),
+                    change.new.to_string().green()
+                )?;
+            }
+        }
+
+        if self.redacted > 0 {
+            writeln!(w)?;
+            writeln!(
+                w,
+                "  {}",
+                format!(
+                    "{} redacted setting(s) not compared (tokens, credentials in URLs).",
+                    self.redacted
+                )
+                .dimmed()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(old: &IronpostConfig, new: &IronpostConfig) -> ConfigDiffReport {
+        diff_configs("old.toml".to_owned(), old, "new.toml".to_owned(), new)
+            .expect("diff should succeed")
+    }
+
+    fn render(report: &ConfigDiffReport) -> String {
+        let mut buf = Vec::new();
+        report
+            .render_text(&mut buf)
+            .expect("text rendering should succeed");
+        String::from_utf8(buf).expect("valid UTF-8")
+    }
+
+    #[test]
+    fn test_diff_identical_configs_is_empty() {
+        let config = IronpostConfig::default();
+
+        let report = diff(&config, &config);
+
+        assert!(report.changes.is_empty());
+        assert!(!report.restart_required);
+        assert!(render(&report).contains("No changes."));
+    }
+
+    #[test]
+    fn test_diff_classifies_active_and_disabled_sections() {
+        let mut old = IronpostConfig::default();
+        old.ebpf.enabled = true;
+        old.container.enabled = false;
+        let mut new = old.clone();
+        new.ebpf.interface = "ens3".to_owned();
+        new.container.poll_interval_secs = old.container.poll_interval_secs + 5;
+
+        let report = diff(&old, &new);
+
+        assert_eq!(report.changes.len(), 2);
+        let container = &report.changes[0];
+        assert_eq!(container.path, "container.poll_interval_secs");
+        assert_eq!(container.impact, ChangeImpact::NoRestart);
+        let ebpf = &report.changes[1];
+        assert_eq!(ebpf.path, "ebpf.interface");
+        assert_eq!(ebpf.new, Value::String("ens3".to_owned()));
+        assert_eq!(ebpf.impact, ChangeImpact::Restart);
+        assert!(report.restart_required);
+    }
+
+    #[test]
+    fn test_diff_enabling_a_section_needs_restart() {
+        let mut old = IronpostConfig::default();
+        old.sbom.enabled = false;
+        let mut new = old.clone();
+        new.sbom.enabled = true;
+        new.sbom.min_severity = "high".to_owned();
+
+        let report = diff(&old, &new);
+
+        assert!(
+            report
+                .changes
+                .iter()
+                .all(|c| c.impact == ChangeImpact::Restart),
+            "changes in a section being enabled take effect on restart"
+        );
+        assert!(report.changes.iter().any(|c| c.path == "sbom.enabled"));
+    }
+
+    #[test]
+    fn test_diff_skips_redacted_settings() {
+        let mut running = IronpostConfig::default();
+        running.log_pipeline.storage.postgres_url =
+            "postgresql://[redacted]@db:5432/ironpost".to_owned();
+        let mut file = IronpostConfig::default();
+        file.log_pipeline.storage.postgres_url =
+            "postgresql://ironpost:secret@db:5432/ironpost".to_owned();
+
+        let report = diff(&running, &file);
+
+        assert!(report.changes.is_empty());
+        assert_eq!(report.redacted, 1);
+        assert!(render(&report).contains("1 redacted setting(s) not compared"));
+    }
+
+    #[test]
+    fn test_flatten_keeps_arrays_whole() {
+        let value = serde_json::json!({ "a": { "b": 1, "c": [1, 2] }, "d": true });
+
+        let flat = flatten(&value);
+
+        assert_eq!(flat.len(), 3);
+        assert_eq!(flat["a.b"], Value::from(1));
+        assert_eq!(flat["a.c"], serde_json::json!([1, 2]));
+        assert_eq!(flat["d"], Value::Bool(true));
+    }
+
+    #[test]
+    fn test_diff_report_render_groups_by_impact() {
+        let mut old = IronpostConfig::default();
+        old.ebpf.enabled = true;
+        old.container.enabled = false;
+        let mut new = old.clone();
+        new.ebpf.xdp_mode = "native".to_owned();
+        new.container.auto_isolate = !old.container.auto_isolate;
+
+        let output = render(&diff(&old, &new));
+
+        assert!(output.contains("Restart required (1)"));
+        assert!(output.contains("No restart needed (section disabled) (1)"));
+        assert!(output.contains("ebpf.xdp_mode"));
+        assert!(output.contains("\"native\""));
+    }
+}
@@ -0,0 +1,793 @@
+//! `ironpost config init` -- guided configuration scaffolding
+//!
+//! Asks about the host (network interface, log files, Docker, directories to
+//! scan, metrics export), validates each answer, and writes a commented
+//! `ironpost.toml` plus a content directory with starter detection rules and
+//! container policies. Defaults are detected from the host, so `--defaults`
+//! produces a working configuration without any questions.
+//!
+//! Questions go to stderr and answers are read from stdin; the summary report
+//! goes through the regular output writer.
+
+use std::io::{BufRead, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tracing::info;
+
+use ironpost_core::config::IronpostConfig;
+use ironpost_core::types::Severity;
+
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Directory the daemon loads detection rules from.
+const DAEMON_RULE_DIR: &str = "/etc/ironpost/rules";
+
+/// Port of the health/control listener (the metrics port must differ).
+const HEALTH_PORT: u16 = 9101;
+
+/// Log files offered as defaults when they exist on the host.
+const CANDIDATE_LOG_FILES: [&str; 4] = [
+    "/var/log/auth.log",
+    "/var/log/secure",
+    "/var/log/syslog",
+    "/var/log/messages",
+];
+
+/// Docker socket offered as the default when it exists.
+const DEFAULT_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+/// Starter detection rules written to `<content>/rules`.
+const STARTER_RULES: [(&str, &str); 2] = [
+    (
+        "ssh_brute_force.yaml",
+        include_str!("../../../examples/rules/ssh_brute_force.yaml"),
+    ),
+    (
+        "authentication_failure.yaml",
+        include_str!("../../../examples/rules/authentication_failure.yaml"),
+    ),
+];
+
+/// Starter container policies written to `<content>/policies`.
+const STARTER_POLICIES: [(&str, &str); 1] = [(
+    "high-web-pause.toml",
+    include_str!("../../../examples/policies/high-web-pause.toml"),
+)];
+
+/// Execute the config init subcommand.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the configuration file exists and `force`
+/// is not set, or if stdin closes before the wizard finishes, and
+/// `CliError::Io` if files cannot be written.
+pub async fn execute(
+    config_path: &Path,
+    force: bool,
+    defaults: bool,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    if config_path.exists() && !force {
+        return Err(CliError::Command(format!(
+            "{} already exists (use --force to overwrite)",
+            config_path.display()
+        )));
+    }
+
+    let answers = tokio::task::spawn_blocking(move || {
+        let host = Host::detect();
+        let stdin = std::io::stdin();
+        let mut prompter = Prompter::new(stdin.lock(), std::io::stderr(), defaults);
+        ask(&mut prompter, &host)
+    })
+    .await
+    .map_err(|e| CliError::Command(format!("config wizard failed: {}", e)))??;
+
+    let config_path = config_path.to_path_buf();
+    let report = tokio::task::spawn_blocking(move || write_scaffold(&config_path, &answers))
+        .await
+        .map_err(|e| CliError::Command(format!("config wizard failed: {}", e)))??;
+
+    info!(path = %report.config_path, "configuration written");
+    writer.render(&report)?;
+    Ok(())
+}
+
+/// What the wizard detected on the host, used for defaults and validation.
+#[derive(Debug, Clone, Default)]
+struct Host {
+    /// Network interfaces other than loopback
+    interfaces: Vec<String>,
+    /// Candidate log files that exist
+    log_files: Vec<String>,
+    /// Whether the default Docker socket exists
+    docker: bool,
+}
+
+impl Host {
+    fn detect() -> Self {
+        let mut interfaces: Vec<String> = std::fs::read_dir("/sys/class/net")
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.file_name().to_string_lossy().into_owned())
+                    .filter(|name| name != "lo")
+                    .collect()
+            })
+            .unwrap_or_default();
+        interfaces.sort();
+        Self {
+            interfaces,
+            log_files: CANDIDATE_LOG_FILES
+                .iter()
+                .filter(|p| Path::new(p).is_file())
+                .map(|p| (*p).to_owned())
+                .collect(),
+            docker: Path::new(DEFAULT_DOCKER_SOCKET).exists(),
+        }
+    }
+}
+
+/// Answers collected by the wizard.
+#[derive(Debug, Clone, PartialEq)]
+struct Answers {
+    /// XDP interface (`None` disables the eBPF engine)
+    interface: Option<String>,
+    /// Log files to tail
+    log_files: Vec<String>,
+    /// Syslog listen address (`None` disables the syslog receiver)
+    syslog_bind: Option<String>,
+    /// Docker socket (`None` disables the container guard)
+    docker_socket: Option<String>,
+    /// Whether matching policies isolate containers automatically
+    auto_isolate: bool,
+    /// Directories to scan (`None` disables the SBOM scanner)
+    scan_dirs: Option<Vec<String>>,
+    /// Minimum vulnerability severity reported
+    min_severity: String,
+    /// Prometheus metrics port (`None` disables the exporter)
+    metrics_port: Option<u16>,
+    /// Directory holding `rules/` and `policies/`
+    content_dir: String,
+}
+
+/// Ask every question, re-asking until each answer is valid.
+fn ask<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    host: &Host,
+) -> Result<Answers, CliError> {
+    prompter.section("Network monitoring (eBPF/XDP, Linux only)")?;
+    let interface = if prompter.confirm(
+        "Monitor a network interface with eBPF?",
+        cfg!(target_os = "linux") && !host.interfaces.is_empty(),
+    )? {
+        let default = host.interfaces.first().map_or("eth0", String::as_str);
+        Some(prompter.ask("Interface", default, |a| {
+            parse_interface(a, &host.interfaces)
+        })?)
+    } else {
+        None
+    };
+
+    prompter.section("Log sources")?;
+    let default_files = if host.log_files.is_empty() {
+        "none".to_owned()
+    } else {
+        host.log_files.join(", ")
+    };
+    let log_files = prompter.ask(
+        "Log files to watch (comma-separated, 'none' to skip)",
+        &default_files,
+        parse_list,
+    )?;
+    let syslog_bind = if prompter.confirm("Receive syslog messages over the network?", false)? {
+        Some(prompter.ask("Syslog listen address", "0.0.0.0:514", parse_socket_addr)?)
+    } else {
+        None
+    };
+
+    prompter.section("Containers")?;
+    let (docker_socket, auto_isolate) =
+        if prompter.confirm("Is Docker available on this host?", host.docker)? {
+            let socket = prompter.ask("Docker socket", DEFAULT_DOCKER_SOCKET, parse_non_empty)?;
+            let auto = prompter.confirm(
+                "Isolate containers automatically when a policy matches?",
+                false,
+            )?;
+            (Some(socket), auto)
+        } else {
+            (None, false)
+        };
+
+    prompter.section("Dependency scanning (SBOM)")?;
+    let (scan_dirs, min_severity) =
+        if prompter.confirm("Scan lockfiles for vulnerabilities?", true)? {
+            let dirs = prompter.ask(
+                "Directories to scan (comma-separated)",
+                ".",
+                parse_existing_dirs,
+            )?;
+            let severity = prompter.ask("Minimum severity to report", "medium", parse_severity)?;
+            (Some(dirs), severity)
+        } else {
+            (None, "medium".to_owned())
+        };
+
+    prompter.section("Alert export")?;
+    let metrics_port = if prompter.confirm(
+        "Expose Prometheus metrics (alert, action and pipeline counters)?",
+        false,
+    )? {
+        Some(prompter.ask("Metrics port", "9100", parse_metrics_port)?)
+    } else {
+        None
+    };
+
+    prompter.section("Rules and policies")?;
+    let content_dir = prompter.ask(
+        "Directory for starter rules and policies",
+        "/etc/ironpost",
+        parse_non_empty,
+    )?;
+
+    Ok(Answers {
+        interface,
+        log_files,
+        syslog_bind,
+        docker_socket,
+        auto_isolate,
+        scan_dirs,
+        min_severity,
+        metrics_port,
+        content_dir,
+    })
+}
+
+/// Line-based question/answer helper.
+///
+/// An empty answer takes the default. In `defaults` mode no questions are
+/// shown and every default is taken (an invalid default is an error).
+struct Prompter<R, W> {
+    input: R,
+    output: W,
+    defaults: bool,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    fn new(input: R, output: W, defaults: bool) -> Self {
+        Self {
+            input,
+            output,
+            defaults,
+        }
+    }
+
+    fn section(&mut self, title: &str) -> Result<(), CliError> {
+        if !self.defaults {
+            writeln!(self.output, "\n{}", title)?;
+        }
+        Ok(())
+    }
+
+    fn ask<T>(
+        &mut self,
+        question: &str,
+        default: &str,
+        parse: impl Fn(&str) -> Result<T, String>,
+    ) -> Result<T, CliError> {
+        loop {
+            let answer = if self.defaults {
+                default.to_owned()
+            } else {
+                write!(self.output, "{} [{}]: ", question, default)?;
+                self.output.flush()?;
+                let mut line = String::new();
+                if self.input.read_line(&mut line)? == 0 {
+                    return Err(CliError::Command(
+                        "input closed before the wizard finished (use --defaults to skip questions)"
+                            .to_owned(),
+                    ));
+                }
+                let line = line.trim();
+                if line.is_empty() {
+                    default.to_owned()
+                } else {
+                    line.to_owned()
+                }
+            };
+            match parse(&answer) {
+                Ok(value) => return Ok(value),
+                Err(reason) if self.defaults => {
+                    return Err(CliError::Command(format!("{}: {}", question, reason)));
+                }
+                Err(reason) => writeln!(self.output, "  {}", reason)?,
+            }
+        }
+    }
+
+    fn confirm(&mut self, question: &str, default: bool) -> Result<bool, CliError> {
+        self.ask(question, if default { "yes" } else { "no" }, parse_yes_no)
+    }
+}
+
+fn parse_yes_no(answer: &str) -> Result<bool, String> {
+    match answer.to_lowercase().as_str() {
+        "y" | "yes" => Ok(true),
+        "n" | "no" => Ok(false),
+        _ => Err("answer yes or no".to_owned()),
+    }
+}
+
+fn parse_non_empty(answer: &str) -> Result<String, String> {
+    if answer.is_empty() {
+        Err("a value is required".to_owned())
+    } else {
+        Ok(answer.to_owned())
+    }
+}
+
+fn parse_interface(answer: &str, known: &[String]) -> Result<String, String> {
+    if known.is_empty() || known.iter().any(|i| i == answer) {
+        parse_non_empty(answer)
+    } else {
+        Err(format!(
+            "unknown interface '{}' (found: {})",
+            answer,
+            known.join(", ")
+        ))
+    }
+}
+
+fn parse_list(answer: &str) -> Result<Vec<String>, String> {
+    if answer.eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+    Ok(answer
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned)
+        .collect())
+}
+
+fn parse_socket_addr(answer: &str) -> Result<String, String> {
+    answer
+        .parse::<SocketAddr>()
+        .map(|addr| addr.to_string())
+        .map_err(|_| format!("'{}' is not an address like 0.0.0.0:514", answer))
+}
+
+fn parse_existing_dirs(answer: &str) -> Result<Vec<String>, String> {
+    let dirs = parse_list(answer)?;
+    if dirs.is_empty() {
+        return Err("at least one directory is required".to_owned());
+    }
+    match dirs.iter().find(|d| !Path::new(d).is_dir()) {
+        Some(missing) => Err(format!("'{}' is not a directory", missing)),
+        None => Ok(dirs),
+    }
+}
+
+fn parse_severity(answer: &str) -> Result<String, String> {
+    Severity::from_str_loose(answer)
+        .map(|s| s.to_string().to_lowercase())
+        .ok_or_else(|| "expected info, low, medium, high or critical".to_owned())
+}
+
+fn parse_metrics_port(answer: &str) -> Result<u16, String> {
+    match answer.parse::<u16>() {
+        Ok(0) | Err(_) => Err("expected a port between 1 and 65535".to_owned()),
+        Ok(HEALTH_PORT) => Err(format!(
+            "port {} is used by the health endpoint",
+            HEALTH_PORT
+        )),
+        Ok(port) => Ok(port),
+    }
+}
+
+/// TOML string literal.
+fn quoted(value: &str) -> String {
+    toml::Value::String(value.to_owned()).to_string()
+}
+
+fn quoted_list(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|v| quoted(v)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// Render the commented configuration file for `answers`.
+fn render_config(answers: &Answers) -> String {
+    let content_dir = Path::new(&answers.content_dir);
+    let policy_dir = content_dir.join("policies").display().to_string();
+
+    let mut sources = Vec::new();
+    if !answers.log_files.is_empty() {
+        sources.push("file".to_owned());
+    }
+    if answers.syslog_bind.is_some() {
+        sources.push("syslog".to_owned());
+    }
+
+    let mut out = String::new();
+    out.push_str(
+        "# Ironpost configuration generated by `ironpost config init`.\n\
+         #\n\
+         # Omitted sections and fields use their defaults; see ironpost.toml.example\n\
+         # for every option. Check changes with `ironpost config validate`.\n",
+    );
+
+    out.push_str(&format!(
+        "\n[general]\n\
+         log_level = \"info\"\n\
+         log_format = \"json\"\n\
+         # Detect and alert, but only simulate blocking and isolation\n\
+         dry_run = false\n\
+         \n\
+         # Network monitoring with an XDP program (Linux, needs CAP_BPF or root)\n\
+         [ebpf]\n\
+         enabled = {}\n\
+         interface = {}\n\
+         # \"skb\" works on every driver; \"native\" is faster where supported\n\
+         xdp_mode = \"skb\"\n",
+        answers.interface.is_some(),
+        quoted(answers.interface.as_deref().unwrap_or("eth0")),
+    ));
+
+    out.push_str(&format!(
+        "\n# Log collection and rule-based detection.\n\
+         # Detection rules are loaded from {}.\n\
+         [log_pipeline]\n\
+         enabled = {}\n\
+         sources = {}\n\
+         watch_paths = {}\n\
+         syslog_bind = {}\n",
+        DAEMON_RULE_DIR,
+        !sources.is_empty(),
+        quoted_list(&sources),
+        quoted_list(&answers.log_files),
+        quoted(answers.syslog_bind.as_deref().unwrap_or("0.0.0.0:514")),
+    ));
+
+    out.push_str(&format!(
+        "\n# Container isolation driven by the policies in policy_path\n\
+         [container]\n\
+         enabled = {}\n\
+         docker_socket = {}\n\
+         policy_path = {}\n\
+         # When false, policy matches are reported but containers are left alone\n\
+         auto_isolate = {}\n",
+        answers.docker_socket.is_some(),
+        quoted(
+            answers
+                .docker_socket
+                .as_deref()
+                .unwrap_or(DEFAULT_DOCKER_SOCKET)
+        ),
+        quoted(&policy_dir),
+        answers.auto_isolate,
+    ));
+
+    out.push_str(&format!(
+        "\n# Dependency vulnerability scanning of lockfiles under scan_dirs\n\
+         [sbom]\n\
+         enabled = {}\n\
+         scan_dirs = {}\n\
+         # info, low, medium, high or critical\n\
+         min_severity = {}\n",
+        answers.scan_dirs.is_some(),
+        quoted_list(answers.scan_dirs.as_deref().unwrap_or(&[".".to_owned()])),
+        quoted(&answers.min_severity),
+    ));
+
+    out.push_str(&format!(
+        "\n# Prometheus exporter (alert, action and pipeline counters)\n\
+         [metrics]\n\
+         enabled = {}\n\
+         listen_addr = \"127.0.0.1\"\n\
+         port = {}\n\
+         \n\
+         # Probes and the control API used by `ironpost status`, `alerts` and `top`\n\
+         [health]\n\
+         enabled = true\n\
+         listen_addr = \"127.0.0.1\"\n\
+         port = {}\n",
+        answers.metrics_port.is_some(),
+        answers.metrics_port.unwrap_or(9100),
+        HEALTH_PORT,
+    ));
+
+    out
+}
+
+/// Write the configuration and starter content.
+fn write_scaffold(config_path: &Path, answers: &Answers) -> Result<InitReport, CliError> {
+    let text = render_config(answers);
+    // The wizard only produces valid answers; fail loudly if the template drifts
+    IronpostConfig::parse(&text)?.validate()?;
+
+    if let Some(parent) = config_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(config_path, &text)?;
+
+    let content_dir = PathBuf::from(&answers.content_dir);
+    let mut created = Vec::new();
+    let mut kept = Vec::new();
+    for (subdir, files) in [
+        ("rules", &STARTER_RULES[..]),
+        ("policies", &STARTER_POLICIES[..]),
+    ] {
+        let dir = content_dir.join(subdir);
+        std::fs::create_dir_all(&dir)?;
+        for (name, contents) in files {
+            let path = dir.join(name);
+            if path.exists() {
+                kept.push(path.display().to_string());
+            } else {
+                std::fs::write(&path, contents)?;
+                created.push(path.display().to_string());
+            }
+        }
+    }
+
+    let mut modules = Vec::new();
+    if answers.interface.is_some() {
+        modules.push("ebpf-engine".to_owned());
+    }
+    if !answers.log_files.is_empty() || answers.syslog_bind.is_some() {
+        modules.push("log-pipeline".to_owned());
+    }
+    if answers.docker_socket.is_some() {
+        modules.push("container-guard".to_owned());
+    }
+    if answers.scan_dirs.is_some() {
+        modules.push("sbom-scanner".to_owned());
+    }
+
+    let mut notes = Vec::new();
+    let rule_dir = content_dir.join("rules");
+    if rule_dir != Path::new(DAEMON_RULE_DIR) {
+        notes.push(format!(
+            "the daemon loads rules from {}; copy {} there to use them",
+            DAEMON_RULE_DIR,
+            rule_dir.display()
+        ));
+    }
+
+    Ok(InitReport {
+        config_path: config_path.display().to_string(),
+        modules,
+        created,
+        kept,
+        notes,
+    })
+}
+
+/// Result of `config init`.
+#[derive(Debug, Serialize)]
+pub struct InitReport {
+    /// Written configuration file
+    pub config_path: String,
+    /// Modules enabled in the configuration
+    pub modules: Vec<String>,
+    /// Starter files written
+    pub created: Vec<String>,
+    /// Starter files left untouched because they already existed
+    pub kept: Vec<String>,
+    /// Follow-up actions
+    pub notes: Vec<String>,
+}
+
+impl Render for InitReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(w, "{} {}", "Wrote".green().bold(), self.config_path)?;
+        let modules = if self.modules.is_empty() {
+            "none".to_owned()
+        } else {
+            self.modules.join(", ")
+        };
+        writeln!(w, "  Modules: {}", modules)?;
+        for path in &self.created {
+            writeln!(w, "  created {}", path)?;
+        }
+        for path in &self.kept {
+            writeln!(w, "  kept    {} (already exists)", path.dimmed())?;
+        }
+        for note in &self.notes {
+            writeln!(w, "  {} {}", "note:".yellow(), note)?;
+        }
+        writeln!(w)?;
+        writeln!(
+            w,
+            "Next: ironpost --config {} config validate",
+            self.config_path
+        )?;
+        writeln!(
+            w,
+            "      ironpost alerts tail   (follow alerts once the daemon runs)"
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host() -> Host {
+        Host {
+            interfaces: vec!["eth0".to_owned(), "wlan0".to_owned()],
+            log_files: vec!["/var/log/auth.log".to_owned()],
+            docker: true,
+        }
+    }
+
+    fn run(input: &str, host: &Host) -> (Result<Answers, CliError>, String) {
+        let mut output = Vec::new();
+        let result = {
+            let mut prompter = Prompter::new(input.as_bytes(), &mut output, false);
+            ask(&mut prompter, host)
+        };
+        (result, String::from_utf8(output).expect("valid UTF-8"))
+    }
+
+    #[test]
+    fn test_ask_reprompts_invalid_answers() {
+        // interface typo, then valid; log files; no syslog; docker yes, socket, no auto;
+        // sbom yes, dir ".", bad severity then high; metrics yes with health port then 9200;
+        // content dir
+        let input = "yes\neth9\nwlan0\n/var/log/a.log, /var/log/b.log\nno\nyes\n\nno\nyes\n.\nsevere\nhigh\nyes\n9101\n9200\n/tmp/ironpost\n";
+
+        let (result, output) = run(input, &host());
+        let answers = result.expect("wizard should finish");
+
+        assert_eq!(answers.interface.as_deref(), Some("wlan0"));
+        assert_eq!(answers.log_files, vec!["/var/log/a.log", "/var/log/b.log"]);
+        assert_eq!(
+            answers.docker_socket.as_deref(),
+            Some(DEFAULT_DOCKER_SOCKET)
+        );
+        assert_eq!(answers.min_severity, "high");
+        assert_eq!(answers.metrics_port, Some(9200));
+        assert_eq!(answers.content_dir, "/tmp/ironpost");
+        assert!(output.contains("unknown interface 'eth9'"));
+        assert!(output.contains("expected info, low, medium, high or critical"));
+        assert!(output.contains("used by the health endpoint"));
+    }
+
+    #[test]
+    fn test_ask_fails_when_input_closes() {
+        let (result, _) = run("yes\n", &host());
+        assert!(matches!(result, Err(CliError::Command(_))));
+    }
+
+    #[test]
+    fn test_defaults_mode_asks_nothing() {
+        let mut output = Vec::new();
+        let answers = {
+            let mut prompter = Prompter::new("".as_bytes(), &mut output, true);
+            ask(&mut prompter, &host()).expect("defaults should be valid")
+        };
+
+        assert!(output.is_empty(), "defaults mode should print no questions");
+        assert_eq!(answers.log_files, vec!["/var/log/auth.log"]);
+        assert!(answers.docker_socket.is_some());
+        assert!(!answers.auto_isolate);
+        assert_eq!(answers.scan_dirs, Some(vec![".".to_owned()]));
+        assert!(answers.metrics_port.is_none());
+    }
+
+    #[test]
+    fn test_parse_helpers() {
+        assert_eq!(parse_yes_no("Y"), Ok(true));
+        assert!(parse_yes_no("maybe").is_err());
+        assert_eq!(parse_list("none"), Ok(Vec::new()));
+        assert_eq!(
+            parse_list(" a, ,b "),
+            Ok(vec!["a".to_owned(), "b".to_owned()])
+        );
+        assert_eq!(
+            parse_socket_addr("127.0.0.1:1514"),
+            Ok("127.0.0.1:1514".to_owned())
+        );
+        assert!(parse_socket_addr("localhost").is_err());
+        assert_eq!(parse_severity("CRIT"), Ok("critical".to_owned()));
+        assert!(parse_metrics_port("0").is_err());
+        assert!(parse_existing_dirs("/definitely/not/here").is_err());
+    }
+
+    #[test]
+    fn test_render_config_is_valid_for_every_module_choice() {
+        let all = Answers {
+            interface: Some("eth0".to_owned()),
+            log_files: vec!["/var/log/auth.log".to_owned()],
+            syslog_bind: Some("0.0.0.0:1514".to_owned()),
+            docker_socket: Some(DEFAULT_DOCKER_SOCKET.to_owned()),
+            auto_isolate: true,
+            scan_dirs: Some(vec!["/srv/app".to_owned()]),
+            min_severity: "high".to_owned(),
+            metrics_port: Some(9200),
+            content_dir: "/etc/ironpost".to_owned(),
+        };
+        let none = Answers {
+            interface: None,
+            log_files: Vec::new(),
+            syslog_bind: None,
+            docker_socket: None,
+            auto_isolate: false,
+            scan_dirs: None,
+            min_severity: "medium".to_owned(),
+            metrics_port: None,
+            content_dir: "content".to_owned(),
+        };
+
+        for answers in [all, none] {
+            let text = render_config(&answers);
+            let config = IronpostConfig::parse(&text).expect("generated config should parse");
+            config.validate().expect("generated config should validate");
+            assert_eq!(config.ebpf.enabled, answers.interface.is_some());
+            assert_eq!(config.container.enabled, answers.docker_socket.is_some());
+            assert_eq!(config.sbom.enabled, answers.scan_dirs.is_some());
+            assert_eq!(config.metrics.enabled, answers.metrics_port.is_some());
+            assert_eq!(config.log_pipeline.watch_paths, answers.log_files);
+        }
+    }
+
+    #[test]
+    fn test_write_scaffold_creates_content_and_keeps_existing_files() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let content_dir = dir.path().join("content");
+        std::fs::create_dir_all(content_dir.join("rules")).expect("create rules dir");
+        std::fs::write(content_dir.join("rules/ssh_brute_force.yaml"), "custom").expect("write");
+        let answers = Answers {
+            interface: None,
+            log_files: vec!["/var/log/auth.log".to_owned()],
+            syslog_bind: None,
+            docker_socket: Some(DEFAULT_DOCKER_SOCKET.to_owned()),
+            auto_isolate: false,
+            scan_dirs: None,
+            min_severity: "medium".to_owned(),
+            metrics_port: None,
+            content_dir: content_dir.display().to_string(),
+        };
+        let config_path = dir.path().join("etc/ironpost.toml");
+
+        let report = write_scaffold(&config_path, &answers).expect("scaffold should be written");
+
+        assert!(config_path.is_file());
+        assert_eq!(report.modules, vec!["log-pipeline", "container-guard"]);
+        assert_eq!(report.kept.len(), 1, "existing rule should be kept");
+        assert_eq!(
+            std::fs::read_to_string(content_dir.join("rules/ssh_brute_force.yaml"))
+                .expect("read rule"),
+            "custom"
+        );
+        assert!(content_dir.join("policies/high-web-pause.toml").is_file());
+        assert_eq!(
+            report.notes.len(),
+            1,
+            "rules outside the daemon rule dir need a note"
+        );
+        let config = std::fs::read_to_string(&config_path).expect("read config");
+        assert!(config.contains(&content_dir.join("policies").display().to_string()));
+    }
+
+    #[test]
+    fn test_starter_content_parses() {
+        for (name, contents) in STARTER_POLICIES {
+            ironpost_container_guard::parse_policy(contents, name)
+                .unwrap_or_else(|e| panic!("{} should parse: {}", name, e));
+        }
+        for (name, contents) in STARTER_RULES {
+            ironpost_log_pipeline::rule::RuleLoader::parse_yaml(contents, name)
+                .unwrap_or_else(|e| panic!("{} should parse: {}", name, e));
+        }
+    }
+}
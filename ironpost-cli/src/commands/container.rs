@@ -0,0 +1,1321 @@
+//! `ironpost container` command handler
+//!
+//! `policies list` shows the isolation policies in the configured policy
+//! directory in the order the guard evaluates them. `policies lint` checks a
+//! directory of TOML policies with `PolicyEngine::lint` so a policy change can
+//! be reviewed before it is deployed. Neither needs a running daemon.
+//!
+//! `policies test` sends a sample alert to `/container/policies/test`, which
+//! evaluates it against the daemon's live policy engine and container
+//! inventory and reports which policy would match and which action the guard
+//! would run. Nothing is isolated.
+//!
+//! `watch` polls the daemon's `/container/events` feed and prints each new
+//! lifecycle change, policy match and isolation action as it arrives.
+//!
+//! `isolate` applies an isolation action through `/container/isolate`,
+//! optionally released automatically after `--duration`. `release` asks the
+//! daemon to undo whichever action its audit trail recorded for the
+//! container -- unpause, restart or network reconnect.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use ironpost_container_guard::{
+    GuardActivity, LintLevel, PolicyEngine, SecurityPolicy, TargetFilter, load_policies_from_dir,
+    load_policy_from_file,
+};
+use ironpost_core::config::IronpostConfig;
+use ironpost_core::types::Severity;
+
+use crate::cli::{ContainerAction, ContainerArgs, IsolateAction, PoliciesAction};
+use crate::client::DaemonClient;
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Daemon control endpoint serving recent container guard events.
+const CONTAINER_EVENTS_PATH: &str = "/container/events";
+
+/// Daemon control endpoint isolating a container.
+const CONTAINER_ISOLATE_PATH: &str = "/container/isolate";
+
+/// Daemon control endpoint releasing an isolated container.
+const CONTAINER_RELEASE_PATH: &str = "/container/release";
+
+/// Daemon control endpoint simulating policy evaluation.
+const CONTAINER_POLICY_TEST_PATH: &str = "/container/policies/test";
+
+/// Poll interval of `container watch`.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Request timeout of `isolate` and `release`.
+///
+/// Covers the guard's default action timeout (30s) across its retries.
+const ISOLATION_TIMEOUT: Duration = Duration::from_secs(150);
+
+/// Execute the `container` command.
+pub async fn execute(
+    args: ContainerArgs,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    match args.action {
+        ContainerAction::Policies { action } => match action {
+            PoliciesAction::List => execute_policies_list(config_path, writer).await,
+            PoliciesAction::Lint { dir } => execute_policies_lint(dir, writer).await,
+            PoliciesAction::Test {
+                alert,
+                severity,
+                source_ip,
+                target_ip,
+                rule,
+                title,
+                container,
+                image,
+            } => {
+                let base = match alert {
+                    Some(path) => Some(read_sample_alert(&path).await?),
+                    None => None,
+                };
+                let overrides = AlertOverrides {
+                    severity: severity.map(Into::into),
+                    source_ip,
+                    target_ip,
+                    rule,
+                    title,
+                };
+                let request = PolicyTestRequest {
+                    alert: sample_alert(base, overrides)?,
+                    container,
+                    image,
+                };
+                let client = guard_client(config_path).await?;
+                let report: PolicyTestReport = client
+                    .post_json(CONTAINER_POLICY_TEST_PATH, &request)
+                    .await?;
+                writer.render(&report)
+            }
+        },
+        ContainerAction::Watch { history } => execute_watch(config_path, history, writer).await,
+        ContainerAction::Isolate {
+            target,
+            action,
+            networks,
+            duration,
+        } => {
+            let request = IsolateRequest {
+                target,
+                action,
+                networks,
+                duration_secs: duration,
+            };
+            let client = isolation_client(config_path).await?;
+            let isolation: Isolation = client.post_json(CONTAINER_ISOLATE_PATH, &request).await?;
+            writer.render(&IsolateReport(isolation))
+        }
+        ContainerAction::Release { target } => {
+            let client = isolation_client(config_path).await?;
+            let isolation: Isolation = client
+                .post_json(CONTAINER_RELEASE_PATH, &ReleaseRequest { target })
+                .await?;
+            writer.render(&ReleaseReport(isolation))
+        }
+    }
+}
+
+/// Daemon client for `isolate` and `release`.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the container guard is disabled.
+async fn isolation_client(config_path: &Path) -> Result<DaemonClient, CliError> {
+    let client = guard_client(config_path)
+        .await?
+        .with_timeout(ISOLATION_TIMEOUT);
+    debug!(addr = client.addr(), "sending container isolation request");
+    Ok(client)
+}
+
+/// Daemon client for requests served by the container guard.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the container guard is disabled.
+async fn guard_client(config_path: &Path) -> Result<DaemonClient, CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    if !config.container.enabled {
+        return Err(CliError::Command(
+            "container guard is disabled in configuration ([container] enabled = false)".to_owned(),
+        ));
+    }
+    DaemonClient::from_config(&config)
+}
+
+/// Read a sample alert JSON object from `path`.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the file cannot be read or is not a JSON
+/// object.
+async fn read_sample_alert(
+    path: &Path,
+) -> Result<serde_json::Map<String, serde_json::Value>, CliError> {
+    let contents = tokio::fs::read_to_string(path).await.map_err(|e| {
+        CliError::Command(format!("failed to read alert {}: {}", path.display(), e))
+    })?;
+    match serde_json::from_str(&contents) {
+        Ok(serde_json::Value::Object(alert)) => Ok(alert),
+        Ok(_) => Err(CliError::Command(format!(
+            "alert {} must be a JSON object",
+            path.display()
+        ))),
+        Err(e) => Err(CliError::Command(format!(
+            "invalid alert {}: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+/// Alert fields given as `policies test` flags.
+#[derive(Debug, Default)]
+struct AlertOverrides {
+    severity: Option<Severity>,
+    source_ip: Option<IpAddr>,
+    target_ip: Option<IpAddr>,
+    rule: Option<String>,
+    title: Option<String>,
+}
+
+/// Merge flag values over the alert read from `--alert`.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if neither the file nor the flags give a
+/// severity.
+fn sample_alert(
+    base: Option<serde_json::Map<String, serde_json::Value>>,
+    overrides: AlertOverrides,
+) -> Result<serde_json::Map<String, serde_json::Value>, CliError> {
+    let mut alert = base.unwrap_or_default();
+    if let Some(severity) = overrides.severity {
+        alert.insert("severity".to_owned(), severity.to_string().into());
+    }
+    if let Some(ip) = overrides.source_ip {
+        alert.insert("source_ip".to_owned(), ip.to_string().into());
+    }
+    if let Some(ip) = overrides.target_ip {
+        alert.insert("target_ip".to_owned(), ip.to_string().into());
+    }
+    if let Some(rule) = overrides.rule {
+        alert.remove("rule_name");
+        alert.insert("rule".to_owned(), rule.into());
+    }
+    if let Some(title) = overrides.title {
+        alert.insert("title".to_owned(), title.into());
+    }
+    if !alert.contains_key("severity") {
+        return Err(CliError::Command(
+            "sample alert has no severity; pass --severity".to_owned(),
+        ));
+    }
+    Ok(alert)
+}
+
+/// Execute the container watch subcommand.
+///
+/// Starts after the newest buffered event (or at the oldest with `history`)
+/// and prints new events every second until Ctrl-C. If the daemon restarts,
+/// following resumes from its new feed.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the container guard is disabled and
+/// `CliError::DaemonUnavailable` if the daemon cannot be reached.
+async fn execute_watch(
+    config_path: &Path,
+    history: bool,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    if !config.container.enabled {
+        return Err(CliError::Command(
+            "container guard is disabled in configuration ([container] enabled = false)".to_owned(),
+        ));
+    }
+
+    let client = DaemonClient::from_config(&config)?;
+    debug!(addr = client.addr(), "watching container events");
+
+    let mut since = if history {
+        0
+    } else {
+        fetch_events(&client, 0).await?.last_seq
+    };
+
+    let mut interval = tokio::time::interval(WATCH_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let feed = fetch_events(&client, since).await?;
+                if feed.last_seq < since {
+                    info!("daemon event feed restarted, following the new feed");
+                    since = 0;
+                    continue;
+                }
+                if feed.truncated {
+                    warn!("some container events were dropped before they could be read");
+                }
+                for event in &feed.events {
+                    writer.render_line(event)?;
+                }
+                since = feed.last_seq;
+            }
+            _ = tokio::signal::ctrl_c() => {
+                debug!("watch interrupted");
+                return Ok(());
+            }
+        }
+    }
+}
+
+pub(crate) async fn fetch_events(client: &DaemonClient, since: u64) -> Result<EventFeed, CliError> {
+    client
+        .get_json(&format!("{}?since={}", CONTAINER_EVENTS_PATH, since))
+        .await
+}
+
+/// Execute the container policies list subcommand.
+///
+/// Files that fail to parse or validate are skipped with a warning, as the
+/// daemon does; use `policies lint` to see why.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the policy directory cannot be read.
+async fn execute_policies_list(config_path: &Path, writer: &OutputWriter) -> Result<(), CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    let policy_dir = PathBuf::from(&config.container.policy_path);
+
+    info!(policy_dir = %policy_dir.display(), "loading container policies");
+
+    let dir = policy_dir.clone();
+    let policies = tokio::task::spawn_blocking(move || load_policies_from_dir(&dir))
+        .await
+        .map_err(|e| CliError::Command(format!("policy loading failed: {}", e)))?
+        .map_err(|e| CliError::Command(e.to_string()))?;
+
+    let engine = build_engine(policies)?;
+    let report = PolicyListReport {
+        policy_dir: policy_dir.display().to_string(),
+        total: engine.policy_count(),
+        policies: engine.policies().iter().map(PolicyEntry::from).collect(),
+    };
+    writer.render(&report)?;
+
+    Ok(())
+}
+
+/// Execute the container policies lint subcommand.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the directory cannot be read or the lint
+/// reports errors (exits with code 1). Warnings alone do not fail.
+async fn execute_policies_lint(dir: PathBuf, writer: &OutputWriter) -> Result<(), CliError> {
+    info!(dir = %dir.display(), "linting container policies");
+
+    let report = tokio::task::spawn_blocking(move || lint_directory(&dir))
+        .await
+        .map_err(|e| CliError::Command(format!("policy lint failed: {}", e)))??;
+    writer.render(&report)?;
+
+    if report.errors > 0 {
+        return Err(CliError::Command(format!(
+            "{} policy errors",
+            report.errors
+        )));
+    }
+
+    Ok(())
+}
+
+/// Load policies into an engine, which orders them by priority.
+fn build_engine(policies: Vec<SecurityPolicy>) -> Result<PolicyEngine, CliError> {
+    let mut engine = PolicyEngine::new();
+    for policy in policies {
+        engine
+            .add_policy(policy)
+            .map_err(|e| CliError::Command(e.to_string()))?;
+    }
+    Ok(engine)
+}
+
+/// Load each `.toml` file in `dir`, in file name order, and lint the set.
+fn lint_directory(dir: &Path) -> Result<PolicyLintReport, CliError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        CliError::Command(format!("failed to read directory {}: {}", dir.display(), e))
+    })?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let file = entry?.path();
+        if file.is_file() && file.extension().is_some_and(|ext| ext == "toml") {
+            files.push(file);
+        }
+    }
+    files.sort();
+
+    let mut issues = Vec::new();
+    let mut policies = Vec::new();
+    let mut files_by_id: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for file in &files {
+        let name = file.file_name().map_or_else(
+            || file.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        match load_policy_from_file(file) {
+            Ok(policy) => {
+                files_by_id.entry(policy.id.clone()).or_default().push(name);
+                policies.push(policy);
+            }
+            Err(e) => issues.push(PolicyIssue {
+                level: LintLevel::Error,
+                policy_id: None,
+                file: Some(name),
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    let engine = build_engine(policies)?;
+    let mut reported_duplicates = Vec::new();
+    for lint in engine.lint() {
+        let defined_in = files_by_id.get(&lint.policy_id);
+        // The engine reports one error per extra copy; one line per ID is enough.
+        if defined_in.is_some_and(|f| f.len() > 1) && lint.level == LintLevel::Error {
+            if reported_duplicates.contains(&lint.policy_id) {
+                continue;
+            }
+            reported_duplicates.push(lint.policy_id.clone());
+        }
+        issues.push(PolicyIssue {
+            level: lint.level,
+            file: defined_in.map(|f| f.join(", ")),
+            policy_id: Some(lint.policy_id),
+            message: lint.message,
+        });
+    }
+
+    let errors = issues
+        .iter()
+        .filter(|i| i.level == LintLevel::Error)
+        .count();
+    Ok(PolicyLintReport {
+        path: dir.display().to_string(),
+        files: files.len(),
+        policies: engine.policy_count(),
+        warnings: issues.len() - errors,
+        errors,
+        issues,
+    })
+}
+
+/// Human-readable form of a target filter.
+fn describe_filter(filter: &TargetFilter) -> String {
+    let mut parts = Vec::new();
+    if !filter.container_names.is_empty() {
+        parts.push(format!("name={}", filter.container_names.join(",")));
+    }
+    if !filter.image_patterns.is_empty() {
+        parts.push(format!("image={}", filter.image_patterns.join(",")));
+    }
+    if parts.is_empty() {
+        "all containers".to_owned()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// `HH:MM:SS` (UTC) of a Unix millisecond timestamp.
+pub(crate) fn format_time(timestamp_ms: u64) -> String {
+    let secs = (timestamp_ms / 1000) % 86_400;
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
+
+/// Response of the daemon's `/container/events` endpoint.
+#[derive(Debug, Deserialize)]
+pub(crate) struct EventFeed {
+    pub(crate) last_seq: u64,
+    #[serde(default)]
+    pub(crate) truncated: bool,
+    pub(crate) events: Vec<WatchEvent>,
+}
+
+/// A container guard event streamed by `container watch`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchEvent {
+    /// Position in the daemon's event feed
+    pub seq: u64,
+    /// Time the daemon recorded the event (Unix milliseconds)
+    pub timestamp_ms: u64,
+    /// Lifecycle change, policy match or isolation action, tagged by `kind`
+    #[serde(flatten)]
+    pub activity: GuardActivity,
+}
+
+/// Policies in evaluation order.
+#[derive(Serialize)]
+pub struct PolicyListReport {
+    /// Directory the policies were loaded from
+    pub policy_dir: String,
+    /// Number of policies
+    pub total: usize,
+    /// Policies, lowest priority value first
+    pub policies: Vec<PolicyEntry>,
+}
+
+/// A single isolation policy.
+#[derive(Serialize)]
+pub struct PolicyEntry {
+    /// Policy ID
+    pub id: String,
+    /// Policy name
+    pub name: String,
+    /// Whether the policy is evaluated
+    pub enabled: bool,
+    /// Evaluation priority (lower is evaluated first)
+    pub priority: u32,
+    /// Minimum alert severity that triggers the policy
+    pub severity_threshold: String,
+    /// Containers the policy applies to
+    pub target_filter: TargetFilter,
+    /// Isolation action (e.g. `pause`, `network_disconnect(bridge)`)
+    pub action: String,
+}
+
+impl From<&SecurityPolicy> for PolicyEntry {
+    fn from(policy: &SecurityPolicy) -> Self {
+        Self {
+            id: policy.id.clone(),
+            name: policy.name.clone(),
+            enabled: policy.enabled,
+            priority: policy.priority,
+            severity_threshold: format!("{:?}", policy.severity_threshold),
+            target_filter: policy.target_filter.clone(),
+            action: policy.action.to_string(),
+        }
+    }
+}
+
+/// Result of linting a policy directory.
+#[derive(Debug, Serialize)]
+pub struct PolicyLintReport {
+    /// Linted directory
+    pub path: String,
+    /// TOML files found
+    pub files: usize,
+    /// Policies that loaded
+    pub policies: usize,
+    /// Issues that would break or reject the policy set
+    pub errors: usize,
+    /// Issues worth reviewing
+    pub warnings: usize,
+    /// All issues, load errors first, then in evaluation order
+    pub issues: Vec<PolicyIssue>,
+}
+
+/// A problem found by the lint.
+#[derive(Debug, Serialize)]
+pub struct PolicyIssue {
+    /// Issue level
+    pub level: LintLevel,
+    /// Policy ID (None if the file did not load)
+    pub policy_id: Option<String>,
+    /// File(s) defining the policy
+    pub file: Option<String>,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl Render for PolicyListReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(
+            w,
+            "Container Policies: {} ({} total)",
+            self.policy_dir.bold(),
+            self.total
+        )?;
+        writeln!(w)?;
+
+        if self.policies.is_empty() {
+            writeln!(w, "No policies loaded.")?;
+            return Ok(());
+        }
+
+        writeln!(
+            w,
+            "{:>8}  {:<20} {:<10} {:<10} {:<30} Action",
+            "Priority", "ID", "Status", "Severity", "Filter"
+        )?;
+        writeln!(w, "{}", "-".repeat(100))?;
+
+        for p in &self.policies {
+            let status = if p.enabled {
+                "enabled".green()
+            } else {
+                "disabled".yellow()
+            };
+            writeln!(
+                w,
+                "{:>8}  {:<20} {:<10} {:<10} {:<30} {}",
+                p.priority,
+                p.id,
+                status,
+                format!("{}+", p.severity_threshold),
+                describe_filter(&p.target_filter),
+                p.action
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Render for WatchEvent {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        let time = format_time(self.timestamp_ms);
+        match &self.activity {
+            GuardActivity::Lifecycle {
+                container_id,
+                container_name,
+                event,
+            } => writeln!(
+                w,
+                "{}  {:<9} {} ({}) {}",
+                time,
+                "container".cyan(),
+                container_name,
+                short_id(container_id),
+                event
+            ),
+            GuardActivity::PolicyMatch {
+                alert_title,
+                severity,
+                policy_id,
+                container_id,
+                container_name,
+                action,
+                standby,
+                ..
+            } => {
+                let note = if *standby {
+                    " (standby, left to the leader)"
+                } else {
+                    ""
+                };
+                writeln!(
+                    w,
+                    "{}  {:<9} {} matched {} ({}): \"{}\" [{:?}] -> {}{}",
+                    time,
+                    "policy".yellow(),
+                    policy_id.bold(),
+                    container_name,
+                    short_id(container_id),
+                    alert_title,
+                    severity,
+                    action,
+                    note
+                )
+            }
+            GuardActivity::Action {
+                action_type,
+                target,
+                success,
+                simulated,
+                ..
+            } => {
+                let result = if *success {
+                    "ok".green()
+                } else {
+                    "failed".red().bold()
+                };
+                let note = if *simulated { " (dry run)" } else { "" };
+                writeln!(
+                    w,
+                    "{}  {:<9} {} {} {}{}",
+                    time,
+                    "action".magenta(),
+                    action_type,
+                    short_id(target),
+                    result,
+                    note
+                )
+            }
+        }
+    }
+}
+
+/// Body of `POST /container/isolate`.
+#[derive(Debug, Serialize)]
+struct IsolateRequest {
+    target: String,
+    action: IsolateAction,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    networks: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_secs: Option<u64>,
+}
+
+/// Body of `POST /container/release`.
+#[derive(Debug, Serialize)]
+struct ReleaseRequest {
+    target: String,
+}
+
+/// Body of `POST /container/policies/test`.
+#[derive(Debug, Serialize)]
+struct PolicyTestRequest {
+    alert: serde_json::Map<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    container: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+}
+
+/// Result of `container policies test`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PolicyTestReport {
+    /// Severity the alert was evaluated with
+    pub severity: Severity,
+    /// Number of containers evaluated
+    pub containers_evaluated: usize,
+    /// Number of policies loaded in the daemon
+    pub policies_loaded: usize,
+    /// Whether the guard isolates on a match
+    pub auto_isolate: bool,
+    /// Whether isolation actions are only simulated
+    pub dry_run: bool,
+    /// Whether the daemon is an HA standby
+    #[serde(default)]
+    pub standby: bool,
+    /// Containers matched by a policy, in evaluation order
+    pub matches: Vec<PolicyTestMatch>,
+}
+
+/// A container matched by a policy in `container policies test`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PolicyTestMatch {
+    /// Full container ID (empty for a hypothetical container)
+    pub container_id: String,
+    /// Container name
+    pub container_name: String,
+    /// Container image
+    pub container_image: String,
+    /// Matched policy ID
+    pub policy_id: String,
+    /// Matched policy name
+    pub policy_name: String,
+    /// Action the policy specifies
+    pub action: String,
+    /// Whether the guard would act on this container
+    pub applied: bool,
+    /// Whether the container is already isolated
+    #[serde(default)]
+    pub already_isolated: bool,
+}
+
+impl Render for PolicyTestReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(
+            w,
+            "Policy test: {} alert against {} containers ({} policies)",
+            self.severity.to_string().bold(),
+            self.containers_evaluated,
+            self.policies_loaded
+        )?;
+        writeln!(w)?;
+
+        for m in &self.matches {
+            let container = if m.container_id.is_empty() {
+                format!("{} [{}]", m.container_name, m.container_image)
+            } else {
+                format!("{} ({})", m.container_name, short_id(&m.container_id))
+            };
+            let marker = if m.applied {
+                "*".red().bold()
+            } else {
+                " ".normal()
+            };
+            writeln!(
+                w,
+                "{} {:<32} {:<20} {}",
+                marker, container, m.policy_id, m.action
+            )?;
+        }
+
+        let Some(applied) = self.matches.iter().find(|m| m.applied) else {
+            return writeln!(w, "No policy matches; no action would run.");
+        };
+        writeln!(w)?;
+        if !self.auto_isolate {
+            return writeln!(
+                w,
+                "Policy {} matches {}, but auto_isolate is disabled; no action would run.",
+                applied.policy_id, applied.container_name
+            );
+        }
+        let runner = if self.standby {
+            "The leader would run"
+        } else {
+            "Would run"
+        };
+        writeln!(
+            w,
+            "{} {} on {} (policy {}){}",
+            runner,
+            applied.action.bold(),
+            applied.container_name.bold(),
+            applied.policy_id,
+            if self.dry_run { " [dry-run]" } else { "" }
+        )?;
+        if applied.already_isolated {
+            writeln!(
+                w,
+                "  Note: {} is already isolated; the existing isolation is kept for release.",
+                applied.container_name
+            )?;
+        }
+        if self.matches.len() > 1 {
+            writeln!(
+                w,
+                "  Only the first matching container is isolated per alert."
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// An isolation applied or released by the daemon.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Isolation {
+    /// Full container ID
+    pub container_id: String,
+    /// Container name
+    pub container_name: String,
+    /// Applied action (e.g. `pause`, `quarantine(bridge)`)
+    pub action: String,
+    /// Networks disconnected by the action
+    #[serde(default)]
+    pub networks: Vec<String>,
+    /// Who applied the isolation (e.g. `manual:ops`, `policy:web-isolate`)
+    pub initiator: String,
+    /// When the isolation was applied (Unix milliseconds)
+    pub applied_at_ms: u64,
+    /// When the isolation is released automatically (Unix milliseconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_ms: Option<u64>,
+    /// Whether the isolation was only simulated (dry-run)
+    #[serde(default)]
+    pub simulated: bool,
+}
+
+/// Result of `container isolate`.
+#[derive(Serialize)]
+#[serde(transparent)]
+pub struct IsolateReport(pub Isolation);
+
+/// Result of `container release`.
+#[derive(Serialize)]
+#[serde(transparent)]
+pub struct ReleaseReport(pub Isolation);
+
+impl Render for IsolateReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        let isolation = &self.0;
+        writeln!(
+            w,
+            "{} {} ({}): {}{}",
+            "Isolated".red().bold(),
+            isolation.container_name.bold(),
+            short_id(&isolation.container_id),
+            isolation.action,
+            if isolation.simulated {
+                " [dry-run]"
+            } else {
+                ""
+            }
+        )?;
+        match isolation.expires_at_ms {
+            Some(at) => writeln!(w, "  Release: automatic at {} UTC", format_time(at)),
+            None => writeln!(
+                w,
+                "  Release: ironpost container release {}",
+                isolation.container_name
+            ),
+        }
+    }
+}
+
+impl Render for ReleaseReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        let isolation = &self.0;
+        writeln!(
+            w,
+            "{} {} ({}): undid {} applied by {}{}",
+            "Released".green().bold(),
+            isolation.container_name.bold(),
+            short_id(&isolation.container_id),
+            isolation.action,
+            isolation.initiator,
+            if isolation.simulated {
+                " [dry-run]"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// Docker-style 12 character short container ID.
+pub(crate) fn short_id(id: &str) -> &str {
+    id.get(..12).unwrap_or(id)
+}
+
+impl Render for PolicyLintReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(w, "Policy Lint: {}", self.path.bold())?;
+        writeln!(
+            w,
+            "  Files: {}, Policies: {}, {}, {}",
+            self.files,
+            self.policies,
+            if self.errors > 0 {
+                format!("{} errors", self.errors).red()
+            } else {
+                format!("{} errors", self.errors).normal()
+            },
+            if self.warnings > 0 {
+                format!("{} warnings", self.warnings).yellow()
+            } else {
+                format!("{} warnings", self.warnings).normal()
+            }
+        )?;
+
+        if !self.issues.is_empty() {
+            writeln!(w)?;
+            for issue in &self.issues {
+                let level = match issue.level {
+                    LintLevel::Error => "error".red().bold(),
+                    LintLevel::Warning => "warning".yellow(),
+                };
+                let subject = match (&issue.policy_id, &issue.file) {
+                    (Some(id), Some(file)) => format!("{} ({})", id, file),
+                    (Some(id), None) => id.clone(),
+                    (None, Some(file)) => file.clone(),
+                    (None, None) => "-".to_owned(),
+                };
+                writeln!(w, "  {}: {}: {}", level, subject, issue.message)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy_toml(id: &str, priority: u32, severity: &str, names: &str) -> String {
+        format!(
+            r#"
+id = "{id}"
+name = "Policy {id}"
+description = "test"
+enabled = true
+severity_threshold = "{severity}"
+priority = {priority}
+
+[target_filter]
+container_names = [{names}]
+image_patterns = []
+labels = []
+
+[action]
+Pause = []
+"#
+        )
+    }
+
+    #[test]
+    fn test_lint_directory_clean() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(
+            dir.path().join("web.toml"),
+            policy_toml("web", 1, "Medium", r#""web-*""#),
+        )
+        .expect("write policy");
+        std::fs::write(
+            dir.path().join("all.toml"),
+            policy_toml("all", 10, "Critical", ""),
+        )
+        .expect("write policy");
+        std::fs::write(dir.path().join("notes.md"), "ignored").expect("write file");
+
+        let report = lint_directory(dir.path()).expect("lint should run");
+
+        assert_eq!(report.files, 2);
+        assert_eq!(report.policies, 2);
+        assert_eq!(report.errors, 0);
+        assert_eq!(report.warnings, 0);
+    }
+
+    #[test]
+    fn test_lint_directory_reports_load_errors_and_shadowing() {
+        // Given: A broken file, and a catch-all policy that hides a narrower one
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(dir.path().join("broken.toml"), "id = ").expect("write policy");
+        std::fs::write(
+            dir.path().join("all.toml"),
+            policy_toml("all", 1, "Low", ""),
+        )
+        .expect("write policy");
+        std::fs::write(
+            dir.path().join("web.toml"),
+            policy_toml("web", 5, "High", r#""web-*""#),
+        )
+        .expect("write policy");
+
+        // When: Linting
+        let report = lint_directory(dir.path()).expect("lint should run");
+
+        // Then: The broken file is an error and the shadowed policy a warning
+        assert_eq!(report.errors, 1);
+        assert_eq!(report.warnings, 1);
+        assert_eq!(report.issues[0].file.as_deref(), Some("broken.toml"));
+        assert_eq!(report.issues[1].policy_id.as_deref(), Some("web"));
+        assert!(report.issues[1].message.contains("shadowed by 'all'"));
+    }
+
+    #[test]
+    fn test_lint_directory_duplicate_ids_name_both_files() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        for file in ["a.toml", "b.toml", "c.toml"] {
+            std::fs::write(
+                dir.path().join(file),
+                policy_toml("dup", 1, "High", r#""web-*""#),
+            )
+            .expect("write policy");
+        }
+
+        let report = lint_directory(dir.path()).expect("lint should run");
+
+        let errors: Vec<&PolicyIssue> = report
+            .issues
+            .iter()
+            .filter(|i| i.level == LintLevel::Error)
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].file.as_deref(), Some("a.toml, b.toml, c.toml"));
+    }
+
+    #[test]
+    fn test_lint_directory_missing() {
+        let err = lint_directory(Path::new("/nonexistent/policies")).expect_err("should fail");
+        assert_eq!(err.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(0), "00:00:00");
+        assert_eq!(format_time(1_700_000_000_123), "22:13:20");
+    }
+
+    #[test]
+    fn test_event_feed_deserializes_daemon_response() {
+        let body = r#"{
+            "last_seq": 2,
+            "truncated": false,
+            "events": [
+                {"seq": 1, "timestamp_ms": 0, "kind": "lifecycle",
+                 "container_id": "abc123def456789", "container_name": "web-1", "event": "started"},
+                {"seq": 2, "timestamp_ms": 0, "kind": "action", "action_id": "a-1",
+                 "action_type": "container_pause", "target": "abc123def456789",
+                 "success": true, "simulated": false}
+            ]
+        }"#;
+
+        let feed: EventFeed = serde_json::from_str(body).expect("should deserialize");
+
+        assert_eq!(feed.last_seq, 2);
+        assert_eq!(feed.events.len(), 2);
+        assert!(matches!(
+            feed.events[1].activity,
+            GuardActivity::Action { success: true, .. }
+        ));
+    }
+
+    #[test]
+    fn test_isolation_reports_render_text() {
+        let body = r#"{
+            "container_id": "abc123def456789", "container_name": "web-1",
+            "action": "quarantine(bridge)", "networks": ["bridge"],
+            "initiator": "manual:ops", "applied_at_ms": 0,
+            "expires_at_ms": 1800000, "simulated": false
+        }"#;
+        let isolation: Isolation = serde_json::from_str(body).expect("should deserialize");
+
+        let mut buffer = Vec::new();
+        IsolateReport(isolation)
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("web-1 (abc123def456)"), "should shorten id");
+        assert!(output.contains("quarantine(bridge)"));
+        assert!(output.contains("automatic at 00:30:00"));
+
+        let body = r#"{
+            "container_id": "abc123def456789", "container_name": "web-1",
+            "action": "pause", "initiator": "policy:web-isolate", "applied_at_ms": 0
+        }"#;
+        let isolation: Isolation = serde_json::from_str(body).expect("should deserialize");
+        let mut buffer = Vec::new();
+        ReleaseReport(isolation)
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("undid pause applied by policy:web-isolate"));
+    }
+
+    #[test]
+    fn test_sample_alert_merges_flags_over_file() {
+        let base: serde_json::Map<String, serde_json::Value> = serde_json::from_str(
+            r#"{"seq": 7, "severity": "High", "rule_name": "ssh_brute_force", "source_ip": "10.0.0.1"}"#,
+        )
+        .expect("valid JSON");
+        let alert = sample_alert(
+            Some(base),
+            AlertOverrides {
+                severity: Some(Severity::Critical),
+                source_ip: Some("1.2.3.4".parse().unwrap()),
+                rule: Some("port_scan".to_owned()),
+                ..AlertOverrides::default()
+            },
+        )
+        .expect("severity is present");
+        assert_eq!(alert["severity"], "Critical");
+        assert_eq!(alert["source_ip"], "1.2.3.4");
+        assert_eq!(alert["rule"], "port_scan");
+        assert!(!alert.contains_key("rule_name"));
+        assert_eq!(alert["seq"], 7);
+
+        let result = sample_alert(None, AlertOverrides::default());
+        assert!(result.is_err(), "severity is required");
+    }
+
+    #[test]
+    fn test_policy_test_report_render_text() {
+        let body = r#"{
+            "severity": "Critical", "containers_evaluated": 3, "policies_loaded": 2,
+            "auto_isolate": true, "dry_run": true, "standby": false,
+            "matches": [
+                {"container_id": "aaa111def456789", "container_name": "web-1",
+                 "container_image": "nginx:latest", "policy_id": "web-isolate",
+                 "policy_name": "Web isolate", "action": "pause", "applied": true,
+                 "already_isolated": false},
+                {"container_id": "bbb222def456789", "container_name": "web-2",
+                 "container_image": "nginx:latest", "policy_id": "web-isolate",
+                 "policy_name": "Web isolate", "action": "pause", "applied": false,
+                 "already_isolated": false}
+            ]
+        }"#;
+        let report: PolicyTestReport = serde_json::from_str(body).expect("should deserialize");
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("against 3 containers (2 policies)"));
+        assert!(output.contains("web-1 (aaa111def456)"));
+        assert!(output.contains("Would run pause on web-1 (policy web-isolate) [dry-run]"));
+        assert!(output.contains("Only the first matching container"));
+
+        let report = PolicyTestReport {
+            auto_isolate: false,
+            ..report
+        };
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("auto_isolate is disabled"));
+
+        let report = PolicyTestReport {
+            matches: Vec::new(),
+            ..report
+        };
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("No policy matches"));
+    }
+
+    #[test]
+    fn test_isolate_request_body() {
+        let request = IsolateRequest {
+            target: "web-1".to_owned(),
+            action: IsolateAction::NetworkDisconnect,
+            networks: vec!["bridge".to_owned()],
+            duration_secs: None,
+        };
+        let body = serde_json::to_value(&request).expect("should serialize");
+        assert_eq!(body["action"], "network_disconnect");
+        assert_eq!(body["networks"][0], "bridge");
+        assert!(body.get("duration_secs").is_none());
+    }
+
+    #[test]
+    fn test_watch_event_render_text() {
+        let event = WatchEvent {
+            seq: 7,
+            timestamp_ms: 0,
+            activity: GuardActivity::PolicyMatch {
+                alert_id: "alert-1".to_owned(),
+                alert_title: "SSH brute force".to_owned(),
+                severity: ironpost_core::types::Severity::High,
+                policy_id: "web-isolate".to_owned(),
+                policy_name: "Isolate web".to_owned(),
+                container_id: "abc123def456789".to_owned(),
+                container_name: "web-1".to_owned(),
+                action: "pause".to_owned(),
+                standby: true,
+            },
+        };
+
+        let mut buffer = Vec::new();
+        event
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("web-isolate"), "should show policy");
+        assert!(output.contains("abc123def456)"), "should shorten id");
+        assert!(output.contains("[High] -> pause"), "should show action");
+        assert!(output.contains("standby"), "should flag standby");
+    }
+
+    #[test]
+    fn test_watch_event_json_line() {
+        let event = WatchEvent {
+            seq: 3,
+            timestamp_ms: 1000,
+            activity: GuardActivity::Lifecycle {
+                container_id: "abc".to_owned(),
+                container_name: "web-1".to_owned(),
+                event: "stopped".to_owned(),
+            },
+        };
+
+        let line = serde_json::to_string(&event).expect("should serialize");
+        assert!(!line.contains('\n'), "should be a single line");
+        let json: serde_json::Value = serde_json::from_str(&line).expect("valid JSON");
+        assert_eq!(json["kind"], "lifecycle");
+        assert_eq!(json["seq"], 3);
+    }
+
+    #[test]
+    fn test_describe_filter() {
+        assert_eq!(describe_filter(&TargetFilter::default()), "all containers");
+        let filter = TargetFilter {
+            container_names: vec!["web-*".to_owned()],
+            image_patterns: vec!["nginx:*".to_owned(), "httpd:*".to_owned()],
+            labels: vec![],
+        };
+        assert_eq!(describe_filter(&filter), "name=web-* image=nginx:*,httpd:*");
+    }
+
+    #[test]
+    fn test_policy_list_report_render_text() {
+        let report = PolicyListReport {
+            policy_dir: "/etc/ironpost/policies".to_owned(),
+            total: 1,
+            policies: vec![PolicyEntry {
+                id: "web-isolate".to_owned(),
+                name: "Isolate web".to_owned(),
+                enabled: true,
+                priority: 10,
+                severity_threshold: "High".to_owned(),
+                target_filter: TargetFilter {
+                    container_names: vec!["web-*".to_owned()],
+                    ..Default::default()
+                },
+                action: "network_disconnect(bridge)".to_owned(),
+            }],
+        };
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("web-isolate"), "should show policy id");
+        assert!(output.contains("High+"), "should show severity");
+        assert!(output.contains("name=web-*"), "should show filter");
+        assert!(
+            output.contains("network_disconnect(bridge)"),
+            "should show action"
+        );
+    }
+
+    #[test]
+    fn test_policy_lint_report_json() {
+        let report = PolicyLintReport {
+            path: "policies".to_owned(),
+            files: 1,
+            policies: 1,
+            errors: 0,
+            warnings: 1,
+            issues: vec![PolicyIssue {
+                level: LintLevel::Warning,
+                policy_id: Some("web".to_owned()),
+                file: Some("web.toml".to_owned()),
+                message: "never matches".to_owned(),
+            }],
+        };
+
+        let json = serde_json::to_value(&report).expect("should serialize");
+        assert_eq!(json["issues"][0]["level"], "warning");
+        assert_eq!(json["warnings"], 1);
+    }
+}
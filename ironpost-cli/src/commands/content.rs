@@ -0,0 +1,96 @@
+//! `ironpost content` command handler
+//!
+//! `rollback` asks the daemon to re-apply a stored content version through
+//! `/content/rollback`. Without a version the one before the current version
+//! is used. Later syncs keep the rollback in place until a new upstream
+//! version is published.
+
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use ironpost_core::config::IronpostConfig;
+
+use crate::cli::{ContentAction, ContentArgs};
+use crate::client::DaemonClient;
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Daemon control endpoint rolling content back.
+const CONTENT_ROLLBACK_PATH: &str = "/content/rollback";
+
+/// Request timeout of `rollback`, which waits for the content to be applied.
+const ROLLBACK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Execute the `content` command.
+pub async fn execute(
+    args: ContentArgs,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    let client = DaemonClient::from_config(&config)?;
+
+    match args.action {
+        ContentAction::Rollback { version } => {
+            let report: RollbackReport = client
+                .with_timeout(ROLLBACK_TIMEOUT)
+                .post_json(CONTENT_ROLLBACK_PATH, &RollbackRequest { version })
+                .await?;
+            writer.render(&report)
+        }
+    }
+}
+
+/// Body of `POST /content/rollback`.
+#[derive(Debug, Serialize)]
+struct RollbackRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+}
+
+/// Result of `content rollback`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RollbackReport {
+    /// Content version now applied
+    pub version: String,
+}
+
+impl Render for RollbackReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(
+            w,
+            "{} content version {} applied",
+            "Rolled back:".green().bold(),
+            self.version.bold()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollback_request_omits_default_version() {
+        let body = serde_json::to_string(&RollbackRequest { version: None }).expect("serialize");
+        assert_eq!(body, "{}");
+    }
+
+    #[test]
+    fn test_rollback_report_renders_version() {
+        let report: RollbackReport =
+            serde_json::from_str(r#"{"version":"a1b2c3d4e5f60718"}"#).expect("should deserialize");
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("a1b2c3d4e5f60718"));
+    }
+}
@@ -0,0 +1,1272 @@
+//! `ironpost ebpf` command handler
+//!
+//! Queries the running daemon's `/ebpf` control endpoint for the XDP attach
+//! state and per-protocol traffic counters, and manages the engine's runtime
+//! blocklist through `/ebpf/blocklist`. Blocklist changes take effect on the
+//! running XDP program immediately. `blocklist list --enrich` annotates
+//! entries with GeoIP country, ASN and reverse DNS. `rules import` merges a
+//! rules file into the engine's filter rules through `/ebpf/rules/import`
+//! with a single map sync.
+
+use std::io::Write;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use ironpost_core::config::IronpostConfig;
+use ironpost_log_pipeline::IpEnrichment;
+
+use crate::cli::{BlockAction, BlocklistAction, EbpfAction, EbpfArgs, EbpfRulesAction};
+use crate::client::DaemonClient;
+use crate::commands::alerts::{enrich_addresses, format_enrichment, load_enricher};
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Daemon control endpoint serving eBPF status.
+pub(crate) const EBPF_STATUS_PATH: &str = "/ebpf";
+
+/// Daemon control endpoint listing (`GET`) and adding (`POST`) blocklist entries.
+const EBPF_BLOCKLIST_PATH: &str = "/ebpf/blocklist";
+
+/// Daemon control endpoint removing blocklist entries.
+const EBPF_BLOCKLIST_REMOVE_PATH: &str = "/ebpf/blocklist/remove";
+
+/// Daemon control endpoint importing filter rules in bulk.
+const EBPF_RULES_IMPORT_PATH: &str = "/ebpf/rules/import";
+
+/// Refresh interval of `ebpf stats --watch`.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Execute the `ebpf` command.
+pub async fn execute(
+    args: EbpfArgs,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    if !config.ebpf.enabled {
+        return Err(CliError::Command(
+            "ebpf engine is disabled in configuration ([ebpf] enabled = false)".to_owned(),
+        ));
+    }
+
+    let client = DaemonClient::from_config(&config)?;
+    debug!(addr = client.addr(), "querying daemon eBPF status");
+
+    match args.action {
+        EbpfAction::Status => {
+            let status: EbpfStatus = client.get_json(EBPF_STATUS_PATH).await?;
+            writer.render(&status)
+        }
+        EbpfAction::Stats { watch: false } => {
+            let status: EbpfStatus = client.get_json(EBPF_STATUS_PATH).await?;
+            writer.render(&EbpfStatsReport::from(status))
+        }
+        EbpfAction::Stats { watch: true } => execute_watch(&client, writer).await,
+        EbpfAction::Blocklist { action } => {
+            execute_blocklist(action, &config, &client, writer).await
+        }
+        EbpfAction::Rules {
+            action: EbpfRulesAction::Import { path },
+        } => {
+            let content = tokio::fs::read_to_string(&path).await?;
+            let rules = parse_rules_import(&content)?;
+            if rules.is_empty() {
+                return Err(CliError::Command(format!(
+                    "no rules to import from {}",
+                    path.display()
+                )));
+            }
+            debug!(count = rules.len(), "importing filter rules");
+
+            let response: RulesImportResponse = client
+                .post_json(EBPF_RULES_IMPORT_PATH, &RulesImportRequest { rules })
+                .await?;
+            writer.render(&RulesImportReport { path, response })
+        }
+    }
+}
+
+/// Run a `blocklist` subcommand against the daemon.
+async fn execute_blocklist(
+    action: BlocklistAction,
+    config: &IronpostConfig,
+    client: &DaemonClient,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    match action {
+        BlocklistAction::Add {
+            target,
+            action,
+            ttl,
+            description,
+        } => {
+            let request = AddRequest {
+                entries: vec![NewEntry {
+                    cidr: target,
+                    action,
+                    ttl_secs: ttl,
+                    description: description.unwrap_or_default(),
+                }],
+            };
+            let response: AddResponse = client.post_json(EBPF_BLOCKLIST_PATH, &request).await?;
+            writer.render(&response)
+        }
+        BlocklistAction::Remove { targets } => {
+            let request = RemoveRequest { entries: targets };
+            let response: RemoveResponse = client
+                .post_json(EBPF_BLOCKLIST_REMOVE_PATH, &request)
+                .await?;
+            writer.render(&response)
+        }
+        BlocklistAction::List { enrich } => {
+            let enricher = enrich.then(|| load_enricher(config)).transpose()?;
+            let mut list: BlocklistList = client.get_json(EBPF_BLOCKLIST_PATH).await?;
+            if let Some(enricher) = &enricher {
+                // Reverse DNS only makes sense for single hosts; networks get
+                // GeoIP for their base address
+                let hosts: Vec<IpAddr> = list
+                    .entries
+                    .iter()
+                    .filter_map(|s| entry_address(&s.entry.cidr))
+                    .filter(|(_, single)| *single)
+                    .map(|(ip, _)| ip)
+                    .collect();
+                let found = enrich_addresses(enricher, hosts).await;
+                for status in &mut list.entries {
+                    status.enrichment = match entry_address(&status.entry.cidr) {
+                        Some((ip, true)) => found.get(&ip).cloned(),
+                        Some((ip, false)) => {
+                            Some(enricher.lookup_geoip(ip)).filter(|e| !e.is_empty())
+                        }
+                        None => None,
+                    };
+                }
+            }
+            writer.render(&list)
+        }
+        BlocklistAction::Import { path, action, ttl } => {
+            let content = tokio::fs::read_to_string(&path).await?;
+            let (entries, skipped_expired) = parse_import(&content, action, ttl, unix_now())?;
+            if entries.is_empty() {
+                return Err(CliError::Command(format!(
+                    "no blocklist entries to import from {}",
+                    path.display()
+                )));
+            }
+            debug!(count = entries.len(), "importing blocklist entries");
+
+            let response: AddResponse = client
+                .post_json(EBPF_BLOCKLIST_PATH, &AddRequest { entries })
+                .await?;
+            writer.render(&ImportReport {
+                path,
+                imported: response.added,
+                skipped_expired,
+            })
+        }
+        BlocklistAction::Export { path } => {
+            let list: BlocklistList = client.get_json(EBPF_BLOCKLIST_PATH).await?;
+            let export = BlocklistExport {
+                entries: list.entries.into_iter().map(|s| s.entry).collect(),
+            };
+            let mut json = serde_json::to_vec_pretty(&export)?;
+            json.push(b'\n');
+            tokio::fs::write(&path, json).await?;
+            writer.render(&ExportReport {
+                path,
+                exported: export.entries.len(),
+            })
+        }
+    }
+}
+
+/// Base address of a blocklist target, and whether it is a single host.
+fn entry_address(cidr: &str) -> Option<(IpAddr, bool)> {
+    let (addr, prefix) = match cidr.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix.parse::<u8>().ok()?)),
+        None => (cidr, None),
+    };
+    let ip: IpAddr = addr.trim().parse().ok()?;
+    let host_prefix = if ip.is_ipv4() { 32 } else { 128 };
+    Some((ip, prefix.is_none_or(|p| p == host_prefix)))
+}
+
+/// Parse an import file into entries to add.
+///
+/// Accepts either a JSON export (`{"entries": [...]}`) or plain text with one
+/// address or CIDR per line; `#` starts a comment, which becomes the entry's
+/// description. JSON entries keep their action and remaining TTL, and entries
+/// that already expired are skipped. Returns the entries and the number of
+/// skipped entries.
+fn parse_import(
+    content: &str,
+    action: BlockAction,
+    ttl: Option<u64>,
+    now: u64,
+) -> Result<(Vec<NewEntry>, usize), CliError> {
+    if content.trim_start().starts_with('{') {
+        let export: BlocklistExport = serde_json::from_str(content)
+            .map_err(|e| CliError::Command(format!("invalid blocklist export: {}", e)))?;
+        let total = export.entries.len();
+        let entries: Vec<NewEntry> = export
+            .entries
+            .into_iter()
+            .filter_map(|entry| {
+                let ttl_secs = match entry.expires_at {
+                    Some(expires_at) if expires_at <= now => return None,
+                    Some(expires_at) => Some(expires_at - now),
+                    None => None,
+                };
+                Some(NewEntry {
+                    cidr: entry.cidr,
+                    action: entry.action,
+                    ttl_secs,
+                    description: entry.description,
+                })
+            })
+            .collect();
+        let skipped = total - entries.len();
+        return Ok((entries, skipped));
+    }
+
+    let entries = content
+        .lines()
+        .filter_map(|line| {
+            let (target, comment) = line.split_once('#').unwrap_or((line, ""));
+            let target = target.trim();
+            (!target.is_empty()).then(|| NewEntry {
+                cidr: target.to_owned(),
+                action,
+                ttl_secs: ttl,
+                description: comment.trim().to_owned(),
+            })
+        })
+        .collect();
+    Ok((entries, 0))
+}
+
+/// Parse a rules file into the rules to import.
+///
+/// Accepts JSON (`{"rules": [...]}`) or the TOML rules file format with
+/// `[[rules]]` tables. Rules are passed through as-is; the daemon validates
+/// them.
+fn parse_rules_import(content: &str) -> Result<Vec<serde_json::Value>, CliError> {
+    let file: RulesFile = if content.trim_start().starts_with('{') {
+        serde_json::from_str(content)
+            .map_err(|e| CliError::Command(format!("invalid rules file: {}", e)))?
+    } else {
+        toml::from_str(content)
+            .map_err(|e| CliError::Command(format!("invalid rules file: {}", e)))?
+    };
+    Ok(file.rules)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Refresh the stats table every second until Ctrl-C.
+async fn execute_watch(client: &DaemonClient, writer: &OutputWriter) -> Result<(), CliError> {
+    let mut interval = tokio::time::interval(WATCH_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let status: EbpfStatus = client.get_json(EBPF_STATUS_PATH).await?;
+                writer.clear_screen()?;
+                writer.render(&EbpfStatsReport::from(status))?;
+            }
+            _ = tokio::signal::ctrl_c() => {
+                debug!("watch interrupted");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Counters for a single protocol, as reported by the daemon.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProtocolCounters {
+    /// Packets processed (cumulative)
+    pub packets: u64,
+    /// Bytes processed (cumulative)
+    pub bytes: u64,
+    /// Packets dropped (cumulative)
+    pub drops: u64,
+    /// Packets that matched a drop decision but were passed (observe-only mode)
+    #[serde(default)]
+    pub would_drops: u64,
+    /// Packets per second over the last poll
+    pub pps: f64,
+    /// Bits per second over the last poll
+    pub bps: f64,
+}
+
+/// Counters for a single destination port, as reported by the daemon.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PortCounters {
+    /// Destination port
+    pub port: u16,
+    /// Transport protocol (tcp, udp)
+    pub protocol: String,
+    /// Counters for traffic to this port
+    #[serde(flatten)]
+    pub counters: ProtocolCounters,
+}
+
+/// Counters for a single network interface, as reported by the daemon.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InterfaceCounters {
+    /// Interface name
+    pub name: String,
+    /// Counters for traffic received on this interface
+    #[serde(flatten)]
+    pub counters: ProtocolCounters,
+}
+
+/// Per-protocol traffic counters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrafficCounters {
+    pub tcp: ProtocolCounters,
+    pub udp: ProtocolCounters,
+    pub icmp: ProtocolCounters,
+    pub other: ProtocolCounters,
+    pub total: ProtocolCounters,
+    /// Busiest destination ports (absent on older daemons)
+    #[serde(default)]
+    pub top_ports: Vec<PortCounters>,
+    /// Counters per attached interface (absent on older daemons)
+    #[serde(default)]
+    pub interfaces: Vec<InterfaceCounters>,
+}
+
+impl TrafficCounters {
+    pub(crate) fn rows(&self) -> [(&'static str, &ProtocolCounters); 5] {
+        [
+            ("tcp", &self.tcp),
+            ("udp", &self.udp),
+            ("icmp", &self.icmp),
+            ("other", &self.other),
+            ("total", &self.total),
+        ]
+    }
+}
+
+/// Connections-per-second for a single source host.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HostConnections {
+    /// Source IPv4 address
+    pub host: String,
+    /// New connections per second since the previous poll
+    pub cps: f64,
+    /// Flows from this host that are still active
+    pub active_flows: usize,
+}
+
+/// Connection tracking counters from the XDP flow table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConnectionCounters {
+    /// Flows seen within the idle timeout
+    pub active: usize,
+    /// Active flows that have not completed a handshake
+    pub new: usize,
+    /// Active flows with traffic in progress
+    pub established: usize,
+    /// Active TCP flows that have seen FIN or RST
+    pub closing: usize,
+    /// Hosts opening the most new connections
+    #[serde(default)]
+    pub top_hosts: Vec<HostConnections>,
+}
+
+/// eBPF engine status returned by the daemon's `/ebpf` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EbpfStatus {
+    /// Whether the XDP program is attached
+    pub attached: bool,
+    /// Engine health from the daemon's last health check
+    pub health: String,
+    /// Network interface
+    pub interface: String,
+    /// XDP attach mode (native, skb, hw)
+    pub xdp_mode: String,
+    /// Whether block rules only log instead of dropping
+    pub dry_run: bool,
+    /// Number of loaded filter rules
+    pub rule_count: usize,
+    /// Number of runtime blocklist entries
+    #[serde(default)]
+    pub blocklist_entries: usize,
+    /// Traffic counters
+    pub stats: TrafficCounters,
+    /// Connection tracking counters (absent on older daemons)
+    #[serde(default)]
+    pub connections: ConnectionCounters,
+}
+
+impl Render for EbpfStatus {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        let attached = if self.attached {
+            "attached".green().bold()
+        } else {
+            "detached".red().bold()
+        };
+        writeln!(w, "XDP:        {} ({})", attached, self.health)?;
+        writeln!(w, "Interface:  {}", self.interface)?;
+        writeln!(w, "Mode:       {}", self.xdp_mode)?;
+        writeln!(w, "Rules:      {}", self.rule_count)?;
+        writeln!(w, "Blocklist:  {} entries", self.blocklist_entries)?;
+        if self.dry_run {
+            writeln!(
+                w,
+                "Dry run:    {}",
+                "yes (drops are logged and passed)".yellow()
+            )?;
+        }
+        write!(
+            w,
+            "Traffic:    {} packets, {}, {} dropped",
+            self.stats.total.packets,
+            format_bytes(self.stats.total.bytes),
+            self.stats.total.drops
+        )?;
+        if self.stats.total.would_drops > 0 {
+            write!(w, ", {} would drop", self.stats.total.would_drops)?;
+        }
+        writeln!(w)?;
+        writeln!(
+            w,
+            "Flows:      {} active ({} new, {} established, {} closing)",
+            self.connections.active,
+            self.connections.new,
+            self.connections.established,
+            self.connections.closing
+        )?;
+        if let Some(top) = self.connections.top_hosts.first().filter(|h| h.cps > 0.0) {
+            writeln!(w, "Top source: {} ({:.1} conn/s)", top.host, top.cps)?;
+        }
+        Ok(())
+    }
+}
+
+/// Per-protocol counters rendered by `ebpf stats`.
+#[derive(Debug, Serialize)]
+pub struct EbpfStatsReport {
+    /// Network interface
+    pub interface: String,
+    /// Whether the XDP program is attached
+    pub attached: bool,
+    /// Traffic counters
+    pub stats: TrafficCounters,
+}
+
+impl From<EbpfStatus> for EbpfStatsReport {
+    fn from(status: EbpfStatus) -> Self {
+        Self {
+            interface: status.interface,
+            attached: status.attached,
+            stats: status.stats,
+        }
+    }
+}
+
+impl Render for EbpfStatsReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        let state = if self.attached {
+            "attached".green()
+        } else {
+            "detached".red()
+        };
+        writeln!(w, "Interface: {} ({})", self.interface, state)?;
+        writeln!(w)?;
+        writeln!(
+            w,
+            "{:<8} {:>14} {:>12} {:>12} {:>12} {:>14}",
+            "Protocol", "Packets", "Bytes", "Drops", "PPS", "Throughput"
+        )?;
+        writeln!(w, "{}", "-".repeat(77))?;
+
+        for (name, c) in self.stats.rows() {
+            let line = format!(
+                "{:<8} {:>14} {:>12} {:>12} {:>12.1} {:>14}",
+                name,
+                c.packets,
+                format_bytes(c.bytes),
+                c.drops,
+                c.pps,
+                format_bps(c.bps)
+            );
+            if name == "total" {
+                writeln!(w, "{}", line.bold())?;
+            } else {
+                writeln!(w, "{}", line)?;
+            }
+        }
+        if self.stats.total.would_drops > 0 {
+            writeln!(
+                w,
+                "{}",
+                format!(
+                    "Would drop: {} packets passed in observe-only mode",
+                    self.stats.total.would_drops
+                )
+                .yellow()
+            )?;
+        }
+
+        if !self.stats.top_ports.is_empty() {
+            writeln!(w)?;
+            writeln!(
+                w,
+                "{:<10} {:>12} {:>12} {:>12} {:>12} {:>14}",
+                "Port", "Packets", "Bytes", "Drops", "PPS", "Throughput"
+            )?;
+            writeln!(w, "{}", "-".repeat(77))?;
+            for p in &self.stats.top_ports {
+                writeln!(
+                    w,
+                    "{:<10} {:>12} {:>12} {:>12} {:>12.1} {:>14}",
+                    format!("{}/{}", p.port, p.protocol),
+                    p.counters.packets,
+                    format_bytes(p.counters.bytes),
+                    p.counters.drops,
+                    p.counters.pps,
+                    format_bps(p.counters.bps)
+                )?;
+            }
+        }
+
+        if !self.stats.interfaces.is_empty() {
+            writeln!(w)?;
+            writeln!(
+                w,
+                "{:<16} {:>12} {:>12} {:>12} {:>12} {:>14}",
+                "Interface", "Packets", "Bytes", "Drops", "PPS", "Throughput"
+            )?;
+            writeln!(w, "{}", "-".repeat(83))?;
+            for i in &self.stats.interfaces {
+                writeln!(
+                    w,
+                    "{:<16} {:>12} {:>12} {:>12} {:>12.1} {:>14}",
+                    i.name,
+                    i.counters.packets,
+                    format_bytes(i.counters.bytes),
+                    i.counters.drops,
+                    i.counters.pps,
+                    format_bps(i.counters.bps)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A blocklist entry as stored by the daemon and written by `export`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlocklistEntry {
+    /// IPv4 address or CIDR
+    pub cidr: String,
+    /// Action applied to matching packets
+    pub action: BlockAction,
+    /// Reason recorded with the entry
+    #[serde(default)]
+    pub description: String,
+    /// When the entry was added (Unix seconds)
+    #[serde(default)]
+    pub added_at: u64,
+    /// When the entry expires (Unix seconds, `None` = never)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+/// A blocklist entry with its hit count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlocklistStatus {
+    #[serde(flatten)]
+    pub entry: BlocklistEntry,
+    /// Packets matched by the entry
+    pub hits: u64,
+    /// GeoIP/ASN/reverse DNS of the target (`--enrich`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enrichment: Option<IpEnrichment>,
+}
+
+/// Runtime blocklist returned by `GET /ebpf/blocklist`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlocklistList {
+    pub entries: Vec<BlocklistStatus>,
+}
+
+impl Render for BlocklistList {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        if self.entries.is_empty() {
+            return writeln!(w, "Blocklist is empty.");
+        }
+
+        let now = unix_now();
+        writeln!(
+            w,
+            "{:<20} {:<8} {:>12} {:<10} Description",
+            "Target", "Action", "Hits", "Expires"
+        )?;
+        writeln!(w, "{}", "-".repeat(72))?;
+        for status in &self.entries {
+            let entry = &status.entry;
+            let action = match entry.action {
+                BlockAction::Block => format!("{:<8}", "block").red(),
+                BlockAction::Monitor => format!("{:<8}", "monitor").yellow(),
+                BlockAction::Allow => format!("{:<8}", "allow").green(),
+                BlockAction::Reflect => format!("{:<8}", "reflect").magenta(),
+            };
+            let expires = match entry.expires_at {
+                Some(at) => format_remaining(at.saturating_sub(now)),
+                None => "never".to_owned(),
+            };
+            writeln!(
+                w,
+                "{:<20} {} {:>12} {:<10} {}",
+                entry.cidr, action, status.hits, expires, entry.description
+            )?;
+            if let Some(enrichment) = &status.enrichment {
+                writeln!(w, "{:<20} {}", "", format_enrichment(enrichment).dimmed())?;
+            }
+        }
+        writeln!(w)?;
+        writeln!(w, "{} entries", self.entries.len())?;
+        Ok(())
+    }
+}
+
+/// File format written by `blocklist export` and read by `blocklist import`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlocklistExport {
+    pub entries: Vec<BlocklistEntry>,
+}
+
+/// An entry sent to `POST /ebpf/blocklist`.
+#[derive(Debug, Serialize)]
+pub struct NewEntry {
+    pub cidr: String,
+    pub action: BlockAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u64>,
+    pub description: String,
+}
+
+/// Body of `POST /ebpf/blocklist`.
+#[derive(Debug, Serialize)]
+pub struct AddRequest {
+    pub entries: Vec<NewEntry>,
+}
+
+/// Response of `POST /ebpf/blocklist`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddResponse {
+    /// Entries added or replaced
+    pub added: usize,
+}
+
+impl Render for AddResponse {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+        writeln!(
+            w,
+            "{} {} blocklist {}",
+            "Added".green(),
+            self.added,
+            plural(self.added)
+        )
+    }
+}
+
+/// Body of `POST /ebpf/blocklist/remove`.
+#[derive(Debug, Serialize)]
+pub struct RemoveRequest {
+    pub entries: Vec<String>,
+}
+
+/// Response of `POST /ebpf/blocklist/remove`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoveResponse {
+    /// Entries removed
+    pub removed: usize,
+    /// Requested entries that were not in the blocklist
+    pub missing: Vec<String>,
+}
+
+impl Render for RemoveResponse {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+        writeln!(
+            w,
+            "{} {} blocklist {}",
+            "Removed".green(),
+            self.removed,
+            plural(self.removed)
+        )?;
+        for target in &self.missing {
+            writeln!(
+                w,
+                "{} {} was not in the blocklist",
+                "warning:".yellow(),
+                target
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of `blocklist import`.
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    /// File the entries were read from
+    pub path: PathBuf,
+    /// Entries added or replaced
+    pub imported: usize,
+    /// Entries skipped because they had already expired
+    pub skipped_expired: usize,
+}
+
+impl Render for ImportReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+        writeln!(
+            w,
+            "{} {} blocklist {} from {}",
+            "Imported".green(),
+            self.imported,
+            plural(self.imported),
+            self.path.display()
+        )?;
+        if self.skipped_expired > 0 {
+            writeln!(
+                w,
+                "Skipped {} expired {}",
+                self.skipped_expired,
+                plural(self.skipped_expired)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Rules file read by `rules import`.
+#[derive(Debug, Deserialize)]
+struct RulesFile {
+    #[serde(default)]
+    rules: Vec<serde_json::Value>,
+}
+
+/// Body of `POST /ebpf/rules/import`.
+#[derive(Debug, Serialize)]
+pub struct RulesImportRequest {
+    pub rules: Vec<serde_json::Value>,
+}
+
+/// Response of `POST /ebpf/rules/import`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RulesImportResponse {
+    /// Rules added
+    pub added: usize,
+    /// Existing rules replaced by a rule with the same ID
+    pub replaced: usize,
+    /// Rules dropped because a later rule in the file had the same ID
+    pub duplicates: usize,
+}
+
+/// Result of `rules import`.
+#[derive(Debug, Serialize)]
+pub struct RulesImportReport {
+    /// File the rules were read from
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub response: RulesImportResponse,
+}
+
+impl Render for RulesImportReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+        writeln!(
+            w,
+            "{} {} new and {} replaced {} from {}",
+            "Imported".green(),
+            self.response.added,
+            self.response.replaced,
+            if self.response.added + self.response.replaced == 1 {
+                "rule"
+            } else {
+                "rules"
+            },
+            self.path.display()
+        )?;
+        if self.response.duplicates > 0 {
+            writeln!(
+                w,
+                "{} skipped {} rules with duplicate IDs (last one wins)",
+                "warning:".yellow(),
+                self.response.duplicates
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of `blocklist export`.
+#[derive(Debug, Serialize)]
+pub struct ExportReport {
+    /// File the entries were written to
+    pub path: PathBuf,
+    /// Entries written
+    pub exported: usize,
+}
+
+impl Render for ExportReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+        writeln!(
+            w,
+            "{} {} blocklist {} to {}",
+            "Exported".green(),
+            self.exported,
+            plural(self.exported),
+            self.path.display()
+        )
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "entry" } else { "entries" }
+}
+
+/// Format a remaining duration with its two largest units (e.g. `2h 5m`).
+fn format_remaining(secs: u64) -> String {
+    let (days, hours, minutes, seconds) = (
+        secs / 86_400,
+        secs % 86_400 / 3_600,
+        secs % 3_600 / 60,
+        secs % 60,
+    );
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Format a byte count with binary units (e.g. `1.5 KiB`).
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Format a bit rate with decimal units (e.g. `12.3 Mbps`).
+pub(crate) fn format_bps(bps: f64) -> String {
+    const UNITS: [&str; 4] = ["bps", "Kbps", "Mbps", "Gbps"];
+    let mut value = bps;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_status() -> EbpfStatus {
+        let json = r#"{
+            "attached": true,
+            "health": "healthy",
+            "interface": "eth0",
+            "xdp_mode": "native",
+            "dry_run": false,
+            "rule_count": 4,
+            "stats": {
+                "tcp": {"packets": 900, "bytes": 1536000, "drops": 12, "pps": 30.0, "bps": 409600.0},
+                "udp": {"packets": 100, "bytes": 20480, "drops": 0, "pps": 3.0, "bps": 8000.0},
+                "icmp": {"packets": 0, "bytes": 0, "drops": 0, "pps": 0.0, "bps": 0.0},
+                "other": {"packets": 0, "bytes": 0, "drops": 0, "pps": 0.0, "bps": 0.0},
+                "total": {"packets": 1000, "bytes": 1556480, "drops": 12, "pps": 33.0, "bps": 417600.0}
+            }
+        }"#;
+        serde_json::from_str(json).expect("daemon JSON should deserialize")
+    }
+
+    #[test]
+    fn test_status_render_text() {
+        let status = sample_status();
+
+        let mut buffer = Vec::new();
+        status
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("attached"), "should show attach state");
+        assert!(output.contains("eth0"), "should show interface");
+        assert!(output.contains("native"), "should show XDP mode");
+        assert!(output.contains("Rules:      4"), "should show rule count");
+        assert!(output.contains("12 dropped"), "should show drops");
+        assert!(
+            output.contains("Flows:      0 active"),
+            "missing counters default"
+        );
+        assert!(
+            !output.contains("Top source"),
+            "no top source without rates"
+        );
+    }
+
+    #[test]
+    fn test_status_render_text_connections() {
+        let mut status = sample_status();
+        status.connections = serde_json::from_str(
+            r#"{
+                "active": 42, "new": 30, "established": 10, "closing": 2,
+                "top_hosts": [{"host": "198.51.100.7", "cps": 12.5, "active_flows": 30}]
+            }"#,
+        )
+        .expect("connection JSON should deserialize");
+
+        let mut buffer = Vec::new();
+        status
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("42 active (30 new, 10 established, 2 closing)"));
+        assert!(output.contains("198.51.100.7 (12.5 conn/s)"));
+    }
+
+    #[test]
+    fn test_status_render_text_detached_dry_run() {
+        let mut status = sample_status();
+        status.attached = false;
+        status.dry_run = true;
+        status.health = "unhealthy: not running".to_owned();
+
+        let mut buffer = Vec::new();
+        status
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("detached"));
+        assert!(output.contains("not running"));
+        assert!(output.contains("Dry run"));
+        assert!(!output.contains("would drop"));
+    }
+
+    #[test]
+    fn test_render_text_reports_would_drops() {
+        let mut status = sample_status();
+        status.dry_run = true;
+        status.stats.total.would_drops = 7;
+
+        let mut buffer = Vec::new();
+        status
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("12 dropped, 7 would drop"));
+
+        let mut buffer = Vec::new();
+        EbpfStatsReport::from(status)
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("Would drop: 7 packets"));
+    }
+
+    #[test]
+    fn test_stats_render_text_lists_protocols() {
+        let report = EbpfStatsReport::from(sample_status());
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        for proto in ["tcp", "udp", "icmp", "other", "total"] {
+            assert!(output.contains(proto), "should list {}", proto);
+        }
+        assert!(output.contains("1.5 MiB"), "should format tcp bytes");
+        assert!(output.contains("409.6 Kbps"), "should format tcp rate");
+        assert!(!output.contains("Port"), "no port table without port data");
+    }
+
+    #[test]
+    fn test_stats_render_text_lists_top_ports() {
+        let mut status = sample_status();
+        status.stats.top_ports = serde_json::from_str(
+            r#"[
+                {"port": 443, "protocol": "tcp", "packets": 800, "bytes": 1228800, "drops": 0, "pps": 25.0, "bps": 320000.0},
+                {"port": 22, "protocol": "tcp", "packets": 100, "bytes": 6400, "drops": 12, "pps": 5.0, "bps": 1600.0}
+            ]"#,
+        )
+        .expect("port JSON should deserialize");
+        let report = EbpfStatsReport::from(status);
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("443/tcp"), "should list hottest port");
+        assert!(output.contains("22/tcp"), "should list second port");
+        assert!(
+            output.find("443/tcp") < output.find("22/tcp"),
+            "should keep daemon order"
+        );
+
+        let json = serde_json::to_value(&report).expect("should serialize");
+        assert_eq!(json["stats"]["top_ports"][1]["drops"], 12);
+    }
+
+    #[test]
+    fn test_stats_render_text_lists_interfaces() {
+        let mut status = sample_status();
+        status.interface = "eth0,eth1".to_owned();
+        status.stats.interfaces = serde_json::from_str(
+            r#"[
+                {"name": "eth0", "packets": 700, "bytes": 1048576, "drops": 10, "pps": 20.0, "bps": 300000.0},
+                {"name": "eth1", "packets": 300, "bytes": 524288, "drops": 2, "pps": 10.0, "bps": 109600.0}
+            ]"#,
+        )
+        .expect("interface JSON should deserialize");
+        let report = EbpfStatsReport::from(status);
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("Interface: eth0,eth1"));
+        assert!(output.contains("eth1"), "should list second interface");
+        assert!(output.contains("1.0 MiB"), "should format eth0 bytes");
+
+        let json = serde_json::to_value(&report).expect("should serialize");
+        assert_eq!(json["stats"]["interfaces"][1]["name"], "eth1");
+        assert_eq!(json["stats"]["interfaces"][0]["drops"], 10);
+    }
+
+    #[test]
+    fn test_stats_report_json_structure() {
+        let report = EbpfStatsReport::from(sample_status());
+
+        let json = serde_json::to_value(&report).expect("should serialize");
+        assert_eq!(json["interface"], "eth0");
+        assert_eq!(json["stats"]["tcp"]["drops"], 12);
+        assert_eq!(json["stats"]["total"]["packets"], 1000);
+    }
+
+    #[test]
+    fn test_blocklist_list_render_text() {
+        let json = r#"{"entries": [
+            {"cidr": "10.0.0.0/24", "action": "block", "description": "scanner",
+             "added_at": 1700000000, "hits": 42},
+            {"cidr": "192.168.1.5", "action": "monitor", "description": "",
+             "added_at": 1700000000, "expires_at": 4102444800, "hits": 0}
+        ]}"#;
+        let list: BlocklistList =
+            serde_json::from_str(json).expect("daemon JSON should deserialize");
+
+        let mut buffer = Vec::new();
+        list.render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("10.0.0.0/24"));
+        assert!(output.contains("42"), "should show hit count");
+        assert!(
+            output.contains("never"),
+            "permanent entry should never expire"
+        );
+        assert!(output.contains("scanner"));
+        assert!(output.contains("2 entries"));
+    }
+
+    #[test]
+    fn test_blocklist_list_render_text_with_enrichment() {
+        let json = r#"{"entries": [
+            {"cidr": "203.0.113.7", "action": "block", "hits": 3,
+             "enrichment": {"country": "NL", "asn": 64500, "hostname": "scan.example"}}
+        ]}"#;
+        let list: BlocklistList =
+            serde_json::from_str(json).expect("daemon JSON should deserialize");
+
+        let mut buffer = Vec::new();
+        list.render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(
+            output.contains("NL, AS64500, scan.example"),
+            "got: {}",
+            output
+        );
+    }
+
+    #[test]
+    fn test_entry_address_distinguishes_hosts_and_networks() {
+        let ip: IpAddr = "10.0.0.1".parse().expect("valid IP");
+        assert_eq!(entry_address("10.0.0.1"), Some((ip, true)));
+        assert_eq!(entry_address("10.0.0.1/32"), Some((ip, true)));
+        assert_eq!(
+            entry_address("10.0.0.0/24"),
+            Some(("10.0.0.0".parse().expect("valid IP"), false))
+        );
+        assert_eq!(entry_address("not-an-ip"), None);
+        assert_eq!(entry_address("10.0.0.0/x"), None);
+    }
+
+    #[test]
+    fn test_parse_import_plain_text() {
+        let content = "# scanners\n10.0.0.1\n\n10.0.1.0/24  # botnet\n";
+
+        let (entries, skipped) =
+            parse_import(content, BlockAction::Monitor, Some(600), 1000).expect("should parse");
+
+        assert_eq!(skipped, 0);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].cidr, "10.0.0.1");
+        assert_eq!(entries[0].action, BlockAction::Monitor);
+        assert_eq!(entries[0].ttl_secs, Some(600));
+        assert_eq!(entries[1].cidr, "10.0.1.0/24");
+        assert_eq!(entries[1].description, "botnet");
+    }
+
+    #[test]
+    fn test_parse_import_export_keeps_remaining_ttl() {
+        let content = r#"{"entries": [
+            {"cidr": "10.0.0.1", "action": "block", "added_at": 500},
+            {"cidr": "10.0.0.2", "action": "monitor", "added_at": 500, "expires_at": 1600},
+            {"cidr": "10.0.0.3", "action": "block", "added_at": 500, "expires_at": 900}
+        ]}"#;
+
+        let (entries, skipped) =
+            parse_import(content, BlockAction::Block, None, 1000).expect("should parse");
+
+        assert_eq!(skipped, 1, "expired entry should be skipped");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].ttl_secs, None);
+        assert_eq!(entries[1].action, BlockAction::Monitor);
+        assert_eq!(entries[1].ttl_secs, Some(600));
+    }
+
+    #[test]
+    fn test_parse_import_rejects_invalid_json() {
+        let result = parse_import("{not json", BlockAction::Block, None, 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_rules_import_accepts_toml_and_json() {
+        let toml = r#"
+[[rules]]
+id = "block-scanner"
+src_ip = "10.0.0.50"
+action = "block"
+description = "Known port scanner"
+
+[[rules]]
+id = "block-telnet"
+dst_port = 23
+protocol = 6
+action = "block"
+description = ""
+"#;
+        let rules = parse_rules_import(toml).expect("should parse TOML");
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "block-scanner");
+        assert_eq!(rules[1]["dst_port"], 23);
+
+        let json = r#"{"rules": [{"id": "a", "src_ip": "10.0.0.1", "action": "monitor", "description": ""}]}"#;
+        let rules = parse_rules_import(json).expect("should parse JSON");
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0]["action"], "monitor");
+
+        assert!(parse_rules_import("[[rules]\nid =").is_err());
+    }
+
+    #[test]
+    fn test_export_roundtrips_through_import() {
+        let export = BlocklistExport {
+            entries: vec![BlocklistEntry {
+                cidr: "10.0.0.0/24".to_owned(),
+                action: BlockAction::Block,
+                description: "scanner".to_owned(),
+                added_at: 100,
+                expires_at: None,
+            }],
+        };
+        let json = serde_json::to_string_pretty(&export).expect("should serialize");
+
+        let (entries, _) =
+            parse_import(&json, BlockAction::Monitor, Some(60), 200).expect("should parse export");
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, BlockAction::Block, "export action wins");
+        assert_eq!(
+            entries[0].ttl_secs, None,
+            "--ttl applies to plain text only"
+        );
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(5), "5s");
+        assert_eq!(format_remaining(125), "2m 5s");
+        assert_eq!(format_remaining(7_500), "2h 5m");
+        assert_eq!(format_remaining(90_000), "1d 1h");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+
+    #[test]
+    fn test_format_bps() {
+        assert_eq!(format_bps(0.0), "0.0 bps");
+        assert_eq!(format_bps(1500.0), "1.5 Kbps");
+        assert_eq!(format_bps(2_000_000_000.0), "2.0 Gbps");
+    }
+}
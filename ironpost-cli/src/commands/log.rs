@@ -0,0 +1,202 @@
+//! `ironpost log` command handler
+//!
+//! `search` queries the daemon's built-in log store (`/logs/search`), which
+//! holds parsed logs when `[log_pipeline.log_store]` is enabled. Results are
+//! newest first; `--offset` pages through larger result sets. `--since` and
+//! `--until` are ages relative to now, and `--field host=...` /
+//! `--field process=...` match the hostname and process name.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use ironpost_core::config::IronpostConfig;
+use ironpost_core::types::Severity;
+
+use crate::cli::{LogAction, LogArgs};
+use crate::client::DaemonClient;
+use crate::commands::report::format_datetime;
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Daemon control endpoint searching stored logs.
+const LOGS_SEARCH_PATH: &str = "/logs/search";
+
+/// Execute the `log` command.
+pub async fn execute(
+    args: LogArgs,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    let client = DaemonClient::from_config(&config)?;
+
+    match args.action {
+        LogAction::Search {
+            query,
+            since,
+            until,
+            fields,
+            severity,
+            limit,
+            offset,
+        } => {
+            let now = now_ms();
+            let mut request = SearchRequest {
+                text: query,
+                since_ms: since.map(|age| now.saturating_sub(age.saturating_mul(1000))),
+                until_ms: until.map(|age| now.saturating_sub(age.saturating_mul(1000))),
+                severity: severity.map(Severity::from),
+                limit,
+                offset,
+                ..Default::default()
+            };
+            for (key, value) in fields {
+                match key.as_str() {
+                    "host" | "hostname" => request.hostname = Some(value),
+                    "process" => request.process = Some(value),
+                    _ => {
+                        request.fields.insert(key, value);
+                    }
+                }
+            }
+            let result: LogSearchResult = client.post_json(LOGS_SEARCH_PATH, &request).await?;
+            writer.render(&result)
+        }
+    }
+}
+
+/// Body of `POST /logs/search`.
+#[derive(Debug, Default, Serialize)]
+struct SearchRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hostname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    process: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    fields: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    severity: Option<Severity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    until_ms: Option<u64>,
+    limit: u64,
+    offset: u64,
+}
+
+/// A log entry held by the daemon's log store.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StoredLog {
+    /// Log timestamp (Unix milliseconds)
+    pub timestamp_ms: u64,
+    /// Collector source
+    pub source: String,
+    /// Hostname
+    pub hostname: String,
+    /// Process name
+    pub process: String,
+    /// Severity
+    pub severity: Severity,
+    /// Log message
+    pub message: String,
+    /// Extra fields
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Result of `log search`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogSearchResult {
+    /// Matching logs, newest first
+    pub logs: Vec<StoredLog>,
+    /// Matches skipped before this page
+    pub offset: u64,
+    /// Page size
+    pub limit: u64,
+    /// Whether another page follows
+    pub has_more: bool,
+}
+
+impl Render for LogSearchResult {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        if self.logs.is_empty() {
+            return writeln!(w, "{}", "No matching logs.".dimmed());
+        }
+        for log in &self.logs {
+            let label = format!("{:<8}", log.severity.to_string());
+            let severity = match log.severity {
+                Severity::Critical => label.red().bold(),
+                Severity::High => label.red(),
+                Severity::Medium => label.yellow(),
+                Severity::Low => label.normal(),
+                Severity::Info => label.dimmed(),
+            };
+            writeln!(
+                w,
+                "{}  {} {} {}: {}",
+                format_datetime(log.timestamp_ms),
+                severity,
+                log.hostname.cyan(),
+                log.process,
+                log.message
+            )?;
+        }
+        if self.has_more {
+            let next = self.offset + self.logs.len() as u64;
+            writeln!(
+                w,
+                "{}",
+                format!("More logs match (next page: --offset {}).", next).dimmed()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_request_omits_unset_filters() {
+        let request = SearchRequest {
+            hostname: Some("web-01".to_owned()),
+            limit: 50,
+            ..Default::default()
+        };
+        let body = serde_json::to_string(&request).expect("serialize");
+        assert_eq!(body, r#"{"hostname":"web-01","limit":50,"offset":0}"#);
+    }
+
+    #[test]
+    fn test_search_result_renders_logs_and_next_page() {
+        let result: LogSearchResult = serde_json::from_str(
+            r#"{"logs":[{"timestamp_ms":86461000,"source":"/var/log/auth.log","hostname":"web-01","process":"sshd","severity":"High","message":"Failed password for root","fields":{"user":"root"}}],"offset":20,"limit":1,"has_more":true}"#,
+        )
+        .expect("should deserialize");
+
+        let mut buffer = Vec::new();
+        result
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("1970-01-02 00:01:01 UTC"));
+        assert!(output.contains("sshd: Failed password for root"));
+        assert!(output.contains("--offset 21"));
+    }
+}
@@ -1,7 +1,23 @@
 //! Command handlers -- one module per subcommand
 
+pub mod alerts;
+pub mod bench;
 pub mod config;
+pub mod config_diff;
+pub mod config_init;
+pub mod container;
+pub mod content;
+pub mod ebpf;
+pub mod log;
+pub mod module;
+pub mod replay;
+pub mod report;
 pub mod rules;
+pub mod rules_new;
+pub mod sbom;
+pub mod sbom_db;
+pub mod sbom_db_sync;
 pub mod scan;
 pub mod start;
 pub mod status;
+pub mod top;
@@ -0,0 +1,163 @@
+//! `ironpost module` command handler
+//!
+//! `quarantined` lists the modules the daemon's crash-loop supervisor took
+//! out of service (`/modules/quarantined`). `unquarantine` lifts quarantine
+//! for a module through `/modules/unquarantine`; the daemon starts it again
+//! right away and quarantines it anew if it keeps failing.
+
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use ironpost_core::config::IronpostConfig;
+
+use crate::cli::{ModuleAction, ModuleArgs};
+use crate::client::DaemonClient;
+use crate::commands::container::format_time;
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Daemon control endpoint listing quarantined modules.
+const MODULES_QUARANTINED_PATH: &str = "/modules/quarantined";
+
+/// Daemon control endpoint lifting quarantine for a module.
+const MODULES_UNQUARANTINE_PATH: &str = "/modules/unquarantine";
+
+/// Request timeout of `unquarantine`, which waits for the module to start.
+const UNQUARANTINE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Execute the `module` command.
+pub async fn execute(
+    args: ModuleArgs,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    let client = DaemonClient::from_config(&config)?;
+
+    match args.action {
+        ModuleAction::Quarantined => {
+            let list: QuarantineList = client.get_json(MODULES_QUARANTINED_PATH).await?;
+            writer.render(&list)
+        }
+        ModuleAction::Unquarantine { name } => {
+            let report: UnquarantineReport = client
+                .with_timeout(UNQUARANTINE_TIMEOUT)
+                .post_json(
+                    MODULES_UNQUARANTINE_PATH,
+                    &UnquarantineRequest { module: name },
+                )
+                .await?;
+            writer.render(&report)
+        }
+    }
+}
+
+/// Body of `POST /modules/unquarantine`.
+#[derive(Debug, Serialize)]
+struct UnquarantineRequest {
+    module: String,
+}
+
+/// A module taken out of service by the daemon.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuarantinedModule {
+    /// Module name
+    pub name: String,
+    /// Last failure before quarantine
+    pub reason: String,
+    /// Failures within the crash-loop window
+    pub failures: u32,
+    /// When the module was quarantined (Unix milliseconds)
+    pub since_ms: u64,
+}
+
+/// Result of `module quarantined`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuarantineList {
+    /// Quarantined modules, by name
+    pub modules: Vec<QuarantinedModule>,
+}
+
+/// Result of `module unquarantine`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnquarantineReport {
+    /// Module that was started again
+    pub module: String,
+}
+
+impl Render for QuarantineList {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        if self.modules.is_empty() {
+            return writeln!(w, "No modules are quarantined.");
+        }
+        writeln!(
+            w,
+            "{:<20} {:>8} {:>10}  Reason",
+            "Module", "Failures", "Since"
+        )?;
+        for module in &self.modules {
+            writeln!(
+                w,
+                "{:<20} {:>8} {:>10}  {}",
+                module.name.red().bold(),
+                module.failures,
+                format_time(module.since_ms),
+                module.reason
+            )?;
+        }
+        writeln!(w, "\nRestart with: ironpost module unquarantine <MODULE>")
+    }
+}
+
+impl Render for UnquarantineReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(
+            w,
+            "{} {}: quarantine lifted, module restarted",
+            "Restarted".green().bold(),
+            self.module.bold()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quarantine_list_renders_modules() {
+        let list: QuarantineList = serde_json::from_str(
+            r#"{"modules":[{"name":"log-pipeline","reason":"bind failed","failures":3,"since_ms":3600000}]}"#,
+        )
+        .expect("should deserialize");
+
+        let mut buffer = Vec::new();
+        list.render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("log-pipeline"));
+        assert!(output.contains("01:00:00"));
+        assert!(output.contains("bind failed"));
+        assert!(output.contains("ironpost module unquarantine"));
+    }
+
+    #[test]
+    fn test_empty_quarantine_list_renders_notice() {
+        let list = QuarantineList {
+            modules: Vec::new(),
+        };
+
+        let mut buffer = Vec::new();
+        list.render_text(&mut buffer)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert_eq!(output, "No modules are quarantined.\n");
+    }
+}
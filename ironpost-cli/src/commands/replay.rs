@@ -0,0 +1,932 @@
+//! `ironpost replay` command handler
+//!
+//! Replays a recorded incident through the detection path so rules and
+//! policies can be checked against real traffic. A pcap capture is decoded
+//! into packet events for the SYN flood, port scan and ICMP flood detectors; a log file
+//! is fed line by line through the `ParserRouter` and the `RuleEngine`.
+//!
+//! Events are paced by their recorded timestamps, scaled by `--speed`, or
+//! sent back to back with `--fast`. The daemon has no packet ingest, so pcap
+//! captures always replay in-process. Log files can instead be written to the
+//! running daemon's syslog TCP listener (`--target daemon`), where they go
+//! through the daemon's own rules and show up in `ironpost alerts`.
+
+use std::io::Write;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tracing::{debug, info, warn};
+
+use ironpost_core::config::IronpostConfig;
+use ironpost_core::types::Severity;
+use ironpost_log_pipeline::{ParserRouter, RuleEngine};
+
+use crate::cli::{ReplayArgs, ReplayKind, ReplayTarget};
+use crate::commands::bench::load_engine;
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// pcap magic number, microsecond timestamps.
+const PCAP_MAGIC_MICROS: u32 = 0xa1b2_c3d4;
+/// pcap magic number, nanosecond timestamps.
+const PCAP_MAGIC_NANOS: u32 = 0xa1b2_3c4d;
+/// pcapng section header block type (not supported).
+const PCAPNG_MAGIC: u32 = 0x0a0d_0d0a;
+
+const PCAP_HEADER_LEN: usize = 24;
+const PCAP_RECORD_HEADER_LEN: usize = 16;
+
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+/// `DLT_RAW` as written by some BSD/macOS captures.
+const DLT_RAW: u32 = 12;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+
+const PROTO_TCP: u8 = 6;
+const PROTO_UDP: u8 = 17;
+
+/// Alerts listed in text output; the rest are counted.
+const MAX_TEXT_ALERTS: usize = 50;
+
+/// Execute the `replay` command.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the file cannot be read or decoded, a pcap
+/// capture is sent to the daemon or replayed off Linux, `CliError::Rule` if
+/// the rules cannot be loaded, and `CliError::DaemonUnavailable` if the
+/// daemon's syslog listener cannot be reached.
+pub async fn execute(
+    args: ReplayArgs,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    let data = tokio::fs::read(&args.file)
+        .await
+        .map_err(|e| CliError::Command(format!("failed to read {}: {}", args.file.display(), e)))?;
+
+    let kind = match args.kind {
+        ReplayKind::Auto => detect_kind(&data),
+        kind => kind,
+    };
+    let speed = (!args.fast).then_some(args.speed);
+    info!(
+        file = %args.file.display(),
+        kind = ?kind,
+        target = ?args.target,
+        speed = ?speed,
+        "replaying recorded events"
+    );
+
+    let mut report = match (kind, args.target) {
+        (ReplayKind::Pcap, ReplayTarget::Daemon) => {
+            return Err(CliError::Command(
+                "the daemon does not accept packet events; replay pcap captures with --target local"
+                    .to_owned(),
+            ));
+        }
+        (ReplayKind::Pcap, ReplayTarget::Local) => {
+            let capture = parse_pcap(&data)?;
+            replay_packets(&capture, speed).await?
+        }
+        (_, ReplayTarget::Local) => {
+            let (engine, rule_dir) = load_engine(&config, args.rules.as_deref()).await?;
+            let lines = read_lines(&configured_parser(&config)?, &data);
+            let mut report = replay_logs_local(&engine, &lines, speed).await?;
+            report.rules = Some(engine.rule_count());
+            report.rule_dir = Some(rule_dir);
+            report
+        }
+        (_, ReplayTarget::Daemon) => {
+            let addr = daemon_syslog_addr(&config, args.syslog_addr.as_deref())?;
+            let lines = read_lines(&configured_parser(&config)?, &data);
+            replay_logs_daemon(&addr, &lines, speed).await?
+        }
+    };
+    report.file = args.file;
+    writer.render(&report)?;
+    Ok(())
+}
+
+/// pcap if `data` starts with a pcap or pcapng magic number, log otherwise.
+fn detect_kind(data: &[u8]) -> ReplayKind {
+    let magic = data
+        .get(..4)
+        .and_then(|b| <[u8; 4]>::try_from(b).ok())
+        .map(u32::from_le_bytes);
+    match magic {
+        Some(m)
+            if [PCAP_MAGIC_MICROS, PCAP_MAGIC_NANOS, PCAPNG_MAGIC].contains(&m)
+                || [PCAP_MAGIC_MICROS, PCAP_MAGIC_NANOS].contains(&m.swap_bytes()) =>
+        {
+            ReplayKind::Pcap
+        }
+        _ => ReplayKind::Log,
+    }
+}
+
+// ---- pcap ----
+
+/// Packets decoded from a pcap capture.
+#[derive(Debug, Default)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+struct PcapCapture {
+    packets: Vec<ReplayPacket>,
+    /// Records that were not IPv4 (IPv6, ARP, ...) or were too short to decode
+    skipped: usize,
+}
+
+/// An IPv4 packet decoded from a pcap record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+struct ReplayPacket {
+    /// Capture time relative to the first record
+    offset: Duration,
+    src: Ipv4Addr,
+    dst: Ipv4Addr,
+    src_port: u16,
+    dst_port: u16,
+    /// Original length on the wire
+    len: u32,
+    protocol: u8,
+    tcp_flags: u8,
+}
+
+/// Decode a classic libpcap capture.
+///
+/// Both byte orders and both timestamp resolutions are accepted. A truncated
+/// last record ends the capture with a warning.
+fn parse_pcap(data: &[u8]) -> Result<PcapCapture, CliError> {
+    let magic = read_u32(data, 0, false)
+        .ok_or_else(|| CliError::Command("file is too short to be a pcap capture".to_owned()))?;
+    let (big_endian, nanos) = match magic {
+        PCAP_MAGIC_MICROS => (false, false),
+        PCAP_MAGIC_NANOS => (false, true),
+        m if m.swap_bytes() == PCAP_MAGIC_MICROS => (true, false),
+        m if m.swap_bytes() == PCAP_MAGIC_NANOS => (true, true),
+        PCAPNG_MAGIC => {
+            return Err(CliError::Command(
+                "pcapng captures are not supported; convert with `editcap -F pcap`".to_owned(),
+            ));
+        }
+        _ => return Err(CliError::Command("not a pcap capture".to_owned())),
+    };
+    let link_type = read_u32(data, 20, big_endian)
+        .ok_or_else(|| CliError::Command("truncated pcap header".to_owned()))?;
+    if ![
+        LINKTYPE_ETHERNET,
+        LINKTYPE_RAW,
+        LINKTYPE_LINUX_SLL,
+        LINKTYPE_IPV4,
+        DLT_RAW,
+    ]
+    .contains(&link_type)
+    {
+        return Err(CliError::Command(format!(
+            "unsupported pcap link type {} (use Ethernet, Linux cooked or raw IP captures)",
+            link_type
+        )));
+    }
+
+    let mut capture = PcapCapture::default();
+    let mut first: Option<Duration> = None;
+    let mut pos = PCAP_HEADER_LEN;
+    while pos < data.len() {
+        let header = (
+            read_u32(data, pos, big_endian),
+            read_u32(data, pos + 4, big_endian),
+            read_u32(data, pos + 8, big_endian),
+            read_u32(data, pos + 12, big_endian),
+        );
+        let (Some(secs), Some(frac), Some(incl_len), Some(orig_len)) = header else {
+            warn!(offset = pos, "truncated pcap record header, stopping");
+            break;
+        };
+        let start = pos + PCAP_RECORD_HEADER_LEN;
+        let Some(frame) = usize::try_from(incl_len)
+            .ok()
+            .and_then(|len| data.get(start..start.checked_add(len)?))
+        else {
+            warn!(offset = pos, "truncated pcap record, stopping");
+            break;
+        };
+        pos = start + frame.len();
+
+        let frac_nanos = if nanos {
+            u64::from(frac)
+        } else {
+            u64::from(frac) * 1_000
+        };
+        let timestamp = Duration::from_secs(u64::from(secs)) + Duration::from_nanos(frac_nanos);
+        let offset = timestamp.saturating_sub(*first.get_or_insert(timestamp));
+
+        match decode_frame(link_type, frame) {
+            Some(mut packet) => {
+                packet.offset = offset;
+                packet.len = orig_len;
+                capture.packets.push(packet);
+            }
+            None => capture.skipped += 1,
+        }
+    }
+    debug!(
+        packets = capture.packets.len(),
+        skipped = capture.skipped,
+        "decoded pcap capture"
+    );
+    Ok(capture)
+}
+
+/// Strip the link-layer header and decode the IPv4 packet inside `frame`.
+fn decode_frame(link_type: u32, frame: &[u8]) -> Option<ReplayPacket> {
+    let ip = match link_type {
+        LINKTYPE_ETHERNET => {
+            let mut ethertype = read_u16(frame, 12)?;
+            let mut offset = 14;
+            if ethertype == ETHERTYPE_VLAN {
+                ethertype = read_u16(frame, 16)?;
+                offset = 18;
+            }
+            if ethertype != ETHERTYPE_IPV4 {
+                return None;
+            }
+            frame.get(offset..)?
+        }
+        LINKTYPE_LINUX_SLL => {
+            if read_u16(frame, 14)? != ETHERTYPE_IPV4 {
+                return None;
+            }
+            frame.get(16..)?
+        }
+        _ => frame,
+    };
+    decode_ipv4(ip)
+}
+
+/// Decode the IPv4 header and, for TCP and UDP, the ports and TCP flags.
+fn decode_ipv4(ip: &[u8]) -> Option<ReplayPacket> {
+    let version_ihl = *ip.first()?;
+    if version_ihl >> 4 != 4 {
+        return None;
+    }
+    let header_len = usize::from(version_ihl & 0x0f) * 4;
+    let protocol = *ip.get(9)?;
+    let src = Ipv4Addr::from(<[u8; 4]>::try_from(ip.get(12..16)?).ok()?);
+    let dst = Ipv4Addr::from(<[u8; 4]>::try_from(ip.get(16..20)?).ok()?);
+    // Only the first fragment carries the transport header
+    let first_fragment = read_u16(ip, 6)? & 0x1fff == 0;
+    let transport = ip.get(header_len..).filter(|_| first_fragment);
+
+    let (src_port, dst_port, tcp_flags) = match (protocol, transport) {
+        (PROTO_TCP, Some(tcp)) => (read_u16(tcp, 0)?, read_u16(tcp, 2)?, *tcp.get(13)?),
+        (PROTO_UDP, Some(udp)) => (read_u16(udp, 0)?, read_u16(udp, 2)?, 0),
+        _ => (0, 0, 0),
+    };
+    Some(ReplayPacket {
+        offset: Duration::ZERO,
+        src,
+        dst,
+        src_port,
+        dst_port,
+        len: u32::from(read_u16(ip, 2)?),
+        protocol,
+        tcp_flags,
+    })
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
+    Some(if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    })
+}
+
+/// Run the decoded packets through the SYN flood, port scan and ICMP flood detectors.
+#[cfg(target_os = "linux")]
+async fn replay_packets(
+    capture: &PcapCapture,
+    speed: Option<f64>,
+) -> Result<ReplayReport, CliError> {
+    use ironpost_ebpf_engine::ironpost_ebpf_common::PacketEventData;
+    use ironpost_ebpf_engine::{
+        IcmpFloodConfig, IcmpFloodDetector, PortScanConfig, PortScanDetector, SynFloodConfig,
+        SynFloodDetector,
+    };
+
+    let syn_flood = SynFloodDetector::new(SynFloodConfig::default());
+    let port_scan = PortScanDetector::new(PortScanConfig::default());
+    let icmp_flood = IcmpFloodDetector::new(IcmpFloodConfig::default());
+
+    let mut report = ReplayReport::new(ReplayKind::Pcap, ReplayTarget::Local, speed);
+    report.skipped = capture.skipped;
+    let started = Instant::now();
+    for packet in &capture.packets {
+        pace(started, packet.offset, speed).await;
+
+        let mut event = PacketEventData::zeroed();
+        event.src_ip = u32::from(packet.src).to_be();
+        event.dst_ip = u32::from(packet.dst).to_be();
+        event.src_port = packet.src_port.to_be();
+        event.dst_port = packet.dst_port.to_be();
+        event.pkt_len = packet.len;
+        event.protocol = packet.protocol;
+        event.tcp_flags = packet.tcp_flags;
+
+        for detected in [
+            syn_flood.detect_packet(&event),
+            port_scan.detect_packet(&event),
+            icmp_flood.detect_packet(&event),
+        ] {
+            if let Some(alert) = detected.map_err(|e| CliError::Command(e.to_string()))? {
+                report.alerts.push(ReplayAlert {
+                    offset_ms: millis(packet.offset),
+                    rule: alert.rule_name,
+                    title: alert.title,
+                    severity: alert.severity,
+                    source: alert.source_ip.map(|ip| ip.to_string()),
+                });
+            }
+        }
+        report.events += 1;
+        report.recorded_ms = millis(packet.offset);
+    }
+    report.elapsed_ms = millis(started.elapsed());
+    Ok(report)
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn replay_packets(
+    _capture: &PcapCapture,
+    _speed: Option<f64>,
+) -> Result<ReplayReport, CliError> {
+    Err(CliError::Command(
+        "packet detectors are only available on Linux".to_owned(),
+    ))
+}
+
+// ---- logs ----
+
+/// A log line with its parsed entry, if the parser accepted it.
+struct ReplayLine<'a> {
+    raw: &'a [u8],
+    entry: Option<ironpost_core::types::LogEntry>,
+    /// Recorded time relative to the first parsed line
+    offset: Duration,
+}
+
+/// Build the parser router from `[log_pipeline.parsers]` so custom patterns
+/// and opt-in parsers recognise the same lines the daemon would.
+fn configured_parser(config: &IronpostConfig) -> Result<ParserRouter, CliError> {
+    ParserRouter::from_config(&config.log_pipeline.parsers)
+        .map_err(|e| CliError::Config(e.to_string()))
+}
+
+/// Split `data` into non-empty lines and parse each one for its timestamp.
+///
+/// Lines the parser rejects keep the offset of the line before them.
+fn read_lines<'a>(parser: &ParserRouter, data: &'a [u8]) -> Vec<ReplayLine<'a>> {
+    let mut first = None;
+    let mut offset = Duration::ZERO;
+    data.split(|&b| b == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+        .map(|raw| {
+            let entry = match parser.parse(raw) {
+                Ok(entry) => {
+                    let at = entry
+                        .timestamp
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default();
+                    offset = at.saturating_sub(*first.get_or_insert(at));
+                    Some(entry)
+                }
+                Err(e) => {
+                    debug!(error = %e, "replayed line failed to parse");
+                    None
+                }
+            };
+            ReplayLine { raw, entry, offset }
+        })
+        .collect()
+}
+
+/// Evaluate every parsed line against `engine`.
+async fn replay_logs_local(
+    engine: &RuleEngine,
+    lines: &[ReplayLine<'_>],
+    speed: Option<f64>,
+) -> Result<ReplayReport, CliError> {
+    let mut report = ReplayReport::new(ReplayKind::Log, ReplayTarget::Local, speed);
+    let started = Instant::now();
+    for line in lines {
+        pace(started, line.offset, speed).await;
+        report.events += 1;
+        report.recorded_ms = millis(line.offset);
+
+        let Some(entry) = &line.entry else {
+            report.skipped += 1;
+            continue;
+        };
+        let matches = engine
+            .evaluate(entry)
+            .map_err(|e| CliError::Rule(e.to_string()))?;
+        report
+            .alerts
+            .extend(matches.into_iter().map(|m| ReplayAlert {
+                offset_ms: millis(line.offset),
+                rule: m.rule.id,
+                title: m.rule.title,
+                severity: m.rule.severity,
+                source: Some(m.entry.hostname).filter(|h| !h.is_empty()),
+            }));
+    }
+    report.elapsed_ms = millis(started.elapsed());
+    Ok(report)
+}
+
+/// Write every line, newline-framed, to the daemon's syslog TCP listener.
+async fn replay_logs_daemon(
+    addr: &str,
+    lines: &[ReplayLine<'_>],
+    speed: Option<f64>,
+) -> Result<ReplayReport, CliError> {
+    let mut stream = TcpStream::connect(addr).await.map_err(|e| {
+        CliError::DaemonUnavailable(format!("cannot connect to syslog listener {}: {}", addr, e))
+    })?;
+
+    let mut report = ReplayReport::new(ReplayKind::Log, ReplayTarget::Daemon, speed);
+    report.sent_to = Some(addr.to_owned());
+    let started = Instant::now();
+    for line in lines {
+        pace(started, line.offset, speed).await;
+        stream.write_all(line.raw).await?;
+        stream.write_all(b"\n").await?;
+        report.events += 1;
+        report.recorded_ms = millis(line.offset);
+        if line.entry.is_none() {
+            report.skipped += 1;
+        }
+    }
+    stream.shutdown().await?;
+    report.elapsed_ms = millis(started.elapsed());
+    Ok(report)
+}
+
+/// `--syslog-addr`, or `log_pipeline.syslog_tcp_bind` with a wildcard bind
+/// address replaced by loopback.
+fn daemon_syslog_addr(config: &IronpostConfig, addr: Option<&str>) -> Result<String, CliError> {
+    if let Some(addr) = addr {
+        return Ok(addr.to_owned());
+    }
+    let pipeline = &config.log_pipeline;
+    if !pipeline.sources.iter().any(|s| s == "syslog") {
+        return Err(CliError::Config(
+            "log_pipeline.sources does not include syslog; pass --syslog-addr".to_owned(),
+        ));
+    }
+    let bind: SocketAddr = pipeline.syslog_tcp_bind.parse().map_err(|e| {
+        CliError::Config(format!(
+            "invalid log_pipeline.syslog_tcp_bind '{}': {}",
+            pipeline.syslog_tcp_bind, e
+        ))
+    })?;
+    let ip = match bind.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ip => ip,
+    };
+    Ok(SocketAddr::new(ip, bind.port()).to_string())
+}
+
+/// Wait until `offset`, scaled by `speed`, has passed since `started`.
+///
+/// `None` replays as fast as possible.
+async fn pace(started: Instant, offset: Duration, speed: Option<f64>) {
+    let Some(speed) = speed else {
+        return;
+    };
+    let delay = Duration::try_from_secs_f64(offset.as_secs_f64() / speed).unwrap_or_default();
+    if let Some(deadline) = started.checked_add(delay) {
+        tokio::time::sleep_until(deadline.into()).await;
+    }
+}
+
+fn millis(elapsed: Duration) -> u64 {
+    u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Replay results.
+#[derive(Debug, Serialize)]
+pub struct ReplayReport {
+    /// Replayed file
+    pub file: PathBuf,
+    /// Input format
+    pub kind: ReplayKind,
+    /// Where events were sent
+    pub target: ReplayTarget,
+    /// Playback speed (`null` with `--fast`)
+    pub speed: Option<f64>,
+    /// Packets or lines replayed
+    pub events: usize,
+    /// Records that were not IPv4, or lines the local parser rejected
+    pub skipped: usize,
+    /// Time span covered by the recorded timestamps
+    pub recorded_ms: u64,
+    /// Wall time of the replay
+    pub elapsed_ms: u64,
+    /// Directory the rules were loaded from (local log replay)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_dir: Option<PathBuf>,
+    /// Detection rules loaded (local log replay)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<usize>,
+    /// Daemon syslog address (daemon target)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_to: Option<String>,
+    /// Alerts raised in-process (empty for the daemon target)
+    pub alerts: Vec<ReplayAlert>,
+}
+
+impl ReplayReport {
+    fn new(kind: ReplayKind, target: ReplayTarget, speed: Option<f64>) -> Self {
+        Self {
+            file: PathBuf::new(),
+            kind,
+            target,
+            speed,
+            events: 0,
+            skipped: 0,
+            recorded_ms: 0,
+            elapsed_ms: 0,
+            rule_dir: None,
+            rules: None,
+            sent_to: None,
+            alerts: Vec::new(),
+        }
+    }
+}
+
+/// An alert raised while replaying.
+#[derive(Debug, Serialize)]
+pub struct ReplayAlert {
+    /// Recorded time of the triggering event, relative to the first event
+    pub offset_ms: u64,
+    /// Rule or detector name
+    pub rule: String,
+    pub title: String,
+    pub severity: Severity,
+    /// Source IP (packets) or hostname (log lines)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl Render for ReplayReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(
+            w,
+            "{} {}",
+            "Replayed".bold(),
+            self.file.display().to_string().cyan()
+        )?;
+        let speed = match self.speed {
+            Some(speed) => format!("{}x", speed),
+            None => "fast".to_owned(),
+        };
+        writeln!(
+            w,
+            "  Kind: {:?}   Target: {:?}   Speed: {}",
+            self.kind, self.target, speed
+        )?;
+        if let (Some(rules), Some(rule_dir)) = (self.rules, &self.rule_dir) {
+            writeln!(w, "  Rules: {} from {}", rules, rule_dir.display())?;
+        }
+        writeln!(
+            w,
+            "  Events: {} ({} skipped), recorded over {} ms, replayed in {} ms",
+            self.events, self.skipped, self.recorded_ms, self.elapsed_ms
+        )?;
+
+        if let Some(addr) = &self.sent_to {
+            writeln!(w, "  Sent to syslog listener {}", addr)?;
+            writeln!(
+                w,
+                "  {}",
+                "Alerts are raised by the daemon; follow them with `ironpost alerts tail`."
+                    .dimmed()
+            )?;
+            return Ok(());
+        }
+
+        writeln!(w)?;
+        if self.alerts.is_empty() {
+            writeln!(w, "{}", "No alerts raised".green())?;
+            return Ok(());
+        }
+        writeln!(w, "{} ({})", "Alerts".bold(), self.alerts.len())?;
+        for alert in self.alerts.iter().take(MAX_TEXT_ALERTS) {
+            let label = format!("{:<8}", alert.severity.to_string());
+            let severity = match alert.severity {
+                Severity::Critical => label.red().bold(),
+                Severity::High => label.red(),
+                Severity::Medium => label.yellow(),
+                Severity::Low => label.normal(),
+                Severity::Info => label.dimmed(),
+            };
+            writeln!(
+                w,
+                "  +{:>8} ms  {} {:<24} {}{}",
+                alert.offset_ms,
+                severity,
+                alert.rule,
+                alert.title,
+                alert
+                    .source
+                    .as_deref()
+                    .map(|s| format!(" ({})", s))
+                    .unwrap_or_default()
+            )?;
+        }
+        if self.alerts.len() > MAX_TEXT_ALERTS {
+            writeln!(
+                w,
+                "  ... and {} more (use --output json for all)",
+                self.alerts.len() - MAX_TEXT_ALERTS
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_log_pipeline::rule::RuleLoader;
+
+    /// Little-endian microsecond pcap with one record per `(secs, micros, frame)`.
+    fn pcap(link_type: u32, records: &[(u32, u32, Vec<u8>)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&PCAP_MAGIC_MICROS.to_le_bytes());
+        data.extend_from_slice(&2_u16.to_le_bytes());
+        data.extend_from_slice(&4_u16.to_le_bytes());
+        data.extend_from_slice(&[0; 8]);
+        data.extend_from_slice(&65_535_u32.to_le_bytes());
+        data.extend_from_slice(&link_type.to_le_bytes());
+        for (secs, micros, frame) in records {
+            let len = u32::try_from(frame.len()).expect("small frame");
+            data.extend_from_slice(&secs.to_le_bytes());
+            data.extend_from_slice(&micros.to_le_bytes());
+            data.extend_from_slice(&len.to_le_bytes());
+            data.extend_from_slice(&len.to_le_bytes());
+            data.extend_from_slice(frame);
+        }
+        data
+    }
+
+    /// IPv4 + TCP header from 198.51.100.7 to 10.0.0.5.
+    fn tcp_packet(src_port: u16, dst_port: u16, flags: u8) -> Vec<u8> {
+        let mut ip = vec![0x45, 0, 0, 40, 0, 0, 0x40, 0, 64, PROTO_TCP, 0, 0];
+        ip.extend_from_slice(&[198, 51, 100, 7, 10, 0, 0, 5]);
+        ip.extend_from_slice(&src_port.to_be_bytes());
+        ip.extend_from_slice(&dst_port.to_be_bytes());
+        ip.extend_from_slice(&[0; 9]);
+        ip.push(flags);
+        ip.extend_from_slice(&[0; 6]);
+        ip
+    }
+
+    fn ethernet(ethertype: u16, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0; 12];
+        frame.extend_from_slice(&ethertype.to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    #[test]
+    fn test_detect_kind() {
+        let capture = pcap(LINKTYPE_ETHERNET, &[]);
+        assert_eq!(detect_kind(&capture), ReplayKind::Pcap);
+        assert_eq!(
+            detect_kind(&PCAP_MAGIC_MICROS.to_be_bytes()),
+            ReplayKind::Pcap
+        );
+        assert_eq!(
+            detect_kind(b"<34>Oct 11 22:14:15 host sshd: hi"),
+            ReplayKind::Log
+        );
+        assert_eq!(detect_kind(b""), ReplayKind::Log);
+    }
+
+    #[test]
+    fn test_parse_pcap_ethernet_tcp() {
+        let data = pcap(
+            LINKTYPE_ETHERNET,
+            &[
+                (
+                    100,
+                    0,
+                    ethernet(ETHERTYPE_IPV4, &tcp_packet(40_000, 22, 0x02)),
+                ),
+                (100, 250_000, ethernet(0x86dd, &[0x60; 40])),
+                (
+                    101,
+                    500_000,
+                    ethernet(ETHERTYPE_IPV4, &tcp_packet(40_001, 443, 0x12)),
+                ),
+            ],
+        );
+
+        let capture = parse_pcap(&data).expect("valid capture");
+
+        assert_eq!(capture.packets.len(), 2);
+        assert_eq!(capture.skipped, 1, "IPv6 frame is skipped");
+        let syn = &capture.packets[0];
+        assert_eq!(syn.src, Ipv4Addr::new(198, 51, 100, 7));
+        assert_eq!(syn.dst, Ipv4Addr::new(10, 0, 0, 5));
+        assert_eq!((syn.src_port, syn.dst_port), (40_000, 22));
+        assert_eq!(syn.protocol, PROTO_TCP);
+        assert_eq!(syn.tcp_flags, 0x02);
+        assert_eq!(syn.offset, Duration::ZERO);
+        assert_eq!(capture.packets[1].offset, Duration::from_millis(1_500));
+    }
+
+    #[test]
+    fn test_parse_pcap_raw_ip_and_truncated_record() {
+        let mut data = pcap(LINKTYPE_RAW, &[(5, 0, tcp_packet(1234, 80, 0x10))]);
+        data.extend_from_slice(&[0; 10]);
+
+        let capture = parse_pcap(&data).expect("valid capture");
+        assert_eq!(capture.packets.len(), 1);
+        assert_eq!(capture.packets[0].dst_port, 80);
+    }
+
+    #[test]
+    fn test_parse_pcap_rejects_unsupported_input() {
+        assert!(parse_pcap(b"not a capture at all").is_err());
+        assert!(parse_pcap(&PCAPNG_MAGIC.to_le_bytes()).is_err());
+        assert!(
+            parse_pcap(&pcap(105, &[])).is_err(),
+            "802.11 is unsupported"
+        );
+    }
+
+    #[test]
+    fn test_read_lines_offsets_from_timestamps() {
+        let data = b"<38>1 2026-10-16T12:00:00Z web-01 sshd 1 - - first\r\n\
+                     not a log line\n\
+                     \n\
+                     <38>1 2026-10-16T12:00:30Z web-01 sshd 1 - - second\n";
+
+        let lines = read_lines(&ParserRouter::with_defaults(), data);
+
+        assert_eq!(lines.len(), 3, "blank lines are dropped");
+        assert!(lines[0].raw.ends_with(b"first"));
+        assert_eq!(lines[0].offset, Duration::ZERO);
+        assert!(lines[1].entry.is_none());
+        assert_eq!(lines[1].offset, Duration::ZERO);
+        assert_eq!(lines[2].offset, Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn test_replay_logs_local_raises_alerts() {
+        let rule = r#"
+id: failed_password
+title: Failed password
+severity: High
+detection:
+  conditions:
+    - field: message
+      modifier: contains
+      value: "Failed password"
+"#;
+        let mut engine = RuleEngine::new();
+        engine
+            .add_rule(RuleLoader::parse_yaml(rule, "test.yml").expect("valid rule"))
+            .expect("rule should load");
+        let data = b"<38>1 2026-10-16T12:00:00Z bastion sshd 1 - - Accepted publickey for alice\n\
+                     <38>1 2026-10-16T12:00:02Z bastion sshd 1 - - Failed password for root\n";
+        let lines = read_lines(&ParserRouter::with_defaults(), data);
+
+        let report = replay_logs_local(&engine, &lines, None)
+            .await
+            .expect("replay should run");
+
+        assert_eq!(report.events, 2);
+        assert_eq!(report.skipped, 0);
+        assert_eq!(report.recorded_ms, 2_000);
+        assert_eq!(report.alerts.len(), 1);
+        assert_eq!(report.alerts[0].rule, "failed_password");
+        assert_eq!(report.alerts[0].offset_ms, 2_000);
+        assert_eq!(report.alerts[0].source.as_deref(), Some("bastion"));
+    }
+
+    #[tokio::test]
+    async fn test_replay_logs_daemon_writes_lines() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().expect("addr").to_string();
+        let server = tokio::spawn(async move {
+            use tokio::io::AsyncReadExt;
+            let (mut socket, _) = listener.accept().await.expect("accept");
+            let mut received = String::new();
+            socket.read_to_string(&mut received).await.expect("read");
+            received
+        });
+        let data =
+            b"<38>Oct 16 12:00:00 web-01 sshd[1]: one\n<38>Oct 16 12:00:01 web-01 sshd[1]: two";
+        let lines = read_lines(&ParserRouter::with_defaults(), data);
+
+        let report = replay_logs_daemon(&addr, &lines, None)
+            .await
+            .expect("replay should run");
+
+        assert_eq!(report.events, 2);
+        assert_eq!(report.sent_to.as_deref(), Some(addr.as_str()));
+        let received = server.await.expect("server task");
+        assert_eq!(
+            received,
+            "<38>Oct 16 12:00:00 web-01 sshd[1]: one\n<38>Oct 16 12:00:01 web-01 sshd[1]: two\n"
+        );
+    }
+
+    #[test]
+    fn test_daemon_syslog_addr() {
+        let mut config = IronpostConfig::default();
+        config.log_pipeline.sources = vec!["syslog".to_owned()];
+        config.log_pipeline.syslog_tcp_bind = "0.0.0.0:601".to_owned();
+        assert_eq!(
+            daemon_syslog_addr(&config, None).expect("addr"),
+            "127.0.0.1:601"
+        );
+        assert_eq!(
+            daemon_syslog_addr(&config, Some("10.0.0.5:1601")).expect("addr"),
+            "10.0.0.5:1601"
+        );
+
+        config.log_pipeline.sources = vec!["file".to_owned()];
+        assert!(matches!(
+            daemon_syslog_addr(&config, None),
+            Err(CliError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pace_scales_offsets() {
+        let started = Instant::now();
+        pace(started, Duration::from_millis(400), Some(20.0)).await;
+        let waited = started.elapsed();
+        assert!(waited >= Duration::from_millis(20));
+        assert!(waited < Duration::from_millis(400));
+
+        let started = Instant::now();
+        pace(started, Duration::from_secs(3600), None).await;
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_report_render_text() {
+        let mut report = ReplayReport::new(ReplayKind::Log, ReplayTarget::Local, Some(10.0));
+        report.file = PathBuf::from("auth.log");
+        report.events = 2;
+        report.rules = Some(1);
+        report.rule_dir = Some(PathBuf::from("/etc/ironpost/rules"));
+        report.alerts.push(ReplayAlert {
+            offset_ms: 2_000,
+            rule: "failed_password".to_owned(),
+            title: "Failed password".to_owned(),
+            severity: Severity::High,
+            source: Some("bastion".to_owned()),
+        });
+
+        let mut buf = Vec::new();
+        report.render_text(&mut buf).expect("render");
+        let output = String::from_utf8(buf).expect("valid UTF-8");
+        assert!(output.contains("auth.log"));
+        assert!(output.contains("Speed: 10x"));
+        assert!(output.contains("failed_password"));
+        assert!(output.contains("(bastion)"));
+
+        let json = serde_json::to_value(&report).expect("serialize");
+        assert_eq!(json["kind"], "log");
+        assert_eq!(json["alerts"][0]["offset_ms"], 2_000);
+        assert!(json.get("sent_to").is_none());
+    }
+}
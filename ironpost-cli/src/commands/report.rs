@@ -0,0 +1,1037 @@
+//! `ironpost report` command handler
+//!
+//! Writes a shareable activity report (Markdown or a standalone HTML page)
+//! for the last `--period` from the running daemon's control endpoints:
+//!
+//! - daemon and module health, and the last SBOM scan task (`/status`)
+//! - alerts by severity and rule, and the most recent ones (`/alerts`)
+//! - container isolation actions that were executed (`/container/events`)
+//! - eBPF traffic counters (`/ebpf`)
+//! - vulnerability findings raised by the SBOM scanner (`/alerts`)
+//!
+//! The daemon keeps alerts and container activity in memory only, so a
+//! report covers at most what it has held since it last started (up to its
+//! buffer sizes). Traffic counters are totals since the XDP program attached
+//! and are not limited to the period.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use ironpost_container_guard::GuardActivity;
+use ironpost_core::config::IronpostConfig;
+use ironpost_core::pipeline::HealthStatus;
+use ironpost_core::types::Severity;
+
+use crate::cli::{ReportArgs, ReportFormat};
+use crate::client::DaemonClient;
+use crate::commands::alerts::{AlertEntry, fetch_alerts};
+use crate::commands::container::fetch_events;
+use crate::commands::ebpf::{EBPF_STATUS_PATH, EbpfStatus, format_bps};
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Daemon control endpoint serving module health and scheduled tasks.
+const STATUS_PATH: &str = "/status";
+
+/// Module name of alerts raised for vulnerability findings.
+const SBOM_MODULE: &str = "sbom-scanner";
+
+/// Scheduled task kind whose last run is reported with the findings.
+const SBOM_SCAN_TASK: &str = "sbom_scan";
+
+/// Most recent alerts listed in the report.
+const MAX_RECENT_ALERTS: usize = 50;
+
+/// Detection rules listed by alert count.
+const MAX_TOP_RULES: usize = 10;
+
+/// Severities from most to least severe, as listed in the report.
+const SEVERITIES: [Severity; 5] = [
+    Severity::Critical,
+    Severity::High,
+    Severity::Medium,
+    Severity::Low,
+    Severity::Info,
+];
+
+/// Execute the `report` command.
+///
+/// # Errors
+///
+/// Returns `CliError::DaemonUnavailable` if the daemon cannot be reached and
+/// `CliError::Io` if the report file cannot be written.
+pub async fn execute(
+    args: ReportArgs,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let config = IronpostConfig::load(config_path).await?;
+    let client = DaemonClient::from_config(&config)?;
+    info!(
+        addr = client.addr(),
+        period_secs = args.period,
+        "collecting report data"
+    );
+
+    let status: DaemonStatus = client.get_json(STATUS_PATH).await?;
+    let traffic = if config.ebpf.enabled {
+        Some(client.get_json::<EbpfStatus>(EBPF_STATUS_PATH).await?)
+    } else {
+        None
+    };
+    let alerts = fetch_alerts(&client, 0).await?.alerts;
+    let activity = if config.container.enabled {
+        fetch_events(&client, 0)
+            .await?
+            .events
+            .into_iter()
+            .map(|e| (e.timestamp_ms, e.activity))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let report = build_report(
+        client.addr(),
+        now_ms(),
+        args.period,
+        status,
+        traffic,
+        alerts,
+        activity,
+    );
+
+    let mut body = Vec::new();
+    match args.format {
+        ReportFormat::Md => write_markdown(&report, &mut body)?,
+        ReportFormat::Html => write_html(&report, &mut body)?,
+    }
+    let path = args
+        .out
+        .unwrap_or_else(|| PathBuf::from(default_output(args.format)));
+    tokio::fs::write(&path, &body).await?;
+    info!(path = %path.display(), "report written");
+
+    writer.render(&ReportSummary {
+        path: path.display().to_string(),
+        format: args.format,
+        period_secs: args.period,
+        alerts: report.alerts.total,
+        actions: report.actions.len(),
+        findings: report.findings.len(),
+    })
+}
+
+/// Default output file name for `format`.
+fn default_output(format: ReportFormat) -> &'static str {
+    match format {
+        ReportFormat::Md => "ironpost-report.md",
+        ReportFormat::Html => "ironpost-report.html",
+    }
+}
+
+/// Assemble the report from the daemon's responses.
+fn build_report(
+    daemon: &str,
+    generated_ms: u64,
+    period_secs: u64,
+    status: DaemonStatus,
+    traffic: Option<EbpfStatus>,
+    alerts: Vec<AlertEntry>,
+    activity: Vec<(u64, GuardActivity)>,
+) -> Report {
+    let from_ms = generated_ms.saturating_sub(period_secs.saturating_mul(1000));
+    let started_ms = generated_ms.saturating_sub(status.uptime_secs.saturating_mul(1000));
+
+    let (mut findings, alerts): (Vec<AlertEntry>, Vec<AlertEntry>) = alerts
+        .into_iter()
+        .filter(|a| a.timestamp_ms >= from_ms)
+        .partition(|a| a.module == SBOM_MODULE);
+
+    let by_severity = SEVERITIES
+        .iter()
+        .map(|s| {
+            (
+                s.to_string(),
+                alerts.iter().filter(|a| a.severity == *s).count(),
+            )
+        })
+        .collect();
+    let mut rule_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for alert in &alerts {
+        *rule_counts.entry(alert.rule.as_str()).or_default() += 1;
+    }
+    let mut top_rules: Vec<RuleCount> = rule_counts
+        .into_iter()
+        .map(|(rule, alerts)| RuleCount {
+            rule: rule.to_owned(),
+            alerts,
+        })
+        .collect();
+    // Stable sort keeps rules with equal counts in name order
+    top_rules.sort_by_key(|r| Reverse(r.alerts));
+    top_rules.truncate(MAX_TOP_RULES);
+    let total = alerts.len();
+    let recent = alerts.into_iter().rev().take(MAX_RECENT_ALERTS).collect();
+
+    let actions = activity
+        .into_iter()
+        .filter(|(timestamp_ms, _)| *timestamp_ms >= from_ms)
+        .filter_map(|(timestamp_ms, activity)| match activity {
+            GuardActivity::Action {
+                action_type,
+                target,
+                success,
+                simulated,
+                ..
+            } => Some(ActionLine {
+                timestamp_ms,
+                action_type,
+                target,
+                success,
+                simulated,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    findings.sort_by_key(|f| Reverse(f.severity));
+    let last_scan = status
+        .tasks
+        .into_iter()
+        .filter(|t| t.kind == SBOM_SCAN_TASK && t.last_run.is_some())
+        .max_by_key(|t| t.last_run);
+
+    Report {
+        daemon: daemon.to_owned(),
+        generated_ms,
+        period_secs,
+        from_ms,
+        partial: started_ms > from_ms,
+        status: status.status.to_string(),
+        uptime_secs: status.uptime_secs,
+        modules: status.modules,
+        alerts: AlertSummary {
+            total,
+            by_severity,
+            top_rules,
+            recent,
+        },
+        actions,
+        traffic,
+        findings,
+        last_scan,
+    }
+}
+
+/// Write `report` as Markdown.
+fn write_markdown(report: &Report, w: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(w, "# Ironpost Activity Report")?;
+    writeln!(w)?;
+    writeln!(w, "- **Daemon:** {}", md(&report.daemon))?;
+    writeln!(
+        w,
+        "- **Period:** {} to {} ({})",
+        format_datetime(report.from_ms),
+        format_datetime(report.generated_ms),
+        format_period(report.period_secs)
+    )?;
+    writeln!(
+        w,
+        "- **Status:** {} (up {})",
+        md(&report.status),
+        format_period(report.uptime_secs)
+    )?;
+    if report.partial {
+        writeln!(w)?;
+        writeln!(
+            w,
+            "> The daemon started during this period; earlier activity is not included."
+        )?;
+    }
+
+    writeln!(w)?;
+    writeln!(w, "## Modules")?;
+    writeln!(w)?;
+    writeln!(w, "| Module | Enabled | Health |")?;
+    writeln!(w, "|---|---|---|")?;
+    for module in &report.modules {
+        writeln!(
+            w,
+            "| {} | {} | {} |",
+            md(&module.name),
+            if module.enabled { "yes" } else { "no" },
+            md(&module.status.to_string())
+        )?;
+    }
+
+    writeln!(w)?;
+    writeln!(w, "## Alerts")?;
+    writeln!(w)?;
+    writeln!(w, "{} alert(s) in the period.", report.alerts.total)?;
+    writeln!(w)?;
+    writeln!(w, "| Severity | Alerts |")?;
+    writeln!(w, "|---|---|")?;
+    for (severity, count) in &report.alerts.by_severity {
+        writeln!(w, "| {} | {} |", severity, count)?;
+    }
+    if !report.alerts.top_rules.is_empty() {
+        writeln!(w)?;
+        writeln!(w, "### Top Rules")?;
+        writeln!(w)?;
+        writeln!(w, "| Rule | Alerts |")?;
+        writeln!(w, "|---|---|")?;
+        for rule in &report.alerts.top_rules {
+            writeln!(w, "| {} | {} |", md(&rule.rule), rule.alerts)?;
+        }
+        writeln!(w)?;
+        writeln!(w, "### Most Recent")?;
+        writeln!(w)?;
+        writeln!(
+            w,
+            "| Time (UTC) | Severity | Module | Rule | Title | Source |"
+        )?;
+        writeln!(w, "|---|---|---|---|---|---|")?;
+        for alert in &report.alerts.recent {
+            writeln!(
+                w,
+                "| {} | {} | {} | {} | {} | {} |",
+                format_datetime(alert.timestamp_ms),
+                alert.severity,
+                md(&alert.module),
+                md(&alert.rule),
+                md(&alert.title),
+                alert.source_ip.map(|ip| ip.to_string()).unwrap_or_default()
+            )?;
+        }
+    }
+
+    writeln!(w)?;
+    writeln!(w, "## Container Actions")?;
+    writeln!(w)?;
+    if report.actions.is_empty() {
+        writeln!(w, "No isolation actions in the period.")?;
+    } else {
+        writeln!(w, "| Time (UTC) | Action | Target | Result |")?;
+        writeln!(w, "|---|---|---|---|")?;
+        for action in &report.actions {
+            writeln!(
+                w,
+                "| {} | {} | {} | {} |",
+                format_datetime(action.timestamp_ms),
+                md(&action.action_type),
+                md(&action.target),
+                action.result()
+            )?;
+        }
+    }
+
+    writeln!(w)?;
+    writeln!(w, "## Traffic")?;
+    writeln!(w)?;
+    match &report.traffic {
+        None => writeln!(w, "eBPF monitoring is disabled.")?,
+        Some(traffic) => {
+            writeln!(
+                w,
+                "Interface {} ({}), totals since the XDP program attached.",
+                md(&traffic.interface),
+                if traffic.attached {
+                    "attached"
+                } else {
+                    "detached"
+                }
+            )?;
+            writeln!(w)?;
+            writeln!(w, "| Protocol | Packets | Bytes | Drops | Rate |")?;
+            writeln!(w, "|---|---|---|---|---|")?;
+            for (name, counters) in traffic.stats.rows() {
+                writeln!(
+                    w,
+                    "| {} | {} | {} | {} | {} |",
+                    name,
+                    counters.packets,
+                    counters.bytes,
+                    counters.drops,
+                    format_bps(counters.bps)
+                )?;
+            }
+        }
+    }
+
+    writeln!(w)?;
+    writeln!(w, "## Vulnerability Findings")?;
+    writeln!(w)?;
+    if let Some(scan) = &report.last_scan {
+        writeln!(w, "Last scan: {}", md(&scan.summary()))?;
+        writeln!(w)?;
+    }
+    if report.findings.is_empty() {
+        writeln!(w, "No findings in the period.")?;
+    } else {
+        writeln!(w, "| Time (UTC) | Severity | Finding |")?;
+        writeln!(w, "|---|---|---|")?;
+        for finding in &report.findings {
+            writeln!(
+                w,
+                "| {} | {} | {} |",
+                format_datetime(finding.timestamp_ms),
+                finding.severity,
+                md(&finding.title)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Write `report` as a standalone HTML page.
+fn write_html(report: &Report, w: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(w, "<!DOCTYPE html>")?;
+    writeln!(w, "<html lang=\"en\">")?;
+    writeln!(w, "<head>")?;
+    writeln!(w, "<meta charset=\"utf-8\">")?;
+    writeln!(w, "<title>Ironpost Activity Report</title>")?;
+    writeln!(
+        w,
+        "<style>body{{font-family:sans-serif;margin:2em;color:#222}}\
+         table{{border-collapse:collapse;margin:0.5em 0 1.5em}}\
+         th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}\
+         th{{background:#f2f2f2}}.note{{color:#a60}}</style>"
+    )?;
+    writeln!(w, "</head>")?;
+    writeln!(w, "<body>")?;
+    writeln!(w, "<h1>Ironpost Activity Report</h1>")?;
+    writeln!(w, "<ul>")?;
+    writeln!(w, "<li><b>Daemon:</b> {}</li>", html(&report.daemon))?;
+    writeln!(
+        w,
+        "<li><b>Period:</b> {} to {} ({})</li>",
+        format_datetime(report.from_ms),
+        format_datetime(report.generated_ms),
+        format_period(report.period_secs)
+    )?;
+    writeln!(
+        w,
+        "<li><b>Status:</b> {} (up {})</li>",
+        html(&report.status),
+        format_period(report.uptime_secs)
+    )?;
+    writeln!(w, "</ul>")?;
+    if report.partial {
+        writeln!(
+            w,
+            "<p class=\"note\">The daemon started during this period; earlier activity is not included.</p>"
+        )?;
+    }
+
+    writeln!(w, "<h2>Modules</h2>")?;
+    let rows: Vec<Vec<String>> = report
+        .modules
+        .iter()
+        .map(|m| {
+            vec![
+                m.name.clone(),
+                if m.enabled { "yes" } else { "no" }.to_owned(),
+                m.status.to_string(),
+            ]
+        })
+        .collect();
+    html_table(w, &["Module", "Enabled", "Health"], &rows)?;
+
+    writeln!(w, "<h2>Alerts</h2>")?;
+    writeln!(w, "<p>{} alert(s) in the period.</p>", report.alerts.total)?;
+    let rows: Vec<Vec<String>> = report
+        .alerts
+        .by_severity
+        .iter()
+        .map(|(severity, count)| vec![severity.clone(), count.to_string()])
+        .collect();
+    html_table(w, &["Severity", "Alerts"], &rows)?;
+    if !report.alerts.top_rules.is_empty() {
+        writeln!(w, "<h3>Top Rules</h3>")?;
+        let rows: Vec<Vec<String>> = report
+            .alerts
+            .top_rules
+            .iter()
+            .map(|r| vec![r.rule.clone(), r.alerts.to_string()])
+            .collect();
+        html_table(w, &["Rule", "Alerts"], &rows)?;
+        writeln!(w, "<h3>Most Recent</h3>")?;
+        let rows: Vec<Vec<String>> = report
+            .alerts
+            .recent
+            .iter()
+            .map(|a| {
+                vec![
+                    format_datetime(a.timestamp_ms),
+                    a.severity.to_string(),
+                    a.module.clone(),
+                    a.rule.clone(),
+                    a.title.clone(),
+                    a.source_ip.map(|ip| ip.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect();
+        html_table(
+            w,
+            &[
+                "Time (UTC)",
+                "Severity",
+                "Module",
+                "Rule",
+                "Title",
+                "Source",
+            ],
+            &rows,
+        )?;
+    }
+
+    writeln!(w, "<h2>Container Actions</h2>")?;
+    if report.actions.is_empty() {
+        writeln!(w, "<p>No isolation actions in the period.</p>")?;
+    } else {
+        let rows: Vec<Vec<String>> = report
+            .actions
+            .iter()
+            .map(|a| {
+                vec![
+                    format_datetime(a.timestamp_ms),
+                    a.action_type.clone(),
+                    a.target.clone(),
+                    a.result().to_owned(),
+                ]
+            })
+            .collect();
+        html_table(w, &["Time (UTC)", "Action", "Target", "Result"], &rows)?;
+    }
+
+    writeln!(w, "<h2>Traffic</h2>")?;
+    match &report.traffic {
+        None => writeln!(w, "<p>eBPF monitoring is disabled.</p>")?,
+        Some(traffic) => {
+            writeln!(
+                w,
+                "<p>Interface {} ({}), totals since the XDP program attached.</p>",
+                html(&traffic.interface),
+                if traffic.attached {
+                    "attached"
+                } else {
+                    "detached"
+                }
+            )?;
+            let rows: Vec<Vec<String>> = traffic
+                .stats
+                .rows()
+                .iter()
+                .map(|(name, c)| {
+                    vec![
+                        (*name).to_owned(),
+                        c.packets.to_string(),
+                        c.bytes.to_string(),
+                        c.drops.to_string(),
+                        format_bps(c.bps),
+                    ]
+                })
+                .collect();
+            html_table(w, &["Protocol", "Packets", "Bytes", "Drops", "Rate"], &rows)?;
+        }
+    }
+
+    writeln!(w, "<h2>Vulnerability Findings</h2>")?;
+    if let Some(scan) = &report.last_scan {
+        writeln!(w, "<p>Last scan: {}</p>", html(&scan.summary()))?;
+    }
+    if report.findings.is_empty() {
+        writeln!(w, "<p>No findings in the period.</p>")?;
+    } else {
+        let rows: Vec<Vec<String>> = report
+            .findings
+            .iter()
+            .map(|f| {
+                vec![
+                    format_datetime(f.timestamp_ms),
+                    f.severity.to_string(),
+                    f.title.clone(),
+                ]
+            })
+            .collect();
+        html_table(w, &["Time (UTC)", "Severity", "Finding"], &rows)?;
+    }
+
+    writeln!(w, "</body>")?;
+    writeln!(w, "</html>")?;
+    Ok(())
+}
+
+/// Write an HTML table; cells are escaped.
+fn html_table(w: &mut dyn Write, headers: &[&str], rows: &[Vec<String>]) -> std::io::Result<()> {
+    writeln!(w, "<table>")?;
+    write!(w, "<tr>")?;
+    for header in headers {
+        write!(w, "<th>{}</th>", header)?;
+    }
+    writeln!(w, "</tr>")?;
+    for row in rows {
+        write!(w, "<tr>")?;
+        for cell in row {
+            write!(w, "<td>{}</td>", html(cell))?;
+        }
+        writeln!(w, "</tr>")?;
+    }
+    writeln!(w, "</table>")
+}
+
+/// Escape text for HTML element content and attribute values.
+fn html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escape text for a Markdown table cell.
+fn md(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '|' => out.push_str("\\|"),
+            '\r' | '\n' => out.push(' '),
+            '\\' | '`' | '*' | '_' | '<' | '>' | '[' | ']' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// `YYYY-MM-DD HH:MM:SS UTC` of a Unix millisecond timestamp.
+pub(crate) fn format_datetime(timestamp_ms: u64) -> String {
+    let secs = timestamp_ms / 1000;
+    let (year, month, day) = civil_from_days(secs / 86_400);
+    let time = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        time / 3600,
+        (time / 60) % 60,
+        time % 60
+    )
+}
+
+/// Convert days since the Unix epoch to a (year, month, day) civil date.
+///
+/// Howard Hinnant's `civil_from_days`, restricted to dates on or after 1970.
+pub(crate) fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Compact duration such as `7d`, `24h`, `1d 6h` or `45m`.
+fn format_period(secs: u64) -> String {
+    let days = secs / 86_400;
+    let hours = (secs % 86_400) / 3600;
+    let minutes = (secs % 3600) / 60;
+    let parts: Vec<String> = [(days, "d"), (hours, "h"), (minutes, "m")]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, unit)| format!("{}{}", n, unit))
+        .collect();
+    if parts.is_empty() {
+        format!("{}s", secs)
+    } else {
+        parts.join(" ")
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+/// Module health and scheduled tasks returned by the daemon's `/status` endpoint.
+#[derive(Debug, Deserialize)]
+struct DaemonStatus {
+    status: HealthStatus,
+    uptime_secs: u64,
+    modules: Vec<ModuleEntry>,
+    #[serde(default)]
+    tasks: Vec<TaskEntry>,
+}
+
+/// Health of a single module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleEntry {
+    /// Module name
+    pub name: String,
+    /// Whether the module is enabled
+    pub enabled: bool,
+    /// Module health
+    pub status: HealthStatus,
+}
+
+/// Most recent run of a scheduled task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskEntry {
+    /// Task name
+    pub name: String,
+    /// Task kind
+    pub kind: String,
+    /// Start time of the most recent run (Unix seconds)
+    pub last_run: Option<u64>,
+    /// Whether the most recent completed run succeeded
+    pub last_success: Option<bool>,
+    /// Summary or error message of the most recent completed run
+    pub last_message: Option<String>,
+}
+
+impl TaskEntry {
+    fn summary(&self) -> String {
+        let when = self
+            .last_run
+            .map(|secs| format_datetime(secs.saturating_mul(1000)))
+            .unwrap_or_default();
+        let outcome = match self.last_success {
+            Some(true) => "succeeded",
+            Some(false) => "failed",
+            None => "running",
+        };
+        match &self.last_message {
+            Some(message) => format!("{} ({}) at {}: {}", self.name, outcome, when, message),
+            None => format!("{} ({}) at {}", self.name, outcome, when),
+        }
+    }
+}
+
+/// Activity report assembled from the daemon.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    /// Daemon address
+    pub daemon: String,
+    /// Generation time (Unix milliseconds)
+    pub generated_ms: u64,
+    /// Period covered, ending at `generated_ms`
+    pub period_secs: u64,
+    /// Start of the period (Unix milliseconds)
+    pub from_ms: u64,
+    /// Whether the daemon started after the period began
+    pub partial: bool,
+    /// Overall daemon health
+    pub status: String,
+    /// Daemon uptime
+    pub uptime_secs: u64,
+    /// Module health
+    pub modules: Vec<ModuleEntry>,
+    /// Alerts in the period, excluding vulnerability findings
+    pub alerts: AlertSummary,
+    /// Container isolation actions in the period
+    pub actions: Vec<ActionLine>,
+    /// eBPF traffic counters (`None` when eBPF is disabled)
+    pub traffic: Option<EbpfStatus>,
+    /// Vulnerability findings in the period, most severe first
+    pub findings: Vec<AlertEntry>,
+    /// Most recent SBOM scan task run
+    pub last_scan: Option<TaskEntry>,
+}
+
+/// Alert counts and the most recent alerts.
+#[derive(Debug, Serialize)]
+pub struct AlertSummary {
+    /// Alerts in the period
+    pub total: usize,
+    /// Alerts per severity, most severe first
+    pub by_severity: Vec<(String, usize)>,
+    /// Rules with the most alerts
+    pub top_rules: Vec<RuleCount>,
+    /// Most recent alerts, newest first
+    pub recent: Vec<AlertEntry>,
+}
+
+/// Alerts raised by one rule.
+#[derive(Debug, Serialize)]
+pub struct RuleCount {
+    /// Detection rule name
+    pub rule: String,
+    /// Alerts in the period
+    pub alerts: usize,
+}
+
+/// An executed container isolation action.
+#[derive(Debug, Serialize)]
+pub struct ActionLine {
+    /// Time the daemon recorded the action (Unix milliseconds)
+    pub timestamp_ms: u64,
+    /// Action type (e.g. `container_pause`)
+    pub action_type: String,
+    /// Target container ID
+    pub target: String,
+    /// Whether the action succeeded
+    pub success: bool,
+    /// Whether the action was only simulated (dry run)
+    pub simulated: bool,
+}
+
+impl ActionLine {
+    fn result(&self) -> &'static str {
+        match (self.success, self.simulated) {
+            (true, true) => "simulated",
+            (true, false) => "succeeded",
+            (false, _) => "failed",
+        }
+    }
+}
+
+/// Summary of a written report.
+#[derive(Debug, Serialize)]
+pub struct ReportSummary {
+    /// Report file
+    pub path: String,
+    /// Report format
+    pub format: ReportFormat,
+    /// Period covered
+    pub period_secs: u64,
+    /// Alerts in the report
+    pub alerts: usize,
+    /// Container actions in the report
+    pub actions: usize,
+    /// Vulnerability findings in the report
+    pub findings: usize,
+}
+
+impl Render for ReportSummary {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(w, "Report written: {}", self.path.bold())?;
+        writeln!(w, "  Period:   last {}", format_period(self.period_secs))?;
+        writeln!(w, "  Alerts:   {}", self.alerts)?;
+        writeln!(w, "  Actions:  {}", self.actions)?;
+        writeln!(w, "  Findings: {}", self.findings)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2026-10-16 12:00:00 UTC
+    const NOW_MS: u64 = 1_792_152_000_000;
+    const HOUR_MS: u64 = 3_600_000;
+
+    fn alert(seq: u64, age_ms: u64, severity: Severity, module: &str, rule: &str) -> AlertEntry {
+        AlertEntry {
+            seq,
+            id: format!("alert-{}", seq),
+            timestamp_ms: NOW_MS - age_ms,
+            severity,
+            module: module.to_owned(),
+            rule: rule.to_owned(),
+            title: format!("{} <alert> | {}", rule, seq),
+            description: String::new(),
+            source_ip: None,
+            target_ip: None,
+            source_enrichment: None,
+            target_enrichment: None,
+        }
+    }
+
+    fn status(uptime_secs: u64) -> DaemonStatus {
+        DaemonStatus {
+            status: HealthStatus::Healthy,
+            uptime_secs,
+            modules: vec![ModuleEntry {
+                name: "log-pipeline".to_owned(),
+                enabled: true,
+                status: HealthStatus::Healthy,
+            }],
+            tasks: vec![TaskEntry {
+                name: "nightly".to_owned(),
+                kind: SBOM_SCAN_TASK.to_owned(),
+                last_run: Some(NOW_MS / 1000 - 3600),
+                last_success: Some(true),
+                last_message: Some("scanned 2 lockfiles, 1 findings".to_owned()),
+            }],
+        }
+    }
+
+    fn action(age_ms: u64, success: bool) -> (u64, GuardActivity) {
+        (
+            NOW_MS - age_ms,
+            GuardActivity::Action {
+                action_id: "act-1".to_owned(),
+                action_type: "container_pause".to_owned(),
+                target: "abc123".to_owned(),
+                success,
+                simulated: false,
+            },
+        )
+    }
+
+    fn sample_report() -> Report {
+        build_report(
+            "127.0.0.1:9100",
+            NOW_MS,
+            86_400,
+            status(7 * 86_400),
+            None,
+            vec![
+                alert(1, 30 * HOUR_MS, Severity::Critical, "log-pipeline", "old"),
+                alert(
+                    2,
+                    2 * HOUR_MS,
+                    Severity::High,
+                    "log-pipeline",
+                    "ssh_brute_force",
+                ),
+                alert(
+                    3,
+                    HOUR_MS,
+                    Severity::Medium,
+                    "log-pipeline",
+                    "ssh_brute_force",
+                ),
+                alert(4, HOUR_MS, Severity::Low, "log-pipeline", "port_scan"),
+                alert(5, HOUR_MS, Severity::Critical, SBOM_MODULE, "CVE-2024-0001"),
+            ],
+            vec![action(30 * HOUR_MS, true), action(HOUR_MS, false)],
+        )
+    }
+
+    #[test]
+    fn test_build_report_limits_to_period() {
+        let report = sample_report();
+
+        assert_eq!(report.alerts.total, 3);
+        assert_eq!(report.alerts.recent[0].seq, 4, "newest first");
+        assert_eq!(report.actions.len(), 1);
+        assert_eq!(report.actions[0].result(), "failed");
+        assert_eq!(report.findings.len(), 1);
+        assert!(!report.partial);
+    }
+
+    #[test]
+    fn test_build_report_counts_severities_and_rules() {
+        let report = sample_report();
+
+        assert_eq!(report.alerts.by_severity[0], ("Critical".to_owned(), 0));
+        assert_eq!(report.alerts.by_severity[1], ("High".to_owned(), 1));
+        assert_eq!(report.alerts.top_rules[0].rule, "ssh_brute_force");
+        assert_eq!(report.alerts.top_rules[0].alerts, 2);
+        assert_eq!(report.alerts.top_rules[1].rule, "port_scan");
+    }
+
+    #[test]
+    fn test_build_report_marks_partial_coverage() {
+        let report = build_report(
+            "127.0.0.1:9100",
+            NOW_MS,
+            86_400,
+            status(3600),
+            None,
+            Vec::new(),
+            Vec::new(),
+        );
+
+        assert!(report.partial);
+        assert_eq!(report.alerts.total, 0);
+        assert!(report.last_scan.is_some());
+    }
+
+    #[test]
+    fn test_markdown_report_sections_and_escaping() {
+        let mut buf = Vec::new();
+
+        write_markdown(&sample_report(), &mut buf).expect("markdown rendering succeeds");
+
+        let output = String::from_utf8(buf).expect("valid UTF-8");
+        assert!(output.starts_with("# Ironpost Activity Report"));
+        assert!(output.contains("2026-10-15 12:00:00 UTC to 2026-10-16 12:00:00 UTC (1d)"));
+        for section in [
+            "## Modules",
+            "## Alerts",
+            "## Container Actions",
+            "## Traffic",
+            "## Vulnerability Findings",
+        ] {
+            assert!(output.contains(section), "missing {}", section);
+        }
+        assert!(output.contains("ssh\\_brute\\_force \\<alert\\> \\| 3"));
+        assert!(output.contains("eBPF monitoring is disabled."));
+        assert!(output.contains("Last scan: nightly (succeeded)"));
+    }
+
+    #[test]
+    fn test_html_report_escapes_content() {
+        let mut buf = Vec::new();
+
+        write_html(&sample_report(), &mut buf).expect("html rendering succeeds");
+
+        let output = String::from_utf8(buf).expect("valid UTF-8");
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains("<h2>Container Actions</h2>"));
+        assert!(output.contains("port_scan &lt;alert&gt; | 4"));
+        assert!(!output.contains("<alert>"));
+        assert!(output.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_format_datetime() {
+        assert_eq!(format_datetime(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_datetime(NOW_MS), "2026-10-16 12:00:00 UTC");
+    }
+
+    #[test]
+    fn test_format_period() {
+        assert_eq!(format_period(86_400), "1d");
+        assert_eq!(format_period(108_000), "1d 6h");
+        assert_eq!(format_period(2700), "45m");
+        assert_eq!(format_period(30), "30s");
+    }
+
+    #[test]
+    fn test_report_summary_render() {
+        let summary = ReportSummary {
+            path: "ironpost-report.md".to_owned(),
+            format: ReportFormat::Md,
+            period_secs: 86_400,
+            alerts: 3,
+            actions: 1,
+            findings: 1,
+        };
+        let mut buf = Vec::new();
+
+        summary.render_text(&mut buf).expect("render succeeds");
+
+        let output = String::from_utf8(buf).expect("valid UTF-8");
+        assert!(output.contains("ironpost-report.md"));
+        assert!(output.contains("last 1d"));
+    }
+}
@@ -1,18 +1,35 @@
 //! `ironpost rules` command handler
-
+//!
+//! `list` asks the running daemon's `/rules` control endpoint for the loaded
+//! rules and their match counts, falling back to the rules directory when the
+//! daemon is not reachable. `validate` and `test` work on rule files directly
+//! and need no daemon; `new` scaffolds a rule and a sample log for `test`
+//! (see [`crate::commands::rules_new`]).
+
+use std::collections::HashMap;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use serde::Serialize;
-use tracing::info;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
+use tracing::{debug, info};
 
 use ironpost_core::config::IronpostConfig;
-use ironpost_log_pipeline::rule::RuleLoader;
+use ironpost_log_pipeline::ParserRouter;
+use ironpost_log_pipeline::rule::{DetectionRule, RuleLoader, RuleMatcher, RuleStatus};
 
 use crate::cli::{RulesAction, RulesArgs};
+use crate::client::DaemonClient;
+use crate::commands::rules_new;
 use crate::error::CliError;
 use crate::output::{OutputWriter, Render};
 
+/// Daemon control endpoint listing loaded rules with match counts.
+const RULES_PATH: &str = "/rules";
+
+/// Maximum number of matching lines kept in a `rules test` report.
+const MAX_REPORTED_MATCHES: usize = 1000;
+
 /// Execute the `rules` command.
 pub async fn execute(
     args: RulesArgs,
@@ -22,12 +39,21 @@ pub async fn execute(
     match args.action {
         RulesAction::List { status } => execute_list(config_path, status, writer).await,
         RulesAction::Validate { path } => execute_validate(&path, writer).await,
+        RulesAction::Test { rule, log } => execute_test(&rule, &log, writer).await,
+        RulesAction::New {
+            id,
+            template,
+            dir,
+            force,
+        } => rules_new::execute(&id, template, &dir, force, writer).await,
     }
 }
 
 /// Execute the rules list subcommand.
 ///
-/// Loads detection rules from the rules directory and optionally filters by status.
+/// Lists the rules loaded in the running daemon with their match counts. When
+/// the daemon cannot be reached, lists the rules in `{data_dir}/rules`
+/// instead, without match counts.
 ///
 /// # Arguments
 ///
@@ -37,7 +63,8 @@ pub async fn execute(
 ///
 /// # Errors
 ///
-/// Returns `CliError::Rule` if rule loading fails (directory not found, invalid YAML, etc.)
+/// Returns `CliError::Command` if the daemon rejects the request, and
+/// `CliError::Rule` if the fallback rule loading fails.
 async fn execute_list(
     config_path: &Path,
     status_filter: Option<String>,
@@ -45,53 +72,34 @@ async fn execute_list(
 ) -> Result<(), CliError> {
     let config = IronpostConfig::load(config_path).await?;
 
-    // Use data_dir as base for rules directory
-    // Rules are stored in {data_dir}/rules by convention
-    let rules_dir = std::path::Path::new(&config.general.data_dir).join("rules");
-    let rules_dir_str = rules_dir
-        .to_str()
-        .ok_or_else(|| CliError::Rule("rules directory path contains invalid UTF-8".to_string()))?;
-
-    info!(rules_dir = rules_dir_str, "loading detection rules");
+    let daemon_rules = match DaemonClient::from_config(&config) {
+        Ok(client) => {
+            debug!(addr = client.addr(), "querying daemon rules");
+            match client.get_json::<DaemonRules>(RULES_PATH).await {
+                Ok(response) => Some(response.rules),
+                Err(CliError::DaemonUnavailable(reason)) => {
+                    info!(reason = %reason, "daemon not reachable, listing rules on disk");
+                    None
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(_) => None,
+    };
 
-    // Load rules from directory
-    let rules = RuleLoader::load_directory(rules_dir_str)
-        .await
-        .map_err(|e| CliError::Rule(format!("failed to load rules: {}", e)))?;
+    let rules = match daemon_rules {
+        Some(rules) => rules,
+        None => load_rule_entries(&config).await?,
+    };
 
-    // Filter by status if provided
-    let filtered_rules: Vec<_> = if let Some(ref filter) = status_filter {
-        rules
-            .into_iter()
-            .filter(|r| {
-                let rule_status = match r.status {
-                    ironpost_log_pipeline::rule::RuleStatus::Enabled => "enabled",
-                    ironpost_log_pipeline::rule::RuleStatus::Disabled => "disabled",
-                    ironpost_log_pipeline::rule::RuleStatus::Test => "test",
-                };
-                rule_status == filter
-            })
-            .collect()
-    } else {
-        rules
+    let filtered_rules: Vec<_> = match status_filter {
+        Some(ref filter) => rules.into_iter().filter(|r| &r.status == filter).collect(),
+        None => rules,
     };
 
     let report = RuleListReport {
         total: filtered_rules.len(),
-        rules: filtered_rules
-            .into_iter()
-            .map(|r| RuleEntry {
-                id: r.id,
-                title: r.title,
-                severity: format!("{:?}", r.severity),
-                status: match r.status {
-                    ironpost_log_pipeline::rule::RuleStatus::Enabled => "enabled".to_owned(),
-                    ironpost_log_pipeline::rule::RuleStatus::Disabled => "disabled".to_owned(),
-                    ironpost_log_pipeline::rule::RuleStatus::Test => "test".to_owned(),
-                },
-                tags: r.tags,
-            })
-            .collect(),
+        rules: filtered_rules,
     };
 
     writer.render(&report)?;
@@ -99,9 +107,36 @@ async fn execute_list(
     Ok(())
 }
 
+/// Load rule entries from `{data_dir}/rules` (no match counts).
+async fn load_rule_entries(config: &IronpostConfig) -> Result<Vec<RuleEntry>, CliError> {
+    // Rules are stored in {data_dir}/rules by convention
+    let rules_dir = Path::new(&config.general.data_dir).join("rules");
+
+    info!(rules_dir = %rules_dir.display(), "loading detection rules");
+
+    let mut rules = RuleLoader::load_directory(&rules_dir)
+        .await
+        .map_err(|e| CliError::Rule(format!("failed to load rules: {}", e)))?;
+    rules.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Ok(rules
+        .into_iter()
+        .map(|r| RuleEntry {
+            severity: format!("{:?}", r.severity),
+            status: status_name(&r.status).to_owned(),
+            id: r.id,
+            title: r.title,
+            tags: r.tags,
+            matches: None,
+        })
+        .collect())
+}
+
 /// Execute the rules validate subcommand.
 ///
-/// Validates all YAML rule files in the specified directory without loading them into the engine.
+/// Checks every YAML rule file in the directory on its own: schema, rule
+/// validation, regex compilation and rule ID uniqueness. Nothing is loaded
+/// into a running engine.
 ///
 /// # Arguments
 ///
@@ -110,44 +145,193 @@ async fn execute_list(
 ///
 /// # Errors
 ///
-/// Returns `CliError::Rule` if one or more rules are invalid (exits with code 1).
+/// Returns `CliError::Rule` if the directory cannot be read or one or more
+/// rules are invalid (exits with code 1).
 async fn execute_validate(path: &Path, writer: &OutputWriter) -> Result<(), CliError> {
     info!(path = %path.display(), "validating detection rules");
 
-    // Attempt to load all rules and collect errors
-    let result = RuleLoader::load_directory(path).await;
-
-    let (valid, invalid, errors) = match result {
-        Ok(rules) => (rules.len(), 0, Vec::new()),
-        Err(e) => {
-            // Try to parse directory and collect per-file errors
-            // For now, report a single error
-            (
-                0,
-                1,
-                vec![RuleError {
-                    file: path.display().to_string(),
-                    error: e.to_string(),
-                }],
-            )
+    let report = validate_directory(path).await?;
+    writer.render(&report)?;
+
+    if report.invalid > 0 {
+        return Err(CliError::Rule(format!("{} invalid rules", report.invalid)));
+    }
+
+    Ok(())
+}
+
+/// Validate each `.yml`/`.yaml` file in `path`, in file name order.
+async fn validate_directory(path: &Path) -> Result<RuleValidationReport, CliError> {
+    let mut files = Vec::new();
+    let mut entries = tokio::fs::read_dir(path).await.map_err(|e| {
+        CliError::Rule(format!(
+            "failed to read directory {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file = entry.path();
+        if file
+            .extension()
+            .is_some_and(|ext| ext == "yml" || ext == "yaml")
+        {
+            files.push(file);
         }
-    };
+    }
+    files.sort();
+
+    let mut matcher = RuleMatcher::new();
+    let mut seen_ids = HashMap::new();
+    let mut errors = Vec::new();
 
-    let report = RuleValidationReport {
+    for file in &files {
+        let name = file.file_name().map_or_else(
+            || file.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        match check_rule_file(file, &mut matcher).await {
+            Ok(rule) => {
+                if let Some(first) = seen_ids.get(&rule.id) {
+                    errors.push(RuleError {
+                        file: name,
+                        error: format!(
+                            "duplicate rule id '{}' (first defined in {})",
+                            rule.id, first
+                        ),
+                    });
+                } else {
+                    seen_ids.insert(rule.id, name);
+                }
+            }
+            Err(e) => errors.push(RuleError {
+                file: name,
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    Ok(RuleValidationReport {
         path: path.display().to_string(),
-        total_files: valid + invalid,
-        valid,
-        invalid,
+        total_files: files.len(),
+        valid: files.len() - errors.len(),
+        invalid: errors.len(),
         errors,
+    })
+}
+
+/// Load a rule file, validate it, and compile its regex conditions.
+async fn check_rule_file(
+    path: &Path,
+    matcher: &mut RuleMatcher,
+) -> Result<DetectionRule, ironpost_log_pipeline::LogPipelineError> {
+    let rule = RuleLoader::load_file(path).await?;
+    rule.validate()?;
+    matcher.compile_rule(&rule)?;
+    Ok(rule)
+}
+
+/// Execute the rules test subcommand.
+///
+/// Parses each line of the sample log with the default parsers and reports
+/// the lines matching the rule's conditions. Thresholds and the rule status
+/// are ignored, so every line whose conditions match is reported.
+///
+/// # Arguments
+///
+/// * `rule_path` - YAML rule file to test
+/// * `log_path` - Sample log file, one log entry per line
+/// * `writer` - Output writer for rendering results
+///
+/// # Errors
+///
+/// Returns `CliError::Rule` if the rule is invalid, and `CliError::Io` if the
+/// log file cannot be read.
+async fn execute_test(
+    rule_path: &Path,
+    log_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    info!(rule = %rule_path.display(), log = %log_path.display(), "testing detection rule");
+
+    let mut matcher = RuleMatcher::new();
+    let rule = check_rule_file(rule_path, &mut matcher)
+        .await
+        .map_err(|e| CliError::Rule(format!("{}: {}", rule_path.display(), e)))?;
+
+    let file = tokio::fs::File::open(log_path).await?;
+    let report = test_rule(&rule, &matcher, log_path, BufReader::new(file)).await?;
+    writer.render(&report)
+}
+
+/// Run `rule` against every line read from `reader`.
+pub(crate) async fn test_rule<R>(
+    rule: &DetectionRule,
+    matcher: &RuleMatcher,
+    log_path: &Path,
+    reader: R,
+) -> Result<RuleTestReport, CliError>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let parser = ParserRouter::with_defaults();
+    let mut report = RuleTestReport {
+        rule_id: rule.id.clone(),
+        rule_title: rule.title.clone(),
+        log_file: log_path.to_path_buf(),
+        lines: 0,
+        matched: 0,
+        unparsed: 0,
+        matches: Vec::new(),
     };
 
-    writer.render(&report)?;
+    let mut lines = reader.split(b'\n');
+    while let Some(raw) = lines.next_segment().await? {
+        report.lines += 1;
+        let raw = raw.strip_suffix(b"\r").unwrap_or(&raw);
+        if raw.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+
+        let entry = match parser.parse(raw) {
+            Ok(entry) => entry,
+            Err(e) => {
+                debug!(line = report.lines, error = %e, "failed to parse log line");
+                report.unparsed += 1;
+                continue;
+            }
+        };
 
-    if invalid > 0 {
-        return Err(CliError::Rule(format!("{} invalid rules", invalid)));
+        if matcher
+            .matches(rule, &entry)
+            .map_err(|e| CliError::Rule(e.to_string()))?
+        {
+            report.matched += 1;
+            if report.matches.len() < MAX_REPORTED_MATCHES {
+                report.matches.push(LineMatch {
+                    line: report.lines,
+                    text: String::from_utf8_lossy(raw).into_owned(),
+                });
+            }
+        }
     }
 
-    Ok(())
+    Ok(report)
+}
+
+/// Lowercase name of a rule status, as used by `--status` and the daemon.
+fn status_name(status: &RuleStatus) -> &'static str {
+    match status {
+        RuleStatus::Enabled => "enabled",
+        RuleStatus::Disabled => "disabled",
+        RuleStatus::Test => "test",
+    }
+}
+
+/// Response of the daemon's `/rules` endpoint.
+#[derive(Deserialize)]
+struct DaemonRules {
+    rules: Vec<RuleEntry>,
 }
 
 /// Rule listing report.
@@ -162,7 +346,7 @@ pub struct RuleListReport {
 }
 
 /// Individual detection rule entry.
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 pub struct RuleEntry {
     /// Unique rule identifier
     pub id: String,
@@ -174,6 +358,10 @@ pub struct RuleEntry {
     pub status: String,
     /// Rule tags for categorization
     pub tags: Vec<String>,
+    /// Log entries matched since the daemon loaded the rule (`None` when
+    /// listed from disk)
+    #[serde(default)]
+    pub matches: Option<u64>,
 }
 
 impl Render for RuleListReport {
@@ -188,10 +376,10 @@ impl Render for RuleListReport {
         writeln!(w)?;
         writeln!(
             w,
-            "{:<25} {:<30} {:<10} {:<10} Tags",
-            "ID", "Title", "Severity", "Status"
+            "{:<25} {:<30} {:<10} {:<10} {:>8}  Tags",
+            "ID", "Title", "Severity", "Status", "Matches"
         )?;
-        writeln!(w, "{}", "-".repeat(90))?;
+        writeln!(w, "{}", "-".repeat(100))?;
 
         for r in &self.rules {
             let status_colored = match r.status.as_str() {
@@ -200,13 +388,16 @@ impl Render for RuleListReport {
                 _ => r.status.normal(),
             };
 
+            let matches = r.matches.map_or_else(|| "-".to_owned(), |m| m.to_string());
+
             writeln!(
                 w,
-                "{:<25} {:<30} {:<10} {:<10} {}",
+                "{:<25} {:<30} {:<10} {:<10} {:>8}  {}",
                 r.id,
                 r.title,
                 r.severity,
                 status_colored,
+                matches,
                 r.tags.join(", ")
             )?;
         }
@@ -270,6 +461,75 @@ impl Render for RuleValidationReport {
     }
 }
 
+/// Result of running a rule against a sample log file.
+#[derive(Serialize)]
+pub struct RuleTestReport {
+    /// Tested rule ID
+    pub rule_id: String,
+    /// Tested rule title
+    pub rule_title: String,
+    /// Sample log file
+    pub log_file: PathBuf,
+    /// Lines read from the log file
+    pub lines: usize,
+    /// Lines matching the rule
+    pub matched: usize,
+    /// Lines no parser could handle
+    pub unparsed: usize,
+    /// Matching lines (at most the first 1000)
+    pub matches: Vec<LineMatch>,
+}
+
+/// A log line matching the tested rule.
+#[derive(Serialize)]
+pub struct LineMatch {
+    /// 1-based line number
+    pub line: usize,
+    /// Raw line text
+    pub text: String,
+}
+
+impl Render for RuleTestReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(
+            w,
+            "Rule Test: {} ({})",
+            self.rule_id.bold(),
+            self.rule_title
+        )?;
+        writeln!(w, "  Log file: {}", self.log_file.display())?;
+        writeln!(
+            w,
+            "  Lines: {}, {}, {} unparsed",
+            self.lines,
+            if self.matched > 0 {
+                format!("{} matched", self.matched).green()
+            } else {
+                format!("{} matched", self.matched).normal()
+            },
+            self.unparsed
+        )?;
+
+        if !self.matches.is_empty() {
+            writeln!(w)?;
+            for m in &self.matches {
+                writeln!(w, "  {:>6}: {}", m.line.to_string().cyan(), m.text)?;
+            }
+            if self.matched > self.matches.len() {
+                writeln!(
+                    w,
+                    "  ... {} more matching lines",
+                    self.matched - self.matches.len()
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,6 +561,7 @@ mod tests {
                 severity: "High".to_owned(),
                 status: "enabled".to_owned(),
                 tags: vec!["test".to_owned(), "security".to_owned()],
+                matches: None,
             }],
         };
 
@@ -328,6 +589,7 @@ mod tests {
                     severity: "Critical".to_owned(),
                     status: "enabled".to_owned(),
                     tags: vec![],
+                    matches: None,
                 },
                 RuleEntry {
                     id: "rule-002".to_owned(),
@@ -335,6 +597,7 @@ mod tests {
                     severity: "Medium".to_owned(),
                     status: "disabled".to_owned(),
                     tags: vec!["test".to_owned()],
+                    matches: None,
                 },
                 RuleEntry {
                     id: "rule-003".to_owned(),
@@ -342,6 +605,7 @@ mod tests {
                     severity: "Low".to_owned(),
                     status: "test".to_owned(),
                     tags: vec!["experimental".to_owned()],
+                    matches: None,
                 },
             ],
         };
@@ -367,6 +631,7 @@ mod tests {
                 severity: "High".to_owned(),
                 status: "enabled".to_owned(),
                 tags: vec!["tag1".to_owned()],
+                matches: None,
             }],
         };
 
@@ -388,6 +653,7 @@ mod tests {
             severity: "Critical".to_owned(),
             status: "enabled".to_owned(),
             tags: vec!["tag1".to_owned(), "tag2".to_owned()],
+            matches: None,
         };
 
         let json = serde_json::to_string(&entry).expect("JSON serialization should succeed");
@@ -522,6 +788,7 @@ mod tests {
             severity: "Medium".to_owned(),
             status: "enabled".to_owned(),
             tags: Vec::new(),
+            matches: None,
         };
 
         let mut buffer = Vec::new();
@@ -547,6 +814,7 @@ mod tests {
             severity: "Low".to_owned(),
             status: "enabled".to_owned(),
             tags: vec![],
+            matches: None,
         };
 
         let json = serde_json::to_string(&entry).expect("should serialize long title");
@@ -591,6 +859,7 @@ mod tests {
                 severity: "High".to_owned(),
                 status: "enabled".to_owned(),
                 tags: vec!["日本".to_owned()],
+                matches: None,
             }],
         };
 
@@ -613,10 +882,167 @@ mod tests {
                 severity: "Medium".to_owned(),
                 status: status.to_owned(),
                 tags: vec![],
+                matches: None,
             };
 
             let json = serde_json::to_string(&entry).expect("should serialize");
             assert!(json.contains(status), "should preserve status: {}", status);
         }
     }
+
+    const SSH_RULE: &str = r#"
+id: ssh_root_login
+title: SSH root login failure
+severity: High
+detection:
+  conditions:
+    - field: process
+      modifier: exact
+      value: sshd
+    - field: message
+      modifier: regex
+      value: "Failed password for root"
+tags: [ssh]
+"#;
+
+    #[tokio::test]
+    async fn test_validate_directory_reports_each_invalid_file() {
+        // Given: One valid rule, a bad regex, a duplicate ID, and a non-YAML file
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(dir.path().join("a_ssh.yaml"), SSH_RULE).expect("write rule");
+        std::fs::write(
+            dir.path().join("b_regex.yaml"),
+            SSH_RULE
+                .replace("ssh_root_login", "bad_regex")
+                .replace("Failed password for root", "([unclosed"),
+        )
+        .expect("write rule");
+        std::fs::write(dir.path().join("c_dup.yml"), SSH_RULE).expect("write rule");
+        std::fs::write(dir.path().join("notes.txt"), "not a rule").expect("write file");
+
+        // When: Validating the directory
+        let report = validate_directory(dir.path())
+            .await
+            .expect("directory should be readable");
+
+        // Then: Each invalid file is reported on its own
+        assert_eq!(report.total_files, 3);
+        assert_eq!(report.valid, 1);
+        assert_eq!(report.invalid, 2);
+        assert_eq!(report.errors[0].file, "b_regex.yaml");
+        assert_eq!(report.errors[1].file, "c_dup.yml");
+        assert!(
+            report.errors[1].error.contains("a_ssh.yaml"),
+            "got: {}",
+            report.errors[1].error
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_directory_missing_dir() {
+        let result = validate_directory(Path::new("/nonexistent/ironpost/rules")).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rule_reports_matching_lines() {
+        // Given: The SSH rule and a sample log with a match, a miss and an unparsable line
+        let rule = RuleLoader::parse_yaml(SSH_RULE, "test").expect("valid rule");
+        let mut matcher = RuleMatcher::new();
+        matcher.compile_rule(&rule).expect("regex should compile");
+        let log = "<34>1 2024-01-15T12:00:00Z web-01 sshd - - - Failed password for root\n\
+                   <34>1 2024-01-15T12:00:01Z web-01 sshd - - - Accepted publickey for deploy\n\
+                   plain text line\n\
+                   \n\
+                   <34>1 2024-01-15T12:00:02Z web-02 sshd - - - Failed password for root\r\n";
+
+        // When: Testing the rule against the sample
+        let report = test_rule(&rule, &matcher, Path::new("auth.log"), log.as_bytes())
+            .await
+            .expect("test should succeed");
+
+        // Then: Matching lines are reported with their line numbers
+        assert_eq!(report.lines, 5);
+        assert_eq!(report.matched, 2);
+        assert_eq!(report.unparsed, 1);
+        let lines: Vec<usize> = report.matches.iter().map(|m| m.line).collect();
+        assert_eq!(lines, vec![1, 5]);
+        assert!(!report.matches[1].text.ends_with('\r'));
+    }
+
+    #[test]
+    fn test_rule_test_report_render_text() {
+        let report = RuleTestReport {
+            rule_id: "ssh_root_login".to_owned(),
+            rule_title: "SSH root login failure".to_owned(),
+            log_file: PathBuf::from("auth.log"),
+            lines: 10,
+            matched: 1,
+            unparsed: 2,
+            matches: vec![LineMatch {
+                line: 7,
+                text: "Failed password for root".to_owned(),
+            }],
+        };
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("ssh_root_login"), "should show rule ID");
+        assert!(output.contains("1 matched"), "should show match count");
+        assert!(output.contains("2 unparsed"), "should show unparsed count");
+        assert!(
+            output.contains("Failed password for root"),
+            "should show matching line"
+        );
+    }
+
+    #[test]
+    fn test_rule_list_report_render_text_match_counts() {
+        let report = RuleListReport {
+            total: 2,
+            rules: vec![
+                RuleEntry {
+                    id: "rule-live".to_owned(),
+                    title: "Live".to_owned(),
+                    severity: "High".to_owned(),
+                    status: "enabled".to_owned(),
+                    tags: vec![],
+                    matches: Some(42),
+                },
+                RuleEntry {
+                    id: "rule-disk".to_owned(),
+                    title: "Disk".to_owned(),
+                    severity: "Low".to_owned(),
+                    status: "enabled".to_owned(),
+                    tags: vec![],
+                    matches: None,
+                },
+            ],
+        };
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("Matches"), "should have matches column");
+        assert!(output.contains("42"), "should show match count");
+    }
+
+    #[test]
+    fn test_daemon_rules_deserialization() {
+        let json = r#"{"rules":[{"id":"r1","title":"Rule","severity":"High",
+            "status":"enabled","tags":["ssh"],"matches":7}]}"#;
+
+        let response: DaemonRules = serde_json::from_str(json).expect("should parse");
+
+        assert_eq!(response.rules.len(), 1);
+        assert_eq!(response.rules[0].matches, Some(7));
+        assert_eq!(response.rules[0].status, "enabled");
+    }
 }
@@ -0,0 +1,467 @@
+//! `ironpost rules new` -- detection rule scaffolding
+//!
+//! Writes `<id>.yaml`, a commented rule skeleton built from a template, and
+//! `<id>.sample.log`, JSON log lines for `ironpost rules test`. Some sample
+//! lines match the rule and some do not, so the pair is a working starting
+//! point: edit both, re-run the test, then deploy the rule.
+//!
+//! Before anything is written, the generated rule is loaded and run against
+//! its sample the same way `rules test` does.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tokio::io::BufReader;
+use tracing::info;
+
+use ironpost_log_pipeline::rule::{RuleLoader, RuleMatcher};
+
+use crate::cli::RuleTemplate;
+use crate::commands::rules::test_rule;
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Directory the daemon loads detection rules from.
+const DAEMON_RULE_DIR: &str = "/etc/ironpost/rules";
+
+/// Execute the rules new subcommand.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if `id` is not a valid rule ID or the files
+/// exist and `force` is not set, and `CliError::Io` if they cannot be written.
+pub async fn execute(
+    id: &str,
+    template: RuleTemplate,
+    dir: &Path,
+    force: bool,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let report = write_scaffold(id, template, dir, force).await?;
+    info!(rule = %report.rule_file.display(), "rule scaffold written");
+    writer.render(&report)?;
+    Ok(())
+}
+
+/// Generate, self-test and write the rule and its sample log.
+async fn write_scaffold(
+    id: &str,
+    template: RuleTemplate,
+    dir: &Path,
+    force: bool,
+) -> Result<RuleScaffoldReport, CliError> {
+    validate_id(id)?;
+
+    let rule_file = dir.join(format!("{}.yaml", id));
+    let sample_log = dir.join(format!("{}.sample.log", id));
+    if !force && let Some(existing) = [&rule_file, &sample_log].into_iter().find(|p| p.exists()) {
+        return Err(CliError::Command(format!(
+            "{} already exists (use --force to overwrite)",
+            existing.display()
+        )));
+    }
+
+    let scaffold = render(template, id);
+    let (lines, matched) = self_test(&scaffold, &sample_log).await?;
+
+    tokio::fs::create_dir_all(dir).await?;
+    tokio::fs::write(&rule_file, &scaffold.rule).await?;
+    tokio::fs::write(&sample_log, &scaffold.sample).await?;
+
+    Ok(RuleScaffoldReport {
+        rule_id: id.to_owned(),
+        template,
+        rule_file,
+        sample_log,
+        lines,
+        matched,
+    })
+}
+
+/// Rule IDs become file names: letters, digits, `_` and `-` only.
+fn validate_id(id: &str) -> Result<(), CliError> {
+    let valid = !id.is_empty()
+        && id.len() <= 128
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(CliError::Command(format!(
+            "invalid rule id '{}': use letters, digits, '_' and '-' (at most 128)",
+            id
+        )))
+    }
+}
+
+/// Load the generated rule and count the sample lines it matches.
+async fn self_test(scaffold: &Scaffold, sample_log: &Path) -> Result<(usize, usize), CliError> {
+    let rule = RuleLoader::parse_yaml(&scaffold.rule, "template")?;
+    let mut matcher = RuleMatcher::new();
+    matcher.compile_rule(&rule)?;
+    let report = test_rule(
+        &rule,
+        &matcher,
+        sample_log,
+        BufReader::new(scaffold.sample.as_bytes()),
+    )
+    .await?;
+    Ok((report.lines, report.matched))
+}
+
+/// Generated rule and sample log contents.
+struct Scaffold {
+    rule: String,
+    sample: String,
+}
+
+fn render(template: RuleTemplate, id: &str) -> Scaffold {
+    match template {
+        RuleTemplate::SshBruteforce => Scaffold {
+            rule: ssh_bruteforce_rule(id),
+            sample: SSH_BRUTEFORCE_SAMPLE.to_owned(),
+        },
+        RuleTemplate::PortScan => Scaffold {
+            rule: port_scan_rule(id),
+            sample: PORT_SCAN_SAMPLE.to_owned(),
+        },
+        RuleTemplate::Custom => Scaffold {
+            rule: custom_rule(id),
+            sample: CUSTOM_SAMPLE.to_owned(),
+        },
+    }
+}
+
+/// Comment block shared by every template.
+fn header(id: &str, template: &str, summary: &str) -> String {
+    format!(
+        "# {summary}
+#
+# Generated by `ironpost rules new --template {template}`. Try it against the
+# paired sample log, adjust both, and re-run until the right lines match:
+#
+#   ironpost rules test {id}.yaml {id}.sample.log
+#
+# `rules test` checks the conditions only; the threshold is applied by the
+# daemon. When the rule is ready, set `status: enabled` and copy it to
+# {DAEMON_RULE_DIR} (or your [content] bundle).
+
+# Unique rule ID, shown in alerts and `ironpost rules list`
+id: {id}
+"
+    )
+}
+
+/// Comment block documenting `detection.conditions`.
+const CONDITIONS_HELP: &str = "\
+  # Every condition must match (AND). `field` is one of hostname, process,
+  # message, source, or an extra field of the entry: JSON keys other than
+  # timestamp/host/process/message/level, `pid` for syslog, and RFC 5424
+  # structured data as `sd_<id>_<param>`.
+  # `modifier`: exact (default), contains, startswith, endswith, regex, cidr.
+";
+
+fn ssh_bruteforce_rule(id: &str) -> String {
+    format!(
+        "{header}title: SSH brute force attempt
+description: Repeated failed SSH logins from the same source address
+# Info, Low, Medium, High or Critical
+severity: High
+# enabled, disabled or test; the daemon only evaluates enabled rules
+status: test
+
+detection:
+{CONDITIONS_HELP}  conditions:
+    - field: process
+      value: sshd
+    - field: message
+      modifier: regex
+      value: '^(Failed password|Invalid user) '
+
+  # Alert when `count` matching entries share the same `field` value within
+  # `timeframe_secs` seconds. Entries without the field are not counted.
+  #   count: 5,  timeframe_secs: 300   -> 5 failures in 5 minutes (noisy hosts)
+  #   count: 20, timeframe_secs: 3600  -> slow guessing spread over an hour
+  threshold:
+    field: source_ip
+    count: 5
+    timeframe_secs: 300
+
+tags:
+  - authentication
+  - brute_force
+  - ssh
+",
+        header = header(
+            id,
+            "ssh-bruteforce",
+            "SSH brute force: repeated authentication failures from one address"
+        ),
+    )
+}
+
+fn port_scan_rule(id: &str) -> String {
+    format!(
+        "{header}title: Port scan blocked by the host firewall
+description: Many firewall drops from the same source address in a short time
+# Info, Low, Medium, High or Critical
+severity: Medium
+# enabled, disabled or test; the daemon only evaluates enabled rules
+status: test
+
+detection:
+{CONDITIONS_HELP}  conditions:
+    - field: process
+      value: kernel
+    # UFW logs `[UFW BLOCK]`; for plain iptables use the --log-prefix you set
+    - field: message
+      modifier: regex
+      value: '\\[UFW BLOCK\\] .*DPT=[0-9]+'
+
+  # Thresholds count matching entries, not distinct ports: every blocked
+  # packet from the source counts towards `count`.
+  #   count: 20,  timeframe_secs: 60   -> fast scans (nmap defaults)
+  #   count: 100, timeframe_secs: 900  -> slow scans that stay under rate limits
+  threshold:
+    field: source_ip
+    count: 20
+    timeframe_secs: 60
+
+tags:
+  - network
+  - reconnaissance
+  - scan
+",
+        header = header(
+            id,
+            "port-scan",
+            "Port scan: bursts of firewall drops from one address"
+        ),
+    )
+}
+
+fn custom_rule(id: &str) -> String {
+    format!(
+        "{header}title: Describe what the rule detects
+description: Longer explanation shown with the alert
+# Info, Low, Medium, High or Critical
+severity: Low
+# enabled, disabled or test; the daemon only evaluates enabled rules
+status: test
+
+detection:
+{CONDITIONS_HELP}  conditions:
+    - field: process
+      value: my-app
+    - field: message
+      modifier: contains
+      value: permission denied
+    # More examples:
+    # - field: hostname
+    #   modifier: startswith
+    #   value: db-
+    # - field: user
+    #   modifier: regex
+    #   value: '^(root|admin)$'
+    # - field: source_ip
+    #   modifier: cidr
+    #   value: 10.0.0.0/8, 192.168.0.0/16
+
+  # Optional: only alert when `count` matches share the same `field` value
+  # within `timeframe_secs` seconds. Without a threshold, every match alerts.
+  # threshold:
+  #   field: user
+  #   count: 3
+  #   timeframe_secs: 600
+
+tags:
+  - custom
+",
+        header = header(
+            id,
+            "custom",
+            "Custom rule: replace the conditions with what you want to detect"
+        ),
+    )
+}
+
+/// Six failures from one address, one successful login, one unrelated line.
+const SSH_BRUTEFORCE_SAMPLE: &str = r#"{"timestamp":"2026-01-15T03:12:01Z","host":"bastion-01","process":"sshd","level":"warn","message":"Failed password for root from 203.0.113.7 port 52211 ssh2","source_ip":"203.0.113.7"}
+{"timestamp":"2026-01-15T03:12:03Z","host":"bastion-01","process":"sshd","level":"warn","message":"Failed password for root from 203.0.113.7 port 52213 ssh2","source_ip":"203.0.113.7"}
+{"timestamp":"2026-01-15T03:12:05Z","host":"bastion-01","process":"sshd","level":"warn","message":"Invalid user admin from 203.0.113.7 port 52220","source_ip":"203.0.113.7"}
+{"timestamp":"2026-01-15T03:12:06Z","host":"bastion-01","process":"sshd","level":"info","message":"Accepted publickey for deploy from 198.51.100.4 port 40022 ssh2","source_ip":"198.51.100.4"}
+{"timestamp":"2026-01-15T03:12:08Z","host":"bastion-01","process":"sshd","level":"warn","message":"Failed password for invalid user admin from 203.0.113.7 port 52224 ssh2","source_ip":"203.0.113.7"}
+{"timestamp":"2026-01-15T03:12:10Z","host":"bastion-01","process":"sshd","level":"warn","message":"Failed password for root from 203.0.113.7 port 52230 ssh2","source_ip":"203.0.113.7"}
+{"timestamp":"2026-01-15T03:12:12Z","host":"bastion-01","process":"sshd","level":"warn","message":"Failed password for ubuntu from 203.0.113.7 port 52236 ssh2","source_ip":"203.0.113.7"}
+{"timestamp":"2026-01-15T03:13:00Z","host":"bastion-01","process":"CRON","level":"info","message":"pam_unix(cron:session): session opened for user root","source_ip":""}
+"#;
+
+/// Six drops to different ports from one address, one allowed packet, one
+/// unrelated kernel line.
+const PORT_SCAN_SAMPLE: &str = r#"{"timestamp":"2026-01-15T04:00:01Z","host":"edge-01","process":"kernel","level":"warn","message":"[UFW BLOCK] IN=eth0 OUT= SRC=198.51.100.23 DST=10.0.0.5 PROTO=TCP SPT=51234 DPT=22","source_ip":"198.51.100.23"}
+{"timestamp":"2026-01-15T04:00:01Z","host":"edge-01","process":"kernel","level":"warn","message":"[UFW BLOCK] IN=eth0 OUT= SRC=198.51.100.23 DST=10.0.0.5 PROTO=TCP SPT=51234 DPT=23","source_ip":"198.51.100.23"}
+{"timestamp":"2026-01-15T04:00:01Z","host":"edge-01","process":"kernel","level":"warn","message":"[UFW BLOCK] IN=eth0 OUT= SRC=198.51.100.23 DST=10.0.0.5 PROTO=TCP SPT=51234 DPT=25","source_ip":"198.51.100.23"}
+{"timestamp":"2026-01-15T04:00:02Z","host":"edge-01","process":"kernel","level":"info","message":"[UFW ALLOW] IN=eth0 OUT= SRC=198.51.100.40 DST=10.0.0.5 PROTO=TCP SPT=40100 DPT=443","source_ip":"198.51.100.40"}
+{"timestamp":"2026-01-15T04:00:02Z","host":"edge-01","process":"kernel","level":"warn","message":"[UFW BLOCK] IN=eth0 OUT= SRC=198.51.100.23 DST=10.0.0.5 PROTO=TCP SPT=51234 DPT=3306","source_ip":"198.51.100.23"}
+{"timestamp":"2026-01-15T04:00:02Z","host":"edge-01","process":"kernel","level":"warn","message":"[UFW BLOCK] IN=eth0 OUT= SRC=198.51.100.23 DST=10.0.0.5 PROTO=TCP SPT=51234 DPT=5432","source_ip":"198.51.100.23"}
+{"timestamp":"2026-01-15T04:00:03Z","host":"edge-01","process":"kernel","level":"warn","message":"[UFW BLOCK] IN=eth0 OUT= SRC=198.51.100.23 DST=10.0.0.5 PROTO=TCP SPT=51234 DPT=8080","source_ip":"198.51.100.23"}
+{"timestamp":"2026-01-15T04:00:05Z","host":"edge-01","process":"kernel","level":"info","message":"eth0: link up, 1000Mbps, full-duplex","source_ip":""}
+"#;
+
+/// Two matching lines and two near misses.
+const CUSTOM_SAMPLE: &str = r#"{"timestamp":"2026-01-15T05:00:00Z","host":"app-01","process":"my-app","level":"error","message":"open /srv/data/report.csv: permission denied","user":"www-data"}
+{"timestamp":"2026-01-15T05:00:04Z","host":"app-01","process":"my-app","level":"info","message":"request completed in 12ms","user":"www-data"}
+{"timestamp":"2026-01-15T05:00:09Z","host":"app-01","process":"other-app","level":"error","message":"open /tmp/x: permission denied","user":"nobody"}
+{"timestamp":"2026-01-15T05:00:12Z","host":"app-01","process":"my-app","level":"error","message":"write /srv/data/cache: permission denied","user":"root"}
+"#;
+
+/// Result of `rules new`.
+#[derive(Serialize)]
+pub struct RuleScaffoldReport {
+    /// Rule ID
+    pub rule_id: String,
+    /// Template the rule was generated from
+    pub template: RuleTemplate,
+    /// Written rule file
+    pub rule_file: PathBuf,
+    /// Written sample log
+    pub sample_log: PathBuf,
+    /// Lines in the sample log
+    pub lines: usize,
+    /// Sample lines matching the rule's conditions
+    pub matched: usize,
+}
+
+impl Render for RuleScaffoldReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        let template = serde_json::to_value(self.template)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_owned))
+            .unwrap_or_default();
+        writeln!(
+            w,
+            "{} rule {} (template: {})",
+            "Created".green().bold(),
+            self.rule_id.bold(),
+            template
+        )?;
+        writeln!(w, "  rule:   {}", self.rule_file.display())?;
+        writeln!(
+            w,
+            "  sample: {} ({} of {} lines match)",
+            self.sample_log.display(),
+            self.matched,
+            self.lines
+        )?;
+        writeln!(w)?;
+        writeln!(
+            w,
+            "Next: ironpost rules test {} {}",
+            self.rule_file.display(),
+            self.sample_log.display()
+        )?;
+        writeln!(
+            w,
+            "      set `status: enabled` and copy the rule to {}",
+            DAEMON_RULE_DIR
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATES: [(RuleTemplate, usize, usize); 3] = [
+        (RuleTemplate::SshBruteforce, 8, 6),
+        (RuleTemplate::PortScan, 8, 6),
+        (RuleTemplate::Custom, 4, 2),
+    ];
+
+    #[tokio::test]
+    async fn test_templates_load_and_match_their_samples() {
+        for (template, lines, matched) in TEMPLATES {
+            let scaffold = render(template, "my_rule");
+
+            let result = self_test(&scaffold, Path::new("my_rule.sample.log"))
+                .await
+                .unwrap_or_else(|e| panic!("{:?} should load: {}", template, e));
+
+            assert_eq!(result, (lines, matched), "{:?}", template);
+            let rule = RuleLoader::parse_yaml(&scaffold.rule, "test").expect("valid rule");
+            assert_eq!(rule.id, "my_rule");
+        }
+    }
+
+    #[test]
+    fn test_threshold_templates_group_by_a_sample_field() {
+        for template in [RuleTemplate::SshBruteforce, RuleTemplate::PortScan] {
+            let scaffold = render(template, "r");
+            let rule = RuleLoader::parse_yaml(&scaffold.rule, "test").expect("valid rule");
+            let threshold = rule.detection.threshold.expect("template has a threshold");
+            assert!(
+                scaffold
+                    .sample
+                    .contains(&format!("\"{}\":", threshold.field)),
+                "sample entries should carry the threshold field"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_id() {
+        assert!(validate_id("ssh_guard-2").is_ok());
+        assert!(validate_id("").is_err());
+        assert!(validate_id("../etc/passwd").is_err());
+        assert!(validate_id("has space").is_err());
+        assert!(validate_id(&"a".repeat(129)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_scaffold_writes_pair_and_refuses_overwrite() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let rules_dir = dir.path().join("rules");
+
+        let report = write_scaffold("ssh_guard", RuleTemplate::SshBruteforce, &rules_dir, false)
+            .await
+            .expect("scaffold should be written");
+
+        assert_eq!(report.matched, 6);
+        let rule = std::fs::read_to_string(rules_dir.join("ssh_guard.yaml")).expect("rule file");
+        assert!(rule.contains("id: ssh_guard"));
+        assert!(rules_dir.join("ssh_guard.sample.log").is_file());
+
+        let again = write_scaffold("ssh_guard", RuleTemplate::Custom, &rules_dir, false).await;
+        assert!(matches!(again, Err(CliError::Command(_))));
+        write_scaffold("ssh_guard", RuleTemplate::Custom, &rules_dir, true)
+            .await
+            .expect("--force should overwrite");
+    }
+
+    #[test]
+    fn test_report_render_text() {
+        let report = RuleScaffoldReport {
+            rule_id: "scan".to_owned(),
+            template: RuleTemplate::PortScan,
+            rule_file: PathBuf::from("scan.yaml"),
+            sample_log: PathBuf::from("scan.sample.log"),
+            lines: 8,
+            matched: 6,
+        };
+
+        let mut buf = Vec::new();
+        report
+            .render_text(&mut buf)
+            .expect("text rendering should succeed");
+        let output = String::from_utf8(buf).expect("valid UTF-8");
+
+        assert!(output.contains("template: port-scan"));
+        assert!(output.contains("6 of 8 lines match"));
+        assert!(output.contains("ironpost rules test scan.yaml scan.sample.log"));
+    }
+}
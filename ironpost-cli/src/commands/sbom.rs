@@ -0,0 +1,1003 @@
+//! `ironpost sbom` command handler
+//!
+//! `generate` parses the supported lockfiles (`Cargo.lock`,
+//! `package-lock.json`) at a path and writes a single CycloneDX or SPDX JSON
+//! document covering all of their packages. Directories are searched one
+//! level deep, like the scanner.
+//!
+//! `scan` matches the packages of lockfiles or an existing CycloneDX/SPDX
+//! document against the local vulnerability database and gates on the
+//! findings for CI use.
+//!
+//! `db update` refreshes the local vulnerability database (see
+//! [`sbom_db`]).
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Serialize;
+use tracing::info;
+
+use ironpost_core::config::IronpostConfig;
+use ironpost_core::types::Severity;
+use ironpost_sbom_scanner::{
+    CargoLockParser, Ecosystem, LockfileDetector, LockfileParser, NpmLockParser, PackageGraph,
+    SbomFormat, SbomGenerator, SbomScannerConfig, VulnDb, VulnMatcher, read_sbom,
+};
+
+use crate::cli::{FailOn, SbomAction, SbomArgs, SbomDbAction, SbomDocumentFormat, SeverityLevel};
+use crate::commands::sbom_db;
+use crate::commands::scan::VulnSummary;
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Execute the `sbom` command.
+pub async fn execute(
+    args: SbomArgs,
+    config_path: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    match args.action {
+        SbomAction::Generate { path, format, out } => {
+            execute_generate(&path, format, out, writer).await
+        }
+        SbomAction::Scan {
+            path,
+            min_severity,
+            fail_on,
+            vuln_db,
+        } => {
+            let vuln_db = match vuln_db {
+                Some(dir) => dir,
+                None => PathBuf::from(IronpostConfig::load(config_path).await?.sbom.vuln_db_path),
+            };
+            execute_scan(&path, min_severity, fail_on, &vuln_db, writer).await
+        }
+        SbomAction::Db {
+            action:
+                SbomDbAction::Update {
+                    offline,
+                    sources,
+                    vuln_db,
+                },
+        } => {
+            let vuln_db = match vuln_db {
+                Some(dir) => dir,
+                None => PathBuf::from(IronpostConfig::load(config_path).await?.sbom.vuln_db_path),
+            };
+            sbom_db::execute_update(offline, sources, vuln_db, writer).await
+        }
+    }
+}
+
+/// Execute the sbom generate subcommand.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if no lockfile is found or a lockfile cannot
+/// be parsed, and `CliError::Io` if the document cannot be written.
+async fn execute_generate(
+    path: &Path,
+    format: SbomDocumentFormat,
+    output: Option<PathBuf>,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    info!(path = %path.display(), format = ?format, "generating SBOM");
+
+    let graphs = load_package_graphs(path).await?;
+    let merged = merge_graphs(path, &graphs);
+
+    let sbom_format = match format {
+        SbomDocumentFormat::Cyclonedx => SbomFormat::CycloneDx,
+        SbomDocumentFormat::Spdx => SbomFormat::Spdx,
+    };
+    let document = SbomGenerator::new(sbom_format)
+        .generate(&merged)
+        .map_err(|e| CliError::Command(format!("failed to generate SBOM: {}", e)))?;
+
+    let output = output.unwrap_or_else(|| PathBuf::from(default_output(format)));
+    tokio::fs::write(&output, document.content.as_bytes()).await?;
+    info!(output = %output.display(), components = document.component_count, "SBOM written");
+
+    let report = build_generate_report(path, format, output, &graphs, document.component_count);
+    writer.render(&report)
+}
+
+/// Default output file name for `format`.
+fn default_output(format: SbomDocumentFormat) -> &'static str {
+    match format {
+        SbomDocumentFormat::Cyclonedx => "sbom.cdx.json",
+        SbomDocumentFormat::Spdx => "sbom.spdx.json",
+    }
+}
+
+/// Parse the lockfile at `path`, or the known lockfiles directly inside it.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the path cannot be read, is not a supported
+/// lockfile, contains no lockfile, or a lockfile fails to parse.
+pub(crate) async fn load_package_graphs(path: &Path) -> Result<Vec<PackageGraph>, CliError> {
+    let detector = LockfileDetector::new();
+    let metadata = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| CliError::Command(format!("{}: {}", path.display(), e)))?;
+
+    let lockfiles = if metadata.is_dir() {
+        let mut found = Vec::new();
+        for (name, _) in detector.known_filenames() {
+            let candidate = path.join(name);
+            if tokio::fs::metadata(&candidate)
+                .await
+                .is_ok_and(|m| m.is_file())
+            {
+                found.push(candidate);
+            }
+        }
+        found
+    } else if detector.is_lockfile(path) {
+        vec![path.to_path_buf()]
+    } else {
+        return Err(CliError::Command(format!(
+            "{}: not a supported lockfile (expected: {})",
+            path.display(),
+            known_names(&detector)
+        )));
+    };
+
+    if lockfiles.is_empty() {
+        return Err(CliError::Command(format!(
+            "no supported lockfiles ({}) found in {}",
+            known_names(&detector),
+            path.display()
+        )));
+    }
+
+    let limits = SbomScannerConfig::default();
+    let parsers: [Box<dyn LockfileParser>; 2] =
+        [Box::new(CargoLockParser), Box::new(NpmLockParser)];
+    let mut graphs = Vec::with_capacity(lockfiles.len());
+
+    for lockfile in &lockfiles {
+        let lockfile_name = lockfile.display().to_string();
+        let size = tokio::fs::metadata(lockfile).await?.len();
+        if usize::try_from(size).unwrap_or(usize::MAX) > limits.max_file_size {
+            return Err(CliError::Command(format!(
+                "{}: lockfile too large ({} bytes, max {})",
+                lockfile_name, size, limits.max_file_size
+            )));
+        }
+
+        let parser = parsers
+            .iter()
+            .find(|p| p.can_parse(lockfile))
+            .ok_or_else(|| CliError::Command(format!("{}: no parser available", lockfile_name)))?;
+        let content = tokio::fs::read_to_string(lockfile).await?;
+        let graph = parser
+            .parse(&content, &lockfile_name)
+            .map_err(|e| CliError::Command(format!("failed to parse {}: {}", lockfile_name, e)))?;
+
+        if graph.package_count() > limits.max_packages {
+            return Err(CliError::Command(format!(
+                "{}: too many packages ({}, max {})",
+                lockfile_name,
+                graph.package_count(),
+                limits.max_packages
+            )));
+        }
+
+        info!(lockfile = %lockfile_name, packages = graph.package_count(), "parsed lockfile");
+        graphs.push(graph);
+    }
+
+    Ok(graphs)
+}
+
+/// Load the packages to scan: an SBOM document, a lockfile, or a directory.
+///
+/// Files that are not known lockfiles are read as CycloneDX/SPDX JSON.
+async fn load_scan_input(path: &Path) -> Result<Vec<PackageGraph>, CliError> {
+    let metadata = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| CliError::Command(format!("{}: {}", path.display(), e)))?;
+    if metadata.is_dir() || LockfileDetector::new().is_lockfile(path) {
+        return load_package_graphs(path).await;
+    }
+
+    let limits = SbomScannerConfig::default();
+    if usize::try_from(metadata.len()).unwrap_or(usize::MAX) > limits.max_file_size {
+        return Err(CliError::Command(format!(
+            "{}: SBOM document too large ({} bytes, max {})",
+            path.display(),
+            metadata.len(),
+            limits.max_file_size
+        )));
+    }
+    let display = path.display().to_string();
+    let content = tokio::fs::read_to_string(path).await?;
+    let graph = read_sbom(&content, &display).map_err(|e| CliError::Command(e.to_string()))?;
+    if graph.package_count() > limits.max_packages {
+        return Err(CliError::Command(format!(
+            "{}: too many packages ({}, max {})",
+            display,
+            graph.package_count(),
+            limits.max_packages
+        )));
+    }
+    Ok(vec![graph])
+}
+
+/// Comma-separated list of the lockfile names the detector knows.
+fn known_names(detector: &LockfileDetector) -> String {
+    detector
+        .known_filenames()
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Combine lockfile graphs into one, keeping the first package per PURL.
+fn merge_graphs(path: &Path, graphs: &[PackageGraph]) -> PackageGraph {
+    let mut seen = HashSet::new();
+    let mut packages = Vec::new();
+    let mut root_packages = Vec::new();
+
+    for graph in graphs {
+        for package in &graph.packages {
+            if seen.insert(package.purl.clone()) {
+                packages.push(package.clone());
+            }
+        }
+        root_packages.extend(graph.root_packages.iter().cloned());
+    }
+
+    PackageGraph {
+        source_file: path.display().to_string(),
+        // Each package carries its own ecosystem; the graph-level one is informational
+        ecosystem: graphs.first().map_or(Ecosystem::Cargo, |g| g.ecosystem),
+        packages,
+        root_packages,
+    }
+}
+
+/// Execute the sbom scan subcommand.
+///
+/// # Errors
+///
+/// Returns `CliError::Scan` (exit code 4) when the gate fails, and
+/// `CliError::Command` if the input or the vulnerability database cannot be
+/// loaded.
+async fn execute_scan(
+    path: &Path,
+    min_severity: SeverityLevel,
+    fail_on: Option<FailOn>,
+    vuln_db: &Path,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    info!(path = %path.display(), vuln_db = %vuln_db.display(), "scanning for vulnerabilities");
+
+    let graphs = load_scan_input(path).await?;
+
+    let db_dir = vuln_db.to_path_buf();
+    let db = tokio::task::spawn_blocking(move || VulnDb::load_from_dir(&db_dir))
+        .await
+        .map_err(|e| CliError::Command(format!("vulnerability database load failed: {}", e)))?
+        .map_err(|e| CliError::Command(e.to_string()))?;
+    if db.entry_count() == 0 {
+        return Err(CliError::Command(format!(
+            "vulnerability database {} is empty or missing",
+            vuln_db.display()
+        )));
+    }
+
+    let min_severity = Severity::from(min_severity);
+    let matcher = VulnMatcher::new(Arc::new(db), min_severity);
+    let threshold = match fail_on {
+        Some(FailOn::Never) => None,
+        Some(FailOn::Info) => Some(Severity::Info),
+        Some(FailOn::Low) => Some(Severity::Low),
+        Some(FailOn::Medium) => Some(Severity::Medium),
+        Some(FailOn::High) => Some(Severity::High),
+        Some(FailOn::Critical) => Some(Severity::Critical),
+        None => Some(min_severity),
+    };
+
+    let report = build_scan_report(path, &graphs, &matcher, threshold)?;
+    writer.render(&report)?;
+
+    if !report.gate.passed {
+        return Err(CliError::Scan(format!(
+            "{} findings at or above the fail-on severity",
+            report.gate.failing
+        )));
+    }
+
+    Ok(())
+}
+
+/// Match every graph and build the severity-sorted report with gate result.
+fn build_scan_report(
+    path: &Path,
+    graphs: &[PackageGraph],
+    matcher: &VulnMatcher,
+    fail_on: Option<Severity>,
+) -> Result<SbomScanReport, CliError> {
+    let mut findings = Vec::new();
+    let mut summary = VulnSummary::default();
+
+    for graph in graphs {
+        for finding in matcher.scan(graph)? {
+            let vuln = finding.vulnerability;
+            match vuln.severity {
+                Severity::Critical => summary.critical += 1,
+                Severity::High => summary.high += 1,
+                Severity::Medium => summary.medium += 1,
+                Severity::Low => summary.low += 1,
+                Severity::Info => summary.info += 1,
+            }
+            findings.push((
+                vuln.severity,
+                ScanFindingEntry {
+                    cve_id: vuln.cve_id,
+                    severity: format!("{:?}", vuln.severity),
+                    package: finding.matched_package.name.clone(),
+                    ecosystem: finding.matched_package.ecosystem.to_string(),
+                    installed_version: vuln.affected_version,
+                    fixed_version: vuln.fixed_version,
+                    dependency_path: dependency_path(graph, &finding.matched_package.name),
+                    source: finding.scan_source,
+                },
+            ));
+        }
+    }
+    summary.total = findings.len();
+
+    findings.sort_by(|(sa, a), (sb, b)| {
+        sb.cmp(sa)
+            .then_with(|| a.cve_id.cmp(&b.cve_id))
+            .then_with(|| a.package.cmp(&b.package))
+    });
+    let failing = fail_on.map_or(0, |t| findings.iter().filter(|(s, _)| *s >= t).count());
+
+    Ok(SbomScanReport {
+        path: path.display().to_string(),
+        sources: graphs.iter().map(|g| g.source_file.clone()).collect(),
+        total_packages: graphs.iter().map(PackageGraph::package_count).sum(),
+        vulnerabilities: summary,
+        findings: findings.into_iter().map(|(_, f)| f).collect(),
+        gate: GateResult {
+            fail_on: fail_on.map(|s| format!("{:?}", s)),
+            failing,
+            passed: failing == 0,
+        },
+    })
+}
+
+/// Shortest chain of package names from a root package to `target`.
+///
+/// Roots are the graph's root packages, or the packages nothing depends on
+/// when those are not in the graph. Returns just `[target]` when no chain is
+/// known.
+fn dependency_path(graph: &PackageGraph, target: &str) -> Vec<String> {
+    let by_name: HashMap<&str, &[String]> = graph
+        .packages
+        .iter()
+        .map(|p| (p.name.as_str(), p.dependencies.as_slice()))
+        .collect();
+
+    let mut roots: Vec<&str> = graph
+        .root_packages
+        .iter()
+        .map(String::as_str)
+        .filter(|r| by_name.contains_key(r))
+        .collect();
+    if roots.is_empty() {
+        let dependents: HashSet<&str> = graph
+            .packages
+            .iter()
+            .flat_map(|p| p.dependencies.iter().map(String::as_str))
+            .collect();
+        roots = graph
+            .packages
+            .iter()
+            .map(|p| p.name.as_str())
+            .filter(|n| !dependents.contains(n))
+            .collect();
+    }
+
+    let mut parents: HashMap<&str, &str> = HashMap::new();
+    let mut visited: HashSet<&str> = roots.iter().copied().collect();
+    let mut queue: VecDeque<&str> = roots.into_iter().collect();
+    while let Some(name) = queue.pop_front() {
+        if name == target {
+            let mut path = vec![name.to_owned()];
+            let mut current = name;
+            while let Some(parent) = parents.get(current) {
+                path.push((*parent).to_owned());
+                current = parent;
+            }
+            path.reverse();
+            return path;
+        }
+        for dep in by_name.get(name).copied().unwrap_or_default() {
+            if visited.insert(dep.as_str()) {
+                parents.insert(dep.as_str(), name);
+                queue.push_back(dep.as_str());
+            }
+        }
+    }
+
+    vec![target.to_owned()]
+}
+
+/// Build the generate report with per-ecosystem package counts.
+fn build_generate_report(
+    path: &Path,
+    format: SbomDocumentFormat,
+    output: PathBuf,
+    graphs: &[PackageGraph],
+    components: usize,
+) -> SbomGenerateReport {
+    let mut by_ecosystem: BTreeMap<String, EcosystemSummary> = BTreeMap::new();
+    for graph in graphs {
+        let ecosystem = graph.ecosystem.to_string();
+        let summary = by_ecosystem
+            .entry(ecosystem.clone())
+            .or_insert_with(|| EcosystemSummary {
+                ecosystem,
+                lockfiles: 0,
+                packages: 0,
+            });
+        summary.lockfiles += 1;
+        summary.packages += graph.package_count();
+    }
+
+    SbomGenerateReport {
+        path: path.display().to_string(),
+        format,
+        output: output.display().to_string(),
+        lockfiles: graphs.iter().map(|g| g.source_file.clone()).collect(),
+        components,
+        ecosystems: by_ecosystem.into_values().collect(),
+    }
+}
+
+/// Result of `sbom generate`.
+#[derive(Serialize)]
+pub struct SbomGenerateReport {
+    /// Input lockfile or directory
+    pub path: String,
+    /// Document format
+    pub format: SbomDocumentFormat,
+    /// File the document was written to
+    pub output: String,
+    /// Lockfiles included in the document
+    pub lockfiles: Vec<String>,
+    /// Components in the document (duplicates across lockfiles removed)
+    pub components: usize,
+    /// Package counts per ecosystem
+    pub ecosystems: Vec<EcosystemSummary>,
+}
+
+/// Packages found for one ecosystem.
+#[derive(Serialize)]
+pub struct EcosystemSummary {
+    /// Ecosystem name (cargo, npm)
+    pub ecosystem: String,
+    /// Number of lockfiles
+    pub lockfiles: usize,
+    /// Packages across those lockfiles
+    pub packages: usize,
+}
+
+/// Result of `sbom scan`.
+#[derive(Serialize)]
+pub struct SbomScanReport {
+    /// Scanned lockfile, SBOM document, or directory
+    pub path: String,
+    /// Lockfiles or documents the packages came from
+    pub sources: Vec<String>,
+    /// Packages checked
+    pub total_packages: usize,
+    /// Reported findings by severity
+    pub vulnerabilities: VulnSummary,
+    /// Reported findings, most severe first
+    pub findings: Vec<ScanFindingEntry>,
+    /// CI gate outcome
+    pub gate: GateResult,
+}
+
+/// A vulnerable package.
+#[derive(Serialize)]
+pub struct ScanFindingEntry {
+    /// CVE identifier
+    pub cve_id: String,
+    /// Vulnerability severity level
+    pub severity: String,
+    /// Package name
+    pub package: String,
+    /// Package ecosystem (cargo, npm, ...)
+    pub ecosystem: String,
+    /// Installed version
+    pub installed_version: String,
+    /// Fixed version (None if no fix available)
+    pub fixed_version: Option<String>,
+    /// Package names from a root package down to the vulnerable one
+    pub dependency_path: Vec<String>,
+    /// Lockfile or document the package came from
+    pub source: String,
+}
+
+/// Outcome of the `--fail-on` gate.
+#[derive(Serialize)]
+pub struct GateResult {
+    /// Severity the gate fails at (None with `--fail-on never`)
+    pub fail_on: Option<String>,
+    /// Findings at or above `fail_on`
+    pub failing: usize,
+    /// Whether the scan passes the gate
+    pub passed: bool,
+}
+
+impl Render for SbomScanReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(w, "Scan: {}", self.path.bold())?;
+        writeln!(
+            w,
+            "Packages: {} from {} source(s)",
+            self.total_packages,
+            self.sources.len()
+        )?;
+
+        let vuln_str = format!(
+            "{} total (C:{} H:{} M:{} L:{} I:{})",
+            self.vulnerabilities.total,
+            self.vulnerabilities.critical,
+            self.vulnerabilities.high,
+            self.vulnerabilities.medium,
+            self.vulnerabilities.low,
+            self.vulnerabilities.info
+        );
+        if self.vulnerabilities.total > 0 {
+            writeln!(w, "Vulnerabilities: {}", vuln_str.red().bold())?;
+        } else {
+            writeln!(w, "Vulnerabilities: {}", vuln_str.green().bold())?;
+        }
+        writeln!(w)?;
+
+        if self.findings.is_empty() {
+            writeln!(w, "{}", "No vulnerabilities found.".green())?;
+        } else {
+            writeln!(
+                w,
+                "{:<10} {:<18} {:<25} {:<12} {:<12} Path",
+                "Severity", "CVE", "Package", "Installed", "Fixed"
+            )?;
+            writeln!(w, "{}", "-".repeat(100))?;
+
+            for f in &self.findings {
+                let severity_colored = match f.severity.as_str() {
+                    "Critical" => f.severity.red().bold(),
+                    "High" => f.severity.red(),
+                    "Medium" => f.severity.yellow(),
+                    "Info" => f.severity.dimmed(),
+                    _ => f.severity.normal(),
+                };
+
+                writeln!(
+                    w,
+                    "{:<10} {:<18} {:<25} {:<12} {:<12} {}",
+                    severity_colored,
+                    f.cve_id,
+                    f.package,
+                    f.installed_version,
+                    f.fixed_version.as_deref().unwrap_or("N/A"),
+                    f.dependency_path.join(" > ")
+                )?;
+            }
+        }
+
+        writeln!(w)?;
+        match (&self.gate.fail_on, self.gate.passed) {
+            (None, _) => writeln!(w, "Gate: {}", "disabled (--fail-on never)".dimmed())?,
+            (Some(level), true) => writeln!(
+                w,
+                "Gate: {} (no findings at or above {})",
+                "passed".green().bold(),
+                level
+            )?,
+            (Some(level), false) => writeln!(
+                w,
+                "Gate: {} ({} findings at or above {})",
+                "failed".red().bold(),
+                self.gate.failing,
+                level
+            )?,
+        }
+
+        Ok(())
+    }
+}
+
+impl Render for SbomGenerateReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        let format = match self.format {
+            SbomDocumentFormat::Cyclonedx => "CycloneDX 1.5",
+            SbomDocumentFormat::Spdx => "SPDX 2.3",
+        };
+        writeln!(w, "SBOM: {} ({})", self.output.bold(), format)?;
+        writeln!(w, "Source: {}", self.path)?;
+        writeln!(w, "Components: {}", self.components)?;
+        writeln!(w)?;
+
+        writeln!(
+            w,
+            "{:<12} {:>10} {:>10}",
+            "Ecosystem", "Lockfiles", "Packages"
+        )?;
+        writeln!(w, "{}", "-".repeat(34))?;
+        for e in &self.ecosystems {
+            writeln!(
+                w,
+                "{:<12} {:>10} {:>10}",
+                e.ecosystem, e.lockfiles, e.packages
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_sbom_scanner::{Package, VersionRange, VulnDbEntry};
+
+    const CARGO_LOCK: &str = r#"
+version = 3
+
+[[package]]
+name = "serde"
+version = "1.0.204"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "bc76f558e0cbb2a839d37354c575f1dc3fdc6546b5be373ba43d95f231bf7c12"
+
+[[package]]
+name = "tokio"
+version = "1.38.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    const NPM_LOCK: &str = r#"{
+  "name": "web",
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "web", "version": "1.0.0" },
+    "node_modules/lodash": { "version": "4.17.21" }
+  }
+}"#;
+
+    #[tokio::test]
+    async fn test_load_package_graphs_from_directory() {
+        // Given: A directory with a Cargo.lock, a package-lock.json and an unrelated file
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(dir.path().join("Cargo.lock"), CARGO_LOCK).expect("write lockfile");
+        std::fs::write(dir.path().join("package-lock.json"), NPM_LOCK).expect("write lockfile");
+        std::fs::write(dir.path().join("README.md"), "docs").expect("write file");
+
+        // When: Loading package graphs
+        let graphs = load_package_graphs(dir.path())
+            .await
+            .expect("lockfiles should parse");
+
+        // Then: Both lockfiles are parsed
+        assert_eq!(graphs.len(), 2);
+        let report = build_generate_report(
+            dir.path(),
+            SbomDocumentFormat::Cyclonedx,
+            PathBuf::from("sbom.cdx.json"),
+            &graphs,
+            0,
+        );
+        let ecosystems: Vec<&str> = report
+            .ecosystems
+            .iter()
+            .map(|e| e.ecosystem.as_str())
+            .collect();
+        assert_eq!(ecosystems, vec!["cargo", "npm"]);
+        assert_eq!(report.ecosystems[0].packages, 2);
+    }
+
+    #[tokio::test]
+    async fn test_load_package_graphs_rejects_unknown_file() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let file = dir.path().join("requirements.txt");
+        std::fs::write(&file, "requests==2.31.0").expect("write file");
+
+        let err = load_package_graphs(&file).await.expect_err("should fail");
+        assert!(err.to_string().contains("not a supported lockfile"));
+    }
+
+    #[tokio::test]
+    async fn test_load_package_graphs_empty_directory() {
+        let dir = tempfile::tempdir().expect("temp dir");
+
+        let err = load_package_graphs(dir.path())
+            .await
+            .expect_err("should fail");
+        assert!(err.to_string().contains("no supported lockfiles"));
+        assert_eq!(err.exit_code(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_merge_graphs_deduplicates_by_purl() {
+        // Given: The same lockfile loaded twice
+        let dir = tempfile::tempdir().expect("temp dir");
+        let lockfile = dir.path().join("Cargo.lock");
+        std::fs::write(&lockfile, CARGO_LOCK).expect("write lockfile");
+        let graph = load_package_graphs(&lockfile)
+            .await
+            .expect("lockfile should parse")
+            .remove(0);
+
+        // When: Merging
+        let merged = merge_graphs(dir.path(), &[graph.clone(), graph]);
+
+        // Then: Each package appears once
+        assert_eq!(merged.package_count(), 2);
+    }
+
+    #[test]
+    fn test_default_output_per_format() {
+        assert_eq!(
+            default_output(SbomDocumentFormat::Cyclonedx),
+            "sbom.cdx.json"
+        );
+        assert_eq!(default_output(SbomDocumentFormat::Spdx), "sbom.spdx.json");
+    }
+
+    #[test]
+    fn test_sbom_generate_report_render_text() {
+        let report = SbomGenerateReport {
+            path: "/src/app".to_owned(),
+            format: SbomDocumentFormat::Spdx,
+            output: "sbom.spdx.json".to_owned(),
+            lockfiles: vec!["/src/app/Cargo.lock".to_owned()],
+            components: 142,
+            ecosystems: vec![EcosystemSummary {
+                ecosystem: "cargo".to_owned(),
+                lockfiles: 1,
+                packages: 142,
+            }],
+        };
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("sbom.spdx.json"), "should show output file");
+        assert!(output.contains("SPDX 2.3"), "should show format");
+        assert!(output.contains("cargo"), "should show ecosystem");
+        assert!(output.contains("142"), "should show package count");
+    }
+
+    #[test]
+    fn test_sbom_generate_report_json() {
+        let report = SbomGenerateReport {
+            path: ".".to_owned(),
+            format: SbomDocumentFormat::Cyclonedx,
+            output: "sbom.cdx.json".to_owned(),
+            lockfiles: vec![],
+            components: 0,
+            ecosystems: vec![],
+        };
+
+        let json = serde_json::to_value(&report).expect("should serialize");
+        assert_eq!(json["format"], "cyclonedx");
+        assert_eq!(json["output"], "sbom.cdx.json");
+    }
+
+    fn package(name: &str, version: &str, dependencies: &[&str]) -> Package {
+        Package {
+            name: name.to_owned(),
+            version: version.to_owned(),
+            ecosystem: Ecosystem::Cargo,
+            purl: Package::make_purl(&Ecosystem::Cargo, name, version),
+            checksum: None,
+            dependencies: dependencies.iter().map(|d| (*d).to_owned()).collect(),
+        }
+    }
+
+    fn sample_graph(root_packages: Vec<String>) -> PackageGraph {
+        PackageGraph {
+            source_file: "Cargo.lock".to_owned(),
+            ecosystem: Ecosystem::Cargo,
+            packages: vec![
+                package("app", "0.1.0", &["hyper", "serde"]),
+                package("hyper", "0.14.10", &["h2"]),
+                package("h2", "0.3.0", &[]),
+                package("serde", "1.0.204", &[]),
+            ],
+            root_packages,
+        }
+    }
+
+    fn entry(cve_id: &str, package: &str, fixed: &str, severity: Severity) -> VulnDbEntry {
+        VulnDbEntry {
+            cve_id: cve_id.to_owned(),
+            package: package.to_owned(),
+            ecosystem: Ecosystem::Cargo,
+            affected_ranges: vec![VersionRange {
+                introduced: Some("0.0.0".to_owned()),
+                fixed: Some(fixed.to_owned()),
+            }],
+            fixed_version: Some(fixed.to_owned()),
+            severity,
+            description: String::new(),
+            published: "2024-01-01".to_owned(),
+        }
+    }
+
+    fn sample_matcher(min_severity: Severity) -> VulnMatcher {
+        let db = VulnDb::from_entries(vec![
+            entry("CVE-2024-0001", "h2", "0.3.26", Severity::High),
+            entry("CVE-2024-0002", "hyper", "0.14.26", Severity::Critical),
+            entry("CVE-2024-0003", "h2", "0.3.24", Severity::Low),
+        ]);
+        VulnMatcher::new(Arc::new(db), min_severity)
+    }
+
+    #[test]
+    fn test_dependency_path_from_root_packages() {
+        let graph = sample_graph(vec!["app".to_owned()]);
+
+        assert_eq!(dependency_path(&graph, "h2"), vec!["app", "hyper", "h2"]);
+        assert_eq!(dependency_path(&graph, "app"), vec!["app"]);
+    }
+
+    #[test]
+    fn test_dependency_path_without_roots_uses_undepended_packages() {
+        let graph = sample_graph(vec![]);
+
+        assert_eq!(dependency_path(&graph, "serde"), vec!["app", "serde"]);
+        assert_eq!(dependency_path(&graph, "missing"), vec!["missing"]);
+    }
+
+    #[test]
+    fn test_scan_report_sorted_by_severity_with_paths() {
+        // Given: A graph with three vulnerable packages
+        let graph = sample_graph(vec!["app".to_owned()]);
+        let matcher = sample_matcher(Severity::Info);
+
+        // When: Building the report with the gate at High
+        let report = build_scan_report(Path::new("."), &[graph], &matcher, Some(Severity::High))
+            .expect("scan should succeed");
+
+        // Then: Findings are most severe first and the gate fails on two of them
+        let cves: Vec<&str> = report.findings.iter().map(|f| f.cve_id.as_str()).collect();
+        assert_eq!(
+            cves,
+            vec!["CVE-2024-0002", "CVE-2024-0001", "CVE-2024-0003"]
+        );
+        assert_eq!(
+            report.findings[1].dependency_path,
+            vec!["app", "hyper", "h2"]
+        );
+        assert_eq!(report.findings[1].installed_version, "0.3.0");
+        assert_eq!(report.vulnerabilities.total, 3);
+        assert_eq!(report.vulnerabilities.critical, 1);
+        assert_eq!(report.gate.failing, 2);
+        assert!(!report.gate.passed);
+    }
+
+    #[test]
+    fn test_scan_report_min_severity_filters_findings() {
+        let graph = sample_graph(vec![]);
+        let matcher = sample_matcher(Severity::Critical);
+
+        let report =
+            build_scan_report(Path::new("."), &[graph], &matcher, Some(Severity::Critical))
+                .expect("scan should succeed");
+
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.gate.failing, 1);
+    }
+
+    #[test]
+    fn test_scan_report_gate_never_passes() {
+        let graph = sample_graph(vec![]);
+        let matcher = sample_matcher(Severity::Info);
+
+        let report = build_scan_report(Path::new("."), &[graph], &matcher, None)
+            .expect("scan should succeed");
+
+        assert_eq!(report.findings.len(), 3);
+        assert!(report.gate.passed);
+        let json = serde_json::to_value(&report).expect("should serialize");
+        assert!(json["gate"]["fail_on"].is_null());
+        assert_eq!(json["findings"][0]["severity"], "Critical");
+    }
+
+    #[tokio::test]
+    async fn test_load_scan_input_reads_sbom_document() {
+        // Given: A CycloneDX document generated from a lockfile
+        let dir = tempfile::tempdir().expect("temp dir");
+        let lockfile = dir.path().join("Cargo.lock");
+        std::fs::write(&lockfile, CARGO_LOCK).expect("write lockfile");
+        let graph = load_package_graphs(&lockfile)
+            .await
+            .expect("lockfile should parse")
+            .remove(0);
+        let document = SbomGenerator::new(SbomFormat::CycloneDx)
+            .generate(&graph)
+            .expect("generation should succeed");
+        let sbom = dir.path().join("sbom.cdx.json");
+        std::fs::write(&sbom, document.content).expect("write sbom");
+
+        // When: Loading the document as scan input
+        let graphs = load_scan_input(&sbom).await.expect("sbom should parse");
+
+        // Then: Its packages are read back
+        assert_eq!(graphs.len(), 1);
+        assert_eq!(graphs[0].package_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_load_scan_input_rejects_invalid_document() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let file = dir.path().join("sbom.json");
+        std::fs::write(&file, "{}").expect("write file");
+
+        let err = load_scan_input(&file).await.expect_err("should fail");
+        assert_eq!(err.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_sbom_scan_report_render_text() {
+        let report = SbomScanReport {
+            path: "/src/app".to_owned(),
+            sources: vec!["/src/app/Cargo.lock".to_owned()],
+            total_packages: 4,
+            vulnerabilities: VulnSummary {
+                total: 1,
+                high: 1,
+                ..Default::default()
+            },
+            findings: vec![ScanFindingEntry {
+                cve_id: "CVE-2024-0001".to_owned(),
+                severity: "High".to_owned(),
+                package: "h2".to_owned(),
+                ecosystem: "cargo".to_owned(),
+                installed_version: "0.3.0".to_owned(),
+                fixed_version: Some("0.3.26".to_owned()),
+                dependency_path: vec!["app".to_owned(), "hyper".to_owned(), "h2".to_owned()],
+                source: "/src/app/Cargo.lock".to_owned(),
+            }],
+            gate: GateResult {
+                fail_on: Some("High".to_owned()),
+                failing: 1,
+                passed: false,
+            },
+        };
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("CVE-2024-0001"), "should show CVE");
+        assert!(output.contains("0.3.26"), "should show fixed version");
+        assert!(output.contains("app > hyper > h2"), "should show path");
+        assert!(output.contains("failed"), "should show gate result");
+    }
+}
@@ -0,0 +1,632 @@
+//! `ironpost sbom db` -- manage the local vulnerability database
+//!
+//! `update` downloads the OSV, GHSA and NVD feeds (`--source` narrows the
+//! set) and merges them into `[sbom] vuln_db_path`; see
+//! [`sbom_db_sync`](super::sbom_db_sync) for what each feed contributes.
+//! `update --offline` instead imports a downloaded database archive
+//! (`.tar.gz` holding `cargo.json`, `npm.json`, `go.json`, `pip.json`).
+//!
+//! Either way the new files are written to a staging directory next to the
+//! database and loaded with the scanner before they replace the current
+//! database, so a failed update never leaves a half-written database behind.
+//! The replaced database is kept as `<vuln_db_path>.previous`.
+//!
+//! Progress goes to stderr; the summary report goes to the output writer.
+//! Scheduled `sbom_scan` tasks use the new database on their next run; the
+//! periodic scanner loads it when the daemon restarts.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use ironpost_sbom_scanner::{VulnDb, VulnDbEntry};
+
+use crate::cli::DbSource;
+use crate::commands::sbom_db_sync::{FeedEndpoints, FeedReport, sync_feeds};
+use crate::error::CliError;
+use crate::output::{OutputWriter, Render};
+
+/// Database files recognised inside an archive (one per ecosystem).
+const DB_FILES: &[&str] = &["cargo.json", "npm.json", "go.json", "pip.json"];
+
+/// Maximum size of a single database file, matching the scanner's limit.
+const MAX_DB_FILE_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Number of hex characters of the content digest used as version ID.
+const VERSION_LEN: usize = 16;
+
+/// Execute the sbom db update subcommand.
+///
+/// Imports `offline` if given, otherwise syncs `sources` (all feeds when
+/// empty).
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the archive is invalid, every feed request
+/// failed or the result does not load, and `CliError::Io` if the database
+/// directory cannot be written.
+pub async fn execute_update(
+    offline: Option<PathBuf>,
+    sources: Vec<DbSource>,
+    vuln_db: PathBuf,
+    writer: &OutputWriter,
+) -> Result<(), CliError> {
+    let report = match offline {
+        Some(archive) => {
+            info!(
+                archive = %archive.display(),
+                vuln_db = %vuln_db.display(),
+                "importing vulnerability database"
+            );
+            tokio::task::spawn_blocking(move || {
+                import_archive(&archive, &vuln_db, &mut std::io::stderr())
+            })
+            .await
+            .map_err(|e| {
+                CliError::Command(format!("vulnerability database import failed: {}", e))
+            })??
+        }
+        None => {
+            let sources = if sources.is_empty() {
+                DbSource::ALL.to_vec()
+            } else {
+                sources
+            };
+            info!(
+                sources = ?sources,
+                vuln_db = %vuln_db.display(),
+                "syncing vulnerability database"
+            );
+            sync_online(
+                &sources,
+                &FeedEndpoints::default(),
+                vuln_db,
+                &mut std::io::stderr(),
+            )
+            .await?
+        }
+    };
+
+    writer.render(&report)?;
+    Ok(())
+}
+
+/// Merge the feeds in `sources` into the database at `db_dir`.
+async fn sync_online(
+    sources: &[DbSource],
+    endpoints: &FeedEndpoints,
+    db_dir: PathBuf,
+    progress: &mut (dyn Write + Send),
+) -> Result<DbUpdateReport, CliError> {
+    let current = if db_dir.exists() {
+        let dir = db_dir.clone();
+        let db = tokio::task::spawn_blocking(move || VulnDb::load_from_dir(&dir))
+            .await
+            .map_err(|e| CliError::Command(format!("vulnerability database load failed: {}", e)))?
+            .map_err(|e| {
+                CliError::Command(format!(
+                    "{}: current vulnerability database is invalid ({}); \
+                     re-import it with --offline <ARCHIVE>",
+                    db_dir.display(),
+                    e
+                ))
+            })?;
+        writeln!(progress, "Current database: {} entries", db.entry_count())?;
+        db.entries().to_vec()
+    } else {
+        Vec::new()
+    };
+
+    let (entries, feeds) = sync_feeds(sources, endpoints, current, progress).await?;
+    let source = format!(
+        "online ({})",
+        feeds
+            .iter()
+            .map(|f| f.source)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let mut report = tokio::task::spawn_blocking(move || {
+        let files = encode_entries(&entries)?;
+        install_files(&files, source, &db_dir, &mut std::io::stderr())
+    })
+    .await
+    .map_err(|e| CliError::Command(format!("vulnerability database update failed: {}", e)))??;
+    report.feeds = feeds;
+    Ok(report)
+}
+
+/// Import the database archive at `archive` into `db_dir`.
+///
+/// Progress lines are written to `progress`.
+fn import_archive(
+    archive: &Path,
+    db_dir: &Path,
+    progress: &mut dyn Write,
+) -> Result<DbUpdateReport, CliError> {
+    writeln!(progress, "Reading {}", archive.display())?;
+    let files = read_archive(archive, progress)?;
+    install_files(&files, archive.display().to_string(), db_dir, progress)
+}
+
+/// Serialize `entries` into one database file per ecosystem.
+fn encode_entries(entries: &[VulnDbEntry]) -> Result<BTreeMap<String, Vec<u8>>, CliError> {
+    let mut grouped: BTreeMap<String, Vec<&VulnDbEntry>> = BTreeMap::new();
+    for entry in entries {
+        grouped
+            .entry(format!("{}.json", entry.ecosystem))
+            .or_default()
+            .push(entry);
+    }
+
+    let mut files = BTreeMap::new();
+    for (name, entries) in grouped {
+        let body = serde_json::to_vec(&entries)?;
+        if u64::try_from(body.len()).unwrap_or(u64::MAX) > MAX_DB_FILE_BYTES {
+            return Err(CliError::Command(format!(
+                "{} would exceed {} bytes ({} entries)",
+                name,
+                MAX_DB_FILE_BYTES,
+                entries.len()
+            )));
+        }
+        files.insert(name, body);
+    }
+    if files.is_empty() {
+        return Err(CliError::Command(
+            "the feeds produced no vulnerability entries".to_owned(),
+        ));
+    }
+    Ok(files)
+}
+
+/// Verify `files` and install them as the database at `db_dir`.
+///
+/// `source` describes where the files came from in the report.
+fn install_files(
+    files: &BTreeMap<String, Vec<u8>>,
+    source: String,
+    db_dir: &Path,
+    progress: &mut dyn Write,
+) -> Result<DbUpdateReport, CliError> {
+    let staging = sibling(db_dir, "staging");
+    let previous = sibling(db_dir, "previous");
+
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+    for (name, body) in files {
+        std::fs::write(staging.join(name), body)?;
+    }
+
+    writeln!(progress, "Verifying {} file(s)", files.len())?;
+    let db = match VulnDb::load_from_dir(&staging) {
+        Ok(db) => db,
+        Err(e) => {
+            // Leave the current database untouched
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(CliError::Command(format!(
+                "{}: invalid vulnerability database: {}",
+                source, e
+            )));
+        }
+    };
+    let mut entries: BTreeMap<String, usize> = BTreeMap::new();
+    for entry in db.entries() {
+        *entries.entry(entry.ecosystem.to_string()).or_default() += 1;
+    }
+
+    writeln!(progress, "Installing into {}", db_dir.display())?;
+    let replaced = db_dir.exists();
+    if replaced {
+        if previous.exists() {
+            std::fs::remove_dir_all(&previous)?;
+        }
+        std::fs::rename(db_dir, &previous)?;
+    }
+    if let Err(e) = std::fs::rename(&staging, db_dir) {
+        if replaced {
+            let _ = std::fs::rename(&previous, db_dir);
+        }
+        return Err(e.into());
+    }
+
+    let report = DbUpdateReport {
+        source,
+        vuln_db: db_dir.display().to_string(),
+        version: db_version(files),
+        total_entries: db.entry_count(),
+        entries,
+        files: files.keys().cloned().collect(),
+        previous: replaced.then(|| previous.display().to_string()),
+        feeds: Vec::new(),
+    };
+    info!(
+        version = %report.version,
+        entries = report.total_entries,
+        "vulnerability database updated"
+    );
+    Ok(report)
+}
+
+/// Read the database files out of a gzip-compressed tar archive.
+///
+/// Files are matched by name at any depth; other entries are skipped. Only
+/// the file contents are read, so entry paths never touch the filesystem.
+fn read_archive(
+    archive: &Path,
+    progress: &mut dyn Write,
+) -> Result<BTreeMap<String, Vec<u8>>, CliError> {
+    let invalid = |e: std::io::Error| {
+        CliError::Command(format!("{}: invalid archive: {}", archive.display(), e))
+    };
+
+    let file = File::open(archive)
+        .map_err(|e| CliError::Command(format!("{}: {}", archive.display(), e)))?;
+    let mut tar = tar::Archive::new(GzDecoder::new(file));
+
+    let mut files = BTreeMap::new();
+    for entry in tar.entries().map_err(invalid)? {
+        let entry = entry.map_err(invalid)?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path().map_err(invalid)?.into_owned();
+        let Some(name) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .filter(|n| DB_FILES.contains(n))
+            .map(str::to_owned)
+        else {
+            continue;
+        };
+        if files.contains_key(&name) {
+            return Err(CliError::Command(format!(
+                "{}: {} appears more than once",
+                archive.display(),
+                name
+            )));
+        }
+
+        // The header size can lie; cap the actual read as well
+        let mut body = Vec::new();
+        entry
+            .take(MAX_DB_FILE_BYTES + 1)
+            .read_to_end(&mut body)
+            .map_err(invalid)?;
+        if u64::try_from(body.len()).unwrap_or(u64::MAX) > MAX_DB_FILE_BYTES {
+            return Err(CliError::Command(format!(
+                "{}: {} exceeds {} bytes",
+                archive.display(),
+                name,
+                MAX_DB_FILE_BYTES
+            )));
+        }
+        writeln!(progress, "  extracted {} ({} bytes)", name, body.len())?;
+        files.insert(name, body);
+    }
+
+    if files.is_empty() {
+        return Err(CliError::Command(format!(
+            "{}: no database files found (expected: {})",
+            archive.display(),
+            DB_FILES.join(", ")
+        )));
+    }
+    Ok(files)
+}
+
+/// `<dir>.<suffix>` next to `dir`.
+fn sibling(dir: &Path, suffix: &str) -> PathBuf {
+    let mut name = dir.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    dir.with_file_name(name)
+}
+
+/// Content-derived database version.
+fn db_version(files: &BTreeMap<String, Vec<u8>>) -> String {
+    let mut hasher = Sha256::new();
+    for (name, body) in files {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(body);
+        hasher.update([0]);
+    }
+    let mut version: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    version.truncate(VERSION_LEN);
+    version
+}
+
+/// Result of a database update.
+#[derive(Debug, Serialize)]
+pub struct DbUpdateReport {
+    /// Archive the database was imported from, or the synced feeds
+    pub source: String,
+    /// Database directory
+    pub vuln_db: String,
+    /// Content-derived database version
+    pub version: String,
+    /// Total number of entries
+    pub total_entries: usize,
+    /// Entries per ecosystem
+    pub entries: BTreeMap<String, usize>,
+    /// Database files installed
+    pub files: Vec<String>,
+    /// Where the replaced database was kept, if there was one
+    pub previous: Option<String>,
+    /// Per-feed results of an online sync
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub feeds: Vec<FeedReport>,
+}
+
+impl Render for DbUpdateReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(w, "{}", "Vulnerability Database Updated".bold())?;
+        writeln!(w, "  Source:   {}", self.source)?;
+        writeln!(w, "  Database: {}", self.vuln_db)?;
+        writeln!(w, "  Version:  {}", self.version.cyan())?;
+        writeln!(w, "  Entries:  {}", self.total_entries)?;
+        for (ecosystem, count) in &self.entries {
+            writeln!(w, "    {:<8} {}", ecosystem, count)?;
+        }
+        if let Some(previous) = &self.previous {
+            writeln!(w, "  Previous: {}", previous.dimmed())?;
+        }
+        if !self.feeds.is_empty() {
+            writeln!(w, "  Feeds:")?;
+            for feed in &self.feeds {
+                let status = if feed.failed.is_empty() {
+                    "ok".green()
+                } else {
+                    format!("{} request(s) failed", feed.failed.len()).yellow()
+                };
+                writeln!(
+                    w,
+                    "    {:<8} {} entries  {}",
+                    feed.source, feed.entries, status
+                )?;
+                for failure in &feed.failed {
+                    writeln!(w, "      {}", failure.dimmed())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_DB: &str = r#"[
+        {"cve_id": "CVE-2024-0001", "package": "serde", "ecosystem": "Cargo",
+         "affected_ranges": [{"introduced": "1.0.0", "fixed": "1.0.100"}],
+         "fixed_version": "1.0.100", "severity": "High",
+         "description": "test", "published": "2024-01-01"},
+        {"cve_id": "CVE-2024-0002", "package": "tokio", "ecosystem": "Cargo",
+         "affected_ranges": [], "fixed_version": null, "severity": "Low",
+         "description": "test", "published": "2024-01-02"}
+    ]"#;
+
+    const NPM_DB: &str = r#"[
+        {"cve_id": "CVE-2024-0003", "package": "lodash", "ecosystem": "Npm",
+         "affected_ranges": [], "fixed_version": null, "severity": "Critical",
+         "description": "test", "published": "2024-02-01"}
+    ]"#;
+
+    fn write_archive(path: &Path, files: &[(&str, &str)]) {
+        let file = File::create(path).expect("archive should be created");
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, body) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(u64::try_from(body.len()).expect("small file"));
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, body.as_bytes())
+                .expect("entry should be appended");
+        }
+        builder
+            .into_inner()
+            .expect("tar should finish")
+            .finish()
+            .expect("gzip should finish");
+    }
+
+    #[test]
+    fn test_import_installs_database_and_counts_entries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let archive = dir.path().join("vulndb.tar.gz");
+        write_archive(
+            &archive,
+            &[
+                ("vulndb/cargo.json", CARGO_DB),
+                ("vulndb/npm.json", NPM_DB),
+                ("vulndb/README", "ignored"),
+            ],
+        );
+        let db_dir = dir.path().join("vuln-db");
+        let mut progress = Vec::new();
+
+        let report = import_archive(&archive, &db_dir, &mut progress).expect("import succeeds");
+
+        assert_eq!(report.total_entries, 3);
+        assert_eq!(report.entries["cargo"], 2);
+        assert_eq!(report.entries["npm"], 1);
+        assert_eq!(report.files, vec!["cargo.json", "npm.json"]);
+        assert_eq!(report.version.len(), VERSION_LEN);
+        assert!(report.previous.is_none());
+        assert!(db_dir.join("cargo.json").is_file());
+        assert!(!db_dir.join("README").exists());
+        assert!(!sibling(&db_dir, "staging").exists());
+        let progress = String::from_utf8(progress).expect("valid UTF-8");
+        assert!(progress.contains("extracted cargo.json"));
+    }
+
+    #[test]
+    fn test_import_keeps_previous_database() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_dir = dir.path().join("vuln-db");
+        std::fs::create_dir(&db_dir).expect("db dir");
+        std::fs::write(db_dir.join("npm.json"), NPM_DB).expect("old db");
+        let archive = dir.path().join("vulndb.tar.gz");
+        write_archive(&archive, &[("cargo.json", CARGO_DB)]);
+
+        let report = import_archive(&archive, &db_dir, &mut Vec::new()).expect("import succeeds");
+
+        let previous = sibling(&db_dir, "previous");
+        assert_eq!(report.previous, Some(previous.display().to_string()));
+        assert!(previous.join("npm.json").is_file());
+        assert!(!db_dir.join("npm.json").exists());
+        assert!(db_dir.join("cargo.json").is_file());
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_database() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_dir = dir.path().join("vuln-db");
+        std::fs::create_dir(&db_dir).expect("db dir");
+        std::fs::write(db_dir.join("npm.json"), NPM_DB).expect("old db");
+        let archive = dir.path().join("vulndb.tar.gz");
+        write_archive(&archive, &[("cargo.json", "{ not json")]);
+
+        let err = import_archive(&archive, &db_dir, &mut Vec::new())
+            .expect_err("invalid database should fail");
+
+        assert!(err.to_string().contains("invalid vulnerability database"));
+        assert!(
+            db_dir.join("npm.json").is_file(),
+            "current database is kept"
+        );
+        assert!(!sibling(&db_dir, "staging").exists());
+    }
+
+    #[test]
+    fn test_import_rejects_archive_without_database_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let archive = dir.path().join("vulndb.tar.gz");
+        write_archive(&archive, &[("README", "nothing here")]);
+
+        let err = import_archive(&archive, &dir.path().join("vuln-db"), &mut Vec::new())
+            .expect_err("empty archive should fail");
+
+        assert!(err.to_string().contains("no database files found"));
+    }
+
+    #[test]
+    fn test_import_rejects_duplicate_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let archive = dir.path().join("vulndb.tar.gz");
+        write_archive(&archive, &[("a/npm.json", NPM_DB), ("b/npm.json", NPM_DB)]);
+
+        let err = import_archive(&archive, &dir.path().join("vuln-db"), &mut Vec::new())
+            .expect_err("duplicate files should fail");
+
+        assert!(err.to_string().contains("appears more than once"));
+    }
+
+    #[test]
+    fn test_db_version_depends_on_content() {
+        let mut a = BTreeMap::new();
+        a.insert("cargo.json".to_owned(), CARGO_DB.as_bytes().to_vec());
+        let mut b = a.clone();
+        b.insert("npm.json".to_owned(), NPM_DB.as_bytes().to_vec());
+
+        assert_eq!(db_version(&a), db_version(&a.clone()));
+        assert_ne!(db_version(&a), db_version(&b));
+    }
+
+    #[tokio::test]
+    async fn test_online_sync_merges_into_current_database() {
+        // Given: A current npm database and a local OSV mirror for crates.io
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_dir = dir.path().join("vuln-db");
+        std::fs::create_dir(&db_dir).expect("db dir");
+        std::fs::write(db_dir.join("npm.json"), NPM_DB).expect("old db");
+        let mirror = dir.path().join("osv");
+        std::fs::create_dir_all(mirror.join("crates.io")).expect("mirror");
+        std::fs::write(
+            mirror.join("crates.io/all.zip"),
+            crate::commands::sbom_db_sync::tests::zip(
+                &[(
+                    "RUSTSEC-2024-0001.json",
+                    crate::commands::sbom_db_sync::tests::OSV_RECORD,
+                )],
+                false,
+            ),
+        )
+        .expect("archive");
+        let endpoints = FeedEndpoints {
+            osv: format!("file://{}", mirror.display()),
+            ..FeedEndpoints::default()
+        };
+
+        // When: Syncing OSV
+        let report = sync_online(
+            &[DbSource::Osv],
+            &endpoints,
+            db_dir.clone(),
+            &mut Vec::new(),
+        )
+        .await
+        .expect("sync succeeds");
+
+        // Then: Both ecosystems are installed and the old database is kept aside
+        assert_eq!(report.total_entries, 2);
+        assert_eq!(report.entries["cargo"], 1);
+        assert_eq!(report.entries["npm"], 1);
+        assert_eq!(report.source, "online (osv)");
+        assert_eq!(report.feeds.len(), 1);
+        assert!(db_dir.join("cargo.json").is_file());
+        assert!(sibling(&db_dir, "previous").join("npm.json").is_file());
+        let db = VulnDb::load_from_dir(&db_dir).expect("installed db loads");
+        assert_eq!(
+            db.lookup("demo", &ironpost_sbom_scanner::Ecosystem::Cargo)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_update_report_render() {
+        let report = DbUpdateReport {
+            source: "vulndb.tar.gz".to_owned(),
+            vuln_db: "/var/lib/ironpost/vuln-db".to_owned(),
+            version: "0123456789abcdef".to_owned(),
+            total_entries: 3,
+            entries: BTreeMap::from([("cargo".to_owned(), 2), ("npm".to_owned(), 1)]),
+            files: vec!["cargo.json".to_owned(), "npm.json".to_owned()],
+            previous: None,
+            feeds: vec![FeedReport {
+                source: "ghsa",
+                entries: 5,
+                failed: vec!["https://api.github.com/advisories: 403".to_owned()],
+            }],
+        };
+        let mut buf = Vec::new();
+
+        report.render_text(&mut buf).expect("render succeeds");
+
+        let output = String::from_utf8(buf).expect("valid UTF-8");
+        assert!(output.contains("0123456789abcdef"));
+        assert!(output.contains("cargo"));
+        assert!(!output.contains("Previous"));
+        assert!(output.contains("1 request(s) failed"));
+    }
+}
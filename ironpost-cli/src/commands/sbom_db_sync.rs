@@ -0,0 +1,744 @@
+//! Online vulnerability feed sync for `ironpost sbom db update`
+//!
+//! Without `--offline`, the update downloads advisories from the selected
+//! feeds and merges them into the current database:
+//!
+//! - `osv`: `<ecosystem>/all.zip` exports for crates.io, npm, Go and PyPI.
+//!   Malicious-package reports (`MAL-*`) are skipped.
+//! - `ghsa`: reviewed GitHub security advisories per ecosystem, paged through
+//!   the `Link` header. `GITHUB_TOKEN` raises the API rate limit.
+//! - `nvd`: CVSS severities of CVEs modified in the last
+//!   [`NVD_WINDOW_DAYS`] days, applied to entries with the same CVE ID.
+//!   `NVD_API_KEY` shortens the pause between pages.
+//!
+//! Feeds are converted with the scanner's feed parsers
+//! (`ironpost_sbom_scanner::vuln::feed`). Entries are keyed by ecosystem,
+//! package and CVE ID: later feeds replace earlier ones, and entries no feed
+//! mentions are kept. A request that fails is reported and skipped; the sync
+//! fails only when every request fails. `file://` endpoints read local
+//! mirrors.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use flate2::read::DeflateDecoder;
+use serde::Serialize;
+
+use ironpost_core::types::Severity;
+use ironpost_sbom_scanner::vuln::feed::{
+    apply_severities, ghsa_ecosystem, merge_entries, osv_ecosystem, parse_ghsa_advisories,
+    parse_nvd_page, parse_osv_record,
+};
+use ironpost_sbom_scanner::{Ecosystem, VulnDbEntry};
+
+use crate::cli::DbSource;
+use crate::commands::report::civil_from_days;
+use crate::error::CliError;
+
+/// OSV ecosystem export bucket.
+pub const OSV_BASE_URL: &str = "https://osv-vulnerabilities.storage.googleapis.com";
+
+/// GitHub global security advisories API.
+pub const GHSA_API_URL: &str = "https://api.github.com/advisories";
+
+/// NVD CVE API 2.0.
+pub const NVD_API_URL: &str = "https://services.nvd.nist.gov/rest/json/cves/2.0";
+
+/// How far back NVD modifications are synced (the API allows at most 120).
+pub const NVD_WINDOW_DAYS: u64 = 30;
+
+/// Ecosystems synced from OSV and GHSA.
+const ECOSYSTEMS: [Ecosystem; 4] = [
+    Ecosystem::Cargo,
+    Ecosystem::Npm,
+    Ecosystem::Go,
+    Ecosystem::Pip,
+];
+
+/// Timeout for a single feed request.
+const FEED_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Largest OSV export accepted.
+const MAX_OSV_ARCHIVE_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Largest single record inside an OSV export.
+const MAX_OSV_RECORD_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Largest GHSA or NVD response page accepted.
+const MAX_PAGE_BYTES: u64 = 32 * 1024 * 1024;
+
+/// GHSA page size (API maximum).
+const GHSA_PAGE_SIZE: u32 = 100;
+
+/// Upper bound on GHSA pages per ecosystem.
+const GHSA_MAX_PAGES: usize = 500;
+
+/// NVD page size (API maximum).
+const NVD_PAGE_SIZE: usize = 2000;
+
+/// Upper bound on NVD pages.
+const NVD_MAX_PAGES: usize = 200;
+
+/// Pause between NVD pages without an API key (5 requests per 30 seconds).
+const NVD_PAUSE: Duration = Duration::from_secs(6);
+
+/// Pause between NVD pages with an API key (50 requests per 30 seconds).
+const NVD_KEY_PAUSE: Duration = Duration::from_millis(600);
+
+/// Feed endpoints; `file://` URLs read a local mirror.
+#[derive(Debug, Clone)]
+pub struct FeedEndpoints {
+    /// OSV export base (`<base>/<ecosystem>/all.zip`)
+    pub osv: String,
+    /// GitHub advisories API
+    pub ghsa: String,
+    /// NVD CVE API
+    pub nvd: String,
+}
+
+impl Default for FeedEndpoints {
+    fn default() -> Self {
+        Self {
+            osv: OSV_BASE_URL.to_owned(),
+            ghsa: GHSA_API_URL.to_owned(),
+            nvd: NVD_API_URL.to_owned(),
+        }
+    }
+}
+
+/// What one feed contributed to the sync.
+#[derive(Debug, Serialize)]
+pub struct FeedReport {
+    /// Feed name (`osv`, `ghsa`, `nvd`)
+    pub source: &'static str,
+    /// Entries added or replaced (`nvd`: entries whose severity changed)
+    pub entries: usize,
+    /// Requests that failed, with the reason
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<String>,
+}
+
+/// Download `sources` and merge them into `current`.
+///
+/// Progress lines are written to `progress`.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if the HTTP client cannot be built or every
+/// feed request failed.
+pub async fn sync_feeds(
+    sources: &[DbSource],
+    endpoints: &FeedEndpoints,
+    current: Vec<VulnDbEntry>,
+    progress: &mut (dyn Write + Send),
+) -> Result<(Vec<VulnDbEntry>, Vec<FeedReport>), CliError> {
+    let client = reqwest::Client::builder()
+        .timeout(FEED_TIMEOUT)
+        .user_agent(concat!("ironpost-cli/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| CliError::Command(format!("failed to build HTTP client: {}", e)))?;
+
+    let mut entries = current;
+    let mut reports = Vec::new();
+    let mut succeeded = 0usize;
+    for source in sources {
+        let mut report = FeedReport {
+            source: source.as_str(),
+            entries: 0,
+            failed: Vec::new(),
+        };
+        match source {
+            DbSource::Osv => {
+                for ecosystem in ECOSYSTEMS {
+                    let url = format!(
+                        "{}/{}/all.zip",
+                        endpoints.osv.trim_end_matches('/'),
+                        osv_ecosystem(ecosystem)
+                    );
+                    writeln!(progress, "Downloading {}", url)?;
+                    match fetch_osv(&client, &url).await {
+                        Ok(update) => {
+                            writeln!(
+                                progress,
+                                "  osv {}: {} entries ({} records skipped)",
+                                osv_ecosystem(ecosystem),
+                                update.len(),
+                                update.skipped
+                            )?;
+                            report.entries += update.len();
+                            entries = merge_entries(entries, update.entries);
+                            succeeded += 1;
+                        }
+                        Err(e) => report.failed.push(failure(progress, &url, e)?),
+                    }
+                }
+            }
+            DbSource::Ghsa => {
+                let token = std::env::var("GITHUB_TOKEN").ok().filter(|t| !t.is_empty());
+                for ecosystem in ECOSYSTEMS {
+                    let url = format!(
+                        "{}?ecosystem={}&type=reviewed&per_page={}",
+                        endpoints.ghsa,
+                        ghsa_ecosystem(ecosystem),
+                        GHSA_PAGE_SIZE
+                    );
+                    writeln!(progress, "Downloading {}", url)?;
+                    match fetch_ghsa(&client, &url, token.as_deref()).await {
+                        Ok(update) => {
+                            writeln!(
+                                progress,
+                                "  ghsa {}: {} entries",
+                                ghsa_ecosystem(ecosystem),
+                                update.len()
+                            )?;
+                            report.entries += update.len();
+                            entries = merge_entries(entries, update);
+                            succeeded += 1;
+                        }
+                        Err(e) => report.failed.push(failure(progress, &url, e)?),
+                    }
+                }
+            }
+            DbSource::Nvd => {
+                let key = std::env::var("NVD_API_KEY").ok().filter(|k| !k.is_empty());
+                writeln!(progress, "Downloading {}", endpoints.nvd)?;
+                match fetch_nvd(&client, &endpoints.nvd, key.as_deref(), progress).await {
+                    Ok(severities) => {
+                        report.entries = apply_severities(&mut entries, &severities);
+                        writeln!(
+                            progress,
+                            "  nvd: {} severities, {} entries updated",
+                            severities.len(),
+                            report.entries
+                        )?;
+                        succeeded += 1;
+                    }
+                    Err(e) => report.failed.push(failure(progress, &endpoints.nvd, e)?),
+                }
+            }
+        }
+        reports.push(report);
+    }
+
+    if succeeded == 0 {
+        let reasons: Vec<String> = reports.into_iter().flat_map(|r| r.failed).collect();
+        return Err(CliError::Command(format!(
+            "online sync failed; every feed request failed: {}",
+            reasons.join("; ")
+        )));
+    }
+    Ok((entries, reports))
+}
+
+/// Log a failed request and return its report line.
+fn failure(progress: &mut (dyn Write + Send), url: &str, error: String) -> std::io::Result<String> {
+    writeln!(progress, "  failed: {}", error)?;
+    Ok(format!("{}: {}", url, error))
+}
+
+/// Entries from one OSV export.
+struct OsvUpdate {
+    entries: Vec<VulnDbEntry>,
+    /// Malicious-package reports and records that failed to parse
+    skipped: usize,
+}
+
+impl OsvUpdate {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Download and convert one OSV ecosystem export.
+async fn fetch_osv(client: &reqwest::Client, url: &str) -> Result<OsvUpdate, String> {
+    let (archive, _) = fetch(client, url, &[], MAX_OSV_ARCHIVE_BYTES).await?;
+    tokio::task::spawn_blocking(move || {
+        let mut skipped = 0;
+        let records = read_zip(&archive, |name| {
+            let keep = name.ends_with(".json") && !name.starts_with("MAL-");
+            if !keep {
+                skipped += 1;
+            }
+            keep
+        })?;
+        let mut entries = Vec::new();
+        for (name, body) in records {
+            match std::str::from_utf8(&body)
+                .map_err(|e| e.to_string())
+                .and_then(|json| parse_osv_record(json).map_err(|e| e.to_string()))
+            {
+                Ok(parsed) => entries.extend(parsed),
+                Err(e) => {
+                    tracing::debug!(record = %name, error = %e, "skipping OSV record");
+                    skipped += 1;
+                }
+            }
+        }
+        Ok(OsvUpdate { entries, skipped })
+    })
+    .await
+    .map_err(|e| format!("OSV conversion failed: {}", e))?
+}
+
+/// Download every GHSA page starting at `url`.
+async fn fetch_ghsa(
+    client: &reqwest::Client,
+    url: &str,
+    token: Option<&str>,
+) -> Result<Vec<VulnDbEntry>, String> {
+    let mut headers = vec![
+        ("Accept", "application/vnd.github+json".to_owned()),
+        ("X-GitHub-Api-Version", "2022-11-28".to_owned()),
+    ];
+    if let Some(token) = token {
+        headers.push(("Authorization", format!("Bearer {}", token)));
+    }
+
+    let mut entries = Vec::new();
+    let mut next = Some(url.to_owned());
+    let mut pages = 0;
+    while let Some(url) = next.take() {
+        pages += 1;
+        if pages > GHSA_MAX_PAGES {
+            return Err(format!("more than {} pages", GHSA_MAX_PAGES));
+        }
+        let (body, link) = fetch(client, &url, &headers, MAX_PAGE_BYTES).await?;
+        let json = String::from_utf8(body).map_err(|_| "response is not UTF-8".to_owned())?;
+        entries.extend(parse_ghsa_advisories(&json).map_err(|e| e.to_string())?);
+        next = link.as_deref().and_then(next_link);
+    }
+    Ok(entries)
+}
+
+/// Download CVSS severities of CVEs modified in the last [`NVD_WINDOW_DAYS`] days.
+async fn fetch_nvd(
+    client: &reqwest::Client,
+    url: &str,
+    api_key: Option<&str>,
+    progress: &mut (dyn Write + Send),
+) -> Result<HashMap<String, Severity>, String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let start = iso8601(now.saturating_sub(NVD_WINDOW_DAYS * 86_400));
+    let end = iso8601(now);
+    let headers: Vec<(&str, String)> = api_key
+        .map(|key| ("apiKey", key.to_owned()))
+        .into_iter()
+        .collect();
+    let pause = if api_key.is_some() {
+        NVD_KEY_PAUSE
+    } else {
+        NVD_PAUSE
+    };
+
+    let mut severities = HashMap::new();
+    let mut start_index = 0usize;
+    for page in 0..NVD_MAX_PAGES {
+        if page > 0 {
+            tokio::time::sleep(pause).await;
+        }
+        let page_url = format!(
+            "{}?lastModStartDate={}&lastModEndDate={}&startIndex={}&resultsPerPage={}",
+            url, start, end, start_index, NVD_PAGE_SIZE
+        );
+        let (body, _) = fetch(client, &page_url, &headers, MAX_PAGE_BYTES).await?;
+        let json = String::from_utf8(body).map_err(|_| "response is not UTF-8".to_owned())?;
+        let parsed = parse_nvd_page(&json).map_err(|e| e.to_string())?;
+        severities.extend(parsed.severities);
+        start_index = parsed.start_index + parsed.results_per_page;
+        let _ = writeln!(
+            progress,
+            "  nvd: {}/{} CVEs",
+            start_index.min(parsed.total_results),
+            parsed.total_results
+        );
+        if parsed.results_per_page == 0 || start_index >= parsed.total_results {
+            return Ok(severities);
+        }
+    }
+    Err(format!("more than {} pages", NVD_MAX_PAGES))
+}
+
+/// `2024-01-15T00:00:00.000%2B00:00` (URL-encoded UTC offset) for `secs`.
+fn iso8601(secs: u64) -> String {
+    let (year, month, day) = civil_from_days(secs / 86_400);
+    let time = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.000%2B00:00",
+        year,
+        month,
+        day,
+        time / 3600,
+        (time / 60) % 60,
+        time % 60
+    )
+}
+
+/// Fetch `url` (or read a `file://` path), capped at `limit` bytes.
+///
+/// Returns the body and the `Link` header, if any.
+async fn fetch(
+    client: &reqwest::Client,
+    url: &str,
+    headers: &[(&str, String)],
+    limit: u64,
+) -> Result<(Vec<u8>, Option<String>), String> {
+    let too_large = || format!("response exceeds {} bytes", limit);
+
+    if let Some(path) = url.strip_prefix("file://") {
+        // Query strings are not part of a mirror's file name
+        let path = path.split('?').next().unwrap_or(path);
+        let metadata = tokio::fs::metadata(path).await.map_err(|e| e.to_string())?;
+        if metadata.len() > limit {
+            return Err(too_large());
+        }
+        let body = tokio::fs::read(path).await.map_err(|e| e.to_string())?;
+        return Ok((body, None));
+    }
+
+    let mut request = client.get(url);
+    for (name, value) in headers {
+        request = request.header(*name, value);
+    }
+    let mut response = request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| e.to_string())?;
+    if response.content_length().is_some_and(|len| len > limit) {
+        return Err(too_large());
+    }
+    let link = response
+        .headers()
+        .get(reqwest::header::LINK)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned);
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        body.extend_from_slice(&chunk);
+        if u64::try_from(body.len()).unwrap_or(u64::MAX) > limit {
+            return Err(too_large());
+        }
+    }
+    Ok((body, link))
+}
+
+/// URL of the `rel="next"` page in a `Link` header.
+fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == r#"rel="next""#)
+            .then(|| {
+                target
+                    .trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_owned()
+            })
+    })
+}
+
+const ZIP_EOCD: u32 = 0x0605_4b50;
+const ZIP64_EOCD_LOCATOR: u32 = 0x0706_4b50;
+const ZIP64_EOCD: u32 = 0x0606_4b50;
+const ZIP_CENTRAL_HEADER: u32 = 0x0201_4b50;
+const ZIP_LOCAL_HEADER: u32 = 0x0403_4b50;
+
+/// Read the entries of a zip archive whose names pass `keep`.
+///
+/// Supports stored and deflated entries and zip64 archives, which is what
+/// the OSV exports use. Entries are capped at [`MAX_OSV_RECORD_BYTES`].
+fn read_zip(
+    data: &[u8],
+    mut keep: impl FnMut(&str) -> bool,
+) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let invalid = |what: &str| format!("invalid zip archive: {}", what);
+
+    let last = data
+        .len()
+        .checked_sub(22)
+        .ok_or_else(|| invalid("too short"))?;
+    let first = last.saturating_sub(0xFFFF);
+    let eocd = (first..=last)
+        .rev()
+        .find(|&at| le_u32(data, at) == Some(ZIP_EOCD))
+        .ok_or_else(|| invalid("end of central directory not found"))?;
+    let field = |value: Option<u64>| value.ok_or_else(|| invalid("truncated header"));
+
+    let mut count = field(le_u16(data, eocd + 10).map(u64::from))?;
+    let mut offset = field(le_u32(data, eocd + 16).map(u64::from))?;
+    if count == 0xFFFF || offset == 0xFFFF_FFFF {
+        let locator = eocd
+            .checked_sub(20)
+            .filter(|&at| le_u32(data, at) == Some(ZIP64_EOCD_LOCATOR))
+            .ok_or_else(|| invalid("zip64 locator not found"))?;
+        let record = to_usize(field(le_u64(data, locator + 8))?)?;
+        if le_u32(data, record) != Some(ZIP64_EOCD) {
+            return Err(invalid("zip64 end of central directory not found"));
+        }
+        count = field(le_u64(data, record + 32))?;
+        offset = field(le_u64(data, record + 48))?;
+    }
+
+    let mut files = Vec::new();
+    let mut at = to_usize(offset)?;
+    for _ in 0..count {
+        if le_u32(data, at) != Some(ZIP_CENTRAL_HEADER) {
+            return Err(invalid("bad central directory entry"));
+        }
+        let method = field(le_u16(data, at + 10).map(u64::from))?;
+        let mut compressed = field(le_u32(data, at + 20).map(u64::from))?;
+        let mut size = field(le_u32(data, at + 24).map(u64::from))?;
+        let name_len = to_usize(field(le_u16(data, at + 28).map(u64::from))?)?;
+        let extra_len = to_usize(field(le_u16(data, at + 30).map(u64::from))?)?;
+        let comment_len = to_usize(field(le_u16(data, at + 32).map(u64::from))?)?;
+        let mut local = field(le_u32(data, at + 42).map(u64::from))?;
+        let name_start = at + 46;
+        let name = data
+            .get(name_start..name_start + name_len)
+            .map(String::from_utf8_lossy)
+            .ok_or_else(|| invalid("truncated file name"))?
+            .into_owned();
+        let extra = data
+            .get(name_start + name_len..name_start + name_len + extra_len)
+            .ok_or_else(|| invalid("truncated extra field"))?;
+        at = name_start + name_len + extra_len + comment_len;
+
+        // zip64 sizes and offset live in extra field 0x0001, in this order
+        if size == 0xFFFF_FFFF || compressed == 0xFFFF_FFFF || local == 0xFFFF_FFFF {
+            let mut pos = 0;
+            while pos + 4 <= extra.len() {
+                let id = field(le_u16(extra, pos).map(u64::from))?;
+                let len = to_usize(field(le_u16(extra, pos + 2).map(u64::from))?)?;
+                if id == 1 {
+                    let mut value_at = pos + 4;
+                    for value in [&mut size, &mut compressed, &mut local] {
+                        if *value == 0xFFFF_FFFF {
+                            *value = field(le_u64(extra, value_at))?;
+                            value_at += 8;
+                        }
+                    }
+                }
+                pos += 4 + len;
+            }
+        }
+
+        if name.ends_with('/') || !keep(&name) {
+            continue;
+        }
+        if size > MAX_OSV_RECORD_BYTES {
+            return Err(format!(
+                "{}: entry exceeds {} bytes",
+                name, MAX_OSV_RECORD_BYTES
+            ));
+        }
+
+        let local = to_usize(local)?;
+        if le_u32(data, local) != Some(ZIP_LOCAL_HEADER) {
+            return Err(invalid("bad local header"));
+        }
+        let local_name_len = to_usize(field(le_u16(data, local + 26).map(u64::from))?)?;
+        let local_extra_len = to_usize(field(le_u16(data, local + 28).map(u64::from))?)?;
+        let start = local + 30 + local_name_len + local_extra_len;
+        let body = data
+            .get(start..start.saturating_add(to_usize(compressed)?))
+            .ok_or_else(|| invalid("truncated entry"))?;
+        let contents = match method {
+            0 => body.to_vec(),
+            8 => {
+                let mut out = Vec::new();
+                DeflateDecoder::new(body)
+                    .take(MAX_OSV_RECORD_BYTES + 1)
+                    .read_to_end(&mut out)
+                    .map_err(|e| format!("{}: {}", name, e))?;
+                out
+            }
+            other => {
+                return Err(format!(
+                    "{}: unsupported compression method {}",
+                    name, other
+                ));
+            }
+        };
+        files.push((name, contents));
+    }
+    Ok(files)
+}
+
+fn le_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le_u64(data: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(at..at + 8)?.try_into().ok()?))
+}
+
+fn to_usize(value: u64) -> Result<usize, String> {
+    usize::try_from(value).map_err(|_| "invalid zip archive: offset out of range".to_owned())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use flate2::Compression;
+    use flate2::write::DeflateEncoder;
+
+    pub(crate) const OSV_RECORD: &str = r#"{
+        "id": "RUSTSEC-2024-0001", "aliases": ["CVE-2024-9999"],
+        "summary": "Memory corruption in demo", "published": "2024-05-01T00:00:00Z",
+        "affected": [{"package": {"ecosystem": "crates.io", "name": "demo"},
+            "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "1.0.1"}]}]}]
+    }"#;
+
+    /// Build a zip archive; entries are deflated when `deflate` is set.
+    pub(crate) fn zip(files: &[(&str, &str)], deflate: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut central = Vec::new();
+        for (name, contents) in files {
+            let body = if deflate {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(contents.as_bytes()).unwrap();
+                encoder.finish().unwrap()
+            } else {
+                contents.as_bytes().to_vec()
+            };
+            let method: u16 = if deflate { 8 } else { 0 };
+            let offset = u32::try_from(out.len()).unwrap();
+            let name_len = u16::try_from(name.len()).unwrap();
+            let compressed = u32::try_from(body.len()).unwrap();
+            let size = u32::try_from(contents.len()).unwrap();
+
+            out.extend_from_slice(&ZIP_LOCAL_HEADER.to_le_bytes());
+            out.extend_from_slice(&[20, 0, 0, 0]);
+            out.extend_from_slice(&method.to_le_bytes());
+            out.extend_from_slice(&[0; 8]);
+            out.extend_from_slice(&compressed.to_le_bytes());
+            out.extend_from_slice(&size.to_le_bytes());
+            out.extend_from_slice(&name_len.to_le_bytes());
+            out.extend_from_slice(&[0, 0]);
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(&body);
+
+            central.extend_from_slice(&ZIP_CENTRAL_HEADER.to_le_bytes());
+            central.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+            central.extend_from_slice(&method.to_le_bytes());
+            central.extend_from_slice(&[0; 8]);
+            central.extend_from_slice(&compressed.to_le_bytes());
+            central.extend_from_slice(&size.to_le_bytes());
+            central.extend_from_slice(&name_len.to_le_bytes());
+            central.extend_from_slice(&[0; 12]);
+            central.extend_from_slice(&offset.to_le_bytes());
+            central.extend_from_slice(name.as_bytes());
+        }
+        let central_offset = u32::try_from(out.len()).unwrap();
+        let central_len = u32::try_from(central.len()).unwrap();
+        let count = u16::try_from(files.len()).unwrap();
+        out.extend_from_slice(&central);
+        out.extend_from_slice(&ZIP_EOCD.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&count.to_le_bytes());
+        out.extend_from_slice(&count.to_le_bytes());
+        out.extend_from_slice(&central_len.to_le_bytes());
+        out.extend_from_slice(&central_offset.to_le_bytes());
+        out.extend_from_slice(&[0, 0]);
+        out
+    }
+
+    #[test]
+    fn test_read_zip_stored_and_deflated() {
+        for deflate in [false, true] {
+            let data = zip(&[("a.json", "{\"a\":1}"), ("skip.txt", "x")], deflate);
+
+            let files = read_zip(&data, |name| name.ends_with(".json")).expect("valid zip");
+
+            assert_eq!(files.len(), 1);
+            assert_eq!(files[0].0, "a.json");
+            assert_eq!(files[0].1, b"{\"a\":1}");
+        }
+        assert!(read_zip(b"not a zip archive at all", |_| true).is_err());
+    }
+
+    #[test]
+    fn test_next_link() {
+        let header = r#"<https://api.github.com/advisories?after=abc>; rel="next", <https://api.github.com/advisories?before=x>; rel="prev""#;
+        assert_eq!(
+            next_link(header).as_deref(),
+            Some("https://api.github.com/advisories?after=abc")
+        );
+        assert!(next_link(r#"<https://x>; rel="prev""#).is_none());
+    }
+
+    #[test]
+    fn test_iso8601() {
+        assert_eq!(iso8601(86_461), "1970-01-02T00:01:01.000%2B00:00");
+    }
+
+    #[tokio::test]
+    async fn test_sync_osv_mirror_merges_and_reports_failures() {
+        // Given: A local OSV mirror with only the crates.io export
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("crates.io")).expect("mkdir");
+        let archive = zip(
+            &[
+                ("RUSTSEC-2024-0001.json", OSV_RECORD),
+                ("MAL-2024-1.json", "{}"),
+                ("broken.json", "{"),
+            ],
+            true,
+        );
+        std::fs::write(dir.path().join("crates.io/all.zip"), archive).expect("write");
+        let endpoints = FeedEndpoints {
+            osv: format!("file://{}", dir.path().display()),
+            ..FeedEndpoints::default()
+        };
+        let current = ironpost_sbom_scanner::VulnDb::from_json(
+            r#"[{"cve_id": "CVE-2023-0001", "package": "lodash", "ecosystem": "Npm",
+                "affected_ranges": [], "fixed_version": null, "severity": "Low",
+                "description": "old", "published": "2023-01-01"}]"#,
+        )
+        .expect("valid db")
+        .entries()
+        .to_vec();
+
+        // When: Syncing OSV only
+        let mut progress = Vec::new();
+        let (entries, reports) = sync_feeds(&[DbSource::Osv], &endpoints, current, &mut progress)
+            .await
+            .expect("one export succeeded");
+
+        // Then: The existing entry is kept, the record is added, missing exports are reported
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().any(|e| e.cve_id == "CVE-2024-9999"));
+        assert!(entries.iter().any(|e| e.cve_id == "CVE-2023-0001"));
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].entries, 1);
+        assert_eq!(reports[0].failed.len(), 3, "npm, Go and PyPI are missing");
+        let progress = String::from_utf8(progress).expect("utf-8");
+        assert!(progress.contains("osv crates.io: 1 entries (2 records skipped)"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_fails_when_every_request_fails() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let endpoints = FeedEndpoints {
+            osv: format!("file://{}", dir.path().display()),
+            ..FeedEndpoints::default()
+        };
+
+        let err = sync_feeds(&[DbSource::Osv], &endpoints, Vec::new(), &mut Vec::new())
+            .await
+            .expect_err("no export exists");
+
+        assert!(err.to_string().contains("every feed request failed"));
+    }
+}
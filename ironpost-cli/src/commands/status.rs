@@ -1,17 +1,27 @@
 //! `ironpost status` command handler
+//!
+//! Asks the running daemon for its structured health report (`/health`):
+//! version, uptime and per-module state with reason codes, state-change
+//! timestamps and restart counts. If the daemon cannot be queried, falls
+//! back to a report built from the configuration and the PID file.
 
 use std::io::Write;
 use std::path::Path;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
 use ironpost_core::config::IronpostConfig;
+use ironpost_core::pipeline::HealthStatus;
 
 use crate::cli::StatusArgs;
+use crate::client::DaemonClient;
 use crate::error::CliError;
 use crate::output::{OutputWriter, Render};
 
+/// Daemon control endpoint serving the structured health report.
+const HEALTH_REPORT_PATH: &str = "/health";
+
 /// Execute the `status` command.
 pub async fn execute(
     args: StatusArgs,
@@ -20,13 +30,25 @@ pub async fn execute(
 ) -> Result<(), CliError> {
     let config = IronpostConfig::load(config_path).await?;
 
-    let report = build_status_report(&config, args.verbose)?;
-
-    writer.render(&report)?;
+    match fetch_health_report(&config).await {
+        Ok(report) => writer.render(&report)?,
+        Err(e) => {
+            debug!(error = %e, "daemon health report unavailable, using local status");
+            let report = build_status_report(&config, args.verbose)?;
+            writer.render(&report)?;
+        }
+    }
 
     Ok(())
 }
 
+/// Fetch the structured health report from the running daemon.
+async fn fetch_health_report(config: &IronpostConfig) -> Result<HealthReport, CliError> {
+    DaemonClient::from_config(config)?
+        .get_json(HEALTH_REPORT_PATH)
+        .await
+}
+
 /// Build a status report from configuration and daemon state.
 ///
 /// Queries daemon status via PID file and constructs module-level health information
@@ -167,8 +189,8 @@ fn check_daemon_status(pid_file: &str) -> (bool, Option<u64>) {
     // Check if process is alive
     let is_running = is_process_alive(pid);
 
-    // Uptime estimation is not trivial without querying the process
-    // For now, just return None (future: add health API endpoint)
+    // Uptime comes from the daemon's `/health` report; the PID file alone
+    // cannot tell when the process started.
     (is_running, None)
 }
 
@@ -240,6 +262,108 @@ pub struct ModuleStatus {
     pub details: Option<String>,
 }
 
+/// Structured health report served by the daemon's `/health` endpoint.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Daemon version
+    pub version: String,
+    /// Overall daemon health (worst of all modules)
+    pub status: HealthStatus,
+    /// Daemon start time (Unix seconds)
+    pub started_at: u64,
+    /// Daemon uptime in seconds
+    pub uptime_secs: u64,
+    /// When the daemon generated this report (Unix seconds)
+    pub generated_at: u64,
+    /// Per-module health, in start order
+    pub modules: Vec<ModuleReport>,
+}
+
+/// Health of a single module in a [`HealthReport`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModuleReport {
+    /// Module name
+    pub name: String,
+    /// Plugin lifecycle state ("running", "stopped", "failed", ...)
+    pub state: String,
+    /// Health check result
+    pub status: HealthStatus,
+    /// Machine-readable reason: "ok" | "degraded" | "unhealthy" | "quarantined" | "failed" | "not_running"
+    pub reason_code: String,
+    /// Human-readable reason, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// When the reason code last changed (Unix seconds)
+    pub since: u64,
+    /// Supervisor restarts since the daemon started
+    pub restarts: u32,
+}
+
+impl Render for HealthReport {
+    fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        use colored::Colorize;
+
+        writeln!(
+            w,
+            "Daemon: {} v{} (uptime: {})",
+            "running".green().bold(),
+            self.version,
+            format_secs(self.uptime_secs)
+        )?;
+
+        writeln!(w)?;
+        writeln!(
+            w,
+            "{:<20} {:<12} {:<13} {:<10} Since",
+            "Module", "State", "Reason", "Restarts"
+        )?;
+        writeln!(w, "{}", "-".repeat(70))?;
+
+        for m in &self.modules {
+            let code = format!("{:<13}", m.reason_code);
+            let code_colored = match m.reason_code.as_str() {
+                "ok" => code.green(),
+                "degraded" | "not_running" => code.yellow(),
+                _ => code.red(),
+            };
+            writeln!(
+                w,
+                "{:<20} {:<12} {} {:<10} {} ago",
+                m.name,
+                m.state,
+                code_colored,
+                m.restarts,
+                format_secs(self.generated_at.saturating_sub(m.since))
+            )?;
+
+            if let Some(reason) = &m.reason {
+                writeln!(w, "  {}", reason.dimmed())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Compact duration such as `2d3h`, `5m12s` or `42s`.
+fn format_secs(secs: u64) -> String {
+    let (days, hours, mins, secs) = (
+        secs / 86_400,
+        (secs / 3600) % 24,
+        (secs / 60) % 60,
+        secs % 60,
+    );
+    if days > 0 {
+        format!("{}d{}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h{}m", hours, mins)
+    } else if mins > 0 {
+        format!("{}m{}s", mins, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
 impl Render for StatusReport {
     fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
         use colored::Colorize;
@@ -622,4 +746,62 @@ mod tests {
         let output = String::from_utf8(buffer).expect("valid UTF-8");
         assert!(output.contains("no"), "should show 'no' for disabled");
     }
+
+    fn health_report_json() -> &'static str {
+        r#"{
+            "version": "0.1.0",
+            "status": {"Degraded": "container-guard quarantined"},
+            "started_at": 1000,
+            "uptime_secs": 3720,
+            "generated_at": 4720,
+            "modules": [
+                {"name": "log-pipeline", "state": "running", "status": "Healthy",
+                 "reason_code": "ok", "since": 1000, "restarts": 0},
+                {"name": "container-guard", "state": "stopped", "status": "Healthy",
+                 "reason_code": "quarantined", "reason": "quarantined: docker unreachable",
+                 "since": 4000, "restarts": 3}
+            ]
+        }"#
+    }
+
+    #[test]
+    fn test_health_report_deserializes_daemon_json() {
+        let report: HealthReport =
+            serde_json::from_str(health_report_json()).expect("should deserialize");
+
+        assert_eq!(report.version, "0.1.0");
+        assert_eq!(report.modules.len(), 2);
+        assert_eq!(report.modules[0].reason, None);
+        assert_eq!(report.modules[1].reason_code, "quarantined");
+        assert_eq!(report.modules[1].restarts, 3);
+    }
+
+    #[test]
+    fn test_health_report_render_text() {
+        let report: HealthReport =
+            serde_json::from_str(health_report_json()).expect("should deserialize");
+
+        let mut buffer = Vec::new();
+        report
+            .render_text(&mut buffer)
+            .expect("text rendering should succeed");
+
+        let output = String::from_utf8(buffer).expect("valid UTF-8");
+        assert!(output.contains("v0.1.0"), "should show version");
+        assert!(output.contains("1h2m"), "should show uptime");
+        assert!(output.contains("quarantined"), "should show reason code");
+        assert!(
+            output.contains("docker unreachable"),
+            "should show reason text"
+        );
+        assert!(output.contains("12m0s ago"), "should show time in state");
+    }
+
+    #[test]
+    fn test_format_secs() {
+        assert_eq!(format_secs(42), "42s");
+        assert_eq!(format_secs(312), "5m12s");
+        assert_eq!(format_secs(3720), "1h2m");
+        assert_eq!(format_secs(86_400 * 2 + 3600 * 3), "2d3h");
+    }
 }
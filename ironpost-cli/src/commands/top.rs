@@ -0,0 +1,683 @@
+//! `ironpost top` command handler
+//!
+//! Full-screen terminal dashboard for servers without browser access. Polls
+//! the running daemon's control endpoints every `--interval` seconds and shows
+//! four panes: module health (`/status`), eBPF traffic counters (`/ebpf`),
+//! live alerts (`/alerts`) and recent container guard policy matches and
+//! isolation actions (`/container/events`). Panes of modules disabled in the
+//! configuration say so instead of polling.
+//!
+//! If the daemon cannot be reached the last data stays on screen and the
+//! footer shows the error; polling continues, so the dashboard recovers when
+//! the daemon comes back.
+//!
+//! Keys: `q`, `Esc` or `Ctrl-C` quit; `r` refreshes immediately.
+
+use std::collections::VecDeque;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use serde::Deserialize;
+use tracing::debug;
+
+use ironpost_container_guard::GuardActivity;
+use ironpost_core::config::IronpostConfig;
+use ironpost_core::pipeline::HealthStatus;
+use ironpost_core::types::Severity;
+
+use crate::cli::TopArgs;
+use crate::client::DaemonClient;
+use crate::commands::alerts::{AlertEntry, AlertPage, fetch_alerts};
+use crate::commands::container::{EventFeed, WatchEvent, fetch_events, format_time, short_id};
+use crate::commands::ebpf::{EBPF_STATUS_PATH, EbpfStatus, format_bps};
+use crate::error::CliError;
+
+/// Daemon control endpoint serving module health.
+const STATUS_PATH: &str = "/status";
+
+/// Alerts kept in the alerts pane.
+const MAX_ALERTS: usize = 200;
+
+/// Policy matches and actions kept in the container pane.
+const MAX_ACTIONS: usize = 100;
+
+/// How long to wait for a key press before redrawing.
+const KEY_POLL: Duration = Duration::from_millis(200);
+
+/// Execute the `top` command.
+///
+/// # Errors
+///
+/// Returns `CliError::Command` if stdout is not a terminal and
+/// `CliError::Io` if the terminal cannot be set up or drawn to.
+pub async fn execute(args: TopArgs, config_path: &Path) -> Result<(), CliError> {
+    if !std::io::stdout().is_terminal() {
+        return Err(CliError::Command(
+            "ironpost top needs an interactive terminal; use `status`, `ebpf stats` or `alerts tail` for scripts"
+                .to_owned(),
+        ));
+    }
+
+    let config = IronpostConfig::load(config_path).await?;
+    let client = DaemonClient::from_config(&config)?;
+    debug!(addr = client.addr(), "starting dashboard");
+
+    let mut dashboard =
+        Dashboard::new(client.addr(), config.ebpf.enabled, config.container.enabled);
+    let interval = Duration::from_secs(args.interval);
+
+    let mut terminal = ratatui::try_init()?;
+    let result = run_dashboard(&mut terminal, &client, &mut dashboard, interval).await;
+    let restored = ratatui::try_restore();
+    result?;
+    restored?;
+    Ok(())
+}
+
+/// Refresh, draw and handle keys until the user quits.
+async fn run_dashboard(
+    terminal: &mut DefaultTerminal,
+    client: &DaemonClient,
+    dashboard: &mut Dashboard,
+    interval: Duration,
+) -> Result<(), CliError> {
+    let mut next_refresh = Instant::now();
+    loop {
+        if Instant::now() >= next_refresh {
+            dashboard.refresh(client).await;
+            next_refresh = Instant::now() + interval;
+        }
+        terminal.draw(|frame| draw(frame, dashboard))?;
+
+        let key = tokio::task::spawn_blocking(|| read_key(KEY_POLL))
+            .await
+            .map_err(|e| CliError::Command(format!("terminal input failed: {}", e)))??;
+        match key.map(key_action) {
+            Some(KeyAction::Quit) => return Ok(()),
+            Some(KeyAction::Refresh) => next_refresh = Instant::now(),
+            Some(KeyAction::None) | None => {}
+        }
+    }
+}
+
+/// Wait up to `timeout` for a key press.
+fn read_key(timeout: Duration) -> std::io::Result<Option<KeyEvent>> {
+    if event::poll(timeout)?
+        && let Event::Key(key) = event::read()?
+        && key.kind == KeyEventKind::Press
+    {
+        return Ok(Some(key));
+    }
+    Ok(None)
+}
+
+/// What a key press does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyAction {
+    Quit,
+    Refresh,
+    None,
+}
+
+fn key_action(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => KeyAction::Quit,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Quit,
+        KeyCode::Char('r') => KeyAction::Refresh,
+        _ => KeyAction::None,
+    }
+}
+
+/// Module health returned by the daemon's `/status` endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct DaemonStatus {
+    status: HealthStatus,
+    uptime_secs: u64,
+    modules: Vec<ModuleEntry>,
+}
+
+/// Health of a single module.
+#[derive(Debug, Clone, Deserialize)]
+struct ModuleEntry {
+    name: String,
+    enabled: bool,
+    status: HealthStatus,
+}
+
+/// Everything the dashboard shows, updated on each refresh.
+struct Dashboard {
+    addr: String,
+    ebpf_enabled: bool,
+    container_enabled: bool,
+    status: Option<DaemonStatus>,
+    ebpf: Option<EbpfStatus>,
+    /// Newest last
+    alerts: VecDeque<AlertEntry>,
+    /// Policy matches and actions, newest last
+    actions: VecDeque<WatchEvent>,
+    alert_seq: u64,
+    event_seq: u64,
+    error: Option<String>,
+    updated_ms: Option<u64>,
+}
+
+impl Dashboard {
+    fn new(addr: &str, ebpf_enabled: bool, container_enabled: bool) -> Self {
+        Self {
+            addr: addr.to_owned(),
+            ebpf_enabled,
+            container_enabled,
+            status: None,
+            ebpf: None,
+            alerts: VecDeque::new(),
+            actions: VecDeque::new(),
+            alert_seq: 0,
+            event_seq: 0,
+            error: None,
+            updated_ms: None,
+        }
+    }
+
+    /// Poll the daemon, keeping the previous data if it fails.
+    async fn refresh(&mut self, client: &DaemonClient) {
+        match self.poll(client).await {
+            Ok(()) => {
+                self.error = None;
+                self.updated_ms = Some(now_ms());
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    async fn poll(&mut self, client: &DaemonClient) -> Result<(), CliError> {
+        self.status = Some(client.get_json(STATUS_PATH).await?);
+        if self.ebpf_enabled {
+            self.ebpf = Some(client.get_json(EBPF_STATUS_PATH).await?);
+        }
+        let page = fetch_alerts(client, self.alert_seq).await?;
+        self.apply_alerts(page);
+        if self.container_enabled {
+            let feed = fetch_events(client, self.event_seq).await?;
+            self.apply_events(feed);
+        }
+        Ok(())
+    }
+
+    fn apply_alerts(&mut self, page: AlertPage) {
+        if page.last_seq < self.alert_seq {
+            // Daemon restarted: read its new feed from the start next time
+            self.alert_seq = 0;
+            return;
+        }
+        self.alerts.extend(page.alerts);
+        let excess = self.alerts.len().saturating_sub(MAX_ALERTS);
+        self.alerts.drain(..excess);
+        self.alert_seq = page.last_seq;
+    }
+
+    fn apply_events(&mut self, feed: EventFeed) {
+        if feed.last_seq < self.event_seq {
+            self.event_seq = 0;
+            return;
+        }
+        self.actions.extend(
+            feed.events
+                .into_iter()
+                .filter(|e| !matches!(e.activity, GuardActivity::Lifecycle { .. })),
+        );
+        let excess = self.actions.len().saturating_sub(MAX_ACTIONS);
+        self.actions.drain(..excess);
+        self.event_seq = feed.last_seq;
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+/// Render the dashboard into `frame`.
+fn draw(frame: &mut Frame, dashboard: &Dashboard) {
+    let [header, upper, lower, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(9),
+        Constraint::Min(6),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [modules, traffic] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(upper);
+    let [alerts, actions] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(lower);
+
+    frame.render_widget(Paragraph::new(header_line(dashboard)), header);
+    draw_modules(frame, modules, dashboard);
+    draw_traffic(frame, traffic, dashboard);
+    draw_alerts(frame, alerts, dashboard);
+    draw_actions(frame, actions, dashboard);
+    frame.render_widget(Paragraph::new(footer_line(dashboard)), footer);
+}
+
+fn header_line(dashboard: &Dashboard) -> Line<'static> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut spans = vec![
+        Span::styled("ironpost top", bold),
+        Span::raw(format!("  {}  ", dashboard.addr)),
+    ];
+    match &dashboard.status {
+        Some(status) => {
+            spans.push(Span::styled(
+                status.status.to_string(),
+                health_style(&status.status).add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::raw(format!("  uptime {}s", status.uptime_secs)));
+        }
+        None => spans.push(Span::styled("connecting...", dimmed())),
+    }
+    Line::from(spans)
+}
+
+fn footer_line(dashboard: &Dashboard) -> Line<'static> {
+    if let Some(error) = &dashboard.error {
+        return Line::from(Span::styled(
+            format!("daemon unreachable: {}", error),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    let updated = dashboard
+        .updated_ms
+        .map(|ms| format!("  updated {}", format_time(ms)))
+        .unwrap_or_default();
+    Line::from(Span::styled(
+        format!("q quit  r refresh{}", updated),
+        dimmed(),
+    ))
+}
+
+fn draw_modules(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let block = Block::bordered().title(" Modules ");
+    let Some(status) = &dashboard.status else {
+        frame.render_widget(waiting().block(block), area);
+        return;
+    };
+    let rows = status.modules.iter().map(|m| {
+        let (text, style) = if m.enabled {
+            (m.status.to_string(), health_style(&m.status))
+        } else {
+            ("disabled".to_owned(), dimmed())
+        };
+        Row::new(vec![Span::raw(m.name.clone()), Span::styled(text, style)])
+    });
+    let table = Table::new(rows, [Constraint::Length(16), Constraint::Min(10)]).block(block);
+    frame.render_widget(table, area);
+}
+
+fn draw_traffic(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let title = match &dashboard.ebpf {
+        Some(ebpf) if ebpf.attached => format!(" Traffic ({}) ", ebpf.interface),
+        Some(ebpf) => format!(" Traffic ({}, detached) ", ebpf.interface),
+        None => " Traffic ".to_owned(),
+    };
+    let block = Block::bordered().title(title);
+    if !dashboard.ebpf_enabled {
+        frame.render_widget(
+            Paragraph::new(Span::styled("eBPF engine disabled", dimmed())).block(block),
+            area,
+        );
+        return;
+    }
+    let Some(ebpf) = &dashboard.ebpf else {
+        frame.render_widget(waiting().block(block), area);
+        return;
+    };
+    let header = Row::new(["Protocol", "Packets", "PPS", "Throughput", "Drops"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let rows = ebpf.stats.rows().map(|(name, c)| {
+        let row = Row::new(vec![
+            name.to_owned(),
+            c.packets.to_string(),
+            format!("{:.1}", c.pps),
+            format_bps(c.bps),
+            c.drops.to_string(),
+        ]);
+        if name == "total" {
+            row.style(Style::default().add_modifier(Modifier::BOLD))
+        } else {
+            row
+        }
+    });
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(14),
+        Constraint::Length(10),
+        Constraint::Length(12),
+        Constraint::Length(10),
+    ];
+    frame.render_widget(Table::new(rows, widths).header(header).block(block), area);
+}
+
+fn draw_alerts(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let block = Block::bordered().title(format!(" Alerts ({}) ", dashboard.alerts.len()));
+    if dashboard.alerts.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled("No alerts.", dimmed())).block(block),
+            area,
+        );
+        return;
+    }
+    let items: Vec<ListItem> = dashboard
+        .alerts
+        .iter()
+        .rev()
+        .map(|a| {
+            let severity = a.severity.to_string();
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{} ", format_time(a.timestamp_ms))),
+                Span::styled(format!("{:<8} ", severity), severity_style(a.severity)),
+                Span::styled(
+                    format!("{:<13} ", a.module),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(a.title.clone()),
+            ]))
+        })
+        .collect();
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+fn draw_actions(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let block = Block::bordered().title(" Container Actions ");
+    if !dashboard.container_enabled {
+        frame.render_widget(
+            Paragraph::new(Span::styled("Container guard disabled", dimmed())).block(block),
+            area,
+        );
+        return;
+    }
+    if dashboard.actions.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled("No actions.", dimmed())).block(block),
+            area,
+        );
+        return;
+    }
+    let items: Vec<ListItem> = dashboard
+        .actions
+        .iter()
+        .rev()
+        .map(|e| ListItem::new(action_line(e)))
+        .collect();
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+fn action_line(event: &WatchEvent) -> Line<'static> {
+    let time = Span::raw(format!("{} ", format_time(event.timestamp_ms)));
+    match &event.activity {
+        GuardActivity::PolicyMatch {
+            policy_id,
+            container_name,
+            action,
+            standby,
+            ..
+        } => {
+            let note = if *standby { " (standby)" } else { "" };
+            Line::from(vec![
+                time,
+                Span::styled("match  ", Style::default().fg(Color::Yellow)),
+                Span::raw(format!(
+                    "{} {} -> {}{}",
+                    policy_id, container_name, action, note
+                )),
+            ])
+        }
+        GuardActivity::Action {
+            action_type,
+            target,
+            success,
+            simulated,
+            ..
+        } => {
+            let result = if *success {
+                Span::styled("ok     ", Style::default().fg(Color::Green))
+            } else {
+                Span::styled(
+                    "failed ",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )
+            };
+            let note = if *simulated { " (dry run)" } else { "" };
+            Line::from(vec![
+                time,
+                result,
+                Span::raw(format!("{} {}{}", action_type, short_id(target), note)),
+            ])
+        }
+        GuardActivity::Lifecycle {
+            container_name,
+            event,
+            ..
+        } => Line::from(vec![
+            time,
+            Span::raw(format!("{} {}", container_name, event)),
+        ]),
+    }
+}
+
+fn waiting() -> Paragraph<'static> {
+    Paragraph::new(Span::styled("waiting for daemon...", dimmed()))
+}
+
+fn dimmed() -> Style {
+    Style::default().add_modifier(Modifier::DIM)
+}
+
+fn health_style(status: &HealthStatus) -> Style {
+    match status {
+        HealthStatus::Healthy => Style::default().fg(Color::Green),
+        HealthStatus::Degraded(_) => Style::default().fg(Color::Yellow),
+        HealthStatus::Unhealthy(_) => Style::default().fg(Color::Red),
+    }
+}
+
+fn severity_style(severity: Severity) -> Style {
+    match severity {
+        Severity::Critical => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        Severity::High => Style::default().fg(Color::Red),
+        Severity::Medium => Style::default().fg(Color::Yellow),
+        Severity::Low => Style::default(),
+        Severity::Info => dimmed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn alert(seq: u64) -> AlertEntry {
+        AlertEntry {
+            seq,
+            id: format!("alert-{}", seq),
+            timestamp_ms: 1_700_000_000_000,
+            severity: Severity::High,
+            module: "log-pipeline".to_owned(),
+            rule: "ssh_brute_force".to_owned(),
+            title: format!("SSH brute force #{}", seq),
+            description: String::new(),
+            source_ip: None,
+            target_ip: None,
+            source_enrichment: None,
+            target_enrichment: None,
+        }
+    }
+
+    fn page(last_seq: u64, seqs: std::ops::RangeInclusive<u64>) -> AlertPage {
+        AlertPage {
+            last_seq,
+            truncated: false,
+            alerts: seqs.map(alert).collect(),
+        }
+    }
+
+    fn screen(dashboard: &Dashboard) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).expect("test terminal");
+        terminal
+            .draw(|frame| draw(frame, dashboard))
+            .expect("draw should succeed");
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_alerts_keeps_newest() {
+        let mut dashboard = Dashboard::new("127.0.0.1:9090", false, false);
+        let last = u64::try_from(MAX_ALERTS).expect("fits") + 5;
+
+        dashboard.apply_alerts(page(last, 1..=last));
+
+        assert_eq!(dashboard.alerts.len(), MAX_ALERTS);
+        assert_eq!(dashboard.alerts.back().map(|a| a.seq), Some(last));
+        assert_eq!(dashboard.alert_seq, last);
+    }
+
+    #[test]
+    fn test_apply_alerts_resets_cursor_on_daemon_restart() {
+        let mut dashboard = Dashboard::new("127.0.0.1:9090", false, false);
+        dashboard.apply_alerts(page(10, 9..=10));
+
+        dashboard.apply_alerts(page(2, 1..=2));
+
+        assert_eq!(dashboard.alert_seq, 0);
+        assert_eq!(
+            dashboard.alerts.len(),
+            2,
+            "old alerts stay until new ones arrive"
+        );
+    }
+
+    #[test]
+    fn test_apply_events_skips_lifecycle() {
+        let mut dashboard = Dashboard::new("127.0.0.1:9090", false, true);
+        let feed = EventFeed {
+            last_seq: 2,
+            truncated: false,
+            events: vec![
+                WatchEvent {
+                    seq: 1,
+                    timestamp_ms: 1_700_000_000_000,
+                    activity: GuardActivity::Lifecycle {
+                        container_id: "abc".to_owned(),
+                        container_name: "web-1".to_owned(),
+                        event: "started".to_owned(),
+                    },
+                },
+                WatchEvent {
+                    seq: 2,
+                    timestamp_ms: 1_700_000_000_000,
+                    activity: GuardActivity::Action {
+                        action_id: "act-1".to_owned(),
+                        action_type: "container_pause".to_owned(),
+                        target: "abc123def456789".to_owned(),
+                        success: true,
+                        simulated: false,
+                    },
+                },
+            ],
+        };
+
+        dashboard.apply_events(feed);
+
+        assert_eq!(dashboard.actions.len(), 1);
+        assert_eq!(dashboard.event_seq, 2);
+    }
+
+    #[test]
+    fn test_key_action() {
+        assert_eq!(
+            key_action(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)),
+            KeyAction::Quit
+        );
+        assert_eq!(
+            key_action(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            KeyAction::Quit
+        );
+        assert_eq!(
+            key_action(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE)),
+            KeyAction::Refresh
+        );
+        assert_eq!(
+            key_action(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE)),
+            KeyAction::None
+        );
+    }
+
+    #[test]
+    fn test_draw_shows_panes_and_disabled_modules() {
+        let mut dashboard = Dashboard::new("127.0.0.1:9090", false, false);
+        dashboard.status = Some(DaemonStatus {
+            status: HealthStatus::Healthy,
+            uptime_secs: 42,
+            modules: vec![ModuleEntry {
+                name: "log-pipeline".to_owned(),
+                enabled: true,
+                status: HealthStatus::Healthy,
+            }],
+        });
+        dashboard.apply_alerts(page(1, 1..=1));
+
+        let output = screen(&dashboard);
+
+        assert!(output.contains("Modules"));
+        assert!(output.contains("log-pipeline"));
+        assert!(output.contains("uptime 42s"));
+        assert!(output.contains("eBPF engine disabled"));
+        assert!(output.contains("Container guard disabled"));
+        assert!(output.contains("SSH brute force #1"));
+    }
+
+    #[test]
+    fn test_draw_shows_error_in_footer() {
+        let mut dashboard = Dashboard::new("127.0.0.1:9090", true, true);
+        dashboard.error = Some("connection refused".to_owned());
+
+        let output = screen(&dashboard);
+
+        assert!(output.contains("daemon unreachable: connection refused"));
+        assert!(output.contains("waiting for daemon..."));
+    }
+
+    #[test]
+    fn test_daemon_status_deserializes_health() {
+        let json = r#"{
+            "status": {"Degraded": "log-pipeline: backlog"},
+            "uptime_secs": 10,
+            "modules": [
+                {"name": "log-pipeline", "enabled": true, "status": {"Degraded": "backlog"}},
+                {"name": "ebpf-engine", "enabled": false, "status": "Healthy"}
+            ],
+            "tasks": [],
+            "resources": {}
+        }"#;
+
+        let status: DaemonStatus = serde_json::from_str(json).expect("should deserialize");
+
+        assert_eq!(status.modules.len(), 2);
+        assert!(!status.modules[1].enabled);
+        assert!(matches!(status.status, HealthStatus::Degraded(_)));
+    }
+}
@@ -18,13 +18,16 @@ pub enum CliError {
 
     /// Cannot connect to the daemon (e.g., for `status`).
     #[error("daemon not reachable: {0}")]
-    #[allow(dead_code)] // Reserved for future use with daemon health API
     DaemonUnavailable(String),
 
     /// JSON serialisation failed during output rendering.
     #[error("json output error: {0}")]
     JsonSerialize(#[from] serde_json::Error),
 
+    /// YAML serialisation failed during output rendering.
+    #[error("yaml output error: {0}")]
+    YamlSerialize(#[from] serde_yaml::Error),
+
     /// IO error (file read, stdout write, etc.).
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
@@ -59,7 +62,11 @@ impl CliError {
             Self::DaemonUnavailable(_) => 3,
             Self::Scan(_) => 4,
             Self::Io(_) => 10,
-            Self::JsonSerialize(_) | Self::Command(_) | Self::Core(_) | Self::Rule(_) => 1,
+            Self::JsonSerialize(_)
+            | Self::YamlSerialize(_)
+            | Self::Command(_)
+            | Self::Core(_)
+            | Self::Rule(_) => 1,
         }
     }
 }
@@ -131,6 +138,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_exit_code_yaml_serialize_error() {
+        let yaml_err = serde_yaml::from_str::<serde_yaml::Value>("key: [unclosed")
+            .expect_err("should fail parsing");
+        let err = CliError::YamlSerialize(yaml_err);
+        assert_eq!(
+            err.exit_code(),
+            1,
+            "yaml serialize error should return exit code 1"
+        );
+    }
+
     #[test]
     fn test_exit_code_rule_error() {
         let err = CliError::Rule("invalid rule".to_owned());
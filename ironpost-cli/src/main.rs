@@ -7,6 +7,7 @@ use clap::Parser;
 use tracing_subscriber::EnvFilter;
 
 mod cli;
+mod client;
 mod commands;
 mod error;
 mod output;
@@ -18,6 +19,12 @@ use output::OutputWriter;
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
+    client::set_overrides(client::ConnectionOverrides {
+        host: cli.host.clone(),
+        socket: cli.socket.clone(),
+        token: cli.token.clone(),
+        insecure: cli.insecure,
+    });
 
     // Initialize tracing with minimal subscriber for CLI
     // Structured JSON would be noisy for interactive use, so we use compact format
@@ -52,8 +59,19 @@ async fn run(cli: Cli, writer: &OutputWriter) -> Result<(), CliError> {
     match cli.command {
         Commands::Start(args) => commands::start::execute(args, &cli.config).await,
         Commands::Status(args) => commands::status::execute(args, &cli.config, writer).await,
+        Commands::Module(args) => commands::module::execute(args, &cli.config, writer).await,
+        Commands::Ebpf(args) => commands::ebpf::execute(args, &cli.config, writer).await,
+        Commands::Container(args) => commands::container::execute(args, &cli.config, writer).await,
+        Commands::Alerts(args) => commands::alerts::execute(args, &cli.config, writer).await,
+        Commands::Log(args) => commands::log::execute(args, &cli.config, writer).await,
+        Commands::Top(args) => commands::top::execute(args, &cli.config).await,
         Commands::Scan(args) => commands::scan::execute(args, &cli.config, writer).await,
+        Commands::Sbom(args) => commands::sbom::execute(args, &cli.config, writer).await,
         Commands::Rules(args) => commands::rules::execute(args, &cli.config, writer).await,
+        Commands::Content(args) => commands::content::execute(args, &cli.config, writer).await,
         Commands::Config(args) => commands::config::execute(args, &cli.config, writer).await,
+        Commands::Report(args) => commands::report::execute(args, &cli.config, writer).await,
+        Commands::Bench(args) => commands::bench::execute(args, &cli.config, writer).await,
+        Commands::Replay(args) => commands::replay::execute(args, &cli.config, writer).await,
     }
 }
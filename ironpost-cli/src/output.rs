@@ -1,4 +1,4 @@
-//! Output formatting abstraction for text vs JSON rendering
+//! Output formatting abstraction for table, JSON and YAML rendering
 //!
 //! All subcommand output flows through [`OutputWriter`] which handles format switching.
 //! This keeps format-specific logic out of command handlers entirely.
@@ -13,7 +13,8 @@ use crate::error::CliError;
 /// Abstraction for writing CLI output in different formats.
 ///
 /// Subcommand handlers call `writer.render(&payload)` where `payload`
-/// implements both `Serialize` (for JSON) and `Render` (for text).
+/// implements both `Serialize` (for JSON and YAML) and `Render` (for tables
+/// and text).
 pub struct OutputWriter {
     format: OutputFormat,
 }
@@ -36,21 +37,82 @@ impl OutputWriter {
     /// Render a payload to stdout.
     ///
     /// For `Text` format, delegates to `Render::render_text()`.
-    /// For `Json` format, serialises via `serde_json`.
+    /// For `Json` and `Yaml` formats, serialises the payload.
     pub fn render<T: Render + Serialize>(&self, payload: &T) -> Result<(), CliError> {
         let stdout = std::io::stdout();
         let mut handle = stdout.lock();
+        self.render_to(&mut handle, payload)
+    }
+
+    /// Render a payload to `w` in the writer's format.
+    pub fn render_to<T: Render + Serialize>(
+        &self,
+        w: &mut dyn Write,
+        payload: &T,
+    ) -> Result<(), CliError> {
         match self.format {
             OutputFormat::Text => {
-                payload.render_text(&mut handle)?;
+                payload.render_text(w)?;
             }
             OutputFormat::Json => {
-                serde_json::to_writer_pretty(&mut handle, payload)?;
-                writeln!(handle)?;
+                serde_json::to_writer_pretty(&mut *w, payload)?;
+                writeln!(w)?;
+            }
+            OutputFormat::Yaml => {
+                serde_yaml::to_writer(&mut *w, payload)?;
             }
         }
         Ok(())
     }
+
+    /// Render one item of a stream to stdout and flush it.
+    ///
+    /// For `Json` format the item is written as a single compact line, so a
+    /// stream of items is valid JSON Lines. For `Yaml` format each item is a
+    /// separate `---` document.
+    pub fn render_line<T: Render + Serialize>(&self, payload: &T) -> Result<(), CliError> {
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        self.render_line_to(&mut handle, payload)?;
+        handle.flush()?;
+        Ok(())
+    }
+
+    /// Render one item of a stream to `w` in the writer's format.
+    pub fn render_line_to<T: Render + Serialize>(
+        &self,
+        w: &mut dyn Write,
+        payload: &T,
+    ) -> Result<(), CliError> {
+        match self.format {
+            OutputFormat::Text => {
+                payload.render_text(w)?;
+            }
+            OutputFormat::Json => {
+                serde_json::to_writer(&mut *w, payload)?;
+                writeln!(w)?;
+            }
+            OutputFormat::Yaml => {
+                writeln!(w, "---")?;
+                serde_yaml::to_writer(&mut *w, payload)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Clear the terminal before a refreshed render (text format only).
+    ///
+    /// JSON and YAML output is left untouched so each refresh stays a
+    /// parseable document.
+    pub fn clear_screen(&self) -> Result<(), CliError> {
+        if let OutputFormat::Text = self.format {
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+            write!(handle, "\x1B[2J\x1B[H")?;
+            handle.flush()?;
+        }
+        Ok(())
+    }
 }
 
 /// Trait for human-readable text rendering.
@@ -122,6 +184,59 @@ mod tests {
         );
     }
 
+    fn sample() -> TestPayload {
+        TestPayload {
+            field1: "value".to_owned(),
+            field2: 7,
+        }
+    }
+
+    fn rendered(format: OutputFormat, line: bool) -> String {
+        let writer = OutputWriter::new(format);
+        let mut buffer = Vec::new();
+        if line {
+            writer
+                .render_line_to(&mut buffer, &sample())
+                .expect("rendering should succeed");
+        } else {
+            writer
+                .render_to(&mut buffer, &sample())
+                .expect("rendering should succeed");
+        }
+        String::from_utf8(buffer).expect("valid UTF-8")
+    }
+
+    #[test]
+    fn test_render_to_each_format() {
+        assert!(rendered(OutputFormat::Text, false).contains("Field1: value"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&rendered(OutputFormat::Json, false)).expect("valid JSON");
+        assert_eq!(json["field2"], 7);
+
+        let yaml: serde_yaml::Value =
+            serde_yaml::from_str(&rendered(OutputFormat::Yaml, false)).expect("valid YAML");
+        assert_eq!(yaml["field1"].as_str(), Some("value"));
+        assert_eq!(yaml["field2"].as_u64(), Some(7));
+    }
+
+    #[test]
+    fn test_render_line_to_streams() {
+        let json = rendered(OutputFormat::Json, true);
+        assert_eq!(
+            json.lines().count(),
+            1,
+            "JSON Lines item should be one line"
+        );
+
+        let yaml = rendered(OutputFormat::Yaml, true);
+        assert!(
+            yaml.starts_with("---\n"),
+            "YAML item should start a document"
+        );
+        assert!(yaml.contains("field1: value"));
+    }
+
     #[test]
     fn test_output_writer_json_pretty_formatting() {
         let payload = TestPayload {
@@ -0,0 +1,274 @@
+//! Alert feed for the control API.
+//!
+//! Served on `GET /alerts` by the health listener (`read-only` role when
+//! `[auth]` is enabled). Keeps the most recent alerts that reached the alert
+//! consumer -- after dedup and storm protection -- in a ring buffer, each with
+//! an increasing sequence number. Clients poll with `?since=<seq>` to receive
+//! only the alerts after the last one they saw; `ironpost alerts tail` does
+//! this once a second and `ironpost alerts list` reads the whole buffer.
+//!
+//! The feed lives in memory only: it starts empty when the daemon starts.
+
+use std::collections::VecDeque;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tokio::sync::{Mutex, broadcast, mpsc};
+
+use ironpost_core::event::AlertEvent;
+use ironpost_core::types::Severity;
+
+use crate::support_bundle::RecentEvents;
+
+/// Path of the alert feed.
+pub const ALERTS_PATH: &str = "/alerts";
+
+/// Number of alerts kept for polling clients.
+pub const ALERT_FEED_CAPACITY: usize = 1000;
+
+/// An alert with its position in the feed.
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedAlert {
+    /// Sequence number (starts at 1, increases by one per alert).
+    pub seq: u64,
+    /// Alert ID.
+    pub id: String,
+    /// Time the alert was created (Unix milliseconds).
+    pub timestamp_ms: u64,
+    /// Alert severity.
+    pub severity: Severity,
+    /// Module that raised the alert (e.g. `log-pipeline`).
+    pub module: String,
+    /// Detection rule name.
+    pub rule: String,
+    /// Alert title.
+    pub title: String,
+    /// Alert description.
+    pub description: String,
+    /// Source IP, when known.
+    pub source_ip: Option<IpAddr>,
+    /// Target IP, when known.
+    pub target_ip: Option<IpAddr>,
+}
+
+/// JSON body returned by `/alerts`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertPage {
+    /// Sequence number of the newest alert (0 before the first one).
+    /// Pass it as `since` on the next poll.
+    pub last_seq: u64,
+    /// Whether alerts after `since` were evicted before this poll.
+    pub truncated: bool,
+    /// Alerts after `since`, oldest first.
+    pub alerts: Vec<FeedAlert>,
+}
+
+/// Bounded, sequenced buffer of recent alerts.
+///
+/// Cheap to clone; all clones share the same buffer.
+#[derive(Clone)]
+pub struct AlertFeed {
+    capacity: usize,
+    inner: Arc<Mutex<FeedBuffer>>,
+}
+
+struct FeedBuffer {
+    last_seq: u64,
+    alerts: VecDeque<FeedAlert>,
+}
+
+impl AlertFeed {
+    /// Create a feed holding at most `capacity` alerts.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Arc::new(Mutex::new(FeedBuffer {
+                last_seq: 0,
+                alerts: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Append an alert, evicting the oldest one when full.
+    pub async fn record(&self, event: &AlertEvent) {
+        let timestamp_ms = event
+            .alert
+            .created_at
+            .duration_since(UNIX_EPOCH)
+            .or_else(|_| SystemTime::now().duration_since(UNIX_EPOCH))
+            .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+            .unwrap_or_default();
+        let mut buffer = self.inner.lock().await;
+        buffer.last_seq += 1;
+        let seq = buffer.last_seq;
+        if buffer.alerts.len() >= self.capacity {
+            buffer.alerts.pop_front();
+        }
+        buffer.alerts.push_back(FeedAlert {
+            seq,
+            id: event.alert.id.clone(),
+            timestamp_ms,
+            severity: event.severity,
+            module: event.metadata.source_module.clone(),
+            rule: event.alert.rule_name.clone(),
+            title: event.alert.title.clone(),
+            description: event.alert.description.clone(),
+            source_ip: event.alert.source_ip,
+            target_ip: event.alert.target_ip,
+        });
+    }
+
+    /// Alerts with a sequence number greater than `since`.
+    pub async fn since(&self, since: u64) -> AlertPage {
+        let buffer = self.inner.lock().await;
+        let alerts: Vec<FeedAlert> = buffer
+            .alerts
+            .iter()
+            .filter(|a| a.seq > since)
+            .cloned()
+            .collect();
+        let truncated = buffer.last_seq > since
+            && alerts
+                .first()
+                .is_none_or(|first| first.seq > since.saturating_add(1));
+        AlertPage {
+            last_seq: buffer.last_seq,
+            truncated,
+            alerts,
+        }
+    }
+}
+
+/// Relay alerts from producers to the consumer, recording each one in
+/// `recent`.
+///
+/// Used when `[alert_gate]` is disabled and container guard consumes alerts,
+/// so the alert feed and support bundles still see them. Runs until the input
+/// channel closes or shutdown is signalled.
+pub fn spawn_alert_tap(
+    mut alert_rx: mpsc::Receiver<AlertEvent>,
+    alert_tx: mpsc::Sender<AlertEvent>,
+    recent: RecentEvents,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                received = alert_rx.recv() => {
+                    let Some(alert) = received else {
+                        tracing::debug!("alert channel closed, exiting alert tap");
+                        break;
+                    };
+                    recent.record_alert(&alert).await;
+                    if alert_tx.send(alert).await.is_err() {
+                        tracing::debug!("alert consumer closed, exiting alert tap");
+                        break;
+                    }
+                }
+                _ = shutdown_rx.recv() => {
+                    tracing::debug!("alert tap shutting down");
+                    break;
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_core::types::Alert;
+
+    fn alert(rule: &str, severity: Severity) -> AlertEvent {
+        let alert = Alert {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: format!("{} detected", rule),
+            description: String::new(),
+            severity,
+            rule_name: rule.to_owned(),
+            source_ip: "10.0.0.1".parse().ok(),
+            target_ip: None,
+            created_at: SystemTime::now(),
+        };
+        AlertEvent::new(alert, severity)
+    }
+
+    #[tokio::test]
+    async fn test_since_returns_alerts_after_cursor() {
+        // Given: A feed with three alerts
+        let feed = AlertFeed::new(10);
+        for rule in ["a", "b", "c"] {
+            feed.record(&alert(rule, Severity::High)).await;
+        }
+
+        // When: Polling after the first alert
+        let page = feed.since(1).await;
+
+        // Then: Only the later alerts are returned
+        assert_eq!(page.last_seq, 3);
+        assert!(!page.truncated);
+        let rules: Vec<&str> = page.alerts.iter().map(|a| a.rule.as_str()).collect();
+        assert_eq!(rules, vec!["b", "c"]);
+        assert!(feed.since(3).await.alerts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_since_reports_evicted_alerts() {
+        // Given: A feed of capacity 2 that has seen four alerts
+        let feed = AlertFeed::new(2);
+        for rule in ["a", "b", "c", "d"] {
+            feed.record(&alert(rule, Severity::Low)).await;
+        }
+
+        // When: Polling from a cursor older than the buffer
+        let page = feed.since(1).await;
+
+        // Then: The remaining alerts are returned and the gap is flagged
+        assert!(page.truncated);
+        assert_eq!(page.alerts.len(), 2);
+        assert_eq!(page.alerts[0].seq, 3);
+        assert!(!feed.since(2).await.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_feed_alert_carries_module_and_rule() {
+        let feed = AlertFeed::new(10);
+        feed.record(&alert("ssh_brute_force", Severity::Critical))
+            .await;
+
+        let json = serde_json::to_value(feed.since(0).await).expect("should serialize");
+        let entry = &json["alerts"][0];
+        assert_eq!(entry["seq"], 1);
+        assert_eq!(entry["rule"], "ssh_brute_force");
+        assert_eq!(entry["severity"], "Critical");
+        assert_eq!(entry["source_ip"], "10.0.0.1");
+        assert!(entry["module"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_alert_tap_records_and_forwards() {
+        // Given: A tap between a producer and a consumer channel
+        let (producer_tx, producer_rx) = mpsc::channel(4);
+        let (consumer_tx, mut consumer_rx) = mpsc::channel(4);
+        let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let recent = RecentEvents::new(10);
+        let task = spawn_alert_tap(producer_rx, consumer_tx, recent.clone(), shutdown_rx);
+
+        // When: An alert is sent and the producer closes
+        producer_tx
+            .send(alert("port_scan", Severity::Medium))
+            .await
+            .expect("should send");
+        drop(producer_tx);
+        task.await
+            .expect("tap should exit when the producer closes");
+
+        // Then: The consumer receives it and the feed records it
+        let forwarded = consumer_rx.recv().await.expect("should forward");
+        assert_eq!(forwarded.alert.rule_name, "port_scan");
+        assert_eq!(recent.alerts().await.len(), 1);
+        assert_eq!(recent.alert_feed().since(0).await.last_seq, 1);
+    }
+}
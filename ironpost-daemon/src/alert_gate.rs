@@ -0,0 +1,645 @@
+//! Daemon-level alert deduplication and storm protection.
+//!
+//! All module alerts pass through the [`AlertGate`] before reaching the
+//! alert consumer (container guard). It is the last line of defence: even if
+//! a module's own limiter is misconfigured, a detection storm cannot flood
+//! downstream systems.
+//!
+//! - **Dedup**: alerts with the same rule and entity (source/target IP) are
+//!   forwarded once per `dedup_window_secs`.
+//! - **Cap**: at most `max_alerts_per_minute` alerts are forwarded per
+//!   one-minute window. Alerts over the cap are suppressed and, when the
+//!   window closes, a single summary alert reports how many were dropped.
+//!
+//! Summary alerts are `Medium` severity so they page operators without
+//! meeting typical isolation thresholds.
+//!
+//! The log pipeline consults the same gate (through [`SharedAlertGate`] as its
+//! [`AlertAdmission`]) before handing an alert to webhook, chat, email,
+//! Elasticsearch or Kafka sinks, so suppressed alerts never leave the host.
+//! The verdict is remembered by alert ID and reused when the alert later
+//! arrives at the relay, so each alert is judged exactly once.
+
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::Instant;
+
+use ironpost_core::config::AlertGateConfig;
+use ironpost_core::event::{AlertEvent, MODULE_DAEMON};
+use ironpost_core::types::{Alert, Severity};
+use ironpost_log_pipeline::AlertAdmission;
+
+use crate::support_bundle::RecentEvents;
+
+/// Length of a rate-cap window.
+const CAP_WINDOW: Duration = Duration::from_secs(60);
+
+/// How often the relay checks whether the cap window has closed.
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Rule name of summary alerts.
+pub const SUMMARY_RULE_NAME: &str = "ironpost_alert_storm";
+
+/// Dedup key: rule plus the entities an alert is about.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DedupKey {
+    rule: String,
+    source_ip: Option<IpAddr>,
+    target_ip: Option<IpAddr>,
+}
+
+impl DedupKey {
+    fn of(alert: &Alert) -> Self {
+        Self {
+            rule: alert.rule_name.clone(),
+            source_ip: alert.source_ip,
+            target_ip: alert.target_ip,
+        }
+    }
+}
+
+/// Gate decision for a single alert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Forward the alert.
+    Forward,
+    /// Same rule + entity was forwarded within the dedup window.
+    Duplicate,
+    /// The per-minute cap was reached.
+    OverCap,
+}
+
+impl Verdict {
+    /// Metric label for suppressed alerts.
+    fn reason(self) -> &'static str {
+        match self {
+            Self::Forward => "none",
+            Self::Duplicate => "duplicate",
+            Self::OverCap => "rate_limit",
+        }
+    }
+}
+
+/// Alerts suppressed in the current cap window.
+#[derive(Debug, Default)]
+struct Suppressed {
+    count: u64,
+    highest: Option<Severity>,
+    rules: HashMap<String, u64>,
+}
+
+/// Dedup and rate-cap state.
+pub struct AlertGate {
+    dedup_window: Duration,
+    max_per_minute: u64,
+    max_tracked_keys: usize,
+    /// Last forward time per dedup key.
+    last_seen: HashMap<DedupKey, Instant>,
+    /// Start of the current cap window.
+    window_start: Instant,
+    /// Alerts forwarded in the current cap window.
+    forwarded: u64,
+    /// Alerts dropped by the cap in the current window.
+    suppressed: Suppressed,
+    /// Verdicts given before the alert reached the relay, by alert ID.
+    decided: HashMap<String, Verdict>,
+    /// Insertion order of `decided`, for bounded eviction.
+    decided_order: VecDeque<String>,
+}
+
+impl AlertGate {
+    /// Create a gate from configuration.
+    pub fn new(config: &AlertGateConfig) -> Self {
+        Self::with_start(config, Instant::now())
+    }
+
+    fn with_start(config: &AlertGateConfig, now: Instant) -> Self {
+        Self {
+            dedup_window: Duration::from_secs(config.dedup_window_secs),
+            max_per_minute: config.max_alerts_per_minute,
+            max_tracked_keys: config.max_tracked_keys,
+            last_seen: HashMap::new(),
+            window_start: now,
+            forwarded: 0,
+            suppressed: Suppressed::default(),
+            decided: HashMap::new(),
+            decided_order: VecDeque::new(),
+        }
+    }
+
+    /// Decide whether to forward `alert` at `now`.
+    pub fn admit(&mut self, alert: &AlertEvent, now: Instant) -> Verdict {
+        let key = (!self.dedup_window.is_zero()).then(|| DedupKey::of(&alert.alert));
+        if let Some(key) = &key
+            && let Some(last) = self.last_seen.get(key)
+            && now.saturating_duration_since(*last) < self.dedup_window
+        {
+            return Verdict::Duplicate;
+        }
+
+        if self.max_per_minute > 0 && self.forwarded >= self.max_per_minute {
+            self.record_suppressed(alert);
+            return Verdict::OverCap;
+        }
+
+        if let Some(key) = key {
+            self.track(key, now);
+        }
+        self.forwarded = self.forwarded.saturating_add(1);
+        Verdict::Forward
+    }
+
+    /// Decide on `alert` before it reaches the relay and remember the verdict.
+    ///
+    /// Used by the log pipeline ahead of its sinks; [`Self::admit_relayed`]
+    /// returns the same verdict when the alert arrives at the relay.
+    pub fn admit_early(&mut self, alert: &AlertEvent, now: Instant) -> Verdict {
+        if let Some(verdict) = self.decided.get(&alert.id) {
+            return *verdict;
+        }
+        let verdict = self.admit(alert, now);
+        if self.decided_order.len() >= self.max_tracked_keys
+            && let Some(oldest) = self.decided_order.pop_front()
+        {
+            self.decided.remove(&oldest);
+        }
+        self.decided.insert(alert.id.clone(), verdict);
+        self.decided_order.push_back(alert.id.clone());
+        verdict
+    }
+
+    /// Decide on an alert arriving at the relay.
+    ///
+    /// Reuses (and forgets) a verdict from [`Self::admit_early`], otherwise
+    /// decides now.
+    pub fn admit_relayed(&mut self, alert: &AlertEvent, now: Instant) -> Verdict {
+        match self.decided.remove(&alert.id) {
+            Some(verdict) => {
+                self.decided_order.retain(|id| *id != alert.id);
+                verdict
+            }
+            None => self.admit(alert, now),
+        }
+    }
+
+    /// Close the cap window if it has elapsed.
+    ///
+    /// Returns a summary alert if any alerts were dropped by the cap during
+    /// the closed window.
+    pub fn roll_window(&mut self, now: Instant) -> Option<AlertEvent> {
+        if now.saturating_duration_since(self.window_start) < CAP_WINDOW {
+            return None;
+        }
+        self.window_start = now;
+        self.forwarded = 0;
+        self.take_summary()
+    }
+
+    /// Summary of alerts dropped in the current window, resetting the count.
+    pub fn take_summary(&mut self) -> Option<AlertEvent> {
+        let suppressed = std::mem::take(&mut self.suppressed);
+        (suppressed.count > 0).then(|| summary_alert(&suppressed, self.max_per_minute))
+    }
+
+    /// Remember a forwarded key, evicting stale or oldest entries when full.
+    fn track(&mut self, key: DedupKey, now: Instant) {
+        if self.last_seen.len() >= self.max_tracked_keys && !self.last_seen.contains_key(&key) {
+            let window = self.dedup_window;
+            self.last_seen
+                .retain(|_, last| now.saturating_duration_since(*last) < window);
+            if self.last_seen.len() >= self.max_tracked_keys
+                && let Some(oldest) = self
+                    .last_seen
+                    .iter()
+                    .min_by_key(|(_, last)| **last)
+                    .map(|(key, _)| key.clone())
+            {
+                self.last_seen.remove(&oldest);
+            }
+        }
+        self.last_seen.insert(key, now);
+    }
+
+    fn record_suppressed(&mut self, alert: &AlertEvent) {
+        let suppressed = &mut self.suppressed;
+        suppressed.count = suppressed.count.saturating_add(1);
+        suppressed.highest = suppressed.highest.max(Some(alert.severity));
+        // Bounded by the number of distinct rule names
+        if suppressed.rules.len() < self.max_tracked_keys
+            || suppressed.rules.contains_key(&alert.alert.rule_name)
+        {
+            *suppressed
+                .rules
+                .entry(alert.alert.rule_name.clone())
+                .or_default() += 1;
+        }
+    }
+}
+
+/// An [`AlertGate`] shared by the relay and the log pipeline's sinks.
+#[derive(Clone)]
+pub struct SharedAlertGate(Arc<Mutex<AlertGate>>);
+
+impl SharedAlertGate {
+    /// Share `gate`.
+    pub fn new(gate: AlertGate) -> Self {
+        Self(Arc::new(Mutex::new(gate)))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, AlertGate> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl AlertAdmission for SharedAlertGate {
+    fn admit(&self, alert: &AlertEvent) -> bool {
+        self.lock().admit_early(alert, Instant::now()) == Verdict::Forward
+    }
+}
+
+/// Build the overflow summary alert.
+fn summary_alert(suppressed: &Suppressed, cap: u64) -> AlertEvent {
+    let mut rules: Vec<(&String, &u64)> = suppressed.rules.iter().collect();
+    rules.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    let top: Vec<String> = rules
+        .iter()
+        .take(5)
+        .map(|(rule, count)| format!("{} ({})", rule, count))
+        .collect();
+    let highest = suppressed.highest.unwrap_or_default();
+
+    let alert = Alert {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: format!("Alert storm: {} alerts suppressed", suppressed.count),
+        description: format!(
+            "More than {} alerts per minute; {} alerts were suppressed (highest severity: {}). Top rules: {}",
+            cap,
+            suppressed.count,
+            highest,
+            top.join(", ")
+        ),
+        severity: Severity::Medium,
+        rule_name: SUMMARY_RULE_NAME.to_owned(),
+        source_ip: None,
+        target_ip: None,
+        created_at: SystemTime::now(),
+    };
+    AlertEvent::with_source(alert, Severity::Medium, MODULE_DAEMON)
+}
+
+/// Relay alerts from producers to the consumer through the gate.
+///
+/// Runs until the input channel closes or shutdown is signalled. The
+/// shutdown broadcast is only sent after consumers have stopped, so
+/// alerts queued during the drain phase are still forwarded. Forwarded
+/// alerts and storm summaries are recorded in `recent`.
+pub fn spawn_alert_gate(
+    gate: SharedAlertGate,
+    mut alert_rx: mpsc::Receiver<AlertEvent>,
+    alert_tx: mpsc::Sender<AlertEvent>,
+    recent: RecentEvents,
+    record_metrics: bool,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(TICK_INTERVAL);
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                received = alert_rx.recv() => {
+                    let Some(alert) = received else {
+                        tracing::debug!("alert channel closed, exiting alert gate");
+                        break;
+                    };
+                    let verdict = gate.lock().admit_relayed(&alert, Instant::now());
+                    match verdict {
+                        Verdict::Forward => {
+                            recent.record_alert(&alert).await;
+                            if alert_tx.send(alert).await.is_err() {
+                                tracing::debug!("alert consumer closed, exiting alert gate");
+                                break;
+                            }
+                        }
+                        verdict => {
+                            tracing::debug!(
+                                rule = %alert.alert.rule_name,
+                                reason = verdict.reason(),
+                                "alert suppressed by alert gate"
+                            );
+                            if record_metrics {
+                                use ironpost_core::metrics as m;
+                                metrics::counter!(
+                                    m::DAEMON_ALERTS_SUPPRESSED_TOTAL,
+                                    m::LABEL_REASON => verdict.reason()
+                                )
+                                .increment(1);
+                            }
+                        }
+                    }
+                }
+                _ = tick.tick() => {
+                    let summary = gate.lock().roll_window(Instant::now());
+                    if let Some(summary) = summary {
+                        tracing::warn!(summary = %summary.alert.title, "alert storm detected");
+                        recent.record_alert(&summary).await;
+                        if alert_tx.send(summary).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                _ = shutdown_rx.recv() => {
+                    tracing::debug!("alert gate shutting down");
+                    break;
+                }
+            }
+        }
+
+        // Consumers are gone at this point; report what the last window dropped
+        let summary = gate.lock().take_summary();
+        if let Some(summary) = summary {
+            tracing::warn!(summary = %summary.alert.title, "alert storm summary at shutdown");
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(dedup_window_secs: u64, max_alerts_per_minute: u64) -> AlertGateConfig {
+        AlertGateConfig {
+            enabled: true,
+            dedup_window_secs,
+            max_alerts_per_minute,
+            max_tracked_keys: 100,
+        }
+    }
+
+    fn alert(rule: &str, source: &str, severity: Severity) -> AlertEvent {
+        let alert = Alert {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: rule.to_owned(),
+            description: String::new(),
+            severity,
+            rule_name: rule.to_owned(),
+            source_ip: source.parse().ok(),
+            target_ip: None,
+            created_at: SystemTime::now(),
+        };
+        AlertEvent::new(alert, severity)
+    }
+
+    #[test]
+    fn test_dedup_by_rule_and_entity() {
+        // Given: A gate with a 60s dedup window and no cap
+        let start = Instant::now();
+        let mut gate = AlertGate::with_start(&config(60, 0), start);
+
+        // When: The same rule fires for the same and a different source
+        let first = gate.admit(&alert("ssh", "10.0.0.1", Severity::High), start);
+        let repeat = gate.admit(&alert("ssh", "10.0.0.1", Severity::High), start);
+        let other = gate.admit(&alert("ssh", "10.0.0.2", Severity::High), start);
+        let later = gate.admit(
+            &alert("ssh", "10.0.0.1", Severity::High),
+            start + Duration::from_secs(61),
+        );
+
+        // Then: Only the repeat within the window is suppressed
+        assert_eq!(first, Verdict::Forward);
+        assert_eq!(repeat, Verdict::Duplicate);
+        assert_eq!(other, Verdict::Forward);
+        assert_eq!(later, Verdict::Forward);
+    }
+
+    #[test]
+    fn test_cap_suppresses_and_summarizes() {
+        // Given: A cap of 2 alerts per minute without dedup
+        let start = Instant::now();
+        let mut gate = AlertGate::with_start(&config(0, 2), start);
+
+        // When: Five alerts arrive in one window
+        let verdicts: Vec<Verdict> = (0..5)
+            .map(|i| {
+                let severity = if i == 4 {
+                    Severity::Critical
+                } else {
+                    Severity::Low
+                };
+                gate.admit(&alert("scan", "10.0.0.1", severity), start)
+            })
+            .collect();
+
+        // Then: Two are forwarded, three suppressed
+        assert_eq!(
+            verdicts,
+            vec![
+                Verdict::Forward,
+                Verdict::Forward,
+                Verdict::OverCap,
+                Verdict::OverCap,
+                Verdict::OverCap
+            ]
+        );
+
+        // And: No summary until the window closes, then exactly one
+        assert!(gate.roll_window(start + Duration::from_secs(30)).is_none());
+        let summary = gate.roll_window(start + CAP_WINDOW).unwrap();
+        assert_eq!(summary.alert.rule_name, SUMMARY_RULE_NAME);
+        assert_eq!(summary.severity, Severity::Medium);
+        assert_eq!(summary.metadata.source_module, MODULE_DAEMON);
+        assert!(summary.alert.title.contains('3'));
+        assert!(summary.alert.description.contains("scan (3)"));
+        assert!(summary.alert.description.contains("Critical"));
+
+        // And: The next window starts fresh
+        assert_eq!(
+            gate.admit(
+                &alert("scan", "10.0.0.1", Severity::Low),
+                start + CAP_WINDOW
+            ),
+            Verdict::Forward
+        );
+        assert!(gate.roll_window(start + CAP_WINDOW * 2).is_none());
+    }
+
+    #[test]
+    fn test_duplicates_do_not_count_against_cap() {
+        let start = Instant::now();
+        let mut gate = AlertGate::with_start(&config(60, 1), start);
+        assert_eq!(
+            gate.admit(&alert("ssh", "10.0.0.1", Severity::High), start),
+            Verdict::Forward
+        );
+        assert_eq!(
+            gate.admit(&alert("ssh", "10.0.0.1", Severity::High), start),
+            Verdict::Duplicate
+        );
+        assert!(gate.take_summary().is_none());
+    }
+
+    #[test]
+    fn test_tracked_keys_are_bounded() {
+        let start = Instant::now();
+        let mut gate = AlertGate::with_start(&config(60, 0), start);
+        for i in 0..250 {
+            let source = format!("10.0.{}.{}", i / 250, i % 250);
+            gate.admit(&alert("scan", &source, Severity::Low), start);
+        }
+        assert!(gate.last_seen.len() <= 100);
+    }
+
+    #[test]
+    fn test_early_verdict_is_reused_by_relay() {
+        // Given: A gate consulted by the log pipeline before its sinks
+        let start = Instant::now();
+        let mut gate = AlertGate::with_start(&config(60, 0), start);
+        let first = alert("ssh", "10.0.0.1", Severity::High);
+        let repeat = alert("ssh", "10.0.0.1", Severity::High);
+
+        // When: Both alerts are judged early, then relayed
+        assert_eq!(gate.admit_early(&first, start), Verdict::Forward);
+        assert_eq!(gate.admit_early(&repeat, start), Verdict::Duplicate);
+
+        // Then: The relay reuses each verdict instead of deciding again
+        assert_eq!(gate.admit_relayed(&first, start), Verdict::Forward);
+        assert_eq!(gate.admit_relayed(&repeat, start), Verdict::Duplicate);
+        assert!(gate.decided.is_empty());
+        assert!(gate.decided_order.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_suppressed_duplicate_never_reaches_sink() {
+        use ironpost_core::pipeline::{BoxFuture, Pipeline};
+        use ironpost_log_pipeline::{
+            AlertSink, LogPipelineBuilder, LogPipelineError, PipelineConfig, RawLog, SinkAlert,
+            SinkDelivery,
+        };
+
+        struct CountingSink(Arc<tokio::sync::Mutex<u32>>);
+
+        impl AlertSink for CountingSink {
+            fn name(&self) -> &str {
+                "counting"
+            }
+
+            fn send<'a>(
+                &'a self,
+                _alert: &'a SinkAlert,
+            ) -> BoxFuture<'a, Result<SinkDelivery, LogPipelineError>> {
+                Box::pin(async move {
+                    *self.0.lock().await += 1;
+                    Ok(SinkDelivery::Sent)
+                })
+            }
+        }
+
+        // Given: A pipeline whose sinks sit behind the shared gate, with
+        // pipeline-level dedup kept short so both matches produce alerts
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("ssh.yml"),
+            "id: ssh_fail\ntitle: SSH Failure\nseverity: High\ndetection:\n  conditions:\n    - field: process\n      value: sshd\n",
+        )
+        .unwrap();
+        let gate = SharedAlertGate::new(AlertGate::new(&config(60, 0)));
+        let delivered = Arc::new(tokio::sync::Mutex::new(0));
+        let (alert_tx, alert_rx) = mpsc::channel(16);
+        let (out_tx, mut out_rx) = mpsc::channel(16);
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let relay = spawn_alert_gate(
+            gate.clone(),
+            alert_rx,
+            out_tx,
+            RecentEvents::new(16),
+            false,
+            shutdown_rx,
+        );
+        let (mut pipeline, _) = LogPipelineBuilder::new()
+            .config(PipelineConfig {
+                rule_dir: dir.path().display().to_string(),
+                sources: vec![],
+                enabled: false,
+                batch_size: 1,
+                alert_dedup_window_secs: 1,
+                ..Default::default()
+            })
+            .alert_sender(alert_tx)
+            .alert_sink(
+                Arc::new(CountingSink(Arc::clone(&delivered))),
+                Severity::Info,
+            )
+            .alert_admission(Arc::new(gate))
+            .build()
+            .unwrap();
+        pipeline.start().await.unwrap();
+
+        // When: The same rule fires twice for the same entity
+        let log = || {
+            RawLog::new(
+                bytes::Bytes::from_static(
+                    b"<34>1 2024-01-15T12:00:00Z host sshd 1234 - - Failed password",
+                ),
+                "test",
+            )
+        };
+        pipeline.raw_log_sender().send(log()).await.unwrap();
+        let first = out_rx.recv().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        pipeline.raw_log_sender().send(log()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        pipeline.stop().await.unwrap();
+
+        // Then: The duplicate reaches neither the consumer nor the sink
+        assert_eq!(first.alert.rule_name, "ssh_fail");
+        assert!(out_rx.try_recv().is_err());
+        assert_eq!(*delivered.lock().await, 1);
+
+        shutdown_tx.send(()).unwrap();
+        relay.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_relay_forwards_and_stops_on_shutdown() {
+        // Given: A running relay
+        let (in_tx, in_rx) = mpsc::channel(16);
+        let (out_tx, mut out_rx) = mpsc::channel(16);
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let task = spawn_alert_gate(
+            SharedAlertGate::new(AlertGate::new(&config(60, 0))),
+            in_rx,
+            out_tx,
+            RecentEvents::new(16),
+            false,
+            shutdown_rx,
+        );
+
+        // When: A duplicate pair and a distinct alert are sent
+        in_tx
+            .send(alert("ssh", "10.0.0.1", Severity::High))
+            .await
+            .unwrap();
+        in_tx
+            .send(alert("ssh", "10.0.0.1", Severity::High))
+            .await
+            .unwrap();
+        in_tx
+            .send(alert("sudo", "10.0.0.1", Severity::High))
+            .await
+            .unwrap();
+
+        // Then: Two alerts are forwarded
+        let first = out_rx.recv().await.unwrap();
+        let second = out_rx.recv().await.unwrap();
+        assert_eq!(first.alert.rule_name, "ssh");
+        assert_eq!(second.alert.rule_name, "sudo");
+
+        shutdown_tx.send(()).unwrap();
+        task.await.unwrap();
+        assert!(out_rx.recv().await.is_none());
+    }
+}
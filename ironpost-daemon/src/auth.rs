@@ -0,0 +1,463 @@
+//! Control API authentication and authorization.
+//!
+//! Requests to control endpoints carry an `Authorization: Bearer <token>`
+//! header. Tokens are configured in `[auth]` by their SHA-256 digest and map
+//! to one of three roles:
+//!
+//! - **read-only**: status endpoints
+//! - **operator**: read-only plus blocklist/policy mutation
+//! - **admin**: everything
+//!
+//! Roles are ordered, so a higher role satisfies any lower requirement.
+//! Liveness and readiness probes stay unauthenticated so orchestrators can
+//! keep using them.
+//!
+//! # Transport
+//!
+//! With `tls_cert_path`/`tls_key_path` set, the control listener serves TLS.
+//! Adding `client_ca_path` enables mTLS: only clients presenting a
+//! certificate signed by that CA can connect. The role still comes from the
+//! bearer token.
+
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig, crypto};
+
+use ironpost_core::config::AuthConfig;
+
+/// Control API role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Role {
+    /// Read status only.
+    ReadOnly,
+    /// Read status and mutate blocklists/policies.
+    Operator,
+    /// Full access.
+    Admin,
+}
+
+impl Role {
+    /// Parse a configured role name.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "read-only" => Some(Self::ReadOnly),
+            "operator" => Some(Self::Operator),
+            "admin" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+
+    /// Role name as used in configuration.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read-only",
+            Self::Operator => "operator",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Whether this role satisfies `required`.
+    pub fn allows(self, required: Role) -> bool {
+        self >= required
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An authenticated caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    /// Token name from configuration.
+    pub name: String,
+    /// Granted role.
+    pub role: Role,
+}
+
+/// Authentication or authorization failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// No bearer token was presented.
+    MissingToken,
+    /// The presented token is not configured.
+    InvalidToken,
+    /// The token's role does not allow the request.
+    Forbidden {
+        /// Token name.
+        name: String,
+        /// Token role.
+        role: Role,
+        /// Role required by the endpoint.
+        required: Role,
+    },
+}
+
+impl AuthError {
+    /// HTTP status code for this failure (401 or 403).
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::MissingToken | Self::InvalidToken => 401,
+            Self::Forbidden { .. } => 403,
+        }
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingToken => f.write_str("missing bearer token"),
+            Self::InvalidToken => f.write_str("invalid token"),
+            Self::Forbidden {
+                name,
+                role,
+                required,
+            } => write!(
+                f,
+                "token '{}' has role '{}', endpoint requires '{}'",
+                name, role, required
+            ),
+        }
+    }
+}
+
+/// Configured token: SHA-256 digest and the identity it grants.
+struct TokenEntry {
+    digest: [u8; 32],
+    identity: Identity,
+}
+
+/// Checks bearer tokens against the configured roles.
+pub struct Authorizer {
+    /// `None` when auth is disabled (every request is allowed).
+    tokens: Option<Vec<TokenEntry>>,
+}
+
+impl Authorizer {
+    /// An authorizer that allows every request.
+    pub fn disabled() -> Self {
+        Self { tokens: None }
+    }
+
+    /// Build an authorizer from `[auth]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a token digest or role is invalid.
+    pub fn from_config(config: &AuthConfig) -> Result<Self> {
+        if !config.enabled {
+            return Ok(Self::disabled());
+        }
+
+        let tokens = config
+            .tokens
+            .iter()
+            .map(|token| {
+                let role = Role::parse(&token.role).ok_or_else(|| {
+                    anyhow::anyhow!("token '{}' has unknown role '{}'", token.name, token.role)
+                })?;
+                let digest = decode_digest(&token.token_sha256).ok_or_else(|| {
+                    anyhow::anyhow!("token '{}' has an invalid SHA-256 digest", token.name)
+                })?;
+                Ok(TokenEntry {
+                    digest,
+                    identity: Identity {
+                        name: token.name.clone(),
+                        role,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            tokens: Some(tokens),
+        })
+    }
+
+    /// Whether requests are checked at all.
+    pub fn is_enabled(&self) -> bool {
+        self.tokens.is_some()
+    }
+
+    /// Authorize a raw HTTP request for an endpoint requiring `required`.
+    ///
+    /// Returns `Ok(None)` when auth is disabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AuthError`] if the token is missing, unknown, or its role is
+    /// insufficient.
+    pub fn authorize(&self, request: &str, required: Role) -> Result<Option<Identity>, AuthError> {
+        let Some(tokens) = &self.tokens else {
+            return Ok(None);
+        };
+
+        let token = bearer_token(request).ok_or(AuthError::MissingToken)?;
+        let presented: [u8; 32] = Sha256::digest(token.as_bytes()).into();
+
+        // Compare against every entry so timing does not reveal which matched
+        let mut matched = None;
+        for entry in tokens {
+            if constant_time_eq(&entry.digest, &presented) && matched.is_none() {
+                matched = Some(&entry.identity);
+            }
+        }
+
+        let identity = matched.ok_or(AuthError::InvalidToken)?;
+        if !identity.role.allows(required) {
+            return Err(AuthError::Forbidden {
+                name: identity.name.clone(),
+                role: identity.role,
+                required,
+            });
+        }
+        Ok(Some(identity.clone()))
+    }
+}
+
+/// Build the TLS acceptor for the control listener.
+///
+/// Returns `Ok(None)` when auth is disabled or no server certificate is
+/// configured.
+///
+/// # Errors
+///
+/// Returns an error if a certificate, key or CA file cannot be loaded.
+pub fn tls_acceptor(config: &AuthConfig) -> Result<Option<TlsAcceptor>> {
+    if !config.enabled || !config.tls_enabled() {
+        return Ok(None);
+    }
+
+    let certs = load_certs(Path::new(&config.tls_cert_path))?;
+    let key = PrivateKeyDer::from_pem_file(&config.tls_key_path)
+        .map_err(|e| anyhow::anyhow!("failed to load TLS key {}: {}", config.tls_key_path, e))?;
+
+    let builder = ServerConfig::builder_with_provider(Arc::new(crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| anyhow::anyhow!("failed to configure TLS: {}", e))?;
+
+    let builder = if config.client_ca_path.is_empty() {
+        builder.with_no_client_auth()
+    } else {
+        let mut roots = RootCertStore::empty();
+        for cert in load_certs(Path::new(&config.client_ca_path))? {
+            roots
+                .add(cert)
+                .map_err(|e| anyhow::anyhow!("invalid client CA certificate: {}", e))?;
+        }
+        let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+            .build()
+            .map_err(|e| anyhow::anyhow!("failed to configure client verification: {}", e))?;
+        builder.with_client_cert_verifier(verifier)
+    };
+
+    let server_config = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| anyhow::anyhow!("invalid TLS certificate or key: {}", e))?;
+
+    tracing::info!(
+        mtls = !config.client_ca_path.is_empty(),
+        "control listener TLS enabled"
+    );
+    Ok(Some(TlsAcceptor::from(Arc::new(server_config))))
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .map_err(|e| anyhow::anyhow!("failed to read {}: {}", path.display(), e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("failed to parse {}: {}", path.display(), e))?;
+    if certs.is_empty() {
+        return Err(anyhow::anyhow!(
+            "no certificates found in {}",
+            path.display()
+        ));
+    }
+    Ok(certs)
+}
+
+/// Extract the bearer token from a raw HTTP request's headers.
+fn bearer_token(request: &str) -> Option<&str> {
+    request
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            if !name.trim().eq_ignore_ascii_case("authorization") {
+                return None;
+            }
+            let (scheme, token) = value.trim().split_once(' ')?;
+            scheme
+                .eq_ignore_ascii_case("bearer")
+                .then(|| token.trim())
+                .filter(|token| !token.is_empty())
+        })
+}
+
+fn decode_digest(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut digest = [0u8; 32];
+    for (byte, pair) in digest.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let pair = std::str::from_utf8(pair).ok()?;
+        *byte = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(digest)
+}
+
+fn constant_time_eq(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_core::config::ApiTokenConfig;
+
+    fn sha256_hex(token: &str) -> String {
+        Sha256::digest(token.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    fn authorizer() -> Authorizer {
+        let config = AuthConfig {
+            enabled: true,
+            tokens: vec![
+                ApiTokenConfig {
+                    name: "grafana".to_owned(),
+                    token_sha256: sha256_hex("viewer-secret"),
+                    role: "read-only".to_owned(),
+                },
+                ApiTokenConfig {
+                    name: "oncall".to_owned(),
+                    token_sha256: sha256_hex("operator-secret"),
+                    role: "operator".to_owned(),
+                },
+            ],
+            ..AuthConfig::default()
+        };
+        Authorizer::from_config(&config).unwrap()
+    }
+
+    fn request(token: &str) -> String {
+        format!(
+            "GET /status HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {}\r\n\r\n",
+            token
+        )
+    }
+
+    #[test]
+    fn test_role_ordering() {
+        assert!(Role::Admin.allows(Role::Operator));
+        assert!(Role::Operator.allows(Role::ReadOnly));
+        assert!(!Role::ReadOnly.allows(Role::Operator));
+        assert_eq!(Role::parse("operator"), Some(Role::Operator));
+        assert_eq!(Role::parse("root"), None);
+    }
+
+    #[test]
+    fn test_disabled_allows_everything() {
+        let auth = Authorizer::disabled();
+        assert!(!auth.is_enabled());
+        assert_eq!(
+            auth.authorize("GET /status HTTP/1.1", Role::Admin),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_valid_token_is_authorized_for_its_role() {
+        // Given: An operator token
+        let auth = authorizer();
+
+        // When: Authorizing read-only and operator requests
+        let read = auth.authorize(&request("operator-secret"), Role::ReadOnly);
+        let write = auth.authorize(&request("operator-secret"), Role::Operator);
+
+        // Then: Both are allowed for the named identity
+        let identity = read.unwrap().unwrap();
+        assert_eq!(identity.name, "oncall");
+        assert_eq!(identity.role, Role::Operator);
+        assert!(write.is_ok());
+    }
+
+    #[test]
+    fn test_insufficient_role_is_forbidden() {
+        let auth = authorizer();
+        let err = auth
+            .authorize(&request("viewer-secret"), Role::Operator)
+            .unwrap_err();
+        assert_eq!(err.status_code(), 403);
+        assert!(matches!(err, AuthError::Forbidden { ref name, .. } if name == "grafana"));
+    }
+
+    #[test]
+    fn test_missing_or_unknown_token_is_unauthorized() {
+        let auth = authorizer();
+
+        let missing = auth
+            .authorize(
+                "GET /status HTTP/1.1\r\nHost: localhost\r\n\r\n",
+                Role::ReadOnly,
+            )
+            .unwrap_err();
+        assert_eq!(missing, AuthError::MissingToken);
+        assert_eq!(missing.status_code(), 401);
+
+        let unknown = auth
+            .authorize(&request("guess"), Role::ReadOnly)
+            .unwrap_err();
+        assert_eq!(unknown, AuthError::InvalidToken);
+    }
+
+    #[test]
+    fn test_bearer_token_parsing() {
+        let req = "GET /status HTTP/1.1\r\nauthorization:  bearer  abc \r\n\r\n";
+        assert_eq!(bearer_token(req), Some("abc"));
+        assert_eq!(
+            bearer_token("GET / HTTP/1.1\r\nAuthorization: Basic abc\r\n\r\n"),
+            None
+        );
+        // Headers end at the first blank line
+        assert_eq!(
+            bearer_token("GET / HTTP/1.1\r\n\r\nAuthorization: Bearer abc"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tls_acceptor_is_none_without_certificate() {
+        let config = AuthConfig {
+            enabled: true,
+            ..AuthConfig::default()
+        };
+        assert!(tls_acceptor(&config).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_tls_acceptor_rejects_missing_certificate_file() {
+        let config = AuthConfig {
+            enabled: true,
+            tls_cert_path: "/nonexistent/server.pem".to_owned(),
+            tls_key_path: "/nonexistent/server-key.pem".to_owned(),
+            ..AuthConfig::default()
+        };
+        assert!(tls_acceptor(&config).is_err());
+    }
+}
@@ -4,7 +4,7 @@
 
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
 /// Ironpost security monitoring daemon.
 ///
@@ -18,6 +18,13 @@ pub struct DaemonCli {
     #[arg(short, long, default_value = "/etc/ironpost/ironpost.toml")]
     pub config: PathBuf,
 
+    /// Fetch the configuration from a remote URL (https://, http://, s3://).
+    ///
+    /// Enables `[remote_config]` with this URL; the local config file then
+    /// only provides the remaining `[remote_config]` settings.
+    #[arg(long)]
+    pub config_url: Option<String>,
+
     /// Override log level (trace, debug, info, warn, error).
     ///
     /// Takes precedence over the config file and environment variables.
@@ -34,7 +41,30 @@ pub struct DaemonCli {
     #[arg(long)]
     pub validate: bool,
 
+    /// Run startup self-tests, print a pass/fail report and exit.
+    ///
+    /// Checks the kernel, BPF capabilities, eBPF binary, Docker socket,
+    /// rule/policy directories, listener ports and vulnerability database.
+    /// Exits non-zero if any check fails.
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "text")]
+    pub doctor: Option<DoctorFormat>,
+
     /// Override PID file path (takes precedence over config file).
     #[arg(long)]
     pub pid_file: Option<String>,
+
+    /// Run in dry-run mode: detect and report, but never block or isolate.
+    ///
+    /// Takes precedence over the config file and environment variables.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Output format of the `--doctor` report.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DoctorFormat {
+    /// Aligned text table.
+    Text,
+    /// JSON document.
+    Json,
 }
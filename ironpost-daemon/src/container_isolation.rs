@@ -0,0 +1,278 @@
+//! Manual container isolation for the control API.
+//!
+//! Served by the health listener next to `/container/events` (`operator`
+//! role when `[auth]` is enabled):
+//!
+//! - `POST /container/isolate` -- apply an isolation action to a container,
+//!   optionally released automatically after `duration_secs`
+//! - `POST /container/release` -- undo the isolation recorded for a container
+//!
+//! Requests go through the guard's [`IsolationControl`], which shares the
+//! audit trail with policy-driven isolation. A release therefore undoes
+//! whichever action was applied -- unpause, restart or network reconnect --
+//! no matter whether a policy or an operator isolated the container.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use ironpost_container_guard::{
+    ContainerGuardError, DockerClient, IsolationAction, IsolationControl, IsolationRecord,
+};
+
+use crate::auth::Identity;
+
+/// Path of the isolate endpoint.
+pub const CONTAINER_ISOLATE_PATH: &str = "/container/isolate";
+
+/// Path of the release endpoint.
+pub const CONTAINER_RELEASE_PATH: &str = "/container/release";
+
+/// Isolation action named in a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionKind {
+    /// Pause the container.
+    Pause,
+    /// Stop the container.
+    Stop,
+    /// Disconnect the container from `networks`.
+    NetworkDisconnect,
+    /// Disconnect from `networks` (all attached ones when empty), then pause.
+    Quarantine,
+}
+
+/// Body of `POST /container/isolate`.
+#[derive(Debug, Deserialize)]
+pub struct IsolateRequest {
+    /// Container name, ID or unique ID prefix.
+    pub target: String,
+    /// Action to apply.
+    pub action: ActionKind,
+    /// Networks for `network_disconnect` and `quarantine`.
+    #[serde(default)]
+    pub networks: Vec<String>,
+    /// Seconds until the isolation is released automatically (`None` = until
+    /// released by hand).
+    #[serde(default)]
+    pub duration_secs: Option<u64>,
+}
+
+impl IsolateRequest {
+    /// The guard action this request asks for.
+    pub fn isolation_action(&self) -> IsolationAction {
+        match self.action {
+            ActionKind::Pause => IsolationAction::Pause,
+            ActionKind::Stop => IsolationAction::Stop,
+            ActionKind::NetworkDisconnect => IsolationAction::NetworkDisconnect {
+                networks: self.networks.clone(),
+            },
+            ActionKind::Quarantine => IsolationAction::Quarantine {
+                networks: self.networks.clone(),
+            },
+        }
+    }
+}
+
+/// Body of `POST /container/release`.
+#[derive(Debug, Deserialize)]
+pub struct ReleaseRequest {
+    /// Container name, ID or unique ID prefix.
+    pub target: String,
+}
+
+/// Response of both endpoints: the isolation that was applied or released.
+#[derive(Debug, Serialize)]
+pub struct IsolationResponse {
+    /// Full container ID.
+    pub container_id: String,
+    /// Container name.
+    pub container_name: String,
+    /// Applied action (e.g. `pause`, `quarantine(bridge)`).
+    pub action: String,
+    /// Networks disconnected by the action (reconnected on release).
+    pub networks: Vec<String>,
+    /// Who applied the isolation (e.g. `manual:ops`, `policy:web-isolate`).
+    pub initiator: String,
+    /// When the isolation was applied (Unix milliseconds).
+    pub applied_at_ms: u64,
+    /// When the isolation is released automatically (Unix milliseconds).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at_ms: Option<u64>,
+    /// Whether the isolation was only simulated (dry-run).
+    pub simulated: bool,
+}
+
+impl From<IsolationRecord> for IsolationResponse {
+    fn from(record: IsolationRecord) -> Self {
+        let networks = match &record.action {
+            IsolationAction::NetworkDisconnect { networks }
+            | IsolationAction::Quarantine { networks } => networks.clone(),
+            IsolationAction::Pause | IsolationAction::Stop => Vec::new(),
+        };
+        Self {
+            container_id: record.container_id,
+            container_name: record.container_name,
+            action: record.action.to_string(),
+            networks,
+            initiator: record.initiator,
+            applied_at_ms: record.applied_at_ms,
+            expires_at_ms: record.expires_at_ms,
+            simulated: record.simulated,
+        }
+    }
+}
+
+/// Handle an authorized isolate or release request.
+///
+/// Only `POST` is accepted. Returns the HTTP status code and JSON body.
+pub async fn handle_isolation_request<D: DockerClient>(
+    control: &IsolationControl<D>,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    identity: Option<&Identity>,
+) -> (u16, String) {
+    if method != "POST" {
+        return (405, r#"{"error":"method not allowed"}"#.to_owned());
+    }
+    let initiator = match identity {
+        Some(identity) => format!("manual:{}", identity.name),
+        None => "manual".to_owned(),
+    };
+
+    let result = if path == CONTAINER_RELEASE_PATH {
+        match serde_json::from_slice::<ReleaseRequest>(body) {
+            Ok(request) => control.release(&request.target, &initiator).await,
+            Err(e) => return bad_request(e),
+        }
+    } else {
+        match serde_json::from_slice::<IsolateRequest>(body) {
+            Ok(request) => {
+                let duration = request.duration_secs.map(Duration::from_secs);
+                control
+                    .isolate(
+                        &request.target,
+                        &request.isolation_action(),
+                        duration,
+                        &initiator,
+                    )
+                    .await
+            }
+            Err(e) => return bad_request(e),
+        }
+    };
+
+    match result {
+        Ok(record) => {
+            tracing::info!(
+                token = %initiator,
+                path = path,
+                container_id = %record.container_id,
+                action = %record.action,
+                "container isolation request completed"
+            );
+            let body = serde_json::to_string(&IsolationResponse::from(record))
+                .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+            (200, body)
+        }
+        Err(e) => (
+            error_status(&e),
+            serde_json::json!({ "error": e.to_string() }).to_string(),
+        ),
+    }
+}
+
+/// HTTP status for a failed isolate or release.
+pub fn error_status(err: &ContainerGuardError) -> u16 {
+    match err {
+        ContainerGuardError::ContainerNotFound(_) | ContainerGuardError::NotIsolated(_) => 404,
+        ContainerGuardError::AlreadyIsolated(_) => 409,
+        ContainerGuardError::InvalidContainerId(_) | ContainerGuardError::Config { .. } => 400,
+        _ => 500,
+    }
+}
+
+fn bad_request(e: serde_json::Error) -> (u16, String) {
+    let message = format!("invalid request body: {}", e);
+    (400, serde_json::json!({ "error": message }).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_isolate_request_parses_actions() {
+        let request: IsolateRequest = serde_json::from_str(
+            r#"{"target":"web-1","action":"network_disconnect","networks":["bridge"],"duration_secs":600}"#,
+        )
+        .expect("valid request");
+        assert_eq!(request.duration_secs, Some(600));
+        assert_eq!(
+            request.isolation_action(),
+            IsolationAction::NetworkDisconnect {
+                networks: vec!["bridge".to_owned()]
+            }
+        );
+
+        let request: IsolateRequest =
+            serde_json::from_str(r#"{"target":"web-1","action":"quarantine"}"#)
+                .expect("valid request");
+        assert_eq!(
+            request.isolation_action(),
+            IsolationAction::Quarantine {
+                networks: Vec::new()
+            }
+        );
+        assert!(request.duration_secs.is_none());
+
+        let invalid = serde_json::from_str::<IsolateRequest>(r#"{"target":"x","action":"kill"}"#);
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_response_lists_disconnected_networks() {
+        let container = ironpost_core::types::ContainerInfo {
+            id: "abc123def456".to_owned(),
+            name: "web-1".to_owned(),
+            image: "nginx:latest".to_owned(),
+            status: "running".to_owned(),
+            created_at: SystemTime::now(),
+        };
+        let record = IsolationRecord::new(
+            &container,
+            IsolationAction::Quarantine {
+                networks: vec!["bridge".to_owned()],
+            },
+            "trace-1",
+            "manual:ops",
+            Some(Duration::from_secs(60)),
+        );
+
+        let response = IsolationResponse::from(record);
+        assert_eq!(response.action, "quarantine(bridge)");
+        assert_eq!(response.networks, vec!["bridge"]);
+        assert!(response.expires_at_ms.is_some());
+    }
+
+    #[test]
+    fn test_error_status() {
+        assert_eq!(
+            error_status(&ContainerGuardError::NotIsolated("web-1".to_owned())),
+            404
+        );
+        assert_eq!(
+            error_status(&ContainerGuardError::AlreadyIsolated("web-1".to_owned())),
+            409
+        );
+        assert_eq!(
+            error_status(&ContainerGuardError::IsolationFailed {
+                container_id: "abc".to_owned(),
+                reason: "pause failed".to_owned(),
+            }),
+            500
+        );
+    }
+}
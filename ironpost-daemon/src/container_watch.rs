@@ -0,0 +1,236 @@
+//! Container guard event feed for the control API.
+//!
+//! Served on `GET /container/events` by the health listener (`read-only`
+//! role when `[auth]` is enabled). Keeps the most recent container guard
+//! activity -- lifecycle changes, policy matches and executed isolation
+//! actions -- in a ring buffer, each with an increasing sequence number.
+//! Clients poll with `?since=<seq>` to receive only the events after the last
+//! one they saw; `ironpost container watch` does this once a second.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tokio::sync::{Mutex, broadcast};
+
+use ironpost_container_guard::GuardActivity;
+
+/// Path of the container event feed.
+pub const CONTAINER_EVENTS_PATH: &str = "/container/events";
+
+/// Number of events kept for polling clients.
+pub const CONTAINER_EVENTS_CAPACITY: usize = 1000;
+
+/// A container guard event with its position in the feed.
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchEvent {
+    /// Sequence number (starts at 1, increases by one per event).
+    pub seq: u64,
+    /// Time the daemon recorded the event (Unix milliseconds).
+    pub timestamp_ms: u64,
+    /// The event itself, tagged by `kind`.
+    #[serde(flatten)]
+    pub activity: GuardActivity,
+}
+
+/// JSON body returned by `/container/events`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerEvents {
+    /// Sequence number of the newest event (0 before the first one).
+    /// Pass it as `since` on the next poll.
+    pub last_seq: u64,
+    /// Whether events after `since` were evicted before this poll.
+    pub truncated: bool,
+    /// Events after `since`, oldest first.
+    pub events: Vec<WatchEvent>,
+}
+
+/// Bounded, sequenced buffer of recent container guard events.
+#[derive(Clone)]
+pub struct ContainerWatch {
+    capacity: usize,
+    inner: Arc<Mutex<WatchBuffer>>,
+}
+
+struct WatchBuffer {
+    last_seq: u64,
+    events: VecDeque<WatchEvent>,
+}
+
+impl ContainerWatch {
+    /// Create a feed holding at most `capacity` events.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Arc::new(Mutex::new(WatchBuffer {
+                last_seq: 0,
+                events: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Append an event, evicting the oldest one when full.
+    pub async fn record(&self, activity: GuardActivity) {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+            .unwrap_or_default();
+        let mut buffer = self.inner.lock().await;
+        buffer.last_seq += 1;
+        let seq = buffer.last_seq;
+        if buffer.events.len() >= self.capacity {
+            buffer.events.pop_front();
+        }
+        buffer.events.push_back(WatchEvent {
+            seq,
+            timestamp_ms,
+            activity,
+        });
+    }
+
+    /// Events with a sequence number greater than `since`.
+    pub async fn since(&self, since: u64) -> ContainerEvents {
+        let buffer = self.inner.lock().await;
+        let events: Vec<WatchEvent> = buffer
+            .events
+            .iter()
+            .filter(|e| e.seq > since)
+            .cloned()
+            .collect();
+        let truncated = buffer.last_seq > since
+            && events
+                .first()
+                .is_none_or(|first| first.seq > since.saturating_add(1));
+        ContainerEvents {
+            last_seq: buffer.last_seq,
+            truncated,
+            events,
+        }
+    }
+}
+
+/// `since` parameter of a request line (`0` when absent or invalid).
+pub fn parse_since(request_line: &str) -> u64 {
+    request_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|target| target.split_once('?'))
+        .and_then(|(_, query)| {
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("since="))
+        })
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Spawn a task that copies guard activity into `watch` until shutdown.
+pub fn spawn_activity_recorder(
+    mut activity_rx: broadcast::Receiver<GuardActivity>,
+    watch: ContainerWatch,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                received = activity_rx.recv() => match received {
+                    Ok(activity) => watch.record(activity).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped = skipped, "container activity recorder lagged, events dropped");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        tracing::debug!("container activity channel closed, exiting recorder");
+                        break;
+                    }
+                },
+                _ = shutdown_rx.recv() => break,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lifecycle(name: &str) -> GuardActivity {
+        GuardActivity::Lifecycle {
+            container_id: format!("{}-id", name),
+            container_name: name.to_owned(),
+            event: "started".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_since_returns_events_after_cursor() {
+        // Given: A feed with three events
+        let watch = ContainerWatch::new(10);
+        for name in ["a", "b", "c"] {
+            watch.record(lifecycle(name)).await;
+        }
+
+        // When: Polling after the first event
+        let events = watch.since(1).await;
+
+        // Then: Only the later events are returned
+        assert_eq!(events.last_seq, 3);
+        assert!(!events.truncated);
+        let seqs: Vec<u64> = events.events.iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![2, 3]);
+        assert!(watch.since(3).await.events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_since_reports_evicted_events() {
+        // Given: A feed of capacity 2 that has seen four events
+        let watch = ContainerWatch::new(2);
+        for name in ["a", "b", "c", "d"] {
+            watch.record(lifecycle(name)).await;
+        }
+
+        // When: Polling from a cursor older than the buffer
+        let events = watch.since(1).await;
+
+        // Then: The remaining events are returned and the gap is flagged
+        assert!(events.truncated);
+        assert_eq!(events.events.len(), 2);
+        assert_eq!(events.events[0].seq, 3);
+        assert!(!watch.since(2).await.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_events_serialize_flat_with_kind() {
+        let watch = ContainerWatch::new(10);
+        watch.record(lifecycle("web")).await;
+
+        let json = serde_json::to_value(watch.since(0).await).expect("should serialize");
+        assert_eq!(json["events"][0]["seq"], 1);
+        assert_eq!(json["events"][0]["kind"], "lifecycle");
+        assert_eq!(json["events"][0]["container_name"], "web");
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(parse_since("GET /container/events?since=42 HTTP/1.1"), 42);
+        assert_eq!(parse_since("GET /container/events?x=1&since=7 HTTP/1.1"), 7);
+        assert_eq!(parse_since("GET /container/events HTTP/1.1"), 0);
+        assert_eq!(parse_since("GET /container/events?since=abc HTTP/1.1"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_activity_recorder_copies_broadcast() {
+        let (activity_tx, activity_rx) = broadcast::channel(16);
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let watch = ContainerWatch::new(10);
+        let task = spawn_activity_recorder(activity_rx, watch.clone(), shutdown_rx);
+
+        activity_tx.send(lifecycle("web")).expect("should send");
+        drop(activity_tx);
+        task.await
+            .expect("recorder should exit when the channel closes");
+        drop(shutdown_tx);
+
+        assert_eq!(watch.since(0).await.events.len(), 1);
+    }
+}
@@ -0,0 +1,730 @@
+//! Centralized rule/policy distribution.
+//!
+//! When `[content]` is enabled, the [`ContentManager`] syncs a bundle of
+//! detection rules and container policies from a local directory or a
+//! remote URL and pushes it into the running modules through their
+//! hot-reload APIs (`RuleEngine::replace_rules`,
+//! `PolicyEngine::replace_policies`).
+//!
+//! # Bundle Formats
+//!
+//! - **Local directory**: `rules/*.yml|*.yaml` and `policies/*.toml`
+//! - **Remote URL**: a JSON document mapping file names to file contents,
+//!   `{"rules": {"ssh.yml": "..."}, "policies": {"web.toml": "..."}}`,
+//!   verified against a SHA-256 checksum file like remote configuration
+//!
+//! # Versioning and Rollback
+//!
+//! A bundle's version is derived from its content, so unchanged bundles are
+//! never re-applied. Each applied version is stored under `store_dir` and
+//! the newest `keep_versions` are kept. A bundle is validated as a whole
+//! before anything is swapped: if any rule or policy is invalid, the running
+//! version stays in place.
+//!
+//! [`ContentManager::rollback`] re-applies a stored version; operators reach
+//! it through `POST /content/rollback` (`ironpost content rollback`). The
+//! rolled-back source version is not re-applied by later syncs; only a new upstream
+//! version replaces the rollback.
+//!
+//! Bundle rules replace the rule set loaded from `log_pipeline.rule_dir`,
+//! and bundle policies replace container guard's policies.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, broadcast, watch};
+
+use ironpost_container_guard::{PolicyEngine, SecurityPolicy};
+use ironpost_core::config::ContentConfig;
+use ironpost_log_pipeline::rule::{DetectionRule, RuleEngine, RuleLoader};
+
+use crate::remote_config;
+
+/// Maximum size of a single rule or policy file in a local bundle.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Number of hex characters of the content digest used as version ID.
+const VERSION_LEN: usize = 16;
+
+/// File under `store_dir` holding the manager state.
+const STATE_FILE: &str = "state.json";
+
+/// Directory under `store_dir` holding applied bundles.
+const VERSIONS_DIR: &str = "versions";
+
+/// A rules + policies bundle (file name -> file content).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentBundle {
+    /// Detection rules (YAML).
+    pub rules: BTreeMap<String, String>,
+    /// Container policies (TOML).
+    pub policies: BTreeMap<String, String>,
+}
+
+impl ContentBundle {
+    /// Read a bundle from a local directory (`rules/`, `policies/`).
+    ///
+    /// A missing subdirectory yields an empty section.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory or file cannot be read or a file
+    /// exceeds the size limit.
+    pub async fn from_dir(dir: &Path) -> Result<Self> {
+        Ok(Self {
+            rules: read_section(&dir.join("rules"), &["yml", "yaml"]).await?,
+            policies: read_section(&dir.join("policies"), &["toml"]).await?,
+        })
+    }
+
+    /// Parse a bundle from its JSON representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the body is not a valid JSON bundle.
+    pub fn from_json(body: &[u8]) -> Result<Self> {
+        serde_json::from_slice(body).map_err(|e| anyhow::anyhow!("invalid content bundle: {}", e))
+    }
+
+    /// Content-derived version ID.
+    pub fn version(&self) -> String {
+        // BTreeMap serialization is ordered, so equal content gives equal JSON
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        let mut digest = remote_config::sha256_hex(&json);
+        digest.truncate(VERSION_LEN);
+        digest
+    }
+
+    /// Parse and validate every rule and policy.
+    ///
+    /// Validation runs against scratch engines, so a bundle that prepares
+    /// successfully is accepted by the live engines as well.
+    fn prepare(&self) -> Result<PreparedContent> {
+        let rules = self
+            .rules
+            .iter()
+            .map(|(name, yaml)| RuleLoader::parse_yaml(yaml, name))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("invalid rule: {}", e))?;
+        let policies = self
+            .policies
+            .iter()
+            .map(|(name, toml)| ironpost_container_guard::parse_policy(toml, name))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("invalid policy: {}", e))?;
+
+        RuleEngine::new()
+            .replace_rules(rules.clone())
+            .map_err(|e| anyhow::anyhow!("invalid rule set: {}", e))?;
+        PolicyEngine::new()
+            .replace_policies(policies.clone())
+            .map_err(|e| anyhow::anyhow!("invalid policy set: {}", e))?;
+
+        Ok(PreparedContent { rules, policies })
+    }
+}
+
+/// A validated bundle ready to be swapped into the engines.
+struct PreparedContent {
+    rules: Vec<DetectionRule>,
+    policies: Vec<SecurityPolicy>,
+}
+
+/// Hot-reload handles of the running modules.
+///
+/// A handle is `None` when the corresponding module is disabled.
+#[derive(Clone, Default)]
+pub struct ContentTargets {
+    /// Log pipeline rule engine.
+    pub rules: Option<Arc<Mutex<RuleEngine>>>,
+    /// Container guard policy engine.
+    pub policies: Option<Arc<Mutex<PolicyEngine>>>,
+}
+
+/// Content distribution status reported on `/status`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ContentStatus {
+    /// Version currently applied to the modules.
+    pub version: Option<String>,
+    /// When the current version was applied (Unix seconds).
+    pub applied_at: Option<u64>,
+    /// Number of rules in the current version.
+    pub rules: usize,
+    /// Number of policies in the current version.
+    pub policies: usize,
+    /// Stored versions available for rollback (oldest first).
+    pub versions: Vec<String>,
+    /// When the source was last synced successfully (Unix seconds).
+    pub last_sync: Option<u64>,
+    /// Error from the last sync or apply attempt.
+    pub last_error: Option<String>,
+}
+
+/// Persisted manager state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct StoreState {
+    /// Version currently applied.
+    current: Option<String>,
+    /// Latest version seen at the source.
+    last_synced: Option<String>,
+    /// Stored versions (oldest first).
+    history: Vec<String>,
+}
+
+/// Syncs rule/policy bundles and applies them to the modules.
+pub struct ContentManager {
+    config: ContentConfig,
+    targets: ContentTargets,
+    client: Option<reqwest::Client>,
+    state: StoreState,
+    status_tx: watch::Sender<ContentStatus>,
+}
+
+impl ContentManager {
+    /// Create a manager, restoring state from `store_dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built.
+    pub async fn new(config: ContentConfig, targets: ContentTargets) -> Result<Self> {
+        let client = if config.is_remote() {
+            Some(remote_config::http_client(config.timeout_secs)?)
+        } else {
+            None
+        };
+
+        let state_path = Path::new(&config.store_dir).join(STATE_FILE);
+        let state = match tokio::fs::read(&state_path).await {
+            Ok(body) => serde_json::from_slice(&body).unwrap_or_else(|e| {
+                tracing::warn!(
+                    path = %state_path.display(),
+                    error = %e,
+                    "corrupt content state, starting fresh"
+                );
+                StoreState::default()
+            }),
+            Err(_) => StoreState::default(),
+        };
+
+        let (status_tx, _) = watch::channel(ContentStatus {
+            versions: state.history.clone(),
+            ..ContentStatus::default()
+        });
+
+        Ok(Self {
+            config,
+            targets,
+            client,
+            state,
+            status_tx,
+        })
+    }
+
+    /// Subscribe to status updates.
+    pub fn subscribe(&self) -> watch::Receiver<ContentStatus> {
+        self.status_tx.subscribe()
+    }
+
+    /// Current status.
+    #[allow(dead_code)] // Public API for control/status endpoints
+    pub fn status(&self) -> ContentStatus {
+        self.status_tx.borrow().clone()
+    }
+
+    /// Initial sync at startup.
+    ///
+    /// If the source is unavailable, the previously applied version is
+    /// restored from the store so modules start with known-good content.
+    pub async fn start(&mut self) {
+        if let Err(e) = self.sync().await {
+            tracing::warn!(error = %e, "content sync failed");
+            if let Some(version) = self.state.current.clone() {
+                match self.apply_stored(&version).await {
+                    Ok(()) => tracing::info!(version = %version, "restored stored content"),
+                    Err(e) => {
+                        tracing::error!(version = %version, error = %e, "failed to restore content");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fetch the bundle and apply it if the source changed.
+    ///
+    /// Returns `Ok(true)` if a new version was applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle cannot be fetched, fails validation,
+    /// or cannot be stored. The running version is kept in all cases.
+    pub async fn sync(&mut self) -> Result<bool> {
+        let result = self.try_sync().await;
+        self.status_tx.send_modify(|status| match &result {
+            Ok(_) => {
+                status.last_sync = unix_now();
+                status.last_error = None;
+            }
+            Err(e) => status.last_error = Some(e.to_string()),
+        });
+        result
+    }
+
+    async fn try_sync(&mut self) -> Result<bool> {
+        let bundle = self.fetch().await?;
+        let version = bundle.version();
+        if self.state.last_synced.as_deref() == Some(version.as_str()) {
+            // Unchanged upstream (or deliberately rolled back from)
+            if self.state.current.is_some() && self.status_tx.borrow().version.is_none() {
+                // Restarted daemon: re-apply the running version
+                if let Some(current) = self.state.current.clone() {
+                    self.apply_stored(&current).await?;
+                }
+            }
+            return Ok(false);
+        }
+
+        // Only bundles that applied cleanly are stored for rollback
+        self.apply(&version, &bundle).await?;
+        self.store(&version, &bundle).await?;
+        self.state.last_synced = Some(version);
+        self.save_state().await?;
+        let versions = self.state.history.clone();
+        self.status_tx
+            .send_modify(|status| status.versions = versions);
+        Ok(true)
+    }
+
+    /// Re-apply a stored version.
+    ///
+    /// With `None`, rolls back to the version stored before the current one.
+    /// Returns the version that was applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no earlier version, the version is not
+    /// stored, or it fails to apply.
+    pub async fn rollback(&mut self, version: Option<&str>) -> Result<String> {
+        let target = match version {
+            Some(version) => version.to_owned(),
+            None => self.previous_version().ok_or_else(|| {
+                anyhow::anyhow!("no earlier content version available for rollback")
+            })?,
+        };
+        if !self.state.history.contains(&target) {
+            return Err(anyhow::anyhow!(
+                "content version '{}' is not stored",
+                target
+            ));
+        }
+
+        self.apply_stored(&target).await?;
+        self.save_state().await?;
+        tracing::warn!(version = %target, "content rolled back");
+        Ok(target)
+    }
+
+    /// Run periodic syncs until shutdown.
+    pub fn spawn(
+        manager: Arc<Mutex<Self>>,
+        interval: Duration,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            // The initial sync already happened at startup
+            interval.tick().await;
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(e) = manager.lock().await.sync().await {
+                            tracing::warn!(error = %e, "content sync failed, keeping current version");
+                        }
+                    }
+                    _ = shutdown_rx.recv() => {
+                        tracing::debug!("content sync task shutting down");
+                        break;
+                    }
+                }
+            }
+        })
+    }
+
+    fn previous_version(&self) -> Option<String> {
+        let current = self.state.current.as_ref()?;
+        let position = self.state.history.iter().position(|v| v == current)?;
+        position
+            .checked_sub(1)
+            .and_then(|i| self.state.history.get(i))
+            .cloned()
+    }
+
+    async fn fetch(&self) -> Result<ContentBundle> {
+        match &self.client {
+            Some(client) => {
+                let body = remote_config::fetch_verified(
+                    client,
+                    &self.config.source,
+                    &self.config.checksum_url,
+                )
+                .await?;
+                ContentBundle::from_json(&body)
+            }
+            None => ContentBundle::from_dir(Path::new(&self.config.source)).await,
+        }
+    }
+
+    async fn apply_stored(&mut self, version: &str) -> Result<()> {
+        let path = self.version_path(version);
+        let body = tokio::fs::read(&path).await.map_err(|e| {
+            anyhow::anyhow!("failed to read stored content {}: {}", path.display(), e)
+        })?;
+        let bundle = ContentBundle::from_json(&body)?;
+        self.apply(version, &bundle).await
+    }
+
+    /// Validate the whole bundle, then swap it into the engines.
+    async fn apply(&mut self, version: &str, bundle: &ContentBundle) -> Result<()> {
+        let prepared = match bundle.prepare() {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                tracing::error!(version = %version, error = %e, "content bundle rejected");
+                return Err(e);
+            }
+        };
+        let rule_count = prepared.rules.len();
+        let policy_count = prepared.policies.len();
+
+        if let Some(engine) = &self.targets.rules {
+            engine
+                .lock()
+                .await
+                .replace_rules(prepared.rules)
+                .map_err(|e| anyhow::anyhow!("failed to apply rules: {}", e))?;
+        }
+        if let Some(engine) = &self.targets.policies {
+            engine
+                .lock()
+                .await
+                .replace_policies(prepared.policies)
+                .map_err(|e| anyhow::anyhow!("failed to apply policies: {}", e))?;
+        }
+
+        self.state.current = Some(version.to_owned());
+        let versions = self.state.history.clone();
+        self.status_tx.send_modify(|status| {
+            status.version = Some(version.to_owned());
+            status.applied_at = unix_now();
+            status.rules = rule_count;
+            status.policies = policy_count;
+            status.versions = versions;
+        });
+        tracing::info!(
+            version = %version,
+            rules = rule_count,
+            policies = policy_count,
+            "content applied"
+        );
+        Ok(())
+    }
+
+    /// Store a bundle version and prune old versions.
+    async fn store(&mut self, version: &str, bundle: &ContentBundle) -> Result<()> {
+        let body = serde_json::to_vec_pretty(bundle)
+            .map_err(|e| anyhow::anyhow!("failed to serialize content bundle: {}", e))?;
+        remote_config::write_atomic(&self.version_path(version), &body).await?;
+
+        self.state.history.retain(|v| v != version);
+        self.state.history.push(version.to_owned());
+        while self.state.history.len() > self.config.keep_versions {
+            let oldest = self.state.history.remove(0);
+            if let Err(e) = tokio::fs::remove_file(self.version_path(&oldest)).await {
+                tracing::debug!(version = %oldest, error = %e, "failed to prune content version");
+            }
+        }
+        Ok(())
+    }
+
+    async fn save_state(&self) -> Result<()> {
+        let body = serde_json::to_vec_pretty(&self.state)
+            .map_err(|e| anyhow::anyhow!("failed to serialize content state: {}", e))?;
+        remote_config::write_atomic(&Path::new(&self.config.store_dir).join(STATE_FILE), &body)
+            .await
+    }
+
+    fn version_path(&self, version: &str) -> PathBuf {
+        Path::new(&self.config.store_dir)
+            .join(VERSIONS_DIR)
+            .join(format!("{}.json", version))
+    }
+}
+
+/// Read all files with one of `extensions` from `dir` (non-recursive).
+async fn read_section(dir: &Path, extensions: &[&str]) -> Result<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(files),
+        Err(e) => {
+            return Err(anyhow::anyhow!("failed to read {}: {}", dir.display(), e));
+        }
+    };
+
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to read {}: {}", dir.display(), e))?
+    {
+        let path = entry.path();
+        let matches_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.contains(&ext));
+        if !matches_extension {
+            continue;
+        }
+
+        let metadata = tokio::fs::metadata(&path)
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to stat {}: {}", path.display(), e))?;
+        if !metadata.is_file() {
+            continue;
+        }
+        if metadata.len() > MAX_FILE_BYTES {
+            return Err(anyhow::anyhow!(
+                "{} exceeds the {} byte limit",
+                path.display(),
+                MAX_FILE_BYTES
+            ));
+        }
+
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to read {}: {}", path.display(), e))?;
+        files.insert(entry.file_name().to_string_lossy().into_owned(), content);
+    }
+    Ok(files)
+}
+
+fn unix_now() -> Option<u64> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULE: &str = r#"
+id: ssh_brute_force
+title: SSH Brute Force
+severity: High
+detection:
+  conditions:
+    - field: message
+      modifier: contains
+      value: "Failed password"
+"#;
+
+    const POLICY: &str = r#"
+id = "pause-web"
+name = "Pause web containers"
+description = "Pause web containers on critical alerts"
+enabled = true
+severity_threshold = "Critical"
+priority = 10
+
+[target_filter]
+container_names = ["web-*"]
+image_patterns = []
+labels = []
+
+[action]
+Pause = []
+"#;
+
+    fn config(store_dir: &Path, source: &Path) -> ContentConfig {
+        ContentConfig {
+            enabled: true,
+            source: source.display().to_string(),
+            store_dir: store_dir.display().to_string(),
+            keep_versions: 2,
+            ..ContentConfig::default()
+        }
+    }
+
+    fn targets() -> ContentTargets {
+        ContentTargets {
+            rules: Some(Arc::new(Mutex::new(RuleEngine::new()))),
+            policies: Some(Arc::new(Mutex::new(PolicyEngine::new()))),
+        }
+    }
+
+    fn write_source(dir: &Path, rule_title: &str) {
+        std::fs::create_dir_all(dir.join("rules")).unwrap();
+        std::fs::create_dir_all(dir.join("policies")).unwrap();
+        std::fs::write(
+            dir.join("rules").join("ssh.yml"),
+            RULE.replace("SSH Brute Force", rule_title),
+        )
+        .unwrap();
+        std::fs::write(dir.join("policies").join("web.toml"), POLICY).unwrap();
+        std::fs::write(dir.join("rules").join("README.md"), "ignored").unwrap();
+    }
+
+    async fn rule_count(targets: &ContentTargets) -> usize {
+        targets.rules.as_ref().unwrap().lock().await.rule_count()
+    }
+
+    async fn policy_count(targets: &ContentTargets) -> usize {
+        targets
+            .policies
+            .as_ref()
+            .unwrap()
+            .lock()
+            .await
+            .policy_count()
+    }
+
+    #[tokio::test]
+    async fn test_bundle_from_dir_and_version_is_content_derived() {
+        // Given: A local bundle directory
+        let source = tempfile::tempdir().unwrap();
+        write_source(source.path(), "SSH Brute Force");
+
+        // When: Reading it twice
+        let a = ContentBundle::from_dir(source.path()).await.unwrap();
+        let b = ContentBundle::from_dir(source.path()).await.unwrap();
+
+        // Then: Only matching files are read and versions are stable
+        assert_eq!(a.rules.len(), 1);
+        assert_eq!(a.policies.len(), 1);
+        assert_eq!(a.version(), b.version());
+        assert_eq!(a.version().len(), VERSION_LEN);
+
+        // And: JSON round-trips to the same version
+        let json = serde_json::to_vec(&a).unwrap();
+        assert_eq!(
+            ContentBundle::from_json(&json).unwrap().version(),
+            a.version()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_applies_bundle_once() {
+        // Given: A manager over a local source
+        let source = tempfile::tempdir().unwrap();
+        let store = tempfile::tempdir().unwrap();
+        write_source(source.path(), "SSH Brute Force");
+        let targets = targets();
+        let mut manager = ContentManager::new(config(store.path(), source.path()), targets.clone())
+            .await
+            .unwrap();
+
+        // When: Syncing twice without changes
+        let first = manager.sync().await.unwrap();
+        let second = manager.sync().await.unwrap();
+
+        // Then: Content is applied once and reported
+        assert!(first);
+        assert!(!second);
+        assert_eq!(rule_count(&targets).await, 1);
+        assert_eq!(policy_count(&targets).await, 1);
+        let status = manager.status();
+        assert!(status.version.is_some());
+        assert_eq!(status.rules, 1);
+        assert_eq!(status.versions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_bundle_keeps_running_version() {
+        // Given: An applied valid bundle
+        let source = tempfile::tempdir().unwrap();
+        let store = tempfile::tempdir().unwrap();
+        write_source(source.path(), "SSH Brute Force");
+        let targets = targets();
+        let mut manager = ContentManager::new(config(store.path(), source.path()), targets.clone())
+            .await
+            .unwrap();
+        manager.sync().await.unwrap();
+        let applied = manager.status().version;
+
+        // When: The source gains an invalid policy
+        std::fs::write(
+            source.path().join("policies").join("broken.toml"),
+            "id = \"\"",
+        )
+        .unwrap();
+        let result = manager.sync().await;
+
+        // Then: The sync fails and the running content is untouched
+        assert!(result.is_err());
+        assert_eq!(manager.status().version, applied);
+        assert!(manager.status().last_error.is_some());
+        assert_eq!(policy_count(&targets).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_and_pruning() {
+        // Given: Three successive versions with keep_versions = 2
+        let source = tempfile::tempdir().unwrap();
+        let store = tempfile::tempdir().unwrap();
+        let mut manager = ContentManager::new(config(store.path(), source.path()), targets())
+            .await
+            .unwrap();
+        let mut versions = Vec::new();
+        for title in ["Rule v1", "Rule v2", "Rule v3"] {
+            write_source(source.path(), title);
+            manager.sync().await.unwrap();
+            versions.push(manager.status().version.unwrap());
+        }
+
+        // When: Rolling back to the previous version
+        let rolled_back = manager.rollback(None).await.unwrap();
+
+        // Then: v2 is applied, v1 was pruned, and a resync keeps the rollback
+        assert_eq!(rolled_back, versions[1]);
+        assert_eq!(manager.status().versions, versions[1..].to_vec());
+        assert!(manager.rollback(Some(&versions[0])).await.is_err());
+        assert!(!manager.sync().await.unwrap());
+        assert_eq!(
+            manager.status().version.as_deref(),
+            Some(versions[1].as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_restores_stored_version_when_source_unavailable() {
+        // Given: Content applied and stored by a previous run
+        let source = tempfile::tempdir().unwrap();
+        let store = tempfile::tempdir().unwrap();
+        write_source(source.path(), "SSH Brute Force");
+        let cfg = config(store.path(), source.path());
+        ContentManager::new(cfg.clone(), targets())
+            .await
+            .unwrap()
+            .sync()
+            .await
+            .unwrap();
+
+        // When: Restarting with an unreadable source
+        std::fs::remove_dir_all(source.path().join("rules")).unwrap();
+        std::fs::write(source.path().join("rules"), "not a directory").unwrap();
+        let targets = targets();
+        let mut manager = ContentManager::new(cfg, targets.clone()).await.unwrap();
+        manager.start().await;
+
+        // Then: The stored version is applied
+        assert_eq!(rule_count(&targets).await, 1);
+        assert_eq!(policy_count(&targets).await, 1);
+    }
+}
@@ -0,0 +1,318 @@
+//! Minimal cron expression parser for the task scheduler.
+//!
+//! Supports the classic 5-field format evaluated in UTC:
+//!
+//! ```text
+//! ┌───────── minute (0-59)
+//! │ ┌─────── hour (0-23)
+//! │ │ ┌───── day of month (1-31)
+//! │ │ │ ┌─── month (1-12)
+//! │ │ │ │ ┌─ day of week (0-7, 0 and 7 are Sunday)
+//! * * * * *
+//! ```
+//!
+//! Each field accepts `*`, single values, ranges (`1-5`), steps (`*/15`,
+//! `10-50/10`) and comma-separated lists. The macros `@hourly`, `@daily`,
+//! `@weekly`, `@monthly` and `@yearly` are also accepted.
+//!
+//! As in standard cron, when both day-of-month and day-of-week are
+//! restricted, a time matches if *either* of them matches.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Upper bound on search steps when looking for the next matching time.
+///
+/// Non-matching months and days are skipped whole, so this reaches far
+/// beyond the 4-year horizon any satisfiable expression (e.g. Feb 29) needs.
+const MAX_SEARCH_STEPS: u32 = 100_000;
+
+/// A parsed cron schedule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    /// Allowed minutes (bit 0-59).
+    minutes: u64,
+    /// Allowed hours (bit 0-23).
+    hours: u64,
+    /// Allowed days of month (bit 1-31).
+    days_of_month: u64,
+    /// Allowed months (bit 1-12).
+    months: u64,
+    /// Allowed days of week (bit 0-6, Sunday = 0).
+    days_of_week: u64,
+    /// Whether day-of-month was `*`.
+    any_day_of_month: bool,
+    /// Whether day-of-week was `*`.
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    /// Parse a cron expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if the expression is malformed
+    /// or a value is out of range.
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let expr = expr.trim();
+        let expanded = match expr {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other if other.starts_with('@') => {
+                return Err(format!("unknown macro '{}'", other));
+            }
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields.as_slice() else {
+            return Err(format!("expected 5 fields, got {}", fields.len()));
+        };
+
+        let mut days_of_week = parse_field(dow, 0, 7, "day of week")?;
+        // 7 is an alias for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days_of_month: parse_field(dom, 1, 31, "day of month")?,
+            months: parse_field(month, 1, 12, "month")?,
+            days_of_week,
+            any_day_of_month: *dom == "*",
+            any_day_of_week: *dow == "*",
+        })
+    }
+
+    /// The first matching time strictly after `after`, truncated to the minute.
+    ///
+    /// Returns `None` if no matching time exists within the search horizon
+    /// (e.g. `0 0 31 2 *`) or `after` predates the Unix epoch.
+    pub fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        let secs = after.duration_since(UNIX_EPOCH).ok()?.as_secs();
+        // Start at the next whole minute
+        let mut minute = secs / 60 + 1;
+
+        for _ in 0..MAX_SEARCH_STEPS {
+            let days = minute / 1440;
+            let (year, month, day) = civil_from_days(days);
+
+            if !bit_set(self.months, month) {
+                let (next_year, next_month) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+                minute = days_from_civil(next_year, next_month, 1) * 1440;
+                continue;
+            }
+
+            if !self.matches_day(day, (days + 4) % 7) {
+                minute = (days + 1) * 1440;
+                continue;
+            }
+
+            let hour = (minute / 60) % 24;
+            if !bit_set(self.hours, hour) {
+                minute = (minute / 60 + 1) * 60;
+                continue;
+            }
+
+            if !bit_set(self.minutes, minute % 60) {
+                minute += 1;
+                continue;
+            }
+
+            return UNIX_EPOCH.checked_add(Duration::from_secs(minute.checked_mul(60)?));
+        }
+
+        None
+    }
+
+    /// Day matching with standard cron OR semantics.
+    fn matches_day(&self, day_of_month: u64, day_of_week: u64) -> bool {
+        let dom = bit_set(self.days_of_month, day_of_month);
+        let dow = bit_set(self.days_of_week, day_of_week);
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => dow,
+            (false, true) => dom,
+            (false, false) => dom || dow,
+        }
+    }
+}
+
+fn bit_set(mask: u64, value: u64) -> bool {
+    value < 64 && mask & (1 << value) != 0
+}
+
+/// Parse one comma-separated field into a bitmask.
+fn parse_field(field: &str, min: u64, max: u64, name: &str) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u64 = step
+                    .parse()
+                    .map_err(|_| format!("invalid step '{}' in {} field", step, name))?;
+                if step == 0 {
+                    return Err(format!("step must be positive in {} field", name));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_value(start, min, max, name)?,
+                parse_value(end, min, max, name)?,
+            )
+        } else {
+            let value = parse_value(range, min, max, name)?;
+            // `5/10` means "from 5 to max, every 10"
+            if step > 1 {
+                (value, max)
+            } else {
+                (value, value)
+            }
+        };
+
+        if start > end {
+            return Err(format!("invalid range '{}' in {} field", range, name));
+        }
+
+        let mut value = start;
+        while value <= end {
+            mask |= 1 << value;
+            value += step;
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_value(value: &str, min: u64, max: u64, name: &str) -> Result<u64, String> {
+    let parsed: u64 = value
+        .parse()
+        .map_err(|_| format!("invalid value '{}' in {} field", value, name))?;
+    if parsed < min || parsed > max {
+        return Err(format!(
+            "{} value {} out of range {}-{}",
+            name, parsed, min, max
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Convert days since the Unix epoch to a (year, month, day) civil date.
+///
+/// Howard Hinnant's `civil_from_days`, restricted to dates on or after 1970.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Convert a civil date (year >= 1970) to days since the Unix epoch.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-01-15 10:30:00 UTC (a Monday).
+    const BASE: u64 = 1_705_314_600;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn next(expr: &str, after: u64) -> u64 {
+        CronSchedule::parse(expr)
+            .unwrap()
+            .next_after(at(after))
+            .unwrap()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn test_civil_date_round_trip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(BASE / 86_400), (2024, 1, 15));
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_every_minute_advances_to_next_minute() {
+        assert_eq!(next("* * * * *", BASE), BASE + 60);
+        assert_eq!(next("* * * * *", BASE + 59), BASE + 60);
+    }
+
+    #[test]
+    fn test_step_and_list_fields() {
+        // 10:30 -> next quarter hour is 10:45
+        assert_eq!(next("*/15 * * * *", BASE), BASE + 15 * 60);
+        // 10:30 -> 14:00 same day
+        assert_eq!(next("0 9,14 * * *", BASE), BASE + 3 * 3600 + 30 * 60);
+    }
+
+    #[test]
+    fn test_daily_macro_rolls_over_to_next_day() {
+        // 2024-01-16 00:00:00 UTC
+        assert_eq!(next("@daily", BASE), 1_705_363_200);
+    }
+
+    #[test]
+    fn test_day_of_week_matching() {
+        // Monday 10:30 -> Friday 2024-01-19 03:00 UTC
+        assert_eq!(next("0 3 * * 5", BASE), 1_705_633_200);
+        // 7 is an alias for Sunday -> 2024-01-21 00:00 UTC
+        assert_eq!(next("0 0 * * 7", BASE), 1_705_795_200);
+    }
+
+    #[test]
+    fn test_month_and_leap_day() {
+        // Next Feb 29 00:00 after 2024-01-15 is 2024-02-29
+        assert_eq!(next("0 0 29 2 *", BASE), 19_782 * 86_400);
+    }
+
+    #[test]
+    fn test_impossible_date_has_no_next_run() {
+        let schedule = CronSchedule::parse("0 0 31 2 *").unwrap();
+        assert!(schedule.next_after(at(BASE)).is_none());
+    }
+
+    #[test]
+    fn test_rejects_malformed_expressions() {
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("5-1 * * * *").is_err());
+        assert!(CronSchedule::parse("@often").is_err());
+        assert!(CronSchedule::parse("a * * * *").is_err());
+    }
+}
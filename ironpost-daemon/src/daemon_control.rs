@@ -0,0 +1,473 @@
+//! Orchestrator commands for the control API.
+//!
+//! Served by the health listener next to `/status`:
+//!
+//! - `GET /modules/quarantined` -- modules the crash-loop supervisor took out
+//!   of service (`read-only` role when `[auth]` is enabled)
+//! - `POST /modules/unquarantine` -- lift quarantine for a module and start
+//!   it again (`operator` role)
+//! - `POST /content/rollback` -- re-apply a stored rule/policy content
+//!   version, by default the one before the current version (`operator` role)
+//!
+//! Quarantine and content state belong to the orchestrator's main loop, so
+//! these handlers do not touch them directly: they send an
+//! [`OrchestratorCommand`] through an [`OrchestratorHandle`] and wait for the
+//! loop to reply.
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::auth::Identity;
+use crate::supervisor::QuarantineRecord;
+
+/// Path listing quarantined modules.
+pub const MODULES_QUARANTINED_PATH: &str = "/modules/quarantined";
+
+/// Path lifting quarantine for a module.
+pub const MODULES_UNQUARANTINE_PATH: &str = "/modules/unquarantine";
+
+/// Path rolling distributed content back.
+pub const CONTENT_ROLLBACK_PATH: &str = "/content/rollback";
+
+/// Commands queued for the main loop.
+const COMMAND_QUEUE_CAPACITY: usize = 16;
+
+/// How long a handler waits for the main loop to answer.
+///
+/// Covers a module restart, which runs inside the loop.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A request executed by the orchestrator's main loop.
+#[derive(Debug)]
+pub enum OrchestratorCommand {
+    /// List quarantined modules.
+    Quarantined {
+        /// Receives the quarantine records.
+        reply: oneshot::Sender<Vec<QuarantineRecord>>,
+    },
+    /// Lift quarantine for `module` and start it again.
+    Unquarantine {
+        /// Module name.
+        module: String,
+        /// Receives the outcome.
+        reply: oneshot::Sender<Result<(), CommandError>>,
+    },
+    /// Roll distributed content back to `version` (`None` = the previous one).
+    RollbackContent {
+        /// Stored content version.
+        version: Option<String>,
+        /// Receives the version now applied.
+        reply: oneshot::Sender<Result<String, CommandError>>,
+    },
+}
+
+/// Failure of a command, with the HTTP status to report it with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandError {
+    /// HTTP status code.
+    pub status: u16,
+    /// Error message.
+    pub message: String,
+}
+
+impl CommandError {
+    /// The command's target does not exist or is not in the expected state.
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            status: 404,
+            message: message.into(),
+        }
+    }
+
+    /// The command conflicts with the current state (e.g. no such version).
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self {
+            status: 409,
+            message: message.into(),
+        }
+    }
+
+    /// The command was accepted but failed.
+    pub fn failed(message: impl Into<String>) -> Self {
+        Self {
+            status: 500,
+            message: message.into(),
+        }
+    }
+
+    fn body(&self) -> String {
+        serde_json::json!({ "error": self.message }).to_string()
+    }
+}
+
+/// Sender side of the command channel.
+///
+/// Cheap to clone; all clones feed the same main loop.
+#[derive(Debug, Clone)]
+pub struct OrchestratorHandle {
+    tx: mpsc::Sender<OrchestratorCommand>,
+}
+
+impl OrchestratorHandle {
+    /// Create a handle and the receiver the main loop reads commands from.
+    pub fn channel() -> (Self, mpsc::Receiver<OrchestratorCommand>) {
+        let (tx, rx) = mpsc::channel(COMMAND_QUEUE_CAPACITY);
+        (Self { tx }, rx)
+    }
+
+    /// Send a command built around a reply channel and wait for the reply.
+    async fn request<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> OrchestratorCommand,
+    ) -> Result<T, CommandError> {
+        let unavailable = || CommandError {
+            status: 503,
+            message: "orchestrator is not accepting commands".to_owned(),
+        };
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(command(reply_tx))
+            .await
+            .map_err(|_| unavailable())?;
+        match tokio::time::timeout(COMMAND_TIMEOUT, reply_rx).await {
+            Ok(Ok(reply)) => Ok(reply),
+            Ok(Err(_)) => Err(unavailable()),
+            Err(_) => Err(CommandError::failed("orchestrator did not answer in time")),
+        }
+    }
+
+    /// Modules currently quarantined.
+    ///
+    /// # Errors
+    ///
+    /// Returns a 503 error if the main loop is not running.
+    pub async fn quarantined(&self) -> Result<Vec<QuarantineRecord>, CommandError> {
+        self.request(|reply| OrchestratorCommand::Quarantined { reply })
+            .await
+    }
+
+    /// Lift quarantine for `module` and start it again.
+    ///
+    /// # Errors
+    ///
+    /// Returns a 404 error if the module is not quarantined and a 500 error
+    /// if it fails to start.
+    pub async fn unquarantine(&self, module: &str) -> Result<(), CommandError> {
+        self.request(|reply| OrchestratorCommand::Unquarantine {
+            module: module.to_owned(),
+            reply,
+        })
+        .await?
+    }
+
+    /// Roll distributed content back to `version` (`None` = the previous one).
+    ///
+    /// # Errors
+    ///
+    /// Returns a 404 error if content distribution is disabled and a 409
+    /// error if the version is not stored or cannot be applied.
+    pub async fn rollback_content(&self, version: Option<&str>) -> Result<String, CommandError> {
+        self.request(|reply| OrchestratorCommand::RollbackContent {
+            version: version.map(str::to_owned),
+            reply,
+        })
+        .await?
+    }
+}
+
+/// A quarantined module as reported by the control API.
+#[derive(Debug, Serialize)]
+pub struct QuarantinedModule {
+    /// Module name.
+    pub name: String,
+    /// Last failure reason before quarantine.
+    pub reason: String,
+    /// Failures within the window that triggered quarantine.
+    pub failures: u32,
+    /// When the module was quarantined (Unix milliseconds).
+    pub since_ms: u64,
+}
+
+impl From<QuarantineRecord> for QuarantinedModule {
+    fn from(record: QuarantineRecord) -> Self {
+        let since_ms = record
+            .since
+            .duration_since(UNIX_EPOCH)
+            .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+            .unwrap_or_default();
+        Self {
+            name: record.name,
+            reason: record.reason,
+            failures: record.failures,
+            since_ms,
+        }
+    }
+}
+
+/// Body of `GET /modules/quarantined`.
+#[derive(Debug, Serialize)]
+pub struct QuarantineList {
+    /// Quarantined modules, by name.
+    pub modules: Vec<QuarantinedModule>,
+}
+
+/// Body of `POST /modules/unquarantine`.
+#[derive(Debug, Deserialize)]
+pub struct UnquarantineRequest {
+    /// Module name (e.g. `log-pipeline`).
+    pub module: String,
+}
+
+/// Response of `POST /modules/unquarantine`.
+#[derive(Debug, Serialize)]
+pub struct UnquarantineResponse {
+    /// Module that was restarted.
+    pub module: String,
+}
+
+/// Body of `POST /content/rollback`.
+#[derive(Debug, Default, Deserialize)]
+pub struct RollbackRequest {
+    /// Version to re-apply (default: the one before the current version).
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// Response of `POST /content/rollback`.
+#[derive(Debug, Serialize)]
+pub struct RollbackResponse {
+    /// Content version now applied.
+    pub version: String,
+}
+
+/// Handle an authorized module request.
+///
+/// Returns the HTTP status code and JSON body.
+pub async fn handle_module_request(
+    handle: &OrchestratorHandle,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    identity: Option<&Identity>,
+) -> (u16, String) {
+    if path == MODULES_QUARANTINED_PATH {
+        if method != "GET" {
+            return (405, r#"{"error":"method not allowed"}"#.to_owned());
+        }
+        return match handle.quarantined().await {
+            Ok(records) => {
+                let mut modules: Vec<QuarantinedModule> =
+                    records.into_iter().map(QuarantinedModule::from).collect();
+                modules.sort_by(|a, b| a.name.cmp(&b.name));
+                let body = serde_json::to_string(&QuarantineList { modules })
+                    .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+                (200, body)
+            }
+            Err(e) => (e.status, e.body()),
+        };
+    }
+
+    if method != "POST" {
+        return (405, r#"{"error":"method not allowed"}"#.to_owned());
+    }
+    let request = match serde_json::from_slice::<UnquarantineRequest>(body) {
+        Ok(request) => request,
+        Err(e) => {
+            let message = format!("invalid request body: {}", e);
+            return (400, serde_json::json!({ "error": message }).to_string());
+        }
+    };
+    let token = identity.map(|i| i.name.as_str()).unwrap_or_default();
+    match handle.unquarantine(&request.module).await {
+        Ok(()) => {
+            tracing::info!(token = %token, module = %request.module, "module quarantine lifted");
+            let body = serde_json::to_string(&UnquarantineResponse {
+                module: request.module,
+            })
+            .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+            (200, body)
+        }
+        Err(e) => {
+            tracing::warn!(token = %token, module = %request.module, error = %e.message, "unquarantine failed");
+            (e.status, e.body())
+        }
+    }
+}
+
+/// Handle an authorized content rollback request.
+///
+/// Only `POST` is accepted; an empty body rolls back to the previous version.
+/// Returns the HTTP status code and JSON body.
+pub async fn handle_rollback_request(
+    handle: &OrchestratorHandle,
+    method: &str,
+    body: &[u8],
+    identity: Option<&Identity>,
+) -> (u16, String) {
+    if method != "POST" {
+        return (405, r#"{"error":"method not allowed"}"#.to_owned());
+    }
+    let request = if body.is_empty() {
+        RollbackRequest::default()
+    } else {
+        match serde_json::from_slice::<RollbackRequest>(body) {
+            Ok(request) => request,
+            Err(e) => {
+                let message = format!("invalid request body: {}", e);
+                return (400, serde_json::json!({ "error": message }).to_string());
+            }
+        }
+    };
+    let token = identity.map(|i| i.name.as_str()).unwrap_or_default();
+    match handle.rollback_content(request.version.as_deref()).await {
+        Ok(version) => {
+            tracing::info!(token = %token, version = %version, "content rolled back via control API");
+            let body = serde_json::to_string(&RollbackResponse { version })
+                .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+            (200, body)
+        }
+        Err(e) => {
+            tracing::warn!(token = %token, error = %e.message, "content rollback failed");
+            (e.status, e.body())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    /// Answer commands like a main loop with `quarantined` out of service.
+    fn spawn_loop(quarantined: Vec<QuarantineRecord>) -> OrchestratorHandle {
+        let (handle, mut rx) = OrchestratorHandle::channel();
+        tokio::spawn(async move {
+            while let Some(command) = rx.recv().await {
+                match command {
+                    OrchestratorCommand::Quarantined { reply } => {
+                        let _ = reply.send(quarantined.clone());
+                    }
+                    OrchestratorCommand::Unquarantine { module, reply } => {
+                        let result = if quarantined.iter().any(|r| r.name == module) {
+                            Ok(())
+                        } else {
+                            Err(CommandError::not_found(format!(
+                                "module '{}' is not quarantined",
+                                module
+                            )))
+                        };
+                        let _ = reply.send(result);
+                    }
+                    OrchestratorCommand::RollbackContent { version, reply } => {
+                        let result = match version.as_deref() {
+                            None => Ok("a1b2c3d4e5f60718".to_owned()),
+                            Some("0011223344556677") => Ok("0011223344556677".to_owned()),
+                            Some(other) => Err(CommandError::conflict(format!(
+                                "content version '{}' is not stored",
+                                other
+                            ))),
+                        };
+                        let _ = reply.send(result);
+                    }
+                }
+            }
+        });
+        handle
+    }
+
+    fn record(name: &str) -> QuarantineRecord {
+        QuarantineRecord {
+            name: name.to_owned(),
+            reason: "bind failed".to_owned(),
+            failures: 3,
+            since: SystemTime::UNIX_EPOCH + Duration::from_secs(60),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lists_quarantined_modules_sorted() {
+        let handle = spawn_loop(vec![record("sbom-scanner"), record("log-pipeline")]);
+
+        let (code, body) =
+            handle_module_request(&handle, "GET", MODULES_QUARANTINED_PATH, b"", None).await;
+
+        assert_eq!(code, 200);
+        let list: serde_json::Value = serde_json::from_str(&body).expect("valid JSON");
+        assert_eq!(list["modules"][0]["name"], "log-pipeline");
+        assert_eq!(list["modules"][0]["since_ms"], 60_000);
+        assert_eq!(list["modules"][1]["name"], "sbom-scanner");
+    }
+
+    #[tokio::test]
+    async fn test_unquarantine_reports_loop_outcome() {
+        let handle = spawn_loop(vec![record("log-pipeline")]);
+
+        let (code, body) = handle_module_request(
+            &handle,
+            "POST",
+            MODULES_UNQUARANTINE_PATH,
+            br#"{"module":"log-pipeline"}"#,
+            None,
+        )
+        .await;
+        assert_eq!(code, 200, "got: {}", body);
+        assert!(body.contains("log-pipeline"));
+
+        let (code, body) = handle_module_request(
+            &handle,
+            "POST",
+            MODULES_UNQUARANTINE_PATH,
+            br#"{"module":"ebpf-engine"}"#,
+            None,
+        )
+        .await;
+        assert_eq!(code, 404);
+        assert!(body.contains("not quarantined"));
+    }
+
+    #[tokio::test]
+    async fn test_rollback_defaults_to_previous_version() {
+        let handle = spawn_loop(Vec::new());
+
+        let (code, body) = handle_rollback_request(&handle, "POST", b"", None).await;
+        assert_eq!(code, 200, "got: {}", body);
+        assert!(body.contains("a1b2c3d4e5f60718"));
+
+        let (code, body) =
+            handle_rollback_request(&handle, "POST", br#"{"version":"0011223344556677"}"#, None)
+                .await;
+        assert_eq!(code, 200, "got: {}", body);
+        assert!(body.contains("0011223344556677"));
+
+        let (code, body) =
+            handle_rollback_request(&handle, "POST", br#"{"version":"ffff"}"#, None).await;
+        assert_eq!(code, 409);
+        assert!(body.contains("not stored"));
+
+        let (code, _) = handle_rollback_request(&handle, "GET", b"", None).await;
+        assert_eq!(code, 405);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_method_body_and_stopped_loop() {
+        let handle = spawn_loop(Vec::new());
+        let (code, _) =
+            handle_module_request(&handle, "POST", MODULES_QUARANTINED_PATH, b"", None).await;
+        assert_eq!(code, 405);
+        let (code, _) =
+            handle_module_request(&handle, "GET", MODULES_UNQUARANTINE_PATH, b"", None).await;
+        assert_eq!(code, 405);
+        let (code, body) =
+            handle_module_request(&handle, "POST", MODULES_UNQUARANTINE_PATH, b"{}", None).await;
+        assert_eq!(code, 400);
+        assert!(body.contains("invalid request body"));
+
+        let (stopped, rx) = OrchestratorHandle::channel();
+        drop(rx);
+        let (code, _) =
+            handle_module_request(&stopped, "GET", MODULES_QUARANTINED_PATH, b"", None).await;
+        assert_eq!(code, 503);
+    }
+}
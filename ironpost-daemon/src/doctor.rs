@@ -0,0 +1,573 @@
+//! Startup self-test (`--doctor`).
+//!
+//! Checks the environment the daemon would start in and prints a pass/fail
+//! report instead of starting:
+//!
+//! - configuration validity
+//! - kernel version and BPF capabilities (eBPF engine)
+//! - kernel BTF and XDP driver support for the configured mode (eBPF engine)
+//! - eBPF binary presence
+//! - Docker socket access (container guard)
+//! - rule and policy directory validity
+//! - listener ports (health, metrics, syslog) bindable
+//! - vulnerability database readable (SBOM scanner)
+//!
+//! Checks for disabled modules are reported as skipped. The process exits
+//! non-zero if any check fails.
+
+use std::io::Write;
+use std::path::Path;
+
+use serde::Serialize;
+
+use ironpost_core::config::IronpostConfig;
+
+/// Minimum kernel version for the eBPF engine (ring buffer + XDP).
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const MIN_KERNEL_VERSION: (u32, u32) = (5, 7);
+
+/// Linux capability numbers (`linux/capability.h`).
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const CAP_NET_ADMIN: u32 = 12;
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const CAP_SYS_ADMIN: u32 = 21;
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const CAP_BPF: u32 = 39;
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    /// The check passed.
+    Pass,
+    /// The daemon can start, but something needs attention.
+    Warn,
+    /// The daemon (or a module) would fail to start.
+    Fail,
+    /// The check does not apply (module disabled).
+    Skip,
+}
+
+impl CheckStatus {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+            Self::Skip => "SKIP",
+        }
+    }
+}
+
+/// Result of a single check.
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    /// Check name.
+    pub name: String,
+    /// Outcome.
+    pub status: CheckStatus,
+    /// Human-readable detail.
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_owned(),
+            status,
+            detail: detail.into(),
+        }
+    }
+
+    fn pass(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Pass, detail)
+    }
+
+    fn fail(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Fail, detail)
+    }
+
+    fn skip(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Skip, detail)
+    }
+}
+
+/// Full self-test report.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    /// Whether every check passed or was skipped/warned.
+    pub ok: bool,
+    /// Individual checks in execution order.
+    pub checks: Vec<Check>,
+}
+
+impl DoctorReport {
+    /// Run all checks against `config`.
+    pub async fn run(config: &IronpostConfig) -> Self {
+        let mut checks = vec![check_config(config)];
+        checks.push(check_kernel(config));
+        checks.push(check_bpf_capabilities(config));
+        checks.push(check_xdp_support(config));
+        checks.push(check_ebpf_binary(config));
+        checks.push(check_docker_socket(config).await);
+        checks.push(check_rules(config).await);
+        checks.push(check_policies(config));
+        checks.extend(check_ports(config).await);
+        checks.push(check_vuln_db(config));
+        Self::from_checks(checks)
+    }
+
+    fn from_checks(checks: Vec<Check>) -> Self {
+        let ok = checks.iter().all(|c| c.status != CheckStatus::Fail);
+        Self { ok, checks }
+    }
+
+    /// Number of failed checks.
+    pub fn failures(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|c| c.status == CheckStatus::Fail)
+            .count()
+    }
+
+    /// Write the report as an aligned text table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn render_text(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        let width = self.checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+        for check in &self.checks {
+            writeln!(
+                w,
+                "[{}] {:width$}  {}",
+                check.status.label(),
+                check.name,
+                check.detail,
+                width = width
+            )?;
+        }
+        writeln!(w)?;
+        if self.ok {
+            writeln!(w, "All checks passed.")
+        } else {
+            writeln!(w, "{} check(s) failed.", self.failures())
+        }
+    }
+}
+
+fn check_config(config: &IronpostConfig) -> Check {
+    match config.validate() {
+        Ok(()) => Check::pass("config", "configuration is valid"),
+        Err(e) => Check::fail("config", e.to_string()),
+    }
+}
+
+fn check_kernel(config: &IronpostConfig) -> Check {
+    const NAME: &str = "kernel";
+    if !config.ebpf.enabled {
+        return Check::skip(NAME, "eBPF engine disabled");
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let release = match std::fs::read_to_string("/proc/sys/kernel/osrelease") {
+            Ok(release) => release,
+            Err(e) => return Check::fail(NAME, format!("cannot read kernel release: {}", e)),
+        };
+        let release = release.trim();
+        match parse_kernel_version(release) {
+            Some(version) if version >= MIN_KERNEL_VERSION => Check::pass(NAME, release),
+            Some(_) => Check::fail(
+                NAME,
+                format!(
+                    "{} is older than the required {}.{}",
+                    release, MIN_KERNEL_VERSION.0, MIN_KERNEL_VERSION.1
+                ),
+            ),
+            None => Check::new(
+                NAME,
+                CheckStatus::Warn,
+                format!("cannot parse kernel release '{}'", release),
+            ),
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Check::fail(NAME, "eBPF engine requires Linux")
+    }
+}
+
+fn check_bpf_capabilities(config: &IronpostConfig) -> Check {
+    const NAME: &str = "bpf-capabilities";
+    if !config.ebpf.enabled {
+        return Check::skip(NAME, "eBPF engine disabled");
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let caps = std::fs::read_to_string("/proc/self/status")
+            .ok()
+            .and_then(|status| parse_cap_eff(&status));
+        match caps {
+            Some(caps) if has_bpf_capabilities(caps) => {
+                Check::pass(NAME, "CAP_BPF + CAP_NET_ADMIN (or CAP_SYS_ADMIN) available")
+            }
+            Some(_) => Check::fail(
+                NAME,
+                "missing CAP_BPF and CAP_NET_ADMIN (or CAP_SYS_ADMIN); run as root or grant capabilities",
+            ),
+            None => Check::new(
+                NAME,
+                CheckStatus::Warn,
+                "cannot read effective capabilities",
+            ),
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Check::fail(NAME, "eBPF engine requires Linux")
+    }
+}
+
+/// Kernel BTF and per-interface XDP driver support, using the engine's
+/// preflight probe. Kernel version and capabilities are reported separately.
+fn check_xdp_support(config: &IronpostConfig) -> Check {
+    const NAME: &str = "xdp-support";
+    if !config.ebpf.enabled {
+        return Check::skip(NAME, "eBPF engine disabled");
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use ironpost_ebpf_engine::PreflightStatus;
+
+        // With `interface = "all"` the engine skips unsupported interfaces, so
+        // only the configured list is probed here. `"auto"` probes the interface
+        // currently carrying the default route.
+        let interfaces = if config.ebpf.attaches_all_interfaces() {
+            Vec::new()
+        } else if config.ebpf.uses_default_route_interface() {
+            match ironpost_ebpf_engine::default_route_interface() {
+                Ok(name) => vec![name],
+                Err(e) => return Check::fail(NAME, e.to_string()),
+            }
+        } else {
+            config.ebpf.interfaces()
+        };
+        let report = ironpost_ebpf_engine::preflight::run(
+            &config.ebpf.xdp_mode,
+            &interfaces,
+            config.ebpf.attaches_all_interfaces(),
+        );
+        let relevant: Vec<_> = report
+            .checks
+            .iter()
+            .filter(|c| c.name == "btf" || c.name.starts_with("xdp:"))
+            .collect();
+        let problems: Vec<String> = relevant
+            .iter()
+            .filter(|c| c.status != PreflightStatus::Pass)
+            .map(|c| c.to_string())
+            .collect();
+        if relevant.iter().any(|c| c.status == PreflightStatus::Fail) {
+            Check::fail(NAME, problems.join("; "))
+        } else if !problems.is_empty() {
+            Check::new(NAME, CheckStatus::Warn, problems.join("; "))
+        } else {
+            Check::pass(
+                NAME,
+                format!("BTF available, {} XDP supported", config.ebpf.xdp_mode),
+            )
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Check::fail(NAME, "eBPF engine requires Linux")
+    }
+}
+
+fn check_ebpf_binary(config: &IronpostConfig) -> Check {
+    const NAME: &str = "ebpf-binary";
+    if !config.ebpf.enabled {
+        return Check::skip(NAME, "eBPF engine disabled");
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use ironpost_ebpf_engine::EbpfObjectSource;
+
+        let path = match ironpost_ebpf_engine::ebpf_object_source() {
+            EbpfObjectSource::Embedded => {
+                return Check::pass(NAME, "embedded in daemon binary");
+            }
+            EbpfObjectSource::File(path) => path,
+        };
+        match std::fs::metadata(&path) {
+            Ok(meta) if meta.is_file() => Check::pass(NAME, path),
+            Ok(_) => Check::fail(NAME, format!("{} is not a file", path)),
+            Err(e) => Check::fail(
+                NAME,
+                format!(
+                    "{}: {} (build with `cargo xtask build-ebpf`, set IRONPOST_EBPF_PATH, or build the daemon with `cargo xtask build --embed-ebpf`)",
+                    path, e
+                ),
+            ),
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Check::fail(NAME, "eBPF engine requires Linux")
+    }
+}
+
+async fn check_docker_socket(config: &IronpostConfig) -> Check {
+    const NAME: &str = "docker-socket";
+    if !config.container.enabled {
+        return Check::skip(NAME, "container guard disabled");
+    }
+
+    let socket = config
+        .container
+        .docker_socket
+        .strip_prefix("unix://")
+        .unwrap_or(&config.container.docker_socket);
+
+    #[cfg(unix)]
+    {
+        match tokio::net::UnixStream::connect(socket).await {
+            Ok(_) => Check::pass(NAME, socket),
+            Err(e) => Check::fail(NAME, format!("cannot connect to {}: {}", socket, e)),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("cannot check {} on this platform", socket),
+        )
+    }
+}
+
+async fn check_rules(config: &IronpostConfig) -> Check {
+    const NAME: &str = "rules";
+    if !config.log_pipeline.enabled {
+        return Check::skip(NAME, "log pipeline disabled");
+    }
+
+    let rule_dir = ironpost_log_pipeline::PipelineConfig::from_core(&config.log_pipeline).rule_dir;
+    match ironpost_log_pipeline::rule::RuleLoader::load_directory(&rule_dir).await {
+        Ok(rules) if rules.is_empty() => Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("{}: no rules found", rule_dir),
+        ),
+        Ok(rules) => Check::pass(NAME, format!("{}: {} rules", rule_dir, rules.len())),
+        Err(e) => Check::fail(NAME, format!("{}: {}", rule_dir, e)),
+    }
+}
+
+fn check_policies(config: &IronpostConfig) -> Check {
+    const NAME: &str = "policies";
+    if !config.container.enabled {
+        return Check::skip(NAME, "container guard disabled");
+    }
+    let policy_dir = &config.container.policy_path;
+    if policy_dir.is_empty() {
+        return Check::skip(NAME, "policy_path not set");
+    }
+
+    match ironpost_container_guard::load_policies_from_dir(Path::new(policy_dir)) {
+        Ok(policies) => Check::pass(NAME, format!("{}: {} policies", policy_dir, policies.len())),
+        Err(e) => Check::fail(NAME, e.to_string()),
+    }
+}
+
+/// Try to bind every listener the daemon would open.
+async fn check_ports(config: &IronpostConfig) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    if config.health.enabled {
+        let addr = format!("{}:{}", config.health.listen_addr, config.health.port);
+        checks.push(check_tcp_bind("health-port", &addr).await);
+    } else {
+        checks.push(Check::skip("health-port", "health endpoint disabled"));
+    }
+
+    if config.metrics.enabled {
+        let addr = format!("{}:{}", config.metrics.listen_addr, config.metrics.port);
+        checks.push(check_tcp_bind("metrics-port", &addr).await);
+    } else {
+        checks.push(Check::skip("metrics-port", "metrics disabled"));
+    }
+
+    if config.log_pipeline.enabled {
+        let sources = &config.log_pipeline.sources;
+        let has = |name: &str| sources.iter().any(|s| s == "syslog" || s == name);
+        if has("syslog_udp") {
+            let addr = &config.log_pipeline.syslog_bind;
+            checks.push(match tokio::net::UdpSocket::bind(addr.as_str()).await {
+                Ok(_) => Check::pass("syslog-udp-port", addr.as_str()),
+                Err(e) => Check::fail("syslog-udp-port", format!("{}: {}", addr, e)),
+            });
+        }
+        if has("syslog_tcp") {
+            checks.push(
+                check_tcp_bind("syslog-tcp-port", &config.log_pipeline.syslog_tcp_bind).await,
+            );
+        }
+        if sources.iter().any(|s| s == "http") {
+            checks.push(check_tcp_bind("http-ingest-port", &config.log_pipeline.http.bind).await);
+        }
+    }
+
+    checks
+}
+
+async fn check_tcp_bind(name: &str, addr: &str) -> Check {
+    match tokio::net::TcpListener::bind(addr).await {
+        Ok(_) => Check::pass(name, addr),
+        Err(e) => Check::fail(name, format!("{}: {}", addr, e)),
+    }
+}
+
+fn check_vuln_db(config: &IronpostConfig) -> Check {
+    const NAME: &str = "vuln-db";
+    if !config.sbom.enabled {
+        return Check::skip(NAME, "SBOM scanner disabled");
+    }
+
+    let path = &config.sbom.vuln_db_path;
+    match ironpost_sbom_scanner::VulnDb::load_from_dir(Path::new(path)) {
+        Ok(db) if db.entry_count() == 0 => Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("{}: database is empty", path),
+        ),
+        Ok(db) => Check::pass(NAME, format!("{}: {} entries", path, db.entry_count())),
+        Err(e) => Check::fail(NAME, e.to_string()),
+    }
+}
+
+/// Parse `major.minor` from a kernel release string (e.g. `6.1.0-18-amd64`).
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_kernel_version(release: &str) -> Option<(u32, u32)> {
+    let mut parts = release.split(['.', '-']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Parse the effective capability set (`CapEff`) from `/proc/<pid>/status`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_cap_eff(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("CapEff:"))
+        .and_then(|hex| u64::from_str_radix(hex.trim(), 16).ok())
+}
+
+/// Whether the capability set can load and attach XDP programs.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn has_bpf_capabilities(caps: u64) -> bool {
+    let has = |cap: u32| caps & (1u64 << cap) != 0;
+    has(CAP_SYS_ADMIN) || (has(CAP_BPF) && has(CAP_NET_ADMIN))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kernel_version() {
+        assert_eq!(parse_kernel_version("6.1.0-18-amd64"), Some((6, 1)));
+        assert_eq!(parse_kernel_version("5.15.133.1-microsoft"), Some((5, 15)));
+        assert_eq!(parse_kernel_version("garbage"), None);
+        assert!(parse_kernel_version("5.4.0").unwrap() < MIN_KERNEL_VERSION);
+    }
+
+    #[test]
+    fn test_parse_cap_eff_and_bpf_capabilities() {
+        // Given: Root (all capabilities) and an unprivileged process
+        let root = "Name:\tironpost\nCapEff:\t000001ffffffffff\n";
+        let user = "Name:\tironpost\nCapEff:\t0000000000000000\n";
+        let bpf_only = (1u64 << CAP_BPF) | (1u64 << CAP_NET_ADMIN);
+
+        // Then: Only sufficient sets pass
+        assert!(has_bpf_capabilities(parse_cap_eff(root).unwrap()));
+        assert!(!has_bpf_capabilities(parse_cap_eff(user).unwrap()));
+        assert!(has_bpf_capabilities(bpf_only));
+        assert!(!has_bpf_capabilities(1u64 << CAP_BPF));
+        assert_eq!(parse_cap_eff("Name:\tx\n"), None);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_modules_are_skipped() {
+        // Given: Every module disabled and no listeners
+        let mut config = IronpostConfig::default();
+        config.ebpf.enabled = false;
+        config.log_pipeline.enabled = false;
+        config.container.enabled = false;
+        config.sbom.enabled = false;
+        config.health.enabled = false;
+        config.metrics.enabled = false;
+
+        // When: Running the doctor
+        let report = DoctorReport::run(&config).await;
+
+        // Then: Only the config check runs, everything else is skipped
+        assert!(report.ok);
+        assert_eq!(report.checks[0].status, CheckStatus::Pass);
+        assert!(
+            report.checks[1..]
+                .iter()
+                .all(|c| c.status == CheckStatus::Skip)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rule_and_port_failures_are_reported() {
+        // Given: A missing rule directory and a port already in use
+        let taken = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut config = IronpostConfig::default();
+        config.ebpf.enabled = false;
+        config.container.enabled = false;
+        config.sbom.enabled = false;
+        config.metrics.enabled = false;
+        config.log_pipeline.enabled = true;
+        config.log_pipeline.sources = vec!["file".to_owned()];
+        config.health.enabled = true;
+        config.health.port = taken.local_addr().unwrap().port();
+
+        // When: Running the doctor
+        let report = DoctorReport::run(&config).await;
+
+        // Then: The health port fails and the report is not ok
+        let health = report
+            .checks
+            .iter()
+            .find(|c| c.name == "health-port")
+            .unwrap();
+        assert_eq!(health.status, CheckStatus::Fail);
+        assert!(!report.ok);
+        assert!(report.failures() >= 1);
+    }
+
+    #[test]
+    fn test_render_text() {
+        let report = DoctorReport::from_checks(vec![
+            Check::pass("config", "configuration is valid"),
+            Check::fail("docker-socket", "cannot connect"),
+            Check::skip("vuln-db", "SBOM scanner disabled"),
+        ]);
+
+        let mut out = Vec::new();
+        report.render_text(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        assert!(text.contains("[PASS] config"));
+        assert!(text.contains("[FAIL] docker-socket  cannot connect"));
+        assert!(text.contains("1 check(s) failed."));
+    }
+}
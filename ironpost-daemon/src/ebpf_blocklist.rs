@@ -0,0 +1,329 @@
+//! eBPF blocklist management for the control API.
+//!
+//! Served by the health listener next to `/ebpf`:
+//!
+//! - `GET /ebpf/blocklist` -- runtime entries with hit counts (`read-only`)
+//! - `POST /ebpf/blocklist` -- add or replace entries (`operator`)
+//! - `POST /ebpf/blocklist/remove` -- remove entries (`operator`)
+//! - `POST /ebpf/rules/import` -- merge filter rules in bulk (`operator`)
+//!
+//! Changes go through the engine's shared [`BlocklistHandle`], so they reach
+//! the kernel blocklist (or, for `allow` entries, the `ALLOWLIST` map)
+//! immediately while the engine is running and are applied on the next start
+//! otherwise. Imported rules are validated and deduplicated as a whole and
+//! synced to the maps once, so importing thousands of rules does not rebuild
+//! the maps per rule.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use ironpost_ebpf_engine::{
+    BlocklistEntry, BlocklistHandle, BlocklistStatus, FilterRule, Ipv4Cidr, RuleAction,
+};
+
+use crate::auth::Identity;
+use crate::ebpf_status::{EBPF_BLOCKLIST_REMOVE_PATH, EBPF_RULES_IMPORT_PATH};
+
+/// A single entry in a `POST /ebpf/blocklist` request.
+#[derive(Debug, Deserialize)]
+pub struct AddEntry {
+    /// IPv4 address or CIDR (`10.0.0.1`, `10.0.0.0/24`).
+    pub cidr: String,
+    /// Action to apply (defaults to `block`).
+    #[serde(default = "default_action")]
+    pub action: RuleAction,
+    /// Seconds until the entry expires (`None` = permanent).
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+    /// Free-form reason.
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Body of `POST /ebpf/blocklist`.
+#[derive(Debug, Deserialize)]
+pub struct AddRequest {
+    /// Entries to add.
+    pub entries: Vec<AddEntry>,
+}
+
+/// Body of `POST /ebpf/blocklist/remove`.
+#[derive(Debug, Deserialize)]
+pub struct RemoveRequest {
+    /// Addresses or CIDRs to remove, as they were added.
+    pub entries: Vec<String>,
+}
+
+/// Body of `POST /ebpf/rules/import`.
+#[derive(Debug, Deserialize)]
+pub struct ImportRulesRequest {
+    /// Filter rules, in the same shape as the `[[rules]]` rules file.
+    pub rules: Vec<FilterRule>,
+}
+
+/// Response of `GET /ebpf/blocklist`.
+#[derive(Debug, Serialize)]
+pub struct ListResponse {
+    /// Runtime entries with hit counts.
+    pub entries: Vec<BlocklistStatus>,
+}
+
+/// Response of `POST /ebpf/blocklist`.
+#[derive(Debug, Serialize)]
+pub struct AddResponse {
+    /// Number of entries added or replaced.
+    pub added: usize,
+}
+
+/// Response of `POST /ebpf/blocklist/remove`.
+#[derive(Debug, Serialize)]
+pub struct RemoveResponse {
+    /// Number of entries removed.
+    pub removed: usize,
+    /// Requested entries that were not in the blocklist.
+    pub missing: Vec<String>,
+}
+
+fn default_action() -> RuleAction {
+    RuleAction::Block
+}
+
+/// Handle an authorized blocklist request.
+///
+/// `method` is `GET` or `POST` (checked by the caller); the rule import path
+/// only accepts `POST`. Returns the HTTP status code and JSON body.
+pub async fn handle_blocklist_request(
+    blocklist: &BlocklistHandle,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    identity: Option<&Identity>,
+) -> (u16, String) {
+    let requested_by = identity.map(|i| i.name.as_str()).unwrap_or_default();
+    let result = match (method, path) {
+        ("POST", EBPF_RULES_IMPORT_PATH) => import_rules(blocklist, body, requested_by).await,
+        (_, EBPF_RULES_IMPORT_PATH) => {
+            return (405, r#"{"error":"method not allowed"}"#.to_owned());
+        }
+        ("GET", _) => Ok(to_json(&ListResponse {
+            entries: blocklist.lock().await.list(),
+        })),
+        ("POST", EBPF_BLOCKLIST_REMOVE_PATH) => remove_entries(blocklist, body, requested_by).await,
+        ("POST", _) => add_entries(blocklist, body, requested_by).await,
+        _ => return (405, r#"{"error":"method not allowed"}"#.to_owned()),
+    };
+
+    match result {
+        Ok(body) => (200, body),
+        Err(message) => (400, serde_json::json!({ "error": message }).to_string()),
+    }
+}
+
+async fn add_entries(
+    blocklist: &BlocklistHandle,
+    body: &[u8],
+    requested_by: &str,
+) -> Result<String, String> {
+    let request: AddRequest =
+        serde_json::from_slice(body).map_err(|e| format!("invalid request body: {}", e))?;
+
+    let entries = request
+        .entries
+        .into_iter()
+        .map(|e| {
+            let cidr = e.cidr.parse::<Ipv4Cidr>().map_err(|err| err.to_string())?;
+            let mut entry = BlocklistEntry::new(cidr, e.action).with_description(e.description);
+            if let Some(ttl) = e.ttl_secs {
+                entry = entry.with_ttl(Duration::from_secs(ttl));
+            }
+            Ok(entry)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let cidrs: Vec<String> = entries.iter().map(|e| e.cidr.to_string()).collect();
+    let added = blocklist
+        .lock()
+        .await
+        .insert_all(entries)
+        .map_err(|e| e.to_string())?;
+    tracing::info!(token = %requested_by, added = added, entries = ?cidrs, "blocklist entries added");
+
+    Ok(to_json(&AddResponse { added }))
+}
+
+async fn remove_entries(
+    blocklist: &BlocklistHandle,
+    body: &[u8],
+    requested_by: &str,
+) -> Result<String, String> {
+    let request: RemoveRequest =
+        serde_json::from_slice(body).map_err(|e| format!("invalid request body: {}", e))?;
+
+    let cidrs = request
+        .entries
+        .iter()
+        .map(|s| s.parse::<Ipv4Cidr>().map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut guard = blocklist.lock().await;
+    let mut removed = 0;
+    let mut missing = Vec::new();
+    for cidr in cidrs {
+        if guard.remove(&cidr).map_err(|e| e.to_string())? {
+            removed += 1;
+        } else {
+            missing.push(cidr.to_string());
+        }
+    }
+    drop(guard);
+    tracing::info!(token = %requested_by, removed = removed, "blocklist entries removed");
+
+    Ok(to_json(&RemoveResponse { removed, missing }))
+}
+
+async fn import_rules(
+    blocklist: &BlocklistHandle,
+    body: &[u8],
+    requested_by: &str,
+) -> Result<String, String> {
+    let request: ImportRulesRequest =
+        serde_json::from_slice(body).map_err(|e| format!("invalid request body: {}", e))?;
+
+    let summary = blocklist
+        .lock()
+        .await
+        .import_rules(request.rules)
+        .map_err(|e| e.to_string())?;
+    tracing::info!(
+        token = %requested_by,
+        added = summary.added,
+        replaced = summary.replaced,
+        duplicates = summary.duplicates,
+        "filter rules imported"
+    );
+
+    Ok(to_json(&summary))
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ebpf_status::EBPF_BLOCKLIST_PATH;
+    use ironpost_ebpf_engine::Blocklist;
+    use std::sync::Arc;
+
+    fn handle() -> BlocklistHandle {
+        Arc::new(tokio::sync::Mutex::new(Blocklist::new()))
+    }
+
+    #[tokio::test]
+    async fn test_add_list_remove_roundtrip() {
+        // Given: An empty blocklist
+        let blocklist = handle();
+
+        // When: Adding a host and a subnet, then removing the host
+        let body = br#"{"entries":[
+            {"cidr":"10.0.0.1","ttl_secs":60,"description":"scanner"},
+            {"cidr":"10.0.1.0/24","action":"monitor"}
+        ]}"#;
+        let (code, added) =
+            handle_blocklist_request(&blocklist, "POST", EBPF_BLOCKLIST_PATH, body, None).await;
+        assert_eq!(code, 200, "{}", added);
+        assert!(added.contains("\"added\":2"));
+
+        let (code, removed) = handle_blocklist_request(
+            &blocklist,
+            "POST",
+            EBPF_BLOCKLIST_REMOVE_PATH,
+            br#"{"entries":["10.0.0.1","10.9.9.9"]}"#,
+            None,
+        )
+        .await;
+        assert_eq!(code, 200);
+        assert!(removed.contains("\"removed\":1"));
+        assert!(removed.contains("10.9.9.9"));
+
+        // Then: Only the subnet remains, with its action and hit count
+        let (code, list) =
+            handle_blocklist_request(&blocklist, "GET", EBPF_BLOCKLIST_PATH, b"", None).await;
+        assert_eq!(code, 200);
+        let list: serde_json::Value = serde_json::from_str(&list).expect("valid JSON");
+        let entries = list["entries"].as_array().expect("entries array");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["cidr"], "10.0.1.0/24");
+        assert_eq!(entries[0]["action"], "monitor");
+        assert_eq!(entries[0]["hits"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_add_rejects_invalid_entries_atomically() {
+        // Given: An empty blocklist
+        let blocklist = handle();
+
+        // When: One of the entries is not a valid IPv4 CIDR
+        let body = br#"{"entries":[{"cidr":"10.0.0.1"},{"cidr":"10.0.0.0/4"}]}"#;
+        let (code, error) =
+            handle_blocklist_request(&blocklist, "POST", EBPF_BLOCKLIST_PATH, body, None).await;
+
+        // Then: The request is rejected and nothing is added
+        assert_eq!(code, 400);
+        assert!(error.contains("too wide"), "got: {}", error);
+        assert!(blocklist.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_rejects_malformed_body() {
+        let blocklist = handle();
+
+        let (code, error) =
+            handle_blocklist_request(&blocklist, "POST", EBPF_BLOCKLIST_PATH, b"not json", None)
+                .await;
+
+        assert_eq!(code, 400);
+        assert!(error.contains("invalid request body"));
+    }
+
+    #[tokio::test]
+    async fn test_import_rules_merges_in_one_request() {
+        // Given: An empty blocklist
+        let blocklist = handle();
+
+        // When: Importing rules with a duplicated ID
+        let body = br#"{"rules":[
+            {"id":"scanner","src_ip":"10.0.0.50","action":"block","description":"first"},
+            {"id":"botnet","src_cidr":"203.0.113.0/24","action":"block","description":""},
+            {"id":"scanner","src_ip":"10.0.0.51","action":"monitor","description":"second"}
+        ]}"#;
+        let (code, response) =
+            handle_blocklist_request(&blocklist, "POST", EBPF_RULES_IMPORT_PATH, body, None).await;
+
+        // Then: The last duplicate wins and both rules are applied
+        assert_eq!(code, 200, "{}", response);
+        let response: serde_json::Value = serde_json::from_str(&response).expect("valid JSON");
+        assert_eq!(response["added"], 2);
+        assert_eq!(response["duplicates"], 1);
+        let guard = blocklist.lock().await;
+        assert_eq!(guard.rule_count(), 2);
+        assert!(
+            guard
+                .rules()
+                .iter()
+                .any(|r| r.id == "scanner" && r.description == "second")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_rules_rejects_get() {
+        let blocklist = handle();
+
+        let (code, _) =
+            handle_blocklist_request(&blocklist, "GET", EBPF_RULES_IMPORT_PATH, b"", None).await;
+
+        assert_eq!(code, 405);
+    }
+}
@@ -0,0 +1,419 @@
+//! eBPF engine status for the control API.
+//!
+//! Served on `GET /ebpf` by the health listener (`read-only` role when
+//! `[auth]` is enabled). Traffic counters are read from the engine's stats
+//! on every request, so they are as fresh as the engine's 1s stats poll
+//! rather than the health snapshot interval.
+//!
+//! Blocklist management under `/ebpf/blocklist` and bulk rule import on
+//! `/ebpf/rules/import` are handled by `crate::ebpf_blocklist` (Linux only).
+
+use serde::Serialize;
+
+#[cfg(target_os = "linux")]
+use std::sync::Arc;
+
+#[cfg(target_os = "linux")]
+use ironpost_core::config::EbpfConfig;
+use ironpost_core::event::MODULE_EBPF;
+
+use crate::health_server::HealthSnapshot;
+
+/// Path of the eBPF status endpoint.
+pub const EBPF_STATUS_PATH: &str = "/ebpf";
+
+/// Path of the blocklist list (`GET`) and add (`POST`) endpoint.
+pub const EBPF_BLOCKLIST_PATH: &str = "/ebpf/blocklist";
+
+/// Path of the blocklist remove endpoint (`POST`).
+pub const EBPF_BLOCKLIST_REMOVE_PATH: &str = "/ebpf/blocklist/remove";
+
+/// Path of the bulk filter rule import endpoint (`POST`).
+pub const EBPF_RULES_IMPORT_PATH: &str = "/ebpf/rules/import";
+
+/// Counters for a single protocol.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProtocolCounters {
+    /// Packets processed (cumulative).
+    pub packets: u64,
+    /// Bytes processed (cumulative).
+    pub bytes: u64,
+    /// Packets dropped (cumulative).
+    pub drops: u64,
+    /// Packets that matched a drop decision but were passed (observe-only mode, cumulative).
+    pub would_drops: u64,
+    /// Packets per second over the last poll.
+    pub pps: f64,
+    /// Bits per second over the last poll.
+    pub bps: f64,
+}
+
+/// Counters for a single destination port.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PortCounters {
+    /// Destination port.
+    pub port: u16,
+    /// Transport protocol (`tcp` or `udp`).
+    pub protocol: String,
+    /// Counters for traffic to this port.
+    #[serde(flatten)]
+    pub counters: ProtocolCounters,
+}
+
+/// Counters for a single network interface.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InterfaceCounters {
+    /// Interface name.
+    pub name: String,
+    /// Counters for traffic received on this interface.
+    #[serde(flatten)]
+    pub counters: ProtocolCounters,
+}
+
+/// Per-protocol traffic counters.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TrafficCounters {
+    /// TCP traffic.
+    pub tcp: ProtocolCounters,
+    /// UDP traffic.
+    pub udp: ProtocolCounters,
+    /// ICMP traffic.
+    pub icmp: ProtocolCounters,
+    /// Other protocols.
+    pub other: ProtocolCounters,
+    /// All protocols.
+    pub total: ProtocolCounters,
+    /// Busiest destination ports, highest packet rate first.
+    pub top_ports: Vec<PortCounters>,
+    /// Counters for each attached interface, sorted by name.
+    pub interfaces: Vec<InterfaceCounters>,
+}
+
+/// Connections-per-second for a single source host.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HostConnections {
+    /// Source IPv4 address.
+    pub host: String,
+    /// New connections per second since the previous poll.
+    pub cps: f64,
+    /// Flows from this host that are still active.
+    pub active_flows: usize,
+}
+
+/// Connection tracking counters from the XDP flow table.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConnectionCounters {
+    /// Flows seen within the idle timeout.
+    pub active: usize,
+    /// Active flows that have not completed a handshake.
+    pub new: usize,
+    /// Active flows with traffic in progress.
+    pub established: usize,
+    /// Active TCP flows that have seen FIN or RST.
+    pub closing: usize,
+    /// Hosts opening the most new connections, highest rate first.
+    pub top_hosts: Vec<HostConnections>,
+}
+
+/// JSON body returned by `/ebpf`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EbpfStatus {
+    /// Whether the XDP program is attached (engine running and not quarantined).
+    pub attached: bool,
+    /// Engine health as reported by the last health check.
+    pub health: String,
+    /// Configured network interfaces (comma-separated list or `all`).
+    pub interface: String,
+    /// XDP attach mode (`native`, `skb`, `hw`).
+    pub xdp_mode: String,
+    /// Whether drop decisions are only logged and counted instead of dropping.
+    pub dry_run: bool,
+    /// Number of loaded filter rules.
+    pub rule_count: usize,
+    /// Number of runtime blocklist entries.
+    pub blocklist_entries: usize,
+    /// Traffic counters.
+    pub stats: TrafficCounters,
+    /// Connection tracking counters.
+    pub connections: ConnectionCounters,
+}
+
+/// Handles needed to report eBPF engine status outside the orchestrator.
+#[derive(Clone)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub struct EbpfSource {
+    interface: String,
+    xdp_mode: String,
+    dry_run: bool,
+    #[cfg(target_os = "linux")]
+    stats: Arc<tokio::sync::Mutex<ironpost_ebpf_engine::TrafficStats>>,
+    #[cfg(target_os = "linux")]
+    conntrack: Arc<tokio::sync::Mutex<ironpost_ebpf_engine::ConnTrackStats>>,
+    #[cfg(target_os = "linux")]
+    blocklist: ironpost_ebpf_engine::BlocklistHandle,
+}
+
+impl EbpfSource {
+    /// Create a source for an engine built from `config`.
+    #[cfg(target_os = "linux")]
+    pub fn new(
+        config: &EbpfConfig,
+        dry_run: bool,
+        stats: Arc<tokio::sync::Mutex<ironpost_ebpf_engine::TrafficStats>>,
+        conntrack: Arc<tokio::sync::Mutex<ironpost_ebpf_engine::ConnTrackStats>>,
+        blocklist: ironpost_ebpf_engine::BlocklistHandle,
+    ) -> Self {
+        Self {
+            interface: config.interface.clone(),
+            xdp_mode: config.xdp_mode.clone(),
+            dry_run,
+            stats,
+            conntrack,
+            blocklist,
+        }
+    }
+
+    /// Shared handle to the engine's blocklist.
+    #[cfg(target_os = "linux")]
+    pub fn blocklist(&self) -> &ironpost_ebpf_engine::BlocklistHandle {
+        &self.blocklist
+    }
+
+    /// Current status, using `snapshot` for the engine's health.
+    pub async fn status(&self, snapshot: &HealthSnapshot) -> EbpfStatus {
+        let module = snapshot
+            .health
+            .modules
+            .iter()
+            .find(|m| m.name == MODULE_EBPF);
+        let (attached, health) = match module {
+            Some(m) if m.enabled => (!m.status.is_unhealthy(), m.status.to_string()),
+            Some(m) => (false, m.status.to_string()),
+            None => (false, "not registered".to_owned()),
+        };
+
+        let (rule_count, blocklist_entries) = self.blocklist_counts().await;
+        EbpfStatus {
+            attached,
+            health,
+            interface: self.interface.clone(),
+            xdp_mode: self.xdp_mode.clone(),
+            dry_run: self.dry_run,
+            rule_count,
+            blocklist_entries,
+            stats: self.counters().await,
+            connections: self.connections().await,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn blocklist_counts(&self) -> (usize, usize) {
+        let blocklist = self.blocklist.lock().await;
+        (blocklist.rule_count(), blocklist.len())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    async fn blocklist_counts(&self) -> (usize, usize) {
+        (0, 0)
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn counters(&self) -> TrafficCounters {
+        let stats = self.stats.lock().await;
+        TrafficCounters {
+            tcp: ProtocolCounters::from(&stats.tcp),
+            udp: ProtocolCounters::from(&stats.udp),
+            icmp: ProtocolCounters::from(&stats.icmp),
+            other: ProtocolCounters::from(&stats.other),
+            total: ProtocolCounters::from(&stats.total),
+            top_ports: stats
+                .top_ports
+                .iter()
+                .map(|p| PortCounters {
+                    port: p.port,
+                    protocol: p.protocol.clone(),
+                    counters: ProtocolCounters::from(&p.metrics),
+                })
+                .collect(),
+            interfaces: stats
+                .interfaces
+                .iter()
+                .map(|i| InterfaceCounters {
+                    name: i.name.clone(),
+                    counters: ProtocolCounters::from(&i.metrics),
+                })
+                .collect(),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    async fn counters(&self) -> TrafficCounters {
+        TrafficCounters::default()
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn connections(&self) -> ConnectionCounters {
+        let conntrack = self.conntrack.lock().await;
+        ConnectionCounters {
+            active: conntrack.active_flows,
+            new: conntrack.new_flows,
+            established: conntrack.established_flows,
+            closing: conntrack.closing_flows,
+            top_hosts: conntrack
+                .top_hosts
+                .iter()
+                .map(|h| HostConnections {
+                    host: h.host.to_string(),
+                    cps: h.cps,
+                    active_flows: h.active_flows,
+                })
+                .collect(),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    async fn connections(&self) -> ConnectionCounters {
+        ConnectionCounters::default()
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<&ironpost_ebpf_engine::ProtoMetrics> for ProtocolCounters {
+    fn from(m: &ironpost_ebpf_engine::ProtoMetrics) -> Self {
+        Self {
+            packets: m.packets,
+            bytes: m.bytes,
+            drops: m.drops,
+            would_drops: m.would_drops,
+            pps: m.pps,
+            bps: m.bps,
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::health::{DaemonHealth, ModuleHealth};
+    use ironpost_core::pipeline::HealthStatus;
+    use ironpost_ebpf_engine::{
+        Blocklist, ConnTrackStats, FilterRule, RawInterfaceStats, RawPortStats, RawProtoStats,
+        RawTrafficSnapshot, RuleAction, TrafficStats,
+    };
+
+    fn snapshot(status: Option<HealthStatus>) -> HealthSnapshot {
+        let modules = status
+            .map(|status| ModuleHealth {
+                name: MODULE_EBPF.to_owned(),
+                enabled: true,
+                status,
+            })
+            .into_iter()
+            .collect();
+        HealthSnapshot::new(DaemonHealth {
+            status: HealthStatus::Healthy,
+            uptime_secs: 1,
+            modules,
+        })
+    }
+
+    fn source() -> EbpfSource {
+        let mut stats = TrafficStats::new();
+        stats.update(RawTrafficSnapshot {
+            tcp: RawProtoStats {
+                packets: 10,
+                bytes: 1500,
+                drops: 2,
+                would_drops: 0,
+            },
+            total: RawProtoStats {
+                packets: 10,
+                bytes: 1500,
+                drops: 2,
+                would_drops: 0,
+            },
+            ports: vec![RawPortStats {
+                port: 22,
+                protocol: 6,
+                stats: RawProtoStats {
+                    packets: 10,
+                    bytes: 1500,
+                    drops: 2,
+                    would_drops: 0,
+                },
+            }],
+            interfaces: vec![RawInterfaceStats {
+                name: "eth0".to_owned(),
+                stats: RawProtoStats {
+                    packets: 10,
+                    bytes: 1500,
+                    drops: 2,
+                    would_drops: 0,
+                },
+            }],
+            ..RawTrafficSnapshot::default()
+        });
+        let rules: Vec<FilterRule> = (1..=3)
+            .map(|i| FilterRule {
+                id: format!("rule-{}", i),
+                src_ip: None,
+                src_cidr: None,
+                dst_ip: None,
+                dst_port: Some(22),
+                protocol: None,
+                action: RuleAction::Block,
+                description: String::new(),
+            })
+            .collect();
+        let mut blocklist = Blocklist::new();
+        blocklist.set_rules(&rules).expect("rules should apply");
+        EbpfSource::new(
+            &EbpfConfig::default(),
+            false,
+            Arc::new(tokio::sync::Mutex::new(stats)),
+            Arc::new(tokio::sync::Mutex::new(ConnTrackStats::new())),
+            Arc::new(tokio::sync::Mutex::new(blocklist)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_counters_and_attach_state() {
+        // Given: A running engine with some TCP traffic
+        let source = source();
+
+        // When: Reading the status
+        let status = source.status(&snapshot(Some(HealthStatus::Healthy))).await;
+
+        // Then: Counters and configuration are reported
+        assert!(status.attached);
+        assert_eq!(status.rule_count, 3);
+        assert_eq!(status.blocklist_entries, 0);
+        assert_eq!(status.stats.tcp.packets, 10);
+        assert_eq!(status.stats.tcp.drops, 2);
+        assert_eq!(status.stats.total.bytes, 1500);
+        assert_eq!(status.stats.top_ports.len(), 1);
+        assert_eq!(status.stats.top_ports[0].port, 22);
+        assert_eq!(status.stats.top_ports[0].protocol, "tcp");
+        assert_eq!(status.stats.top_ports[0].counters.drops, 2);
+        assert_eq!(status.stats.interfaces.len(), 1);
+        assert_eq!(status.stats.interfaces[0].name, "eth0");
+        assert_eq!(status.stats.interfaces[0].counters.packets, 10);
+        assert_eq!(status.connections.active, 0);
+    }
+
+    #[tokio::test]
+    async fn test_status_not_attached_when_unhealthy_or_missing() {
+        let source = source();
+
+        let stopped = source
+            .status(&snapshot(Some(HealthStatus::Unhealthy(
+                "not running".to_owned(),
+            ))))
+            .await;
+        let missing = source.status(&snapshot(None)).await;
+
+        assert!(!stopped.attached);
+        assert!(stopped.health.contains("not running"));
+        assert!(!missing.attached);
+    }
+}
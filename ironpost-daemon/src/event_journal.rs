@@ -0,0 +1,610 @@
+//! Persistent event journal and startup replay.
+//!
+//! With `[persistence]` enabled, a journal relay sits in front of each event
+//! consumer: alerts on their way to container guard (or the alert drain task
+//! when the guard is disabled) and action events on their way to the action
+//! logger. Every event is appended to `<dir>/events.jsonl` before it is
+//! forwarded, and an ack record is appended once the consumer has taken
+//! everything queued so far from its channel.
+//!
+//! On startup the journal is read back. Events without an ack that are newer
+//! than `max_replay_age_secs` are forwarded again by the relays ahead of new
+//! events; log pipeline alerts are also re-sent to the pipeline's alert sinks
+//! and outputs. Delivery is at-least-once: an event a consumer took just
+//! before a crash may be replayed.
+//!
+//! The journal is rewritten with only unacknowledged events on startup and
+//! whenever it grows past `max_journal_bytes`. All file I/O goes through
+//! `tokio::fs`.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex, broadcast, mpsc};
+
+use ironpost_core::config::PersistenceConfig;
+use ironpost_core::event::{ActionEvent, AlertEvent, MODULE_LOG_PIPELINE};
+
+/// Journal file name inside `[persistence] dir`.
+pub const JOURNAL_FILE: &str = "events.jsonl";
+
+/// How often a relay checks whether its consumer has caught up.
+const ACK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A single journal line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournalRecord {
+    /// An alert handed to the alert consumer.
+    Alert {
+        /// The journaled alert.
+        event: AlertEvent,
+    },
+    /// An action event handed to the action logger.
+    Action {
+        /// The journaled action.
+        event: ActionEvent,
+    },
+    /// Events the consumer has taken.
+    Ack {
+        /// IDs of the acknowledged events.
+        ids: Vec<String>,
+    },
+}
+
+impl JournalRecord {
+    /// ID of the journaled event (`None` for acks).
+    fn event_id(&self) -> Option<&str> {
+        match self {
+            Self::Alert { event } => Some(&event.id),
+            Self::Action { event } => Some(&event.id),
+            Self::Ack { .. } => None,
+        }
+    }
+
+    /// When the journaled event happened (`None` for acks).
+    fn timestamp(&self) -> Option<SystemTime> {
+        match self {
+            Self::Alert { event } => Some(event.metadata.timestamp),
+            Self::Action { event } => Some(event.metadata.timestamp),
+            Self::Ack { .. } => None,
+        }
+    }
+}
+
+/// An event type the journal records and replays.
+pub trait JournaledEvent: Clone + Send + 'static {
+    /// Label used in logs.
+    const KIND: &'static str;
+
+    /// Event ID, the ack key.
+    fn id(&self) -> &str;
+
+    /// Journal record holding a copy of the event.
+    fn to_record(&self) -> JournalRecord;
+}
+
+impl JournaledEvent for AlertEvent {
+    const KIND: &'static str = "alert";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn to_record(&self) -> JournalRecord {
+        JournalRecord::Alert {
+            event: self.clone(),
+        }
+    }
+}
+
+impl JournaledEvent for ActionEvent {
+    const KIND: &'static str = "action";
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn to_record(&self) -> JournalRecord {
+        JournalRecord::Action {
+            event: self.clone(),
+        }
+    }
+}
+
+/// Unacknowledged events read back at startup, oldest first.
+#[derive(Debug, Default)]
+pub struct Replay {
+    /// Alerts to hand to the alert consumer again.
+    pub alerts: Vec<AlertEvent>,
+    /// Action events to hand to the action logger again.
+    pub actions: Vec<ActionEvent>,
+}
+
+impl Replay {
+    /// Replayed alerts raised by the log pipeline, which also go to its sinks.
+    pub fn pipeline_alerts(&self) -> Vec<AlertEvent> {
+        self.alerts
+            .iter()
+            .filter(|alert| alert.metadata.source_module == MODULE_LOG_PIPELINE)
+            .cloned()
+            .collect()
+    }
+
+    /// Whether nothing needs replaying.
+    pub fn is_empty(&self) -> bool {
+        self.alerts.is_empty() && self.actions.is_empty()
+    }
+}
+
+/// Append-only event journal shared by the journal relays.
+#[derive(Clone)]
+pub struct EventJournal {
+    inner: Arc<Mutex<JournalFile>>,
+}
+
+struct JournalFile {
+    path: PathBuf,
+    file: tokio::fs::File,
+    size: u64,
+    max_bytes: u64,
+    /// Next sequence number, which keeps unacknowledged events in order.
+    next_seq: u64,
+    /// Unacknowledged events by sequence number.
+    pending: BTreeMap<u64, JournalRecord>,
+    /// Sequence number by event ID.
+    pending_ids: HashMap<String, u64>,
+}
+
+impl EventJournal {
+    /// Open the journal in `config.dir` and read back unacknowledged events.
+    ///
+    /// Unreadable lines (e.g. a write cut short by a crash) are skipped. The
+    /// file is then rewritten with only the events to replay, which stay
+    /// pending until their consumer takes them again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or the journal
+    /// cannot be read or rewritten.
+    pub async fn open(config: &PersistenceConfig) -> io::Result<(Self, Replay)> {
+        let dir = Path::new(&config.dir);
+        tokio::fs::create_dir_all(dir).await?;
+        let path = dir.join(JOURNAL_FILE);
+        let contents = match tokio::fs::read(&path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        let max_age = (config.max_replay_age_secs > 0)
+            .then(|| Duration::from_secs(config.max_replay_age_secs));
+        let records = unacknowledged(&contents, max_age, SystemTime::now());
+
+        let mut replay = Replay::default();
+        for record in &records {
+            match record {
+                JournalRecord::Alert { event } => replay.alerts.push(event.clone()),
+                JournalRecord::Action { event } => replay.actions.push(event.clone()),
+                JournalRecord::Ack { .. } => {}
+            }
+        }
+        if !replay.is_empty() {
+            tracing::info!(
+                path = %path.display(),
+                alerts = replay.alerts.len(),
+                actions = replay.actions.len(),
+                "found unacknowledged events in journal"
+            );
+        }
+
+        let mut journal = JournalFile {
+            file: rewrite(&path, &records).await?,
+            size: 0,
+            path,
+            max_bytes: config.max_journal_bytes,
+            next_seq: 0,
+            pending: BTreeMap::new(),
+            pending_ids: HashMap::new(),
+        };
+        journal.size = journal.file.metadata().await?.len();
+        for record in records {
+            journal.track(record);
+        }
+        Ok((
+            Self {
+                inner: Arc::new(Mutex::new(journal)),
+            },
+            replay,
+        ))
+    }
+
+    /// Append an event. Events already pending are not written again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record cannot be written.
+    pub async fn append(&self, record: JournalRecord) -> io::Result<()> {
+        let mut journal = self.inner.lock().await;
+        if record
+            .event_id()
+            .is_some_and(|id| journal.pending_ids.contains_key(id))
+        {
+            return Ok(());
+        }
+        journal.write(&record).await?;
+        journal.track(record);
+        Ok(())
+    }
+
+    /// Record that the consumer has taken the events with `ids`.
+    ///
+    /// Rewrites the journal once it has grown past `max_journal_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ack cannot be written or the journal cannot be
+    /// rewritten.
+    pub async fn ack(&self, ids: Vec<String>) -> io::Result<()> {
+        let mut journal = self.inner.lock().await;
+        let ids: Vec<String> = ids
+            .into_iter()
+            .filter(|id| journal.pending_ids.contains_key(id))
+            .collect();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        journal
+            .write(&JournalRecord::Ack { ids: ids.clone() })
+            .await?;
+        for id in &ids {
+            if let Some(seq) = journal.pending_ids.remove(id) {
+                journal.pending.remove(&seq);
+            }
+        }
+        if journal.size > journal.max_bytes {
+            journal.compact().await?;
+        }
+        Ok(())
+    }
+
+    /// Number of unacknowledged events.
+    pub async fn pending(&self) -> usize {
+        self.inner.lock().await.pending.len()
+    }
+}
+
+impl JournalFile {
+    fn track(&mut self, record: JournalRecord) {
+        if let Some(id) = record.event_id() {
+            self.pending_ids.insert(id.to_owned(), self.next_seq);
+            self.pending.insert(self.next_seq, record);
+            self.next_seq += 1;
+        }
+    }
+
+    async fn write(&mut self, record: &JournalRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record).map_err(io::Error::other)?;
+        line.push(b'\n');
+        self.file.write_all(&line).await?;
+        self.file.flush().await?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Rewrite the journal with only the pending events.
+    async fn compact(&mut self) -> io::Result<()> {
+        let records: Vec<JournalRecord> = self.pending.values().cloned().collect();
+        self.file = rewrite(&self.path, &records).await?;
+        self.size = self.file.metadata().await?.len();
+        tracing::debug!(
+            path = %self.path.display(),
+            pending = records.len(),
+            bytes = self.size,
+            "compacted event journal"
+        );
+        Ok(())
+    }
+}
+
+/// Events in `contents` without an ack, oldest first, skipping those older
+/// than `max_age`.
+fn unacknowledged(
+    contents: &[u8],
+    max_age: Option<Duration>,
+    now: SystemTime,
+) -> Vec<JournalRecord> {
+    let mut events: Vec<JournalRecord> = Vec::new();
+    let mut acked: HashSet<String> = HashSet::new();
+    let mut skipped = 0usize;
+    for line in contents.split(|byte| *byte == b'\n') {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        match serde_json::from_slice::<JournalRecord>(line) {
+            Ok(JournalRecord::Ack { ids }) => acked.extend(ids),
+            Ok(record) => events.push(record),
+            Err(_) => skipped += 1,
+        }
+    }
+    if skipped > 0 {
+        tracing::warn!(lines = skipped, "skipped unreadable event journal lines");
+    }
+
+    let mut seen: HashSet<String> = HashSet::new();
+    events.retain(|record| {
+        let Some(id) = record.event_id() else {
+            return false;
+        };
+        if acked.contains(id) || !seen.insert(id.to_owned()) {
+            return false;
+        }
+        match (max_age, record.timestamp()) {
+            (Some(max_age), Some(at)) => now.duration_since(at).unwrap_or_default() <= max_age,
+            _ => true,
+        }
+    });
+    events
+}
+
+/// Replace the journal at `path` with `records` and open it for appending.
+async fn rewrite(path: &Path, records: &[JournalRecord]) -> io::Result<tokio::fs::File> {
+    let mut contents = Vec::new();
+    for record in records {
+        serde_json::to_writer(&mut contents, record).map_err(io::Error::other)?;
+        contents.push(b'\n');
+    }
+    let tmp = path.with_extension("jsonl.tmp");
+    tokio::fs::write(&tmp, &contents).await?;
+    tokio::fs::rename(&tmp, path).await?;
+    tokio::fs::OpenOptions::new().append(true).open(path).await
+}
+
+/// Relay events from `rx` to the consumer behind `tx`, journaling each one.
+///
+/// `replay` is forwarded first. Forwarded events are acknowledged whenever
+/// the consumer has emptied its channel. Runs until the input closes, the
+/// consumer goes away or shutdown is signalled; the shutdown broadcast is
+/// only sent after consumers have stopped, so a final ack covers events the
+/// consumer took during the drain phase.
+pub fn spawn_journal_relay<T: JournaledEvent>(
+    journal: EventJournal,
+    replay: Vec<T>,
+    mut rx: mpsc::Receiver<T>,
+    tx: mpsc::Sender<T>,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut unacked = Vec::new();
+        if !replay.is_empty() {
+            tracing::info!(
+                kind = T::KIND,
+                events = replay.len(),
+                "replaying unacknowledged events"
+            );
+        }
+        for event in replay {
+            if !forward(&journal, &tx, event, &mut unacked).await {
+                return;
+            }
+        }
+
+        let mut tick = tokio::time::interval(ACK_INTERVAL);
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                received = rx.recv() => {
+                    let Some(event) = received else {
+                        tracing::debug!(kind = T::KIND, "event channel closed, exiting journal relay");
+                        break;
+                    };
+                    if !forward(&journal, &tx, event, &mut unacked).await {
+                        break;
+                    }
+                }
+                _ = tick.tick() => acknowledge(&journal, &tx, &mut unacked).await,
+                _ = shutdown_rx.recv() => {
+                    tracing::debug!(kind = T::KIND, "journal relay shutting down");
+                    break;
+                }
+            }
+        }
+        acknowledge(&journal, &tx, &mut unacked).await;
+        if !unacked.is_empty() {
+            let pending = journal.pending().await;
+            tracing::info!(
+                kind = T::KIND,
+                events = unacked.len(),
+                pending = pending,
+                "events left unacknowledged, will replay on next start"
+            );
+        }
+    })
+}
+
+/// Journal and forward one event. Returns `false` once the consumer is gone.
+async fn forward<T: JournaledEvent>(
+    journal: &EventJournal,
+    tx: &mpsc::Sender<T>,
+    event: T,
+    unacked: &mut Vec<String>,
+) -> bool {
+    match journal.append(event.to_record()).await {
+        Ok(()) => unacked.push(event.id().to_owned()),
+        Err(e) => {
+            tracing::warn!(kind = T::KIND, event_id = %event.id(), error = %e, "failed to journal event");
+        }
+    }
+    if tx.send(event).await.is_err() {
+        tracing::debug!(
+            kind = T::KIND,
+            "event consumer closed, exiting journal relay"
+        );
+        return false;
+    }
+    acknowledge(journal, tx, unacked).await;
+    true
+}
+
+/// Acknowledge forwarded events once the consumer has taken all of them.
+async fn acknowledge<T>(journal: &EventJournal, tx: &mpsc::Sender<T>, unacked: &mut Vec<String>) {
+    if unacked.is_empty() || tx.is_closed() || tx.capacity() < tx.max_capacity() {
+        return;
+    }
+    if let Err(e) = journal.ack(std::mem::take(unacked)).await {
+        tracing::warn!(error = %e, "failed to acknowledge journaled events");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_core::event::MODULE_SBOM_SCANNER;
+    use ironpost_core::types::{Alert, Severity};
+
+    fn config(dir: &Path) -> PersistenceConfig {
+        PersistenceConfig {
+            enabled: true,
+            dir: dir.display().to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn alert(id: &str, source_module: &'static str) -> AlertEvent {
+        let alert = Alert {
+            id: id.to_owned(),
+            title: id.to_owned(),
+            description: String::new(),
+            severity: Severity::High,
+            rule_name: "ssh_fail".to_owned(),
+            source_ip: None,
+            target_ip: None,
+            created_at: SystemTime::now(),
+        };
+        let mut event = AlertEvent::with_source(alert, Severity::High, source_module);
+        event.id = id.to_owned();
+        event
+    }
+
+    #[tokio::test]
+    async fn test_unacknowledged_events_are_replayed_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let (journal, replay) = EventJournal::open(&config(dir.path())).await.unwrap();
+        assert!(replay.is_empty());
+
+        // Given: Two alerts and an action journaled, one alert acknowledged
+        journal
+            .append(alert("a1", MODULE_LOG_PIPELINE).to_record())
+            .await
+            .unwrap();
+        journal
+            .append(alert("a2", MODULE_SBOM_SCANNER).to_record())
+            .await
+            .unwrap();
+        let mut action = ActionEvent::new("container_pause", "web", true);
+        action.id = "x1".to_owned();
+        journal.append(action.to_record()).await.unwrap();
+        journal.ack(vec!["a1".to_owned()]).await.unwrap();
+        drop(journal);
+
+        // When: The daemon restarts
+        let (journal, replay) = EventJournal::open(&config(dir.path())).await.unwrap();
+
+        // Then: Only the unacknowledged events come back, and stay pending
+        let alerts: Vec<&str> = replay.alerts.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(alerts, ["a2"]);
+        assert_eq!(replay.actions.len(), 1);
+        assert!(replay.pipeline_alerts().is_empty());
+        assert_eq!(journal.pending().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_old_and_corrupt_entries_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut old = alert("old", MODULE_LOG_PIPELINE);
+        old.metadata.timestamp = SystemTime::now() - Duration::from_secs(7200);
+        let mut contents = serde_json::to_string(&old.to_record()).unwrap();
+        contents.push('\n');
+        contents.push_str(
+            &serde_json::to_string(&alert("new", MODULE_LOG_PIPELINE).to_record()).unwrap(),
+        );
+        contents.push_str("\n{\"type\":\"alert\",\"ev");
+        std::fs::write(dir.path().join(JOURNAL_FILE), contents).unwrap();
+
+        let (_, replay) = EventJournal::open(&config(dir.path())).await.unwrap();
+        let alerts: Vec<&str> = replay.alerts.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(alerts, ["new"]);
+        assert_eq!(replay.pipeline_alerts().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_journal_is_compacted_past_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = PersistenceConfig {
+            max_journal_bytes: 4096,
+            ..config(dir.path())
+        };
+        let (journal, _) = EventJournal::open(&config).await.unwrap();
+        for i in 0..50 {
+            let id = format!("a{i}");
+            journal
+                .append(alert(&id, MODULE_LOG_PIPELINE).to_record())
+                .await
+                .unwrap();
+            journal.ack(vec![id]).await.unwrap();
+        }
+        journal
+            .append(alert("kept", MODULE_LOG_PIPELINE).to_record())
+            .await
+            .unwrap();
+
+        let size = std::fs::metadata(dir.path().join(JOURNAL_FILE))
+            .unwrap()
+            .len();
+        assert!(size < 4096, "journal was not compacted: {size} bytes");
+        drop(journal);
+        let (_, replay) = EventJournal::open(&config).await.unwrap();
+        assert_eq!(replay.alerts.len(), 1);
+        assert_eq!(replay.alerts[0].id, "kept");
+    }
+
+    #[tokio::test]
+    async fn test_relay_replays_first_and_acks_consumed_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let (journal, _) = EventJournal::open(&config(dir.path())).await.unwrap();
+        let (in_tx, in_rx) = mpsc::channel(4);
+        let (out_tx, mut out_rx) = mpsc::channel(4);
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let task = spawn_journal_relay(
+            journal.clone(),
+            vec![alert("replayed", MODULE_LOG_PIPELINE)],
+            in_rx,
+            out_tx,
+            shutdown_rx,
+        );
+
+        in_tx
+            .send(alert("live", MODULE_LOG_PIPELINE))
+            .await
+            .unwrap();
+        assert_eq!(out_rx.recv().await.unwrap().id, "replayed");
+        assert_eq!(out_rx.recv().await.unwrap().id, "live");
+
+        // The consumer has emptied the channel, so the next tick acks both
+        for _ in 0..30 {
+            if journal.pending().await == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(journal.pending().await, 0);
+
+        shutdown_tx.send(()).unwrap();
+        task.await.unwrap();
+    }
+}
@@ -9,10 +9,30 @@
 //! - All Healthy -> Healthy
 //! - Any Degraded, none Unhealthy -> Degraded(reason)
 //! - Any Unhealthy -> Unhealthy(reason)
+//!
+//! # Liveness vs Readiness
+//!
+//! - **Liveness** (`/healthz`): the daemon process is alive and its main loop
+//!   keeps refreshing health snapshots. Module failures do not affect liveness
+//!   unless `health.liveness_fail_on_unhealthy` is set.
+//! - **Readiness** (`/readyz`): the required modules are running and healthy.
+//!   Modules listed in `health.readiness_tolerate_degraded` may be degraded.
+//!
+//! # Health Report
+//!
+//! [`HealthReport`] is the structured form served by `/health` and shown by
+//! `ironpost status`: daemon version and uptime, and per module a
+//! [`ReasonCode`], the time of its last state change and how often the
+//! supervisor restarted it. [`HealthTracker`] keeps the history between checks.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::Serialize;
 
+use ironpost_core::config::HealthConfig;
 use ironpost_core::pipeline::HealthStatus;
+use ironpost_core::plugin::PluginState;
 
 /// Aggregated health report for the entire daemon.
 #[derive(Debug, Clone, Serialize)]
@@ -38,6 +58,201 @@ pub struct ModuleHealth {
     pub status: HealthStatus,
 }
 
+/// Machine-readable reason for a module's state in a [`HealthReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasonCode {
+    /// Running and healthy.
+    Ok,
+    /// Running but degraded (see `reason`).
+    Degraded,
+    /// Running but unhealthy (see `reason`).
+    Unhealthy,
+    /// Taken out of service after a crash loop.
+    Quarantined,
+    /// Crashed; the supervisor restarts it on the next health check.
+    Failed,
+    /// Not started yet, or stopped.
+    NotRunning,
+}
+
+impl ReasonCode {
+    /// Classify a module from its plugin state and health check result.
+    pub fn classify(state: PluginState, status: &HealthStatus, quarantined: bool) -> Self {
+        if quarantined {
+            return Self::Quarantined;
+        }
+        match state {
+            PluginState::Failed => Self::Failed,
+            PluginState::Running => match status {
+                HealthStatus::Healthy => Self::Ok,
+                HealthStatus::Degraded(_) => Self::Degraded,
+                HealthStatus::Unhealthy(_) => Self::Unhealthy,
+            },
+            PluginState::Created | PluginState::Initialized | PluginState::Stopped => {
+                Self::NotRunning
+            }
+        }
+    }
+}
+
+/// Structured daemon health served by `/health` and `ironpost status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// Daemon version.
+    pub version: String,
+    /// Overall daemon health status (worst of all modules).
+    pub status: HealthStatus,
+    /// Daemon start time (Unix seconds).
+    pub started_at: u64,
+    /// Daemon uptime in seconds.
+    pub uptime_secs: u64,
+    /// When this report was generated (Unix seconds).
+    pub generated_at: u64,
+    /// Per-module reports, in start order.
+    pub modules: Vec<ModuleReport>,
+}
+
+/// Health of a single module in a [`HealthReport`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleReport {
+    /// Module name (e.g., "ebpf-engine", "log-pipeline").
+    pub name: String,
+    /// Plugin lifecycle state (e.g., "running", "failed").
+    pub state: String,
+    /// Health check result.
+    pub status: HealthStatus,
+    /// Machine-readable reason for the current state.
+    pub reason_code: ReasonCode,
+    /// Human-readable reason (health check or quarantine message).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// When the reason code last changed (Unix seconds).
+    pub since: u64,
+    /// Number of supervisor restarts since the daemon started.
+    pub restarts: u32,
+}
+
+/// Observation of one module, fed to [`HealthTracker::report`].
+#[derive(Debug, Clone)]
+pub struct ModuleObservation {
+    /// Module name.
+    pub name: String,
+    /// Plugin lifecycle state.
+    pub state: PluginState,
+    /// Health check result.
+    pub status: HealthStatus,
+    /// Quarantine reason, if the module is quarantined.
+    pub quarantine_reason: Option<String>,
+}
+
+/// Per-module state history used to build [`HealthReport`]s.
+#[derive(Debug)]
+pub struct HealthTracker {
+    /// Daemon start time.
+    started_at: SystemTime,
+    /// Last reason code, when it was first seen, and restart count.
+    modules: HashMap<String, TrackedModule>,
+}
+
+#[derive(Debug)]
+struct TrackedModule {
+    code: Option<ReasonCode>,
+    since: SystemTime,
+    restarts: u32,
+}
+
+impl HealthTracker {
+    /// Create a tracker for a daemon started at `started_at`.
+    pub fn new(started_at: SystemTime) -> Self {
+        Self {
+            started_at,
+            modules: HashMap::new(),
+        }
+    }
+
+    /// Count a supervisor restart of `name`.
+    pub fn record_restart(&mut self, name: &str) {
+        let started_at = self.started_at;
+        self.modules
+            .entry(name.to_owned())
+            .or_insert_with(|| TrackedModule {
+                code: None,
+                since: started_at,
+                restarts: 0,
+            })
+            .restarts += 1;
+    }
+
+    /// Build a report from the current observations at `now`.
+    ///
+    /// A module's `since` moves to `now` whenever its reason code differs
+    /// from the previous report; the first report uses the daemon start time.
+    pub fn report(
+        &mut self,
+        status: HealthStatus,
+        observations: Vec<ModuleObservation>,
+        now: SystemTime,
+    ) -> HealthReport {
+        let started_at = self.started_at;
+        let modules = observations
+            .into_iter()
+            .map(|obs| {
+                let quarantined = obs.quarantine_reason.is_some();
+                let reason_code = ReasonCode::classify(obs.state, &obs.status, quarantined);
+                let tracked =
+                    self.modules
+                        .entry(obs.name.clone())
+                        .or_insert_with(|| TrackedModule {
+                            code: None,
+                            since: started_at,
+                            restarts: 0,
+                        });
+                match tracked.code {
+                    Some(previous) if previous != reason_code => tracked.since = now,
+                    _ => {}
+                }
+                tracked.code = Some(reason_code);
+
+                let reason = match (&obs.quarantine_reason, &obs.status) {
+                    (Some(reason), _) => Some(reason.clone()),
+                    (None, HealthStatus::Degraded(reason) | HealthStatus::Unhealthy(reason)) => {
+                        Some(reason.clone())
+                    }
+                    (None, HealthStatus::Healthy) => None,
+                };
+                ModuleReport {
+                    state: obs.state.to_string(),
+                    name: obs.name,
+                    status: obs.status,
+                    reason_code,
+                    reason,
+                    since: unix_secs(tracked.since),
+                    restarts: tracked.restarts,
+                }
+            })
+            .collect();
+
+        HealthReport {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            status,
+            started_at: unix_secs(started_at),
+            uptime_secs: now
+                .duration_since(started_at)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            generated_at: unix_secs(now),
+            modules,
+        }
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
 /// Aggregate multiple module health statuses into a single status.
 ///
 /// Returns the worst status found: Unhealthy > Degraded > Healthy.
@@ -72,6 +287,98 @@ pub fn aggregate_status(modules: &[ModuleHealth]) -> HealthStatus {
     }
 }
 
+/// Result of a liveness or readiness probe evaluation.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProbeResult {
+    /// Whether the probe passed.
+    pub ok: bool,
+    /// Reasons the probe failed (empty when `ok` is true).
+    pub reasons: Vec<String>,
+}
+
+impl ProbeResult {
+    fn from_reasons(reasons: Vec<String>) -> Self {
+        Self {
+            ok: reasons.is_empty(),
+            reasons,
+        }
+    }
+}
+
+/// Evaluate daemon liveness.
+///
+/// A stale health snapshot (older than `max_staleness_secs`) means the main
+/// loop has stopped making progress, which fails liveness regardless of
+/// module state. Unhealthy modules only fail liveness when the policy
+/// enables `liveness_fail_on_unhealthy`.
+pub fn evaluate_liveness(
+    health: &DaemonHealth,
+    snapshot_age_secs: u64,
+    max_staleness_secs: u64,
+    policy: &HealthConfig,
+) -> ProbeResult {
+    let mut reasons = Vec::new();
+
+    if snapshot_age_secs > max_staleness_secs {
+        reasons.push(format!(
+            "health snapshot is stale ({}s old, max {}s)",
+            snapshot_age_secs, max_staleness_secs
+        ));
+    }
+
+    if policy.liveness_fail_on_unhealthy {
+        for module in health.modules.iter().filter(|m| m.enabled) {
+            if let HealthStatus::Unhealthy(reason) = &module.status {
+                reasons.push(format!("{}: {}", module.name, reason));
+            }
+        }
+    }
+
+    ProbeResult::from_reasons(reasons)
+}
+
+/// Evaluate daemon readiness against the configured policy.
+///
+/// - If `readiness_required` is empty, every enabled module is required.
+/// - A required module that is not registered fails readiness.
+/// - A required module that is unhealthy fails readiness.
+/// - A required module that is degraded fails readiness unless it is listed
+///   in `readiness_tolerate_degraded`.
+/// - Modules that are not required never affect readiness.
+pub fn evaluate_readiness(modules: &[ModuleHealth], policy: &HealthConfig) -> ProbeResult {
+    let mut reasons = Vec::new();
+
+    for required in &policy.readiness_required {
+        if !modules.iter().any(|m| m.enabled && &m.name == required) {
+            reasons.push(format!("{}: not running", required));
+        }
+    }
+
+    let is_required = |name: &str| {
+        policy.readiness_required.is_empty() || policy.readiness_required.iter().any(|r| r == name)
+    };
+
+    for module in modules.iter().filter(|m| m.enabled && is_required(&m.name)) {
+        match &module.status {
+            HealthStatus::Healthy => {}
+            HealthStatus::Degraded(reason) => {
+                let tolerated = policy
+                    .readiness_tolerate_degraded
+                    .iter()
+                    .any(|t| t == &module.name);
+                if !tolerated {
+                    reasons.push(format!("{}: degraded: {}", module.name, reason));
+                }
+            }
+            HealthStatus::Unhealthy(reason) => {
+                reasons.push(format!("{}: unhealthy: {}", module.name, reason));
+            }
+        }
+    }
+
+    ProbeResult::from_reasons(reasons)
+}
+
 /// Spawn a background task that periodically checks module health
 /// and logs the aggregated result.
 ///
@@ -0,0 +1,908 @@
+//! Liveness and readiness HTTP endpoints.
+//!
+//! Serves probe endpoints on a dedicated listener (`[health]` section):
+//!
+//! - `GET /healthz` -- liveness (200 when alive, 503 otherwise)
+//! - `GET /readyz` -- readiness (200 when ready, 503 otherwise)
+//! - `GET /status` -- full status: module health, scheduled tasks, resource
+//!   usage and distributed content version (always 200)
+//! - `GET /ebpf` -- eBPF attach state and traffic counters
+//!   (see [`crate::ebpf_status`])
+//! - `GET|POST /ebpf/blocklist`, `POST /ebpf/blocklist/remove` -- runtime
+//!   blocklist management (Linux only, see `crate::ebpf_blocklist`)
+//! - `POST /ebpf/rules/import` -- bulk filter rule import with a single map
+//!   sync (Linux only, see `crate::ebpf_blocklist`)
+//! - `GET /rules` -- loaded detection rules with match counts
+//!   (see [`crate::rules_status`])
+//! - `POST /logs/search` -- search the built-in log store
+//!   (see [`crate::log_search`])
+//! - `GET /alerts?since=<seq>` -- recent alerts (see [`crate::alert_feed`])
+//! - `GET /container/events?since=<seq>` -- recent container guard activity
+//!   (see [`crate::container_watch`])
+//! - `POST /container/isolate`, `POST /container/release` -- manual container
+//!   isolation (see [`crate::container_isolation`])
+//! - `POST /container/policies/test` -- evaluate a sample alert against the
+//!   live container policies without isolating anything
+//!   (see [`crate::policy_simulation`])
+//! - `GET /modules/quarantined`, `POST /modules/unquarantine` -- crash-loop
+//!   quarantine management (see [`crate::daemon_control`])
+//! - `POST /content/rollback` -- roll distributed rules/policies back to a
+//!   stored version (see [`crate::daemon_control`])
+//! - `GET /config` -- effective configuration with secrets redacted, as
+//!   JSON (used by `ironpost config diff --running`)
+//! - `GET /support-bundle` -- support bundle tarball for bug reports
+//!   (see [`crate::support_bundle`])
+//!
+//! With `unix_socket` set, the same endpoints are also served on a unix
+//! socket for local CLI use (see [`bind_control_socket`]).
+//!
+//! Probes are always unauthenticated. With `[auth]` enabled, every other
+//! endpoint requires a bearer token with at least the role below (see
+//! [`required_role`]), and the listener can serve TLS or mTLS (see
+//! [`crate::auth`]):
+//!
+//! - `read-only` -- `/status`, `/health`, `/ebpf`, blocklist reads, `/rules`,
+//!   `/logs/search`, `/alerts`, `/container/events`, policy simulation,
+//!   `/modules/quarantined`
+//! - `operator` -- blocklist changes, rule imports, container isolation and
+//!   release, `/modules/unquarantine`, `/content/rollback`
+//! - `admin` -- `/config`, `/support-bundle`
+//!
+//! Without `[auth]`, state-changing requests (the `operator` routes, see
+//! [`is_mutating`]) are only served on the unix socket and get 403 over TCP.
+//!
+//! The orchestrator periodically publishes a [`HealthSnapshot`] through a
+//! `watch` channel; the server only reads the latest snapshot, so probe
+//! requests never block on module health checks.
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::Arc;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::{Mutex, broadcast, watch};
+use tokio_rustls::TlsAcceptor;
+
+use ironpost_container_guard::{BollardDockerClient, IsolationControl, PolicySimulator};
+use ironpost_core::config::HealthConfig;
+use ironpost_log_pipeline::{LogStore, RuleEngine};
+
+use crate::alert_feed::{ALERTS_PATH, AlertFeed};
+use crate::auth::{Authorizer, Identity, Role};
+use crate::container_isolation::{
+    CONTAINER_ISOLATE_PATH, CONTAINER_RELEASE_PATH, handle_isolation_request,
+};
+use crate::container_watch::{CONTAINER_EVENTS_PATH, ContainerWatch, parse_since};
+use crate::content::ContentStatus;
+use crate::daemon_control::{
+    CONTENT_ROLLBACK_PATH, MODULES_QUARANTINED_PATH, MODULES_UNQUARANTINE_PATH, OrchestratorHandle,
+    handle_module_request, handle_rollback_request,
+};
+use crate::ebpf_status::{
+    EBPF_BLOCKLIST_PATH, EBPF_BLOCKLIST_REMOVE_PATH, EBPF_RULES_IMPORT_PATH, EBPF_STATUS_PATH,
+    EbpfSource,
+};
+use crate::health::{
+    DaemonHealth, HealthReport, ModuleHealth, ProbeResult, evaluate_liveness, evaluate_readiness,
+};
+use crate::log_search::{LOGS_SEARCH_PATH, handle_log_search_request};
+use crate::policy_simulation::{CONTAINER_POLICY_TEST_PATH, handle_policy_test_request};
+use crate::resources::ResourceReport;
+use crate::rules_status::{RULES_PATH, rules_status};
+use crate::scheduler::TaskStatus;
+use crate::support_bundle::{BundleSources, redact_config};
+
+/// Path of the structured health report.
+pub const HEALTH_REPORT_PATH: &str = "/health";
+
+/// Path of the support bundle export.
+pub const SUPPORT_BUNDLE_PATH: &str = "/support-bundle";
+
+/// Path of the effective configuration export.
+pub const CONFIG_PATH: &str = "/config";
+
+/// Body of the 403 returned for state-changing requests over TCP without `[auth]`.
+const LOCAL_ONLY_BODY: &str = r#"{"error":"state-changing requests over TCP require [auth]; enable [auth] or use the control unix socket"}"#;
+
+/// Maximum size of the request line and headers.
+const MAX_REQUEST_BYTES: usize = 1024;
+
+/// Maximum request body accepted by control endpoints (blocklist imports).
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Timeout for reading a probe request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A snapshot is considered stale after this many missed check intervals.
+const STALENESS_INTERVALS: u64 = 3;
+
+/// Latest aggregated health, stamped with the time it was collected.
+#[derive(Debug, Clone)]
+pub struct HealthSnapshot {
+    /// Aggregated daemon health.
+    pub health: DaemonHealth,
+    /// Structured health report (`/health`, `None` until the orchestrator
+    /// attaches one).
+    pub report: Option<HealthReport>,
+    /// When the snapshot was collected.
+    pub checked_at: Instant,
+    /// Scheduled task status at collection time.
+    pub tasks: Vec<TaskStatus>,
+    /// Resource usage at collection time.
+    pub resources: ResourceReport,
+    /// Rule/policy content status (`None` when `[content]` is disabled).
+    pub content: Option<ContentStatus>,
+}
+
+impl HealthSnapshot {
+    /// Create a snapshot collected now.
+    pub fn new(health: DaemonHealth) -> Self {
+        Self {
+            health,
+            report: None,
+            checked_at: Instant::now(),
+            tasks: Vec::new(),
+            resources: ResourceReport::default(),
+            content: None,
+        }
+    }
+
+    /// Attach the structured health report.
+    pub fn with_report(mut self, report: HealthReport) -> Self {
+        self.report = Some(report);
+        self
+    }
+
+    /// Attach scheduled task status.
+    pub fn with_tasks(mut self, tasks: Vec<TaskStatus>) -> Self {
+        self.tasks = tasks;
+        self
+    }
+
+    /// Attach resource usage.
+    pub fn with_resources(mut self, resources: ResourceReport) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    /// Attach rule/policy content status.
+    pub fn with_content(mut self, content: Option<ContentStatus>) -> Self {
+        self.content = content;
+        self
+    }
+}
+
+/// Live state served by control endpoints outside the health snapshot.
+#[derive(Clone, Default)]
+pub struct ControlSources {
+    /// Support bundle sources (`/support-bundle`, `/config`).
+    pub bundle: Option<BundleSources>,
+    /// eBPF engine status (`/ebpf`, `None` when the engine is disabled).
+    pub ebpf: Option<EbpfSource>,
+    /// Log pipeline rule engine (`/rules`, `None` when the pipeline is disabled).
+    pub rules: Option<Arc<Mutex<RuleEngine>>>,
+    /// Built-in log store (`/logs/search`, `None` unless
+    /// `[log_pipeline.log_store]` is enabled).
+    pub logs: Option<Arc<LogStore>>,
+    /// Recent alert feed (`/alerts`).
+    pub alerts: Option<AlertFeed>,
+    /// Container guard event feed (`/container/events`, `None` when the
+    /// guard is disabled).
+    pub container: Option<ContainerWatch>,
+    /// Manual container isolation (`/container/isolate`, `/container/release`,
+    /// `None` when the guard is disabled).
+    pub isolation: Option<IsolationControl<BollardDockerClient>>,
+    /// Container policy simulation (`/container/policies/test`, `None` when
+    /// the guard is disabled).
+    pub policy_test: Option<PolicySimulator<BollardDockerClient>>,
+    /// Orchestrator command channel (`/modules/quarantined`,
+    /// `/modules/unquarantine`, `/content/rollback`).
+    pub orchestrator: Option<OrchestratorHandle>,
+}
+
+/// JSON body returned by the probe endpoints.
+#[derive(Debug, Serialize)]
+struct ProbeResponse<'a> {
+    status: &'static str,
+    uptime_secs: u64,
+    reasons: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modules: Option<&'a [ModuleHealth]>,
+}
+
+/// JSON body returned by `/status`.
+#[derive(Debug, Serialize)]
+struct StatusResponse<'a> {
+    #[serde(flatten)]
+    health: &'a DaemonHealth,
+    tasks: &'a [TaskStatus],
+    resources: &'a ResourceReport,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<&'a ContentStatus>,
+}
+
+/// Bind the health endpoint listener.
+///
+/// # Errors
+///
+/// Returns an error if the address is invalid or the socket cannot be bound.
+pub async fn bind_health_listener(config: &HealthConfig) -> Result<TcpListener> {
+    let addr: SocketAddr = format!("{}:{}", config.listen_addr, config.port)
+        .parse()
+        .map_err(|e| anyhow::anyhow!("invalid health listen address: {}", e))?;
+
+    if addr.ip().is_unspecified() {
+        tracing::warn!(
+            listen_addr = %addr,
+            "health endpoint is exposed on all interfaces; restrict listen_addr in untrusted networks"
+        );
+    }
+
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to bind health endpoint {}: {}", addr, e))?;
+
+    tracing::info!(listen_addr = %addr, "health endpoints active (/healthz, /readyz, /health, /status)");
+    Ok(listener)
+}
+
+/// Bind the control API unix socket at `path` (`health.unix_socket`).
+///
+/// A stale socket left by a previous run is replaced; any other file at
+/// `path` is an error. The socket is made accessible to the daemon's user and
+/// group only (`0660`). `[auth]` applies as on the TCP listener.
+///
+/// # Errors
+///
+/// Returns an error if the path is taken by a non-socket file or the socket
+/// cannot be bound.
+#[cfg(unix)]
+pub async fn bind_control_socket(path: &str) -> Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    match tokio::fs::symlink_metadata(path).await {
+        Ok(meta) if meta.file_type().is_socket() => tokio::fs::remove_file(path)
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to remove stale socket {}: {}", path, e))?,
+        Ok(_) => anyhow::bail!("{} exists and is not a socket", path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => anyhow::bail!("failed to inspect {}: {}", path, e),
+    }
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let listener = tokio::net::UnixListener::bind(path)
+        .map_err(|e| anyhow::anyhow!("failed to bind control socket {}: {}", path, e))?;
+    tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o660)).await?;
+
+    tracing::info!(path, "control API socket active");
+    Ok(listener)
+}
+
+/// Spawn the control API server for the unix socket.
+///
+/// Serves the same endpoints as [`spawn_health_server`], without TLS.
+#[cfg(unix)]
+pub fn spawn_socket_server(
+    listener: tokio::net::UnixListener,
+    policy: HealthConfig,
+    auth: Arc<Authorizer>,
+    control: ControlSources,
+    snapshot_rx: watch::Receiver<HealthSnapshot>,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    match accepted {
+                        Ok((stream, _)) => {
+                            let policy = policy.clone();
+                            let auth = Arc::clone(&auth);
+                            let control = control.clone();
+                            let snapshot = snapshot_rx.borrow().clone();
+                            tokio::spawn(async move {
+                                if let Err(e) = handle_connection(stream, true, &snapshot, &policy, &auth, &control).await {
+                                    tracing::debug!(error = %e, "control socket connection failed");
+                                }
+                            });
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, "failed to accept control socket connection");
+                        }
+                    }
+                }
+                _ = shutdown_rx.recv() => {
+                    tracing::debug!("control socket server shutting down");
+                    break;
+                }
+            }
+        }
+        if let Ok(addr) = listener.local_addr()
+            && let Some(path) = addr.as_pathname()
+        {
+            let _ = std::fs::remove_file(path);
+        }
+    })
+}
+
+/// Spawn the health endpoint server.
+///
+/// Each connection is handled in its own task. With a TLS acceptor, the
+/// handshake (including client certificate verification for mTLS) runs
+/// before the request is read. `/support-bundle`, `/ebpf` and `/rules` are
+/// only served when the matching `control` source is set. The server exits
+/// when the shutdown broadcast is received.
+pub fn spawn_health_server(
+    listener: TcpListener,
+    policy: HealthConfig,
+    auth: Arc<Authorizer>,
+    tls: Option<TlsAcceptor>,
+    control: ControlSources,
+    snapshot_rx: watch::Receiver<HealthSnapshot>,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    match accepted {
+                        Ok((stream, peer)) => {
+                            let policy = policy.clone();
+                            let auth = Arc::clone(&auth);
+                            let tls = tls.clone();
+                            let control = control.clone();
+                            let snapshot = snapshot_rx.borrow().clone();
+                            tokio::spawn(async move {
+                                let result = match tls {
+                                    Some(acceptor) => {
+                                        match tokio::time::timeout(READ_TIMEOUT, acceptor.accept(stream)).await {
+                                            Ok(Ok(stream)) => {
+                                                handle_connection(stream, false, &snapshot, &policy, &auth, &control).await
+                                            }
+                                            Ok(Err(e)) => Err(e),
+                                            Err(_) => Err(std::io::Error::new(
+                                                std::io::ErrorKind::TimedOut,
+                                                "TLS handshake timed out",
+                                            )),
+                                        }
+                                    }
+                                    None => handle_connection(stream, false, &snapshot, &policy, &auth, &control).await,
+                                };
+                                if let Err(e) = result {
+                                    tracing::debug!(peer = %peer, error = %e, "health probe connection failed");
+                                }
+                            });
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, "failed to accept health probe connection");
+                        }
+                    }
+                }
+                _ = shutdown_rx.recv() => {
+                    tracing::debug!("health server shutting down");
+                    break;
+                }
+            }
+        }
+    })
+}
+
+/// Read a single request, route it, and write the response.
+///
+/// `local` is set for the unix control socket, whose file permissions stand
+/// in for authentication: without `[auth]`, state-changing requests are only
+/// accepted there.
+async fn handle_connection<S>(
+    mut stream: S,
+    local: bool,
+    snapshot: &HealthSnapshot,
+    policy: &HealthConfig,
+    auth: &Authorizer,
+    control: &ControlSources,
+) -> std::io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let read = tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream))
+        .await
+        .map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::TimedOut, "request read timed out")
+        })??;
+    let Some((request, body)) = read else {
+        return write_json(&mut stream, 413, r#"{"error":"request body too large"}"#).await;
+    };
+
+    let request_line = request.lines().next().unwrap_or_default();
+    let path = request_path(request_line);
+    let method = request_line.split_whitespace().next().unwrap_or_default();
+    if !local && !auth.is_enabled() && is_mutating(method, path) {
+        tracing::warn!(
+            path = path,
+            "state-changing control request over TCP denied: [auth] is disabled"
+        );
+        return write_json(&mut stream, 403, LOCAL_ONLY_BODY).await;
+    }
+    #[cfg(target_os = "linux")]
+    if let Some(source) = &control.ebpf
+        && (path == EBPF_BLOCKLIST_PATH
+            || path == EBPF_BLOCKLIST_REMOVE_PATH
+            || path == EBPF_RULES_IMPORT_PATH)
+    {
+        let required = if method == "GET" {
+            Role::ReadOnly
+        } else {
+            Role::Operator
+        };
+        let (status, body) = match authorize(&request, path, required, auth) {
+            Ok(identity) => {
+                crate::ebpf_blocklist::handle_blocklist_request(
+                    source.blocklist(),
+                    method,
+                    path,
+                    &body,
+                    identity.as_ref(),
+                )
+                .await
+            }
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(control) = &control.isolation
+        && (path == CONTAINER_ISOLATE_PATH || path == CONTAINER_RELEASE_PATH)
+    {
+        let (status, body) = match authorize(&request, path, Role::Operator, auth) {
+            Ok(identity) => {
+                handle_isolation_request(control, method, path, &body, identity.as_ref()).await
+            }
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(orchestrator) = &control.orchestrator
+        && (path == MODULES_QUARANTINED_PATH || path == MODULES_UNQUARANTINE_PATH)
+    {
+        let required = if path == MODULES_QUARANTINED_PATH {
+            Role::ReadOnly
+        } else {
+            Role::Operator
+        };
+        let (status, body) = match authorize(&request, path, required, auth) {
+            Ok(identity) => {
+                handle_module_request(orchestrator, method, path, &body, identity.as_ref()).await
+            }
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(orchestrator) = &control.orchestrator
+        && path == CONTENT_ROLLBACK_PATH
+    {
+        let (status, body) = match authorize(&request, path, Role::Operator, auth) {
+            Ok(identity) => {
+                handle_rollback_request(orchestrator, method, &body, identity.as_ref()).await
+            }
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(simulator) = &control.policy_test
+        && path == CONTAINER_POLICY_TEST_PATH
+    {
+        let (status, body) = match authorize(&request, path, Role::ReadOnly, auth) {
+            Ok(_) => handle_policy_test_request(simulator, method, &body).await,
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(source) = &control.ebpf
+        && path == EBPF_STATUS_PATH
+    {
+        let (status, body) = match authorize_get(&request, path, Role::ReadOnly, auth) {
+            Ok(_) => {
+                let status = source.status(snapshot).await;
+                let body = serde_json::to_string(&status)
+                    .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+                (200, body)
+            }
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(engine) = &control.rules
+        && path == RULES_PATH
+    {
+        let (status, body) = match authorize_get(&request, path, Role::ReadOnly, auth) {
+            Ok(_) => {
+                let status = rules_status(engine).await;
+                let body = serde_json::to_string(&status)
+                    .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+                (200, body)
+            }
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(store) = &control.logs
+        && path == LOGS_SEARCH_PATH
+    {
+        let (status, body) = match authorize(&request, path, Role::ReadOnly, auth) {
+            Ok(_) => handle_log_search_request(store, method, &body).await,
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(feed) = &control.alerts
+        && path == ALERTS_PATH
+    {
+        let (status, body) = match authorize_get(&request, path, Role::ReadOnly, auth) {
+            Ok(_) => {
+                let page = feed.since(parse_since(request_line)).await;
+                let body = serde_json::to_string(&page)
+                    .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+                (200, body)
+            }
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(watch) = &control.container
+        && path == CONTAINER_EVENTS_PATH
+    {
+        let (status, body) = match authorize_get(&request, path, Role::ReadOnly, auth) {
+            Ok(_) => {
+                let events = watch.since(parse_since(request_line)).await;
+                let body = serde_json::to_string(&events)
+                    .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+                (200, body)
+            }
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(sources) = &control.bundle
+        && path == CONFIG_PATH
+    {
+        let (status, body) = match authorize_get(&request, path, Role::Admin, auth) {
+            Ok(_) => {
+                let body = serde_json::to_string(&redact_config(&sources.config))
+                    .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+                (200, body)
+            }
+            Err(denied) => denied,
+        };
+        return write_json(&mut stream, status, &body).await;
+    }
+    if let Some(sources) = &control.bundle
+        && path == SUPPORT_BUNDLE_PATH
+    {
+        return match support_bundle_response(&request, snapshot, auth, sources).await {
+            Ok((name, archive)) => {
+                let headers = format!(
+                    "Content-Disposition: attachment; filename=\"{}.tar.gz\"\r\n",
+                    name
+                );
+                write_response(&mut stream, 200, "application/gzip", &headers, &archive).await
+            }
+            Err((status, body)) => write_json(&mut stream, status, &body).await,
+        };
+    }
+
+    let (status, body) = handle_request(&request, snapshot, policy, auth);
+    write_json(&mut stream, status, &body).await
+}
+
+/// Read the request head and, if `Content-Length` is set, its body.
+///
+/// The head is limited to [`MAX_REQUEST_BYTES`]; anything past it is treated
+/// as the start of the body. Returns `None` when the declared body exceeds
+/// [`MAX_BODY_BYTES`].
+async fn read_request<S>(stream: &mut S) -> std::io::Result<Option<(String, Vec<u8>)>>
+where
+    S: AsyncRead + Unpin,
+{
+    let mut buf = Vec::with_capacity(MAX_REQUEST_BYTES);
+    let mut chunk = [0u8; MAX_REQUEST_BYTES];
+    let head_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() >= MAX_REQUEST_BYTES {
+            break buf.len();
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break buf.len();
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..head_end]).into_owned();
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        return Ok(None);
+    }
+
+    let mut body = buf.split_off(head_end);
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+    Ok(Some((head, body)))
+}
+
+/// Check that a live control request is a `GET` authorized for `required`.
+///
+/// Returns the error status and JSON body when the request is rejected.
+fn authorize_get(
+    request: &str,
+    path: &str,
+    required: Role,
+    auth: &Authorizer,
+) -> Result<Option<Identity>, (u16, String)> {
+    let method = request.split_whitespace().next().unwrap_or_default();
+    if method != "GET" {
+        return Err((405, r#"{"error":"method not allowed"}"#.to_owned()));
+    }
+    authorize(request, path, required, auth)
+}
+
+/// Check that a live control request is authorized for `required`.
+///
+/// Returns the error status and JSON body when the request is rejected.
+fn authorize(
+    request: &str,
+    path: &str,
+    required: Role,
+    auth: &Authorizer,
+) -> Result<Option<Identity>, (u16, String)> {
+    auth.authorize(request, required).map_err(|e| {
+        tracing::warn!(path = path, reason = %e, "control request denied");
+        let body = serde_json::json!({ "error": e.to_string() }).to_string();
+        (e.status_code(), body)
+    })
+}
+
+/// Authorize a support bundle request and build the archive.
+///
+/// Returns the archive name and contents, or the error status and JSON body.
+async fn support_bundle_response(
+    request: &str,
+    snapshot: &HealthSnapshot,
+    auth: &Authorizer,
+    sources: &BundleSources,
+) -> Result<(String, Vec<u8>), (u16, String)> {
+    let identity = authorize_get(request, SUPPORT_BUNDLE_PATH, Role::Admin, auth)?;
+    let requested_by = identity.map(|i| i.name).unwrap_or_default();
+    tracing::info!(token = %requested_by, "support bundle requested");
+
+    let bundle = sources.collect(snapshot).await;
+    match bundle.to_tar_gz() {
+        Ok(archive) => Ok((bundle.archive_name(), archive)),
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to write support bundle");
+            let body = serde_json::json!({ "error": "failed to write support bundle" }).to_string();
+            Err((500, body))
+        }
+    }
+}
+
+async fn write_json<S>(stream: &mut S, status: u16, body: &str) -> std::io::Result<()>
+where
+    S: AsyncWrite + Unpin,
+{
+    let challenge = if status == 401 {
+        "WWW-Authenticate: Bearer\r\n"
+    } else {
+        ""
+    };
+    write_response(
+        stream,
+        status,
+        "application/json",
+        challenge,
+        body.as_bytes(),
+    )
+    .await
+}
+
+async fn write_response<S>(
+    stream: &mut S,
+    status: u16,
+    content_type: &str,
+    headers: &str,
+    body: &[u8],
+) -> std::io::Result<()>
+where
+    S: AsyncWrite + Unpin,
+{
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+        status_line(status),
+        content_type,
+        body.len(),
+        headers
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body).await?;
+    stream.shutdown().await
+}
+
+/// Whether a request changes daemon state.
+///
+/// These are the `operator` routes plus `POST /ebpf/blocklist`. Without
+/// `[auth]`, they are refused over TCP and only served on the unix socket.
+pub fn is_mutating(method: &str, path: &str) -> bool {
+    required_role(path) == Some(Role::Operator) || (path == EBPF_BLOCKLIST_PATH && method != "GET")
+}
+
+/// Role required to access `path` (`None` for unauthenticated probes).
+///
+/// For `/ebpf/blocklist` this is the role needed to read it; adding entries
+/// (`POST`) requires `operator`.
+pub fn required_role(path: &str) -> Option<Role> {
+    match path {
+        "/status"
+        | HEALTH_REPORT_PATH
+        | EBPF_STATUS_PATH
+        | EBPF_BLOCKLIST_PATH
+        | RULES_PATH
+        | LOGS_SEARCH_PATH
+        | ALERTS_PATH
+        | CONTAINER_EVENTS_PATH
+        | CONTAINER_POLICY_TEST_PATH
+        | MODULES_QUARANTINED_PATH => Some(Role::ReadOnly),
+        EBPF_BLOCKLIST_REMOVE_PATH
+        | EBPF_RULES_IMPORT_PATH
+        | CONTAINER_ISOLATE_PATH
+        | CONTAINER_RELEASE_PATH
+        | MODULES_UNQUARANTINE_PATH
+        | CONTENT_ROLLBACK_PATH => Some(Role::Operator),
+        CONFIG_PATH | SUPPORT_BUNDLE_PATH => Some(Role::Admin),
+        _ => None,
+    }
+}
+
+/// Authorize and route a raw HTTP request.
+///
+/// Returns the HTTP status code and JSON body.
+pub fn handle_request(
+    request: &str,
+    snapshot: &HealthSnapshot,
+    policy: &HealthConfig,
+    auth: &Authorizer,
+) -> (u16, String) {
+    let request_line = request.lines().next().unwrap_or_default();
+    let path = request_path(request_line);
+
+    if let Some(required) = required_role(path) {
+        match auth.authorize(request, required) {
+            Ok(Some(identity)) => {
+                tracing::debug!(token = %identity.name, role = %identity.role, path = path, "control request authorized");
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(path = path, reason = %e, "control request denied");
+                let body = serde_json::json!({ "error": e.to_string() }).to_string();
+                return (e.status_code(), body);
+            }
+        }
+    }
+
+    route_request(request_line, snapshot, policy)
+}
+
+/// Request path without the query string (e.g. `/readyz?verbose` -> `/readyz`).
+fn request_path(request_line: &str) -> &str {
+    request_line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+}
+
+/// Route a probe request line (e.g. `GET /readyz HTTP/1.1`).
+///
+/// Returns the HTTP status code and JSON body.
+pub fn route_request(
+    request_line: &str,
+    snapshot: &HealthSnapshot,
+    policy: &HealthConfig,
+) -> (u16, String) {
+    let method = request_line.split_whitespace().next().unwrap_or_default();
+    let path = request_path(request_line);
+
+    if method != "GET" && method != "HEAD" {
+        return (405, r#"{"error":"method not allowed"}"#.to_owned());
+    }
+
+    let health = &snapshot.health;
+    match path {
+        "/healthz" => {
+            let max_staleness = policy
+                .check_interval_secs
+                .saturating_mul(STALENESS_INTERVALS);
+            let age = snapshot.checked_at.elapsed().as_secs();
+            let result = evaluate_liveness(health, age, max_staleness, policy);
+            probe_response(&result, health, false)
+        }
+        "/readyz" => {
+            let result = evaluate_readiness(&health.modules, policy);
+            probe_response(&result, health, true)
+        }
+        "/status" => (200, status_body(snapshot)),
+        HEALTH_REPORT_PATH => match &snapshot.report {
+            Some(report) => (
+                200,
+                serde_json::to_string(report)
+                    .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned()),
+            ),
+            None => (
+                503,
+                r#"{"error":"health report not available yet"}"#.to_owned(),
+            ),
+        },
+        _ => (404, r#"{"error":"not found"}"#.to_owned()),
+    }
+}
+
+/// JSON body of `/status` for `snapshot`.
+pub fn status_body(snapshot: &HealthSnapshot) -> String {
+    let response = StatusResponse {
+        health: &snapshot.health,
+        tasks: &snapshot.tasks,
+        resources: &snapshot.resources,
+        content: snapshot.content.as_ref(),
+    };
+    serde_json::to_string(&response)
+        .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned())
+}
+
+fn probe_response(
+    result: &ProbeResult,
+    health: &DaemonHealth,
+    with_modules: bool,
+) -> (u16, String) {
+    let response = ProbeResponse {
+        status: if result.ok { "ok" } else { "fail" },
+        uptime_secs: health.uptime_secs,
+        reasons: &result.reasons,
+        modules: with_modules.then_some(health.modules.as_slice()),
+    };
+    let code = if result.ok { 200 } else { 503 };
+    let body = serde_json::to_string(&response)
+        .unwrap_or_else(|_| r#"{"status":"fail","reasons":["serialization error"]}"#.to_owned());
+    (code, body)
+}
+
+fn status_line(code: u16) -> &'static str {
+    match code {
+        200 => "200 OK",
+        400 => "400 Bad Request",
+        401 => "401 Unauthorized",
+        403 => "403 Forbidden",
+        404 => "404 Not Found",
+        405 => "405 Method Not Allowed",
+        409 => "409 Conflict",
+        413 => "413 Payload Too Large",
+        500 => "500 Internal Server Error",
+        _ => "503 Service Unavailable",
+    }
+}
@@ -0,0 +1,254 @@
+//! High-availability leader election.
+//!
+//! Two daemons in an HA pair share a lock file (`[ha]` section). The
+//! instance holding an exclusive lock on it is the leader; the other is a
+//! standby that retries every `retry_interval_secs`.
+//!
+//! Both instances collect and detect. Leadership is published through a
+//! `watch` channel wired into container-guard's isolation gate, so only the
+//! leader performs destructive isolation actions.
+//!
+//! The lock is held by the open file handle, so the operating system
+//! releases it automatically if the leader crashes. The lock file contains
+//! the current leader's node ID for operators.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Seek, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::{broadcast, watch};
+
+use ironpost_core::config::HaConfig;
+
+/// Leader election over an exclusive file lock.
+pub struct LeaderElector {
+    /// Shared lock file path.
+    lock_path: PathBuf,
+    /// This instance's node ID.
+    node_id: String,
+    /// Standby retry interval.
+    retry_interval: Duration,
+    /// Lock file handle while leader.
+    lock_file: Option<File>,
+}
+
+impl LeaderElector {
+    /// Create an elector from configuration.
+    ///
+    /// An empty `node_id` is replaced by a random one.
+    pub fn new(config: &HaConfig) -> Self {
+        let node_id = if config.node_id.is_empty() {
+            format!("ironpost-{}", uuid::Uuid::new_v4().simple())
+        } else {
+            config.node_id.clone()
+        };
+        Self {
+            lock_path: PathBuf::from(&config.lock_path),
+            node_id,
+            retry_interval: Duration::from_secs(config.retry_interval_secs),
+            lock_file: None,
+        }
+    }
+
+    /// This instance's node ID.
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// Whether this instance currently holds leadership.
+    pub fn is_leader(&self) -> bool {
+        self.lock_file.is_some()
+    }
+
+    /// Try to become leader without blocking.
+    ///
+    /// Returns `Ok(true)` if this instance is (or already was) the leader
+    /// and `Ok(false)` if another instance holds the lock.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file cannot be opened or locked for a
+    /// reason other than contention.
+    pub fn try_acquire(&mut self) -> Result<bool> {
+        if self.lock_file.is_some() {
+            return Ok(true);
+        }
+
+        if let Some(parent) = self.lock_path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                anyhow::anyhow!(
+                    "failed to create lock directory {}: {}",
+                    parent.display(),
+                    e
+                )
+            })?;
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.lock_path)
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "failed to open lock file {}: {}",
+                    self.lock_path.display(),
+                    e
+                )
+            })?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Ok(false),
+            Err(TryLockError::Error(e)) => {
+                return Err(anyhow::anyhow!(
+                    "failed to lock {}: {}",
+                    self.lock_path.display(),
+                    e
+                ));
+            }
+        }
+
+        // Record the holder for operators; the lock itself is what matters
+        if let Err(e) = write_holder(&mut file, &self.node_id) {
+            tracing::warn!(error = %e, "failed to record leader in lock file");
+        }
+
+        self.lock_file = Some(file);
+        Ok(true)
+    }
+
+    /// Give up leadership.
+    pub fn release(&mut self) {
+        if let Some(file) = self.lock_file.take() {
+            if let Err(e) = file.unlock() {
+                tracing::warn!(error = %e, "failed to unlock leader lock file");
+            }
+            tracing::info!(node_id = %self.node_id, "leadership released");
+        }
+    }
+
+    /// Run the election loop until shutdown.
+    ///
+    /// A standby retries every `retry_interval`; the leader keeps the lock
+    /// until shutdown, then releases it so the peer can take over promptly.
+    pub fn spawn(
+        mut self,
+        leader_tx: watch::Sender<bool>,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.retry_interval);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if self.is_leader() {
+                            continue;
+                        }
+                        match self.try_acquire() {
+                            Ok(true) => {
+                                tracing::warn!(
+                                    node_id = %self.node_id,
+                                    "acquired leadership, isolation actions enabled"
+                                );
+                                leader_tx.send_replace(true);
+                            }
+                            Ok(false) => {
+                                tracing::debug!(
+                                    node_id = %self.node_id,
+                                    "standby, leader lock held by peer"
+                                );
+                            }
+                            Err(e) => {
+                                tracing::error!(error = %e, "leader election failed");
+                            }
+                        }
+                    }
+                    _ = shutdown_rx.recv() => {
+                        leader_tx.send_replace(false);
+                        self.release();
+                        break;
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Replace the lock file contents with the holder's node ID.
+fn write_holder(file: &mut File, node_id: &str) -> std::io::Result<()> {
+    file.set_len(0)?;
+    file.rewind()?;
+    writeln!(file, "{}", node_id)?;
+    file.sync_data()
+}
+
+impl Drop for LeaderElector {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(lock_path: &std::path::Path, node_id: &str) -> HaConfig {
+        HaConfig {
+            enabled: true,
+            lock_path: lock_path.display().to_string(),
+            node_id: node_id.to_owned(),
+            ..HaConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_only_one_instance_acquires_leadership() {
+        // Given: Two electors sharing a lock file
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ha").join("leader.lock");
+        let mut a = LeaderElector::new(&config(&path, "node-a"));
+        let mut b = LeaderElector::new(&config(&path, "node-b"));
+
+        // When: Both try to acquire
+        let a_leader = a.try_acquire().unwrap();
+        let b_leader = b.try_acquire().unwrap();
+
+        // Then: Only the first becomes leader and is recorded in the file
+        assert!(a_leader);
+        assert!(!b_leader);
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.trim(), "node-a");
+    }
+
+    #[test]
+    fn test_standby_takes_over_after_release() {
+        // Given: node-a is leader
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("leader.lock");
+        let mut a = LeaderElector::new(&config(&path, "node-a"));
+        let mut b = LeaderElector::new(&config(&path, "node-b"));
+        assert!(a.try_acquire().unwrap());
+
+        // When: node-a releases leadership
+        a.release();
+
+        // Then: node-b can take over
+        assert!(!a.is_leader());
+        assert!(b.try_acquire().unwrap());
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.trim(), "node-b");
+    }
+
+    #[test]
+    fn test_empty_node_id_is_generated() {
+        let dir = tempfile::tempdir().unwrap();
+        let elector = LeaderElector::new(&config(&dir.path().join("leader.lock"), ""));
+        assert!(elector.node_id().starts_with("ironpost-"));
+    }
+}
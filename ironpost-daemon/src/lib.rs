@@ -3,7 +3,30 @@
 //! This library exposes internal modules for integration testing.
 //! In production, `ironpost-daemon` is used as a binary (main.rs).
 
+pub mod alert_feed;
+pub mod alert_gate;
+pub mod auth;
+pub mod container_isolation;
+pub mod container_watch;
+pub mod content;
+pub mod cron;
+pub mod daemon_control;
+pub mod doctor;
+#[cfg(target_os = "linux")]
+pub mod ebpf_blocklist;
+pub mod ebpf_status;
+pub mod event_journal;
 pub mod health;
+pub mod health_server;
+pub mod leader;
+pub mod log_search;
 pub mod metrics_server;
 pub mod modules;
 pub mod orchestrator;
+pub mod policy_simulation;
+pub mod remote_config;
+pub mod resources;
+pub mod rules_status;
+pub mod scheduler;
+pub mod supervisor;
+pub mod support_bundle;
@@ -0,0 +1,283 @@
+//! Stored log search for the control API.
+//!
+//! `POST /logs/search` (`read-only` role when `[auth]` is enabled) searches
+//! the log pipeline's built-in log store (`[log_pipeline.log_store]`). Filters
+//! travel as a JSON body so free-text queries need no URL encoding. Results
+//! are newest first; `offset` pages through larger result sets and
+//! `has_more` tells whether another page exists.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use ironpost_core::types::{LogEntry, Severity};
+use ironpost_log_pipeline::{LogQuery, LogStore};
+
+/// Path of the log search endpoint.
+pub const LOGS_SEARCH_PATH: &str = "/logs/search";
+
+/// Page size when the request does not set `limit`.
+pub const DEFAULT_SEARCH_LIMIT: usize = 100;
+
+/// Largest page a single request may ask for.
+pub const MAX_SEARCH_LIMIT: usize = 1000;
+
+/// Largest `offset + limit`, which bounds the entries held per request.
+pub const MAX_SEARCH_WINDOW: usize = 100_000;
+
+/// Body of `POST /logs/search`. Every filter is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct LogSearchRequest {
+    /// Text the message contains (case-insensitive).
+    pub text: Option<String>,
+    /// Exact hostname.
+    pub hostname: Option<String>,
+    /// Exact process name.
+    pub process: Option<String>,
+    /// Extra fields that must match exactly.
+    pub fields: BTreeMap<String, String>,
+    /// Minimum severity.
+    pub severity: Option<Severity>,
+    /// Oldest timestamp to include (Unix milliseconds).
+    pub since_ms: Option<u64>,
+    /// Newest timestamp to include (Unix milliseconds).
+    pub until_ms: Option<u64>,
+    /// Page size (0 = [`DEFAULT_SEARCH_LIMIT`]).
+    pub limit: usize,
+    /// Matches to skip, newest first.
+    pub offset: usize,
+}
+
+/// A stored log entry as reported by the control API.
+#[derive(Debug, Serialize)]
+pub struct StoredLog {
+    /// Log timestamp (Unix milliseconds).
+    pub timestamp_ms: u64,
+    /// Collector source (file path, syslog, ...).
+    pub source: String,
+    /// Hostname.
+    pub hostname: String,
+    /// Process name.
+    pub process: String,
+    /// Severity.
+    pub severity: Severity,
+    /// Log message.
+    pub message: String,
+    /// Extra fields.
+    pub fields: BTreeMap<String, String>,
+}
+
+impl From<LogEntry> for StoredLog {
+    fn from(entry: LogEntry) -> Self {
+        Self {
+            timestamp_ms: unix_ms(entry.timestamp),
+            source: entry.source,
+            hostname: entry.hostname,
+            process: entry.process,
+            severity: entry.severity,
+            message: entry.message,
+            fields: entry.fields.into_iter().collect(),
+        }
+    }
+}
+
+/// Response of `POST /logs/search`.
+#[derive(Debug, Serialize)]
+pub struct LogSearchResponse {
+    /// Matching logs, newest first.
+    pub logs: Vec<StoredLog>,
+    /// Matches skipped before `logs`.
+    pub offset: usize,
+    /// Page size used.
+    pub limit: usize,
+    /// Whether more matches follow this page.
+    pub has_more: bool,
+}
+
+/// Handle an authorized log search request.
+///
+/// Only `POST` is accepted; an empty body returns the newest logs. The store
+/// is read on a blocking thread. Returns the HTTP status code and JSON body.
+pub async fn handle_log_search_request(
+    store: &Arc<LogStore>,
+    method: &str,
+    body: &[u8],
+) -> (u16, String) {
+    if method != "POST" {
+        return (405, r#"{"error":"method not allowed"}"#.to_owned());
+    }
+    let request = if body.is_empty() {
+        LogSearchRequest::default()
+    } else {
+        match serde_json::from_slice::<LogSearchRequest>(body) {
+            Ok(request) => request,
+            Err(e) => return bad_request(format!("invalid request body: {}", e)),
+        }
+    };
+    let limit = if request.limit == 0 {
+        DEFAULT_SEARCH_LIMIT
+    } else {
+        request.limit
+    };
+    if limit > MAX_SEARCH_LIMIT {
+        return bad_request(format!("limit must be at most {}", MAX_SEARCH_LIMIT));
+    }
+    if request.offset.saturating_add(limit) > MAX_SEARCH_WINDOW {
+        return bad_request(format!(
+            "offset + limit must be at most {}; narrow the time range instead",
+            MAX_SEARCH_WINDOW
+        ));
+    }
+    if let (Some(since), Some(until)) = (request.since_ms, request.until_ms)
+        && since > until
+    {
+        return bad_request("since must not be later than until".to_owned());
+    }
+
+    let offset = request.offset;
+    let query = LogQuery {
+        text: request.text,
+        hostname: request.hostname,
+        process: request.process,
+        fields: request.fields.into_iter().collect(),
+        severity: request.severity,
+        since: request.since_ms.map(from_unix_ms),
+        until: request.until_ms.map(from_unix_ms),
+        // One extra match tells whether another page follows.
+        limit: limit + 1,
+        offset,
+    };
+    let store = Arc::clone(store);
+    let mut logs = match tokio::task::spawn_blocking(move || store.search(&query)).await {
+        Ok(Ok(logs)) => logs,
+        Ok(Err(e)) => {
+            tracing::warn!(error = %e, "log search failed");
+            let message = format!("log search failed: {}", e);
+            return (500, serde_json::json!({ "error": message }).to_string());
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "log search task failed");
+            return (500, r#"{"error":"log search failed"}"#.to_owned());
+        }
+    };
+    let has_more = logs.len() > limit;
+    logs.truncate(limit);
+
+    let response = LogSearchResponse {
+        logs: logs.into_iter().map(StoredLog::from).collect(),
+        offset,
+        limit,
+        has_more,
+    };
+    let body = serde_json::to_string(&response)
+        .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+    (200, body)
+}
+
+fn bad_request(message: String) -> (u16, String) {
+    (400, serde_json::json!({ "error": message }).to_string())
+}
+
+fn unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+fn from_unix_ms(ms: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_core::config::LogStoreConfig;
+
+    fn store(dir: &std::path::Path) -> Arc<LogStore> {
+        let store = LogStore::open(&LogStoreConfig {
+            enabled: true,
+            dir: dir.display().to_string(),
+            ..Default::default()
+        })
+        .expect("store should open");
+        let entries: Vec<LogEntry> = (0..5u64)
+            .map(|i| LogEntry {
+                source: "/var/log/auth.log".to_owned(),
+                timestamp: from_unix_ms(1_000_000 + i * 1000),
+                hostname: if i % 2 == 0 { "web-01" } else { "db-01" }.to_owned(),
+                process: "sshd".to_owned(),
+                message: format!("Failed password for root ({i})"),
+                severity: if i == 4 {
+                    Severity::High
+                } else {
+                    Severity::Low
+                },
+                fields: vec![("user".to_owned(), "root".to_owned())],
+            })
+            .collect();
+        store.append(&entries).expect("append should succeed");
+        Arc::new(store)
+    }
+
+    async fn search(store: &Arc<LogStore>, body: &str) -> serde_json::Value {
+        let (code, body) = handle_log_search_request(store, "POST", body.as_bytes()).await;
+        assert_eq!(code, 200, "got: {}", body);
+        serde_json::from_str(&body).expect("valid JSON")
+    }
+
+    #[tokio::test]
+    async fn test_search_filters_and_pages() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = store(dir.path());
+
+        let page = search(&store, r#"{"hostname":"web-01","limit":2}"#).await;
+        let messages: Vec<&str> = page["logs"]
+            .as_array()
+            .expect("logs array")
+            .iter()
+            .map(|log| log["message"].as_str().expect("message"))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "Failed password for root (4)",
+                "Failed password for root (2)"
+            ]
+        );
+        assert_eq!(page["has_more"], true);
+        assert_eq!(page["logs"][0]["fields"]["user"], "root");
+        assert_eq!(page["logs"][0]["timestamp_ms"], 1_004_000);
+
+        let page = search(&store, r#"{"hostname":"web-01","limit":2,"offset":2}"#).await;
+        assert_eq!(page["logs"].as_array().map(Vec::len), Some(1));
+        assert_eq!(page["has_more"], false);
+
+        let page = search(&store, r#"{"severity":"High","since_ms":1003000}"#).await;
+        assert_eq!(page["logs"].as_array().map(Vec::len), Some(1));
+        assert_eq!(page["logs"][0]["severity"], "High");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_requests() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = store(dir.path());
+
+        let (code, _) = handle_log_search_request(&store, "GET", b"").await;
+        assert_eq!(code, 405);
+        let (code, _) = handle_log_search_request(&store, "POST", b"not json").await;
+        assert_eq!(code, 400);
+        let (code, body) = handle_log_search_request(&store, "POST", br#"{"limit":5000}"#).await;
+        assert_eq!(code, 400);
+        assert!(body.contains("at most 1000"));
+        let (code, _) =
+            handle_log_search_request(&store, "POST", br#"{"since_ms":10,"until_ms":5}"#).await;
+        assert_eq!(code, 400);
+
+        let (code, body) = handle_log_search_request(&store, "POST", b"").await;
+        assert_eq!(code, 200);
+        assert!(body.contains(r#""limit":100"#));
+    }
+}
@@ -11,20 +11,47 @@
 //! ```text
 //! ironpost-daemon --config /etc/ironpost/ironpost.toml
 //! ironpost-daemon --validate    # validate config and exit
+//! ironpost-daemon --doctor      # run startup self-tests and exit
 //! ironpost-daemon --log-level debug --log-format pretty
 //! ```
 
+mod alert_feed;
+mod alert_gate;
+mod auth;
 mod cli;
+mod container_isolation;
+mod container_watch;
+mod content;
+mod cron;
+mod daemon_control;
+mod doctor;
+#[cfg(target_os = "linux")]
+mod ebpf_blocklist;
+mod ebpf_status;
+mod event_journal;
 mod health;
+mod health_server;
+mod leader;
+mod log_search;
 mod logging;
 mod metrics_server;
 mod modules;
 mod orchestrator;
+mod policy_simulation;
+mod remote_config;
+mod resources;
+mod rules_status;
+mod scheduler;
+mod supervisor;
+mod support_bundle;
+
+use std::io::Write;
 
 use anyhow::Result;
 use clap::Parser;
 
-use crate::cli::DaemonCli;
+use crate::cli::{DaemonCli, DoctorFormat};
+use crate::doctor::DoctorReport;
 use crate::orchestrator::Orchestrator;
 
 #[tokio::main]
@@ -44,6 +71,23 @@ async fn main() -> Result<()> {
         ironpost_core::config::IronpostConfig::default()
     };
 
+    // Fetch remote configuration (the local file only bootstraps the source)
+    if let Some(ref url) = cli.config_url {
+        config.remote_config.enabled = true;
+        config.remote_config.url = url.clone();
+    }
+    let remote = if config.remote_config.enabled {
+        config
+            .remote_config
+            .validate()
+            .map_err(|e| anyhow::anyhow!("invalid remote config source: {}", e))?;
+        let loaded = remote_config::load(&config.remote_config).await?;
+        config = loaded.config;
+        Some((loaded.digest, loaded.from_cache))
+    } else {
+        None
+    };
+
     // Apply CLI overrides
     if let Some(ref level) = cli.log_level {
         config.general.log_level = level.clone();
@@ -54,6 +98,30 @@ async fn main() -> Result<()> {
     if let Some(ref pid_file) = cli.pid_file {
         config.general.pid_file = pid_file.clone();
     }
+    if cli.dry_run {
+        config.general.dry_run = true;
+    }
+
+    // Self-test mode
+    if let Some(format) = cli.doctor {
+        let report = DoctorReport::run(&config).await;
+        let stdout = std::io::stdout();
+        let mut out = stdout.lock();
+        match format {
+            DoctorFormat::Text => report.render_text(&mut out)?,
+            DoctorFormat::Json => {
+                serde_json::to_writer_pretty(&mut out, &report)?;
+                writeln!(out)?;
+            }
+        }
+        if !report.ok {
+            return Err(anyhow::anyhow!(
+                "doctor: {} check(s) failed",
+                report.failures()
+            ));
+        }
+        return Ok(());
+    }
 
     // Validate-only mode
     if cli.validate {
@@ -88,8 +156,28 @@ async fn main() -> Result<()> {
         "ironpost-daemon starting"
     );
 
+    if let Some((ref digest, from_cache)) = remote {
+        if from_cache {
+            tracing::warn!(
+                url = %config.remote_config.url,
+                cache_path = %config.remote_config.cache_path,
+                digest = %digest,
+                "remote config unavailable, using last verified cached copy"
+            );
+        } else {
+            tracing::info!(
+                url = %config.remote_config.url,
+                digest = %digest,
+                "configuration loaded from remote source"
+            );
+        }
+    }
+
     // Build and run the orchestrator
     let mut orchestrator = Orchestrator::build_from_config(config).await?;
+    if let Some((digest, _)) = remote {
+        orchestrator.set_remote_config_digest(digest);
+    }
     orchestrator.run().await?;
 
     tracing::info!("ironpost-daemon shut down cleanly");
@@ -11,29 +11,92 @@
 //! 3. SBOM Scanner (produces AlertEvents)
 //! 4. Container Guard (consumes AlertEvents, produces ActionEvents)
 //!
-//! # Shutdown Order (same as startup - producers first)
+//! # High Availability
 //!
-//! 1. eBPF Engine (stop producing PacketEvents)
-//! 2. Log Pipeline (drain buffer, stop producing AlertEvents)
-//! 3. SBOM Scanner (stop producing AlertEvents)
-//! 4. Container Guard (drain remaining AlertEvents)
+//! With `[ha]` enabled, a [`LeaderElector`] decides which instance of an HA
+//! pair is the leader. Both instances collect and detect, but container
+//! guard only executes isolation actions while this instance is the leader.
+//!
+//! # Content Distribution
+//!
+//! With `[content]` enabled, a [`ContentManager`] pushes versioned rule and
+//! policy bundles into the log pipeline and container guard after startup
+//! and on every sync interval. See [`crate::content`].
+//!
+//! # Alert Gate
+//!
+//! With `[alert_gate]` enabled (the default), producers send alerts to an
+//! [`AlertGate`] relay that deduplicates and rate-caps them before they
+//! reach container guard. The log pipeline consults the same gate before its
+//! alert sinks and outputs. See [`crate::alert_gate`].
+//!
+//! # Event Persistence
+//!
+//! With `[persistence]` enabled, an [`EventJournal`] relay records alerts and
+//! action events before they reach their consumer and replays unacknowledged
+//! ones on the next start. See [`crate::event_journal`].
+//!
+//! # Crash-Loop Supervision
+//!
+//! Plugins that repeatedly fail to start or crash at runtime are quarantined
+//! by the [`Supervisor`] instead of blocking startup or restarting forever.
+//! See [`crate::supervisor`].
+//!
+//! # Phased Shutdown
+//!
+//! Each phase is bounded by its own timeout from the `[shutdown]` section:
+//!
+//! 1. Stop producers -- scheduled tasks, eBPF Engine, Log Pipeline
+//!    (flushes its buffer), SBOM Scanner
+//! 2. Drain channels -- wait until the alert channel is empty or the
+//!    drain timeout elapses
+//! 3. Stop consumers -- Container Guard
+//! 4. Flush sinks -- action logger writes remaining ActionEvents,
+//!    background tasks exit
 
 use std::path::Path;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::Result;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{Mutex, broadcast, mpsc, watch};
 
+use ironpost_container_guard::{
+    BollardDockerClient, GuardActivity, IsolationControl, PolicySimulator,
+};
+use ironpost_core::channel::{InstrumentedLink, LinkOptions, instrumented_channel};
 use ironpost_core::config::IronpostConfig;
-use ironpost_core::event::{ActionEvent, AlertEvent};
-use ironpost_core::plugin::PluginRegistry;
-
-use crate::health::{DaemonHealth, ModuleHealth, aggregate_status};
+use ironpost_core::error::{IronpostError, PluginError};
+use ironpost_core::event::{ActionEvent, AlertEvent, MODULE_DAEMON, PacketEvent};
+use ironpost_core::pipeline::HealthStatus;
+use ironpost_core::plugin::{
+    ChannelUsage, PluginInfo, PluginRegistry, PluginState, PluginType, ResourceUsage,
+};
+use ironpost_core::types::{Alert, Severity};
+use ironpost_log_pipeline::LogStore;
+
+use crate::alert_feed;
+use crate::alert_gate::{self, AlertGate, SharedAlertGate};
+use crate::auth::{self, Authorizer};
+use crate::container_watch::{CONTAINER_EVENTS_CAPACITY, ContainerWatch, spawn_activity_recorder};
+use crate::content::{ContentManager, ContentStatus, ContentTargets};
+use crate::daemon_control::{CommandError, OrchestratorCommand, OrchestratorHandle};
+use crate::ebpf_status::EbpfSource;
+use crate::event_journal::{self, EventJournal, Replay};
+use crate::health::{
+    DaemonHealth, HealthReport, HealthTracker, ModuleHealth, ModuleObservation, aggregate_status,
+};
+use crate::health_server::{self, ControlSources, HealthSnapshot};
+use crate::leader::LeaderElector;
 use crate::metrics_server;
+use crate::remote_config;
+use crate::resources::{ModuleUsage, ResourceReport};
+use crate::scheduler::{JobContext, Scheduler, SchedulerHandle, TaskStatus};
+use crate::supervisor::{QuarantineRecord, Supervisor};
+use crate::support_bundle::{BundleSources, RECENT_EVENTS_CAPACITY, RecentEvents};
 
-/// Channel capacity constants.
-const PACKET_CHANNEL_CAPACITY: usize = 1024;
-const ALERT_CHANNEL_CAPACITY: usize = 256;
+/// Poll interval while waiting for channels to drain during shutdown.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// The main daemon orchestrator.
 ///
@@ -52,6 +115,61 @@ pub struct Orchestrator {
     start_time: Instant,
     /// Optional action event receiver (for logging/audit).
     action_rx: Option<mpsc::Receiver<ActionEvent>>,
+    /// Alert link (producers -> alert gate/consumer), kept to hand out
+    /// senders and to observe queue depth during the drain phase.
+    ///
+    /// Dropped once draining completes so the channel can close.
+    alert_link: Option<InstrumentedLink<AlertEvent>>,
+    /// Packet link (eBPF engine -> log pipeline), kept for depth reporting.
+    packet_link: Option<InstrumentedLink<PacketEvent>>,
+    /// Relay tasks of links with a `drop_oldest`/`spill` overflow policy.
+    link_tasks: Vec<tokio::task::JoinHandle<()>>,
+    /// Sender side of the gated alert channel (alert gate or tap -> consumer),
+    /// kept to observe its depth during the drain phase.
+    gated_alert_tx: Option<mpsc::Sender<AlertEvent>>,
+    /// Alert gate (or alert tap) relay task.
+    alert_gate_task: Option<tokio::task::JoinHandle<()>>,
+    /// Sender side of the journaled alert channel (journal relay -> consumer),
+    /// kept to observe its depth during the drain phase.
+    journaled_alert_tx: Option<mpsc::Sender<AlertEvent>>,
+    /// Event journal relay tasks (`[persistence]`).
+    journal_tasks: Vec<tokio::task::JoinHandle<()>>,
+    /// Crash-loop tracker and quarantine state.
+    supervisor: Supervisor,
+    /// Module state-change and restart history for health reports.
+    health_tracker: Mutex<HealthTracker>,
+    /// Scheduler for recurring tasks, taken when the main loop starts.
+    scheduler: Option<Scheduler>,
+    /// Handle to the running scheduler (stopped with the producers).
+    scheduler_handle: Option<SchedulerHandle>,
+    /// Scheduled task status updates.
+    task_status_rx: Option<watch::Receiver<Vec<TaskStatus>>>,
+    /// Leadership publisher for HA mode, taken when the election loop starts.
+    leader_tx: Option<watch::Sender<bool>>,
+    /// Digest of the applied remote configuration (enables the refresher).
+    remote_config_digest: Option<String>,
+    /// Rule/policy content manager (`None` when `[content]` is disabled).
+    content: Option<Arc<Mutex<ContentManager>>>,
+    /// Content status updates.
+    content_rx: Option<watch::Receiver<ContentStatus>>,
+    /// Rule and policy engines (listed in support bundles).
+    content_targets: ContentTargets,
+    /// Built-in log store (`None` unless `[log_pipeline.log_store]` is enabled).
+    log_store: Option<Arc<LogStore>>,
+    /// Recent alerts and actions (included in support bundles).
+    recent: RecentEvents,
+    /// eBPF engine status source (`None` when the engine is disabled).
+    ebpf: Option<EbpfSource>,
+    /// Container guard event feed (`None` when the guard is disabled).
+    container_watch: Option<ContainerWatch>,
+    /// Manual isolation handle (`None` when the guard is disabled).
+    container_isolation: Option<IsolationControl<BollardDockerClient>>,
+    /// Policy simulation handle (`None` when the guard is disabled).
+    policy_simulator: Option<PolicySimulator<BollardDockerClient>>,
+    /// Control API command sender (served by the health listener).
+    commands: OrchestratorHandle,
+    /// Control API command receiver, taken when the main loop starts.
+    command_rx: Option<mpsc::Receiver<OrchestratorCommand>>,
 }
 
 impl Orchestrator {
@@ -95,28 +213,140 @@ impl Orchestrator {
             tracing::info!(port = config.metrics.port, "metrics endpoint enabled");
         }
 
+        if config.general.dry_run {
+            tracing::warn!(
+                "dry-run mode enabled: blocking and isolation actions will be simulated only"
+            );
+        }
+
         tracing::debug!("creating inter-module channels");
 
         // Create channels
-        let (packet_tx, _packet_rx_for_ebpf) =
-            mpsc::channel::<ironpost_core::event::PacketEvent>(PACKET_CHANNEL_CAPACITY);
-        let (alert_tx, alert_rx) = mpsc::channel::<AlertEvent>(ALERT_CHANNEL_CAPACITY);
         let (shutdown_tx, _) = broadcast::channel(16);
+        let packet_options = LinkOptions::from_config(&config.channels.packets, &config.channels);
+        let alert_options = LinkOptions::from_config(&config.channels.alerts, &config.channels);
+        let (packet_link, _packet_rx_for_ebpf, packet_task) = instrumented_channel::<PacketEvent>(
+            "packets",
+            &packet_options,
+            shutdown_tx.subscribe(),
+        );
+        let (alert_link, alert_rx, alert_task) =
+            instrumented_channel::<AlertEvent>("alerts", &alert_options, shutdown_tx.subscribe());
+        let link_tasks: Vec<_> = [packet_task, alert_task].into_iter().flatten().collect();
+        let packet_tx = packet_link.sender();
+        let alert_tx = alert_link.sender();
+        tracing::debug!(
+            packets = packet_options.policy.as_str(),
+            alerts = alert_options.policy.as_str(),
+            "channel overflow policies"
+        );
+
+        let recent = RecentEvents::new(RECENT_EVENTS_CAPACITY);
+
+        // Daemon-level dedup/storm protection between producers and the consumer
+        let mut shared_gate = None;
+        let (alert_rx, gated_alert_tx, alert_gate_task) = if config.alert_gate.enabled {
+            tracing::info!(
+                dedup_window_secs = config.alert_gate.dedup_window_secs,
+                max_alerts_per_minute = config.alert_gate.max_alerts_per_minute,
+                "alert gate enabled"
+            );
+            let (gated_tx, gated_rx) = mpsc::channel::<AlertEvent>(alert_options.capacity);
+            let gate = SharedAlertGate::new(AlertGate::new(&config.alert_gate));
+            shared_gate = Some(gate.clone());
+            let task = alert_gate::spawn_alert_gate(
+                gate,
+                alert_rx,
+                gated_tx.clone(),
+                recent.clone(),
+                config.metrics.enabled,
+                shutdown_tx.subscribe(),
+            );
+            (gated_rx, Some(gated_tx), Some(task))
+        } else if config.container.enabled {
+            // Without a gate the consumer reads producers directly; tap the
+            // channel so recent alerts and the alert feed still see them
+            let (tapped_tx, tapped_rx) = mpsc::channel::<AlertEvent>(alert_options.capacity);
+            let task = alert_feed::spawn_alert_tap(
+                alert_rx,
+                tapped_tx.clone(),
+                recent.clone(),
+                shutdown_tx.subscribe(),
+            );
+            (tapped_rx, Some(tapped_tx), Some(task))
+        } else {
+            (alert_rx, None, None)
+        };
+
+        // Event journal in front of the consumers; unacknowledged events from
+        // the previous run are replayed ahead of new ones
+        let mut journal_tasks = Vec::new();
+        let (journal, mut replay) = if config.persistence.enabled {
+            let (journal, replay) = EventJournal::open(&config.persistence).await.map_err(|e| {
+                anyhow::anyhow!(
+                    "failed to open event journal in {}: {}",
+                    config.persistence.dir,
+                    e
+                )
+            })?;
+            tracing::info!(dir = %config.persistence.dir, "event persistence enabled");
+            (Some(journal), replay)
+        } else {
+            (None, Replay::default())
+        };
+        let pipeline_replay = replay.pipeline_alerts();
+        let (alert_rx, journaled_alert_tx) = match &journal {
+            Some(journal) => {
+                let (tx, rx) = mpsc::channel::<AlertEvent>(alert_options.capacity);
+                journal_tasks.push(event_journal::spawn_journal_relay(
+                    journal.clone(),
+                    std::mem::take(&mut replay.alerts),
+                    alert_rx,
+                    tx.clone(),
+                    shutdown_tx.subscribe(),
+                ));
+                (rx, Some(tx))
+            }
+            None => (alert_rx, None),
+        };
+
+        // HA leadership gate: standby until the election loop acquires the lock
+        let (leader_tx, leader_rx) = watch::channel(false);
+        if config.ha.enabled {
+            tracing::info!(
+                backend = %config.ha.backend,
+                lock_path = %config.ha.lock_path,
+                "HA mode enabled: isolation actions run on the leader only"
+            );
+        }
 
         let mut plugins = PluginRegistry::new();
         let mut action_rx = None;
+        let mut content_targets = ContentTargets::default();
+        let mut log_store = None;
+        #[cfg_attr(not(target_os = "linux"), allow(unused_mut))]
+        let mut ebpf: Option<EbpfSource> = None;
 
         // Initialize eBPF engine (Linux only)
         #[cfg(target_os = "linux")]
         {
             if config.ebpf.enabled {
                 tracing::info!("initializing eBPF engine");
-                let engine_config = ironpost_ebpf_engine::EngineConfig::from_core(&config.ebpf);
+                let mut engine_config = ironpost_ebpf_engine::EngineConfig::from_core(&config.ebpf);
+                engine_config.dry_run |= config.general.dry_run;
                 let (engine, _packet_rx) = ironpost_ebpf_engine::EbpfEngine::builder()
                     .config(engine_config)
                     .event_sender(packet_tx.clone())
+                    .alert_sender(alert_tx.clone())
                     .build()
                     .map_err(|e| anyhow::anyhow!("failed to build eBPF engine: {}", e))?;
+                ebpf = Some(EbpfSource::new(
+                    &config.ebpf,
+                    engine.config().dry_run,
+                    engine.stats(),
+                    engine.conntrack(),
+                    engine.blocklist(),
+                ));
                 plugins.register(Box::new(engine))?;
             }
         }
@@ -146,9 +376,18 @@ impl Orchestrator {
                     .packet_receiver(dummy_rx)
             };
 
+            // Sinks and outputs see only alerts the daemon gate forwards
+            let builder = match &shared_gate {
+                Some(gate) => builder.alert_admission(Arc::new(gate.clone())),
+                None => builder,
+            }
+            .replay_alerts(pipeline_replay)
+            .dry_run(config.general.dry_run);
             let (pipeline, _) = builder
                 .build()
                 .map_err(|e| anyhow::anyhow!("failed to build log pipeline: {}", e))?;
+            content_targets.rules = Some(pipeline.rule_engine_arc());
+            log_store = pipeline.log_store();
             plugins.register(Box::new(pipeline))?;
         }
 
@@ -165,28 +404,93 @@ impl Orchestrator {
         }
 
         // Initialize container guard
+        let mut container_watch = None;
+        let mut container_isolation = None;
+        let mut policy_simulator = None;
         if config.container.enabled {
             tracing::info!("initializing container guard");
-            let guard_config =
+            let mut guard_config =
                 ironpost_container_guard::ContainerGuardConfig::from_core(&config.container);
-            let docker = std::sync::Arc::new(
-                ironpost_container_guard::BollardDockerClient::connect_local()?,
-            );
-            let (guard, rx) = ironpost_container_guard::ContainerGuardBuilder::new()
+            guard_config.dry_run = config.general.dry_run;
+            let docker = std::sync::Arc::new(BollardDockerClient::connect_local()?);
+            let mut builder = ironpost_container_guard::ContainerGuardBuilder::new()
                 .config(guard_config)
                 .docker_client(docker)
-                .alert_receiver(alert_rx)
+                .alert_receiver(alert_rx);
+            if config.ha.enabled {
+                builder = builder.isolation_gate(leader_rx);
+            }
+            let (guard, rx) = builder
                 .build()
                 .map_err(|e| anyhow::anyhow!("failed to build container guard: {}", e))?;
+            content_targets.policies = Some(guard.policy_engine_arc());
+            let watch = ContainerWatch::new(CONTAINER_EVENTS_CAPACITY);
+            spawn_activity_recorder(
+                guard.subscribe_activity(),
+                watch.clone(),
+                shutdown_tx.subscribe(),
+            );
+            container_watch = Some(watch);
+            container_isolation = Some(guard.isolation_control());
+            policy_simulator = Some(guard.policy_simulator());
             plugins.register(Box::new(guard))?;
-            action_rx = rx;
+            action_rx = match (&journal, rx) {
+                (Some(journal), Some(rx)) => {
+                    let (tx, journaled_rx) = mpsc::channel::<ActionEvent>(alert_options.capacity);
+                    journal_tasks.push(event_journal::spawn_journal_relay(
+                        journal.clone(),
+                        std::mem::take(&mut replay.actions),
+                        rx,
+                        tx,
+                        shutdown_tx.subscribe(),
+                    ));
+                    Some(journaled_rx)
+                }
+                (_, rx) => rx,
+            };
         } else {
             // When container guard is disabled, spawn a task to drain alerts (prevents send errors)
             tracing::debug!("container guard disabled, spawning alert drain task");
             let shutdown_rx = shutdown_tx.subscribe();
-            tokio::spawn(drain_alerts(alert_rx, shutdown_rx));
+            tokio::spawn(drain_alerts(alert_rx, recent.clone(), shutdown_rx));
         }
 
+        // Build the scheduler (cron expressions are validated here)
+        let mut scheduler_config = config.scheduler.clone();
+        if scheduler_config.enabled
+            && log_store.is_some()
+            && crate::scheduler::register_log_store_retention(&mut scheduler_config)
+        {
+            tracing::info!(
+                task = crate::scheduler::LOG_STORE_RETENTION_TASK,
+                "registered built-in log store retention task"
+            );
+        }
+        let scheduler = if scheduler_config.enabled && !scheduler_config.tasks.is_empty() {
+            let scheduler = Scheduler::new(&scheduler_config)?;
+            tracing::info!(tasks = scheduler.len(), "scheduler initialized");
+            Some(scheduler)
+        } else {
+            None
+        };
+        let task_status_rx = scheduler.as_ref().map(Scheduler::subscribe);
+
+        // Build the content manager (applied once plugins have started)
+        let content = if config.content.enabled {
+            tracing::info!(source = %config.content.source, "content distribution enabled");
+            let manager =
+                ContentManager::new(config.content.clone(), content_targets.clone()).await?;
+            Some(Arc::new(Mutex::new(manager)))
+        } else {
+            None
+        };
+        let content_rx = match &content {
+            Some(manager) => Some(manager.lock().await.subscribe()),
+            None => None,
+        };
+
+        let (commands, command_rx) = OrchestratorHandle::channel();
+
         tracing::info!(total_plugins = plugins.count(), "orchestrator initialized");
 
         // Record daemon metrics
@@ -195,11 +499,36 @@ impl Orchestrator {
         }
 
         Ok(Self {
-            config,
             plugins,
             shutdown_tx,
             start_time: Instant::now(),
             action_rx,
+            alert_link: Some(alert_link),
+            packet_link: Some(packet_link),
+            link_tasks,
+            gated_alert_tx,
+            alert_gate_task,
+            journaled_alert_tx,
+            journal_tasks,
+            supervisor: Supervisor::new(config.supervisor.clone()),
+            health_tracker: Mutex::new(HealthTracker::new(SystemTime::now())),
+            scheduler,
+            scheduler_handle: None,
+            task_status_rx,
+            leader_tx: config.ha.enabled.then_some(leader_tx),
+            remote_config_digest: None,
+            content,
+            content_rx,
+            content_targets,
+            log_store,
+            recent,
+            ebpf,
+            container_watch,
+            container_isolation,
+            policy_simulator,
+            commands,
+            command_rx: Some(command_rx),
+            config,
         })
     }
 
@@ -213,6 +542,27 @@ impl Orchestrator {
     /// - `SIGTERM` (from systemd, Docker, or `kill`)
     /// - `SIGINT` (Ctrl+C)
     pub async fn run(&mut self) -> Result<()> {
+        // Bind health endpoints before anything else so a port conflict
+        // fails fast without leaving a PID file or started plugins behind.
+        let health_listener = if self.config.health.enabled {
+            let auth = Arc::new(Authorizer::from_config(&self.config.auth)?);
+            let tls = auth::tls_acceptor(&self.config.auth)?;
+            if !auth.is_enabled() {
+                tracing::info!("control API auth disabled; /status is unauthenticated");
+            }
+            let listener = health_server::bind_health_listener(&self.config.health).await?;
+            Some((listener, auth, tls))
+        } else {
+            None
+        };
+        #[cfg(unix)]
+        let control_socket = match &health_listener {
+            Some(_) if !self.config.health.unix_socket.is_empty() => {
+                Some(health_server::bind_control_socket(&self.config.health.unix_socket).await?)
+            }
+            _ => None,
+        };
+
         // Write PID file if configured
         if !self.config.general.pid_file.is_empty() {
             let path = Path::new(&self.config.general.pid_file);
@@ -220,44 +570,68 @@ impl Orchestrator {
         }
 
         // Initialize and start all plugins
-        tracing::info!("initializing all plugins");
-        if let Err(e) = self.plugins.init_all().await {
-            tracing::error!(error = %e, "plugin initialization failed");
+        if let Err(e) = self.start_plugins().await {
+            // Cleanup PID file on startup failure
             if !self.config.general.pid_file.is_empty() {
                 let path = Path::new(&self.config.general.pid_file);
                 remove_pid_file(path);
             }
-            return Err(e.into());
+            return Err(e);
         }
 
-        tracing::info!("starting all plugins");
-        if let Err(e) = self.plugins.start_all().await {
-            // Rollback: stop any plugins that were successfully started
-            tracing::warn!("startup failed, rolling back already-started plugins");
-            if let Err(stop_err) = self.plugins.stop_all().await {
-                tracing::error!(
-                    startup_error = %e,
-                    rollback_error = %stop_err,
-                    "rollback also failed during startup failure cleanup"
-                );
-            }
-
-            // Cleanup PID file on startup failure
-            if !self.config.general.pid_file.is_empty() {
-                let path = Path::new(&self.config.general.pid_file);
-                remove_pid_file(path);
+        // Apply distributed rules/policies over the ones loaded at startup
+        let mut content_task = match self.content.clone() {
+            Some(manager) => {
+                manager.lock().await.start().await;
+                (self.config.content.sync_interval_secs > 0).then(|| {
+                    let interval = Duration::from_secs(self.config.content.sync_interval_secs);
+                    let shutdown_rx = self.shutdown_tx.subscribe();
+                    ContentManager::spawn(manager, interval, shutdown_rx)
+                })
             }
-            return Err(e.into());
-        }
+            None => None,
+        };
 
         // Spawn action logger task
         let mut action_logger_task = if let Some(action_rx) = self.action_rx.take() {
             let shutdown_rx = self.shutdown_tx.subscribe();
-            Some(spawn_action_logger(action_rx, shutdown_rx))
+            Some(spawn_action_logger(
+                action_rx,
+                self.recent.clone(),
+                self.container_watch.clone(),
+                shutdown_rx,
+            ))
         } else {
             None
         };
 
+        // Spawn HA leader election loop
+        let mut leader_task = self.leader_tx.take().map(|leader_tx| {
+            let elector = LeaderElector::new(&self.config.ha);
+            tracing::info!(node_id = %elector.node_id(), "starting leader election");
+            let shutdown_rx = self.shutdown_tx.subscribe();
+            elector.spawn(leader_tx, shutdown_rx)
+        });
+
+        // Spawn remote config refresher
+        let mut remote_config_task = match self.remote_config_digest.take() {
+            Some(digest) if self.config.remote_config.refresh_interval_secs > 0 => {
+                let shutdown_rx = self.shutdown_tx.subscribe();
+                match remote_config::spawn_refresher(
+                    self.config.remote_config.clone(),
+                    digest,
+                    shutdown_rx,
+                ) {
+                    Ok(task) => Some(task),
+                    Err(e) => {
+                        tracing::error!(error = %e, "failed to start remote config refresher");
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
         // Spawn uptime updater task
         let mut uptime_updater_task = if self.config.metrics.enabled {
             let shutdown_rx = self.shutdown_tx.subscribe();
@@ -267,60 +641,530 @@ impl Orchestrator {
             None
         };
 
-        // Main event loop
+        // Spawn health endpoint server
+        let (health_tx, health_rx) = watch::channel(self.snapshot().await);
+
+        // Start recurring tasks
+        if let Some(scheduler) = self.scheduler.take()
+            && let Some(alert_tx) = self.alert_link.as_ref().map(InstrumentedLink::sender)
+        {
+            let ctx = JobContext {
+                sbom: self.config.sbom.clone(),
+                log_store: self.log_store.clone(),
+                alert_tx,
+                snapshot_rx: health_rx.clone(),
+            };
+            self.scheduler_handle = Some(scheduler.spawn(ctx));
+        }
+
+        let control = ControlSources {
+            bundle: Some(self.bundle_sources()),
+            ebpf: self.ebpf.clone(),
+            rules: self.content_targets.rules.clone(),
+            logs: self.log_store.clone(),
+            alerts: Some(self.recent.alert_feed().clone()),
+            container: self.container_watch.clone(),
+            isolation: self.container_isolation.clone(),
+            policy_test: self.policy_simulator.clone(),
+            orchestrator: Some(self.commands.clone()),
+        };
+        #[cfg(unix)]
+        let mut socket_server_task = match (control_socket, &health_listener) {
+            (Some(socket), Some((_, auth, _))) => Some(health_server::spawn_socket_server(
+                socket,
+                self.config.health.clone(),
+                Arc::clone(auth),
+                control.clone(),
+                health_rx.clone(),
+                self.shutdown_tx.subscribe(),
+            )),
+            _ => None,
+        };
+        #[cfg(not(unix))]
+        let mut socket_server_task: Option<tokio::task::JoinHandle<()>> = None;
+        let mut health_server_task = health_listener.map(|(listener, auth, tls)| {
+            let shutdown_rx = self.shutdown_tx.subscribe();
+            health_server::spawn_health_server(
+                listener,
+                self.config.health.clone(),
+                auth,
+                tls,
+                control,
+                health_rx,
+                shutdown_rx,
+            )
+        });
+
+        // Main event loop: refresh health snapshots until a shutdown signal arrives
         tracing::info!("entering main event loop");
-        let signal = wait_for_shutdown_signal().await?;
+        let signal = self.wait_for_shutdown_refreshing_health(&health_tx).await?;
         tracing::info!(signal = signal, "shutdown signal received");
 
-        // Initiate shutdown
-        tracing::info!("broadcasting shutdown signal to all tasks");
-        let _ = self.shutdown_tx.send(());
+        // Phased shutdown: producers -> drain -> consumers -> sinks
+        let background_tasks: Vec<_> = [
+            action_logger_task.take(),
+            uptime_updater_task.take(),
+            leader_task.take(),
+            remote_config_task.take(),
+            content_task.take(),
+            self.alert_gate_task.take(),
+            health_server_task.take(),
+            socket_server_task.take(),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(std::mem::take(&mut self.link_tasks))
+        .chain(std::mem::take(&mut self.journal_tasks))
+        .collect();
+        let result = self.shutdown(background_tasks).await;
+
+        // Remove PID file (even if a shutdown phase failed)
+        if !self.config.general.pid_file.is_empty() {
+            let path = Path::new(&self.config.general.pid_file);
+            remove_pid_file(path);
+        }
+
+        result
+    }
+
+    /// Initialize and start all plugins.
+    ///
+    /// With supervision enabled, each plugin is started individually and
+    /// retried with backoff; a plugin that keeps failing is quarantined and
+    /// startup continues without it. With supervision disabled, the first
+    /// failure aborts startup and rolls back already-started plugins.
+    async fn start_plugins(&mut self) -> Result<()> {
+        if !self.supervisor.enabled() {
+            tracing::info!("initializing all plugins");
+            if let Err(e) = self.plugins.init_all().await {
+                tracing::error!(error = %e, "plugin initialization failed");
+                return Err(e.into());
+            }
+
+            tracing::info!("starting all plugins");
+            if let Err(e) = self.plugins.start_all().await {
+                // Rollback: stop any plugins that were successfully started
+                tracing::warn!("startup failed, rolling back already-started plugins");
+                if let Err(stop_err) = self.plugins.stop_all().await {
+                    tracing::error!(
+                        startup_error = %e,
+                        rollback_error = %stop_err,
+                        "rollback also failed during startup failure cleanup"
+                    );
+                }
+                return Err(e.into());
+            }
+            return Ok(());
+        }
 
-        // Wait for action logger to finish
-        if let Some(task) = action_logger_task.take() {
-            let _ = task.await;
+        tracing::info!("starting all plugins under supervision");
+        let names: Vec<String> = self
+            .plugins
+            .list()
+            .into_iter()
+            .map(|info| info.name.clone())
+            .collect();
+
+        for name in names {
+            let mut attempt = 0u32;
+            loop {
+                match self.start_plugin(&name).await {
+                    Ok(()) => break,
+                    Err(e) => {
+                        attempt = attempt.saturating_add(1);
+                        tracing::warn!(
+                            plugin = %name,
+                            attempt = attempt,
+                            error = %e,
+                            "plugin failed to start"
+                        );
+                        if self.supervisor.record_failure(&name, &e.to_string()) {
+                            self.quarantine(&name).await;
+                            break;
+                        }
+                        tokio::time::sleep(self.supervisor.backoff(attempt)).await;
+                    }
+                }
+            }
         }
 
-        // Wait for uptime updater to finish
-        if let Some(task) = uptime_updater_task.take() {
-            let _ = task.await;
+        Ok(())
+    }
+
+    /// Initialize (if needed) and start a single plugin.
+    ///
+    /// Plugins in `Created` or `Failed` state are re-initialized first so a
+    /// failed plugin can be retried.
+    async fn start_plugin(&mut self, name: &str) -> Result<(), IronpostError> {
+        let plugin = self
+            .plugins
+            .get_mut(name)
+            .ok_or_else(|| PluginError::NotFound {
+                name: name.to_owned(),
+            })?;
+        if matches!(plugin.state(), PluginState::Created | PluginState::Failed) {
+            plugin.init().await?;
         }
+        plugin.start().await
+    }
 
-        // Stop all modules
-        self.shutdown().await?;
+    /// Detect crashed plugins and restart or quarantine them.
+    ///
+    /// Called on every health tick. A plugin in `Failed` state counts as a
+    /// crash; each failed restart attempt counts as another failure.
+    async fn supervise(&mut self) {
+        if !self.supervisor.enabled() {
+            return;
+        }
 
-        // Remove PID file
-        if !self.config.general.pid_file.is_empty() {
-            let path = Path::new(&self.config.general.pid_file);
-            remove_pid_file(path);
+        let crashed: Vec<String> = self
+            .plugins
+            .list()
+            .into_iter()
+            .map(|info| info.name.clone())
+            .filter(|name| {
+                !self.supervisor.is_quarantined(name)
+                    && self
+                        .plugins
+                        .get(name)
+                        .is_some_and(|p| p.state() == PluginState::Failed)
+            })
+            .collect();
+
+        for name in crashed {
+            tracing::warn!(plugin = %name, "plugin crashed, attempting restart");
+            if self
+                .supervisor
+                .record_failure(&name, "plugin entered failed state")
+            {
+                self.quarantine(&name).await;
+                continue;
+            }
+            if let Err(e) = self.start_plugin(&name).await {
+                tracing::warn!(plugin = %name, error = %e, "plugin restart failed");
+                if self.supervisor.record_failure(&name, &e.to_string()) {
+                    self.quarantine(&name).await;
+                }
+            } else {
+                tracing::info!(plugin = %name, "plugin restarted");
+                self.record_restart(&name);
+            }
+        }
+    }
+
+    /// Take a plugin out of service after the supervisor quarantined it.
+    ///
+    /// The plugin is stopped if still running and an operational alert is
+    /// raised: it is logged and recorded in the alert feed (`/alerts`) and
+    /// support bundles. The alert is deliberately not sent on the alert
+    /// channel: container-guard consumes that channel and could react to it
+    /// with isolation actions.
+    async fn quarantine(&mut self, name: &str) {
+        if let Some(plugin) = self.plugins.get_mut(name)
+            && plugin.state() == PluginState::Running
+            && let Err(e) = plugin.stop().await
+        {
+            tracing::warn!(plugin = %name, error = %e, "failed to stop quarantined plugin");
+        }
+
+        let Some(record) = self.supervisor.quarantine_record(name) else {
+            return;
+        };
+        let alert = quarantine_alert(record, self.config.supervisor.failure_window_secs);
+        tracing::error!(
+            alert_id = %alert.id,
+            plugin = %record.name,
+            failures = record.failures,
+            reason = %record.reason,
+            "{}",
+            alert
+        );
+        self.recent.record_alert(&alert).await;
+    }
+
+    /// Lift quarantine for a module and try to start it again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module is not quarantined or fails to start.
+    /// A start failure counts toward the crash-loop window again.
+    pub async fn unquarantine(&mut self, name: &str) -> Result<()> {
+        if !self.supervisor.unquarantine(name) {
+            return Err(anyhow::anyhow!("module '{}' is not quarantined", name));
         }
+        tracing::info!(plugin = %name, "quarantine lifted, restarting module");
 
+        if let Err(e) = self.start_plugin(name).await {
+            if self.supervisor.record_failure(name, &e.to_string()) {
+                self.quarantine(name).await;
+            }
+            return Err(anyhow::anyhow!(
+                "failed to restart module '{}': {}",
+                name,
+                e
+            ));
+        }
+        self.record_restart(name);
         Ok(())
     }
 
-    /// Perform graceful shutdown of all plugins.
+    /// Count a successful restart of `name` in the health report.
+    fn record_restart(&mut self, name: &str) {
+        self.health_tracker.get_mut().record_restart(name);
+    }
+
+    /// Modules currently quarantined.
+    pub fn quarantined_modules(&self) -> Vec<QuarantineRecord> {
+        self.supervisor.quarantined().into_iter().cloned().collect()
+    }
+
+    /// Execute a control API command on the main loop.
+    pub async fn handle_command(&mut self, command: OrchestratorCommand) {
+        match command {
+            OrchestratorCommand::Quarantined { reply } => {
+                let _ = reply.send(self.quarantined_modules());
+            }
+            OrchestratorCommand::Unquarantine { module, reply } => {
+                let result = if self.supervisor.is_quarantined(&module) {
+                    self.unquarantine(&module)
+                        .await
+                        .map_err(|e| CommandError::failed(e.to_string()))
+                } else {
+                    Err(CommandError::not_found(format!(
+                        "module '{}' is not quarantined",
+                        module
+                    )))
+                };
+                let _ = reply.send(result);
+            }
+            OrchestratorCommand::RollbackContent { version, reply } => {
+                let result = if self.content.is_some() {
+                    self.rollback_content(version.as_deref())
+                        .await
+                        .map_err(|e| CommandError::conflict(e.to_string()))
+                } else {
+                    Err(CommandError::not_found(
+                        "content distribution is not enabled",
+                    ))
+                };
+                let _ = reply.send(result);
+            }
+        }
+    }
+
+    /// Wait for a shutdown signal while periodically publishing health snapshots.
+    ///
+    /// Snapshots feed the `/healthz` and `/readyz` endpoints. If the loop stops
+    /// refreshing, liveness fails once the snapshot becomes stale. Control API
+    /// commands are executed between ticks, and a fresh snapshot is published
+    /// after each one.
+    async fn wait_for_shutdown_refreshing_health(
+        &mut self,
+        health_tx: &watch::Sender<HealthSnapshot>,
+    ) -> Result<&'static str> {
+        let shutdown_signal = wait_for_shutdown_signal();
+        tokio::pin!(shutdown_signal);
+        let mut command_rx = self.command_rx.take();
+
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.health.check_interval_secs));
+        // The initial snapshot was published at startup; skip the immediate tick.
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                signal = &mut shutdown_signal => return signal,
+                _ = interval.tick() => {
+                    self.supervise().await;
+                    let snapshot = self.snapshot().await;
+                    tracing::debug!(status = %snapshot.health.status, "health snapshot refreshed");
+                    health_tx.send_replace(snapshot);
+                }
+                Some(command) = next_command(&mut command_rx) => {
+                    self.handle_command(command).await;
+                    health_tx.send_replace(self.snapshot().await);
+                }
+            }
+        }
+    }
+
+    /// Perform phased graceful shutdown.
     ///
-    /// Stops plugins in registration order (producers first, consumers last).
-    /// This allows consumers to drain remaining events from their channels.
-    async fn shutdown(&mut self) -> Result<()> {
-        tracing::info!("stopping all plugins");
-        self.plugins.stop_all().await.map_err(|e| e.into())
+    /// Every phase runs even if an earlier one fails or times out, so that
+    /// consumers and sinks still get a chance to flush. Errors from all
+    /// phases are collected and returned together.
+    async fn shutdown(&mut self, background_tasks: Vec<tokio::task::JoinHandle<()>>) -> Result<()> {
+        let timeouts = self.config.shutdown.clone();
+        let mut errors = Vec::new();
+
+        // Phase 1: stop producers (log pipeline flushes its buffer here).
+        // Scheduled jobs may emit alerts, so the scheduler stops first.
+        tracing::info!(
+            phase = "stop_producers",
+            timeout_secs = timeouts.stop_producers_timeout_secs,
+            "shutdown phase started"
+        );
+        let scheduler = self.scheduler_handle.take();
+        let plugins = &mut self.plugins;
+        let stop_producers = async move {
+            if let Some(scheduler) = scheduler {
+                scheduler.stop().await;
+            }
+            plugins.stop_matching(is_producer).await
+        };
+        if let Err(e) = run_phase(
+            "stop_producers",
+            timeouts.stop_producers_timeout_secs,
+            stop_producers,
+        )
+        .await
+        {
+            errors.push(e);
+        }
+
+        // Phase 2: drain the alert channel into consumers
+        tracing::info!(
+            phase = "drain",
+            timeout_secs = timeouts.drain_timeout_secs,
+            "shutdown phase started"
+        );
+        // Producers have stopped, so nothing reads the packet link anymore
+        self.packet_link = None;
+        if let Some(alert_link) = self.alert_link.take() {
+            let drain_timeout = Duration::from_secs(timeouts.drain_timeout_secs);
+            let deadline = Instant::now() + drain_timeout;
+            let mut remaining = wait_for_link_drain(&alert_link, drain_timeout).await;
+            // Alerts relayed by the gate must reach the consumer as well
+            if let Some(gated_alert_tx) = self.gated_alert_tx.take() {
+                let left = deadline.saturating_duration_since(Instant::now());
+                remaining += wait_for_drain(&gated_alert_tx, left).await;
+            }
+            if let Some(journaled_alert_tx) = self.journaled_alert_tx.take() {
+                let left = deadline.saturating_duration_since(Instant::now());
+                remaining += wait_for_drain(&journaled_alert_tx, left).await;
+            }
+            if remaining > 0 {
+                tracing::warn!(
+                    remaining = remaining,
+                    "drain timed out, queued alerts will be dropped"
+                );
+                errors.push(format!("drain: timed out with {} alerts queued", remaining));
+            } else {
+                tracing::debug!("alert channel drained");
+            }
+        }
+
+        // Phase 3: stop consumers
+        tracing::info!(
+            phase = "stop_consumers",
+            timeout_secs = timeouts.stop_consumers_timeout_secs,
+            "shutdown phase started"
+        );
+        if let Err(e) = run_phase(
+            "stop_consumers",
+            timeouts.stop_consumers_timeout_secs,
+            self.plugins.stop_matching(|info| !is_producer(info)),
+        )
+        .await
+        {
+            errors.push(e);
+        }
+
+        // Phase 4: flush sinks (action logger drains remaining events on shutdown)
+        tracing::info!(
+            phase = "flush",
+            timeout_secs = timeouts.flush_timeout_secs,
+            "shutdown phase started"
+        );
+        let _ = self.shutdown_tx.send(());
+        let flush = async {
+            for task in background_tasks {
+                let _ = task.await;
+            }
+            Ok::<(), ironpost_core::error::IronpostError>(())
+        };
+        if let Err(e) = run_phase("flush", timeouts.flush_timeout_secs, flush).await {
+            errors.push(e);
+        }
+
+        if errors.is_empty() {
+            tracing::info!("graceful shutdown completed");
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "shutdown incomplete: {}",
+                errors.join("; ")
+            ))
+        }
+    }
+
+    /// Record the digest of the remote configuration this instance runs with.
+    ///
+    /// Enables periodic refresh when `remote_config.refresh_interval_secs > 0`.
+    pub fn set_remote_config_digest(&mut self, digest: String) {
+        self.remote_config_digest = Some(digest);
     }
 
     /// Get the current aggregated health status.
-    #[allow(dead_code)] // Future health endpoint
+    #[allow(dead_code)] // Public API for tests
     pub async fn health(&self) -> DaemonHealth {
+        self.collect_health().await.0
+    }
+
+    /// Collect the aggregated health status and the structured health report.
+    async fn collect_health(&self) -> (DaemonHealth, HealthReport) {
         let statuses = self.plugins.health_check_all().await;
+        let observations: Vec<ModuleObservation> = statuses
+            .iter()
+            .map(|(name, state, status)| ModuleObservation {
+                name: name.clone(),
+                state: *state,
+                status: status.clone(),
+                quarantine_reason: self
+                    .supervisor
+                    .quarantine_record(name)
+                    .map(|record| format!("quarantined: {}", record.reason)),
+            })
+            .collect();
         let modules: Vec<ModuleHealth> = statuses
             .into_iter()
-            .map(|(name, _plugin_state, status)| ModuleHealth {
-                name,
-                enabled: true, // All registered plugins are enabled
-                status,
+            .map(|(name, _plugin_state, status)| {
+                match self.supervisor.quarantine_record(&name) {
+                    // Quarantined plugins are reported but excluded from aggregation
+                    Some(record) => ModuleHealth {
+                        status: HealthStatus::Unhealthy(format!("quarantined: {}", record.reason)),
+                        name,
+                        enabled: false,
+                    },
+                    None => ModuleHealth {
+                        name,
+                        enabled: true,
+                        status,
+                    },
+                }
             })
             .collect();
 
-        let overall_status = aggregate_status(&modules);
+        let mut overall_status = aggregate_status(&modules);
+
+        // Any quarantined module degrades the daemon as a whole
+        let mut quarantined: Vec<&str> = self
+            .supervisor
+            .quarantined()
+            .into_iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        if !quarantined.is_empty() {
+            quarantined.sort_unstable();
+            let note = format!("quarantined: {}", quarantined.join(", "));
+            overall_status = match overall_status {
+                HealthStatus::Healthy => HealthStatus::Degraded(note),
+                HealthStatus::Degraded(reason) => {
+                    HealthStatus::Degraded(format!("{}; {}", reason, note))
+                }
+                unhealthy => unhealthy,
+            };
+        }
         let uptime_secs = self.start_time.elapsed().as_secs();
 
         // Update uptime metric
@@ -330,11 +1174,122 @@ impl Orchestrator {
             metrics::gauge!(m::DAEMON_UPTIME_SECONDS).set(uptime_secs as f64);
         }
 
-        DaemonHealth {
+        let report = self.health_tracker.lock().await.report(
+            overall_status.clone(),
+            observations,
+            SystemTime::now(),
+        );
+
+        let health = DaemonHealth {
             status: overall_status,
             uptime_secs,
             modules,
+        };
+        (health, report)
+    }
+
+    /// Collect health, scheduled task status and resource usage.
+    async fn snapshot(&self) -> HealthSnapshot {
+        let (health, report) = self.collect_health().await;
+        let resources = self.resource_report().await;
+        if self.config.metrics.enabled {
+            resources.record_metrics();
+        }
+        HealthSnapshot::new(health)
+            .with_report(report)
+            .with_tasks(self.scheduled_tasks())
+            .with_resources(resources)
+            .with_content(self.content_status())
+    }
+
+    /// Build a support bundle tarball (gzip-compressed) of the current state.
+    #[allow(dead_code)] // Public API for control/status endpoints
+    pub async fn support_bundle(&self) -> Result<Vec<u8>> {
+        let snapshot = self.snapshot().await;
+        self.bundle_sources().collect(&snapshot).await.to_tar_gz()
+    }
+
+    fn bundle_sources(&self) -> BundleSources {
+        BundleSources {
+            config: self.config.clone(),
+            recent: self.recent.clone(),
+            targets: self.content_targets.clone(),
+        }
+    }
+
+    /// Collect per-module resource usage plus process and runtime figures.
+    ///
+    /// The daemon itself is reported under [`MODULE_DAEMON`] as the owner of
+    /// the shared alert channels and the alert gate relay.
+    pub async fn resource_report(&self) -> ResourceReport {
+        let mut modules: Vec<ModuleUsage> = self
+            .plugins
+            .resource_usage_all()
+            .await
+            .into_iter()
+            .map(|(name, usage)| ModuleUsage { name, usage })
+            .collect();
+
+        if let Some(alert_link) = &self.alert_link {
+            let mut channels = vec![alert_link.usage()];
+            if let Some(packet_link) = &self.packet_link {
+                channels.push(packet_link.usage());
+            }
+            if let Some(gated_alert_tx) = &self.gated_alert_tx {
+                channels.push(ChannelUsage::from_sender("gated_alerts", gated_alert_tx));
+            }
+            if let Some(journaled_alert_tx) = &self.journaled_alert_tx {
+                channels.push(ChannelUsage::from_sender(
+                    "journaled_alerts",
+                    journaled_alert_tx,
+                ));
+            }
+            let depth: usize = channels.iter().map(|c| c.depth).sum();
+            let memory_bytes =
+                ResourceUsage::estimate_bytes(depth, std::mem::size_of::<AlertEvent>());
+            modules.push(ModuleUsage {
+                name: MODULE_DAEMON.to_owned(),
+                usage: ResourceUsage {
+                    tasks: usize::from(self.alert_gate_task.is_some())
+                        + self.link_tasks.len()
+                        + self.journal_tasks.len(),
+                    channels,
+                    memory_bytes,
+                },
+            });
         }
+
+        ResourceReport::collect(modules)
+    }
+
+    /// Current rule/policy content status (`None` when disabled).
+    pub fn content_status(&self) -> Option<ContentStatus> {
+        self.content_rx.as_ref().map(|rx| rx.borrow().clone())
+    }
+
+    /// Roll distributed content back to a stored version.
+    ///
+    /// With `None`, rolls back to the version before the current one.
+    /// Returns the version now applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if content distribution is disabled or the version
+    /// cannot be applied.
+    pub async fn rollback_content(&self, version: Option<&str>) -> Result<String> {
+        let manager = self
+            .content
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("content distribution is not enabled"))?;
+        manager.lock().await.rollback(version).await
+    }
+
+    /// Current status of scheduled tasks (empty if none are configured).
+    pub fn scheduled_tasks(&self) -> Vec<TaskStatus> {
+        self.task_status_rx
+            .as_ref()
+            .map(|rx| rx.borrow().clone())
+            .unwrap_or_default()
     }
 
     /// Get a reference to the loaded configuration.
@@ -344,6 +1299,104 @@ impl Orchestrator {
     }
 }
 
+/// Next control API command; pending forever once the receiver is gone.
+async fn next_command(
+    rx: &mut Option<mpsc::Receiver<OrchestratorCommand>>,
+) -> Option<OrchestratorCommand> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Build the operational alert emitted when a module is quarantined.
+fn quarantine_alert(record: &QuarantineRecord, window_secs: u64) -> AlertEvent {
+    let alert = Alert {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: format!("Module quarantined: {}", record.name),
+        description: format!(
+            "{} failed {} times within {}s and was quarantined (last error: {})",
+            record.name, record.failures, window_secs, record.reason
+        ),
+        severity: Severity::High,
+        rule_name: "daemon_module_quarantine".to_owned(),
+        source_ip: None,
+        target_ip: None,
+        created_at: std::time::SystemTime::now(),
+    };
+    AlertEvent::with_source(alert, Severity::High, MODULE_DAEMON)
+}
+
+/// Whether a plugin produces events (stopped before the drain phase).
+///
+/// Enforcers consume alerts and are stopped after the drain phase; every
+/// other plugin type is treated as a producer.
+fn is_producer(info: &PluginInfo) -> bool {
+    info.plugin_type != PluginType::Enforcer
+}
+
+/// Run a shutdown phase bounded by `timeout_secs`.
+///
+/// Returns a human-readable error if the phase fails or times out.
+async fn run_phase<F>(name: &str, timeout_secs: u64, phase: F) -> std::result::Result<(), String>
+where
+    F: std::future::Future<Output = std::result::Result<(), ironpost_core::error::IronpostError>>,
+{
+    match tokio::time::timeout(Duration::from_secs(timeout_secs), phase).await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => {
+            tracing::error!(phase = name, error = %e, "shutdown phase failed");
+            Err(format!("{}: {}", name, e))
+        }
+        Err(_) => {
+            tracing::warn!(
+                phase = name,
+                timeout_secs = timeout_secs,
+                "shutdown phase timed out"
+            );
+            Err(format!("{}: timed out after {}s", name, timeout_secs))
+        }
+    }
+}
+
+/// Wait until the channel behind `tx` is empty or `timeout` elapses.
+///
+/// Returns the number of events still queued (0 when fully drained).
+/// A closed channel is reported as drained since nothing can consume it.
+async fn wait_for_drain<T>(tx: &mpsc::Sender<T>, timeout: Duration) -> usize {
+    wait_until_empty(
+        || {
+            if tx.is_closed() {
+                0
+            } else {
+                tx.max_capacity().saturating_sub(tx.capacity())
+            }
+        },
+        timeout,
+    )
+    .await
+}
+
+/// Wait until `link` (including any relay buffer) is empty or `timeout` elapses.
+async fn wait_for_link_drain<T>(link: &InstrumentedLink<T>, timeout: Duration) -> usize {
+    wait_until_empty(|| link.depth(), timeout).await
+}
+
+/// Poll `queued` until it reports 0 or `timeout` elapses.
+async fn wait_until_empty(queued: impl Fn() -> usize, timeout: Duration) -> usize {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        let remaining = queued();
+        if remaining == 0 {
+            return 0;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return remaining;
+        }
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+}
+
 /// Wait for a shutdown signal (SIGTERM or SIGINT).
 ///
 /// Returns the name of the signal that triggered the shutdown.
@@ -458,9 +1511,10 @@ fn remove_pid_file(path: &Path) {
 ///
 /// This prevents alert producers (log pipeline, SBOM scanner) from encountering
 /// send errors when the container guard is not running. Alerts are logged but
-/// not acted upon.
+/// not acted upon; the most recent ones are kept in `recent`.
 async fn drain_alerts(
     mut alert_rx: mpsc::Receiver<AlertEvent>,
+    recent: RecentEvents,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) {
     loop {
@@ -474,6 +1528,7 @@ async fn drain_alerts(
                             severity = %alert.severity,
                             "alert received but container guard disabled (alert dropped)"
                         );
+                        recent.record_alert(&alert).await;
                     }
                     None => {
                         tracing::debug!("alert channel closed, exiting drain task");
@@ -492,9 +1547,12 @@ async fn drain_alerts(
 /// Spawn a background task that logs received ActionEvents.
 ///
 /// ActionEvents represent completed isolation actions from container-guard.
-/// This task logs them for audit purposes.
+/// This task logs them for audit purposes, keeps the most recent ones
+/// in `recent` and appends them to the container event feed.
 fn spawn_action_logger(
     mut action_rx: mpsc::Receiver<ActionEvent>,
+    recent: RecentEvents,
+    watch: Option<ContainerWatch>,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
@@ -503,14 +1561,11 @@ fn spawn_action_logger(
                 action_result = action_rx.recv() => {
                     match action_result {
                         Some(action) => {
-                            tracing::info!(
-                                action_id = %action.id,
-                                action_type = %action.action_type,
-                                target = %action.target,
-                                success = action.success,
-                                timestamp = ?action.metadata.timestamp,
-                                "isolation action completed"
-                            );
+                            log_action(&action);
+                            recent.record_action(&action).await;
+                            if let Some(watch) = &watch {
+                                watch.record(GuardActivity::from(&action)).await;
+                            }
                         }
                         None => {
                             tracing::debug!("action channel closed, exiting logger");
@@ -519,7 +1574,17 @@ fn spawn_action_logger(
                     }
                 }
                 _ = shutdown_rx.recv() => {
-                    tracing::debug!("action logger shutting down");
+                    // Flush actions that were queued before shutdown
+                    let mut flushed = 0usize;
+                    while let Ok(action) = action_rx.try_recv() {
+                        log_action(&action);
+                        recent.record_action(&action).await;
+                        if let Some(watch) = &watch {
+                            watch.record(GuardActivity::from(&action)).await;
+                        }
+                        flushed += 1;
+                    }
+                    tracing::debug!(flushed = flushed, "action logger shutting down");
                     break;
                 }
             }
@@ -527,6 +1592,19 @@ fn spawn_action_logger(
     })
 }
 
+/// Log a single completed action event.
+fn log_action(action: &ActionEvent) {
+    tracing::info!(
+        action_id = %action.id,
+        action_type = %action.action_type,
+        target = %action.target,
+        success = action.success,
+        simulated = action.simulated,
+        timestamp = ?action.metadata.timestamp,
+        "isolation action completed"
+    );
+}
+
 /// Record daemon-level metrics (build info, plugins registered).
 ///
 /// This should be called once during orchestrator initialization.
@@ -702,7 +1780,7 @@ mod tests {
         let (action_tx, action_rx) = mpsc::channel(16);
         let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
 
-        let task = spawn_action_logger(action_rx, shutdown_rx);
+        let task = spawn_action_logger(action_rx, RecentEvents::new(16), None, shutdown_rx);
 
         // When: Sending an action event
         let action = ActionEvent {
@@ -715,6 +1793,7 @@ mod tests {
             action_type: "isolate".to_string(),
             target: "container123".to_string(),
             success: true,
+            simulated: false,
         };
         action_tx.send(action).await.expect("should send action");
 
@@ -732,7 +1811,7 @@ mod tests {
         let (_action_tx, action_rx) = mpsc::channel::<ActionEvent>(16);
         let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
 
-        let task = spawn_action_logger(action_rx, shutdown_rx);
+        let task = spawn_action_logger(action_rx, RecentEvents::new(16), None, shutdown_rx);
 
         // When: Sending shutdown signal
         let _ = shutdown_tx.send(());
@@ -744,4 +1823,145 @@ mod tests {
             "action logger should shut down within timeout"
         );
     }
+
+    fn test_action(target: &str) -> ActionEvent {
+        ActionEvent::new("container_pause", target, true)
+    }
+
+    #[tokio::test]
+    async fn test_spawn_action_logger_flushes_queued_events_on_shutdown() {
+        // Given: Actions queued before the logger observes them
+        let (action_tx, action_rx) = mpsc::channel(16);
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        for i in 0..5 {
+            action_tx
+                .send(test_action(&format!("c{i}")))
+                .await
+                .expect("should send action");
+        }
+        let _ = shutdown_tx.send(());
+
+        // When: Running the logger after shutdown was already signalled
+        let task = spawn_action_logger(action_rx, RecentEvents::new(16), None, shutdown_rx);
+        let result = tokio::time::timeout(Duration::from_secs(1), task).await;
+
+        // Then: The queue is fully drained before exit
+        assert!(result.is_ok(), "logger should exit after flushing");
+        assert_eq!(action_tx.capacity(), action_tx.max_capacity());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_drain_returns_zero_when_consumed() {
+        // Given: A channel with queued events and an active consumer
+        let (tx, mut rx) = mpsc::channel::<ActionEvent>(16);
+        for i in 0..3 {
+            tx.send(test_action(&format!("c{i}"))).await.expect("send");
+        }
+        let consumer = tokio::spawn(async move { while rx.recv().await.is_some() {} });
+
+        // When: Waiting for drain
+        let remaining = wait_for_drain(&tx, Duration::from_secs(1)).await;
+
+        // Then: Nothing remains queued
+        assert_eq!(remaining, 0);
+        drop(tx);
+        let _ = consumer.await;
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_drain_times_out_without_consumer() {
+        // Given: Queued events and a receiver that never reads
+        let (tx, _rx) = mpsc::channel::<ActionEvent>(16);
+        tx.send(test_action("c0")).await.expect("send");
+        tx.send(test_action("c1")).await.expect("send");
+
+        // When: Waiting with a short timeout
+        let remaining = wait_for_drain(&tx, Duration::from_millis(100)).await;
+
+        // Then: The queued count is reported
+        assert_eq!(remaining, 2);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_link_drain_counts_relay_buffer() {
+        // Given: A drop-oldest link whose consumer has not read yet
+        let dir = tempfile::tempdir().expect("tempdir");
+        let options = LinkOptions {
+            capacity: 2,
+            policy: ironpost_core::channel::OverflowPolicy::DropOldest,
+            spill_dir: dir.path().to_path_buf(),
+            max_spill_bytes: 0,
+        };
+        let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let (link, mut rx, _task) = instrumented_channel::<u32>("test", &options, shutdown_rx);
+        for i in 0..4 {
+            link.sender().send(i).await.expect("send");
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // When: Waiting without a consumer
+        let remaining = wait_for_link_drain(&link, Duration::from_millis(100)).await;
+
+        // Then: Messages held by the relay are reported
+        assert_eq!(remaining, 4);
+
+        // When: The consumer catches up
+        let consumer = tokio::spawn(async move { while rx.recv().await.is_some() {} });
+        let remaining = wait_for_link_drain(&link, Duration::from_secs(1)).await;
+
+        // Then: The link is fully drained
+        assert_eq!(remaining, 0);
+        consumer.abort();
+    }
+
+    #[tokio::test]
+    async fn test_run_phase_reports_timeout() {
+        // Given: A phase that never completes
+        let phase = async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok(())
+        };
+
+        // When: Running it with a 1s timeout
+        let result = run_phase("stop_consumers", 1, phase).await;
+
+        // Then: A timeout error naming the phase is returned
+        let err = result.expect_err("phase should time out");
+        assert!(err.contains("stop_consumers"));
+        assert!(err.contains("timed out"));
+    }
+
+    #[test]
+    fn test_is_producer_excludes_enforcers() {
+        let info = |plugin_type| PluginInfo {
+            name: "p".to_owned(),
+            version: "0.1.0".to_owned(),
+            description: String::new(),
+            plugin_type,
+        };
+        assert!(is_producer(&info(PluginType::Detector)));
+        assert!(is_producer(&info(PluginType::LogPipeline)));
+        assert!(is_producer(&info(PluginType::Scanner)));
+        assert!(!is_producer(&info(PluginType::Enforcer)));
+    }
+
+    #[test]
+    fn test_quarantine_alert_describes_module() {
+        // Given: A quarantine record
+        let record = QuarantineRecord {
+            name: "log-pipeline".to_owned(),
+            reason: "bind failed".to_owned(),
+            failures: 3,
+            since: std::time::SystemTime::now(),
+        };
+
+        // When: Building the alert
+        let event = quarantine_alert(&record, 300);
+
+        // Then: The alert names the module and originates from the daemon
+        assert_eq!(event.metadata.source_module, MODULE_DAEMON);
+        assert!(event.alert.title.contains("log-pipeline"));
+        assert!(event.alert.description.contains("3 times within 300s"));
+        assert_eq!(event.severity, Severity::High);
+    }
 }
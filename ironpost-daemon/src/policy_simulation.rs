@@ -0,0 +1,271 @@
+//! Container policy simulation for the control API.
+//!
+//! `POST /container/policies/test` (`read-only` role when `[auth]` is
+//! enabled) evaluates a sample alert against the guard's live policy engine
+//! and container inventory and reports which policy would match and which
+//! action would run. Nothing is executed and nothing is written to the
+//! isolation audit trail, so the endpoint is safe to call on a production
+//! daemon.
+
+use std::net::IpAddr;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use ironpost_container_guard::{
+    ContainerSelector, DockerClient, PolicySimulation, PolicySimulator, SimulatedMatch,
+};
+use ironpost_core::event::{AlertEvent, MODULE_CONTAINER_GUARD};
+use ironpost_core::types::{Alert, Severity};
+
+use crate::container_isolation::error_status;
+
+/// Path of the policy simulation endpoint.
+pub const CONTAINER_POLICY_TEST_PATH: &str = "/container/policies/test";
+
+/// Container name used for a hypothetical container given only an image.
+const DEFAULT_SIMULATED_NAME: &str = "simulated";
+
+/// Sample alert to evaluate.
+///
+/// Unknown fields are ignored, so an alert printed by
+/// `ironpost alerts list --output json` can be submitted as is.
+#[derive(Debug, Deserialize)]
+pub struct SampleAlert {
+    /// Alert severity (case-insensitive, e.g. `critical` or `High`).
+    #[serde(deserialize_with = "deserialize_severity")]
+    pub severity: Severity,
+    /// Alert title.
+    #[serde(default)]
+    pub title: String,
+    /// Alert description.
+    #[serde(default)]
+    pub description: String,
+    /// Detection rule name.
+    #[serde(default, alias = "rule_name")]
+    pub rule: String,
+    /// Source IP address.
+    #[serde(default)]
+    pub source_ip: Option<IpAddr>,
+    /// Target IP address.
+    #[serde(default)]
+    pub target_ip: Option<IpAddr>,
+}
+
+impl SampleAlert {
+    /// Build the alert event the guard would receive.
+    pub fn to_alert_event(&self) -> AlertEvent {
+        let title = if self.title.is_empty() {
+            "policy simulation".to_owned()
+        } else {
+            self.title.clone()
+        };
+        let alert = Alert {
+            id: "simulated".to_owned(),
+            title,
+            description: self.description.clone(),
+            severity: self.severity,
+            rule_name: self.rule.clone(),
+            source_ip: self.source_ip,
+            target_ip: self.target_ip,
+            created_at: SystemTime::now(),
+        };
+        AlertEvent::with_source(alert, self.severity, MODULE_CONTAINER_GUARD)
+    }
+}
+
+fn deserialize_severity<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Severity, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    Severity::from_str_loose(&value)
+        .ok_or_else(|| serde::de::Error::custom(format!("unknown severity '{}'", value)))
+}
+
+/// Body of `POST /container/policies/test`.
+#[derive(Debug, Deserialize)]
+pub struct PolicyTestRequest {
+    /// Alert to evaluate.
+    pub alert: SampleAlert,
+    /// Running container name, ID or unique ID prefix; with `image`, the
+    /// name of a hypothetical container. Every running container when unset.
+    #[serde(default)]
+    pub container: Option<String>,
+    /// Image of a hypothetical container that does not need to be running.
+    #[serde(default)]
+    pub image: Option<String>,
+}
+
+impl PolicyTestRequest {
+    /// The containers this request evaluates.
+    pub fn selector(&self) -> ContainerSelector {
+        match (&self.container, &self.image) {
+            (name, Some(image)) => ContainerSelector::Hypothetical {
+                name: name
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_SIMULATED_NAME.to_owned()),
+                image: image.clone(),
+            },
+            (Some(target), None) => ContainerSelector::Target(target.clone()),
+            (None, None) => ContainerSelector::All,
+        }
+    }
+}
+
+/// A container matched by a policy.
+#[derive(Debug, Serialize)]
+pub struct PolicyTestMatch {
+    /// Full container ID (empty for a hypothetical container).
+    pub container_id: String,
+    /// Container name.
+    pub container_name: String,
+    /// Container image.
+    pub container_image: String,
+    /// Matched policy ID.
+    pub policy_id: String,
+    /// Matched policy name.
+    pub policy_name: String,
+    /// Action the policy specifies (e.g. `pause`, `network_disconnect(bridge)`).
+    pub action: String,
+    /// Whether the guard would act on this container (first match only).
+    pub applied: bool,
+    /// Whether the container already has an active isolation.
+    pub already_isolated: bool,
+}
+
+impl From<SimulatedMatch> for PolicyTestMatch {
+    fn from(m: SimulatedMatch) -> Self {
+        Self {
+            container_id: m.container_id,
+            container_name: m.container_name,
+            container_image: m.container_image,
+            policy_id: m.policy_id,
+            policy_name: m.policy_name,
+            action: m.action.to_string(),
+            applied: m.applied,
+            already_isolated: m.already_isolated,
+        }
+    }
+}
+
+/// Response of `POST /container/policies/test`.
+#[derive(Debug, Serialize)]
+pub struct PolicyTestResponse {
+    /// Severity the alert was evaluated with.
+    pub severity: Severity,
+    /// Number of containers evaluated.
+    pub containers_evaluated: usize,
+    /// Number of policies loaded in the live engine.
+    pub policies_loaded: usize,
+    /// Whether the guard isolates on a match (`[container] auto_isolate`).
+    pub auto_isolate: bool,
+    /// Whether isolation actions are only simulated (`[general] dry_run`).
+    pub dry_run: bool,
+    /// Whether this instance is an HA standby (the leader isolates).
+    pub standby: bool,
+    /// Containers matched by a policy, in evaluation order.
+    pub matches: Vec<PolicyTestMatch>,
+}
+
+impl PolicyTestResponse {
+    fn new(severity: Severity, simulation: PolicySimulation) -> Self {
+        Self {
+            severity,
+            containers_evaluated: simulation.containers_evaluated,
+            policies_loaded: simulation.policies_loaded,
+            auto_isolate: simulation.auto_isolate,
+            dry_run: simulation.dry_run,
+            standby: simulation.standby,
+            matches: simulation.matches.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Handle an authorized policy simulation request.
+///
+/// Only `POST` is accepted. Returns the HTTP status code and JSON body.
+pub async fn handle_policy_test_request<D: DockerClient>(
+    simulator: &PolicySimulator<D>,
+    method: &str,
+    body: &[u8],
+) -> (u16, String) {
+    if method != "POST" {
+        return (405, r#"{"error":"method not allowed"}"#.to_owned());
+    }
+    let request = match serde_json::from_slice::<PolicyTestRequest>(body) {
+        Ok(request) => request,
+        Err(e) => {
+            let message = format!("invalid request body: {}", e);
+            return (400, serde_json::json!({ "error": message }).to_string());
+        }
+    };
+
+    let alert = request.alert.to_alert_event();
+    match simulator.simulate(&alert, &request.selector()).await {
+        Ok(simulation) => {
+            let response = PolicyTestResponse::new(alert.severity, simulation);
+            let body = serde_json::to_string(&response)
+                .unwrap_or_else(|_| r#"{"error":"serialization error"}"#.to_owned());
+            (200, body)
+        }
+        Err(e) => (
+            error_status(&e),
+            serde_json::json!({ "error": e.to_string() }).to_string(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_alert_accepts_feed_alert_json() {
+        let request: PolicyTestRequest = serde_json::from_str(
+            r#"{"alert":{"seq":7,"id":"a1","timestamp_ms":1,"severity":"Critical","module":"log-pipeline",
+                "rule":"ssh_brute_force","title":"SSH brute force","description":"",
+                "source_ip":"1.2.3.4","target_ip":null}}"#,
+        )
+        .expect("valid request");
+        assert_eq!(request.alert.severity, Severity::Critical);
+        assert_eq!(request.selector(), ContainerSelector::All);
+
+        let event = request.alert.to_alert_event();
+        assert_eq!(event.severity, Severity::Critical);
+        assert_eq!(event.alert.rule_name, "ssh_brute_force");
+        assert_eq!(event.alert.source_ip, Some("1.2.3.4".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_request_selector_and_severity_parsing() {
+        let request: PolicyTestRequest =
+            serde_json::from_str(r#"{"alert":{"severity":"high"},"container":"web-1"}"#)
+                .expect("valid request");
+        assert_eq!(request.alert.severity, Severity::High);
+        assert_eq!(
+            request.alert.to_alert_event().alert.title,
+            "policy simulation"
+        );
+        assert_eq!(
+            request.selector(),
+            ContainerSelector::Target("web-1".to_owned())
+        );
+
+        let request: PolicyTestRequest = serde_json::from_str(
+            r#"{"alert":{"severity":"crit","rule_name":"r"},"image":"nginx:1.25"}"#,
+        )
+        .expect("valid request");
+        assert_eq!(request.alert.rule, "r");
+        assert_eq!(
+            request.selector(),
+            ContainerSelector::Hypothetical {
+                name: "simulated".to_owned(),
+                image: "nginx:1.25".to_owned(),
+            }
+        );
+
+        let invalid =
+            serde_json::from_str::<PolicyTestRequest>(r#"{"alert":{"severity":"urgent"}}"#);
+        assert!(invalid.is_err());
+        let missing = serde_json::from_str::<PolicyTestRequest>(r#"{"alert":{}}"#);
+        assert!(missing.is_err());
+    }
+}
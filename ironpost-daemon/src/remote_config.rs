@@ -0,0 +1,491 @@
+//! Remote configuration fetch.
+//!
+//! When `[remote_config]` is enabled (or `--config-url` is given), the local
+//! `ironpost.toml` only bootstraps the fetch: the effective configuration is
+//! downloaded from an HTTP(S) or S3 URL, verified against a SHA-256 checksum
+//! file, and cached at `cache_path`.
+//!
+//! # Checksum Verification
+//!
+//! Every download is paired with a checksum file (`{url}.sha256` unless
+//! `checksum_url` is set) in `sha256sum` format. A mismatch rejects the
+//! download and keeps the last verified copy.
+//!
+//! # S3 URLs
+//!
+//! `s3://bucket/key` is fetched from `https://bucket.s3.amazonaws.com/key`
+//! without request signing, so the object must be readable by the host
+//! (bucket policy or VPC endpoint). Use a presigned HTTPS URL otherwise.
+//!
+//! # Refresh
+//!
+//! Rule/policy bundles are rewritten in place whenever their content
+//! changes. The configuration itself is not hot-reloaded: a changed remote
+//! configuration is verified and cached, and a restart applies it.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use tokio::sync::broadcast;
+
+use ironpost_core::config::{IronpostConfig, RemoteBundle, RemoteConfigSource};
+
+/// Maximum size of a downloaded configuration, checksum, or bundle file.
+const MAX_DOWNLOAD_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Result of loading the remote configuration at startup.
+pub struct RemoteLoad {
+    /// Effective configuration (remote content, local `[remote_config]`).
+    pub config: IronpostConfig,
+    /// SHA-256 of the configuration file that was applied.
+    pub digest: String,
+    /// Whether the cached copy was used because the fetch failed.
+    pub from_cache: bool,
+}
+
+/// Fetch, verify, and cache the remote configuration, then sync bundles.
+///
+/// Falls back to the cached copy if the remote source is unreachable, fails
+/// verification, or does not validate. The cache is only replaced by
+/// content that validated.
+///
+/// # Errors
+///
+/// Returns an error if neither the remote source nor the cache yields a
+/// parseable configuration.
+pub async fn load(source: &RemoteConfigSource) -> Result<RemoteLoad> {
+    let client = build_client(source)?;
+
+    let fetched = fetch_verified(&client, &source.url, &source.checksum_url)
+        .await
+        .and_then(|body| accept_remote(&body, source).map(|config| (body, config)));
+
+    let (body, config, from_cache) = match fetched {
+        Ok((body, config)) => {
+            write_atomic(Path::new(&source.cache_path), &body).await?;
+            (body, config, false)
+        }
+        Err(fetch_err) => {
+            let cached = tokio::fs::read(&source.cache_path).await.map_err(|e| {
+                anyhow::anyhow!(
+                    "failed to load remote config ({}) and no cached copy at {}: {}",
+                    fetch_err,
+                    source.cache_path,
+                    e
+                )
+            })?;
+            let config = parse_remote(&cached, source)?;
+            (cached, config, true)
+        }
+    };
+
+    sync_bundles(&client, &source.bundles).await;
+
+    Ok(RemoteLoad {
+        config,
+        digest: sha256_hex(&body),
+        from_cache,
+    })
+}
+
+/// Spawn the periodic refresh task.
+///
+/// Each tick re-fetches bundles and the configuration. A changed, valid
+/// configuration is cached and reported; the daemon must be restarted to
+/// apply it.
+pub fn spawn_refresher(
+    source: RemoteConfigSource,
+    applied_digest: String,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let client = build_client(&source)?;
+    let period = Duration::from_secs(source.refresh_interval_secs);
+
+    Ok(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        // The initial fetch already happened at startup
+        interval.tick().await;
+        let mut cached_digest = applied_digest.clone();
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    sync_bundles(&client, &source.bundles).await;
+                    cached_digest =
+                        refresh_config(&client, &source, &applied_digest, cached_digest).await;
+                }
+                _ = shutdown_rx.recv() => {
+                    tracing::debug!("remote config refresher shutting down");
+                    break;
+                }
+            }
+        }
+    }))
+}
+
+/// Re-fetch the configuration once; returns the digest of the cached copy.
+async fn refresh_config(
+    client: &reqwest::Client,
+    source: &RemoteConfigSource,
+    applied_digest: &str,
+    cached_digest: String,
+) -> String {
+    let body = match fetch_verified(client, &source.url, &source.checksum_url).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!(error = %e, "remote config refresh failed, keeping cached copy");
+            return cached_digest;
+        }
+    };
+
+    let digest = sha256_hex(&body);
+    if digest == cached_digest {
+        return cached_digest;
+    }
+
+    if let Err(e) = accept_remote(&body, source) {
+        tracing::error!(error = %e, "refreshed remote config is invalid, not caching it");
+        return cached_digest;
+    }
+    if let Err(e) = write_atomic(Path::new(&source.cache_path), &body).await {
+        tracing::error!(error = %e, "failed to cache refreshed remote config");
+        return cached_digest;
+    }
+
+    if digest == applied_digest {
+        tracing::info!("remote config reverted to the running version");
+    } else {
+        tracing::warn!(
+            digest = %digest,
+            running_digest = %applied_digest,
+            "remote config changed; restart the daemon to apply it"
+        );
+    }
+    digest
+}
+
+/// Download each bundle and rewrite it locally if its content changed.
+async fn sync_bundles(client: &reqwest::Client, bundles: &[RemoteBundle]) {
+    for bundle in bundles {
+        let body = match fetch_verified(client, &bundle.url, &bundle.checksum_url).await {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!(
+                    url = %bundle.url,
+                    error = %e,
+                    "bundle fetch failed, keeping local copy"
+                );
+                continue;
+            }
+        };
+
+        let path = Path::new(&bundle.path);
+        if tokio::fs::read(path)
+            .await
+            .is_ok_and(|existing| existing == body)
+        {
+            continue;
+        }
+
+        match write_atomic(path, &body).await {
+            Ok(()) => tracing::info!(
+                url = %bundle.url,
+                path = %bundle.path,
+                digest = %sha256_hex(&body),
+                "bundle updated"
+            ),
+            Err(e) => tracing::error!(path = %bundle.path, error = %e, "failed to write bundle"),
+        }
+    }
+}
+
+/// Parse remote content, keeping the local `[remote_config]` section.
+fn parse_remote(body: &[u8], source: &RemoteConfigSource) -> Result<IronpostConfig> {
+    let text = std::str::from_utf8(body)
+        .map_err(|e| anyhow::anyhow!("remote config is not valid UTF-8: {}", e))?;
+    let mut config = IronpostConfig::parse(text)
+        .map_err(|e| anyhow::anyhow!("failed to parse remote config: {}", e))?;
+    config.apply_env_overrides();
+    config.remote_config = source.clone();
+    Ok(config)
+}
+
+/// Parse and validate fetched content before it may replace the cache.
+fn accept_remote(body: &[u8], source: &RemoteConfigSource) -> Result<IronpostConfig> {
+    let config = parse_remote(body, source)?;
+    config
+        .validate()
+        .map_err(|e| anyhow::anyhow!("validation failed: {}", e))?;
+    Ok(config)
+}
+
+fn build_client(source: &RemoteConfigSource) -> Result<reqwest::Client> {
+    http_client(source.timeout_secs)
+}
+
+/// HTTP client shared by remote fetches (config, bundles, content).
+pub(crate) fn http_client(timeout_secs: u64) -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout_secs))
+        .user_agent(concat!("ironpost-daemon/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| anyhow::anyhow!("failed to build HTTP client: {}", e))
+}
+
+/// Download `url` and verify it against its checksum file.
+pub(crate) async fn fetch_verified(
+    client: &reqwest::Client,
+    url: &str,
+    checksum_url: &str,
+) -> Result<Vec<u8>> {
+    let checksum_url = if checksum_url.is_empty() {
+        format!("{}.sha256", url)
+    } else {
+        checksum_url.to_owned()
+    };
+
+    let body = fetch(client, url).await?;
+    let checksum = fetch(client, &checksum_url).await?;
+    let checksum = std::str::from_utf8(&checksum)
+        .map_err(|e| anyhow::anyhow!("checksum file is not valid UTF-8: {}", e))?;
+    verify_sha256(&body, checksum)?;
+    Ok(body)
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let resolved = resolve_url(url);
+    let response = client
+        .get(&resolved)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| anyhow::anyhow!("GET {} failed: {}", resolved, e))?;
+
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_DOWNLOAD_BYTES)
+    {
+        return Err(anyhow::anyhow!(
+            "{} exceeds the {} byte limit",
+            resolved,
+            MAX_DOWNLOAD_BYTES
+        ));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to read {}: {}", resolved, e))?;
+    if u64::try_from(bytes.len()).unwrap_or(u64::MAX) > MAX_DOWNLOAD_BYTES {
+        return Err(anyhow::anyhow!(
+            "{} exceeds the {} byte limit",
+            resolved,
+            MAX_DOWNLOAD_BYTES
+        ));
+    }
+    Ok(bytes.to_vec())
+}
+
+/// Map `s3://bucket/key` to its virtual-hosted HTTPS endpoint.
+fn resolve_url(url: &str) -> String {
+    match url
+        .strip_prefix("s3://")
+        .and_then(|rest| rest.split_once('/'))
+    {
+        Some((bucket, key)) => format!("https://{}.s3.amazonaws.com/{}", bucket, key),
+        None => url.to_owned(),
+    }
+}
+
+/// Verify `body` against a `sha256sum`-style checksum (`<hex>  <name>` or `<hex>`).
+fn verify_sha256(body: &[u8], checksum: &str) -> Result<()> {
+    let expected = checksum
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("checksum file is empty"))?
+        .to_ascii_lowercase();
+    if expected.len() != 64 || !expected.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(anyhow::anyhow!(
+            "checksum file does not contain a SHA-256 digest"
+        ));
+    }
+
+    let actual = sha256_hex(body);
+    if actual != expected {
+        return Err(anyhow::anyhow!(
+            "checksum mismatch: expected {}, got {}",
+            expected,
+            actual
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn sha256_hex(body: &[u8]) -> String {
+    Sha256::digest(body)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Write via a temporary file and rename so readers never see partial content.
+pub(crate) async fn write_atomic(path: &Path, body: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to create {}: {}", parent.display(), e))?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    tokio::fs::write(&tmp, body)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to write {}: {}", path.display(), e))?;
+    tokio::fs::rename(&tmp, path)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to replace {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn test_resolve_s3_url() {
+        assert_eq!(
+            resolve_url("s3://fleet-config/ironpost/ironpost.toml"),
+            "https://fleet-config.s3.amazonaws.com/ironpost/ironpost.toml"
+        );
+        assert_eq!(
+            resolve_url("https://config.example.com/ironpost.toml"),
+            "https://config.example.com/ironpost.toml"
+        );
+    }
+
+    #[test]
+    fn test_verify_sha256_accepts_sha256sum_format() {
+        verify_sha256(b"", EMPTY_SHA256).unwrap();
+        verify_sha256(
+            b"",
+            &format!("{}  ironpost.toml\n", EMPTY_SHA256.to_uppercase()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_verify_sha256_rejects_mismatch_and_garbage() {
+        let err = verify_sha256(b"tampered", EMPTY_SHA256).unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"));
+        assert!(verify_sha256(b"", "").is_err());
+        assert!(verify_sha256(b"", "not-a-digest").is_err());
+    }
+
+    #[test]
+    fn test_parse_remote_keeps_local_source() {
+        // Given: Remote content that tries to redirect the config source
+        let local = RemoteConfigSource {
+            enabled: true,
+            url: "https://config.example.com/ironpost.toml".to_owned(),
+            ..RemoteConfigSource::default()
+        };
+        let body = br#"
+[general]
+log_level = "debug"
+
+[remote_config]
+url = "https://attacker.example.com/ironpost.toml"
+"#;
+
+        // When: Parsing the remote config
+        let config = parse_remote(body, &local).unwrap();
+
+        // Then: Remote settings apply, but the local source is retained
+        assert_eq!(config.general.log_level, "debug");
+        assert_eq!(config.remote_config.url, local.url);
+    }
+
+    /// Serve fixed files over plain HTTP on an ephemeral port.
+    async fn serve(files: Vec<(&'static str, Vec<u8>)>) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = vec![0u8; 4096];
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let response = match files.iter().find(|(p, _)| *p == path) {
+                    Some((_, body)) => {
+                        let mut response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            body.len()
+                        )
+                        .into_bytes();
+                        response.extend_from_slice(body);
+                        response
+                    }
+                    None => {
+                        b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_vec()
+                    }
+                };
+                let _ = stream.write_all(&response).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_load_keeps_cache_when_remote_is_invalid() {
+        // Given: A good cached copy and a remote config that passes the
+        // checksum but fails validation
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = dir.path().join("remote.toml");
+        let good = b"[general]\nlog_level = \"debug\"\n".to_vec();
+        std::fs::write(&cache_path, &good).unwrap();
+
+        let bad = b"[general]\nlog_level = \"verbose\"\n".to_vec();
+        let checksum = sha256_hex(&bad).into_bytes();
+        let base = serve(vec![
+            ("/ironpost.toml", bad),
+            ("/ironpost.toml.sha256", checksum),
+        ])
+        .await;
+        let source = RemoteConfigSource {
+            enabled: true,
+            url: format!("{}/ironpost.toml", base),
+            cache_path: cache_path.display().to_string(),
+            ..RemoteConfigSource::default()
+        };
+
+        // When: Loading the remote config
+        let loaded = load(&source).await.unwrap();
+
+        // Then: The cached copy is used and left intact
+        assert!(loaded.from_cache);
+        assert_eq!(loaded.config.general.log_level, "debug");
+        assert_eq!(loaded.digest, sha256_hex(&good));
+        assert_eq!(std::fs::read(&cache_path).unwrap(), good);
+    }
+
+    #[tokio::test]
+    async fn test_write_atomic_creates_parent_and_replaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("remote.toml");
+
+        write_atomic(&path, b"first").await.unwrap();
+        write_atomic(&path, b"second").await.unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"second");
+        assert!(!dir.path().join("cache").join("remote.toml.tmp").exists());
+    }
+}
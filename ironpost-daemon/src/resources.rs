@@ -0,0 +1,259 @@
+//! Per-module resource usage reporting.
+//!
+//! Attributes daemon resource consumption to subsystems:
+//!
+//! - **Modules** report their own background task count, channel depths and
+//!   a memory estimate through `Plugin::resource_usage`.
+//! - **Runtime** figures come from tokio's stable runtime metrics.
+//! - **Process** CPU time, resident memory and thread count are read from
+//!   `/proc/self` (Linux only; `None` elsewhere).
+//!
+//! Tokio tasks of all modules share the same worker threads, so CPU time is
+//! only available for the process as a whole. Per-module task counts and
+//! channel depths are the usual way to tell which subsystem is busy.
+//!
+//! The orchestrator collects a [`ResourceReport`] on every health tick,
+//! publishes it on `/status` and, when metrics are enabled, exports it as
+//! Prometheus gauges.
+
+use serde::Serialize;
+
+use ironpost_core::plugin::ResourceUsage;
+
+/// Clock ticks per second used by `/proc/<pid>/stat` (`USER_HZ`).
+///
+/// Fixed at 100 by the Linux userspace ABI on all supported architectures.
+#[cfg(target_os = "linux")]
+const CLOCK_TICKS_PER_SEC: f64 = 100.0;
+
+/// Resource usage of one module.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleUsage {
+    /// Module name.
+    pub name: String,
+    /// Reported usage.
+    #[serde(flatten)]
+    pub usage: ResourceUsage,
+}
+
+/// Tokio runtime figures.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RuntimeUsage {
+    /// Number of worker threads.
+    pub workers: usize,
+    /// Number of tasks that have been spawned and not yet completed.
+    pub alive_tasks: usize,
+    /// Number of tasks waiting in the global run queue.
+    pub global_queue_depth: usize,
+}
+
+impl RuntimeUsage {
+    /// Read metrics from the current tokio runtime.
+    ///
+    /// Returns zeroes when called outside a runtime.
+    pub fn current() -> Self {
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                let metrics = handle.metrics();
+                Self {
+                    workers: metrics.num_workers(),
+                    alive_tasks: metrics.num_alive_tasks(),
+                    global_queue_depth: metrics.global_queue_depth(),
+                }
+            }
+            Err(_) => Self::default(),
+        }
+    }
+}
+
+/// Process-wide figures from `/proc/self`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProcessUsage {
+    /// Total user + system CPU time in seconds.
+    pub cpu_seconds: Option<f64>,
+    /// Resident set size in bytes.
+    pub resident_memory_bytes: Option<u64>,
+    /// Number of OS threads.
+    pub threads: Option<u64>,
+}
+
+impl ProcessUsage {
+    /// Read usage of the current process.
+    ///
+    /// Fields that cannot be read are left as `None`.
+    pub fn current() -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            let mut usage = std::fs::read_to_string("/proc/self/status")
+                .map(|status| parse_proc_status(&status))
+                .unwrap_or_default();
+            usage.cpu_seconds = std::fs::read_to_string("/proc/self/stat")
+                .ok()
+                .and_then(|stat| parse_proc_stat_cpu_seconds(&stat));
+            usage
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Self::default()
+        }
+    }
+}
+
+/// Point-in-time resource report published on `/status`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResourceReport {
+    /// Process-wide usage.
+    pub process: ProcessUsage,
+    /// Tokio runtime usage.
+    pub runtime: RuntimeUsage,
+    /// Usage per module, in registration order.
+    pub modules: Vec<ModuleUsage>,
+}
+
+impl ResourceReport {
+    /// Build a report from per-module usage plus current process and runtime figures.
+    pub fn collect(modules: Vec<ModuleUsage>) -> Self {
+        Self {
+            process: ProcessUsage::current(),
+            runtime: RuntimeUsage::current(),
+            modules,
+        }
+    }
+
+    /// Export the report as Prometheus gauges.
+    pub fn record_metrics(&self) {
+        use ironpost_core::metrics as m;
+
+        for module in &self.modules {
+            let name = module.name.clone();
+            #[allow(clippy::cast_precision_loss)]
+            metrics::gauge!(m::DAEMON_MODULE_TASKS, m::LABEL_MODULE => name.clone())
+                .set(module.usage.tasks as f64);
+            #[allow(clippy::cast_precision_loss)]
+            metrics::gauge!(m::DAEMON_MODULE_MEMORY_BYTES, m::LABEL_MODULE => name.clone())
+                .set(module.usage.memory_bytes as f64);
+            for channel in &module.usage.channels {
+                #[allow(clippy::cast_precision_loss)]
+                metrics::gauge!(
+                    m::DAEMON_MODULE_CHANNEL_DEPTH,
+                    m::LABEL_MODULE => name.clone(),
+                    m::LABEL_CHANNEL => channel.name.clone()
+                )
+                .set(channel.depth as f64);
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        metrics::gauge!(m::DAEMON_RUNTIME_ALIVE_TASKS).set(self.runtime.alive_tasks as f64);
+        if let Some(cpu_seconds) = self.process.cpu_seconds {
+            metrics::gauge!(m::DAEMON_PROCESS_CPU_SECONDS).set(cpu_seconds);
+        }
+        if let Some(rss) = self.process.resident_memory_bytes {
+            #[allow(clippy::cast_precision_loss)]
+            metrics::gauge!(m::DAEMON_PROCESS_RESIDENT_MEMORY_BYTES).set(rss as f64);
+        }
+    }
+}
+
+/// Extract `VmRSS` and `Threads` from `/proc/<pid>/status`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_proc_status(status: &str) -> ProcessUsage {
+    let mut usage = ProcessUsage::default();
+    for line in status.lines() {
+        if let Some(value) = line.strip_prefix("VmRSS:") {
+            // Reported as "<n> kB"
+            usage.resident_memory_bytes = value
+                .split_whitespace()
+                .next()
+                .and_then(|kb| kb.parse::<u64>().ok())
+                .and_then(|kb| kb.checked_mul(1024));
+        } else if let Some(value) = line.strip_prefix("Threads:") {
+            usage.threads = value.trim().parse().ok();
+        }
+    }
+    usage
+}
+
+/// Extract total CPU seconds (`utime + stime`) from `/proc/<pid>/stat`.
+///
+/// The command name field may contain spaces and parentheses, so fields are
+/// counted from the last `)`.
+#[cfg(target_os = "linux")]
+fn parse_proc_stat_cpu_seconds(stat: &str) -> Option<f64> {
+    let (_, rest) = stat.rsplit_once(')')?;
+    // Fields after the command name start at field 3 (state); utime and
+    // stime are fields 14 and 15.
+    let mut fields = rest.split_whitespace().skip(11);
+    let utime: u64 = fields.next()?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    #[allow(clippy::cast_precision_loss)]
+    let ticks = utime.saturating_add(stime) as f64;
+    Some(ticks / CLOCK_TICKS_PER_SEC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_status() {
+        // Given: A trimmed /proc/self/status
+        let status = "Name:\tironpost-daemon\nVmRSS:\t   20480 kB\nThreads:\t7\n";
+
+        // When: Parsing it
+        let usage = parse_proc_status(status);
+
+        // Then: RSS is converted to bytes and threads are read
+        assert_eq!(usage.resident_memory_bytes, Some(20480 * 1024));
+        assert_eq!(usage.threads, Some(7));
+        assert_eq!(usage.cpu_seconds, None);
+    }
+
+    #[test]
+    fn test_parse_proc_status_missing_fields() {
+        let usage = parse_proc_status("Name:\tkthreadd\n");
+        assert_eq!(usage, ProcessUsage::default());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_proc_stat_handles_spaces_in_command() {
+        // Given: A command name containing spaces and parentheses
+        let stat = "1234 (ironpost (worker) 1) S 1 1234 1234 0 -1 4194560 \
+                    100 0 0 0 250 50 0 0 20 0 7 0 12345 1000000 5000";
+
+        // When: Parsing CPU time
+        let cpu = parse_proc_stat_cpu_seconds(stat);
+
+        // Then: utime (250) + stime (50) ticks = 3 seconds
+        assert_eq!(cpu, Some(3.0));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_current_process_usage_is_available() {
+        let usage = ProcessUsage::current();
+        assert!(usage.resident_memory_bytes.is_some_and(|rss| rss > 0));
+        assert!(usage.threads.is_some_and(|threads| threads > 0));
+        assert!(usage.cpu_seconds.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_runtime_usage_counts_alive_tasks() {
+        // Given: A task that stays alive until released
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let task = tokio::spawn(async move {
+            let _ = rx.await;
+        });
+
+        // When: Reading runtime metrics
+        let usage = RuntimeUsage::current();
+
+        // Then: The spawned task is counted
+        assert!(usage.workers >= 1);
+        assert!(usage.alive_tasks >= 1);
+
+        let _ = tx.send(());
+        task.await.unwrap();
+    }
+}
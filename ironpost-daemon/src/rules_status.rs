@@ -0,0 +1,116 @@
+//! Detection rule listing for the control API.
+//!
+//! Served on `GET /rules` by the health listener (`read-only` role when
+//! `[auth]` is enabled). Lists the rules currently loaded in the log
+//! pipeline's rule engine together with how many log entries matched each
+//! rule's conditions since it was loaded.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use ironpost_core::types::Severity;
+use ironpost_log_pipeline::RuleEngine;
+use ironpost_log_pipeline::rule::RuleStatus;
+
+/// Path of the rule listing endpoint.
+pub const RULES_PATH: &str = "/rules";
+
+/// A loaded detection rule with its match count.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleStatusEntry {
+    /// Rule ID.
+    pub id: String,
+    /// Rule title.
+    pub title: String,
+    /// Alert severity.
+    pub severity: Severity,
+    /// Rule status (`enabled`, `disabled`, `test`).
+    pub status: RuleStatus,
+    /// Rule tags.
+    pub tags: Vec<String>,
+    /// Log entries that matched the rule's conditions (cumulative).
+    pub matches: u64,
+}
+
+/// JSON body returned by `/rules`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RulesStatus {
+    /// Loaded rules, ordered by ID.
+    pub rules: Vec<RuleStatusEntry>,
+}
+
+/// List the rules loaded in `engine` with their match counts.
+pub async fn rules_status(engine: &Arc<Mutex<RuleEngine>>) -> RulesStatus {
+    let engine = engine.lock().await;
+    let rules = engine
+        .rules()
+        .into_iter()
+        .map(|rule| RuleStatusEntry {
+            id: rule.id.clone(),
+            title: rule.title.clone(),
+            severity: rule.severity,
+            status: rule.status.clone(),
+            tags: rule.tags.clone(),
+            matches: engine.match_count(&rule.id),
+        })
+        .collect();
+    RulesStatus { rules }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironpost_core::types::LogEntry;
+    use ironpost_log_pipeline::rule::{DetectionCondition, DetectionRule};
+    use std::time::SystemTime;
+
+    fn rule(id: &str) -> DetectionRule {
+        DetectionRule {
+            id: id.to_owned(),
+            title: format!("Rule {}", id),
+            description: String::new(),
+            severity: Severity::High,
+            status: RuleStatus::Enabled,
+            detection: DetectionCondition {
+                conditions: vec![],
+                ..Default::default()
+            },
+            correlation: None,
+            tags: vec!["ssh".to_owned()],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rules_status_reports_match_counts() {
+        // Given: An engine with two rules, one of which matched a log entry
+        let mut engine = RuleEngine::new();
+        engine.add_rule(rule("b_rule")).expect("rule should load");
+        let entry = LogEntry {
+            source: "test".to_owned(),
+            timestamp: SystemTime::now(),
+            hostname: "web-01".to_owned(),
+            process: "sshd".to_owned(),
+            message: "Failed password".to_owned(),
+            severity: Severity::Info,
+            fields: vec![],
+        };
+        engine.evaluate(&entry).expect("evaluation should succeed");
+        engine.add_rule(rule("a_rule")).expect("rule should load");
+        let engine = Arc::new(Mutex::new(engine));
+
+        // When: Listing the rules
+        let status = rules_status(&engine).await;
+
+        // Then: Rules are ordered by ID with their match counts
+        let ids: Vec<&str> = status.rules.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["a_rule", "b_rule"]);
+        assert_eq!(status.rules[0].matches, 0);
+        assert_eq!(status.rules[1].matches, 1);
+
+        let json = serde_json::to_value(&status).expect("should serialize");
+        assert_eq!(json["rules"][1]["status"], "enabled");
+        assert_eq!(json["rules"][1]["severity"], "High");
+    }
+}
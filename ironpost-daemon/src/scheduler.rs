@@ -0,0 +1,726 @@
+//! Recurring task scheduler.
+//!
+//! Runs jobs from the `[scheduler]` section on cron schedules (UTC):
+//!
+//! - `sbom_scan` -- one-shot SBOM scan of `sbom.scan_dirs`; findings are
+//!   sent as alerts like periodic scans
+//! - `vuln_db_check` -- load the vulnerability database at
+//!   `sbom.vuln_db_path` and report its entry count. The job only verifies
+//!   the files on disk; `ironpost sbom db update` refreshes them.
+//!   `vuln_db_update` is accepted as a deprecated alias.
+//! - `log_store_retention` -- delete log store segments older than
+//!   `log_pipeline.log_store.retention_days`. Registered automatically
+//!   (`@hourly`) when the log store is enabled and no task of this kind is
+//!   configured.
+//! - `report` -- write a JSON status report (health, modules, tasks) to
+//!   the task's `output_path`
+//!
+//! Each job runs in its own task so a slow job never delays others. A job
+//! that is still running when its next run comes due is skipped rather than
+//! started twice. Runs missed while the daemon was down are not caught up.
+//!
+//! Per-task status (last run, outcome, next run) is published through a
+//! `watch` channel and served by the `/status` endpoint.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::Serialize;
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::task::{AbortHandle, JoinHandle};
+
+use ironpost_core::config::{SbomConfig, ScheduledTaskConfig, SchedulerConfig};
+use ironpost_core::event::AlertEvent;
+use ironpost_core::pipeline::Pipeline;
+use ironpost_log_pipeline::LogStore;
+
+use crate::cron::CronSchedule;
+use crate::health_server::HealthSnapshot;
+
+/// Longest the scheduler sleeps before re-checking the clock.
+///
+/// Bounds the error when the wall clock jumps (NTP sync, suspend/resume).
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Name of the built-in retention task registered for the log store.
+pub const LOG_STORE_RETENTION_TASK: &str = "log-store-retention";
+
+/// Deprecated configuration name of [`TaskKind::VulnDbCheck`].
+const DEPRECATED_VULN_DB_UPDATE: &str = "vuln_db_update";
+
+/// Kind of scheduled job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskKind {
+    /// One-shot SBOM scan.
+    SbomScan,
+    /// Vulnerability database load check.
+    VulnDbCheck,
+    /// Log store retention (expired segment removal).
+    LogStoreRetention,
+    /// JSON status report.
+    Report,
+}
+
+impl TaskKind {
+    /// Parse a task kind from its configuration name.
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "sbom_scan" => Some(Self::SbomScan),
+            "vuln_db_check" | DEPRECATED_VULN_DB_UPDATE => Some(Self::VulnDbCheck),
+            "log_store_retention" => Some(Self::LogStoreRetention),
+            "report" => Some(Self::Report),
+            _ => None,
+        }
+    }
+
+    /// Configuration name of this kind.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SbomScan => "sbom_scan",
+            Self::VulnDbCheck => "vuln_db_check",
+            Self::LogStoreRetention => "log_store_retention",
+            Self::Report => "report",
+        }
+    }
+}
+
+/// Status of a scheduled task, as reported by the status API.
+///
+/// Timestamps are Unix seconds (UTC).
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskStatus {
+    /// Task name.
+    pub name: String,
+    /// Task kind (`sbom_scan`, `vuln_db_check`, `log_store_retention`, `report`).
+    pub kind: &'static str,
+    /// Cron expression.
+    pub schedule: String,
+    /// Whether the task is enabled.
+    pub enabled: bool,
+    /// Whether a run is currently in progress.
+    pub running: bool,
+    /// Start time of the most recent run.
+    pub last_run: Option<u64>,
+    /// Duration of the most recent completed run in milliseconds.
+    pub last_duration_ms: Option<u64>,
+    /// Whether the most recent completed run succeeded.
+    pub last_success: Option<bool>,
+    /// Summary or error message of the most recent completed run.
+    pub last_message: Option<String>,
+    /// Next scheduled run (`None` if disabled or the schedule never matches).
+    pub next_run: Option<u64>,
+    /// Total completed runs.
+    pub runs: u64,
+    /// Total failed runs.
+    pub failures: u64,
+    /// Runs skipped because the previous run was still in progress.
+    pub skipped: u64,
+}
+
+/// Shared inputs for job execution.
+#[derive(Clone)]
+pub struct JobContext {
+    /// SBOM settings used by `sbom_scan` and `vuln_db_check`.
+    pub sbom: SbomConfig,
+    /// Log store used by `log_store_retention` (`None` if disabled).
+    pub log_store: Option<Arc<LogStore>>,
+    /// Alert channel for scan findings.
+    pub alert_tx: mpsc::Sender<AlertEvent>,
+    /// Latest health snapshot, used by `report`.
+    pub snapshot_rx: watch::Receiver<HealthSnapshot>,
+}
+
+/// A configured task with its parsed schedule.
+struct ScheduledTask {
+    kind: TaskKind,
+    schedule: CronSchedule,
+    output_path: String,
+    next_run: Option<SystemTime>,
+    running: Option<AbortHandle>,
+    status: TaskStatus,
+}
+
+/// Completion notice sent by a finished job.
+struct JobDone {
+    index: usize,
+    elapsed: Duration,
+    result: std::result::Result<String, String>,
+}
+
+/// Cron-driven scheduler for recurring daemon jobs.
+pub struct Scheduler {
+    tasks: Vec<ScheduledTask>,
+    status_tx: watch::Sender<Vec<TaskStatus>>,
+}
+
+impl Scheduler {
+    /// Build a scheduler from configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a task has an unknown kind or an invalid cron
+    /// expression.
+    pub fn new(config: &SchedulerConfig) -> Result<Self> {
+        Self::new_at(config, SystemTime::now())
+    }
+
+    fn new_at(config: &SchedulerConfig, now: SystemTime) -> Result<Self> {
+        let mut tasks = Vec::with_capacity(config.tasks.len());
+        for task in &config.tasks {
+            tasks.push(ScheduledTask::new(task, now)?);
+        }
+
+        let (status_tx, _) = watch::channel(Vec::new());
+        let scheduler = Self { tasks, status_tx };
+        scheduler.publish();
+        Ok(scheduler)
+    }
+
+    /// Number of configured tasks.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Whether no tasks are configured.
+    #[allow(dead_code)] // Public API paired with len()
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Current status of all tasks.
+    pub fn statuses(&self) -> Vec<TaskStatus> {
+        self.tasks.iter().map(|t| t.status.clone()).collect()
+    }
+
+    /// Subscribe to task status updates.
+    pub fn subscribe(&self) -> watch::Receiver<Vec<TaskStatus>> {
+        self.status_tx.subscribe()
+    }
+
+    /// Start the scheduler loop.
+    ///
+    /// The returned handle stops the loop and aborts in-flight jobs.
+    pub fn spawn(mut self, ctx: JobContext) -> SchedulerHandle {
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        let (done_tx, mut done_rx) = mpsc::channel(self.tasks.len().max(1));
+
+        let task = tokio::spawn(async move {
+            tracing::info!(tasks = self.tasks.len(), "scheduler started");
+            loop {
+                let now = SystemTime::now();
+                for index in self.due(now) {
+                    self.start_task(index, now, &ctx, &done_tx);
+                }
+                self.publish();
+
+                let sleep = self.time_until_next(now).min(MAX_SLEEP);
+                tokio::select! {
+                    _ = &mut stop_rx => break,
+                    Some(done) = done_rx.recv() => self.complete(done),
+                    _ = tokio::time::sleep(sleep) => {}
+                }
+            }
+
+            for task in &mut self.tasks {
+                if let Some(handle) = task.running.take() {
+                    tracing::info!(task = %task.status.name, "aborting scheduled task");
+                    handle.abort();
+                    task.status.running = false;
+                }
+            }
+            self.publish();
+            tracing::info!("scheduler stopped");
+        });
+
+        SchedulerHandle { stop_tx, task }
+    }
+
+    /// Indices of enabled tasks whose next run is at or before `now`.
+    fn due(&self, now: SystemTime) -> Vec<usize> {
+        self.tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.next_run.is_some_and(|next| next <= now))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Time until the earliest upcoming run.
+    fn time_until_next(&self, now: SystemTime) -> Duration {
+        self.tasks
+            .iter()
+            .filter_map(|t| t.next_run)
+            .min()
+            .map_or(MAX_SLEEP, |next| {
+                next.duration_since(now).unwrap_or(Duration::ZERO)
+            })
+    }
+
+    fn start_task(
+        &mut self,
+        index: usize,
+        now: SystemTime,
+        ctx: &JobContext,
+        done_tx: &mpsc::Sender<JobDone>,
+    ) {
+        let Some(task) = self.tasks.get_mut(index) else {
+            return;
+        };
+        task.reschedule(now);
+
+        if task.running.is_some() {
+            task.status.skipped += 1;
+            tracing::warn!(
+                task = %task.status.name,
+                "previous run still in progress, skipping scheduled run"
+            );
+            return;
+        }
+
+        tracing::info!(task = %task.status.name, kind = task.kind.as_str(), "running scheduled task");
+        task.status.running = true;
+        task.status.last_run = unix_secs(now);
+
+        let job = tokio::spawn(run_job(task.kind, ctx.clone(), task.output_path.clone()));
+        task.running = Some(job.abort_handle());
+
+        let done_tx = done_tx.clone();
+        let started = Instant::now();
+        tokio::spawn(async move {
+            let result = match job.await {
+                Ok(result) => result,
+                Err(e) if e.is_cancelled() => return,
+                Err(e) => Err(format!("job panicked: {}", e)),
+            };
+            let _ = done_tx
+                .send(JobDone {
+                    index,
+                    elapsed: started.elapsed(),
+                    result,
+                })
+                .await;
+        });
+    }
+
+    fn complete(&mut self, done: JobDone) {
+        let Some(task) = self.tasks.get_mut(done.index) else {
+            return;
+        };
+        task.running = None;
+
+        let status = &mut task.status;
+        status.running = false;
+        status.runs += 1;
+        status.last_duration_ms = Some(u64::try_from(done.elapsed.as_millis()).unwrap_or(u64::MAX));
+        match done.result {
+            Ok(summary) => {
+                tracing::info!(task = %status.name, summary = %summary, "scheduled task completed");
+                status.last_success = Some(true);
+                status.last_message = Some(summary);
+            }
+            Err(e) => {
+                tracing::error!(task = %status.name, error = %e, "scheduled task failed");
+                status.failures += 1;
+                status.last_success = Some(false);
+                status.last_message = Some(e);
+            }
+        }
+        self.publish();
+    }
+
+    fn publish(&self) {
+        self.status_tx.send_replace(self.statuses());
+    }
+}
+
+impl ScheduledTask {
+    fn new(config: &ScheduledTaskConfig, now: SystemTime) -> Result<Self> {
+        let kind = TaskKind::parse(&config.kind).ok_or_else(|| {
+            anyhow::anyhow!(
+                "scheduled task '{}': unknown kind '{}'",
+                config.name,
+                config.kind
+            )
+        })?;
+        if config.kind == DEPRECATED_VULN_DB_UPDATE {
+            tracing::warn!(
+                task = %config.name,
+                "task kind 'vuln_db_update' is deprecated and only verifies the database; \
+                 use 'vuln_db_check', and 'ironpost sbom db update' to refresh it"
+            );
+        }
+        let schedule = CronSchedule::parse(&config.schedule).map_err(|e| {
+            anyhow::anyhow!(
+                "scheduled task '{}': invalid schedule '{}': {}",
+                config.name,
+                config.schedule,
+                e
+            )
+        })?;
+
+        let mut task = Self {
+            kind,
+            schedule,
+            output_path: config.output_path.clone(),
+            next_run: None,
+            running: None,
+            status: TaskStatus {
+                name: config.name.clone(),
+                kind: kind.as_str(),
+                schedule: config.schedule.clone(),
+                enabled: config.enabled,
+                running: false,
+                last_run: None,
+                last_duration_ms: None,
+                last_success: None,
+                last_message: None,
+                next_run: None,
+                runs: 0,
+                failures: 0,
+                skipped: 0,
+            },
+        };
+        task.reschedule(now);
+        if task.status.enabled && task.next_run.is_none() {
+            tracing::warn!(
+                task = %config.name,
+                schedule = %config.schedule,
+                "schedule never matches, task will not run"
+            );
+        }
+        Ok(task)
+    }
+
+    /// Compute the next run strictly after `now`.
+    fn reschedule(&mut self, now: SystemTime) {
+        self.next_run = if self.status.enabled {
+            self.schedule.next_after(now)
+        } else {
+            None
+        };
+        self.status.next_run = self.next_run.and_then(unix_secs);
+    }
+}
+
+/// Handle to a running scheduler.
+pub struct SchedulerHandle {
+    stop_tx: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+impl SchedulerHandle {
+    /// Stop the scheduler, abort in-flight jobs, and wait for the loop to exit.
+    pub async fn stop(self) {
+        let _ = self.stop_tx.send(());
+        let _ = self.task.await;
+    }
+}
+
+/// Execute a single job and return a human-readable summary.
+async fn run_job(
+    kind: TaskKind,
+    ctx: JobContext,
+    output_path: String,
+) -> std::result::Result<String, String> {
+    match kind {
+        TaskKind::SbomScan => run_sbom_scan(&ctx).await,
+        TaskKind::VulnDbCheck => run_vuln_db_check(&ctx).await,
+        TaskKind::LogStoreRetention => run_log_store_retention(&ctx).await,
+        TaskKind::Report => run_report(&ctx, &output_path).await,
+    }
+}
+
+async fn run_sbom_scan(ctx: &JobContext) -> std::result::Result<String, String> {
+    // A dedicated scanner instance with no periodic task of its own;
+    // start() loads the current vulnerability database.
+    let mut config = ironpost_sbom_scanner::SbomScannerConfig::from_core(&ctx.sbom);
+    config.scan_interval_secs = 0;
+    let (mut scanner, _) = ironpost_sbom_scanner::SbomScannerBuilder::new()
+        .config(config)
+        .alert_sender(ctx.alert_tx.clone())
+        .build()
+        .map_err(|e| format!("failed to build scanner: {}", e))?;
+
+    Pipeline::start(&mut scanner)
+        .await
+        .map_err(|e| format!("failed to start scanner: {}", e))?;
+    let result = scanner.scan_once().await;
+    if let Err(e) = Pipeline::stop(&mut scanner).await {
+        tracing::debug!(error = %e, "failed to stop one-shot scanner");
+    }
+
+    let results = result.map_err(|e| format!("scan failed: {}", e))?;
+    let findings: usize = results.iter().map(|r| r.findings.len()).sum();
+    Ok(format!(
+        "scanned {} lockfiles, {} findings",
+        results.len(),
+        findings
+    ))
+}
+
+async fn run_vuln_db_check(ctx: &JobContext) -> std::result::Result<String, String> {
+    let path = ctx.sbom.vuln_db_path.clone();
+    let db = tokio::task::spawn_blocking(move || {
+        ironpost_sbom_scanner::VulnDb::load_from_dir(std::path::Path::new(&path))
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))?
+    .map_err(|e| format!("failed to load vulnerability database: {}", e))?;
+
+    Ok(format!(
+        "verified {} vulnerability entries in {}",
+        db.entry_count(),
+        ctx.sbom.vuln_db_path
+    ))
+}
+
+async fn run_log_store_retention(ctx: &JobContext) -> std::result::Result<String, String> {
+    let store = ctx
+        .log_store
+        .clone()
+        .ok_or_else(|| "log store is not enabled ([log_pipeline.log_store])".to_owned())?;
+    let (removed, segments, bytes) = tokio::task::spawn_blocking(move || {
+        let removed = store.enforce_retention(SystemTime::now());
+        (removed, store.segment_count(), store.bytes())
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))?;
+
+    Ok(format!(
+        "removed {} expired segments, {} segments ({} bytes) retained",
+        removed, segments, bytes
+    ))
+}
+
+/// Add the built-in `log_store_retention` task unless one is configured
+/// (or its name is already taken).
+///
+/// Returns `true` if the task was added.
+pub fn register_log_store_retention(config: &mut SchedulerConfig) -> bool {
+    let kind = TaskKind::LogStoreRetention.as_str();
+    if config
+        .tasks
+        .iter()
+        .any(|t| t.kind == kind || t.name == LOG_STORE_RETENTION_TASK)
+    {
+        return false;
+    }
+    config.tasks.push(ScheduledTaskConfig {
+        name: LOG_STORE_RETENTION_TASK.to_owned(),
+        kind: kind.to_owned(),
+        schedule: "@hourly".to_owned(),
+        ..ScheduledTaskConfig::default()
+    });
+    true
+}
+
+/// JSON document written by `report` tasks.
+#[derive(Serialize)]
+struct StatusReport<'a> {
+    generated_at: Option<u64>,
+    #[serde(flatten)]
+    health: &'a crate::health::DaemonHealth,
+    tasks: &'a [TaskStatus],
+}
+
+async fn run_report(ctx: &JobContext, output_path: &str) -> std::result::Result<String, String> {
+    let snapshot = ctx.snapshot_rx.borrow().clone();
+    let report = StatusReport {
+        generated_at: unix_secs(SystemTime::now()),
+        health: &snapshot.health,
+        tasks: &snapshot.tasks,
+    };
+    let body = serde_json::to_vec_pretty(&report)
+        .map_err(|e| format!("failed to serialize report: {}", e))?;
+
+    // Write to a temporary file and rename so readers never see a partial report
+    let tmp_path = format!("{}.tmp", output_path);
+    tokio::fs::write(&tmp_path, &body)
+        .await
+        .map_err(|e| format!("failed to write {}: {}", tmp_path, e))?;
+    tokio::fs::rename(&tmp_path, output_path)
+        .await
+        .map_err(|e| format!("failed to rename report to {}: {}", output_path, e))?;
+
+    Ok(format!("wrote {} bytes to {}", body.len(), output_path))
+}
+
+fn unix_secs(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-01-15 10:30:00 UTC.
+    const BASE: u64 = 1_705_314_600;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn task(name: &str, kind: &str, schedule: &str) -> ScheduledTaskConfig {
+        ScheduledTaskConfig {
+            name: name.to_owned(),
+            kind: kind.to_owned(),
+            schedule: schedule.to_owned(),
+            ..ScheduledTaskConfig::default()
+        }
+    }
+
+    fn config(tasks: Vec<ScheduledTaskConfig>) -> SchedulerConfig {
+        SchedulerConfig {
+            enabled: true,
+            tasks,
+        }
+    }
+
+    #[test]
+    fn test_new_computes_next_run_per_task() {
+        // Given: An hourly scan and a daily DB update
+        let config = config(vec![
+            task("scan", "sbom_scan", "@hourly"),
+            task("db", "vuln_db_check", "0 3 * * *"),
+        ]);
+
+        // When: Building the scheduler at 10:30
+        let scheduler = Scheduler::new_at(&config, at(BASE)).unwrap();
+
+        // Then: Next runs are 11:00 today and 03:00 tomorrow
+        let statuses = scheduler.statuses();
+        assert_eq!(statuses[0].next_run, Some(BASE + 30 * 60));
+        assert_eq!(statuses[1].next_run, Some(1_705_374_000));
+        assert!(statuses.iter().all(|s| s.last_run.is_none()));
+    }
+
+    #[test]
+    fn test_disabled_task_has_no_next_run() {
+        let mut disabled = task("scan", "sbom_scan", "* * * * *");
+        disabled.enabled = false;
+        let scheduler = Scheduler::new_at(&config(vec![disabled]), at(BASE)).unwrap();
+
+        assert_eq!(scheduler.statuses()[0].next_run, None);
+        assert!(scheduler.due(at(BASE + 3600)).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_schedule_is_rejected() {
+        let err = Scheduler::new(&config(vec![task("scan", "sbom_scan", "61 * * * *")]))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("scan"));
+        assert!(err.to_string().contains("invalid schedule"));
+    }
+
+    #[test]
+    fn test_due_and_time_until_next() {
+        // Given: Tasks at 10:45 and 11:00
+        let scheduler = Scheduler::new_at(
+            &config(vec![
+                task("quarter", "vuln_db_check", "45 * * * *"),
+                task("hourly", "sbom_scan", "@hourly"),
+            ]),
+            at(BASE),
+        )
+        .unwrap();
+
+        // Then: Nothing is due now, the next run is 15 minutes away
+        assert!(scheduler.due(at(BASE)).is_empty());
+        assert_eq!(
+            scheduler.time_until_next(at(BASE)),
+            Duration::from_secs(15 * 60)
+        );
+
+        // And at 10:50 only the 10:45 task is due
+        assert_eq!(scheduler.due(at(BASE + 20 * 60)), vec![0]);
+    }
+
+    #[test]
+    fn test_complete_records_outcome() {
+        let mut scheduler = Scheduler::new_at(
+            &config(vec![task("db", "vuln_db_check", "@daily")]),
+            at(BASE),
+        )
+        .unwrap();
+        let rx = scheduler.subscribe();
+
+        scheduler.complete(JobDone {
+            index: 0,
+            elapsed: Duration::from_millis(250),
+            result: Err("db missing".to_owned()),
+        });
+
+        let status = &rx.borrow()[0];
+        assert_eq!(status.runs, 1);
+        assert_eq!(status.failures, 1);
+        assert_eq!(status.last_success, Some(false));
+        assert_eq!(status.last_duration_ms, Some(250));
+        assert_eq!(status.last_message.as_deref(), Some("db missing"));
+    }
+
+    #[test]
+    fn test_task_kind_round_trip() {
+        for kind in [
+            TaskKind::SbomScan,
+            TaskKind::VulnDbCheck,
+            TaskKind::LogStoreRetention,
+            TaskKind::Report,
+        ] {
+            assert_eq!(TaskKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(
+            TaskKind::parse("vuln_db_update"),
+            Some(TaskKind::VulnDbCheck)
+        );
+        assert_eq!(TaskKind::parse("log_rotate"), None);
+    }
+
+    #[test]
+    fn test_register_log_store_retention_once() {
+        // Given: No retention task configured
+        let mut config = config(vec![task("scan", "sbom_scan", "@hourly")]);
+
+        // When: Registering the built-in task twice
+        assert!(register_log_store_retention(&mut config));
+        assert!(!register_log_store_retention(&mut config));
+
+        // Then: One hourly retention task is added
+        assert_eq!(config.tasks.len(), 2);
+        assert_eq!(config.tasks[1].name, LOG_STORE_RETENTION_TASK);
+        assert_eq!(config.tasks[1].kind, "log_store_retention");
+        assert!(Scheduler::new_at(&config, at(BASE)).is_ok());
+    }
+
+    #[test]
+    fn test_configured_retention_task_is_kept() {
+        let mut config = config(vec![task("prune", "log_store_retention", "0 4 * * *")]);
+
+        assert!(!register_log_store_retention(&mut config));
+        assert_eq!(config.tasks.len(), 1);
+        assert_eq!(config.tasks[0].schedule, "0 4 * * *");
+    }
+
+    #[tokio::test]
+    async fn test_log_store_retention_requires_log_store() {
+        let (alert_tx, _alert_rx) = mpsc::channel(1);
+        let (_snapshot_tx, snapshot_rx) =
+            watch::channel(HealthSnapshot::new(crate::health::DaemonHealth {
+                status: ironpost_core::pipeline::HealthStatus::Healthy,
+                uptime_secs: 0,
+                modules: Vec::new(),
+            }));
+        let ctx = JobContext {
+            sbom: SbomConfig::default(),
+            log_store: None,
+            alert_tx,
+            snapshot_rx,
+        };
+
+        let err = run_job(TaskKind::LogStoreRetention, ctx, String::new())
+            .await
+            .unwrap_err();
+        assert!(err.contains("not enabled"));
+    }
+}
@@ -0,0 +1,203 @@
+//! Crash-loop detection and module quarantine.
+//!
+//! The [`Supervisor`] tracks start and runtime failures per module in a
+//! sliding window. A module that fails `max_failures` times within
+//! `failure_window_secs` is quarantined: the orchestrator stops trying to
+//! (re)start it, reports the daemon as Degraded, and emits an alert.
+//!
+//! Quarantine is lifted explicitly via [`Supervisor::unquarantine`]
+//! (exposed as `POST /modules/unquarantine` and `ironpost module
+//! unquarantine`), which also resets the failure history so the module gets
+//! a fresh window.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+
+use ironpost_core::config::SupervisorConfig;
+
+/// A module that has been quarantined after repeated failures.
+#[derive(Debug, Clone, Serialize)]
+pub struct QuarantineRecord {
+    /// Module name.
+    pub name: String,
+    /// Last failure reason before quarantine.
+    pub reason: String,
+    /// Number of failures within the window that triggered quarantine.
+    pub failures: u32,
+    /// When the module was quarantined.
+    pub since: SystemTime,
+}
+
+/// Tracks module failures and quarantine state.
+pub struct Supervisor {
+    /// Crash-loop policy.
+    policy: SupervisorConfig,
+    /// Failure timestamps per module (oldest first).
+    failures: HashMap<String, VecDeque<Instant>>,
+    /// Quarantined modules by name.
+    quarantined: HashMap<String, QuarantineRecord>,
+}
+
+impl Supervisor {
+    /// Create a supervisor with the given policy.
+    pub fn new(policy: SupervisorConfig) -> Self {
+        Self {
+            policy,
+            failures: HashMap::new(),
+            quarantined: HashMap::new(),
+        }
+    }
+
+    /// Whether crash-loop supervision is enabled.
+    pub fn enabled(&self) -> bool {
+        self.policy.enabled
+    }
+
+    /// Record a failure for `name`.
+    ///
+    /// Returns `true` if this failure pushed the module into quarantine.
+    /// Failures older than the window are discarded first.
+    pub fn record_failure(&mut self, name: &str, reason: &str) -> bool {
+        self.record_failure_at(name, reason, Instant::now())
+    }
+
+    fn record_failure_at(&mut self, name: &str, reason: &str, now: Instant) -> bool {
+        if self.quarantined.contains_key(name) {
+            return false;
+        }
+
+        let window = Duration::from_secs(self.policy.failure_window_secs);
+        let history = self.failures.entry(name.to_owned()).or_default();
+        while history
+            .front()
+            .is_some_and(|t| now.saturating_duration_since(*t) > window)
+        {
+            history.pop_front();
+        }
+        history.push_back(now);
+
+        let count = u32::try_from(history.len()).unwrap_or(u32::MAX);
+        if count < self.policy.max_failures {
+            return false;
+        }
+
+        self.failures.remove(name);
+        self.quarantined.insert(
+            name.to_owned(),
+            QuarantineRecord {
+                name: name.to_owned(),
+                reason: reason.to_owned(),
+                failures: count,
+                since: SystemTime::now(),
+            },
+        );
+        true
+    }
+
+    /// Backoff to wait before the given restart attempt (1-based).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        Duration::from_millis(
+            self.policy
+                .restart_backoff_ms
+                .saturating_mul(u64::from(attempt)),
+        )
+    }
+
+    /// Whether `name` is currently quarantined.
+    pub fn is_quarantined(&self, name: &str) -> bool {
+        self.quarantined.contains_key(name)
+    }
+
+    /// Quarantine record for `name`, if any.
+    pub fn quarantine_record(&self, name: &str) -> Option<&QuarantineRecord> {
+        self.quarantined.get(name)
+    }
+
+    /// All quarantined modules.
+    pub fn quarantined(&self) -> Vec<&QuarantineRecord> {
+        self.quarantined.values().collect()
+    }
+
+    /// Lift quarantine for `name` and reset its failure history.
+    ///
+    /// Returns `false` if the module was not quarantined.
+    pub fn unquarantine(&mut self, name: &str) -> bool {
+        self.failures.remove(name);
+        self.quarantined.remove(name).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_failures: u32, window_secs: u64) -> SupervisorConfig {
+        SupervisorConfig {
+            max_failures,
+            failure_window_secs: window_secs,
+            ..SupervisorConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_quarantine_after_max_failures_in_window() {
+        // Given: A supervisor with max 3 failures in 60s
+        let mut supervisor = Supervisor::new(policy(3, 60));
+        let now = Instant::now();
+
+        // When: Recording 3 failures within the window
+        assert!(!supervisor.record_failure_at("log-pipeline", "boom", now));
+        assert!(!supervisor.record_failure_at("log-pipeline", "boom", now));
+        let quarantined = supervisor.record_failure_at("log-pipeline", "boom", now);
+
+        // Then: The module is quarantined on the third failure
+        assert!(quarantined);
+        assert!(supervisor.is_quarantined("log-pipeline"));
+        let record = supervisor.quarantine_record("log-pipeline").unwrap();
+        assert_eq!(record.failures, 3);
+        assert_eq!(record.reason, "boom");
+    }
+
+    #[test]
+    fn test_failures_outside_window_are_forgotten() {
+        // Given: A supervisor with max 2 failures in 10s
+        let mut supervisor = Supervisor::new(policy(2, 10));
+        let start = Instant::now();
+
+        // When: Two failures are 20s apart
+        assert!(!supervisor.record_failure_at("sbom-scanner", "err", start));
+        let later = start + Duration::from_secs(20);
+        let quarantined = supervisor.record_failure_at("sbom-scanner", "err", later);
+
+        // Then: The old failure expired, so no quarantine
+        assert!(!quarantined);
+        assert!(!supervisor.is_quarantined("sbom-scanner"));
+    }
+
+    #[test]
+    fn test_unquarantine_resets_history() {
+        // Given: A quarantined module
+        let mut supervisor = Supervisor::new(policy(1, 60));
+        assert!(supervisor.record_failure("container-guard", "docker down"));
+
+        // When: Lifting quarantine
+        assert!(supervisor.unquarantine("container-guard"));
+
+        // Then: The module is active again and unknown modules report false
+        assert!(!supervisor.is_quarantined("container-guard"));
+        assert!(!supervisor.unquarantine("container-guard"));
+        assert!(supervisor.quarantined().is_empty());
+    }
+
+    #[test]
+    fn test_backoff_scales_with_attempt() {
+        let supervisor = Supervisor::new(SupervisorConfig {
+            restart_backoff_ms: 100,
+            ..SupervisorConfig::default()
+        });
+        assert_eq!(supervisor.backoff(1), Duration::from_millis(100));
+        assert_eq!(supervisor.backoff(3), Duration::from_millis(300));
+    }
+}
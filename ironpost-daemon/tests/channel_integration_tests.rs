@@ -8,9 +8,11 @@
 use tokio::sync::mpsc;
 use tokio::time::{Duration, timeout};
 
-use bytes::Bytes;
-use ironpost_core::event::{ActionEvent, AlertEvent, EventMetadata, PacketEvent};
-use ironpost_core::types::{Alert, PacketInfo, Severity};
+use ironpost_core::event::{ActionEvent, AlertEvent, PacketEvent};
+use ironpost_core::types::Severity;
+use ironpost_testkit::{
+    ActionEventBuilder, AlertEventBuilder, DEFAULT_TIMEOUT, PacketEventBuilder, recv_within,
+};
 
 #[tokio::test]
 async fn test_packet_event_channel_send_receive() {
@@ -18,35 +20,16 @@ async fn test_packet_event_channel_send_receive() {
     let (tx, mut rx) = mpsc::channel::<PacketEvent>(16);
 
     // When: Sending a packet event
-    let packet_info = PacketInfo {
-        src_ip: "192.168.1.100".parse().expect("valid IP"),
-        dst_ip: "10.0.0.1".parse().expect("valid IP"),
-        src_port: 54321,
-        dst_port: 80,
-        protocol: 6, // TCP
-        size: 1500,
-        timestamp: std::time::SystemTime::now(),
-    };
-    let packet = PacketEvent {
-        id: uuid::Uuid::new_v4().to_string(),
-        metadata: EventMetadata {
-            timestamp: std::time::SystemTime::now(),
-            source_module: "ebpf-engine".to_string(),
-            trace_id: uuid::Uuid::new_v4().to_string(),
-        },
-        packet_info,
-        raw_data: Bytes::from_static(b"raw packet data"),
-    };
+    let packet = PacketEventBuilder::tcp("192.168.1.100", 80)
+        .raw_data(b"raw packet data")
+        .build();
 
     tx.send(packet.clone())
         .await
         .expect("should send packet event");
 
     // Then: Receiving should succeed
-    let received = timeout(Duration::from_secs(1), rx.recv())
-        .await
-        .expect("should not timeout")
-        .expect("should receive event");
+    let received = recv_within(&mut rx, DEFAULT_TIMEOUT).await;
 
     assert_eq!(received.packet_info.src_ip.to_string(), "192.168.1.100");
     assert_eq!(received.packet_info.dst_port, 80);
@@ -63,10 +46,7 @@ async fn test_alert_event_channel_send_receive() {
     tx.send(alert.clone()).await.expect("should send alert");
 
     // Then: Should receive alert
-    let received = timeout(Duration::from_secs(1), rx.recv())
-        .await
-        .expect("should not timeout")
-        .expect("should receive alert");
+    let received = recv_within(&mut rx, DEFAULT_TIMEOUT).await;
 
     assert_eq!(received.alert.rule_name, "Test Rule");
     assert!(matches!(received.severity, Severity::Medium));
@@ -78,25 +58,12 @@ async fn test_action_event_channel_send_receive() {
     let (tx, mut rx) = mpsc::channel::<ActionEvent>(16);
 
     // When: Sending an action event
-    let action = ActionEvent {
-        id: uuid::Uuid::new_v4().to_string(),
-        metadata: EventMetadata {
-            timestamp: std::time::SystemTime::now(),
-            source_module: "container-guard".to_string(),
-            trace_id: uuid::Uuid::new_v4().to_string(),
-        },
-        action_type: "isolate".to_string(),
-        target: "container-abc123".to_string(),
-        success: true,
-    };
+    let action = ActionEventBuilder::new("isolate", "container-abc123").build();
 
     tx.send(action.clone()).await.expect("should send action");
 
     // Then: Should receive action
-    let received = timeout(Duration::from_secs(1), rx.recv())
-        .await
-        .expect("should not timeout")
-        .expect("should receive action");
+    let received = recv_within(&mut rx, DEFAULT_TIMEOUT).await;
 
     assert_eq!(received.action_type, "isolate");
     assert_eq!(received.target, "container-abc123");
@@ -196,25 +163,7 @@ async fn test_channel_receiver_closes_gracefully() {
     // Given: A channel with pending messages
     let (tx, mut rx) = mpsc::channel::<PacketEvent>(16);
 
-    let packet_info = PacketInfo {
-        src_ip: "1.2.3.4".parse().expect("valid IP"),
-        dst_ip: "5.6.7.8".parse().expect("valid IP"),
-        src_port: 1234,
-        dst_port: 80,
-        protocol: 6, // TCP
-        size: 1000,
-        timestamp: std::time::SystemTime::now(),
-    };
-    let packet = PacketEvent {
-        id: uuid::Uuid::new_v4().to_string(),
-        metadata: EventMetadata {
-            timestamp: std::time::SystemTime::now(),
-            source_module: "test".to_string(),
-            trace_id: uuid::Uuid::new_v4().to_string(),
-        },
-        packet_info,
-        raw_data: Bytes::from_static(b"data"),
-    };
+    let packet = PacketEventBuilder::tcp("1.2.3.4", 80).build();
 
     tx.send(packet).await.expect("should send");
 
@@ -315,17 +264,7 @@ async fn test_channel_capacity_one_backpressure() {
     tokio::time::sleep(Duration::from_millis(10)).await;
 
     // Send action (should succeed immediately with capacity 1)
-    let action = ActionEvent {
-        id: uuid::Uuid::new_v4().to_string(),
-        metadata: EventMetadata {
-            timestamp: std::time::SystemTime::now(),
-            source_module: "test".to_string(),
-            trace_id: uuid::Uuid::new_v4().to_string(),
-        },
-        action_type: "test".to_string(),
-        target: "target".to_string(),
-        success: true,
-    };
+    let action = ActionEventBuilder::new("test", "target").build();
 
     tx.send(action.clone()).await.expect("should send");
 
@@ -334,27 +273,6 @@ async fn test_channel_capacity_one_backpressure() {
     assert_eq!(received.action_type, "test");
 }
 
-// Helper function to create test alerts
-fn create_test_alert(rule_name: &str) -> AlertEvent {
-    let alert = Alert {
-        id: uuid::Uuid::new_v4().to_string(),
-        title: rule_name.to_string(),
-        description: "Test alert".to_string(),
-        severity: Severity::Medium,
-        rule_name: "Test Rule".to_string(),
-        source_ip: None,
-        target_ip: None,
-        created_at: std::time::SystemTime::now(),
-    };
-
-    AlertEvent {
-        id: uuid::Uuid::new_v4().to_string(),
-        metadata: EventMetadata {
-            timestamp: std::time::SystemTime::now(),
-            source_module: "test".to_string(),
-            trace_id: uuid::Uuid::new_v4().to_string(),
-        },
-        alert,
-        severity: Severity::Medium,
-    }
+fn create_test_alert(title: &str) -> AlertEvent {
+    AlertEventBuilder::new("Test Rule").title(title).build()
 }
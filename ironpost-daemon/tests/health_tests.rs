@@ -2,8 +2,38 @@
 //!
 //! Tests the health status aggregation logic and module health reporting.
 
+use ironpost_core::config::{
+    ApiTokenConfig, AuthConfig, HealthConfig, ScheduledTaskConfig, SchedulerConfig,
+};
 use ironpost_core::pipeline::HealthStatus;
-use ironpost_daemon::health::{ModuleHealth, aggregate_status};
+use ironpost_core::plugin::{ChannelUsage, PluginState, ResourceUsage};
+use ironpost_daemon::auth::{Authorizer, Role};
+use ironpost_daemon::content::ContentStatus;
+use ironpost_daemon::health::{
+    DaemonHealth, HealthTracker, ModuleHealth, ModuleObservation, ReasonCode, aggregate_status,
+    evaluate_liveness, evaluate_readiness,
+};
+use ironpost_daemon::health_server::{
+    HealthSnapshot, handle_request, required_role, route_request,
+};
+use ironpost_daemon::resources::{ModuleUsage, ResourceReport};
+use ironpost_daemon::scheduler::Scheduler;
+
+fn module(name: &str, status: HealthStatus) -> ModuleHealth {
+    ModuleHealth {
+        name: name.to_string(),
+        enabled: true,
+        status,
+    }
+}
+
+fn daemon_health(modules: Vec<ModuleHealth>) -> DaemonHealth {
+    DaemonHealth {
+        status: aggregate_status(&modules),
+        uptime_secs: 42,
+        modules,
+    }
+}
 
 #[test]
 fn test_aggregate_status_all_healthy() {
@@ -370,3 +400,705 @@ fn test_aggregate_status_unicode_in_module_name() {
     // Then: Should handle unicode without panic
     assert!(status.is_healthy(), "should handle unicode in module names");
 }
+
+#[test]
+fn test_readiness_tolerates_degraded_sbom_scanner() {
+    // Given: Required modules healthy, sbom-scanner degraded (tolerated by default)
+    let modules = vec![
+        module("ebpf-engine", HealthStatus::Healthy),
+        module("log-pipeline", HealthStatus::Healthy),
+        module(
+            "sbom-scanner",
+            HealthStatus::Degraded("vuln db stale".to_string()),
+        ),
+    ];
+    let policy = HealthConfig {
+        readiness_required: vec!["log-pipeline".to_string(), "ebpf-engine".to_string()],
+        ..HealthConfig::default()
+    };
+
+    // When: Evaluating readiness
+    let result = evaluate_readiness(&modules, &policy);
+
+    // Then: Daemon should be ready
+    assert!(result.ok, "unexpected reasons: {:?}", result.reasons);
+}
+
+#[test]
+fn test_readiness_fails_when_required_module_degraded() {
+    // Given: A required module that is degraded and not tolerated
+    let modules = vec![module(
+        "log-pipeline",
+        HealthStatus::Degraded("buffer 90% full".to_string()),
+    )];
+    let policy = HealthConfig::default();
+
+    // When: Evaluating readiness
+    let result = evaluate_readiness(&modules, &policy);
+
+    // Then: Daemon should not be ready
+    assert!(!result.ok);
+    assert!(result.reasons[0].contains("log-pipeline"));
+}
+
+#[test]
+fn test_readiness_fails_when_required_module_missing() {
+    // Given: ebpf-engine is required but not registered
+    let modules = vec![module("log-pipeline", HealthStatus::Healthy)];
+    let policy = HealthConfig {
+        readiness_required: vec!["ebpf-engine".to_string()],
+        ..HealthConfig::default()
+    };
+
+    // When: Evaluating readiness
+    let result = evaluate_readiness(&modules, &policy);
+
+    // Then: Missing module should be reported
+    assert!(!result.ok);
+    assert!(result.reasons[0].contains("ebpf-engine: not running"));
+}
+
+#[test]
+fn test_readiness_ignores_non_required_modules() {
+    // Given: A non-required module is unhealthy
+    let modules = vec![
+        module("log-pipeline", HealthStatus::Healthy),
+        module(
+            "container-guard",
+            HealthStatus::Unhealthy("docker down".to_string()),
+        ),
+    ];
+    let policy = HealthConfig {
+        readiness_required: vec!["log-pipeline".to_string()],
+        ..HealthConfig::default()
+    };
+
+    // When: Evaluating readiness
+    let result = evaluate_readiness(&modules, &policy);
+
+    // Then: Daemon should still be ready
+    assert!(result.ok);
+}
+
+#[test]
+fn test_liveness_ignores_unhealthy_modules_by_default() {
+    // Given: An unhealthy module and a fresh snapshot
+    let health = daemon_health(vec![module(
+        "log-pipeline",
+        HealthStatus::Unhealthy("stopped".to_string()),
+    )]);
+
+    // When: Evaluating liveness with the default policy
+    let result = evaluate_liveness(&health, 0, 15, &HealthConfig::default());
+
+    // Then: Daemon is still alive
+    assert!(result.ok);
+}
+
+#[test]
+fn test_liveness_fails_on_unhealthy_when_configured() {
+    // Given: liveness_fail_on_unhealthy enabled
+    let health = daemon_health(vec![module(
+        "log-pipeline",
+        HealthStatus::Unhealthy("stopped".to_string()),
+    )]);
+    let policy = HealthConfig {
+        liveness_fail_on_unhealthy: true,
+        ..HealthConfig::default()
+    };
+
+    // When: Evaluating liveness
+    let result = evaluate_liveness(&health, 0, 15, &policy);
+
+    // Then: Liveness fails
+    assert!(!result.ok);
+}
+
+#[test]
+fn test_liveness_fails_on_stale_snapshot() {
+    // Given: A snapshot older than the staleness limit
+    let health = daemon_health(vec![]);
+
+    // When: Evaluating liveness
+    let result = evaluate_liveness(&health, 20, 15, &HealthConfig::default());
+
+    // Then: Liveness fails with a stale reason
+    assert!(!result.ok);
+    assert!(result.reasons[0].contains("stale"));
+}
+
+#[test]
+fn test_route_request_healthz_and_readyz() {
+    // Given: A snapshot where the only module is unhealthy
+    let snapshot = HealthSnapshot::new(daemon_health(vec![module(
+        "log-pipeline",
+        HealthStatus::Unhealthy("stopped".to_string()),
+    )]));
+    let policy = HealthConfig::default();
+
+    // When: Routing probe requests
+    let (live_code, live_body) = route_request("GET /healthz HTTP/1.1", &snapshot, &policy);
+    let (ready_code, ready_body) = route_request("GET /readyz HTTP/1.1", &snapshot, &policy);
+
+    // Then: Alive but not ready
+    assert_eq!(live_code, 200);
+    assert!(live_body.contains(r#""status":"ok""#));
+    assert_eq!(ready_code, 503);
+    assert!(ready_body.contains("log-pipeline"));
+}
+
+#[test]
+fn test_route_request_unknown_path_and_method() {
+    // Given: A healthy snapshot
+    let snapshot = HealthSnapshot::new(daemon_health(vec![]));
+    let policy = HealthConfig::default();
+
+    // When/Then: Unknown path returns 404, non-GET returns 405
+    assert_eq!(
+        route_request("GET /nope HTTP/1.1", &snapshot, &policy).0,
+        404
+    );
+    assert_eq!(
+        route_request("POST /readyz HTTP/1.1", &snapshot, &policy).0,
+        405
+    );
+}
+
+#[test]
+fn test_route_request_status_includes_tasks() {
+    // Given: A snapshot with one scheduled task
+    let scheduler = Scheduler::new(&SchedulerConfig {
+        enabled: true,
+        tasks: vec![ScheduledTaskConfig {
+            name: "nightly-scan".to_string(),
+            kind: "sbom_scan".to_string(),
+            schedule: "0 3 * * *".to_string(),
+            ..ScheduledTaskConfig::default()
+        }],
+    })
+    .unwrap();
+    let snapshot = HealthSnapshot::new(daemon_health(vec![])).with_tasks(scheduler.statuses());
+
+    // When: Requesting the status endpoint
+    let (code, body) = route_request("GET /status HTTP/1.1", &snapshot, &HealthConfig::default());
+
+    // Then: Health and task status are both reported
+    assert_eq!(code, 200);
+    assert!(body.contains(r#""uptime_secs""#));
+    assert!(body.contains(r#""name":"nightly-scan""#));
+    assert!(body.contains(r#""next_run":"#));
+    assert!(body.contains(r#""last_run":null"#));
+}
+
+fn observation(name: &str, state: PluginState, status: HealthStatus) -> ModuleObservation {
+    ModuleObservation {
+        name: name.to_string(),
+        state,
+        status,
+        quarantine_reason: None,
+    }
+}
+
+#[test]
+fn test_reason_code_classification() {
+    let degraded = HealthStatus::Degraded("slow".to_string());
+    assert_eq!(
+        ReasonCode::classify(PluginState::Running, &HealthStatus::Healthy, false),
+        ReasonCode::Ok
+    );
+    assert_eq!(
+        ReasonCode::classify(PluginState::Running, &degraded, false),
+        ReasonCode::Degraded
+    );
+    assert_eq!(
+        ReasonCode::classify(PluginState::Failed, &HealthStatus::Healthy, false),
+        ReasonCode::Failed
+    );
+    assert_eq!(
+        ReasonCode::classify(PluginState::Stopped, &HealthStatus::Healthy, false),
+        ReasonCode::NotRunning
+    );
+    assert_eq!(
+        ReasonCode::classify(PluginState::Stopped, &degraded, true),
+        ReasonCode::Quarantined
+    );
+}
+
+#[test]
+fn test_health_tracker_records_state_changes_and_restarts() {
+    // Given: A daemon started 100 seconds ago with one healthy module
+    let started = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000);
+    let mut tracker = HealthTracker::new(started);
+    let at = |secs: u64| started + std::time::Duration::from_secs(secs);
+
+    let first = tracker.report(
+        HealthStatus::Healthy,
+        vec![observation(
+            "log-pipeline",
+            PluginState::Running,
+            HealthStatus::Healthy,
+        )],
+        at(100),
+    );
+    assert_eq!(first.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(first.started_at, 1_000);
+    assert_eq!(first.uptime_secs, 100);
+    assert_eq!(first.generated_at, 1_100);
+    assert_eq!(first.modules[0].reason_code, ReasonCode::Ok);
+    assert_eq!(first.modules[0].since, 1_000);
+    assert_eq!(first.modules[0].restarts, 0);
+    assert_eq!(first.modules[0].reason, None);
+
+    // When: The module degrades, then is restarted by the supervisor
+    let degraded = HealthStatus::Degraded("buffer 90% full".to_string());
+    let second = tracker.report(
+        degraded.clone(),
+        vec![observation(
+            "log-pipeline",
+            PluginState::Running,
+            degraded.clone(),
+        )],
+        at(200),
+    );
+    tracker.record_restart("log-pipeline");
+    let third = tracker.report(
+        degraded.clone(),
+        vec![observation("log-pipeline", PluginState::Running, degraded)],
+        at(300),
+    );
+
+    // Then: `since` moves only on a reason-code change and restarts accumulate
+    assert_eq!(second.modules[0].reason_code, ReasonCode::Degraded);
+    assert_eq!(second.modules[0].since, 1_200);
+    assert_eq!(second.modules[0].reason.as_deref(), Some("buffer 90% full"));
+    assert_eq!(third.modules[0].since, 1_200);
+    assert_eq!(third.modules[0].restarts, 1);
+}
+
+#[test]
+fn test_route_request_health_report() {
+    let policy = HealthConfig::default();
+
+    // Without a report the endpoint is not ready yet
+    let snapshot = HealthSnapshot::new(daemon_health(vec![]));
+    assert_eq!(
+        route_request("GET /health HTTP/1.1", &snapshot, &policy).0,
+        503
+    );
+
+    // Given: A snapshot with a quarantined module in its report
+    let mut tracker = HealthTracker::new(std::time::SystemTime::now());
+    let report = tracker.report(
+        HealthStatus::Degraded("quarantined: container-guard".to_string()),
+        vec![ModuleObservation {
+            quarantine_reason: Some("quarantined: docker unreachable".to_string()),
+            ..observation(
+                "container-guard",
+                PluginState::Stopped,
+                HealthStatus::Healthy,
+            )
+        }],
+        std::time::SystemTime::now(),
+    );
+    let snapshot = HealthSnapshot::new(daemon_health(vec![])).with_report(report);
+
+    // When: Requesting the health report
+    let (code, body) = route_request("GET /health HTTP/1.1", &snapshot, &policy);
+
+    // Then: The structured report is served
+    assert_eq!(code, 200);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    let module = &json["modules"][0];
+    assert_eq!(module["name"], "container-guard");
+    assert_eq!(module["state"], "stopped");
+    assert_eq!(module["reason_code"], "quarantined");
+    assert_eq!(module["reason"], "quarantined: docker unreachable");
+    assert_eq!(module["restarts"], 0);
+    assert_eq!(required_role("/health"), Some(Role::ReadOnly));
+}
+
+#[test]
+fn test_route_request_status_includes_resources() {
+    // Given: A snapshot with resource usage for one module
+    let report = ResourceReport {
+        modules: vec![ModuleUsage {
+            name: "log-pipeline".to_string(),
+            usage: ResourceUsage {
+                tasks: 3,
+                channels: vec![ChannelUsage {
+                    name: "raw_logs".to_string(),
+                    depth: 42,
+                    capacity: 1024,
+                }],
+                memory_bytes: 4096,
+            },
+        }],
+        ..ResourceReport::default()
+    };
+    let snapshot = HealthSnapshot::new(daemon_health(vec![])).with_resources(report);
+
+    // When: Requesting the status endpoint
+    let (code, body) = route_request("GET /status HTTP/1.1", &snapshot, &HealthConfig::default());
+
+    // Then: Per-module usage is reported alongside process and runtime figures
+    assert_eq!(code, 200);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let module = &json["resources"]["modules"][0];
+    assert_eq!(module["name"], "log-pipeline");
+    assert_eq!(module["tasks"], 3);
+    assert_eq!(module["channels"][0]["depth"], 42);
+    assert_eq!(module["memory_bytes"], 4096);
+    assert!(json["resources"]["process"].is_object());
+    assert!(json["resources"]["runtime"]["alive_tasks"].is_number());
+}
+
+#[test]
+fn test_route_request_status_reports_content_only_when_enabled() {
+    // Given: Snapshots with and without content distribution
+    let disabled = HealthSnapshot::new(daemon_health(vec![]));
+    let enabled = HealthSnapshot::new(daemon_health(vec![])).with_content(Some(ContentStatus {
+        version: Some("0123456789abcdef".to_string()),
+        rules: 4,
+        policies: 2,
+        versions: vec!["0123456789abcdef".to_string()],
+        ..ContentStatus::default()
+    }));
+
+    // When: Requesting the status endpoint for both
+    let (_, disabled_body) =
+        route_request("GET /status HTTP/1.1", &disabled, &HealthConfig::default());
+    let (_, enabled_body) =
+        route_request("GET /status HTTP/1.1", &enabled, &HealthConfig::default());
+
+    // Then: The content version is reported only when enabled
+    let disabled_json: serde_json::Value = serde_json::from_str(&disabled_body).unwrap();
+    assert!(disabled_json.get("content").is_none());
+    let enabled_json: serde_json::Value = serde_json::from_str(&enabled_body).unwrap();
+    assert_eq!(enabled_json["content"]["version"], "0123456789abcdef");
+    assert_eq!(enabled_json["content"]["rules"], 4);
+    assert_eq!(enabled_json["content"]["policies"], 2);
+}
+
+fn token_authorizer(token: &str, role: &str) -> Authorizer {
+    use sha2::{Digest, Sha256};
+    let digest: String = Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Authorizer::from_config(&AuthConfig {
+        enabled: true,
+        tokens: vec![ApiTokenConfig {
+            name: "dashboard".to_string(),
+            token_sha256: digest,
+            role: role.to_string(),
+        }],
+        ..AuthConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_handle_request_probes_stay_unauthenticated() {
+    // Given: Auth enabled and a healthy daemon
+    let auth = token_authorizer("secret", "read-only");
+    let snapshot = HealthSnapshot::new(daemon_health(vec![]));
+
+    // When: Probing without a token
+    let (live, _) = handle_request(
+        "GET /healthz HTTP/1.1\r\n\r\n",
+        &snapshot,
+        &HealthConfig::default(),
+        &auth,
+    );
+    let (ready, _) = handle_request(
+        "GET /readyz HTTP/1.1\r\n\r\n",
+        &snapshot,
+        &HealthConfig::default(),
+        &auth,
+    );
+
+    // Then: Probes are answered
+    assert_eq!(live, 200);
+    assert_eq!(ready, 200);
+}
+
+#[test]
+fn test_handle_request_status_requires_token() {
+    // Given: Auth enabled with a read-only token
+    let auth = token_authorizer("secret", "read-only");
+    let snapshot = HealthSnapshot::new(daemon_health(vec![]));
+    let config = HealthConfig::default();
+
+    // When: Requesting status without, with a wrong, and with a valid token
+    let (missing, body) = handle_request("GET /status HTTP/1.1\r\n\r\n", &snapshot, &config, &auth);
+    let (wrong, _) = handle_request(
+        "GET /status HTTP/1.1\r\nAuthorization: Bearer guess\r\n\r\n",
+        &snapshot,
+        &config,
+        &auth,
+    );
+    let (ok, _) = handle_request(
+        "GET /status HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n",
+        &snapshot,
+        &config,
+        &auth,
+    );
+
+    // Then: Only the valid token is served
+    assert_eq!(missing, 401);
+    assert!(body.contains("missing bearer token"));
+    assert_eq!(wrong, 401);
+    assert_eq!(ok, 200);
+}
+
+#[test]
+fn test_handle_request_without_auth_serves_status() {
+    let snapshot = HealthSnapshot::new(daemon_health(vec![]));
+    let (code, _) = handle_request(
+        "GET /status HTTP/1.1\r\n\r\n",
+        &snapshot,
+        &HealthConfig::default(),
+        &Authorizer::disabled(),
+    );
+    assert_eq!(code, 200);
+}
+
+#[test]
+fn test_handle_request_support_bundle_requires_admin() {
+    // Given: Auth enabled with a read-only token
+    let auth = token_authorizer("secret", "read-only");
+    let snapshot = HealthSnapshot::new(daemon_health(vec![]));
+
+    // When: Requesting the support bundle with the read-only token
+    let (code, _) = handle_request(
+        "GET /support-bundle HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n",
+        &snapshot,
+        &HealthConfig::default(),
+        &auth,
+    );
+
+    // Then: The request is forbidden
+    assert_eq!(required_role("/support-bundle"), Some(Role::Admin));
+    assert_eq!(code, 403);
+}
+
+#[test]
+fn test_required_role_ebpf_status_is_read_only() {
+    assert_eq!(required_role("/ebpf"), Some(Role::ReadOnly));
+}
+
+#[test]
+fn test_required_role_ebpf_blocklist() {
+    assert_eq!(required_role("/ebpf/blocklist"), Some(Role::ReadOnly));
+    assert_eq!(
+        required_role("/ebpf/blocklist/remove"),
+        Some(Role::Operator)
+    );
+    assert_eq!(required_role("/ebpf/rules/import"), Some(Role::Operator));
+}
+
+#[test]
+fn test_required_role_rules() {
+    assert_eq!(required_role("/rules"), Some(Role::ReadOnly));
+}
+
+#[test]
+fn test_required_role_container_events() {
+    assert_eq!(required_role("/container/events"), Some(Role::ReadOnly));
+}
+
+#[test]
+fn test_required_role_alerts() {
+    assert_eq!(required_role("/alerts"), Some(Role::ReadOnly));
+}
+
+#[test]
+fn test_required_role_config() {
+    assert_eq!(required_role("/config"), Some(Role::Admin));
+}
+
+#[test]
+fn test_is_mutating_covers_state_changing_routes() {
+    use ironpost_daemon::health_server::is_mutating;
+
+    for path in [
+        "/container/isolate",
+        "/container/release",
+        "/ebpf/blocklist/remove",
+        "/ebpf/rules/import",
+        "/modules/unquarantine",
+        "/content/rollback",
+    ] {
+        assert!(is_mutating("POST", path), "{} should be mutating", path);
+    }
+    assert!(is_mutating("POST", "/ebpf/blocklist"));
+    assert!(!is_mutating("GET", "/ebpf/blocklist"));
+    assert!(!is_mutating("POST", "/logs/search"));
+    assert!(!is_mutating("POST", "/container/policies/test"));
+    assert!(!is_mutating("GET", "/support-bundle"));
+}
+
+/// Send a raw HTTP request and return the whole response.
+async fn exchange<S>(mut stream: S, request: &str) -> String
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+/// Send a raw HTTP request and return the status code.
+async fn send_request<S>(stream: S, request: &str) -> u16
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    exchange(stream, request)
+        .await
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_mutating_requests_over_tcp_require_auth() {
+    use ironpost_daemon::daemon_control::OrchestratorHandle;
+    use ironpost_daemon::health_server::{ControlSources, spawn_health_server};
+    use std::sync::Arc;
+
+    // Given: Control listeners with an orchestrator whose main loop is gone
+    let (handle, command_rx) = OrchestratorHandle::channel();
+    drop(command_rx);
+    let control = ControlSources {
+        orchestrator: Some(handle),
+        ..ControlSources::default()
+    };
+    let (_snapshot_tx, snapshot_rx) =
+        tokio::sync::watch::channel(HealthSnapshot::new(daemon_health(vec![])));
+    let (shutdown_tx, _) = tokio::sync::broadcast::channel(1);
+    let unquarantine = "POST /modules/unquarantine HTTP/1.1\r\nContent-Length: 25\r\n\r\n{\"module\":\"log-pipeline\"}";
+    let serve_tcp = |auth: Authorizer| {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let addr = listener.local_addr().unwrap();
+        spawn_health_server(
+            tokio::net::TcpListener::from_std(listener).unwrap(),
+            HealthConfig::default(),
+            Arc::new(auth),
+            None,
+            control.clone(),
+            snapshot_rx.clone(),
+            shutdown_tx.subscribe(),
+        );
+        addr
+    };
+
+    // When/Then: Without [auth], TCP requests are refused before reaching the handler
+    let addr = serve_tcp(Authorizer::disabled());
+    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    assert_eq!(send_request(stream, unquarantine).await, 403);
+    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let rollback = "POST /content/rollback HTTP/1.1\r\n\r\n";
+    assert_eq!(send_request(stream, rollback).await, 403);
+    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let list = "GET /modules/quarantined HTTP/1.1\r\n\r\n";
+    assert_eq!(send_request(stream, list).await, 503);
+
+    // When/Then: With [auth], the token decides
+    let addr = serve_tcp(token_authorizer("op-token", "operator"));
+    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    assert_eq!(send_request(stream, unquarantine).await, 401);
+    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let authorized = unquarantine.replacen("\r\n", "\r\nAuthorization: Bearer op-token\r\n", 1);
+    assert_eq!(send_request(stream, &authorized).await, 503);
+
+    // When/Then: The unix socket accepts them without [auth]
+    #[cfg(unix)]
+    {
+        use ironpost_daemon::health_server::{bind_control_socket, spawn_socket_server};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("control.sock");
+        let path = path.to_str().unwrap();
+        let listener = bind_control_socket(path).await.unwrap();
+        spawn_socket_server(
+            listener,
+            HealthConfig::default(),
+            Arc::new(Authorizer::disabled()),
+            control.clone(),
+            snapshot_rx.clone(),
+            shutdown_tx.subscribe(),
+        );
+        let stream = tokio::net::UnixStream::connect(path).await.unwrap();
+        assert_eq!(send_request(stream, unquarantine).await, 503);
+    }
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn test_config_endpoint_redacts_sink_secrets() {
+    use ironpost_core::config::{AlertSinkConfig, IronpostConfig};
+    use ironpost_daemon::health_server::{ControlSources, spawn_health_server};
+    use ironpost_daemon::support_bundle::{BundleSources, RecentEvents};
+    use std::sync::Arc;
+
+    // Given: A config with a Slack sink and a webhook sink with an auth header
+    let mut config = IronpostConfig::default();
+    config.log_pipeline.sinks.push(AlertSinkConfig {
+        name: "slack".to_owned(),
+        kind: "slack".to_owned(),
+        url: "https://hooks.slack.com/services/T000/B000/XXXXXXXX".to_owned(),
+        ..Default::default()
+    });
+    config.log_pipeline.sinks.push(AlertSinkConfig {
+        name: "soar".to_owned(),
+        kind: "webhook".to_owned(),
+        url: "https://soar.example.com/hook".to_owned(),
+        headers: [("Authorization".to_owned(), "Bearer s3cret".to_owned())].into(),
+        ..Default::default()
+    });
+    let control = ControlSources {
+        bundle: Some(BundleSources {
+            config,
+            recent: RecentEvents::new(10),
+            targets: Default::default(),
+        }),
+        ..ControlSources::default()
+    };
+    let (_snapshot_tx, snapshot_rx) =
+        tokio::sync::watch::channel(HealthSnapshot::new(daemon_health(vec![])));
+    let (shutdown_tx, _) = tokio::sync::broadcast::channel(1);
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let addr = listener.local_addr().unwrap();
+    spawn_health_server(
+        tokio::net::TcpListener::from_std(listener).unwrap(),
+        HealthConfig::default(),
+        Arc::new(token_authorizer("admin-token", "admin")),
+        None,
+        control,
+        snapshot_rx,
+        shutdown_tx.subscribe(),
+    );
+
+    // When: An admin reads the effective configuration
+    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let response = exchange(
+        stream,
+        "GET /config HTTP/1.1\r\nAuthorization: Bearer admin-token\r\n\r\n",
+    )
+    .await;
+
+    // Then: Sink URL paths and header values are masked
+    assert!(response.starts_with("HTTP/1.1 200"), "got: {}", response);
+    assert!(response.contains("https://hooks.slack.com/[redacted]"));
+    assert!(response.contains(r#""Authorization":"[redacted]""#));
+    assert!(!response.contains("XXXXXXXX"));
+    assert!(!response.contains("s3cret"));
+    let _ = shutdown_tx.send(());
+}
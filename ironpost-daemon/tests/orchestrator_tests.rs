@@ -6,61 +6,26 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use ironpost_core::config::IronpostConfig;
+use ironpost_testkit::TestConfig;
 use tokio::time::sleep;
 
 /// Helper function to create a minimal test config.
 fn minimal_test_config() -> IronpostConfig {
-    let toml_str = r#"
-[general]
-log_level = "info"
-pid_file = ""
-
-[metrics]
-enabled = false
-
-[ebpf]
-enabled = false
-
-[log_pipeline]
-enabled = false
-
-[container]
-enabled = false
-
-[sbom]
-enabled = false
-"#;
-    IronpostConfig::parse(toml_str).expect("failed to parse minimal config")
+    TestConfig::new().build()
 }
 
 /// Helper function to create a config with log-pipeline enabled.
 fn log_pipeline_only_config() -> IronpostConfig {
-    let toml_str = r#"
-[general]
-log_level = "info"
-pid_file = ""
-
-[metrics]
-enabled = false
-
-[ebpf]
-enabled = false
-
-[log_pipeline]
-enabled = true
-sources = ["syslog"]
-syslog_bind = "0.0.0.0:514"
-watch_paths = []
-batch_size = 100
-flush_interval_secs = 5
-
-[container]
-enabled = false
-
-[sbom]
-enabled = false
-"#;
-    IronpostConfig::parse(toml_str).expect("failed to parse log-pipeline config")
+    TestConfig::new()
+        .log_pipeline(true)
+        .with(|config| {
+            config.log_pipeline.sources = vec!["syslog".to_owned()];
+            config.log_pipeline.syslog_bind = "0.0.0.0:514".to_owned();
+            config.log_pipeline.watch_paths = Vec::new();
+            config.log_pipeline.batch_size = 100;
+            config.log_pipeline.flush_interval_secs = 5;
+        })
+        .build()
 }
 
 /// Helper function to create a config with container-guard enabled (requires log-pipeline).
@@ -333,3 +298,68 @@ enabled = false
     assert!(!retrieved_config.container.enabled);
     assert!(!retrieved_config.sbom.enabled);
 }
+
+#[tokio::test]
+async fn test_orchestrator_commands_report_quarantine_state() {
+    use ironpost_daemon::daemon_control::OrchestratorCommand;
+
+    // Given: An orchestrator with no quarantined modules
+    let mut orchestrator =
+        ironpost_daemon::orchestrator::Orchestrator::build_from_config(minimal_test_config())
+            .await
+            .expect("orchestrator should build");
+
+    // When: Listing quarantined modules through the command channel path
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    orchestrator
+        .handle_command(OrchestratorCommand::Quarantined { reply })
+        .await;
+
+    // Then: The list is empty
+    assert!(rx.await.expect("reply sent").is_empty());
+
+    // When: Lifting quarantine for a module that is not quarantined
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    orchestrator
+        .handle_command(OrchestratorCommand::Unquarantine {
+            module: "log-pipeline".to_owned(),
+            reply,
+        })
+        .await;
+
+    // Then: The command is rejected as not found
+    let err = rx
+        .await
+        .expect("reply sent")
+        .expect_err("module is not quarantined");
+    assert_eq!(err.status, 404);
+    assert!(err.message.contains("not quarantined"));
+}
+
+#[tokio::test]
+async fn test_orchestrator_rollback_requires_content_distribution() {
+    use ironpost_daemon::daemon_control::OrchestratorCommand;
+
+    // Given: An orchestrator with [content] disabled
+    let mut orchestrator =
+        ironpost_daemon::orchestrator::Orchestrator::build_from_config(minimal_test_config())
+            .await
+            .expect("orchestrator should build");
+
+    // When: Rolling content back through the command channel path
+    let (reply, rx) = tokio::sync::oneshot::channel();
+    orchestrator
+        .handle_command(OrchestratorCommand::RollbackContent {
+            version: None,
+            reply,
+        })
+        .await;
+
+    // Then: The rollback is rejected as not found
+    let err = rx
+        .await
+        .expect("reply sent")
+        .expect_err("content distribution is disabled");
+    assert_eq!(err.status, 404);
+    assert!(err.message.contains("not enabled"));
+}
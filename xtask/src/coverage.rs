@@ -0,0 +1,105 @@
+//! `cargo xtask coverage` — 워크스페이스 코드 커버리지 (cargo-llvm-cov)
+//!
+//! `cargo llvm-cov`로 워크스페이스 테스트를 실행하고 커버리지를 집계합니다.
+//! `#![no_std]` 크레이트(`ironpost-ebpf-common`)는 호스트 테스트 대상이 아니므로
+//! 제외하고, BPF 타겟 전용인 커널 프로그램은 워크스페이스에서 이미 제외되어 있습니다.
+//!
+//! `cargo install cargo-llvm-cov`와 `rustup component add llvm-tools-preview`가
+//! 필요합니다.
+
+use std::process::Command;
+
+/// 커버리지에서 제외하는 `#![no_std]` 크레이트
+const NO_STD_CRATES: &[&str] = &["ironpost-ebpf-common"];
+
+/// `coverage` 옵션
+pub struct CoverageOptions {
+    /// HTML 리포트 생성 (`target/llvm-cov/html`)
+    pub html: bool,
+    /// LCOV 파일 생성 (`target/llvm-cov/lcov.info`, CI 업로드용)
+    pub lcov: bool,
+    /// 라인 커버리지가 이 비율(%) 미만이면 실패
+    pub fail_under: Option<f64>,
+}
+
+/// 커버리지를 측정합니다.
+pub fn run(opts: &CoverageOptions) -> Result<(), String> {
+    let root = crate::workspace_root();
+    println!("Running workspace tests with coverage instrumentation...");
+    let status = Command::new("cargo")
+        .current_dir(&root)
+        .args(llvm_cov_args(opts))
+        .status()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+    if !status.success() {
+        return Err(
+            "cargo llvm-cov failed (install with `cargo install cargo-llvm-cov` and `rustup component add llvm-tools-preview`)"
+                .to_owned(),
+        );
+    }
+
+    if opts.html {
+        println!(
+            "HTML report: {}",
+            root.join("target/llvm-cov/html/index.html").display()
+        );
+    }
+    if opts.lcov {
+        println!(
+            "LCOV report: {}",
+            root.join("target/llvm-cov/lcov.info").display()
+        );
+    }
+    Ok(())
+}
+
+/// `cargo llvm-cov` 인자를 만듭니다.
+fn llvm_cov_args(opts: &CoverageOptions) -> Vec<String> {
+    let mut args: Vec<String> = vec!["llvm-cov".into(), "--workspace".into()];
+    for krate in NO_STD_CRATES {
+        args.push("--exclude".into());
+        args.push((*krate).into());
+    }
+    if opts.html {
+        args.push("--html".into());
+    } else if opts.lcov {
+        args.extend([
+            "--lcov".into(),
+            "--output-path".into(),
+            "target/llvm-cov/lcov.info".into(),
+        ]);
+    }
+    if let Some(percent) = opts.fail_under {
+        args.push("--fail-under-lines".into());
+        args.push(percent.to_string());
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_llvm_cov_args_exclude_no_std_crates() {
+        let args = llvm_cov_args(&CoverageOptions {
+            html: false,
+            lcov: true,
+            fail_under: Some(70.5),
+        });
+        assert_eq!(
+            args,
+            [
+                "llvm-cov",
+                "--workspace",
+                "--exclude",
+                "ironpost-ebpf-common",
+                "--lcov",
+                "--output-path",
+                "target/llvm-cov/lcov.info",
+                "--fail-under-lines",
+                "70.5",
+            ]
+        );
+    }
+}
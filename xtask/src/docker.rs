@@ -0,0 +1,193 @@
+//! `cargo xtask docker` — eBPF 포함 데몬 컨테이너 이미지 빌드
+//!
+//! `docker/Dockerfile.daemon`을 `docker buildx`로 빌드합니다. eBPF 커널
+//! 프로그램은 빌드 머신에서 한 번 컴파일하고(BPF 바이트코드는 아키텍처 무관),
+//! 데몬과 CLI는 `--arch`마다 교차 컴파일합니다. 결과 이미지는 데몬, eBPF
+//! 오브젝트, 기본 설정과 예제 룰/정책만 포함하며 privileged 컨테이너나
+//! DaemonSet(`docker/kubernetes/daemonset.yaml`)으로 실행합니다.
+//!
+//! 단일 아키텍처는 로컬 Docker에 로드하고, 여러 아키텍처는 `--push`로
+//! 레지스트리에 멀티 플랫폼 이미지로 올려야 합니다.
+
+use std::path::Path;
+use std::process::Command;
+
+use clap::ValueEnum;
+
+/// 이미지 빌드에 사용하는 Dockerfile (워크스페이스 루트 기준)
+const DOCKERFILE: &str = "docker/Dockerfile.daemon";
+/// `--tag`가 없을 때 사용하는 이미지 이름
+const DEFAULT_IMAGE: &str = "ironpost-daemon";
+
+/// 이미지 대상 아키텍처
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DockerArch {
+    /// x86_64
+    Amd64,
+    /// aarch64
+    Arm64,
+}
+
+impl DockerArch {
+    /// `--platform` 값
+    fn platform(self) -> &'static str {
+        match self {
+            Self::Amd64 => "linux/amd64",
+            Self::Arm64 => "linux/arm64",
+        }
+    }
+
+    /// 빌드 머신의 아키텍처 (지원하지 않으면 `None`)
+    fn host() -> Option<Self> {
+        match std::env::consts::ARCH {
+            "x86_64" => Some(Self::Amd64),
+            "aarch64" => Some(Self::Arm64),
+            _ => None,
+        }
+    }
+}
+
+/// `docker` 옵션
+pub struct DockerOptions {
+    /// 대상 아키텍처 (비어 있으면 빌드 머신 아키텍처)
+    pub arch: Vec<DockerArch>,
+    /// 이미지 태그 (기본값: `ironpost-daemon:<버전>`)
+    pub tag: Option<String>,
+    /// 빌드 후 레지스트리에 push
+    pub push: bool,
+    /// Dockerfile에 전달할 추가 빌드 인자 (`KEY=VALUE`, 예: `BPF_LINKER_VERSION=0.9.15`)
+    pub build_args: Vec<String>,
+}
+
+/// 이미지를 빌드합니다.
+pub fn run(opts: &DockerOptions) -> Result<(), String> {
+    let root = crate::workspace_root();
+    let version = crate::package::package_version(&root)?;
+    let arch = if opts.arch.is_empty() {
+        vec![DockerArch::host().ok_or_else(|| {
+            format!(
+                "unsupported host architecture {}; pass --arch",
+                std::env::consts::ARCH
+            )
+        })?]
+    } else {
+        opts.arch.clone()
+    };
+    if arch.len() > 1 && !opts.push {
+        return Err(
+            "multi-arch images cannot be loaded into the local docker; add --push".to_owned(),
+        );
+    }
+
+    let tag = opts
+        .tag
+        .clone()
+        .unwrap_or_else(|| format!("{DEFAULT_IMAGE}:{version}"));
+    let args = buildx_args(&root, &arch, &tag, &version, opts.push, &opts.build_args);
+
+    println!("Building {tag} for {}...", platforms(&arch));
+    let status = Command::new("docker")
+        .current_dir(&root)
+        .args(&args)
+        .status()
+        .map_err(|e| format!("failed to run docker (is Docker with buildx installed?): {e}"))?;
+    if !status.success() {
+        return Err("docker image build failed".to_owned());
+    }
+
+    if opts.push {
+        println!("Pushed {tag}");
+    } else {
+        println!("Image {tag} loaded into the local docker");
+    }
+    Ok(())
+}
+
+/// `docker buildx build` 인자를 만듭니다.
+fn buildx_args(
+    root: &Path,
+    arch: &[DockerArch],
+    tag: &str,
+    version: &str,
+    push: bool,
+    build_args: &[String],
+) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        "buildx".into(),
+        "build".into(),
+        "--file".into(),
+        root.join(DOCKERFILE).display().to_string(),
+        "--platform".into(),
+        platforms(arch),
+        "--tag".into(),
+        tag.to_owned(),
+        "--build-arg".into(),
+        format!("IRONPOST_VERSION={version}"),
+    ];
+    for build_arg in build_args {
+        args.push("--build-arg".into());
+        args.push(build_arg.clone());
+    }
+    args.push(if push { "--push" } else { "--load" }.into());
+    args.push(root.display().to_string());
+    args
+}
+
+/// `--platform` 값 (쉼표로 구분)
+fn platforms(arch: &[DockerArch]) -> String {
+    arch.iter()
+        .map(|a| a.platform())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buildx_args_single_arch_loads_locally() {
+        let args = buildx_args(
+            Path::new("/src"),
+            &[DockerArch::Arm64],
+            "ironpost-daemon:0.1.0",
+            "0.1.0",
+            false,
+            &["BPF_LINKER_VERSION=0.9.14".to_owned()],
+        );
+        assert_eq!(
+            args,
+            [
+                "buildx",
+                "build",
+                "--file",
+                "/src/docker/Dockerfile.daemon",
+                "--platform",
+                "linux/arm64",
+                "--tag",
+                "ironpost-daemon:0.1.0",
+                "--build-arg",
+                "IRONPOST_VERSION=0.1.0",
+                "--build-arg",
+                "BPF_LINKER_VERSION=0.9.14",
+                "--load",
+                "/src",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_buildx_args_multi_arch_pushes() {
+        let args = buildx_args(
+            Path::new("/src"),
+            &[DockerArch::Amd64, DockerArch::Arm64],
+            "registry.example.com/ironpost-daemon:0.1.0",
+            "0.1.0",
+            true,
+            &[],
+        );
+        assert!(args.contains(&"linux/amd64,linux/arm64".to_owned()));
+        assert!(args.contains(&"--push".to_owned()));
+        assert!(!args.contains(&"--load".to_owned()));
+    }
+}
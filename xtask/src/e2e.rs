@@ -0,0 +1,603 @@
+//! `cargo xtask test-e2e` — 커널 경로 종단 간 테스트
+//!
+//! 네트워크 네임스페이스와 veth 쌍으로 격리된 토폴로지를 만들고, 실제
+//! `ironpost-daemon`이 호스트 쪽 veth에 XDP 프로그램을 붙이게 한 뒤 내장
+//! 패킷 생성기(`e2e-send`)로 트래픽을 보내 통계와 차단 목록 동작을 검증합니다.
+//!
+//! ```text
+//!  root netns                            ironpost-e2e netns
+//!  ipe2e-host 10.203.0.1/24  <--veth-->  ipe2e-peer 10.203.0.2/24, 10.203.0.3/24
+//!  (XDP + UDP 수신기)                    (패킷 생성기)
+//! ```
+//!
+//! 검증 결과는 데몬의 제어 API(`/ebpf`, `/ebpf/blocklist`)와 수신기가 실제로
+//! 받은 패킷 수로 판정합니다. `--vm`을 지정하면 같은 테스트를 vmtest로 띄운
+//! 경량 VM 안에서 실행하므로 호스트의 네트워크 설정을 건드리지 않습니다.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+/// 테스트용 네트워크 네임스페이스 이름
+const NETNS: &str = "ironpost-e2e";
+/// 루트 네임스페이스 쪽 veth (XDP 부착 대상)
+const HOST_IF: &str = "ipe2e-host";
+/// 테스트 네임스페이스 쪽 veth
+const PEER_IF: &str = "ipe2e-peer";
+/// 호스트 쪽 주소 (UDP 수신기)
+const HOST_IP: Ipv4Addr = Ipv4Addr::new(10, 203, 0, 1);
+/// 차단하지 않는 송신 주소
+const ALLOWED_IP: Ipv4Addr = Ipv4Addr::new(10, 203, 0, 2);
+/// 차단 목록에 추가하는 송신 주소
+const BLOCKED_IP: Ipv4Addr = Ipv4Addr::new(10, 203, 0, 3);
+/// 수신기 UDP 포트
+const UDP_PORT: u16 = 47_000;
+/// 테스트 데몬의 제어 API 포트 (기본 9101과 겹치지 않게)
+const CONTROL_PORT: u16 = 19_101;
+/// 시나리오당 전송 패킷 수
+const PACKETS: u64 = 200;
+/// 데몬이 XDP를 붙일 때까지 기다리는 최대 시간
+const ATTACH_TIMEOUT: Duration = Duration::from_secs(30);
+/// 통계 카운터가 반영될 때까지 기다리는 최대 시간
+const STATS_TIMEOUT: Duration = Duration::from_secs(10);
+/// 수신기가 마지막 패킷 이후 더 기다리는 시간
+const RECEIVE_IDLE: Duration = Duration::from_millis(500);
+
+/// `test-e2e` 옵션
+pub struct E2eOptions {
+    /// 릴리스 빌드로 테스트
+    pub release: bool,
+    /// vmtest VM 안에서 실행
+    pub vm: bool,
+    /// VM 커널 이미지 (`--vm`)
+    pub kernel: Option<PathBuf>,
+    /// 빌드 생략 (이미 빌드된 바이너리 사용)
+    pub no_build: bool,
+    /// 종료 후 네임스페이스와 임시 디렉토리 유지 (디버깅용)
+    pub keep: bool,
+}
+
+/// `test-e2e`를 실행합니다.
+pub fn run(opts: &E2eOptions) -> Result<(), String> {
+    if !cfg!(target_os = "linux") {
+        return Err(format!(
+            "test-e2e needs Linux (current platform: {})",
+            std::env::consts::OS
+        ));
+    }
+    if !opts.no_build {
+        build(opts.release)?;
+    }
+    if opts.vm {
+        return run_in_vm(opts);
+    }
+    if !is_root()? {
+        return Err(
+            "test-e2e needs root (CAP_NET_ADMIN and CAP_BPF); rerun with sudo or use --vm"
+                .to_owned(),
+        );
+    }
+
+    let profile = if opts.release { "release" } else { "debug" };
+    let root = crate::workspace_root();
+    let work_dir = std::env::temp_dir().join(format!("ironpost-e2e-{}", std::process::id()));
+    fs::create_dir_all(work_dir.join("data"))
+        .map_err(|e| format!("failed to create {}: {e}", work_dir.display()))?;
+
+    let topology = Topology::create(opts.keep)?;
+    println!("Created {NETNS} namespace with veth {HOST_IF} <-> {PEER_IF}");
+
+    let config_path = work_dir.join("ironpost.toml");
+    fs::write(&config_path, daemon_config(&work_dir))
+        .map_err(|e| format!("failed to write daemon config: {e}"))?;
+    let log_path = work_dir.join("daemon.log");
+    let daemon = Daemon::spawn(
+        &root.join("target").join(profile).join("ironpost-daemon"),
+        &config_path,
+        &root
+            .join("target/bpfel-unknown-none")
+            .join(profile)
+            .join("ironpost-ebpf"),
+        &log_path,
+    )?;
+
+    let result = run_scenarios();
+
+    drop(daemon);
+    drop(topology);
+    match &result {
+        Ok(()) => println!("\ntest-e2e passed"),
+        Err(_) => eprintln!("\nDaemon log: {}", log_path.display()),
+    }
+    if !opts.keep && result.is_ok() {
+        let _ = fs::remove_dir_all(&work_dir);
+    }
+    result
+}
+
+/// 숨은 `e2e-send` 서브커맨드: `source`에서 `target`으로 UDP 패킷을 보냅니다.
+///
+/// 테스트 네임스페이스 안에서 `ip netns exec`로 실행되는 내장 패킷 생성기입니다.
+pub fn send(source: IpAddr, target: SocketAddr, count: u64) -> Result<(), String> {
+    let socket = UdpSocket::bind((source, 0))
+        .map_err(|e| format!("failed to bind packet generator to {source}: {e}"))?;
+    for seq in 0..count {
+        let payload = format!("ironpost-e2e {seq}");
+        socket
+            .send_to(payload.as_bytes(), target)
+            .map_err(|e| format!("failed to send packet {seq} to {target}: {e}"))?;
+        // veth 수신 큐가 넘치지 않도록 조금씩 나눠 보냅니다
+        if seq % 50 == 49 {
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+    Ok(())
+}
+
+/// 시나리오를 순서대로 실행합니다. 첫 실패에서 멈춥니다.
+fn run_scenarios() -> Result<(), String> {
+    let receiver = UdpSocket::bind((HOST_IP, UDP_PORT))
+        .map_err(|e| format!("failed to bind receiver on {HOST_IP}:{UDP_PORT}: {e}"))?;
+    receiver
+        .set_read_timeout(Some(Duration::from_millis(100)))
+        .map_err(|e| format!("failed to configure receiver: {e}"))?;
+
+    step("XDP program attaches to the veth", || {
+        wait_until(ATTACH_TIMEOUT, || {
+            let status = ebpf_status()?;
+            Ok(status["attached"] == Value::Bool(true))
+        })
+        .map_err(|e| format!("daemon did not attach to {HOST_IF}: {e}"))
+    })?;
+
+    step("allowed traffic passes and is counted", || {
+        let before = counter(&ebpf_status()?, "udp", "packets");
+        send_from(ALLOWED_IP)?;
+        let received = receive(&receiver);
+        expect_received(&received, ALLOWED_IP, PACKETS)?;
+        wait_until(STATS_TIMEOUT, || {
+            Ok(counter(&ebpf_status()?, "udp", "packets") >= before + PACKETS)
+        })
+        .map_err(|e| format!("udp packet counter did not grow by {PACKETS}: {e}"))
+    })?;
+
+    step("blocklisted source is dropped in XDP", || {
+        let (status, body) = request(
+            "POST",
+            "/ebpf/blocklist",
+            Some(&format!(
+                r#"{{"entries":[{{"cidr":"{BLOCKED_IP}","description":"xtask test-e2e"}}]}}"#
+            )),
+        )?;
+        if status != 200 {
+            return Err(format!("blocklist add returned {status}: {body}"));
+        }
+
+        let before = counter(&ebpf_status()?, "udp", "drops");
+        send_from(BLOCKED_IP)?;
+        send_from(ALLOWED_IP)?;
+        let received = receive(&receiver);
+        expect_received(&received, BLOCKED_IP, 0)?;
+        expect_received(&received, ALLOWED_IP, PACKETS)?;
+        wait_until(STATS_TIMEOUT, || {
+            Ok(counter(&ebpf_status()?, "udp", "drops") >= before + PACKETS)
+        })
+        .map_err(|e| format!("udp drop counter did not grow by {PACKETS}: {e}"))
+    })?;
+
+    step("removed entry lets traffic through again", || {
+        let (status, body) = request(
+            "POST",
+            "/ebpf/blocklist/remove",
+            Some(&format!(r#"{{"entries":["{BLOCKED_IP}"]}}"#)),
+        )?;
+        if status != 200 {
+            return Err(format!("blocklist remove returned {status}: {body}"));
+        }
+        send_from(BLOCKED_IP)?;
+        let received = receive(&receiver);
+        expect_received(&received, BLOCKED_IP, PACKETS)
+    })
+}
+
+/// 시나리오 하나를 실행하고 결과를 출력합니다.
+fn step(name: &str, f: impl FnOnce() -> Result<(), String>) -> Result<(), String> {
+    print!("  {name} ... ");
+    let _ = std::io::stdout().flush();
+    match f() {
+        Ok(()) => {
+            println!("ok");
+            Ok(())
+        }
+        Err(e) => {
+            println!("FAILED");
+            Err(format!("{name}: {e}"))
+        }
+    }
+}
+
+/// 테스트 네임스페이스 안에서 패킷 생성기를 실행합니다.
+fn send_from(source: Ipv4Addr) -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| format!("failed to locate xtask: {e}"))?;
+    let target = SocketAddr::from((HOST_IP, UDP_PORT));
+    let status = Command::new("ip")
+        .args(["netns", "exec", NETNS])
+        .arg(exe)
+        .args(["e2e-send", "--source", &source.to_string()])
+        .args(["--target", &target.to_string()])
+        .args(["--count", &PACKETS.to_string()])
+        .status()
+        .map_err(|e| format!("failed to run packet generator: {e}"))?;
+    if !status.success() {
+        return Err(format!("packet generator for {source} failed"));
+    }
+    Ok(())
+}
+
+/// 수신이 멈출 때까지 받은 패킷 수를 송신 주소별로 셉니다.
+fn receive(socket: &UdpSocket) -> HashMap<IpAddr, u64> {
+    let mut counts = HashMap::new();
+    let mut buf = [0u8; 128];
+    let mut last = Instant::now();
+    while last.elapsed() < RECEIVE_IDLE {
+        if let Ok((_, from)) = socket.recv_from(&mut buf) {
+            *counts.entry(from.ip()).or_insert(0) += 1;
+            last = Instant::now();
+        }
+    }
+    counts
+}
+
+fn expect_received(
+    received: &HashMap<IpAddr, u64>,
+    source: Ipv4Addr,
+    expected: u64,
+) -> Result<(), String> {
+    let got = received
+        .get(&IpAddr::V4(source))
+        .copied()
+        .unwrap_or_default();
+    if got != expected {
+        return Err(format!(
+            "received {got} packets from {source}, expected {expected}"
+        ));
+    }
+    Ok(())
+}
+
+/// `timeout` 안에 `condition`이 참이 될 때까지 확인합니다.
+fn wait_until(
+    timeout: Duration,
+    mut condition: impl FnMut() -> Result<bool, String>,
+) -> Result<(), String> {
+    let deadline = Instant::now() + timeout;
+    let mut last_error = String::from("condition not met");
+    while Instant::now() < deadline {
+        match condition() {
+            Ok(true) => return Ok(()),
+            Ok(false) => last_error = String::from("condition not met"),
+            Err(e) => last_error = e,
+        }
+        thread::sleep(Duration::from_millis(250));
+    }
+    Err(format!(
+        "timed out after {}s ({last_error})",
+        timeout.as_secs()
+    ))
+}
+
+fn ebpf_status() -> Result<Value, String> {
+    let (status, body) = request("GET", "/ebpf", None)?;
+    if status != 200 {
+        return Err(format!("GET /ebpf returned {status}: {body}"));
+    }
+    serde_json::from_str(&body).map_err(|e| format!("invalid /ebpf response: {e}"))
+}
+
+/// `/ebpf` 응답의 `stats.<protocol>.<field>` 카운터
+fn counter(status: &Value, protocol: &str, field: &str) -> u64 {
+    status["stats"][protocol][field]
+        .as_u64()
+        .unwrap_or_default()
+}
+
+/// 테스트 데몬의 제어 API에 HTTP 요청을 보냅니다.
+fn request(method: &str, path: &str, body: Option<&str>) -> Result<(u16, String), String> {
+    let mut stream = TcpStream::connect(("127.0.0.1", CONTROL_PORT))
+        .map_err(|e| format!("control API not reachable: {e}"))?;
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .map_err(|e| format!("failed to configure control connection: {e}"))?;
+    let body = body.unwrap_or_default();
+    let head = format!(
+        "{method} {path} HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream
+        .write_all(head.as_bytes())
+        .and_then(|()| stream.write_all(body.as_bytes()))
+        .map_err(|e| format!("failed to send {method} {path}: {e}"))?;
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .map_err(|e| format!("failed to read {method} {path} response: {e}"))?;
+    parse_response(&response)
+}
+
+/// HTTP 응답에서 상태 코드와 본문을 분리합니다.
+fn parse_response(response: &str) -> Result<(u16, String), String> {
+    let status = response
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| format!("malformed HTTP response: {response:?}"))?;
+    let body = response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body.to_owned())
+        .unwrap_or_default();
+    Ok((status, body))
+}
+
+/// 테스트 데몬 설정: eBPF와 제어 API만 켜고 나머지 모듈은 끕니다.
+fn daemon_config(work_dir: &Path) -> String {
+    format!(
+        r#"[general]
+log_level = "debug"
+log_format = "pretty"
+data_dir = "{dir}/data"
+pid_file = "{dir}/ironpost.pid"
+
+[ebpf]
+enabled = true
+interface = "{HOST_IF}"
+xdp_mode = "skb"
+
+[log_pipeline]
+enabled = false
+
+[container]
+enabled = false
+
+[sbom]
+enabled = false
+
+[metrics]
+enabled = false
+
+[health]
+enabled = true
+listen_addr = "127.0.0.1"
+port = {CONTROL_PORT}
+"#,
+        dir = work_dir.display()
+    )
+}
+
+/// 테스트 네임스페이스와 veth 쌍. drop 시 삭제됩니다.
+struct Topology {
+    keep: bool,
+}
+
+impl Topology {
+    fn create(keep: bool) -> Result<Self, String> {
+        // 이전 실행이 남긴 토폴로지 정리
+        let _ = ip(&["netns", "del", NETNS]);
+        let _ = ip(&["link", "del", HOST_IF]);
+
+        let topology = Self { keep };
+        ip(&["netns", "add", NETNS])?;
+        ip(&[
+            "link", "add", HOST_IF, "type", "veth", "peer", "name", PEER_IF,
+        ])?;
+        ip(&["link", "set", PEER_IF, "netns", NETNS])?;
+        ip(&["addr", "add", &format!("{HOST_IP}/24"), "dev", HOST_IF])?;
+        ip(&["link", "set", HOST_IF, "up"])?;
+        for addr in [ALLOWED_IP, BLOCKED_IP] {
+            ip(&[
+                "netns",
+                "exec",
+                NETNS,
+                "ip",
+                "addr",
+                "add",
+                &format!("{addr}/24"),
+                "dev",
+                PEER_IF,
+            ])?;
+        }
+        ip(&["netns", "exec", NETNS, "ip", "link", "set", PEER_IF, "up"])?;
+        ip(&["netns", "exec", NETNS, "ip", "link", "set", "lo", "up"])?;
+        Ok(topology)
+    }
+}
+
+impl Drop for Topology {
+    fn drop(&mut self) {
+        if self.keep {
+            println!("Keeping {NETNS} namespace (remove with: ip netns del {NETNS})");
+            return;
+        }
+        // 네임스페이스를 지우면 veth 쌍도 함께 사라집니다
+        let _ = ip(&["netns", "del", NETNS]);
+    }
+}
+
+fn ip(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("ip")
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run ip (is iproute2 installed?): {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "ip {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// 테스트용 데몬 프로세스. drop 시 종료됩니다.
+struct Daemon {
+    child: Child,
+}
+
+impl Daemon {
+    fn spawn(binary: &Path, config: &Path, ebpf: &Path, log: &Path) -> Result<Self, String> {
+        if !binary.exists() {
+            return Err(format!("{} not found; build it first", binary.display()));
+        }
+        if !ebpf.exists() {
+            return Err(format!(
+                "{} not found; run `cargo xtask build-ebpf`",
+                ebpf.display()
+            ));
+        }
+        let log_file =
+            fs::File::create(log).map_err(|e| format!("failed to create daemon log: {e}"))?;
+        let stderr = log_file
+            .try_clone()
+            .map_err(|e| format!("failed to create daemon log: {e}"))?;
+        let child = Command::new(binary)
+            .arg("--config")
+            .arg(config)
+            .env("IRONPOST_EBPF_PATH", ebpf)
+            .stdin(Stdio::null())
+            .stdout(log_file)
+            .stderr(stderr)
+            .spawn()
+            .map_err(|e| format!("failed to start {}: {e}", binary.display()))?;
+        Ok(Self { child })
+    }
+}
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        // SIGTERM으로 정상 종료시켜 XDP 프로그램이 분리되게 합니다
+        let pid = self.child.id().to_string();
+        let _ = Command::new("kill").args(["-TERM", &pid]).status();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline {
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// 테스트에 필요한 eBPF 프로그램과 데몬을 빌드합니다.
+fn build(release: bool) -> Result<(), String> {
+    println!("Building eBPF kernel program...");
+    crate::build_ebpf(release);
+
+    let mut cmd = Command::new("cargo");
+    cmd.current_dir(crate::workspace_root())
+        .args(["build", "-p", "ironpost-daemon"]);
+    if release {
+        cmd.arg("--release");
+    }
+    let status = cmd
+        .status()
+        .map_err(|e| format!("failed to build ironpost-daemon: {e}"))?;
+    if !status.success() {
+        return Err("ironpost-daemon build failed".to_owned());
+    }
+    Ok(())
+}
+
+/// vmtest로 띄운 VM 안에서 이 테스트를 다시 실행합니다.
+///
+/// vmtest는 호스트 루트 파일시스템을 VM에 공유하므로 호스트에서 빌드한
+/// 바이너리를 그대로 사용합니다.
+fn run_in_vm(opts: &E2eOptions) -> Result<(), String> {
+    let kernel = opts
+        .kernel
+        .clone()
+        .or_else(|| std::env::var_os("IRONPOST_E2E_KERNEL").map(PathBuf::from))
+        .ok_or("--vm needs --kernel <bzImage> or IRONPOST_E2E_KERNEL")?;
+    let exe = std::env::current_exe().map_err(|e| format!("failed to locate xtask: {e}"))?;
+    let mut inner = format!("{} test-e2e --no-build", exe.display());
+    if opts.release {
+        inner.push_str(" --release");
+    }
+    if opts.keep {
+        inner.push_str(" --keep");
+    }
+
+    println!("Running test-e2e in a VM with kernel {}", kernel.display());
+    let status = Command::new("vmtest")
+        .current_dir(crate::workspace_root())
+        .arg("--kernel")
+        .arg(&kernel)
+        .arg(&inner)
+        .status()
+        .map_err(|e| format!("failed to run vmtest (install with `cargo install vmtest`): {e}"))?;
+    if !status.success() {
+        return Err("test-e2e failed inside the VM".to_owned());
+    }
+    Ok(())
+}
+
+pub(crate) fn is_root() -> Result<bool, String> {
+    let output = Command::new("id")
+        .arg("-u")
+        .output()
+        .map_err(|e| format!("failed to run id: {e}"))?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim() == "0")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let (status, body) =
+            parse_response("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"a\":1}")
+                .unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(body, "{\"a\":1}");
+
+        assert!(parse_response("garbage").is_err());
+    }
+
+    #[test]
+    fn test_daemon_config_is_valid_toml_section_layout() {
+        let config = daemon_config(Path::new("/tmp/ironpost-e2e-1"));
+        assert!(config.contains(&format!("interface = \"{HOST_IF}\"")));
+        assert!(config.contains(&format!("port = {CONTROL_PORT}")));
+        assert!(config.contains("data_dir = \"/tmp/ironpost-e2e-1/data\""));
+    }
+
+    #[test]
+    fn test_counter_reads_ebpf_stats() {
+        let status: Value =
+            serde_json::from_str(r#"{"stats":{"udp":{"packets":12,"drops":3}}}"#).unwrap();
+        assert_eq!(counter(&status, "udp", "packets"), 12);
+        assert_eq!(counter(&status, "udp", "drops"), 3);
+        assert_eq!(counter(&status, "tcp", "packets"), 0);
+    }
+
+    #[test]
+    fn test_send_and_receive_on_loopback() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .unwrap();
+        let target = receiver.local_addr().unwrap();
+
+        send("127.0.0.1".parse().unwrap(), target, 20).unwrap();
+        let received = receive(&receiver);
+        assert_eq!(received.get(&target.ip()).copied(), Some(20));
+    }
+}
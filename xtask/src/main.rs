@@ -1,4 +1,13 @@
+mod coverage;
+mod docker;
+mod e2e;
+mod package;
+mod release;
+mod verify_ebpf;
+
 use clap::{Parser, Subcommand};
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Ironpost 빌드 태스크
@@ -20,6 +29,10 @@ enum Commands {
         /// eBPF 커널 프로그램 포함 (Linux 전용)
         #[arg(long)]
         all: bool,
+
+        /// eBPF 오브젝트를 먼저 빌드해 데몬 바이너리에 포함 (Linux 전용, IRONPOST_EBPF_PATH 불필요)
+        #[arg(long)]
+        embed_ebpf: bool,
     },
 
     /// eBPF 커널 프로그램만 빌드 (Linux 전용)
@@ -28,20 +41,160 @@ enum Commands {
         #[arg(long)]
         release: bool,
     },
+
+    /// eBPF 커널 프로그램을 빌드하고 커널 검증기에 로드해 명령어 수와 검증기 에러 보고 (Linux 전용, root 필요)
+    VerifyEbpf {
+        /// 빌드 생략 (이미 빌드된 오브젝트와 검증 헬퍼 사용)
+        #[arg(long)]
+        no_build: bool,
+
+        /// 검증할 eBPF 오브젝트 (기본값: target/bpfel-unknown-none/release/ironpost-ebpf)
+        #[arg(long)]
+        object: Option<PathBuf>,
+
+        /// 프로그램별 검증기 처리 명령어 수 상한 (넘으면 실패)
+        #[arg(long)]
+        max_insns: Option<u32>,
+
+        /// 정적 명령어 수만 보고 (커널 로드 생략, root 불필요)
+        #[arg(long)]
+        static_only: bool,
+    },
+
+    /// 네트워크 네임스페이스에서 XDP 경로 종단 간 테스트 (Linux 전용, root 필요)
+    TestE2e {
+        /// 릴리스 빌드로 테스트
+        #[arg(long)]
+        release: bool,
+
+        /// vmtest 경량 VM 안에서 실행 (호스트 네트워크를 건드리지 않음)
+        #[arg(long)]
+        vm: bool,
+
+        /// VM 커널 이미지 (기본값: IRONPOST_E2E_KERNEL 환경변수)
+        #[arg(long, requires = "vm")]
+        kernel: Option<PathBuf>,
+
+        /// 빌드 생략 (이미 빌드된 데몬과 eBPF 프로그램 사용)
+        #[arg(long)]
+        no_build: bool,
+
+        /// 종료 후 네임스페이스와 임시 디렉토리 유지 (디버깅용)
+        #[arg(long)]
+        keep: bool,
+    },
+
+    /// 릴리스 바이너리, eBPF 오브젝트, 기본 설정과 systemd 유닛으로 deb/rpm 패키지 생성 (Linux 전용)
+    Package {
+        /// 패키지 형식
+        #[arg(long, value_enum)]
+        format: package::PackageFormat,
+
+        /// 패키지를 저장할 디렉토리
+        #[arg(long, default_value = "target/package")]
+        out_dir: PathBuf,
+
+        /// 빌드 생략 (이미 빌드된 릴리스 바이너리 사용)
+        #[arg(long)]
+        no_build: bool,
+    },
+
+    /// 데몬, eBPF 오브젝트, 기본 설정을 담은 컨테이너 이미지 빌드 (docker buildx 필요)
+    Docker {
+        /// 대상 아키텍처 (여러 번 지정 가능, 기본값: 빌드 머신 아키텍처)
+        #[arg(long, value_enum)]
+        arch: Vec<docker::DockerArch>,
+
+        /// 이미지 태그 (기본값: ironpost-daemon:<버전>)
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// 빌드 후 레지스트리에 push (여러 아키텍처일 때 필수)
+        #[arg(long)]
+        push: bool,
+
+        /// Dockerfile에 전달할 추가 빌드 인자 (KEY=VALUE, 여러 번 지정 가능)
+        #[arg(long = "build-arg", value_name = "KEY=VALUE")]
+        build_args: Vec<String>,
+    },
+
+    /// 워크스페이스 코드 커버리지 측정 (cargo-llvm-cov, no_std 크레이트 제외)
+    Coverage {
+        /// HTML 리포트 생성 (target/llvm-cov/html)
+        #[arg(long, conflicts_with = "lcov")]
+        html: bool,
+
+        /// LCOV 파일 생성 (target/llvm-cov/lcov.info)
+        #[arg(long)]
+        lcov: bool,
+
+        /// 라인 커버리지가 이 비율(%) 미만이면 실패
+        #[arg(long, value_name = "PERCENT")]
+        fail_under: Option<f64>,
+    },
+
+    /// 아키텍처별 데몬/CLI 교차 컴파일과 eBPF 오브젝트를 dist 디렉토리에 아카이브로 수집
+    Release {
+        /// 대상 아키텍처 (쉼표로 구분)
+        #[arg(
+            long,
+            value_enum,
+            value_delimiter = ',',
+            default_value = "x86_64,aarch64"
+        )]
+        targets: Vec<release::ReleaseTarget>,
+
+        /// 결과물을 모을 디렉토리
+        #[arg(long, default_value = "dist")]
+        out_dir: PathBuf,
+
+        /// 교차 컴파일 도구
+        #[arg(long, value_enum, default_value = "cargo")]
+        builder: release::ReleaseBuilder,
+
+        /// eBPF 오브젝트 빌드 생략 (이미 빌드된 오브젝트 사용)
+        #[arg(long)]
+        no_ebpf_build: bool,
+    },
+
+    /// test-e2e 내장 패킷 생성기 (테스트 네임스페이스 안에서 실행됨)
+    #[command(hide = true)]
+    E2eSend {
+        /// 송신 주소
+        #[arg(long)]
+        source: IpAddr,
+
+        /// 수신 주소와 포트
+        #[arg(long)]
+        target: SocketAddr,
+
+        /// 전송할 UDP 패킷 수
+        #[arg(long)]
+        count: u64,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Build { release, all } => {
-            build_workspace(release);
-            if all {
-                if !cfg!(target_os = "linux") {
-                    eprintln!("ERROR: eBPF builds are only supported on Linux");
-                    eprintln!("Current platform: {}", std::env::consts::OS);
-                    std::process::exit(1);
-                }
+        Commands::Build {
+            release,
+            all,
+            embed_ebpf,
+        } => {
+            if (all || embed_ebpf) && !cfg!(target_os = "linux") {
+                eprintln!("ERROR: eBPF builds are only supported on Linux");
+                eprintln!("Current platform: {}", std::env::consts::OS);
+                std::process::exit(1);
+            }
+            if embed_ebpf {
+                // 임베드 경로는 릴리스 오브젝트로 고정되어 있으므로 항상 릴리스로 빌드
+                println!("Building eBPF kernel program for embedding...");
+                build_ebpf(true);
+            }
+            build_workspace(release, embed_ebpf);
+            if all && !embed_ebpf {
                 println!("\nBuilding eBPF kernel program...");
                 build_ebpf(release);
             }
@@ -54,10 +207,120 @@ fn main() {
             }
             build_ebpf(release);
         }
+        Commands::VerifyEbpf {
+            no_build,
+            object,
+            max_insns,
+            static_only,
+        } => {
+            let opts = verify_ebpf::VerifyOptions {
+                no_build,
+                object,
+                max_insns,
+                static_only,
+            };
+            if let Err(e) = verify_ebpf::run(&opts) {
+                eprintln!("ERROR: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::TestE2e {
+            release,
+            vm,
+            kernel,
+            no_build,
+            keep,
+        } => {
+            let opts = e2e::E2eOptions {
+                release,
+                vm,
+                kernel,
+                no_build,
+                keep,
+            };
+            if let Err(e) = e2e::run(&opts) {
+                eprintln!("ERROR: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Package {
+            format,
+            out_dir,
+            no_build,
+        } => {
+            let opts = package::PackageOptions {
+                format,
+                out_dir,
+                no_build,
+            };
+            if let Err(e) = package::run(&opts) {
+                eprintln!("ERROR: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Docker {
+            arch,
+            tag,
+            push,
+            build_args,
+        } => {
+            let opts = docker::DockerOptions {
+                arch,
+                tag,
+                push,
+                build_args,
+            };
+            if let Err(e) = docker::run(&opts) {
+                eprintln!("ERROR: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Coverage {
+            html,
+            lcov,
+            fail_under,
+        } => {
+            let opts = coverage::CoverageOptions {
+                html,
+                lcov,
+                fail_under,
+            };
+            if let Err(e) = coverage::run(&opts) {
+                eprintln!("ERROR: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Release {
+            targets,
+            out_dir,
+            builder,
+            no_ebpf_build,
+        } => {
+            let opts = release::ReleaseOptions {
+                targets,
+                out_dir,
+                builder,
+                no_ebpf_build,
+            };
+            if let Err(e) = release::run(&opts) {
+                eprintln!("ERROR: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::E2eSend {
+            source,
+            target,
+            count,
+        } => {
+            if let Err(e) = e2e::send(source, target, count) {
+                eprintln!("ERROR: {e}");
+                std::process::exit(1);
+            }
+        }
     }
 }
 
-fn build_workspace(release: bool) {
+fn build_workspace(release: bool, embed_ebpf: bool) {
     let mut cmd = Command::new("cargo");
 
     cmd.args(["build", "--workspace"]);
@@ -66,6 +329,10 @@ fn build_workspace(release: bool) {
         cmd.arg("--release");
     }
 
+    if embed_ebpf {
+        cmd.args(["--features", "ironpost-daemon/embed-ebpf"]);
+    }
+
     println!("Building workspace...");
     let status = cmd.status().expect("failed to build workspace");
     if !status.success() {
@@ -100,3 +367,11 @@ fn build_ebpf(release: bool) {
 
     println!("eBPF build succeeded");
 }
+
+/// 워크스페이스 루트 (`xtask/`의 상위 디렉토리)
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
@@ -0,0 +1,457 @@
+//! `cargo xtask package` — deb/rpm 패키지 생성
+//!
+//! 릴리스 바이너리(데몬, CLI)와 eBPF 오브젝트를 빌드하고, 기본 설정, 예제
+//! 탐지 룰/격리 정책, systemd 유닛과 함께 설치 가능한 패키지로 묶습니다.
+//!
+//! ```text
+//! /usr/bin/ironpost-daemon             (setcap: XDP/BPF/특권 포트)
+//! /usr/bin/ironpost-cli, /usr/bin/ironpost -> ironpost-cli
+//! /usr/lib/ironpost/ironpost-ebpf      (IRONPOST_EBPF_PATH)
+//! /etc/ironpost/ironpost.toml          (conffile)
+//! /etc/ironpost/rules/*.yaml, /etc/ironpost/policies/*.toml
+//! /lib/systemd/system/ironpost.service (rpm: /usr/lib/systemd/system)
+//! ```
+//!
+//! 유닛과 설치 스크립트는 `packaging/`에 있으며 deb와 rpm이 같은 스크립트를
+//! 사용합니다. deb은 `dpkg-deb`, rpm은 `rpmbuild`가 필요합니다.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use clap::ValueEnum;
+
+/// 패키지 이름
+const PACKAGE_NAME: &str = "ironpost";
+/// 패키지 관리자
+const MAINTAINER: &str = "Ironpost Maintainers <ironpost@users.noreply.github.com>";
+/// 패키지 설명 (한 줄)
+const SUMMARY: &str = "Unified eBPF, log, container and SBOM security monitoring";
+/// 데몬 바이너리에 부여하는 파일 capability
+pub const DAEMON_CAPS: &str =
+    "cap_net_admin,cap_net_bind_service,cap_bpf,cap_perfmon,cap_sys_resource";
+
+/// 패키지 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PackageFormat {
+    /// Debian/Ubuntu (.deb)
+    Deb,
+    /// RHEL/Fedora (.rpm)
+    Rpm,
+}
+
+impl PackageFormat {
+    /// systemd 유닛 설치 디렉토리
+    fn unit_dir(self) -> &'static str {
+        match self {
+            Self::Deb => "lib/systemd/system",
+            Self::Rpm => "usr/lib/systemd/system",
+        }
+    }
+}
+
+/// `package` 옵션
+pub struct PackageOptions {
+    /// 패키지 형식
+    pub format: PackageFormat,
+    /// 결과 패키지를 저장할 디렉토리
+    pub out_dir: PathBuf,
+    /// 빌드 생략 (이미 빌드된 릴리스 바이너리 사용)
+    pub no_build: bool,
+}
+
+/// 패키지를 만들고 결과 파일 경로를 반환합니다.
+pub fn run(opts: &PackageOptions) -> Result<PathBuf, String> {
+    if !cfg!(target_os = "linux") {
+        return Err(format!(
+            "packages can only be built on Linux (current platform: {})",
+            std::env::consts::OS
+        ));
+    }
+    let root = crate::workspace_root();
+    if !opts.no_build {
+        build_release(&root)?;
+    }
+
+    let version = package_version(&root)?;
+    let format = match opts.format {
+        PackageFormat::Deb => "deb",
+        PackageFormat::Rpm => "rpm",
+    };
+    let work_dir = root.join("target/package").join(format);
+    if work_dir.exists() {
+        fs::remove_dir_all(&work_dir)
+            .map_err(|e| format!("failed to clean {}: {e}", work_dir.display()))?;
+    }
+    let stage = work_dir.join("root");
+    let conffiles = stage_files(&root, &stage, opts.format)?;
+
+    let out_dir = if opts.out_dir.is_absolute() {
+        opts.out_dir.clone()
+    } else {
+        root.join(&opts.out_dir)
+    };
+    create_dir(&out_dir)?;
+
+    let package = match opts.format {
+        PackageFormat::Deb => build_deb(&root, &stage, &conffiles, &version, &out_dir)?,
+        PackageFormat::Rpm => build_rpm(&root, &work_dir, &stage, &conffiles, &version, &out_dir)?,
+    };
+    println!("Package written to {}", package.display());
+    Ok(package)
+}
+
+/// 데몬, CLI 릴리스 바이너리와 eBPF 오브젝트를 빌드합니다.
+fn build_release(root: &Path) -> Result<(), String> {
+    println!("Building release binaries...");
+    let status = Command::new("cargo")
+        .current_dir(root)
+        .args([
+            "build",
+            "--release",
+            "-p",
+            "ironpost-daemon",
+            "-p",
+            "ironpost-cli",
+        ])
+        .status()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+    if !status.success() {
+        return Err("release build failed".to_owned());
+    }
+
+    println!("\nBuilding eBPF kernel program...");
+    crate::build_ebpf(true);
+    Ok(())
+}
+
+/// 설치될 파일을 `stage` 아래에 배치하고, 설정 파일(conffile) 목록을 반환합니다.
+fn stage_files(root: &Path, stage: &Path, format: PackageFormat) -> Result<Vec<String>, String> {
+    let release = root.join("target/release");
+    install(
+        &release.join("ironpost-daemon"),
+        stage,
+        "usr/bin/ironpost-daemon",
+        0o755,
+    )?;
+    install(
+        &release.join("ironpost-cli"),
+        stage,
+        "usr/bin/ironpost-cli",
+        0o755,
+    )?;
+    symlink("ironpost-cli", &stage.join("usr/bin/ironpost"))?;
+    install(
+        &root.join("target/bpfel-unknown-none/release/ironpost-ebpf"),
+        stage,
+        "usr/lib/ironpost/ironpost-ebpf",
+        0o644,
+    )?;
+    install(
+        &root.join("packaging/ironpost.service"),
+        stage,
+        &format!("{}/ironpost.service", format.unit_dir()),
+        0o644,
+    )?;
+
+    let mut conffiles = vec!["/etc/ironpost/ironpost.toml".to_owned()];
+    install(
+        &root.join("ironpost.toml.example"),
+        stage,
+        "etc/ironpost/ironpost.toml",
+        0o644,
+    )?;
+    for (dir, extension) in [("rules", "yaml"), ("policies", "toml")] {
+        for source in sorted_files(&root.join("examples").join(dir), extension)? {
+            let name = source
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let dest = format!("etc/ironpost/{dir}/{name}");
+            install(&source, stage, &dest, 0o644)?;
+            conffiles.push(format!("/{dest}"));
+        }
+    }
+    Ok(conffiles)
+}
+
+/// `dir`에서 확장자가 `extension`인 파일을 이름 순으로 반환합니다.
+fn sorted_files(dir: &Path, extension: &str) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("failed to read {}: {e}", dir.display()))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// `source`를 `stage/dest`로 복사하고 권한을 설정합니다.
+fn install(source: &Path, stage: &Path, dest: &str, mode: u32) -> Result<(), String> {
+    if !source.exists() {
+        return Err(format!(
+            "{} not found (build it first or drop --no-build)",
+            source.display()
+        ));
+    }
+    let target = stage.join(dest);
+    if let Some(parent) = target.parent() {
+        create_dir(parent)?;
+    }
+    fs::copy(source, &target).map_err(|e| format!("failed to copy {}: {e}", source.display()))?;
+    set_mode(&target, mode)
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+        .map_err(|e| format!("failed to set permissions on {}: {e}", path.display()))
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(target: &str, link: &Path) -> Result<(), String> {
+    std::os::unix::fs::symlink(target, link)
+        .map_err(|e| format!("failed to link {}: {e}", link.display()))
+}
+
+#[cfg(not(unix))]
+fn symlink(_target: &str, link: &Path) -> Result<(), String> {
+    Err(format!("cannot create {} on this platform", link.display()))
+}
+
+fn create_dir(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {e}", dir.display()))
+}
+
+/// `dpkg-deb`로 .deb 패키지를 만듭니다.
+fn build_deb(
+    root: &Path,
+    stage: &Path,
+    conffiles: &[String],
+    version: &str,
+    out_dir: &Path,
+) -> Result<PathBuf, String> {
+    let arch = deb_arch(std::env::consts::ARCH);
+    let debian = stage.join("DEBIAN");
+    create_dir(&debian)?;
+    write(&debian.join("control"), &deb_control(version, arch))?;
+    write(
+        &debian.join("conffiles"),
+        &format!("{}\n", conffiles.join("\n")),
+    )?;
+    for script in ["postinst", "prerm", "postrm"] {
+        install(&root.join("packaging").join(script), &debian, script, 0o755)?;
+    }
+
+    let package = out_dir.join(format!("{PACKAGE_NAME}_{version}_{arch}.deb"));
+    println!("Building {}...", package.display());
+    let status = Command::new("dpkg-deb")
+        .args(["--root-owner-group", "--build"])
+        .arg(stage)
+        .arg(&package)
+        .status()
+        .map_err(|e| format!("failed to run dpkg-deb (is dpkg installed?): {e}"))?;
+    if !status.success() {
+        return Err("dpkg-deb failed".to_owned());
+    }
+    Ok(package)
+}
+
+/// `rpmbuild`로 .rpm 패키지를 만듭니다.
+fn build_rpm(
+    root: &Path,
+    work_dir: &Path,
+    stage: &Path,
+    conffiles: &[String],
+    version: &str,
+    out_dir: &Path,
+) -> Result<PathBuf, String> {
+    let mut scripts = Vec::new();
+    for script in ["postinst", "prerm", "postrm"] {
+        let path = root.join("packaging").join(script);
+        scripts.push(
+            fs::read_to_string(&path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?,
+        );
+    }
+    let spec_path = work_dir.join("ironpost.spec");
+    write(
+        &spec_path,
+        &rpm_spec(version, conffiles, [&scripts[0], &scripts[1], &scripts[2]]),
+    )?;
+
+    println!("Building rpm in {}...", work_dir.display());
+    let status = Command::new("rpmbuild")
+        .arg("-bb")
+        .arg("--define")
+        .arg(format!("_topdir {}", work_dir.join("rpmbuild").display()))
+        .arg("--define")
+        .arg(format!("_stagedir {}", stage.display()))
+        .arg(&spec_path)
+        .status()
+        .map_err(|e| format!("failed to run rpmbuild (is rpm-build installed?): {e}"))?;
+    if !status.success() {
+        return Err("rpmbuild failed".to_owned());
+    }
+
+    let arch = std::env::consts::ARCH;
+    let name = format!("{PACKAGE_NAME}-{version}-1.{arch}.rpm");
+    let built = work_dir.join("rpmbuild/RPMS").join(arch).join(&name);
+    let package = out_dir.join(name);
+    fs::copy(&built, &package).map_err(|e| format!("failed to copy {}: {e}", built.display()))?;
+    Ok(package)
+}
+
+/// Debian `control` 파일
+fn deb_control(version: &str, arch: &str) -> String {
+    format!(
+        "Package: {PACKAGE_NAME}\n\
+         Version: {version}\n\
+         Architecture: {arch}\n\
+         Maintainer: {MAINTAINER}\n\
+         Section: net\n\
+         Priority: optional\n\
+         Depends: libc6, libcap2-bin\n\
+         Homepage: https://github.com/dongwonkwak/ironpost\n\
+         Description: {SUMMARY}\n \
+         Ironpost combines eBPF/XDP network detection, a log analysis pipeline,\n \
+         container isolation and SBOM vulnerability scanning in one daemon.\n"
+    )
+}
+
+/// RPM spec 파일. `%install`은 미리 배치한 `_stagedir`를 그대로 복사합니다.
+///
+/// 설정 파일의 그룹과 권한은 `ironpost` 사용자를 만든 뒤 `%post`에서 설정합니다.
+fn rpm_spec(version: &str, conffiles: &[String], [postinst, prerm, postrm]: [&str; 3]) -> String {
+    let config = conffiles
+        .iter()
+        .map(|path| format!("%config(noreplace) {path}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "Name: {PACKAGE_NAME}\n\
+         Version: {version}\n\
+         Release: 1\n\
+         Summary: {SUMMARY}\n\
+         License: MIT\n\
+         URL: https://github.com/dongwonkwak/ironpost\n\
+         Requires: libcap, shadow-utils\n\
+         AutoReqProv: no\n\
+         \n\
+         %description\n\
+         Ironpost combines eBPF/XDP network detection, a log analysis pipeline,\n\
+         container isolation and SBOM vulnerability scanning in one daemon.\n\
+         \n\
+         %install\n\
+         cp -a %{{_stagedir}}/. %{{buildroot}}/\n\
+         \n\
+         %files\n\
+         %caps({DAEMON_CAPS}=ep) /usr/bin/ironpost-daemon\n\
+         /usr/bin/ironpost-cli\n\
+         /usr/bin/ironpost\n\
+         /usr/lib/ironpost/ironpost-ebpf\n\
+         /usr/lib/systemd/system/ironpost.service\n\
+         %dir /etc/ironpost\n\
+         %dir /etc/ironpost/rules\n\
+         %dir /etc/ironpost/policies\n\
+         {config}\n\
+         \n\
+         %post\n{postinst}\n\
+         %preun\n{prerm}\n\
+         %postun\n{postrm}\n"
+    )
+}
+
+/// Rust 아키텍처 이름을 Debian 아키텍처 이름으로 변환합니다.
+fn deb_arch(arch: &str) -> &str {
+    match arch {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "arm" => "armhf",
+        other => other,
+    }
+}
+
+/// 데몬 크레이트의 버전을 패키지 버전으로 사용합니다.
+pub(crate) fn package_version(root: &Path) -> Result<String, String> {
+    let manifest = root.join("ironpost-daemon/Cargo.toml");
+    let contents = fs::read_to_string(&manifest)
+        .map_err(|e| format!("failed to read {}: {e}", manifest.display()))?;
+    manifest_version(&contents).ok_or_else(|| format!("no version in {}", manifest.display()))
+}
+
+/// `[package]` 섹션의 `version = "..."` 값을 찾습니다.
+fn manifest_version(manifest: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        if in_package
+            && let Some(value) = line.strip_prefix("version")
+            && let Some(value) = value.trim_start().strip_prefix('=')
+        {
+            return Some(value.trim().trim_matches('"').to_owned());
+        }
+    }
+    None
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_version_reads_package_section() {
+        let manifest = "[package]\nname = \"ironpost-daemon\"\nversion = \"0.3.1\"\n\n[dependencies]\nversion = \"9\"\n";
+        assert_eq!(manifest_version(manifest).as_deref(), Some("0.3.1"));
+        assert!(manifest_version("[dependencies]\nversion = \"1\"\n").is_none());
+    }
+
+    #[test]
+    fn test_deb_control_fields() {
+        let control = deb_control("0.1.0", deb_arch("x86_64"));
+        assert!(control.contains("Package: ironpost\n"));
+        assert!(control.contains("Architecture: amd64\n"));
+        assert!(control.contains("Depends: libc6, libcap2-bin\n"));
+        // Continuation lines of the long description start with a space
+        assert!(control.contains("\n Ironpost combines"));
+        assert!(control.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_rpm_spec_sets_caps_and_config() {
+        let spec = rpm_spec(
+            "0.1.0",
+            &[
+                "/etc/ironpost/ironpost.toml".to_owned(),
+                "/etc/ironpost/rules/ssh.yaml".to_owned(),
+            ],
+            ["post-script", "preun-script", "postun-script"],
+        );
+        assert!(spec.contains(&format!("%caps({DAEMON_CAPS}=ep) /usr/bin/ironpost-daemon")));
+        assert!(spec.contains("%config(noreplace) /etc/ironpost/ironpost.toml"));
+        assert!(spec.contains("%config(noreplace) /etc/ironpost/rules/ssh.yaml"));
+        assert!(spec.contains("%post\npost-script"));
+        assert!(spec.contains("%install\ncp -a %{_stagedir}/. %{buildroot}/"));
+    }
+
+    #[test]
+    fn test_postinst_caps_match_rpm_caps() {
+        let postinst = include_str!("../../packaging/postinst");
+        assert!(postinst.contains(&format!("setcap {DAEMON_CAPS}+ep")));
+    }
+}
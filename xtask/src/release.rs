@@ -0,0 +1,311 @@
+//! `cargo xtask release` — 교차 컴파일 릴리스 매트릭스
+//!
+//! `--targets`의 아키텍처마다 데몬과 CLI를 교차 컴파일하고, eBPF 오브젝트(BPF
+//! 바이트코드는 아키텍처 무관이라 한 번만 빌드)와 기본 설정을 모아 `dist/`에
+//! 아카이브와 체크섬을 만듭니다.
+//!
+//! ```text
+//! dist/
+//! ├── ironpost-<버전>-x86_64-unknown-linux-gnu/
+//! │   ├── ironpost-daemon, ironpost-cli, ironpost-ebpf
+//! │   └── ironpost.toml.example, LICENSE, README.md
+//! ├── ironpost-<버전>-x86_64-unknown-linux-gnu.tar.gz
+//! ├── ironpost-<버전>-aarch64-unknown-linux-gnu.tar.gz
+//! └── SHA256SUMS
+//! ```
+//!
+//! 빌드 머신과 다른 아키텍처는 `cargo`(크로스 gcc 링커 필요, 예:
+//! `gcc-aarch64-linux-gnu`) 또는 `cross`(Docker 기반)로 빌드합니다.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use clap::ValueEnum;
+
+/// 아카이브에 함께 넣는 워크스페이스 파일
+const EXTRA_FILES: &[&str] = &["ironpost.toml.example", "LICENSE", "README.md"];
+
+/// 릴리스 대상 아키텍처
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReleaseTarget {
+    /// x86_64-unknown-linux-gnu
+    #[value(name = "x86_64")]
+    X86_64,
+    /// aarch64-unknown-linux-gnu
+    Aarch64,
+}
+
+impl ReleaseTarget {
+    /// Rust 타겟 트리플
+    fn triple(self) -> &'static str {
+        match self {
+            Self::X86_64 => "x86_64-unknown-linux-gnu",
+            Self::Aarch64 => "aarch64-unknown-linux-gnu",
+        }
+    }
+
+    /// 교차 링커로 사용하는 gcc
+    fn cross_linker(self) -> &'static str {
+        match self {
+            Self::X86_64 => "x86_64-linux-gnu-gcc",
+            Self::Aarch64 => "aarch64-linux-gnu-gcc",
+        }
+    }
+
+    /// 빌드 머신과 같은 아키텍처인지 여부
+    fn is_host(self) -> bool {
+        cfg!(target_os = "linux")
+            && match self {
+                Self::X86_64 => cfg!(target_arch = "x86_64"),
+                Self::Aarch64 => cfg!(target_arch = "aarch64"),
+            }
+    }
+}
+
+/// 교차 컴파일 도구
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReleaseBuilder {
+    /// cargo + 크로스 gcc 링커
+    Cargo,
+    /// cross (Docker 기반 교차 컴파일)
+    Cross,
+}
+
+/// `release` 옵션
+pub struct ReleaseOptions {
+    /// 대상 아키텍처
+    pub targets: Vec<ReleaseTarget>,
+    /// 결과물을 모을 디렉토리
+    pub out_dir: PathBuf,
+    /// 교차 컴파일 도구
+    pub builder: ReleaseBuilder,
+    /// eBPF 오브젝트 빌드 생략 (이미 빌드된 오브젝트 사용)
+    pub no_ebpf_build: bool,
+}
+
+/// 릴리스 매트릭스를 빌드하고 아카이브 목록을 반환합니다.
+pub fn run(opts: &ReleaseOptions) -> Result<Vec<PathBuf>, String> {
+    if opts.targets.is_empty() {
+        return Err("no release targets given".to_owned());
+    }
+    let root = crate::workspace_root();
+    let version = crate::package::package_version(&root)?;
+    let out_dir = if opts.out_dir.is_absolute() {
+        opts.out_dir.clone()
+    } else {
+        root.join(&opts.out_dir)
+    };
+
+    if !opts.no_ebpf_build {
+        if !cfg!(target_os = "linux") {
+            return Err(format!(
+                "eBPF builds are only supported on Linux (current platform: {}); pass --no-ebpf-build with a prebuilt object",
+                std::env::consts::OS
+            ));
+        }
+        println!("Building eBPF kernel program...");
+        crate::build_ebpf(true);
+    }
+    let ebpf = root.join("target/bpfel-unknown-none/release/ironpost-ebpf");
+    if !ebpf.is_file() {
+        return Err(format!("eBPF object not found: {}", ebpf.display()));
+    }
+
+    fs::create_dir_all(&out_dir)
+        .map_err(|e| format!("failed to create {}: {e}", out_dir.display()))?;
+    let mut archives = Vec::new();
+    for &target in &opts.targets {
+        println!("\nBuilding {} ({:?})...", target.triple(), opts.builder);
+        build_target(&root, target, opts.builder)?;
+        archives.push(collect(&root, &out_dir, target, &version, &ebpf)?);
+    }
+
+    write_checksums(&out_dir, &archives)?;
+    println!("\nRelease artifacts in {}:", out_dir.display());
+    for archive in &archives {
+        println!("  {}", archive.display());
+    }
+    Ok(archives)
+}
+
+/// 한 타겟의 데몬과 CLI를 릴리스 모드로 빌드합니다.
+fn build_target(root: &Path, target: ReleaseTarget, builder: ReleaseBuilder) -> Result<(), String> {
+    let program = match builder {
+        ReleaseBuilder::Cargo => "cargo",
+        ReleaseBuilder::Cross => "cross",
+    };
+    let mut cmd = Command::new(program);
+    cmd.current_dir(root).args(build_args(target));
+    if builder == ReleaseBuilder::Cargo && !target.is_host() {
+        for (name, value) in cross_env(target) {
+            if std::env::var_os(&name).is_none() {
+                cmd.env(name, value);
+            }
+        }
+    }
+    let status = cmd
+        .status()
+        .map_err(|e| format!("failed to run {program}: {e}"))?;
+    if !status.success() {
+        return Err(format!("release build for {} failed", target.triple()));
+    }
+    Ok(())
+}
+
+/// `cargo build` 인자
+fn build_args(target: ReleaseTarget) -> Vec<String> {
+    [
+        "build",
+        "--release",
+        "--locked",
+        "--target",
+        target.triple(),
+        "-p",
+        "ironpost-daemon",
+        "-p",
+        "ironpost-cli",
+    ]
+    .map(str::to_owned)
+    .to_vec()
+}
+
+/// 교차 gcc를 링커(`CARGO_TARGET_<TRIPLE>_LINKER`)와 C 컴파일러(`CC_<triple>`,
+/// C 소스를 포함한 의존성용)로 지정하는 환경변수
+fn cross_env(target: ReleaseTarget) -> [(String, String); 2] {
+    let triple = target.triple().replace('-', "_");
+    let gcc = target.cross_linker().to_owned();
+    [
+        (
+            format!("CARGO_TARGET_{}_LINKER", triple.to_uppercase()),
+            gcc.clone(),
+        ),
+        (format!("CC_{triple}"), gcc),
+    ]
+}
+
+/// 아카이브 기본 이름 (`ironpost-<버전>-<트리플>`)
+fn artifact_name(version: &str, target: ReleaseTarget) -> String {
+    format!("ironpost-{version}-{}", target.triple())
+}
+
+/// 바이너리, eBPF 오브젝트와 부가 파일을 모아 `.tar.gz`로 묶습니다.
+fn collect(
+    root: &Path,
+    out_dir: &Path,
+    target: ReleaseTarget,
+    version: &str,
+    ebpf: &Path,
+) -> Result<PathBuf, String> {
+    let name = artifact_name(version, target);
+    let stage = out_dir.join(&name);
+    if stage.exists() {
+        fs::remove_dir_all(&stage)
+            .map_err(|e| format!("failed to clean {}: {e}", stage.display()))?;
+    }
+    fs::create_dir_all(&stage).map_err(|e| format!("failed to create {}: {e}", stage.display()))?;
+
+    let release = root.join("target").join(target.triple()).join("release");
+    let mut files: Vec<PathBuf> = ["ironpost-daemon", "ironpost-cli"]
+        .iter()
+        .map(|bin| release.join(bin))
+        .collect();
+    files.push(ebpf.to_path_buf());
+    files.extend(EXTRA_FILES.iter().map(|file| root.join(file)));
+    for source in &files {
+        let file_name = source
+            .file_name()
+            .ok_or_else(|| format!("invalid path {}", source.display()))?;
+        fs::copy(source, stage.join(file_name))
+            .map_err(|e| format!("failed to copy {}: {e}", source.display()))?;
+    }
+
+    let archive = out_dir.join(format!("{name}.tar.gz"));
+    let status = Command::new("tar")
+        .current_dir(out_dir)
+        .arg("-czf")
+        .arg(&archive)
+        .arg(&name)
+        .status()
+        .map_err(|e| format!("failed to run tar: {e}"))?;
+    if !status.success() {
+        return Err(format!("failed to create {}", archive.display()));
+    }
+    Ok(archive)
+}
+
+/// 아카이브의 SHA-256 체크섬을 `SHA256SUMS`에 기록합니다.
+fn write_checksums(out_dir: &Path, archives: &[PathBuf]) -> Result<(), String> {
+    let names: Vec<String> = archives
+        .iter()
+        .filter_map(|a| a.file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .collect();
+    let output = Command::new("sha256sum")
+        .current_dir(out_dir)
+        .args(&names)
+        .output()
+        .or_else(|_| {
+            Command::new("shasum")
+                .current_dir(out_dir)
+                .args(["-a", "256"])
+                .args(&names)
+                .output()
+        })
+        .map_err(|e| format!("failed to run sha256sum: {e}"))?;
+    if !output.status.success() {
+        return Err("failed to compute checksums".to_owned());
+    }
+    let path = out_dir.join("SHA256SUMS");
+    fs::write(&path, &output.stdout).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_targets_parse_from_comma_list() {
+        assert_eq!(
+            ReleaseTarget::from_str("x86_64", false),
+            Ok(ReleaseTarget::X86_64)
+        );
+        assert_eq!(
+            ReleaseTarget::from_str("aarch64", false),
+            Ok(ReleaseTarget::Aarch64)
+        );
+        assert!(ReleaseTarget::from_str("riscv64", false).is_err());
+    }
+
+    #[test]
+    fn test_build_args_and_cross_env() {
+        let args = build_args(ReleaseTarget::Aarch64);
+        assert_eq!(
+            args,
+            [
+                "build",
+                "--release",
+                "--locked",
+                "--target",
+                "aarch64-unknown-linux-gnu",
+                "-p",
+                "ironpost-daemon",
+                "-p",
+                "ironpost-cli",
+            ]
+        );
+        let [linker, cc] = cross_env(ReleaseTarget::Aarch64);
+        assert_eq!(
+            linker,
+            (
+                "CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER".to_owned(),
+                "aarch64-linux-gnu-gcc".to_owned()
+            )
+        );
+        assert_eq!(cc.0, "CC_aarch64_unknown_linux_gnu");
+        assert_eq!(
+            artifact_name("0.1.0", ReleaseTarget::X86_64),
+            "ironpost-0.1.0-x86_64-unknown-linux-gnu"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! `cargo xtask verify-ebpf` — eBPF 커널 프로그램 검증기 사전 점검
+//!
+//! 커널 프로그램을 빌드한 뒤 두 단계로 점검합니다.
+//!
+//! 1. 정적 분석: ELF 오브젝트의 실행 섹션마다 명령어 슬롯 수를 셉니다 (권한 불필요).
+//! 2. 검증기 로드: `ironpost-ebpf-engine`의 `verify` 예제로 프로그램을 커널에
+//!    로드만 하고(어태치 없음) 검증기가 처리한 명령어 수를 보고합니다. 거부되면
+//!    검증기 로그를 그대로 출력합니다. root(`CAP_BPF`)가 필요합니다.
+//!
+//! aya가 생성하는 레거시 `maps` 섹션은 libbpf 1.0+(`bpftool prog load`)이 읽지
+//! 못하므로, 데몬과 같은 aya 로더로 검증합니다.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// eBPF 명령어 하나의 크기 (바이트)
+const INSN_SIZE: u64 = 8;
+/// 실행 가능 섹션 플래그 (`SHF_EXECINSTR`)
+const SHF_EXECINSTR: u64 = 0x4;
+
+/// `verify-ebpf` 옵션
+pub struct VerifyOptions {
+    /// 빌드 생략 (이미 빌드된 오브젝트와 검증 헬퍼 사용)
+    pub no_build: bool,
+    /// 검증할 오브젝트 (기본값: 릴리스 빌드 결과)
+    pub object: Option<PathBuf>,
+    /// 프로그램별 검증기 처리 명령어 수 상한
+    pub max_insns: Option<u32>,
+    /// 정적 분석만 수행 (커널 로드 생략, root 불필요)
+    pub static_only: bool,
+}
+
+/// ELF 실행 섹션 하나의 정적 분석 결과
+#[derive(Debug, PartialEq, Eq)]
+struct Section {
+    name: String,
+    insns: u64,
+}
+
+/// 검증기를 통과한 프로그램 하나의 결과 (`verify` 예제의 JSON 출력)
+#[derive(Debug, PartialEq, Eq)]
+struct ProgramReport {
+    name: String,
+    program_type: String,
+    xlated_insns: Option<u64>,
+    verified_insns: Option<u64>,
+    jited_bytes: u64,
+}
+
+/// 검증을 실행합니다.
+pub fn run(opts: &VerifyOptions) -> Result<(), String> {
+    if !cfg!(target_os = "linux") {
+        return Err(format!(
+            "verify-ebpf needs Linux (current platform: {})",
+            std::env::consts::OS
+        ));
+    }
+    let root = crate::workspace_root();
+    if !opts.no_build {
+        println!("Building eBPF kernel program...");
+        crate::build_ebpf(true);
+        if !opts.static_only {
+            build_helper(&root)?;
+        }
+    }
+
+    let object = opts
+        .object
+        .clone()
+        .unwrap_or_else(|| root.join("target/bpfel-unknown-none/release/ironpost-ebpf"));
+    let data =
+        fs::read(&object).map_err(|e| format!("failed to read {}: {e}", object.display()))?;
+    let sections = program_sections(&data)?;
+    println!("\nStatic instruction count ({})", object.display());
+    for section in &sections {
+        println!("  {:<32} {:>8} insns", section.name, section.insns);
+    }
+    if opts.static_only {
+        return Ok(());
+    }
+
+    if !crate::e2e::is_root()? {
+        return Err(
+            "loading programs into the verifier needs root (CAP_BPF); rerun with sudo -E or pass --static-only"
+                .to_owned(),
+        );
+    }
+    let reports = load_into_verifier(&root, &object)?;
+    println!("\nKernel verifier: passed");
+    println!(
+        "  {:<24} {:<8} {:>12} {:>14} {:>12}",
+        "PROGRAM", "TYPE", "XLATED", "VERIFIED", "JITED BYTES"
+    );
+    for report in &reports {
+        println!(
+            "  {:<24} {:<8} {:>12} {:>14} {:>12}",
+            report.name,
+            report.program_type,
+            optional(report.xlated_insns),
+            optional(report.verified_insns),
+            report.jited_bytes
+        );
+    }
+
+    if let Some(max) = opts.max_insns {
+        check_budget(&reports, max)?;
+        println!("\nAll programs within the {max} verified instruction budget");
+    }
+    Ok(())
+}
+
+/// 검증 헬퍼(`ironpost-ebpf-engine`의 `verify` 예제)를 빌드합니다.
+fn build_helper(root: &Path) -> Result<(), String> {
+    let status = Command::new("cargo")
+        .current_dir(root)
+        .args([
+            "build",
+            "--release",
+            "-p",
+            "ironpost-ebpf-engine",
+            "--example",
+            "verify",
+        ])
+        .status()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+    if !status.success() {
+        return Err("verify helper build failed".to_owned());
+    }
+    Ok(())
+}
+
+/// 검증 헬퍼로 오브젝트를 커널에 로드하고 프로그램별 결과를 반환합니다.
+fn load_into_verifier(root: &Path, object: &Path) -> Result<Vec<ProgramReport>, String> {
+    let helper = root.join("target/release/examples/verify");
+    let output = Command::new(&helper)
+        .arg(object)
+        .output()
+        .map_err(|e| format!("failed to run {}: {e}", helper.display()))?;
+    if !output.status.success() {
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr).trim_end());
+        return Err("kernel verifier rejected the eBPF object".to_owned());
+    }
+    parse_reports(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// `verify` 예제의 JSON 출력을 해석합니다.
+fn parse_reports(json: &str) -> Result<Vec<ProgramReport>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("invalid verify output: {e}"))?;
+    let programs = value
+        .as_array()
+        .ok_or("invalid verify output: expected an array")?;
+    Ok(programs
+        .iter()
+        .map(|p| ProgramReport {
+            name: p["name"].as_str().unwrap_or_default().to_owned(),
+            program_type: p["program_type"].as_str().unwrap_or_default().to_owned(),
+            xlated_insns: p["xlated_insns"].as_u64(),
+            verified_insns: p["verified_insns"].as_u64(),
+            jited_bytes: p["jited_bytes"].as_u64().unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// 검증기 처리 명령어 수가 `max`를 넘는 프로그램이 있으면 에러를 반환합니다.
+///
+/// 커널이 `verified_insns`를 제공하지 않으면(5.16 미만) 변환된 명령어 수로 비교합니다.
+fn check_budget(reports: &[ProgramReport], max: u32) -> Result<(), String> {
+    let over: Vec<String> = reports
+        .iter()
+        .filter_map(|r| {
+            let insns = r.verified_insns.or(r.xlated_insns)?;
+            (insns > u64::from(max)).then(|| format!("{} ({insns})", r.name))
+        })
+        .collect();
+    if over.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "verified instruction budget {max} exceeded: {}",
+            over.join(", ")
+        ))
+    }
+}
+
+fn optional(value: Option<u64>) -> String {
+    value.map_or_else(|| "-".to_owned(), |v| v.to_string())
+}
+
+/// ELF64 little-endian 오브젝트에서 실행 섹션과 명령어 슬롯 수를 읽습니다.
+///
+/// `ld_imm64`는 슬롯 두 개를 차지하므로 실제 명령어 수보다 조금 클 수 있습니다.
+fn program_sections(data: &[u8]) -> Result<Vec<Section>, String> {
+    if data.get(..4) != Some(b"\x7fELF".as_slice()) {
+        return Err("not an ELF object".to_owned());
+    }
+    if data.get(4) != Some(&2) || data.get(5) != Some(&1) {
+        return Err("expected a 64-bit little-endian ELF object (bpfel)".to_owned());
+    }
+    let shoff = read_u64(data, 0x28)?;
+    let shentsize = u64::from(read_u16(data, 0x3A)?);
+    let shnum = u64::from(read_u16(data, 0x3C)?);
+    let shstrndx = u64::from(read_u16(data, 0x3E)?);
+
+    let header = |index: u64| -> Result<usize, String> {
+        usize::try_from(shoff.saturating_add(index.saturating_mul(shentsize)))
+            .map_err(|_| "section header out of range".into())
+    };
+    let strtab = read_u64(data, header(shstrndx)?.saturating_add(24))?;
+
+    let mut sections = Vec::new();
+    for index in 0..shnum {
+        let base = header(index)?;
+        let flags = read_u64(data, base + 8)?;
+        let size = read_u64(data, base + 32)?;
+        if flags & SHF_EXECINSTR == 0 || size == 0 {
+            continue;
+        }
+        let name_offset = u64::from(read_u32(data, base)?);
+        sections.push(Section {
+            name: read_str(data, strtab.saturating_add(name_offset))?,
+            insns: size / INSN_SIZE,
+        });
+    }
+    if sections.is_empty() {
+        return Err("no program sections in ELF object".to_owned());
+    }
+    Ok(sections)
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], String> {
+    data.get(offset..offset.saturating_add(N))
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| format!("truncated ELF object at offset {offset:#x}"))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, String> {
+    read_bytes(data, offset).map(u16::from_le_bytes)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    read_bytes(data, offset).map(u32::from_le_bytes)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, String> {
+    read_bytes(data, offset).map(u64::from_le_bytes)
+}
+
+/// `offset`에서 시작하는 NUL 종료 문자열
+fn read_str(data: &[u8], offset: u64) -> Result<String, String> {
+    let start = usize::try_from(offset).map_err(|_| "string offset out of range")?;
+    let bytes = data.get(start..).ok_or("string offset out of range")?;
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 섹션 헤더 세 개(NULL, `xdp`, `.shstrtab`)를 가진 최소 ELF64 오브젝트
+    fn tiny_elf(xdp_size: u64) -> Vec<u8> {
+        let strtab = b"\0xdp\0.shstrtab\0";
+        let strtab_offset = 64u64;
+        let shoff = strtab_offset + strtab.len() as u64;
+
+        let mut data = vec![0u8; 64];
+        data[..4].copy_from_slice(b"\x7fELF");
+        data[4] = 2;
+        data[5] = 1;
+        data[0x28..0x30].copy_from_slice(&shoff.to_le_bytes());
+        data[0x3A..0x3C].copy_from_slice(&64u16.to_le_bytes());
+        data[0x3C..0x3E].copy_from_slice(&3u16.to_le_bytes());
+        data[0x3E..0x40].copy_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(strtab);
+
+        let section = |name: u32, flags: u64, offset: u64, size: u64| {
+            let mut header = vec![0u8; 64];
+            header[..4].copy_from_slice(&name.to_le_bytes());
+            header[8..16].copy_from_slice(&flags.to_le_bytes());
+            header[24..32].copy_from_slice(&offset.to_le_bytes());
+            header[32..40].copy_from_slice(&size.to_le_bytes());
+            header
+        };
+        data.extend(section(0, 0, 0, 0));
+        data.extend(section(1, 0x2 | SHF_EXECINSTR, 0, xdp_size));
+        data.extend(section(5, 0, strtab_offset, strtab.len() as u64));
+        data
+    }
+
+    #[test]
+    fn test_program_sections_counts_executable_sections() {
+        let sections = program_sections(&tiny_elf(96)).unwrap();
+        assert_eq!(
+            sections,
+            vec![Section {
+                name: "xdp".to_owned(),
+                insns: 12
+            }]
+        );
+
+        assert!(program_sections(&tiny_elf(0)).is_err());
+        assert!(program_sections(b"not an elf").is_err());
+        assert!(program_sections(&tiny_elf(96)[..0x30]).is_err());
+    }
+
+    #[test]
+    fn test_parse_reports_and_budget() {
+        let reports = parse_reports(
+            r#"[{"name":"ironpost_xdp","program_type":"Xdp","xlated_insns":410,"verified_insns":1830,"jited_bytes":2210},
+                {"name":"old_kernel","program_type":"Xdp","xlated_insns":90,"verified_insns":null,"jited_bytes":0}]"#,
+        )
+        .unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].verified_insns, Some(1830));
+        assert_eq!(reports[1].verified_insns, None);
+
+        assert!(check_budget(&reports, 2000).is_ok());
+        let err = check_budget(&reports, 1000).unwrap_err();
+        assert!(err.contains("ironpost_xdp (1830)"));
+        assert!(!err.contains("old_kernel"));
+        assert!(
+            check_budget(&reports, 50)
+                .unwrap_err()
+                .contains("old_kernel (90)")
+        );
+
+        assert!(parse_reports("{}").is_err());
+    }
+}